@@ -48,7 +48,7 @@ fn benchmark_http(c: &mut Criterion) {
                             "out",
                             &["in"],
                             sinks::http::HttpSinkConfig {
-                                uri: out_addr.to_string().parse::<http::Uri>().unwrap().into(),
+                                uri: out_addr.to_string().try_into().unwrap(),
                                 compression: *compression,
                                 method: Default::default(),
                                 auth: Default::default(),
@@ -58,6 +58,7 @@ fn benchmark_http(c: &mut Criterion) {
                                     .into(),
                                 request: Default::default(),
                                 tls: Default::default(),
+                                signing: Default::default(),
                                 acknowledgements: Default::default(),
                             },
                         );
@@ -5,7 +5,9 @@ use criterion::{
     criterion_group, measurement::WallTime, BatchSize, BenchmarkGroup, BenchmarkId, Criterion,
     SamplingMode, Throughput,
 };
-use vector::transforms::dedupe::{CacheConfig, Dedupe, DedupeConfig, FieldMatchConfig};
+use vector::transforms::dedupe::{
+    CacheConfig, Dedupe, DedupeConfig, EvictionPolicy, FieldMatchConfig,
+};
 use vector_core::transform::Transform;
 
 use crate::common::{consume, FixedLogStream};
@@ -23,6 +25,14 @@ impl fmt::Display for Param {
     }
 }
 
+fn cache_config() -> CacheConfig {
+    CacheConfig {
+        num_events: NonZeroUsize::new(4).unwrap(),
+        ttl_secs: None,
+        eviction: EvictionPolicy::Lru,
+    }
+}
+
 fn dedupe(c: &mut Criterion) {
     let mut group: BenchmarkGroup<WallTime> =
         c.benchmark_group("vector::transforms::dedupe::Dedupe");
@@ -39,10 +49,11 @@ fn dedupe(c: &mut Criterion) {
             slug: "field_ignore_message",
             input: fixed_stream.clone(),
             dedupe_config: DedupeConfig {
+                duplicate_count_field: None,
                 fields: Some(FieldMatchConfig::IgnoreFields(vec![String::from(
                     "message",
                 )])),
-                cache: CacheConfig { num_events: 4 },
+                cache: cache_config(),
             },
         },
         // Modification of previous where field "message" is matched.
@@ -50,8 +61,9 @@ fn dedupe(c: &mut Criterion) {
             slug: "field_match_message",
             input: fixed_stream.clone(),
             dedupe_config: DedupeConfig {
+                duplicate_count_field: None,
                 fields: Some(FieldMatchConfig::MatchFields(vec![String::from("message")])),
-                cache: CacheConfig { num_events: 4 },
+                cache: cache_config(),
             },
         },
         // Measurement where ignore fields do not exist in the event.
@@ -59,7 +71,8 @@ fn dedupe(c: &mut Criterion) {
             slug: "field_ignore_done",
             input: fixed_stream.clone(),
             dedupe_config: DedupeConfig {
-                cache: CacheConfig { num_events: 4 },
+                duplicate_count_field: None,
+                cache: cache_config(),
                 fields: Some(FieldMatchConfig::IgnoreFields(vec![
                     String::from("abcde"),
                     String::from("eabcd"),
@@ -75,7 +88,8 @@ fn dedupe(c: &mut Criterion) {
             slug: "field_match_done",
             input: fixed_stream.clone(),
             dedupe_config: DedupeConfig {
-                cache: CacheConfig { num_events: 4 },
+                duplicate_count_field: None,
+                cache: cache_config(),
                 fields: Some(FieldMatchConfig::MatchFields(vec![
                     String::from("abcde"),
                     String::from("eabcd"),
@@ -9,6 +9,7 @@ mod json;
 mod logfmt;
 mod native;
 mod native_json;
+mod pretty;
 mod raw_message;
 mod text;
 
@@ -21,6 +22,7 @@ pub use json::{JsonSerializer, JsonSerializerConfig};
 pub use logfmt::{LogfmtSerializer, LogfmtSerializerConfig};
 pub use native::{NativeSerializer, NativeSerializerConfig};
 pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
+pub use pretty::{PrettySerializer, PrettySerializerConfig, PrettySerializerOptions};
 pub use raw_message::{RawMessageSerializer, RawMessageSerializerConfig};
 pub use text::{TextSerializer, TextSerializerConfig};
 use vector_core::event::Event;
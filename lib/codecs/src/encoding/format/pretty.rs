@@ -0,0 +1,264 @@
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use value::Value;
+use vector_common::TimeZone;
+use vector_config::configurable_component;
+use vector_core::{config::DataType, event::Event, schema};
+
+/// The format of timestamps, once converted to the configured timezone.
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f %:z";
+
+const KEY_COLOR: &str = "\x1b[36m";
+const STRING_COLOR: &str = "\x1b[32m";
+const NUMBER_COLOR: &str = "\x1b[33m";
+const TIMESTAMP_COLOR: &str = "\x1b[35m";
+const NULL_COLOR: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Config used to build a `PrettySerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PrettySerializerConfig {
+    /// Options for the pretty serializer.
+    #[serde(default)]
+    pub pretty: PrettySerializerOptions,
+}
+
+impl PrettySerializerConfig {
+    /// Creates a new `PrettySerializerConfig`.
+    pub const fn new(pretty: PrettySerializerOptions) -> Self {
+        Self { pretty }
+    }
+
+    /// Build the `PrettySerializer` from this configuration.
+    pub const fn build(&self) -> PrettySerializer {
+        PrettySerializer {
+            colors: self.pretty.colors,
+            timezone: self.pretty.timezone,
+        }
+    }
+
+    /// The data type of events that are accepted by `PrettySerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log | DataType::Metric
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Pretty serializer options.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct PrettySerializerOptions {
+    /// Whether keys and values are colorized with ANSI escape codes.
+    ///
+    /// Pagers such as `less` show colors with their `-R` option.
+    #[serde(default = "default_colors")]
+    pub colors: bool,
+
+    /// The timezone timestamps are shown in.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub timezone: TimeZone,
+}
+
+const fn default_colors() -> bool {
+    true
+}
+
+impl Default for PrettySerializerOptions {
+    fn default() -> Self {
+        Self {
+            colors: default_colors(),
+            timezone: TimeZone::default(),
+        }
+    }
+}
+
+/// Serializer that converts a log to bytes by writing each of its fields on a line, with keys
+/// aligned and values colorized, or converts a metric to bytes by calling its `Display`
+/// implementation.
+#[derive(Debug, Clone)]
+pub struct PrettySerializer {
+    colors: bool,
+    timezone: TimeZone,
+}
+
+impl PrettySerializer {
+    /// Creates a new `PrettySerializer`.
+    pub const fn new(colors: bool, timezone: TimeZone) -> Self {
+        Self { colors, timezone }
+    }
+
+    /// Appends `text` to `output`, in `color` if colors are enabled.
+    fn paint(&self, output: &mut String, color: &str, text: &str) {
+        if self.colors {
+            output.push_str(color);
+            output.push_str(text);
+            output.push_str(RESET);
+        } else {
+            output.push_str(text);
+        }
+    }
+
+    fn write_value(&self, output: &mut String, value: &Value) {
+        match value {
+            Value::Bytes(bytes) => {
+                let string = String::from_utf8_lossy(bytes)
+                    .replace('\n', "\\n")
+                    .replace('\r', "\\r")
+                    .replace('\t', "\\t");
+                self.paint(output, STRING_COLOR, &string);
+            }
+            Value::Timestamp(timestamp) => {
+                self.paint(output, TIMESTAMP_COLOR, &self.format_timestamp(timestamp));
+            }
+            Value::Integer(_) | Value::Float(_) | Value::Boolean(_) => {
+                self.paint(output, NUMBER_COLOR, &value.to_string());
+            }
+            Value::Null => self.paint(output, NULL_COLOR, "null"),
+            Value::Object(_) => output.push_str("{}"),
+            Value::Array(_) => output.push_str("[]"),
+            Value::Regex(regex) => self.paint(output, STRING_COLOR, regex.as_str()),
+        }
+    }
+
+    fn format_timestamp(&self, timestamp: &DateTime<Utc>) -> String {
+        match self.timezone {
+            TimeZone::Local => timestamp
+                .with_timezone(&Local)
+                .format(TIMESTAMP_FORMAT)
+                .to_string(),
+            TimeZone::Named(tz) => timestamp
+                .with_timezone(&tz)
+                .format(TIMESTAMP_FORMAT)
+                .to_string(),
+        }
+    }
+}
+
+/// Collects the non-container values of `value`, and its empty containers, with their paths.
+fn leaves<'a>(path: String, value: &'a Value, output: &mut Vec<(String, &'a Value)>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                leaves(path, value, output);
+            }
+        }
+        Value::Array(array) if !array.is_empty() => {
+            for (index, value) in array.iter().enumerate() {
+                leaves(format!("{}[{}]", path, index), value, output);
+            }
+        }
+        _ => output.push((path, value)),
+    }
+}
+
+impl Encoder<Event> for PrettySerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        match event {
+            Event::Log(log) => {
+                let mut fields = Vec::new();
+                if !matches!(log.value(), Value::Object(map) if map.is_empty()) {
+                    leaves(String::new(), log.value(), &mut fields);
+                }
+                let width = fields
+                    .iter()
+                    .map(|(path, _)| path.chars().count())
+                    .max()
+                    .unwrap_or_default();
+
+                // Events are separated by an empty line, following the one ending each of them.
+                let mut output = String::new();
+                for (path, value) in fields {
+                    let key = format!("{:width$}", path, width = width);
+                    self.paint(&mut output, KEY_COLOR, &key);
+                    output.push_str("  ");
+                    self.write_value(&mut output, value);
+                    output.push('\n');
+                }
+                buffer.put(output.as_bytes());
+            }
+            Event::Metric(metric) => {
+                buffer.put(metric.to_string().as_bytes());
+                buffer.put_u8(b'\n');
+            }
+            Event::Trace(_) => {}
+        };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use chrono::{TimeZone as _, Utc};
+    use vector_common::btreemap;
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn serialize(colors: bool, event: Event) -> String {
+        let timezone = TimeZone::parse("Europe/Brussels").unwrap();
+        let mut serializer = PrettySerializer::new(colors, timezone);
+        let mut bytes = BytesMut::new();
+        serializer.encode(event, &mut bytes).unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    fn event() -> Event {
+        let mut log = LogEvent::default();
+        log.insert("message", "hello\nworld");
+        log.insert("status", 200);
+        log.insert("tags.env", "dev");
+        log.insert("tags.ids", vec![Value::from(1), Value::Null]);
+        log.insert(
+            "timestamp",
+            Utc.ymd(2022, 10, 14).and_hms_milli(12, 0, 0, 250),
+        );
+        Event::Log(log)
+    }
+
+    #[test]
+    fn serialize_pretty() {
+        assert_eq!(
+            serialize(false, event()),
+            concat!(
+                "message      hello\\nworld\n",
+                "status       200\n",
+                "tags.env     dev\n",
+                "tags.ids[0]  1\n",
+                "tags.ids[1]  null\n",
+                "timestamp    2022-10-14 14:00:00.250 +02:00\n",
+            )
+        );
+    }
+
+    #[test]
+    fn serialize_pretty_colors() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "message" => "hello",
+            "status" => 200,
+        }));
+
+        assert_eq!(
+            serialize(true, event),
+            concat!(
+                "\x1b[36mmessage\x1b[0m  \x1b[32mhello\x1b[0m\n",
+                "\x1b[36mstatus \x1b[0m  \x1b[33m200\x1b[0m\n",
+            )
+        );
+    }
+}
@@ -11,8 +11,8 @@ pub use format::{
     AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, GelfSerializer,
     GelfSerializerConfig, JsonSerializer, JsonSerializerConfig, LogfmtSerializer,
     LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
-    NativeSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig, TextSerializer,
-    TextSerializerConfig,
+    NativeSerializerConfig, PrettySerializer, PrettySerializerConfig, PrettySerializerOptions,
+    RawMessageSerializer, RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
@@ -224,6 +224,17 @@ pub enum SerializerConfig {
     /// [experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
     NativeJson,
 
+    /// Encodes an event as human-readable text, writing each field on its own line, with aligned
+    /// keys and colorized values.
+    ///
+    /// Events are separated by an empty line. This encoding is meant for reading events while
+    /// developing, such as in a terminal, not for being parsed.
+    Pretty {
+        /// Pretty-specific encoder options.
+        #[serde(default)]
+        pretty: PrettySerializerOptions,
+    },
+
     /// No encoding.
     ///
     /// This "encoding" simply uses the `message` field of a log event.
@@ -279,6 +290,14 @@ impl From<NativeJsonSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<PrettySerializerConfig> for SerializerConfig {
+    fn from(config: PrettySerializerConfig) -> Self {
+        Self::Pretty {
+            pretty: config.pretty,
+        }
+    }
+}
+
 impl From<RawMessageSerializerConfig> for SerializerConfig {
     fn from(_: RawMessageSerializerConfig) -> Self {
         Self::RawMessage
@@ -305,6 +324,9 @@ impl SerializerConfig {
             SerializerConfig::NativeJson => {
                 Ok(Serializer::NativeJson(NativeJsonSerializerConfig.build()))
             }
+            SerializerConfig::Pretty { pretty } => Ok(Serializer::Pretty(
+                PrettySerializerConfig::new(pretty.clone()).build(),
+            )),
             SerializerConfig::RawMessage => {
                 Ok(Serializer::RawMessage(RawMessageSerializerConfig.build()))
             }
@@ -333,6 +355,7 @@ impl SerializerConfig {
             | SerializerConfig::Json
             | SerializerConfig::Logfmt
             | SerializerConfig::NativeJson
+            | SerializerConfig::Pretty { .. }
             | SerializerConfig::RawMessage
             | SerializerConfig::Text => FramingConfig::NewlineDelimited,
         }
@@ -349,6 +372,9 @@ impl SerializerConfig {
             SerializerConfig::Logfmt => LogfmtSerializerConfig.input_type(),
             SerializerConfig::Native => NativeSerializerConfig.input_type(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.input_type(),
+            SerializerConfig::Pretty { pretty } => {
+                PrettySerializerConfig::new(pretty.clone()).input_type()
+            }
             SerializerConfig::RawMessage => RawMessageSerializerConfig.input_type(),
             SerializerConfig::Text => TextSerializerConfig.input_type(),
         }
@@ -365,6 +391,9 @@ impl SerializerConfig {
             SerializerConfig::Logfmt => LogfmtSerializerConfig.schema_requirement(),
             SerializerConfig::Native => NativeSerializerConfig.schema_requirement(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
+            SerializerConfig::Pretty { pretty } => {
+                PrettySerializerConfig::new(pretty.clone()).schema_requirement()
+            }
             SerializerConfig::RawMessage => RawMessageSerializerConfig.schema_requirement(),
             SerializerConfig::Text => TextSerializerConfig.schema_requirement(),
         }
@@ -386,6 +415,8 @@ pub enum Serializer {
     Native(NativeSerializer),
     /// Uses a `NativeJsonSerializer` for serialization.
     NativeJson(NativeJsonSerializer),
+    /// Uses a `PrettySerializer` for serialization.
+    Pretty(PrettySerializer),
     /// Uses a `RawMessageSerializer` for serialization.
     RawMessage(RawMessageSerializer),
     /// Uses a `TextSerializer` for serialization.
@@ -401,6 +432,7 @@ impl Serializer {
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
+            | Serializer::Pretty(_)
             | Serializer::RawMessage(_) => false,
         }
     }
@@ -420,6 +452,7 @@ impl Serializer {
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
+            | Serializer::Pretty(_)
             | Serializer::RawMessage(_) => {
                 panic!("Serializer does not support JSON")
             }
@@ -463,6 +496,12 @@ impl From<NativeJsonSerializer> for Serializer {
     }
 }
 
+impl From<PrettySerializer> for Serializer {
+    fn from(serializer: PrettySerializer) -> Self {
+        Self::Pretty(serializer)
+    }
+}
+
 impl From<RawMessageSerializer> for Serializer {
     fn from(serializer: RawMessageSerializer) -> Self {
         Self::RawMessage(serializer)
@@ -486,6 +525,7 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
             Serializer::Logfmt(serializer) => serializer.encode(event, buffer),
             Serializer::Native(serializer) => serializer.encode(event, buffer),
             Serializer::NativeJson(serializer) => serializer.encode(event, buffer),
+            Serializer::Pretty(serializer) => serializer.encode(event, buffer),
             Serializer::RawMessage(serializer) => serializer.encode(event, buffer),
             Serializer::Text(serializer) => serializer.encode(event, buffer),
         }
@@ -24,7 +24,8 @@ pub use encoding::{
     GelfSerializer, GelfSerializerConfig, JsonSerializer, JsonSerializerConfig,
     LengthDelimitedEncoder, LengthDelimitedEncoderConfig, LogfmtSerializer, LogfmtSerializerConfig,
     NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig,
-    NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig, RawMessageSerializer,
+    NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig, PrettySerializer,
+    PrettySerializerConfig, PrettySerializerOptions, RawMessageSerializer,
     RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
 };
 pub use gelf::{gelf_fields, VALID_FIELD_REGEX};
@@ -1,6 +1,7 @@
 use std::{
+    fmt,
     fs::{self, File},
-    io::{self, BufRead, Seek},
+    io::{self, BufRead, Read, Seek},
     path::PathBuf,
     time::{Duration, Instant},
 };
@@ -76,53 +77,56 @@ impl FileWatcher {
             false
         };
 
-        let gzipped = is_gzipped(&mut reader)?;
+        let compression = detect_compression(&mut reader)?;
 
         // Determine the actual position at which we should start reading
         let (reader, file_position): (Box<dyn BufRead>, FilePosition) =
-            match (gzipped, too_old, read_from) {
-                (true, true, _) => {
+            match (compression, too_old, read_from) {
+                (Some(compression), true, _) => {
                     debug!(
-                        message = "Not reading gzipped file older than `ignore_older`.",
+                        message = "Not reading compressed file older than `ignore_older`.",
                         ?path,
+                        %compression,
                     );
                     (Box::new(null_reader()), 0)
                 }
-                (true, _, ReadFrom::Checkpoint(file_position)) => {
+                (Some(compression), false, ReadFrom::Checkpoint(file_position)) => {
                     debug!(
-                        message = "Not re-reading gzipped file with existing stored offset.",
+                        message = "Resuming compressed file from stored offset.",
                         ?path,
+                        %compression,
                         %file_position
                     );
-                    (Box::new(null_reader()), file_position)
+                    decompressed_reader(compression, reader, file_position)?
                 }
-                // TODO: This may become the default, leading us to stop reading gzipped files that
-                // we were reading before. Should we merge this and the next branch to read
+                // TODO: This may become the default, leading us to stop reading compressed files
+                // that we were reading before. Should we merge this and the next branch to read
                 // compressed file from the beginning even when `read_from = "end"` (implicitly via
                 // default or explicitly via config)?
-                (true, _, ReadFrom::End) => {
+                (Some(compression), false, ReadFrom::End) => {
                     debug!(
                         message = "Can't read from the end of already-compressed file.",
                         ?path,
+                        %compression,
                     );
                     (Box::new(null_reader()), 0)
                 }
-                (true, false, ReadFrom::Beginning) => {
-                    (Box::new(io::BufReader::new(MultiGzDecoder::new(reader))), 0)
+                (Some(compression), false, ReadFrom::Beginning) => {
+                    decompressed_reader(compression, reader, 0)?
                 }
-                (false, true, _) => {
+                (None, true, _) => {
                     let pos = reader.seek(io::SeekFrom::End(0)).unwrap();
                     (Box::new(reader), pos)
                 }
-                (false, false, ReadFrom::Checkpoint(file_position)) => {
+                (None, false, ReadFrom::Checkpoint(file_position)) => {
                     let pos = reader.seek(io::SeekFrom::Start(file_position)).unwrap();
                     (Box::new(reader), pos)
                 }
-                (false, false, ReadFrom::Beginning) => {
+                (None, false, ReadFrom::Beginning) => {
                     let pos = reader.seek(io::SeekFrom::Start(0)).unwrap();
                     (Box::new(reader), pos)
                 }
-                (false, false, ReadFrom::End) => {
+                (None, false, ReadFrom::End) => {
                     let pos = reader.seek(io::SeekFrom::End(0)).unwrap();
                     (Box::new(reader), pos)
                 }
@@ -155,16 +159,17 @@ impl FileWatcher {
         let file_handle = File::open(&path)?;
         if (file_handle.portable_dev()?, file_handle.portable_ino()?) != (self.devno, self.inode) {
             let mut reader = io::BufReader::new(fs::File::open(&path)?);
-            let gzipped = is_gzipped(&mut reader)?;
-            let new_reader: Box<dyn BufRead> = if gzipped {
-                if self.file_position != 0 {
-                    Box::new(null_reader())
-                } else {
-                    Box::new(io::BufReader::new(MultiGzDecoder::new(reader)))
+            let new_reader: Box<dyn BufRead> = match detect_compression(&mut reader)? {
+                Some(compression) => {
+                    let (reader, file_position) =
+                        decompressed_reader(compression, reader, self.file_position)?;
+                    self.file_position = file_position;
+                    reader
+                }
+                None => {
+                    reader.seek(io::SeekFrom::Start(self.file_position))?;
+                    Box::new(reader)
                 }
-            } else {
-                reader.seek(io::SeekFrom::Start(self.file_position))?;
-                Box::new(reader)
             };
             self.reader = new_reader;
             self.devno = file_handle.portable_dev()?;
@@ -270,11 +275,57 @@ impl FileWatcher {
     }
 }
 
-fn is_gzipped(r: &mut io::BufReader<fs::File>) -> io::Result<bool> {
+/// The compression formats that are transparently decompressed when reading a file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Compression {
+    Gzip,
+    Zstd,
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Compression::Gzip => f.write_str("gzip"),
+            Compression::Zstd => f.write_str("zstd"),
+        }
+    }
+}
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+fn detect_compression(r: &mut io::BufReader<fs::File>) -> io::Result<Option<Compression>> {
     let header_bytes = r.fill_buf()?;
     // WARN: The paired `BufReader::consume` is not called intentionally. If we
-    // do we'll chop a decent part of the potential gzip stream off.
-    Ok(header_bytes.starts_with(&[0x1f, 0x8b]))
+    // do we'll chop a decent part of the potential compressed stream off.
+    Ok(if header_bytes.starts_with(GZIP_MAGIC) {
+        Some(Compression::Gzip)
+    } else if header_bytes.starts_with(ZSTD_MAGIC) {
+        Some(Compression::Zstd)
+    } else {
+        None
+    })
+}
+
+/// Wraps `reader` in a decoder for `compression`, positioned at `file_position`.
+///
+/// The file position of a compressed file is an offset into its uncompressed contents. As
+/// compressed streams can't be seeked into, the contents are decompressed and discarded up to
+/// that offset. Returns the position actually reached, which is short of `file_position` if the
+/// file was shorter.
+fn decompressed_reader(
+    compression: Compression,
+    reader: io::BufReader<fs::File>,
+    file_position: FilePosition,
+) -> io::Result<(Box<dyn BufRead>, FilePosition)> {
+    let mut reader: Box<dyn BufRead> = match compression {
+        Compression::Gzip => Box::new(io::BufReader::new(MultiGzDecoder::new(reader))),
+        Compression::Zstd => Box::new(io::BufReader::new(
+            zstd::stream::read::Decoder::with_buffer(reader)?,
+        )),
+    };
+    let skipped = io::copy(&mut (&mut reader).take(file_position), &mut io::sink())?;
+    Ok((reader, skipped))
 }
 
 fn null_reader() -> impl BufRead {
@@ -0,0 +1,77 @@
+use std::{fs, io::Write, path::Path};
+
+use bytes::Bytes;
+use flate2::{write::GzEncoder, Compression};
+
+use crate::{file_watcher::FileWatcher, FilePosition, ReadFrom};
+
+const CONTENTS: &[u8] = b"first line\nsecond line\nthird line\n";
+
+fn write_gzip(path: &Path) {
+    let mut encoder = GzEncoder::new(fs::File::create(path).unwrap(), Compression::default());
+    encoder.write_all(CONTENTS).unwrap();
+    encoder.finish().unwrap();
+}
+
+fn write_zstd(path: &Path) {
+    fs::write(path, zstd::stream::encode_all(CONTENTS, 0).unwrap()).unwrap();
+}
+
+fn read_all(path: &Path, read_from: ReadFrom) -> (Vec<(FilePosition, Bytes)>, FilePosition) {
+    let mut watcher = FileWatcher::new(
+        path.to_path_buf(),
+        read_from,
+        None,
+        100_000,
+        Bytes::from("\n"),
+    )
+    .expect("must be able to create");
+    watcher.set_file_findable(false);
+
+    let mut lines = Vec::new();
+    while let Some(line) = watcher.read_line().unwrap() {
+        lines.push((line.offset, line.bytes));
+    }
+    (lines, watcher.get_file_position())
+}
+
+fn assert_resumes_from_checkpoint(write: fn(&Path)) {
+    let dir = tempfile::TempDir::new().expect("could not create tempdir");
+    let path = dir.path().join("a_file.log.compressed");
+    write(&path);
+
+    let (lines, position) = read_all(&path, ReadFrom::Beginning);
+    assert_eq!(
+        lines,
+        vec![
+            (0, Bytes::from("first line")),
+            (11, Bytes::from("second line")),
+            (23, Bytes::from("third line")),
+        ]
+    );
+    assert_eq!(position, CONTENTS.len() as FilePosition);
+
+    // Checkpointed positions are offsets into the uncompressed contents.
+    let (lines, _) = read_all(&path, ReadFrom::Checkpoint(11));
+    assert_eq!(
+        lines,
+        vec![
+            (11, Bytes::from("second line")),
+            (23, Bytes::from("third line")),
+        ]
+    );
+
+    let (lines, position) = read_all(&path, ReadFrom::Checkpoint(position));
+    assert!(lines.is_empty());
+    assert_eq!(position, CONTENTS.len() as FilePosition);
+}
+
+#[test]
+fn gzip_resumes_from_checkpoint() {
+    assert_resumes_from_checkpoint(write_gzip);
+}
+
+#[test]
+fn zstd_resumes_from_checkpoint() {
+    assert_resumes_from_checkpoint(write_zstd);
+}
@@ -15,8 +15,8 @@ pub mod util {
 
     const NANOS_RANGE: i64 = 1_000_000_000;
 
-    // (<Timestamp in nanos>, <Line>)
-    pub struct Entry(pub i64, pub String);
+    // (<Timestamp in nanos>, <Line>, <Structured metadata>)
+    pub struct Entry(pub i64, pub String, pub Vec<(String, String)>);
 
     impl From<Entry> for logproto::EntryAdapter {
         fn from(entry: Entry) -> Self {
@@ -26,6 +26,11 @@ pub mod util {
                     nanos: (entry.0 % NANOS_RANGE) as i32,
                 }),
                 line: entry.1,
+                structured_metadata: entry
+                    .2
+                    .into_iter()
+                    .map(|(name, value)| logproto::LabelPair { name, value })
+                    .collect(),
             }
         }
     }
@@ -77,8 +82,10 @@ pub mod util {
 #[cfg(test)]
 mod tests {
     use super::util;
+    use crate::logproto;
     use crate::util::{Batch, Entry};
     use chrono::prelude::*;
+    use prost::Message;
     use std::collections::HashMap;
 
     #[test]
@@ -99,9 +106,9 @@ mod tests {
     #[test]
     fn encode_batch() {
         let ts1 = Utc.timestamp(1640244790, 0);
-        let entry1 = Entry(ts1.timestamp_nanos(), "hello".into());
+        let entry1 = Entry(ts1.timestamp_nanos(), "hello".into(), vec![]);
         let ts2 = Utc.timestamp(1640244791, 0);
-        let entry2 = Entry(ts2.timestamp_nanos(), "world".into());
+        let entry2 = Entry(ts2.timestamp_nanos(), "world".into(), vec![]);
         let labels = vec![("source".into(), "protobuf-test".into())]
             .into_iter()
             .collect();
@@ -116,4 +123,21 @@ mod tests {
         let buf = batch.encode();
         assert_eq!(expect, buf);
     }
+
+    #[test]
+    fn encode_structured_metadata() {
+        let entry = Entry(0, "hello".into(), vec![("trace_id".into(), "abc".into())]);
+        let batch = Batch(HashMap::new(), vec![entry]);
+        let buf = snap::raw::Decoder::new()
+            .decompress_vec(&batch.encode())
+            .unwrap();
+        let request = logproto::PushRequest::decode(buf.as_slice()).unwrap();
+        assert_eq!(
+            request.streams[0].entries[0].structured_metadata,
+            vec![logproto::LabelPair {
+                name: "trace_id".into(),
+                value: "abc".into(),
+            }]
+        );
+    }
 }
@@ -1,27 +1,41 @@
 use super::proto::{
     common::v1::{any_value::Value as PBValue, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, SeverityNumber},
+    metrics::v1::{
+        exponential_histogram_data_point::Buckets, metric::Data, number_data_point,
+        AggregationTemporality, DataPointFlags, ExponentialHistogramDataPoint, HistogramDataPoint,
+        Metric as OtlpMetric, NumberDataPoint, ResourceMetrics, SummaryDataPoint,
+    },
     resource::v1::Resource,
+    trace::v1::{
+        span::{Event as SpanEvent, Link},
+        ResourceSpans, Span, Status as SpanStatus,
+    },
 };
 use bytes::Bytes;
-use chrono::{TimeZone, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use ordered_float::NotNan;
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, num::NonZeroU32};
 use value::Value;
 use vector_core::{
     config::log_schema,
-    event::{Event, LogEvent},
+    event::{
+        metric::{Bucket, Quantile},
+        Event, LogEvent, Metric, MetricKind, MetricTags, MetricValue, TraceEvent,
+    },
 };
 
-const RESOURCE_KEY: &str = "resources";
-const ATTRIBUTES_KEY: &str = "attributes";
-const TRACE_ID_KEY: &str = "trace_id";
-const SPAN_ID_KEY: &str = "span_id";
-const SEVERITY_TEXT_KEY: &str = "severity_text";
-const SEVERITY_NUMBER_KEY: &str = "severity_number";
-const OBSERVED_TIMESTAMP_KEY: &str = "observed_timestamp";
-const DROPPED_ATTRIBUTES_COUNT_KEY: &str = "dropped_attributes_count";
-const FLAGS_KEY: &str = "flags";
+pub(crate) const RESOURCE_KEY: &str = "resources";
+pub(crate) const ATTRIBUTES_KEY: &str = "attributes";
+pub(crate) const TRACE_ID_KEY: &str = "trace_id";
+pub(crate) const SPAN_ID_KEY: &str = "span_id";
+pub(crate) const SEVERITY_TEXT_KEY: &str = "severity_text";
+pub(crate) const SEVERITY_NUMBER_KEY: &str = "severity_number";
+pub(crate) const OBSERVED_TIMESTAMP_KEY: &str = "observed_timestamp";
+pub(crate) const DROPPED_ATTRIBUTES_COUNT_KEY: &str = "dropped_attributes_count";
+pub(crate) const FLAGS_KEY: &str = "flags";
+pub(crate) const INGEST_TIMESTAMP_KEY: &str = "ingest_timestamp";
+pub(crate) const RESOURCE_TAG_PREFIX: &str = "resource.";
 
 impl IntoIterator for ResourceLogs {
     type Item = Event;
@@ -43,6 +57,61 @@ impl IntoIterator for ResourceLogs {
     }
 }
 
+impl IntoIterator for ResourceSpans {
+    type Item = Event;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+    fn into_iter(self) -> Self::IntoIter {
+        let resource = self.resource;
+        let now = Utc::now();
+        self.scope_spans
+            .into_iter()
+            .flat_map(|scope_span| scope_span.spans)
+            .map(|span| {
+                ResourceSpan {
+                    resource: resource.clone(),
+                    span,
+                }
+                .into_event(now)
+            })
+            .collect::<Vec<Self::Item>>()
+            .into_iter()
+    }
+}
+
+impl IntoIterator for ResourceMetrics {
+    type Item = Event;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+    fn into_iter(self) -> Self::IntoIter {
+        let resource_tags = self
+            .resource
+            .map(|resource| {
+                resource
+                    .attributes
+                    .into_iter()
+                    .filter_map(|kv| {
+                        kv_into_tag(kv)
+                            .map(|(key, value)| (RESOURCE_TAG_PREFIX.to_owned() + &key, value))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let now = Utc::now();
+        self.scope_metrics
+            .into_iter()
+            .flat_map(|scope_metric| scope_metric.metrics)
+            .flat_map(|metric| {
+                ResourceMetric {
+                    resource_tags: &resource_tags,
+                    metric,
+                }
+                .into_metrics(now)
+            })
+            .map(Event::Metric)
+            .collect::<Vec<Self::Item>>()
+            .into_iter()
+    }
+}
+
 impl From<PBValue> for Value {
     fn from(av: PBValue) -> Self {
         match av {
@@ -67,6 +136,16 @@ struct ResourceLog {
     log_record: LogRecord,
 }
 
+struct ResourceSpan {
+    resource: Option<Resource>,
+    span: Span,
+}
+
+struct ResourceMetric<'a> {
+    resource_tags: &'a [(String, String)],
+    metric: OtlpMetric,
+}
+
 fn kv_list_into_value(arr: Vec<KeyValue>) -> Value {
     Value::Object(
         arr.into_iter()
@@ -144,3 +223,364 @@ impl From<ResourceLog> for Event {
         le.into()
     }
 }
+
+impl ResourceSpan {
+    fn into_event(self, now: DateTime<Utc>) -> Event {
+        let span = self.span;
+        let mut trace = TraceEvent::default();
+
+        trace.insert(TRACE_ID_KEY, hex::encode(span.trace_id));
+        trace.insert(SPAN_ID_KEY, hex::encode(span.span_id));
+        if !span.parent_span_id.is_empty() {
+            trace.insert("parent_span_id", hex::encode(span.parent_span_id));
+        }
+        if !span.trace_state.is_empty() {
+            trace.insert("trace_state", span.trace_state);
+        }
+        trace.insert("name", span.name);
+        trace.insert("kind", span.kind);
+        trace.insert(
+            "start_time_unix_nano",
+            Utc.timestamp_nanos(span.start_time_unix_nano as i64),
+        );
+        trace.insert(
+            "end_time_unix_nano",
+            Utc.timestamp_nanos(span.end_time_unix_nano as i64),
+        );
+        if !span.attributes.is_empty() {
+            trace.insert(ATTRIBUTES_KEY, kv_list_into_value(span.attributes));
+        }
+        trace.insert(DROPPED_ATTRIBUTES_COUNT_KEY, span.dropped_attributes_count);
+        if !span.events.is_empty() {
+            trace.insert(
+                "events",
+                Value::Array(span.events.into_iter().map(Into::into).collect()),
+            );
+        }
+        trace.insert("dropped_events_count", span.dropped_events_count);
+        if !span.links.is_empty() {
+            trace.insert(
+                "links",
+                Value::Array(span.links.into_iter().map(Into::into).collect()),
+            );
+        }
+        trace.insert("dropped_links_count", span.dropped_links_count);
+        if let Some(status) = span.status {
+            trace.insert("status", Value::from(status));
+        }
+        if let Some(resource) = self.resource {
+            if !resource.attributes.is_empty() {
+                trace.insert(RESOURCE_KEY, kv_list_into_value(resource.attributes));
+            }
+        }
+        trace.insert(INGEST_TIMESTAMP_KEY, now);
+        trace.insert(log_schema().source_type_key(), Bytes::from("opentelemetry"));
+
+        trace.into()
+    }
+}
+
+impl From<SpanEvent> for Value {
+    fn from(event: SpanEvent) -> Self {
+        let mut obj: BTreeMap<String, Value> = BTreeMap::new();
+        obj.insert("name".into(), event.name.into());
+        obj.insert(
+            "time_unix_nano".into(),
+            Value::Timestamp(Utc.timestamp_nanos(event.time_unix_nano as i64)),
+        );
+        obj.insert("attributes".into(), kv_list_into_value(event.attributes));
+        obj.insert(
+            DROPPED_ATTRIBUTES_COUNT_KEY.into(),
+            Value::Integer(event.dropped_attributes_count as i64),
+        );
+        Value::Object(obj)
+    }
+}
+
+impl From<Link> for Value {
+    fn from(link: Link) -> Self {
+        let mut obj: BTreeMap<String, Value> = BTreeMap::new();
+        obj.insert(TRACE_ID_KEY.into(), Value::from(hex::encode(link.trace_id)));
+        obj.insert(SPAN_ID_KEY.into(), Value::from(hex::encode(link.span_id)));
+        obj.insert("trace_state".into(), link.trace_state.into());
+        obj.insert("attributes".into(), kv_list_into_value(link.attributes));
+        obj.insert(
+            DROPPED_ATTRIBUTES_COUNT_KEY.into(),
+            Value::Integer(link.dropped_attributes_count as i64),
+        );
+        Value::Object(obj)
+    }
+}
+
+impl From<SpanStatus> for Value {
+    fn from(status: SpanStatus) -> Self {
+        let mut obj: BTreeMap<String, Value> = BTreeMap::new();
+        obj.insert("message".into(), status.message.into());
+        obj.insert("code".into(), status.code.into());
+        Value::Object(obj)
+    }
+}
+
+impl<'a> ResourceMetric<'a> {
+    fn into_metrics(self, now: DateTime<Utc>) -> Vec<Metric> {
+        let name = self.metric.name;
+        let resource_tags = self.resource_tags;
+        let point = |kind: MetricKind,
+                     value: MetricValue,
+                     attributes: Vec<KeyValue>,
+                     start_time_unix_nano: u64,
+                     time_unix_nano: u64| {
+            DataPoint {
+                kind,
+                value,
+                attributes,
+                start_time_unix_nano,
+                time_unix_nano,
+            }
+            .into_metric(&name, resource_tags, now)
+        };
+
+        match self.metric.data {
+            Some(Data::Gauge(gauge)) => gauge
+                .data_points
+                .into_iter()
+                .filter(|data_point| has_recorded_value(data_point.flags))
+                .filter_map(|data_point| {
+                    let value = number_value(&data_point)?;
+                    Some(point(
+                        MetricKind::Absolute,
+                        MetricValue::Gauge { value },
+                        data_point.attributes,
+                        data_point.start_time_unix_nano,
+                        data_point.time_unix_nano,
+                    ))
+                })
+                .collect(),
+            Some(Data::Sum(sum)) => {
+                let kind = metric_kind(sum.aggregation_temporality);
+                sum.data_points
+                    .into_iter()
+                    .filter(|data_point| has_recorded_value(data_point.flags))
+                    .filter_map(|data_point| {
+                        let value = number_value(&data_point)?;
+                        // Only monotonic sums are counters, others can go up and down like gauges.
+                        let value = if sum.is_monotonic {
+                            MetricValue::Counter { value }
+                        } else {
+                            MetricValue::Gauge { value }
+                        };
+                        Some(point(
+                            kind,
+                            value,
+                            data_point.attributes,
+                            data_point.start_time_unix_nano,
+                            data_point.time_unix_nano,
+                        ))
+                    })
+                    .collect()
+            }
+            Some(Data::Histogram(histogram)) => {
+                let kind = metric_kind(histogram.aggregation_temporality);
+                histogram
+                    .data_points
+                    .into_iter()
+                    .filter(|data_point| has_recorded_value(data_point.flags))
+                    .map(|data_point| {
+                        let value = histogram_value(&data_point);
+                        point(
+                            kind,
+                            value,
+                            data_point.attributes,
+                            data_point.start_time_unix_nano,
+                            data_point.time_unix_nano,
+                        )
+                    })
+                    .collect()
+            }
+            Some(Data::ExponentialHistogram(histogram)) => {
+                let kind = metric_kind(histogram.aggregation_temporality);
+                histogram
+                    .data_points
+                    .into_iter()
+                    .filter(|data_point| has_recorded_value(data_point.flags))
+                    .map(|data_point| {
+                        let value = exponential_histogram_value(&data_point);
+                        point(
+                            kind,
+                            value,
+                            data_point.attributes,
+                            data_point.start_time_unix_nano,
+                            data_point.time_unix_nano,
+                        )
+                    })
+                    .collect()
+            }
+            Some(Data::Summary(summary)) => summary
+                .data_points
+                .into_iter()
+                .filter(|data_point| has_recorded_value(data_point.flags))
+                .map(|data_point| {
+                    let value = summary_value(&data_point);
+                    // Summaries don't have a temporality, but are reported since a fixed start time.
+                    point(
+                        MetricKind::Absolute,
+                        value,
+                        data_point.attributes,
+                        data_point.start_time_unix_nano,
+                        data_point.time_unix_nano,
+                    )
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+struct DataPoint {
+    kind: MetricKind,
+    value: MetricValue,
+    attributes: Vec<KeyValue>,
+    start_time_unix_nano: u64,
+    time_unix_nano: u64,
+}
+
+impl DataPoint {
+    fn into_metric(
+        self,
+        name: &str,
+        resource_tags: &[(String, String)],
+        now: DateTime<Utc>,
+    ) -> Metric {
+        let tags = resource_tags
+            .iter()
+            .cloned()
+            .chain(self.attributes.into_iter().filter_map(kv_into_tag))
+            .collect::<MetricTags>();
+
+        // If time_unix_nano is not present (0 represents missing or unknown timestamp) use the current time
+        let timestamp = if self.time_unix_nano > 0 {
+            Utc.timestamp_nanos(self.time_unix_nano as i64)
+        } else {
+            now
+        };
+
+        // Delta data points cover the interval since their start time.
+        let interval_ms = match self.kind {
+            MetricKind::Incremental
+                if self.start_time_unix_nano > 0
+                    && self.time_unix_nano > self.start_time_unix_nano =>
+            {
+                let interval_ms = (self.time_unix_nano - self.start_time_unix_nano) / 1_000_000;
+                NonZeroU32::new(interval_ms.try_into().unwrap_or(u32::MAX))
+            }
+            _ => None,
+        };
+
+        Metric::new(name, self.kind, self.value)
+            .with_tags((!tags.is_empty()).then_some(tags))
+            .with_timestamp(Some(timestamp))
+            .with_interval_ms(interval_ms)
+    }
+}
+
+fn kv_into_tag(kv: KeyValue) -> Option<(String, String)> {
+    let value = match kv.value?.value? {
+        PBValue::StringValue(value) => value,
+        value => Value::from(value).to_string_lossy().into_owned(),
+    };
+    Some((kv.key, value))
+}
+
+fn metric_kind(aggregation_temporality: i32) -> MetricKind {
+    if aggregation_temporality == AggregationTemporality::Delta as i32 {
+        MetricKind::Incremental
+    } else {
+        MetricKind::Absolute
+    }
+}
+
+fn has_recorded_value(flags: u32) -> bool {
+    flags & DataPointFlags::FlagNoRecordedValue as u32 == 0
+}
+
+fn number_value(data_point: &NumberDataPoint) -> Option<f64> {
+    match data_point.value? {
+        number_data_point::Value::AsDouble(value) => Some(value),
+        number_data_point::Value::AsInt(value) => Some(value as f64),
+    }
+}
+
+fn histogram_value(data_point: &HistogramDataPoint) -> MetricValue {
+    // The last bucket counts the values above the last explicit bound, which is implied by the total count.
+    let buckets = data_point
+        .explicit_bounds
+        .iter()
+        .zip(&data_point.bucket_counts)
+        .map(|(&upper_limit, &count)| Bucket { upper_limit, count })
+        .collect();
+
+    MetricValue::AggregatedHistogram {
+        buckets,
+        count: data_point.count,
+        sum: data_point.sum.unwrap_or_default(),
+    }
+}
+
+fn exponential_histogram_value(data_point: &ExponentialHistogramDataPoint) -> MetricValue {
+    // The bucket at `index` counts the values in (base^index, base^(index + 1)], negative values being counted
+    // by their absolute value.
+    let base = 2f64.powf(2f64.powi(-data_point.scale));
+    let negative = data_point
+        .negative
+        .as_ref()
+        .map(|buckets| exponential_buckets(buckets, |index| -base.powi(index)))
+        .unwrap_or_default();
+    let positive = data_point
+        .positive
+        .as_ref()
+        .map(|buckets| exponential_buckets(buckets, |index| base.powi(index + 1)))
+        .unwrap_or_default();
+
+    let buckets = negative
+        .into_iter()
+        .rev()
+        .chain(std::iter::once(Bucket {
+            upper_limit: 0.0,
+            count: data_point.zero_count,
+        }))
+        .chain(positive)
+        .collect();
+
+    MetricValue::AggregatedHistogram {
+        buckets,
+        count: data_point.count,
+        sum: data_point.sum.unwrap_or_default(),
+    }
+}
+
+fn exponential_buckets(buckets: &Buckets, upper_limit: impl Fn(i32) -> f64) -> Vec<Bucket> {
+    buckets
+        .bucket_counts
+        .iter()
+        .zip(buckets.offset..)
+        .map(|(&count, index)| Bucket {
+            upper_limit: upper_limit(index),
+            count,
+        })
+        .collect()
+}
+
+fn summary_value(data_point: &SummaryDataPoint) -> MetricValue {
+    MetricValue::AggregatedSummary {
+        quantiles: data_point
+            .quantile_values
+            .iter()
+            .map(|quantile| Quantile {
+                quantile: quantile.quantile,
+                value: quantile.value,
+            })
+            .collect(),
+        count: data_point.count,
+        sum: data_point.sum,
+    }
+}
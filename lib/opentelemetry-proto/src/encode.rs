@@ -0,0 +1,530 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use value::Value;
+use vector_core::{
+    config::log_schema,
+    event::{
+        metric::{Bucket, MetricSketch, Sample},
+        LogEvent, Metric, MetricKind, MetricValue, TraceEvent,
+    },
+};
+
+use super::{
+    convert::{
+        ATTRIBUTES_KEY, DROPPED_ATTRIBUTES_COUNT_KEY, FLAGS_KEY, OBSERVED_TIMESTAMP_KEY,
+        RESOURCE_KEY, RESOURCE_TAG_PREFIX, SEVERITY_NUMBER_KEY, SEVERITY_TEXT_KEY, SPAN_ID_KEY,
+        TRACE_ID_KEY,
+    },
+    proto::{
+        common::v1::{any_value::Value as PBValue, AnyValue, ArrayValue, KeyValue, KeyValueList},
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        metrics::v1::{
+            exponential_histogram_data_point::Buckets, metric::Data, number_data_point,
+            summary_data_point::ValueAtQuantile, AggregationTemporality, ExponentialHistogram,
+            ExponentialHistogramDataPoint, Gauge, Histogram, HistogramDataPoint,
+            Metric as OtlpMetric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum, Summary,
+            SummaryDataPoint,
+        },
+        resource::v1::Resource,
+        trace::v1::{
+            span::{Event as SpanEvent, Link},
+            status::StatusCode,
+            ResourceSpans, ScopeSpans, Span, Status as SpanStatus,
+        },
+    },
+};
+
+/// The scale of the exponential histograms distributions are encoded as, whose bucket boundaries
+/// grow by a factor of 2^(2^-3).
+const EXPONENTIAL_HISTOGRAM_SCALE: i32 = 3;
+
+/// The quantiles sketches are summarized by.
+const SKETCH_QUANTILES: [f64; 5] = [0.5, 0.75, 0.9, 0.95, 0.99];
+
+/// Encodes log events as resource logs, reversing their decoding by the `opentelemetry` source.
+///
+/// The fields of events that aren't those of log records are encoded as attributes, and the logs
+/// of each resource are grouped in a single scope.
+pub fn encode_logs(logs: impl IntoIterator<Item = LogEvent>) -> Vec<ResourceLogs> {
+    group_by_resource(logs.into_iter().map(log_record))
+        .into_iter()
+        .map(|(resource, log_records)| ResourceLogs {
+            resource,
+            scope_logs: vec![ScopeLogs {
+                scope: None,
+                log_records,
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        })
+        .collect()
+}
+
+/// Encodes trace events as resource spans, reversing their decoding by the `opentelemetry`
+/// source.
+pub fn encode_spans(traces: impl IntoIterator<Item = TraceEvent>) -> Vec<ResourceSpans> {
+    group_by_resource(traces.into_iter().map(span))
+        .into_iter()
+        .map(|(resource, spans)| ResourceSpans {
+            resource,
+            scope_spans: vec![ScopeSpans {
+                scope: None,
+                spans,
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        })
+        .collect()
+}
+
+/// Encodes metrics as resource metrics, their tags prefixed with `resource.` being the attributes
+/// of their resource.
+///
+/// Distributions are encoded as exponential histograms and sketches as summaries.
+pub fn encode_metrics(metrics: impl IntoIterator<Item = Metric>) -> Vec<ResourceMetrics> {
+    group_by_resource(metrics.into_iter().map(metric))
+        .into_iter()
+        .map(|(resource, metrics)| ResourceMetrics {
+            resource,
+            scope_metrics: vec![ScopeMetrics {
+                scope: None,
+                metrics,
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        })
+        .collect()
+}
+
+/// Groups items by their resource, keeping the order in which resources are first seen.
+fn group_by_resource<T>(
+    items: impl Iterator<Item = (Option<Resource>, T)>,
+) -> Vec<(Option<Resource>, Vec<T>)> {
+    let mut groups: Vec<(Option<Resource>, Vec<T>)> = Vec::new();
+    for (resource, item) in items {
+        match groups.iter_mut().find(|(other, _)| *other == resource) {
+            Some((_, items)) => items.push(item),
+            None => groups.push((resource, vec![item])),
+        }
+    }
+    groups
+}
+
+fn any_value(value: Value) -> AnyValue {
+    let value = match value {
+        Value::Bytes(bytes) => PBValue::StringValue(String::from_utf8_lossy(&bytes).into_owned()),
+        Value::Integer(value) => PBValue::IntValue(value),
+        Value::Float(value) => PBValue::DoubleValue(value.into_inner()),
+        Value::Boolean(value) => PBValue::BoolValue(value),
+        Value::Array(values) => PBValue::ArrayValue(ArrayValue {
+            values: values.into_iter().map(any_value).collect(),
+        }),
+        Value::Object(fields) => PBValue::KvlistValue(KeyValueList {
+            values: key_values(fields),
+        }),
+        Value::Null => return AnyValue { value: None },
+        value @ (Value::Regex(_) | Value::Timestamp(_)) => {
+            PBValue::StringValue(value.to_string_lossy().into_owned())
+        }
+    };
+    AnyValue { value: Some(value) }
+}
+
+fn key_values(fields: BTreeMap<String, Value>) -> Vec<KeyValue> {
+    fields
+        .into_iter()
+        .map(|(key, value)| KeyValue {
+            key,
+            value: Some(any_value(value)),
+        })
+        .collect()
+}
+
+fn resource(value: Option<Value>) -> Option<Resource> {
+    match value? {
+        Value::Object(attributes) => Some(Resource {
+            attributes: key_values(attributes),
+            dropped_attributes_count: 0,
+        }),
+        _ => None,
+    }
+}
+
+fn attributes(value: Option<Value>) -> Vec<KeyValue> {
+    match value {
+        Some(Value::Object(attributes)) => key_values(attributes),
+        _ => Vec::new(),
+    }
+}
+
+/// Decodes an ID encoded in hexadecimal, leaving invalid IDs empty.
+fn id(value: Option<Value>) -> Vec<u8> {
+    match value {
+        Some(Value::Bytes(bytes)) => hex::decode(bytes).unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+fn string(value: Option<Value>) -> String {
+    match value {
+        Some(Value::Bytes(bytes)) => String::from_utf8_lossy(&bytes).into_owned(),
+        _ => String::new(),
+    }
+}
+
+fn integer(value: Option<Value>) -> i64 {
+    match value {
+        Some(Value::Integer(value)) => value,
+        _ => 0,
+    }
+}
+
+/// The nanoseconds since the epoch of a timestamp, missing timestamps being zero.
+fn unix_nano(value: Option<Value>) -> u64 {
+    match value {
+        Some(Value::Timestamp(timestamp)) => timestamp_unix_nano(timestamp),
+        _ => 0,
+    }
+}
+
+fn timestamp_unix_nano(timestamp: DateTime<Utc>) -> u64 {
+    timestamp.timestamp_nanos().try_into().unwrap_or_default()
+}
+
+fn log_record(mut log: LogEvent) -> (Option<Resource>, LogRecord) {
+    let body = log.remove(log_schema().message_key()).map(any_value);
+    let time_unix_nano = unix_nano(log.remove(log_schema().timestamp_key()));
+    log.remove(log_schema().source_type_key());
+
+    let mut fields = match log.into_parts().0 {
+        Value::Object(fields) => fields,
+        _ => BTreeMap::new(),
+    };
+    let resource = resource(fields.remove(RESOURCE_KEY));
+    let mut record = LogRecord {
+        time_unix_nano,
+        observed_time_unix_nano: unix_nano(fields.remove(OBSERVED_TIMESTAMP_KEY)),
+        severity_number: integer(fields.remove(SEVERITY_NUMBER_KEY)) as i32,
+        severity_text: string(fields.remove(SEVERITY_TEXT_KEY)),
+        body,
+        attributes: Vec::new(),
+        dropped_attributes_count: integer(fields.remove(DROPPED_ATTRIBUTES_COUNT_KEY)) as u32,
+        flags: integer(fields.remove(FLAGS_KEY)) as u32,
+        trace_id: id(fields.remove(TRACE_ID_KEY)),
+        span_id: id(fields.remove(SPAN_ID_KEY)),
+    };
+
+    // The other fields of events, sent by sources other than `opentelemetry`, are attributes too.
+    let mut attributes = match fields.remove(ATTRIBUTES_KEY) {
+        Some(Value::Object(attributes)) => attributes,
+        _ => BTreeMap::new(),
+    };
+    for (key, value) in fields {
+        attributes.entry(key).or_insert(value);
+    }
+    record.attributes = key_values(attributes);
+
+    (resource, record)
+}
+
+fn span(trace: TraceEvent) -> (Option<Resource>, Span) {
+    let mut fields = trace.into_parts().0;
+    let resource = resource(fields.remove(RESOURCE_KEY));
+    let events = match fields.remove("events") {
+        Some(Value::Array(events)) => events.into_iter().filter_map(span_event).collect(),
+        _ => Vec::new(),
+    };
+    let links = match fields.remove("links") {
+        Some(Value::Array(links)) => links.into_iter().filter_map(span_link).collect(),
+        _ => Vec::new(),
+    };
+    let status = match fields.remove("status") {
+        Some(Value::Object(mut status)) => Some(SpanStatus {
+            message: string(status.remove("message")),
+            code: integer(status.remove("code")) as i32,
+        }),
+        _ => None,
+    };
+    let span = Span {
+        trace_id: id(fields.remove(TRACE_ID_KEY)),
+        span_id: id(fields.remove(SPAN_ID_KEY)),
+        trace_state: string(fields.remove("trace_state")),
+        parent_span_id: id(fields.remove("parent_span_id")),
+        name: string(fields.remove("name")),
+        kind: integer(fields.remove("kind")) as i32,
+        start_time_unix_nano: unix_nano(fields.remove("start_time_unix_nano")),
+        end_time_unix_nano: unix_nano(fields.remove("end_time_unix_nano")),
+        attributes: attributes(fields.remove(ATTRIBUTES_KEY)),
+        dropped_attributes_count: integer(fields.remove(DROPPED_ATTRIBUTES_COUNT_KEY)) as u32,
+        events,
+        dropped_events_count: integer(fields.remove("dropped_events_count")) as u32,
+        links,
+        dropped_links_count: integer(fields.remove("dropped_links_count")) as u32,
+        status: status
+            .filter(|status| status.code != StatusCode::Unset as i32 || !status.message.is_empty()),
+    };
+    (resource, span)
+}
+
+fn span_event(event: Value) -> Option<SpanEvent> {
+    match event {
+        Value::Object(mut event) => Some(SpanEvent {
+            time_unix_nano: unix_nano(event.remove("time_unix_nano")),
+            name: string(event.remove("name")),
+            attributes: attributes(event.remove(ATTRIBUTES_KEY)),
+            dropped_attributes_count: integer(event.remove(DROPPED_ATTRIBUTES_COUNT_KEY)) as u32,
+        }),
+        _ => None,
+    }
+}
+
+fn span_link(link: Value) -> Option<Link> {
+    match link {
+        Value::Object(mut link) => Some(Link {
+            trace_id: id(link.remove(TRACE_ID_KEY)),
+            span_id: id(link.remove(SPAN_ID_KEY)),
+            trace_state: string(link.remove("trace_state")),
+            attributes: attributes(link.remove(ATTRIBUTES_KEY)),
+            dropped_attributes_count: integer(link.remove(DROPPED_ATTRIBUTES_COUNT_KEY)) as u32,
+        }),
+        _ => None,
+    }
+}
+
+fn aggregation_temporality(kind: MetricKind) -> i32 {
+    match kind {
+        MetricKind::Incremental => AggregationTemporality::Delta as i32,
+        MetricKind::Absolute => AggregationTemporality::Cumulative as i32,
+    }
+}
+
+fn metric(metric: Metric) -> (Option<Resource>, OtlpMetric) {
+    let time_unix_nano = metric.timestamp().map_or(0, timestamp_unix_nano);
+    // Incremental metrics cover the interval since their start time.
+    let start_time_unix_nano = match (metric.kind(), metric.interval_ms()) {
+        (MetricKind::Incremental, Some(interval_ms)) if time_unix_nano > 0 => {
+            time_unix_nano.saturating_sub(u64::from(interval_ms.get()) * 1_000_000)
+        }
+        _ => 0,
+    };
+
+    let mut resource_attributes = Vec::new();
+    let mut attributes = Vec::new();
+    if let Some(tags) = metric.tags() {
+        for (key, value) in tags.iter_single() {
+            let value = Some(AnyValue {
+                value: Some(PBValue::StringValue(value.to_owned())),
+            });
+            match key.strip_prefix(RESOURCE_TAG_PREFIX) {
+                Some(key) => resource_attributes.push(KeyValue {
+                    key: key.to_owned(),
+                    value,
+                }),
+                None => attributes.push(KeyValue {
+                    key: key.to_owned(),
+                    value,
+                }),
+            }
+        }
+    }
+    let resource = (!resource_attributes.is_empty()).then(|| Resource {
+        attributes: resource_attributes,
+        dropped_attributes_count: 0,
+    });
+
+    let number = |value: f64| NumberDataPoint {
+        attributes: attributes.clone(),
+        start_time_unix_nano,
+        time_unix_nano,
+        value: Some(number_data_point::Value::AsDouble(value)),
+        exemplars: Vec::new(),
+        flags: 0,
+    };
+    let summary = |count: u64, sum: f64, quantile_values: Vec<ValueAtQuantile>| {
+        Data::Summary(Summary {
+            data_points: vec![SummaryDataPoint {
+                attributes: attributes.clone(),
+                start_time_unix_nano,
+                time_unix_nano,
+                count,
+                sum,
+                quantile_values,
+                flags: 0,
+            }],
+        })
+    };
+    let aggregation_temporality = aggregation_temporality(metric.kind());
+
+    let data = match metric.value() {
+        MetricValue::Counter { value } => Data::Sum(Sum {
+            data_points: vec![number(*value)],
+            aggregation_temporality,
+            is_monotonic: true,
+        }),
+        // Incremental gauges are changes of their values, which are only expressed by sums.
+        MetricValue::Gauge { value } if metric.kind() == MetricKind::Incremental => {
+            Data::Sum(Sum {
+                data_points: vec![number(*value)],
+                aggregation_temporality,
+                is_monotonic: false,
+            })
+        }
+        MetricValue::Gauge { value } => Data::Gauge(Gauge {
+            data_points: vec![number(*value)],
+        }),
+        MetricValue::Set { values } => Data::Gauge(Gauge {
+            data_points: vec![number(values.len() as f64)],
+        }),
+        MetricValue::Distribution { samples, .. } => {
+            Data::ExponentialHistogram(ExponentialHistogram {
+                data_points: vec![ExponentialHistogramDataPoint {
+                    attributes: attributes.clone(),
+                    start_time_unix_nano,
+                    time_unix_nano,
+                    ..exponential_histogram(samples)
+                }],
+                aggregation_temporality,
+            })
+        }
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => {
+            let (explicit_bounds, bucket_counts) = histogram_buckets(buckets, *count);
+            Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    attributes: attributes.clone(),
+                    start_time_unix_nano,
+                    time_unix_nano,
+                    count: *count,
+                    sum: Some(*sum),
+                    bucket_counts,
+                    explicit_bounds,
+                    exemplars: Vec::new(),
+                    flags: 0,
+                    min: None,
+                    max: None,
+                }],
+                aggregation_temporality,
+            })
+        }
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => summary(
+            *count,
+            *sum,
+            quantiles
+                .iter()
+                .map(|quantile| ValueAtQuantile {
+                    quantile: quantile.quantile,
+                    value: quantile.value,
+                })
+                .collect(),
+        ),
+        MetricValue::Sketch {
+            sketch: MetricSketch::AgentDDSketch(sketch),
+        } => summary(
+            u64::from(sketch.count()),
+            sketch.sum().unwrap_or_default(),
+            SKETCH_QUANTILES
+                .iter()
+                .filter_map(|&quantile| {
+                    Some(ValueAtQuantile {
+                        quantile,
+                        value: sketch.quantile(quantile)?,
+                    })
+                })
+                .collect(),
+        ),
+    };
+
+    let name = match metric.namespace() {
+        Some(namespace) => format!("{}.{}", namespace, metric.name()),
+        None => metric.name().to_owned(),
+    };
+    (
+        resource,
+        OtlpMetric {
+            name,
+            description: String::new(),
+            unit: String::new(),
+            data: Some(data),
+        },
+    )
+}
+
+/// The explicit bounds and counts of the buckets of a histogram, the last bucket counting the
+/// values above the last bound, which includes those of buckets with infinite upper limits.
+fn histogram_buckets(buckets: &[Bucket], count: u64) -> (Vec<f64>, Vec<u64>) {
+    let (explicit_bounds, mut bucket_counts): (Vec<_>, Vec<_>) = buckets
+        .iter()
+        .filter(|bucket| bucket.upper_limit.is_finite())
+        .map(|bucket| (bucket.upper_limit, bucket.count))
+        .unzip();
+    let counted = bucket_counts.iter().sum::<u64>();
+    bucket_counts.push(count.saturating_sub(counted));
+    (explicit_bounds, bucket_counts)
+}
+
+/// Encodes samples as an exponential histogram, whose bucket at `index` counts the values in
+/// (base^index, base^(index + 1)], negative values being counted by their absolute value.
+///
+/// Infinite and NaN values are left out, as they don't belong to any bucket.
+fn exponential_histogram(samples: &[Sample]) -> ExponentialHistogramDataPoint {
+    let mut count = 0;
+    let mut sum = 0.0;
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    let mut zero_count = 0;
+    let mut positive = BTreeMap::new();
+    let mut negative = BTreeMap::new();
+    for sample in samples.iter().filter(|sample| sample.value.is_finite()) {
+        let rate = u64::from(sample.rate);
+        count += rate;
+        sum += sample.value * rate as f64;
+        min = min.min(sample.value);
+        max = max.max(sample.value);
+        if sample.value == 0.0 {
+            zero_count += rate;
+        } else if sample.value > 0.0 {
+            *positive.entry(bucket_index(sample.value)).or_insert(0) += rate;
+        } else {
+            *negative.entry(bucket_index(-sample.value)).or_insert(0) += rate;
+        }
+    }
+
+    ExponentialHistogramDataPoint {
+        attributes: Vec::new(),
+        start_time_unix_nano: 0,
+        time_unix_nano: 0,
+        count,
+        sum: Some(sum),
+        scale: EXPONENTIAL_HISTOGRAM_SCALE,
+        zero_count,
+        positive: exponential_buckets(&positive),
+        negative: exponential_buckets(&negative),
+        flags: 0,
+        exemplars: Vec::new(),
+        min: (count > 0).then_some(min),
+        max: (count > 0).then_some(max),
+    }
+}
+
+fn bucket_index(value: f64) -> i32 {
+    (value.log2() * f64::from(1 << EXPONENTIAL_HISTOGRAM_SCALE)).ceil() as i32 - 1
+}
+
+fn exponential_buckets(buckets: &BTreeMap<i32, u64>) -> Option<Buckets> {
+    let offset = *buckets.keys().next()?;
+    let last = *buckets.keys().next_back()?;
+    Some(Buckets {
+        offset,
+        bucket_counts: (offset..=last)
+            .map(|index| buckets.get(&index).copied().unwrap_or_default())
+            .collect(),
+    })
+}
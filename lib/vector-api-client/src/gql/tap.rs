@@ -53,6 +53,21 @@ impl From<TapEncodingFormat>
     }
 }
 
+impl output_events_by_component_id_patterns_subscription::EventDeliveryStatus {
+    /// The delivery status of events, as printed by clients.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::PENDING => "pending",
+            Self::DROPPED => "dropped",
+            Self::DELIVERED => "delivered",
+            Self::ERRORED => "errored",
+            Self::REJECTED => "rejected",
+            Self::RECORDED => "recorded",
+            Self::Other(status) => status,
+        }
+    }
+}
+
 pub trait TapSubscriptionExt {
     /// Executes an output events subscription.
     fn output_events_by_component_id_patterns_subscription(
@@ -62,6 +77,7 @@ pub trait TapSubscriptionExt {
         encoding: TapEncodingFormat,
         limit: i64,
         interval: i64,
+        delivery_status: bool,
     ) -> crate::BoxedSubscription<OutputEventsByComponentIdPatternsSubscription>;
 }
 
@@ -74,6 +90,7 @@ impl TapSubscriptionExt for crate::SubscriptionClient {
         encoding: TapEncodingFormat,
         limit: i64,
         interval: i64,
+        delivery_status: bool,
     ) -> BoxedSubscription<OutputEventsByComponentIdPatternsSubscription> {
         let request_body = OutputEventsByComponentIdPatternsSubscription::build_query(
             output_events_by_component_id_patterns_subscription::Variables {
@@ -82,6 +99,7 @@ impl TapSubscriptionExt for crate::SubscriptionClient {
                 limit,
                 interval,
                 encoding: encoding.into(),
+                delivery_status,
             },
         );
 
@@ -86,6 +86,23 @@ impl EventFinalizers {
         }
     }
 
+    /// Returns the status of the event finalizers in the collection, once it holds the last
+    /// references to all of them.
+    ///
+    /// This is `None` while copies of their events are still being processed elsewhere, as their
+    /// status may change until then.
+    #[must_use]
+    pub fn final_status(&self) -> Option<EventStatus> {
+        self.0
+            .iter()
+            .try_fold(EventStatus::Dropped, |status, finalizer| {
+                (Arc::strong_count(finalizer) == 1).then(|| match finalizer.status.load() {
+                    EventStatus::Dropped => status,
+                    finalizer_status => status.update(finalizer_status),
+                })
+            })
+    }
+
     /// Consumes all event finalizers and updates their underlying batches immediately.
     pub fn update_sources(&mut self) {
         let finalizers = mem::take(&mut self.0);
@@ -443,6 +460,26 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
     }
 
+    #[test]
+    fn final_status() {
+        let (fin1, _receiver) = make_finalizer();
+        let fin2 = fin1.clone();
+        assert_eq!(fin1.final_status(), None);
+
+        fin2.update_status(EventStatus::Delivered);
+        drop(fin2);
+        assert_eq!(fin1.final_status(), Some(EventStatus::Delivered));
+
+        let (mut fin3, _receiver) = make_finalizer();
+        fin3.update_status(EventStatus::Errored);
+        fin3.merge(fin1);
+        assert_eq!(fin3.final_status(), Some(EventStatus::Errored));
+        assert_eq!(
+            EventFinalizers::default().final_status(),
+            Some(EventStatus::Dropped)
+        );
+    }
+
     #[test]
     fn merge_events() {
         let mut fin0 = EventFinalizers::default();
@@ -107,7 +107,7 @@ pub struct ByteSize(pub usize);
 pub struct Count(pub usize);
 
 /// Holds the tuple `(count_of_events, size_of_events_in_bytes)`.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub struct CountByteSize(pub usize, pub usize);
 
 // Wrapper types used to hold parameters for registering events
@@ -1,7 +1,70 @@
-use std::ops::Add;
+use std::{collections::BTreeMap, ops::Add, sync::Arc, time::Duration};
+
+use metrics::{counter, gauge, histogram, Label};
+
+use crate::internal_event::{
+    register, ByteSize, BytesSent, CountByteSize, EventsSent, InternalEvent,
+    InternalEventHandle as _, Output, Registered, SharedString,
+};
+
+/// Where events come from, which the counts and sizes of the events of requests are grouped by.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct EventOrigin {
+    /// The source the events were received by.
+    pub source: Option<Arc<str>>,
+    /// The service the events are about.
+    pub service: Option<Arc<str>>,
+    /// The tenant the events belong to.
+    pub tenant: Option<Arc<str>>,
+}
+
+impl EventOrigin {
+    /// The tags of internal metrics about events of this origin, for the parts of it that are known.
+    fn labels(&self) -> Vec<Label> {
+        [
+            ("source", &self.source),
+            ("service", &self.service),
+            ("tenant", &self.tenant),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| {
+            value
+                .as_ref()
+                .map(|value| Label::new(key, value.to_string()))
+        })
+        .collect()
+    }
+}
+
+/// The counts and estimated JSON-encoded sizes of events, grouped by their origins.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GroupedCountByteSize {
+    sizes: BTreeMap<EventOrigin, CountByteSize>,
+}
+
+impl GroupedCountByteSize {
+    /// Adds `count` events of `origin`, of `byte_size` bytes in all.
+    pub fn add(&mut self, origin: EventOrigin, count: usize, byte_size: usize) {
+        let size = self.sizes.entry(origin).or_default();
+        size.0 += count;
+        size.1 += byte_size;
+    }
+
+    /// Adds all of the events of `other`.
+    pub fn merge(&mut self, other: &Self) {
+        for (origin, size) in &other.sizes {
+            self.add(origin.clone(), size.0, size.1);
+        }
+    }
+
+    /// Returns the count and size of the events of each origin.
+    pub fn iter(&self) -> impl Iterator<Item = (&EventOrigin, CountByteSize)> {
+        self.sizes.iter().map(|(origin, size)| (origin, *size))
+    }
+}
 
 /// Metadata for batch requests.
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct RequestMetadata {
     /// Number of events represented by this batch request.
     event_count: usize,
@@ -15,9 +78,18 @@ pub struct RequestMetadata {
     ///
     /// This is akin to the bytes sent/received over the network, regardless of whether or not compression was used.
     request_wire_size: usize,
+    /// Count and estimated JSON-encoded size of the events in this batch request, grouped by their
+    /// origins.
+    ///
+    /// This is empty for requests whose events aren't known by the time they're built.
+    grouped_sizes: GroupedCountByteSize,
+    /// The endpoint, or partition, of the destination this batch request is sent to.
+    ///
+    /// The duration and compression ratio of requests are tagged with it, so it should only take a
+    /// handful of values.
+    endpoint: Option<Arc<str>>,
 }
 
-// TODO: Make this struct the object which emits the actual internal telemetry i.e. events sent, bytes sent, etc.
 impl RequestMetadata {
     #[must_use]
     pub fn new(
@@ -33,9 +105,25 @@ impl RequestMetadata {
             events_estimated_json_encoded_byte_size,
             request_encoded_size,
             request_wire_size,
+            grouped_sizes: GroupedCountByteSize::default(),
+            endpoint: None,
         }
     }
 
+    /// Sets the counts and sizes of the events of this batch request, grouped by their origins.
+    #[must_use]
+    pub fn with_grouped_sizes(mut self, grouped_sizes: GroupedCountByteSize) -> Self {
+        self.grouped_sizes = grouped_sizes;
+        self
+    }
+
+    /// Sets the endpoint, or partition, of the destination this batch request is sent to.
+    #[must_use]
+    pub fn with_endpoint(mut self, endpoint: impl Into<Arc<str>>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
     #[must_use]
     pub const fn event_count(&self) -> usize {
         self.event_count
@@ -61,6 +149,47 @@ impl RequestMetadata {
         self.request_wire_size
     }
 
+    #[must_use]
+    pub const fn grouped_sizes(&self) -> &GroupedCountByteSize {
+        &self.grouped_sizes
+    }
+
+    #[must_use]
+    pub fn endpoint(&self) -> Option<&str> {
+        self.endpoint.as_deref()
+    }
+
+    /// The tags of internal metrics about this batch request, for the endpoint it's sent to.
+    fn endpoint_labels(&self) -> Vec<Label> {
+        self.endpoint
+            .iter()
+            .map(|endpoint| Label::new("endpoint", endpoint.to_string()))
+            .collect()
+    }
+
+    /// Returns the ratio of the encoded size of this batch request to its on-the-wire size.
+    ///
+    /// This is `None` for requests without any bytes on the wire.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn compression_ratio(&self) -> Option<f64> {
+        (self.request_wire_size > 0)
+            .then(|| self.request_encoded_size as f64 / self.request_wire_size as f64)
+    }
+
+    /// Adds bytes to the encoded and on-the-wire sizes of this batch request, as its payload is
+    /// assembled.
+    pub fn add_request_sizes(&mut self, encoded_size: usize, wire_size: usize) {
+        self.request_encoded_size += encoded_size;
+        self.request_wire_size += wire_size;
+    }
+
+    /// Sets the encoded and on-the-wire sizes of this batch request, once its payload is final.
+    pub fn set_request_sizes(&mut self, encoded_size: usize, wire_size: usize) {
+        self.request_encoded_size = encoded_size;
+        self.request_wire_size = wire_size;
+    }
+
     /// Constructs a `RequestMetadata` by summation of the "batch" of `RequestMetadata` provided.
     #[must_use]
     pub fn from_batch<T: IntoIterator<Item = RequestMetadata>>(metadata_iter: T) -> Self {
@@ -78,6 +207,8 @@ impl<'a> Add<&'a RequestMetadata> for RequestMetadata {
 
     /// Adds the other `RequestMetadata` to this one.
     fn add(self, other: &'a Self::Output) -> Self::Output {
+        let mut grouped_sizes = self.grouped_sizes;
+        grouped_sizes.merge(&other.grouped_sizes);
         Self::Output {
             event_count: self.event_count + other.event_count,
             events_byte_size: self.events_byte_size + other.events_byte_size,
@@ -85,8 +216,92 @@ impl<'a> Add<&'a RequestMetadata> for RequestMetadata {
                 + other.events_estimated_json_encoded_byte_size,
             request_encoded_size: self.request_encoded_size + other.request_encoded_size,
             request_wire_size: self.request_wire_size + other.request_wire_size,
+            grouped_sizes,
+            endpoint: self.endpoint.or_else(|| other.endpoint.clone()),
+        }
+    }
+}
+
+// Emits the events and bytes sent in delivered requests, as described by their metadata, along with
+// the events sent of each origin. Bytes are only emitted when a protocol is given, and are counted
+// before compression.
+crate::registered_event!(
+    RequestsSent {
+        protocol: Option<SharedString>,
+    } => {
+        events_sent: Registered<EventsSent> = register(EventsSent::from(Output(None))),
+        bytes_sent: Option<Registered<BytesSent>> = self
+            .protocol
+            .map(|protocol| register(BytesSent { protocol })),
+    }
+
+    fn emit(&self, metadata: RequestMetadata) {
+        self.events_sent.emit(CountByteSize(
+            metadata.event_count,
+            metadata.events_estimated_json_encoded_byte_size,
+        ));
+        if let Some(bytes_sent) = &self.bytes_sent {
+            bytes_sent.emit(ByteSize(metadata.request_encoded_size));
+        }
+        for (origin, CountByteSize(count, byte_size)) in metadata.grouped_sizes.iter() {
+            let labels = origin.labels();
+            counter!("component_sent_events_by_origin_total", count as u64, &labels);
+            counter!("component_sent_event_bytes_by_origin_total", byte_size as u64, &labels);
+        }
+    }
+);
+
+/// The events of a request that failed, grouped by their origins.
+#[derive(Debug)]
+pub struct RequestEventsDropped<'a> {
+    pub metadata: &'a RequestMetadata,
+}
+
+impl<'a> InternalEvent for RequestEventsDropped<'a> {
+    fn emit(self) {
+        for (origin, CountByteSize(count, byte_size)) in self.metadata.grouped_sizes.iter() {
+            let labels = origin.labels();
+            counter!(
+                "component_discarded_events_by_origin_total",
+                count as u64,
+                &labels
+            );
+            counter!(
+                "component_discarded_event_bytes_by_origin_total",
+                byte_size as u64,
+                &labels
+            );
         }
     }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("RequestEventsDropped")
+    }
+}
+
+/// A request that completed, whether it succeeded or not, after `duration`.
+#[derive(Debug)]
+pub struct RequestCompleted<'a> {
+    pub metadata: &'a RequestMetadata,
+    pub duration: Duration,
+}
+
+impl<'a> InternalEvent for RequestCompleted<'a> {
+    fn emit(self) {
+        let labels = self.metadata.endpoint_labels();
+        histogram!("component_request_duration_seconds", self.duration, &labels);
+        if let Some(compression_ratio) = self.metadata.compression_ratio() {
+            gauge!(
+                "component_request_compression_ratio",
+                compression_ratio,
+                &labels
+            );
+        }
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("RequestCompleted")
+    }
 }
 
 /// Objects implementing this trait have metadata that describes the request.
@@ -94,3 +309,75 @@ pub trait MetaDescriptive {
     /// Returns the `RequestMetadata` associated with this object.
     fn get_metadata(&self) -> RequestMetadata;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn origin(source: &str, tenant: Option<&str>) -> EventOrigin {
+        EventOrigin {
+            source: Some(Arc::from(source)),
+            service: None,
+            tenant: tenant.map(Arc::from),
+        }
+    }
+
+    #[test]
+    fn sums_grouped_sizes_of_batches() {
+        let mut first = GroupedCountByteSize::default();
+        first.add(origin("in", Some("acme")), 2, 20);
+        first.add(origin("in", None), 1, 5);
+        let mut second = GroupedCountByteSize::default();
+        second.add(origin("in", Some("acme")), 1, 10);
+
+        let metadata = RequestMetadata::from_batch([
+            RequestMetadata::new(3, 0, 0, 0, 25).with_grouped_sizes(first),
+            RequestMetadata::new(1, 0, 0, 0, 10).with_grouped_sizes(second),
+        ]);
+        assert_eq!(metadata.event_count(), 4);
+        assert_eq!(
+            metadata.grouped_sizes().iter().collect::<Vec<_>>(),
+            vec![
+                (&origin("in", None), CountByteSize(1, 5)),
+                (&origin("in", Some("acme")), CountByteSize(3, 30)),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_endpoint_of_batches() {
+        let metadata = RequestMetadata::from_batch([
+            RequestMetadata::new(1, 0, 0, 0, 0),
+            RequestMetadata::new(1, 0, 0, 0, 0).with_endpoint("series"),
+        ]);
+        assert_eq!(metadata.endpoint(), Some("series"));
+        assert_eq!(
+            metadata.endpoint_labels(),
+            vec![Label::new("endpoint", "series")]
+        );
+    }
+
+    #[test]
+    fn tracks_request_sizes() {
+        let mut metadata = RequestMetadata::new(2, 0, 0, 0, 0);
+        assert_eq!(metadata.compression_ratio(), None);
+
+        metadata.add_request_sizes(60, 20);
+        metadata.add_request_sizes(40, 5);
+        assert_eq!(metadata.request_encoded_size(), 100);
+        assert_eq!(metadata.request_wire_size(), 25);
+        assert_eq!(metadata.compression_ratio(), Some(4.0));
+
+        metadata.set_request_sizes(10, 10);
+        assert_eq!(metadata.compression_ratio(), Some(1.0));
+    }
+
+    #[test]
+    fn labels_known_parts_of_origins() {
+        let labels = origin("in", Some("acme")).labels();
+        assert_eq!(
+            labels,
+            vec![Label::new("source", "in"), Label::new("tenant", "acme")]
+        );
+    }
+}
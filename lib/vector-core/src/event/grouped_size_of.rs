@@ -0,0 +1,30 @@
+use vector_common::request_metadata::GroupedCountByteSize;
+
+/// Return the count and estimated JSON-encoded size of events, grouped by their origins.
+///
+/// The origin of an event is made of the source it was received by, the service it's about, and
+/// the tenant it belongs to, as described by `EventMetadata::origin`.
+pub trait GroupedSizeOf {
+    /// Adds the events to `grouped_sizes`.
+    fn add_grouped_sizes(&self, grouped_sizes: &mut GroupedCountByteSize);
+
+    fn grouped_size_of(&self) -> GroupedCountByteSize {
+        let mut grouped_sizes = GroupedCountByteSize::default();
+        self.add_grouped_sizes(&mut grouped_sizes);
+        grouped_sizes
+    }
+}
+
+impl<T: GroupedSizeOf> GroupedSizeOf for &T {
+    fn add_grouped_sizes(&self, grouped_sizes: &mut GroupedCountByteSize) {
+        T::add_grouped_sizes(self, grouped_sizes);
+    }
+}
+
+impl<T: GroupedSizeOf> GroupedSizeOf for Vec<T> {
+    fn add_grouped_sizes(&self, grouped_sizes: &mut GroupedCountByteSize) {
+        for item in self {
+            item.add_grouped_sizes(grouped_sizes);
+        }
+    }
+}
@@ -14,11 +14,15 @@ use crossbeam_utils::atomic::AtomicCell;
 use lookup::lookup_v2::TargetPath;
 use lookup::PathPrefix;
 use serde::{Deserialize, Serialize, Serializer};
-use vector_common::EventDataEq;
+use vector_common::{
+    request_metadata::{EventOrigin, GroupedCountByteSize},
+    EventDataEq,
+};
 
 use super::{
     estimated_json_encoded_size_of::EstimatedJsonEncodedSizeOf,
     finalization::{BatchNotifier, EventFinalizer},
+    grouped_size_of::GroupedSizeOf,
     metadata::EventMetadata,
     util, EventFinalizers, Finalizable, Value,
 };
@@ -206,6 +210,12 @@ impl EstimatedJsonEncodedSizeOf for LogEvent {
     }
 }
 
+impl GroupedSizeOf for LogEvent {
+    fn add_grouped_sizes(&self, grouped_sizes: &mut GroupedCountByteSize) {
+        grouped_sizes.add(self.origin(), 1, self.estimated_json_encoded_size_of());
+    }
+}
+
 impl LogEvent {
     #[must_use]
     pub fn new_with_metadata(metadata: EventMetadata) -> Self {
@@ -271,6 +281,13 @@ impl LogEvent {
             .and_then(|path| self.inner.fields.get_by_path(path))
     }
 
+    /// Returns the origin of the event, whose service is the string value of the field with the
+    /// `service` meaning, if any.
+    pub fn origin(&self) -> EventOrigin {
+        let service = self.get_by_meaning("service").and_then(Value::as_str);
+        self.metadata.origin(service.as_deref())
+    }
+
     // TODO(Jean): Once the event API uses `Lookup`, the allocation here can be removed.
     pub fn find_key_by_meaning(&self, meaning: impl AsRef<str>) -> Option<String> {
         self.metadata()
@@ -5,7 +5,7 @@ use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use value::{Kind, Secrets, Value};
-use vector_common::EventDataEq;
+use vector_common::{request_metadata::EventOrigin, EventDataEq};
 
 use super::{BatchNotifier, EventFinalizer, EventFinalizers, EventStatus};
 use crate::config::LogNamespace;
@@ -13,6 +13,7 @@ use crate::{schema, ByteSizeOf};
 
 const DATADOG_API_KEY: &str = "datadog_api_key";
 const SPLUNK_HEC_TOKEN: &str = "splunk_hec_token";
+const TENANT: &str = "tenant";
 
 /// The top-level metadata structure contained by both `struct Metric`
 /// and `struct LogEvent` types.
@@ -35,6 +36,10 @@ pub struct EventMetadata {
     /// TODO(Jean): must not skip serialization to track schemas across restarts.
     #[serde(default = "default_schema_definition", skip)]
     schema_definition: Arc<schema::Definition>,
+
+    /// The ID of the source the event was received by.
+    #[serde(default, skip)]
+    source_id: Option<Arc<str>>,
 }
 
 fn default_metadata_value() -> Value {
@@ -98,6 +103,7 @@ impl Default for EventMetadata {
             secrets: Secrets::new(),
             finalizers: Default::default(),
             schema_definition: default_schema_definition(),
+            source_id: None,
         }
     }
 }
@@ -202,6 +208,33 @@ impl EventMetadata {
     pub fn set_schema_definition(&mut self, definition: &Arc<schema::Definition>) {
         self.schema_definition = Arc::clone(definition);
     }
+
+    /// Get the ID of the source the event was received by, if it's known.
+    pub fn source_id(&self) -> Option<&Arc<str>> {
+        self.source_id.as_ref()
+    }
+
+    /// Set the ID of the source the event was received by.
+    pub fn set_source_id(&mut self, source_id: &Arc<str>) {
+        self.source_id = Some(Arc::clone(source_id));
+    }
+
+    /// Returns the origin of the event, given the service it's about.
+    ///
+    /// The tenant of the event is the string value of the `tenant` field of the metadata, if any.
+    pub fn origin(&self, service: Option<&str>) -> EventOrigin {
+        let tenant = self
+            .value
+            .as_object()
+            .and_then(|fields| fields.get(TENANT))
+            .and_then(Value::as_str)
+            .map(|tenant| Arc::from(tenant.as_ref()));
+        EventOrigin {
+            source: self.source_id.clone(),
+            service: service.map(Arc::from),
+            tenant,
+        }
+    }
 }
 
 impl EventDataEq for EventMetadata {
@@ -249,4 +282,26 @@ mod test {
         assert_eq!(metadata.datadog_api_key().unwrap().as_ref(), SECRET);
         assert_eq!(metadata.splunk_hec_token().unwrap().as_ref(), SECRET2);
     }
+
+    #[test]
+    fn origin() {
+        assert_eq!(
+            EventMetadata::default().origin(None),
+            EventOrigin::default()
+        );
+
+        let mut metadata = EventMetadata::default_with_value(Value::from(BTreeMap::from([(
+            TENANT.to_string(),
+            Value::from("acme"),
+        )])));
+        metadata.set_source_id(&Arc::from("in"));
+        assert_eq!(
+            metadata.origin(Some("checkout")),
+            EventOrigin {
+                source: Some(Arc::from("in")),
+                service: Some(Arc::from("checkout")),
+                tenant: Some(Arc::from("acme")),
+            }
+        );
+    }
 }
@@ -7,7 +7,10 @@ use std::{
 };
 
 use chrono::{DateTime, Utc};
-use vector_common::EventDataEq;
+use vector_common::{
+    request_metadata::{EventOrigin, GroupedCountByteSize},
+    EventDataEq,
+};
 use vector_config::configurable_component;
 #[cfg(feature = "vrl")]
 use vrl_lib::prelude::VrlValueConvert;
@@ -15,7 +18,7 @@ use vrl_lib::prelude::VrlValueConvert;
 use crate::{
     event::{
         estimated_json_encoded_size_of::EstimatedJsonEncodedSizeOf, BatchNotifier, EventFinalizer,
-        EventFinalizers, EventMetadata, Finalizable,
+        EventFinalizers, EventMetadata, Finalizable, GroupedSizeOf,
     },
     ByteSizeOf,
 };
@@ -339,6 +342,12 @@ impl Metric {
         self.tags().and_then(|t| t.get(name)).map(ToOwned::to_owned)
     }
 
+    /// Returns the origin of the metric, whose service is the value of its `service` tag, if any.
+    pub fn origin(&self) -> EventOrigin {
+        let service = self.tags().and_then(|tags| tags.get("service"));
+        self.metadata.origin(service)
+    }
+
     /// Inserts a tag into this metric.
     ///
     /// If the metric did not have this tag, `None` will be returned. Otherwise, `Some(String)` will be returned,
@@ -457,6 +466,12 @@ impl EstimatedJsonEncodedSizeOf for Metric {
     }
 }
 
+impl GroupedSizeOf for Metric {
+    fn add_grouped_sizes(&self, grouped_sizes: &mut GroupedCountByteSize) {
+        grouped_sizes.add(self.origin(), 1, self.estimated_json_encoded_size_of());
+    }
+}
+
 impl Finalizable for Metric {
     fn take_finalizers(&mut self) -> EventFinalizers {
         self.metadata.take_finalizers()
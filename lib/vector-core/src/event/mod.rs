@@ -12,6 +12,7 @@ pub use finalization::{
     BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizer, EventFinalizers, EventStatus,
     Finalizable,
 };
+pub use grouped_size_of::GroupedSizeOf;
 pub use log_event::LogEvent;
 pub use metadata::{EventMetadata, WithMetadata};
 pub use metric::{Metric, MetricKind, MetricTags, MetricValue, StatisticKind};
@@ -19,7 +20,11 @@ pub use r#ref::{EventMutRef, EventRef};
 use serde::{Deserialize, Serialize};
 pub use trace::TraceEvent;
 use vector_buffers::EventCount;
-use vector_common::{finalization, EventDataEq};
+use vector_common::{
+    finalization,
+    request_metadata::{EventOrigin, GroupedCountByteSize},
+    EventDataEq,
+};
 #[cfg(feature = "vrl")]
 pub use vrl_target::{TargetEvents, VrlTarget};
 
@@ -27,6 +32,7 @@ pub mod array;
 pub mod discriminant;
 pub mod error;
 mod estimated_json_encoded_size_of;
+mod grouped_size_of;
 mod log_event;
 #[cfg(feature = "lua")]
 pub mod lua;
@@ -74,6 +80,16 @@ impl EstimatedJsonEncodedSizeOf for Event {
     }
 }
 
+impl GroupedSizeOf for Event {
+    fn add_grouped_sizes(&self, grouped_sizes: &mut GroupedCountByteSize) {
+        match self {
+            Event::Log(log_event) => log_event.add_grouped_sizes(grouped_sizes),
+            Event::Metric(metric_event) => metric_event.add_grouped_sizes(grouped_sizes),
+            Event::Trace(trace_event) => trace_event.add_grouped_sizes(grouped_sizes),
+        }
+    }
+}
+
 impl EventCount for Event {
     fn event_count(&self) -> usize {
         1
@@ -255,6 +271,14 @@ impl Event {
         }
     }
 
+    pub fn origin(&self) -> EventOrigin {
+        match self {
+            Self::Log(log) => log.origin(),
+            Self::Metric(metric) => metric.origin(),
+            Self::Trace(trace) => trace.origin(),
+        }
+    }
+
     /// Destroy the event and return the metadata.
     pub fn into_metadata(self) -> EventMetadata {
         match self {
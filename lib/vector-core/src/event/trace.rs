@@ -2,11 +2,14 @@ use std::{collections::BTreeMap, fmt::Debug};
 
 use serde::{Deserialize, Serialize};
 use vector_buffers::EventCount;
-use vector_common::EventDataEq;
+use vector_common::{
+    request_metadata::{EventOrigin, GroupedCountByteSize},
+    EventDataEq,
+};
 
 use super::{
     BatchNotifier, EstimatedJsonEncodedSizeOf, EventFinalizer, EventFinalizers, EventMetadata,
-    Finalizable, LogEvent, Value,
+    Finalizable, GroupedSizeOf, LogEvent, Value,
 };
 use crate::ByteSizeOf;
 
@@ -71,6 +74,10 @@ impl TraceEvent {
         self.0.get(key.as_ref())
     }
 
+    pub fn origin(&self) -> EventOrigin {
+        self.0.origin()
+    }
+
     pub fn get_mut(&mut self, key: impl AsRef<str>) -> Option<&mut Value> {
         self.0.get_mut(key.as_ref())
     }
@@ -112,6 +119,12 @@ impl EstimatedJsonEncodedSizeOf for TraceEvent {
     }
 }
 
+impl GroupedSizeOf for TraceEvent {
+    fn add_grouped_sizes(&self, grouped_sizes: &mut GroupedCountByteSize) {
+        self.0.add_grouped_sizes(grouped_sizes);
+    }
+}
+
 impl EventCount for TraceEvent {
     fn event_count(&self) -> usize {
         1
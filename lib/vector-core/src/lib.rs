@@ -58,7 +58,7 @@ pub use vector_buffers as buffers;
 pub use vector_common::event_test_util;
 pub use vector_common::{byte_size_of::ByteSizeOf, internal_event};
 
-pub use event::EstimatedJsonEncodedSizeOf;
+pub use event::{EstimatedJsonEncodedSizeOf, GroupedSizeOf};
 
 #[macro_use]
 extern crate tracing;
@@ -1,14 +1,15 @@
-use std::{collections::VecDeque, fmt, future::poll_fn, task::Poll};
+use std::{collections::VecDeque, fmt, future::poll_fn, task::Poll, time::Instant};
 
 use futures::{poll, FutureExt, Stream, StreamExt, TryFutureExt};
 use tokio::{pin, select};
 use tower::Service;
 use tracing::Instrument;
 use vector_common::internal_event::{
-    register, ByteSize, BytesSent, CallError, CountByteSize, EventsSent, InternalEventHandle as _,
-    Output, PollReadyError, Registered, SharedString,
+    register, CallError, InternalEventHandle as _, PollReadyError, Registered, SharedString,
+};
+use vector_common::request_metadata::{
+    MetaDescriptive, RequestCompleted, RequestEventsDropped, RequestMetadata, RequestsSent,
 };
-use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 
 use super::FuturesUnorderedCount;
 use crate::{
@@ -18,13 +19,14 @@ use crate::{
 
 pub trait DriverResponse {
     fn event_status(&self) -> EventStatus;
-    fn events_sent(&self) -> CountByteSize;
 
-    /// Return the number of bytes that were sent in the request that returned this response.
-    // TODO, remove the default implementation once all sinks have
-    // implemented this function.
-    fn bytes_sent(&self) -> Option<usize> {
-        None
+    /// Returns the metadata of the events that were delivered by the request that returned this
+    /// response, whose metadata is `metadata`.
+    ///
+    /// Services that can deliver only some of the events of a request override this to describe
+    /// those that were, rather than all of them.
+    fn delivered_metadata(&self, metadata: RequestMetadata) -> RequestMetadata {
+        metadata
     }
 }
 
@@ -36,7 +38,9 @@ pub trait DriverResponse {
 /// managing waiting for the service to be ready before processing more items, and so on.
 ///
 /// Additionally, `Driver` handles event finalization, which triggers acknowledgements
-/// to the source or disk buffer.
+/// to the source or disk buffer, and emits the events and bytes sent in each delivered request, as
+/// described by its `RequestMetadata`, along with the events of each origin that were sent or
+/// dropped, and the duration and compression ratio of each request.
 ///
 /// This capability is parameterized so any implementation which can define how to interpret the
 /// response for each request, as well as define how many events a request is compromised of, can be
@@ -58,8 +62,8 @@ impl<St, Svc> Driver<St, Svc> {
 
     /// Set the protocol name for this driver.
     ///
-    /// If this is set, the driver will fetch and use the `bytes_sent` value from responses in a
-    /// `BytesSent` event.
+    /// If this is set, the driver will emit the encoded size of delivered requests in a `BytesSent`
+    /// event.
     #[must_use]
     pub fn protocol(mut self, protocol: impl Into<SharedString>) -> Self {
         self.protocol = Some(protocol.into());
@@ -98,8 +102,7 @@ where
         let batched_input = input.ready_chunks(1024);
         pin!(batched_input);
 
-        let bytes_sent = protocol.map(|protocol| register(BytesSent { protocol }));
-        let events_sent = register(EventsSent::from(Output(None)));
+        let requests_sent = register(RequestsSent { protocol });
 
         loop {
             // Core behavior of the loop:
@@ -165,10 +168,10 @@ where
                             request_id,
                         );
                         let finalizers = req.take_finalizers();
-                        let bytes_sent = bytes_sent.clone();
-                        let events_sent = events_sent.clone();
+                        let requests_sent = requests_sent.clone();
 
                         let metadata = req.get_metadata();
+                        let started = Instant::now();
 
                         let fut = svc.call(req)
                             .err_into()
@@ -176,9 +179,9 @@ where
                                 result,
                                 request_id,
                                 finalizers,
-                                &metadata,
-                                &bytes_sent,
-                                &events_sent,
+                                metadata,
+                                started,
+                                &requests_sent,
                             ))
                             .instrument(info_span!("request", request_id).or_current());
 
@@ -202,28 +205,31 @@ where
         result: Result<Svc::Response, Svc::Error>,
         request_id: usize,
         finalizers: EventFinalizers,
-        metadata: &RequestMetadata,
-        bytes_sent: &Option<Registered<BytesSent>>,
-        events_sent: &Registered<EventsSent>,
+        metadata: RequestMetadata,
+        started: Instant,
+        requests_sent: &Registered<RequestsSent>,
     ) {
+        emit(RequestCompleted {
+            metadata: &metadata,
+            duration: started.elapsed(),
+        });
         match result {
             Err(error) => {
-                Self::emit_call_error(Some(error), request_id, metadata.event_count());
+                Self::emit_call_error(Some(error), request_id, &metadata);
                 finalizers.update_status(EventStatus::Rejected);
             }
             Ok(response) => {
-                trace!(message = "Service call succeeded.", request_id);
+                trace!(
+                    message = "Service call succeeded.",
+                    request_id,
+                    compression_ratio = ?metadata.compression_ratio(),
+                );
                 finalizers.update_status(response.event_status());
                 if response.event_status() == EventStatus::Delivered {
-                    if let Some(bytes_sent) = bytes_sent {
-                        if let Some(byte_size) = response.bytes_sent() {
-                            bytes_sent.emit(ByteSize(byte_size));
-                        }
-                    }
-                    events_sent.emit(response.events_sent());
+                    requests_sent.emit(response.delivered_metadata(metadata));
                 // This condition occurs specifically when the `HttpBatchService::call()` is called *within* the `Service::call()`
                 } else if response.event_status() == EventStatus::Rejected {
-                    Self::emit_call_error(None, request_id, metadata.event_count());
+                    Self::emit_call_error(None, request_id, &metadata);
                     finalizers.update_status(EventStatus::Rejected);
                 }
             }
@@ -231,14 +237,16 @@ where
         drop(finalizers); // suppress "argument not consumed" warning
     }
 
-    /// Emit the `Error` and `EventsDropped` internal events.
+    /// Emit the `Error` and `EventsDropped` internal events, along with the events dropped of each
+    /// origin.
     /// This scenario occurs after retries have been attempted.
-    fn emit_call_error(error: Option<Svc::Error>, request_id: usize, count: usize) {
+    fn emit_call_error(error: Option<Svc::Error>, request_id: usize, metadata: &RequestMetadata) {
         emit(CallError {
             error,
             request_id,
-            count,
+            count: metadata.event_count(),
         });
+        emit(RequestEventsDropped { metadata });
     }
 }
 
@@ -263,9 +271,8 @@ mod tests {
     use tower::Service;
     use vector_common::{
         finalization::{BatchNotifier, EventFinalizer, EventFinalizers, EventStatus, Finalizable},
-        request_metadata::RequestMetadata,
+        request_metadata::{MetaDescriptive, RequestMetadata},
     };
-    use vector_common::{internal_event::CountByteSize, request_metadata::MetaDescriptive};
 
     use super::{Driver, DriverResponse};
 
@@ -298,7 +305,7 @@ mod tests {
 
     impl MetaDescriptive for DelayRequest {
         fn get_metadata(&self) -> RequestMetadata {
-            self.2
+            self.2.clone()
         }
     }
 
@@ -308,10 +315,6 @@ mod tests {
         fn event_status(&self) -> EventStatus {
             EventStatus::Delivered
         }
-
-        fn events_sent(&self) -> CountByteSize {
-            CountByteSize(1, 1)
-        }
     }
 
     // Generic service that takes a usize and applies an arbitrary delay to returning it.
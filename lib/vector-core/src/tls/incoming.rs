@@ -1,7 +1,7 @@
 use std::{
     collections::HashMap,
     future::Future,
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
@@ -343,6 +343,8 @@ pub struct CertificateMetadata {
     pub organization_name: Option<String>,
     pub organizational_unit_name: Option<String>,
     pub common_name: Option<String>,
+    /// The DNS names, email addresses, URIs and IP addresses of the certificate.
+    pub subject_alt_names: Vec<String>,
 }
 
 impl CertificateMetadata {
@@ -380,6 +382,21 @@ impl From<X509> for CertificateMetadata {
             };
             subject_metadata.insert(entry.object().to_string(), data_string);
         }
+        let subject_alt_names = cert
+            .subject_alt_names()
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| {
+                        name.dnsname()
+                            .or_else(|| name.email())
+                            .or_else(|| name.uri())
+                            .map(ToOwned::to_owned)
+                            .or_else(|| name.ipaddress().and_then(ip_address_to_string))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
         Self {
             country_name: subject_metadata.get("countryName").cloned(),
             state_or_province_name: subject_metadata.get("stateOrProvinceName").cloned(),
@@ -387,13 +404,28 @@ impl From<X509> for CertificateMetadata {
             organization_name: subject_metadata.get("organizationName").cloned(),
             organizational_unit_name: subject_metadata.get("organizationalUnitName").cloned(),
             common_name: subject_metadata.get("commonName").cloned(),
+            subject_alt_names,
         }
     }
 }
 
+fn ip_address_to_string(octets: &[u8]) -> Option<String> {
+    let address = match octets.len() {
+        4 => IpAddr::from(<[u8; 4]>::try_from(octets).ok()?),
+        16 => IpAddr::from(<[u8; 16]>::try_from(octets).ok()?),
+        _ => return None,
+    };
+    Some(address.to_string())
+}
+
 #[derive(Clone)]
 pub struct MaybeTlsConnectInfo {
     pub remote_addr: SocketAddr,
+    /// The certificate of the peer itself.
+    ///
+    /// On the server side, `peer_certs` doesn't include the certificate of the client, only the
+    /// chain of certificates it sent along with it.
+    pub peer_certificate: Option<Certificate>,
     pub peer_certs: Option<Vec<Certificate>>,
 }
 
@@ -403,6 +435,11 @@ impl Connected for MaybeTlsIncomingStream<TcpStream> {
     fn connect_info(&self) -> Self::ConnectInfo {
         MaybeTlsConnectInfo {
             remote_addr: self.peer_addr(),
+            peer_certificate: self
+                .ssl_stream()
+                .and_then(|s| s.ssl().peer_certificate())
+                .and_then(|c| c.to_pem().ok())
+                .map(Certificate::from_pem),
             peer_certs: self
                 .ssl_stream()
                 .and_then(|s| s.ssl().peer_cert_chain())
@@ -429,6 +466,7 @@ mod test {
             organization_name: Some("organization".to_owned()),
             organizational_unit_name: Some("org_unit".to_owned()),
             state_or_province_name: Some("state".to_owned()),
+            subject_alt_names: vec!["common.example.com".to_owned()],
         };
 
         let expected = format!(
@@ -452,6 +490,7 @@ mod test {
             organization_name: Some("organization".to_owned()),
             organizational_unit_name: Some("org_unit".to_owned()),
             state_or_province_name: None,
+            subject_alt_names: Vec::new(),
         };
 
         let expected = format!(
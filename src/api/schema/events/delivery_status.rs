@@ -0,0 +1,39 @@
+use async_graphql::Enum;
+
+use crate::event::{EventFinalizers, EventStatus};
+
+#[derive(Enum, Debug, Copy, Clone, PartialEq, Eq)]
+/// Delivery status of the event, as reported to the source it was received by
+pub enum EventDeliveryStatus {
+    /// Copies of the event are still being processed
+    Pending,
+    /// All copies of the event were dropped without being delivered
+    Dropped,
+    /// All copies of the event were delivered
+    Delivered,
+    /// At least one copy of the event failed to be delivered, with a retriable error
+    Errored,
+    /// At least one copy of the event was rejected
+    Rejected,
+    /// The status of the event was reported before it was delivered, once it was written to a disk
+    /// buffer
+    Recorded,
+}
+
+impl EventDeliveryStatus {
+    /// Returns the delivery status of an event with `finalizers`, or `None` when its delivery isn't
+    /// tracked, as acknowledgements aren't enabled for it.
+    pub fn of(finalizers: &EventFinalizers) -> Option<Self> {
+        if finalizers.is_empty() {
+            return None;
+        }
+        Some(match finalizers.final_status() {
+            None => Self::Pending,
+            Some(EventStatus::Dropped) => Self::Dropped,
+            Some(EventStatus::Delivered) => Self::Delivered,
+            Some(EventStatus::Errored) => Self::Errored,
+            Some(EventStatus::Rejected) => Self::Rejected,
+            Some(EventStatus::Recorded) => Self::Recorded,
+        })
+    }
+}
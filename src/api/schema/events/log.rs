@@ -4,7 +4,7 @@ use async_graphql::Object;
 use chrono::{DateTime, Utc};
 use vector_common::encode_logfmt;
 
-use super::EventEncodingType;
+use super::{delivery_status::EventDeliveryStatus, EventEncodingType};
 use crate::{
     event::{self, Value},
     topology::TapOutput,
@@ -28,6 +28,10 @@ impl Log {
     pub fn get_timestamp(&self) -> Option<&DateTime<Utc>> {
         self.event.get("timestamp")?.as_timestamp()
     }
+
+    pub fn get_delivery_status(&self) -> Option<EventDeliveryStatus> {
+        EventDeliveryStatus::of(self.event.metadata().finalizers())
+    }
 }
 
 #[Object]
@@ -58,6 +62,11 @@ impl Log {
         self.get_timestamp()
     }
 
+    /// Delivery status of the log event, when acknowledgements are enabled for it
+    async fn delivery_status(&self) -> Option<EventDeliveryStatus> {
+        self.get_delivery_status()
+    }
+
     /// Log event as an encoded string format
     async fn string(&self, encoding: EventEncodingType) -> String {
         match encoding {
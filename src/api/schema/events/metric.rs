@@ -5,7 +5,7 @@ use chrono::{DateTime, Utc};
 use serde_json::Value;
 use vector_common::encode_logfmt;
 
-use super::EventEncodingType;
+use super::{delivery_status::EventDeliveryStatus, EventEncodingType};
 use crate::{
     event::{self},
     topology::TapOutput,
@@ -21,6 +21,10 @@ impl Metric {
     pub const fn new(output: TapOutput, event: event::Metric) -> Self {
         Self { output, event }
     }
+
+    pub fn get_delivery_status(&self) -> Option<EventDeliveryStatus> {
+        EventDeliveryStatus::of(self.event.metadata().finalizers())
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Enum)]
@@ -118,6 +122,11 @@ impl Metric {
         })
     }
 
+    /// Delivery status of the metric event, when acknowledgements are enabled for it
+    async fn delivery_status(&self) -> Option<EventDeliveryStatus> {
+        self.get_delivery_status()
+    }
+
     /// Metric event as an encoded string format
     async fn string(&self, encoding: EventEncodingType) -> String {
         match encoding {
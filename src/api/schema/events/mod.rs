@@ -1,3 +1,4 @@
+pub mod delivery_status;
 mod encoding;
 pub mod log;
 pub mod metric;
@@ -5,9 +6,10 @@ pub mod notification;
 pub mod output;
 pub mod trace;
 
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 
 use async_graphql::{Context, Subscription};
+use delivery_status::EventDeliveryStatus;
 use encoding::EventEncodingType;
 use futures::{stream, Stream, StreamExt};
 use output::OutputEventsPayload;
@@ -56,6 +58,7 @@ impl EventsSubscription {
         inputs_patterns: Option<Vec<String>>,
         #[graphql(default = 500)] interval: u32,
         #[graphql(default = 100, validator(minimum = 1, maximum = 10_000))] limit: u32,
+        #[graphql(default = false)] delivery_status: bool,
     ) -> impl Stream<Item = Vec<OutputEventsPayload>> + 'a {
         let watch_rx = ctx.data_unchecked::<WatchRx>().clone();
 
@@ -64,18 +67,28 @@ impl EventsSubscription {
             for_inputs: inputs_patterns.unwrap_or_default().into_iter().collect(),
         };
         // Client input is confined to `u32` to provide sensible bounds.
-        create_events_stream(watch_rx, patterns, interval as u64, limit as usize)
+        create_events_stream(
+            watch_rx,
+            patterns,
+            interval as u64,
+            limit as usize,
+            delivery_status,
+        )
     }
 }
 
 /// Creates an events stream based on component ids, and a provided interval. Will emit
 /// control messages that bubble up the application if the sink goes away. The stream contains
 /// all matching events; filtering should be done at the caller level.
+///
+/// With `delivery_status`, sampled events are held until their delivery status is final, so that
+/// it's part of the results they're sent back to the client with.
 pub(crate) fn create_events_stream(
     watch_rx: WatchRx,
     patterns: TapPatterns,
     interval: u64,
     limit: usize,
+    delivery_status: bool,
 ) -> impl Stream<Item = Vec<OutputEventsPayload>> {
     // Channel for receiving individual tap payloads. Since we can process at most `limit` per
     // interval, this is capped to the same value.
@@ -106,6 +119,9 @@ pub(crate) fn create_events_stream(
         // they will be sampled and added to results.
         let mut results = Vec::<SortableOutputEventsPayload>::with_capacity(limit);
 
+        // Sampled events whose delivery is still pending, in the order they were sampled in.
+        let mut pending = VecDeque::<OutputEventsPayload>::new();
+
         // Random number generator to allow for sampling. Speed trumps cryptographic security here.
         // The RNG must be Send + Sync to use with the `select!` loop below, hence `SmallRng`.
         let mut rng = SmallRng::from_entropy();
@@ -150,7 +166,7 @@ pub(crate) fn create_events_stream(
                     }
                 }
                 _ = interval.tick() => {
-                    // If there are any existing results after the interval tick, emit.
+                    let mut ready = Vec::new();
                     if !results.is_empty() {
                         // Reset the batch count, to adjust sampling probability for the next round.
                         batch = 0;
@@ -158,13 +174,29 @@ pub(crate) fn create_events_stream(
                         // Since events will appear out of order per the random sampling
                         // strategy, drain the existing results and sort by timestamp.
                         results.sort_by_key(|r| r.batch);
-                        let results = results.drain(..)
-                            .map(|r| r.payload)
-                            .collect();
+                        ready.extend(results.drain(..).map(|r| r.payload));
+                    }
 
+                    if delivery_status {
+                        // Events are held until their delivery status is final, only emitting the
+                        // oldest ones regardless when more than `limit` are held.
+                        pending.extend(ready.drain(..));
+                        let overflow = pending.len().saturating_sub(limit);
+                        ready.extend(pending.drain(..overflow));
+                        let (still_pending, finalized): (VecDeque<_>, VecDeque<_>) = pending
+                            .drain(..)
+                            .partition(|payload| {
+                                payload.delivery_status() == Some(EventDeliveryStatus::Pending)
+                            });
+                        pending = still_pending;
+                        ready.extend(finalized);
+                    }
+
+                    // If there are any existing results after the interval tick, emit.
+                    if !ready.is_empty() {
                         // If we get an error here, it likely means that the subscription has
                         // gone has away. This is a valid/common situation.
-                        if let Err(err) = event_tx.send(results).await {
+                        if let Err(err) = event_tx.send(ready).await {
                             debug!(message = "Couldn't send events.", error = ?err);
                             break;
                         }
@@ -1,6 +1,9 @@
 use async_graphql::Union;
 
-use super::{log::Log, metric::Metric, notification::EventNotification, trace::Trace};
+use super::{
+    delivery_status::EventDeliveryStatus, log::Log, metric::Metric,
+    notification::EventNotification, trace::Trace,
+};
 use crate::api::tap::TapPayload;
 
 #[derive(Union, Debug, Clone)]
@@ -20,6 +23,19 @@ pub enum OutputEventsPayload {
     Trace(Trace),
 }
 
+impl OutputEventsPayload {
+    /// Returns the delivery status of the event of this payload, for events whose delivery is
+    /// tracked.
+    pub fn delivery_status(&self) -> Option<EventDeliveryStatus> {
+        match self {
+            Self::Log(log) => log.get_delivery_status(),
+            Self::Metric(metric) => metric.get_delivery_status(),
+            Self::Trace(trace) => trace.get_delivery_status(),
+            Self::Notification(_) => None,
+        }
+    }
+}
+
 /// Convert an `api::TapPayload` to the equivalent GraphQL type.
 impl From<TapPayload> for Vec<OutputEventsPayload> {
     fn from(t: TapPayload) -> Self {
@@ -1,7 +1,7 @@
 use async_graphql::Object;
 use vector_common::encode_logfmt;
 
-use super::EventEncodingType;
+use super::{delivery_status::EventDeliveryStatus, EventEncodingType};
 use crate::{
     event::{self, Value},
     topology::TapOutput,
@@ -17,6 +17,10 @@ impl Trace {
     pub const fn new(output: TapOutput, event: event::TraceEvent) -> Self {
         Self { output, event }
     }
+
+    pub fn get_delivery_status(&self) -> Option<EventDeliveryStatus> {
+        EventDeliveryStatus::of(self.event.metadata().finalizers())
+    }
 }
 
 #[Object]
@@ -37,6 +41,11 @@ impl Trace {
         self.output.component_kind
     }
 
+    /// Delivery status of the trace event, when acknowledgements are enabled for it
+    async fn delivery_status(&self) -> Option<EventDeliveryStatus> {
+        self.get_delivery_status()
+    }
+
     /// Trace event as an encoded string format
     async fn string(&self, encoding: EventEncodingType) -> String {
         match encoding {
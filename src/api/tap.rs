@@ -423,9 +423,11 @@ mod tests {
 
     use super::*;
     use crate::api::schema::events::output::OutputEventsPayload;
-    use crate::api::schema::events::{create_events_stream, log, metric};
+    use crate::api::schema::events::{
+        create_events_stream, delivery_status::EventDeliveryStatus, log, metric,
+    };
     use crate::config::{Config, OutputId};
-    use crate::event::{LogEvent, Metric, MetricKind, MetricValue};
+    use crate::event::{BatchNotifier, EventStatus, LogEvent, Metric, MetricKind, MetricValue};
     use crate::sinks::blackhole::BlackholeConfig;
     use crate::sources::demo_logs::{DemoLogsConfig, OutputFormat};
     use crate::test_util::{start_topology, trace_init};
@@ -565,6 +567,31 @@ mod tests {
         }
     }
 
+    #[test]
+    /// Tapped events should only have a final delivery status once all other copies of them are
+    /// finalized.
+    fn delivery_status() {
+        let output = TapOutput {
+            output_id: OutputId::from(&ComponentKey::from("test")),
+            component_kind: "source",
+            component_type: "demo".to_string(),
+        };
+        let untracked = OutputEventsPayload::Log(log::Log::new(output.clone(), LogEvent::from("")));
+        assert_eq!(untracked.delivery_status(), None);
+
+        let (batch, _receiver) = BatchNotifier::new_with_receiver();
+        let event = LogEvent::from("").with_batch_notifier(&batch);
+        let tapped = OutputEventsPayload::Log(log::Log::new(output, event.clone()));
+        assert_eq!(tapped.delivery_status(), Some(EventDeliveryStatus::Pending));
+
+        event.metadata().update_status(EventStatus::Delivered);
+        drop(event);
+        assert_eq!(
+            tapped.delivery_status(),
+            Some(EventDeliveryStatus::Delivered)
+        );
+    }
+
     #[tokio::test]
     async fn integration_test_source_log() {
         trace_init();
@@ -585,6 +612,7 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
+                simulate: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -596,6 +624,7 @@ mod tests {
             TapPatterns::new(HashSet::from(["in".to_string()]), HashSet::new()),
             500,
             100,
+            false,
         );
 
         let source_tap_events: Vec<_> = source_tap_stream.take(2).collect().await;
@@ -633,8 +662,10 @@ mod tests {
                     name: None,
                     namespace: None,
                     tags: None,
+                    cardinality_limit: None,
                     metric: MetricTypeConfig::Gauge,
                 }],
+                all_metrics: false,
             },
         );
         config.add_sink(
@@ -643,6 +674,7 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
+                simulate: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -654,6 +686,7 @@ mod tests {
             TapPatterns::new(HashSet::from(["to_metric".to_string()]), HashSet::new()),
             500,
             100,
+            false,
         );
 
         let source_tap_events: Vec<_> = source_tap_stream.take(2).collect().await;
@@ -693,6 +726,7 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
+                simulate: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -704,6 +738,7 @@ mod tests {
             TapPatterns::new(HashSet::from(["transform".to_string()]), HashSet::new()),
             500,
             100,
+            false,
         );
 
         let transform_tap_events: Vec<_> = transform_tap_stream.take(2).collect().await;
@@ -746,6 +781,7 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
+                simulate: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -760,6 +796,7 @@ mod tests {
             ),
             500,
             100,
+            false,
         );
 
         let tap_events: Vec<_> = tap_stream.take(4).collect().await;
@@ -820,6 +857,7 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
+                simulate: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -831,6 +869,7 @@ mod tests {
             TapPatterns::new(HashSet::new(), HashSet::from(["out".to_string()])),
             500,
             100,
+            false,
         );
 
         let tap_events: Vec<_> = tap_stream.take(2).collect().await;
@@ -880,6 +919,7 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
+                simulate: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -894,6 +934,7 @@ mod tests {
             ),
             500,
             100,
+            false,
         );
 
         let transform_tap_events: Vec<_> =
@@ -956,6 +997,7 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
+                simulate: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -967,6 +1009,7 @@ mod tests {
             TapPatterns::new(HashSet::from(["transform*".to_string()]), HashSet::new()),
             500,
             100,
+            false,
         );
 
         let transform_tap_notifications = transform_tap_all_outputs_stream.next().await.unwrap();
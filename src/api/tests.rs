@@ -142,6 +142,7 @@ async fn integration_test_source_log() {
         BlackholeConfig {
             print_interval_secs: 1,
             rate: None,
+            simulate: None,
         },
     );
 
@@ -192,6 +193,7 @@ async fn integration_test_source_metric() {
         BlackholeConfig {
             print_interval_secs: 1,
             rate: None,
+            simulate: None,
         },
     );
 
@@ -235,6 +237,7 @@ async fn integration_test_transform() {
         BlackholeConfig {
             print_interval_secs: 1,
             rate: None,
+            simulate: None,
         },
     );
 
@@ -283,6 +286,7 @@ async fn integration_test_tap_non_default_output() {
         BlackholeConfig {
             print_interval_secs: 1,
             rate: None,
+            simulate: None,
         },
     );
 
@@ -355,6 +359,7 @@ async fn integration_test_tap_multiple_outputs() {
         BlackholeConfig {
             print_interval_secs: 1,
             rate: None,
+            simulate: None,
         },
     );
 
@@ -4,7 +4,7 @@ use indexmap::{IndexMap, IndexSet};
 
 use super::{
     builder::ConfigBuilder, graph::Graph, id::Inputs, schema, validation, ComponentKey, Config,
-    OutputId, SourceConfig, TransformConfig,
+    OutputId, SinkConfig, SourceConfig, TransformConfig, DEAD_LETTER,
 };
 
 /// to handle the expansions when building the graph we need to be able to get the list of inputs
@@ -42,6 +42,10 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
 
     expand_globs(&mut builder);
 
+    if let Err(dead_letter_errors) = route_dead_letters(&mut builder) {
+        errors.extend(dead_letter_errors);
+    }
+
     if let Err(type_errors) = validation::check_shape(&builder) {
         errors.extend(type_errors);
     }
@@ -196,6 +200,12 @@ pub(crate) fn expand_globs(config: &mut ConfigBuilder) {
                     port: output.port,
                 })
         }))
+        .chain(config.sinks.iter().flat_map(|(key, s)| {
+            s.outputs().into_iter().map(|output| OutputId {
+                component: key.clone(),
+                port: output.port,
+            })
+        }))
         .map(|output_id| output_id.to_string())
         .collect::<IndexSet<String>>();
 
@@ -208,6 +218,57 @@ pub(crate) fn expand_globs(config: &mut ConfigBuilder) {
     }
 }
 
+/// Adds the `dead_letter` output of each sink with a dead-letter component to the inputs of that
+/// component.
+fn route_dead_letters(config: &mut ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    let routes = config
+        .sinks
+        .iter()
+        .filter_map(|(key, sink)| {
+            sink.dead_letter
+                .clone()
+                .map(|dead_letter| (key.clone(), dead_letter))
+        })
+        .collect::<Vec<_>>();
+
+    for (key, dead_letter) in routes {
+        if config.sinks[&key]
+            .inner
+            .outputs()
+            .iter()
+            .any(|output| output.port.as_deref() == Some(DEAD_LETTER))
+        {
+            errors.push(format!(
+                "Sink {:?} has a dead-letter component, but already has an output named {:?}.",
+                key.id(),
+                DEAD_LETTER
+            ));
+            continue;
+        }
+
+        let input = OutputId::from((&key, DEAD_LETTER.to_owned())).to_string();
+        if let Some(transform) = config.transforms.get_mut(&dead_letter) {
+            transform.inputs.extend(Some(input));
+        } else if let Some(sink) = config.sinks.get_mut(&dead_letter) {
+            sink.inputs.extend(Some(input));
+        } else {
+            errors.push(format!(
+                "Sink {:?} has dead-letter component {:?}, which doesn't match any transforms or sinks.",
+                key.id(),
+                dead_letter.id()
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 enum InputMatcher {
     Pattern(glob::Pattern),
     String(String),
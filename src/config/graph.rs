@@ -17,6 +17,7 @@ pub enum Node {
     },
     Sink {
         ty: DataType,
+        outputs: Vec<Output>,
     },
 }
 
@@ -92,6 +93,7 @@ impl Graph {
                 id.clone(),
                 Node::Sink {
                     ty: config.inner.input().data_type(),
+                    outputs: config.outputs(),
                 },
             );
         }
@@ -164,7 +166,7 @@ impl Graph {
         match self.nodes[key] {
             Node::Source { .. } => panic!("no inputs on sources"),
             Node::Transform { in_ty, .. } => in_ty,
-            Node::Sink { ty } => ty,
+            Node::Sink { ty, .. } => ty,
         }
     }
 
@@ -172,16 +174,16 @@ impl Graph {
     ///
     /// # Panics
     ///
-    /// Will panic if the given id is not present in the graph or identifies a sink, which can't
-    /// have inputs.
+    /// Will panic if the given id is not present in the graph.
     fn get_output_type(&self, id: &OutputId) -> DataType {
         match &self.nodes[&id.component] {
-            Node::Source { outputs } | Node::Transform { outputs, .. } => outputs
+            Node::Source { outputs }
+            | Node::Transform { outputs, .. }
+            | Node::Sink { outputs, .. } => outputs
                 .iter()
                 .find(|output| output.port == id.port)
                 .map(|output| output.ty)
                 .expect("output didn't exist"),
-            Node::Sink { .. } => panic!("no outputs on sinks"),
         }
     }
 
@@ -266,14 +268,15 @@ impl Graph {
         self.nodes
             .iter()
             .flat_map(|(key, node)| match node {
-                Node::Sink { .. } => vec![],
-                Node::Source { outputs } | Node::Transform { outputs, .. } => outputs
+                Node::Source { outputs }
+                | Node::Transform { outputs, .. }
+                | Node::Sink { outputs, .. } => outputs
                     .iter()
                     .map(|output| OutputId {
                         component: key.clone(),
                         port: output.port.clone(),
                     })
-                    .collect(),
+                    .collect::<Vec<_>>(),
             })
             .collect()
     }
@@ -351,7 +354,7 @@ impl Graph {
             .into_iter()
             .filter(|path| {
                 if let Some(key) = path.last() {
-                    matches!(self.nodes.get(key), Some(Node::Sink { ty: _ }))
+                    matches!(self.nodes.get(key), Some(Node::Sink { .. }))
                 } else {
                     false
                 }
@@ -413,7 +416,13 @@ mod test {
         fn add_sink(&mut self, id: &str, ty: DataType, inputs: Vec<&str>) {
             let id = ComponentKey::from(id);
             let inputs = clean_inputs(inputs);
-            self.nodes.insert(id.clone(), Node::Sink { ty });
+            self.nodes.insert(
+                id.clone(),
+                Node::Sink {
+                    ty,
+                    outputs: Vec::new(),
+                },
+            );
             for from in inputs {
                 self.edges.push(Edge {
                     from,
@@ -422,6 +431,16 @@ mod test {
             }
         }
 
+        fn add_sink_output(&mut self, id: &str, name: &str, ty: DataType) {
+            let id = id.into();
+            match self.nodes.get_mut(&id) {
+                Some(Node::Sink { outputs, .. }) => {
+                    outputs.push(Output::default(ty).with_port(name))
+                }
+                _ => panic!("invalid sink"),
+            }
+        }
+
         fn test_add_input(&mut self, node: &str, input: &str) -> Result<(), String> {
             let available_inputs = self.input_map().unwrap();
             let expansions = IndexMap::new();
@@ -617,6 +636,35 @@ mod test {
         );
     }
 
+    #[test]
+    fn allows_sink_outputs() {
+        let mut graph = Graph::default();
+        graph.add_source("log_source", DataType::Log);
+        graph.add_sink("log_sink", DataType::Log, vec!["log_source"]);
+        graph.add_sink_output("log_sink", "rejected", DataType::Log);
+        graph.add_sink("rejected_log_sink", DataType::Log, vec![]);
+        graph.add_sink("metric_sink", DataType::Metric, vec![]);
+
+        assert_eq!(
+            Ok(()),
+            graph.test_add_input("rejected_log_sink", "log_sink.rejected")
+        );
+        assert_eq!(Ok(()), graph.typecheck());
+        assert_eq!(Ok(()), graph.check_for_cycles());
+
+        assert_eq!(
+            Ok(()),
+            graph.test_add_input("metric_sink", "log_sink.rejected")
+        );
+        assert_eq!(
+            Err(vec![
+                "Data type mismatch between log_sink.rejected (Log) and metric_sink (Metric)"
+                    .into()
+            ]),
+            graph.typecheck()
+        );
+    }
+
     #[test]
     fn disallows_ambiguous_inputs() {
         let mut graph = Graph::default();
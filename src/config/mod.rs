@@ -50,7 +50,7 @@ pub use loading::{
 };
 pub use provider::ProviderConfig;
 pub use secret::SecretBackend;
-pub use sink::{SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter};
+pub use sink::{SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter, DEAD_LETTER};
 pub use source::{SourceConfig, SourceContext, SourceOuter};
 pub use transform::{
     InnerTopology, InnerTopologyTransform, TransformConfig, TransformContext, TransformOuter,
@@ -202,6 +202,15 @@ impl Config {
                     .map(|input| (sink.clone(), input.clone()))
                     .collect();
                 self.propagate_acks_rec(inputs);
+            } else if let Some(upstream) = self.sinks.get(component) {
+                // Events sent to the outputs of a sink are acknowledged once they're delivered
+                // downstream, so its own inputs must carry their finalizers too.
+                let inputs = upstream
+                    .inputs
+                    .iter()
+                    .map(|input| (sink.clone(), input.clone()))
+                    .collect();
+                self.propagate_acks_rec(inputs);
             }
         }
     }
@@ -573,7 +582,9 @@ mod tests {
     use crate::{config, topology};
     use indoc::indoc;
 
-    use super::{builder::ConfigBuilder, format, load_from_str, ComponentKey, ConfigDiff, Format};
+    use super::{
+        builder::ConfigBuilder, format, load_from_str, ComponentKey, ConfigDiff, Format, OutputId,
+    };
 
     async fn load(config: &str, format: config::Format) -> Result<Vec<String>, Vec<String>> {
         match config::load_from_str(config, format) {
@@ -631,6 +642,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn dead_letter_routing() {
+        let config = load_from_str(
+            indoc! {r#"
+                [sources.in]
+                type = "test_basic"
+
+                [sinks.out]
+                type = "test_basic"
+                inputs = ["in"]
+                dead_letter = "dlq"
+
+                [sinks.dlq]
+                type = "test_basic"
+                inputs = []
+            "#},
+            Format::Toml,
+        )
+        .unwrap();
+
+        let dlq = config.sink(&ComponentKey::from("dlq")).unwrap();
+        assert_eq!(
+            dlq.inputs,
+            vec![OutputId::from((
+                &ComponentKey::from("out"),
+                "dead_letter".to_owned()
+            ))]
+        );
+    }
+
+    #[tokio::test]
+    async fn bad_dead_letter() {
+        let err = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            dead_letter = "in"
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            vec![
+                "Sink \"out\" has dead-letter component \"in\", which doesn't match any transforms or sinks.",
+            ],
+            err,
+        );
+    }
+
     #[tokio::test]
     async fn duplicate_name() {
         let err = load(
@@ -4,12 +4,18 @@ use serde::Serialize;
 use vector_buffers::{BufferConfig, BufferType};
 use vector_config::{configurable_component, Configurable, NamedComponent};
 use vector_core::{
-    config::{AcknowledgementsConfig, GlobalOptions, Input},
+    config::{AcknowledgementsConfig, GlobalOptions, Input, Output},
     sink::VectorSink,
 };
 
 use super::{id::Inputs, schema, ComponentKey, ProxyConfig, Resource};
-use crate::sinks::{util::UriSerde, Healthcheck, Sinks};
+use crate::{
+    sinks::{util::UriSerde, Healthcheck, Sinks},
+    SourceSender,
+};
+
+/// The name of the output of sinks with a dead-letter component.
+pub const DEAD_LETTER: &str = "dead_letter";
 
 /// Fully resolved sink component.
 #[configurable_component]
@@ -49,6 +55,15 @@ where
     )]
     proxy: ProxyConfig,
 
+    /// The component the events this sink fails to deliver are sent to.
+    ///
+    /// Events the sink rejects, or gives up retrying to deliver, are sent to this transform or
+    /// sink through the `dead_letter` output of the sink, annotated with the details of the
+    /// failure. Their sources are then acknowledged once they are delivered by this component.
+    #[configurable(metadata(docs::examples = "my_dlq_sink"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dead_letter: Option<ComponentKey>,
+
     #[serde(flatten)]
     #[configurable(metadata(docs::hidden))]
     pub inner: Sinks,
@@ -70,6 +85,7 @@ where
             healthcheck_uri: None,
             inner: inner.into(),
             proxy: Default::default(),
+            dead_letter: None,
         }
     }
 
@@ -108,6 +124,16 @@ where
         &self.proxy
     }
 
+    /// Gets the list of outputs of the sink, including its `dead_letter` output if it has a
+    /// dead-letter component.
+    pub fn outputs(&self) -> Vec<Output> {
+        let mut outputs = self.inner.outputs();
+        if self.dead_letter.is_some() {
+            outputs.push(Output::default(self.inner.input().data_type()).with_port(DEAD_LETTER));
+        }
+        outputs
+    }
+
     pub(super) fn map_inputs<U>(self, f: impl Fn(&T) -> U) -> SinkOuter<U>
     where
         U: Configurable + Serialize,
@@ -128,6 +154,7 @@ where
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            dead_letter: self.dead_letter,
         }
     }
 }
@@ -190,6 +217,14 @@ pub trait SinkConfig: NamedComponent + core::fmt::Debug + Send + Sync {
     /// Gets the input configuration for this sink.
     fn input(&self) -> Input;
 
+    /// Gets the list of outputs exposed by this sink, if any.
+    ///
+    /// Sinks don't output the events they deliver, but some can send the events they fail to
+    /// deliver to named outputs, for other components to handle them.
+    fn outputs(&self) -> Vec<Output> {
+        Vec::new()
+    }
+
     /// Gets the list of resources, if any, used by this sink.
     ///
     /// Resources represent dependencies -- network ports, file descriptors, and so on -- that
@@ -211,6 +246,8 @@ pub struct SinkContext {
     pub globals: GlobalOptions,
     pub proxy: ProxyConfig,
     pub schema: schema::Options,
+    /// The sender of the outputs of the sink, if it has any.
+    pub out: Option<SourceSender>,
 }
 
 impl SinkContext {
@@ -221,6 +258,7 @@ impl SinkContext {
             globals: GlobalOptions::default(),
             proxy: ProxyConfig::default(),
             schema: schema::Options::default(),
+            out: None,
         }
     }
 
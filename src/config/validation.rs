@@ -7,7 +7,8 @@ use std::{collections::HashMap, path::PathBuf};
 use vector_core::internal_event::DEFAULT_OUTPUT;
 
 use super::{
-    builder::ConfigBuilder, ComponentKey, Config, OutputId, Resource, SourceConfig, TransformConfig,
+    builder::ConfigBuilder, ComponentKey, Config, OutputId, Resource, SinkConfig, SourceConfig,
+    TransformConfig,
 };
 
 /// Check that provide + topology config aren't present in the same builder, which is an error.
@@ -185,6 +186,19 @@ pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         }
     }
 
+    for (key, sink) in config.sinks.iter() {
+        if sink
+            .outputs()
+            .iter()
+            .map(|output| output.port.as_deref().unwrap_or(""))
+            .any(|name| name == DEFAULT_OUTPUT)
+        {
+            errors.push(format!(
+                "Sink {key} cannot have a named output with reserved name: `{DEFAULT_OUTPUT}`"
+            ));
+        }
+    }
+
     if errors.is_empty() {
         Ok(())
     } else {
@@ -359,7 +373,20 @@ pub fn warnings(config: &Config) -> Vec<String> {
             .collect::<Vec<_>>()
     });
 
-    for (input_type, id) in transform_ids.chain(source_ids) {
+    let sink_ids = config.sinks.iter().flat_map(|(key, sink)| {
+        sink.outputs()
+            .iter()
+            .map(|output| {
+                if let Some(port) = &output.port {
+                    ("sink", OutputId::from((key, port.clone())))
+                } else {
+                    ("sink", OutputId::from(key))
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+
+    for (input_type, id) in transform_ids.chain(source_ids).chain(sink_ids) {
         if !config
             .transforms
             .iter()
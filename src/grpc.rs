@@ -0,0 +1,121 @@
+//! Calling and serving gRPC methods described by descriptor sets, only known at runtime.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use bytes::Buf;
+use prost::Message;
+use prost_reflect::{
+    DescriptorError, DescriptorPool, DynamicMessage, MessageDescriptor, MethodDescriptor,
+};
+use snafu::{OptionExt, ResultExt, Snafu};
+use tonic::{
+    codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    Status,
+};
+
+#[derive(Debug, Snafu)]
+pub(crate) enum MethodError {
+    #[snafu(display("Could not read descriptor set file {:?}: {}", path, source))]
+    ReadDescriptorSet { path: PathBuf, source: io::Error },
+    #[snafu(display("Could not decode descriptor set file {:?}: {}", path, source))]
+    DecodeDescriptorSet {
+        path: PathBuf,
+        source: DescriptorError,
+    },
+    #[snafu(display("Method {:?} is not of the form `package.Service/Method`", method))]
+    InvalidMethod { method: String },
+    #[snafu(display("Service {:?} is not defined in the descriptor set", service))]
+    ServiceNotFound { service: String },
+    #[snafu(display("Method {:?} is not defined by service {:?}", method, service))]
+    MethodNotFound { service: String, method: String },
+}
+
+/// Loads the descriptor set file at `path`.
+pub(crate) fn load_descriptor_pool(path: &Path) -> Result<DescriptorPool, MethodError> {
+    let descriptor_set = fs::read(path).with_context(|_| ReadDescriptorSetSnafu { path })?;
+    DescriptorPool::decode(descriptor_set.as_slice())
+        .with_context(|_| DecodeDescriptorSetSnafu { path })
+}
+
+/// Finds `method`, of the form `package.Service/Method`, in `pool`.
+pub(crate) fn find_method(
+    pool: &DescriptorPool,
+    method: &str,
+) -> Result<MethodDescriptor, MethodError> {
+    let (service_name, method_name) = method
+        .split_once('/')
+        .with_context(|| InvalidMethodSnafu { method })?;
+
+    pool.get_service_by_name(service_name)
+        .with_context(|| ServiceNotFoundSnafu {
+            service: service_name,
+        })?
+        .methods()
+        .find(|method| method.name() == method_name)
+        .with_context(|| MethodNotFoundSnafu {
+            service: service_name,
+            method: method_name,
+        })
+}
+
+/// The path requests calling `method` are sent to.
+pub(crate) fn method_path(method: &MethodDescriptor) -> String {
+    format!("/{}/{}", method.parent_service().full_name(), method.name())
+}
+
+/// Encodes and decodes the messages described by a descriptor only known at runtime.
+#[derive(Clone)]
+pub(crate) struct DynamicCodec {
+    decoded: MessageDescriptor,
+}
+
+impl DynamicCodec {
+    /// Creates a codec decoding the messages described by `decoded`.
+    pub(crate) const fn new(decoded: MessageDescriptor) -> Self {
+        Self { decoded }
+    }
+}
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicEncoder;
+    type Decoder = DynamicDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        DynamicEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        DynamicDecoder(self.decoded.clone())
+    }
+}
+
+pub(crate) struct DynamicEncoder;
+
+impl Encoder for DynamicEncoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        item.encode(dst)
+            .map_err(|error| Status::internal(error.to_string()))
+    }
+}
+
+pub(crate) struct DynamicDecoder(MessageDescriptor);
+
+impl Decoder for DynamicDecoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        let buf = src.copy_to_bytes(src.remaining());
+        DynamicMessage::decode(self.0.clone(), buf)
+            .map(Some)
+            .map_err(|error| Status::invalid_argument(error.to_string()))
+    }
+}
@@ -1,11 +1,18 @@
 use std::{
     fmt,
+    sync::{Arc, RwLock, Weak},
     task::{Context, Poll},
+    time::Duration,
 };
 
 use futures::future::BoxFuture;
 use headers::{Authorization, HeaderMapExt};
-use http::{header::HeaderValue, request::Builder, uri::InvalidUri, HeaderMap, Request, Uri};
+use http::{
+    header::{HeaderValue, CONTENT_TYPE},
+    request::Builder,
+    uri::InvalidUri,
+    HeaderMap, Request, StatusCode, Uri,
+};
 use hyper::{
     body::{Body, HttpBody},
     client,
@@ -40,6 +47,20 @@ pub enum HttpError {
     BuildRequest { source: http::Error },
 }
 
+#[derive(Debug, Snafu)]
+pub enum OAuth2Error {
+    #[snafu(display("Invalid OAuth2 token endpoint: {}", source))]
+    InvalidTokenEndpoint { source: InvalidUri },
+    #[snafu(display("Failed to request OAuth2 access token: {}", source))]
+    RequestToken { source: HttpError },
+    #[snafu(display("Failed to read OAuth2 access token: {}", source))]
+    ReadToken { source: hyper::Error },
+    #[snafu(display("OAuth2 token endpoint responded with {}: {}", status, body))]
+    TokenRejected { status: StatusCode, body: String },
+    #[snafu(display("Failed to parse OAuth2 access token: {}", source))]
+    ParseToken { source: serde_json::Error },
+}
+
 impl HttpError {
     pub const fn is_retriable(&self) -> bool {
         match self {
@@ -257,10 +278,58 @@ pub enum Auth {
         /// The bearer token to send.
         token: SensitiveString,
     },
+
+    /// OAuth2 authentication, with the client credentials grant.
+    ///
+    /// Access tokens are requested from the token endpoint with the credentials of the client, and
+    /// passed as bearer tokens. They're cached, and refreshed before they expire.
+    ///
+    /// This is only supported by the `http`, `loki`, and `prometheus_remote_write` sinks, and the
+    /// `remote_lookup` transform. Other components fail to build with it.
+    #[serde(rename = "oauth2")]
+    OAuth2(#[configurable(derived)] OAuth2Config),
+}
+
+/// OAuth2 client credentials.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OAuth2Config {
+    /// The token endpoint of the authorization server, which access tokens are requested from.
+    #[configurable(metadata(docs::examples = "https://auth.example.com/oauth2/token"))]
+    pub token_endpoint: String,
+
+    /// The identifier of the client.
+    pub client_id: String,
+
+    /// The secret of the client.
+    pub client_secret: SensitiveString,
+
+    /// The scopes to request access tokens for.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "logs:write"))]
+    pub scopes: Vec<String>,
+
+    /// How long before they expire access tokens are refreshed, in seconds.
+    ///
+    /// Access tokens expiring sooner than this are refreshed halfway through their lifetime.
+    #[serde(default = "default_oauth2_refresh_before_secs")]
+    pub refresh_before_secs: u64,
 }
 
+const fn default_oauth2_refresh_before_secs() -> u64 {
+    60
+}
+
+/// How long to wait before requesting an access token again, when refreshing it failed.
+const OAUTH2_RETRY_DELAY: Duration = Duration::from_secs(10);
+
 pub trait MaybeAuth: Sized {
     fn choose_one(&self, other: &Self) -> crate::Result<Self>;
+
+    /// Fails for OAuth2 credentials, which components that don't build an `HttpAuthenticator`
+    /// can't send.
+    fn ensure_static(&self) -> crate::Result<()>;
 }
 
 impl MaybeAuth for Option<Auth> {
@@ -271,9 +340,29 @@ impl MaybeAuth for Option<Auth> {
             Ok(self.clone().or_else(|| other.clone()))
         }
     }
+
+    fn ensure_static(&self) -> crate::Result<()> {
+        match self {
+            Some(Auth::OAuth2(_)) => {
+                Err("OAuth2 authentication is not supported by this component.".into())
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 impl Auth {
+    /// Builds the authenticator of requests, requesting the first access token of OAuth2
+    /// credentials with `client`.
+    pub async fn build(&self, client: &HttpClient) -> crate::Result<HttpAuthenticator> {
+        Ok(match self {
+            Auth::OAuth2(config) => {
+                HttpAuthenticator::OAuth2(OAuth2Token::new(config, client).await?)
+            }
+            auth => HttpAuthenticator::Static(auth.clone()),
+        })
+    }
+
     pub fn apply<B>(&self, req: &mut Request<B>) {
         self.apply_headers_map(req.headers_mut())
     }
@@ -291,14 +380,201 @@ impl Auth {
                 let auth = Authorization::basic(user.as_str(), password.inner());
                 map.typed_insert(auth);
             }
-            Auth::Bearer { token } => match Authorization::bearer(token.inner()) {
-                Ok(auth) => map.typed_insert(auth),
-                Err(error) => error!(message = "Invalid bearer token.", token = %token, %error),
-            },
+            Auth::Bearer { token } => insert_bearer(map, token),
+            Auth::OAuth2(_) => error!(
+                message = "OAuth2 authentication is not supported by this component.",
+                internal_log_rate_limit = true
+            ),
+        }
+    }
+}
+
+fn insert_bearer(map: &mut HeaderMap, token: &SensitiveString) {
+    match Authorization::bearer(token.inner()) {
+        Ok(auth) => map.typed_insert(auth),
+        Err(error) => error!(message = "Invalid bearer token.", token = %token, %error),
+    }
+}
+
+/// Builds the authenticator of requests for `auth`, if it's set.
+pub async fn build_authenticator(
+    auth: Option<&Auth>,
+    client: &HttpClient,
+) -> crate::Result<Option<HttpAuthenticator>> {
+    match auth {
+        Some(auth) => Ok(Some(auth.build(client).await?)),
+        None => Ok(None),
+    }
+}
+
+/// The authentication of HTTP requests, with the access tokens of OAuth2 credentials kept up to
+/// date.
+#[derive(Clone, Debug)]
+pub enum HttpAuthenticator {
+    Static(Auth),
+    OAuth2(Arc<OAuth2Token>),
+}
+
+impl HttpAuthenticator {
+    pub fn apply<B>(&self, req: &mut Request<B>) {
+        match self {
+            Self::Static(auth) => auth.apply(req),
+            Self::OAuth2(token) => insert_bearer(req.headers_mut(), &token.access_token()),
         }
     }
 }
 
+/// The access token of OAuth2 credentials, which is refreshed in the background until the
+/// authenticators sharing it are dropped.
+#[derive(Debug)]
+pub struct OAuth2Token {
+    config: OAuth2Config,
+    token_endpoint: Uri,
+    client: HttpClient,
+    access_token: RwLock<SensitiveString>,
+}
+
+#[derive(serde::Deserialize)]
+struct OAuth2TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+impl OAuth2Token {
+    async fn new(config: &OAuth2Config, client: &HttpClient) -> crate::Result<Arc<Self>> {
+        let token_endpoint = config
+            .token_endpoint
+            .parse()
+            .context(InvalidTokenEndpointSnafu)?;
+        let token = Arc::new(Self {
+            config: config.clone(),
+            token_endpoint,
+            client: client.clone(),
+            access_token: RwLock::new(SensitiveString::default()),
+        });
+
+        let expires_in = token.fetch().await?;
+        let refresh_delay = expires_in.map(|expires_in| token.refresh_delay(expires_in));
+        tokio::spawn(Self::refresh(Arc::downgrade(&token), refresh_delay));
+
+        Ok(token)
+    }
+
+    fn access_token(&self) -> SensitiveString {
+        self.access_token.read().expect("poisoned lock").clone()
+    }
+
+    /// Requests a new access token, returning how long it's valid for, in seconds.
+    async fn fetch(&self) -> Result<Option<u64>, OAuth2Error> {
+        let mut form = url::form_urlencoded::Serializer::new(String::new());
+        form.append_pair("grant_type", "client_credentials");
+        if !self.config.scopes.is_empty() {
+            form.append_pair("scope", &self.config.scopes.join(" "));
+        }
+
+        let mut request = Request::post(&self.token_endpoint)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(form.finish()))
+            .expect("Building request never fails.");
+        request.headers_mut().typed_insert(Authorization::basic(
+            &self.config.client_id,
+            self.config.client_secret.inner(),
+        ));
+
+        debug!(message = "Requesting OAuth2 access token.", token_endpoint = %self.token_endpoint);
+        let response = self.client.send(request).await.context(RequestTokenSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadTokenSnafu)?;
+        if !status.is_success() {
+            let body = String::from_utf8_lossy(&body).into_owned();
+            return Err(OAuth2Error::TokenRejected { status, body });
+        }
+
+        let response: OAuth2TokenResponse =
+            serde_json::from_slice(&body).context(ParseTokenSnafu)?;
+        *self.access_token.write().expect("poisoned lock") = response.access_token.into();
+        Ok(response.expires_in)
+    }
+
+    /// How long to wait before refreshing an access token valid for `expires_in` seconds.
+    fn refresh_delay(&self, expires_in: u64) -> Duration {
+        let delay = if expires_in > self.config.refresh_before_secs {
+            expires_in - self.config.refresh_before_secs
+        } else {
+            expires_in / 2
+        };
+        Duration::from_secs(delay.max(1))
+    }
+
+    /// Refreshes the access token before it expires, for as long as it's used. Access tokens
+    /// without an expiration are never refreshed.
+    async fn refresh(token: Weak<Self>, mut delay: Option<Duration>) {
+        while let Some(wait) = delay {
+            tokio::time::sleep(wait).await;
+            let token = match token.upgrade() {
+                Some(token) => token,
+                None => break,
+            };
+
+            delay = match token.fetch().await {
+                Ok(expires_in) => expires_in.map(|expires_in| token.refresh_delay(expires_in)),
+                Err(error) => {
+                    error!(message = "Failed to refresh OAuth2 access token.", %error);
+                    Some(OAUTH2_RETRY_DELAY)
+                }
+            };
+        }
+    }
+}
+
+/// The hash algorithm used to compute the HMAC.
+#[cfg(any(feature = "sinks-http", feature = "sources-utils-http-signature"))]
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HmacAlgorithm {
+    /// SHA-1.
+    Sha1,
+
+    /// SHA-256.
+    #[derivative(Default)]
+    Sha256,
+
+    /// SHA-512.
+    Sha512,
+}
+
+#[cfg(any(feature = "sinks-http", feature = "sources-utils-http-signature"))]
+impl HmacAlgorithm {
+    pub fn message_digest(self) -> openssl::hash::MessageDigest {
+        use openssl::hash::MessageDigest;
+
+        match self {
+            Self::Sha1 => MessageDigest::sha1(),
+            Self::Sha256 => MessageDigest::sha256(),
+            Self::Sha512 => MessageDigest::sha512(),
+        }
+    }
+}
+
+/// The encoding of the signature in the header value.
+#[cfg(any(feature = "sinks-http", feature = "sources-utils-http-signature"))]
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureEncoding {
+    /// Hexadecimal.
+    #[derivative(Default)]
+    Hex,
+
+    /// Base64.
+    Base64,
+}
+
 pub fn get_http_scheme_from_uri(uri: &Uri) -> &'static str {
     // If there's no scheme, we just use "http" since it provides the most semantic relevance without inadvertently
     // implying things it can't know i.e. returning "https" when we're not actually sure HTTPS was used.
@@ -315,7 +591,11 @@ pub fn get_http_scheme_from_uri(uri: &Uri) -> &'static str {
 
 #[cfg(test)]
 mod tests {
+    use futures::StreamExt;
+    use hyper::Response;
+
     use super::*;
+    use crate::{sinks::util::test::build_test_server_generic, test_util::next_addr};
 
     #[test]
     fn test_default_request_headers_defaults() {
@@ -346,4 +626,43 @@ mod tests {
             Some(&HeaderValue::from_static("foo"))
         );
     }
+
+    #[tokio::test]
+    async fn oauth2_requests_access_tokens() {
+        let addr = next_addr();
+        let (rx, _trigger, server) = build_test_server_generic(addr, || {
+            Response::new(Body::from(
+                r#"{"access_token":"some-token","token_type":"Bearer","expires_in":3600}"#,
+            ))
+        });
+        tokio::spawn(server);
+
+        let auth = Auth::OAuth2(OAuth2Config {
+            token_endpoint: format!("http://{}/token", addr),
+            client_id: "vector".to_owned(),
+            client_secret: "secret".to_owned().into(),
+            scopes: vec!["logs:write".to_owned(), "metrics:write".to_owned()],
+            refresh_before_secs: 60,
+        });
+        let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
+        let authenticator = auth.build(&client).await.unwrap();
+
+        let mut request = Request::post("http://example.com").body(()).unwrap();
+        authenticator.apply(&mut request);
+        assert_eq!(
+            request.headers().get("Authorization"),
+            Some(&HeaderValue::from_static("Bearer some-token")),
+        );
+
+        let (parts, body) = rx.take(1).collect::<Vec<_>>().await.remove(0);
+        assert_eq!(parts.uri.path(), "/token");
+        assert_eq!(
+            parts.headers.typed_get(),
+            Some(Authorization::basic("vector", "secret"))
+        );
+        assert_eq!(
+            &body[..],
+            b"grant_type=client_credentials&scope=logs%3Awrite+metrics%3Awrite"
+        );
+    }
 }
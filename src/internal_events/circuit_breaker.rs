@@ -0,0 +1,68 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+/// The state of the circuit breaker of a sink's requests.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircuitBreakerState {
+    /// Requests are sent.
+    Closed,
+    /// Requests fail without being sent.
+    Open,
+    /// A single request is sent, to probe whether the service recovered.
+    HalfOpen,
+}
+
+impl CircuitBreakerState {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CircuitBreakerStateChanged {
+    pub state: CircuitBreakerState,
+    /// The error rate of the requests that opened the circuit.
+    pub error_rate: Option<f64>,
+}
+
+impl InternalEvent for CircuitBreakerStateChanged {
+    fn emit(self) {
+        match self.state {
+            CircuitBreakerState::Open => warn!(
+                message = "Circuit breaker opened; failing requests without sending them.",
+                error_rate = ?self.error_rate,
+            ),
+            CircuitBreakerState::HalfOpen => {
+                info!(message = "Circuit breaker half-opened; probing the service.")
+            }
+            CircuitBreakerState::Closed => info!(message = "Circuit breaker closed."),
+        }
+        counter!(
+            "circuit_breaker_state_changes_total", 1,
+            "state" => self.state.as_str(),
+        );
+        let open = if self.state == CircuitBreakerState::Closed {
+            0.0
+        } else {
+            1.0
+        };
+        gauge!("circuit_breaker_open", open);
+    }
+}
+
+#[derive(Debug)]
+pub struct CircuitBreakerRequestRejected;
+
+impl InternalEvent for CircuitBreakerRequestRejected {
+    fn emit(self) {
+        debug!(
+            message = "Circuit breaker is open; request not sent.",
+            internal_log_rate_limit = true,
+        );
+        counter!("circuit_breaker_rejected_requests_total", 1);
+    }
+}
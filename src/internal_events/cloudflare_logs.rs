@@ -0,0 +1,92 @@
+use std::{error::Error, io, path::Path};
+
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct CloudflareLogsReadError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for CloudflareLogsReadError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to pull logs.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::REQUEST_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct CloudflareLogsParseError<'a> {
+    pub error: &'a dyn Error,
+}
+
+impl<'a> InternalEvent for CloudflareLogsParseError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse logs.",
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::PARSER_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct CloudflareLogsRetentionExceeded {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl InternalEvent for CloudflareLogsRetentionExceeded {
+    fn emit(self) {
+        warn!(
+            message = "Logs are no longer retained by Cloudflare, skipping them.",
+            start = %self.start,
+            end = %self.end,
+            internal_log_rate_limit = true,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct CloudflareLogsCheckpointError<'a> {
+    pub path: &'a Path,
+    pub error: io::Error,
+}
+
+impl<'a> InternalEvent for CloudflareLogsCheckpointError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to persist checkpoint.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::WRITER_FAILED,
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use metrics::{counter, gauge};
+use vector_core::{event::metric::MetricSeries, internal_event::InternalEvent};
+
+#[derive(Debug)]
+pub struct CounterDeltaReset<'a> {
+    pub series: &'a MetricSeries,
+}
+
+impl<'a> InternalEvent for CounterDeltaReset<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Counter was reset.",
+            series = %self.series,
+            internal_log_rate_limit = true,
+        );
+        counter!("counter_resets_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct CounterDeltaSeriesTracked {
+    pub count: usize,
+}
+
+impl InternalEvent for CounterDeltaSeriesTracked {
+    fn emit(self) {
+        gauge!("counter_delta_series", self.count as f64);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::emit;
-use metrics::counter;
+use metrics::{counter, gauge};
 use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
 
 #[derive(Debug)]
@@ -16,3 +16,20 @@ impl InternalEvent for DedupeEventsDropped {
         counter!("events_discarded_total", self.count as u64); // Deprecated
     }
 }
+
+#[derive(Debug)]
+pub struct DedupeCacheLookup {
+    pub hit: bool,
+    pub cache_size: usize,
+}
+
+impl InternalEvent for DedupeCacheLookup {
+    fn emit(self) {
+        if self.hit {
+            counter!("dedupe_cache_hits_total", 1);
+        } else {
+            counter!("dedupe_cache_misses_total", 1);
+        }
+        gauge!("dedupe_cache_events", self.cache_size as f64);
+    }
+}
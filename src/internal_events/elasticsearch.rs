@@ -0,0 +1,42 @@
+use metrics::counter;
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+use vector_core::internal_event::InternalEvent;
+
+use crate::emit;
+
+#[derive(Debug)]
+pub struct ElasticsearchDocumentsRejected {
+    pub count: usize,
+    pub reason: String,
+    /// Whether the documents were sent to the `rejected` output, rather than dropped.
+    pub rerouted: bool,
+}
+
+impl InternalEvent for ElasticsearchDocumentsRejected {
+    fn emit(self) {
+        error!(
+            message = "Documents rejected by Elasticsearch.",
+            count = self.count as u64,
+            reason = %self.reason,
+            rerouted = self.rerouted,
+            error_code = "documents_rejected",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "documents_rejected",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        if !self.rerouted {
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                count: self.count,
+                reason: "Documents rejected by Elasticsearch.",
+            });
+        }
+    }
+}
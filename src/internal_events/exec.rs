@@ -2,7 +2,6 @@ use std::time::Duration;
 
 use crate::emit;
 use metrics::{counter, histogram};
-use tokio::time::error::Elapsed;
 use vector_common::internal_event::{
     error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
 };
@@ -79,7 +78,6 @@ impl InternalEvent for ExecFailedError<'_> {
 pub struct ExecTimeoutError<'a> {
     pub command: &'a str,
     pub elapsed_seconds: u64,
-    pub error: Elapsed,
 }
 
 impl InternalEvent for ExecTimeoutError<'_> {
@@ -88,7 +86,6 @@ impl InternalEvent for ExecTimeoutError<'_> {
             message = "Timeout during exec.",
             command = %self.command,
             elapsed_seconds = %self.elapsed_seconds,
-            error = %self.error,
             error_type = error_type::TIMED_OUT,
             stage = error_stage::RECEIVING,
             internal_log_rate_limit = true,
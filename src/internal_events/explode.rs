@@ -0,0 +1,16 @@
+use vector_core::internal_event::InternalEvent;
+
+use crate::emit;
+use vector_common::internal_event::{ComponentEventsDropped, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct ExplodeEmptyArray;
+
+impl InternalEvent for ExplodeEmptyArray {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Array field was empty."
+        });
+    }
+}
@@ -4,7 +4,11 @@ use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, UNINTEN
 
 use crate::emit;
 
-#[cfg(any(feature = "sources-file", feature = "sources-kubernetes_logs"))]
+#[cfg(any(
+    feature = "sources-docker_logs",
+    feature = "sources-file",
+    feature = "sources-kubernetes_logs"
+))]
 pub use self::source::*;
 use vector_common::internal_event::{error_stage, error_type};
 
@@ -77,7 +81,11 @@ impl<'a, P: std::fmt::Debug> InternalEvent for FileIoError<'a, P> {
     }
 }
 
-#[cfg(any(feature = "sources-file", feature = "sources-kubernetes_logs"))]
+#[cfg(any(
+    feature = "sources-docker_logs",
+    feature = "sources-file",
+    feature = "sources-kubernetes_logs"
+))]
 mod source {
     use std::{io::Error, path::Path, time::Duration};
 
@@ -0,0 +1,67 @@
+use std::{io, path::Path, time::Duration};
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct GithubAuditReadError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for GithubAuditReadError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read audit log.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::REQUEST_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct GithubAuditRateLimited {
+    pub wait: Duration,
+}
+
+impl InternalEvent for GithubAuditRateLimited {
+    fn emit(self) {
+        warn!(
+            message = "Rate limit of the GitHub API exceeded, waiting for it to be reset.",
+            wait_secs = %self.wait.as_secs(),
+            internal_log_rate_limit = true,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct GithubAuditCheckpointError<'a> {
+    pub path: &'a Path,
+    pub error: io::Error,
+}
+
+impl<'a> InternalEvent for GithubAuditCheckpointError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to persist audit log cursor.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::WRITER_FAILED,
+        );
+    }
+}
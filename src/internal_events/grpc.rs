@@ -48,3 +48,27 @@ where
         );
     }
 }
+
+#[derive(Debug)]
+pub struct GrpcClientRateLimited<'a> {
+    pub client: &'a str,
+}
+
+impl InternalEvent for GrpcClientRateLimited<'_> {
+    fn emit(self) {
+        error!(
+            message = "Client exceeded its rate limit, rejecting request.",
+            client = %self.client,
+            error_code = "client_rate_limited",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "client_rate_limited",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
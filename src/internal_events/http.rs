@@ -1,7 +1,8 @@
 use std::error::Error;
 
+use crate::emit;
 use metrics::{counter, histogram};
-use vector_core::internal_event::InternalEvent;
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, UNINTENTIONAL};
 
 use vector_common::internal_event::{error_stage, error_type};
 
@@ -149,3 +150,31 @@ impl InternalEvent for HttpInternalError {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct HttpInvalidUriError<'a> {
+    pub uri: &'a str,
+    pub error: http::uri::InvalidUri,
+}
+
+impl<'a> InternalEvent for HttpInvalidUriError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Rendered URI is invalid.",
+            uri = %self.uri,
+            error = %self.error,
+            error_type = error_type::TEMPLATE_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::TEMPLATE_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: 1,
+            reason: "Rendered URI is invalid.",
+        });
+    }
+}
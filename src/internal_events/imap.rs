@@ -0,0 +1,50 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct ImapReadError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for ImapReadError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read messages from IMAP server.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::READER_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ImapParseError<E> {
+    pub uid: u32,
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for ImapParseError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse message.",
+            uid = %self.uid,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::PARSER_FAILED,
+        );
+    }
+}
@@ -1,7 +1,10 @@
 use metrics::{counter, gauge};
 use vector_core::{internal_event::InternalEvent, update_counter};
 
-use vector_common::internal_event::{error_stage, error_type};
+use crate::emit;
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
 
 #[derive(Debug)]
 pub struct KafkaBytesReceived<'a> {
@@ -176,3 +179,33 @@ impl InternalEvent for KafkaHeaderExtractionError<'_> {
         counter!("kafka_header_extraction_failures_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct KafkaTransactionError {
+    pub error: rdkafka::error::KafkaError,
+    pub count: usize,
+}
+
+impl InternalEvent for KafkaTransactionError {
+    fn emit(self) {
+        let reason = "Failed to commit transaction.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_code = "kafka_transaction",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_transaction",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason,
+        });
+    }
+}
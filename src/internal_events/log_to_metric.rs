@@ -73,3 +73,49 @@ impl<'a> InternalEvent for LogToMetricParseFloatError<'a> {
         emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason })
     }
 }
+
+/// A metric of a new series was derived beyond the cardinality limit of its metric.
+#[derive(Debug)]
+pub struct LogToMetricCardinalityLimitExceeded<'a> {
+    pub name: &'a str,
+    pub action: &'static str,
+}
+
+impl<'a> InternalEvent for LogToMetricCardinalityLimitExceeded<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Cardinality limit of metric reached.",
+            metric_name = %self.name,
+            action = %self.action,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "log_to_metric_cardinality_limit_exceeded_total", 1,
+            "action" => self.action,
+        );
+    }
+}
+
+pub struct LogToMetricDeserializeError {
+    pub error: serde_json::Error,
+}
+
+impl InternalEvent for LogToMetricDeserializeError {
+    fn emit(self) {
+        let reason = "Log failed to deserialize as a metric.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason })
+    }
+}
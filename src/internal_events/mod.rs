@@ -26,9 +26,16 @@ mod aws_kinesis_firehose;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 mod aws_sqs;
 mod batch;
+#[cfg(feature = "sinks-cassandra")]
+mod cassandra;
+mod circuit_breaker;
+#[cfg(feature = "sources-cloudflare_logs")]
+mod cloudflare_logs;
 mod codecs;
 mod common;
 mod conditions;
+#[cfg(feature = "transforms-counter_delta")]
+mod counter_delta;
 #[cfg(feature = "sinks-datadog_metrics")]
 mod datadog_metrics;
 #[cfg(feature = "sinks-datadog_traces")]
@@ -41,11 +48,15 @@ mod demo_logs;
 mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 mod docker_logs;
+#[cfg(feature = "sinks-elasticsearch")]
+mod elasticsearch;
 mod encoding_transcode;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
 mod exec;
+#[cfg(feature = "transforms-explode")]
+mod explode;
 #[cfg(any(feature = "sources-file-descriptor", feature = "sources-stdin"))]
 mod file_descriptor;
 #[cfg(feature = "transforms-filter")]
@@ -54,7 +65,13 @@ mod filter;
 mod fluent;
 #[cfg(feature = "sources-gcp_pubsub")]
 mod gcp_pubsub;
-#[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
+#[cfg(feature = "sources-github_audit")]
+mod github_audit;
+#[cfg(any(
+    feature = "sources-grpc_server",
+    feature = "sources-opentelemetry",
+    feature = "sources-vector"
+))]
 mod grpc;
 mod heartbeat;
 #[cfg(feature = "sources-host_metrics")]
@@ -63,6 +80,10 @@ mod http;
 pub mod http_client;
 #[cfg(feature = "sources-utils-http-client")]
 mod http_client_source;
+#[cfg(feature = "sinks-iceberg")]
+mod iceberg;
+#[cfg(feature = "sources-imap")]
+mod imap;
 #[cfg(feature = "sinks-influxdb")]
 mod influxdb;
 #[cfg(feature = "sources-internal_logs")]
@@ -83,14 +104,28 @@ mod loki;
 mod lua;
 #[cfg(feature = "transforms-metric_to_log")]
 mod metric_to_log;
+#[cfg(feature = "sources-mongodb_change_stream")]
+mod mongodb_change_stream;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
-#[cfg(feature = "sinks-nats")]
+#[cfg(feature = "sinks-mqtt")]
+mod mqtt;
+#[cfg(feature = "sources-mysql_cdc")]
+mod mysql_cdc;
+#[cfg(any(feature = "sinks-nats", feature = "sources-nats"))]
 mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
+#[cfg(feature = "sources-okta_system_log")]
+mod okta_system_log;
 mod open;
+#[cfg(feature = "sinks-pagerduty_events")]
+mod pagerduty;
 mod parser;
+#[cfg(feature = "transforms-pii_redaction")]
+mod pii_redaction;
+#[cfg(feature = "sources-postgres_cdc")]
+mod postgres_cdc;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
 mod process;
@@ -98,14 +133,24 @@ mod process;
 mod prometheus;
 #[cfg(feature = "sinks-pulsar")]
 mod pulsar;
+#[cfg(feature = "sinks-questdb")]
+mod questdb;
 #[cfg(feature = "sources-redis")]
 mod redis;
 #[cfg(feature = "transforms-reduce")]
 mod reduce;
 mod remap;
+#[cfg(feature = "transforms-remote_lookup")]
+mod remote_lookup;
 mod sample;
+#[cfg(feature = "transforms-schema_enforcement")]
+mod schema_enforcement;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
+#[cfg(feature = "sources-sftp")]
+mod sftp;
+#[cfg(feature = "sinks-smtp")]
+mod smtp;
 mod socket;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
@@ -113,16 +158,23 @@ mod splunk_hec;
 mod statsd_sink;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 mod tag_cardinality_limit;
+#[cfg(feature = "transforms-tail_sampling")]
+mod tail_sampling;
 mod tcp;
 mod template;
 #[cfg(feature = "transforms-throttle")]
 mod throttle;
 mod udp;
 mod unix;
+#[cfg(feature = "transforms-wasm")]
+mod wasm;
 #[cfg(feature = "sinks-websocket")]
 mod websocket;
+#[cfg(all(windows, feature = "sources-windows_event_log"))]
+mod windows_event_log;
 
 #[cfg(any(
+    feature = "sources-docker_logs",
     feature = "sources-file",
     feature = "sources-kubernetes_logs",
     feature = "sinks-file",
@@ -130,6 +182,8 @@ mod websocket;
 mod file;
 mod windows;
 
+#[cfg(feature = "sources-mongodb_change_stream")]
+pub(crate) use mongodb_change_stream::*;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub(crate) use mongodb_metrics::*;
 
@@ -158,7 +212,13 @@ pub(crate) use self::aws_kinesis::*;
 pub(crate) use self::aws_kinesis_firehose::*;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 pub(crate) use self::aws_sqs::*;
+#[cfg(feature = "sinks-cassandra")]
+pub(crate) use self::cassandra::*;
+#[cfg(feature = "sources-cloudflare_logs")]
+pub(crate) use self::cloudflare_logs::*;
 pub(crate) use self::codecs::*;
+#[cfg(feature = "transforms-counter_delta")]
+pub(crate) use self::counter_delta::*;
 #[cfg(feature = "sinks-datadog_metrics")]
 pub(crate) use self::datadog_metrics::*;
 #[cfg(feature = "sinks-datadog_traces")]
@@ -171,11 +231,16 @@ pub(crate) use self::demo_logs::*;
 pub(crate) use self::dnstap::*;
 #[cfg(feature = "sources-docker_logs")]
 pub(crate) use self::docker_logs::*;
+#[cfg(feature = "sinks-elasticsearch")]
+pub(crate) use self::elasticsearch::*;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub(crate) use self::eventstoredb_metrics::*;
 #[cfg(feature = "sources-exec")]
 pub(crate) use self::exec::*;
+#[cfg(feature = "transforms-explode")]
+pub(crate) use self::explode::*;
 #[cfg(any(
+    feature = "sources-docker_logs",
     feature = "sources-file",
     feature = "sources-kubernetes_logs",
     feature = "sinks-file",
@@ -189,7 +254,13 @@ pub(crate) use self::filter::*;
 pub(crate) use self::fluent::*;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub(crate) use self::gcp_pubsub::*;
-#[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
+#[cfg(feature = "sources-github_audit")]
+pub(crate) use self::github_audit::*;
+#[cfg(any(
+    feature = "sources-grpc_server",
+    feature = "sources-opentelemetry",
+    feature = "sources-vector"
+))]
 pub(crate) use self::grpc::*;
 #[cfg(feature = "sources-host_metrics")]
 pub(crate) use self::host_metrics::*;
@@ -198,10 +269,15 @@ pub(crate) use self::host_metrics::*;
     feature = "sources-utils-http-encoding",
     feature = "sources-datadog_agent",
     feature = "sources-splunk_hec",
+    feature = "sinks-http",
 ))]
 pub(crate) use self::http::*;
 #[cfg(feature = "sources-utils-http-client")]
 pub(crate) use self::http_client_source::*;
+#[cfg(feature = "sinks-iceberg")]
+pub(crate) use self::iceberg::*;
+#[cfg(feature = "sources-imap")]
+pub(crate) use self::imap::*;
 #[cfg(feature = "sinks-influxdb")]
 pub(crate) use self::influxdb::*;
 #[cfg(feature = "sources-internal_logs")]
@@ -223,33 +299,57 @@ pub(crate) use self::loki::*;
 pub(crate) use self::lua::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
-#[cfg(feature = "sinks-nats")]
+#[cfg(feature = "sinks-mqtt")]
+pub(crate) use self::mqtt::*;
+#[cfg(feature = "sources-mysql_cdc")]
+pub(crate) use self::mysql_cdc::*;
+#[cfg(any(feature = "sinks-nats", feature = "sources-nats"))]
 pub(crate) use self::nats::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
+#[cfg(feature = "sources-okta_system_log")]
+pub(crate) use self::okta_system_log::*;
+#[cfg(feature = "sinks-pagerduty_events")]
+pub(crate) use self::pagerduty::*;
 pub(crate) use self::parser::*;
+#[cfg(feature = "transforms-pii_redaction")]
+pub(crate) use self::pii_redaction::*;
+#[cfg(feature = "sources-postgres_cdc")]
+pub(crate) use self::postgres_cdc::*;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub(crate) use self::postgresql_metrics::*;
 #[cfg(any(feature = "sources-prometheus", feature = "sinks-prometheus"))]
 pub(crate) use self::prometheus::*;
 #[cfg(feature = "sinks-pulsar")]
 pub(crate) use self::pulsar::*;
+#[cfg(feature = "sinks-questdb")]
+pub(crate) use self::questdb::*;
 #[cfg(feature = "sources-redis")]
 pub(crate) use self::redis::*;
 #[cfg(feature = "transforms-reduce")]
 pub(crate) use self::reduce::*;
 #[cfg(feature = "transforms-remap")]
 pub(crate) use self::remap::*;
+#[cfg(feature = "transforms-remote_lookup")]
+pub(crate) use self::remote_lookup::*;
 #[cfg(feature = "transforms-sample")]
 pub(crate) use self::sample::*;
+#[cfg(feature = "transforms-schema_enforcement")]
+pub(crate) use self::schema_enforcement::*;
 #[cfg(feature = "sinks-sematext")]
 pub(crate) use self::sematext_metrics::*;
+#[cfg(feature = "sources-sftp")]
+pub(crate) use self::sftp::*;
+#[cfg(feature = "sinks-smtp")]
+pub(crate) use self::smtp::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 pub(crate) use self::splunk_hec::*;
 #[cfg(feature = "sinks-statsd")]
 pub(crate) use self::statsd_sink::*;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub(crate) use self::tag_cardinality_limit::*;
+#[cfg(feature = "transforms-tail_sampling")]
+pub(crate) use self::tail_sampling::*;
 #[cfg(feature = "transforms-throttle")]
 pub(crate) use self::throttle::*;
 #[cfg(all(
@@ -265,13 +365,18 @@ pub(crate) use self::throttle::*;
     unix
 ))]
 pub(crate) use self::unix::*;
+#[cfg(feature = "transforms-wasm")]
+pub(crate) use self::wasm::*;
 #[cfg(feature = "sinks-websocket")]
 pub(crate) use self::websocket::*;
 #[cfg(windows)]
 pub(crate) use self::windows::*;
+#[cfg(all(windows, feature = "sources-windows_event_log"))]
+pub(crate) use self::windows_event_log::*;
 pub(crate) use self::{
-    adaptive_concurrency::*, batch::*, common::*, conditions::*, encoding_transcode::*,
-    heartbeat::*, open::*, process::*, socket::*, tcp::*, template::*, udp::*,
+    adaptive_concurrency::*, batch::*, circuit_breaker::*, common::*, conditions::*,
+    encoding_transcode::*, heartbeat::*, open::*, process::*, socket::*, tcp::*, template::*,
+    udp::*,
 };
 
 // this version won't be needed once all `InternalEvent`s implement `name()`
@@ -0,0 +1,53 @@
+use std::{io, path::Path};
+
+use metrics::counter;
+use mongodb::error::Error as MongoError;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct MongoDbChangeStreamError {
+    pub error: MongoError,
+}
+
+impl InternalEvent for MongoDbChangeStreamError {
+    fn emit(self) {
+        error!(
+            message = "Failed to read change stream.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::READER_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MongoDbChangeStreamCheckpointError<'a> {
+    pub path: &'a Path,
+    pub error: io::Error,
+}
+
+impl<'a> InternalEvent for MongoDbChangeStreamCheckpointError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to persist resume token.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::WRITER_FAILED,
+        );
+    }
+}
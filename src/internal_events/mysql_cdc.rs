@@ -0,0 +1,52 @@
+use std::{io, path::Path};
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct MysqlCdcReadError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for MysqlCdcReadError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read binary log.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::READER_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MysqlCdcCheckpointError<'a> {
+    pub path: &'a Path,
+    pub error: io::Error,
+}
+
+impl<'a> InternalEvent for MysqlCdcCheckpointError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to persist binary log position.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::WRITER_FAILED,
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use std::{io, path::Path, time::Duration};
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct OktaSystemLogReadError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for OktaSystemLogReadError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read System Log.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::REQUEST_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct OktaSystemLogRateLimited {
+    pub wait: Duration,
+}
+
+impl InternalEvent for OktaSystemLogRateLimited {
+    fn emit(self) {
+        warn!(
+            message = "Rate limit of the Okta API exceeded, waiting for it to be reset.",
+            wait_secs = %self.wait.as_secs(),
+            internal_log_rate_limit = true,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct OktaSystemLogCheckpointError<'a> {
+    pub path: &'a Path,
+    pub error: io::Error,
+}
+
+impl<'a> InternalEvent for OktaSystemLogCheckpointError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to persist System Log cursor.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::WRITER_FAILED,
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use crate::emit;
+use metrics::counter;
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct PagerdutyInvalidEventAction<'a> {
+    pub action: &'a str,
+}
+
+impl<'a> InternalEvent for PagerdutyInvalidEventAction<'a> {
+    fn emit(self) {
+        let reason = "Event action must be `trigger`, `acknowledge`, or `resolve`.";
+        error!(
+            message = reason,
+            action = %self.action,
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
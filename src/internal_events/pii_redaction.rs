@@ -0,0 +1,25 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct PiiRedactionDetected {
+    pub detector: &'static str,
+    pub action: &'static str,
+    pub count: usize,
+}
+
+impl InternalEvent for PiiRedactionDetected {
+    fn emit(self) {
+        trace!(
+            message = "Personal data or secrets detected.",
+            detector = %self.detector,
+            action = %self.action,
+            count = %self.count,
+        );
+        counter!(
+            "pii_redaction_detections_total", self.count as u64,
+            "detector" => self.detector,
+            "action" => self.action,
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct PostgresCdcQueryError<'a, E> {
+    pub error: E,
+    pub slot_name: &'a str,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for PostgresCdcQueryError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "PostgreSQL query error.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            slot_name = %self.slot_name,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct PostgresCdcDecodeError<'a, E> {
+    pub error: E,
+    pub slot_name: &'a str,
+    pub lsn: &'a str,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for PostgresCdcDecodeError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to decode logical replication message.",
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            slot_name = %self.slot_name,
+            lsn = %self.lsn,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
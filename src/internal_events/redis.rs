@@ -34,3 +34,51 @@ impl InternalEvent for RedisReceiveEventError {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct RedisStreamAckError {
+    pub error: redis::RedisError,
+    pub count: usize,
+}
+
+impl InternalEvent for RedisStreamAckError {
+    fn emit(self) {
+        error!(
+            message = "Failed to acknowledge messages.",
+            error = %self.error,
+            count = %self.count,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct RedisStreamFieldMissingError<'a> {
+    pub id: &'a str,
+    pub field: &'a str,
+}
+
+impl<'a> InternalEvent for RedisStreamFieldMissingError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Message is missing the configured field, discarding.",
+            id = %self.id,
+            field = %self.field,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use metrics::counter;
+use metrics::{counter, gauge};
 use vector_core::internal_event::InternalEvent;
 
 #[derive(Debug)]
@@ -9,3 +9,27 @@ impl InternalEvent for ReduceStaleEventFlushed {
         counter!("stale_events_flushed_total", 1);
     }
 }
+
+/// A group was flushed before its transaction ended, on a group change or on reaching its size
+/// limit.
+#[derive(Debug)]
+pub struct ReduceGroupFlushed {
+    pub reason: &'static str,
+}
+
+impl InternalEvent for ReduceGroupFlushed {
+    fn emit(self) {
+        counter!("reduce_groups_flushed_total", 1, "reason" => self.reason);
+    }
+}
+
+#[derive(Debug)]
+pub struct ReduceBufferedBytes {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for ReduceBufferedBytes {
+    fn emit(self) {
+        gauge!("reduce_buffered_bytes", self.byte_size as f64);
+    }
+}
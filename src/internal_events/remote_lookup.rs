@@ -0,0 +1,71 @@
+use crate::emit;
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct RemoteLookupCacheHit;
+
+impl InternalEvent for RemoteLookupCacheHit {
+    fn emit(self) {
+        counter!("remote_lookup_cache_hits_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct RemoteLookupCacheMiss;
+
+impl InternalEvent for RemoteLookupCacheMiss {
+    fn emit(self) {
+        counter!("remote_lookup_cache_misses_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub enum RemoteLookupFailed {
+    Error(crate::Error),
+    TimedOut,
+}
+
+impl InternalEvent for RemoteLookupFailed {
+    fn emit(self) {
+        let error_type = match self {
+            Self::Error(error) => {
+                error!(
+                    message = "Lookup failed.",
+                    %error,
+                    error_type = error_type::REQUEST_FAILED,
+                    stage = error_stage::PROCESSING,
+                    internal_log_rate_limit = true,
+                );
+                error_type::REQUEST_FAILED
+            }
+            Self::TimedOut => {
+                error!(
+                    message = "Lookup timed out.",
+                    error_type = error_type::TIMED_OUT,
+                    stage = error_stage::PROCESSING,
+                    internal_log_rate_limit = true,
+                );
+                error_type::TIMED_OUT
+            }
+        };
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct RemoteLookupEventDropped;
+
+impl InternalEvent for RemoteLookupEventDropped {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Lookup failed.",
+        })
+    }
+}
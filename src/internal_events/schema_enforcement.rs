@@ -0,0 +1,18 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct SchemaEnforcementEventRejected {
+    pub violations: usize,
+}
+
+impl InternalEvent for SchemaEnforcementEventRejected {
+    fn emit(self) {
+        debug!(
+            message = "Event does not conform to the schema.",
+            violations = %self.violations,
+            internal_log_rate_limit = true,
+        );
+        counter!("schema_enforcement_rejected_events_total", 1);
+    }
+}
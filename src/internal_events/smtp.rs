@@ -0,0 +1,33 @@
+use crate::emit;
+use metrics::counter;
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct SmtpInvalidAddress<'a> {
+    pub address: &'a str,
+    pub error: lettre::address::AddressError,
+}
+
+impl<'a> InternalEvent for SmtpInvalidAddress<'a> {
+    fn emit(self) {
+        let reason = "Recipient is not a valid email address.";
+        error!(
+            message = reason,
+            address = %self.address,
+            error = %self.error,
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -15,7 +15,7 @@ impl<'a> InternalEvent for TagCardinalityLimitRejectingEvent<'a> {
             tag_value = self.tag_value,
             internal_log_rate_limit = true,
         );
-        counter!("tag_value_limit_exceeded_total", 1);
+        counter!("tag_value_limit_exceeded_total", 1, "tag_key" => self.tag_key.to_owned());
 
         emit!(ComponentEventsDropped::<INTENTIONAL> {
             count: 1,
@@ -37,7 +37,7 @@ impl<'a> InternalEvent for TagCardinalityLimitRejectingTag<'a> {
             tag_value = self.tag_value,
             internal_log_rate_limit = true,
         );
-        counter!("tag_value_limit_exceeded_total", 1);
+        counter!("tag_value_limit_exceeded_total", 1, "tag_key" => self.tag_key.to_owned());
     }
 }
 
@@ -51,6 +51,6 @@ impl<'a> InternalEvent for TagCardinalityValueLimitReached<'a> {
             message = "Value_limit reached for key. New values for this key will be rejected.",
             key = %self.key,
         );
-        counter!("value_limit_reached_total", 1);
+        counter!("value_limit_reached_total", 1, "tag_key" => self.key.to_owned());
     }
 }
@@ -0,0 +1,42 @@
+use crate::emit;
+use metrics::counter;
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct TailSamplingTraceDropped {
+    pub spans: usize,
+}
+
+impl InternalEvent for TailSamplingTraceDropped {
+    fn emit(self) {
+        counter!("tail_sampling_traces_dropped_total", 1);
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.spans,
+            reason: "Trace not kept by any sampling policy.",
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct TailSamplingTraceKept {
+    pub spans: usize,
+}
+
+impl InternalEvent for TailSamplingTraceKept {
+    fn emit(self) {
+        trace!(message = "Trace kept by sampling policies.", spans = %self.spans);
+        counter!("tail_sampling_traces_kept_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct TailSamplingLateSpanDropped;
+
+impl InternalEvent for TailSamplingLateSpanDropped {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Span of a trace not kept by any sampling policy.",
+        })
+    }
+}
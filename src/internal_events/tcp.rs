@@ -94,6 +94,33 @@ impl InternalEvent for TcpSocketTlsConnectionError {
     }
 }
 
+#[derive(Debug)]
+pub struct TcpSocketClientUnauthorized<'a> {
+    pub peer_addr: SocketAddr,
+    pub subject: &'a str,
+}
+
+impl<'a> InternalEvent for TcpSocketClientUnauthorized<'a> {
+    fn emit(self) {
+        error!(
+            message = "Client certificate isn't authorized, closing connection.",
+            peer_addr = %self.peer_addr,
+            subject = %self.subject,
+            error_code = "client_unauthorized",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "client_unauthorized",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+            "mode" => "tcp",
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct TcpSendAckError {
     pub error: std::io::Error,
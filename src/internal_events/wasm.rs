@@ -0,0 +1,31 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::emit;
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+
+#[derive(Debug)]
+pub struct WasmExecutionError {
+    pub error: anyhow::Error,
+}
+
+impl InternalEvent for WasmExecutionError {
+    fn emit(self) {
+        let reason = "Error in WebAssembly module.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,102 @@
+use std::{io, path::Path};
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct WindowsEventLogSubscribeError<'a> {
+    pub channel: &'a str,
+    pub error: io::Error,
+}
+
+impl<'a> InternalEvent for WindowsEventLogSubscribeError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to subscribe to event log channel.",
+            channel = %self.channel,
+            error = %self.error,
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::CONNECTION_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogReadError<'a> {
+    pub channel: &'a str,
+    pub error: io::Error,
+}
+
+impl<'a> InternalEvent for WindowsEventLogReadError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read events from event log channel.",
+            channel = %self.channel,
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::READER_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogParseError<'a> {
+    pub channel: &'a str,
+    pub error: roxmltree::Error,
+}
+
+impl<'a> InternalEvent for WindowsEventLogParseError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Invalid event XML, discarding.",
+            channel = %self.channel,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::PARSER_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogBookmarkError<'a> {
+    pub path: &'a Path,
+    pub error: io::Error,
+}
+
+impl<'a> InternalEvent for WindowsEventLogBookmarkError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to persist event log bookmarks.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::WRITER_FAILED,
+        );
+    }
+}
@@ -69,6 +69,8 @@ pub mod enrichment_tables;
 #[cfg(feature = "gcp")]
 pub mod gcp;
 pub(crate) mod graph;
+#[cfg(any(feature = "sources-grpc_server", feature = "sinks-grpc"))]
+pub(crate) mod grpc;
 pub mod heartbeat;
 pub mod http;
 #[allow(unreachable_pub)]
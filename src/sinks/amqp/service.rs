@@ -12,7 +12,6 @@ use std::{
 use tower::Service;
 use vector_common::{
     finalization::{EventFinalizers, EventStatus, Finalizable},
-    internal_event::CountByteSize,
     request_metadata::{MetaDescriptive, RequestMetadata},
 };
 use vector_core::stream::DriverResponse;
@@ -53,27 +52,17 @@ impl Finalizable for AmqpRequest {
 
 impl MetaDescriptive for AmqpRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
 
 /// A successful response from `AMQP`.
-pub(super) struct AmqpResponse {
-    byte_size: usize,
-}
+pub(super) struct AmqpResponse;
 
 impl DriverResponse for AmqpResponse {
     fn event_status(&self) -> EventStatus {
         EventStatus::Delivered
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(1, self.byte_size)
-    }
-
-    fn bytes_sent(&self) -> Option<usize> {
-        Some(self.byte_size)
-    }
 }
 
 /// The tower service that handles the actual sending of data to `AMQP`.
@@ -88,6 +77,9 @@ pub(super) enum AmqpError {
 
     #[snafu(display("Failed AMQP request: {}", error))]
     AmqpDeliveryFailed { error: lapin::Error },
+
+    #[snafu(display("Received Negative Acknowledgement from AMQP server"))]
+    AmqpNack,
 }
 
 impl Service<AmqpRequest> for AmqpService {
@@ -105,12 +97,14 @@ impl Service<AmqpRequest> for AmqpService {
         let channel = Arc::clone(&self.channel);
 
         Box::pin(async move {
-            channel
+            if let Err(error) = channel
                 .confirm_select(lapin::options::ConfirmSelectOptions::default())
                 .await
-                .unwrap();
+            {
+                emit!(AmqpAcknowledgementError { error: &error });
+                return Err(AmqpError::AmqpAcknowledgementFailed { error });
+            }
 
-            let byte_size = req.body.len();
             let fut = channel
                 .basic_publish(
                     &req.exchange,
@@ -123,16 +117,15 @@ impl Service<AmqpRequest> for AmqpService {
 
             match fut {
                 Ok(result) => match result.await {
-                    Ok(lapin::publisher_confirm::Confirmation::Nack(_)) => {
-                        warn!("Received Negative Acknowledgement from AMQP server.");
-                        Ok(AmqpResponse { byte_size })
-                    }
+                    // The server didn't take responsibility for the message, so its events aren't
+                    // acknowledged as delivered.
+                    Ok(lapin::publisher_confirm::Confirmation::Nack(_)) => Err(AmqpError::AmqpNack),
                     Err(error) => {
                         // TODO: In due course the caller could emit these on error.
                         emit!(AmqpAcknowledgementError { error: &error });
                         Err(AmqpError::AmqpAcknowledgementFailed { error })
                     }
-                    Ok(_) => Ok(AmqpResponse { byte_size }),
+                    Ok(_) => Ok(AmqpResponse),
                 },
                 Err(error) => {
                     // TODO: In due course the caller could emit these on error.
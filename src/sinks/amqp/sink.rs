@@ -12,7 +12,8 @@ use serde::Serialize;
 use std::sync::Arc;
 use tower::ServiceBuilder;
 use vector_buffers::EventCount;
-use vector_core::{sink::StreamSink, ByteSizeOf, EstimatedJsonEncodedSizeOf};
+use vector_common::request_metadata::GroupedCountByteSize;
+use vector_core::{sink::StreamSink, ByteSizeOf, EstimatedJsonEncodedSizeOf, GroupedSizeOf};
 
 use super::{
     config::AmqpSinkConfig, encoder::AmqpEncoder, request_builder::AmqpRequestBuilder,
@@ -50,6 +51,12 @@ impl EstimatedJsonEncodedSizeOf for AmqpEvent {
     }
 }
 
+impl GroupedSizeOf for AmqpEvent {
+    fn add_grouped_sizes(&self, grouped_sizes: &mut GroupedCountByteSize) {
+        self.event.add_grouped_sizes(grouped_sizes);
+    }
+}
+
 pub(super) struct AmqpSink {
     pub(super) channel: Arc<lapin::Channel>,
     exchange: Template,
@@ -41,7 +41,7 @@ impl Finalizable for CloudwatchRequest {
 
 impl MetaDescriptive for CloudwatchRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
 
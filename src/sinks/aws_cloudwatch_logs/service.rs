@@ -22,8 +22,7 @@ use tower::{
     timeout::Timeout,
     Service, ServiceBuilder, ServiceExt,
 };
-use vector_common::request_metadata::MetaDescriptive;
-use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
+use vector_core::stream::DriverResponse;
 use vrl::prelude::fmt::Debug;
 
 use crate::{
@@ -98,10 +97,7 @@ impl From<SdkError<DescribeLogStreamsError>> for CloudwatchError {
 }
 
 #[derive(Debug)]
-pub struct CloudwatchResponse {
-    events_count: usize,
-    events_byte_size: usize,
-}
+pub struct CloudwatchResponse;
 
 impl crate::sinks::util::sink::Response for CloudwatchResponse {
     fn is_successful(&self) -> bool {
@@ -117,10 +113,6 @@ impl DriverResponse for CloudwatchResponse {
     fn event_status(&self) -> EventStatus {
         EventStatus::Delivered
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(self.events_count, self.events_byte_size)
-    }
 }
 
 impl CloudwatchLogsPartitionSvc {
@@ -158,9 +150,6 @@ impl Service<BatchCloudwatchRequest> for CloudwatchLogsPartitionSvc {
     }
 
     fn call(&mut self, req: BatchCloudwatchRequest) -> Self::Future {
-        let events_count = req.get_metadata().event_count();
-        let events_byte_size = req.get_metadata().events_byte_size();
-
         let key = req.key;
         let events = req
             .events
@@ -201,10 +190,7 @@ impl Service<BatchCloudwatchRequest> for CloudwatchLogsPartitionSvc {
         };
 
         svc.oneshot(events)
-            .map_ok(move |_x| CloudwatchResponse {
-                events_count,
-                events_byte_size,
-            })
+            .map_ok(|_x| CloudwatchResponse)
             .map_err(Into::into)
             .boxed()
     }
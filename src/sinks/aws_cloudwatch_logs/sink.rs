@@ -81,7 +81,7 @@ impl Finalizable for BatchCloudwatchRequest {
 
 impl MetaDescriptive for BatchCloudwatchRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
 
@@ -0,0 +1,271 @@
+//! Aggregation of records in the format of the [Kinesis Producer Library][kpl], which packs many
+//! user records, with their partition keys, into a single Kinesis record.
+//!
+//! [kpl]: https://github.com/awslabs/amazon-kinesis-producer/blob/master/aggregation-format.md
+
+use std::collections::HashMap;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use md5::{Digest, Md5};
+use prost::encoding::{encode_key, encode_varint, encoded_len_varint, key_len, WireType};
+use vector_common::{finalization::EventFinalizers, request_metadata::RequestMetadata};
+
+use super::{record::Record, request_builder::KinesisRequest, sink::KinesisKey};
+
+/// The bytes aggregated records start with, telling them apart from other records.
+const MAGIC: [u8; 4] = [0xF3, 0x89, 0x9A, 0xC2];
+
+/// The size of the MD5 digest of the protobuf message, which aggregated records end with.
+const DIGEST_SIZE: usize = 16;
+
+/// The largest aggregated record, which leaves room for its partition key under the 1 MiB
+/// limit of Kinesis records.
+pub const MAX_AGGREGATED_RECORD_SIZE: usize = 1_000_000;
+
+// The fields of the `AggregatedRecord` and `Record` messages.
+const PARTITION_KEY_TABLE_TAG: u32 = 1;
+const RECORDS_TAG: u32 = 3;
+const PARTITION_KEY_INDEX_TAG: u32 = 1;
+const DATA_TAG: u32 = 3;
+
+/// Builds an `AggregatedRecord` message, one user record at a time.
+#[derive(Default)]
+struct Aggregator {
+    message: BytesMut,
+    partition_key_indexes: HashMap<String, u64>,
+}
+
+impl Aggregator {
+    fn length_delimited_len(tag: u32, len: usize) -> usize {
+        key_len(tag) + encoded_len_varint(len as u64) + len
+    }
+
+    fn record_len(index: u64, data: &[u8]) -> usize {
+        key_len(PARTITION_KEY_INDEX_TAG)
+            + encoded_len_varint(index)
+            + Self::length_delimited_len(DATA_TAG, data.len())
+    }
+
+    /// The size of the aggregated record, after adding a user record to it.
+    fn size_with(&self, partition_key: &str, data: &[u8]) -> usize {
+        let (index, key_size) = match self.partition_key_indexes.get(partition_key) {
+            Some(index) => (*index, 0),
+            None => (
+                self.partition_key_indexes.len() as u64,
+                Self::length_delimited_len(PARTITION_KEY_TABLE_TAG, partition_key.len()),
+            ),
+        };
+        let record_size = Self::length_delimited_len(RECORDS_TAG, Self::record_len(index, data));
+        MAGIC.len() + self.message.len() + key_size + record_size + DIGEST_SIZE
+    }
+
+    fn is_empty(&self) -> bool {
+        self.message.is_empty()
+    }
+
+    fn push(&mut self, partition_key: &str, data: &[u8]) {
+        let index = match self.partition_key_indexes.get(partition_key) {
+            Some(index) => *index,
+            None => {
+                let index = self.partition_key_indexes.len() as u64;
+                self.partition_key_indexes
+                    .insert(partition_key.to_owned(), index);
+                // Fields may come in any order, so keys are added to the table as they're seen.
+                encode_key(
+                    PARTITION_KEY_TABLE_TAG,
+                    WireType::LengthDelimited,
+                    &mut self.message,
+                );
+                encode_varint(partition_key.len() as u64, &mut self.message);
+                self.message.put_slice(partition_key.as_bytes());
+                index
+            }
+        };
+
+        encode_key(RECORDS_TAG, WireType::LengthDelimited, &mut self.message);
+        encode_varint(Self::record_len(index, data) as u64, &mut self.message);
+        encode_key(PARTITION_KEY_INDEX_TAG, WireType::Varint, &mut self.message);
+        encode_varint(index, &mut self.message);
+        encode_key(DATA_TAG, WireType::LengthDelimited, &mut self.message);
+        encode_varint(data.len() as u64, &mut self.message);
+        self.message.put_slice(data);
+    }
+
+    fn finish(self) -> Bytes {
+        let mut record = BytesMut::with_capacity(MAGIC.len() + self.message.len() + DIGEST_SIZE);
+        record.put_slice(&MAGIC);
+        record.put_slice(&self.message);
+        record.put_slice(&Md5::digest(&self.message));
+        record.freeze()
+    }
+}
+
+/// The user records packed into an aggregated record so far.
+#[derive(Default)]
+struct PendingRecord {
+    aggregator: Aggregator,
+    partition_key: Option<String>,
+    finalizers: EventFinalizers,
+    metadata: Vec<RequestMetadata>,
+}
+
+impl PendingRecord {
+    /// The partition key the user record of `key` is added with.
+    fn partition_key_for(&self, key: &KinesisKey, preserve_partition_keys: bool) -> String {
+        match &self.partition_key {
+            Some(shared) if !preserve_partition_keys => shared.clone(),
+            _ => key.partition_key.clone(),
+        }
+    }
+
+    fn finish<R: Record>(self) -> KinesisRequest<R> {
+        let partition_key = self.partition_key.unwrap_or_default();
+        let payload = self.aggregator.finish();
+        let mut metadata = RequestMetadata::from_batch(self.metadata);
+        // Aggregated records are framed, so they're larger than their user records in all.
+        metadata.set_request_sizes(payload.len(), payload.len());
+        KinesisRequest {
+            record: R::new(&payload, &partition_key),
+            key: KinesisKey { partition_key },
+            payload,
+            finalizers: self.finalizers,
+            metadata,
+        }
+    }
+}
+
+/// Packs `requests` into as few aggregated records as fit under the size limit of records.
+///
+/// The aggregated records are routed by the partition key of their first user record. When
+/// `preserve_partition_keys` is false, the user records share that key too, so that the random
+/// keys of events don't fill the partition key tables of records.
+pub fn aggregate<R: Record>(
+    requests: Vec<KinesisRequest<R>>,
+    preserve_partition_keys: bool,
+) -> Vec<KinesisRequest<R>> {
+    let mut records = Vec::new();
+    let mut pending = PendingRecord::default();
+    for request in requests {
+        let partition_key = pending.partition_key_for(&request.key, preserve_partition_keys);
+        if !pending.aggregator.is_empty()
+            && pending
+                .aggregator
+                .size_with(&partition_key, &request.payload)
+                > MAX_AGGREGATED_RECORD_SIZE
+        {
+            records.push(std::mem::take(&mut pending).finish());
+        }
+
+        let partition_key = pending.partition_key_for(&request.key, preserve_partition_keys);
+        pending.aggregator.push(&partition_key, &request.payload);
+        pending.partition_key.get_or_insert(partition_key);
+        pending.finalizers.merge(request.finalizers);
+        pending.metadata.push(request.metadata);
+    }
+    if !pending.aggregator.is_empty() {
+        records.push(pending.finish());
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_aggregated_records() {
+        let mut aggregator = Aggregator::default();
+        aggregator.push("a", b"x");
+        aggregator.push("b", b"yz");
+        aggregator.push("a", b"");
+        let expected_size = aggregator.size_with("a", b"w");
+        let record = aggregator.finish();
+
+        let message: &[u8] = &[
+            0x0A, 1, b'a', // partition_key_table: "a"
+            0x1A, 5, 0x08, 0, 0x1A, 1, b'x', // records: { partition_key_index: 0, data: "x" }
+            0x0A, 1, b'b', // partition_key_table: "b"
+            0x1A, 6, 0x08, 1, 0x1A, 2, b'y', b'z', // records: { 1, "yz" }
+            0x1A, 4, 0x08, 0, 0x1A, 0, // records: { 0, "" }
+        ];
+        assert_eq!(&record[..4], MAGIC);
+        assert_eq!(&record[4..record.len() - DIGEST_SIZE], message);
+        assert_eq!(
+            &record[record.len() - DIGEST_SIZE..],
+            &Md5::digest(message)[..]
+        );
+        assert_eq!(expected_size, record.len() + 7);
+    }
+
+    #[derive(Clone)]
+    struct TestRecord {
+        payload: Bytes,
+        partition_key: String,
+    }
+
+    impl Record for TestRecord {
+        type T = Self;
+
+        fn new(payload_bytes: &Bytes, partition_key: &str) -> Self {
+            Self {
+                payload: payload_bytes.clone(),
+                partition_key: partition_key.to_owned(),
+            }
+        }
+
+        fn encoded_length(&self) -> usize {
+            self.payload.len()
+        }
+
+        fn get(self) -> Self::T {
+            self
+        }
+    }
+
+    fn request(partition_key: &str, size: usize) -> KinesisRequest<TestRecord> {
+        let payload = Bytes::from(vec![b'x'; size]);
+        KinesisRequest {
+            key: KinesisKey {
+                partition_key: partition_key.to_owned(),
+            },
+            record: TestRecord::new(&payload, partition_key),
+            payload,
+            finalizers: EventFinalizers::default(),
+            metadata: RequestMetadata::new(1, size, size, size, size),
+        }
+    }
+
+    #[test]
+    fn splits_aggregated_records() {
+        let requests = vec![
+            request("a", 400_000),
+            request("b", 400_000),
+            request("c", 400_000),
+        ];
+
+        let records = aggregate(requests, true);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].key.partition_key, "a");
+        assert_eq!(records[0].record.partition_key, "a");
+        assert_eq!(records[0].metadata.event_count(), 2);
+        assert_eq!(
+            records[0].metadata.request_encoded_size(),
+            records[0].payload.len()
+        );
+        assert_eq!(records[1].key.partition_key, "c");
+        assert!(records
+            .iter()
+            .all(|record| record.payload.len() <= MAX_AGGREGATED_RECORD_SIZE));
+    }
+
+    #[test]
+    fn shares_random_partition_keys() {
+        let records = aggregate(vec![request("a", 1), request("b", 1)], false);
+
+        assert_eq!(records.len(), 1);
+        let mut aggregator = Aggregator::default();
+        aggregator.push("a", b"x");
+        aggregator.push("a", b"x");
+        assert_eq!(records[0].payload, aggregator.finish());
+    }
+}
@@ -79,6 +79,7 @@ impl KinesisSinkBaseConfig {
 pub async fn build_sink<C, R, RR, E, RT>(
     config: &KinesisSinkBaseConfig,
     partition_key_field: Option<String>,
+    aggregate: bool,
     batch_settings: BatcherSettings,
     client: C,
 ) -> crate::Result<VectorSink>
@@ -120,6 +121,7 @@ where
         service,
         request_builder,
         partition_key_field,
+        aggregate,
         _phantom: PhantomData,
     };
     Ok(VectorSink::from_event_streamsink(sink))
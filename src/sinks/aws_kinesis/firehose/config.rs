@@ -139,6 +139,7 @@ impl SinkConfig for KinesisFirehoseSinkConfig {
         >(
             &self.base,
             None,
+            false,
             batch_settings,
             KinesisFirehoseClient { client },
         )
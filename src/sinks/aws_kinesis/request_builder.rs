@@ -36,8 +36,10 @@ where
 {
     pub key: KinesisKey,
     pub record: R,
+    /// The encoded event of the record, which is needed to aggregate it with others.
+    pub payload: Bytes,
     pub finalizers: EventFinalizers,
-    metadata: RequestMetadata,
+    pub metadata: RequestMetadata,
 }
 
 impl<R> Finalizable for KinesisRequest<R>
@@ -54,7 +56,7 @@ where
     R: Record,
 {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
 
@@ -122,6 +124,7 @@ where
                 partition_key: kinesis_metadata.partition_key.clone(),
             },
             record,
+            payload: payload_bytes,
             finalizers: kinesis_metadata.finalizers,
             metadata,
         }
@@ -7,8 +7,7 @@ use aws_smithy_client::SdkError;
 use aws_types::region::Region;
 use futures::future::BoxFuture;
 use tower::Service;
-use vector_common::request_metadata::MetaDescriptive;
-use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
+use vector_core::stream::DriverResponse;
 
 use super::{
     record::{Record, SendRecord},
@@ -39,19 +38,12 @@ where
     }
 }
 
-pub struct KinesisResponse {
-    count: usize,
-    events_byte_size: usize,
-}
+pub struct KinesisResponse;
 
 impl DriverResponse for KinesisResponse {
     fn event_status(&self) -> EventStatus {
         EventStatus::Delivered
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(self.count, self.events_byte_size)
-    }
 }
 
 impl<R, C, T, E> Service<BatchKinesisRequest<R>> for KinesisService<C, T, E>
@@ -72,9 +64,6 @@ where
 
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, requests: BatchKinesisRequest<R>) -> Self::Future {
-        let events_byte_size = requests.get_metadata().events_byte_size();
-        let count = requests.get_metadata().event_count();
-
         let records = requests
             .events
             .into_iter()
@@ -92,10 +81,7 @@ where
                 return Err(e);
             }
 
-            Ok(KinesisResponse {
-                count,
-                events_byte_size,
-            })
+            Ok(KinesisResponse)
         })
     }
 }
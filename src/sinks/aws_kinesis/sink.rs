@@ -20,6 +20,7 @@ use crate::{
 };
 
 use super::{
+    aggregation,
     record::Record,
     request_builder::{KinesisRequest, KinesisRequestBuilder},
 };
@@ -37,6 +38,7 @@ pub struct KinesisSink<S, R> {
     pub service: S,
     pub request_builder: KinesisRequestBuilder<R>,
     pub partition_key_field: Option<String>,
+    pub aggregate: bool,
     pub _phantom: PhantomData<R>,
 }
 
@@ -52,6 +54,10 @@ where
         let request_builder_concurrency_limit = NonZeroUsize::new(50);
 
         let partition_key_field = self.partition_key_field.clone();
+        let aggregate = self.aggregate;
+        // Without a partition key field, aggregated records share the key of their first user
+        // record, so that events aren't batched by their random keys.
+        let batch_by_key = !aggregate || partition_key_field.is_some();
 
         input
             .filter_map(|event| {
@@ -73,13 +79,17 @@ where
             })
             .batched_partitioned(
                 KinesisPartitioner {
+                    batch_by_key,
                     _phantom: PhantomData,
                 },
                 self.batch_settings,
             )
-            .map(|(key, events)| {
+            .map(move |(key, mut events)| {
                 let metadata =
                     RequestMetadata::from_batch(events.iter().map(|req| req.get_metadata()));
+                if aggregate {
+                    events = aggregation::aggregate(events, batch_by_key);
+                }
                 BatchKinesisRequest {
                     key,
                     events,
@@ -168,7 +178,7 @@ where
                 partition_key: self.key.partition_key.clone(),
             },
             events: self.events.to_vec(),
-            metadata: self.metadata,
+            metadata: self.metadata.clone(),
         }
     }
 }
@@ -187,7 +197,7 @@ where
     R: Record + Clone,
 {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
 
@@ -195,6 +205,7 @@ struct KinesisPartitioner<R>
 where
     R: Record,
 {
+    batch_by_key: bool,
     _phantom: PhantomData<R>,
 }
 
@@ -206,6 +217,12 @@ where
     type Key = KinesisKey;
 
     fn partition(&self, item: &Self::Item) -> Self::Key {
-        item.key.clone()
+        if self.batch_by_key {
+            item.key.clone()
+        } else {
+            KinesisKey {
+                partition_key: String::new(),
+            }
+        }
     }
 }
@@ -77,6 +77,21 @@ pub struct KinesisStreamsSinkConfig {
     /// If not specified, a unique partition key will be generated for each Kinesis record.
     pub partition_key_field: Option<String>,
 
+    /// Whether to aggregate events into records in the [aggregation format][kpl_aggregation] of
+    /// the Kinesis Producer Library.
+    ///
+    /// Aggregation packs many events into each record, of up to 1 MB, which lowers the number of
+    /// records, and so the cost, of streams with many small events. Consumers must deaggregate
+    /// records, which the Kinesis Client Library does on its own, and AWS Lambda functions can do
+    /// with the deaggregation libraries of the Kinesis Producer Library.
+    ///
+    /// The partition keys of events are kept in the records, but the records themselves are
+    /// routed to shards by the partition key of their first event.
+    ///
+    /// [kpl_aggregation]: https://github.com/awslabs/amazon-kinesis-producer/blob/master/aggregation-format.md
+    #[serde(default)]
+    pub aggregate: bool,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: BatchConfig<KinesisDefaultBatchSettings>,
@@ -129,12 +144,14 @@ impl SinkConfig for KinesisStreamsSinkConfig {
         let client = self.create_client(&cx.proxy).await?;
         let healthcheck = self.clone().healthcheck(client.clone()).boxed();
 
-        let batch_settings = self
-            .batch
-            .validate()?
-            .limit_max_bytes(MAX_PAYLOAD_SIZE)?
-            .limit_max_events(MAX_PAYLOAD_EVENTS)?
-            .into_batcher_settings()?;
+        let batch = self.batch.validate()?.limit_max_bytes(MAX_PAYLOAD_SIZE)?;
+        // Aggregated batches are sent in a handful of records, however many events they hold.
+        let batch = if self.aggregate {
+            batch
+        } else {
+            batch.limit_max_events(MAX_PAYLOAD_EVENTS)?
+        };
+        let batch_settings = batch.into_batcher_settings()?;
 
         let sink = build_sink::<
             KinesisStreamClient,
@@ -144,7 +161,8 @@ impl SinkConfig for KinesisStreamsSinkConfig {
             KinesisRetryLogic,
         >(
             &self.base,
-            None,
+            self.partition_key_field.clone(),
+            self.aggregate,
             batch_settings,
             KinesisStreamClient { client },
         )
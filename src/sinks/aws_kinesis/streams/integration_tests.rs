@@ -45,6 +45,7 @@ async fn kinesis_put_records() {
 
     let config = KinesisStreamsSinkConfig {
         partition_key_field: None,
+        aggregate: false,
         batch,
         base,
     };
@@ -9,7 +9,12 @@ use tower::ServiceBuilder;
 use vector_config::configurable_component;
 use vector_core::sink::VectorSink;
 
-use super::sink::S3RequestOptions;
+use super::{
+    parquet::ParquetSerializerConfig,
+    sink::{S3Encoder, S3RequestOptions},
+};
+#[cfg(feature = "codecs-parquet")]
+use crate::codecs::Transformer;
 use crate::{
     aws::{AwsAuthentication, RegionOrEndpoint},
     codecs::{Encoder, EncodingConfigWithFraming, SinkType},
@@ -20,7 +25,7 @@ use crate::{
     sinks::{
         s3_common::{
             self,
-            config::{S3Options, S3RetryLogic},
+            config::{S3MultipartUploadConfig, S3Options, S3RetryLogic, MIN_PART_SIZE_BYTES},
             partitioner::S3KeyPartitioner,
             service::S3Service,
             sink::S3Sink,
@@ -38,6 +43,8 @@ use crate::{
 const DEFAULT_KEY_PREFIX: &str = "date=%F/";
 const DEFAULT_FILENAME_TIME_FORMAT: &str = "%s";
 const DEFAULT_FILENAME_APPEND_UUID: bool = true;
+#[cfg(feature = "codecs-parquet")]
+const PARQUET_CONTENT_TYPE: &str = "application/vnd.apache.parquet";
 
 /// Configuration for the `aws_s3` sink.
 #[configurable_component(sink("aws_s3"))]
@@ -54,9 +61,23 @@ pub struct S3SinkConfig {
     /// Prefixes are useful for partitioning objects, such as by creating an object key that
     /// stores objects under a particular "directory". If using a prefix for this purpose, it must end
     /// in `/` in order to act as a directory path: Vector will **not** add a trailing `/` automatically.
+    ///
+    /// When `partition_by` is set, no prefix is applied by default.
     #[configurable(metadata(docs::templateable))]
     pub key_prefix: Option<String>,
 
+    /// Hive-style partitions of the object keys, rendered as `name=value/` directories after the
+    /// key prefix, in order.
+    ///
+    /// Values are escaped the way Hive escapes the values of partitions. Events whose values are
+    /// empty, or are missing the fields they are rendered from, are written to the
+    /// `__HIVE_DEFAULT_PARTITION__` partition instead of being dropped.
+    ///
+    /// Each partition is batched on its own, so objects are written to each of them whenever
+    /// their batch reaches `batch.max_bytes` or `batch.timeout_secs`.
+    #[serde(default)]
+    pub partition_by: Vec<S3HivePartition>,
+
     /// The timestamp format for the time component of the object key.
     ///
     /// By default, object keys are appended with a timestamp that reflects when the objects are
@@ -97,6 +118,9 @@ pub struct S3SinkConfig {
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
+    #[configurable(derived)]
+    pub batch_encoding: Option<S3BatchEncoding>,
+
     #[configurable(derived)]
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
@@ -109,6 +133,9 @@ pub struct S3SinkConfig {
     #[serde(default)]
     pub request: TowerRequestConfig,
 
+    #[configurable(derived)]
+    pub multipart_upload: Option<S3MultipartUploadConfig>,
+
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
 
@@ -125,20 +152,77 @@ pub struct S3SinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// A Hive-style partition of object keys.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct S3HivePartition {
+    /// The name of the partition.
+    #[configurable(metadata(docs::examples = "dt"))]
+    #[configurable(metadata(docs::examples = "region"))]
+    pub name: String,
+
+    /// The value of the partition.
+    #[configurable(metadata(docs::examples = "%Y-%m-%d"))]
+    #[configurable(metadata(docs::examples = "{{ region }}"))]
+    pub value: Template,
+}
+
+/// Encoding applied to whole batches of events, instead of to each event.
+///
+/// When set, objects are encoded with it rather than with `encoding.codec` and `framing`, and are
+/// not compressed with `compression`. The other `encoding` options still apply to events.
+///
+/// Batch encodings are only available in builds of Vector with the `codecs-parquet` feature.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "codec", rename_all = "snake_case")]
+pub enum S3BatchEncoding {
+    /// Batches are encoded as [Apache Parquet][apache_parquet] files.
+    ///
+    /// [apache_parquet]: https://parquet.apache.org/
+    Parquet(#[configurable(derived)] ParquetSerializerConfig),
+}
+
+#[cfg(feature = "codecs-parquet")]
+impl S3BatchEncoding {
+    /// Encodes the objects of `options` with this encoding, applying `transformer` to events.
+    fn apply(&self, mut options: S3RequestOptions, transformer: Transformer) -> S3RequestOptions {
+        match self {
+            Self::Parquet(config) => {
+                options
+                    .api_options
+                    .content_type
+                    .get_or_insert_with(|| PARQUET_CONTENT_TYPE.into());
+                options
+                    .filename_extension
+                    .get_or_insert_with(|| "parquet".into());
+                options.encoder = S3Encoder::Parquet(transformer, config.build());
+                // Parquet files compress their own pages.
+                options.compression = Compression::None;
+            }
+        }
+        options
+    }
+}
+
 impl GenerateConfig for S3SinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             bucket: "".to_owned(),
             key_prefix: None,
+            partition_by: Vec::new(),
             filename_time_format: None,
             filename_append_uuid: None,
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::default(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
+            batch_encoding: None,
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
+            multipart_upload: None,
             tls: Some(TlsConfig::default()),
             auth: AwsAuthentication::default(),
             acknowledgements: Default::default(),
@@ -173,16 +257,21 @@ impl S3SinkConfig {
         // limits, rate limits, and whatever else the client should have.
         let request_limits = self.request.unwrap_with(&Default::default());
         let service = ServiceBuilder::new()
-            .settings(request_limits, S3RetryLogic)
+            .partitioned_settings(request_limits, S3RetryLogic)
             .service(service);
 
         // Configure our partitioning/batching.
         let batch_settings = self.batch.into_batcher_settings()?;
+        let default_key_prefix = if self.partition_by.is_empty() {
+            DEFAULT_KEY_PREFIX
+        } else {
+            ""
+        };
         let key_prefix = self
             .key_prefix
             .as_ref()
             .cloned()
-            .unwrap_or_else(|| DEFAULT_KEY_PREFIX.into())
+            .unwrap_or_else(|| default_key_prefix.into())
             .try_into()?;
         let ssekms_key_id = self
             .options
@@ -191,7 +280,21 @@ impl S3SinkConfig {
             .cloned()
             .map(|ssekms_key_id| Template::try_from(ssekms_key_id.as_str()))
             .transpose()?;
-        let partitioner = S3KeyPartitioner::new(key_prefix, ssekms_key_id);
+        let partitions = self
+            .partition_by
+            .iter()
+            .map(|partition| (partition.name.clone(), partition.value.clone()))
+            .collect();
+        let tags = self
+            .options
+            .tags
+            .iter()
+            .flatten()
+            .map(|(name, value)| Ok((name.clone(), Template::try_from(value.as_str())?)))
+            .collect::<crate::Result<_>>()?;
+        let partitioner = S3KeyPartitioner::new(key_prefix, ssekms_key_id)
+            .with_hive_partitions(partitions)
+            .with_tags(tags);
 
         // And now collect all of the S3-specific options and configuration knobs.
         let filename_time_format = self
@@ -213,9 +316,23 @@ impl S3SinkConfig {
             filename_extension: self.filename_extension.clone(),
             filename_time_format,
             filename_append_uuid,
-            encoder: (transformer, encoder),
+            encoder: S3Encoder::Framed((transformer, encoder)),
             compression: self.compression,
         };
+        #[cfg(feature = "codecs-parquet")]
+        let request_options = match &self.batch_encoding {
+            Some(batch_encoding) => {
+                batch_encoding.apply(request_options, self.encoding.transformer())
+            }
+            None => request_options,
+        };
+        #[cfg(not(feature = "codecs-parquet"))]
+        if self.batch_encoding.is_some() {
+            return Err(
+                "`batch_encoding` requires Vector to be built with the `codecs-parquet` feature"
+                    .into(),
+            );
+        }
 
         let sink = S3Sink::new(service, request_options, partitioner, batch_settings);
 
@@ -227,16 +344,55 @@ impl S3SinkConfig {
     }
 
     pub async fn create_service(&self, proxy: &ProxyConfig) -> crate::Result<S3Service> {
-        s3_common::config::create_service(&self.region, &self.auth, proxy, &self.tls).await
+        if let Some(multipart_upload) = &self.multipart_upload {
+            if multipart_upload.part_size_bytes < MIN_PART_SIZE_BYTES {
+                return Err(format!(
+                    "`multipart_upload.part_size_bytes` must be at least {} bytes",
+                    MIN_PART_SIZE_BYTES
+                )
+                .into());
+            }
+        }
+
+        let service =
+            s3_common::config::create_service(&self.region, &self.auth, proxy, &self.tls).await?;
+        Ok(service.with_multipart_upload(self.multipart_upload))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::S3SinkConfig;
+    use super::{S3BatchEncoding, S3SinkConfig};
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<S3SinkConfig>();
     }
+
+    #[test]
+    fn parses_partitioned_parquet_output() {
+        let config: S3SinkConfig = toml::from_str(
+            r#"bucket = "logs"
+            encoding.codec = "json"
+            batch_encoding.codec = "parquet"
+            batch_encoding.schema.timestamp = "timestamp"
+            batch_encoding.schema.message = "string"
+            partition_by = [
+                { name = "dt", value = "%Y-%m-%d" },
+                { name = "region", value = "{{ region }}" },
+            ]"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            config.batch_encoding,
+            Some(S3BatchEncoding::Parquet(ref parquet)) if parquet.schema.len() == 2
+        ));
+        let names: Vec<_> = config
+            .partition_by
+            .iter()
+            .map(|partition| partition.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["dt", "region"]);
+    }
 }
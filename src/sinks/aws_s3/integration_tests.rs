@@ -1,7 +1,8 @@
 #![cfg(all(test, feature = "aws-s3-integration-tests"))]
 
 use std::{
-    io::{BufRead, BufReader},
+    io::{BufRead, BufReader, Read},
+    num::NonZeroUsize,
     time::Duration,
 };
 
@@ -26,14 +27,20 @@ use vector_core::{
     event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, EventArray, LogEvent},
 };
 
-use super::S3SinkConfig;
+use super::{
+    config::{S3BatchEncoding, S3HivePartition},
+    parquet::{ParquetColumnType, ParquetCompression, ParquetSerializerConfig},
+    S3SinkConfig,
+};
 use crate::test_util::components::{run_and_assert_sink_error, COMPONENT_ERROR_TAGS};
 use crate::{
     aws::{create_client, AwsAuthentication, RegionOrEndpoint},
     common::s3::S3ClientBuilder,
     config::SinkContext,
     sinks::{
-        s3_common::config::{S3Options, S3ServerSideEncryption},
+        s3_common::config::{
+            S3MultipartUploadConfig, S3Options, S3ServerSideEncryption, MIN_PART_SIZE_BYTES,
+        },
         util::{BatchConfig, Compression, TowerRequestConfig},
     },
     test_util::{
@@ -204,6 +211,97 @@ async fn s3_rotate_files_after_the_buffer_size_is_reached() {
     assert_eq!(&lines[20..30], response_lines[2].as_slice());
 }
 
+#[tokio::test]
+async fn s3_multipart_upload() {
+    let cx = SinkContext::new_test();
+
+    let bucket = uuid::Uuid::new_v4().to_string();
+
+    create_bucket(&bucket, false).await;
+
+    let mut config = config(&bucket, 1000000);
+    config.batch.max_bytes = Some(4 * MIN_PART_SIZE_BYTES);
+    config.multipart_upload = Some(S3MultipartUploadConfig {
+        part_size_bytes: MIN_PART_SIZE_BYTES,
+        concurrency: NonZeroUsize::new(2).unwrap(),
+    });
+    let prefix = config.key_prefix.clone();
+    let service = config.create_service(&cx.globals.proxy).await.unwrap();
+    let sink = config.build_processor(service).unwrap();
+
+    // Enough lines for three parts.
+    let (lines, events, receiver) = make_events_batch(1000, 12_000);
+    run_and_assert_sink_compliance(sink, events, &AWS_SINK_TAGS).await;
+    assert_eq!(receiver.await, BatchStatus::Delivered);
+
+    let keys = get_keys(&bucket, prefix.unwrap()).await;
+    assert_eq!(keys.len(), 1);
+
+    let obj = get_object(&bucket, keys[0].clone()).await;
+    let response_lines = get_lines(obj).await;
+    assert_eq!(lines, response_lines);
+}
+
+#[tokio::test]
+async fn s3_parquet_hive_partitions() {
+    let cx = SinkContext::new_test();
+
+    let bucket = uuid::Uuid::new_v4().to_string();
+
+    create_bucket(&bucket, false).await;
+
+    let prefix = random_string(10) + "/";
+    let config = S3SinkConfig {
+        key_prefix: Some(prefix.clone()),
+        partition_by: vec![S3HivePartition {
+            name: "i".into(),
+            value: "{{ i }}".try_into().unwrap(),
+        }],
+        batch_encoding: Some(S3BatchEncoding::Parquet(ParquetSerializerConfig {
+            schema: [("message".to_owned(), ParquetColumnType::String)].into(),
+            compression: ParquetCompression::Snappy,
+        })),
+        ..config(&bucket, 10)
+    };
+    let service = config.create_service(&cx.globals.proxy).await.unwrap();
+    let sink = config.build_processor(service).unwrap();
+
+    let (lines, _events) = random_lines_with_stream(100, 20, None);
+    let events = lines.into_iter().enumerate().map(|(i, line)| {
+        let mut e = LogEvent::from(line);
+        if i < 10 {
+            e.insert("i", "a/b");
+        }
+        Event::from(e)
+    });
+
+    run_and_assert_sink_compliance(sink, stream::iter(events), &AWS_SINK_TAGS).await;
+
+    // Hard-coded sleeps are bad, but we're waiting on localstack's state to converge.
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let mut keys = get_keys(&bucket, prefix.clone()).await;
+    keys.sort();
+    assert_eq!(keys.len(), 2);
+    assert!(keys[0].starts_with(&format!("{}i=__HIVE_DEFAULT_PARTITION__/", prefix)));
+    assert!(keys[1].starts_with(&format!("{}i=a%2Fb/", prefix)));
+
+    for key in keys {
+        assert!(key.ends_with(".parquet"));
+        let object = get_object(&bucket, key).await;
+        assert_eq!(
+            object.content_type(),
+            Some("application/vnd.apache.parquet")
+        );
+        let mut body = Vec::new();
+        get_object_output_body(object)
+            .await
+            .read_to_end(&mut body)
+            .unwrap();
+        assert!(body.starts_with(b"PAR1"));
+    }
+}
+
 #[tokio::test]
 async fn s3_gzip() {
     // Here, we're creating a bunch of events, approximately 3000, while setting our batch size
@@ -383,15 +481,18 @@ fn config(bucket: &str, batch_size: usize) -> S3SinkConfig {
     S3SinkConfig {
         bucket: bucket.to_string(),
         key_prefix: Some(random_string(10) + "/date=%F"),
+        partition_by: Vec::new(),
         filename_time_format: None,
         filename_append_uuid: None,
         filename_extension: None,
         options: S3Options::default(),
         region: RegionOrEndpoint::with_both("minio", s3_address()),
         encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
+        batch_encoding: None,
         compression: Compression::None,
         batch,
         request: TowerRequestConfig::default(),
+        multipart_upload: None,
         tls: Default::default(),
         auth: Default::default(),
         acknowledgements: Default::default(),
@@ -0,0 +1,71 @@
+//! Encoding batches of events as Parquet files.
+
+use std::collections::BTreeMap;
+
+use derivative::Derivative;
+use vector_config::configurable_component;
+
+#[cfg(feature = "codecs-parquet")]
+mod serializer;
+
+#[cfg(feature = "codecs-parquet")]
+pub use self::serializer::ParquetSerializer;
+
+/// Configuration for encoding batches of events as Parquet files.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ParquetSerializerConfig {
+    /// The columns of the files, by name, with the types of their values.
+    ///
+    /// Columns hold the values of the fields of events at the same paths, and are ordered by name.
+    /// Values that don't fit the types of their columns are written as nulls.
+    pub schema: BTreeMap<String, ParquetColumnType>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: ParquetCompression,
+}
+
+/// The type of the values of a column.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetColumnType {
+    /// Booleans.
+    Boolean,
+
+    /// 64-bit signed integers.
+    Int64,
+
+    /// 64-bit floating point numbers, which integers are converted to.
+    Double,
+
+    /// UTF-8 strings, which other values are converted to, with objects and arrays encoded as JSON.
+    String,
+
+    /// Timestamps, in microseconds since the Unix epoch in UTC, which RFC 3339 strings are parsed
+    /// as.
+    Timestamp,
+}
+
+/// Compression codec applied to the pages of Parquet files.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetCompression {
+    /// No compression.
+    None,
+
+    /// [Snappy][snappy] compression.
+    ///
+    /// [snappy]: https://github.com/google/snappy
+    #[derivative(Default)]
+    Snappy,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+}
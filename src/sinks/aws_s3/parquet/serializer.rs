@@ -0,0 +1,224 @@
+//! Writing batches of events as Parquet files.
+
+use std::sync::Arc;
+
+use chrono::DateTime;
+use parquet::{
+    basic::{Compression, LogicalType, Repetition, TimeUnit, Type as PhysicalType},
+    data_type::{BoolType, ByteArray, ByteArrayType, DataType, DoubleType, Int64Type},
+    errors::ParquetError,
+    file::{
+        properties::WriterProperties,
+        writer::{SerializedColumnWriter, SerializedFileWriter},
+    },
+    format::MicroSeconds,
+    schema::types::Type,
+};
+
+use super::{ParquetColumnType, ParquetCompression, ParquetSerializerConfig};
+use crate::event::{LogEvent, Value};
+
+impl ParquetSerializerConfig {
+    pub fn build(&self) -> ParquetSerializer {
+        ParquetSerializer {
+            columns: self
+                .schema
+                .iter()
+                .map(|(name, column_type)| (name.clone(), *column_type))
+                .collect(),
+            compression: self.compression,
+        }
+    }
+}
+
+impl From<ParquetCompression> for Compression {
+    fn from(compression: ParquetCompression) -> Self {
+        match compression {
+            ParquetCompression::None => Self::UNCOMPRESSED,
+            ParquetCompression::Snappy => Self::SNAPPY,
+            ParquetCompression::Gzip => Self::GZIP,
+        }
+    }
+}
+
+/// Encodes batches of events as Parquet files, in a single row group.
+#[derive(Clone, Debug)]
+pub struct ParquetSerializer {
+    columns: Vec<(String, ParquetColumnType)>,
+    compression: ParquetCompression,
+}
+
+impl ParquetSerializer {
+    pub fn encode(&self, events: &[LogEvent]) -> Result<Vec<u8>, ParquetError> {
+        let mut fields = self
+            .columns
+            .iter()
+            .map(|(name, column_type)| parquet_type(name, *column_type).map(Arc::new))
+            .collect::<Result<Vec<_>, _>>()?;
+        let schema = Type::group_type_builder("events")
+            .with_fields(&mut fields)
+            .build()?;
+        let properties = WriterProperties::builder()
+            .set_compression(self.compression.into())
+            .build();
+
+        let mut buffer = Vec::new();
+        let mut writer =
+            SerializedFileWriter::new(&mut buffer, Arc::new(schema), Arc::new(properties))?;
+        let mut row_group = writer.next_row_group()?;
+        for (name, column_type) in &self.columns {
+            let mut column_writer = row_group
+                .next_column()?
+                .expect("columns should match the schema");
+            let values = events
+                .iter()
+                .map(|log| log.get(name.as_str()).filter(|value| !value.is_null()));
+            match column_type {
+                ParquetColumnType::Boolean => {
+                    write_column::<BoolType, _>(&mut column_writer, values, |value| match value {
+                        Value::Boolean(boolean) => Some(*boolean),
+                        _ => None,
+                    })
+                }
+                ParquetColumnType::Int64 => {
+                    write_column::<Int64Type, _>(&mut column_writer, values, |value| match value {
+                        Value::Integer(integer) => Some(*integer),
+                        _ => None,
+                    })
+                }
+                ParquetColumnType::Double => {
+                    write_column::<DoubleType, _>(&mut column_writer, values, |value| match value {
+                        Value::Float(float) => Some(float.into_inner()),
+                        Value::Integer(integer) => Some(*integer as f64),
+                        _ => None,
+                    })
+                }
+                ParquetColumnType::String => {
+                    write_column::<ByteArrayType, _>(&mut column_writer, values, |value| {
+                        Some(ByteArray::from(value.to_string_lossy().as_bytes().to_vec()))
+                    })
+                }
+                ParquetColumnType::Timestamp => {
+                    write_column::<Int64Type, _>(&mut column_writer, values, |value| match value {
+                        Value::Timestamp(timestamp) => Some(timestamp.timestamp_micros()),
+                        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+                            .ok()
+                            .and_then(|string| DateTime::parse_from_rfc3339(string).ok())
+                            .map(|timestamp| timestamp.timestamp_micros()),
+                        _ => None,
+                    })
+                }
+            }?;
+            column_writer.close()?;
+        }
+        row_group.close()?;
+        writer.close()?;
+
+        Ok(buffer)
+    }
+}
+
+fn parquet_type(name: &str, column_type: ParquetColumnType) -> Result<Type, ParquetError> {
+    let (physical_type, logical_type) = match column_type {
+        ParquetColumnType::Boolean => (PhysicalType::BOOLEAN, None),
+        ParquetColumnType::Int64 => (PhysicalType::INT64, None),
+        ParquetColumnType::Double => (PhysicalType::DOUBLE, None),
+        ParquetColumnType::String => (PhysicalType::BYTE_ARRAY, Some(LogicalType::String)),
+        ParquetColumnType::Timestamp => (
+            PhysicalType::INT64,
+            Some(LogicalType::Timestamp {
+                is_adjusted_to_u_t_c: true,
+                unit: TimeUnit::MICROS(MicroSeconds {}),
+            }),
+        ),
+    };
+
+    Type::primitive_type_builder(name, physical_type)
+        .with_repetition(Repetition::OPTIONAL)
+        .with_logical_type(logical_type)
+        .build()
+}
+
+fn write_column<'a, T: DataType, F>(
+    writer: &mut SerializedColumnWriter<'_>,
+    values: impl Iterator<Item = Option<&'a Value>>,
+    convert: F,
+) -> Result<(), ParquetError>
+where
+    F: Fn(&Value) -> Option<T::T>,
+{
+    let mut definition_levels = Vec::new();
+    let mut present = Vec::new();
+    for value in values {
+        match value.and_then(&convert) {
+            Some(value) => {
+                definition_levels.push(1);
+                present.push(value);
+            }
+            None => definition_levels.push(0),
+        }
+    }
+
+    writer
+        .typed::<T>()
+        .write_batch(&present, Some(&definition_levels), None)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use bytes::Bytes;
+    use chrono::{TimeZone, Utc};
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::RowAccessor,
+    };
+
+    use super::*;
+
+    #[test]
+    fn encodes_events() {
+        let serializer = ParquetSerializerConfig {
+            schema: BTreeMap::from([
+                ("timestamp".to_owned(), ParquetColumnType::Timestamp),
+                ("message".to_owned(), ParquetColumnType::String),
+                ("status".to_owned(), ParquetColumnType::Int64),
+                ("tags".to_owned(), ParquetColumnType::String),
+            ]),
+            compression: ParquetCompression::Snappy,
+        }
+        .build();
+
+        let mut first = LogEvent::default();
+        first.insert("timestamp", Utc.ymd(2023, 1, 10).and_hms(12, 30, 0));
+        first.insert("message", "hello");
+        first.insert("status", 200);
+        first.insert("tags", vec!["a", "b"]);
+        let mut second = LogEvent::default();
+        second.insert("timestamp", "2023-01-10T12:30:01Z");
+        second.insert("status", "OK");
+
+        let file = serializer.encode(&[first, second]).unwrap();
+
+        let reader = SerializedFileReader::new(Bytes::from(file)).unwrap();
+        let schema = reader.metadata().file_metadata().schema_descr();
+        let names: Vec<_> = schema
+            .columns()
+            .iter()
+            .map(|column| column.name())
+            .collect();
+        assert_eq!(names, vec!["message", "status", "tags", "timestamp"]);
+
+        let rows: Vec<_> = reader.get_row_iter(None).unwrap().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get_string(0).unwrap(), "hello");
+        assert_eq!(rows[0].get_long(1).unwrap(), 200);
+        assert_eq!(rows[0].get_string(2).unwrap(), r#"["a","b"]"#);
+        assert_eq!(rows[0].get_timestamp_micros(3).unwrap(), 1673353800000000);
+        assert!(rows[1].get_string(0).is_err());
+        assert!(rows[1].get_long(1).is_err());
+        assert_eq!(rows[1].get_timestamp_micros(3).unwrap(), 1673353801000000);
+    }
+}
@@ -7,6 +7,8 @@ use uuid::Uuid;
 use vector_common::request_metadata::RequestMetadata;
 use vector_core::event::Finalizable;
 
+#[cfg(feature = "codecs-parquet")]
+use super::parquet::ParquetSerializer;
 use crate::{
     codecs::{Encoder, Transformer},
     event::Event,
@@ -17,12 +19,46 @@ use crate::{
             service::{S3Metadata, S3Request},
         },
         util::{
-            metadata::RequestMetadataBuilder, request_builder::EncodeResult, Compression,
+            encoding, metadata::RequestMetadataBuilder, request_builder::EncodeResult, Compression,
             RequestBuilder,
         },
     },
 };
 
+/// Encodes batches of events into the bodies of objects.
+#[derive(Clone)]
+pub enum S3Encoder {
+    /// Events are encoded one after the other, with the codec and framing of `encoding`.
+    Framed((Transformer, Encoder<Framer>)),
+
+    /// Batches are encoded as Parquet files.
+    #[cfg(feature = "codecs-parquet")]
+    Parquet(Transformer, ParquetSerializer),
+}
+
+impl encoding::Encoder<Vec<Event>> for S3Encoder {
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        match self {
+            Self::Framed(encoder) => encoder.encode_input(events, writer),
+            #[cfg(feature = "codecs-parquet")]
+            Self::Parquet(transformer, serializer) => {
+                let logs: Vec<_> = events
+                    .into_iter()
+                    .map(|mut event| {
+                        transformer.transform(&mut event);
+                        event.into_log()
+                    })
+                    .collect();
+                let file = serializer
+                    .encode(&logs)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+                encoding::write_all(writer, logs.len(), &file)?;
+                Ok(file.len())
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct S3RequestOptions {
     pub bucket: String,
@@ -30,14 +66,14 @@ pub struct S3RequestOptions {
     pub filename_append_uuid: bool,
     pub filename_extension: Option<String>,
     pub api_options: S3Options,
-    pub encoder: (Transformer, Encoder<Framer>),
+    pub encoder: S3Encoder,
     pub compression: Compression,
 }
 
 impl RequestBuilder<(S3PartitionKey, Vec<Event>)> for S3RequestOptions {
     type Metadata = S3Metadata;
     type Events = Vec<Event>;
-    type Encoder = (Transformer, Encoder<Framer>);
+    type Encoder = S3Encoder;
     type Payload = Bytes;
     type Request = S3Request;
     type Error = io::Error; // TODO: this is ugly.
@@ -86,6 +122,7 @@ impl RequestBuilder<(S3PartitionKey, Vec<Event>)> for S3RequestOptions {
         let ssekms_key_id = s3metadata.partition_key.ssekms_key_id.clone();
         let mut s3_options = self.api_options.clone();
         s3_options.ssekms_key_id = ssekms_key_id;
+        s3_options.tags = s3metadata.partition_key.tags.clone();
 
         let extension = self
             .filename_extension
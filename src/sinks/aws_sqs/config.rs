@@ -25,10 +25,18 @@ pub(super) enum BuildError {
     MessageGroupIdMissing,
     #[snafu(display("`message_group_id` is not allowed with non-FIFO queue."))]
     MessageGroupIdNotAllowed,
-    #[snafu(display("invalid topic template: {}", source))]
-    TopicTemplate { source: TemplateParseError },
+    #[snafu(display("invalid message_group_id template: {}", source))]
+    MessageGroupIdTemplate { source: TemplateParseError },
+    #[snafu(display("`message_deduplication_id` is not allowed with non-FIFO queue."))]
+    MessageDeduplicationIdNotAllowed,
     #[snafu(display("invalid message_deduplication_id template: {}", source))]
     MessageDeduplicationIdTemplate { source: TemplateParseError },
+    #[snafu(display("`content_based_deduplication` is not allowed with non-FIFO queue."))]
+    ContentBasedDeduplicationNotAllowed,
+    #[snafu(display(
+        "`content_based_deduplication` can't be enabled along with `message_deduplication_id`."
+    ))]
+    ContentBasedDeduplicationConflict,
 }
 
 /// Configuration for the `aws_sqs` sink.
@@ -48,7 +56,11 @@ pub struct SqsSinkConfig {
 
     /// The tag that specifies that a message belongs to a specific message group.
     ///
+    /// This value is a template, and is required for FIFO queues, which keep the order of the
+    /// messages of each group. Events whose group ID fails to render are dropped.
+    ///
     /// Can be applied only to FIFO queues.
+    #[configurable(metadata(docs::examples = "{{ application }}"))]
     pub message_group_id: Option<String>,
 
     /// The message deduplication ID value to allow AWS to identify duplicate messages.
@@ -57,8 +69,21 @@ pub struct SqsSinkConfig {
     /// documentation][deduplication_id_docs] for more about how AWS does message deduplication.
     ///
     /// [deduplication_id_docs]: https://docs.aws.amazon.com/AWSSimpleQueueService/latest/SQSDeveloperGuide/using-messagededuplicationid-property.html
+    ///
+    /// Can be applied only to FIFO queues.
+    #[configurable(metadata(docs::examples = "{{ transaction_id }}"))]
     pub message_deduplication_id: Option<String>,
 
+    /// Whether to deduplicate messages by their body.
+    ///
+    /// When enabled, the deduplication ID of messages is the SHA-256 hash of their body, as with
+    /// the content-based deduplication of FIFO queues, so that queues without it, or whose
+    /// attributes can't be changed, deduplicate messages the same way.
+    ///
+    /// Can be applied only to FIFO queues, and not along with `message_deduplication_id`.
+    #[serde(default)]
+    pub content_based_deduplication: bool,
+
     #[configurable(derived)]
     #[serde(default)]
     pub request: TowerRequestConfig,
@@ -150,11 +175,14 @@ impl SqsSinkConfig {
         .await
     }
 
+    fn is_fifo(&self) -> bool {
+        self.queue_url.ends_with(".fifo")
+    }
+
     pub fn message_group_id(&self) -> crate::Result<Option<Template>> {
-        let fifo = self.queue_url.ends_with(".fifo");
-        match (self.message_group_id.as_ref(), fifo) {
+        match (self.message_group_id.as_ref(), self.is_fifo()) {
             (Some(value), true) => Ok(Some(
-                Template::try_from(value.clone()).context(TopicTemplateSnafu)?,
+                Template::try_from(value.clone()).context(MessageGroupIdTemplateSnafu)?,
             )),
             (Some(_), false) => Err(Box::new(BuildError::MessageGroupIdNotAllowed)),
             (None, true) => Err(Box::new(BuildError::MessageGroupIdMissing)),
@@ -163,10 +191,64 @@ impl SqsSinkConfig {
     }
 
     pub fn message_deduplication_id(&self) -> crate::Result<Option<Template>> {
-        Ok(self
-            .message_deduplication_id
-            .clone()
-            .map(Template::try_from)
-            .transpose()?)
+        if self.content_based_deduplication {
+            if !self.is_fifo() {
+                return Err(Box::new(BuildError::ContentBasedDeduplicationNotAllowed));
+            }
+            if self.message_deduplication_id.is_some() {
+                return Err(Box::new(BuildError::ContentBasedDeduplicationConflict));
+            }
+        }
+        match self.message_deduplication_id.as_ref() {
+            Some(_) if !self.is_fifo() => {
+                Err(Box::new(BuildError::MessageDeduplicationIdNotAllowed))
+            }
+            Some(value) => Ok(Some(
+                Template::try_from(value.clone()).context(MessageDeduplicationIdTemplateSnafu)?,
+            )),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(queue: &str, options: &str) -> SqsSinkConfig {
+        toml::from_str(&format!(
+            r#"queue_url = "https://sqs.us-east-2.amazonaws.com/123456789012/{}"
+            region = "us-east-2"
+            encoding.codec = "json"
+            {}"#,
+            queue, options
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn validates_fifo_options() {
+        let fifo = config(
+            "MyQueue.fifo",
+            r#"message_group_id = "{{ application }}"
+            content_based_deduplication = true"#,
+        );
+        assert!(fifo.message_group_id().unwrap().is_some());
+        assert!(fifo.message_deduplication_id().unwrap().is_none());
+
+        let standard = config("MyQueue", r#"message_deduplication_id = "{{ id }}""#);
+        assert!(standard.message_deduplication_id().is_err());
+
+        let standard = config("MyQueue", "content_based_deduplication = true");
+        assert!(standard.message_deduplication_id().is_err());
+
+        let conflicting = config(
+            "MyQueue.fifo",
+            r#"message_deduplication_id = "{{ id }}"
+            content_based_deduplication = true"#,
+        );
+        assert!(conflicting.message_deduplication_id().is_err());
+
+        assert!(config("MyQueue.fifo", "").message_group_id().is_err());
     }
 }
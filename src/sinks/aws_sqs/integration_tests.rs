@@ -55,6 +55,7 @@ async fn sqs_send_message_batch() {
         encoding: TextSerializerConfig::new().into(),
         message_group_id: None,
         message_deduplication_id: None,
+        content_based_deduplication: false,
         request: Default::default(),
         tls: Default::default(),
         assume_role: None,
@@ -1,19 +1,87 @@
 use bytes::Bytes;
+use sha2::{Digest, Sha256};
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_core::ByteSizeOf;
 
 use super::config::SqsSinkConfig;
 use crate::{
     codecs::{Encoder, Transformer},
-    event::{Event, EventFinalizers, Finalizable},
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
     internal_events::TemplateRenderingError,
     sinks::util::{
-        metadata::RequestMetadataBuilder, request_builder::EncodeResult, Compression,
-        EncodedLength, RequestBuilder,
+        metadata::RequestMetadataBuilder, processed_event::ProcessedEvent,
+        request_builder::EncodeResult, Compression, EncodedLength, RequestBuilder,
     },
     template::Template,
 };
 
+/// The message group and deduplication IDs rendered for an event.
+#[derive(Clone)]
+pub struct MessageIds {
+    pub message_group_id: Option<String>,
+    pub message_deduplication_id: Option<String>,
+}
+
+pub type SqsProcessedEvent = ProcessedEvent<Event, MessageIds>;
+
+/// The templates of the message group and deduplication IDs of messages.
+#[derive(Clone)]
+pub(crate) struct MessageIdTemplates {
+    message_group_id: Option<Template>,
+    message_deduplication_id: Option<Template>,
+}
+
+fn render(
+    template: &Option<Template>,
+    event: &Event,
+    field: &'static str,
+) -> Result<Option<String>, ()> {
+    template
+        .as_ref()
+        .map(|template| {
+            template.render_string(event).map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+        })
+        .transpose()
+}
+
+impl MessageIdTemplates {
+    pub fn new(config: &SqsSinkConfig) -> crate::Result<Self> {
+        Ok(Self {
+            message_group_id: config.message_group_id()?,
+            message_deduplication_id: config.message_deduplication_id()?,
+        })
+    }
+
+    fn render_ids(&self, event: &Event) -> Result<MessageIds, ()> {
+        Ok(MessageIds {
+            message_group_id: render(&self.message_group_id, event, "message_group_id")?,
+            message_deduplication_id: render(
+                &self.message_deduplication_id,
+                event,
+                "message_deduplication_id",
+            )?,
+        })
+    }
+
+    /// Renders the IDs of the message of `event`, or drops it when they fail to render, since
+    /// FIFO queues reject messages without a group ID, and would deduplicate them wrongly.
+    pub fn render(&self, mut event: Event) -> Option<SqsProcessedEvent> {
+        match self.render_ids(&event) {
+            Ok(metadata) => Some(SqsProcessedEvent { event, metadata }),
+            Err(()) => {
+                event.take_finalizers().update_status(EventStatus::Rejected);
+                None
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct SqsMetadata {
     pub finalizers: EventFinalizers,
@@ -24,8 +92,7 @@ pub struct SqsMetadata {
 #[derive(Clone)]
 pub(crate) struct SqsRequestBuilder {
     encoder: (Transformer, Encoder<()>),
-    message_group_id: Option<Template>,
-    message_deduplication_id: Option<Template>,
+    content_based_deduplication: bool,
     queue_url: String,
 }
 
@@ -37,14 +104,13 @@ impl SqsRequestBuilder {
 
         Ok(Self {
             encoder: (transformer, encoder),
-            message_group_id: config.message_group_id()?,
-            message_deduplication_id: config.message_deduplication_id()?,
+            content_based_deduplication: config.content_based_deduplication,
             queue_url: config.queue_url,
         })
     }
 }
 
-impl RequestBuilder<Event> for SqsRequestBuilder {
+impl RequestBuilder<SqsProcessedEvent> for SqsRequestBuilder {
     type Metadata = SqsMetadata;
     type Events = Event;
     type Encoder = (Transformer, Encoder<()>);
@@ -62,43 +128,18 @@ impl RequestBuilder<Event> for SqsRequestBuilder {
 
     fn split_input(
         &self,
-        mut event: Event,
+        processed_event: SqsProcessedEvent,
     ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
-        let message_group_id = match self.message_group_id {
-            Some(ref tpl) => match tpl.render_string(&event) {
-                Ok(value) => Some(value),
-                Err(error) => {
-                    emit!(TemplateRenderingError {
-                        error,
-                        field: Some("message_group_id"),
-                        drop_event: true,
-                    });
-                    None
-                }
-            },
-            None => None,
-        };
-        let message_deduplication_id = match self.message_deduplication_id {
-            Some(ref tpl) => match tpl.render_string(&event) {
-                Ok(value) => Some(value),
-                Err(error) => {
-                    emit!(TemplateRenderingError {
-                        error,
-                        field: Some("message_deduplication_id"),
-                        drop_event: true,
-                    });
-                    None
-                }
-            },
-            None => None,
-        };
-
+        let SqsProcessedEvent {
+            mut event,
+            metadata: ids,
+        } = processed_event;
         let builder = RequestMetadataBuilder::from_events(&event);
 
         let sqs_metadata = SqsMetadata {
             finalizers: event.take_finalizers(),
-            message_group_id,
-            message_deduplication_id,
+            message_group_id: ids.message_group_id,
+            message_deduplication_id: ids.message_deduplication_id,
         };
         (sqs_metadata, builder, event)
     }
@@ -111,11 +152,17 @@ impl RequestBuilder<Event> for SqsRequestBuilder {
     ) -> Self::Request {
         let payload_bytes = payload.into_payload();
         let message_body = String::from(std::str::from_utf8(&payload_bytes).unwrap());
+        // Like the content-based deduplication of FIFO queues, but for queues without it.
+        let message_deduplication_id = if self.content_based_deduplication {
+            Some(hex::encode(Sha256::digest(message_body.as_bytes())))
+        } else {
+            sqs_metadata.message_deduplication_id
+        };
 
         SendMessageEntry {
             message_body,
             message_group_id: sqs_metadata.message_group_id,
-            message_deduplication_id: sqs_metadata.message_deduplication_id,
+            message_deduplication_id,
             queue_url: self.queue_url.clone(),
             finalizers: sqs_metadata.finalizers,
             metadata,
@@ -155,6 +202,6 @@ impl Finalizable for SendMessageEntry {
 
 impl MetaDescriptive for SendMessageEntry {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
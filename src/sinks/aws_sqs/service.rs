@@ -4,9 +4,7 @@ use aws_sdk_sqs::{error::SendMessageError, types::SdkError, Client as SqsClient}
 use futures::{future::BoxFuture, TryFutureExt};
 use tower::Service;
 use tracing::Instrument;
-use vector_core::{
-    event::EventStatus, internal_event::CountByteSize, stream::DriverResponse, ByteSizeOf,
-};
+use vector_core::{event::EventStatus, stream::DriverResponse};
 
 use super::request_builder::SendMessageEntry;
 
@@ -33,7 +31,6 @@ impl Service<SendMessageEntry> for SqsService {
 
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, entry: SendMessageEntry) -> Self::Future {
-        let byte_size = entry.size_of();
         let client = self.client.clone();
 
         Box::pin(async move {
@@ -44,23 +41,17 @@ impl Service<SendMessageEntry> for SqsService {
                 .set_message_deduplication_id(entry.message_deduplication_id)
                 .queue_url(entry.queue_url)
                 .send()
-                .map_ok(|_| SendMessageResponse { byte_size })
+                .map_ok(|_| SendMessageResponse)
                 .instrument(info_span!("request").or_current())
                 .await
         })
     }
 }
 
-pub(crate) struct SendMessageResponse {
-    byte_size: usize,
-}
+pub(crate) struct SendMessageResponse;
 
 impl DriverResponse for SendMessageResponse {
     fn event_status(&self) -> EventStatus {
         EventStatus::Delivered
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(1, self.byte_size)
-    }
 }
@@ -1,11 +1,15 @@
 use std::num::NonZeroUsize;
 
 use aws_sdk_sqs::Client as SqsClient;
-use futures::stream::BoxStream;
+use futures::{future, stream::BoxStream};
 use futures_util::StreamExt;
 use vector_core::sink::StreamSink;
 
-use super::{config::SqsSinkConfig, request_builder::SqsRequestBuilder, service::SqsService};
+use super::{
+    config::SqsSinkConfig,
+    request_builder::{MessageIdTemplates, SqsRequestBuilder},
+    service::SqsService,
+};
 use crate::internal_events::SinkRequestBuildError;
 use crate::{
     event::Event,
@@ -25,6 +29,7 @@ impl SinkBatchSettings for SqsSinkDefaultBatchSettings {
 
 #[derive(Clone)]
 pub(crate) struct SqsSink {
+    message_ids: MessageIdTemplates,
     request_builder: SqsRequestBuilder,
     service: SqsService,
     request: TowerRequestConfig,
@@ -34,6 +39,7 @@ impl SqsSink {
     pub fn new(config: SqsSinkConfig, client: SqsClient) -> crate::Result<Self> {
         let request = config.request;
         Ok(SqsSink {
+            message_ids: MessageIdTemplates::new(&config)?,
             request_builder: SqsRequestBuilder::new(config)?,
             service: SqsService::new(client),
             request,
@@ -50,7 +56,9 @@ impl SqsSink {
             .settings(request, super::retry::SqsRetryLogic)
             .service(self.service);
 
+        let message_ids = self.message_ids;
         input
+            .filter_map(|event| future::ready(message_ids.render(event)))
             .request_builder(request_builder_concurrency_limit, self.request_builder)
             .filter_map(|req| async move {
                 req.map_err(|error| {
@@ -1,4 +1,4 @@
-use std::{convert::TryInto, sync::Arc};
+use std::{convert::TryInto, sync::Arc, time::Duration};
 
 use azure_storage_blobs::prelude::*;
 use codecs::{encoding::Framer, JsonSerializerConfig, NewlineDelimitedEncoderConfig};
@@ -6,7 +6,7 @@ use tower::ServiceBuilder;
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
-use super::request_builder::AzureBlobRequestOptions;
+use super::request_builder::{AppendBlobs, AzureBlobRequestOptions};
 use crate::{
     codecs::{Encoder, EncodingConfigWithFraming, SinkType},
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
@@ -89,6 +89,14 @@ pub struct AzureBlobSinkConfig {
     /// blob keys must be unique.
     pub blob_append_uuid: Option<bool>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub blob_type: AzureBlobType,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub append: AzureAppendBlobConfig,
+
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
@@ -113,6 +121,50 @@ pub struct AzureBlobSinkConfig {
     pub(super) acknowledgements: AcknowledgementsConfig,
 }
 
+/// The type of the blobs batches are written to.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AzureBlobType {
+    /// Each batch is written to a new block blob.
+    #[default]
+    Block,
+
+    /// Batches are appended to append blobs, which are rolled over to new blobs according to the
+    /// `append` options.
+    ///
+    /// The blob prefix of a batch selects the append blob it is appended to, so events of
+    /// different blob prefixes are appended to different blobs.
+    Append,
+}
+
+/// Rollover options for append blobs.
+#[configurable_component]
+#[derive(Clone, Copy, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct AzureAppendBlobConfig {
+    /// The maximum size of each append blob, in bytes.
+    ///
+    /// Batches that would make an append blob exceed this size are appended to a new blob.
+    /// Append blobs can hold at most 50,000 blocks of up to 4 MiB, which also makes batches roll
+    /// over to a new blob.
+    pub max_bytes: usize,
+
+    /// The maximum age of each append blob, in seconds.
+    ///
+    /// Batches sent once an append blob reached this age are appended to a new blob.
+    pub max_age_secs: u64,
+}
+
+impl Default for AzureAppendBlobConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: 256 * 1024 * 1024,
+            max_age_secs: 3600,
+        }
+    }
+}
+
 impl GenerateConfig for AzureBlobSinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
@@ -122,6 +174,8 @@ impl GenerateConfig for AzureBlobSinkConfig {
             blob_prefix: Some(String::from("blob")),
             blob_time_format: Some(String::from("%s")),
             blob_append_uuid: Some(true),
+            blob_type: AzureBlobType::Block,
+            append: AzureAppendBlobConfig::default(),
             encoding: (Some(NewlineDelimitedEncoderConfig::new()), JsonSerializerConfig::new()).into(),
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
@@ -196,6 +250,7 @@ impl AzureBlobSinkConfig {
             blob_append_uuid,
             encoder: (transformer, encoder),
             compression: self.compression,
+            append_blobs: self.append_blobs(),
         };
 
         let sink = AzureBlobSink::new(
@@ -208,6 +263,16 @@ impl AzureBlobSinkConfig {
         Ok(VectorSink::from_event_streamsink(sink))
     }
 
+    fn append_blobs(&self) -> Option<AppendBlobs> {
+        match self.blob_type {
+            AzureBlobType::Block => None,
+            AzureBlobType::Append => Some(AppendBlobs::new(
+                self.append.max_bytes,
+                Duration::from_secs(self.append.max_age_secs),
+            )),
+        }
+    }
+
     pub fn key_partitioner(&self) -> crate::Result<KeyPartitioner> {
         let blob_prefix = self
             .blob_prefix
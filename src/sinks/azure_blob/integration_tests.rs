@@ -223,6 +223,8 @@ impl AzureBlobSinkConfig {
                 blob_prefix: None,
                 blob_time_format: None,
                 blob_append_uuid: None,
+                blob_type: Default::default(),
+                append: Default::default(),
                 encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
                 compression: Compression::None,
                 batch: Default::default(),
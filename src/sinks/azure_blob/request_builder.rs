@@ -1,3 +1,9 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
 use bytes::Bytes;
 use chrono::Utc;
 use codecs::encoding::Framer;
@@ -9,7 +15,10 @@ use crate::{
     codecs::{Encoder, Transformer},
     event::{Event, Finalizable},
     sinks::{
-        azure_common::config::{AzureBlobMetadata, AzureBlobRequest},
+        azure_common::{
+            config::{AzureBlobMetadata, AzureBlobRequest, AzureBlobWrite},
+            service::MAX_APPEND_BLOCK_BYTES,
+        },
         util::{
             metadata::RequestMetadataBuilder, request_builder::EncodeResult, Compression,
             RequestBuilder,
@@ -17,6 +26,9 @@ use crate::{
     },
 };
 
+/// The maximum number of blocks of an append blob.
+const MAX_APPEND_BLOB_BLOCKS: usize = 50_000;
+
 #[derive(Clone)]
 pub struct AzureBlobRequestOptions {
     pub container_name: String,
@@ -24,6 +36,72 @@ pub struct AzureBlobRequestOptions {
     pub blob_append_uuid: bool,
     pub encoder: (Transformer, Encoder<Framer>),
     pub compression: Compression,
+    pub append_blobs: Option<AppendBlobs>,
+}
+
+/// The append blob batches of a partition are appended to.
+struct AppendBlob {
+    name: String,
+    bytes: usize,
+    blocks: usize,
+    created_at: Instant,
+}
+
+/// The append blobs of partitions, which are rolled over to new blobs once they reach `max_bytes`
+/// or `max_age`.
+#[derive(Clone)]
+pub struct AppendBlobs {
+    max_bytes: usize,
+    max_age: Duration,
+    blobs: Arc<Mutex<HashMap<String, AppendBlob>>>,
+}
+
+impl AppendBlobs {
+    pub fn new(max_bytes: usize, max_age: Duration) -> Self {
+        Self {
+            max_bytes,
+            max_age,
+            blobs: Default::default(),
+        }
+    }
+
+    /// The name of the blob the `len` bytes of a batch of the partition `prefix` are appended to,
+    /// and whether the blob has to be created, in which case it is named by `new_name`.
+    fn append(
+        &self,
+        prefix: &str,
+        len: usize,
+        new_name: impl FnOnce() -> String,
+    ) -> (String, bool) {
+        let blocks = (len + MAX_APPEND_BLOCK_BYTES - 1) / MAX_APPEND_BLOCK_BYTES;
+        let mut blobs = self.blobs.lock().expect("append blobs mutex poisoned");
+        // Blobs of partitions that stopped receiving events are only forgotten once they expire.
+        blobs.retain(|_, blob| blob.created_at.elapsed() < self.max_age);
+
+        match blobs.get_mut(prefix) {
+            Some(blob)
+                if blob.bytes + len <= self.max_bytes
+                    && blob.blocks + blocks <= MAX_APPEND_BLOB_BLOCKS =>
+            {
+                blob.bytes += len;
+                blob.blocks += blocks;
+                (blob.name.clone(), false)
+            }
+            _ => {
+                let name = new_name();
+                blobs.insert(
+                    prefix.to_owned(),
+                    AppendBlob {
+                        name: name.clone(),
+                        bytes: len,
+                        blocks,
+                        created_at: Instant::now(),
+                    },
+                );
+                (name, true)
+            }
+        }
+    }
 }
 
 impl RequestBuilder<(String, Vec<Event>)> for AzureBlobRequestOptions {
@@ -74,14 +152,20 @@ impl RequestBuilder<(String, Vec<Event>)> for AzureBlobRequestOptions {
                 .unwrap_or_else(|| formatted_ts.to_string())
         };
 
-        let extension = self.compression.extension();
-        azure_metadata.partition_key = format!(
-            "{}{}.{}",
-            azure_metadata.partition_key, blob_name, extension
-        );
-
         let blob_data = payload.into_payload();
 
+        let extension = self.compression.extension();
+        let prefix = azure_metadata.partition_key;
+        let new_name = || format!("{}{}.{}", prefix, blob_name, extension);
+        let (name, write) = match &self.append_blobs {
+            None => (new_name(), AzureBlobWrite::Block),
+            Some(append_blobs) => {
+                let (name, create) = append_blobs.append(&prefix, blob_data.len(), new_name);
+                (name, AzureBlobWrite::Append { create })
+            }
+        };
+        azure_metadata.partition_key = name;
+
         debug!(
             message = "Sending events.",
             bytes = ?blob_data.len(),
@@ -96,6 +180,7 @@ impl RequestBuilder<(String, Vec<Event>)> for AzureBlobRequestOptions {
             content_type: self.compression.content_type(),
             metadata: azure_metadata,
             request_metadata,
+            write,
         }
     }
 }
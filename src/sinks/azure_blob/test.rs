@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use bytes::Bytes;
 use chrono::Utc;
 use codecs::{
@@ -7,9 +9,10 @@ use codecs::{
 use vector_core::partition::Partitioner;
 
 use super::config::AzureBlobSinkConfig;
-use super::request_builder::AzureBlobRequestOptions;
+use super::request_builder::{AppendBlobs, AzureBlobRequestOptions};
 use crate::codecs::EncodingConfigWithFraming;
 use crate::event::{Event, LogEvent};
+use crate::sinks::azure_common::config::AzureBlobWrite;
 use crate::sinks::util::{request_builder::RequestBuilder, Compression};
 use crate::{codecs::Encoder, sinks::util::request_builder::EncodeResult};
 
@@ -21,6 +24,8 @@ fn default_config(encoding: EncodingConfigWithFraming) -> AzureBlobSinkConfig {
         blob_prefix: Default::default(),
         blob_time_format: Default::default(),
         blob_append_uuid: Default::default(),
+        blob_type: Default::default(),
+        append: Default::default(),
         encoding,
         compression: Compression::gzip_default(),
         batch: Default::default(),
@@ -65,6 +70,7 @@ fn azure_blob_build_request_without_compression() {
             ),
         ),
         compression,
+        append_blobs: None,
     };
 
     let (metadata, request_metadata_builder, _events) =
@@ -110,6 +116,7 @@ fn azure_blob_build_request_with_compression() {
             ),
         ),
         compression,
+        append_blobs: None,
     };
     let (metadata, request_metadata_builder, _events) =
         request_options.split_input((key, vec![log]));
@@ -154,6 +161,7 @@ fn azure_blob_build_request_with_time_format() {
             ),
         ),
         compression,
+        append_blobs: None,
     };
 
     let (metadata, request_metadata_builder, _events) =
@@ -202,6 +210,7 @@ fn azure_blob_build_request_with_uuid() {
             ),
         ),
         compression,
+        append_blobs: None,
     };
 
     let (metadata, request_metadata_builder, _events) =
@@ -215,3 +224,52 @@ fn azure_blob_build_request_with_uuid() {
     assert_eq!(request.content_encoding, None);
     assert_eq!(request.content_type, "text/plain");
 }
+
+#[test]
+fn azure_blob_build_request_with_append_blobs() {
+    let sink_config = AzureBlobSinkConfig {
+        blob_prefix: Some("blob".into()),
+        ..default_config((None::<FramingConfig>, TextSerializerConfig::new()).into())
+    };
+    let request_options = AzureBlobRequestOptions {
+        container_name: String::from("logs"),
+        blob_time_format: String::from(""),
+        blob_append_uuid: true,
+        encoder: (
+            Default::default(),
+            Encoder::<Framer>::new(
+                NewlineDelimitedEncoder::new().into(),
+                TextSerializer::new().into(),
+            ),
+        ),
+        compression: Compression::None,
+        append_blobs: Some(AppendBlobs::new(10, Duration::from_secs(3600))),
+    };
+
+    let build_request = |payload: &'static [u8]| {
+        let log = Event::Log(LogEvent::from("test message"));
+        let key = sink_config
+            .key_partitioner()
+            .unwrap()
+            .partition(&log)
+            .expect("key wasn't provided");
+        let (metadata, request_metadata_builder, _events) =
+            request_options.split_input((key, vec![log]));
+
+        let payload = EncodeResult::uncompressed(Bytes::from_static(payload));
+        let request_metadata = request_metadata_builder.build(&payload);
+        request_options.build_request(metadata, request_metadata, payload)
+    };
+
+    let first = build_request(b"12345");
+    assert_eq!(first.write, AzureBlobWrite::Append { create: true });
+    assert!(first.metadata.partition_key.starts_with("blob"));
+
+    let second = build_request(b"12345");
+    assert_eq!(second.write, AzureBlobWrite::Append { create: false });
+    assert_eq!(second.metadata.partition_key, first.metadata.partition_key);
+
+    let third = build_request(b"1");
+    assert_eq!(third.write, AzureBlobWrite::Append { create: true });
+    assert_ne!(third.metadata.partition_key, first.metadata.partition_key);
+}
@@ -3,13 +3,13 @@ use std::sync::Arc;
 use azure_core::{error::HttpError, RetryOptions};
 use azure_identity::{AutoRefreshingTokenCredential, DefaultAzureCredential};
 use azure_storage::{prelude::*, CloudLocation, ConnectionString};
-use azure_storage_blobs::{blob::operations::PutBlockBlobResponse, prelude::*};
+use azure_storage_blobs::prelude::*;
 use bytes::Bytes;
 use futures::FutureExt;
 use http::StatusCode;
 use snafu::Snafu;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
-use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
+use vector_core::stream::DriverResponse;
 
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
@@ -23,6 +23,16 @@ pub struct AzureBlobRequest {
     pub content_type: &'static str,
     pub metadata: AzureBlobMetadata,
     pub request_metadata: RequestMetadata,
+    pub write: AzureBlobWrite,
+}
+
+/// How the data of a request is written to its blob.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AzureBlobWrite {
+    /// The data is written as a new block blob.
+    Block,
+    /// The data is appended to an append blob, which is created first if `create` is set.
+    Append { create: bool },
 }
 
 impl Finalizable for AzureBlobRequest {
@@ -33,7 +43,7 @@ impl Finalizable for AzureBlobRequest {
 
 impl MetaDescriptive for AzureBlobRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.request_metadata
+        self.request_metadata.clone()
     }
 }
 
@@ -59,25 +69,12 @@ impl RetryLogic for AzureBlobRetryLogic {
 }
 
 #[derive(Debug)]
-pub struct AzureBlobResponse {
-    pub inner: PutBlockBlobResponse,
-    pub count: usize,
-    pub events_byte_size: usize,
-    pub byte_size: usize,
-}
+pub struct AzureBlobResponse;
 
 impl DriverResponse for AzureBlobResponse {
     fn event_status(&self) -> EventStatus {
         EventStatus::Delivered
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(self.count, self.events_byte_size)
-    }
-
-    fn bytes_sent(&self) -> Option<usize> {
-        Some(self.byte_size)
-    }
 }
 
 #[derive(Debug, Snafu)]
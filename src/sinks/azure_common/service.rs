@@ -1,24 +1,52 @@
 use std::{
+    collections::HashMap,
     result::Result as StdResult,
-    sync::Arc,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
 };
 
 use azure_storage_blobs::prelude::*;
-use futures::future::BoxFuture;
+use futures::{
+    channel::oneshot,
+    future::{BoxFuture, Shared},
+    FutureExt,
+};
 use tower::Service;
 use tracing::Instrument;
 
-use crate::sinks::azure_common::config::{AzureBlobRequest, AzureBlobResponse};
+use crate::sinks::azure_common::config::{AzureBlobRequest, AzureBlobResponse, AzureBlobWrite};
+
+/// The maximum size of the blocks appended to append blobs.
+pub(crate) const MAX_APPEND_BLOCK_BYTES: usize = 4 * 1024 * 1024;
 
 #[derive(Clone)]
 pub(crate) struct AzureBlobService {
     client: Arc<ContainerClient>,
+    /// The completion of the last request to each append blob, which the next request to the same
+    /// blob waits for, so that blobs are created before being appended to and batches are
+    /// appended in order.
+    appends: Arc<Mutex<HashMap<String, Shared<oneshot::Receiver<()>>>>>,
 }
 
 impl AzureBlobService {
     pub fn new(client: Arc<ContainerClient>) -> AzureBlobService {
-        AzureBlobService { client }
+        AzureBlobService {
+            client,
+            appends: Default::default(),
+        }
+    }
+
+    /// Queues a request to the append blob `name`, returning the completion of the request it has
+    /// to wait for, and the sender completing this one when dropped.
+    fn queue_append(
+        &self,
+        name: &str,
+    ) -> (Option<Shared<oneshot::Receiver<()>>>, oneshot::Sender<()>) {
+        let (done, completion) = oneshot::channel();
+        let mut appends = self.appends.lock().expect("appends mutex poisoned");
+        appends.retain(|_, completion| completion.clone().now_or_never().is_none());
+        let previous = appends.insert(name.to_owned(), completion.shared());
+        (previous, done)
     }
 }
 
@@ -35,32 +63,83 @@ impl Service<AzureBlobRequest> for AzureBlobService {
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, request: AzureBlobRequest) -> Self::Future {
         let this = self.clone();
+        let queued = match request.write {
+            AzureBlobWrite::Block => None,
+            AzureBlobWrite::Append { .. } => {
+                Some(self.queue_append(&request.metadata.partition_key))
+            }
+        };
 
         Box::pin(async move {
             let client = this
                 .client
                 .blob_client(request.metadata.partition_key.as_str());
-            let byte_size = request.blob_data.len();
-            let blob = client
-                .put_block_blob(request.blob_data)
-                .content_type(request.content_type);
-            let blob = match request.content_encoding {
-                Some(encoding) => blob.content_encoding(encoding),
-                None => blob,
+
+            let result = match request.write {
+                AzureBlobWrite::Block => {
+                    let blob = client
+                        .put_block_blob(request.blob_data)
+                        .content_type(request.content_type);
+                    let blob = match request.content_encoding {
+                        Some(encoding) => blob.content_encoding(encoding),
+                        None => blob,
+                    };
+
+                    blob.into_future()
+                        .instrument(info_span!("request").or_current())
+                        .await
+                        .map(|_| ())
+                }
+                AzureBlobWrite::Append { create } => {
+                    let (previous, _done) = queued.expect("appends should be queued");
+                    if let Some(previous) = previous {
+                        // Only the order of requests matters, not whether the previous one failed.
+                        let _ = previous.await;
+                    }
+
+                    append(
+                        &client,
+                        create,
+                        request.blob_data,
+                        request.content_type,
+                        request.content_encoding,
+                    )
+                    .instrument(info_span!("request").or_current())
+                    .await
+                }
             };
 
-            let result = blob
-                .into_future()
-                .instrument(info_span!("request").or_current())
-                .await
-                .map_err(|err| err.into());
-
-            result.map(|inner| AzureBlobResponse {
-                inner,
-                count: request.metadata.count,
-                events_byte_size: request.metadata.byte_size,
-                byte_size,
-            })
+            result.map_err(|err| err.into()).map(|()| AzureBlobResponse)
         })
     }
 }
+
+/// Appends `data` to the append blob of `client`, in blocks of at most `MAX_APPEND_BLOCK_BYTES`,
+/// creating the blob first if `create` is set.
+async fn append(
+    client: &BlobClient,
+    create: bool,
+    data: bytes::Bytes,
+    content_type: &'static str,
+    content_encoding: Option<&'static str>,
+) -> azure_core::Result<()> {
+    if create {
+        let blob = client.put_append_blob().content_type(content_type);
+        let blob = match content_encoding {
+            Some(encoding) => blob.content_encoding(encoding),
+            None => blob,
+        };
+        blob.into_future().await?;
+    }
+
+    let mut start = 0;
+    while start < data.len() {
+        let end = data.len().min(start + MAX_APPEND_BLOCK_BYTES);
+        client
+            .append_block(data.slice(start..end))
+            .into_future()
+            .await?;
+        start = end;
+    }
+    Ok(())
+}
@@ -0,0 +1,139 @@
+//! Authenticated requests to the endpoints of Azure Data Explorer clusters.
+
+use std::sync::Arc;
+
+use azure_core::auth::TokenCredential;
+use bytes::Bytes;
+use http::{header, Request, StatusCode};
+use hyper::Body;
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+use snafu::{ResultExt, Snafu};
+
+use crate::http::{HttpClient, HttpError};
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(super)))]
+pub enum AzureDataExplorerError {
+    #[snafu(display("Failed to build the request: {}", source))]
+    BuildRequest { source: http::Error },
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("Failed to read the response: {}", source))]
+    ReadResponse { source: hyper::Error },
+    #[snafu(display("Failed to get a token from Azure Active Directory: {}", source))]
+    Token { source: azure_core::Error },
+    #[snafu(display("Unexpected status {}: {}", status, body))]
+    UnexpectedStatus { status: StatusCode, body: String },
+    #[snafu(display("Invalid response: {}", source))]
+    InvalidResponse { source: serde_json::Error },
+    #[snafu(display(
+        "The ingestion resources of the cluster don't include any {}",
+        resource
+    ))]
+    MissingResource { resource: &'static str },
+}
+
+impl AzureDataExplorerError {
+    pub fn is_retriable(&self) -> bool {
+        match self {
+            Self::Request { .. } | Self::ReadResponse { .. } | Self::Token { .. } => true,
+            Self::UnexpectedStatus { status, .. } => {
+                status.is_server_error()
+                    || *status == StatusCode::TOO_MANY_REQUESTS
+                    || *status == StatusCode::REQUEST_TIMEOUT
+            }
+            Self::BuildRequest { .. }
+            | Self::InvalidResponse { .. }
+            | Self::MissingResource { .. } => false,
+        }
+    }
+}
+
+pub fn unexpected_status(status: StatusCode, body: &[u8]) -> AzureDataExplorerError {
+    AzureDataExplorerError::UnexpectedStatus {
+        status,
+        body: String::from_utf8_lossy(body).into_owned(),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ManagementResponse {
+    tables: Vec<ResultTable>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ResultTable {
+    rows: Vec<Vec<JsonValue>>,
+}
+
+pub struct KustoClient {
+    pub http: HttpClient,
+    pub credential: Arc<dyn TokenCredential>,
+    /// The resource tokens are requested for, which is the URI of the cluster.
+    pub resource: String,
+}
+
+impl KustoClient {
+    pub async fn send(
+        &self,
+        request: Request<Body>,
+    ) -> Result<(StatusCode, Bytes), AzureDataExplorerError> {
+        let response = self.http.send(request).await.context(RequestSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadResponseSnafu)?;
+        Ok((status, body))
+    }
+
+    /// Sends `request` authenticated with a token of the cluster.
+    pub async fn send_authenticated(
+        &self,
+        mut request: Request<Body>,
+    ) -> Result<(StatusCode, Bytes), AzureDataExplorerError> {
+        let token = self
+            .credential
+            .get_token(&self.resource)
+            .await
+            .context(TokenSnafu)?;
+        request.headers_mut().insert(
+            header::AUTHORIZATION,
+            format!("Bearer {}", token.token.secret())
+                .parse()
+                .expect("tokens should be valid header values"),
+        );
+        self.send(request).await
+    }
+
+    /// Runs a management command against the `database` of the service at `endpoint`, returning
+    /// the rows of its primary result.
+    pub async fn management(
+        &self,
+        endpoint: &str,
+        database: &str,
+        command: &str,
+    ) -> Result<Vec<Vec<JsonValue>>, AzureDataExplorerError> {
+        let body = json!({ "db": database, "csl": command });
+        let request = Request::post(format!("{}/v1/rest/mgmt", endpoint))
+            .header(header::ACCEPT, "application/json")
+            .header(header::CONTENT_TYPE, "application/json; charset=utf-8")
+            .body(Body::from(body.to_string()))
+            .context(BuildRequestSnafu)?;
+
+        let (status, body) = self.send_authenticated(request).await?;
+        if status != StatusCode::OK {
+            return Err(unexpected_status(status, &body));
+        }
+        let response: ManagementResponse =
+            serde_json::from_slice(&body).context(InvalidResponseSnafu)?;
+        Ok(response
+            .tables
+            .into_iter()
+            .next()
+            .map(|table| table.rows)
+            .unwrap_or_default())
+    }
+}
@@ -0,0 +1,350 @@
+use std::sync::Arc;
+
+use azure_core::auth::TokenCredential;
+use azure_identity::{
+    AutoRefreshingTokenCredential, ClientSecretCredential, DefaultAzureCredential,
+    TokenCredentialOptions,
+};
+use futures::FutureExt;
+use http::uri::{InvalidUri, Uri};
+use indoc::indoc;
+use snafu::{ResultExt, Snafu};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use super::{
+    client::KustoClient,
+    ingest::QueuedIngestor,
+    service::{Ingestor, StreamingIngestor},
+    sink::AzureDataExplorerSink,
+};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::HttpClient,
+    sinks::{
+        util::{BatchConfig, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// The maximum size of the data of streaming ingestion requests, before compression.
+const MAX_STREAMING_BYTES: usize = 4_000_000;
+
+#[derive(Debug, Snafu)]
+enum AzureDataExplorerConfigError {
+    #[snafu(display("Invalid endpoint URI: {}", source))]
+    Uri { source: InvalidUri },
+    #[snafu(display("The endpoint URI has no host"))]
+    MissingHost,
+}
+
+/// How batches are ingested into the table.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestionMode {
+    /// Batches are uploaded to the storage of the cluster and queued for ingestion.
+    ///
+    /// This is the most reliable and efficient way of ingesting large volumes of events, but the
+    /// cluster aggregates queued batches before ingesting them, so events become available after
+    /// a delay set by the ingestion batching policy of the table.
+    #[default]
+    Queued,
+
+    /// Batches are ingested directly by the engine of the cluster.
+    ///
+    /// Events are available within seconds, but streaming ingestion must be enabled on the
+    /// cluster and the table. Batches are limited to 4MB of uncompressed data, and are flushed
+    /// every second unless `batch.timeout_secs` is set.
+    Streaming,
+}
+
+/// Azure Active Directory authentication strategies.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+pub enum AzureDataExplorerAuth {
+    /// Credentials are read from the environment, such as those of managed identities or of the
+    /// Azure CLI.
+    #[default]
+    Default,
+
+    /// The client secret of an application registered in Azure Active Directory.
+    ClientSecret {
+        /// The ID of the Azure Active Directory tenant of the application.
+        #[configurable(metadata(docs::examples = "00000000-0000-0000-0000-000000000000"))]
+        tenant_id: String,
+
+        /// The ID of the application.
+        #[configurable(metadata(docs::examples = "00000000-0000-0000-0000-000000000000"))]
+        client_id: String,
+
+        /// The client secret of the application.
+        #[configurable(metadata(docs::examples = "${AZURE_CLIENT_SECRET}"))]
+        client_secret: SensitiveString,
+    },
+}
+
+/// Configuration for the `azure_data_explorer` sink.
+#[configurable_component(sink("azure_data_explorer"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AzureDataExplorerConfig {
+    /// The URI of the cluster.
+    #[configurable(metadata(docs::examples = "https://mycluster.westeurope.kusto.windows.net"))]
+    pub endpoint: String,
+
+    /// The URI of the data management service of the cluster, which queued ingestion goes
+    /// through.
+    ///
+    /// By default, this is the URI of the cluster with its host prefixed with `ingest-`.
+    #[configurable(metadata(
+        docs::examples = "https://ingest-mycluster.westeurope.kusto.windows.net"
+    ))]
+    pub ingestion_endpoint: Option<String>,
+
+    /// The database of the table.
+    #[configurable(metadata(docs::examples = "logs"))]
+    pub database: String,
+
+    /// The table to ingest events into.
+    ///
+    /// The table must already exist. Events are ingested as JSON, so without a `mapping`, their
+    /// top-level fields are ingested into the columns of the same names.
+    #[configurable(metadata(docs::examples = "events"))]
+    pub table: String,
+
+    /// The name of the JSON ingestion mapping of the table that events are ingested with.
+    #[configurable(metadata(docs::examples = "events_mapping"))]
+    pub mapping: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub ingestion_mode: IngestionMode,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AzureDataExplorerAuth,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<AzureDataExplorerDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// Queued batches are aggregated by the cluster before being ingested, which is most efficient
+/// with batches of 100MB to 1GB of uncompressed data, so batches are large and infrequent by
+/// default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AzureDataExplorerDefaultBatchSettings;
+
+impl SinkBatchSettings for AzureDataExplorerDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(100_000_000);
+    const TIMEOUT_SECS: f64 = 300.0;
+}
+
+impl GenerateConfig for AzureDataExplorerConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            endpoint = "https://mycluster.westeurope.kusto.windows.net"
+            database = "logs"
+            table = "events"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for AzureDataExplorerConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let endpoint = self.endpoint.trim_end_matches('/').to_owned();
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = Arc::new(KustoClient {
+            http: HttpClient::new(tls, &cx.proxy)?,
+            credential: self.credential(),
+            resource: endpoint.clone(),
+        });
+
+        let mut batch = self.batch;
+        let ingestor = match self.ingestion_mode {
+            IngestionMode::Queued => Ingestor::Queued(QueuedIngestor::new(
+                Arc::clone(&client),
+                self.ingestion_endpoint()?,
+                self.database.clone(),
+                self.table.clone(),
+                self.mapping.clone(),
+            )),
+            IngestionMode::Streaming => {
+                batch.max_bytes = batch.max_bytes.or(Some(MAX_STREAMING_BYTES));
+                batch.timeout_secs = batch.timeout_secs.or(Some(1.0));
+                Ingestor::Streaming(StreamingIngestor {
+                    client: Arc::clone(&client),
+                    endpoint: endpoint.clone(),
+                    database: self.database.clone(),
+                    table: self.table.clone(),
+                    mapping: self.mapping.clone(),
+                })
+            }
+        };
+        let batch = match self.ingestion_mode {
+            IngestionMode::Queued => batch.validate()?,
+            IngestionMode::Streaming => batch.validate()?.limit_max_bytes(MAX_STREAMING_BYTES)?,
+        };
+        let ingestor = Arc::new(ingestor);
+
+        let healthcheck = healthcheck(
+            Arc::clone(&ingestor),
+            client,
+            endpoint,
+            self.database.clone(),
+        )
+        .boxed();
+
+        let sink = AzureDataExplorerSink {
+            ingestor,
+            transformer: self.encoding.clone(),
+            batch_settings: batch.into_batcher_settings()?,
+            request: self.request.unwrap_with(&TowerRequestConfig::default()),
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl AzureDataExplorerConfig {
+    fn credential(&self) -> Arc<dyn TokenCredential> {
+        let credential: Arc<dyn TokenCredential> = match &self.auth {
+            AzureDataExplorerAuth::Default => Arc::new(DefaultAzureCredential::default()),
+            AzureDataExplorerAuth::ClientSecret {
+                tenant_id,
+                client_id,
+                client_secret,
+            } => Arc::new(ClientSecretCredential::new(
+                azure_core::new_http_client(),
+                tenant_id.clone(),
+                client_id.clone(),
+                client_secret.inner().to_owned(),
+                TokenCredentialOptions::default(),
+            )),
+        };
+        Arc::new(AutoRefreshingTokenCredential::new(credential))
+    }
+
+    fn ingestion_endpoint(&self) -> crate::Result<String> {
+        if let Some(endpoint) = &self.ingestion_endpoint {
+            return Ok(endpoint.trim_end_matches('/').to_owned());
+        }
+
+        let uri: Uri = self.endpoint.parse().context(UriSnafu)?;
+        let host = uri
+            .host()
+            .ok_or(AzureDataExplorerConfigError::MissingHost)?;
+        let port = uri
+            .port()
+            .map(|port| format!(":{}", port))
+            .unwrap_or_default();
+        Ok(format!(
+            "{}://ingest-{}{}",
+            uri.scheme_str().unwrap_or("https"),
+            host,
+            port
+        ))
+    }
+}
+
+async fn healthcheck(
+    ingestor: Arc<Ingestor>,
+    client: Arc<KustoClient>,
+    endpoint: String,
+    database: String,
+) -> crate::Result<()> {
+    match ingestor.as_ref() {
+        Ingestor::Queued(ingestor) => {
+            ingestor.resources().await?;
+        }
+        Ingestor::Streaming(_) => {
+            client
+                .management(&endpoint, &database, ".show version")
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureDataExplorerConfig>();
+    }
+
+    #[test]
+    fn derives_ingestion_endpoint() {
+        let config: AzureDataExplorerConfig = toml::from_str(indoc! {r#"
+            endpoint = "https://mycluster.westeurope.kusto.windows.net/"
+            database = "logs"
+            table = "events"
+        "#})
+        .unwrap();
+
+        assert_eq!(
+            config.ingestion_endpoint().unwrap(),
+            "https://ingest-mycluster.westeurope.kusto.windows.net"
+        );
+    }
+
+    #[test]
+    fn parses_client_secret_auth() {
+        let config: AzureDataExplorerConfig = toml::from_str(indoc! {r#"
+            endpoint = "https://mycluster.westeurope.kusto.windows.net"
+            database = "logs"
+            table = "events"
+            ingestion_mode = "streaming"
+            auth.strategy = "client_secret"
+            auth.tenant_id = "tenant"
+            auth.client_id = "client"
+            auth.client_secret = "secret"
+        "#})
+        .unwrap();
+
+        assert_eq!(config.ingestion_mode, IngestionMode::Streaming);
+        assert!(matches!(
+            config.auth,
+            AzureDataExplorerAuth::ClientSecret { .. }
+        ));
+    }
+}
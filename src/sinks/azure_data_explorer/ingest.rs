@@ -0,0 +1,281 @@
+//! Queued ingestion, through the storage resources of the data management service of clusters.
+//!
+//! Batches are uploaded to blob containers of the service, and ingestion messages referencing the
+//! blobs are posted to its queues, from which the service ingests them in the background.
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use http::{header, Request, StatusCode};
+use hyper::Body;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use snafu::ResultExt;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use super::client::{unexpected_status, AzureDataExplorerError, BuildRequestSnafu, KustoClient};
+
+/// How long ingestion resources are used for before fetching them again, as they're rotated by
+/// the service.
+const RESOURCES_TTL: Duration = Duration::from_secs(3600);
+
+/// The version of the storage REST API requests are made with.
+const STORAGE_API_VERSION: &str = "2019-12-12";
+
+/// The database management commands about ingestion are run against.
+const MANAGEMENT_DATABASE: &str = "NetDefaultDB";
+
+/// The storage resources and authorization context batches are ingested with.
+#[derive(Debug, Default, PartialEq)]
+pub struct IngestionResources {
+    pub queues: Vec<String>,
+    pub containers: Vec<String>,
+    pub authorization_context: String,
+}
+
+impl IngestionResources {
+    /// Collects the resources listed in the rows of the `.get ingestion resources` command.
+    fn from_rows(rows: Vec<Vec<JsonValue>>) -> Self {
+        let mut resources = Self::default();
+        for row in rows {
+            if let [JsonValue::String(name), JsonValue::String(uri), ..] = row.as_slice() {
+                match name.as_str() {
+                    "SecuredReadyForAggregationQueue" => resources.queues.push(uri.clone()),
+                    "TempStorage" => resources.containers.push(uri.clone()),
+                    _ => {}
+                }
+            }
+        }
+        resources
+    }
+}
+
+/// Splits the SAS URI of a storage resource into the URI of the resource and its SAS token.
+fn sas_uri(uri: &str) -> (&str, &str) {
+    match uri.split_once('?') {
+        Some((resource, token)) => (resource.trim_end_matches('/'), token),
+        None => (uri.trim_end_matches('/'), ""),
+    }
+}
+
+/// The message posted to ingestion queues for each blob.
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct IngestionMessage<'a> {
+    id: Uuid,
+    blob_path: &'a str,
+    raw_data_size: usize,
+    database_name: &'a str,
+    table_name: &'a str,
+    retain_blob_on_success: bool,
+    flush_immediately: bool,
+    additional_properties: IngestionProperties<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IngestionProperties<'a> {
+    authorization_context: &'a str,
+    format: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ingestion_mapping_reference: Option<&'a str>,
+}
+
+pub struct QueuedIngestor {
+    pub client: Arc<KustoClient>,
+    pub ingestion_endpoint: String,
+    pub database: String,
+    pub table: String,
+    pub mapping: Option<String>,
+    resources: Mutex<Option<(Instant, Arc<IngestionResources>)>>,
+    next: AtomicUsize,
+}
+
+impl QueuedIngestor {
+    pub fn new(
+        client: Arc<KustoClient>,
+        ingestion_endpoint: String,
+        database: String,
+        table: String,
+        mapping: Option<String>,
+    ) -> Self {
+        Self {
+            client,
+            ingestion_endpoint,
+            database,
+            table,
+            mapping,
+            resources: Mutex::new(None),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the ingestion resources, fetching them if they haven't been, or have expired.
+    pub async fn resources(&self) -> Result<Arc<IngestionResources>, AzureDataExplorerError> {
+        let mut cached = self.resources.lock().await;
+        if let Some((fetched_at, resources)) = cached.as_ref() {
+            if fetched_at.elapsed() < RESOURCES_TTL {
+                return Ok(Arc::clone(resources));
+            }
+        }
+
+        let rows = self
+            .client
+            .management(
+                &self.ingestion_endpoint,
+                MANAGEMENT_DATABASE,
+                ".get ingestion resources",
+            )
+            .await?;
+        let mut resources = IngestionResources::from_rows(rows);
+        if resources.queues.is_empty() {
+            return Err(AzureDataExplorerError::MissingResource { resource: "queue" });
+        }
+        if resources.containers.is_empty() {
+            return Err(AzureDataExplorerError::MissingResource {
+                resource: "temporary storage",
+            });
+        }
+
+        let rows = self
+            .client
+            .management(
+                &self.ingestion_endpoint,
+                MANAGEMENT_DATABASE,
+                ".get kusto identity token",
+            )
+            .await?;
+        resources.authorization_context = rows
+            .into_iter()
+            .next()
+            .and_then(|row| row.into_iter().next())
+            .and_then(|value| value.as_str().map(ToOwned::to_owned))
+            .ok_or(AzureDataExplorerError::MissingResource {
+                resource: "identity token",
+            })?;
+
+        let resources = Arc::new(resources);
+        *cached = Some((Instant::now(), Arc::clone(&resources)));
+        Ok(resources)
+    }
+
+    /// Uploads the gzipped `body` to a blob, and posts the message to ingest it.
+    ///
+    /// Blobs are named after the `id` of the batch, so that retries overwrite the blob of previous
+    /// attempts instead of uploading another.
+    pub async fn ingest(
+        &self,
+        id: Uuid,
+        body: Bytes,
+        raw_size: usize,
+    ) -> Result<(), AzureDataExplorerError> {
+        let resources = self.resources().await?;
+        // Spreads batches over the resources, as recommended to avoid throttling.
+        let index = self.next.fetch_add(1, Ordering::Relaxed);
+
+        let container = &resources.containers[index % resources.containers.len()];
+        let (container, token) = sas_uri(container);
+        let blob_uri = format!(
+            "{}/{}__{}__{}.multijson.gz?{}",
+            container, self.database, self.table, id, token
+        );
+        let request = Request::put(blob_uri.as_str())
+            .header("x-ms-blob-type", "BlockBlob")
+            .header("x-ms-version", STORAGE_API_VERSION)
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(Body::from(body))
+            .context(BuildRequestSnafu)?;
+        let (status, response) = self.client.send(request).await?;
+        if status != StatusCode::CREATED {
+            return Err(unexpected_status(status, &response));
+        }
+
+        let message = IngestionMessage {
+            id,
+            blob_path: &blob_uri,
+            raw_data_size: raw_size,
+            database_name: &self.database,
+            table_name: &self.table,
+            retain_blob_on_success: false,
+            flush_immediately: false,
+            additional_properties: IngestionProperties {
+                authorization_context: &resources.authorization_context,
+                format: "multijson",
+                ingestion_mapping_reference: self.mapping.as_deref(),
+            },
+        };
+        let message = serde_json::to_vec(&message).expect("messages should serialize");
+        let body = format!(
+            "<QueueMessage><MessageText>{}</MessageText></QueueMessage>",
+            base64::encode(message)
+        );
+
+        let queue = &resources.queues[index % resources.queues.len()];
+        let (queue, token) = sas_uri(queue);
+        let request = Request::post(format!("{}/messages?{}", queue, token))
+            .header("x-ms-version", STORAGE_API_VERSION)
+            .header(header::CONTENT_TYPE, "application/xml")
+            .body(Body::from(body))
+            .context(BuildRequestSnafu)?;
+        let (status, response) = self.client.send(request).await?;
+        if status != StatusCode::CREATED {
+            return Err(unexpected_status(status, &response));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn splits_sas_uri() {
+        assert_eq!(
+            sas_uri("https://account.blob.core.windows.net/container/?sv=2019&sig=abc"),
+            (
+                "https://account.blob.core.windows.net/container",
+                "sv=2019&sig=abc"
+            )
+        );
+        assert_eq!(
+            sas_uri("https://account.queue.core.windows.net/queue"),
+            ("https://account.queue.core.windows.net/queue", "")
+        );
+    }
+
+    #[test]
+    fn collects_ingestion_resources() {
+        let rows = vec![
+            vec![
+                json!("SecuredReadyForAggregationQueue"),
+                json!("https://q1"),
+            ],
+            vec![json!("TempStorage"), json!("https://c1")],
+            vec![json!("FailedIngestionsQueue"), json!("https://failed")],
+            vec![
+                json!("SecuredReadyForAggregationQueue"),
+                json!("https://q2"),
+            ],
+        ];
+
+        assert_eq!(
+            IngestionResources::from_rows(rows),
+            IngestionResources {
+                queues: vec!["https://q1".to_owned(), "https://q2".to_owned()],
+                containers: vec!["https://c1".to_owned()],
+                authorization_context: String::new(),
+            }
+        );
+    }
+}
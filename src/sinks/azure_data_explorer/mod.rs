@@ -0,0 +1,14 @@
+//! The `azure_data_explorer` sink.
+//!
+//! Batches of events are ingested as JSON into tables of [Azure Data Explorer][adx] clusters,
+//! either queued through the data management service of the cluster, or streamed to its engine.
+//!
+//! [adx]: https://azure.microsoft.com/en-us/products/data-explorer/
+
+mod client;
+mod config;
+mod ingest;
+mod service;
+mod sink;
+
+pub use self::config::AzureDataExplorerConfig;
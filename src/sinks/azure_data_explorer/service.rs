@@ -0,0 +1,145 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use http::{header, Request, StatusCode};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use snafu::ResultExt;
+use tower::Service;
+use uuid::Uuid;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    stream::DriverResponse,
+};
+
+use super::{
+    client::{unexpected_status, AzureDataExplorerError, BuildRequestSnafu, KustoClient},
+    ingest::QueuedIngestor,
+};
+use crate::sinks::util::retries::RetryLogic;
+
+/// How batches are ingested into the table.
+pub enum Ingestor {
+    Queued(QueuedIngestor),
+    Streaming(StreamingIngestor),
+}
+
+impl Ingestor {
+    async fn ingest(&self, request: &KustoRequest) -> Result<(), AzureDataExplorerError> {
+        match self {
+            Self::Queued(ingestor) => {
+                ingestor
+                    .ingest(request.id, request.body.clone(), request.raw_size)
+                    .await
+            }
+            Self::Streaming(ingestor) => ingestor.ingest(request.body.clone()).await,
+        }
+    }
+}
+
+/// Streaming ingestion, with requests to the engine service of clusters.
+pub struct StreamingIngestor {
+    pub client: Arc<KustoClient>,
+    pub endpoint: String,
+    pub database: String,
+    pub table: String,
+    pub mapping: Option<String>,
+}
+
+impl StreamingIngestor {
+    async fn ingest(&self, body: Bytes) -> Result<(), AzureDataExplorerError> {
+        let mut uri = format!(
+            "{}/v1/rest/ingest/{}/{}?streamFormat=multijson",
+            self.endpoint,
+            utf8_percent_encode(&self.database, NON_ALPHANUMERIC),
+            utf8_percent_encode(&self.table, NON_ALPHANUMERIC),
+        );
+        if let Some(mapping) = &self.mapping {
+            uri.push_str("&mappingName=");
+            uri.extend(utf8_percent_encode(mapping, NON_ALPHANUMERIC));
+        }
+
+        let request = Request::post(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::CONTENT_ENCODING, "gzip")
+            .body(Body::from(body))
+            .context(BuildRequestSnafu)?;
+        let (status, response) = self.client.send_authenticated(request).await?;
+        if status != StatusCode::OK {
+            return Err(unexpected_status(status, &response));
+        }
+        Ok(())
+    }
+}
+
+/// A batch of events, encoded as gzipped JSON lines.
+#[derive(Clone)]
+pub struct KustoRequest {
+    /// Identifies the batch across retries.
+    pub id: Uuid,
+    pub body: Bytes,
+    /// The size of the batch before it was compressed.
+    pub raw_size: usize,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for KustoRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for KustoRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata.clone()
+    }
+}
+
+pub struct KustoResponse;
+
+impl DriverResponse for KustoResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+}
+
+#[derive(Clone)]
+pub struct KustoService {
+    pub ingestor: Arc<Ingestor>,
+}
+
+impl Service<KustoRequest> for KustoService {
+    type Response = KustoResponse;
+    type Error = AzureDataExplorerError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: KustoRequest) -> Self::Future {
+        let ingestor = Arc::clone(&self.ingestor);
+        Box::pin(async move {
+            ingestor.ingest(&request).await?;
+            Ok(KustoResponse)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KustoRetryLogic;
+
+impl RetryLogic for KustoRetryLogic {
+    type Error = AzureDataExplorerError;
+    type Response = KustoResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
@@ -0,0 +1,114 @@
+use std::{io::Write, num::NonZeroUsize, sync::Arc};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use flate2::write::GzEncoder;
+use futures::{stream::BoxStream, StreamExt};
+use tower::ServiceBuilder;
+use uuid::Uuid;
+use vector_core::{
+    event::{EventFinalizers, Finalizable},
+    stream::BatcherSettings,
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use super::service::{Ingestor, KustoRequest, KustoRetryLogic, KustoService};
+use crate::{
+    codecs::Transformer,
+    event::Event,
+    sinks::util::{
+        metadata::RequestMetadataBuilder, ServiceBuilderExt, SinkBuilderExt, StreamSink,
+        TowerRequestSettings,
+    },
+};
+
+/// An event, encoded as a line of JSON, with the sizes it had before being transformed.
+///
+/// Events are batched by their encoded size, which is what ingestion is sized by.
+struct EncodedEvent {
+    line: Vec<u8>,
+    finalizers: EventFinalizers,
+    byte_size: usize,
+    json_size: usize,
+}
+
+impl ByteSizeOf for EncodedEvent {
+    fn size_of(&self) -> usize {
+        self.line.len()
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
+pub struct AzureDataExplorerSink {
+    pub ingestor: Arc<Ingestor>,
+    pub transformer: Transformer,
+    pub batch_settings: BatcherSettings,
+    pub request: TowerRequestSettings,
+}
+
+#[async_trait]
+impl StreamSink<Event> for AzureDataExplorerSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let service = ServiceBuilder::new()
+            .settings(self.request, KustoRetryLogic)
+            .service(KustoService {
+                ingestor: self.ingestor,
+            });
+
+        let transformer = self.transformer;
+        input
+            .map(|mut event| {
+                let byte_size = event.size_of();
+                let json_size = event.estimated_json_encoded_size_of();
+                transformer.transform(&mut event);
+                let mut line = serde_json::to_vec(event.as_log()).expect("events should serialize");
+                line.push(b'\n');
+                EncodedEvent {
+                    line,
+                    finalizers: event.take_finalizers(),
+                    byte_size,
+                    json_size,
+                }
+            })
+            .batched(self.batch_settings.into_byte_size_config())
+            .map(|events| {
+                let count = events.len();
+                let mut byte_size = 0;
+                let mut json_size = 0;
+                let mut raw_size = 0;
+                let mut finalizers = EventFinalizers::default();
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                for event in events {
+                    byte_size += event.byte_size;
+                    json_size += event.json_size;
+                    raw_size += event.line.len();
+                    finalizers.merge(event.finalizers);
+                    encoder
+                        .write_all(&event.line)
+                        .expect("writing to a vector should not fail");
+                }
+                let body = encoder
+                    .finish()
+                    .expect("writing to a vector should not fail");
+                let metadata = RequestMetadataBuilder::new(count, byte_size, json_size)
+                    .with_request_size(
+                        NonZeroUsize::new(body.len()).expect("gzip output should not be empty"),
+                    );
+
+                KustoRequest {
+                    id: Uuid::new_v4(),
+                    body: Bytes::from(body),
+                    raw_size,
+                    finalizers,
+                    metadata,
+                }
+            })
+            .into_driver(service)
+            .protocol("https")
+            .run()
+            .await
+    }
+}
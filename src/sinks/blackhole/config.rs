@@ -3,7 +3,10 @@ use vector_config::configurable_component;
 
 use crate::{
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
-    sinks::{blackhole::sink::BlackholeSink, Healthcheck, VectorSink},
+    sinks::{
+        blackhole::{simulation::BlackholeSimulationConfig, sink::BlackholeSink},
+        Healthcheck, VectorSink,
+    },
 };
 
 const fn default_print_interval_secs() -> u64 {
@@ -28,6 +31,10 @@ pub struct BlackholeConfig {
     /// By default, there is no limit.
     pub rate: Option<usize>,
 
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub simulate: Option<BlackholeSimulationConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -40,7 +47,15 @@ pub struct BlackholeConfig {
 #[async_trait::async_trait]
 impl SinkConfig for BlackholeConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let sink = BlackholeSink::new(self.clone());
+        let simulation = match &self.simulate {
+            Some(_) if self.rate.is_some() => return Err(
+                "`rate` can't be set with `simulate`, set `simulate.max_events_per_sec` instead."
+                    .into(),
+            ),
+            Some(simulate) => Some(simulate.build()?),
+            None => None,
+        };
+        let sink = BlackholeSink::new(self.clone(), simulation);
         let healthcheck = future::ok(()).boxed();
 
         Ok((VectorSink::Stream(Box::new(sink)), healthcheck))
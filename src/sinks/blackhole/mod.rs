@@ -1,4 +1,5 @@
 mod config;
+mod simulation;
 mod sink;
 
 pub use config::BlackholeConfig;
@@ -21,9 +22,10 @@ mod tests {
         let config = BlackholeConfig {
             print_interval_secs: 10,
             rate: None,
+            simulate: None,
             acknowledgements: Default::default(),
         };
-        let sink = BlackholeSink::new(config);
+        let sink = BlackholeSink::new(config, None);
         let sink = VectorSink::Stream(Box::new(sink));
 
         let (_input_lines, events) = random_events_with_stream(100, 10, None);
@@ -0,0 +1,437 @@
+//! Simulated downstream service of the `blackhole` sink.
+//!
+//! Batches of events are sent as requests to a service that doesn't do anything with them, but
+//! takes time to respond, fails, throttles, or rejects some of them, and caps its throughput, so
+//! that adaptive concurrency, retries, and buffering can be load tested through the same request
+//! stack as the other sinks.
+
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{future::BoxFuture, stream::BoxStream, StreamExt};
+use rand::{distributions::Uniform, thread_rng, Rng};
+use rand_distr::{Exp, Normal};
+use snafu::Snafu;
+use tokio::time::{sleep, sleep_until, Instant};
+use tower::{Service, ServiceBuilder};
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_config::configurable_component;
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    stream::{BatcherSettings, DriverResponse},
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    event::{Event, EventArray, EventContainer},
+    sinks::util::{
+        metadata::RequestMetadataBuilder, retries::RetryLogic, BatchConfig, ServiceBuilderExt,
+        SinkBatchSettings, SinkBuilderExt, TowerRequestConfig, TowerRequestSettings,
+    },
+};
+
+/// Simulated downstream service options.
+///
+/// When set, batches of events are sent as requests to a simulated service, instead of being
+/// consumed as soon as they are received.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct BlackholeSimulationConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub latency: BlackholeLatency,
+
+    /// The fraction of requests, between `0` and `1`, that fail with an error that is retried.
+    #[serde(default)]
+    pub error_rate: f64,
+
+    /// The fraction of requests, between `0` and `1`, that are throttled, as with an HTTP `429 Too
+    /// Many Requests` response, and retried.
+    #[serde(default)]
+    pub throttle_rate: f64,
+
+    /// The fraction of requests, between `0` and `1`, whose events are rejected without being
+    /// retried.
+    #[serde(default)]
+    pub reject_rate: f64,
+
+    /// The number of events, per second, that the simulated service is able to handle.
+    ///
+    /// Requests wait for the service to have handled the events of the previous ones before
+    /// taking their latency. By default, there is no limit.
+    pub max_events_per_sec: Option<u64>,
+
+    /// The number of bytes, per second, that the simulated service is able to handle.
+    ///
+    /// The bytes of requests are the estimated size of their events encoded as JSON. Requests wait
+    /// for the service to have handled the bytes of the previous ones before taking their latency.
+    /// By default, there is no limit.
+    pub max_bytes_per_sec: Option<u64>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<BlackholeDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+}
+
+/// The distribution of the latency of simulated requests.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "distribution", rename_all = "snake_case", deny_unknown_fields)]
+pub enum BlackholeLatency {
+    /// Requests take a constant time.
+    Constant {
+        /// The latency, in milliseconds.
+        ms: f64,
+    },
+
+    /// Requests take a time uniformly distributed between a minimum and a maximum.
+    Uniform {
+        /// The minimum latency, in milliseconds.
+        min_ms: f64,
+
+        /// The maximum latency, in milliseconds.
+        max_ms: f64,
+    },
+
+    /// Requests take a normally distributed time, which is never negative.
+    Normal {
+        /// The mean latency, in milliseconds.
+        mean_ms: f64,
+
+        /// The standard deviation of the latency, in milliseconds.
+        std_dev_ms: f64,
+    },
+
+    /// Requests take an exponentially distributed time.
+    Exponential {
+        /// The mean latency, in milliseconds.
+        mean_ms: f64,
+    },
+}
+
+impl Default for BlackholeLatency {
+    fn default() -> Self {
+        Self::Constant { ms: 0.0 }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BlackholeDefaultBatchSettings;
+
+impl SinkBatchSettings for BlackholeDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    const MAX_BYTES: Option<usize> = Some(1_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+impl BlackholeSimulationConfig {
+    /// Builds the simulated service the events are sent to.
+    pub(super) fn build(&self) -> crate::Result<Simulation> {
+        for (name, rate) in [
+            ("error_rate", self.error_rate),
+            ("throttle_rate", self.throttle_rate),
+            ("reject_rate", self.reject_rate),
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(format!("`simulate.{}` must be between 0 and 1.", name).into());
+            }
+        }
+        if self.error_rate + self.throttle_rate + self.reject_rate > 1.0 {
+            return Err(
+                "The sum of `simulate.error_rate`, `simulate.throttle_rate` and `simulate.reject_rate` can't be larger than 1."
+                    .into(),
+            );
+        }
+        if self.max_events_per_sec == Some(0) || self.max_bytes_per_sec == Some(0) {
+            return Err(
+                "`simulate.max_events_per_sec` and `simulate.max_bytes_per_sec` must be larger than 0."
+                    .into(),
+            );
+        }
+
+        Ok(Simulation {
+            batch_settings: self.batch.into_batcher_settings()?,
+            request: self.request.unwrap_with(&TowerRequestConfig::default()),
+            service: SimulatedService {
+                latency: self.latency.sampler()?,
+                error_rate: self.error_rate,
+                throttle_rate: self.throttle_rate,
+                reject_rate: self.reject_rate,
+                max_events_per_sec: self.max_events_per_sec,
+                max_bytes_per_sec: self.max_bytes_per_sec,
+                available_at: Arc::new(Mutex::new(Instant::now())),
+            },
+        })
+    }
+}
+
+impl BlackholeLatency {
+    fn sampler(&self) -> crate::Result<LatencySampler> {
+        let invalid = |error: &dyn std::fmt::Display| {
+            format!("Invalid `simulate.latency` distribution: {}.", error)
+        };
+        Ok(match *self {
+            Self::Constant { ms } if ms >= 0.0 => LatencySampler::Constant(ms),
+            Self::Uniform { min_ms, max_ms } if 0.0 <= min_ms && min_ms <= max_ms => {
+                LatencySampler::Uniform(Uniform::new_inclusive(min_ms, max_ms))
+            }
+            Self::Normal {
+                mean_ms,
+                std_dev_ms,
+            } => LatencySampler::Normal(
+                Normal::new(mean_ms, std_dev_ms).map_err(|error| invalid(&error))?,
+            ),
+            Self::Exponential { mean_ms } => LatencySampler::Exponential(
+                Exp::new(1.0 / mean_ms).map_err(|error| invalid(&error))?,
+            ),
+            _ => return Err(invalid(&"latencies can't be negative").into()),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+enum LatencySampler {
+    Constant(f64),
+    Uniform(Uniform<f64>),
+    Normal(Normal<f64>),
+    Exponential(Exp<f64>),
+}
+
+impl LatencySampler {
+    fn sample(&self) -> Duration {
+        let ms = match self {
+            Self::Constant(ms) => *ms,
+            Self::Uniform(distribution) => thread_rng().sample(distribution),
+            Self::Normal(distribution) => thread_rng().sample(distribution),
+            Self::Exponential(distribution) => thread_rng().sample(distribution),
+        };
+        Duration::from_secs_f64(ms.max(0.0) / 1000.0)
+    }
+}
+
+/// The simulated service, with the settings of the requests sent to it.
+pub struct Simulation {
+    batch_settings: BatcherSettings,
+    request: TowerRequestSettings,
+    service: SimulatedService,
+}
+
+impl Simulation {
+    /// Sends the events of `input` to the simulated service, until it ends.
+    pub(super) async fn run(self, input: BoxStream<'_, EventArray>) -> Result<(), ()> {
+        let service = ServiceBuilder::new()
+            .settings(self.request, SimulatedRetryLogic)
+            .service(self.service);
+
+        input
+            .flat_map(|events| futures::stream::iter(events.into_events()))
+            .batched(self.batch_settings.into_byte_size_config())
+            .map(|mut events: Vec<Event>| {
+                let finalizers = events.take_finalizers();
+                let json_size = events.estimated_json_encoded_size_of();
+                let metadata = RequestMetadataBuilder::from_events(&events).with_request_size(
+                    NonZeroUsize::new(json_size.max(1)).expect("size should not be zero"),
+                );
+                SimulatedRequest {
+                    finalizers,
+                    metadata,
+                }
+            })
+            .into_driver(service)
+            .protocol("blackhole")
+            .run()
+            .await
+    }
+}
+
+#[derive(Clone)]
+struct SimulatedRequest {
+    finalizers: EventFinalizers,
+    metadata: RequestMetadata,
+}
+
+impl Finalizable for SimulatedRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for SimulatedRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata.clone()
+    }
+}
+
+struct SimulatedResponse {
+    status: EventStatus,
+}
+
+impl DriverResponse for SimulatedResponse {
+    fn event_status(&self) -> EventStatus {
+        self.status
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum SimulatedError {
+    #[snafu(display("Simulated request failure"))]
+    Failed,
+    #[snafu(display("Simulated request throttling"))]
+    Throttled,
+}
+
+#[derive(Clone)]
+struct SimulatedService {
+    latency: LatencySampler,
+    error_rate: f64,
+    throttle_rate: f64,
+    reject_rate: f64,
+    max_events_per_sec: Option<u64>,
+    max_bytes_per_sec: Option<u64>,
+    /// When the service is done handling the events of the previous requests, if it caps its
+    /// throughput.
+    available_at: Arc<Mutex<Instant>>,
+}
+
+impl SimulatedService {
+    /// Reserves the time the service takes to handle the events of `metadata`, returning when it
+    /// is done with them.
+    fn reserve(&self, metadata: &RequestMetadata) -> Option<Instant> {
+        let secs = |count: usize, per_sec: Option<u64>| {
+            per_sec.map_or(0.0, |per_sec| count as f64 / per_sec as f64)
+        };
+        let secs = secs(metadata.event_count(), self.max_events_per_sec).max(secs(
+            metadata.request_encoded_size(),
+            self.max_bytes_per_sec,
+        ));
+        if secs == 0.0 {
+            return None;
+        }
+
+        let mut available_at = self
+            .available_at
+            .lock()
+            .expect("available_at mutex poisoned");
+        let done = (*available_at).max(Instant::now()) + Duration::from_secs_f64(secs);
+        *available_at = done;
+        Some(done)
+    }
+}
+
+impl Service<SimulatedRequest> for SimulatedService {
+    type Response = SimulatedResponse;
+    type Error = SimulatedError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: SimulatedRequest) -> Self::Future {
+        let done = self.reserve(&request.metadata);
+        let latency = self.latency.sample();
+        let roll = thread_rng().gen::<f64>();
+        let (error_rate, throttle_rate, reject_rate) =
+            (self.error_rate, self.throttle_rate, self.reject_rate);
+
+        Box::pin(async move {
+            if let Some(done) = done {
+                sleep_until(done).await;
+            }
+            sleep(latency).await;
+
+            if roll < error_rate {
+                Err(SimulatedError::Failed)
+            } else if roll < error_rate + throttle_rate {
+                Err(SimulatedError::Throttled)
+            } else {
+                let status = if roll < error_rate + throttle_rate + reject_rate {
+                    EventStatus::Rejected
+                } else {
+                    EventStatus::Delivered
+                };
+                Ok(SimulatedResponse { status })
+            }
+        })
+    }
+}
+
+#[derive(Clone)]
+struct SimulatedRetryLogic;
+
+impl RetryLogic for SimulatedRetryLogic {
+    type Error = SimulatedError;
+    type Response = SimulatedResponse;
+
+    fn is_retriable_error(&self, _error: &Self::Error) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn simulation(config: &str) -> crate::Result<Simulation> {
+        toml::from_str::<BlackholeSimulationConfig>(config)
+            .unwrap()
+            .build()
+    }
+
+    #[test]
+    fn validates_rates() {
+        assert!(simulation("error_rate = 0.5\nthrottle_rate = 0.5").is_ok());
+        assert!(simulation("error_rate = 1.5").is_err());
+        assert!(simulation("error_rate = 0.5\nreject_rate = 0.6").is_err());
+        assert!(simulation("max_bytes_per_sec = 0").is_err());
+    }
+
+    #[test]
+    fn samples_latencies() {
+        let sampler = |config: &str| {
+            simulation(config)
+                .map(|simulation| simulation.service.latency)
+                .ok()
+        };
+
+        let constant = sampler("latency.distribution = \"constant\"\nlatency.ms = 250").unwrap();
+        assert_eq!(constant.sample(), Duration::from_millis(250));
+
+        let uniform =
+            sampler("latency.distribution = \"uniform\"\nlatency.min_ms = 10\nlatency.max_ms = 20")
+                .unwrap();
+        for _ in 0..100 {
+            let latency = uniform.sample();
+            assert!(Duration::from_millis(10) <= latency && latency <= Duration::from_millis(20));
+        }
+
+        assert!(sampler("latency.distribution = \"constant\"\nlatency.ms = -1").is_none());
+        assert!(sampler("latency.distribution = \"exponential\"\nlatency.mean_ms = -1").is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn caps_throughput() {
+        let service = simulation("max_events_per_sec = 10").unwrap().service;
+        let metadata =
+            RequestMetadataBuilder::new(5, 0, 0).with_request_size(NonZeroUsize::new(1).unwrap());
+
+        let start = Instant::now();
+        assert_eq!(
+            service.reserve(&metadata),
+            Some(start + Duration::from_millis(500))
+        );
+        assert_eq!(
+            service.reserve(&metadata),
+            Some(start + Duration::from_secs(1))
+        );
+    }
+}
@@ -20,20 +20,25 @@ use vector_core::EstimatedJsonEncodedSizeOf;
 
 use crate::{
     event::{EventArray, EventContainer},
-    sinks::{blackhole::config::BlackholeConfig, util::StreamSink},
+    sinks::{
+        blackhole::{config::BlackholeConfig, simulation::Simulation},
+        util::StreamSink,
+    },
 };
 
 pub struct BlackholeSink {
     total_events: Arc<AtomicUsize>,
     total_raw_bytes: Arc<AtomicUsize>,
     config: BlackholeConfig,
+    simulation: Option<Simulation>,
     last: Option<Instant>,
 }
 
 impl BlackholeSink {
-    pub fn new(config: BlackholeConfig) -> Self {
+    pub fn new(config: BlackholeConfig, simulation: Option<Simulation>) -> Self {
         BlackholeSink {
             config,
+            simulation,
             total_events: Arc::new(AtomicUsize::new(0)),
             total_raw_bytes: Arc::new(AtomicUsize::new(0)),
             last: None,
@@ -78,6 +83,26 @@ impl StreamSink<EventArray> for BlackholeSink {
             });
         }
 
+        if let Some(simulation) = self.simulation.take() {
+            // Events are counted as they are received, while the driver of the requests reports
+            // those sent to the simulated service.
+            let total_events = Arc::clone(&self.total_events);
+            let total_raw_bytes = Arc::clone(&self.total_raw_bytes);
+            let input = input
+                .inspect(move |events| {
+                    let _ = total_events.fetch_add(events.len(), Ordering::AcqRel);
+                    let _ = total_raw_bytes
+                        .fetch_add(events.estimated_json_encoded_size_of(), Ordering::AcqRel);
+                })
+                .boxed();
+            let result = simulation.run(input).await;
+
+            // Notify the reporting task to shutdown.
+            let _ = shutdown.send(());
+
+            return result;
+        }
+
         while let Some(events) = input.next().await {
             if let Some(rate) = self.config.rate {
                 let factor: f32 = 1.0 / rate as f32;
@@ -0,0 +1,312 @@
+use std::{path::PathBuf, sync::Arc};
+
+use futures::FutureExt;
+use indexmap::IndexMap;
+use openssl::{
+    error::ErrorStack,
+    ssl::{SslContext, SslContextBuilder, SslMethod},
+};
+use scylla::{batch::BatchType, statement::Consistency, SessionBuilder};
+use snafu::{ResultExt, Snafu};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use super::sink::CassandraSink;
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{
+        util::{BatchConfig, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`nodes` must not be empty"))]
+    NoNodes,
+    #[snafu(display("`columns` must not be empty"))]
+    NoColumns,
+    #[snafu(display("failed to create tls context: {}", source))]
+    TlsFailed { source: ErrorStack },
+}
+
+/// The consistency level of writes.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CassandraConsistency {
+    /// A write must be written to at least one node, or stored as a hint.
+    Any,
+
+    /// A write must be written to at least one replica.
+    One,
+
+    /// A write must be written to at least two replicas.
+    Two,
+
+    /// A write must be written to at least three replicas.
+    Three,
+
+    /// A write must be written to a quorum of the replicas of all datacenters.
+    Quorum,
+
+    /// A write must be written to all replicas.
+    All,
+
+    /// A write must be written to a quorum of the replicas of the local datacenter.
+    #[default]
+    LocalQuorum,
+
+    /// A write must be written to a quorum of the replicas of each datacenter.
+    EachQuorum,
+
+    /// A write must be written to at least one replica of the local datacenter.
+    LocalOne,
+}
+
+impl From<CassandraConsistency> for Consistency {
+    fn from(consistency: CassandraConsistency) -> Self {
+        match consistency {
+            CassandraConsistency::Any => Self::Any,
+            CassandraConsistency::One => Self::One,
+            CassandraConsistency::Two => Self::Two,
+            CassandraConsistency::Three => Self::Three,
+            CassandraConsistency::Quorum => Self::Quorum,
+            CassandraConsistency::All => Self::All,
+            CassandraConsistency::LocalQuorum => Self::LocalQuorum,
+            CassandraConsistency::EachQuorum => Self::EachQuorum,
+            CassandraConsistency::LocalOne => Self::LocalOne,
+        }
+    }
+}
+
+/// The type of the batches rows are written in.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CassandraBatchType {
+    /// Batches are written without the batch log.
+    ///
+    /// As batches only hold the rows of a single partition, they are still applied atomically.
+    #[default]
+    Unlogged,
+
+    /// Batches are written through the batch log.
+    Logged,
+}
+
+impl From<CassandraBatchType> for BatchType {
+    fn from(batch_type: CassandraBatchType) -> Self {
+        match batch_type {
+            CassandraBatchType::Unlogged => Self::Unlogged,
+            CassandraBatchType::Logged => Self::Logged,
+        }
+    }
+}
+
+/// Authentication of the sink with the cluster.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CassandraAuthConfig {
+    /// The username to authenticate with.
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub username: String,
+
+    /// The password to authenticate with.
+    pub password: SensitiveString,
+}
+
+/// Configuration of TLS when connecting to the cluster.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CassandraTlsConfig {
+    /// Absolute path to an additional CA certificate file.
+    ///
+    /// The certificate must be in the DER or PEM (X.509) format.
+    pub ca_file: PathBuf,
+}
+
+/// Configuration for the `cassandra` sink.
+#[configurable_component(sink("cassandra"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CassandraConfig {
+    /// The nodes of the cluster to connect to first, from which the others are discovered.
+    #[configurable(metadata(docs::examples = "127.0.0.1:9042"))]
+    pub nodes: Vec<String>,
+
+    /// The keyspace of the table to write events to.
+    #[configurable(metadata(docs::examples = "vector"))]
+    #[configurable(metadata(docs::examples = "logs_{{ tenant }}"))]
+    pub keyspace: Template,
+
+    /// The table to write events to.
+    #[configurable(metadata(docs::examples = "events"))]
+    #[configurable(metadata(docs::examples = "events_{{ application }}"))]
+    pub table: Template,
+
+    /// A map of the columns of the table to the fields of events whose values they hold.
+    ///
+    /// Values are converted to the types of their columns, and columns of fields missing from
+    /// events are null.
+    #[configurable(metadata(docs::additional_props_description = "The field of the column."))]
+    pub columns: IndexMap<String, String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub consistency: CassandraConsistency,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch_type: CassandraBatchType,
+
+    #[configurable(derived)]
+    pub auth: Option<CassandraAuthConfig>,
+
+    #[configurable(derived)]
+    pub tls: Option<CassandraTlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<CassandraDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CassandraDefaultBatchSettings;
+
+impl SinkBatchSettings for CassandraDefaultBatchSettings {
+    // Batches of a single partition are best kept small, as they're only written to its replicas.
+    const MAX_EVENTS: Option<usize> = Some(100);
+    const MAX_BYTES: Option<usize> = Some(1_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+impl GenerateConfig for CassandraConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"nodes = ["127.0.0.1:9042"]
+            keyspace = "vector"
+            table = "events"
+            columns.id = "id"
+            columns.message = "message"
+            columns.time = "timestamp""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for CassandraConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if self.nodes.is_empty() {
+            return Err(BuildError::NoNodes.into());
+        }
+        if self.columns.is_empty() {
+            return Err(BuildError::NoColumns.into());
+        }
+
+        let mut builder = SessionBuilder::new().known_nodes(self.nodes.as_slice());
+        if let Some(auth) = &self.auth {
+            builder = builder.user(auth.username.as_str(), auth.password.inner());
+        }
+        if let Some(tls) = &self.tls {
+            builder = builder.ssl_context(Some(tls_context(tls).context(TlsFailedSnafu)?));
+        }
+        let session = Arc::new(builder.build().await?);
+
+        let healthcheck = {
+            let session = Arc::clone(&session);
+            async move {
+                session
+                    .query("SELECT release_version FROM system.local", ())
+                    .await?;
+                Ok(())
+            }
+            .boxed()
+        };
+
+        let sink = CassandraSink {
+            session,
+            keyspace: self.keyspace.clone(),
+            table: self.table.clone(),
+            columns: self
+                .columns
+                .iter()
+                .map(|(column, field)| (column.clone(), field.clone()))
+                .collect(),
+            consistency: self.consistency.into(),
+            batch_type: self.batch_type.into(),
+            transformer: self.encoding.clone(),
+            batch_settings: self.batch.into_batcher_settings()?,
+            request: self.request.unwrap_with(&TowerRequestConfig::default()),
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+fn tls_context(tls: &CassandraTlsConfig) -> Result<SslContext, ErrorStack> {
+    let mut builder = SslContextBuilder::new(SslMethod::tls_client())?;
+    builder.set_ca_file(tls.ca_file.clone())?;
+    Ok(builder.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<CassandraConfig>();
+    }
+
+    #[test]
+    fn parses_consistency() {
+        let config: CassandraConfig = toml::from_str(
+            r#"nodes = ["127.0.0.1:9042"]
+            keyspace = "vector"
+            table = "events_{{ application }}"
+            columns.message = "message"
+            consistency = "each_quorum""#,
+        )
+        .unwrap();
+
+        assert_eq!(config.consistency, CassandraConsistency::EachQuorum);
+        assert_eq!(config.batch_type, CassandraBatchType::Unlogged);
+        assert_eq!(
+            Consistency::from(config.consistency),
+            Consistency::EachQuorum
+        );
+    }
+}
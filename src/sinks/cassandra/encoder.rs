@@ -0,0 +1,212 @@
+use bytes::Bytes;
+use chrono::{Duration, SecondsFormat};
+use scylla::{
+    frame::{
+        response::result::{ColumnType, CqlValue},
+        value::{SerializeValuesError, SerializedValues},
+    },
+    statement::prepared_statement::{PartitionKeyError, PreparedStatement},
+};
+use snafu::{ResultExt, Snafu};
+use uuid::Uuid;
+use vector_core::event::{LogEvent, Value};
+
+#[derive(Debug, Snafu)]
+pub enum EncodeError {
+    #[snafu(display(
+        "the value of field {:?} can't be converted to the {:?} type of column {:?}",
+        field,
+        column_type,
+        column
+    ))]
+    IncompatibleValue {
+        field: String,
+        column: String,
+        column_type: ColumnType,
+    },
+    #[snafu(display("failed to serialize values: {}", source))]
+    Serialize { source: SerializeValuesError },
+    #[snafu(display("failed to compute the partition key: {}", source))]
+    PartitionKey { source: PartitionKeyError },
+}
+
+/// Quotes `identifier`, so that it's used as is, whatever its case or characters.
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Builds the statement inserting rows into `table`, which binds the values of `columns` in order.
+pub fn insert_statement(keyspace: &str, table: &str, columns: &[String]) -> String {
+    format!(
+        "INSERT INTO {}.{} ({}) VALUES ({})",
+        quote_identifier(keyspace),
+        quote_identifier(table),
+        columns
+            .iter()
+            .map(|column| quote_identifier(column))
+            .collect::<Vec<_>>()
+            .join(", "),
+        vec!["?"; columns.len()].join(", ")
+    )
+}
+
+/// Encodes the values of `fields` of `log` as the values bound by `statement`, with the partition
+/// key they make up.
+pub fn encode_row(
+    statement: &PreparedStatement,
+    fields: &[String],
+    log: &LogEvent,
+) -> Result<(SerializedValues, Bytes), EncodeError> {
+    let columns = &statement.get_prepared_metadata().col_specs;
+    let mut values = SerializedValues::with_capacity(fields.len());
+    for (field, column) in fields.iter().zip(columns) {
+        let value =
+            match log.get(field.as_str()) {
+                None | Some(Value::Null) => None,
+                Some(value) => Some(to_cql(value, &column.typ).ok_or_else(|| {
+                    EncodeError::IncompatibleValue {
+                        field: field.clone(),
+                        column: column.name.clone(),
+                        column_type: column.typ.clone(),
+                    }
+                })?),
+            };
+        values.add_value(&value).context(SerializeSnafu)?;
+    }
+    let partition_key = statement
+        .compute_partition_key(&values)
+        .context(PartitionKeySnafu)?;
+    Ok((values, partition_key))
+}
+
+/// Converts `value` to the CQL type `column_type`, if they're compatible.
+fn to_cql(value: &Value, column_type: &ColumnType) -> Option<CqlValue> {
+    Some(match (column_type, value) {
+        (ColumnType::Ascii, value) => CqlValue::Ascii(to_text(value)),
+        (ColumnType::Text, value) => CqlValue::Text(to_text(value)),
+        (ColumnType::Boolean, Value::Boolean(boolean)) => CqlValue::Boolean(*boolean),
+        (ColumnType::TinyInt, Value::Integer(integer)) => {
+            CqlValue::TinyInt((*integer).try_into().ok()?)
+        }
+        (ColumnType::SmallInt, Value::Integer(integer)) => {
+            CqlValue::SmallInt((*integer).try_into().ok()?)
+        }
+        (ColumnType::Int, Value::Integer(integer)) => CqlValue::Int((*integer).try_into().ok()?),
+        (ColumnType::BigInt, Value::Integer(integer)) => CqlValue::BigInt(*integer),
+        (ColumnType::Float, Value::Float(float)) => CqlValue::Float(float.into_inner() as f32),
+        (ColumnType::Float, Value::Integer(integer)) => CqlValue::Float(*integer as f32),
+        (ColumnType::Double, Value::Float(float)) => CqlValue::Double(float.into_inner()),
+        (ColumnType::Double, Value::Integer(integer)) => CqlValue::Double(*integer as f64),
+        (ColumnType::Timestamp, Value::Timestamp(timestamp)) => {
+            CqlValue::Timestamp(Duration::milliseconds(timestamp.timestamp_millis()))
+        }
+        (ColumnType::Timestamp, Value::Integer(millis)) => {
+            CqlValue::Timestamp(Duration::milliseconds(*millis))
+        }
+        (ColumnType::Uuid, Value::Bytes(bytes)) => CqlValue::Uuid(parse_uuid(bytes)?),
+        (ColumnType::Timeuuid, Value::Bytes(bytes)) => CqlValue::Timeuuid(parse_uuid(bytes)?),
+        (ColumnType::Inet, Value::Bytes(bytes)) => {
+            CqlValue::Inet(std::str::from_utf8(bytes).ok()?.parse().ok()?)
+        }
+        (ColumnType::Blob, Value::Bytes(bytes)) => CqlValue::Blob(bytes.to_vec()),
+        (ColumnType::List(item_type), Value::Array(items)) => CqlValue::List(
+            items
+                .iter()
+                .map(|item| to_cql(item, item_type))
+                .collect::<Option<_>>()?,
+        ),
+        (ColumnType::Set(item_type), Value::Array(items)) => CqlValue::Set(
+            items
+                .iter()
+                .map(|item| to_cql(item, item_type))
+                .collect::<Option<_>>()?,
+        ),
+        (ColumnType::Map(key_type, value_type), Value::Object(map)) => CqlValue::Map(
+            map.iter()
+                .map(|(key, value)| {
+                    Some((
+                        to_cql(&Value::from(key.as_str()), key_type)?,
+                        to_cql(value, value_type)?,
+                    ))
+                })
+                .collect::<Option<_>>()?,
+        ),
+        _ => return None,
+    })
+}
+
+fn to_text(value: &Value) -> String {
+    match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        Value::Timestamp(timestamp) => timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        Value::Object(_) | Value::Array(_) => {
+            serde_json::to_string(value).expect("values should serialize")
+        }
+        value => value.to_string_lossy().into_owned(),
+    }
+}
+
+fn parse_uuid(bytes: &[u8]) -> Option<Uuid> {
+    Uuid::parse_str(std::str::from_utf8(bytes).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    #[test]
+    fn builds_insert_statement() {
+        assert_eq!(
+            insert_statement(
+                "vector",
+                "Events",
+                &["id".to_owned(), "message".to_owned(), "time".to_owned()]
+            ),
+            r#"INSERT INTO "vector"."Events" ("id", "message", "time") VALUES (?, ?, ?)"#
+        );
+    }
+
+    #[test]
+    fn converts_values_to_column_types() {
+        assert_eq!(
+            to_cql(&Value::from(200), &ColumnType::Int),
+            Some(CqlValue::Int(200))
+        );
+        assert_eq!(to_cql(&Value::from(200), &ColumnType::TinyInt), None);
+        assert_eq!(
+            to_cql(&Value::from(200), &ColumnType::Text),
+            Some(CqlValue::Text("200".to_owned()))
+        );
+        assert_eq!(
+            to_cql(
+                &Value::from(Utc.ymd(2023, 1, 2).and_hms(3, 4, 5)),
+                &ColumnType::Timestamp
+            ),
+            Some(CqlValue::Timestamp(Duration::milliseconds(
+                1_672_628_645_000
+            )))
+        );
+        assert_eq!(
+            to_cql(
+                &Value::from("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+                &ColumnType::Uuid
+            ),
+            Some(CqlValue::Uuid(
+                Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap()
+            ))
+        );
+        assert_eq!(
+            to_cql(
+                &Value::Array(vec!["a".into(), "b".into()]),
+                &ColumnType::Set(Box::new(ColumnType::Text))
+            ),
+            Some(CqlValue::Set(vec![
+                CqlValue::Text("a".to_owned()),
+                CqlValue::Text("b".to_owned())
+            ]))
+        );
+        assert_eq!(to_cql(&Value::from("not a uuid"), &ColumnType::Uuid), None);
+    }
+}
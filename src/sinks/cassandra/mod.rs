@@ -0,0 +1,11 @@
+//! The `cassandra` sink.
+//!
+//! Events are written as rows of a table of an Apache Cassandra or ScyllaDB cluster, with prepared
+//! `INSERT` statements sent in batches holding the rows of a single partition.
+
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+pub use self::config::CassandraConfig;
@@ -0,0 +1,130 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::future::BoxFuture;
+use scylla::{
+    batch::{Batch, BatchType},
+    frame::value::SerializedValues,
+    statement::{prepared_statement::PreparedStatement, Consistency},
+    transport::errors::{DbError, QueryError},
+    Session,
+};
+use tower::Service;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    stream::DriverResponse,
+};
+
+use crate::sinks::util::retries::RetryLogic;
+
+/// A batch of rows of a single partition, with the statement inserting them.
+#[derive(Clone)]
+pub struct CassandraRequest {
+    pub statement: PreparedStatement,
+    pub rows: Vec<SerializedValues>,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for CassandraRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for CassandraRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata.clone()
+    }
+}
+
+pub struct CassandraResponse;
+
+impl DriverResponse for CassandraResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+}
+
+#[derive(Clone)]
+pub struct CassandraService {
+    pub session: Arc<Session>,
+    pub consistency: Consistency,
+    pub batch_type: BatchType,
+}
+
+impl Service<CassandraRequest> for CassandraService {
+    type Response = CassandraResponse;
+    type Error = QueryError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: CassandraRequest) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move {
+            let mut batch = Batch::new(service.batch_type);
+            for _ in &request.rows {
+                batch.append_statement(request.statement.clone());
+            }
+            batch.set_consistency(service.consistency);
+
+            // The batch is routed to a replica of its partition, from the first of its statements.
+            service.session.batch(&batch, request.rows).await?;
+            Ok(CassandraResponse)
+        })
+    }
+}
+
+/// Whether requests or statements failing with `error` are retried, which are those failing
+/// because of the connection or of the availability of the replicas, rather than because of the
+/// rows themselves.
+pub fn is_retriable(error: &QueryError) -> bool {
+    match error {
+        QueryError::IoError(_) | QueryError::TimeoutError | QueryError::RequestTimeout(_) => true,
+        QueryError::DbError(error, _) => matches!(
+            error,
+            DbError::Unavailable { .. }
+                | DbError::Overloaded
+                | DbError::IsBootstrapping
+                | DbError::WriteTimeout { .. }
+                | DbError::ServerError
+        ),
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CassandraRetryLogic;
+
+impl RetryLogic for CassandraRetryLogic {
+    type Error = QueryError;
+    type Response = CassandraResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        is_retriable(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_unavailable_replicas() {
+        assert!(is_retriable(&QueryError::TimeoutError));
+        assert!(is_retriable(&QueryError::DbError(
+            DbError::Overloaded,
+            "overloaded".to_owned()
+        )));
+        assert!(!is_retriable(&QueryError::DbError(
+            DbError::Invalid,
+            "unknown column".to_owned()
+        )));
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{stream::BoxStream, StreamExt};
+use scylla::{
+    batch::BatchType,
+    frame::value::SerializedValues,
+    statement::{prepared_statement::PreparedStatement, Consistency},
+    transport::errors::QueryError,
+    Session,
+};
+use tower::ServiceBuilder;
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    partition::Partitioner,
+    stream::BatcherSettings,
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use super::{
+    encoder::{encode_row, insert_statement},
+    service::{is_retriable, CassandraRequest, CassandraRetryLogic, CassandraService},
+};
+use crate::{
+    codecs::Transformer,
+    event::Event,
+    internal_events::{CassandraEncodingError, TemplateRenderingError},
+    sinks::util::{
+        metadata::RequestMetadataBuilder, ServiceBuilderExt, SinkBuilderExt, StreamSink,
+        TowerRequestSettings,
+    },
+    template::Template,
+};
+
+/// How long to wait before preparing a statement again, when the cluster can't be reached.
+const PREPARE_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The partition of a row, which is only batched with the rows of the same partition.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct BatchKey {
+    keyspace: String,
+    table: String,
+    partition_key: Bytes,
+}
+
+/// An event, encoded as the values of a row, with the sizes it had before being transformed.
+struct EncodedRow {
+    key: BatchKey,
+    statement: PreparedStatement,
+    values: SerializedValues,
+    finalizers: EventFinalizers,
+    byte_size: usize,
+    json_size: usize,
+}
+
+impl ByteSizeOf for EncodedRow {
+    fn size_of(&self) -> usize {
+        self.values.size()
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
+struct RowPartitioner;
+
+impl Partitioner for RowPartitioner {
+    type Item = EncodedRow;
+    type Key = BatchKey;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        item.key.clone()
+    }
+}
+
+/// Encodes events as rows of the tables their templates render, whose statements are prepared as
+/// they are first written to.
+struct RowEncoder {
+    session: Arc<Session>,
+    keyspace: Template,
+    table: Template,
+    columns: Vec<String>,
+    fields: Vec<String>,
+    transformer: Transformer,
+    statements: Mutex<HashMap<(String, String), PreparedStatement>>,
+}
+
+impl RowEncoder {
+    /// Gets the statement inserting rows into `table`, waiting for the cluster to be reachable if
+    /// it has to be prepared.
+    async fn statement(
+        &self,
+        keyspace: &str,
+        table: &str,
+    ) -> Result<PreparedStatement, QueryError> {
+        let key = (keyspace.to_owned(), table.to_owned());
+        let cached = self
+            .statements
+            .lock()
+            .expect("poisoned lock")
+            .get(&key)
+            .cloned();
+        if let Some(statement) = cached {
+            return Ok(statement);
+        }
+
+        let query = insert_statement(keyspace, table, &self.columns);
+        let statement = loop {
+            match self.session.prepare(query.as_str()).await {
+                Ok(statement) => break statement,
+                Err(error) if is_retriable(&error) => {
+                    error!(message = "Failed to prepare the statement, retrying.", %error);
+                    tokio::time::sleep(PREPARE_RETRY_INTERVAL).await;
+                }
+                Err(error) => return Err(error),
+            }
+        };
+        self.statements
+            .lock()
+            .expect("poisoned lock")
+            .insert(key, statement.clone());
+        Ok(statement)
+    }
+
+    async fn encode(&self, mut event: Event) -> Option<EncodedRow> {
+        let keyspace = self.render(&self.keyspace, &event, "keyspace")?;
+        let table = self.render(&self.table, &event, "table")?;
+
+        let byte_size = event.size_of();
+        let json_size = event.estimated_json_encoded_size_of();
+        self.transformer.transform(&mut event);
+        let finalizers = event.take_finalizers();
+
+        let result = match self.statement(&keyspace, &table).await {
+            Ok(statement) => encode_row(&statement, &self.fields, event.as_log())
+                .map(|(values, partition_key)| (statement, values, partition_key))
+                .map_err(|error| error.to_string()),
+            Err(error) => Err(error.to_string()),
+        };
+        match result {
+            Ok((statement, values, partition_key)) => Some(EncodedRow {
+                key: BatchKey {
+                    keyspace,
+                    table,
+                    partition_key,
+                },
+                statement,
+                values,
+                finalizers,
+                byte_size,
+                json_size,
+            }),
+            Err(error) => {
+                emit!(CassandraEncodingError { error });
+                finalizers.update_status(EventStatus::Rejected);
+                None
+            }
+        }
+    }
+
+    fn render(&self, template: &Template, event: &Event, field: &'static str) -> Option<String> {
+        template
+            .render_string(event)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+pub struct CassandraSink {
+    pub session: Arc<Session>,
+    pub keyspace: Template,
+    pub table: Template,
+    /// The columns of the table mapped to the fields of events.
+    pub columns: Vec<(String, String)>,
+    pub consistency: Consistency,
+    pub batch_type: BatchType,
+    pub transformer: Transformer,
+    pub batch_settings: BatcherSettings,
+    pub request: TowerRequestSettings,
+}
+
+#[async_trait]
+impl StreamSink<Event> for CassandraSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let service = ServiceBuilder::new()
+            .settings(self.request, CassandraRetryLogic)
+            .service(CassandraService {
+                session: Arc::clone(&self.session),
+                consistency: self.consistency,
+                batch_type: self.batch_type,
+            });
+
+        let (columns, fields) = self.columns.into_iter().unzip();
+        let encoder = Arc::new(RowEncoder {
+            session: self.session,
+            keyspace: self.keyspace,
+            table: self.table,
+            columns,
+            fields,
+            transformer: self.transformer,
+            statements: Mutex::new(HashMap::new()),
+        });
+
+        input
+            .filter_map(move |event| {
+                let encoder = Arc::clone(&encoder);
+                async move { encoder.encode(event).await }
+            })
+            // Batches only hold rows of a single partition, so that each is sent to one of its
+            // replicas, which can apply it at once.
+            .batched_partitioned(RowPartitioner, self.batch_settings)
+            .map(|(_, rows)| {
+                let count = rows.len();
+                let mut byte_size = 0;
+                let mut json_size = 0;
+                let mut request_size = 0;
+                let mut finalizers = EventFinalizers::default();
+                let statement = rows[0].statement.clone();
+                let rows = rows
+                    .into_iter()
+                    .map(|row| {
+                        byte_size += row.byte_size;
+                        json_size += row.json_size;
+                        request_size += row.values.size();
+                        finalizers.merge(row.finalizers);
+                        row.values
+                    })
+                    .collect();
+                let metadata = RequestMetadataBuilder::new(count, byte_size, json_size)
+                    .with_request_size(
+                        NonZeroUsize::new(request_size.max(1)).expect("size should not be zero"),
+                    );
+
+                CassandraRequest {
+                    statement,
+                    rows,
+                    finalizers,
+                    metadata,
+                }
+            })
+            .into_driver(service)
+            .protocol("cql")
+            .run()
+            .await
+    }
+}
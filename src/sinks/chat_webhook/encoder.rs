@@ -0,0 +1,91 @@
+use serde_json::{json, Value as JsonValue};
+use vector_config::configurable_component;
+
+/// The chat platform of the webhook, which decides the format of messages.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatPlatform {
+    /// [Slack incoming webhooks][slack].
+    ///
+    /// [slack]: https://api.slack.com/messaging/webhooks
+    #[default]
+    Slack,
+
+    /// [Mattermost incoming webhooks][mattermost], which accept the messages of Slack.
+    ///
+    /// [mattermost]: https://developers.mattermost.com/integrate/webhooks/incoming/
+    Mattermost,
+
+    /// [Microsoft Teams webhooks][teams], whose messages are sent as Adaptive Cards.
+    ///
+    /// [teams]: https://learn.microsoft.com/en-us/microsoftteams/platform/webhooks-and-connectors/how-to/add-incoming-webhook
+    Teams,
+}
+
+impl ChatPlatform {
+    /// The longest text the platform shows in a message, in characters.
+    const fn max_text_length(self) -> usize {
+        match self {
+            Self::Slack => 40_000,
+            Self::Mattermost => 16_383,
+            Self::Teams => 20_000,
+        }
+    }
+
+    /// Encodes the payload of a message holding `text`, which is truncated to the length the
+    /// platform shows.
+    pub fn encode(self, text: &str) -> JsonValue {
+        let text = truncate(text, self.max_text_length());
+        match self {
+            Self::Slack | Self::Mattermost => json!({ "text": text }),
+            Self::Teams => json!({
+                "type": "message",
+                "attachments": [{
+                    "contentType": "application/vnd.microsoft.card.adaptive",
+                    "content": {
+                        "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                        "type": "AdaptiveCard",
+                        "version": "1.4",
+                        "body": [{ "type": "TextBlock", "text": text, "wrap": true }],
+                    },
+                }],
+            }),
+        }
+    }
+}
+
+fn truncate(text: &str, max_length: usize) -> String {
+    if text.chars().count() <= max_length {
+        return text.to_owned();
+    }
+    let end = text
+        .char_indices()
+        .nth(max_length.saturating_sub(1))
+        .map_or(text.len(), |(index, _)| index);
+    format!("{}…", &text[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_messages() {
+        assert_eq!(
+            ChatPlatform::Mattermost.encode("disk full"),
+            json!({ "text": "disk full" })
+        );
+        assert_eq!(
+            ChatPlatform::Teams.encode("disk full")["attachments"][0]["content"]["body"][0]["text"],
+            "disk full"
+        );
+    }
+
+    #[test]
+    fn truncates_long_texts() {
+        assert_eq!(truncate("abcdef", 4), "abc…");
+        assert_eq!(truncate("abcd", 4), "abcd");
+        assert_eq!(truncate("ééééé", 3), "éé…");
+    }
+}
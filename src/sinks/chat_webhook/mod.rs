@@ -0,0 +1,80 @@
+//! The `chat_webhook` sink.
+//!
+//! Posts events rendered by a template as the messages of Slack, Mattermost, or Microsoft Teams
+//! incoming webhooks, one message per event, or per batch of events.
+
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+pub use self::config::ChatWebhookConfig;
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use http::{Response, StatusCode};
+    use hyper::Body;
+    use serde_json::{json, Value as JsonValue};
+    use vector_core::event::{BatchNotifier, BatchStatus, Event, LogEvent};
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        sinks::util::test::build_test_server_generic,
+        test_util::{
+            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+            next_addr,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ChatWebhookConfig>();
+    }
+
+    #[tokio::test]
+    async fn aggregates_batches() {
+        let addr = next_addr();
+        let config: ChatWebhookConfig = toml::from_str(&format!(
+            r#"endpoint = "http://{}/hooks/alerts"
+            platform = "mattermost"
+            text = "{{{{ host }}}}: {{{{ message }}}}"
+            mode = "aggregated"
+            batch.timeout_secs = 0.1"#,
+            addr
+        ))
+        .unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (mut rx, trigger, server) = build_test_server_generic(addr, || {
+            Response::builder()
+                .status(StatusCode::OK)
+                .body(Body::from("ok"))
+                .unwrap()
+        });
+        tokio::spawn(server);
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let events: Vec<_> = ["db-1", "db-2"]
+            .into_iter()
+            .map(|host| {
+                let mut log = LogEvent::from("disk full").with_batch_notifier(&batch);
+                log.insert("host", host);
+                Event::from(log)
+            })
+            .collect();
+        drop(batch);
+        run_and_assert_sink_compliance(sink, futures::stream::iter(events), &HTTP_SINK_TAGS).await;
+        drop(trigger);
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let (parts, body) = rx.next().await.unwrap();
+        assert_eq!(parts.uri.path(), "/hooks/alerts");
+        let message: JsonValue = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            message,
+            json!({ "text": "db-1: disk full\ndb-2: disk full" })
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use http::{header::CONTENT_TYPE, Request, StatusCode, Uri};
+use snafu::{ResultExt, Snafu};
+use tower::Service;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    stream::DriverResponse,
+};
+
+use crate::{
+    http::{HttpClient, HttpError},
+    sinks::util::retries::RetryLogic,
+};
+
+#[derive(Debug, Snafu)]
+pub enum ChatWebhookError {
+    #[snafu(display("Request failed: {}", source))]
+    Http { source: HttpError },
+    #[snafu(display("Request failed with status {}: {}", status, body))]
+    Status { status: StatusCode, body: String },
+}
+
+/// A message, encoded as the JSON payload of the webhook.
+#[derive(Clone)]
+pub struct ChatWebhookRequest {
+    pub body: Bytes,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for ChatWebhookRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for ChatWebhookRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata.clone()
+    }
+}
+
+pub struct ChatWebhookResponse;
+
+impl DriverResponse for ChatWebhookResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+}
+
+#[derive(Clone)]
+pub struct ChatWebhookService {
+    pub client: HttpClient,
+    pub endpoint: Uri,
+}
+
+impl Service<ChatWebhookRequest> for ChatWebhookService {
+    type Response = ChatWebhookResponse;
+    type Error = ChatWebhookError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: ChatWebhookRequest) -> Self::Future {
+        let client = self.client.clone();
+        let http_request = Request::post(&self.endpoint)
+            .header(CONTENT_TYPE, "application/json")
+            .body(hyper::Body::from(request.body))
+            .expect("Invalid request");
+
+        Box::pin(async move {
+            let response = client.send(http_request).await.context(HttpSnafu)?;
+            let status = response.status();
+            if !status.is_success() {
+                // The body of errors explains why messages were rejected, such as `invalid_payload`.
+                let body = hyper::body::to_bytes(response.into_body())
+                    .await
+                    .map(|body| String::from_utf8_lossy(&body).into_owned())
+                    .unwrap_or_default();
+                return Err(ChatWebhookError::Status { status, body });
+            }
+
+            Ok(ChatWebhookResponse)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatWebhookRetryLogic;
+
+impl RetryLogic for ChatWebhookRetryLogic {
+    type Error = ChatWebhookError;
+    type Response = ChatWebhookResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            ChatWebhookError::Http { .. } => true,
+            ChatWebhookError::Status { status, .. } => {
+                *status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::num::NonZeroUsize;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{stream::BoxStream, StreamExt};
+use tower::ServiceBuilder;
+use vector_core::{
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    stream::BatcherSettings,
+};
+
+use super::{
+    config::MessageMode,
+    encoder::ChatPlatform,
+    service::{ChatWebhookRequest, ChatWebhookRetryLogic, ChatWebhookService},
+};
+use crate::{
+    emit,
+    internal_events::TemplateRenderingError,
+    sinks::util::{
+        metadata::RequestMetadataBuilder, ServiceBuilderExt, SinkBuilderExt, StreamSink,
+        TowerRequestSettings,
+    },
+    template::Template,
+};
+
+pub struct ChatWebhookSink {
+    pub service: ChatWebhookService,
+    pub protocol: String,
+    pub platform: ChatPlatform,
+    pub text: Template,
+    pub mode: MessageMode,
+    pub batch_settings: BatcherSettings,
+    pub request: TowerRequestSettings,
+}
+
+/// Renders the text of `event`, or returns `None` after rejecting it when its template fails to
+/// render.
+fn render(text: &Template, mut event: Event) -> Option<(String, EventFinalizers)> {
+    let finalizers = event.take_finalizers();
+    match text.render_string(&event) {
+        Ok(text) => Some((text, finalizers)),
+        Err(error) => {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some("text"),
+                drop_event: true,
+            });
+            finalizers.update_status(EventStatus::Rejected);
+            None
+        }
+    }
+}
+
+impl ChatWebhookSink {
+    fn request(
+        platform: ChatPlatform,
+        text: &str,
+        finalizers: EventFinalizers,
+        builder: &RequestMetadataBuilder,
+    ) -> ChatWebhookRequest {
+        let body = Bytes::from(
+            serde_json::to_vec(&platform.encode(text)).expect("messages should serialize"),
+        );
+        let metadata = builder.with_request_size(
+            NonZeroUsize::new(body.len()).expect("messages should not be empty"),
+        );
+        ChatWebhookRequest {
+            body,
+            finalizers,
+            metadata,
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for ChatWebhookSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let sink = *self;
+        let service = ServiceBuilder::new()
+            .settings(sink.request, ChatWebhookRetryLogic)
+            .service(sink.service);
+        let platform = sink.platform;
+        let text = sink.text;
+
+        let requests = match sink.mode {
+            MessageMode::Individual => input
+                .filter_map(move |event| {
+                    let builder = RequestMetadataBuilder::from_events(&event);
+                    let request = render(&text, event).map(|(text, finalizers)| {
+                        Self::request(platform, &text, finalizers, &builder)
+                    });
+                    futures::future::ready(request)
+                })
+                .boxed(),
+            // The texts of the events of batches are sent as the lines of a single message.
+            MessageMode::Aggregated => input
+                .batched(sink.batch_settings.into_byte_size_config())
+                .filter_map(move |events| {
+                    let builder = RequestMetadataBuilder::from_events(&events);
+                    let mut lines = Vec::with_capacity(events.len());
+                    let mut finalizers = EventFinalizers::default();
+                    for event in events {
+                        if let Some((line, event_finalizers)) = render(&text, event) {
+                            lines.push(line);
+                            finalizers.merge(event_finalizers);
+                        }
+                    }
+                    let request = (!lines.is_empty())
+                        .then(|| Self::request(platform, &lines.join("\n"), finalizers, &builder));
+                    futures::future::ready(request)
+                })
+                .boxed(),
+        };
+
+        requests
+            .into_driver(service)
+            .protocol(sink.protocol)
+            .run()
+            .await
+    }
+}
@@ -1,3 +1,4 @@
+use derivative::Derivative;
 use vector_config::configurable_component;
 
 use crate::{
@@ -14,7 +15,7 @@ use crate::{
     tls::TlsConfig,
 };
 
-use super::http_sink::build_http_sink;
+use super::{http_sink::build_http_sink, native_sink::build_native_sink};
 
 /// Configuration for the `clickhouse` sink.
 #[configurable_component(sink("clickhouse"))]
@@ -22,9 +23,16 @@ use super::http_sink::build_http_sink;
 #[serde(deny_unknown_fields)]
 pub struct ClickhouseConfig {
     /// The endpoint of the Clickhouse server.
+    ///
+    /// With the `native` protocol, this is the address of its TCP interface, such as
+    /// `tcp://localhost:9000`.
     #[serde(alias = "host")]
     pub endpoint: UriSerde,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub protocol: Protocol,
+
     /// The table that data will be inserted into.
     pub table: String,
 
@@ -35,6 +43,12 @@ pub struct ClickhouseConfig {
     #[serde(default)]
     pub skip_unknown_fields: bool,
 
+    /// Sets `async_insert`, having Clickhouse buffer inserted data and write it in larger parts.
+    ///
+    /// Inserts are still only acknowledged once their data is written.
+    #[serde(default)]
+    pub async_insert: bool,
+
     #[configurable(derived)]
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
@@ -69,14 +83,35 @@ pub struct ClickhouseConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// The protocol used to insert data into Clickhouse.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Protocol {
+    /// Insert rows encoded as `JSONEachRow` through the HTTP interface.
+    #[derivative(Default)]
+    Http,
+
+    /// Insert blocks of columns through the native TCP interface.
+    ///
+    /// The fields of events are converted to the types of the columns of the table, which is
+    /// cheaper for both Vector and Clickhouse than parsing JSON. Blocks are compressed with LZ4
+    /// unless `compression` is `none`.
+    ///
+    /// TLS and bearer authentication are not supported with this protocol.
+    Native,
+}
+
 impl_generate_config_from_default!(ClickhouseConfig);
 
 #[async_trait::async_trait]
 impl SinkConfig for ClickhouseConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        // later we can build different sink(http, native) here
-        // according to the clickhouseConfig
-        build_http_sink(self, cx).await
+        match self.protocol {
+            Protocol::Http => build_http_sink(self, cx).await,
+            Protocol::Native => build_native_sink(self).await,
+        }
     }
 
     fn input(&self) -> Input {
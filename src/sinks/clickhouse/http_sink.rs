@@ -34,6 +34,7 @@ pub(crate) async fn build_http_sink(
         auth: cfg.auth.choose_one(&cfg.endpoint.auth)?,
         ..cfg.clone()
     };
+    config.auth.ensure_static()?;
 
     let sink = BatchedHttpSink::with_logic(
         config.clone(),
@@ -90,6 +91,7 @@ impl HttpSink for ClickhouseConfig {
             database,
             &self.table,
             self.skip_unknown_fields,
+            self.async_insert,
         )
         .expect("Unable to encode uri");
 
@@ -126,7 +128,13 @@ async fn healthcheck(client: HttpClient, config: ClickhouseConfig) -> crate::Res
     }
 }
 
-fn set_uri_query(uri: &Uri, database: &str, table: &str, skip_unknown: bool) -> crate::Result<Uri> {
+fn set_uri_query(
+    uri: &Uri,
+    database: &str,
+    table: &str,
+    skip_unknown: bool,
+    async_insert: bool,
+) -> crate::Result<Uri> {
     let query = url::form_urlencoded::Serializer::new(String::new())
         .append_pair(
             "query",
@@ -147,6 +155,9 @@ fn set_uri_query(uri: &Uri, database: &str, table: &str, skip_unknown: bool) ->
     if skip_unknown {
         uri.push_str("input_format_skip_unknown_fields=1&");
     }
+    if async_insert {
+        uri.push_str("async_insert=1&");
+    }
     uri.push_str(query.as_str());
 
     uri.parse::<Uri>()
@@ -209,6 +220,7 @@ mod tests {
             "my_database",
             "my_table",
             false,
+            false,
         )
         .unwrap();
         assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&query=INSERT+INTO+%22my_database%22.%22my_table%22+FORMAT+JSONEachRow");
@@ -218,9 +230,20 @@ mod tests {
             "my_database",
             "my_\"table\"",
             false,
+            false,
         )
         .unwrap();
         assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&query=INSERT+INTO+%22my_database%22.%22my_%5C%22table%5C%22%22+FORMAT+JSONEachRow");
+
+        let uri = set_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "my_database",
+            "my_table",
+            false,
+            true,
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&async_insert=1&query=INSERT+INTO+%22my_database%22.%22my_table%22+FORMAT+JSONEachRow");
     }
 
     #[test]
@@ -230,6 +253,7 @@ mod tests {
             "my_database",
             "my_table",
             false,
+            false,
         )
         .unwrap_err();
     }
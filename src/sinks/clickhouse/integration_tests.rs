@@ -24,7 +24,7 @@ use crate::{
     config::{log_schema, SinkConfig, SinkContext},
     sinks::util::{BatchConfig, Compression, TowerRequestConfig},
     test_util::{
-        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS, SINK_TAGS},
         random_string, trace_init,
     },
 };
@@ -33,6 +33,10 @@ fn clickhouse_address() -> String {
     std::env::var("CLICKHOUSE_ADDRESS").unwrap_or_else(|_| "http://localhost:8123".into())
 }
 
+fn clickhouse_native_address() -> String {
+    std::env::var("CLICKHOUSE_NATIVE_ADDRESS").unwrap_or_else(|_| "tcp://localhost:9000".into())
+}
+
 #[tokio::test]
 async fn insert_events() {
     trace_init();
@@ -86,6 +90,78 @@ async fn insert_events() {
     assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
 }
 
+#[tokio::test]
+async fn insert_events_native() {
+    trace_init();
+
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(2);
+
+    let config = ClickhouseConfig {
+        endpoint: clickhouse_native_address().parse().unwrap(),
+        protocol: Protocol::Native,
+        table: table.clone(),
+        batch,
+        request: TowerRequestConfig {
+            retry_attempts: Some(1),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(host);
+    client
+        .create_table(
+            &table,
+            "host String, timestamp DateTime, message String, count Nullable(UInt32), items Array(String)",
+        )
+        .await;
+
+    let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (mut first_event, mut receiver) = make_event();
+    first_event.as_mut_log().insert("count", 3);
+    first_event
+        .as_mut_log()
+        .insert("items", vec!["item1", "item2"]);
+    let (mut second_event, _) = make_event();
+    second_event.as_mut_log().insert("host", "example.org");
+
+    run_and_assert_sink_compliance(
+        sink,
+        stream::iter(vec![first_event.clone(), second_event]),
+        &SINK_TAGS,
+    )
+    .await;
+
+    let output = client.select_all(&table).await;
+    assert_eq!(2, output.rows);
+
+    let first_log = first_event.into_log();
+    assert_eq!(output.data[0]["message"], "raw log line");
+    assert_eq!(output.data[0]["count"], 3);
+    assert_eq!(
+        output.data[0]["items"],
+        serde_json::json!(["item1", "item2"])
+    );
+    assert_eq!(
+        output.data[0]["timestamp"],
+        first_log
+            .get(log_schema().timestamp_key())
+            .and_then(|timestamp| timestamp.as_timestamp())
+            .unwrap()
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    );
+    assert_eq!(output.data[1]["count"], Value::Null);
+    assert_eq!(output.data[1]["items"], serde_json::json!([]));
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+}
+
 #[tokio::test]
 async fn skip_unknown_fields() {
     trace_init();
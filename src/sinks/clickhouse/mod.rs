@@ -2,4 +2,5 @@ mod config;
 mod http_sink;
 #[cfg(all(test, feature = "clickhouse-integration-tests"))]
 mod integration_tests;
-pub use self::config::ClickhouseConfig;
+mod native_sink;
+pub use self::config::{ClickhouseConfig, Protocol};
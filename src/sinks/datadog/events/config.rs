@@ -12,13 +12,14 @@ use crate::{
         datadog::{
             events::{
                 service::{DatadogEventsResponse, DatadogEventsService},
-                sink::DatadogEventsSink,
+                sink::{DatadogEventsSink, EventTemplates},
             },
             get_api_base_endpoint, get_api_validate_endpoint, healthcheck, Region,
         },
         util::{http::HttpStatusRetryLogic, ServiceBuilderExt, TowerRequestConfig},
         Healthcheck, VectorSink,
     },
+    template::Template,
     tls::{MaybeTlsSettings, TlsEnableableConfig},
 };
 
@@ -49,6 +50,33 @@ pub struct DatadogEventsConfig {
     /// [api_key]: https://docs.datadoghq.com/api/?lang=bash#authentication
     pub default_api_key: SensitiveString,
 
+    /// The template of the title of events.
+    ///
+    /// By default, the `title` field of events is used, and events without one are dropped.
+    #[configurable(metadata(docs::examples = "{{ service }} is unhealthy"))]
+    pub title: Option<Template>,
+
+    /// The template of the text of events.
+    ///
+    /// By default, the `text` field of events is used, or their message if they don't have one.
+    #[configurable(metadata(docs::examples = "{{ message }}"))]
+    pub text: Option<Template>,
+
+    /// The templates of the tags added to events.
+    ///
+    /// Tags are added to those of the `tags` field of events.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "env:{{ environment }}"))]
+    pub tags: Vec<Template>,
+
+    /// The template of the alert type of events.
+    ///
+    /// It must render to one of `error`, `warning`, `info`, `success`, `user_update`,
+    /// `recommendation`, or `snapshot`. By default, the `alert_type` field of events is used, and
+    /// Datadog considers events without one as `info`.
+    #[configurable(metadata(docs::examples = "{{ level }}"))]
+    pub alert_type: Option<Template>,
+
     #[configurable(derived)]
     pub(super) tls: Option<TlsEnableableConfig>,
 
@@ -115,7 +143,15 @@ impl DatadogEventsConfig {
             .settings(request_settings, retry_logic)
             .service(service);
 
-        let sink = DatadogEventsSink { service };
+        let sink = DatadogEventsSink {
+            service,
+            templates: EventTemplates {
+                title: self.title.clone(),
+                text: self.text.clone(),
+                tags: self.tags.clone(),
+                alert_type: self.alert_type.clone(),
+            },
+        };
 
         Ok(VectorSink::from_event_streamsink(sink))
     }
@@ -43,7 +43,7 @@ impl ElementCount for DatadogEventsRequest {
 
 impl MetaDescriptive for DatadogEventsRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.request_metadata
+        self.request_metadata.clone()
     }
 }
 
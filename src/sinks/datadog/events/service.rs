@@ -8,8 +8,7 @@ use futures::{
 use http::Request;
 use hyper::Body;
 use tower::{Service, ServiceExt};
-use vector_common::request_metadata::MetaDescriptive;
-use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
+use vector_core::stream::DriverResponse;
 
 use crate::{
     event::EventStatus,
@@ -23,22 +22,12 @@ use crate::{
 pub struct DatadogEventsResponse {
     pub(self) event_status: EventStatus,
     pub http_status: http::StatusCode,
-    pub event_byte_size: usize,
 }
 
 impl DriverResponse for DatadogEventsResponse {
     fn event_status(&self) -> EventStatus {
         self.event_status
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(1, self.event_byte_size)
-    }
-
-    fn bytes_sent(&self) -> Option<usize> {
-        // HttpBatchService emits EndpointBytesSend
-        None
-    }
 }
 
 #[derive(Clone)]
@@ -90,7 +79,6 @@ impl Service<DatadogEventsRequest> for DatadogEventsService {
 
         Box::pin(async move {
             http_service.ready().await?;
-            let event_byte_size = req.get_metadata().events_byte_size();
             let http_response = http_service.call(req).await?;
             let event_status = if http_response.is_successful() {
                 EventStatus::Delivered
@@ -102,7 +90,6 @@ impl Service<DatadogEventsRequest> for DatadogEventsService {
             Ok(DatadogEventsResponse {
                 event_status,
                 http_status: http_response.status(),
-                event_byte_size,
             })
         })
     }
@@ -7,16 +7,83 @@ use vector_core::stream::DriverResponse;
 
 use crate::{
     config::log_schema,
-    event::Event,
-    internal_events::{ParserMissingFieldError, SinkRequestBuildError, DROP_EVENT},
+    event::{Event, Value},
+    internal_events::{
+        ParserMissingFieldError, SinkRequestBuildError, TemplateRenderingError, DROP_EVENT,
+    },
     sinks::{
         datadog::events::request_builder::{DatadogEventsRequest, DatadogEventsRequestBuilder},
         util::{SinkBuilderExt, StreamSink},
     },
+    template::Template,
 };
 
 pub struct DatadogEventsSink<S> {
     pub(super) service: S,
+    pub(super) templates: EventTemplates,
+}
+
+/// The templates of the fields of events, which take precedence over the fields themselves.
+#[derive(Clone, Debug, Default)]
+pub(super) struct EventTemplates {
+    pub(super) title: Option<Template>,
+    pub(super) text: Option<Template>,
+    pub(super) tags: Vec<Template>,
+    pub(super) alert_type: Option<Template>,
+}
+
+impl EventTemplates {
+    fn render(template: &Template, event: &Event, field: &'static str) -> Option<String> {
+        template
+            .render_string(event)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: false,
+                })
+            })
+            .ok()
+    }
+
+    /// Sets the fields of `event` rendered from the templates. Fields whose template fails to
+    /// render are left as they are.
+    fn apply(&self, event: Event) -> Event {
+        let fields = [
+            ("title", self.title.as_ref()),
+            ("text", self.text.as_ref()),
+            ("alert_type", self.alert_type.as_ref()),
+        ];
+        let rendered = fields
+            .into_iter()
+            .filter_map(|(field, template)| {
+                template
+                    .and_then(|template| Self::render(template, &event, field))
+                    .map(|value| (field, value))
+            })
+            .collect::<Vec<_>>();
+        let tags = self
+            .tags
+            .iter()
+            .filter_map(|template| Self::render(template, &event, "tags"))
+            .collect::<Vec<_>>();
+
+        let mut log = event.into_log();
+        for (field, value) in rendered {
+            log.insert(field, value);
+        }
+        if !tags.is_empty() {
+            let mut all_tags = match log.remove("tags") {
+                Some(Value::Array(existing)) => existing,
+                Some(existing) => vec![existing],
+                None => Vec::new(),
+            };
+            all_tags.extend(tags.into_iter().map(Value::from));
+            log.insert("tags", Value::Array(all_tags));
+        }
+
+        Event::from(log)
+    }
 }
 
 impl<S> DatadogEventsSink<S>
@@ -28,8 +95,10 @@ where
 {
     async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let concurrency_limit = NonZeroUsize::new(50);
+        let templates = self.templates;
 
         input
+            .map(move |event| templates.apply(event))
             .filter_map(ensure_required_fields)
             .request_builder(concurrency_limit, DatadogEventsRequestBuilder::new())
             .filter_map(|request| async move {
@@ -14,7 +14,7 @@ use vector_core::event::{BatchNotifier, BatchStatus};
 use super::*;
 use crate::{
     config::SinkConfig,
-    event::EventArray,
+    event::{EventArray, LogEvent},
     sinks::util::test::{build_test_server_status, load_sink},
     test_util::{
         components::{self, COMPONENT_ERROR_TAGS, HTTP_SINK_TAGS},
@@ -180,3 +180,42 @@ async fn filter_out_fields() {
         assert!(json.get("invalid").is_none());
     }
 }
+
+#[tokio::test]
+async fn templated_fields() {
+    let (mut config, cx) = load_sink::<DatadogEventsConfig>(indoc! {r#"
+            default_api_key = "atoken"
+            title = "{{ service }} is unhealthy"
+            tags = ["env:{{ environment }}"]
+            alert_type = "{{ level }}"
+        "#})
+    .unwrap();
+
+    let addr = next_addr();
+    config.endpoint = Some(format!("http://{}", addr));
+
+    let (sink, _) = config.build(cx).await.unwrap();
+
+    let (rx, _trigger, server) = build_test_server_status(addr, StatusCode::OK);
+    tokio::spawn(server);
+
+    let mut log = LogEvent::from("connection refused");
+    log.insert("service", "api");
+    log.insert("environment", "prod");
+    log.insert("level", "error");
+    log.insert("tags", vec!["team:core"]);
+
+    components::run_and_assert_sink_compliance(
+        sink,
+        futures::stream::once(async { EventArray::from(log) }),
+        &HTTP_SINK_TAGS,
+    )
+    .await;
+    let output = rx.take(1).collect::<Vec<_>>().await;
+
+    let json = serde_json::from_slice::<serde_json::Value>(&output[0].1[..]).unwrap();
+    assert_eq!(json["title"], "api is unhealthy");
+    assert_eq!(json["text"], "connection refused");
+    assert_eq!(json["alert_type"], "error");
+    assert_eq!(json["tags"], serde_json::json!(["team:core", "env:prod"]));
+}
@@ -15,7 +15,6 @@ use tracing::Instrument;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_core::{
     event::{EventFinalizers, EventStatus, Finalizable},
-    internal_event::CountByteSize,
     stream::DriverResponse,
 };
 
@@ -43,7 +42,6 @@ pub struct LogApiRequest {
     pub compression: Compression,
     pub body: Bytes,
     pub finalizers: EventFinalizers,
-    pub uncompressed_size: usize,
     pub metadata: RequestMetadata,
 }
 
@@ -55,30 +53,19 @@ impl Finalizable for LogApiRequest {
 
 impl MetaDescriptive for LogApiRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
 
 #[derive(Debug)]
 pub struct LogApiResponse {
     event_status: EventStatus,
-    count: usize,
-    events_byte_size: usize,
-    raw_byte_size: usize,
 }
 
 impl DriverResponse for LogApiResponse {
     fn event_status(&self) -> EventStatus {
         self.event_status
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(self.count, self.events_byte_size)
-    }
-
-    fn bytes_sent(&self) -> Option<usize> {
-        Some(self.raw_byte_size)
-    }
 }
 
 /// Wrapper for the Datadog API.
@@ -135,10 +122,6 @@ impl Service<LogApiRequest> for LogApiService {
             http_request
         };
 
-        let count = request.get_metadata().event_count();
-        let events_byte_size = request.get_metadata().events_byte_size();
-        let raw_byte_size = request.uncompressed_size;
-
         let http_request = http_request
             .header(CONTENT_LENGTH, request.body.len())
             .body(Body::from(request.body))
@@ -148,9 +131,6 @@ impl Service<LogApiRequest> for LogApiService {
             DatadogApiError::from_result(client.call(http_request).in_current_span().await).map(
                 |_| LogApiResponse {
                     event_status: EventStatus::Delivered,
-                    count,
-                    events_byte_size,
-                    raw_byte_size,
                 },
             )
         })
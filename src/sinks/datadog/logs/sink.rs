@@ -273,14 +273,12 @@ impl RequestBuilder<(Option<Arc<str>>, Vec<Event>)> for LogRequestBuilder {
         payload: EncodeResult<Self::Payload>,
     ) -> Self::Request {
         let (api_key, finalizers) = dd_metadata;
-        let uncompressed_size = payload.uncompressed_byte_size;
 
         LogApiRequest {
             api_key,
             compression: self.compression,
             body: payload.into_payload(),
             finalizers,
-            uncompressed_size,
             metadata,
         }
     }
@@ -364,14 +362,12 @@ impl RequestBuilder<(Option<Arc<str>>, Vec<Event>)> for SemanticLogRequestBuilde
         payload: EncodeResult<Self::Payload>,
     ) -> Self::Request {
         let (api_key, finalizers) = dd_metadata;
-        let uncompressed_size = payload.uncompressed_byte_size;
 
         LogApiRequest {
             api_key,
             compression: self.compression,
             body: payload.into_payload(),
             finalizers,
-            uncompressed_size,
             metadata,
         }
     }
@@ -1,7 +1,7 @@
 use bytes::Bytes;
 use serde_json::error::Category;
 use snafu::Snafu;
-use std::{num::NonZeroUsize, sync::Arc};
+use std::sync::Arc;
 use vector_common::request_metadata::RequestMetadata;
 use vector_core::event::{EventFinalizers, Finalizable, Metric};
 
@@ -115,7 +115,6 @@ pub struct DDMetricsMetadata {
     api_key: Option<Arc<str>>,
     endpoint: DatadogMetricsEndpoint,
     finalizers: EventFinalizers,
-    raw_bytes: usize,
 }
 
 /// Incremental request builder specific to Datadog metrics.
@@ -209,21 +208,14 @@ impl IncrementalRequestBuilder<((Option<Arc<str>>, DatadogMetricsEndpoint), Vec<
             if n > 0 {
                 match encoder.finish() {
                     Ok((payload, mut metrics, raw_bytes_written)) => {
+                        let request_metadata =
+                            request_metadata(&metrics, raw_bytes_written, &payload);
                         let finalizers = metrics.take_finalizers();
                         let metadata = DDMetricsMetadata {
                             api_key: api_key.as_ref().map(Arc::clone),
                             endpoint,
                             finalizers,
-                            raw_bytes: raw_bytes_written,
                         };
-                        let builder = RequestMetadataBuilder::new(
-                            metrics.len(),
-                            raw_bytes_written,
-                            raw_bytes_written,
-                        );
-                        let bytes_len = NonZeroUsize::new(payload.len())
-                            .expect("payload should never be zero length");
-                        let request_metadata = builder.with_request_size(bytes_len);
                         results.push(Ok(((metadata, request_metadata), payload)));
                     }
                     Err(err) => match err {
@@ -292,11 +284,10 @@ impl IncrementalRequestBuilder<((Option<Arc<str>>, DatadogMetricsEndpoint), Vec<
         DatadogMetricsRequest {
             api_key: ddmetrics_metadata.api_key,
             payload,
-            uri,
             content_type: ddmetrics_metadata.endpoint.content_type(),
             finalizers: ddmetrics_metadata.finalizers,
-            raw_bytes: ddmetrics_metadata.raw_bytes,
-            metadata: request_metadata,
+            metadata: request_metadata.with_endpoint(uri.to_string()),
+            uri,
         }
     }
 }
@@ -329,18 +320,13 @@ fn encode_now_or_never(
     encoder
         .finish()
         .map(|(payload, mut processed, raw_bytes_written)| {
+            let request_metadata = request_metadata(&processed, raw_bytes_written, &payload);
             let finalizers = processed.take_finalizers();
             let ddmetrics_metadata = DDMetricsMetadata {
                 api_key,
                 endpoint,
                 finalizers,
-                raw_bytes: raw_bytes_written,
             };
-            let builder =
-                RequestMetadataBuilder::new(metrics_len, raw_bytes_written, raw_bytes_written);
-            let bytes_len =
-                NonZeroUsize::new(payload.len()).expect("payload should never be zero length");
-            let request_metadata = builder.with_request_size(bytes_len);
 
             ((ddmetrics_metadata, request_metadata), payload)
         })
@@ -348,3 +334,17 @@ fn encode_now_or_never(
             dropped_events: metrics_len as u64,
         })
 }
+
+/// Builds the metadata of a request whose payload encodes `metrics`, in `raw_bytes_written` bytes
+/// before compression.
+fn request_metadata(
+    metrics: &[Metric],
+    raw_bytes_written: usize,
+    payload: &Bytes,
+) -> RequestMetadata {
+    let mut builder = RequestMetadataBuilder::default();
+    for metric in metrics {
+        builder.track_event(metric);
+    }
+    builder.with_request_sizes(raw_bytes_written, payload.len())
+}
@@ -13,7 +13,6 @@ use tower::Service;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_core::{
     event::{EventFinalizers, EventStatus, Finalizable},
-    internal_event::CountByteSize,
     stream::DriverResponse,
 };
 
@@ -61,7 +60,6 @@ pub struct DatadogMetricsRequest {
     pub uri: Uri,
     pub content_type: &'static str,
     pub finalizers: EventFinalizers,
-    pub raw_bytes: usize,
     pub metadata: RequestMetadata,
 }
 
@@ -113,7 +111,7 @@ impl Finalizable for DatadogMetricsRequest {
 
 impl MetaDescriptive for DatadogMetricsRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
 
@@ -122,9 +120,6 @@ impl MetaDescriptive for DatadogMetricsRequest {
 pub struct DatadogMetricsResponse {
     status_code: StatusCode,
     body: Bytes,
-    batch_size: usize,
-    byte_size: usize,
-    raw_byte_size: usize,
 }
 
 impl DriverResponse for DatadogMetricsResponse {
@@ -137,14 +132,6 @@ impl DriverResponse for DatadogMetricsResponse {
             EventStatus::Errored
         }
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(self.batch_size, self.byte_size)
-    }
-
-    fn bytes_sent(&self) -> Option<usize> {
-        Some(self.raw_byte_size)
-    }
 }
 
 #[derive(Clone)]
@@ -182,10 +169,6 @@ impl Service<DatadogMetricsRequest> for DatadogMetricsService {
         let api_key = self.api_key.clone();
 
         Box::pin(async move {
-            let byte_size = request.get_metadata().events_byte_size();
-            let batch_size = request.get_metadata().event_count();
-            let raw_byte_size = request.raw_bytes;
-
             let request = request
                 .into_http_request(api_key)
                 .context(BuildRequestSnafu)
@@ -203,9 +186,6 @@ impl Service<DatadogMetricsRequest> for DatadogMetricsService {
             Ok(DatadogMetricsResponse {
                 status_code: parts.status,
                 body,
-                batch_size,
-                byte_size,
-                raw_byte_size,
             })
         })
     }
@@ -150,12 +150,10 @@ impl ApmStatsSender {
                 reason: e.to_string(),
                 dropped_events: 0,
             })?;
-        let uncompressed_size = encoded_payload.len();
         let metadata = DDTracesMetadata {
             api_key,
             endpoint: DatadogTracesEndpoint::APMStats,
             finalizers: EventFinalizers::default(),
-            uncompressed_size,
             content_type: "application/msgpack".to_string(),
         };
 
@@ -1,7 +1,6 @@
 use std::{
     collections::BTreeMap,
     io::Write,
-    num::NonZeroUsize,
     sync::{Arc, Mutex},
 };
 
@@ -90,7 +89,6 @@ pub struct DDTracesMetadata {
     pub api_key: Arc<str>,
     pub endpoint: DatadogTracesEndpoint,
     pub finalizers: EventFinalizers,
-    pub uncompressed_size: usize,
     pub content_type: String,
 }
 
@@ -122,6 +120,7 @@ impl IncrementalRequestBuilder<(PartitionKey, Vec<Event>)> for DatadogTracesRequ
             .for_each(|r| match r {
                 Ok((payload, mut processed)) => {
                     let uncompressed_size = payload.len();
+                    let metadata_builder = RequestMetadataBuilder::from_events(&processed);
                     let metadata = DDTracesMetadata {
                         api_key: key
                             .api_key
@@ -129,7 +128,6 @@ impl IncrementalRequestBuilder<(PartitionKey, Vec<Event>)> for DatadogTracesRequ
                             .unwrap_or_else(|| Arc::clone(&self.api_key)),
                         endpoint: DatadogTracesEndpoint::Traces,
                         finalizers: processed.take_finalizers(),
-                        uncompressed_size,
                         content_type: "application/x-protobuf".to_string(),
                     };
 
@@ -138,15 +136,8 @@ impl IncrementalRequestBuilder<(PartitionKey, Vec<Event>)> for DatadogTracesRequ
                         Ok(()) => {
                             let bytes = compressor.into_inner().freeze();
 
-                            // build RequestMetadata
-                            let builder = RequestMetadataBuilder::new(
-                                n,
-                                uncompressed_size,
-                                uncompressed_size,
-                            );
-                            let bytes_len = NonZeroUsize::new(bytes.len())
-                                .expect("payload should never be zero length");
-                            let request_metadata = builder.with_request_size(bytes_len);
+                            let request_metadata =
+                                metadata_builder.with_request_sizes(uncompressed_size, bytes.len());
 
                             results.push(Ok(((metadata, request_metadata), bytes)))
                         }
@@ -200,13 +191,13 @@ pub fn build_request(
     if let Some(ce) = compression.content_encoding() {
         headers.insert("Content-Encoding".to_string(), ce.to_string());
     }
+    let uri = endpoint_configuration.get_uri_for_endpoint(ddtraces_metadata.endpoint);
     TraceApiRequest {
         body: payload,
         headers,
         finalizers: ddtraces_metadata.finalizers,
-        uri: endpoint_configuration.get_uri_for_endpoint(ddtraces_metadata.endpoint),
-        uncompressed_size: ddtraces_metadata.uncompressed_size,
-        metadata: request_metadata,
+        metadata: request_metadata.with_endpoint(uri.to_string()),
+        uri,
     }
 }
 
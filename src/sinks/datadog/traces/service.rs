@@ -12,7 +12,6 @@ use tower::Service;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_core::{
     event::{EventFinalizers, EventStatus, Finalizable},
-    internal_event::CountByteSize,
     stream::DriverResponse,
 };
 
@@ -60,7 +59,6 @@ pub struct TraceApiRequest {
     pub headers: BTreeMap<String, String>,
     pub finalizers: EventFinalizers,
     pub uri: Uri,
-    pub uncompressed_size: usize,
     pub metadata: RequestMetadata,
 }
 
@@ -82,7 +80,7 @@ impl Finalizable for TraceApiRequest {
 
 impl MetaDescriptive for TraceApiRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
 
@@ -90,9 +88,6 @@ impl MetaDescriptive for TraceApiRequest {
 pub struct TraceApiResponse {
     status_code: StatusCode,
     body: Bytes,
-    batch_size: usize,
-    byte_size: usize,
-    uncompressed_size: usize,
 }
 
 impl DriverResponse for TraceApiResponse {
@@ -105,14 +100,6 @@ impl DriverResponse for TraceApiResponse {
             EventStatus::Errored
         }
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(self.batch_size, self.byte_size)
-    }
-
-    fn bytes_sent(&self) -> Option<usize> {
-        Some(self.uncompressed_size)
-    }
 }
 
 /// Wrapper for the Datadog API.
@@ -146,9 +133,6 @@ impl Service<TraceApiRequest> for TraceApiService {
         let client = self.client.clone();
 
         Box::pin(async move {
-            let byte_size = request.get_metadata().events_byte_size();
-            let batch_size = request.get_metadata().event_count();
-            let uncompressed_size = request.uncompressed_size;
             let http_request = request.into_http_request().context(BuildRequestSnafu)?;
 
             let response = client.send(http_request).await?;
@@ -161,9 +145,6 @@ impl Service<TraceApiRequest> for TraceApiService {
             Ok(TraceApiResponse {
                 status_code: parts.status,
                 body,
-                batch_size,
-                byte_size,
-                uncompressed_size,
             })
         })
     }
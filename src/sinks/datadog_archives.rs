@@ -43,7 +43,7 @@ use crate::{
     sinks::{
         azure_common::{
             self,
-            config::{AzureBlobMetadata, AzureBlobRequest, AzureBlobRetryLogic},
+            config::{AzureBlobMetadata, AzureBlobRequest, AzureBlobRetryLogic, AzureBlobWrite},
             service::AzureBlobService,
             sink::AzureBlobSink,
         },
@@ -678,6 +678,8 @@ impl RequestBuilder<(S3PartitionKey, Vec<Event>)> for DatadogS3RequestBuilder {
                 ssekms_key_id: s3_options.ssekms_key_id,
                 storage_class: s3_options.storage_class,
                 tags: s3_options.tags.map(|tags| tags.into_iter().collect()),
+                checksum_algorithm: None,
+                object_lock: None,
                 content_encoding: None,
                 content_type: None,
             },
@@ -842,6 +844,7 @@ impl RequestBuilder<(String, Vec<Event>)> for DatadogAzureRequestBuilder {
             content_type: "application/gzip",
             metadata,
             request_metadata,
+            write: AzureBlobWrite::Block,
         }
     }
 }
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use deltalake::DeltaTableBuilder;
+use futures::FutureExt;
+use vector_config::configurable_component;
+
+use super::{schema::SchemaEvolution, sink::DeltaLakeSink};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{
+        util::{BatchConfig, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+};
+
+/// Configuration for the `delta_lake` sink.
+#[configurable_component(sink("delta_lake"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DeltaLakeConfig {
+    /// The URI of the table.
+    ///
+    /// Tables stored in Azure Data Lake Storage are addressed with `abfss://` or `az://` URIs, and
+    /// in Google Cloud Storage with `gs://` URIs. Tables stored in S3 aren't supported.
+    ///
+    /// The table must already exist.
+    #[configurable(metadata(docs::examples = "abfss://logs@account.dfs.core.windows.net/events"))]
+    #[configurable(metadata(docs::examples = "gs://warehouse/logs/events"))]
+    table_uri: String,
+
+    /// Options of the storage of the table, such as its credentials.
+    ///
+    /// These are the options of the object stores of [delta-rs][delta_rs], such as
+    /// `AZURE_STORAGE_ACCOUNT_KEY` or `GOOGLE_SERVICE_ACCOUNT`. Options that aren't set are read
+    /// from the environment variables of the same names.
+    ///
+    /// [delta_rs]: https://docs.rs/deltalake/0.5.0/deltalake/
+    #[serde(default)]
+    storage_options: HashMap<String, String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    schema_evolution: SchemaEvolution,
+
+    /// How many times commits are attempted, when other writers commit to the table first.
+    ///
+    /// When every attempt failed, the batch is retried as a whole, and written to new data files.
+    #[serde(default = "default_max_commit_attempts")]
+    max_commit_attempts: usize,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    batch: BatchConfig<DeltaLakeDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+const fn default_max_commit_attempts() -> usize {
+    10
+}
+
+/// Each batch is written to data files and committed to the transaction log of the table, so
+/// batches are large and infrequent by default, to keep the number of files and commits small.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DeltaLakeDefaultBatchSettings;
+
+impl SinkBatchSettings for DeltaLakeDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(100_000_000);
+    const TIMEOUT_SECS: f64 = 300.0;
+}
+
+impl GenerateConfig for DeltaLakeConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"table_uri = "gs://warehouse/logs/events""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for DeltaLakeConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if self.max_commit_attempts == 0 {
+            return Err("`max_commit_attempts` must be greater than zero".into());
+        }
+        if !is_supported_uri(&self.table_uri) {
+            return Err(format!(
+                "The storage of the table `{}` isn't supported",
+                self.table_uri
+            )
+            .into());
+        }
+
+        let healthcheck = {
+            let table_uri = self.table_uri.clone();
+            let storage_options = self.storage_options.clone();
+            async move {
+                DeltaTableBuilder::from_uri(table_uri)
+                    .with_storage_options(storage_options)
+                    .load()
+                    .await?;
+                Ok(())
+            }
+            .boxed()
+        };
+
+        let sink = DeltaLakeSink {
+            table_uri: self.table_uri.clone(),
+            storage_options: self.storage_options.clone(),
+            schema_evolution: self.schema_evolution,
+            max_commit_attempts: self.max_commit_attempts,
+            transformer: self.encoding.clone(),
+            batch_settings: self.batch.into_batcher_settings()?,
+            request: self.request.unwrap_with(&TowerRequestConfig::default()),
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Returns whether the table at `table_uri` is stored in a local directory, Azure Data Lake Storage
+/// or Google Cloud Storage, the storages delta-rs is built with.
+fn is_supported_uri(table_uri: &str) -> bool {
+    match table_uri.split_once("://") {
+        Some((scheme, _)) => matches!(
+            scheme,
+            "file" | "az" | "adl" | "azure" | "adls2" | "abfs" | "abfss" | "gs"
+        ),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<DeltaLakeConfig>();
+    }
+
+    #[test]
+    fn parses_schema_evolution() {
+        let config: DeltaLakeConfig = toml::from_str(
+            r#"table_uri = "gs://warehouse/logs/events"
+            schema_evolution = "merge""#,
+        )
+        .unwrap();
+
+        assert_eq!(config.schema_evolution, SchemaEvolution::Merge);
+        assert_eq!(config.max_commit_attempts, 10);
+    }
+
+    #[tokio::test]
+    async fn rejects_s3_tables() {
+        let config: DeltaLakeConfig =
+            toml::from_str(r#"table_uri = "s3://warehouse/logs/events""#).unwrap();
+
+        let error = config.build(SinkContext::new_test()).await.err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "The storage of the table `s3://warehouse/logs/events` isn't supported"
+        );
+    }
+}
@@ -0,0 +1,12 @@
+//! The `delta_lake` sink.
+//!
+//! Batches of events are written to Parquet data files of an existing Delta Lake table with
+//! [delta-rs][delta_rs], and appended to it in commits to its transaction log.
+//!
+//! [delta_rs]: https://github.com/delta-io/delta-rs
+
+mod config;
+mod schema;
+mod sink;
+
+pub use self::config::DeltaLakeConfig;
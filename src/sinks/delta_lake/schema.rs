@@ -0,0 +1,200 @@
+//! Converting events to rows of the schemas of tables, and evolving those schemas.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+use deltalake::{Schema, SchemaDataType, SchemaField};
+use serde_json::Value as JsonValue;
+use vector_config::configurable_component;
+
+use crate::event::{LogEvent, Value};
+
+/// How the schema of the table evolves with the fields of events.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaEvolution {
+    /// The schema of the table is left as is.
+    ///
+    /// Fields of events the table has no columns for aren't written.
+    #[default]
+    None,
+
+    /// Columns are added to the table for the top-level fields of events it has no columns for.
+    ///
+    /// The types of new columns are inferred from the values of those fields. Only fields holding
+    /// strings, integers, floats, booleans and timestamps add columns.
+    Merge,
+}
+
+/// Returns the columns missing from `schema` for the top-level fields of `events`, sorted by name.
+pub fn missing_columns(schema: &Schema, events: &[LogEvent]) -> Vec<SchemaField> {
+    let mut columns = BTreeMap::new();
+    for event in events {
+        let fields = match event.as_map() {
+            Some(fields) => fields,
+            None => continue,
+        };
+        for (name, value) in fields {
+            if columns.contains_key(name) || has_column(schema, name) {
+                continue;
+            }
+            if let Some(data_type) = infer_type(value) {
+                columns.insert(name.clone(), data_type);
+            }
+        }
+    }
+
+    columns
+        .into_iter()
+        .map(|(name, data_type)| {
+            SchemaField::new(
+                name,
+                SchemaDataType::primitive(data_type.into()),
+                true,
+                HashMap::new(),
+            )
+        })
+        .collect()
+}
+
+fn has_column(schema: &Schema, name: &str) -> bool {
+    schema
+        .get_fields()
+        .iter()
+        .any(|field| field.get_name() == name)
+}
+
+fn infer_type(value: &Value) -> Option<&'static str> {
+    match value {
+        Value::Bytes(_) => Some("string"),
+        Value::Integer(_) => Some("long"),
+        Value::Float(_) => Some("double"),
+        Value::Boolean(_) => Some("boolean"),
+        Value::Timestamp(_) => Some("timestamp"),
+        _ => None,
+    }
+}
+
+/// Converts `event` to a row of `schema`, as the JSON object delta-rs writes rows from.
+pub fn to_row(schema: &Schema, event: &LogEvent) -> JsonValue {
+    let row = schema
+        .get_fields()
+        .iter()
+        .filter_map(|field| {
+            let value = event.as_map()?.get(field.get_name())?;
+            let value = column_value(value, field.get_type())?;
+            Some((field.get_name().to_owned(), value))
+        })
+        .collect();
+    JsonValue::Object(row)
+}
+
+/// Converts `value` to a value of a column of `data_type`, returning `None` if it doesn't fit the
+/// column, which is then left null.
+fn column_value(value: &Value, data_type: &SchemaDataType) -> Option<JsonValue> {
+    let primitive = match data_type {
+        SchemaDataType::primitive(primitive) => primitive.as_str(),
+        _ => return serde_json::to_value(value).ok(),
+    };
+
+    match (primitive, value) {
+        ("string", Value::Bytes(bytes)) => Some(String::from_utf8_lossy(bytes).into()),
+        ("string", Value::Timestamp(timestamp)) => Some(timestamp.to_rfc3339().into()),
+        ("long" | "integer" | "short" | "byte", Value::Integer(integer)) => Some((*integer).into()),
+        ("double" | "float", Value::Integer(integer)) => Some((*integer).into()),
+        ("double" | "float", Value::Float(float)) => Some(float.into_inner().into()),
+        ("boolean", Value::Boolean(boolean)) => Some((*boolean).into()),
+        // Timestamps are written as microseconds since the epoch, and dates as days since it.
+        ("timestamp", Value::Timestamp(timestamp)) => Some(timestamp.timestamp_micros().into()),
+        ("date", Value::Timestamp(timestamp)) => {
+            let epoch = NaiveDate::from_ymd(1970, 1, 1);
+            Some((timestamp.naive_utc().date() - epoch).num_days().into())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    use super::*;
+
+    fn schema() -> Schema {
+        let field = |name: &str, data_type: &str| {
+            SchemaField::new(
+                name.into(),
+                SchemaDataType::primitive(data_type.into()),
+                true,
+                HashMap::new(),
+            )
+        };
+        Schema::new(vec![
+            field("timestamp", "timestamp"),
+            field("date", "date"),
+            field("host", "string"),
+            field("status", "integer"),
+            field("duration", "double"),
+        ])
+    }
+
+    #[test]
+    fn converts_events_to_rows() {
+        let timestamp = Utc.ymd(2023, 1, 10).and_hms(12, 30, 0);
+        let mut event = LogEvent::default();
+        event.insert("timestamp", timestamp);
+        event.insert("date", timestamp);
+        event.insert("host", "host-a");
+        event.insert("status", 200);
+        event.insert("duration", 12);
+        event.insert("message", "GET /");
+
+        assert_eq!(
+            to_row(&schema(), &event),
+            json!({
+                "timestamp": 1673353800000000i64,
+                "date": 19367,
+                "host": "host-a",
+                "status": 200,
+                "duration": 12,
+            })
+        );
+    }
+
+    #[test]
+    fn leaves_mismatched_values_null() {
+        let mut event = LogEvent::default();
+        event.insert("status", "OK");
+        event.insert("host", 3);
+
+        assert_eq!(to_row(&schema(), &event), json!({}));
+    }
+
+    #[test]
+    fn finds_missing_columns() {
+        let mut first = LogEvent::default();
+        first.insert("host", "host-a");
+        first.insert("message", "GET /");
+        first.insert("tags", vec!["a", "b"]);
+        let mut second = LogEvent::default();
+        second.insert("message", 3);
+        second.insert("bytes", 512);
+        second.insert("sampled", true);
+
+        let columns = missing_columns(&schema(), &[first, second]);
+        let columns: Vec<_> = columns
+            .iter()
+            .map(|field| (field.get_name(), field.get_type().clone()))
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                ("bytes", SchemaDataType::primitive("long".into())),
+                ("message", SchemaDataType::primitive("string".into())),
+                ("sampled", SchemaDataType::primitive("boolean".into())),
+            ]
+        );
+    }
+}
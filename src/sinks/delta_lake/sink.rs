@@ -0,0 +1,322 @@
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use deltalake::{
+    action::{Action, MetaData},
+    writer::{DeltaWriter, JsonWriter},
+    DeltaTable, DeltaTableBuilder, DeltaTableError, DeltaTransactionOptions, Schema,
+};
+use futures::{future::BoxFuture, stream::BoxStream, StreamExt};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Mutex;
+use tower::{Service, ServiceBuilder};
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable, LogEvent},
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use super::schema::{missing_columns, to_row, SchemaEvolution};
+use crate::{
+    codecs::Transformer,
+    event::Event,
+    sinks::util::{
+        metadata::RequestMetadataBuilder, retries::RetryLogic, ServiceBuilderExt, SinkBuilderExt,
+        StreamSink, TowerRequestSettings,
+    },
+};
+
+/// How long to wait before opening the table again, when it can't be opened on start up.
+const OPEN_TABLE_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Snafu)]
+pub enum DeltaLakeError {
+    #[snafu(display("{}", source))]
+    Table { source: DeltaTableError },
+    #[snafu(display("Failed to encode the metadata of the table: {}", source))]
+    EncodeMetadata { source: serde_json::Error },
+}
+
+fn is_retriable(error: &DeltaTableError) -> bool {
+    matches!(
+        error,
+        DeltaTableError::ObjectStore { .. } | DeltaTableError::VersionAlreadyExists(_)
+    )
+}
+
+/// An event, with the sizes it had before being transformed.
+struct EncodedEvent {
+    log: LogEvent,
+    finalizers: EventFinalizers,
+    byte_size: usize,
+    json_size: usize,
+}
+
+impl ByteSizeOf for EncodedEvent {
+    fn size_of(&self) -> usize {
+        self.byte_size
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
+/// Writes data files to the table and commits them.
+///
+/// The table is held behind a lock, as its state is updated by every commit, so batches are
+/// written one at a time.
+struct TableWriter {
+    table: Mutex<DeltaTable>,
+    schema_evolution: SchemaEvolution,
+    max_commit_attempts: usize,
+}
+
+impl TableWriter {
+    async fn append(&self, events: &[LogEvent]) -> Result<(), DeltaLakeError> {
+        let mut table = self.table.lock().await;
+        table.update().await.context(TableSnafu)?;
+
+        if self.schema_evolution == SchemaEvolution::Merge {
+            self.commit(&mut table, |table| {
+                let schema = table.get_schema().context(TableSnafu)?;
+                let columns = missing_columns(schema, events);
+                if columns.is_empty() {
+                    return Ok(None);
+                }
+
+                let mut metadata = table.get_metadata().context(TableSnafu)?.clone();
+                let mut fields = schema.get_fields().clone();
+                fields.extend(columns);
+                metadata.schema = Schema::new(fields);
+                let metadata = MetaData::try_from(metadata).context(EncodeMetadataSnafu)?;
+                Ok(Some(vec![Action::metaData(metadata)]))
+            })
+            .await?;
+        }
+
+        let schema = table.get_schema().context(TableSnafu)?;
+        let rows = events.iter().map(|log| to_row(schema, log)).collect();
+        let mut writer = JsonWriter::for_table(&table).context(TableSnafu)?;
+        writer.write(rows).await.context(TableSnafu)?;
+        let actions: Vec<_> = writer
+            .flush()
+            .await
+            .context(TableSnafu)?
+            .into_iter()
+            .map(Action::add)
+            .collect();
+
+        // Appends don't conflict with other commits, so they are committed as they are on top of
+        // whatever was committed concurrently.
+        self.commit(&mut table, |_| Ok(Some(actions.clone()))).await
+    }
+
+    /// Commits the actions built from the state of the table, building them again from its
+    /// updated state when other writers committed first, for up to `max_commit_attempts`.
+    async fn commit<F>(&self, table: &mut DeltaTable, build: F) -> Result<(), DeltaLakeError>
+    where
+        F: Fn(&DeltaTable) -> Result<Option<Vec<Action>>, DeltaLakeError>,
+    {
+        let mut attempt = 1;
+        loop {
+            let actions = match build(table)? {
+                Some(actions) => actions,
+                None => return Ok(()),
+            };
+
+            // Conflicts are retried here rather than by delta-rs, to build the actions again.
+            let result = {
+                let mut transaction =
+                    table.create_transaction(Some(DeltaTransactionOptions::new(0)));
+                transaction.add_actions(actions);
+                transaction.commit(None, None).await
+            };
+            match result {
+                Ok(_) => return Ok(()),
+                Err(DeltaTableError::VersionAlreadyExists(version))
+                    if attempt < self.max_commit_attempts =>
+                {
+                    debug!(
+                        message = "Table was updated concurrently, committing again.",
+                        %version,
+                        %attempt
+                    );
+                    attempt += 1;
+                    table.update().await.context(TableSnafu)?;
+                }
+                Err(error) => return Err(error).context(TableSnafu),
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+struct DeltaLakeRequest {
+    events: Vec<LogEvent>,
+    finalizers: EventFinalizers,
+    metadata: RequestMetadata,
+}
+
+impl Finalizable for DeltaLakeRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for DeltaLakeRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata.clone()
+    }
+}
+
+struct DeltaLakeResponse;
+
+impl DriverResponse for DeltaLakeResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+}
+
+#[derive(Clone)]
+struct DeltaLakeService {
+    writer: Arc<TableWriter>,
+}
+
+impl Service<DeltaLakeRequest> for DeltaLakeService {
+    type Response = DeltaLakeResponse;
+    type Error = DeltaLakeError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: DeltaLakeRequest) -> Self::Future {
+        let writer = Arc::clone(&self.writer);
+        Box::pin(async move {
+            writer.append(&request.events).await?;
+            Ok(DeltaLakeResponse)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DeltaLakeRetryLogic;
+
+impl RetryLogic for DeltaLakeRetryLogic {
+    type Error = DeltaLakeError;
+    type Response = DeltaLakeResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            DeltaLakeError::Table { source } => is_retriable(source),
+            DeltaLakeError::EncodeMetadata { .. } => false,
+        }
+    }
+}
+
+pub struct DeltaLakeSink {
+    pub table_uri: String,
+    pub storage_options: HashMap<String, String>,
+    pub schema_evolution: SchemaEvolution,
+    pub max_commit_attempts: usize,
+    pub transformer: Transformer,
+    pub batch_settings: BatcherSettings,
+    pub request: TowerRequestSettings,
+}
+
+impl DeltaLakeSink {
+    /// Opens the table, waiting for its storage to be reachable.
+    async fn open_table(&self) -> Result<DeltaTable, DeltaTableError> {
+        loop {
+            match DeltaTableBuilder::from_uri(&self.table_uri)
+                .with_storage_options(self.storage_options.clone())
+                .load()
+                .await
+            {
+                Ok(table) => return Ok(table),
+                Err(error) if is_retriable(&error) => {
+                    error!(message = "Failed to open the table, retrying.", %error);
+                    tokio::time::sleep(OPEN_TABLE_RETRY_INTERVAL).await;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for DeltaLakeSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let table = match self.open_table().await {
+            Ok(table) => table,
+            Err(error) => {
+                error!(message = "Failed to open the table.", %error);
+                return Err(());
+            }
+        };
+
+        let service = ServiceBuilder::new()
+            .settings(self.request, DeltaLakeRetryLogic)
+            .service(DeltaLakeService {
+                writer: Arc::new(TableWriter {
+                    table: Mutex::new(table),
+                    schema_evolution: self.schema_evolution,
+                    max_commit_attempts: self.max_commit_attempts,
+                }),
+            });
+
+        let transformer = self.transformer;
+        input
+            .map(|mut event| {
+                let byte_size = event.size_of();
+                let json_size = event.estimated_json_encoded_size_of();
+                transformer.transform(&mut event);
+                EncodedEvent {
+                    finalizers: event.take_finalizers(),
+                    log: event.into_log(),
+                    byte_size,
+                    json_size,
+                }
+            })
+            .batched(self.batch_settings.into_byte_size_config())
+            .map(|events| {
+                let mut byte_size = 0;
+                let mut json_size = 0;
+                let mut finalizers = EventFinalizers::default();
+                let events: Vec<_> = events
+                    .into_iter()
+                    .map(|event| {
+                        byte_size += event.byte_size;
+                        json_size += event.json_size;
+                        finalizers.merge(event.finalizers);
+                        event.log
+                    })
+                    .collect();
+                // The size of a request isn't known until its data files are written, so the size
+                // of its events is used instead.
+                let metadata = RequestMetadataBuilder::new(events.len(), byte_size, json_size)
+                    .with_request_size(
+                        NonZeroUsize::new(byte_size).expect("batches should never be empty"),
+                    );
+
+                DeltaLakeRequest {
+                    events,
+                    finalizers,
+                    metadata,
+                }
+            })
+            .into_driver(service)
+            .protocol("https")
+            .run()
+            .await
+    }
+}
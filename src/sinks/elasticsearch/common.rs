@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use aws_types::credentials::SharedCredentialsProvider;
 use aws_types::region::Region;
 use bytes::{Buf, Bytes};
-use http::{Response, StatusCode, Uri};
+use http::{HeaderValue, Response, StatusCode, Uri};
 use hyper::{body, Body};
 use serde::Deserialize;
 use snafu::ResultExt;
@@ -18,7 +18,8 @@ use crate::{
     http::{Auth, HttpClient, MaybeAuth},
     sinks::{
         elasticsearch::{
-            ElasticsearchAuth, ElasticsearchCommonMode, ElasticsearchConfig, ParseError,
+            ElasticsearchAuth, ElasticsearchCommonMode, ElasticsearchConfig, OpenSearchServiceType,
+            ParseError,
         },
         util::{http::RequestConfig, TowerRequestConfig, UriSerde},
         HealthcheckError,
@@ -37,6 +38,7 @@ pub struct ElasticsearchCommon {
     pub request_builder: ElasticsearchRequestBuilder,
     pub tls_settings: TlsSettings,
     pub region: Option<Region>,
+    pub service_type: OpenSearchServiceType,
     pub request: RequestConfig,
     pub query_params: HashMap<String, String>,
     pub metric_to_log: MetricToLog,
@@ -72,15 +74,22 @@ impl ElasticsearchCommon {
         let http_auth = authorization.choose_one(&uri.auth)?;
         let base_url = uri.uri.to_string().trim_end_matches('/').to_owned();
 
+        let service_type = config.opensearch_service_type;
+        let region = config
+            .aws
+            .as_ref()
+            .and_then(|config| config.region())
+            .or_else(|| endpoint_region(&uri.uri, service_type));
+
         let aws_auth = match &config.auth {
-            Some(ElasticsearchAuth::Basic { .. }) | None => None,
+            Some(ElasticsearchAuth::Basic { .. }) | None => {
+                if service_type != OpenSearchServiceType::Managed {
+                    return Err(ParseError::AwsAuthRequired { service_type }.into());
+                }
+                None
+            }
             Some(ElasticsearchAuth::Aws(aws)) => {
-                let region = config
-                    .aws
-                    .as_ref()
-                    .map(|config| config.region())
-                    .ok_or(ParseError::RegionRequired)?
-                    .ok_or(ParseError::RegionRequired)?;
+                let region = region.clone().ok_or(ParseError::RegionRequired)?;
 
                 Some(aws.credentials_provider(region).await?)
             }
@@ -103,12 +112,17 @@ impl ElasticsearchCommon {
             query_params.insert("pipeline".into(), pipeline.into());
         }
 
-        let bulk_url = {
-            let mut query = url::form_urlencoded::Serializer::new(String::new());
-            for (p, v) in &query_params {
-                query.append_pair(&p[..], &v[..]);
+        let bulk_url = match service_type {
+            // Pipelines ingest documents at the path of their HTTP source, which is part of the
+            // endpoint.
+            OpenSearchServiceType::Ingestion => base_url.clone(),
+            OpenSearchServiceType::Managed | OpenSearchServiceType::Serverless => {
+                let mut query = url::form_urlencoded::Serializer::new(String::new());
+                for (p, v) in &query_params {
+                    query.append_pair(&p[..], &v[..]);
+                }
+                format!("{}/_bulk?{}", base_url, query.finish())
             }
-            format!("{}/_bulk?{}", base_url, query.finish())
         };
         let bulk_uri = bulk_url.parse::<Uri>().unwrap();
 
@@ -123,8 +137,6 @@ impl ElasticsearchCommon {
             LogNamespace::Legacy,
         );
 
-        let region = config.aws.as_ref().and_then(|config| config.region());
-
         let version = if let Some(version) = *version {
             version
         } else {
@@ -132,12 +144,18 @@ impl ElasticsearchCommon {
                 ElasticsearchApiVersion::V6 => 6,
                 ElasticsearchApiVersion::V7 => 7,
                 ElasticsearchApiVersion::V8 => 8,
+                // Serverless collections and ingestion pipelines don't expose the version of a
+                // cluster, and don't support mapping types.
+                ElasticsearchApiVersion::Auto if service_type != OpenSearchServiceType::Managed => {
+                    8
+                }
                 ElasticsearchApiVersion::Auto => {
                     match get_version(
                         &base_url,
                         &http_auth,
                         &aws_auth,
                         &region,
+                        service_type,
                         &request,
                         &tls_settings,
                         proxy_config,
@@ -176,7 +194,9 @@ impl ElasticsearchCommon {
                 transformer: config.encoding.clone(),
                 doc_type,
                 suppress_type_name,
+                json_array: service_type == OpenSearchServiceType::Ingestion,
             },
+            endpoint: Some(base_url.as_str().into()),
         };
 
         Ok(Self {
@@ -189,6 +209,7 @@ impl ElasticsearchCommon {
             query_params,
             request,
             region,
+            service_type,
             tls_settings,
             metric_to_log,
         })
@@ -231,11 +252,17 @@ impl ElasticsearchCommon {
     }
 
     pub async fn healthcheck(self, client: HttpClient) -> crate::Result<()> {
+        // Serverless collections and ingestion pipelines don't expose the health of a cluster.
+        if self.service_type != OpenSearchServiceType::Managed {
+            return Ok(());
+        }
+
         match get(
             &self.base_url,
             &self.http_auth,
             &self.aws_auth,
             &self.region,
+            self.service_type,
             &self.request,
             client,
             "/_cluster/health",
@@ -250,11 +277,43 @@ impl ElasticsearchCommon {
 }
 
 pub async fn sign_request(
+    service_type: OpenSearchServiceType,
     request: &mut http::Request<Bytes>,
     credentials_provider: &SharedCredentialsProvider,
     region: &Option<Region>,
 ) -> crate::Result<()> {
-    crate::aws::sign_request("es", request, credentials_provider, region).await
+    if service_type == OpenSearchServiceType::Serverless {
+        // OpenSearch Serverless requires the hash of the payload to be sent with the signature.
+        let hash = openssl::sha::sha256(request.body())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        request
+            .headers_mut()
+            .insert("x-amz-content-sha256", HeaderValue::from_str(&hash)?);
+    }
+    crate::aws::sign_request(
+        service_type.signing_name(),
+        request,
+        credentials_provider,
+        region,
+    )
+    .await
+}
+
+/// Gets the region of the endpoint of a serverless collection or an ingestion pipeline, whose host
+/// is of the form `<name>.<region>.<service>.amazonaws.com`.
+fn endpoint_region(uri: &Uri, service_type: OpenSearchServiceType) -> Option<Region> {
+    if service_type == OpenSearchServiceType::Managed {
+        return None;
+    }
+    let labels = uri.host()?.split('.').collect::<Vec<_>>();
+    match labels.as_slice() {
+        [_, .., region, service, "amazonaws", "com"] if *service == service_type.signing_name() => {
+            Some(Region::new(region.to_string()))
+        }
+        _ => None,
+    }
 }
 
 async fn get_version(
@@ -262,6 +321,7 @@ async fn get_version(
     http_auth: &Option<Auth>,
     aws_auth: &Option<SharedCredentialsProvider>,
     region: &Option<Region>,
+    service_type: OpenSearchServiceType,
     request: &RequestConfig,
     tls_settings: &TlsSettings,
     proxy_config: &ProxyConfig,
@@ -277,6 +337,7 @@ async fn get_version(
         http_auth,
         aws_auth,
         region,
+        service_type,
         request,
         client,
         "/_cluster/state/version",
@@ -296,6 +357,7 @@ async fn get(
     http_auth: &Option<Auth>,
     aws_auth: &Option<SharedCredentialsProvider>,
     region: &Option<Region>,
+    service_type: OpenSearchServiceType,
     request: &RequestConfig,
     client: HttpClient,
     path: &str,
@@ -313,7 +375,7 @@ async fn get(
     let mut request = builder.body(Bytes::new())?;
 
     if let Some(credentials_provider) = aws_auth {
-        sign_request(&mut request, credentials_provider, region).await?;
+        sign_request(service_type, &mut request, credentials_provider, region).await?;
     }
     client
         .send(request.map(hyper::Body::from))
@@ -10,7 +10,9 @@ use vector_config::configurable_component;
 use crate::{
     aws::RegionOrEndpoint,
     codecs::Transformer,
-    config::{log_schema, AcknowledgementsConfig, DataType, Input, SinkConfig, SinkContext},
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, Input, Output, SinkConfig, SinkContext,
+    },
     event::{EventRef, LogEvent, Value},
     http::HttpClient,
     internal_events::TemplateRenderingError,
@@ -18,10 +20,11 @@ use crate::{
         elasticsearch::{
             health::ElasticsearchHealthLogic,
             retry::ElasticsearchRetryLogic,
-            service::{ElasticsearchService, HttpRequestBuilder},
+            service::{ElasticsearchService, HttpRequestBuilder, REJECTED},
             sink::ElasticsearchSink,
             BatchActionTemplateSnafu, ElasticsearchApiVersion, ElasticsearchAuth,
             ElasticsearchCommon, ElasticsearchCommonMode, ElasticsearchMode, IndexTemplateSnafu,
+            OpenSearchServiceType,
         },
         util::{
             http::RequestConfig, service::HealthConfig, BatchConfig, Compression,
@@ -77,10 +80,22 @@ pub struct ElasticsearchConfig {
 
     /// Whether or not to retry successful requests containing partial failures.
     ///
+    /// Only the documents that failed with retriable errors, which are backpressure and server
+    /// errors, are written again.
+    ///
     /// To avoid duplicates in Elasticsearch, please use option `id_key`.
     #[serde(default)]
     pub request_retry_partial: bool,
 
+    /// Reroutes the documents rejected by Elasticsearch to a named output instead of dropping them.
+    ///
+    /// Documents can be rejected one by one within successful requests, for instance because of
+    /// mapping conflicts. When `reroute_rejected` is set to `true`, the original events of these
+    /// documents are forwarded to a specially-named output, `rejected`, annotated with the status
+    /// and error returned by Elasticsearch.
+    #[serde(default)]
+    pub reroute_rejected: bool,
+
     /// The name of the event key that should map to Elasticsearch’s [`_id` field][es_id].
     ///
     /// By default, Vector does not set the `_id` field, which allows Elasticsearch to set this
@@ -126,6 +141,10 @@ pub struct ElasticsearchConfig {
     #[configurable(derived)]
     pub aws: Option<RegionOrEndpoint>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub opensearch_service_type: OpenSearchServiceType,
+
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
 
@@ -388,7 +407,11 @@ impl DataStreamConfig {
 impl SinkConfig for ElasticsearchConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let commons = ElasticsearchCommon::parse_many(self, cx.proxy()).await?;
-        let common = commons[0].clone();
+        let mut common = commons[0].clone();
+        if commons.len() > 1 {
+            // The endpoint of a request is only picked once it's sent.
+            common.request_builder.endpoint = None;
+        }
 
         let client = HttpClient::new(common.tls_settings.clone(), cx.proxy())?;
 
@@ -406,7 +429,13 @@ impl SinkConfig for ElasticsearchConfig {
                 let endpoint = common.base_url.clone();
 
                 let http_request_builder = HttpRequestBuilder::new(&common, self);
-                let service = ElasticsearchService::new(client.clone(), http_request_builder);
+                let service = ElasticsearchService::new(
+                    client.clone(),
+                    http_request_builder,
+                    common.request_builder.clone(),
+                    cx.out.clone(),
+                    self.request_retry_partial,
+                );
 
                 (endpoint, service)
             })
@@ -439,6 +468,14 @@ impl SinkConfig for ElasticsearchConfig {
         Input::new(DataType::Metric | DataType::Log)
     }
 
+    fn outputs(&self) -> Vec<Output> {
+        if self.reroute_rejected {
+            vec![Output::default(DataType::Log).with_port(REJECTED)]
+        } else {
+            Vec::new()
+        }
+    }
+
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &self.acknowledgements
     }
@@ -2,7 +2,8 @@ use std::{io, io::Write};
 
 use serde::Serialize;
 use vector_buffers::EventCount;
-use vector_core::{event::Event, ByteSizeOf, EstimatedJsonEncodedSizeOf};
+use vector_common::request_metadata::GroupedCountByteSize;
+use vector_core::{event::Event, ByteSizeOf, EstimatedJsonEncodedSizeOf, GroupedSizeOf};
 
 use crate::{
     codecs::Transformer,
@@ -13,7 +14,7 @@ use crate::{
     },
 };
 
-#[derive(Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ProcessedEvent {
     pub index: String,
     pub bulk_action: BulkAction,
@@ -39,6 +40,12 @@ impl EstimatedJsonEncodedSizeOf for ProcessedEvent {
     }
 }
 
+impl GroupedSizeOf for ProcessedEvent {
+    fn add_grouped_sizes(&self, grouped_sizes: &mut GroupedCountByteSize) {
+        self.log.add_grouped_sizes(grouped_sizes);
+    }
+}
+
 impl EventCount for ProcessedEvent {
     fn event_count(&self) -> usize {
         // An Elasticsearch ProcessedEvent is mapped one-to-one with an event.
@@ -51,6 +58,17 @@ pub struct ElasticsearchEncoder {
     pub transformer: Transformer,
     pub doc_type: String,
     pub suppress_type_name: bool,
+    /// Whether documents are encoded as a JSON array, as expected by OpenSearch Ingestion
+    /// pipelines, rather than as bulk actions.
+    pub json_array: bool,
+}
+
+impl ElasticsearchEncoder {
+    fn transform(&self, log: LogEvent) -> LogEvent {
+        let mut event = Event::from(log);
+        self.transformer.transform(&mut event);
+        event.into_log()
+    }
 }
 
 impl Encoder<Vec<ProcessedEvent>> for ElasticsearchEncoder {
@@ -59,13 +77,20 @@ impl Encoder<Vec<ProcessedEvent>> for ElasticsearchEncoder {
         input: Vec<ProcessedEvent>,
         writer: &mut dyn Write,
     ) -> std::io::Result<usize> {
+        if self.json_array {
+            let logs = input
+                .into_iter()
+                .map(|event| self.transform(event.log))
+                .collect::<Vec<_>>();
+            return as_tracked_write::<_, _, io::Error>(writer, &logs, |mut writer, logs| {
+                serde_json::to_writer(&mut writer, logs)?;
+                Ok(())
+            });
+        }
+
         let mut written_bytes = 0;
         for event in input {
-            let log = {
-                let mut event = Event::from(event.log);
-                self.transformer.transform(&mut event);
-                event.into_log()
-            };
+            let log = self.transform(event.log);
             written_bytes += write_bulk_action(
                 writer,
                 event.bulk_action.as_str(),
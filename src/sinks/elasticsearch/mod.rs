@@ -192,6 +192,44 @@ impl Default for ElasticsearchApiVersion {
     }
 }
 
+/// The type of Amazon OpenSearch service written to.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum OpenSearchServiceType {
+    /// Elasticsearch or OpenSearch clusters, including Amazon OpenSearch Service domains.
+    Managed,
+
+    /// Amazon OpenSearch Serverless collections.
+    ///
+    /// Requests are signed for the `aoss` service, which requires AWS authentication.
+    Serverless,
+
+    /// Amazon OpenSearch Ingestion pipelines.
+    ///
+    /// Documents are sent as JSON arrays to the path of the HTTP source of the pipeline, which
+    /// must be part of the endpoint, and requests are signed for the `osis` service, which
+    /// requires AWS authentication.
+    Ingestion,
+}
+
+impl OpenSearchServiceType {
+    /// The name of the service that requests are signed for, with AWS authentication.
+    pub const fn signing_name(self) -> &'static str {
+        match self {
+            Self::Managed => "es",
+            Self::Serverless => "aoss",
+            Self::Ingestion => "osis",
+        }
+    }
+}
+
+impl Default for OpenSearchServiceType {
+    fn default() -> Self {
+        Self::Managed
+    }
+}
+
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub))]
 pub enum ParseError {
@@ -205,6 +243,11 @@ pub enum ParseError {
     BatchActionTemplate { source: TemplateParseError },
     #[snafu(display("aws.region required when AWS authentication is in use"))]
     RegionRequired,
+    #[snafu(display(
+        "AWS authentication is required by the {:?} OpenSearch service type",
+        service_type
+    ))]
+    AwsAuthRequired { service_type: OpenSearchServiceType },
     #[snafu(display("Endpoints option must be specified"))]
     EndpointRequired,
     #[snafu(display(
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use bytes::Bytes;
 use vector_common::request_metadata::RequestMetadata;
 use vector_core::ByteSizeOf;
@@ -7,7 +9,7 @@ use crate::{
     sinks::{
         elasticsearch::{
             encoder::{ElasticsearchEncoder, ProcessedEvent},
-            service::ElasticsearchRequest,
+            service::{BulkDocuments, ElasticsearchRequest},
         },
         util::{
             metadata::RequestMetadataBuilder, request_builder::EncodeResult, Compression,
@@ -20,12 +22,15 @@ use crate::{
 pub struct ElasticsearchRequestBuilder {
     pub compression: Compression,
     pub encoder: ElasticsearchEncoder,
+    /// The endpoint the requests are sent to, if there's only one.
+    pub endpoint: Option<Arc<str>>,
 }
 
 pub struct Metadata {
     finalizers: EventFinalizers,
     batch_size: usize,
     events_byte_size: usize,
+    documents: Vec<ProcessedEvent>,
 }
 
 impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
@@ -56,10 +61,14 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
 
         let metadata_builder = RequestMetadataBuilder::from_events(&events);
 
+        // The documents are kept with the finalizers of their events, which are taken from the
+        // events encoded in the payload, so that they can be finalized on their own.
+        let documents = events.clone();
         let es_metadata = Metadata {
             finalizers: events.take_finalizers(),
             batch_size: events.len(),
             events_byte_size,
+            documents,
         };
         (es_metadata, metadata_builder, events)
     }
@@ -70,12 +79,17 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
         metadata: RequestMetadata,
         payload: EncodeResult<Self::Payload>,
     ) -> Self::Request {
+        let metadata = match &self.endpoint {
+            Some(endpoint) => metadata.with_endpoint(Arc::clone(endpoint)),
+            None => metadata,
+        };
         ElasticsearchRequest {
             payload: payload.into_payload(),
             finalizers: es_metadata.finalizers,
             batch_size: es_metadata.batch_size,
             events_byte_size: es_metadata.events_byte_size,
             metadata,
+            documents: Arc::new(BulkDocuments::new(es_metadata.documents)),
         }
     }
 }
@@ -9,13 +9,20 @@ use crate::{
     },
 };
 
+/// Whether items failing with `status` can be written again, which are those failing because of
+/// backpressure or server errors.
+pub(super) fn is_retriable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
 #[derive(Deserialize, Debug)]
-struct EsResultResponse {
-    items: Vec<EsResultItem>,
+pub(super) struct EsResultResponse {
+    /// The results of the bulk actions, in the order of the documents of the request.
+    pub(super) items: Vec<EsResultItem>,
 }
 
 impl EsResultResponse {
-    fn parse(body: &str) -> Result<Self, String> {
+    pub(super) fn parse(body: &str) -> Result<Self, String> {
         serde_json::from_str::<EsResultResponse>(body).map_err(|json_error| {
             format!(
                 "some messages failed, could not parse response, error: {}",
@@ -35,10 +42,7 @@ impl EsResultResponse {
     }
 
     /// Selects the first error since logging all errors would be quite verbose and many are duplicates.
-    /// If partial retry is enabled and we don't retry, this is because there is no retriable error in the
-    /// response, thus all errors are equally interesting so logging the first is sufficient.
-    /// When partial retry is disabled, we don't retry on any error.
-    fn get_error_reason(&self, body: &str) -> String {
+    pub(super) fn get_error_reason(&self, body: &str) -> String {
         match self
             .items
             .iter()
@@ -51,7 +55,7 @@ impl EsResultResponse {
 }
 
 #[derive(Deserialize, Debug)]
-enum EsResultItem {
+pub(super) enum EsResultItem {
     #[serde(rename = "index")]
     Index(EsIndexResult),
     #[serde(rename = "create")]
@@ -60,7 +64,7 @@ enum EsResultItem {
 
 impl EsResultItem {
     #[allow(clippy::missing_const_for_fn)] // const cannot run destructor
-    fn result(&self) -> &EsIndexResult {
+    pub(super) fn result(&self) -> &EsIndexResult {
         match self {
             EsResultItem::Index(r) => r,
             EsResultItem::Create(r) => r,
@@ -69,16 +73,16 @@ impl EsResultItem {
 }
 
 #[derive(Deserialize, Debug)]
-struct EsIndexResult {
-    status: Option<u16>,
-    error: Option<EsErrorDetails>,
+pub(super) struct EsIndexResult {
+    pub(super) status: Option<u16>,
+    pub(super) error: Option<EsErrorDetails>,
 }
 
 #[derive(Deserialize, Debug)]
-struct EsErrorDetails {
-    reason: String,
+pub(super) struct EsErrorDetails {
+    pub(super) reason: String,
     #[serde(rename = "type")]
-    err_type: String,
+    pub(super) err_type: String,
 }
 
 #[derive(Clone)]
@@ -122,13 +126,11 @@ impl RetryLogic for ElasticsearchRetryLogic {
                         Ok(resp) => {
                             if self.retry_partial {
                                 // We will retry if there exists at least one item that
-                                // failed with a retriable error.
-                                // Those are backpressure and server errors.
-                                if let Some((status, error)) =
-                                    resp.iter_status().find(|(status, _)| {
-                                        *status == StatusCode::TOO_MANY_REQUESTS
-                                            || status.is_server_error()
-                                    })
+                                // failed with a retriable error, in which case only those items
+                                // are written again.
+                                if let Some((status, error)) = resp
+                                    .iter_status()
+                                    .find(|(status, _)| is_retriable_status(*status))
                                 {
                                     let msg = if let Some(error) = error {
                                         format!(
@@ -142,7 +144,9 @@ impl RetryLogic for ElasticsearchRetryLogic {
                                 }
                             }
 
-                            RetryAction::DontRetry(resp.get_error_reason(&body).into())
+                            // The other items were either written or rejected one by one by the
+                            // service, so there is nothing left for this request to do.
+                            RetryAction::Successful
                         }
                         Err(msg) => RetryAction::DontRetry(msg.into()),
                     }
@@ -177,11 +181,13 @@ mod tests {
         assert!(matches!(
             logic.should_retry_response(&ElasticsearchResponse {
                 http_response: response,
-                event_status: EventStatus::Rejected,
-                batch_size: 1,
-                events_byte_size: 1,
+                event_status: EventStatus::Delivered,
+                batch_size: 0,
+                events_byte_size: 0,
+                events_estimated_json_encoded_byte_size: 0,
+                grouped_sizes: Default::default(),
             }),
-            RetryAction::DontRetry(_)
+            RetryAction::Successful
         ));
     }
 
@@ -198,9 +204,11 @@ mod tests {
         assert!(matches!(
             logic.should_retry_response(&ElasticsearchResponse {
                 http_response: response,
-                event_status: EventStatus::Errored,
+                event_status: EventStatus::Delivered,
                 batch_size: 1,
                 events_byte_size: 1,
+                events_estimated_json_encoded_byte_size: 1,
+                grouped_sizes: Default::default(),
             }),
             RetryAction::Retry(_)
         ));
@@ -1,6 +1,6 @@
 use std::{
     collections::HashMap,
-    sync::Arc,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
 };
 
@@ -8,23 +8,38 @@ use aws_types::credentials::SharedCredentialsProvider;
 use aws_types::region::Region;
 use bytes::Bytes;
 use futures::future::BoxFuture;
-use http::{Response, Uri};
+use http::{Response, StatusCode, Uri};
 use hyper::{service::Service, Body, Request};
+use lookup::{lookup_v2::ValuePath, metadata_path, path, PathPrefix};
 use tower::ServiceExt;
-use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
-use vector_core::{internal_event::CountByteSize, stream::DriverResponse, ByteSizeOf};
+use vector_common::request_metadata::{GroupedCountByteSize, MetaDescriptive, RequestMetadata};
+use vector_core::{
+    config::{log_schema, LogNamespace},
+    stream::DriverResponse,
+    ByteSizeOf, EstimatedJsonEncodedSizeOf, GroupedSizeOf,
+};
 
 use crate::sinks::elasticsearch::sign_request;
 use crate::{
-    event::{EventFinalizers, EventStatus, Finalizable},
+    event::{Event, EventFinalizers, EventStatus, Finalizable, LogEvent},
     http::{Auth, HttpClient},
+    internal_events::ElasticsearchDocumentsRejected,
     sinks::util::{
         http::{HttpBatchService, RequestConfig},
-        Compression, ElementCount,
+        Compression, ElementCount, RequestBuilder,
     },
+    SourceSender,
+};
+
+use super::{
+    encoder::ProcessedEvent,
+    request_builder::ElasticsearchRequestBuilder,
+    retry::{is_retriable_status, EsErrorDetails, EsResultResponse},
+    ElasticsearchCommon, ElasticsearchConfig, OpenSearchServiceType,
 };
 
-use super::{ElasticsearchCommon, ElasticsearchConfig};
+/// The name of the output of the documents rejected by Elasticsearch.
+pub const REJECTED: &str = "rejected";
 
 #[derive(Clone, Debug)]
 pub struct ElasticsearchRequest {
@@ -33,6 +48,7 @@ pub struct ElasticsearchRequest {
     pub batch_size: usize,
     pub events_byte_size: usize,
     pub metadata: RequestMetadata,
+    pub documents: Arc<BulkDocuments>,
 }
 
 impl ByteSizeOf for ElasticsearchRequest {
@@ -55,22 +71,223 @@ impl Finalizable for ElasticsearchRequest {
 
 impl MetaDescriptive for ElasticsearchRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
+    }
+}
+
+/// The documents of a request, shared by the clones of the request made to retry it, which keep
+/// track of the documents that are still to be written.
+///
+/// Each document keeps the finalizers of its event, so that it can be finalized on its own.
+#[derive(Debug)]
+pub struct BulkDocuments {
+    events: Vec<ProcessedEvent>,
+    state: Mutex<BulkState>,
+}
+
+#[derive(Debug, Default)]
+struct BulkState {
+    /// The indices of the documents still to be written, with their payload, once an attempt
+    /// wrote or rejected the others.
+    pending: Option<(Vec<usize>, Bytes)>,
+    delivered_count: usize,
+    delivered_byte_size: usize,
+    delivered_json_encoded_byte_size: usize,
+    delivered_grouped_sizes: GroupedCountByteSize,
+}
+
+/// The documents of a bulk response, grouped by how they were handled by Elasticsearch.
+#[derive(Debug, Default)]
+struct BulkResolution {
+    delivered: Vec<usize>,
+    retriable: Vec<usize>,
+    /// The rejected documents, with the details of their errors.
+    rejected: Vec<(usize, serde_json::Value)>,
+    /// The reason of the first rejection, for logging.
+    reason: Option<String>,
+}
+
+impl BulkDocuments {
+    pub fn new(events: Vec<ProcessedEvent>) -> Self {
+        Self {
+            events,
+            state: Mutex::default(),
+        }
+    }
+
+    fn pending(&self) -> Option<(Vec<usize>, Bytes)> {
+        self.state.lock().expect("poisoned lock").pending.clone()
+    }
+
+    /// Sorts the documents at `indices`, which were written by an attempt, by the statuses of
+    /// their items in the bulk response `body`.
+    ///
+    /// Items failing with a retriable status are only retried if `retry_partial` is set, and are
+    /// rejected otherwise.
+    fn resolve(
+        &self,
+        indices: &[usize],
+        body: &str,
+        retry_partial: bool,
+    ) -> Result<BulkResolution, String> {
+        let mut resolution = BulkResolution::default();
+        if !body.contains("\"errors\":true") {
+            resolution.delivered = indices.to_vec();
+            return Ok(resolution);
+        }
+
+        let response = EsResultResponse::parse(body)?;
+        if response.items.len() != indices.len() {
+            return Err(format!(
+                "some messages failed, response has {} items for {} documents",
+                response.items.len(),
+                indices.len()
+            ));
+        }
+        for (&index, item) in indices.iter().zip(&response.items) {
+            let result = item.result();
+            match result
+                .status
+                .and_then(|status| StatusCode::from_u16(status).ok())
+            {
+                Some(status) if retry_partial && is_retriable_status(status) => {
+                    resolution.retriable.push(index)
+                }
+                Some(status) if !status.is_success() => resolution.rejected.push((
+                    index,
+                    rejection_details(&self.events[index].index, status, result.error.as_ref()),
+                )),
+                _ => resolution.delivered.push(index),
+            }
+        }
+        if !resolution.rejected.is_empty() {
+            resolution.reason = Some(response.get_error_reason(body));
+        }
+        Ok(resolution)
+    }
+
+    /// Records the delivered documents of `resolution`, and encodes its retriable documents as the
+    /// payload of the next attempt.
+    fn update(&self, resolution: &BulkResolution, request_builder: &ElasticsearchRequestBuilder) {
+        let mut state = self.state.lock().expect("poisoned lock");
+        state.delivered_count += resolution.delivered.len();
+        state.delivered_byte_size += resolution
+            .delivered
+            .iter()
+            .map(|&index| self.events[index].log.size_of())
+            .sum::<usize>();
+        state.delivered_json_encoded_byte_size += resolution
+            .delivered
+            .iter()
+            .map(|&index| self.events[index].log.estimated_json_encoded_size_of())
+            .sum::<usize>();
+        for &index in &resolution.delivered {
+            self.events[index].add_grouped_sizes(&mut state.delivered_grouped_sizes);
+        }
+
+        if resolution.retriable.is_empty() {
+            state.pending = Some((Vec::new(), Bytes::new()));
+            return;
+        }
+        let retriable = resolution
+            .retriable
+            .iter()
+            .map(|&index| self.events[index].clone())
+            .collect::<Vec<_>>();
+        state.pending = match request_builder.encode_events(retriable) {
+            Ok(payload) => Some((resolution.retriable.clone(), payload.into_payload())),
+            Err(error) => {
+                error!(message = "Failed to encode documents to retry.", %error);
+                self.update_status(&resolution.retriable, EventStatus::Errored);
+                Some((Vec::new(), Bytes::new()))
+            }
+        };
+    }
+
+    fn update_status(&self, indices: &[usize], status: EventStatus) {
+        for &index in indices {
+            self.events[index].log.metadata().update_status(status);
+        }
+    }
+
+    fn response(
+        &self,
+        http_response: Response<Bytes>,
+        event_status: EventStatus,
+    ) -> ElasticsearchResponse {
+        let state = self.state.lock().expect("poisoned lock");
+        ElasticsearchResponse {
+            http_response,
+            event_status,
+            batch_size: state.delivered_count,
+            events_byte_size: state.delivered_byte_size,
+            events_estimated_json_encoded_byte_size: state.delivered_json_encoded_byte_size,
+            grouped_sizes: state.delivered_grouped_sizes.clone(),
+        }
+    }
+}
+
+impl Drop for BulkDocuments {
+    fn drop(&mut self) {
+        // The documents still to be written when the last clone of their request is dropped ran
+        // out of retries.
+        let state = self.state.get_mut().expect("poisoned lock");
+        if let Some((indices, _)) = &state.pending {
+            for &index in indices {
+                self.events[index]
+                    .log
+                    .metadata()
+                    .update_status(EventStatus::Errored);
+            }
+        }
     }
 }
 
+fn rejection_details(
+    index: &str,
+    status: StatusCode,
+    error: Option<&EsErrorDetails>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "index": index,
+        "status": status.as_u16(),
+        "error_type": error.map(|error| &error.err_type),
+        "reason": error.map(|error| &error.reason),
+    })
+}
+
+/// Annotates `log` with the details of its rejection by Elasticsearch.
+fn annotate_rejected(log: &mut LogEvent, details: serde_json::Value) {
+    match log.namespace() {
+        LogNamespace::Legacy => log.insert(
+            (
+                PathPrefix::Event,
+                log_schema().metadata_key().concat(path!("rejected")),
+            ),
+            details,
+        ),
+        LogNamespace::Vector => log.insert(metadata_path!("elasticsearch", "rejected"), details),
+    };
+}
+
 #[derive(Clone)]
 pub struct ElasticsearchService {
     batch_service: HttpBatchService<
         BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>>,
         ElasticsearchRequest,
     >,
+    request_builder: ElasticsearchRequestBuilder,
+    rejected: Option<SourceSender>,
+    retry_partial: bool,
 }
 
 impl ElasticsearchService {
     pub fn new(
         http_client: HttpClient<Body>,
         http_request_builder: HttpRequestBuilder,
+        request_builder: ElasticsearchRequestBuilder,
+        rejected: Option<SourceSender>,
+        retry_partial: bool,
     ) -> ElasticsearchService {
         let http_request_builder = Arc::new(http_request_builder);
         let batch_service = HttpBatchService::new(http_client, move |req| {
@@ -79,7 +296,12 @@ impl ElasticsearchService {
                 Box::pin(async move { request_builder.build_request(req).await });
             future
         });
-        ElasticsearchService { batch_service }
+        ElasticsearchService {
+            batch_service,
+            request_builder,
+            rejected,
+            retry_partial,
+        }
     }
 }
 
@@ -87,6 +309,7 @@ pub struct HttpRequestBuilder {
     pub bulk_uri: Uri,
     pub query_params: HashMap<String, String>,
     pub region: Option<Region>,
+    pub service_type: OpenSearchServiceType,
     pub compression: Compression,
     pub http_request_config: RequestConfig,
     pub http_auth: Option<Auth>,
@@ -101,6 +324,7 @@ impl HttpRequestBuilder {
             http_auth: common.http_auth.clone(),
             query_params: common.query_params.clone(),
             region: common.region.clone(),
+            service_type: common.service_type,
             compression: config.compression,
             credentials_provider: common.aws_auth.clone(),
         }
@@ -112,7 +336,13 @@ impl HttpRequestBuilder {
     ) -> Result<Request<Bytes>, crate::Error> {
         let mut builder = Request::post(&self.bulk_uri);
 
-        builder = builder.header("Content-Type", "application/x-ndjson");
+        let content_type = match self.service_type {
+            OpenSearchServiceType::Ingestion => "application/json",
+            OpenSearchServiceType::Managed | OpenSearchServiceType::Serverless => {
+                "application/x-ndjson"
+            }
+        };
+        builder = builder.header("Content-Type", content_type);
 
         if let Some(ce) = self.compression.content_encoding() {
             builder = builder.header("Content-Encoding", ce);
@@ -135,7 +365,13 @@ impl HttpRequestBuilder {
             .expect("Invalid http request value used");
 
         if let Some(credentials_provider) = &self.credentials_provider {
-            sign_request(&mut request, credentials_provider, &self.region).await?;
+            sign_request(
+                self.service_type,
+                &mut request,
+                credentials_provider,
+                &self.region,
+            )
+            .await?;
         }
 
         Ok(request)
@@ -147,6 +383,8 @@ pub struct ElasticsearchResponse {
     pub event_status: EventStatus,
     pub batch_size: usize,
     pub events_byte_size: usize,
+    pub events_estimated_json_encoded_byte_size: usize,
+    pub grouped_sizes: GroupedCountByteSize,
 }
 
 impl DriverResponse for ElasticsearchResponse {
@@ -154,8 +392,17 @@ impl DriverResponse for ElasticsearchResponse {
         self.event_status
     }
 
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(self.batch_size, self.events_byte_size)
+    fn delivered_metadata(&self, metadata: RequestMetadata) -> RequestMetadata {
+        // Documents rejected by Elasticsearch, or still failing once retries are exhausted, are
+        // not counted as sent.
+        RequestMetadata::new(
+            self.batch_size,
+            self.events_byte_size,
+            metadata.request_encoded_size(),
+            metadata.request_wire_size(),
+            self.events_estimated_json_encoded_byte_size,
+        )
+        .with_grouped_sizes(self.grouped_sizes.clone())
     }
 }
 
@@ -172,35 +419,164 @@ impl Service<ElasticsearchRequest> for ElasticsearchService {
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, req: ElasticsearchRequest) -> Self::Future {
         let mut http_service = self.batch_service.clone();
+        let request_builder = self.request_builder.clone();
+        let mut rejected_output = self.rejected.clone();
+        let retry_partial = self.retry_partial;
         Box::pin(async move {
             http_service.ready().await?;
-            let batch_size = req.batch_size;
-            let events_byte_size = req.events_byte_size;
+            let documents = Arc::clone(&req.documents);
+            // Retries only write the documents that previous attempts failed to write.
+            let (indices, req) = match documents.pending() {
+                None => ((0..documents.events.len()).collect::<Vec<_>>(), req),
+                Some((indices, _)) if indices.is_empty() => {
+                    return Ok(documents.response(Response::default(), EventStatus::Delivered));
+                }
+                Some((indices, payload)) => (indices, ElasticsearchRequest { payload, ..req }),
+            };
             let http_response = http_service.call(req).await?;
 
-            let event_status = get_event_status(&http_response);
-            Ok(ElasticsearchResponse {
-                event_status,
-                http_response,
-                batch_size,
-                events_byte_size,
-            })
+            let mut event_status = get_event_status(&http_response);
+            if event_status == EventStatus::Delivered {
+                let body = String::from_utf8_lossy(http_response.body());
+                match documents.resolve(&indices, &body, retry_partial) {
+                    Ok(resolution) => {
+                        documents.update(&resolution, &request_builder);
+                        if let Some(reason) = resolution.reason {
+                            send_rejected(
+                                &documents,
+                                resolution.rejected,
+                                reason,
+                                rejected_output.as_mut(),
+                            )
+                            .await;
+                        }
+                    }
+                    Err(error) => {
+                        error!(message = "Failed to handle bulk response.", %error);
+                        event_status = EventStatus::Rejected;
+                    }
+                }
+            }
+            Ok(documents.response(http_response, event_status))
         })
     }
 }
 
+/// Sends the rejected documents to the `rejected` output, if the sink has one, or rejects their
+/// events otherwise.
+async fn send_rejected(
+    documents: &BulkDocuments,
+    rejected: Vec<(usize, serde_json::Value)>,
+    reason: String,
+    output: Option<&mut SourceSender>,
+) {
+    let indices = rejected.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+    let rerouted = match output {
+        Some(output) => {
+            let events = rejected.into_iter().map(|(index, details)| {
+                let mut log = documents.events[index].log.clone();
+                annotate_rejected(&mut log, details);
+                Event::from(log)
+            });
+            output.send_batch_named(REJECTED, events).await.is_ok()
+        }
+        None => false,
+    };
+    if !rerouted {
+        documents.update_status(&indices, EventStatus::Rejected);
+    }
+    emit!(ElasticsearchDocumentsRejected {
+        count: indices.len(),
+        reason,
+        rerouted,
+    });
+}
+
 fn get_event_status(response: &Response<Bytes>) -> EventStatus {
     let status = response.status();
     if status.is_success() {
-        let body = String::from_utf8_lossy(response.body());
-        if body.contains("\"errors\":true") {
-            EventStatus::Rejected
-        } else {
-            EventStatus::Delivered
-        }
+        // Documents failing to be written are handled one by one.
+        EventStatus::Delivered
     } else if status.is_server_error() {
         EventStatus::Errored
     } else {
         EventStatus::Rejected
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{event::Value, sinks::elasticsearch::BulkAction};
+
+    fn documents(count: usize) -> BulkDocuments {
+        BulkDocuments::new(
+            (0..count)
+                .map(|index| ProcessedEvent {
+                    index: "vector".into(),
+                    bulk_action: BulkAction::Index,
+                    log: LogEvent::from(format!("message {}", index)),
+                    id: None,
+                })
+                .collect(),
+        )
+    }
+
+    const BODY: &str = r#"{"took":12,"errors":true,"items":[{"index":{"_index":"vector","status":201}},{"index":{"_index":"vector","status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse field [message]"}}},{"index":{"_index":"vector","status":429,"error":{"type":"es_rejected_execution_exception","reason":"rejected execution"}}}]}"#;
+
+    #[test]
+    fn resolves_bulk_items() {
+        let resolution = documents(3).resolve(&[0, 1, 2], BODY, true).unwrap();
+
+        assert_eq!(resolution.delivered, vec![0]);
+        assert_eq!(resolution.retriable, vec![2]);
+        assert_eq!(resolution.rejected.len(), 1);
+        assert_eq!(resolution.rejected[0].0, 1);
+        assert_eq!(
+            resolution.rejected[0].1,
+            serde_json::json!({
+                "index": "vector",
+                "status": 400,
+                "error_type": "mapper_parsing_exception",
+                "reason": "failed to parse field [message]",
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_retriable_items_without_partial_retries() {
+        let resolution = documents(3).resolve(&[0, 1, 2], BODY, false).unwrap();
+
+        assert_eq!(resolution.delivered, vec![0]);
+        assert!(resolution.retriable.is_empty());
+        assert_eq!(
+            resolution
+                .rejected
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn resolves_retried_items_by_position() {
+        let body = r#"{"took":3,"errors":true,"items":[{"index":{"_index":"vector","status":500}},{"index":{"_index":"vector","status":201}}]}"#;
+        let resolution = documents(4).resolve(&[1, 3], body, true).unwrap();
+
+        assert_eq!(resolution.retriable, vec![1]);
+        assert_eq!(resolution.delivered, vec![3]);
+        assert!(documents(4).resolve(&[1, 2, 3], body, true).is_err());
+    }
+
+    #[test]
+    fn annotates_rejected_documents() {
+        let mut log = LogEvent::from("message");
+        annotate_rejected(&mut log, serde_json::json!({ "status": 400 }));
+
+        assert_eq!(
+            log.get("metadata.rejected.status"),
+            Some(&Value::from(400_i64))
+        );
+    }
+}
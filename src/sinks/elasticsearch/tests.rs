@@ -1,12 +1,16 @@
 use std::{collections::BTreeMap, convert::TryFrom};
 
+use aws_types::region::Region;
+
 use crate::{
+    aws::AwsAuthentication,
     codecs::Transformer,
     event::{LogEvent, Metric, MetricKind, MetricValue, Value},
     sinks::{
         elasticsearch::{
             sink::process_log, BulkAction, BulkConfig, DataStreamConfig, ElasticsearchApiVersion,
-            ElasticsearchCommon, ElasticsearchConfig, ElasticsearchMode,
+            ElasticsearchAuth, ElasticsearchCommon, ElasticsearchConfig, ElasticsearchMode,
+            OpenSearchServiceType,
         },
         util::encoding::Encoder,
     },
@@ -352,3 +356,81 @@ async fn allows_using_only_fields() {
     assert_eq!(std::str::from_utf8(&encoded).unwrap(), expected);
     assert_eq!(encoded.len(), encoded_size);
 }
+
+fn aws_access_key_auth() -> Option<ElasticsearchAuth> {
+    Some(ElasticsearchAuth::Aws(AwsAuthentication::AccessKey {
+        access_key_id: String::from("access-key").into(),
+        secret_access_key: String::from("secret-key").into(),
+    }))
+}
+
+#[tokio::test]
+async fn serverless_requires_aws_auth() {
+    let config = ElasticsearchConfig {
+        endpoints: vec![String::from("https://abc123.eu-west-1.aoss.amazonaws.com")],
+        opensearch_service_type: OpenSearchServiceType::Serverless,
+        ..Default::default()
+    };
+
+    assert!(ElasticsearchCommon::parse_single(&config).await.is_err());
+}
+
+#[tokio::test]
+async fn infers_region_of_serverless_collections() {
+    let config = ElasticsearchConfig {
+        endpoints: vec![String::from("https://abc123.eu-west-1.aoss.amazonaws.com")],
+        auth: aws_access_key_auth(),
+        opensearch_service_type: OpenSearchServiceType::Serverless,
+        ..Default::default()
+    };
+    let es = ElasticsearchCommon::parse_single(&config).await.unwrap();
+
+    assert_eq!(es.region, Some(Region::new("eu-west-1")));
+    assert_eq!(es.bulk_uri.path(), "/_bulk");
+    assert!(es.request_builder.encoder.suppress_type_name);
+}
+
+#[tokio::test]
+async fn encodes_json_arrays_for_ingestion_pipelines() {
+    use chrono::{TimeZone, Utc};
+
+    use crate::config::log_schema;
+
+    let config = ElasticsearchConfig {
+        endpoints: vec![String::from(
+            "https://my-pipeline-abc123.us-east-1.osis.amazonaws.com/log/ingest",
+        )],
+        auth: aws_access_key_auth(),
+        opensearch_service_type: OpenSearchServiceType::Ingestion,
+        ..Default::default()
+    };
+    let es = ElasticsearchCommon::parse_single(&config).await.unwrap();
+    assert_eq!(
+        es.bulk_uri.to_string(),
+        "https://my-pipeline-abc123.us-east-1.osis.amazonaws.com/log/ingest"
+    );
+    assert_eq!(es.region, Some(Region::new("us-east-1")));
+
+    let events = ["hello", "there"]
+        .into_iter()
+        .map(|message| {
+            let mut log = LogEvent::from(message);
+            log.insert(
+                log_schema().timestamp_key(),
+                Utc.ymd(2020, 12, 1).and_hms(1, 2, 3),
+            );
+            process_log(log, &es.mode, &None, &config.encoding).unwrap()
+        })
+        .collect();
+
+    let mut encoded = vec![];
+    let encoded_size = es
+        .request_builder
+        .encoder
+        .encode_input(events, &mut encoded)
+        .unwrap();
+
+    let expected = r#"[{"message":"hello","timestamp":"2020-12-01T01:02:03Z"},{"message":"there","timestamp":"2020-12-01T01:02:03Z"}]"#;
+    assert_eq!(std::str::from_utf8(&encoded).unwrap(), expected);
+    assert_eq!(encoded.len(), encoded_size);
+}
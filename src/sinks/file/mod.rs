@@ -1,4 +1,4 @@
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
 use async_trait::async_trait;
@@ -33,9 +33,11 @@ use crate::{
     template::Template,
 };
 mod bytes_path;
+mod rotation;
 use std::convert::TryFrom;
 
 use bytes_path::BytesPath;
+pub use rotation::FileRotationConfig;
 
 /// Configuration for the `file` sink.
 #[configurable_component(sink("file"))]
@@ -60,6 +62,9 @@ pub struct FileSinkConfig {
     )]
     pub compression: Compression,
 
+    #[configurable(derived)]
+    pub rotate: Option<FileRotationConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -76,6 +81,7 @@ impl GenerateConfig for FileSinkConfig {
             idle_timeout_secs: None,
             encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
             compression: Default::default(),
+            rotate: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -151,6 +157,20 @@ impl OutFile {
     }
 }
 
+/// An open file, with what it is rotated by.
+struct OpenFile {
+    file: OutFile,
+    /// The size of the file when opened, plus the bytes written to it since.
+    size: u64,
+    created_at: SystemTime,
+}
+
+impl OpenFile {
+    async fn close(&mut self) -> Result<(), std::io::Error> {
+        self.file.close().await
+    }
+}
+
 #[async_trait::async_trait]
 impl SinkConfig for FileSinkConfig {
     async fn build(
@@ -178,8 +198,9 @@ pub struct FileSink {
     transformer: Transformer,
     encoder: Encoder<Framer>,
     idle_timeout: Duration,
-    files: ExpiringHashMap<Bytes, OutFile>,
+    files: ExpiringHashMap<Bytes, OpenFile>,
     compression: Compression,
+    rotation: Option<FileRotationConfig>,
     events_sent: Registered<EventsSent>,
 }
 
@@ -188,6 +209,9 @@ impl FileSink {
         let transformer = config.encoding.transformer();
         let (framer, serializer) = config.encoding.build(SinkType::StreamBased)?;
         let encoder = Encoder::<Framer>::new(framer, serializer);
+        if let Some(rotation) = &config.rotate {
+            rotation.validate(config.compression)?;
+        }
 
         Ok(Self {
             path: config.path.clone(),
@@ -196,6 +220,7 @@ impl FileSink {
             idle_timeout: Duration::from_secs(config.idle_timeout_secs.unwrap_or(30)),
             files: ExpiringHashMap::default(),
             compression: config.compression,
+            rotation: config.rotate.clone(),
             events_sent: register!(EventsSent::from(Output(None))),
         })
     }
@@ -327,7 +352,15 @@ impl FileSink {
                 }
             };
 
-            let outfile = OutFile::new(file, self.compression);
+            let size = match file.metadata().await {
+                Ok(metadata) => metadata.len(),
+                Err(_) => 0,
+            };
+            let outfile = OpenFile {
+                created_at: rotation::created_at(&file).await,
+                file: OutFile::new(file, self.compression),
+                size,
+            };
 
             self.files.insert_at(path.clone(), outfile, next_deadline);
             emit!(FileOpen {
@@ -339,14 +372,24 @@ impl FileSink {
         trace!(message = "Writing an event to file.", path = ?path);
         let event_size = event.estimated_json_encoded_size_of();
         let finalizers = event.take_finalizers();
-        match write_event_to_file(file, event, &self.transformer, &mut self.encoder).await {
+        match write_event_to_file(&mut file.file, event, &self.transformer, &mut self.encoder).await
+        {
             Ok(byte_size) => {
+                file.size += byte_size as u64;
+                let rotate = self.rotation.as_ref().map_or(false, |rotation| {
+                    rotation.is_due(file.size, file.created_at)
+                });
+
                 finalizers.update_status(EventStatus::Delivered);
                 self.events_sent.emit(CountByteSize(1, event_size));
                 emit!(FileBytesSent {
                     byte_size,
                     file: String::from_utf8_lossy(&path),
                 });
+
+                if rotate {
+                    self.rotate(path).await;
+                }
             }
             Err(error) => {
                 finalizers.update_status(EventStatus::Errored);
@@ -360,6 +403,40 @@ impl FileSink {
             }
         }
     }
+
+    /// Closes the open file at `path` and rotates it.
+    async fn rotate(&mut self, path: Bytes) {
+        let rotation = match &self.rotation {
+            Some(rotation) => rotation,
+            None => return,
+        };
+        if let Some((mut file, _)) = self.files.remove(&path) {
+            emit!(FileOpen {
+                count: self.files.len()
+            });
+            if let Err(error) = file.close().await {
+                emit!(FileIoError {
+                    error,
+                    code: "failed_closing_file",
+                    message: "Failed to close file.",
+                    path: &path,
+                    dropped_events: 0,
+                });
+                return;
+            }
+        }
+
+        trace!(message = "Rotating file.", ?path);
+        if let Err(error) = rotation.rotate(BytesPath::new(path.clone()).as_ref()).await {
+            emit!(FileIoError {
+                error,
+                code: "failed_rotating_file",
+                message: "Failed to rotate the file.",
+                path: &path,
+                dropped_events: 0,
+            });
+        }
+    }
 }
 
 async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
@@ -434,6 +511,7 @@ mod tests {
             idle_timeout_secs: None,
             encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
             compression: Compression::None,
+            rotate: None,
             acknowledgements: Default::default(),
         };
 
@@ -456,6 +534,7 @@ mod tests {
             idle_timeout_secs: None,
             encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
             compression: Compression::Gzip,
+            rotate: None,
             acknowledgements: Default::default(),
         };
 
@@ -478,6 +557,7 @@ mod tests {
             idle_timeout_secs: None,
             encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
             compression: Compression::Zstd,
+            rotate: None,
             acknowledgements: Default::default(),
         };
 
@@ -505,6 +585,7 @@ mod tests {
             idle_timeout_secs: None,
             encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
             compression: Compression::None,
+            rotate: None,
             acknowledgements: Default::default(),
         };
 
@@ -582,6 +663,7 @@ mod tests {
             idle_timeout_secs: Some(1),
             encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
             compression: Compression::None,
+            rotate: None,
             acknowledgements: Default::default(),
         };
 
@@ -625,6 +707,46 @@ mod tests {
         sink_handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn rotation() {
+        let directory = temp_dir();
+        let path = directory.join("rotated.log");
+
+        let config = FileSinkConfig {
+            path: path.to_string_lossy().as_ref().try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
+            compression: Compression::None,
+            rotate: Some(FileRotationConfig {
+                max_bytes: Some(100),
+                compression: Compression::Gzip,
+                max_files: Some(2),
+                ..Default::default()
+            }),
+            acknowledgements: Default::default(),
+        };
+
+        // Each file is rotated once two 64 character lines are written to it.
+        let (input, _) = random_lines_with_stream(64, 10, None);
+
+        run_assert_log_sink(config, input.clone()).await;
+
+        let mut rotated: Vec<_> = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        rotated.sort();
+        assert_eq!(rotated.len(), 2);
+        let output: Vec<_> = rotated
+            .into_iter()
+            .flat_map(|path| {
+                assert!(path.to_string_lossy().ends_with(".gz"));
+                lines_from_gzip_file(path)
+            })
+            .collect();
+        assert_eq!(output, input[6..]);
+    }
+
     async fn run_assert_log_sink(config: FileSinkConfig, events: Vec<String>) {
         run_assert_sink(
             config,
@@ -0,0 +1,190 @@
+//! Rotation of the files written by the `file` sink.
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
+use chrono::Utc;
+use tokio::{
+    fs::{self, File},
+    io::{self, AsyncWrite, AsyncWriteExt},
+};
+use vector_config::configurable_component;
+
+use super::Compression;
+
+/// The format of the timestamps appended to the names of rotated files, which sort in the order
+/// files were rotated in.
+const ROTATED_TIME_FORMAT: &str = "%Y%m%d-%H%M%S%.6f";
+
+/// Rotation options for the written files.
+///
+/// Files are rotated by renaming them, appending the time of their rotation to their name, such as
+/// `/var/log/vector.log.20221014-120000.000000`. Events are then written to a new file.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct FileRotationConfig {
+    /// The size, in bytes, written files are rotated at.
+    ///
+    /// Files are rotated once writing an event makes them reach this size, which includes the size
+    /// of files written before they were opened by Vector.
+    pub max_bytes: Option<u64>,
+
+    /// The age, in seconds, written files are rotated at.
+    ///
+    /// Files are rotated once an event is written to them after they reached this age, which is
+    /// counted from their creation, or from when Vector opened them if the filesystem does not
+    /// record the creation of files.
+    pub max_age_secs: Option<u64>,
+
+    /// The compression rotated files are compressed with once rotated.
+    ///
+    /// The extension of the compression is appended to the names of compressed files. Only
+    /// uncompressed files written with `compression` set to `none` can be compressed once rotated.
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub compression: Compression,
+
+    /// The maximum number of rotated files kept for each written file.
+    ///
+    /// The oldest rotated files are deleted once there are more of them.
+    pub max_files: Option<usize>,
+
+    /// The maximum total size, in bytes, of the rotated files kept for each written file.
+    ///
+    /// The oldest rotated files are deleted once their total size is larger.
+    pub max_total_bytes: Option<u64>,
+}
+
+impl FileRotationConfig {
+    pub(super) fn validate(&self, compression: Compression) -> crate::Result<()> {
+        if self.max_bytes.is_none() && self.max_age_secs.is_none() {
+            return Err("Either `rotate.max_bytes` or `rotate.max_age_secs` must be set.".into());
+        }
+        if compression != Compression::None && self.compression != Compression::None {
+            return Err(
+                "`rotate.compression` can't be set for files compressed as set by `compression`."
+                    .into(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Whether a file of `size` bytes, created at `created_at`, is due to be rotated.
+    pub(super) fn is_due(&self, size: u64, created_at: SystemTime) -> bool {
+        self.max_bytes.map_or(false, |max_bytes| size >= max_bytes)
+            || self.max_age_secs.map_or(false, |max_age_secs| {
+                created_at
+                    .elapsed()
+                    .map_or(false, |age| age >= Duration::from_secs(max_age_secs))
+            })
+    }
+
+    /// Rotates the closed file at `path`, compressing it and deleting the rotated files of `path`
+    /// that are no longer kept.
+    pub(super) async fn rotate(&self, path: &Path) -> io::Result<()> {
+        let rotated =
+            append_to_file_name(path, &Utc::now().format(ROTATED_TIME_FORMAT).to_string());
+        fs::rename(path, &rotated).await?;
+
+        match self.compression {
+            Compression::None => (),
+            Compression::Gzip => {
+                compress(&rotated, "gz", |file| Box::new(GzipEncoder::new(file))).await?
+            }
+            Compression::Zstd => {
+                compress(&rotated, "zst", |file| Box::new(ZstdEncoder::new(file))).await?
+            }
+        }
+
+        self.prune(path).await
+    }
+
+    /// Deletes the oldest rotated files of `path` exceeding `max_files` or `max_total_bytes`.
+    async fn prune(&self, path: &Path) -> io::Result<()> {
+        if self.max_files.is_none() && self.max_total_bytes.is_none() {
+            return Ok(());
+        }
+
+        let directory = match path.parent() {
+            Some(parent) if parent != Path::new("") => parent,
+            _ => Path::new("."),
+        };
+        let prefix = format!(
+            "{}.",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+
+        let mut rotated = Vec::new();
+        let mut entries = fs::read_dir(directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let is_rotated = name
+                .strip_prefix(prefix.as_str())
+                .map_or(false, |time| time.starts_with(|c: char| c.is_ascii_digit()));
+            if is_rotated {
+                rotated.push((name, entry.metadata().await?.len()));
+            }
+        }
+        // Newest first.
+        rotated.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+
+        let mut total_bytes = 0;
+        for (index, (name, len)) in rotated.into_iter().enumerate() {
+            total_bytes += len;
+            if self.max_files.map_or(false, |max_files| index >= max_files)
+                || self
+                    .max_total_bytes
+                    .map_or(false, |max_total_bytes| total_bytes > max_total_bytes)
+            {
+                fs::remove_file(directory.join(name)).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The creation time of `file`, or the current time if it is unknown.
+pub(super) async fn created_at(file: &File) -> SystemTime {
+    file.metadata()
+        .await
+        .and_then(|metadata| metadata.created())
+        .unwrap_or_else(|_| SystemTime::now())
+}
+
+/// Appends `.<suffix>` to the file name of `path`.
+fn append_to_file_name(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Compresses the file at `path` to a file with the `extension` appended to its name, and deletes
+/// it.
+async fn compress(
+    path: &Path,
+    extension: &str,
+    encoder: impl FnOnce(File) -> Box<dyn AsyncWrite + Send + Unpin>,
+) -> io::Result<()> {
+    let compressed = append_to_file_name(path, extension);
+    let mut encoder = encoder(File::create(&compressed).await?);
+    io::copy(&mut File::open(path).await?, &mut encoder).await?;
+    encoder.shutdown().await?;
+    drop(encoder);
+    fs::OpenOptions::new()
+        .write(true)
+        .open(&compressed)
+        .await?
+        .sync_all()
+        .await?;
+
+    fs::remove_file(path).await
+}
@@ -0,0 +1,232 @@
+use futures::FutureExt;
+use http::uri::{InvalidUri, Scheme, Uri};
+use indoc::indoc;
+use snafu::{ResultExt, Snafu};
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
+use vector_config::configurable_component;
+
+use super::{
+    proto::{big_query_write_client::BigQueryWriteClient, GetWriteStreamRequest, WriteStreamView},
+    service::{default_stream, routed, AuthInterceptor, BigqueryClient},
+    sink::BigquerySink,
+};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    gcp::{GcpAuthConfig, GcpAuthenticator, Scope},
+    sinks::{
+        util::{BatchConfig, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+const BIGQUERY_STORAGE_URL: &str = "https://bigquerystorage.googleapis.com";
+
+#[derive(Debug, Snafu)]
+enum BigqueryConfigError {
+    #[snafu(display("Invalid endpoint URI: {}", source))]
+    Uri { source: InvalidUri },
+    #[snafu(display("Could not create endpoint: {}", source))]
+    Endpoint { source: tonic::transport::Error },
+    #[snafu(display("Could not set up endpoint TLS settings: {}", source))]
+    EndpointTls { source: tonic::transport::Error },
+}
+
+/// How rows are written to the table.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BigqueryWriteMode {
+    /// Rows are appended to the default stream of the table.
+    ///
+    /// Rows are available as soon as they are appended, but the rows of batches that are retried
+    /// may be written more than once.
+    #[default]
+    Default,
+
+    /// The rows of each batch are appended to a pending stream, which is committed to the table
+    /// once they all are.
+    ///
+    /// Rows are appended at offsets of the stream, so the rows of batches that are retried are
+    /// written exactly once, and batches are available in the table all at once.
+    Pending,
+}
+
+/// Configuration for the `gcp_bigquery` sink.
+#[configurable_component(sink("gcp_bigquery"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BigqueryConfig {
+    /// The project of the table.
+    #[configurable(metadata(docs::examples = "my-project"))]
+    pub project: String,
+
+    /// The dataset of the table.
+    #[configurable(metadata(docs::examples = "my_dataset"))]
+    pub dataset: String,
+
+    /// The table to write events to.
+    ///
+    /// The table must already exist. Its columns hold the values of the top-level fields of events
+    /// of the same names, and those missing from events, or holding values that don't fit the
+    /// types of their columns, are left null.
+    #[configurable(metadata(docs::examples = "events"))]
+    pub table: String,
+
+    /// The endpoint of the BigQuery Storage API.
+    #[configurable(metadata(docs::examples = "https://bigquerystorage.googleapis.com"))]
+    pub endpoint: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub write_mode: BigqueryWriteMode,
+
+    #[serde(default, flatten)]
+    pub auth: GcpAuthConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<BigqueryDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BigqueryDefaultBatchSettings;
+
+impl SinkBatchSettings for BigqueryDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(10_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+impl GenerateConfig for BigqueryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            project = "my-project"
+            dataset = "my_dataset"
+            table = "events"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for BigqueryConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let auth = self.auth.build(Scope::CloudPlatform).await?;
+        auth.spawn_regenerate_token();
+        let client = self.client(auth)?;
+        let table = format!(
+            "projects/{}/datasets/{}/tables/{}",
+            self.project, self.dataset, self.table
+        );
+
+        let healthcheck = healthcheck(client.clone(), default_stream(&table)).boxed();
+
+        let sink = BigquerySink {
+            client,
+            table,
+            write_mode: self.write_mode,
+            transformer: self.encoding.clone(),
+            batch_settings: self.batch.into_batcher_settings()?,
+            request: self.request.unwrap_with(&TowerRequestConfig::default()),
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl BigqueryConfig {
+    fn client(&self, auth: GcpAuthenticator) -> crate::Result<BigqueryClient> {
+        let uri: Uri = self
+            .endpoint
+            .as_deref()
+            .unwrap_or(BIGQUERY_STORAGE_URL)
+            .parse()
+            .context(UriSnafu)?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let host = uri.host().unwrap_or("bigquerystorage.googleapis.com");
+        let mut tls_config = ClientTlsConfig::new().domain_name(host);
+        if let Some((cert, key)) = tls.identity_pem() {
+            tls_config = tls_config.identity(Identity::from_pem(cert, key));
+        }
+        for authority in tls.authorities_pem() {
+            tls_config = tls_config.ca_certificate(Certificate::from_pem(authority));
+        }
+
+        let mut endpoint: Endpoint = uri.to_string().parse().context(EndpointSnafu)?;
+        if uri.scheme() != Some(&Scheme::HTTP) {
+            endpoint = endpoint.tls_config(tls_config).context(EndpointTlsSnafu)?;
+        }
+
+        Ok(BigQueryWriteClient::with_interceptor(
+            endpoint.connect_lazy(),
+            AuthInterceptor { auth },
+        ))
+    }
+}
+
+async fn healthcheck(mut client: BigqueryClient, stream: String) -> crate::Result<()> {
+    let request = GetWriteStreamRequest {
+        name: stream.clone(),
+        view: WriteStreamView::Basic as i32,
+    };
+    client
+        .get_write_stream(routed(request, "name", &stream))
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<BigqueryConfig>();
+    }
+
+    #[test]
+    fn parses_write_mode() {
+        let config: BigqueryConfig = toml::from_str(indoc! {r#"
+            project = "my-project"
+            dataset = "my_dataset"
+            table = "events"
+            write_mode = "pending"
+        "#})
+        .unwrap();
+
+        assert_eq!(config.write_mode, BigqueryWriteMode::Pending);
+    }
+}
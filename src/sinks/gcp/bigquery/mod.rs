@@ -0,0 +1,43 @@
+//! The `gcp_bigquery` sink.
+//!
+//! Events are written as rows of a BigQuery table with the [Storage Write API][write_api], either
+//! appended to the default stream of the table or to pending streams committed once whole.
+//!
+//! [write_api]: https://cloud.google.com/bigquery/docs/write-api
+
+mod config;
+mod schema;
+mod service;
+mod sink;
+
+pub use self::config::BigqueryConfig;
+
+// prost emits some generated code that includes clones on `Arc`
+// objects, which causes a clippy ding on this block. We don't
+// directly control the generated code, so allow this lint here.
+#[allow(clippy::clone_on_ref_ptr)]
+#[allow(warnings)]
+mod proto {
+    // The generated code refers to the messages of other packages by relative paths, so the
+    // modules are nested the way the packages are.
+    pub mod google {
+        pub mod cloud {
+            pub mod bigquery {
+                pub mod storage {
+                    pub mod v1 {
+                        include!(concat!(
+                            env!("OUT_DIR"),
+                            "/google.cloud.bigquery.storage.v1.rs"
+                        ));
+                    }
+                }
+            }
+        }
+
+        pub mod rpc {
+            include!(concat!(env!("OUT_DIR"), "/google.rpc.rs"));
+        }
+    }
+
+    pub use self::google::cloud::bigquery::storage::v1::*;
+}
@@ -0,0 +1,375 @@
+//! Converting events to rows of the schemas of tables, encoded as Protocol Buffers messages.
+
+use std::collections::BTreeMap;
+
+use bytes::BufMut;
+use chrono::{DateTime, NaiveDate, Utc};
+use prost::encoding::{encode_key, encode_varint, WireType};
+use prost_types::{
+    field_descriptor_proto::{Label, Type},
+    DescriptorProto, FieldDescriptorProto,
+};
+
+use super::proto::{
+    table_field_schema::{Mode, Type as ColumnType},
+    TableFieldSchema,
+};
+use crate::event::{LogEvent, Value};
+
+/// Encodes events as rows of a table, as messages of a descriptor built from the schema of the
+/// table.
+///
+/// Columns hold the values of the top-level fields of events of the same names. Values that don't
+/// fit the types of their columns are left out, so their columns are left null.
+#[derive(Clone, Debug)]
+pub struct RowEncoder {
+    descriptor: DescriptorProto,
+    columns: Vec<Column>,
+}
+
+#[derive(Clone, Debug)]
+struct Column {
+    name: String,
+    number: u32,
+    repeated: bool,
+    kind: ColumnKind,
+}
+
+#[derive(Clone, Debug)]
+enum ColumnKind {
+    String,
+    Json,
+    Int64,
+    Double,
+    Bool,
+    Bytes,
+    Timestamp,
+    Date,
+    Time,
+    Datetime,
+    Struct(Vec<Column>),
+}
+
+impl RowEncoder {
+    pub fn new(fields: &[TableFieldSchema]) -> Self {
+        let (descriptor, columns) = message("Row", fields);
+        Self {
+            descriptor,
+            columns,
+        }
+    }
+
+    /// The descriptor of the messages rows are encoded as, which is self-contained.
+    pub const fn descriptor(&self) -> &DescriptorProto {
+        &self.descriptor
+    }
+
+    pub fn encode(&self, log: &LogEvent) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        if let Some(fields) = log.as_map() {
+            encode_message(&self.columns, fields, &mut buffer);
+        }
+        buffer
+    }
+}
+
+/// Builds the descriptor of the messages of `fields`, with the messages of their structs nested
+/// in it.
+fn message(name: &str, fields: &[TableFieldSchema]) -> (DescriptorProto, Vec<Column>) {
+    let mut descriptor = DescriptorProto {
+        name: Some(name.to_owned()),
+        ..Default::default()
+    };
+    let mut columns = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let number = index as u32 + 1;
+        let mut type_name = None;
+        let (field_type, kind) = match field.r#type() {
+            ColumnType::String
+            | ColumnType::Numeric
+            | ColumnType::Bignumeric
+            | ColumnType::Geography
+            | ColumnType::Interval => (Type::String, ColumnKind::String),
+            ColumnType::Json => (Type::String, ColumnKind::Json),
+            ColumnType::Int64 => (Type::Int64, ColumnKind::Int64),
+            ColumnType::Double => (Type::Double, ColumnKind::Double),
+            ColumnType::Bool => (Type::Bool, ColumnKind::Bool),
+            ColumnType::Bytes => (Type::Bytes, ColumnKind::Bytes),
+            // Timestamps are written as microseconds since the epoch, and dates as days since it.
+            ColumnType::Timestamp => (Type::Int64, ColumnKind::Timestamp),
+            ColumnType::Date => (Type::Int32, ColumnKind::Date),
+            ColumnType::Time => (Type::String, ColumnKind::Time),
+            ColumnType::Datetime => (Type::String, ColumnKind::Datetime),
+            ColumnType::Struct => {
+                let name = format!("Struct{}", number);
+                let (nested, columns) = message(&name, &field.fields);
+                descriptor.nested_type.push(nested);
+                type_name = Some(name);
+                (Type::Message, ColumnKind::Struct(columns))
+            }
+            ColumnType::Unspecified => {
+                warn!(
+                    message = "Column has a type that isn't supported, leaving it null.",
+                    column = %field.name
+                );
+                continue;
+            }
+        };
+
+        let repeated = field.mode() == Mode::Repeated;
+        let label = if repeated {
+            Label::Repeated
+        } else {
+            Label::Optional
+        };
+        descriptor.field.push(FieldDescriptorProto {
+            name: Some(field.name.clone()),
+            number: Some(number as i32),
+            label: Some(label as i32),
+            r#type: Some(field_type as i32),
+            type_name,
+            ..Default::default()
+        });
+        columns.push(Column {
+            name: field.name.clone(),
+            number,
+            repeated,
+            kind,
+        });
+    }
+
+    (descriptor, columns)
+}
+
+fn encode_message(columns: &[Column], fields: &BTreeMap<String, Value>, buffer: &mut Vec<u8>) {
+    for column in columns {
+        match fields.get(&column.name) {
+            None | Some(Value::Null) => (),
+            Some(Value::Array(values)) if column.repeated => {
+                for value in values {
+                    encode_value(column, value, buffer);
+                }
+            }
+            Some(value) => encode_value(column, value, buffer),
+        }
+    }
+}
+
+fn encode_value(column: &Column, value: &Value, buffer: &mut Vec<u8>) {
+    let tag = column.number;
+    match (&column.kind, value) {
+        (_, Value::Null) => (),
+        (ColumnKind::String, value) => {
+            encode_bytes(tag, value.to_string_lossy().as_bytes(), buffer);
+        }
+        (ColumnKind::Json, value) => {
+            let json = serde_json::to_vec(value).expect("values should always encode as JSON");
+            encode_bytes(tag, &json, buffer);
+        }
+        (ColumnKind::Int64, Value::Integer(integer)) => encode_int(tag, *integer, buffer),
+        (ColumnKind::Double, Value::Integer(integer)) => {
+            encode_double(tag, *integer as f64, buffer);
+        }
+        (ColumnKind::Double, Value::Float(float)) => encode_double(tag, float.into_inner(), buffer),
+        (ColumnKind::Bool, Value::Boolean(boolean)) => encode_int(tag, *boolean as i64, buffer),
+        (ColumnKind::Bytes, Value::Bytes(bytes)) => encode_bytes(tag, bytes, buffer),
+        (ColumnKind::Timestamp, value) => {
+            if let Some(timestamp) = timestamp(value) {
+                encode_int(tag, timestamp.timestamp_micros(), buffer);
+            }
+        }
+        (ColumnKind::Date, value) => {
+            let date = match value {
+                Value::Timestamp(timestamp) => Some(timestamp.naive_utc().date()),
+                Value::Bytes(bytes) => std::str::from_utf8(bytes)
+                    .ok()
+                    .and_then(|string| NaiveDate::parse_from_str(string, "%Y-%m-%d").ok()),
+                _ => None,
+            };
+            if let Some(date) = date {
+                let epoch = NaiveDate::from_ymd(1970, 1, 1);
+                encode_int(tag, (date - epoch).num_days(), buffer);
+            }
+        }
+        (ColumnKind::Time, Value::Timestamp(timestamp)) => {
+            let time = timestamp.format("%H:%M:%S%.6f").to_string();
+            encode_bytes(tag, time.as_bytes(), buffer);
+        }
+        (ColumnKind::Datetime, Value::Timestamp(timestamp)) => {
+            let datetime = timestamp.format("%Y-%m-%d %H:%M:%S%.6f").to_string();
+            encode_bytes(tag, datetime.as_bytes(), buffer);
+        }
+        (ColumnKind::Time | ColumnKind::Datetime, Value::Bytes(bytes)) => {
+            encode_bytes(tag, bytes, buffer);
+        }
+        (ColumnKind::Struct(columns), Value::Object(fields)) => {
+            let mut nested = Vec::new();
+            encode_message(columns, fields, &mut nested);
+            encode_bytes(tag, &nested, buffer);
+        }
+        _ => (),
+    }
+}
+
+fn timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Timestamp(timestamp) => Some(*timestamp),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|string| DateTime::parse_from_rfc3339(string).ok())
+            .map(|timestamp| timestamp.with_timezone(&Utc)),
+        _ => None,
+    }
+}
+
+fn encode_bytes(tag: u32, bytes: &[u8], buffer: &mut Vec<u8>) {
+    encode_key(tag, WireType::LengthDelimited, buffer);
+    encode_varint(bytes.len() as u64, buffer);
+    buffer.put_slice(bytes);
+}
+
+/// Encodes `int64`, `int32` and `bool` fields, which negative `int32` values are sign-extended for.
+fn encode_int(tag: u32, integer: i64, buffer: &mut Vec<u8>) {
+    encode_key(tag, WireType::Varint, buffer);
+    encode_varint(integer as u64, buffer);
+}
+
+fn encode_double(tag: u32, double: f64, buffer: &mut Vec<u8>) {
+    encode_key(tag, WireType::SixtyFourBit, buffer);
+    buffer.put_f64_le(double);
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use prost::Message;
+
+    use super::*;
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Row {
+        #[prost(string, optional, tag = "1")]
+        message: Option<String>,
+        #[prost(int64, optional, tag = "2")]
+        status: Option<i64>,
+        #[prost(int64, optional, tag = "3")]
+        timestamp: Option<i64>,
+        #[prost(int32, optional, tag = "4")]
+        date: Option<i32>,
+        #[prost(string, repeated, tag = "5")]
+        tags: Vec<String>,
+        #[prost(message, optional, tag = "6")]
+        http: Option<Http>,
+        #[prost(string, optional, tag = "7")]
+        payload: Option<String>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Http {
+        #[prost(string, optional, tag = "1")]
+        method: Option<String>,
+        #[prost(double, optional, tag = "2")]
+        duration: Option<f64>,
+    }
+
+    fn field(name: &str, column_type: ColumnType, mode: Mode) -> TableFieldSchema {
+        TableFieldSchema {
+            name: name.to_owned(),
+            r#type: column_type as i32,
+            mode: mode as i32,
+            ..Default::default()
+        }
+    }
+
+    fn encoder() -> RowEncoder {
+        RowEncoder::new(&[
+            field("message", ColumnType::String, Mode::Nullable),
+            field("status", ColumnType::Int64, Mode::Nullable),
+            field("timestamp", ColumnType::Timestamp, Mode::Required),
+            field("date", ColumnType::Date, Mode::Nullable),
+            field("tags", ColumnType::String, Mode::Repeated),
+            TableFieldSchema {
+                fields: vec![
+                    field("method", ColumnType::String, Mode::Nullable),
+                    field("duration", ColumnType::Double, Mode::Nullable),
+                ],
+                ..field("http", ColumnType::Struct, Mode::Nullable)
+            },
+            field("payload", ColumnType::Json, Mode::Nullable),
+        ])
+    }
+
+    #[test]
+    fn builds_descriptor() {
+        let encoder = encoder();
+        let descriptor = encoder.descriptor();
+
+        assert_eq!(descriptor.name(), "Row");
+        let fields: Vec<_> = descriptor
+            .field
+            .iter()
+            .map(|field| (field.name(), field.number(), field.label(), field.r#type()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("message", 1, Label::Optional, Type::String),
+                ("status", 2, Label::Optional, Type::Int64),
+                ("timestamp", 3, Label::Optional, Type::Int64),
+                ("date", 4, Label::Optional, Type::Int32),
+                ("tags", 5, Label::Repeated, Type::String),
+                ("http", 6, Label::Optional, Type::Message),
+                ("payload", 7, Label::Optional, Type::String),
+            ]
+        );
+        assert_eq!(descriptor.field[5].type_name(), "Struct6");
+        assert_eq!(descriptor.nested_type.len(), 1);
+        assert_eq!(descriptor.nested_type[0].name(), "Struct6");
+        assert_eq!(descriptor.nested_type[0].field.len(), 2);
+    }
+
+    #[test]
+    fn encodes_rows() {
+        let timestamp = Utc.ymd(2023, 1, 10).and_hms(12, 30, 0);
+        let mut event = LogEvent::default();
+        event.insert("message", "GET /");
+        event.insert("status", 200);
+        event.insert("timestamp", timestamp);
+        event.insert("date", "2023-01-10");
+        event.insert("tags", vec!["a", "b"]);
+        event.insert("http.method", "GET");
+        event.insert("http.duration", 12);
+        event.insert("payload.size", 3);
+        event.insert("host", "host-a");
+
+        let row = Row::decode(encoder().encode(&event).as_slice()).unwrap();
+
+        assert_eq!(
+            row,
+            Row {
+                message: Some("GET /".to_owned()),
+                status: Some(200),
+                timestamp: Some(1673353800000000),
+                date: Some(19367),
+                tags: vec!["a".to_owned(), "b".to_owned()],
+                http: Some(Http {
+                    method: Some("GET".to_owned()),
+                    duration: Some(12.0),
+                }),
+                payload: Some(r#"{"size":3}"#.to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn leaves_mismatched_values_null() {
+        let mut event = LogEvent::default();
+        event.insert("status", "OK");
+        event.insert("timestamp", "yesterday");
+        event.insert("http", "GET");
+
+        let row = Row::decode(encoder().encode(&event).as_slice()).unwrap();
+
+        assert_eq!(row, Row::default());
+    }
+}
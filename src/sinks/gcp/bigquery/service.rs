@@ -0,0 +1,367 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use futures::future::BoxFuture;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::OnceCell;
+use tonic::{
+    metadata::MetadataValue,
+    service::{interceptor::InterceptedService, Interceptor},
+    transport::Channel,
+    Code, Request, Status,
+};
+use tower::Service;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    stream::DriverResponse,
+};
+
+use super::{
+    config::BigqueryWriteMode,
+    proto::{
+        append_rows_request::{ProtoData, Rows},
+        append_rows_response::Response,
+        big_query_write_client::BigQueryWriteClient,
+        storage_error::StorageErrorCode,
+        write_stream, AppendRowsRequest, BatchCommitWriteStreamsRequest, CreateWriteStreamRequest,
+        FinalizeWriteStreamRequest, ProtoRows, ProtoSchema, WriteStream,
+    },
+};
+use crate::{gcp::GcpAuthenticator, sinks::util::retries::RetryLogic};
+
+/// The maximum size of the rows of an `AppendRows` request, which has to be smaller than 10MB
+/// with its writer schema. Larger requests are split in several.
+const MAX_APPEND_ROWS_BYTES: usize = 9_000_000;
+
+pub type BigqueryClient = BigQueryWriteClient<InterceptedService<Channel, AuthInterceptor>>;
+
+/// Authenticates requests, with the token or API key of the authenticator.
+#[derive(Clone)]
+pub struct AuthInterceptor {
+    pub auth: GcpAuthenticator,
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let (key, value) = match &self.auth {
+            GcpAuthenticator::ApiKey(api_key) => ("x-goog-api-key", api_key.to_string()),
+            auth => match auth.make_token() {
+                Some(token) => ("authorization", token),
+                None => return Ok(request),
+            },
+        };
+        let value = MetadataValue::try_from(&value).map_err(|_| {
+            Status::new(
+                Code::FailedPrecondition,
+                "Invalid token text returned by GCP",
+            )
+        })?;
+        request.metadata_mut().insert(key, value);
+        Ok(request)
+    }
+}
+
+/// Builds a request for `resource`, with the routing header the API requires to route it.
+pub fn routed<T>(message: T, param: &str, resource: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    let params = format!(
+        "{}={}",
+        param,
+        utf8_percent_encode(resource, NON_ALPHANUMERIC)
+    );
+    let params = MetadataValue::try_from(&params).expect("percent-encoded values should be ASCII");
+    request
+        .metadata_mut()
+        .insert("x-goog-request-params", params);
+    request
+}
+
+pub fn default_stream(table: &str) -> String {
+    format!("{}/streams/_default", table)
+}
+
+#[derive(Debug, Snafu)]
+pub enum BigqueryError {
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: Status },
+    #[snafu(display("Failed to append rows: {}", message))]
+    Append { code: Code, message: String },
+    #[snafu(display("{} rows were rejected, the first of them because: {}", count, message))]
+    InvalidRows { count: usize, message: String },
+    #[snafu(display("Failed to commit the stream: {}", message))]
+    Commit { message: String },
+    #[snafu(display("Stream of responses ended before every append was acknowledged"))]
+    MissingResponse,
+}
+
+/// Whether requests failing with `code` are retried, which includes those exceeding quotas.
+pub const fn is_retriable(code: Code) -> bool {
+    matches!(
+        code,
+        Code::ResourceExhausted
+            | Code::Unavailable
+            | Code::DeadlineExceeded
+            | Code::Aborted
+            | Code::Internal
+            | Code::Unknown
+            | Code::Cancelled
+    )
+}
+
+/// The pending stream the rows of a request are written to.
+///
+/// It's shared by the retries of the request, so that they write to the same stream, at the same
+/// offsets: rows appended, and streams finalized or committed, by previous attempts aren't written
+/// twice.
+#[derive(Debug, Default)]
+pub struct PendingStream {
+    name: OnceCell<String>,
+    finalized: AtomicBool,
+}
+
+#[derive(Clone)]
+pub struct BigqueryRequest {
+    pub rows: Vec<Vec<u8>>,
+    pub stream: Arc<PendingStream>,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for BigqueryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for BigqueryRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata.clone()
+    }
+}
+
+pub struct BigqueryResponse;
+
+impl DriverResponse for BigqueryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+}
+
+#[derive(Clone)]
+pub struct BigqueryService {
+    pub client: BigqueryClient,
+    pub table: String,
+    pub write_mode: BigqueryWriteMode,
+    pub writer_schema: ProtoSchema,
+}
+
+impl BigqueryService {
+    /// Appends `rows` to `stream`, at offsets starting from zero if `offsets` is set.
+    async fn append(
+        &mut self,
+        stream: &str,
+        rows: &[Vec<u8>],
+        offsets: bool,
+    ) -> Result<(), BigqueryError> {
+        let mut requests = Vec::new();
+        let mut offset = 0;
+        for chunk in chunk_rows(rows) {
+            // The writer schema only needs to be sent with the first request of a connection.
+            let writer_schema = requests.is_empty().then(|| self.writer_schema.clone());
+            requests.push(AppendRowsRequest {
+                write_stream: stream.to_owned(),
+                offset: offsets.then_some(offset),
+                rows: Some(Rows::ProtoRows(ProtoData {
+                    writer_schema,
+                    rows: Some(ProtoRows {
+                        serialized_rows: chunk.to_vec(),
+                    }),
+                })),
+                trace_id: "vector".to_owned(),
+            });
+            offset += chunk.len() as i64;
+        }
+
+        let count = requests.len();
+        let request = routed(futures::stream::iter(requests), "write_stream", stream);
+        let mut responses = self
+            .client
+            .append_rows(request)
+            .await
+            .context(RequestSnafu)?
+            .into_inner();
+        for _ in 0..count {
+            let response = responses
+                .message()
+                .await
+                .context(RequestSnafu)?
+                .ok_or(BigqueryError::MissingResponse)?;
+            if let Some(error) = response.row_errors.first() {
+                return Err(BigqueryError::InvalidRows {
+                    count: response.row_errors.len(),
+                    message: error.message.clone(),
+                });
+            }
+            if let Some(Response::Error(status)) = response.response {
+                let code = Code::from(status.code);
+                // Rows at offsets that already exist were appended by a previous attempt.
+                if !(offsets && code == Code::AlreadyExists) {
+                    return Err(BigqueryError::Append {
+                        code,
+                        message: status.message,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the rows of `request` to its pending stream, which is committed once they are all
+    /// appended to it.
+    async fn write_pending(&mut self, request: &BigqueryRequest) -> Result<(), BigqueryError> {
+        let pending = &request.stream;
+        let name = pending
+            .name
+            .get_or_try_init(|| create_pending_stream(self.client.clone(), self.table.clone()))
+            .await?
+            .clone();
+
+        if !pending.finalized.load(Ordering::Acquire) {
+            self.append(&name, &request.rows, true).await?;
+            let finalize = FinalizeWriteStreamRequest { name: name.clone() };
+            self.client
+                .finalize_write_stream(routed(finalize, "name", &name))
+                .await
+                .context(RequestSnafu)?;
+            pending.finalized.store(true, Ordering::Release);
+        }
+
+        let commit = BatchCommitWriteStreamsRequest {
+            parent: self.table.clone(),
+            write_streams: vec![name],
+        };
+        let response = self
+            .client
+            .batch_commit_write_streams(routed(commit, "parent", &self.table))
+            .await
+            .context(RequestSnafu)?
+            .into_inner();
+        // Streams committed by a previous attempt, whose response was lost, are committed already.
+        match response
+            .stream_errors
+            .into_iter()
+            .find(|error| error.code() != StorageErrorCode::StreamAlreadyCommitted)
+        {
+            Some(error) => Err(BigqueryError::Commit {
+                message: error.error_message,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+async fn create_pending_stream(
+    mut client: BigqueryClient,
+    table: String,
+) -> Result<String, BigqueryError> {
+    let request = CreateWriteStreamRequest {
+        parent: table.clone(),
+        write_stream: Some(WriteStream {
+            r#type: write_stream::Type::Pending as i32,
+            ..Default::default()
+        }),
+    };
+    let stream = client
+        .create_write_stream(routed(request, "parent", &table))
+        .await
+        .context(RequestSnafu)?
+        .into_inner();
+    Ok(stream.name)
+}
+
+/// Splits `rows` in chunks small enough to be appended in a single request each.
+fn chunk_rows(rows: &[Vec<u8>]) -> Vec<&[Vec<u8>]> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut size = 0;
+    for (index, row) in rows.iter().enumerate() {
+        if index > start && size + row.len() > MAX_APPEND_ROWS_BYTES {
+            chunks.push(&rows[start..index]);
+            start = index;
+            size = 0;
+        }
+        size += row.len();
+    }
+    if start < rows.len() {
+        chunks.push(&rows[start..]);
+    }
+    chunks
+}
+
+impl Service<BigqueryRequest> for BigqueryService {
+    type Response = BigqueryResponse;
+    type Error = BigqueryError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: BigqueryRequest) -> Self::Future {
+        let mut service = self.clone();
+        Box::pin(async move {
+            match service.write_mode {
+                BigqueryWriteMode::Default => {
+                    let stream = default_stream(&service.table);
+                    service.append(&stream, &request.rows, false).await?;
+                }
+                BigqueryWriteMode::Pending => service.write_pending(&request).await?,
+            }
+            Ok(BigqueryResponse)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BigqueryRetryLogic;
+
+impl RetryLogic for BigqueryRetryLogic {
+    type Error = BigqueryError;
+    type Response = BigqueryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            BigqueryError::Request { source } => is_retriable(source.code()),
+            BigqueryError::Append { code, .. } => is_retriable(*code),
+            BigqueryError::MissingResponse => true,
+            BigqueryError::InvalidRows { .. } | BigqueryError::Commit { .. } => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_rows() {
+        let rows = vec![
+            vec![0; 4_000_000],
+            vec![0; 4_000_000],
+            vec![0; 2_000_000],
+            vec![0; 10_000_000],
+        ];
+
+        let sizes: Vec<_> = chunk_rows(&rows).iter().map(|chunk| chunk.len()).collect();
+
+        assert_eq!(sizes, vec![2, 1, 1]);
+    }
+}
@@ -0,0 +1,161 @@
+use std::{num::NonZeroUsize, time::Duration};
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tonic::Status;
+use tower::ServiceBuilder;
+use vector_core::{
+    event::{EventFinalizers, Finalizable},
+    stream::BatcherSettings,
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use super::{
+    config::BigqueryWriteMode,
+    proto::{GetWriteStreamRequest, ProtoSchema, TableSchema, WriteStreamView},
+    schema::RowEncoder,
+    service::{
+        default_stream, is_retriable, routed, BigqueryClient, BigqueryRequest, BigqueryRetryLogic,
+        BigqueryService,
+    },
+};
+use crate::{
+    codecs::Transformer,
+    event::Event,
+    sinks::util::{
+        metadata::RequestMetadataBuilder, ServiceBuilderExt, SinkBuilderExt, StreamSink,
+        TowerRequestSettings,
+    },
+};
+
+/// How long to wait before loading the schema of the table again, when it can't be loaded on
+/// start up.
+const LOAD_SCHEMA_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// An event, encoded as a row, with the sizes it had before being transformed.
+///
+/// Rows are batched by their encoded size, which is what the size of requests is limited by.
+struct EncodedRow {
+    row: Vec<u8>,
+    finalizers: EventFinalizers,
+    byte_size: usize,
+    json_size: usize,
+}
+
+impl ByteSizeOf for EncodedRow {
+    fn size_of(&self) -> usize {
+        self.row.len()
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
+pub struct BigquerySink {
+    pub client: BigqueryClient,
+    pub table: String,
+    pub write_mode: BigqueryWriteMode,
+    pub transformer: Transformer,
+    pub batch_settings: BatcherSettings,
+    pub request: TowerRequestSettings,
+}
+
+impl BigquerySink {
+    /// Loads the schema of the table, waiting for the API to be reachable.
+    async fn load_schema(&self) -> Result<TableSchema, Status> {
+        let stream = default_stream(&self.table);
+        loop {
+            let request = GetWriteStreamRequest {
+                name: stream.clone(),
+                view: WriteStreamView::Full as i32,
+            };
+            match self
+                .client
+                .clone()
+                .get_write_stream(routed(request, "name", &stream))
+                .await
+            {
+                Ok(response) => return Ok(response.into_inner().table_schema.unwrap_or_default()),
+                Err(error) if is_retriable(error.code()) => {
+                    error!(message = "Failed to load the schema of the table, retrying.", %error);
+                    tokio::time::sleep(LOAD_SCHEMA_RETRY_INTERVAL).await;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for BigquerySink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let schema = match self.load_schema().await {
+            Ok(schema) => schema,
+            Err(error) => {
+                error!(message = "Failed to load the schema of the table.", %error);
+                return Err(());
+            }
+        };
+        let encoder = RowEncoder::new(&schema.fields);
+
+        let service = ServiceBuilder::new()
+            .settings(self.request, BigqueryRetryLogic)
+            .service(BigqueryService {
+                client: self.client.clone(),
+                table: self.table.clone(),
+                write_mode: self.write_mode,
+                writer_schema: ProtoSchema {
+                    proto_descriptor: Some(encoder.descriptor().clone()),
+                },
+            });
+
+        let transformer = self.transformer;
+        input
+            .map(|mut event| {
+                let byte_size = event.size_of();
+                let json_size = event.estimated_json_encoded_size_of();
+                transformer.transform(&mut event);
+                EncodedRow {
+                    finalizers: event.take_finalizers(),
+                    row: encoder.encode(&event.into_log()),
+                    byte_size,
+                    json_size,
+                }
+            })
+            .batched(self.batch_settings.into_byte_size_config())
+            .map(|rows| {
+                let mut byte_size = 0;
+                let mut json_size = 0;
+                let mut request_size = 0;
+                let mut finalizers = EventFinalizers::default();
+                let rows: Vec<_> = rows
+                    .into_iter()
+                    .map(|row| {
+                        byte_size += row.byte_size;
+                        json_size += row.json_size;
+                        request_size += row.row.len();
+                        finalizers.merge(row.finalizers);
+                        row.row
+                    })
+                    .collect();
+                // Rows of events without any of the fields of the table are empty.
+                let metadata = RequestMetadataBuilder::new(rows.len(), byte_size, json_size)
+                    .with_request_size(
+                        NonZeroUsize::new(request_size.max(1))
+                            .expect("size should be at least one"),
+                    );
+
+                BigqueryRequest {
+                    rows,
+                    stream: Default::default(),
+                    finalizers,
+                    metadata,
+                }
+            })
+            .into_driver(service)
+            .protocol("https")
+            .run()
+            .await
+    }
+}
@@ -270,7 +270,7 @@ impl Finalizable for ChronicleRequest {
 
 impl MetaDescriptive for ChronicleRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
 
@@ -457,8 +457,6 @@ impl Service<ChronicleRequest> for ChronicleService {
             HeaderValue::from_str(&request.body.len().to_string()).unwrap(),
         );
 
-        let metadata = request.get_metadata();
-
         let mut http_request = builder.body(Body::from(request.body)).unwrap();
         self.creds.apply(&mut http_request);
 
@@ -468,10 +466,7 @@ impl Service<ChronicleRequest> for ChronicleService {
                 Ok(response) => {
                     let status = response.status();
                     if status.is_success() {
-                        Ok(GcsResponse {
-                            inner: response,
-                            metadata,
-                        })
+                        Ok(GcsResponse { inner: response })
                     } else {
                         Err(ChronicleResponseError::ServerError { code: status })
                     }
@@ -6,13 +6,17 @@ use codecs::encoding::Framer;
 use http::header::{HeaderName, HeaderValue};
 use http::Uri;
 use indoc::indoc;
+use percent_encoding::{utf8_percent_encode, CONTROLS};
 use snafu::ResultExt;
 use snafu::Snafu;
 use tower::ServiceBuilder;
 use uuid::Uuid;
 use vector_common::request_metadata::RequestMetadata;
 use vector_config::configurable_component;
-use vector_core::event::{EventFinalizers, Finalizable};
+use vector_core::{
+    event::{EventFinalizers, Finalizable},
+    partition::Partitioner,
+};
 
 use crate::sinks::util::metadata::RequestMetadataBuilder;
 use crate::{
@@ -21,6 +25,7 @@ use crate::{
     event::Event,
     gcp::{GcpAuthConfig, GcpAuthenticator, Scope},
     http::{get_http_scheme_from_uri, HttpClient},
+    internal_events::TemplateRenderingError,
     serde::json::to_string,
     sinks::{
         gcs_common::{
@@ -31,9 +36,8 @@ use crate::{
             sink::GcsSink,
         },
         util::{
-            batch::BatchConfig, partitioner::KeyPartitioner, request_builder::EncodeResult,
-            BulkSizeBasedDefaultBatchSettings, Compression, RequestBuilder, ServiceBuilderExt,
-            TowerRequestConfig,
+            batch::BatchConfig, request_builder::EncodeResult, BulkSizeBasedDefaultBatchSettings,
+            Compression, RequestBuilder, ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
@@ -70,12 +74,41 @@ pub struct GcsSinkConfig {
     /// [storage_classes]: https://cloud.google.com/storage/docs/storage-classes
     storage_class: Option<GcsStorageClass>,
 
+    /// The Cloud KMS key to encrypt the created objects with, instead of the default key of the
+    /// bucket.
+    ///
+    /// For more information, see [Customer-managed encryption keys][cmek].
+    ///
+    /// [cmek]: https://cloud.google.com/storage/docs/encryption/customer-managed-keys
+    #[configurable(metadata(
+        docs::examples = "projects/my-project/locations/global/keyRings/my-keyring/cryptoKeys/my-key"
+    ))]
+    kms_key_name: Option<String>,
+
     /// The set of metadata `key:value` pairs for the created objects.
     ///
+    /// The values of metadata are templates, and events are batched by the values they render.
+    ///
     /// For more information, see [Custom metadata][custom_metadata].
     ///
     /// [custom_metadata]: https://cloud.google.com/storage/docs/metadata#custom-metadata
-    metadata: Option<HashMap<String, String>>,
+    #[configurable(metadata(
+        docs::additional_props_description = "The template of the value of the metadata."
+    ))]
+    metadata: Option<HashMap<String, Template>>,
+
+    /// The custom time of the created objects, which lifecycle rules can use as the age of objects.
+    ///
+    /// The custom time is a template, which must render to an [RFC 3339][rfc3339] timestamp, and
+    /// events are batched by the values it renders.
+    ///
+    /// For more information, see [Custom-Time][custom_time].
+    ///
+    /// [rfc3339]: https://datatracker.ietf.org/doc/html/rfc3339
+    /// [custom_time]: https://cloud.google.com/storage/docs/metadata#custom-time
+    #[configurable(metadata(docs::examples = "{{ timestamp }}"))]
+    #[configurable(metadata(docs::examples = "%Y-%m-%dT00:00:00Z"))]
+    custom_time: Option<Template>,
 
     /// A prefix to apply to all object keys.
     ///
@@ -152,7 +185,9 @@ fn default_config(encoding: EncodingConfigWithFraming) -> GcsSinkConfig {
         bucket: Default::default(),
         acl: Default::default(),
         storage_class: Default::default(),
+        kms_key_name: Default::default(),
         metadata: Default::default(),
+        custom_time: Default::default(),
         key_prefix: Default::default(),
         filename_time_format: Default::default(),
         filename_append_uuid: Default::default(),
@@ -235,11 +270,76 @@ impl GcsSinkConfig {
         Ok(VectorSink::from_event_streamsink(sink))
     }
 
-    fn key_partitioner(&self) -> crate::Result<KeyPartitioner> {
-        Ok(KeyPartitioner::new(
-            Template::try_from(self.key_prefix.as_deref().unwrap_or("date=%F/"))
+    fn key_partitioner(&self) -> crate::Result<GcsKeyPartitioner> {
+        let metadata = self
+            .metadata
+            .iter()
+            .flatten()
+            .map(|(name, value)| Ok((HeaderName::from_bytes(name.as_bytes())?, value.clone())))
+            .collect::<crate::Result<_>>()?;
+        Ok(GcsKeyPartitioner {
+            key_prefix: Template::try_from(self.key_prefix.as_deref().unwrap_or("date=%F/"))
                 .context(KeyPrefixTemplateSnafu)?,
-        ))
+            metadata,
+            custom_time: self.custom_time.clone(),
+        })
+    }
+}
+
+/// The rendered key prefix and headers of the objects of a batch.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct GcsPartitionKey {
+    key_prefix: String,
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+/// Partitions events by the key prefix, metadata and custom time rendered for them.
+struct GcsKeyPartitioner {
+    key_prefix: Template,
+    metadata: Vec<(HeaderName, Template)>,
+    custom_time: Option<Template>,
+}
+
+impl Partitioner for GcsKeyPartitioner {
+    type Item = Event;
+    type Key = Option<GcsPartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let render = |template: &Template, field| {
+            template
+                .render_string(item)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some(field),
+                        drop_event: true,
+                    });
+                })
+                .ok()
+        };
+        // Characters that can't be sent in headers are percent-encoded.
+        let render_header = |template: &Template, field| {
+            render(template, field).map(|value| {
+                HeaderValue::from_str(&utf8_percent_encode(&value, CONTROLS).to_string())
+                    .expect("percent-encoded values should be valid header values")
+            })
+        };
+
+        let key_prefix = render(&self.key_prefix, "key_prefix")?;
+        let mut headers = Vec::with_capacity(self.metadata.len() + 1);
+        for (name, template) in &self.metadata {
+            headers.push((name.clone(), render_header(template, "metadata")?));
+        }
+        if let Some(custom_time) = &self.custom_time {
+            headers.push((
+                HeaderName::from_static("x-goog-custom-time"),
+                render_header(custom_time, "custom_time")?,
+            ));
+        }
+        Some(GcsPartitionKey {
+            key_prefix,
+            headers,
+        })
     }
 }
 
@@ -260,8 +360,8 @@ struct RequestSettings {
     compression: Compression,
 }
 
-impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
-    type Metadata = (String, EventFinalizers);
+impl RequestBuilder<(GcsPartitionKey, Vec<Event>)> for RequestSettings {
+    type Metadata = (GcsPartitionKey, EventFinalizers);
     type Events = Vec<Event>;
     type Encoder = (Transformer, Encoder<Framer>);
     type Payload = Bytes;
@@ -278,7 +378,7 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
 
     fn split_input(
         &self,
-        input: (String, Vec<Event>),
+        input: (GcsPartitionKey, Vec<Event>),
     ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
         let (partition_key, mut events) = input;
         let finalizers = events.take_finalizers();
@@ -306,7 +406,11 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
             }
         };
 
-        let key = format!("{}{}.{}", key, filename, self.extension);
+        let key_prefix = key.key_prefix;
+        let mut headers = self.headers.clone();
+        headers.extend(key.headers);
+
+        let key = format!("{}{}.{}", key_prefix, filename, self.extension);
         let body = payload.into_payload();
 
         GcsRequest {
@@ -318,7 +422,7 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
                 content_type: self.content_type.clone(),
                 content_encoding: self.content_encoding.clone(),
                 storage_class: self.storage_class.clone(),
-                headers: self.headers.clone(),
+                headers,
             },
             metadata,
         }
@@ -340,16 +444,13 @@ impl RequestSettings {
             .map(|ce| HeaderValue::from_str(&to_string(ce)).unwrap());
         let storage_class = config.storage_class.unwrap_or_default();
         let storage_class = HeaderValue::from_str(&to_string(storage_class)).unwrap();
-        let metadata = config
-            .metadata
-            .as_ref()
-            .map(|metadata| {
-                metadata
-                    .iter()
-                    .map(make_header)
-                    .collect::<Result<Vec<_>, _>>()
-            })
-            .unwrap_or_else(|| Ok(vec![]))?;
+        let mut headers = Vec::new();
+        if let Some(kms_key_name) = &config.kms_key_name {
+            headers.push((
+                HeaderName::from_static("x-goog-encryption-kms-key-name"),
+                HeaderValue::from_str(kms_key_name)?,
+            ));
+        }
         let extension = config
             .filename_extension
             .clone()
@@ -364,7 +465,7 @@ impl RequestSettings {
             content_type,
             content_encoding,
             storage_class,
-            headers: metadata,
+            headers,
             extension,
             time_format,
             append_uuid,
@@ -374,20 +475,11 @@ impl RequestSettings {
     }
 }
 
-// Make a header pair from a key-value string pair
-fn make_header((name, value): (&String, &String)) -> crate::Result<(HeaderName, HeaderValue)> {
-    Ok((
-        HeaderName::from_bytes(name.as_bytes())?,
-        HeaderValue::from_str(value)?,
-    ))
-}
-
 #[cfg(test)]
 mod tests {
     use codecs::encoding::FramingConfig;
     use codecs::{JsonSerializerConfig, NewlineDelimitedEncoderConfig, TextSerializerConfig};
     use futures_util::{future::ready, stream};
-    use vector_core::partition::Partitioner;
 
     use crate::event::LogEvent;
     use crate::test_util::{
@@ -439,7 +531,54 @@ mod tests {
             .partition(&Event::Log(event))
             .expect("key wasn't provided");
 
-        assert_eq!(key, "key: value");
+        assert_eq!(key.key_prefix, "key: value");
+    }
+
+    #[test]
+    fn gcs_build_request_headers() {
+        let mut event = LogEvent::from("hello world");
+        event.insert("team", "billing");
+        event.insert("day", "2023-01-15");
+
+        let sink_config = GcsSinkConfig {
+            kms_key_name: Some("projects/p/locations/global/keyRings/r/cryptoKeys/k".into()),
+            metadata: Some(HashMap::from([(
+                "x-goog-meta-team".to_owned(),
+                Template::try_from("{{ team }}").unwrap(),
+            )])),
+            custom_time: Some(Template::try_from("{{ day }}T00:00:00Z").unwrap()),
+            ..default_config((None::<FramingConfig>, TextSerializerConfig::new()).into())
+        };
+        let event = Event::Log(event);
+        let key = sink_config
+            .key_partitioner()
+            .unwrap()
+            .partition(&event)
+            .expect("key wasn't provided");
+        let request_settings = request_settings(&sink_config);
+        let (metadata, metadata_request_builder, _events) =
+            request_settings.split_input((key, vec![event]));
+        let payload = EncodeResult::uncompressed(Bytes::new());
+        let request_metadata = metadata_request_builder.build(&payload);
+        let req = request_settings.build_request(metadata, request_metadata, payload);
+
+        let headers: Vec<_> = req
+            .settings
+            .headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.to_str().unwrap()))
+            .collect();
+        assert_eq!(
+            headers,
+            vec![
+                (
+                    "x-goog-encryption-kms-key-name",
+                    "projects/p/locations/global/keyRings/r/cryptoKeys/k"
+                ),
+                ("x-goog-meta-team", "billing"),
+                ("x-goog-custom-time", "2023-01-15T00:00:00Z"),
+            ]
+        );
     }
 
     fn request_settings(sink_config: &GcsSinkConfig) -> RequestSettings {
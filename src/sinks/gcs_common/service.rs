@@ -9,7 +9,7 @@ use http::{
 use hyper::Body;
 use tower::Service;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
-use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
+use vector_core::stream::DriverResponse;
 
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
@@ -51,7 +51,7 @@ impl Finalizable for GcsRequest {
 
 impl MetaDescriptive for GcsRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
 
@@ -70,7 +70,6 @@ pub struct GcsRequestSettings {
 #[derive(Debug)]
 pub struct GcsResponse {
     pub inner: http::Response<Body>,
-    pub metadata: RequestMetadata,
 }
 
 impl DriverResponse for GcsResponse {
@@ -83,17 +82,6 @@ impl DriverResponse for GcsResponse {
             EventStatus::Rejected
         }
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(
-            self.metadata.event_count(),
-            self.metadata.events_estimated_json_encoded_byte_size(),
-        )
-    }
-
-    fn bytes_sent(&self) -> Option<usize> {
-        Some(self.metadata.request_encoded_size())
-    }
 }
 
 impl Service<GcsRequest> for GcsService {
@@ -109,7 +97,6 @@ impl Service<GcsRequest> for GcsService {
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, request: GcsRequest) -> Self::Future {
         let settings = request.settings;
-        let metadata = request.metadata;
 
         let uri = format!("{}{}", self.base_url, request.key)
             .parse::<Uri>()
@@ -137,7 +124,7 @@ impl Service<GcsRequest> for GcsService {
         let mut client = self.client.clone();
         Box::pin(async move {
             let result = client.call(http_request).await;
-            result.map(|inner| GcsResponse { inner, metadata })
+            result.map(|inner| GcsResponse { inner })
         })
     }
 }
@@ -0,0 +1,255 @@
+use std::path::PathBuf;
+
+use futures::FutureExt;
+use http::uri::{InvalidUri, PathAndQuery, Scheme, Uri};
+use indoc::indoc;
+use prost_reflect::{MessageDescriptor, MethodDescriptor};
+use snafu::{OptionExt, ResultExt, Snafu};
+use tonic::{
+    client::Grpc,
+    transport::{Certificate, ClientTlsConfig, Endpoint, Identity},
+};
+use vector_config::configurable_component;
+
+use super::{
+    encoder::MessageEncoder,
+    service::GrpcService,
+    sink::{CallMode, GrpcSink},
+};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    grpc::{find_method, load_descriptor_pool, method_path},
+    sinks::{
+        util::{BatchConfig, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+#[derive(Debug, Snafu)]
+enum GrpcConfigError {
+    #[snafu(display("Invalid endpoint URI: {}", source))]
+    Uri { source: InvalidUri },
+    #[snafu(display("Could not create endpoint: {}", source))]
+    Endpoint { source: tonic::transport::Error },
+    #[snafu(display("Could not set up endpoint TLS settings: {}", source))]
+    EndpointTls { source: tonic::transport::Error },
+    #[snafu(display("Message {:?} is not defined in the descriptor set", message_type))]
+    MessageNotFound { message_type: String },
+    #[snafu(display("Method {:?} streams its responses, which is not supported", method))]
+    ServerStreaming { method: String },
+    #[snafu(display("Method {:?} streams messages other than {:?}", method, message_type))]
+    StreamedMessageMismatch {
+        method: String,
+        message_type: String,
+    },
+    #[snafu(display(
+        "Method {:?} takes neither {:?} messages nor messages with a single repeated field of them",
+        method,
+        message_type
+    ))]
+    UnaryMessageMismatch {
+        method: String,
+        message_type: String,
+    },
+}
+
+/// Configuration for the `grpc` sink.
+#[configurable_component(sink("grpc"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcSinkConfig {
+    /// The endpoint of the gRPC server to call.
+    ///
+    /// Requests are sent over TLS, unless the scheme of the endpoint is `http`.
+    #[configurable(metadata(docs::examples = "http://localhost:50051"))]
+    pub endpoint: String,
+
+    /// The path to the file descriptor set defining the called method and the messages it uses.
+    ///
+    /// The descriptor set can be generated with `protoc --include_imports --descriptor_set_out=<path>`.
+    #[configurable(metadata(docs::examples = "/etc/vector/telemetry.desc"))]
+    pub descriptor_set_file: PathBuf,
+
+    /// The fully qualified name of the called method, in the form `package.Service/Method`.
+    ///
+    /// Unary and client streaming methods are supported. Client streaming methods are called once
+    /// per batch, streaming a message per event. Unary methods taking messages of the message type
+    /// are called once per event, while those taking messages with a single repeated field of them
+    /// are called once per batch. The responses of the method are ignored.
+    #[configurable(metadata(docs::examples = "telemetry.v1.TelemetryService/Push"))]
+    pub method: String,
+
+    /// The fully qualified name of the message type events are encoded as.
+    ///
+    /// Fields of messages hold the values of the top-level fields of events of the same names, and
+    /// those missing from events, or holding values that don't fit their types, are left unset.
+    ///
+    /// By default, events are encoded as the input messages of the method.
+    #[configurable(metadata(docs::examples = "telemetry.v1.Telemetry"))]
+    pub message_type: Option<String>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<GrpcDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GrpcDefaultBatchSettings;
+
+impl SinkBatchSettings for GrpcDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    const MAX_BYTES: Option<usize> = Some(4_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+impl GenerateConfig for GrpcSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            endpoint = "http://localhost:50051"
+            descriptor_set_file = "/etc/vector/telemetry.desc"
+            method = "telemetry.v1.TelemetryService/Push"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for GrpcSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let pool = load_descriptor_pool(&self.descriptor_set_file)?;
+        let method = find_method(&pool, &self.method)?;
+        let message = match &self.message_type {
+            Some(message_type) => {
+                pool.get_message_by_name(message_type)
+                    .with_context(|| MessageNotFoundSnafu {
+                        message_type: message_type.clone(),
+                    })?
+            }
+            None => method.input(),
+        };
+        let call_mode = self.call_mode(&method, &message)?;
+
+        let endpoint = self.endpoint()?;
+        let healthcheck = healthcheck(endpoint.clone()).boxed();
+
+        let path = PathAndQuery::try_from(method_path(&method)).context(UriSnafu)?;
+        let sink = GrpcSink {
+            service: GrpcService {
+                client: Grpc::new(endpoint.connect_lazy()),
+                path,
+                output: method.output(),
+            },
+            encoder: MessageEncoder::new(message),
+            call_mode,
+            transformer: self.encoding.clone(),
+            batch_settings: self.batch.into_batcher_settings()?,
+            request: self.request.unwrap_with(&TowerRequestConfig::default()),
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl GrpcSinkConfig {
+    fn endpoint(&self) -> crate::Result<Endpoint> {
+        let uri: Uri = self.endpoint.parse().context(UriSnafu)?;
+
+        let mut endpoint: Endpoint = uri.to_string().parse().context(EndpointSnafu)?;
+        if uri.scheme() != Some(&Scheme::HTTP) {
+            let tls = TlsSettings::from_options(&self.tls)?;
+            let mut tls_config = ClientTlsConfig::new();
+            if let Some(host) = uri.host() {
+                tls_config = tls_config.domain_name(host);
+            }
+            if let Some((cert, key)) = tls.identity_pem() {
+                tls_config = tls_config.identity(Identity::from_pem(cert, key));
+            }
+            for authority in tls.authorities_pem() {
+                tls_config = tls_config.ca_certificate(Certificate::from_pem(authority));
+            }
+            endpoint = endpoint.tls_config(tls_config).context(EndpointTlsSnafu)?;
+        }
+
+        Ok(endpoint)
+    }
+
+    /// Determines how the messages events are encoded as are sent to `method`.
+    fn call_mode(
+        &self,
+        method: &MethodDescriptor,
+        message: &MessageDescriptor,
+    ) -> Result<CallMode, GrpcConfigError> {
+        if method.is_server_streaming() {
+            return Err(GrpcConfigError::ServerStreaming {
+                method: self.method.clone(),
+            });
+        }
+
+        let input = method.input();
+        if input.full_name() == message.full_name() {
+            return Ok(if method.is_client_streaming() {
+                CallMode::ClientStreaming
+            } else {
+                CallMode::Unary
+            });
+        }
+        if method.is_client_streaming() {
+            return Err(GrpcConfigError::StreamedMessageMismatch {
+                method: self.method.clone(),
+                message_type: message.full_name().to_owned(),
+            });
+        }
+
+        let mut fields = input.fields().filter(|field| {
+            field.is_list()
+                && field
+                    .kind()
+                    .as_message()
+                    .map_or(false, |kind| kind.full_name() == message.full_name())
+        });
+        match (fields.next(), fields.next()) {
+            (Some(field), None) => Ok(CallMode::Wrapped { input, field }),
+            _ => Err(GrpcConfigError::UnaryMessageMismatch {
+                method: self.method.clone(),
+                message_type: message.full_name().to_owned(),
+            }),
+        }
+    }
+}
+
+async fn healthcheck(endpoint: Endpoint) -> crate::Result<()> {
+    endpoint.connect().await?;
+    Ok(())
+}
@@ -0,0 +1,382 @@
+//! Converting events to messages of descriptors only known at runtime.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use prost_reflect::{
+    DynamicMessage, FieldDescriptor, Kind, MapKey, MessageDescriptor, Value as ReflectValue,
+};
+
+use crate::event::{LogEvent, Value};
+
+const TIMESTAMP_MESSAGE: &str = "google.protobuf.Timestamp";
+
+/// Encodes events as messages of a descriptor.
+///
+/// Fields of messages hold the values of the top-level fields of events of the same names. Values
+/// that don't fit the types of their fields are left out, so their fields are left unset.
+#[derive(Clone, Debug)]
+pub struct MessageEncoder {
+    descriptor: MessageDescriptor,
+}
+
+impl MessageEncoder {
+    pub const fn new(descriptor: MessageDescriptor) -> Self {
+        Self { descriptor }
+    }
+
+    pub fn encode(&self, log: &LogEvent) -> DynamicMessage {
+        match log.as_map() {
+            Some(fields) => encode_message(&self.descriptor, fields),
+            None => DynamicMessage::new(self.descriptor.clone()),
+        }
+    }
+}
+
+fn encode_message(
+    descriptor: &MessageDescriptor,
+    fields: &BTreeMap<String, Value>,
+) -> DynamicMessage {
+    let mut message = DynamicMessage::new(descriptor.clone());
+    for field in descriptor.fields() {
+        if let Some(value) = fields
+            .get(field.name())
+            .and_then(|value| encode_field(&field, value))
+        {
+            message.set_field(&field, value);
+        }
+    }
+    message
+}
+
+fn encode_field(field: &FieldDescriptor, value: &Value) -> Option<ReflectValue> {
+    let kind = field.kind();
+    if field.is_map() {
+        let entry = kind.as_message()?;
+        let key_kind = entry.map_entry_key_field().kind();
+        let value_kind = entry.map_entry_value_field().kind();
+        let entries = match value {
+            Value::Object(entries) => entries,
+            _ => return None,
+        };
+        Some(ReflectValue::Map(
+            entries
+                .iter()
+                .filter_map(|(key, value)| {
+                    Some((
+                        encode_map_key(&key_kind, key)?,
+                        encode_value(&value_kind, value)?,
+                    ))
+                })
+                .collect::<HashMap<_, _>>(),
+        ))
+    } else if field.is_list() {
+        match value {
+            Value::Array(values) => Some(ReflectValue::List(
+                values
+                    .iter()
+                    .filter_map(|value| encode_value(&kind, value))
+                    .collect(),
+            )),
+            value => encode_value(&kind, value).map(|value| ReflectValue::List(vec![value])),
+        }
+    } else {
+        encode_value(&kind, value)
+    }
+}
+
+fn encode_value(kind: &Kind, value: &Value) -> Option<ReflectValue> {
+    let value = match (kind, value) {
+        (_, Value::Null) => return None,
+        (Kind::Double, value) => ReflectValue::F64(float(value)?),
+        (Kind::Float, value) => ReflectValue::F32(float(value)? as f32),
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, Value::Integer(integer)) => {
+            ReflectValue::I32(i32::try_from(*integer).ok()?)
+        }
+        (Kind::Int64 | Kind::Sint64 | Kind::Sfixed64, Value::Integer(integer)) => {
+            ReflectValue::I64(*integer)
+        }
+        (Kind::Uint32 | Kind::Fixed32, Value::Integer(integer)) => {
+            ReflectValue::U32(u32::try_from(*integer).ok()?)
+        }
+        (Kind::Uint64 | Kind::Fixed64, Value::Integer(integer)) => {
+            ReflectValue::U64(u64::try_from(*integer).ok()?)
+        }
+        (Kind::Bool, Value::Boolean(boolean)) => ReflectValue::Bool(*boolean),
+        (Kind::String, Value::Timestamp(timestamp)) => {
+            ReflectValue::String(timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        }
+        (Kind::String, value @ (Value::Object(_) | Value::Array(_))) => {
+            ReflectValue::String(serde_json::to_string(value).expect("values should serialize"))
+        }
+        (Kind::String, value) => ReflectValue::String(value.to_string_lossy().into_owned()),
+        (Kind::Bytes, Value::Bytes(bytes)) => ReflectValue::Bytes(bytes.clone()),
+        (Kind::Enum(descriptor), Value::Bytes(name)) => ReflectValue::EnumNumber(
+            descriptor
+                .get_value_by_name(std::str::from_utf8(name).ok()?)?
+                .number(),
+        ),
+        (Kind::Enum(_), Value::Integer(number)) => {
+            ReflectValue::EnumNumber(i32::try_from(*number).ok()?)
+        }
+        (Kind::Message(descriptor), value) if descriptor.full_name() == TIMESTAMP_MESSAGE => {
+            ReflectValue::Message(encode_timestamp(descriptor, timestamp(value)?))
+        }
+        (Kind::Message(descriptor), Value::Object(fields)) => {
+            ReflectValue::Message(encode_message(descriptor, fields))
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn encode_map_key(kind: &Kind, key: &str) -> Option<MapKey> {
+    let key = match kind {
+        Kind::String => MapKey::String(key.to_owned()),
+        Kind::Bool => MapKey::Bool(key.parse().ok()?),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => MapKey::I32(key.parse().ok()?),
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => MapKey::I64(key.parse().ok()?),
+        Kind::Uint32 | Kind::Fixed32 => MapKey::U32(key.parse().ok()?),
+        Kind::Uint64 | Kind::Fixed64 => MapKey::U64(key.parse().ok()?),
+        _ => return None,
+    };
+    Some(key)
+}
+
+fn float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Float(float) => Some(float.into_inner()),
+        Value::Integer(integer) => Some(*integer as f64),
+        _ => None,
+    }
+}
+
+fn timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Timestamp(timestamp) => Some(*timestamp),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|string| DateTime::parse_from_rfc3339(string).ok())
+            .map(|timestamp| timestamp.with_timezone(&Utc)),
+        _ => None,
+    }
+}
+
+/// Encodes `timestamp` as a `google.protobuf.Timestamp` message.
+fn encode_timestamp(descriptor: &MessageDescriptor, timestamp: DateTime<Utc>) -> DynamicMessage {
+    let mut message = DynamicMessage::new(descriptor.clone());
+    message.set_field_by_name("seconds", ReflectValue::I64(timestamp.timestamp()));
+    message.set_field_by_name(
+        "nanos",
+        ReflectValue::I32(timestamp.timestamp_subsec_nanos() as i32),
+    );
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use prost::Message;
+    use prost_reflect::DescriptorPool;
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+        FileDescriptorProto, FileDescriptorSet, MessageOptions,
+    };
+
+    use super::*;
+
+    fn field(name: &str, number: i32, label: Label, r#type: Type) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.into()),
+            number: Some(number),
+            label: Some(label.into()),
+            r#type: Some(r#type.into()),
+            json_name: Some(name.into()),
+            ..Default::default()
+        }
+    }
+
+    fn typed_field(
+        name: &str,
+        number: i32,
+        label: Label,
+        r#type: Type,
+        type_name: &str,
+    ) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            type_name: Some(type_name.into()),
+            ..field(name, number, label, r#type)
+        }
+    }
+
+    fn encoder() -> MessageEncoder {
+        let file = FileDescriptorProto {
+            name: Some("record.proto".into()),
+            package: Some("test".into()),
+            syntax: Some("proto3".into()),
+            message_type: vec![
+                DescriptorProto {
+                    name: Some("Record".into()),
+                    field: vec![
+                        field("message", 1, Label::Optional, Type::String),
+                        field("status", 2, Label::Optional, Type::Int32),
+                        field("ratio", 3, Label::Optional, Type::Double),
+                        field("tags", 4, Label::Repeated, Type::String),
+                        typed_field("level", 5, Label::Optional, Type::Enum, ".test.Level"),
+                        typed_field(
+                            "labels",
+                            6,
+                            Label::Repeated,
+                            Type::Message,
+                            ".test.Record.LabelsEntry",
+                        ),
+                        typed_field(
+                            "timestamp",
+                            7,
+                            Label::Optional,
+                            Type::Message,
+                            ".google.protobuf.Timestamp",
+                        ),
+                        typed_field("user", 8, Label::Optional, Type::Message, ".test.User"),
+                    ],
+                    nested_type: vec![DescriptorProto {
+                        name: Some("LabelsEntry".into()),
+                        field: vec![
+                            field("key", 1, Label::Optional, Type::String),
+                            field("value", 2, Label::Optional, Type::String),
+                        ],
+                        options: Some(MessageOptions {
+                            map_entry: Some(true),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                DescriptorProto {
+                    name: Some("User".into()),
+                    field: vec![field("id", 1, Label::Optional, Type::Uint64)],
+                    ..Default::default()
+                },
+            ],
+            enum_type: vec![EnumDescriptorProto {
+                name: Some("Level".into()),
+                value: ["UNKNOWN", "INFO", "ERROR"]
+                    .iter()
+                    .enumerate()
+                    .map(|(number, name)| EnumValueDescriptorProto {
+                        name: Some((*name).into()),
+                        number: Some(number as i32),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }],
+            dependency: vec!["google/protobuf/timestamp.proto".into()],
+            ..Default::default()
+        };
+        let timestamp = FileDescriptorProto {
+            name: Some("google/protobuf/timestamp.proto".into()),
+            package: Some("google.protobuf".into()),
+            syntax: Some("proto3".into()),
+            message_type: vec![DescriptorProto {
+                name: Some("Timestamp".into()),
+                field: vec![
+                    field("seconds", 1, Label::Optional, Type::Int64),
+                    field("nanos", 2, Label::Optional, Type::Int32),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let descriptor_set = FileDescriptorSet {
+            file: vec![timestamp, file],
+        }
+        .encode_to_vec();
+
+        let pool = DescriptorPool::decode(descriptor_set.as_slice()).unwrap();
+        MessageEncoder::new(pool.get_message_by_name("test.Record").unwrap())
+    }
+
+    #[test]
+    fn encodes_messages() {
+        let mut log = LogEvent::from("hello");
+        log.insert("status", 200);
+        log.insert("ratio", 1);
+        log.insert("tags", Value::Array(vec!["a".into(), "b".into()]));
+        log.insert("level", "ERROR");
+        log.insert("labels.env", "prod");
+        log.insert("timestamp", Utc.ymd(2023, 1, 2).and_hms_nano(3, 4, 5, 6));
+        log.insert("user.id", 42);
+
+        let message = encoder().encode(&log);
+
+        assert_eq!(
+            message.get_field_by_name("message").unwrap().as_str(),
+            Some("hello")
+        );
+        assert_eq!(
+            message.get_field_by_name("status").unwrap().as_i32(),
+            Some(200)
+        );
+        assert_eq!(
+            message.get_field_by_name("ratio").unwrap().as_f64(),
+            Some(1.0)
+        );
+        assert_eq!(
+            message.get_field_by_name("tags").unwrap().as_list(),
+            Some(
+                &[
+                    ReflectValue::String("a".into()),
+                    ReflectValue::String("b".into())
+                ][..]
+            )
+        );
+        assert_eq!(
+            message.get_field_by_name("level").unwrap().as_enum_number(),
+            Some(2)
+        );
+        assert_eq!(
+            message.get_field_by_name("labels").unwrap().as_map(),
+            Some(&HashMap::from([(
+                MapKey::String("env".into()),
+                ReflectValue::String("prod".into())
+            )]))
+        );
+        let timestamp = message.get_field_by_name("timestamp").unwrap();
+        let timestamp = timestamp.as_message().unwrap();
+        assert_eq!(
+            timestamp.get_field_by_name("seconds").unwrap().as_i64(),
+            Some(1_672_628_645)
+        );
+        assert_eq!(
+            timestamp.get_field_by_name("nanos").unwrap().as_i32(),
+            Some(6)
+        );
+        let user = message.get_field_by_name("user").unwrap();
+        assert_eq!(
+            user.as_message()
+                .unwrap()
+                .get_field_by_name("id")
+                .unwrap()
+                .as_u64(),
+            Some(42)
+        );
+    }
+
+    #[test]
+    fn leaves_mismatched_values_unset() {
+        let mut log = LogEvent::from("hello");
+        log.insert("status", "ok");
+        log.insert("level", "DEBUG");
+        log.insert("user", "vector");
+
+        let message = encoder().encode(&log);
+
+        assert!(message.has_field_by_name("message"));
+        assert!(!message.has_field_by_name("status"));
+        assert!(!message.has_field_by_name("level"));
+        assert!(!message.has_field_by_name("user"));
+    }
+}
@@ -0,0 +1,249 @@
+//! The `grpc` sink.
+//!
+//! Encodes events as messages of a type defined in a descriptor set, and sends them to a unary or
+//! client streaming method of a gRPC server.
+
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+pub use config::GrpcSinkConfig;
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use bytes::{Buf, Bytes};
+    use futures::StreamExt;
+    use http::request::Parts;
+    use prost::Message;
+    use prost_reflect::{DescriptorPool, DynamicMessage};
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+        MethodDescriptorProto, ServiceDescriptorProto,
+    };
+    use vector_core::event::{BatchNotifier, BatchStatus};
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        sinks::util::test::build_test_server_generic,
+        test_util::{
+            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+            next_addr, random_lines_with_stream,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GrpcSinkConfig>();
+    }
+
+    fn field(name: &str, number: i32, label: Label, r#type: Type) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.into()),
+            number: Some(number),
+            label: Some(label.into()),
+            r#type: Some(r#type.into()),
+            json_name: Some(name.into()),
+            ..Default::default()
+        }
+    }
+
+    fn descriptor_set() -> Vec<u8> {
+        let mut records = field("records", 1, Label::Repeated, Type::Message);
+        records.type_name = Some(".telemetry.v1.Telemetry".into());
+
+        let method = |name: &str, input: &str, client_streaming: bool| MethodDescriptorProto {
+            name: Some(name.into()),
+            input_type: Some(format!(".telemetry.v1.{}", input)),
+            output_type: Some(".telemetry.v1.PushResponse".into()),
+            client_streaming: Some(client_streaming),
+            ..Default::default()
+        };
+
+        FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("telemetry.proto".into()),
+                package: Some("telemetry.v1".into()),
+                syntax: Some("proto3".into()),
+                message_type: vec![
+                    DescriptorProto {
+                        name: Some("Telemetry".into()),
+                        field: vec![field("message", 1, Label::Optional, Type::String)],
+                        ..Default::default()
+                    },
+                    DescriptorProto {
+                        name: Some("TelemetryBatch".into()),
+                        field: vec![records],
+                        ..Default::default()
+                    },
+                    DescriptorProto {
+                        name: Some("PushResponse".into()),
+                        ..Default::default()
+                    },
+                ],
+                service: vec![ServiceDescriptorProto {
+                    name: Some("TelemetryService".into()),
+                    method: vec![
+                        method("Push", "Telemetry", false),
+                        method("PushBatch", "TelemetryBatch", false),
+                        method("PushStream", "Telemetry", true),
+                    ],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+        .encode_to_vec()
+    }
+
+    fn config(
+        endpoint: &str,
+        method: &str,
+        message_type: Option<&str>,
+    ) -> (GrpcSinkConfig, tempfile::NamedTempFile) {
+        let mut descriptor_set_file = tempfile::NamedTempFile::new().unwrap();
+        descriptor_set_file.write_all(&descriptor_set()).unwrap();
+
+        let mut config = format!(
+            r#"endpoint = "{}"
+            descriptor_set_file = "{}"
+            method = "{}""#,
+            endpoint,
+            descriptor_set_file.path().display(),
+            method
+        );
+        if let Some(message_type) = message_type {
+            config.push_str(&format!("\nmessage_type = \"{}\"", message_type));
+        }
+
+        (toml::from_str(&config).unwrap(), descriptor_set_file)
+    }
+
+    /// Splits the body of a request in its messages, decoded as `message_type`.
+    fn messages(mut body: Bytes, message_type: &str) -> Vec<DynamicMessage> {
+        let pool = DescriptorPool::decode(descriptor_set().as_slice()).unwrap();
+        let descriptor = pool.get_message_by_name(message_type).unwrap();
+
+        let mut messages = Vec::new();
+        while body.has_remaining() {
+            assert_eq!(body.get_u8(), 0);
+            let len = body.get_u32() as usize;
+            messages.push(DynamicMessage::decode(descriptor.clone(), body.split_to(len)).unwrap());
+        }
+        messages
+    }
+
+    fn message_field(message: &DynamicMessage) -> String {
+        message
+            .get_field_by_name("message")
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    async fn send_lines(
+        method: &str,
+        message_type: Option<&str>,
+        num_lines: usize,
+    ) -> (Vec<String>, Vec<(Parts, Bytes)>) {
+        let address = next_addr();
+        let (config, _descriptor_set_file) =
+            config(&format!("http://{}", address), method, message_type);
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (rx, trigger, server) = build_test_server_generic(address, move || {
+            hyper::Response::builder()
+                .header("grpc-status", "0")
+                .header("content-type", "application/grpc")
+                .body(hyper::Body::from(vec![0; 5]))
+                .unwrap()
+        });
+        tokio::spawn(server);
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (lines, events) = random_lines_with_stream(8, num_lines, Some(batch));
+        run_and_assert_sink_compliance(sink, events, &HTTP_SINK_TAGS).await;
+        drop(trigger);
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+        (lines, rx.collect::<Vec<_>>().await)
+    }
+
+    #[tokio::test]
+    async fn calls_unary_methods_per_event() {
+        let (lines, requests) = send_lines("telemetry.v1.TelemetryService/Push", None, 3).await;
+
+        assert_eq!(requests.len(), 3);
+        let received: Vec<_> = requests
+            .into_iter()
+            .flat_map(|(parts, body)| {
+                assert_eq!(parts.uri.path(), "/telemetry.v1.TelemetryService/Push");
+                messages(body, "telemetry.v1.Telemetry")
+            })
+            .map(|message| message_field(&message))
+            .collect();
+        assert_eq!(received, lines);
+    }
+
+    #[tokio::test]
+    async fn wraps_batches_in_unary_messages() {
+        let (lines, requests) = send_lines(
+            "telemetry.v1.TelemetryService/PushBatch",
+            Some("telemetry.v1.Telemetry"),
+            3,
+        )
+        .await;
+
+        assert_eq!(requests.len(), 1);
+        let (parts, body) = requests.into_iter().next().unwrap();
+        assert_eq!(parts.uri.path(), "/telemetry.v1.TelemetryService/PushBatch");
+        let batches = messages(body, "telemetry.v1.TelemetryBatch");
+        assert_eq!(batches.len(), 1);
+        let records = batches[0].get_field_by_name("records").unwrap();
+        let received: Vec<_> = records
+            .as_list()
+            .unwrap()
+            .iter()
+            .map(|record| message_field(record.as_message().unwrap()))
+            .collect();
+        assert_eq!(received, lines);
+    }
+
+    #[tokio::test]
+    async fn streams_batches_to_client_streaming_methods() {
+        let (lines, requests) =
+            send_lines("telemetry.v1.TelemetryService/PushStream", None, 3).await;
+
+        assert_eq!(requests.len(), 1);
+        let (parts, body) = requests.into_iter().next().unwrap();
+        assert_eq!(
+            parts.uri.path(),
+            "/telemetry.v1.TelemetryService/PushStream"
+        );
+        let received: Vec<_> = messages(body, "telemetry.v1.Telemetry")
+            .iter()
+            .map(message_field)
+            .collect();
+        assert_eq!(received, lines);
+    }
+
+    #[tokio::test]
+    async fn rejects_mismatched_message_types() {
+        let (config, _descriptor_set_file) = config(
+            "http://localhost:50051",
+            "telemetry.v1.TelemetryService/PushStream",
+            Some("telemetry.v1.TelemetryBatch"),
+        );
+
+        let error = config.build(SinkContext::new_test()).await.err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"Method "telemetry.v1.TelemetryService/PushStream" streams messages other than "telemetry.v1.TelemetryBatch""#
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use http::uri::PathAndQuery;
+use prost_reflect::{DynamicMessage, MessageDescriptor};
+use snafu::{ResultExt, Snafu};
+use tonic::{client::Grpc, transport::Channel, Code, Status};
+use tower::Service;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    stream::DriverResponse,
+};
+
+use crate::{grpc::DynamicCodec, sinks::util::retries::RetryLogic};
+
+#[derive(Debug, Snafu)]
+pub enum GrpcError {
+    #[snafu(display("Failed to connect: {}", source))]
+    Connect { source: tonic::transport::Error },
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: Status },
+}
+
+/// The messages of a call.
+#[derive(Clone)]
+pub enum GrpcMessages {
+    /// The message of a call to a unary method.
+    Unary(DynamicMessage),
+    /// The messages streamed in a call to a client streaming method.
+    Streaming(Vec<DynamicMessage>),
+}
+
+#[derive(Clone)]
+pub struct GrpcRequest {
+    pub messages: GrpcMessages,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for GrpcRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for GrpcRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata.clone()
+    }
+}
+
+pub struct GrpcResponse;
+
+impl DriverResponse for GrpcResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+}
+
+/// Calls the configured method, ignoring the messages it responds with.
+#[derive(Clone)]
+pub struct GrpcService {
+    pub client: Grpc<Channel>,
+    pub path: PathAndQuery,
+    pub output: MessageDescriptor,
+}
+
+impl Service<GrpcRequest> for GrpcService {
+    type Response = GrpcResponse;
+    type Error = GrpcError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Readiness of the client is awaited in `call()`, as it is only cloned there.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: GrpcRequest) -> Self::Future {
+        let mut service = self.clone();
+        Box::pin(async move {
+            service.client.ready().await.context(ConnectSnafu)?;
+
+            let codec = DynamicCodec::new(service.output.clone());
+            match request.messages {
+                GrpcMessages::Unary(message) => service
+                    .client
+                    .unary(tonic::Request::new(message), service.path, codec)
+                    .await
+                    .context(RequestSnafu)?,
+                GrpcMessages::Streaming(messages) => service
+                    .client
+                    .client_streaming(
+                        tonic::Request::new(futures::stream::iter(messages)),
+                        service.path,
+                        codec,
+                    )
+                    .await
+                    .context(RequestSnafu)?,
+            };
+
+            Ok(GrpcResponse)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GrpcRetryLogic;
+
+impl RetryLogic for GrpcRetryLogic {
+    type Error = GrpcError;
+    type Response = GrpcResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            GrpcError::Connect { .. } => true,
+            GrpcError::Request { source } => !matches!(
+                source.code(),
+                // The codes of calls that fail the same way when they're retried, see
+                // <https://github.com/grpc/grpc/blob/master/doc/statuscodes.md>.
+                Code::NotFound
+                    | Code::InvalidArgument
+                    | Code::AlreadyExists
+                    | Code::PermissionDenied
+                    | Code::OutOfRange
+                    | Code::Unimplemented
+                    | Code::Unauthenticated
+            ),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::num::NonZeroUsize;
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use prost::Message;
+use prost_reflect::{DynamicMessage, FieldDescriptor, MessageDescriptor, Value as ReflectValue};
+use tower::ServiceBuilder;
+use vector_core::{
+    event::{EventFinalizers, Finalizable},
+    stream::BatcherSettings,
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use super::{
+    encoder::MessageEncoder,
+    service::{GrpcMessages, GrpcRequest, GrpcRetryLogic, GrpcService},
+};
+use crate::{
+    codecs::Transformer,
+    event::Event,
+    sinks::util::{
+        metadata::RequestMetadataBuilder, ServiceBuilderExt, SinkBuilderExt, StreamSink,
+        TowerRequestSettings,
+    },
+};
+
+/// How the messages of batches are sent to the method.
+#[derive(Clone, Debug)]
+pub enum CallMode {
+    /// Each message is sent in its own call, as the method takes a single one.
+    Unary,
+    /// The messages of each batch are set as the repeated `field` of the message of their call.
+    Wrapped {
+        input: MessageDescriptor,
+        field: FieldDescriptor,
+    },
+    /// The messages of each batch are streamed in their call.
+    ClientStreaming,
+}
+
+impl CallMode {
+    /// The messages of the call sending the messages of a batch.
+    fn messages(&self, messages: Vec<DynamicMessage>) -> GrpcMessages {
+        match self {
+            Self::Unary => GrpcMessages::Unary(
+                messages
+                    .into_iter()
+                    .next()
+                    .expect("batches should not be empty"),
+            ),
+            Self::Wrapped { input, field } => {
+                let mut message = DynamicMessage::new(input.clone());
+                message.set_field(
+                    field,
+                    ReflectValue::List(messages.into_iter().map(ReflectValue::Message).collect()),
+                );
+                GrpcMessages::Unary(message)
+            }
+            Self::ClientStreaming => GrpcMessages::Streaming(messages),
+        }
+    }
+}
+
+/// An event, encoded as a message, with the sizes it had before being transformed.
+///
+/// Messages are batched by their encoded size, which is what the size of requests is limited by.
+struct EncodedMessage {
+    message: DynamicMessage,
+    finalizers: EventFinalizers,
+    byte_size: usize,
+    json_size: usize,
+}
+
+impl ByteSizeOf for EncodedMessage {
+    fn size_of(&self) -> usize {
+        self.message.encoded_len()
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
+pub struct GrpcSink {
+    pub service: GrpcService,
+    pub encoder: MessageEncoder,
+    pub call_mode: CallMode,
+    pub transformer: Transformer,
+    pub batch_settings: BatcherSettings,
+    pub request: TowerRequestSettings,
+}
+
+#[async_trait]
+impl StreamSink<Event> for GrpcSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let service = ServiceBuilder::new()
+            .settings(self.request, GrpcRetryLogic)
+            .service(self.service);
+
+        let mut batch_settings = self.batch_settings;
+        if let CallMode::Unary = self.call_mode {
+            batch_settings.item_limit = 1;
+        }
+
+        let encoder = self.encoder;
+        let call_mode = self.call_mode;
+        let transformer = self.transformer;
+        input
+            .map(|mut event| {
+                let byte_size = event.size_of();
+                let json_size = event.estimated_json_encoded_size_of();
+                transformer.transform(&mut event);
+                EncodedMessage {
+                    finalizers: event.take_finalizers(),
+                    message: encoder.encode(&event.into_log()),
+                    byte_size,
+                    json_size,
+                }
+            })
+            .batched(batch_settings.into_byte_size_config())
+            .map(|messages| {
+                let count = messages.len();
+                let mut byte_size = 0;
+                let mut json_size = 0;
+                let mut request_size = 0;
+                let mut finalizers = EventFinalizers::default();
+                let messages: Vec<_> = messages
+                    .into_iter()
+                    .map(|message| {
+                        byte_size += message.byte_size;
+                        json_size += message.json_size;
+                        request_size += message.message.encoded_len();
+                        finalizers.merge(message.finalizers);
+                        message.message
+                    })
+                    .collect();
+                // Messages of events without any of the fields of the message type are empty.
+                let metadata = RequestMetadataBuilder::new(count, byte_size, json_size)
+                    .with_request_size(
+                        NonZeroUsize::new(request_size.max(1))
+                            .expect("size should be at least one"),
+                    );
+
+                GrpcRequest {
+                    messages: call_mode.messages(messages),
+                    finalizers,
+                    metadata,
+                }
+            })
+            .into_driver(service)
+            .protocol("grpc")
+            .run()
+            .await
+    }
+}
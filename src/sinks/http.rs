@@ -6,30 +6,53 @@ use flate2::write::{GzEncoder, ZlibEncoder};
 use futures::{future, FutureExt, SinkExt};
 use http::{
     header::{self, HeaderName, HeaderValue},
-    Method, Request, StatusCode, Uri,
+    Method, Request, StatusCode,
 };
 use hyper::Body;
 use indexmap::IndexMap;
+use openssl::{
+    pkey::{PKey, Private},
+    sign::Signer,
+};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::Encoder as _;
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
 use crate::{
     codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     event::Event,
-    http::{Auth, HttpClient, MaybeAuth},
+    http::{
+        build_authenticator, Auth, HmacAlgorithm, HttpAuthenticator, HttpClient, MaybeAuth,
+        SignatureEncoding,
+    },
+    internal_events::{HttpInvalidUriError, TemplateRenderingError},
     sinks::util::{
         self,
-        http::{BatchedHttpSink, HttpEventEncoder, RequestConfig},
-        BatchConfig, Buffer, Compression, RealtimeSizeBasedDefaultBatchSettings,
-        TowerRequestConfig, UriSerde,
+        http::{HttpEventEncoder, PartitionHttpSink, RequestConfig},
+        BatchConfig, Buffer, Compression, PartitionBuffer, PartitionInnerBuffer,
+        RealtimeSizeBasedDefaultBatchSettings, TowerRequestConfig, UriSerde,
     },
+    template::Template,
     tls::{TlsConfig, TlsSettings},
 };
 
+/// The characters percent-encoded in the values of fields rendered in the URI, leaving only the
+/// unreserved ones, so that they can't add path segments or query parameters.
+const URI_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
 #[derive(Debug, Snafu)]
 enum BuildError {
+    #[snafu(display("invalid uri: {}", source))]
+    InvalidUri { source: http::uri::InvalidUri },
+    #[snafu(display("only the path and query of the uri can be templated"))]
+    TemplatedUriAuthority,
     #[snafu(display("{}: {}", source, name))]
     InvalidHeaderName {
         name: String,
@@ -50,7 +73,13 @@ pub struct HttpSinkConfig {
     /// The full URI to make HTTP requests to.
     ///
     /// This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
-    pub uri: UriSerde,
+    ///
+    /// The path and query can be templated from the fields of events, in which case events are
+    /// batched per rendered URI. The values of fields are percent-encoded. Events for which the
+    /// URI can't be rendered, or is invalid, are dropped.
+    #[configurable(metadata(docs::examples = "https://10.22.212.22:9000/endpoint"))]
+    #[configurable(metadata(docs::examples = "https://example.com/api/{{ tenant }}/logs"))]
+    pub uri: Template,
 
     /// The HTTP method to use when making the request.
     pub method: Option<HttpMethod>,
@@ -80,6 +109,9 @@ pub struct HttpSinkConfig {
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
 
+    #[configurable(derived)]
+    pub signing: Option<HttpSigningConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -89,6 +121,41 @@ pub struct HttpSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// Request signing configuration.
+///
+/// Requests are signed with an HMAC of the values of the signed headers, each followed by a
+/// newline, and of the body, as it's sent after compression. The signature is sent in its own
+/// header, so that webhooks can verify where requests come from.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HttpSigningConfig {
+    /// The shared secret used to sign requests.
+    pub secret: SensitiveString,
+
+    /// The name of the header carrying the signature.
+    #[configurable(metadata(docs::examples = "X-Signature"))]
+    pub header: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub algorithm: HmacAlgorithm,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub encoding: SignatureEncoding,
+
+    /// A prefix to add to the signature in the header value, such as `sha256=`.
+    pub prefix: Option<String>,
+
+    /// The headers whose values are signed along with the body, in order.
+    ///
+    /// Headers missing from requests are signed as empty values.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "X-Timestamp"))]
+    pub headers: Vec<String>,
+}
+
 /// HTTP method.
 ///
 /// A subset of the HTTP methods described in [RFC 9110, section 9.1][rfc9110] are supported.
@@ -144,15 +211,75 @@ impl HttpSinkConfig {
     }
 }
 
+/// Signs requests with an HMAC of the values of some of their headers and of their body.
+struct RequestSigner {
+    key: PKey<Private>,
+    header: HeaderName,
+    algorithm: HmacAlgorithm,
+    encoding: SignatureEncoding,
+    prefix: String,
+    headers: Vec<HeaderName>,
+}
+
+impl RequestSigner {
+    fn new(config: &HttpSigningConfig) -> crate::Result<Self> {
+        if config.secret.inner().is_empty() {
+            return Err("Signing secret must not be empty.".into());
+        }
+        let header_name = |name: &String| {
+            HeaderName::from_bytes(name.as_bytes())
+                .with_context(|_| InvalidHeaderNameSnafu { name })
+        };
+        let prefix = config.prefix.clone().unwrap_or_default();
+        HeaderValue::from_str(&prefix).with_context(|_| InvalidHeaderValueSnafu {
+            value: prefix.clone(),
+        })?;
+
+        Ok(Self {
+            key: PKey::hmac(config.secret.inner().as_bytes())?,
+            header: header_name(&config.header)?,
+            algorithm: config.algorithm,
+            encoding: config.encoding,
+            prefix,
+            headers: config
+                .headers
+                .iter()
+                .map(header_name)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn sign(&self, request: &mut Request<Bytes>) -> crate::Result<()> {
+        let mut signer = Signer::new(self.algorithm.message_digest(), &self.key)?;
+        for name in &self.headers {
+            if let Some(value) = request.headers().get(name) {
+                signer.update(value.as_bytes())?;
+            }
+            signer.update(b"\n")?;
+        }
+        signer.update(request.body())?;
+
+        let signature = signer.sign_to_vec()?;
+        let signature = match self.encoding {
+            SignatureEncoding::Hex => hex::encode(signature),
+            SignatureEncoding::Base64 => base64::encode(signature),
+        };
+        let value = HeaderValue::from_str(&format!("{}{}", self.prefix, signature))?;
+        request.headers_mut().insert(self.header.clone(), value);
+        Ok(())
+    }
+}
+
 struct HttpSink {
-    pub uri: UriSerde,
+    pub uri: Template,
     pub method: Option<HttpMethod>,
-    pub auth: Option<Auth>,
+    pub auth: Option<HttpAuthenticator>,
     pub compression: Compression,
     pub transformer: Transformer,
     pub encoder: Encoder<Framer>,
     pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
     pub request: RequestConfig,
+    pub signer: Option<RequestSigner>,
 }
 
 #[cfg(test)]
@@ -161,7 +288,7 @@ fn default_sink(encoding: EncodingConfigWithFraming) -> HttpSink {
     let encoder = Encoder::<Framer>::new(framing, serializer);
 
     HttpSink {
-        uri: Default::default(),
+        uri: Template::try_from("http://127.0.0.1/").unwrap(),
         method: Default::default(),
         auth: Default::default(),
         compression: Default::default(),
@@ -169,6 +296,7 @@ fn default_sink(encoding: EncodingConfigWithFraming) -> HttpSink {
         encoder,
         batch: Default::default(),
         request: Default::default(),
+        signer: None,
     }
 }
 
@@ -180,29 +308,43 @@ impl SinkConfig for HttpSinkConfig {
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let client = self.build_http_client(&cx)?;
 
-        let healthcheck = match cx.healthcheck.uri {
-            Some(healthcheck_uri) => {
-                healthcheck(healthcheck_uri, self.auth.clone(), client.clone()).boxed()
-            }
-            None => future::ok(()).boxed(),
-        };
-
         let mut request = self.request.clone();
         request.add_old_option(self.headers.clone());
         validate_headers(&request.headers, &self.auth)?;
 
+        // Templated URIs can only be checked once rendered, when encoding events.
+        if Template::try_from(uri_authority(self.uri.get_ref()))?.is_dynamic() {
+            return Err(BuildError::TemplatedUriAuthority.into());
+        }
+        if !self.uri.is_dynamic() {
+            let uri: UriSerde = self.uri.get_ref().parse().context(InvalidUriSnafu)?;
+            self.auth.choose_one(&uri.auth)?;
+        }
+
         let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
         let encoder = Encoder::<Framer>::new(framer, serializer);
 
+        let signer = self.signing.as_ref().map(RequestSigner::new).transpose()?;
+
+        let auth = build_authenticator(self.auth.as_ref(), &client).await?;
+
+        let healthcheck = match cx.healthcheck.uri {
+            Some(healthcheck_uri) => {
+                healthcheck(healthcheck_uri, auth.clone(), client.clone()).boxed()
+            }
+            None => future::ok(()).boxed(),
+        };
+
         let sink = HttpSink {
-            uri: self.uri.with_default_parts(),
+            uri: self.uri.clone(),
             method: self.method,
-            auth: self.auth.choose_one(&self.uri.auth)?,
+            auth,
             compression: self.compression,
             transformer: self.encoding.transformer(),
             encoder,
             batch: self.batch,
             request,
+            signer,
         };
 
         let request = sink
@@ -211,9 +353,9 @@ impl SinkConfig for HttpSinkConfig {
             .unwrap_with(&TowerRequestConfig::default());
 
         let batch = sink.batch.into_batch_settings()?;
-        let sink = BatchedHttpSink::new(
+        let sink = PartitionHttpSink::new(
             sink,
-            Buffer::new(batch.size, Compression::None),
+            PartitionBuffer::new(Buffer::new(batch.size, Compression::None)),
             request,
             batch.timeout,
             client,
@@ -234,36 +376,68 @@ impl SinkConfig for HttpSinkConfig {
     }
 }
 
+/// Returns the scheme and authority of `uri`, which precede its path and query.
+fn uri_authority(uri: &str) -> &str {
+    let start = uri.find("://").map_or(0, |index| index + 3);
+    let end = uri[start..]
+        .find(|c| matches!(c, '/' | '?' | '#'))
+        .map_or(uri.len(), |index| start + index);
+    &uri[..end]
+}
+
 pub struct HttpSinkEventEncoder {
     encoder: Encoder<Framer>,
     transformer: Transformer,
+    uri: Template,
 }
 
-impl HttpEventEncoder<BytesMut> for HttpSinkEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<BytesMut> {
+impl HttpEventEncoder<PartitionInnerBuffer<BytesMut, String>> for HttpSinkEventEncoder {
+    fn encode_event(&mut self, mut event: Event) -> Option<PartitionInnerBuffer<BytesMut, String>> {
+        let uri = self
+            .uri
+            .render_string_escaped(&event, |value| {
+                utf8_percent_encode(value, URI_COMPONENT).into()
+            })
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("uri"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+        if self.uri.is_dynamic() {
+            if let Err(error) = uri.parse::<UriSerde>() {
+                emit!(HttpInvalidUriError { uri: &uri, error });
+                return None;
+            }
+        }
+
         self.transformer.transform(&mut event);
 
         let mut body = BytesMut::new();
         self.encoder.encode(event, &mut body).ok()?;
 
-        Some(body)
+        Some(PartitionInnerBuffer::new(body, uri))
     }
 }
 
 #[async_trait::async_trait]
 impl util::http::HttpSink for HttpSink {
-    type Input = BytesMut;
-    type Output = BytesMut;
+    type Input = PartitionInnerBuffer<BytesMut, String>;
+    type Output = PartitionInnerBuffer<BytesMut, String>;
     type Encoder = HttpSinkEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         HttpSinkEventEncoder {
             encoder: self.encoder.clone(),
             transformer: self.transformer.clone(),
+            uri: self.uri.clone(),
         }
     }
 
-    async fn build_request(&self, mut body: Self::Output) -> crate::Result<http::Request<Bytes>> {
+    async fn build_request(&self, output: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let (mut body, uri) = output.into_parts();
         let method = match &self.method.unwrap_or(HttpMethod::Post) {
             HttpMethod::Get => Method::GET,
             HttpMethod::Head => Method::HEAD,
@@ -274,7 +448,7 @@ impl util::http::HttpSink for HttpSink {
             HttpMethod::Trace => Method::TRACE,
             HttpMethod::Patch => Method::PATCH,
         };
-        let uri: Uri = self.uri.uri.clone();
+        let uri = uri.parse::<UriSerde>()?.with_default_parts();
 
         let content_type = {
             use Framer::*;
@@ -308,7 +482,7 @@ impl util::http::HttpSink for HttpSink {
             }
         };
 
-        let mut builder = Request::builder().method(method).uri(uri);
+        let mut builder = Request::builder().method(method).uri(uri.uri);
 
         if let Some(content_type) = content_type {
             builder = builder.header("Content-Type", content_type);
@@ -339,23 +513,39 @@ impl util::http::HttpSink for HttpSink {
         }
 
         let mut request = builder.body(body.freeze()).unwrap();
+        apply_auth(&mut request, &self.auth, &uri.auth)?;
 
-        if let Some(auth) = &self.auth {
-            auth.apply(&mut request);
+        if let Some(signer) = &self.signer {
+            signer.sign(&mut request)?;
         }
 
         Ok(request)
     }
 }
 
-async fn healthcheck(uri: UriSerde, auth: Option<Auth>, client: HttpClient) -> crate::Result<()> {
-    let auth = auth.choose_one(&uri.auth)?;
+/// Authenticates `request` with the configured authentication, or the credentials of its URI.
+fn apply_auth<B>(
+    request: &mut Request<B>,
+    auth: &Option<HttpAuthenticator>,
+    uri_auth: &Option<Auth>,
+) -> crate::Result<()> {
+    match (auth, uri_auth) {
+        (Some(_), Some(_)) => return Err("Two authorization credentials was provided.".into()),
+        (Some(auth), None) => auth.apply(request),
+        (None, Some(auth)) => auth.apply(request),
+        (None, None) => {}
+    }
+    Ok(())
+}
+
+async fn healthcheck(
+    uri: UriSerde,
+    auth: Option<HttpAuthenticator>,
+    client: HttpClient,
+) -> crate::Result<()> {
     let uri = uri.with_default_parts();
     let mut request = Request::head(&uri.uri).body(Body::empty()).unwrap();
-
-    if let Some(auth) = auth {
-        auth.apply(&mut request);
-    }
+    apply_auth(&mut request, &auth, &uri.auth)?;
 
     let response = client.send(request).await?;
 
@@ -426,11 +616,25 @@ mod tests {
 
         let sink = default_sink((None::<FramingConfig>, TextSerializerConfig::new()).into());
         let mut encoder = sink.build_encoder();
-        let bytes = encoder.encode_event(event).unwrap();
+        let (bytes, _) = encoder.encode_event(event).unwrap().into_parts();
 
         assert_eq!(bytes, Vec::from("hello world\n"));
     }
 
+    #[test]
+    fn http_encode_event_escapes_uri_fields() {
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("tenant", "a/b c");
+        event.as_mut_log().insert("query", "x&y=z");
+
+        let mut sink = default_sink((None::<FramingConfig>, TextSerializerConfig::new()).into());
+        sink.uri = Template::try_from("http://127.0.0.1/api/{{ tenant }}?q={{ query }}").unwrap();
+        let mut encoder = sink.build_encoder();
+        let (_, uri) = encoder.encode_event(event).unwrap().into_parts();
+
+        assert_eq!(uri, "http://127.0.0.1/api/a%2Fb%20c?q=x%26y%3Dz");
+    }
+
     #[test]
     fn http_encode_event_ndjson() {
         let event = Event::Log(LogEvent::from("hello world"));
@@ -443,7 +647,7 @@ mod tests {
                 .into(),
         );
         let mut encoder = sink.build_encoder();
-        let bytes = encoder.encode_event(event).unwrap();
+        let (bytes, _) = encoder.encode_event(event).unwrap().into_parts();
 
         #[derive(Deserialize, Debug)]
         #[serde(deny_unknown_fields)]
@@ -489,6 +693,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn http_signs_requests() {
+        let signer = RequestSigner::new(&HttpSigningConfig {
+            secret: "It's a Secret to Everybody".to_owned().into(),
+            header: "X-Signature".to_owned(),
+            algorithm: HmacAlgorithm::Sha256,
+            encoding: SignatureEncoding::Hex,
+            prefix: Some("sha256=".to_owned()),
+            headers: vec!["X-Timestamp".to_owned(), "X-Missing".to_owned()],
+        })
+        .unwrap();
+
+        let mut request = Request::post("http://example.com")
+            .header("X-Timestamp", "1700000000")
+            .body(Bytes::from("hello world"))
+            .unwrap();
+        signer.sign(&mut request).unwrap();
+
+        let key = PKey::hmac(b"It's a Secret to Everybody").unwrap();
+        let mut expected = Signer::new(openssl::hash::MessageDigest::sha256(), &key).unwrap();
+        expected.update(b"1700000000\n\nhello world").unwrap();
+        let expected = format!("sha256={}", hex::encode(expected.sign_to_vec().unwrap()));
+        assert_eq!(request.headers()["X-Signature"], expected);
+    }
+
     // TODO: Fix failure on GH Actions using macos-latest image.
     #[cfg(not(target_os = "macos"))]
     #[tokio::test]
@@ -511,6 +740,18 @@ mod tests {
         let _ = config.build(cx).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn http_rejects_templated_uri_authority() {
+        let config = r#"
+        uri = "http://{{ host }}/logs"
+        encoding.codec = "text"
+        "#;
+        let config: HttpSinkConfig = toml::from_str(config).unwrap();
+
+        let error = config.build(SinkContext::new_test()).await.err().unwrap();
+        assert_downcast_matches!(error, BuildError, BuildError::TemplatedUriAuthority);
+    }
+
     #[tokio::test]
     async fn http_happy_path_post() {
         run_sink(
@@ -579,6 +820,45 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn http_partitions_batches_by_templated_uri() {
+        let in_addr = next_addr();
+        let config = format!(
+            r#"
+                uri = "http://{addr}/api/{{{{ tenant }}}}/logs"
+                encoding.codec = "json"
+            "#,
+            addr = in_addr,
+        );
+        let config: HttpSinkConfig = toml::from_str(&config).unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (rx, trigger, server) = build_test_server(in_addr);
+        tokio::spawn(server);
+
+        let events = ["a", "b", "a"].into_iter().map(|tenant| {
+            let mut log = LogEvent::from(format!("hello {}", tenant));
+            log.insert("tenant", tenant);
+            Event::Log(log)
+        });
+        components::run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS)
+            .await;
+        drop(trigger);
+
+        let mut requests = rx
+            .map(|(parts, body)| {
+                let lines: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+                (parts.uri.path().to_owned(), lines.len())
+            })
+            .collect::<Vec<_>>()
+            .await;
+        requests.sort();
+        assert_eq!(
+            requests,
+            vec![("/api/a/logs".to_owned(), 2), ("/api/b/logs".to_owned(), 1)]
+        );
+    }
+
     #[tokio::test]
     async fn retries_on_no_connection() {
         components::assert_sink_compliance(&HTTP_SINK_TAGS, async {
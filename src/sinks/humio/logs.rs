@@ -183,6 +183,7 @@ impl HumioLogsConfig {
             timestamp_key: timestamp_key(),
             endpoint_target: EndpointTarget::Event,
             auto_extract_timestamp: None,
+            metrics: Default::default(),
         }
     }
 }
@@ -0,0 +1,202 @@
+use std::str::FromStr;
+
+use aws_sdk_glue::{
+    model::{Table, TableInput},
+    types::SdkError,
+    Client as GlueClient,
+};
+use aws_smithy_http::endpoint::Endpoint;
+use http::Uri;
+use snafu::ResultExt;
+use vector_config::configurable_component;
+
+use super::{
+    CatalogError, GetTableSnafu, InvalidResponseSnafu, LoadedTable, NewSnapshot, TableSnafu,
+    TableStorageSnafu, UpdateTableSnafu,
+};
+use crate::{
+    aws::{create_client, AwsAuthentication, ClientBuilder, RegionOrEndpoint},
+    config::ProxyConfig,
+    sinks::iceberg::{
+        storage::Storage,
+        table::{next_metadata_location, TableMetadata},
+    },
+    tls::TlsConfig,
+};
+
+/// The table parameters Iceberg keeps the locations of the metadata files of tables in.
+const METADATA_LOCATION: &str = "metadata_location";
+const PREVIOUS_METADATA_LOCATION: &str = "previous_metadata_location";
+
+pub struct GlueClientBuilder;
+
+impl ClientBuilder for GlueClientBuilder {
+    type Config = aws_sdk_glue::config::Config;
+    type Client = aws_sdk_glue::client::Client;
+    type DefaultMiddleware = aws_sdk_glue::middleware::DefaultMiddleware;
+
+    fn default_middleware() -> Self::DefaultMiddleware {
+        aws_sdk_glue::middleware::DefaultMiddleware::new()
+    }
+
+    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
+        aws_sdk_glue::client::Client::with_config(client, config.into())
+    }
+}
+
+/// Configuration for the `glue` catalog of the `iceberg` sink.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct GlueCatalogConfig {
+    /// The ID of the Data Catalog, which is the ID of the AWS account owning it.
+    ///
+    /// By default, the Data Catalog of the account Vector authenticates as is used.
+    #[configurable(metadata(docs::examples = "123456789012"))]
+    catalog_id: Option<String>,
+
+    /// A custom endpoint for the Glue API.
+    #[configurable(metadata(docs::examples = "http://127.0.0.0:5000/path/to/service"))]
+    endpoint: Option<String>,
+}
+
+impl GlueCatalogConfig {
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn build(
+        &self,
+        database: &str,
+        table: &str,
+        storage: Storage,
+        region: &RegionOrEndpoint,
+        auth: &AwsAuthentication,
+        proxy: &ProxyConfig,
+        tls: &Option<TlsConfig>,
+    ) -> crate::Result<GlueCatalog> {
+        let endpoint = self
+            .endpoint
+            .as_deref()
+            .map(Uri::from_str)
+            .transpose()?
+            .map(Endpoint::immutable);
+        let client =
+            create_client::<GlueClientBuilder>(auth, region.region(), endpoint, proxy, tls, true)
+                .await?;
+
+        Ok(GlueCatalog {
+            client,
+            storage,
+            catalog_id: self.catalog_id.clone(),
+            database: database.into(),
+            table: table.into(),
+        })
+    }
+}
+
+pub struct GlueCatalog {
+    client: GlueClient,
+    storage: Storage,
+    catalog_id: Option<String>,
+    database: String,
+    table: String,
+}
+
+impl GlueCatalog {
+    async fn get_table(&self) -> Result<(Table, String), CatalogError> {
+        let output = self
+            .client
+            .get_table()
+            .set_catalog_id(self.catalog_id.clone())
+            .database_name(&self.database)
+            .name(&self.table)
+            .send()
+            .await
+            .context(GetTableSnafu)?;
+
+        let table = output
+            .table()
+            .cloned()
+            .ok_or(CatalogError::MissingMetadataLocation)?;
+        let metadata_location = table
+            .parameters()
+            .and_then(|parameters| parameters.get(METADATA_LOCATION))
+            .cloned()
+            .ok_or(CatalogError::MissingMetadataLocation)?;
+        Ok((table, metadata_location))
+    }
+
+    pub async fn load_table(&self) -> Result<LoadedTable, CatalogError> {
+        let (_, metadata_location) = self.get_table().await?;
+        let metadata = self
+            .storage
+            .get(&metadata_location)
+            .await
+            .context(TableStorageSnafu)?;
+        let metadata = serde_json::from_slice(&metadata).context(InvalidResponseSnafu)?;
+
+        Ok(LoadedTable {
+            metadata: TableMetadata::parse(metadata).context(TableSnafu)?,
+            metadata_location: Some(metadata_location),
+        })
+    }
+
+    /// Writes the metadata of the table with `snapshot` appended to it, and points the table to
+    /// it, using the version of the table in Glue to detect concurrent updates.
+    pub async fn commit(
+        &self,
+        base: &LoadedTable,
+        snapshot: &NewSnapshot,
+    ) -> Result<(), CatalogError> {
+        let (table, metadata_location) = self.get_table().await?;
+        if base.metadata_location.as_ref() != Some(&metadata_location) {
+            return Err(CatalogError::Conflict);
+        }
+
+        let metadata = base.metadata.with_snapshot(
+            snapshot.to_json(base.metadata.format_version),
+            snapshot.snapshot_id,
+            snapshot.sequence_number,
+            snapshot.timestamp_ms,
+            &metadata_location,
+        );
+        let new_location = next_metadata_location(&base.metadata.location, &metadata_location);
+        self.storage
+            .put(
+                &new_location,
+                serde_json::to_vec(&metadata).expect("metadata should be valid json"),
+            )
+            .await
+            .context(TableStorageSnafu)?;
+
+        let mut parameters = table.parameters().cloned().unwrap_or_default();
+        parameters.insert(METADATA_LOCATION.into(), new_location);
+        parameters.insert(PREVIOUS_METADATA_LOCATION.into(), metadata_location);
+        let input = TableInput::builder()
+            .name(&self.table)
+            .set_description(table.description().map(Into::into))
+            .set_owner(table.owner().map(Into::into))
+            .set_table_type(table.table_type().map(Into::into))
+            .set_storage_descriptor(table.storage_descriptor().cloned())
+            .set_partition_keys(table.partition_keys().map(<[_]>::to_vec))
+            .set_parameters(Some(parameters))
+            .build();
+
+        match self
+            .client
+            .update_table()
+            .set_catalog_id(self.catalog_id.clone())
+            .database_name(&self.database)
+            .table_input(input)
+            .set_version_id(table.version_id().map(Into::into))
+            .send()
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(SdkError::ServiceError { err, .. })
+                if err.is_concurrent_modification_exception() =>
+            {
+                Err(CatalogError::Conflict)
+            }
+            Err(error) => Err(error).context(UpdateTableSnafu),
+        }
+    }
+}
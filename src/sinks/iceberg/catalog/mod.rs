@@ -0,0 +1,184 @@
+//! The catalogs tracking the current metadata of tables, which snapshots are committed to.
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value as JsonValue};
+use snafu::Snafu;
+use vector_config::configurable_component;
+
+use super::{
+    storage::{Storage, StorageError},
+    table::{TableError, TableMetadata},
+};
+use crate::{
+    aws::{AwsAuthentication, RegionOrEndpoint},
+    config::ProxyConfig,
+    tls::TlsConfig,
+};
+
+mod glue;
+mod rest;
+
+pub use self::{
+    glue::{GlueCatalog, GlueCatalogConfig},
+    rest::{RestCatalog, RestCatalogConfig},
+};
+
+#[derive(Debug, Snafu)]
+pub enum CatalogError {
+    #[snafu(display("Table was updated concurrently"))]
+    Conflict,
+    #[snafu(display("Catalog request failed: {}", source))]
+    Request { source: crate::http::HttpError },
+    #[snafu(display("Failed to build catalog request: {}", source))]
+    BuildRequest { source: http::Error },
+    #[snafu(display("Failed to read catalog response: {}", source))]
+    ReadResponse { source: hyper::Error },
+    #[snafu(display("Catalog responded with status {}: {}", status, body))]
+    UnexpectedStatus {
+        status: http::StatusCode,
+        body: String,
+    },
+    #[snafu(display("Invalid catalog response: {}", source))]
+    InvalidResponse { source: serde_json::Error },
+    #[snafu(display("Failed to get the table from Glue: {}", source))]
+    GetTable {
+        source: aws_sdk_glue::types::SdkError<aws_sdk_glue::error::GetTableError>,
+    },
+    #[snafu(display("Failed to update the table in Glue: {}", source))]
+    UpdateTable {
+        source: aws_sdk_glue::types::SdkError<aws_sdk_glue::error::UpdateTableError>,
+    },
+    #[snafu(display("Table has no metadata location, and may not be an Iceberg table"))]
+    MissingMetadataLocation,
+    #[snafu(display("{}", source))]
+    Table { source: TableError },
+    #[snafu(display("{}", source))]
+    TableStorage { source: StorageError },
+}
+
+impl CatalogError {
+    /// Whether the request may succeed when retried.
+    pub const fn is_retriable(&self) -> bool {
+        match self {
+            Self::Conflict
+            | Self::ReadResponse { .. }
+            | Self::GetTable { .. }
+            | Self::UpdateTable { .. }
+            | Self::TableStorage { .. } => true,
+            Self::Request { source } => source.is_retriable(),
+            Self::UnexpectedStatus { status, .. } => {
+                status.is_server_error() || *status == http::StatusCode::TOO_MANY_REQUESTS
+            }
+            Self::BuildRequest { .. }
+            | Self::InvalidResponse { .. }
+            | Self::MissingMetadataLocation
+            | Self::Table { .. } => false,
+        }
+    }
+}
+
+/// The catalog tracking the table.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CatalogConfig {
+    /// An [Iceberg REST catalog][rest_catalog].
+    ///
+    /// [rest_catalog]: https://iceberg.apache.org/concepts/catalog/#decoupling-using-the-rest-catalog
+    Rest(#[configurable(derived)] RestCatalogConfig),
+
+    /// The [AWS Glue Data Catalog][glue_catalog], in the region of the sink.
+    ///
+    /// [glue_catalog]: https://docs.aws.amazon.com/glue/latest/dg/components-overview.html#data-catalog-intro
+    Glue(#[configurable(derived)] GlueCatalogConfig),
+}
+
+impl CatalogConfig {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn build(
+        &self,
+        namespace: &str,
+        table: &str,
+        storage: Storage,
+        region: &RegionOrEndpoint,
+        auth: &AwsAuthentication,
+        proxy: &ProxyConfig,
+        tls: &Option<TlsConfig>,
+    ) -> crate::Result<Catalog> {
+        Ok(match self {
+            Self::Rest(config) => Catalog::Rest(config.build(namespace, table, proxy, tls)?),
+            Self::Glue(config) => Catalog::Glue(
+                config
+                    .build(namespace, table, storage, region, auth, proxy, tls)
+                    .await?,
+            ),
+        })
+    }
+}
+
+pub enum Catalog {
+    Rest(RestCatalog),
+    Glue(GlueCatalog),
+}
+
+impl Catalog {
+    pub async fn load_table(&self) -> Result<LoadedTable, CatalogError> {
+        match self {
+            Self::Rest(catalog) => catalog.load_table().await,
+            Self::Glue(catalog) => catalog.load_table().await,
+        }
+    }
+
+    /// Commits `snapshot` to the table, failing with a conflict unless the table is still at the
+    /// snapshot it was loaded at in `base`.
+    pub async fn commit(
+        &self,
+        base: &LoadedTable,
+        snapshot: &NewSnapshot,
+    ) -> Result<(), CatalogError> {
+        match self {
+            Self::Rest(catalog) => catalog.commit(base, snapshot).await,
+            Self::Glue(catalog) => catalog.commit(base, snapshot).await,
+        }
+    }
+}
+
+pub struct LoadedTable {
+    pub metadata: TableMetadata,
+    pub metadata_location: Option<String>,
+}
+
+/// A snapshot appending data files to a table.
+#[derive(Clone, Debug)]
+pub struct NewSnapshot {
+    pub snapshot_id: i64,
+    pub parent_snapshot_id: Option<i64>,
+    pub sequence_number: i64,
+    pub timestamp_ms: i64,
+    pub manifest_list: String,
+    pub schema_id: i32,
+    pub summary: BTreeMap<&'static str, String>,
+}
+
+impl NewSnapshot {
+    pub fn to_json(&self, format_version: u8) -> JsonValue {
+        let mut summary = json!(self.summary);
+        summary["operation"] = json!("append");
+
+        let mut snapshot = json!({
+            "snapshot-id": self.snapshot_id,
+            "timestamp-ms": self.timestamp_ms,
+            "manifest-list": self.manifest_list,
+            "schema-id": self.schema_id,
+            "summary": summary,
+        });
+        if let Some(parent_snapshot_id) = self.parent_snapshot_id {
+            snapshot["parent-snapshot-id"] = json!(parent_snapshot_id);
+        }
+        if format_version > 1 {
+            snapshot["sequence-number"] = json!(self.sequence_number);
+        }
+        snapshot
+    }
+}
@@ -0,0 +1,233 @@
+use bytes::Bytes;
+use http::{Request, StatusCode};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+use snafu::ResultExt;
+use tokio::sync::OnceCell;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use super::{
+    BuildRequestSnafu, CatalogError, InvalidResponseSnafu, LoadedTable, NewSnapshot,
+    ReadResponseSnafu, RequestSnafu, TableSnafu,
+};
+use crate::{
+    config::ProxyConfig,
+    http::HttpClient,
+    sinks::{iceberg::table::TableMetadata, util::UriSerde},
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Configuration for the `rest` catalog of the `iceberg` sink.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RestCatalogConfig {
+    /// The URI of the catalog.
+    #[configurable(metadata(docs::examples = "http://localhost:8181"))]
+    uri: UriSerde,
+
+    /// The warehouse of the table, for catalogs serving several warehouses.
+    #[configurable(metadata(docs::examples = "s3://warehouse"))]
+    warehouse: Option<String>,
+
+    /// The bearer token requests to the catalog are authenticated with.
+    #[configurable(metadata(docs::examples = "${ICEBERG_CATALOG_TOKEN}"))]
+    token: Option<SensitiveString>,
+}
+
+impl RestCatalogConfig {
+    pub(super) fn build(
+        &self,
+        namespace: &str,
+        table: &str,
+        proxy: &ProxyConfig,
+        tls: &Option<TlsConfig>,
+    ) -> crate::Result<RestCatalog> {
+        let tls_settings = TlsSettings::from_options(tls)?;
+        let client = HttpClient::new(tls_settings, proxy)?;
+
+        Ok(RestCatalog {
+            client,
+            base: self
+                .uri
+                .with_default_parts()
+                .uri
+                .to_string()
+                .trim_end_matches('/')
+                .to_string(),
+            warehouse: self.warehouse.clone(),
+            token: self.token.clone(),
+            namespace: namespace_path(namespace),
+            table: utf8_percent_encode(table, NON_ALPHANUMERIC).to_string(),
+            prefix: OnceCell::new(),
+        })
+    }
+}
+
+/// Encodes namespaces for the paths of the catalog, with the levels of nested namespaces, such
+/// as `analytics.logs`, separated by the unit separator character.
+fn namespace_path(namespace: &str) -> String {
+    namespace
+        .split('.')
+        .map(|level| utf8_percent_encode(level, NON_ALPHANUMERIC).to_string())
+        .collect::<Vec<_>>()
+        .join("%1F")
+}
+
+#[derive(Deserialize)]
+struct ConfigResponse {
+    #[serde(default)]
+    defaults: JsonValue,
+    #[serde(default)]
+    overrides: JsonValue,
+}
+
+#[derive(Deserialize)]
+struct LoadTableResponse {
+    #[serde(rename = "metadata-location")]
+    metadata_location: Option<String>,
+    metadata: JsonValue,
+}
+
+pub struct RestCatalog {
+    client: HttpClient,
+    base: String,
+    warehouse: Option<String>,
+    token: Option<SensitiveString>,
+    namespace: String,
+    table: String,
+    /// The prefix of the paths of the catalog, fetched from its configuration when first needed.
+    prefix: OnceCell<String>,
+}
+
+impl RestCatalog {
+    async fn send(&self, request: Request<Body>) -> Result<(StatusCode, Bytes), CatalogError> {
+        let mut request = request;
+        if let Some(token) = &self.token {
+            request.headers_mut().insert(
+                http::header::AUTHORIZATION,
+                format!("Bearer {}", token.inner())
+                    .parse()
+                    .expect("tokens should be valid header values"),
+            );
+        }
+
+        let response = self.client.send(request).await.context(RequestSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadResponseSnafu)?;
+        Ok((status, body))
+    }
+
+    async fn table_uri(&self) -> Result<String, CatalogError> {
+        let prefix = self
+            .prefix
+            .get_or_try_init(|| async {
+                let mut uri = format!("{}/v1/config", self.base);
+                if let Some(warehouse) = &self.warehouse {
+                    uri.push_str("?warehouse=");
+                    uri.extend(utf8_percent_encode(warehouse, NON_ALPHANUMERIC));
+                }
+                let request = Request::get(uri)
+                    .body(Body::empty())
+                    .context(BuildRequestSnafu)?;
+
+                let (status, body) = self.send(request).await?;
+                if status != StatusCode::OK {
+                    return Err(unexpected_status(status, &body));
+                }
+                let config: ConfigResponse =
+                    serde_json::from_slice(&body).context(InvalidResponseSnafu)?;
+                let prefix = config.overrides["prefix"]
+                    .as_str()
+                    .or_else(|| config.defaults["prefix"].as_str())
+                    .map(|prefix| format!("{}/", prefix.trim_matches('/')))
+                    .unwrap_or_default();
+                Ok(prefix)
+            })
+            .await?;
+
+        Ok(format!(
+            "{}/v1/{}namespaces/{}/tables/{}",
+            self.base, prefix, self.namespace, self.table
+        ))
+    }
+
+    pub async fn load_table(&self) -> Result<LoadedTable, CatalogError> {
+        let request = Request::get(self.table_uri().await?)
+            .body(Body::empty())
+            .context(BuildRequestSnafu)?;
+
+        let (status, body) = self.send(request).await?;
+        if status != StatusCode::OK {
+            return Err(unexpected_status(status, &body));
+        }
+        let response: LoadTableResponse =
+            serde_json::from_slice(&body).context(InvalidResponseSnafu)?;
+        Ok(LoadedTable {
+            metadata: TableMetadata::parse(response.metadata).context(TableSnafu)?,
+            metadata_location: response.metadata_location,
+        })
+    }
+
+    pub async fn commit(
+        &self,
+        base: &LoadedTable,
+        snapshot: &NewSnapshot,
+    ) -> Result<(), CatalogError> {
+        let body = json!({
+            "requirements": [{
+                "type": "assert-ref-snapshot-id",
+                "ref": "main",
+                "snapshot-id": snapshot.parent_snapshot_id,
+            }],
+            "updates": [
+                {
+                    "action": "add-snapshot",
+                    "snapshot": snapshot.to_json(base.metadata.format_version),
+                },
+                {
+                    "action": "set-snapshot-ref",
+                    "ref-name": "main",
+                    "type": "branch",
+                    "snapshot-id": snapshot.snapshot_id,
+                },
+            ],
+        });
+        let request = Request::post(self.table_uri().await?)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string()))
+            .context(BuildRequestSnafu)?;
+
+        match self.send(request).await? {
+            (StatusCode::OK, _) => Ok(()),
+            (StatusCode::CONFLICT, _) => Err(CatalogError::Conflict),
+            (status, body) => Err(unexpected_status(status, &body)),
+        }
+    }
+}
+
+fn unexpected_status(status: StatusCode, body: &[u8]) -> CatalogError {
+    CatalogError::UnexpectedStatus {
+        status,
+        body: String::from_utf8_lossy(body).into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_namespaces() {
+        assert_eq!(namespace_path("logs"), "logs");
+        assert_eq!(
+            namespace_path("analytics.web logs"),
+            "analytics%1Fweb%20logs"
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::{collections::HashMap, sync::Arc};
+
+use futures::FutureExt;
+use vector_config::configurable_component;
+
+use super::{
+    catalog::CatalogConfig, data_file::ParquetCompression, sink::IcebergSink, storage::Storage,
+};
+use crate::{
+    aws::{create_client, AwsAuthentication, RegionOrEndpoint},
+    codecs::Transformer,
+    common::s3::S3ClientBuilder,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{
+        util::{BatchConfig, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+    tls::TlsConfig,
+};
+
+/// Configuration for the `iceberg` sink.
+#[configurable_component(sink("iceberg"))]
+#[derive(Clone, Debug)]
+pub struct IcebergConfig {
+    #[configurable(derived)]
+    catalog: CatalogConfig,
+
+    /// The namespace of the table.
+    ///
+    /// The levels of nested namespaces are separated by dots. With the `glue` catalog, this is the
+    /// name of the database of the table.
+    #[configurable(metadata(docs::examples = "logs"))]
+    #[configurable(metadata(docs::examples = "analytics.logs"))]
+    namespace: String,
+
+    /// The name of the table.
+    ///
+    /// The table must already exist. Its schema and partition spec are loaded when the sink
+    /// starts, and the sink must be reloaded to write with new ones.
+    #[configurable(metadata(docs::examples = "events"))]
+    table: String,
+
+    /// Templates rendering the values of columns, by the names of the columns.
+    ///
+    /// By default, columns are written with the values of the top-level fields of events with
+    /// the same names. Rendered values are parsed as values of the types of their columns, which
+    /// makes templates suited to deriving the source columns of partition fields from events.
+    #[serde(default)]
+    column_templates: HashMap<String, Template>,
+
+    /// The [AWS region][aws_region] of the storage of the table, and of the `glue` catalog.
+    ///
+    /// The `endpoint` option is only used for the storage of the table.
+    ///
+    /// [aws_region]: https://docs.aws.amazon.com/general/latest/gr/rande.html#regional-endpoints
+    #[serde(flatten)]
+    region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    auth: AwsAuthentication,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    compression: ParquetCompression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    batch: BatchConfig<IcebergDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    encoding: Transformer,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// Each batch is written to a data file and committed as a snapshot of the table, so batches
+/// are large and infrequent by default, to keep the number of files and snapshots small.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IcebergDefaultBatchSettings;
+
+impl SinkBatchSettings for IcebergDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(100_000_000);
+    const TIMEOUT_SECS: f64 = 300.0;
+}
+
+impl GenerateConfig for IcebergConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"catalog.type = "rest"
+            catalog.uri = "http://localhost:8181"
+            namespace = "logs"
+            table = "events"
+            region = "us-east-1""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for IcebergConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let client = create_client::<S3ClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &cx.proxy,
+            &self.tls,
+            true,
+        )
+        .await?;
+        let storage = Storage::new(client);
+        let catalog = Arc::new(
+            self.catalog
+                .build(
+                    &self.namespace,
+                    &self.table,
+                    storage.clone(),
+                    &self.region,
+                    &self.auth,
+                    &cx.proxy,
+                    &self.tls,
+                )
+                .await?,
+        );
+
+        let healthcheck = {
+            let catalog = Arc::clone(&catalog);
+            async move {
+                catalog.load_table().await?;
+                Ok(())
+            }
+            .boxed()
+        };
+
+        let sink = IcebergSink {
+            catalog,
+            storage,
+            column_templates: self.column_templates.clone(),
+            transformer: self.encoding.clone(),
+            compression: self.compression,
+            batch_settings: self.batch.into_batcher_settings()?,
+            request: self.request.unwrap_with(&TowerRequestConfig::default()),
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<IcebergConfig>();
+    }
+
+    #[test]
+    fn parses_glue_catalog() {
+        let config: IcebergConfig = toml::from_str(
+            r#"catalog.type = "glue"
+            catalog.catalog_id = "123456789012"
+            namespace = "logs"
+            table = "events"
+            region = "us-east-1"
+            column_templates.date = "%Y-%m-%d""#,
+        )
+        .unwrap();
+
+        assert!(matches!(config.catalog, CatalogConfig::Glue(_)));
+        assert!(config.column_templates.contains_key("date"));
+    }
+}
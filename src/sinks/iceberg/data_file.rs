@@ -0,0 +1,266 @@
+//! Writing rows to Parquet data files.
+
+use std::sync::Arc;
+
+use derivative::Derivative;
+use parquet::{
+    basic::{Compression, LogicalType, Repetition, TimeUnit, Type as PhysicalType},
+    data_type::{
+        BoolType, ByteArray, ByteArrayType, DataType, DoubleType, FloatType, Int32Type, Int64Type,
+    },
+    errors::ParquetError,
+    file::{
+        properties::WriterProperties,
+        writer::{SerializedColumnWriter, SerializedFileWriter},
+    },
+    format::MicroSeconds,
+    schema::types::Type,
+};
+use vector_config::configurable_component;
+
+use super::{
+    row::Datum,
+    table::{Column, ColumnType},
+};
+
+/// Compression codec applied to the pages of data files.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetCompression {
+    /// No compression.
+    None,
+
+    /// [Snappy][snappy] compression.
+    ///
+    /// [snappy]: https://github.com/google/snappy
+    #[derivative(Default)]
+    Snappy,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+}
+
+impl From<ParquetCompression> for Compression {
+    fn from(compression: ParquetCompression) -> Self {
+        match compression {
+            ParquetCompression::None => Self::UNCOMPRESSED,
+            ParquetCompression::Snappy => Self::SNAPPY,
+            ParquetCompression::Gzip => Self::GZIP,
+        }
+    }
+}
+
+/// Returns the Parquet file holding `rows`, whose values are in the order of `columns`.
+///
+/// Columns events can't be written to are left out, with Iceberg reading them as null.
+pub fn write_parquet(
+    columns: &[Column],
+    rows: &[Vec<Option<Datum>>],
+    compression: ParquetCompression,
+) -> Result<Vec<u8>, ParquetError> {
+    let mut fields = columns
+        .iter()
+        .filter_map(|column| column.column_type.map(|column_type| (column, column_type)))
+        .map(|(column, column_type)| parquet_type(column, column_type).map(Arc::new))
+        .collect::<Result<Vec<_>, _>>()?;
+    let schema = Type::group_type_builder("table")
+        .with_fields(&mut fields)
+        .build()?;
+    let properties = WriterProperties::builder()
+        .set_compression(compression.into())
+        .build();
+
+    let mut buffer = Vec::new();
+    let mut writer =
+        SerializedFileWriter::new(&mut buffer, Arc::new(schema), Arc::new(properties))?;
+    let mut row_group = writer.next_row_group()?;
+    for (index, column) in columns.iter().enumerate() {
+        let column_type = match column.column_type {
+            Some(column_type) => column_type,
+            None => continue,
+        };
+        let mut column_writer = row_group
+            .next_column()?
+            .expect("columns should match the schema");
+        let values = rows.iter().map(|row| row[index].as_ref());
+        match column_type {
+            ColumnType::Boolean => {
+                write_column::<BoolType, _>(&mut column_writer, column, values, |datum| match datum
+                {
+                    Datum::Boolean(boolean) => Some(*boolean),
+                    _ => None,
+                })
+            }
+            ColumnType::Int | ColumnType::Date => write_column::<Int32Type, _>(
+                &mut column_writer,
+                column,
+                values,
+                |datum| match datum {
+                    Datum::Int(int) => Some(*int),
+                    _ => None,
+                },
+            ),
+            ColumnType::Long | ColumnType::Timestamp | ColumnType::Timestamptz => {
+                write_column::<Int64Type, _>(
+                    &mut column_writer,
+                    column,
+                    values,
+                    |datum| match datum {
+                        Datum::Long(long) => Some(*long),
+                        _ => None,
+                    },
+                )
+            }
+            ColumnType::Float => write_column::<FloatType, _>(
+                &mut column_writer,
+                column,
+                values,
+                |datum| match datum {
+                    Datum::Float(float) => Some(*float),
+                    _ => None,
+                },
+            ),
+            ColumnType::Double => {
+                write_column::<DoubleType, _>(&mut column_writer, column, values, |datum| {
+                    match datum {
+                        Datum::Double(double) => Some(*double),
+                        _ => None,
+                    }
+                })
+            }
+            ColumnType::String | ColumnType::Binary => {
+                write_column::<ByteArrayType, _>(&mut column_writer, column, values, |datum| {
+                    match datum {
+                        Datum::String(string) => Some(ByteArray::from(string.as_bytes().to_vec())),
+                        Datum::Binary(bytes) => Some(ByteArray::from(bytes.to_vec())),
+                        _ => None,
+                    }
+                })
+            }
+        }?;
+        column_writer.close()?;
+    }
+    row_group.close()?;
+    writer.close()?;
+
+    Ok(buffer)
+}
+
+/// The Parquet type of a column, annotated with its Iceberg field id.
+fn parquet_type(column: &Column, column_type: ColumnType) -> Result<Type, ParquetError> {
+    let (physical_type, logical_type) = match column_type {
+        ColumnType::Boolean => (PhysicalType::BOOLEAN, None),
+        ColumnType::Int => (PhysicalType::INT32, None),
+        ColumnType::Long => (PhysicalType::INT64, None),
+        ColumnType::Float => (PhysicalType::FLOAT, None),
+        ColumnType::Double => (PhysicalType::DOUBLE, None),
+        ColumnType::Date => (PhysicalType::INT32, Some(LogicalType::Date)),
+        ColumnType::Timestamp | ColumnType::Timestamptz => (
+            PhysicalType::INT64,
+            Some(LogicalType::Timestamp {
+                is_adjusted_to_u_t_c: column_type == ColumnType::Timestamptz,
+                unit: TimeUnit::MICROS(MicroSeconds {}),
+            }),
+        ),
+        ColumnType::String => (PhysicalType::BYTE_ARRAY, Some(LogicalType::String)),
+        ColumnType::Binary => (PhysicalType::BYTE_ARRAY, None),
+    };
+
+    Type::primitive_type_builder(&column.name, physical_type)
+        .with_repetition(if column.required {
+            Repetition::REQUIRED
+        } else {
+            Repetition::OPTIONAL
+        })
+        .with_logical_type(logical_type)
+        .with_id(column.id)
+        .build()
+}
+
+fn write_column<'a, T: DataType, F>(
+    writer: &mut SerializedColumnWriter<'_>,
+    column: &Column,
+    values: impl Iterator<Item = Option<&'a Datum>>,
+    convert: F,
+) -> Result<(), ParquetError>
+where
+    F: Fn(&Datum) -> Option<T::T>,
+{
+    let mut definition_levels = Vec::new();
+    let mut present = Vec::new();
+    for value in values {
+        match value.and_then(&convert) {
+            Some(value) => {
+                definition_levels.push(1);
+                present.push(value);
+            }
+            None => definition_levels.push(0),
+        }
+    }
+
+    let definition_levels = (!column.required).then_some(&definition_levels[..]);
+    writer
+        .typed::<T>()
+        .write_batch(&present, definition_levels, None)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::RowAccessor,
+    };
+
+    use super::*;
+
+    fn column(id: i32, name: &str, required: bool, column_type: Option<ColumnType>) -> Column {
+        Column {
+            id,
+            name: name.into(),
+            required,
+            column_type,
+        }
+    }
+
+    #[test]
+    fn writes_rows() {
+        let columns = vec![
+            column(1, "timestamp", true, Some(ColumnType::Timestamptz)),
+            column(2, "tags", false, None),
+            column(3, "message", false, Some(ColumnType::String)),
+            column(4, "status", false, Some(ColumnType::Int)),
+        ];
+        let rows = vec![
+            vec![
+                Some(Datum::Long(1673353800123456)),
+                None,
+                Some(Datum::String("hello".into())),
+                Some(Datum::Int(200)),
+            ],
+            vec![Some(Datum::Long(1673353800123457)), None, None, None],
+        ];
+
+        let file = write_parquet(&columns, &rows, ParquetCompression::Snappy).unwrap();
+
+        let reader = SerializedFileReader::new(Bytes::from(file)).unwrap();
+        let schema = reader.metadata().file_metadata().schema_descr();
+        let names: Vec<_> = schema
+            .columns()
+            .iter()
+            .map(|column| column.name())
+            .collect();
+        assert_eq!(names, vec!["timestamp", "message", "status"]);
+
+        let rows: Vec<_> = reader.get_row_iter(None).unwrap().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get_string(1).unwrap(), "hello");
+        assert_eq!(rows[0].get_int(2).unwrap(), 200);
+        assert!(rows[1].get_string(1).is_err());
+    }
+}
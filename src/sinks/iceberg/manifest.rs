@@ -0,0 +1,532 @@
+//! Writing the manifests of new data files, and the manifest lists of snapshots.
+
+use avro_rs::types::Value;
+use serde_json::{json, Value as JsonValue};
+use snafu::{ResultExt, Snafu};
+
+use super::{
+    avro::{write_container, AvroValue},
+    row::{Datum, PartitionKey},
+    table::{ColumnType, TableMetadata},
+};
+
+/// The size Iceberg records for the blocks of data files, which is required by format version 1.
+const BLOCK_SIZE_IN_BYTES: i64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Snafu)]
+pub enum ManifestError {
+    #[snafu(display("Invalid manifest list: {}", source))]
+    InvalidManifestList { source: avro_rs::Error },
+    #[snafu(display("Manifest list entry is missing field {:?}", field))]
+    MissingField { field: &'static str },
+}
+
+/// A Parquet data file written to the storage of a table.
+#[derive(Clone, Debug)]
+pub struct DataFile {
+    pub path: String,
+    pub partition: PartitionKey,
+    pub record_count: i64,
+    pub file_size_in_bytes: i64,
+}
+
+/// Returns the manifest adding `file` to the table in the snapshot `snapshot_id`.
+pub fn write_manifest(table: &TableMetadata, snapshot_id: i64, file: &DataFile) -> Vec<u8> {
+    let v2 = table.format_version > 1;
+
+    let partition_fields: Vec<JsonValue> = table
+        .partition_fields
+        .iter()
+        .map(|field| {
+            let source_type = table.columns[field.source]
+                .column_type
+                .expect("partition source columns should have supported types");
+            json!({
+                "name": field.name,
+                "type": ["null", avro_type(field.transform.result_type(source_type))],
+                "default": null,
+                "field-id": field.field_id,
+            })
+        })
+        .collect();
+
+    let mut data_file_fields = vec![];
+    if v2 {
+        data_file_fields.push(required("content", json!("int"), 134));
+    }
+    data_file_fields.extend([
+        required("file_path", json!("string"), 100),
+        required("file_format", json!("string"), 101),
+        json!({
+            "name": "partition",
+            "type": { "type": "record", "name": "r102", "fields": partition_fields },
+            "field-id": 102,
+        }),
+        required("record_count", json!("long"), 103),
+        required("file_size_in_bytes", json!("long"), 104),
+    ]);
+    if !v2 {
+        data_file_fields.push(required("block_size_in_bytes", json!("long"), 105));
+    }
+
+    let mut entry_fields = vec![required("status", json!("int"), 0)];
+    if v2 {
+        entry_fields.extend([
+            optional("snapshot_id", json!("long"), 1),
+            optional("sequence_number", json!("long"), 3),
+            optional("file_sequence_number", json!("long"), 4),
+        ]);
+    } else {
+        entry_fields.push(required("snapshot_id", json!("long"), 1));
+    }
+    entry_fields.push(json!({
+        "name": "data_file",
+        "type": { "type": "record", "name": "r2", "fields": data_file_fields },
+        "field-id": 2,
+    }));
+    let schema = json!({ "type": "record", "name": "manifest_entry", "fields": entry_fields });
+
+    let partition = AvroValue::Record(
+        file.partition
+            .iter()
+            .map(|value| AvroValue::optional(value.as_ref().map(avro_value)))
+            .collect(),
+    );
+    let mut data_file = vec![];
+    if v2 {
+        // Data, rather than deletes.
+        data_file.push(AvroValue::Int(0));
+    }
+    data_file.extend([
+        AvroValue::String(file.path.clone()),
+        AvroValue::String("PARQUET".into()),
+        partition,
+        AvroValue::Long(file.record_count),
+        AvroValue::Long(file.file_size_in_bytes),
+    ]);
+    if !v2 {
+        data_file.push(AvroValue::Long(BLOCK_SIZE_IN_BYTES));
+    }
+
+    // The entry is added, with its sequence numbers inherited from the manifest list.
+    let mut entry = vec![AvroValue::Int(1)];
+    if v2 {
+        entry.extend([
+            AvroValue::optional(Some(AvroValue::Long(snapshot_id))),
+            AvroValue::optional(None),
+            AvroValue::optional(None),
+        ]);
+    } else {
+        entry.push(AvroValue::Long(snapshot_id));
+    }
+    entry.push(AvroValue::Record(data_file));
+
+    let mut metadata = vec![
+        ("schema", table.schema_json.clone()),
+        ("schema-id", table.schema_id.to_string()),
+        ("partition-spec", table.spec_json.clone()),
+        ("partition-spec-id", table.spec_id.to_string()),
+        ("format-version", table.format_version.to_string()),
+    ];
+    if v2 {
+        metadata.push(("content", "data".into()));
+    }
+
+    write_container(&schema, &metadata, &[AvroValue::Record(entry)])
+}
+
+fn required(name: &str, field_type: JsonValue, field_id: i32) -> JsonValue {
+    json!({ "name": name, "type": field_type, "field-id": field_id })
+}
+
+fn optional(name: &str, field_type: JsonValue, field_id: i32) -> JsonValue {
+    json!({ "name": name, "type": ["null", field_type], "default": null, "field-id": field_id })
+}
+
+fn avro_type(column_type: ColumnType) -> JsonValue {
+    match column_type {
+        ColumnType::Boolean => json!("boolean"),
+        ColumnType::Int => json!("int"),
+        ColumnType::Long => json!("long"),
+        ColumnType::Float => json!("float"),
+        ColumnType::Double => json!("double"),
+        ColumnType::Date => json!({ "type": "int", "logicalType": "date" }),
+        ColumnType::Timestamp => {
+            json!({ "type": "long", "logicalType": "timestamp-micros", "adjust-to-utc": false })
+        }
+        ColumnType::Timestamptz => {
+            json!({ "type": "long", "logicalType": "timestamp-micros", "adjust-to-utc": true })
+        }
+        ColumnType::String => json!("string"),
+        ColumnType::Binary => json!("bytes"),
+    }
+}
+
+fn avro_value(datum: &Datum) -> AvroValue {
+    match datum {
+        Datum::Boolean(boolean) => AvroValue::Boolean(*boolean),
+        Datum::Int(int) => AvroValue::Int(*int),
+        Datum::Long(long) => AvroValue::Long(*long),
+        Datum::Float(float) => AvroValue::Float(*float),
+        Datum::Double(double) => AvroValue::Double(*double),
+        Datum::String(string) => AvroValue::String(string.clone()),
+        Datum::Binary(bytes) => AvroValue::Bytes(bytes.to_vec()),
+    }
+}
+
+/// The summary of the values of a partition field in the data files of a manifest.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FieldSummary {
+    pub contains_null: bool,
+    pub contains_nan: Option<bool>,
+    pub lower_bound: Option<Vec<u8>>,
+    pub upper_bound: Option<Vec<u8>>,
+}
+
+/// An entry of a manifest list.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ManifestFile {
+    pub path: String,
+    pub length: i64,
+    pub partition_spec_id: i32,
+    pub content: i32,
+    pub sequence_number: i64,
+    pub min_sequence_number: i64,
+    pub added_snapshot_id: i64,
+    pub added_files_count: Option<i32>,
+    pub existing_files_count: Option<i32>,
+    pub deleted_files_count: Option<i32>,
+    pub added_rows_count: Option<i64>,
+    pub existing_rows_count: Option<i64>,
+    pub deleted_rows_count: Option<i64>,
+    pub partitions: Option<Vec<FieldSummary>>,
+    pub key_metadata: Option<Vec<u8>>,
+}
+
+/// Returns the entries of the manifest list `file`, so that they can be carried over to the
+/// manifest lists of later snapshots.
+pub fn read_manifest_list(file: &[u8]) -> Result<Vec<ManifestFile>, ManifestError> {
+    let reader = avro_rs::Reader::new(file).context(InvalidManifestListSnafu)?;
+
+    let mut manifests = Vec::new();
+    for value in reader {
+        let value = value.context(InvalidManifestListSnafu)?;
+        let fields = match &value {
+            Value::Record(fields) => fields,
+            _ => {
+                return Err(ManifestError::MissingField {
+                    field: "manifest_path",
+                })
+            }
+        };
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| match value {
+                    Value::Union(value) => value.as_ref(),
+                    value => value,
+                })
+                .filter(|value| **value != Value::Null)
+        };
+        let int = |name: &str| match field(name) {
+            Some(Value::Int(int)) => Some(*int),
+            _ => None,
+        };
+        let long = |name: &str| match field(name) {
+            Some(Value::Long(long)) => Some(*long),
+            Some(Value::Int(int)) => Some(i64::from(*int)),
+            _ => None,
+        };
+
+        let path = match field("manifest_path") {
+            Some(Value::String(path)) => path.clone(),
+            _ => {
+                return Err(ManifestError::MissingField {
+                    field: "manifest_path",
+                })
+            }
+        };
+        let partitions = match field("partitions") {
+            Some(Value::Array(summaries)) => {
+                Some(summaries.iter().map(read_field_summary).collect())
+            }
+            _ => None,
+        };
+        manifests.push(ManifestFile {
+            path,
+            length: long("manifest_length").ok_or(ManifestError::MissingField {
+                field: "manifest_length",
+            })?,
+            partition_spec_id: int("partition_spec_id").ok_or(ManifestError::MissingField {
+                field: "partition_spec_id",
+            })?,
+            content: int("content").unwrap_or_default(),
+            sequence_number: long("sequence_number").unwrap_or_default(),
+            min_sequence_number: long("min_sequence_number").unwrap_or_default(),
+            added_snapshot_id: long("added_snapshot_id").ok_or(ManifestError::MissingField {
+                field: "added_snapshot_id",
+            })?,
+            // Format version 1 names the counts of files after data files.
+            added_files_count: int("added_files_count").or_else(|| int("added_data_files_count")),
+            existing_files_count: int("existing_files_count")
+                .or_else(|| int("existing_data_files_count")),
+            deleted_files_count: int("deleted_files_count")
+                .or_else(|| int("deleted_data_files_count")),
+            added_rows_count: long("added_rows_count"),
+            existing_rows_count: long("existing_rows_count"),
+            deleted_rows_count: long("deleted_rows_count"),
+            partitions,
+            key_metadata: match field("key_metadata") {
+                Some(Value::Bytes(bytes)) => Some(bytes.clone()),
+                _ => None,
+            },
+        });
+    }
+    Ok(manifests)
+}
+
+fn read_field_summary(summary: &Value) -> FieldSummary {
+    let fields = match summary {
+        Value::Record(fields) => fields.as_slice(),
+        _ => &[],
+    };
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| match value {
+                Value::Union(value) => value.as_ref(),
+                value => value,
+            })
+    };
+    let bytes = |name: &str| match field(name) {
+        Some(Value::Bytes(bytes)) => Some(bytes.clone()),
+        _ => None,
+    };
+    FieldSummary {
+        contains_null: matches!(field("contains_null"), Some(Value::Boolean(true))),
+        contains_nan: match field("contains_nan") {
+            Some(Value::Boolean(contains_nan)) => Some(*contains_nan),
+            _ => None,
+        },
+        lower_bound: bytes("lower_bound"),
+        upper_bound: bytes("upper_bound"),
+    }
+}
+
+/// Returns the manifest list of a snapshot holding `manifests`.
+pub fn write_manifest_list(
+    table: &TableMetadata,
+    snapshot_id: i64,
+    parent_snapshot_id: Option<i64>,
+    sequence_number: i64,
+    manifests: &[ManifestFile],
+) -> Vec<u8> {
+    let v2 = table.format_version > 1;
+    // Counts are required by format version 2, and optional before it.
+    let count = |count_type: &str| -> JsonValue {
+        if v2 {
+            json!(count_type)
+        } else {
+            json!(["null", count_type])
+        }
+    };
+
+    let field_summary = json!({
+        "type": "record",
+        "name": "r508",
+        "fields": [
+            required("contains_null", json!("boolean"), 509),
+            optional("contains_nan", json!("boolean"), 518),
+            optional("lower_bound", json!("bytes"), 510),
+            optional("upper_bound", json!("bytes"), 511),
+        ],
+    });
+    let mut fields = vec![
+        required("manifest_path", json!("string"), 500),
+        required("manifest_length", json!("long"), 501),
+        required("partition_spec_id", json!("int"), 502),
+    ];
+    if v2 {
+        fields.extend([
+            required("content", json!("int"), 517),
+            required("sequence_number", json!("long"), 515),
+            required("min_sequence_number", json!("long"), 516),
+        ]);
+    }
+    fields.extend([
+        required("added_snapshot_id", json!("long"), 503),
+        required("added_files_count", count("int"), 504),
+        required("existing_files_count", count("int"), 505),
+        required("deleted_files_count", count("int"), 506),
+        required("added_rows_count", count("long"), 512),
+        required("existing_rows_count", count("long"), 513),
+        required("deleted_rows_count", count("long"), 514),
+        json!({
+            "name": "partitions",
+            "type": ["null", { "type": "array", "items": field_summary, "element-id": 508 }],
+            "default": null,
+            "field-id": 507,
+        }),
+        optional("key_metadata", json!("bytes"), 519),
+    ]);
+    let schema = json!({ "type": "record", "name": "manifest_file", "fields": fields });
+
+    let records: Vec<AvroValue> = manifests
+        .iter()
+        .map(|manifest| {
+            let int_count = |count: Option<i32>| {
+                if v2 {
+                    AvroValue::Int(count.unwrap_or_default())
+                } else {
+                    AvroValue::optional(count.map(AvroValue::Int))
+                }
+            };
+            let long_count = |count: Option<i64>| {
+                if v2 {
+                    AvroValue::Long(count.unwrap_or_default())
+                } else {
+                    AvroValue::optional(count.map(AvroValue::Long))
+                }
+            };
+
+            let mut record = vec![
+                AvroValue::String(manifest.path.clone()),
+                AvroValue::Long(manifest.length),
+                AvroValue::Int(manifest.partition_spec_id),
+            ];
+            if v2 {
+                record.extend([
+                    AvroValue::Int(manifest.content),
+                    AvroValue::Long(manifest.sequence_number),
+                    AvroValue::Long(manifest.min_sequence_number),
+                ]);
+            }
+            record.extend([
+                AvroValue::Long(manifest.added_snapshot_id),
+                int_count(manifest.added_files_count),
+                int_count(manifest.existing_files_count),
+                int_count(manifest.deleted_files_count),
+                long_count(manifest.added_rows_count),
+                long_count(manifest.existing_rows_count),
+                long_count(manifest.deleted_rows_count),
+                AvroValue::optional(manifest.partitions.as_ref().map(|summaries| {
+                    AvroValue::Array(
+                        summaries
+                            .iter()
+                            .map(|summary| {
+                                AvroValue::Record(vec![
+                                    AvroValue::Boolean(summary.contains_null),
+                                    AvroValue::optional(
+                                        summary.contains_nan.map(AvroValue::Boolean),
+                                    ),
+                                    AvroValue::optional(
+                                        summary.lower_bound.clone().map(AvroValue::Bytes),
+                                    ),
+                                    AvroValue::optional(
+                                        summary.upper_bound.clone().map(AvroValue::Bytes),
+                                    ),
+                                ])
+                            })
+                            .collect(),
+                    )
+                })),
+                AvroValue::optional(manifest.key_metadata.clone().map(AvroValue::Bytes)),
+            ]);
+            AvroValue::Record(record)
+        })
+        .collect();
+
+    let mut metadata = vec![
+        ("snapshot-id", snapshot_id.to_string()),
+        (
+            "parent-snapshot-id",
+            parent_snapshot_id.map_or_else(|| "null".into(), |id| id.to_string()),
+        ),
+        ("format-version", table.format_version.to_string()),
+    ];
+    if v2 {
+        metadata.push(("sequence-number", sequence_number.to_string()));
+    }
+
+    write_container(&schema, &metadata, &records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::table::tests::metadata, *};
+
+    #[test]
+    fn writes_readable_manifests() {
+        let table = TableMetadata::parse(metadata()).unwrap();
+        let file = DataFile {
+            path: "s3://warehouse/logs/events/data/a.parquet".into(),
+            partition: vec![Some(Datum::Int(19367)), None],
+            record_count: 2,
+            file_size_in_bytes: 1024,
+        };
+
+        let manifest = write_manifest(&table, 8, &file);
+        let entries: Vec<Value> = avro_rs::Reader::new(&manifest[..])
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(entries.len(), 1);
+        match &entries[0] {
+            Value::Record(fields) => {
+                assert_eq!(fields[0], ("status".into(), Value::Int(1)));
+                assert_eq!(
+                    fields[1],
+                    ("snapshot_id".into(), Value::Union(Box::new(Value::Long(8))))
+                );
+            }
+            value => panic!("unexpected manifest entry {:?}", value),
+        }
+    }
+
+    #[test]
+    fn carries_over_manifest_lists() {
+        let table = TableMetadata::parse(metadata()).unwrap();
+        let manifests = vec![
+            ManifestFile {
+                path: "s3://warehouse/logs/events/metadata/a-m0.avro".into(),
+                length: 4096,
+                sequence_number: 3,
+                min_sequence_number: 3,
+                added_snapshot_id: 7,
+                added_files_count: Some(1),
+                added_rows_count: Some(10),
+                partitions: Some(vec![FieldSummary {
+                    contains_null: false,
+                    contains_nan: None,
+                    lower_bound: Some(vec![1, 2, 3, 4]),
+                    upper_bound: Some(vec![1, 2, 3, 5]),
+                }]),
+                ..Default::default()
+            },
+            ManifestFile {
+                path: "s3://warehouse/logs/events/metadata/b-m0.avro".into(),
+                length: 2048,
+                sequence_number: 4,
+                min_sequence_number: 4,
+                added_snapshot_id: 8,
+                added_files_count: Some(1),
+                added_rows_count: Some(2),
+                ..Default::default()
+            },
+        ];
+
+        let list = write_manifest_list(&table, 8, Some(7), 4, &manifests);
+        let mut read = read_manifest_list(&list).unwrap();
+        // Counts are required by format version 2.
+        for manifest in &mut read {
+            assert_eq!(manifest.existing_files_count, Some(0));
+            manifest.existing_files_count = None;
+            manifest.deleted_files_count = None;
+            manifest.existing_rows_count = None;
+            manifest.deleted_rows_count = None;
+        }
+        assert_eq!(read, manifests);
+    }
+}
@@ -0,0 +1,16 @@
+//! The `iceberg` sink.
+//!
+//! Batches of events are written to Parquet data files in the storage of an existing Apache
+//! Iceberg table, and appended to it in snapshots committed to its catalog.
+
+mod avro;
+mod catalog;
+mod config;
+mod data_file;
+mod manifest;
+mod row;
+mod sink;
+mod storage;
+mod table;
+
+pub use self::config::IcebergConfig;
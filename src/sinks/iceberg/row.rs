@@ -0,0 +1,347 @@
+//! Converting events to the rows of tables, and rows to the partitions they belong to.
+
+use std::hash::{Hash, Hasher};
+
+use bytes::Bytes;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Timelike};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use super::table::{ColumnType, PartitionField, TableMetadata, Transform};
+use crate::event::Value;
+
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+const MICROS_PER_HOUR: i64 = 3_600_000_000;
+
+/// The characters left as they are in the partition values of data file paths.
+const PARTITION_VALUE: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.');
+
+/// The value of a column, stored the way Iceberg stores values of its type.
+#[derive(Clone, Debug)]
+pub enum Datum {
+    Boolean(bool),
+    /// The values of `int` and `date` columns.
+    Int(i32),
+    /// The values of `long`, `timestamp` and `timestamptz` columns.
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    Binary(Bytes),
+}
+
+// Floats are compared by their bits, so that rows are grouped into partitions deterministically.
+impl PartialEq for Datum {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Boolean(a), Self::Boolean(b)) => a == b,
+            (Self::Int(a), Self::Int(b)) => a == b,
+            (Self::Long(a), Self::Long(b)) => a == b,
+            (Self::Float(a), Self::Float(b)) => a.to_bits() == b.to_bits(),
+            (Self::Double(a), Self::Double(b)) => a.to_bits() == b.to_bits(),
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::Binary(a), Self::Binary(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Datum {}
+
+impl Hash for Datum {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::Boolean(boolean) => boolean.hash(state),
+            Self::Int(int) => int.hash(state),
+            Self::Long(long) => long.hash(state),
+            Self::Float(float) => float.to_bits().hash(state),
+            Self::Double(double) => double.to_bits().hash(state),
+            Self::String(string) => string.hash(state),
+            Self::Binary(bytes) => bytes.hash(state),
+        }
+    }
+}
+
+impl Datum {
+    /// Converts `value` to a value of a column of type `column_type`, if it can be.
+    ///
+    /// Strings are parsed as values of the other types, so that the rendered values of templates
+    /// can be written to any column.
+    pub fn convert(value: &Value, column_type: ColumnType) -> Option<Self> {
+        if let Value::Bytes(bytes) = value {
+            let string = String::from_utf8_lossy(bytes);
+            return match column_type {
+                ColumnType::Boolean => string.parse().ok().map(Self::Boolean),
+                ColumnType::Int => string.parse().ok().map(Self::Int),
+                ColumnType::Long => string.parse().ok().map(Self::Long),
+                ColumnType::Float => string.parse().ok().map(Self::Float),
+                ColumnType::Double => string.parse().ok().map(Self::Double),
+                ColumnType::Date => NaiveDate::parse_from_str(&string, "%Y-%m-%d")
+                    .ok()
+                    .map(|date| Self::Int(days_since_epoch(date))),
+                ColumnType::Timestamp | ColumnType::Timestamptz => {
+                    DateTime::parse_from_rfc3339(&string)
+                        .ok()
+                        .map(|timestamp| Self::Long(timestamp.timestamp_micros()))
+                }
+                ColumnType::String => Some(Self::String(string.into_owned())),
+                ColumnType::Binary => Some(Self::Binary(bytes.clone())),
+            };
+        }
+
+        match (column_type, value) {
+            (ColumnType::Boolean, Value::Boolean(boolean)) => Some(Self::Boolean(*boolean)),
+            (ColumnType::Int, Value::Integer(int)) => i32::try_from(*int).ok().map(Self::Int),
+            (ColumnType::Long, Value::Integer(int)) => Some(Self::Long(*int)),
+            (ColumnType::Float, Value::Float(float)) => {
+                Some(Self::Float(float.into_inner() as f32))
+            }
+            (ColumnType::Float, Value::Integer(int)) => Some(Self::Float(*int as f32)),
+            (ColumnType::Double, Value::Float(float)) => Some(Self::Double(float.into_inner())),
+            (ColumnType::Double, Value::Integer(int)) => Some(Self::Double(*int as f64)),
+            (ColumnType::Date, Value::Timestamp(timestamp)) => {
+                Some(Self::Int(timestamp.timestamp().div_euclid(86_400) as i32))
+            }
+            (ColumnType::Timestamp | ColumnType::Timestamptz, Value::Timestamp(timestamp)) => {
+                Some(Self::Long(timestamp.timestamp_micros()))
+            }
+            (ColumnType::String, Value::Object(_) | Value::Array(_)) => Some(Self::String(
+                serde_json::to_string(value).expect("values should be valid json"),
+            )),
+            (ColumnType::String, value) => Some(Self::String(value.to_string_lossy().into_owned())),
+            _ => None,
+        }
+    }
+
+    /// Applies the `transform` of a partition field to this value of a column of type
+    /// `source_type`.
+    pub fn transform(&self, transform: Transform, source_type: ColumnType) -> Option<Self> {
+        let micros = |datum: &Self| match (source_type, datum) {
+            (ColumnType::Date, Self::Int(days)) => Some(i64::from(*days) * MICROS_PER_DAY),
+            (_, Self::Long(micros)) => Some(*micros),
+            _ => None,
+        };
+
+        match transform {
+            Transform::Identity => Some(self.clone()),
+            Transform::Void => None,
+            Transform::Year => {
+                let timestamp = from_micros(micros(self)?)?;
+                Some(Self::Int(timestamp.year() - 1970))
+            }
+            Transform::Month => {
+                let timestamp = from_micros(micros(self)?)?;
+                Some(Self::Int(
+                    (timestamp.year() - 1970) * 12 + timestamp.month0() as i32,
+                ))
+            }
+            Transform::Day => Some(Self::Int(micros(self)?.div_euclid(MICROS_PER_DAY) as i32)),
+            Transform::Hour => Some(Self::Int(micros(self)?.div_euclid(MICROS_PER_HOUR) as i32)),
+            Transform::Truncate(width) => Some(match self {
+                Self::Int(int) => Self::Int(int - int.rem_euclid(width as i32)),
+                Self::Long(long) => Self::Long(long - long.rem_euclid(i64::from(width))),
+                Self::String(string) => Self::String(string.chars().take(width as usize).collect()),
+                Self::Binary(bytes) => Self::Binary(bytes.slice(..bytes.len().min(width as usize))),
+                _ => return None,
+            }),
+        }
+    }
+}
+
+fn days_since_epoch(date: NaiveDate) -> i32 {
+    (date - NaiveDate::from_ymd(1970, 1, 1)).num_days() as i32
+}
+
+fn from_micros(micros: i64) -> Option<NaiveDateTime> {
+    NaiveDateTime::from_timestamp_opt(
+        micros.div_euclid(1_000_000),
+        (micros.rem_euclid(1_000_000) * 1000) as u32,
+    )
+}
+
+/// The values of the partition fields of a row, in the order of the fields in the partition spec.
+pub type PartitionKey = Vec<Option<Datum>>;
+
+pub fn partition_key(table: &TableMetadata, row: &[Option<Datum>]) -> PartitionKey {
+    table
+        .partition_fields
+        .iter()
+        .map(|field| {
+            let source_type = table.columns[field.source].column_type?;
+            row[field.source]
+                .as_ref()?
+                .transform(field.transform, source_type)
+        })
+        .collect()
+}
+
+/// Returns the directories data files of the partition are written to, such as
+/// `timestamp_day=2023-01-10/host=example.com`.
+pub fn partition_path(table: &TableMetadata, key: &PartitionKey) -> String {
+    table
+        .partition_fields
+        .iter()
+        .zip(key)
+        .map(|(field, value)| {
+            let value = value.as_ref().map_or_else(
+                || "null".to_string(),
+                |value| human_value(table, field, value),
+            );
+            format!(
+                "{}={}",
+                utf8_percent_encode(&field.name, PARTITION_VALUE),
+                utf8_percent_encode(&value, PARTITION_VALUE)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Formats partition values the way Iceberg does in the paths of data files.
+fn human_value(table: &TableMetadata, field: &PartitionField, value: &Datum) -> String {
+    let source_type = table.columns[field.source].column_type;
+    let result_type = source_type.map(|source_type| field.transform.result_type(source_type));
+    match (field.transform, result_type, value) {
+        (Transform::Year, _, Datum::Int(years)) => format!("{:04}", 1970 + years),
+        (Transform::Month, _, Datum::Int(months)) => format!(
+            "{:04}-{:02}",
+            1970 + months.div_euclid(12),
+            months.rem_euclid(12) + 1
+        ),
+        (Transform::Hour, _, Datum::Int(hours)) => from_micros(i64::from(*hours) * MICROS_PER_HOUR)
+            .map(|timestamp| {
+                format!(
+                    "{}-{:02}",
+                    timestamp.date().format("%Y-%m-%d"),
+                    timestamp.hour()
+                )
+            })
+            .unwrap_or_default(),
+        (_, Some(ColumnType::Date), Datum::Int(days)) => {
+            from_micros(i64::from(*days) * MICROS_PER_DAY)
+                .map(|timestamp| timestamp.date().format("%Y-%m-%d").to_string())
+                .unwrap_or_default()
+        }
+        (_, Some(ColumnType::Timestamp | ColumnType::Timestamptz), Datum::Long(micros)) => {
+            from_micros(*micros)
+                .map(|timestamp| timestamp.format("%Y-%m-%dT%H:%M:%S%.6f").to_string())
+                .unwrap_or_default()
+        }
+        (_, _, Datum::Boolean(boolean)) => boolean.to_string(),
+        (_, _, Datum::Int(int)) => int.to_string(),
+        (_, _, Datum::Long(long)) => long.to_string(),
+        (_, _, Datum::Float(float)) => float.to_string(),
+        (_, _, Datum::Double(double)) => double.to_string(),
+        (_, _, Datum::String(string)) => string.clone(),
+        (_, _, Datum::Binary(bytes)) => base64::encode(bytes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{super::table::tests::metadata, *};
+
+    #[test]
+    fn converts_values() {
+        let timestamp = Utc.ymd(2023, 1, 10).and_hms_micro(12, 30, 0, 123_456);
+
+        for (value, column_type, expected) in [
+            (Value::from(200), ColumnType::Int, Some(Datum::Int(200))),
+            (Value::from(i64::MAX), ColumnType::Int, None),
+            (Value::from("200"), ColumnType::Long, Some(Datum::Long(200))),
+            (Value::from(0.5), ColumnType::Float, Some(Datum::Float(0.5))),
+            (Value::from("yes"), ColumnType::Boolean, None),
+            (
+                Value::from(false),
+                ColumnType::String,
+                Some(Datum::String("false".into())),
+            ),
+            (
+                Value::from(timestamp),
+                ColumnType::Date,
+                Some(Datum::Int(19367)),
+            ),
+            (
+                Value::from("2023-01-10"),
+                ColumnType::Date,
+                Some(Datum::Int(19367)),
+            ),
+            (
+                Value::from(timestamp),
+                ColumnType::Timestamptz,
+                Some(Datum::Long(1673353800123456)),
+            ),
+            (
+                Value::from("2023-01-10T12:30:00.123456Z"),
+                ColumnType::Timestamp,
+                Some(Datum::Long(1673353800123456)),
+            ),
+            (Value::from(timestamp), ColumnType::Boolean, None),
+        ] {
+            assert_eq!(
+                Datum::convert(&value, column_type),
+                expected,
+                "{:?} as {:?}",
+                value,
+                column_type
+            );
+        }
+    }
+
+    #[test]
+    fn applies_transforms() {
+        let timestamp = Datum::Long(1673353800123456);
+        let source_type = ColumnType::Timestamptz;
+
+        for (transform, expected) in [
+            (Transform::Year, Some(Datum::Int(53))),
+            (Transform::Month, Some(Datum::Int(636))),
+            (Transform::Day, Some(Datum::Int(19367))),
+            (Transform::Hour, Some(Datum::Int(464820))),
+            (Transform::Void, None),
+        ] {
+            assert_eq!(timestamp.transform(transform, source_type), expected);
+        }
+
+        assert_eq!(
+            Datum::Int(-3).transform(Transform::Truncate(10), ColumnType::Int),
+            Some(Datum::Int(-10))
+        );
+        assert_eq!(
+            Datum::String("example.com".into())
+                .transform(Transform::Truncate(3), ColumnType::String),
+            Some(Datum::String("exa".into()))
+        );
+    }
+
+    #[test]
+    fn builds_partition_paths() {
+        let table = TableMetadata::parse(metadata()).unwrap();
+        let row = vec![
+            Some(Datum::Long(1673353800123456)),
+            Some(Datum::String("host a/b".into())),
+            None,
+            None,
+            None,
+        ];
+
+        let key = partition_key(&table, &row);
+        assert_eq!(
+            key,
+            vec![
+                Some(Datum::Int(19367)),
+                Some(Datum::String("host a/b".into()))
+            ]
+        );
+        assert_eq!(
+            partition_path(&table, &key),
+            "timestamp_day=2023-01-10/host=host%20a%2Fb"
+        );
+        assert_eq!(
+            partition_path(&table, &vec![None, None]),
+            "timestamp_day=null/host=null"
+        );
+    }
+}
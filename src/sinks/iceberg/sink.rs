@@ -0,0 +1,489 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::NonZeroUsize,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures::{future::BoxFuture, stream::BoxStream, StreamExt};
+use parquet::errors::ParquetError;
+use snafu::{ResultExt, Snafu};
+use tower::{Service, ServiceBuilder};
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    partition::Partitioner,
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use super::{
+    catalog::{Catalog, CatalogError, LoadedTable, NewSnapshot},
+    data_file::{write_parquet, ParquetCompression},
+    manifest::{
+        read_manifest_list, write_manifest, write_manifest_list, DataFile, ManifestError,
+        ManifestFile,
+    },
+    row::{partition_key, partition_path, Datum, PartitionKey},
+    storage::{Storage, StorageError},
+    table::TableMetadata,
+};
+use crate::{
+    codecs::Transformer,
+    event::{Event, Value},
+    internal_events::{IcebergEncodingError, TemplateRenderingError},
+    sinks::util::{
+        metadata::RequestMetadataBuilder, retries::RetryLogic, ServiceBuilderExt, SinkBuilderExt,
+        StreamSink, TowerRequestSettings,
+    },
+    template::Template,
+};
+
+/// How many times commits failing with conflicts are attempted again, with the table reloaded,
+/// before their requests are retried as a whole.
+const MAX_COMMIT_ATTEMPTS: usize = 5;
+
+/// How long to wait before loading the table again, when it can't be loaded on start up.
+const LOAD_TABLE_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Snafu)]
+pub enum IcebergError {
+    #[snafu(display("{}", source))]
+    Catalog { source: CatalogError },
+    #[snafu(display("{}", source))]
+    Storage { source: StorageError },
+    #[snafu(display("Failed to write data file: {}", source))]
+    WriteParquet { source: ParquetError },
+    #[snafu(display("{}", source))]
+    Manifest { source: ManifestError },
+    #[snafu(display(
+        "Partition spec of the table changed from {} to {}, reload Vector to write with it",
+        expected,
+        actual
+    ))]
+    PartitionSpecChanged { expected: i32, actual: i32 },
+}
+
+/// A row of the table, holding a value for each of its columns.
+struct EncodedRow {
+    partition: PartitionKey,
+    values: Vec<Option<Datum>>,
+    finalizers: EventFinalizers,
+    byte_size: usize,
+    json_size: usize,
+}
+
+impl ByteSizeOf for EncodedRow {
+    fn size_of(&self) -> usize {
+        self.byte_size
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
+struct RowEncoder {
+    table: Arc<TableMetadata>,
+    column_templates: HashMap<String, Template>,
+    transformer: Transformer,
+}
+
+impl RowEncoder {
+    /// Converts `event` to a row, returning `None` if it can't be.
+    ///
+    /// Errors are emitted by the encoder itself, and the events that can't be encoded are
+    /// rejected.
+    fn encode(&self, mut event: Event) -> Option<EncodedRow> {
+        let byte_size = event.size_of();
+        let json_size = event.estimated_json_encoded_size_of();
+        self.transformer.transform(&mut event);
+        let finalizers = event.take_finalizers();
+        let log = event.into_log();
+
+        let mut values = Vec::with_capacity(self.table.columns.len());
+        for column in &self.table.columns {
+            let value = match self.column_templates.get(&column.name) {
+                Some(template) => match template.render_string(&log) {
+                    Ok(value) => Some(Value::from(value)),
+                    Err(error) => {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some("column_templates"),
+                            drop_event: true,
+                        });
+                        finalizers.update_status(EventStatus::Rejected);
+                        return None;
+                    }
+                },
+                None => log.get(column.name.as_str()).cloned(),
+            };
+
+            let datum = match (value.filter(|value| !value.is_null()), column.column_type) {
+                (Some(value), Some(column_type)) => match Datum::convert(&value, column_type) {
+                    Some(datum) => Some(datum),
+                    None => {
+                        return self.reject(
+                            format!("column {:?} can't hold value {}", column.name, value),
+                            finalizers,
+                        )
+                    }
+                },
+                _ => None,
+            };
+            if column.required && datum.is_none() {
+                return self.reject(
+                    format!("required column {:?} has no value", column.name),
+                    finalizers,
+                );
+            }
+            values.push(datum);
+        }
+
+        Some(EncodedRow {
+            partition: partition_key(&self.table, &values),
+            values,
+            finalizers,
+            byte_size,
+            json_size,
+        })
+    }
+
+    fn reject(&self, error_message: String, finalizers: EventFinalizers) -> Option<EncodedRow> {
+        emit!(IcebergEncodingError {
+            error_message,
+            count: 1,
+        });
+        finalizers.update_status(EventStatus::Rejected);
+        None
+    }
+}
+
+struct RowPartitioner;
+
+impl Partitioner for RowPartitioner {
+    type Item = EncodedRow;
+    type Key = PartitionKey;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        item.partition.clone()
+    }
+}
+
+/// Writes data files to the table and commits them.
+struct TableWriter {
+    catalog: Arc<Catalog>,
+    storage: Storage,
+    compression: ParquetCompression,
+    /// The table, as loaded when the sink started, which rows are converted with.
+    table: Arc<TableMetadata>,
+}
+
+impl TableWriter {
+    async fn append(
+        &self,
+        partition: PartitionKey,
+        rows: &[Vec<Option<Datum>>],
+    ) -> Result<(), IcebergError> {
+        let file = write_parquet(&self.table.columns, rows, self.compression)
+            .context(WriteParquetSnafu)?;
+        let mut directory = partition_path(&self.table, &partition);
+        if !directory.is_empty() {
+            directory.push('/');
+        }
+        let path = format!(
+            "{}/data/{}{}-{}.parquet",
+            self.table.location,
+            directory,
+            chrono::Utc::now().format("%Y%m%d%H%M%S"),
+            uuid::Uuid::new_v4()
+        );
+        let data_file = DataFile {
+            path,
+            partition,
+            record_count: rows.len() as i64,
+            file_size_in_bytes: file.len() as i64,
+        };
+        self.storage
+            .put(&data_file.path, file)
+            .await
+            .context(StorageSnafu)?;
+
+        let mut attempt = 1;
+        loop {
+            let base = self.catalog.load_table().await.context(CatalogSnafu)?;
+            match self.commit(&base, &data_file).await {
+                Err(IcebergError::Catalog {
+                    source: CatalogError::Conflict,
+                }) if attempt < MAX_COMMIT_ATTEMPTS => {
+                    debug!(message = "Table was updated concurrently, committing again.", %attempt);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Commits a snapshot adding `data_file` to the table as it is in `base`.
+    async fn commit(&self, base: &LoadedTable, data_file: &DataFile) -> Result<(), IcebergError> {
+        let metadata = &base.metadata;
+        if metadata.spec_id != self.table.spec_id {
+            return Err(IcebergError::PartitionSpecChanged {
+                expected: self.table.spec_id,
+                actual: metadata.spec_id,
+            });
+        }
+
+        let snapshot_id = snapshot_id();
+        let parent_snapshot_id = metadata
+            .current_snapshot
+            .as_ref()
+            .map(|snapshot| snapshot.snapshot_id);
+        let sequence_number = metadata.last_sequence_number + 1;
+
+        let manifest = write_manifest(&self.table, snapshot_id, data_file);
+        let manifest_file = ManifestFile {
+            path: format!(
+                "{}/metadata/{}-m0.avro",
+                metadata.location,
+                uuid::Uuid::new_v4()
+            ),
+            length: manifest.len() as i64,
+            partition_spec_id: self.table.spec_id,
+            content: 0,
+            sequence_number,
+            min_sequence_number: sequence_number,
+            added_snapshot_id: snapshot_id,
+            added_files_count: Some(1),
+            existing_files_count: Some(0),
+            deleted_files_count: Some(0),
+            added_rows_count: Some(data_file.record_count),
+            existing_rows_count: Some(0),
+            deleted_rows_count: Some(0),
+            partitions: None,
+            key_metadata: None,
+        };
+        self.storage
+            .put(&manifest_file.path, manifest)
+            .await
+            .context(StorageSnafu)?;
+
+        // The manifests of the current snapshot are carried over to the new one.
+        let mut manifests = vec![manifest_file];
+        if let Some(manifest_list) = metadata
+            .current_snapshot
+            .as_ref()
+            .and_then(|snapshot| snapshot.manifest_list.as_deref())
+        {
+            let manifest_list = self
+                .storage
+                .get(manifest_list)
+                .await
+                .context(StorageSnafu)?;
+            manifests.extend(read_manifest_list(&manifest_list).context(ManifestSnafu)?);
+        }
+        let manifest_list = format!(
+            "{}/metadata/snap-{}-1-{}.avro",
+            metadata.location,
+            snapshot_id,
+            uuid::Uuid::new_v4()
+        );
+        self.storage
+            .put(
+                &manifest_list,
+                write_manifest_list(
+                    metadata,
+                    snapshot_id,
+                    parent_snapshot_id,
+                    sequence_number,
+                    &manifests,
+                ),
+            )
+            .await
+            .context(StorageSnafu)?;
+
+        let snapshot = NewSnapshot {
+            snapshot_id,
+            parent_snapshot_id,
+            sequence_number,
+            timestamp_ms: chrono::Utc::now().timestamp_millis(),
+            manifest_list,
+            schema_id: self.table.schema_id,
+            summary: BTreeMap::from([
+                ("added-data-files", "1".to_string()),
+                ("added-records", data_file.record_count.to_string()),
+                ("added-files-size", data_file.file_size_in_bytes.to_string()),
+                ("changed-partition-count", "1".to_string()),
+            ]),
+        };
+        self.catalog
+            .commit(base, &snapshot)
+            .await
+            .context(CatalogSnafu)
+    }
+}
+
+/// Returns a random, positive, snapshot id, the way Iceberg generates them.
+fn snapshot_id() -> i64 {
+    let (high, low) = uuid::Uuid::new_v4().as_u64_pair();
+    ((high ^ low) & i64::MAX as u64) as i64
+}
+
+#[derive(Clone)]
+struct IcebergRequest {
+    partition: PartitionKey,
+    rows: Vec<Vec<Option<Datum>>>,
+    finalizers: EventFinalizers,
+    metadata: RequestMetadata,
+}
+
+impl Finalizable for IcebergRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for IcebergRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata.clone()
+    }
+}
+
+struct IcebergResponse;
+
+impl DriverResponse for IcebergResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+}
+
+#[derive(Clone)]
+struct IcebergService {
+    writer: Arc<TableWriter>,
+}
+
+impl Service<IcebergRequest> for IcebergService {
+    type Response = IcebergResponse;
+    type Error = IcebergError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: IcebergRequest) -> Self::Future {
+        let writer = Arc::clone(&self.writer);
+        Box::pin(async move {
+            writer.append(request.partition, &request.rows).await?;
+            Ok(IcebergResponse)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IcebergRetryLogic;
+
+impl RetryLogic for IcebergRetryLogic {
+    type Error = IcebergError;
+    type Response = IcebergResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            IcebergError::Catalog { source } => source.is_retriable(),
+            IcebergError::Storage { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+pub struct IcebergSink {
+    pub catalog: Arc<Catalog>,
+    pub storage: Storage,
+    pub column_templates: HashMap<String, Template>,
+    pub transformer: Transformer,
+    pub compression: ParquetCompression,
+    pub batch_settings: BatcherSettings,
+    pub request: TowerRequestSettings,
+}
+
+impl IcebergSink {
+    /// Loads the table, waiting for its catalog to be reachable.
+    async fn load_table(&self) -> Result<TableMetadata, CatalogError> {
+        loop {
+            match self.catalog.load_table().await {
+                Ok(table) => return Ok(table.metadata),
+                Err(error) if error.is_retriable() => {
+                    error!(message = "Failed to load the table, retrying.", %error);
+                    tokio::time::sleep(LOAD_TABLE_RETRY_INTERVAL).await;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for IcebergSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let table = match self.load_table().await {
+            Ok(table) => Arc::new(table),
+            Err(error) => {
+                error!(message = "Failed to load the table.", %error);
+                return Err(());
+            }
+        };
+
+        let encoder = RowEncoder {
+            table: Arc::clone(&table),
+            column_templates: self.column_templates,
+            transformer: self.transformer,
+        };
+        let service = ServiceBuilder::new()
+            .settings(self.request, IcebergRetryLogic)
+            .service(IcebergService {
+                writer: Arc::new(TableWriter {
+                    catalog: self.catalog,
+                    storage: self.storage,
+                    compression: self.compression,
+                    table,
+                }),
+            });
+
+        input
+            .filter_map(|event| futures::future::ready(encoder.encode(event)))
+            .batched_partitioned(RowPartitioner, self.batch_settings)
+            .map(|(partition, rows)| {
+                let mut byte_size = 0;
+                let mut json_size = 0;
+                let mut finalizers = EventFinalizers::default();
+                let rows: Vec<_> = rows
+                    .into_iter()
+                    .map(|row| {
+                        byte_size += row.byte_size;
+                        json_size += row.json_size;
+                        finalizers.merge(row.finalizers);
+                        row.values
+                    })
+                    .collect();
+                // The size of a request isn't known until its data file is written, so the size of
+                // its events is used instead.
+                let metadata = RequestMetadataBuilder::new(rows.len(), byte_size, json_size)
+                    .with_request_size(
+                        NonZeroUsize::new(byte_size).expect("batches should never be empty"),
+                    );
+
+                IcebergRequest {
+                    partition,
+                    rows,
+                    finalizers,
+                    metadata,
+                }
+            })
+            .into_driver(service)
+            .protocol("https")
+            .run()
+            .await
+    }
+}
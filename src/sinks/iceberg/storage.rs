@@ -0,0 +1,103 @@
+//! Reading and writing the files of tables in S3.
+
+use aws_sdk_s3::{
+    error::{GetObjectError, PutObjectError},
+    types::{ByteStream, SdkError},
+    Client as S3Client,
+};
+use bytes::Bytes;
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum StorageError {
+    #[snafu(display("Location {:?} isn't in S3", location))]
+    UnsupportedLocation { location: String },
+    #[snafu(display("Failed to get {:?}: {}", location, source))]
+    GetObject {
+        location: String,
+        source: SdkError<GetObjectError>,
+    },
+    #[snafu(display("Failed to read {:?}: {}", location, source))]
+    ReadObject {
+        location: String,
+        source: aws_smithy_http::byte_stream::Error,
+    },
+    #[snafu(display("Failed to put {:?}: {}", location, source))]
+    PutObject {
+        location: String,
+        source: SdkError<PutObjectError>,
+    },
+}
+
+#[derive(Clone)]
+pub struct Storage {
+    client: S3Client,
+}
+
+impl Storage {
+    pub const fn new(client: S3Client) -> Self {
+        Self { client }
+    }
+
+    pub async fn get(&self, location: &str) -> Result<Bytes, StorageError> {
+        let (bucket, key) = split_location(location)?;
+        let object = self
+            .client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .context(GetObjectSnafu { location })?;
+        let body = object
+            .body
+            .collect()
+            .await
+            .context(ReadObjectSnafu { location })?;
+        Ok(body.into_bytes())
+    }
+
+    pub async fn put(&self, location: &str, body: Vec<u8>) -> Result<(), StorageError> {
+        let (bucket, key) = split_location(location)?;
+        self.client
+            .put_object()
+            .bucket(bucket)
+            .key(key)
+            .body(ByteStream::from(body))
+            .send()
+            .await
+            .context(PutObjectSnafu { location })?;
+        Ok(())
+    }
+}
+
+/// Splits locations such as `s3://bucket/path/to/file` into their bucket and key.
+fn split_location(location: &str) -> Result<(&str, &str), StorageError> {
+    ["s3://", "s3a://", "s3n://"]
+        .iter()
+        .find_map(|scheme| location.strip_prefix(scheme))
+        .and_then(|path| path.split_once('/'))
+        .filter(|(bucket, key)| !bucket.is_empty() && !key.is_empty())
+        .ok_or_else(|| StorageError::UnsupportedLocation {
+            location: location.into(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_locations() {
+        assert_eq!(
+            split_location("s3://warehouse/logs/events/data/a.parquet").unwrap(),
+            ("warehouse", "logs/events/data/a.parquet")
+        );
+        assert_eq!(
+            split_location("s3a://warehouse/a.avro").unwrap(),
+            ("warehouse", "a.avro")
+        );
+        assert!(split_location("gs://warehouse/a.avro").is_err());
+        assert!(split_location("s3://warehouse").is_err());
+    }
+}
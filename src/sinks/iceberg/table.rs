@@ -0,0 +1,534 @@
+//! The parts of the metadata of Iceberg tables that the sink relies on.
+
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum TableError {
+    #[snafu(display("Invalid table metadata: {}", source))]
+    InvalidMetadata { source: serde_json::Error },
+    #[snafu(display("Table has no schema with id {}", id))]
+    MissingSchema { id: i32 },
+    #[snafu(display("Table has no partition spec with id {}", id))]
+    MissingPartitionSpec { id: i32 },
+    #[snafu(display("Snapshot {} has no manifest list", id))]
+    MissingManifestList { id: i64 },
+    #[snafu(display(
+        "Required column {:?} has type {}, which events can't be written to",
+        name,
+        column_type
+    ))]
+    UnsupportedColumn { name: String, column_type: String },
+    #[snafu(display(
+        "Partition field {:?} has transform {:?}, which can't be applied to its source column",
+        name,
+        transform
+    ))]
+    UnsupportedTransform { name: String, transform: String },
+}
+
+/// The primitive types of the columns events can be written to.
+///
+/// Dates are written as days since the Unix epoch, and timestamps as microseconds since it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColumnType {
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Date,
+    Timestamp,
+    Timestamptz,
+    String,
+    Binary,
+}
+
+impl ColumnType {
+    fn parse(column_type: &JsonValue) -> Option<Self> {
+        Some(match column_type.as_str()? {
+            "boolean" => Self::Boolean,
+            "int" => Self::Int,
+            "long" => Self::Long,
+            "float" => Self::Float,
+            "double" => Self::Double,
+            "date" => Self::Date,
+            "timestamp" => Self::Timestamp,
+            "timestamptz" => Self::Timestamptz,
+            "string" => Self::String,
+            "binary" => Self::Binary,
+            _ => return None,
+        })
+    }
+
+    const fn is_temporal(self) -> bool {
+        matches!(self, Self::Date | Self::Timestamp | Self::Timestamptz)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Column {
+    pub id: i32,
+    pub name: String,
+    pub required: bool,
+    /// The type of the column, unless it's one events can't be written to, in which case the
+    /// column is left out of data files and read as null.
+    pub column_type: Option<ColumnType>,
+}
+
+/// The transforms of partition fields, producing the partition values of rows from their source
+/// columns.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Transform {
+    Identity,
+    Year,
+    Month,
+    Day,
+    Hour,
+    Truncate(u32),
+    Void,
+}
+
+impl Transform {
+    fn parse(transform: &str) -> Option<Self> {
+        Some(match transform {
+            "identity" => Self::Identity,
+            "year" => Self::Year,
+            "month" => Self::Month,
+            "day" => Self::Day,
+            "hour" => Self::Hour,
+            "void" => Self::Void,
+            _ => {
+                let width = transform.strip_prefix("truncate[")?.strip_suffix(']')?;
+                Self::Truncate(width.parse().ok().filter(|width| *width > 0)?)
+            }
+        })
+    }
+
+    fn applies_to(self, column_type: ColumnType) -> bool {
+        match self {
+            Self::Identity | Self::Void => true,
+            Self::Year | Self::Month | Self::Day => column_type.is_temporal(),
+            Self::Hour => matches!(column_type, ColumnType::Timestamp | ColumnType::Timestamptz),
+            Self::Truncate(_) => matches!(
+                column_type,
+                ColumnType::Int | ColumnType::Long | ColumnType::String | ColumnType::Binary
+            ),
+        }
+    }
+
+    /// The type of the partition values produced from a source column of type `source_type`.
+    pub const fn result_type(self, source_type: ColumnType) -> ColumnType {
+        match self {
+            Self::Year | Self::Month | Self::Hour => ColumnType::Int,
+            Self::Day => ColumnType::Date,
+            Self::Identity | Self::Truncate(_) | Self::Void => source_type,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PartitionField {
+    pub field_id: i32,
+    pub name: String,
+    /// The index of the source column in the columns of the table.
+    pub source: usize,
+    pub transform: Transform,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Snapshot {
+    pub snapshot_id: i64,
+    pub manifest_list: Option<String>,
+    #[serde(default)]
+    pub sequence_number: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawMetadata {
+    format_version: u8,
+    location: String,
+    #[serde(default)]
+    last_sequence_number: i64,
+    current_snapshot_id: Option<i64>,
+    #[serde(default)]
+    snapshots: Vec<Snapshot>,
+    current_schema_id: Option<i32>,
+    #[serde(default)]
+    schemas: Vec<RawSchema>,
+    schema: Option<RawSchema>,
+    default_spec_id: Option<i32>,
+    #[serde(default)]
+    partition_specs: Vec<RawPartitionSpec>,
+    partition_spec: Option<Vec<RawPartitionField>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawSchema {
+    #[serde(default)]
+    schema_id: i32,
+    fields: Vec<RawColumn>,
+}
+
+#[derive(Deserialize)]
+struct RawColumn {
+    id: i32,
+    name: String,
+    required: bool,
+    r#type: JsonValue,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawPartitionSpec {
+    spec_id: i32,
+    fields: Vec<RawPartitionField>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawPartitionField {
+    source_id: i32,
+    field_id: Option<i32>,
+    name: String,
+    transform: String,
+}
+
+/// The metadata of a table, as loaded from its catalog.
+#[derive(Clone, Debug)]
+pub struct TableMetadata {
+    pub format_version: u8,
+    pub location: String,
+    pub last_sequence_number: i64,
+    pub current_snapshot: Option<Snapshot>,
+    pub schema_id: i32,
+    pub columns: Vec<Column>,
+    pub spec_id: i32,
+    pub partition_fields: Vec<PartitionField>,
+    /// The JSON of the current schema and partition spec, which manifests are annotated with.
+    pub schema_json: String,
+    pub spec_json: String,
+    raw: JsonValue,
+}
+
+impl TableMetadata {
+    pub fn parse(raw: JsonValue) -> Result<Self, TableError> {
+        let metadata: RawMetadata =
+            serde_json::from_value(raw.clone()).context(InvalidMetadataSnafu)?;
+
+        // Tables of the first format version may only have the legacy `schema` and
+        // `partition-spec` fields.
+        let schema_id = metadata.current_schema_id.unwrap_or_default();
+        let (schema_json, schema) = match metadata.current_schema_id {
+            Some(id) => {
+                let index = metadata
+                    .schemas
+                    .iter()
+                    .position(|schema| schema.schema_id == id)
+                    .ok_or(TableError::MissingSchema { id })?;
+                (raw["schemas"][index].clone(), &metadata.schemas[index])
+            }
+            None => (
+                raw["schema"].clone(),
+                metadata
+                    .schema
+                    .as_ref()
+                    .ok_or(TableError::MissingSchema { id: 0 })?,
+            ),
+        };
+        let spec_id = metadata.default_spec_id.unwrap_or_default();
+        let (spec_json, spec_fields) = match metadata.default_spec_id {
+            Some(id) if !metadata.partition_specs.is_empty() => {
+                let index = metadata
+                    .partition_specs
+                    .iter()
+                    .position(|spec| spec.spec_id == id)
+                    .ok_or(TableError::MissingPartitionSpec { id })?;
+                (
+                    raw["partition-specs"][index]["fields"].clone(),
+                    &metadata.partition_specs[index].fields,
+                )
+            }
+            Some(id) if metadata.partition_spec.is_none() => {
+                return Err(TableError::MissingPartitionSpec { id })
+            }
+            _ => (
+                raw["partition-spec"].clone(),
+                metadata
+                    .partition_spec
+                    .as_ref()
+                    .ok_or(TableError::MissingPartitionSpec { id: 0 })?,
+            ),
+        };
+
+        let mut columns = Vec::with_capacity(schema.fields.len());
+        for column in &schema.fields {
+            let column_type = ColumnType::parse(&column.r#type);
+            if column.required && column_type.is_none() {
+                return Err(TableError::UnsupportedColumn {
+                    name: column.name.clone(),
+                    column_type: column.r#type.to_string(),
+                });
+            }
+            columns.push(Column {
+                id: column.id,
+                name: column.name.clone(),
+                required: column.required,
+                column_type,
+            });
+        }
+
+        let mut partition_fields = Vec::with_capacity(spec_fields.len());
+        for (index, field) in spec_fields.iter().enumerate() {
+            let unsupported = || TableError::UnsupportedTransform {
+                name: field.name.clone(),
+                transform: field.transform.clone(),
+            };
+            let source = columns
+                .iter()
+                .position(|column| column.id == field.source_id)
+                .ok_or_else(unsupported)?;
+            let transform = Transform::parse(&field.transform)
+                .filter(|transform| {
+                    columns[source]
+                        .column_type
+                        .map_or(false, |column_type| transform.applies_to(column_type))
+                })
+                .ok_or_else(unsupported)?;
+            partition_fields.push(PartitionField {
+                // Partition fields of legacy specs are numbered from 1000 in their order.
+                field_id: field.field_id.unwrap_or(1000 + index as i32),
+                name: field.name.clone(),
+                source,
+                transform,
+            });
+        }
+
+        let current_snapshot = metadata
+            .current_snapshot_id
+            .filter(|id| *id != -1)
+            .and_then(|id| {
+                metadata
+                    .snapshots
+                    .into_iter()
+                    .find(|snapshot| snapshot.snapshot_id == id)
+            });
+        if let Some(snapshot) = &current_snapshot {
+            if snapshot.manifest_list.is_none() {
+                return Err(TableError::MissingManifestList {
+                    id: snapshot.snapshot_id,
+                });
+            }
+        }
+
+        Ok(Self {
+            format_version: metadata.format_version,
+            location: metadata.location.trim_end_matches('/').to_string(),
+            last_sequence_number: metadata.last_sequence_number,
+            current_snapshot,
+            schema_id,
+            columns,
+            spec_id,
+            partition_fields,
+            schema_json: schema_json.to_string(),
+            spec_json: spec_json.to_string(),
+            raw,
+        })
+    }
+
+    /// Returns the metadata of the table once `snapshot` is appended to it, for catalogs that
+    /// only track the location of the metadata files of tables.
+    pub fn with_snapshot(
+        &self,
+        snapshot: JsonValue,
+        snapshot_id: i64,
+        sequence_number: i64,
+        timestamp_ms: i64,
+        previous_location: &str,
+    ) -> JsonValue {
+        let mut raw = self.raw.clone();
+        let previous_timestamp_ms = raw["last-updated-ms"].clone();
+
+        raw["last-updated-ms"] = json!(timestamp_ms);
+        if self.format_version > 1 {
+            raw["last-sequence-number"] = json!(sequence_number);
+        }
+        raw["current-snapshot-id"] = json!(snapshot_id);
+        push(&mut raw, "snapshots", snapshot);
+        push(
+            &mut raw,
+            "snapshot-log",
+            json!({ "timestamp-ms": timestamp_ms, "snapshot-id": snapshot_id }),
+        );
+        if !raw["refs"].is_object() {
+            raw["refs"] = json!({});
+        }
+        raw["refs"]["main"] = json!({ "snapshot-id": snapshot_id, "type": "branch" });
+        push(
+            &mut raw,
+            "metadata-log",
+            json!({ "timestamp-ms": previous_timestamp_ms, "metadata-file": previous_location }),
+        );
+        raw
+    }
+}
+
+fn push(raw: &mut JsonValue, key: &str, value: JsonValue) {
+    match raw[key].as_array_mut() {
+        Some(values) => values.push(value),
+        None => raw[key] = json!([value]),
+    }
+}
+
+/// Returns the location of the metadata file following the one at `previous_location`, numbered
+/// after it the way Iceberg itself numbers them.
+pub fn next_metadata_location(table_location: &str, previous_location: &str) -> String {
+    let version = previous_location
+        .rsplit('/')
+        .next()
+        .and_then(|name| name.split('-').next())
+        .and_then(|version| version.parse::<u64>().ok())
+        .map_or(0, |version| version + 1);
+    format!(
+        "{}/metadata/{:05}-{}.metadata.json",
+        table_location,
+        version,
+        uuid::Uuid::new_v4()
+    )
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    pub fn metadata() -> JsonValue {
+        json!({
+            "format-version": 2,
+            "table-uuid": "4b8c3c2e-6c3a-4fd0-9b42-0c1d2e3f4a5b",
+            "location": "s3://warehouse/logs/events/",
+            "last-sequence-number": 3,
+            "last-updated-ms": 1673352000000i64,
+            "last-column-id": 5,
+            "current-schema-id": 1,
+            "schemas": [
+                { "schema-id": 0, "type": "struct", "fields": [] },
+                { "schema-id": 1, "type": "struct", "fields": [
+                    { "id": 1, "name": "timestamp", "required": true, "type": "timestamptz" },
+                    { "id": 2, "name": "host", "required": false, "type": "string" },
+                    { "id": 3, "name": "status", "required": false, "type": "int" },
+                    { "id": 4, "name": "message", "required": false, "type": "string" },
+                    { "id": 5, "name": "tags", "required": false, "type": {
+                        "type": "list",
+                        "element-id": 6,
+                        "element": "string",
+                        "element-required": false
+                    } }
+                ] }
+            ],
+            "default-spec-id": 0,
+            "partition-specs": [
+                { "spec-id": 0, "fields": [
+                    { "source-id": 1, "field-id": 1000, "name": "timestamp_day",
+                      "transform": "day" },
+                    { "source-id": 2, "field-id": 1001, "name": "host", "transform": "identity" }
+                ] }
+            ],
+            "last-partition-id": 1001,
+            "current-snapshot-id": 7,
+            "snapshots": [
+                { "snapshot-id": 7, "sequence-number": 3, "timestamp-ms": 1673352000000i64,
+                  "manifest-list": "s3://warehouse/logs/events/metadata/snap-7-1-a.avro",
+                  "summary": { "operation": "append" } }
+            ]
+        })
+    }
+
+    #[test]
+    fn parses_metadata() {
+        let metadata = TableMetadata::parse(metadata()).unwrap();
+
+        assert_eq!(metadata.location, "s3://warehouse/logs/events");
+        assert_eq!(metadata.current_snapshot.unwrap().snapshot_id, 7);
+        let column_types: Vec<_> = metadata
+            .columns
+            .iter()
+            .map(|column| column.column_type)
+            .collect();
+        assert_eq!(
+            column_types,
+            vec![
+                Some(ColumnType::Timestamptz),
+                Some(ColumnType::String),
+                Some(ColumnType::Int),
+                Some(ColumnType::String),
+                None
+            ]
+        );
+        let transforms: Vec<_> = metadata
+            .partition_fields
+            .iter()
+            .map(|field| (field.source, field.transform))
+            .collect();
+        assert_eq!(
+            transforms,
+            vec![(0, Transform::Day), (1, Transform::Identity)]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_tables() {
+        let mut raw = metadata();
+        raw["schemas"][1]["fields"][4]["required"] = json!(true);
+        assert!(matches!(
+            TableMetadata::parse(raw),
+            Err(TableError::UnsupportedColumn { .. })
+        ));
+
+        let mut raw = metadata();
+        raw["partition-specs"][0]["fields"][1]["transform"] = json!("bucket[16]");
+        assert!(matches!(
+            TableMetadata::parse(raw),
+            Err(TableError::UnsupportedTransform { .. })
+        ));
+
+        let mut raw = metadata();
+        raw["partition-specs"][0]["fields"][1]["transform"] = json!("hour");
+        assert!(matches!(
+            TableMetadata::parse(raw),
+            Err(TableError::UnsupportedTransform { .. })
+        ));
+    }
+
+    #[test]
+    fn appends_snapshots() {
+        let metadata = TableMetadata::parse(metadata()).unwrap();
+        let raw = metadata.with_snapshot(
+            json!({ "snapshot-id": 8 }),
+            8,
+            4,
+            1673353800000,
+            "s3://warehouse/logs/events/metadata/00002-a.metadata.json",
+        );
+
+        assert_eq!(raw["current-snapshot-id"], 8);
+        assert_eq!(raw["last-sequence-number"], 4);
+        assert_eq!(raw["snapshots"].as_array().unwrap().len(), 2);
+        assert_eq!(raw["refs"]["main"]["snapshot-id"], 8);
+        assert_eq!(
+            raw["metadata-log"][0],
+            json!({
+                "timestamp-ms": 1673352000000i64,
+                "metadata-file": "s3://warehouse/logs/events/metadata/00002-a.metadata.json"
+            })
+        );
+        assert!(next_metadata_location(
+            &metadata.location,
+            "s3://warehouse/logs/events/metadata/00002-a.metadata.json"
+        )
+        .starts_with("s3://warehouse/logs/events/metadata/00003-"));
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use codecs::JsonSerializerConfig;
 use futures::FutureExt;
+use indexmap::IndexMap;
 use rdkafka::ClientConfig;
 use vector_config::configurable_component;
 
@@ -15,6 +16,7 @@ use crate::{
         util::{BatchConfig, NoDefaultsBatchSettings},
         Healthcheck, VectorSink,
     },
+    template::Template,
 };
 
 pub(crate) const QUEUED_MIN_MESSAGES: u64 = 100000;
@@ -78,6 +80,33 @@ pub struct KafkaSinkConfig {
     #[serde(alias = "headers_field")] // accidentally released as `headers_field` in 0.18
     pub headers_key: Option<String>,
 
+    /// A map of the headers of records to the templates rendering their values for each event.
+    ///
+    /// These headers take precedence over those of `headers_key` and of the `kafka` source.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The template of the value of the header."
+    ))]
+    pub headers: IndexMap<String, Template>,
+
+    /// Whether the headers of the records read by the `kafka` source are written to records.
+    ///
+    /// This requires the `vector` log namespace, where the source keeps them in the
+    /// `%kafka.headers` metadata of events, while with the legacy namespace, they're written with
+    /// `headers_key`.
+    #[serde(default)]
+    pub forward_source_headers: bool,
+
+    /// Whether the producer is idempotent, which keeps it from writing duplicate records when it
+    /// retries sending them.
+    ///
+    /// Idempotence is always enabled for transactional producers.
+    #[serde(default)]
+    pub idempotence: bool,
+
+    #[configurable(derived)]
+    pub transaction: Option<KafkaTransactionConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -87,6 +116,40 @@ pub struct KafkaSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// Transactional production of records, which writes them exactly once.
+///
+/// Records are written in transactions, committed at an interval, and events are acknowledged once
+/// the transaction holding their records is committed. Consumers must read with the
+/// `read_committed` isolation level to skip the records of aborted transactions.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaTransactionConfig {
+    /// The transactional identifier of the producer.
+    ///
+    /// It must be unique to the sink, and stay the same when Vector restarts, so that the
+    /// transaction left open by the previous producer is aborted before new records are written.
+    #[configurable(metadata(docs::examples = "vector-kafka-sink"))]
+    pub id: String,
+
+    /// The interval, in milliseconds, at which transactions are committed.
+    #[serde(default = "default_commit_interval_ms")]
+    pub commit_interval_ms: u64,
+
+    /// The timeout, in milliseconds, after which the broker aborts transactions that aren't
+    /// committed.
+    #[serde(default = "default_transaction_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+const fn default_commit_interval_ms() -> u64 {
+    1000
+}
+
+const fn default_transaction_timeout_ms() -> u64 {
+    60000 // default in librdkafka
+}
+
 const fn default_socket_timeout_ms() -> u64 {
     60000 // default in librdkafka
 }
@@ -120,6 +183,17 @@ impl KafkaSinkConfig {
                     .set("compression.codec", &to_string(self.compression))
                     .set("message.timeout.ms", &self.message_timeout_ms.to_string());
 
+                if self.idempotence {
+                    client_config.set("enable.idempotence", "true");
+                }
+                if let Some(transaction) = &self.transaction {
+                    // Transactional producers are idempotent.
+                    client_config.set("transactional.id", &transaction.id).set(
+                        "transaction.timeout.ms",
+                        &transaction.timeout_ms.to_string(),
+                    );
+                }
+
                 if let Some(value) = self.batch.timeout_secs {
                     // Delay in milliseconds to wait for messages in the producer queue to accumulate before
                     // constructing message batches (MessageSets) to transmit to brokers. A higher value
@@ -209,6 +283,10 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            headers: IndexMap::new(),
+            forward_source_headers: false,
+            idempotence: false,
+            transaction: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -240,4 +318,25 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    #[test]
+    fn sets_transactional_options() {
+        let config: KafkaSinkConfig = toml::from_str(
+            r#"bootstrap_servers = "localhost:9092"
+            topic = "logs"
+            encoding.codec = "json"
+            transaction.id = "vector-kafka-sink""#,
+        )
+        .unwrap();
+
+        let client_config = config.to_rdkafka(KafkaRole::Producer).unwrap();
+        assert_eq!(
+            client_config.get("transactional.id"),
+            Some("vector-kafka-sink")
+        );
+        assert_eq!(client_config.get("transaction.timeout.ms"), Some("60000"));
+
+        let client_config = config.to_rdkafka(KafkaRole::Consumer).unwrap();
+        assert_eq!(client_config.get("transactional.id"), None);
+    }
 }
@@ -1,6 +1,8 @@
-use std::num::NonZeroUsize;
+use std::{collections::BTreeMap, num::NonZeroUsize};
 
 use bytes::{Bytes, BytesMut};
+use indexmap::IndexMap;
+use lookup::path;
 use rdkafka::message::{Header, OwnedHeaders};
 use tokio_util::codec::Encoder as _;
 use vector_core::config::LogSchema;
@@ -19,6 +21,8 @@ use crate::{
 pub struct KafkaRequestBuilder {
     pub key_field: Option<String>,
     pub headers_key: Option<String>,
+    pub headers: IndexMap<String, Template>,
+    pub forward_source_headers: bool,
     pub topic_template: Template,
     pub transformer: Transformer,
     pub encoder: Encoder<()>,
@@ -45,7 +49,12 @@ impl KafkaRequestBuilder {
             finalizers: event.take_finalizers(),
             key: get_key(&event, &self.key_field),
             timestamp_millis: get_timestamp_millis(&event, self.log_schema),
-            headers: get_headers(&event, &self.headers_key),
+            headers: get_headers(
+                &event,
+                &self.headers_key,
+                self.forward_source_headers,
+                &self.headers,
+            ),
             topic,
         };
         self.transformer.transform(&mut event);
@@ -89,39 +98,76 @@ fn get_timestamp_millis(event: &Event, log_schema: &'static LogSchema) -> Option
     .map(|ts| ts.timestamp_millis())
 }
 
-fn get_headers(event: &Event, headers_key: &Option<String>) -> Option<OwnedHeaders> {
-    headers_key.as_ref().and_then(|headers_key| {
-        if let Event::Log(log) = event {
-            if let Some(headers) = log.get(headers_key.as_str()) {
-                match headers {
-                    Value::Object(headers_map) => {
-                        let mut owned_headers = OwnedHeaders::new_with_capacity(headers_map.len());
-                        for (key, value) in headers_map {
-                            if let Value::Bytes(value_bytes) = value {
-                                owned_headers = owned_headers.insert(Header {
-                                    key,
-                                    value: Some(value_bytes.as_ref()),
-                                });
-                            } else {
-                                emit!(KafkaHeaderExtractionError {
-                                    header_field: headers_key
-                                });
-                            }
-                        }
-                        return Some(owned_headers);
-                    }
-                    _ => {
-                        emit!(KafkaHeaderExtractionError {
-                            header_field: headers_key
-                        });
-                    }
-                }
+/// Collects the headers of the record of `event`, which are, by increasing precedence, the headers
+/// read by the `kafka` source, those of the `headers_key` field, and the templated ones.
+fn get_headers(
+    event: &Event,
+    headers_key: &Option<String>,
+    forward_source_headers: bool,
+    templates: &IndexMap<String, Template>,
+) -> Option<OwnedHeaders> {
+    let mut headers = IndexMap::new();
+
+    if forward_source_headers {
+        if let Some(Value::Object(source_headers)) =
+            event.metadata().value().get(path!("kafka", "headers"))
+        {
+            insert_headers(&mut headers, source_headers, "%kafka.headers");
+        }
+    }
+
+    if let (Some(headers_key), Event::Log(log)) = (headers_key, event) {
+        match log.get(headers_key.as_str()) {
+            Some(Value::Object(headers_map)) => {
+                insert_headers(&mut headers, headers_map, headers_key)
             }
+            Some(_) => emit!(KafkaHeaderExtractionError {
+                header_field: headers_key
+            }),
+            None => {}
         }
-        None
+    }
+
+    for (key, template) in templates {
+        match template.render(event) {
+            Ok(value) => {
+                headers.insert(key.clone(), value);
+            }
+            Err(error) => emit!(TemplateRenderingError {
+                field: Some(key.as_str()),
+                drop_event: false,
+                error,
+            }),
+        }
+    }
+
+    (!headers.is_empty()).then(|| {
+        headers.iter().fold(
+            OwnedHeaders::new_with_capacity(headers.len()),
+            |owned_headers, (key, value)| {
+                owned_headers.insert(Header {
+                    key,
+                    value: Some(value.as_ref()),
+                })
+            },
+        )
     })
 }
 
+fn insert_headers(
+    headers: &mut IndexMap<String, Bytes>,
+    headers_map: &BTreeMap<String, Value>,
+    header_field: &str,
+) {
+    for (key, value) in headers_map {
+        if let Value::Bytes(value_bytes) = value {
+            headers.insert(key.clone(), value_bytes.clone());
+        } else {
+            emit!(KafkaHeaderExtractionError { header_field });
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
@@ -142,10 +188,44 @@ mod tests {
         let mut event = Event::Log(LogEvent::from("hello"));
         event.as_mut_log().insert(headers_key, header_values);
 
-        let headers = get_headers(&event, &Some(headers_key.to_string())).unwrap();
+        let headers = get_headers(
+            &event,
+            &Some(headers_key.to_string()),
+            false,
+            &IndexMap::new(),
+        )
+        .unwrap();
         assert_eq!(headers.get(0).key, "a-key");
         assert_eq!(headers.get(0).value.unwrap(), "a-value".as_bytes());
         assert_eq!(headers.get(1).key, "b-key");
         assert_eq!(headers.get(1).value.unwrap(), "b-value".as_bytes());
     }
+
+    #[test]
+    fn kafka_get_templated_and_source_headers() {
+        let mut event = Event::Log(LogEvent::from("hello"));
+        event.as_mut_log().insert("trace_id", "abc123");
+        event.metadata_mut().value_mut().insert(
+            path!("kafka", "headers"),
+            Value::from(BTreeMap::from([
+                ("trace-id".to_string(), Value::from("old")),
+                ("tenant".to_string(), Value::from("acme")),
+            ])),
+        );
+
+        let templates = IndexMap::from([(
+            "trace-id".to_string(),
+            Template::try_from("{{ trace_id }}").unwrap(),
+        )]);
+
+        let headers = get_headers(&event, &None, true, &templates).unwrap();
+        assert_eq!(headers.count(), 2);
+        assert_eq!(headers.get(0).key, "tenant");
+        assert_eq!(headers.get(0).value.unwrap(), "acme".as_bytes());
+        assert_eq!(headers.get(1).key, "trace-id");
+        assert_eq!(headers.get(1).value.unwrap(), "abc123".as_bytes());
+
+        let headers = get_headers(&event, &None, false, &templates).unwrap();
+        assert_eq!(headers.count(), 1);
+    }
 }
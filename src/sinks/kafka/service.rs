@@ -11,9 +11,7 @@ use rdkafka::{
 use tower::Service;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_core::{
-    internal_event::{
-        ByteSize, BytesSent, CountByteSize, InternalEventHandle as _, Protocol, Registered,
-    },
+    internal_event::{ByteSize, BytesSent, InternalEventHandle as _, Protocol, Registered},
     stream::DriverResponse,
 };
 
@@ -36,18 +34,34 @@ pub struct KafkaRequestMetadata {
     pub topic: String,
 }
 
-pub struct KafkaResponse {
-    event_byte_size: usize,
+impl KafkaRequest {
+    /// Builds the record of the request, which takes its headers.
+    pub(super) fn record(&mut self) -> FutureRecord<'_, [u8], [u8]> {
+        let mut record = FutureRecord::to(&self.metadata.topic).payload(self.body.as_ref());
+        if let Some(key) = &self.metadata.key {
+            record = record.key(&key[..]);
+        }
+        if let Some(timestamp) = self.metadata.timestamp_millis {
+            record = record.timestamp(timestamp);
+        }
+        if let Some(headers) = self.metadata.headers.take() {
+            record = record.headers(headers);
+        }
+        record
+    }
+
+    /// The number of bytes sent for the request.
+    pub(super) fn encoded_size(&self) -> usize {
+        self.body.len() + self.metadata.key.as_ref().map(|x| x.len()).unwrap_or(0)
+    }
 }
 
+pub struct KafkaResponse;
+
 impl DriverResponse for KafkaResponse {
     fn event_status(&self) -> EventStatus {
         EventStatus::Delivered
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(1, self.event_byte_size)
-    }
 }
 
 impl Finalizable for KafkaRequest {
@@ -58,7 +72,7 @@ impl Finalizable for KafkaRequest {
 
 impl MetaDescriptive for KafkaRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.request_metadata
+        self.request_metadata.clone()
     }
 }
 
@@ -86,31 +100,21 @@ impl Service<KafkaRequest> for KafkaService {
         Poll::Ready(Ok(()))
     }
 
-    fn call(&mut self, request: KafkaRequest) -> Self::Future {
+    fn call(&mut self, mut request: KafkaRequest) -> Self::Future {
         let this = self.clone();
 
         Box::pin(async move {
-            let event_byte_size = request.get_metadata().events_byte_size();
-
-            let mut record =
-                FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
-            if let Some(key) = &request.metadata.key {
-                record = record.key(&key[..]);
-            }
-            if let Some(timestamp) = request.metadata.timestamp_millis {
-                record = record.timestamp(timestamp);
-            }
-            if let Some(headers) = request.metadata.headers {
-                record = record.headers(headers);
-            }
+            let encoded_size = request.encoded_size();
 
             // rdkafka will internally retry forever if the queue is full
-            match this.kafka_producer.send(record, Timeout::Never).await {
+            match this
+                .kafka_producer
+                .send(request.record(), Timeout::Never)
+                .await
+            {
                 Ok((_partition, _offset)) => {
-                    this.bytes_sent.emit(ByteSize(
-                        request.body.len() + request.metadata.key.map(|x| x.len()).unwrap_or(0),
-                    ));
-                    Ok(KafkaResponse { event_byte_size })
+                    this.bytes_sent.emit(ByteSize(encoded_size));
+                    Ok(KafkaResponse)
                 }
                 Err((kafka_err, _original_record)) => Err(kafka_err),
             }
@@ -2,32 +2,48 @@ use std::convert::TryFrom;
 
 use async_trait::async_trait;
 use futures::{future, stream::BoxStream, StreamExt};
+use indexmap::IndexMap;
 use rdkafka::{
     consumer::{BaseConsumer, Consumer},
-    error::KafkaError,
-    producer::FutureProducer,
+    error::{KafkaError, KafkaResult, RDKafkaErrorCode},
+    producer::{DeliveryFuture, FutureProducer, Producer},
     ClientConfig,
 };
 use snafu::{ResultExt, Snafu};
 use tokio::time::Duration;
 use tower::limit::ConcurrencyLimit;
-use vector_core::config::log_schema;
+use vector_core::{
+    config::log_schema,
+    internal_event::{
+        ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle as _, Output, Protocol,
+    },
+};
 
-use super::config::{KafkaRole, KafkaSinkConfig};
+use super::config::{KafkaRole, KafkaSinkConfig, KafkaTransactionConfig};
 use crate::{
     codecs::{Encoder, Transformer},
-    event::{Event, LogEvent},
+    event::{Event, EventFinalizers, EventStatus, Finalizable, LogEvent},
+    internal_events::KafkaTransactionError,
     kafka::KafkaStatisticsContext,
     sinks::{
         kafka::{
-            config::QUEUED_MIN_MESSAGES, request_builder::KafkaRequestBuilder,
-            service::KafkaService,
+            config::QUEUED_MIN_MESSAGES,
+            request_builder::KafkaRequestBuilder,
+            service::{KafkaRequest, KafkaService},
         },
-        util::{builder::SinkBuilderExt, StreamSink},
+        util::{builder::SinkBuilderExt, retries::ExponentialBackoff, StreamSink},
     },
     template::{Template, TemplateParseError},
 };
 
+type KafkaProducer = FutureProducer<KafkaStatisticsContext>;
+
+/// How long to wait for the queue of the producer to have room for a record, when it's full.
+const QUEUE_FULL_DELAY: Duration = Duration::from_millis(100);
+
+/// The longest to wait before trying again to commit a transaction.
+const COMMIT_MAX_DELAY: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Snafu)]
 pub(super) enum BuildError {
     #[snafu(display("creating kafka producer failed: {}", source))]
@@ -39,15 +55,16 @@ pub(super) enum BuildError {
 pub struct KafkaSink {
     transformer: Transformer,
     encoder: Encoder<()>,
-    service: KafkaService,
+    producer: KafkaProducer,
     topic: Template,
     key_field: Option<String>,
     headers_key: Option<String>,
+    headers: IndexMap<String, Template>,
+    forward_source_headers: bool,
+    transaction: Option<KafkaTransactionConfig>,
 }
 
-pub(crate) fn create_producer(
-    client_config: ClientConfig,
-) -> crate::Result<FutureProducer<KafkaStatisticsContext>> {
+pub(crate) fn create_producer(client_config: ClientConfig) -> crate::Result<KafkaProducer> {
     let producer = client_config
         .create_with_context(KafkaStatisticsContext)
         .context(KafkaCreateFailedSnafu)?;
@@ -64,26 +81,38 @@ impl KafkaSink {
 
         Ok(KafkaSink {
             headers_key: config.headers_key,
+            headers: config.headers,
+            forward_source_headers: config.forward_source_headers,
             transformer,
             encoder,
-            service: KafkaService::new(producer),
+            producer,
             topic: Template::try_from(config.topic).context(TopicTemplateSnafu)?,
             key_field: config.key_field,
+            transaction: config.transaction,
         })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
-        // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
-        let service = ConcurrencyLimit::new(self.service, QUEUED_MIN_MESSAGES as usize);
-        let mut request_builder = KafkaRequestBuilder {
+        let request_builder = KafkaRequestBuilder {
             key_field: self.key_field,
             headers_key: self.headers_key,
+            headers: self.headers,
+            forward_source_headers: self.forward_source_headers,
             topic_template: self.topic,
             transformer: self.transformer,
             encoder: self.encoder,
             log_schema: log_schema(),
         };
+        if let Some(transaction) = self.transaction {
+            return run_transactional(self.producer, transaction, request_builder, input).await;
+        }
 
+        // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
+        let service = ConcurrencyLimit::new(
+            KafkaService::new(self.producer),
+            QUEUED_MIN_MESSAGES as usize,
+        );
+        let mut request_builder = request_builder;
         input
             .filter_map(|event|
                 // request_builder is fallible but the places it can fail are emitting
@@ -95,6 +124,190 @@ impl KafkaSink {
     }
 }
 
+/// A record produced in the current transaction.
+struct Produced {
+    delivery: DeliveryFuture,
+    finalizers: EventFinalizers,
+    event_byte_size: usize,
+    encoded_size: usize,
+}
+
+/// Runs `f` with the producer on a blocking thread, as the transactional calls of rdkafka block
+/// until the brokers answer, and committing flushes the records of the transaction.
+async fn blocking<T, F>(producer: &KafkaProducer, f: F) -> KafkaResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&KafkaProducer) -> KafkaResult<T> + Send + 'static,
+{
+    let producer = producer.clone();
+    tokio::task::spawn_blocking(move || f(&producer))
+        .await
+        .expect("transactional call of the producer panicked")
+}
+
+/// Enqueues the record of `request` in the producer, waiting for room in its queue when it's full.
+///
+/// The event of records the producer refuses is rejected.
+async fn produce(producer: &KafkaProducer, mut request: KafkaRequest) -> KafkaResult<Produced> {
+    let event_byte_size = request.request_metadata.events_byte_size();
+    let encoded_size = request.encoded_size();
+    let finalizers = request.take_finalizers();
+
+    let mut record = request.record();
+    let delivery = loop {
+        match producer.send_result(record) {
+            Ok(delivery) => break delivery,
+            Err((KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull), returned)) => {
+                record = returned;
+                tokio::time::sleep(QUEUE_FULL_DELAY).await;
+            }
+            Err((error, _)) => {
+                finalizers.update_status(EventStatus::Rejected);
+                return Err(error);
+            }
+        }
+    };
+
+    Ok(Produced {
+        delivery,
+        finalizers,
+        event_byte_size,
+        encoded_size,
+    })
+}
+
+/// Writes events in transactions, committed every `commit_interval_ms`, acknowledging them once
+/// the transaction holding their records is committed.
+///
+/// Transactions failing to commit, or holding a record the producer refuses, are aborted, and their
+/// events are errored, so that sources supporting acknowledgements can deliver them again, while
+/// the records of the aborted transaction are never read by consumers of committed records. The
+/// sink only stops when the producer can't abort transactions anymore.
+async fn run_transactional(
+    producer: KafkaProducer,
+    transaction: KafkaTransactionConfig,
+    mut request_builder: KafkaRequestBuilder,
+    input: BoxStream<'_, Event>,
+) -> Result<(), ()> {
+    let timeout = Duration::from_millis(transaction.timeout_ms);
+    // Fences off the previous producer with the same transactional identifier, aborting the
+    // transaction it left open.
+    if let Err(error) = blocking(&producer, move |producer| {
+        producer.init_transactions(timeout)
+    })
+    .await
+    {
+        emit!(KafkaTransactionError { error, count: 0 });
+        return Err(());
+    }
+
+    let bytes_sent = register!(BytesSent::from(Protocol("kafka".into())));
+    let events_sent = register!(EventsSent::from(Output(None)));
+
+    let mut input = input.fuse();
+    let mut commit_interval =
+        tokio::time::interval(Duration::from_millis(transaction.commit_interval_ms));
+    let mut produced: Vec<Produced> = Vec::new();
+    loop {
+        let done = tokio::select! {
+            event = input.next() => match event {
+                Some(event) => {
+                    let request = match request_builder.build_request(event) {
+                        Some(request) => request,
+                        // `build_request` emits the errors of events it drops.
+                        None => continue,
+                    };
+                    if produced.is_empty() {
+                        if let Err(error) = producer.begin_transaction() {
+                            emit!(KafkaTransactionError { error, count: 1 });
+                            return Err(());
+                        }
+                    }
+                    match produce(&producer, request).await {
+                        Ok(record) => produced.push(record),
+                        Err(error) => {
+                            emit!(KafkaTransactionError {
+                                error,
+                                count: produced.len() + 1,
+                            });
+                            let records = std::mem::take(&mut produced);
+                            if abort(&producer, timeout, records).await.is_err() {
+                                return Err(());
+                            }
+                        }
+                    }
+                    continue;
+                }
+                None => true,
+            },
+            _ = commit_interval.tick() => false,
+        };
+
+        if !produced.is_empty() {
+            let records = std::mem::take(&mut produced);
+            let count = records.len();
+            match commit(&producer, timeout).await {
+                Ok(()) => {
+                    for record in records {
+                        // Committing waits for every record of the transaction to be delivered.
+                        let _ = record.delivery.await;
+                        record.finalizers.update_status(EventStatus::Delivered);
+                        events_sent.emit(CountByteSize(1, record.event_byte_size));
+                        bytes_sent.emit(ByteSize(record.encoded_size));
+                    }
+                }
+                Err(error) => {
+                    emit!(KafkaTransactionError { error, count });
+                    if abort(&producer, timeout, records).await.is_err() {
+                        return Err(());
+                    }
+                }
+            }
+        }
+
+        if done {
+            return Ok(());
+        }
+    }
+}
+
+/// Aborts the current transaction, erroring the events of its `records`.
+async fn abort(
+    producer: &KafkaProducer,
+    timeout: Duration,
+    records: Vec<Produced>,
+) -> Result<(), ()> {
+    for record in records {
+        record.finalizers.update_status(EventStatus::Errored);
+    }
+    blocking(producer, move |producer| {
+        producer.abort_transaction(timeout)
+    })
+    .await
+    .map_err(|error| emit!(KafkaTransactionError { error, count: 0 }))
+}
+
+/// Commits the current transaction, trying again with a backoff as long as its failures are
+/// retriable.
+async fn commit(producer: &KafkaProducer, timeout: Duration) -> KafkaResult<()> {
+    let mut backoff = ExponentialBackoff::from_millis(2)
+        .factor(250)
+        .max_delay(COMMIT_MAX_DELAY);
+    loop {
+        match blocking(producer, move |producer| {
+            producer.commit_transaction(timeout)
+        })
+        .await
+        {
+            Err(KafkaError::Transaction(error)) if error.is_retriable() => {
+                warn!(message = "Retrying to commit transaction.", %error);
+                tokio::time::sleep(backoff.next().unwrap()).await;
+            }
+            result => return result,
+        }
+    }
+}
+
 pub(crate) async fn healthcheck(config: KafkaSinkConfig) -> crate::Result<()> {
     trace!("Healthcheck started.");
     let client = config.to_rdkafka(KafkaRole::Consumer).unwrap();
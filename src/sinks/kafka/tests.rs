@@ -21,11 +21,11 @@ mod integration_test {
     use vector_core::event::{BatchNotifier, BatchStatus};
 
     use crate::{
-        event::Value,
+        event::{Event, LogEvent, Value},
         kafka::{KafkaAuthConfig, KafkaCompression, KafkaSaslConfig},
         sinks::{
             kafka::{
-                config::{KafkaRole, KafkaSinkConfig},
+                config::{KafkaRole, KafkaSinkConfig, KafkaTransactionConfig},
                 sink::KafkaSink,
                 *,
             },
@@ -65,6 +65,10 @@ mod integration_test {
             message_timeout_ms: 300000,
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            headers: Default::default(),
+            forward_source_headers: false,
+            idempotence: false,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config).await.unwrap();
@@ -120,6 +124,10 @@ mod integration_test {
             batch,
             librdkafka_options,
             headers_key: None,
+            headers: Default::default(),
+            forward_source_headers: false,
+            idempotence: false,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         config.clone().to_rdkafka(KafkaRole::Consumer)?;
@@ -230,6 +238,57 @@ mod integration_test {
         .await;
     }
 
+    #[tokio::test]
+    async fn kafka_transaction_aborted_on_refused_record() {
+        crate::test_util::trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let config = KafkaSinkConfig {
+            bootstrap_servers: kafka_address(9091),
+            topic: topic.clone(),
+            key_field: None,
+            encoding: TextSerializerConfig::new().into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: 60000,
+            message_timeout_ms: 300000,
+            librdkafka_options: HashMap::from([
+                ("batch.size".to_owned(), "1000".to_owned()),
+                ("message.max.bytes".to_owned(), "1000".to_owned()),
+            ]),
+            headers_key: None,
+            headers: Default::default(),
+            forward_source_headers: false,
+            idempotence: false,
+            transaction: Some(KafkaTransactionConfig {
+                id: random_string(10),
+                commit_interval_ms: 60000,
+                timeout_ms: 60000,
+            }),
+            acknowledgements: Default::default(),
+        };
+
+        let (before, before_receiver) = BatchNotifier::new_with_receiver();
+        let (refused, refused_receiver) = BatchNotifier::new_with_receiver();
+        let (after, after_receiver) = BatchNotifier::new_with_receiver();
+        let events = vec![
+            Event::from(LogEvent::from("before").with_batch_notifier(&before)),
+            Event::from(LogEvent::from(random_string(2000)).with_batch_notifier(&refused)),
+            Event::from(LogEvent::from("after").with_batch_notifier(&after)),
+        ];
+        drop((before, refused, after));
+
+        let sink = KafkaSink::new(config).unwrap();
+        let sink = VectorSink::from_event_streamsink(sink);
+        sink.run_events(events).await.expect("Running sink failed");
+
+        // The transaction holding the refused record is aborted, and the sink keeps writing.
+        assert_eq!(before_receiver.await, BatchStatus::Errored);
+        assert_eq!(refused_receiver.await, BatchStatus::Rejected);
+        assert_eq!(after_receiver.await, BatchStatus::Delivered);
+    }
+
     async fn kafka_happy_path(
         server: String,
         sasl: Option<KafkaSaslConfig>,
@@ -251,6 +310,10 @@ mod integration_test {
             message_timeout_ms: 300000,
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
+            headers: Default::default(),
+            forward_source_headers: false,
+            idempotence: false,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
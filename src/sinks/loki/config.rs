@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    num::{NonZeroU64, NonZeroU8},
+};
 
 use futures::future::FutureExt;
 use vector_config::configurable_component;
@@ -7,7 +10,7 @@ use super::{healthcheck::healthcheck, sink::LokiSink};
 use crate::{
     codecs::EncodingConfig,
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
-    http::{Auth, HttpClient, MaybeAuth},
+    http::{build_authenticator, Auth, HttpClient, MaybeAuth},
     sinks::{
         util::{BatchConfig, Compression, SinkBatchSettings, TowerRequestConfig, UriSerde},
         VectorSink,
@@ -99,6 +102,23 @@ pub struct LokiConfig {
     #[serde(default = "crate::serde::default_false")]
     pub remove_label_fields: bool,
 
+    /// A set of structured metadata that is attached to each event.
+    ///
+    /// Structured metadata are key/value pairs that aren't indexed by Loki, which makes them a
+    /// better fit than labels for high cardinality attributes, such as trace IDs.
+    ///
+    /// Both keys and values are templateable, and keys can be suffixed with a “*” to allow the
+    /// expansion of objects into multiple pairs, as with `labels`.
+    ///
+    /// Note: Structured metadata are only supported by Loki 2.9 or later, and must be enabled
+    /// with `allow_structured_metadata` in its limits.
+    #[serde(default)]
+    pub structured_metadata: HashMap<Template, Template>,
+
+    /// Whether or not to delete fields from the event when they are used as structured metadata.
+    #[serde(default = "crate::serde::default_false")]
+    pub remove_structured_metadata_fields: bool,
+
     /// Whether or not to remove the timestamp from the event payload.
     ///
     /// The timestamp will still be sent as event metadata for Loki to use for indexing.
@@ -113,6 +133,19 @@ pub struct LokiConfig {
     #[serde(default)]
     pub out_of_order_action: OutOfOrderAction,
 
+    /// How far behind the latest timestamp of its stream, in seconds, the timestamp of an event can
+    /// be and still be sent as is.
+    ///
+    /// Events further behind are handled according to `out_of_order_action`. Loki 2.4.0 and newer
+    /// accept events out of order within half of its `max_chunk_age`, which is an hour by default,
+    /// but reject those further behind with `400` responses.
+    #[serde(default)]
+    pub out_of_order_window_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub shard_streams: ShardStreamsConfig,
+
     #[configurable(derived)]
     pub auth: Option<Auth>,
 
@@ -175,6 +208,42 @@ pub enum OutOfOrderAction {
     Accept,
 }
 
+/// Automatic sharding of streams.
+///
+/// Loki limits the rate at which each stream, which is each unique set of labels, can be written
+/// to, and rejects pushes exceeding it with `429` responses. When enabled, streams whose rate
+/// exceeds `rate_limit_bytes` are split into several streams, with a label holding the shard of
+/// each event.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(default)]
+pub struct ShardStreamsConfig {
+    /// Whether or not to shard streams whose rate exceeds `rate_limit_bytes`.
+    pub enabled: bool,
+
+    /// The rate of a stream, in bytes per second, above which it is sharded.
+    ///
+    /// This should be below the `per_stream_rate_limit` of Loki, which is 3MB by default.
+    pub rate_limit_bytes: NonZeroU64,
+
+    /// The maximum number of shards that a stream is split into.
+    pub max_shards: NonZeroU8,
+
+    /// The name of the label holding the shard of events.
+    pub label: String,
+}
+
+impl Default for ShardStreamsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rate_limit_bytes: NonZeroU64::new(1_536 * 1024).unwrap(),
+            max_shards: NonZeroU8::new(16).unwrap(),
+            label: "__stream_shard__".to_string(),
+        }
+    }
+}
+
 impl GenerateConfig for LokiConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(
@@ -210,6 +279,12 @@ impl SinkConfig for LokiConfig {
             }
         }
 
+        if self.shard_streams.enabled
+            && !valid_label_name(&Template::try_from(self.shard_streams.label.as_str())?)
+        {
+            return Err(format!("Invalid shard label name {:?}", self.shard_streams.label).into());
+        }
+
         let client = self.build_client(cx)?;
 
         let config = LokiConfig {
@@ -217,9 +292,11 @@ impl SinkConfig for LokiConfig {
             ..self.clone()
         };
 
-        let sink = LokiSink::new(config.clone(), client.clone())?;
+        let auth = build_authenticator(config.auth.as_ref(), &client).await?;
+
+        let sink = LokiSink::new(config.clone(), client.clone(), auth.clone())?;
 
-        let healthcheck = healthcheck(config, client).boxed();
+        let healthcheck = healthcheck(config, client, auth).boxed();
 
         Ok((VectorSink::from_event_streamsink(sink), healthcheck))
     }
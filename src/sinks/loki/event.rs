@@ -3,9 +3,10 @@ use std::{collections::HashMap, io};
 use bytes::Bytes;
 use serde::{ser::SerializeSeq, Serialize};
 use vector_buffers::EventCount;
+use vector_common::request_metadata::{EventOrigin, GroupedCountByteSize};
 use vector_core::{
     event::{EventFinalizers, Finalizable},
-    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+    ByteSizeOf, EstimatedJsonEncodedSizeOf, GroupedSizeOf,
 };
 
 use crate::sinks::util::encoding::{write_all, Encoder};
@@ -43,6 +44,7 @@ impl Encoder<Vec<LokiRecord>> for LokiBatchEncoder {
                         loki_logproto::util::Entry(
                             event.timestamp,
                             String::from_utf8_lossy(&event.event).into_owned(),
+                            event.structured_metadata.clone(),
                         )
                     })
                     .collect();
@@ -81,11 +83,17 @@ impl From<Vec<LokiRecord>> for LokiBatch {
 pub struct LokiEvent {
     pub timestamp: i64,
     pub event: Bytes,
+    /// The key/value pairs attached to the entry, which aren't indexed by Loki.
+    pub structured_metadata: Labels,
 }
 
 impl ByteSizeOf for LokiEvent {
     fn allocated_bytes(&self) -> usize {
-        self.timestamp.allocated_bytes() + self.event.allocated_bytes()
+        self.timestamp.allocated_bytes()
+            + self.event.allocated_bytes()
+            + self.structured_metadata.iter().fold(0, |res, item| {
+                res + item.0.allocated_bytes() + item.1.allocated_bytes()
+            })
     }
 }
 
@@ -96,11 +104,28 @@ impl EstimatedJsonEncodedSizeOf for LokiEvent {
         static COLON_SIZE: usize = 1;
         static QUOTES_SIZE: usize = 2;
 
+        let structured_metadata_size = if self.structured_metadata.is_empty() {
+            0
+        } else {
+            COLON_SIZE
+                + BRACKETS_SIZE
+                + self
+                    .structured_metadata
+                    .iter()
+                    .map(|(key, value)| {
+                        key.estimated_json_encoded_size_of()
+                            + COLON_SIZE
+                            + value.estimated_json_encoded_size_of()
+                    })
+                    .sum::<usize>()
+        };
+
         BRACKETS_SIZE
             + QUOTES_SIZE
             + self.timestamp.estimated_json_encoded_size_of()
             + COLON_SIZE
             + self.event.estimated_json_encoded_size_of()
+            + structured_metadata_size
     }
 }
 
@@ -109,10 +134,23 @@ impl Serialize for LokiEvent {
     where
         S: serde::Serializer,
     {
-        let mut seq = serializer.serialize_seq(Some(2))?;
+        let len = if self.structured_metadata.is_empty() {
+            2
+        } else {
+            3
+        };
+        let mut seq = serializer.serialize_seq(Some(len))?;
         seq.serialize_element(&self.timestamp.to_string())?;
         let event = String::from_utf8_lossy(&self.event);
         seq.serialize_element(&event)?;
+        if !self.structured_metadata.is_empty() {
+            let structured_metadata: HashMap<&str, &str> = self
+                .structured_metadata
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect();
+            seq.serialize_element(&structured_metadata)?;
+        }
         seq.end()
     }
 }
@@ -123,6 +161,8 @@ pub struct LokiRecord {
     pub labels: Labels,
     pub event: LokiEvent,
     pub finalizers: EventFinalizers,
+    /// The origin of the event the record was encoded from.
+    pub origin: EventOrigin,
 }
 
 impl ByteSizeOf for LokiRecord {
@@ -141,6 +181,16 @@ impl EstimatedJsonEncodedSizeOf for LokiRecord {
     }
 }
 
+impl GroupedSizeOf for LokiRecord {
+    fn add_grouped_sizes(&self, grouped_sizes: &mut GroupedCountByteSize) {
+        grouped_sizes.add(
+            self.origin.clone(),
+            1,
+            self.estimated_json_encoded_size_of(),
+        );
+    }
+}
+
 impl EventCount for LokiRecord {
     fn event_count(&self) -> usize {
         // A Loki record is mapped one-to-one with an event.
@@ -1,10 +1,11 @@
 use super::config::LokiConfig;
-use crate::http::HttpClient;
+use crate::http::{HttpAuthenticator, HttpClient};
 
 async fn fetch_status(
     endpoint: &str,
     config: &LokiConfig,
     client: &HttpClient,
+    auth: &Option<HttpAuthenticator>,
 ) -> crate::Result<http::StatusCode> {
     let endpoint = config.endpoint.append_path(endpoint)?;
 
@@ -12,19 +13,23 @@ async fn fetch_status(
         .body(hyper::Body::empty())
         .expect("Building request never fails.");
 
-    if let Some(auth) = &config.auth {
+    if let Some(auth) = auth {
         auth.apply(&mut req);
     }
 
     Ok(client.send(req).await?.status())
 }
 
-pub async fn healthcheck(config: LokiConfig, client: HttpClient) -> crate::Result<()> {
-    let status = match fetch_status("ready", &config, &client).await? {
+pub async fn healthcheck(
+    config: LokiConfig,
+    client: HttpClient,
+    auth: Option<HttpAuthenticator>,
+) -> crate::Result<()> {
+    let status = match fetch_status("ready", &config, &client, &auth).await? {
         // Issue https://github.com/vectordotdev/vector/issues/6463
         http::StatusCode::NOT_FOUND => {
             debug!("Endpoint `/ready` not found. Retrying healthcheck with top level query.");
-            fetch_status("", &config, &client).await?
+            fetch_status("", &config, &client, &auth).await?
         }
         status => status,
     };
@@ -9,14 +9,13 @@ use tracing::Instrument;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_core::{
     event::{EventFinalizers, EventStatus, Finalizable},
-    internal_event::CountByteSize,
     stream::DriverResponse,
 };
 
 use crate::sinks::loki::config::{CompressionConfigAdapter, ExtendedCompression};
 use crate::{
-    http::{Auth, HttpClient},
-    sinks::util::{retries::RetryLogic, UriSerde},
+    http::{HttpAuthenticator, HttpClient},
+    sinks::util::{adaptive_concurrency::ConcurrencyPartition, retries::RetryLogic, UriSerde},
 };
 
 #[derive(Clone)]
@@ -47,26 +46,13 @@ pub enum LokiError {
     HttpError { error: crate::http::HttpError },
 }
 
-#[derive(Debug, Snafu)]
-pub struct LokiResponse {
-    metadata: RequestMetadata,
-}
+#[derive(Debug)]
+pub struct LokiResponse;
 
 impl DriverResponse for LokiResponse {
     fn event_status(&self) -> EventStatus {
         EventStatus::Delivered
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(
-            self.metadata.event_count(),
-            self.metadata.events_estimated_json_encoded_byte_size(),
-        )
-    }
-
-    fn bytes_sent(&self) -> Option<usize> {
-        Some(self.metadata.request_encoded_size())
-    }
 }
 
 #[derive(Clone)]
@@ -84,9 +70,15 @@ impl Finalizable for LokiRequest {
     }
 }
 
+impl ConcurrencyPartition for LokiRequest {
+    fn concurrency_partition(&self) -> Option<String> {
+        self.tenant_id.clone()
+    }
+}
+
 impl MetaDescriptive for LokiRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
 
@@ -94,6 +86,7 @@ impl MetaDescriptive for LokiRequest {
 pub struct LokiService {
     endpoint: UriSerde,
     client: HttpClient,
+    auth: Option<HttpAuthenticator>,
 }
 
 impl LokiService {
@@ -101,11 +94,15 @@ impl LokiService {
         client: HttpClient,
         endpoint: UriSerde,
         path: String,
-        auth: Option<Auth>,
+        auth: Option<HttpAuthenticator>,
     ) -> crate::Result<Self> {
-        let endpoint = endpoint.append_path(&path)?.with_auth(auth);
+        let endpoint = endpoint.append_path(&path)?;
 
-        Ok(Self { client, endpoint })
+        Ok(Self {
+            endpoint,
+            client,
+            auth,
+        })
     }
 }
 
@@ -127,8 +124,6 @@ impl Service<LokiRequest> for LokiService {
         };
         let mut req = http::Request::post(&self.endpoint.uri).header("Content-Type", content_type);
 
-        let metadata = request.get_metadata();
-
         if let Some(tenant_id) = request.tenant_id {
             req = req.header("X-Scope-OrgID", tenant_id);
         }
@@ -140,7 +135,7 @@ impl Service<LokiRequest> for LokiService {
         let body = hyper::Body::from(request.payload);
         let mut req = req.body(body).unwrap();
 
-        if let Some(auth) = &self.endpoint.auth {
+        if let Some(auth) = &self.auth {
             auth.apply(&mut req);
         }
 
@@ -152,7 +147,7 @@ impl Service<LokiRequest> for LokiService {
                     let status = response.status();
 
                     if status.is_success() {
-                        Ok(LokiResponse { metadata })
+                        Ok(LokiResponse)
                     } else {
                         Err(LokiError::ServerError { code: status })
                     }
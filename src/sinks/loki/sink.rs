@@ -1,4 +1,9 @@
-use std::{collections::HashMap, num::NonZeroUsize};
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use bytes::{Bytes, BytesMut};
 use futures::{stream::BoxStream, StreamExt};
@@ -16,7 +21,7 @@ use vector_core::{
 };
 
 use super::{
-    config::{LokiConfig, OutOfOrderAction},
+    config::{LokiConfig, OutOfOrderAction, ShardStreamsConfig},
     event::{LokiBatchEncoder, LokiEvent, LokiRecord, PartitionKey},
     service::{LokiRequest, LokiRetryLogic, LokiService},
 };
@@ -28,7 +33,7 @@ use crate::sinks::{
 use crate::{
     codecs::{Encoder, Transformer},
     config::log_schema,
-    http::{get_http_scheme_from_uri, HttpClient},
+    http::{get_http_scheme_from_uri, HttpAuthenticator, HttpClient},
     internal_events::{
         LokiEventUnlabeled, LokiOutOfOrderEventDropped, LokiOutOfOrderEventRewritten,
         SinkRequestBuildError, TemplateRenderingError,
@@ -36,7 +41,7 @@ use crate::{
     sinks::util::{
         builder::SinkBuilderExt,
         request_builder::EncodeResult,
-        service::{ServiceBuilderExt, Svc},
+        service::{PartitionedSvc, ServiceBuilderExt},
         Compression, RequestBuilder,
     },
     template::Template,
@@ -86,6 +91,7 @@ impl Partitioner for RecordPartitioner {
 pub struct LokiRequestBuilder {
     compression: CompressionConfigAdapter,
     encoder: LokiBatchEncoder,
+    endpoint: Arc<str>,
 }
 
 #[derive(Debug, Snafu)]
@@ -147,7 +153,7 @@ impl RequestBuilder<(PartitionKey, Vec<LokiRecord>)> for LokiRequestBuilder {
             finalizers,
             payload: payload.into_payload(),
             tenant_id,
-            metadata,
+            metadata: metadata.with_endpoint(Arc::clone(&self.endpoint)),
         }
     }
 }
@@ -159,14 +165,21 @@ pub(super) struct EventEncoder {
     encoder: Encoder<()>,
     labels: HashMap<Template, Template>,
     remove_label_fields: bool,
+    structured_metadata: HashMap<Template, Template>,
+    remove_structured_metadata_fields: bool,
     remove_timestamp: bool,
 }
 
 impl EventEncoder {
-    fn build_labels(&self, event: &Event) -> Vec<(String, String)> {
+    /// Renders the key/value pairs of `templates` for `event`, expanding the objects of the keys
+    /// suffixed with `*` into a pair per field.
+    fn render_pairs(
+        templates: &HashMap<Template, Template>,
+        event: &Event,
+    ) -> Vec<(String, String)> {
         let mut vec: Vec<(String, String)> = Vec::new();
 
-        for (key_template, value_template) in self.labels.iter() {
+        for (key_template, value_template) in templates.iter() {
             if let (Ok(key), Ok(value)) = (
                 key_template.render_string(event),
                 value_template.render_string(event),
@@ -192,13 +205,12 @@ impl EventEncoder {
         vec
     }
 
-    fn remove_label_fields(&self, event: &mut Event) {
-        if self.remove_label_fields {
-            for template in self.labels.values() {
-                if let Some(fields) = template.get_fields() {
-                    for field in fields {
-                        event.as_mut_log().remove(field.as_str());
-                    }
+    /// Removes the fields used by the values of `templates` from `event`.
+    fn remove_fields(templates: &HashMap<Template, Template>, event: &mut Event) {
+        for template in templates.values() {
+            if let Some(fields) = template.get_fields() {
+                for field in fields {
+                    event.as_mut_log().remove(field.as_str());
                 }
             }
         }
@@ -206,9 +218,16 @@ impl EventEncoder {
 
     pub(super) fn encode_event(&mut self, mut event: Event) -> Option<LokiRecord> {
         let tenant_id = self.key_partitioner.partition(&event);
+        let origin = event.origin();
         let finalizers = event.take_finalizers();
-        let mut labels = self.build_labels(&event);
-        self.remove_label_fields(&mut event);
+        let mut labels = Self::render_pairs(&self.labels, &event);
+        let structured_metadata = Self::render_pairs(&self.structured_metadata, &event);
+        if self.remove_label_fields {
+            Self::remove_fields(&self.labels, &mut event);
+        }
+        if self.remove_structured_metadata_fields {
+            Self::remove_fields(&self.structured_metadata, &mut event);
+        }
 
         let schema = log_schema();
         let timestamp_key = schema.timestamp_key();
@@ -240,9 +259,11 @@ impl EventEncoder {
             event: LokiEvent {
                 timestamp,
                 event: bytes.freeze(),
+                structured_metadata,
             },
             partition,
             finalizers,
+            origin,
         })
     }
 }
@@ -282,16 +303,84 @@ impl ByteSizeOf for FilteredRecord {
     }
 }
 
+/// How long the rate of streams is measured over, before their number of shards is updated.
+const SHARD_RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// The rate of a stream over the current window, and the shards it's split into.
+struct StreamRate {
+    window_start: Instant,
+    window_bytes: u64,
+    shards: u64,
+    next_shard: u64,
+}
+
+/// Splits the streams whose rate exceeds the limit into several streams, by adding a label holding
+/// the shard of their events, which are distributed between shards in turn.
+struct StreamSharder {
+    streams: HashMap<PartitionKey, StreamRate>,
+    label: String,
+    rate_limit_bytes: u64,
+    max_shards: u64,
+}
+
+impl StreamSharder {
+    fn new(config: ShardStreamsConfig) -> Self {
+        Self {
+            streams: HashMap::new(),
+            label: config.label,
+            rate_limit_bytes: config.rate_limit_bytes.get(),
+            max_shards: u64::from(config.max_shards.get()),
+        }
+    }
+
+    fn shard_record(&mut self, mut record: LokiRecord, now: Instant) -> LokiRecord {
+        let stream = self
+            .streams
+            .entry(record.partition.clone())
+            .or_insert_with(|| StreamRate {
+                window_start: now,
+                window_bytes: 0,
+                shards: 1,
+                next_shard: 0,
+            });
+
+        let elapsed = now.duration_since(stream.window_start);
+        if elapsed >= SHARD_RATE_WINDOW {
+            let rate = stream.window_bytes as f64 / elapsed.as_secs_f64();
+            let shards = (rate / self.rate_limit_bytes as f64).ceil() as u64;
+            stream.shards = shards.clamp(1, self.max_shards);
+            stream.window_start = now;
+            stream.window_bytes = 0;
+        }
+        stream.window_bytes += record.event.event.len() as u64;
+
+        if stream.shards > 1 {
+            let shard = stream.next_shard % stream.shards;
+            stream.next_shard = stream.next_shard.wrapping_add(1);
+            record.labels.push((self.label.clone(), shard.to_string()));
+            record.partition =
+                PartitionKey::new(record.partition.tenant_id.clone(), &mut record.labels);
+        }
+        record
+    }
+}
+
 struct RecordFilter {
     timestamps: HashMap<PartitionKey, i64>,
     out_of_order_action: OutOfOrderAction,
+    /// How far behind the latest timestamp of their stream, in nanoseconds, events can be and still
+    /// be sent as is.
+    out_of_order_window: i64,
 }
 
 impl RecordFilter {
-    fn new(out_of_order_action: OutOfOrderAction) -> Self {
+    fn new(out_of_order_action: OutOfOrderAction, out_of_order_window_secs: u64) -> Self {
         Self {
             timestamps: HashMap::new(),
             out_of_order_action,
+            out_of_order_window: i64::try_from(out_of_order_window_secs)
+                .unwrap_or(i64::MAX)
+                .saturating_mul(1_000_000_000),
         }
     }
 }
@@ -299,7 +388,7 @@ impl RecordFilter {
 impl RecordFilter {
     pub fn filter_record(&mut self, mut record: LokiRecord) -> Option<FilteredRecord> {
         if let Some(latest) = self.timestamps.get_mut(&record.partition) {
-            if record.event.timestamp < *latest {
+            if record.event.timestamp < latest.saturating_sub(self.out_of_order_window) {
                 match self.out_of_order_action {
                     OutOfOrderAction::Drop => None,
                     OutOfOrderAction::RewriteTimestamp => {
@@ -309,7 +398,7 @@ impl RecordFilter {
                     OutOfOrderAction::Accept => Some(FilteredRecord::valid(record)),
                 }
             } else {
-                *latest = record.event.timestamp;
+                *latest = (*latest).max(record.event.timestamp);
                 Some(FilteredRecord::valid(record))
             }
         } else {
@@ -325,13 +414,19 @@ pub struct LokiSink {
     pub(super) encoder: EventEncoder,
     batch_settings: BatcherSettings,
     out_of_order_action: OutOfOrderAction,
-    service: Svc<LokiService, LokiRetryLogic>,
+    out_of_order_window_secs: u64,
+    shard_streams: ShardStreamsConfig,
+    service: PartitionedSvc<LokiService, LokiRetryLogic>,
     protocol: &'static str,
 }
 
 impl LokiSink {
     #[allow(clippy::missing_const_for_fn)] // const cannot run destructor
-    pub fn new(config: LokiConfig, client: HttpClient) -> crate::Result<Self> {
+    pub fn new(
+        config: LokiConfig,
+        client: HttpClient,
+        auth: Option<HttpAuthenticator>,
+    ) -> crate::Result<Self> {
         let compression = config.compression;
 
         // if Vector is configured to allow events with out of order timestamps, then then we can
@@ -352,13 +447,14 @@ impl LokiSink {
         };
 
         let protocol = get_http_scheme_from_uri(&config.endpoint.uri);
+        let endpoint = config.endpoint.append_path(&config.path)?.uri.to_string();
         let service = tower::ServiceBuilder::new()
-            .settings(request_limits, LokiRetryLogic)
+            .partitioned_settings(request_limits, LokiRetryLogic)
             .service(LokiService::new(
                 client,
                 config.endpoint,
                 config.path,
-                config.auth,
+                auth,
             )?);
 
         let transformer = config.encoding.transformer();
@@ -375,6 +471,7 @@ impl LokiSink {
             request_builder: LokiRequestBuilder {
                 compression,
                 encoder: batch_encoder,
+                endpoint: endpoint.into(),
             },
             encoder: EventEncoder {
                 key_partitioner: KeyPartitioner::new(config.tenant_id),
@@ -382,10 +479,14 @@ impl LokiSink {
                 encoder,
                 labels: config.labels,
                 remove_label_fields: config.remove_label_fields,
+                structured_metadata: config.structured_metadata,
+                remove_structured_metadata_fields: config.remove_structured_metadata_fields,
                 remove_timestamp: config.remove_timestamp,
             },
             batch_settings: config.batch.into_batcher_settings()?,
             out_of_order_action: config.out_of_order_action,
+            out_of_order_window_secs: config.out_of_order_window_secs,
+            shard_streams: config.shard_streams,
             service,
             protocol,
         })
@@ -393,7 +494,11 @@ impl LokiSink {
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let mut encoder = self.encoder.clone();
-        let mut filter = RecordFilter::new(self.out_of_order_action);
+        let mut filter = RecordFilter::new(self.out_of_order_action, self.out_of_order_window_secs);
+        let mut sharder = self
+            .shard_streams
+            .enabled
+            .then(|| StreamSharder::new(self.shard_streams.clone()));
 
         // out_of_order_action's that require a complete ordering are limited to building 1 request
         // at a time
@@ -407,6 +512,10 @@ impl LokiSink {
         input
             .map(|event| encoder.encode_event(event))
             .filter_map(|event| async { event })
+            .map(|record| match sharder.as_mut() {
+                Some(sharder) => sharder.shard_record(record, Instant::now()),
+                None => record,
+            })
             .map(|record| filter.filter_record(record))
             .batched_partitioned(RecordPartitioner::default(), self.batch_settings)
             .filter_map(|(partition, batch)| async {
@@ -467,16 +576,24 @@ mod tests {
     use std::{
         collections::{BTreeMap, HashMap},
         convert::TryFrom,
+        num::{NonZeroU64, NonZeroU8},
+        time::{Duration, Instant},
     };
 
-    use codecs::JsonSerializer;
+    use codecs::{JsonSerializer, TextSerializer};
     use futures::stream::StreamExt;
     use vector_core::event::{Event, LogEvent, Value};
 
-    use super::{EventEncoder, KeyPartitioner, RecordFilter};
+    use super::{EventEncoder, KeyPartitioner, RecordFilter, StreamSharder};
     use crate::{
-        codecs::Encoder, config::log_schema, sinks::loki::config::OutOfOrderAction,
-        template::Template, test_util::random_lines,
+        codecs::Encoder,
+        config::log_schema,
+        sinks::loki::{
+            config::{OutOfOrderAction, ShardStreamsConfig},
+            event::LokiRecord,
+        },
+        template::Template,
+        test_util::random_lines,
     };
 
     #[test]
@@ -487,6 +604,8 @@ mod tests {
             encoder: Encoder::<()>::new(JsonSerializer::new().into()),
             labels: HashMap::default(),
             remove_label_fields: false,
+            structured_metadata: HashMap::default(),
+            remove_structured_metadata_fields: false,
             remove_timestamp: false,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
@@ -526,6 +645,8 @@ mod tests {
             encoder: Encoder::<()>::new(JsonSerializer::new().into()),
             labels,
             remove_label_fields: false,
+            structured_metadata: HashMap::default(),
+            remove_structured_metadata_fields: false,
             remove_timestamp: false,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
@@ -550,6 +671,47 @@ mod tests {
         assert_eq!(labels["test_key_two"], "baz".to_string());
     }
 
+    #[test]
+    fn encoder_with_structured_metadata() {
+        let mut structured_metadata = HashMap::default();
+        structured_metadata.insert(
+            Template::try_from("trace_id").unwrap(),
+            Template::try_from("{{ trace_id }}").unwrap(),
+        );
+        structured_metadata.insert(
+            Template::try_from("attr_*").unwrap(),
+            Template::try_from("{{ attributes }}").unwrap(),
+        );
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializer::new().into()),
+            labels: HashMap::default(),
+            remove_label_fields: false,
+            structured_metadata,
+            remove_structured_metadata_fields: true,
+            remove_timestamp: false,
+        };
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        let log = event.as_mut_log();
+        log.insert("trace_id", "abc123");
+
+        let mut attributes = BTreeMap::default();
+        attributes.insert("user".to_string(), Value::from("alice"));
+        log.insert("attributes", Value::from(attributes));
+
+        let record = encoder.encode_event(event).unwrap();
+        let line = String::from_utf8_lossy(&record.event.event);
+        assert!(!line.contains("trace_id"));
+        assert!(!line.contains("attributes"));
+
+        let structured_metadata: HashMap<String, String> =
+            record.event.structured_metadata.into_iter().collect();
+        assert_eq!(structured_metadata.len(), 2);
+        assert_eq!(structured_metadata["trace_id"], "abc123".to_string());
+        assert_eq!(structured_metadata["attr_user"], "alice".to_string());
+    }
+
     #[test]
     fn encoder_no_ts() {
         let mut encoder = EventEncoder {
@@ -558,6 +720,8 @@ mod tests {
             encoder: Encoder::<()>::new(JsonSerializer::new().into()),
             labels: HashMap::default(),
             remove_label_fields: false,
+            structured_metadata: HashMap::default(),
+            remove_structured_metadata_fields: false,
             remove_timestamp: true,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
@@ -586,6 +750,8 @@ mod tests {
             encoder: Encoder::<()>::new(JsonSerializer::new().into()),
             labels,
             remove_label_fields: true,
+            structured_metadata: HashMap::default(),
+            remove_structured_metadata_fields: false,
             remove_timestamp: false,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
@@ -605,6 +771,8 @@ mod tests {
             encoder: Encoder::<()>::new(JsonSerializer::new().into()),
             labels: HashMap::default(),
             remove_label_fields: false,
+            structured_metadata: HashMap::default(),
+            remove_structured_metadata_fields: false,
             remove_timestamp: false,
         };
         let base = chrono::Utc::now();
@@ -623,7 +791,7 @@ mod tests {
                 event
             })
             .collect::<Vec<_>>();
-        let mut filter = RecordFilter::new(OutOfOrderAction::Drop);
+        let mut filter = RecordFilter::new(OutOfOrderAction::Drop, 0);
         let stream = futures::stream::iter(events)
             .map(|event| encoder.encode_event(event))
             .filter_map(|event| async { event })
@@ -638,4 +806,82 @@ mod tests {
         }
         assert_eq!(result.len(), 17);
     }
+    fn encode_at(
+        encoder: &mut EventEncoder,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> LokiRecord {
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event
+            .as_mut_log()
+            .insert(log_schema().timestamp_key(), timestamp);
+        encoder.encode_event(event).unwrap()
+    }
+
+    #[test]
+    fn filter_out_of_order_window() {
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializer::new().into()),
+            labels: HashMap::default(),
+            remove_label_fields: false,
+            structured_metadata: HashMap::default(),
+            remove_structured_metadata_fields: false,
+            remove_timestamp: false,
+        };
+        let base = chrono::Utc::now();
+        let mut filter = RecordFilter::new(OutOfOrderAction::Drop, 10);
+
+        let latest = encode_at(&mut encoder, base);
+        assert!(filter.filter_record(latest).is_some());
+
+        let within = encode_at(&mut encoder, base - chrono::Duration::seconds(5));
+        let within = filter.filter_record(within).unwrap();
+        assert!(!within.rewritten);
+
+        let behind = encode_at(&mut encoder, base - chrono::Duration::seconds(20));
+        assert!(filter.filter_record(behind).is_none());
+    }
+
+    #[test]
+    fn shard_streams_over_rate_limit() {
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(TextSerializer::new().into()),
+            labels: HashMap::default(),
+            remove_label_fields: false,
+            structured_metadata: HashMap::default(),
+            remove_structured_metadata_fields: false,
+            remove_timestamp: false,
+        };
+        let mut sharder = StreamSharder::new(ShardStreamsConfig {
+            enabled: true,
+            rate_limit_bytes: NonZeroU64::new(25).unwrap(),
+            max_shards: NonZeroU8::new(4).unwrap(),
+            label: "__stream_shard__".to_string(),
+        });
+        let start = Instant::now();
+
+        // The stream isn't sharded until its rate is known.
+        for _ in 0..5 {
+            let record = sharder.shard_record(encode_at(&mut encoder, chrono::Utc::now()), start);
+            assert_eq!(
+                record.labels,
+                vec![("agent".to_string(), "vector".to_string())]
+            );
+        }
+
+        // 55 bytes were written over the first second, so the stream is split into 3 shards.
+        let later = start + Duration::from_secs(1);
+        let shards = (0..6)
+            .map(|_| {
+                let record =
+                    sharder.shard_record(encode_at(&mut encoder, chrono::Utc::now()), later);
+                let labels: HashMap<_, _> = record.labels.into_iter().collect();
+                labels["__stream_shard__"].clone()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(shards, vec!["0", "1", "2", "0", "1", "2"]);
+    }
 }
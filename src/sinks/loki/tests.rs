@@ -4,7 +4,7 @@ use super::{config::LokiConfig, healthcheck::healthcheck, sink::LokiSink};
 use crate::{
     config::ProxyConfig,
     event::{Event, LogEvent},
-    http::HttpClient,
+    http::{build_authenticator, HttpClient},
     sinks::util::test::{build_test_server, load_sink},
     test_util,
     tls::TlsSettings,
@@ -27,7 +27,7 @@ async fn interpolate_labels() {
     )
     .unwrap();
     let client = config.build_client(cx).unwrap();
-    let mut sink = LokiSink::new(config, client).unwrap();
+    let mut sink = LokiSink::new(config, client, None).unwrap();
 
     let mut e1 = Event::Log(LogEvent::from("hello world"));
 
@@ -68,7 +68,7 @@ async fn use_label_from_dropped_fields() {
     )
     .unwrap();
     let client = config.build_client(cx).unwrap();
-    let mut sink = LokiSink::new(config, client).unwrap();
+    let mut sink = LokiSink::new(config, client, None).unwrap();
 
     let mut e1 = Event::Log(LogEvent::from("hello world"));
 
@@ -115,7 +115,10 @@ async fn healthcheck_includes_auth() {
     let proxy = ProxyConfig::default();
     let client = HttpClient::new(tls, &proxy).expect("could not create HTTP client");
 
-    healthcheck(config.clone(), client)
+    let auth = build_authenticator(config.auth.as_ref(), &client)
+        .await
+        .unwrap();
+    healthcheck(config.clone(), client, auth)
         .await
         .expect("healthcheck failed");
 
@@ -144,7 +147,7 @@ async fn healthcheck_grafana_cloud() {
     let proxy = ProxyConfig::default();
     let client = HttpClient::new(tls, &proxy).expect("could not create HTTP client");
 
-    healthcheck(config, client)
+    healthcheck(config, client, None)
         .await
         .expect("healthcheck failed");
 }
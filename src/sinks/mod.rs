@@ -27,10 +27,16 @@ pub mod axiom;
 pub mod azure_blob;
 #[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
 pub mod azure_common;
+#[cfg(feature = "sinks-azure_data_explorer")]
+pub mod azure_data_explorer;
 #[cfg(feature = "sinks-azure_monitor_logs")]
 pub mod azure_monitor_logs;
 #[cfg(feature = "sinks-blackhole")]
 pub mod blackhole;
+#[cfg(feature = "sinks-cassandra")]
+pub mod cassandra;
+#[cfg(feature = "sinks-chat_webhook")]
+pub mod chat_webhook;
 #[cfg(feature = "sinks-clickhouse")]
 pub mod clickhouse;
 #[cfg(feature = "sinks-console")]
@@ -44,6 +50,8 @@ pub mod console;
 pub mod datadog;
 #[cfg(feature = "sinks-datadog_archives")]
 pub mod datadog_archives;
+#[cfg(feature = "sinks-delta_lake")]
+pub mod delta_lake;
 #[cfg(feature = "sinks-elasticsearch")]
 pub mod elasticsearch;
 #[cfg(feature = "sinks-file")]
@@ -52,12 +60,16 @@ pub mod file;
 pub mod gcp;
 #[cfg(any(feature = "sinks-gcp"))]
 pub mod gcs_common;
+#[cfg(feature = "sinks-grpc")]
+pub mod grpc;
 #[cfg(feature = "sinks-honeycomb")]
 pub mod honeycomb;
 #[cfg(feature = "sinks-http")]
 pub mod http;
 #[cfg(feature = "sinks-humio")]
 pub mod humio;
+#[cfg(feature = "sinks-iceberg")]
+pub mod iceberg;
 #[cfg(any(feature = "sinks-influxdb", feature = "prometheus-integration-tests"))]
 pub mod influxdb;
 #[cfg(feature = "sinks-kafka")]
@@ -66,16 +78,26 @@ pub mod kafka;
 pub mod logdna;
 #[cfg(feature = "sinks-loki")]
 pub mod loki;
+#[cfg(feature = "sinks-mqtt")]
+pub mod mqtt;
 #[cfg(feature = "sinks-nats")]
 pub mod nats;
 #[cfg(feature = "sinks-new_relic")]
 pub mod new_relic;
+#[cfg(feature = "sinks-opentelemetry")]
+pub mod opentelemetry;
+#[cfg(feature = "sinks-pagerduty_events")]
+pub mod pagerduty_events;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
+#[cfg(feature = "sinks-postgres")]
+pub mod postgres;
 #[cfg(feature = "sinks-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sinks-pulsar")]
 pub mod pulsar;
+#[cfg(feature = "sinks-questdb")]
+pub mod questdb;
 #[cfg(feature = "sinks-redis")]
 pub mod redis;
 #[cfg(all(
@@ -85,14 +107,24 @@ pub mod redis;
 pub mod s3_common;
 #[cfg(feature = "sinks-sematext")]
 pub mod sematext;
+#[cfg(feature = "sinks-sentry")]
+pub mod sentry;
+#[cfg(feature = "sinks-smtp")]
+pub mod smtp;
+#[cfg(feature = "sinks-snowflake")]
+pub mod snowflake;
 #[cfg(feature = "sinks-socket")]
 pub mod socket;
 #[cfg(feature = "sinks-splunk_hec")]
 pub mod splunk_hec;
+#[cfg(feature = "sinks-sql")]
+pub mod sql;
 #[cfg(feature = "sinks-statsd")]
 pub mod statsd;
 #[cfg(feature = "sinks-vector")]
 pub mod vector;
+#[cfg(feature = "sinks-victoriametrics")]
+pub mod victoriametrics;
 #[cfg(feature = "sinks-websocket")]
 pub mod websocket;
 
@@ -174,6 +206,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-azure_blob")]
     AzureBlob(#[configurable(derived)] azure_blob::AzureBlobSinkConfig),
 
+    /// Azure Data Explorer.
+    #[cfg(feature = "sinks-azure_data_explorer")]
+    AzureDataExplorer(#[configurable(derived)] azure_data_explorer::AzureDataExplorerConfig),
+
     /// Azure Monitor Logs.
     #[cfg(feature = "sinks-azure_monitor_logs")]
     AzureMonitorLogs(#[configurable(derived)] azure_monitor_logs::AzureMonitorLogsConfig),
@@ -182,6 +218,14 @@ pub enum Sinks {
     #[cfg(feature = "sinks-blackhole")]
     Blackhole(#[configurable(derived)] blackhole::BlackholeConfig),
 
+    /// Apache Cassandra.
+    #[cfg(feature = "sinks-cassandra")]
+    Cassandra(#[configurable(derived)] cassandra::CassandraConfig),
+
+    /// Chat webhooks.
+    #[cfg(feature = "sinks-chat_webhook")]
+    ChatWebhook(#[configurable(derived)] chat_webhook::ChatWebhookConfig),
+
     /// Clickhouse.
     #[cfg(feature = "sinks-clickhouse")]
     Clickhouse(#[configurable(derived)] clickhouse::ClickhouseConfig),
@@ -210,6 +254,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-datadog_traces")]
     DatadogTraces(#[configurable(derived)] datadog::traces::DatadogTracesConfig),
 
+    /// Delta Lake.
+    #[cfg(feature = "sinks-delta_lake")]
+    DeltaLake(#[configurable(derived)] delta_lake::DeltaLakeConfig),
+
     /// Elasticsearch.
     #[cfg(feature = "sinks-elasticsearch")]
     Elasticsearch(#[configurable(derived)] elasticsearch::ElasticsearchConfig),
@@ -218,6 +266,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-file")]
     File(#[configurable(derived)] file::FileSinkConfig),
 
+    /// GCP BigQuery.
+    #[cfg(feature = "sinks-gcp")]
+    GcpBigquery(#[configurable(derived)] gcp::bigquery::BigqueryConfig),
+
     /// Google Chronicle (unstructured).
     #[cfg(feature = "sinks-gcp")]
     GcpChronicleUnstructured(
@@ -240,6 +292,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-gcp")]
     GcpPubsub(#[configurable(derived)] gcp::pubsub::PubsubConfig),
 
+    /// gRPC.
+    #[cfg(feature = "sinks-grpc")]
+    Grpc(#[configurable(derived)] grpc::GrpcSinkConfig),
+
     /// Honeycomb.
     #[cfg(feature = "sinks-honeycomb")]
     Honeycomb(#[configurable(derived)] honeycomb::HoneycombConfig),
@@ -256,6 +312,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-humio")]
     HumioMetrics(#[configurable(derived)] humio::metrics::HumioMetricsConfig),
 
+    /// Apache Iceberg.
+    #[cfg(feature = "sinks-iceberg")]
+    Iceberg(#[configurable(derived)] iceberg::IcebergConfig),
+
     /// InfluxDB Logs.
     #[cfg(any(feature = "sinks-influxdb", feature = "prometheus-integration-tests"))]
     InfluxdbLogs(#[configurable(derived)] influxdb::logs::InfluxDbLogsConfig),
@@ -276,6 +336,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-loki")]
     Loki(#[configurable(derived)] loki::LokiConfig),
 
+    /// MQTT.
+    #[cfg(feature = "sinks-mqtt")]
+    Mqtt(#[configurable(derived)] mqtt::MqttSinkConfig),
+
     /// NATS.
     #[cfg(feature = "sinks-nats")]
     Nats(#[configurable(derived)] self::nats::NatsSinkConfig),
@@ -284,10 +348,22 @@ pub enum Sinks {
     #[cfg(feature = "sinks-new_relic")]
     NewRelic(#[configurable(derived)] new_relic::NewRelicConfig),
 
+    /// OpenTelemetry.
+    #[cfg(feature = "sinks-opentelemetry")]
+    Opentelemetry(#[configurable(derived)] opentelemetry::OpentelemetryConfig),
+
+    /// PagerDuty Events.
+    #[cfg(feature = "sinks-pagerduty_events")]
+    PagerdutyEvents(#[configurable(derived)] pagerduty_events::PagerdutyEventsConfig),
+
     /// Papertrail.
     #[cfg(feature = "sinks-papertrail")]
     Papertrail(#[configurable(derived)] papertrail::PapertrailConfig),
 
+    /// PostgreSQL.
+    #[cfg(feature = "sinks-postgres")]
+    Postgres(#[configurable(derived)] postgres::PostgresConfig),
+
     /// Prometheus Exporter.
     #[cfg(feature = "sinks-prometheus")]
     PrometheusExporter(#[configurable(derived)] prometheus::exporter::PrometheusExporterConfig),
@@ -300,6 +376,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-pulsar")]
     Pulsar(#[configurable(derived)] pulsar::PulsarSinkConfig),
 
+    /// QuestDB.
+    #[cfg(feature = "sinks-questdb")]
+    Questdb(#[configurable(derived)] questdb::QuestDbConfig),
+
     /// Redis.
     #[cfg(feature = "sinks-redis")]
     Redis(#[configurable(derived)] redis::RedisSinkConfig),
@@ -312,6 +392,18 @@ pub enum Sinks {
     #[cfg(feature = "sinks-sematext")]
     SematextMetrics(#[configurable(derived)] sematext::metrics::SematextMetricsConfig),
 
+    /// Sentry.
+    #[cfg(feature = "sinks-sentry")]
+    Sentry(#[configurable(derived)] sentry::SentryConfig),
+
+    /// SMTP.
+    #[cfg(feature = "sinks-smtp")]
+    Smtp(#[configurable(derived)] smtp::SmtpConfig),
+
+    /// Snowflake.
+    #[cfg(feature = "sinks-snowflake")]
+    Snowflake(#[configurable(derived)] snowflake::SnowflakeConfig),
+
     /// Socket.
     #[cfg(feature = "sinks-socket")]
     Socket(#[configurable(derived)] socket::SocketSinkConfig),
@@ -324,6 +416,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-splunk_hec")]
     SplunkHecMetrics(#[configurable(derived)] splunk_hec::metrics::config::HecMetricsSinkConfig),
 
+    /// SQL.
+    #[cfg(feature = "sinks-sql")]
+    Sql(#[configurable(derived)] sql::SqlConfig),
+
     /// StatsD.
     #[cfg(feature = "sinks-statsd")]
     Statsd(#[configurable(derived)] statsd::StatsdSinkConfig),
@@ -364,6 +460,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-vector")]
     Vector(#[configurable(derived)] vector::VectorConfig),
 
+    /// VictoriaMetrics.
+    #[cfg(feature = "sinks-victoriametrics")]
+    Victoriametrics(#[configurable(derived)] victoriametrics::VictoriaMetricsConfig),
+
     /// Websocket.
     #[cfg(feature = "sinks-websocket")]
     Websocket(#[configurable(derived)] websocket::WebSocketSinkConfig),
@@ -394,10 +494,16 @@ impl NamedComponent for Sinks {
             Self::Axiom(config) => config.get_component_name(),
             #[cfg(feature = "sinks-azure_blob")]
             Self::AzureBlob(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-azure_data_explorer")]
+            Self::AzureDataExplorer(config) => config.get_component_name(),
             #[cfg(feature = "sinks-azure_monitor_logs")]
             Self::AzureMonitorLogs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-blackhole")]
             Self::Blackhole(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-cassandra")]
+            Self::Cassandra(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-chat_webhook")]
+            Self::ChatWebhook(config) => config.get_component_name(),
             #[cfg(feature = "sinks-clickhouse")]
             Self::Clickhouse(config) => config.get_component_name(),
             #[cfg(feature = "sinks-console")]
@@ -412,11 +518,15 @@ impl NamedComponent for Sinks {
             Self::DatadogMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sinks-datadog_traces")]
             Self::DatadogTraces(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-delta_lake")]
+            Self::DeltaLake(config) => config.get_component_name(),
             #[cfg(feature = "sinks-elasticsearch")]
             Self::Elasticsearch(config) => config.get_component_name(),
             #[cfg(feature = "sinks-file")]
             Self::File(config) => config.get_component_name(),
             #[cfg(feature = "sinks-gcp")]
+            Self::GcpBigquery(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-gcp")]
             Self::GcpChronicleUnstructured(config) => config.get_component_name(),
             #[cfg(feature = "sinks-gcp")]
             Self::GcpStackdriverLogs(config) => config.get_component_name(),
@@ -426,6 +536,8 @@ impl NamedComponent for Sinks {
             Self::GcpCloudStorage(config) => config.get_component_name(),
             #[cfg(feature = "sinks-gcp")]
             Self::GcpPubsub(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-grpc")]
+            Self::Grpc(config) => config.get_component_name(),
             #[cfg(feature = "sinks-honeycomb")]
             Self::Honeycomb(config) => config.get_component_name(),
             #[cfg(feature = "sinks-http")]
@@ -434,6 +546,8 @@ impl NamedComponent for Sinks {
             Self::HumioLogs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-humio")]
             Self::HumioMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-iceberg")]
+            Self::Iceberg(config) => config.get_component_name(),
             #[cfg(any(feature = "sinks-influxdb", feature = "prometheus-integration-tests"))]
             Self::InfluxdbLogs(config) => config.get_component_name(),
             #[cfg(any(feature = "sinks-influxdb", feature = "prometheus-integration-tests"))]
@@ -444,30 +558,48 @@ impl NamedComponent for Sinks {
             Self::Logdna(config) => config.get_component_name(),
             #[cfg(feature = "sinks-loki")]
             Self::Loki(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-mqtt")]
+            Self::Mqtt(config) => config.get_component_name(),
             #[cfg(feature = "sinks-nats")]
             Self::Nats(config) => config.get_component_name(),
             #[cfg(feature = "sinks-new_relic")]
             Self::NewRelic(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-opentelemetry")]
+            Self::Opentelemetry(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-pagerduty_events")]
+            Self::PagerdutyEvents(config) => config.get_component_name(),
             #[cfg(feature = "sinks-papertrail")]
             Self::Papertrail(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-postgres")]
+            Self::Postgres(config) => config.get_component_name(),
             #[cfg(feature = "sinks-prometheus")]
             Self::PrometheusExporter(config) => config.get_component_name(),
             #[cfg(feature = "sinks-prometheus")]
             Self::PrometheusRemoteWrite(config) => config.get_component_name(),
             #[cfg(feature = "sinks-pulsar")]
             Self::Pulsar(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-questdb")]
+            Self::Questdb(config) => config.get_component_name(),
             #[cfg(feature = "sinks-redis")]
             Self::Redis(config) => config.get_component_name(),
             #[cfg(feature = "sinks-sematext")]
             Self::SematextLogs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-sematext")]
             Self::SematextMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-sentry")]
+            Self::Sentry(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-smtp")]
+            Self::Smtp(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-snowflake")]
+            Self::Snowflake(config) => config.get_component_name(),
             #[cfg(feature = "sinks-socket")]
             Self::Socket(config) => config.get_component_name(),
             #[cfg(feature = "sinks-splunk_hec")]
             Self::SplunkHecLogs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-splunk_hec")]
             Self::SplunkHecMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-sql")]
+            Self::Sql(config) => config.get_component_name(),
             #[cfg(feature = "sinks-statsd")]
             Self::Statsd(config) => config.get_component_name(),
             #[cfg(all(test, feature = "sources-demo_logs"))]
@@ -486,6 +618,8 @@ impl NamedComponent for Sinks {
             Self::UnitTestStream(config) => config.get_component_name(),
             #[cfg(feature = "sinks-vector")]
             Self::Vector(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-victoriametrics")]
+            Self::Victoriametrics(config) => config.get_component_name(),
             #[cfg(feature = "sinks-websocket")]
             Self::Websocket(config) => config.get_component_name(),
         }
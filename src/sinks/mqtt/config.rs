@@ -0,0 +1,259 @@
+use std::time::Duration;
+
+use codecs::JsonSerializerConfig;
+use futures::FutureExt;
+use rumqttc::{
+    AsyncClient, Event as MqttEvent, MqttOptions, Packet, QoS, TlsConfiguration, Transport,
+};
+use snafu::{ResultExt, Snafu};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use super::sink::MqttSink;
+use crate::{
+    codecs::{Encoder, EncodingConfig},
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{Healthcheck, VectorSink},
+    template::Template,
+    tls::{MaybeTlsSettings, TlsEnableableConfig, TlsError},
+};
+
+/// The largest packet allowed by MQTT, which rumqttc otherwise limits to 10 KiB.
+const MAX_PACKET_SIZE: usize = 268_435_455;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid TLS configuration: {}", source))]
+    Tls { source: TlsError },
+    #[snafu(display("`password` can only be set along with `user`"))]
+    PasswordWithoutUser,
+}
+
+/// The quality of service of the messages published.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MqttQoS {
+    /// Messages are published at most once, and never acknowledged by the broker.
+    AtMostOnce,
+
+    /// Messages are published at least once, until the broker acknowledges them.
+    #[default]
+    AtLeastOnce,
+
+    /// Messages are published exactly once, with a two-step acknowledgement from the broker.
+    ExactlyOnce,
+}
+
+impl From<MqttQoS> for QoS {
+    fn from(qos: MqttQoS) -> Self {
+        match qos {
+            MqttQoS::AtMostOnce => QoS::AtMostOnce,
+            MqttQoS::AtLeastOnce => QoS::AtLeastOnce,
+            MqttQoS::ExactlyOnce => QoS::ExactlyOnce,
+        }
+    }
+}
+
+/// Configuration for the `mqtt` sink.
+#[configurable_component(sink("mqtt"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSinkConfig {
+    /// The host of the MQTT broker to publish messages to.
+    #[configurable(metadata(docs::examples = "mqtt.example.com"))]
+    pub host: String,
+
+    /// The port of the MQTT broker.
+    #[serde(default = "default_port")]
+    pub port: u16,
+
+    /// The user to authenticate with.
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub user: Option<String>,
+
+    /// The password of the user.
+    #[configurable(metadata(docs::examples = "${MQTT_PASSWORD}"))]
+    pub password: Option<SensitiveString>,
+
+    /// The client identifier of the connection to the broker.
+    ///
+    /// By default, a random identifier is used, which is different every time Vector starts.
+    #[configurable(metadata(docs::examples = "vector-edge-1"))]
+    pub client_id: Option<String>,
+
+    /// The interval, in seconds, at which the connection is kept alive when no messages are published.
+    #[serde(default = "default_keep_alive_secs")]
+    pub keep_alive_secs: u64,
+
+    /// Whether the broker discards the session of the client when it connects.
+    ///
+    /// When disabled, along with a fixed `client_id`, messages published with a quality of service
+    /// of `at_least_once` or `exactly_once` that weren't acknowledged when the connection was lost
+    /// are published again when it's opened again.
+    #[serde(default = "crate::serde::default_true")]
+    pub clean_session: bool,
+
+    /// The topic to publish messages to.
+    #[configurable(metadata(docs::examples = "vector"))]
+    #[configurable(metadata(docs::examples = "devices/{{ device_id }}/events"))]
+    pub topic: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub quality_of_service: MqttQoS,
+
+    /// Whether the broker retains the last message published to each topic, for new subscribers.
+    #[serde(default)]
+    pub retain: bool,
+
+    #[configurable(derived)]
+    pub encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+const fn default_port() -> u16 {
+    1883
+}
+
+const fn default_keep_alive_secs() -> u64 {
+    60
+}
+
+impl GenerateConfig for MqttSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            host: "localhost".to_owned(),
+            port: default_port(),
+            user: None,
+            password: None,
+            client_id: None,
+            keep_alive_secs: default_keep_alive_secs(),
+            clean_session: true,
+            topic: Template::try_from("vector").unwrap(),
+            quality_of_service: MqttQoS::default(),
+            retain: false,
+            encoding: JsonSerializerConfig::new().into(),
+            tls: None,
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for MqttSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let client_id = self
+            .client_id
+            .clone()
+            .unwrap_or_else(|| format!("vector-{:016x}", rand::random::<u64>()));
+        let options = self.options(client_id.clone())?;
+
+        let transformer = self.encoding.transformer();
+        let serializer = self.encoding.build()?;
+        let sink = MqttSink {
+            options,
+            topic: self.topic.clone(),
+            quality_of_service: self.quality_of_service.into(),
+            retain: self.retain,
+            transformer,
+            encoder: Encoder::<()>::new(serializer),
+        };
+
+        let healthcheck = healthcheck(self.options(format!("{}-healthcheck", client_id))?).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & (DataType::Log | DataType::Metric))
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl MqttSinkConfig {
+    fn options(&self, client_id: String) -> Result<MqttOptions, BuildError> {
+        let mut options = MqttOptions::new(client_id, self.host.clone(), self.port);
+        options
+            .set_keep_alive(Duration::from_secs(self.keep_alive_secs))
+            .set_clean_session(self.clean_session)
+            .set_max_packet_size(MAX_PACKET_SIZE, MAX_PACKET_SIZE);
+
+        match (&self.user, &self.password) {
+            (Some(user), password) => {
+                let password = password.as_ref().map(|password| password.inner());
+                options.set_credentials(user.clone(), password.unwrap_or_default());
+            }
+            (None, Some(_)) => return Err(BuildError::PasswordWithoutUser),
+            (None, None) => {}
+        }
+
+        if let MaybeTlsSettings::Tls(tls) =
+            MaybeTlsSettings::from_config(&self.tls, false).context(TlsSnafu)?
+        {
+            let alpn = self
+                .tls
+                .as_ref()
+                .and_then(|tls| tls.options.alpn_protocols.clone())
+                .map(|protocols| protocols.into_iter().map(String::into_bytes).collect());
+            options.set_transport(Transport::Tls(TlsConfiguration::Simple {
+                ca: tls.authorities_pem().flatten().collect(),
+                alpn,
+                client_auth: tls.identity_pem(),
+            }));
+        }
+
+        Ok(options)
+    }
+}
+
+/// Checks that the broker accepts connections of the client.
+async fn healthcheck(options: MqttOptions) -> crate::Result<()> {
+    let (_client, mut eventloop) = AsyncClient::new(options, 1);
+    loop {
+        // Connections refused by the broker are errors of the event loop.
+        if let MqttEvent::Incoming(Packet::ConnAck(_)) = eventloop.poll().await? {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSinkConfig>();
+    }
+
+    #[test]
+    fn rejects_password_without_user() {
+        let config: MqttSinkConfig = toml::from_str(
+            r#"host = "localhost"
+            topic = "vector"
+            password = "secret"
+            encoding.codec = "json""#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            config.options("vector".to_owned()),
+            Err(BuildError::PasswordWithoutUser)
+        ));
+    }
+}
@@ -0,0 +1,4 @@
+mod config;
+mod sink;
+
+pub use config::MqttSinkConfig;
@@ -0,0 +1,261 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use futures::{stream::BoxStream, StreamExt};
+use rumqttc::{AsyncClient, Event as MqttEvent, EventLoop, MqttOptions, Outgoing, Packet, QoS};
+use tokio::sync::mpsc;
+use tokio_util::codec::Encoder as _;
+use vector_core::{
+    internal_event::{
+        ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle as _, Output, Protocol,
+    },
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    codecs::{Encoder, Transformer},
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::{MqttClientError, MqttConnectionError, TemplateRenderingError},
+    sinks::util::StreamSink,
+    template::Template,
+};
+
+/// The number of messages queued in the client before publishing waits for the connection.
+const CLIENT_CAPACITY: usize = 100;
+
+/// How long to wait before opening the connection again when it fails.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+pub struct MqttSink {
+    pub options: MqttOptions,
+    pub topic: Template,
+    pub quality_of_service: QoS,
+    pub retain: bool,
+    pub transformer: Transformer,
+    pub encoder: Encoder<()>,
+}
+
+/// A message published by the sink, waiting for the broker to acknowledge it.
+struct Publish {
+    finalizers: EventFinalizers,
+    event_byte_size: usize,
+    bytes: usize,
+}
+
+/// The messages published by the sink that the broker hasn't acknowledged yet.
+///
+/// The event loop of the client doesn't tell which message it sends, only the packet identifier it
+/// assigns to it, but it sends messages in the order they're published, so they're matched with
+/// their identifiers in that order.
+#[derive(Default)]
+struct InFlight {
+    /// The messages published, but not sent yet.
+    queued: VecDeque<Publish>,
+    /// The messages sent, by the identifier of their packet.
+    sent: HashMap<u16, Publish>,
+}
+
+impl InFlight {
+    fn is_empty(&self) -> bool {
+        self.queued.is_empty() && self.sent.is_empty()
+    }
+
+    /// Returns the message acknowledged by `event`, if any.
+    fn handle(&mut self, event: &MqttEvent) -> Option<Publish> {
+        match event {
+            // Messages published at most once have no identifier, and are never acknowledged.
+            MqttEvent::Outgoing(Outgoing::Publish(0)) => self.queued.pop_front(),
+            MqttEvent::Outgoing(Outgoing::Publish(pkid)) => {
+                // Messages sent again after the connection was opened again keep their identifier.
+                if !self.sent.contains_key(pkid) {
+                    if let Some(publish) = self.queued.pop_front() {
+                        self.sent.insert(*pkid, publish);
+                    }
+                }
+                None
+            }
+            MqttEvent::Incoming(Packet::PubAck(ack)) => self.sent.remove(&ack.pkid),
+            MqttEvent::Incoming(Packet::PubComp(comp)) => self.sent.remove(&comp.pkid),
+            _ => None,
+        }
+    }
+}
+
+/// Polls the event loop of the client, which opens the connection again whenever it fails, until
+/// the sink stops.
+async fn poll(mut eventloop: EventLoop, events: mpsc::UnboundedSender<MqttEvent>) {
+    loop {
+        match eventloop.poll().await {
+            Ok(event) => {
+                if events.send(event).is_err() {
+                    break;
+                }
+            }
+            Err(error) => {
+                emit!(MqttConnectionError { error });
+                if events.is_closed() {
+                    break;
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for MqttSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let (client, eventloop) = AsyncClient::new(self.options.clone(), CLIENT_CAPACITY);
+        // The channel is unbounded, as the event loop must keep being polled for publishing to
+        // make progress, and it only holds events of the messages published.
+        let (events_tx, mut events) = mpsc::unbounded_channel();
+        tokio::spawn(poll(eventloop, events_tx));
+
+        let bytes_sent = register!(BytesSent::from(Protocol::from("mqtt")));
+        let events_sent = register!(EventsSent::from(Output(None)));
+
+        let mut input = input.fuse();
+        let mut input_done = false;
+        let mut in_flight = InFlight::default();
+        while !input_done || !in_flight.is_empty() {
+            tokio::select! {
+                biased;
+
+                Some(event) = events.recv() => {
+                    if let Some(publish) = in_flight.handle(&event) {
+                        publish.finalizers.update_status(EventStatus::Delivered);
+                        events_sent.emit(CountByteSize(1, publish.event_byte_size));
+                        bytes_sent.emit(ByteSize(publish.bytes));
+                    }
+                },
+
+                event = input.next(), if !input_done => {
+                    let mut event = match event {
+                        Some(event) => event,
+                        None => {
+                            input_done = true;
+                            continue;
+                        }
+                    };
+                    let finalizers = event.take_finalizers();
+
+                    let topic = match self.topic.render_string(&event) {
+                        Ok(topic) => topic,
+                        Err(error) => {
+                            emit!(TemplateRenderingError {
+                                error,
+                                field: Some("topic"),
+                                drop_event: true,
+                            });
+                            finalizers.update_status(EventStatus::Rejected);
+                            continue;
+                        }
+                    };
+
+                    self.transformer.transform(&mut event);
+                    let event_byte_size = event.estimated_json_encoded_size_of();
+                    let mut payload = BytesMut::new();
+                    if self.encoder.encode(event, &mut payload).is_err() {
+                        // Error is handled by `Encoder`.
+                        finalizers.update_status(EventStatus::Rejected);
+                        continue;
+                    }
+
+                    let bytes = payload.len();
+                    match client
+                        .publish(topic, self.quality_of_service, self.retain, payload.to_vec())
+                        .await
+                    {
+                        Ok(()) => in_flight.queued.push_back(Publish {
+                            finalizers,
+                            event_byte_size,
+                            bytes,
+                        }),
+                        Err(error) => {
+                            emit!(MqttClientError { error });
+                            finalizers.update_status(EventStatus::Errored);
+                            return Err(());
+                        }
+                    }
+                },
+
+                else => break,
+            }
+        }
+
+        let _ = client.disconnect().await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rumqttc::{PubAck, PubComp};
+
+    use super::*;
+
+    fn publish(in_flight: &mut InFlight, bytes: usize) {
+        in_flight.queued.push_back(Publish {
+            finalizers: EventFinalizers::default(),
+            event_byte_size: bytes,
+            bytes,
+        });
+    }
+
+    fn acknowledged(in_flight: &mut InFlight, event: MqttEvent) -> Option<usize> {
+        in_flight.handle(&event).map(|publish| publish.bytes)
+    }
+
+    #[test]
+    fn acknowledges_messages_in_order() {
+        let mut in_flight = InFlight::default();
+        publish(&mut in_flight, 1);
+        publish(&mut in_flight, 2);
+        publish(&mut in_flight, 3);
+
+        let sent = |pkid| MqttEvent::Outgoing(Outgoing::Publish(pkid));
+        assert_eq!(acknowledged(&mut in_flight, sent(1)), None);
+        assert_eq!(acknowledged(&mut in_flight, sent(2)), None);
+        assert_eq!(
+            acknowledged(
+                &mut in_flight,
+                MqttEvent::Incoming(Packet::PubAck(PubAck::new(2)))
+            ),
+            Some(2)
+        );
+        // Messages sent again aren't matched with the next ones.
+        assert_eq!(acknowledged(&mut in_flight, sent(1)), None);
+        assert_eq!(acknowledged(&mut in_flight, sent(3)), None);
+        assert_eq!(
+            acknowledged(
+                &mut in_flight,
+                MqttEvent::Incoming(Packet::PubAck(PubAck::new(1)))
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            acknowledged(
+                &mut in_flight,
+                MqttEvent::Incoming(Packet::PubComp(PubComp::new(3)))
+            ),
+            Some(3)
+        );
+        assert!(in_flight.is_empty());
+    }
+
+    #[test]
+    fn acknowledges_messages_published_at_most_once_when_sent() {
+        let mut in_flight = InFlight::default();
+        publish(&mut in_flight, 1);
+
+        assert_eq!(
+            acknowledged(&mut in_flight, MqttEvent::Outgoing(Outgoing::Publish(0))),
+            Some(1)
+        );
+        assert!(in_flight.is_empty());
+    }
+}
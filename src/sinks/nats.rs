@@ -1,9 +1,10 @@
-use std::convert::TryFrom;
+use std::{convert::TryFrom, io, time::Duration};
 
 use async_trait::async_trait;
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
 use codecs::JsonSerializerConfig;
 use futures::{stream::BoxStream, FutureExt, StreamExt, TryFutureExt};
+use nats::jetstream::{JetStream, PublishAck, PublishOptions};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::Encoder as _;
 use vector_common::internal_event::{
@@ -34,6 +35,13 @@ enum BuildError {
     Config { source: NatsConfigError },
     #[snafu(display("NATS Connect Error: {}", source))]
     Connect { source: std::io::Error },
+    #[snafu(display("invalid message ID template: {}", source))]
+    MessageIdTemplate { source: TemplateParseError },
+    #[snafu(display("JetStream stream {:?} is not available: {}", stream, source))]
+    StreamInfo {
+        stream: String,
+        source: std::io::Error,
+    },
 }
 
 /**
@@ -74,12 +82,64 @@ pub struct NatsSinkConfig {
 
     #[configurable(derived)]
     auth: Option<NatsAuthConfig>,
+
+    #[configurable(derived)]
+    jetstream: Option<NatsSinkJetStreamConfig>,
 }
 
 fn default_name() -> String {
     String::from("vector")
 }
 
+/// Configuration for publishing messages to JetStream.
+///
+/// When set, messages are published to the JetStream stream bound to `subject`, and events are
+/// only acknowledged once the stream has acknowledged their message. Publishing is retried when
+/// the acknowledgement isn't received, so messages should be given an ID for the stream to discard
+/// their duplicates.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NatsSinkJetStreamConfig {
+    /// The name of the stream that messages are expected to be stored in.
+    ///
+    /// Messages published to a subject bound to another stream are rejected by JetStream, and the
+    /// healthcheck fails if the stream doesn't exist.
+    expected_stream: Option<String>,
+
+    /// The ID of messages, sent in their `Nats-Msg-Id` header.
+    ///
+    /// JetStream discards the messages with the same ID as one it stored within the duplicate
+    /// window of the stream.
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(docs::examples = "{{ id }}"))]
+    message_id: Option<String>,
+
+    /// The amount of time, in milliseconds, to wait for JetStream to acknowledge a message.
+    #[serde(default = "default_ack_timeout_ms")]
+    ack_timeout_ms: u64,
+
+    /// The number of times publishing a message is retried when it isn't acknowledged.
+    #[serde(default = "default_retry_attempts")]
+    retry_attempts: usize,
+
+    /// The amount of time, in milliseconds, to wait before retrying to publish a message.
+    #[serde(default = "default_retry_backoff_ms")]
+    retry_backoff_ms: u64,
+}
+
+const fn default_ack_timeout_ms() -> u64 {
+    5000
+}
+
+const fn default_retry_attempts() -> usize {
+    5
+}
+
+const fn default_retry_backoff_ms() -> u64 {
+    1000
+}
+
 impl GenerateConfig for NatsSinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
@@ -90,6 +150,7 @@ impl GenerateConfig for NatsSinkConfig {
             subject: "from.vector".into(),
             tls: None,
             url: "nats://127.0.0.1:4222".into(),
+            jetstream: None,
         })
         .unwrap()
     }
@@ -129,28 +190,143 @@ impl NatsSinkConfig {
 
         options.connect(&self.url).await.context(ConnectSnafu)
     }
+
+    /// Connects to JetStream.
+    ///
+    /// The JetStream API of the client is blocking, so it is run on a blocking thread.
+    async fn connect_jetstream(&self) -> Result<JetStream, BuildError> {
+        let options: nats::Options =
+            from_tls_auth_config(&self.connection_name, &self.auth, &self.tls)
+                .context(ConfigSnafu)?;
+        let url = self.url.clone();
+
+        tokio::task::spawn_blocking(move || options.connect(&url).map(nats::jetstream::new))
+            .await
+            .unwrap_or_else(|error| Err(io::Error::new(io::ErrorKind::Other, error)))
+            .context(ConnectSnafu)
+    }
 }
 
 async fn healthcheck(config: NatsSinkConfig) -> crate::Result<()> {
-    config.connect().map_ok(|_| ()).map_err(|e| e.into()).await
+    let stream = match config
+        .jetstream
+        .as_ref()
+        .and_then(|jetstream| jetstream.expected_stream.clone())
+    {
+        Some(stream) => stream,
+        None => return config.connect().map_ok(|_| ()).map_err(|e| e.into()).await,
+    };
+
+    let context = config.connect_jetstream().await?;
+    tokio::task::spawn_blocking(move || match context.stream_info(&stream) {
+        Ok(_) => Ok(()),
+        Err(source) => Err(BuildError::StreamInfo { stream, source }.into()),
+    })
+    .await?
+}
+
+/// How messages are published.
+enum Publisher {
+    /// Messages are published with the core NATS protocol, which doesn't acknowledge them.
+    Core(nats::asynk::Connection),
+    /// Messages are published to JetStream, which acknowledges them.
+    JetStream(JetStreamPublisher),
+}
+
+struct JetStreamPublisher {
+    context: JetStream,
+    expected_stream: Option<String>,
+    message_id: Option<Template>,
+    ack_timeout: Duration,
+    retry_attempts: usize,
+    retry_backoff: Duration,
+}
+
+impl JetStreamPublisher {
+    /// Publishes `data` to `subject`, retrying until it is acknowledged or the retries are
+    /// exhausted.
+    ///
+    /// The JetStream API of the client is blocking, so it is run on a blocking thread.
+    async fn publish(
+        &self,
+        subject: String,
+        data: Bytes,
+        message_id: Option<String>,
+    ) -> io::Result<PublishAck> {
+        let options = PublishOptions {
+            timeout: Some(self.ack_timeout),
+            id: message_id,
+            expected_stream: self.expected_stream.clone(),
+            ..Default::default()
+        };
+
+        let mut attempts = 0;
+        loop {
+            let context = self.context.clone();
+            let (subject, data, options) = (subject.clone(), data.clone(), options.clone());
+            let result = tokio::task::spawn_blocking(move || {
+                context.publish_with_options(&subject, &data, &options)
+            })
+            .await
+            .unwrap_or_else(|error| Err(io::Error::new(io::ErrorKind::Other, error)));
+
+            match result {
+                Err(error) if is_retriable(&error) && attempts < self.retry_attempts => {
+                    attempts += 1;
+                    warn!(
+                        message = "Retrying after error.",
+                        %error,
+                        attempts,
+                        internal_log_rate_limit = true,
+                    );
+                    tokio::time::sleep(self.retry_backoff).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Whether publishing a message to JetStream failing with `error` is retried.
+///
+/// The errors returned by JetStream, such as when the subject isn't bound to the expected stream,
+/// would be returned again, while the others are those of the connection, or the acknowledgement
+/// of the message not being received in time.
+fn is_retriable(error: &io::Error) -> bool {
+    error.kind() != io::ErrorKind::Other
 }
 
 pub struct NatsSink {
     transformer: Transformer,
     encoder: Encoder<()>,
-    connection: nats::asynk::Connection,
+    publisher: Publisher,
     subject: Template,
 }
 
 impl NatsSink {
     async fn new(config: NatsSinkConfig) -> Result<Self, BuildError> {
-        let connection = config.connect().await?;
+        let publisher = match &config.jetstream {
+            None => Publisher::Core(config.connect().await?),
+            Some(jetstream) => Publisher::JetStream(JetStreamPublisher {
+                context: config.connect_jetstream().await?,
+                expected_stream: jetstream.expected_stream.clone(),
+                message_id: jetstream
+                    .message_id
+                    .clone()
+                    .map(Template::try_from)
+                    .transpose()
+                    .context(MessageIdTemplateSnafu)?,
+                ack_timeout: Duration::from_millis(jetstream.ack_timeout_ms),
+                retry_attempts: jetstream.retry_attempts,
+                retry_backoff: Duration::from_millis(jetstream.retry_backoff_ms),
+            }),
+        };
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build().context(EncodingSnafu)?;
         let encoder = Encoder::<()>::new(serializer);
 
         Ok(NatsSink {
-            connection,
+            publisher,
             transformer,
             encoder,
             subject: Template::try_from(config.subject).context(SubjectTemplateSnafu)?,
@@ -180,6 +356,25 @@ impl StreamSink<Event> for NatsSink {
                 }
             };
 
+            let message_id = match &self.publisher {
+                Publisher::JetStream(JetStreamPublisher {
+                    message_id: Some(message_id),
+                    ..
+                }) => match message_id.render_string(&event) {
+                    Ok(message_id) => Some(message_id),
+                    Err(error) => {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some("jetstream.message_id"),
+                            drop_event: true,
+                        });
+                        finalizers.update_status(EventStatus::Rejected);
+                        continue;
+                    }
+                },
+                _ => None,
+            };
+
             self.transformer.transform(&mut event);
 
             let event_byte_size = event.estimated_json_encoded_size_of();
@@ -191,9 +386,29 @@ impl StreamSink<Event> for NatsSink {
                 continue;
             }
 
-            match self.connection.publish(&subject, &bytes).await {
+            let result = match &self.publisher {
+                Publisher::Core(connection) => connection.publish(&subject, &bytes).await,
+                Publisher::JetStream(publisher) => publisher
+                    .publish(subject, bytes.clone().freeze(), message_id)
+                    .await
+                    .map(|ack| {
+                        if ack.duplicate {
+                            debug!(
+                                message = "Message discarded as a duplicate by JetStream.",
+                                stream = %ack.stream,
+                                sequence = ack.sequence,
+                            );
+                        }
+                    }),
+            };
+
+            match result {
                 Err(error) => {
-                    finalizers.update_status(EventStatus::Errored);
+                    if is_retriable(&error) || matches!(self.publisher, Publisher::Core(_)) {
+                        finalizers.update_status(EventStatus::Errored);
+                    } else {
+                        finalizers.update_status(EventStatus::Rejected);
+                    }
 
                     emit!(NatsEventSendError { error });
                 }
@@ -218,6 +433,43 @@ mod tests {
     fn generate_config() {
         crate::test_util::test_generate_config::<NatsSinkConfig>();
     }
+
+    #[test]
+    fn parses_jetstream_config() {
+        let config: NatsSinkConfig = toml::from_str(
+            r#"
+            subject = "events"
+            url = "nats://127.0.0.1:4222"
+            encoding.codec = "json"
+            jetstream.expected_stream = "EVENTS"
+            jetstream.message_id = "{{ id }}"
+            "#,
+        )
+        .unwrap();
+
+        let jetstream = config.jetstream.unwrap();
+        assert_eq!(jetstream.expected_stream.as_deref(), Some("EVENTS"));
+        assert_eq!(jetstream.message_id.as_deref(), Some("{{ id }}"));
+        assert_eq!(jetstream.ack_timeout_ms, 5000);
+        assert_eq!(jetstream.retry_attempts, 5);
+        assert_eq!(jetstream.retry_backoff_ms, 1000);
+    }
+
+    #[test]
+    fn retries_connection_errors() {
+        assert!(is_retriable(&io::Error::new(
+            io::ErrorKind::TimedOut,
+            "timed out"
+        )));
+        assert!(is_retriable(&io::Error::new(
+            io::ErrorKind::NotConnected,
+            "disconnected"
+        )));
+        assert!(!is_retriable(&io::Error::new(
+            io::ErrorKind::Other,
+            "expected stream does not match"
+        )));
+    }
 }
 
 #[cfg(feature = "nats-integration-tests")]
@@ -290,6 +542,7 @@ mod integration_tests {
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
+            jetstream: None,
             subject: subject.clone(),
             url,
             tls: None,
@@ -316,6 +569,7 @@ mod integration_tests {
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
+            jetstream: None,
             subject: subject.clone(),
             url,
             tls: None,
@@ -344,6 +598,7 @@ mod integration_tests {
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
+            jetstream: None,
             subject: subject.clone(),
             url,
             tls: None,
@@ -375,6 +630,7 @@ mod integration_tests {
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
+            jetstream: None,
             subject: subject.clone(),
             url,
             tls: None,
@@ -405,6 +661,7 @@ mod integration_tests {
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
+            jetstream: None,
             subject: subject.clone(),
             url,
             tls: None,
@@ -435,6 +692,7 @@ mod integration_tests {
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
+            jetstream: None,
             subject: subject.clone(),
             url,
             tls: None,
@@ -466,6 +724,7 @@ mod integration_tests {
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
+            jetstream: None,
             subject: subject.clone(),
             url,
             tls: None,
@@ -497,6 +756,7 @@ mod integration_tests {
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
+            jetstream: None,
             subject: subject.clone(),
             url,
             tls: Some(TlsEnableableConfig {
@@ -529,6 +789,7 @@ mod integration_tests {
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
+            jetstream: None,
             subject: subject.clone(),
             url,
             tls: None,
@@ -555,6 +816,7 @@ mod integration_tests {
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
+            jetstream: None,
             subject: subject.clone(),
             url,
             tls: Some(TlsEnableableConfig {
@@ -589,6 +851,7 @@ mod integration_tests {
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
+            jetstream: None,
             subject: subject.clone(),
             url,
             tls: Some(TlsEnableableConfig {
@@ -621,6 +884,7 @@ mod integration_tests {
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
+            jetstream: None,
             subject: subject.clone(),
             url,
             tls: Some(TlsEnableableConfig {
@@ -657,6 +921,7 @@ mod integration_tests {
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
+            jetstream: None,
             subject: subject.clone(),
             url,
             tls: Some(TlsEnableableConfig {
@@ -16,7 +16,6 @@ use tracing::Instrument;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_core::{
     event::{EventFinalizers, EventStatus, Finalizable},
-    internal_event::CountByteSize,
     stream::DriverResponse,
 };
 
@@ -40,31 +39,19 @@ impl Finalizable for NewRelicApiRequest {
 
 impl MetaDescriptive for NewRelicApiRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
 
 #[derive(Debug)]
 pub struct NewRelicApiResponse {
     event_status: EventStatus,
-    metadata: RequestMetadata,
 }
 
 impl DriverResponse for NewRelicApiResponse {
     fn event_status(&self) -> EventStatus {
         self.event_status
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(
-            self.metadata.event_count(),
-            self.metadata.events_estimated_json_encoded_byte_size(),
-        )
-    }
-
-    fn bytes_sent(&self) -> Option<usize> {
-        Some(self.metadata.request_encoded_size())
-    }
 }
 
 #[derive(Debug, Clone)]
@@ -97,7 +84,6 @@ impl Service<NewRelicApiRequest> for NewRelicApiService {
         };
 
         let payload_len = request.payload.len();
-        let metadata = request.get_metadata();
         let http_request = http_request
             .header(CONTENT_LENGTH, payload_len)
             .body(Body::from(request.payload))
@@ -107,7 +93,6 @@ impl Service<NewRelicApiRequest> for NewRelicApiService {
             match client.call(http_request).in_current_span().await {
                 Ok(_) => Ok(NewRelicApiResponse {
                     event_status: EventStatus::Delivered,
-                    metadata,
                 }),
                 Err(_) => Err(NewRelicSinkError::new("HTTP request error")),
             }
@@ -0,0 +1,230 @@
+use futures::FutureExt;
+use http::{
+    header::{HeaderName, HeaderValue, InvalidHeaderName, InvalidHeaderValue},
+    uri::{InvalidUri, Scheme, Uri},
+    HeaderMap,
+};
+use indexmap::IndexMap;
+use indoc::indoc;
+use snafu::{ResultExt, Snafu};
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
+use vector_config::configurable_component;
+
+use super::{
+    service::{OpentelemetryClient, OpentelemetryService},
+    sink::OpentelemetrySink,
+};
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::HttpClient,
+    sinks::{
+        util::{BatchConfig, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+#[derive(Debug, Snafu)]
+enum OpentelemetryConfigError {
+    #[snafu(display("Invalid endpoint URI: {}", source))]
+    Uri { source: InvalidUri },
+    #[snafu(display("Could not create endpoint: {}", source))]
+    Endpoint { source: tonic::transport::Error },
+    #[snafu(display("Could not set up endpoint TLS settings: {}", source))]
+    EndpointTls { source: tonic::transport::Error },
+    #[snafu(display("Invalid header name {:?}: {}", name, source))]
+    HeaderName {
+        name: String,
+        source: InvalidHeaderName,
+    },
+    #[snafu(display("Invalid value of header {:?}: {}", name, source))]
+    HeaderValue {
+        name: String,
+        source: InvalidHeaderValue,
+    },
+}
+
+/// The protocol of the OTLP requests.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OpentelemetryProtocol {
+    /// Requests are calls to the `Export` methods of the OTLP gRPC services.
+    #[default]
+    Grpc,
+
+    /// Requests are protobuf-encoded, posted over HTTP to `/v1/logs`, `/v1/metrics`, and
+    /// `/v1/traces` under the path of the endpoint.
+    Http,
+}
+
+/// The compression of the OTLP requests.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OpentelemetryCompression {
+    /// Requests are not compressed.
+    None,
+
+    /// Requests are compressed with [gzip][gzip].
+    ///
+    /// [gzip]: https://www.gzip.org/
+    #[default]
+    Gzip,
+}
+
+/// Configuration for the `opentelemetry` sink.
+#[configurable_component(sink("opentelemetry"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    /// The endpoint of the OTLP receiver to send events to.
+    ///
+    /// Requests are sent over TLS, unless the scheme of the endpoint is `http`.
+    #[configurable(metadata(docs::examples = "http://localhost:4317"))]
+    #[configurable(metadata(docs::examples = "https://otlp.example.com:4318"))]
+    pub endpoint: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub protocol: OpentelemetryProtocol,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: OpentelemetryCompression,
+
+    /// Headers sent with each request, as the metadata of gRPC calls.
+    ///
+    /// These usually hold the credentials of the receiver.
+    #[configurable(metadata(docs::additional_props_description = "The value of the header."))]
+    #[serde(default)]
+    pub headers: IndexMap<String, String>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<OpentelemetryDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OpentelemetryDefaultBatchSettings;
+
+impl SinkBatchSettings for OpentelemetryDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    // Receivers usually reject gRPC messages above 4 MB.
+    const MAX_BYTES: Option<usize> = Some(4_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            endpoint = "http://localhost:4317"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for OpentelemetryConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let uri: Uri = self.endpoint.parse().context(UriSnafu)?;
+        let headers = self.headers()?;
+        let gzip = self.compression == OpentelemetryCompression::Gzip;
+
+        let (client, healthcheck) = match self.protocol {
+            OpentelemetryProtocol::Grpc => {
+                let endpoint = self.grpc_endpoint(&uri)?;
+                let healthcheck = healthcheck(endpoint.clone()).boxed();
+                let client = OpentelemetryClient::Grpc {
+                    channel: endpoint.connect_lazy(),
+                    headers,
+                    gzip,
+                };
+                (client, healthcheck)
+            }
+            // There is no way to check the health of OTLP/HTTP receivers without sending them
+            // events.
+            OpentelemetryProtocol::Http => {
+                let tls = TlsSettings::from_options(&self.tls)?;
+                let client = OpentelemetryClient::Http {
+                    client: HttpClient::new(tls, cx.proxy())?,
+                    endpoint: uri,
+                    headers,
+                    gzip,
+                };
+                (client, futures::future::ok(()).boxed())
+            }
+        };
+
+        let sink = OpentelemetrySink {
+            service: OpentelemetryService { client },
+            batch_settings: self.batch.into_batcher_settings()?,
+            request: self.request.unwrap_with(&TowerRequestConfig::default()),
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl OpentelemetryConfig {
+    fn headers(&self) -> Result<HeaderMap, OpentelemetryConfigError> {
+        self.headers
+            .iter()
+            .map(|(name, value)| {
+                let header_name =
+                    HeaderName::from_bytes(name.as_bytes()).context(HeaderNameSnafu { name })?;
+                let header_value =
+                    HeaderValue::from_str(value).context(HeaderValueSnafu { name })?;
+                Ok((header_name, header_value))
+            })
+            .collect()
+    }
+
+    fn grpc_endpoint(&self, uri: &Uri) -> crate::Result<Endpoint> {
+        let mut endpoint: Endpoint = uri.to_string().parse().context(EndpointSnafu)?;
+        if uri.scheme() != Some(&Scheme::HTTP) {
+            let tls = TlsSettings::from_options(&self.tls)?;
+            let mut tls_config = ClientTlsConfig::new();
+            if let Some(host) = uri.host() {
+                tls_config = tls_config.domain_name(host);
+            }
+            if let Some((cert, key)) = tls.identity_pem() {
+                tls_config = tls_config.identity(Identity::from_pem(cert, key));
+            }
+            for authority in tls.authorities_pem() {
+                tls_config = tls_config.ca_certificate(Certificate::from_pem(authority));
+            }
+            endpoint = endpoint.tls_config(tls_config).context(EndpointTlsSnafu)?;
+        }
+
+        Ok(endpoint)
+    }
+}
+
+async fn healthcheck(endpoint: Endpoint) -> crate::Result<()> {
+    endpoint.connect().await?;
+    Ok(())
+}
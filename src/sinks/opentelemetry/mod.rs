@@ -0,0 +1,128 @@
+//! The `opentelemetry` sink.
+//!
+//! Exports logs, metrics, and traces to receivers of the OpenTelemetry protocol (OTLP), over gRPC
+//! or HTTP.
+
+mod config;
+mod service;
+mod sink;
+
+pub use config::OpentelemetryConfig;
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use bytes::{Buf, Bytes};
+    use flate2::read::GzDecoder;
+    use futures::StreamExt;
+    use http::request::Parts;
+    use opentelemetry_proto::proto::{
+        collector::logs::v1::ExportLogsServiceRequest,
+        common::v1::{any_value::Value as PBValue, AnyValue},
+    };
+    use prost::Message;
+    use vector_core::event::{BatchNotifier, BatchStatus};
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        sinks::util::test::build_test_server_generic,
+        test_util::{
+            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+            next_addr, random_lines_with_stream,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetryConfig>();
+    }
+
+    async fn send_lines(
+        config: &str,
+        response: fn() -> hyper::Response<hyper::Body>,
+    ) -> (Vec<String>, Vec<(Parts, Bytes)>) {
+        let address = next_addr();
+        let config: OpentelemetryConfig =
+            toml::from_str(&config.replace("ADDRESS", &address.to_string())).unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (rx, trigger, server) = build_test_server_generic(address, response);
+        tokio::spawn(server);
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (lines, events) = random_lines_with_stream(8, 3, Some(batch));
+        run_and_assert_sink_compliance(sink, events, &HTTP_SINK_TAGS).await;
+        drop(trigger);
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+        (lines, rx.collect::<Vec<_>>().await)
+    }
+
+    fn bodies(request: ExportLogsServiceRequest) -> Vec<String> {
+        request
+            .resource_logs
+            .into_iter()
+            .flat_map(|resource_logs| resource_logs.scope_logs)
+            .flat_map(|scope_logs| scope_logs.log_records)
+            .map(|record| match record.body {
+                Some(AnyValue {
+                    value: Some(PBValue::StringValue(body)),
+                }) => body,
+                body => panic!("unexpected body {:?}", body),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn exports_logs_over_grpc() {
+        let (lines, requests) = send_lines(
+            r#"endpoint = "http://ADDRESS"
+            compression = "none"
+            headers.authorization = "Bearer token""#,
+            || {
+                hyper::Response::builder()
+                    .header("grpc-status", "0")
+                    .header("content-type", "application/grpc")
+                    .body(hyper::Body::from(vec![0; 5]))
+                    .unwrap()
+            },
+        )
+        .await;
+
+        assert_eq!(requests.len(), 1);
+        let (parts, mut body) = requests.into_iter().next().unwrap();
+        assert_eq!(
+            parts.uri.path(),
+            "/opentelemetry.proto.collector.logs.v1.LogsService/Export"
+        );
+        assert_eq!(parts.headers["authorization"], "Bearer token");
+        assert_eq!(body.get_u8(), 0);
+        let len = body.get_u32() as usize;
+        let request = ExportLogsServiceRequest::decode(body.split_to(len)).unwrap();
+        assert_eq!(bodies(request), lines);
+    }
+
+    #[tokio::test]
+    async fn exports_logs_over_http() {
+        let (lines, requests) = send_lines(
+            r#"endpoint = "http://ADDRESS/otlp"
+            protocol = "http""#,
+            || hyper::Response::new(hyper::Body::empty()),
+        )
+        .await;
+
+        assert_eq!(requests.len(), 1);
+        let (parts, body) = requests.into_iter().next().unwrap();
+        assert_eq!(parts.uri.path(), "/otlp/v1/logs");
+        assert_eq!(parts.headers["content-type"], "application/x-protobuf");
+        assert_eq!(parts.headers["content-encoding"], "gzip");
+        let mut decoded = Vec::new();
+        GzDecoder::new(body.reader())
+            .read_to_end(&mut decoded)
+            .unwrap();
+        let request = ExportLogsServiceRequest::decode(decoded.as_slice()).unwrap();
+        assert_eq!(bodies(request), lines);
+    }
+}
@@ -0,0 +1,321 @@
+use std::{
+    io::Write,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use flate2::{write::GzEncoder, Compression};
+use futures::future::BoxFuture;
+use http::{
+    header::{CONTENT_ENCODING, CONTENT_TYPE},
+    HeaderMap, Request, StatusCode, Uri,
+};
+use opentelemetry_proto::proto::collector::{
+    logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+    metrics::v1::{metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest},
+    trace::v1::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+};
+use prost::Message;
+use snafu::{ResultExt, Snafu};
+use tonic::{codec::CompressionEncoding, metadata::MetadataMap, transport::Channel, Code, Status};
+use tower::Service;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    stream::DriverResponse,
+};
+
+use crate::{
+    http::{HttpClient, HttpError},
+    sinks::util::retries::RetryLogic,
+};
+
+#[derive(Debug, Snafu)]
+pub enum OpentelemetryError {
+    #[snafu(display("Request failed: {}", source))]
+    Grpc { source: Status },
+    #[snafu(display("Request failed: {}", source))]
+    Http { source: HttpError },
+    #[snafu(display("Request failed with status {}", status))]
+    HttpStatus { status: StatusCode },
+}
+
+/// An export request of one of the OTLP services.
+#[derive(Clone)]
+pub enum ExportRequest {
+    Logs(ExportLogsServiceRequest),
+    Metrics(ExportMetricsServiceRequest),
+    Traces(ExportTraceServiceRequest),
+}
+
+impl ExportRequest {
+    pub fn encoded_len(&self) -> usize {
+        match self {
+            Self::Logs(request) => request.encoded_len(),
+            Self::Metrics(request) => request.encoded_len(),
+            Self::Traces(request) => request.encoded_len(),
+        }
+    }
+
+    /// The path OTLP/HTTP receivers accept the request at.
+    const fn http_path(&self) -> &'static str {
+        match self {
+            Self::Logs(_) => "v1/logs",
+            Self::Metrics(_) => "v1/metrics",
+            Self::Traces(_) => "v1/traces",
+        }
+    }
+
+    fn encode_to_vec(&self) -> Vec<u8> {
+        match self {
+            Self::Logs(request) => request.encode_to_vec(),
+            Self::Metrics(request) => request.encode_to_vec(),
+            Self::Traces(request) => request.encode_to_vec(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OpentelemetryRequest {
+    pub export: ExportRequest,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for OpentelemetryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for OpentelemetryRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata.clone()
+    }
+}
+
+pub struct OpentelemetryResponse;
+
+impl DriverResponse for OpentelemetryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+}
+
+/// The client sending the export requests, over either gRPC or HTTP.
+#[derive(Clone)]
+pub enum OpentelemetryClient {
+    Grpc {
+        channel: Channel,
+        headers: HeaderMap,
+        gzip: bool,
+    },
+    Http {
+        client: HttpClient,
+        endpoint: Uri,
+        headers: HeaderMap,
+        gzip: bool,
+    },
+}
+
+#[derive(Clone)]
+pub struct OpentelemetryService {
+    pub client: OpentelemetryClient,
+}
+
+impl Service<OpentelemetryRequest> for OpentelemetryService {
+    type Response = OpentelemetryResponse;
+    type Error = OpentelemetryError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Readiness of the gRPC clients is awaited in `call()`, as they are only created there.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: OpentelemetryRequest) -> Self::Future {
+        let client = self.client.clone();
+        Box::pin(async move {
+            match client {
+                OpentelemetryClient::Grpc {
+                    channel,
+                    headers,
+                    gzip,
+                } => export_grpc(channel, headers, gzip, request.export).await?,
+                OpentelemetryClient::Http {
+                    client,
+                    endpoint,
+                    headers,
+                    gzip,
+                } => export_http(client, &endpoint, headers, gzip, &request.export).await?,
+            }
+
+            Ok(OpentelemetryResponse)
+        })
+    }
+}
+
+async fn export_grpc(
+    channel: Channel,
+    headers: HeaderMap,
+    gzip: bool,
+    export: ExportRequest,
+) -> Result<(), OpentelemetryError> {
+    let metadata = MetadataMap::from_headers(headers);
+
+    // The responses may report events the receivers rejected, which can't be retried.
+    match export {
+        ExportRequest::Logs(message) => {
+            let mut client = LogsServiceClient::new(channel);
+            if gzip {
+                client = client.send_compressed(CompressionEncoding::Gzip);
+            }
+            client
+                .export(grpc_request(message, metadata))
+                .await
+                .map(drop)
+        }
+        ExportRequest::Metrics(message) => {
+            let mut client = MetricsServiceClient::new(channel);
+            if gzip {
+                client = client.send_compressed(CompressionEncoding::Gzip);
+            }
+            client
+                .export(grpc_request(message, metadata))
+                .await
+                .map(drop)
+        }
+        ExportRequest::Traces(message) => {
+            let mut client = TraceServiceClient::new(channel);
+            if gzip {
+                client = client.send_compressed(CompressionEncoding::Gzip);
+            }
+            client
+                .export(grpc_request(message, metadata))
+                .await
+                .map(drop)
+        }
+    }
+    .context(GrpcSnafu)
+}
+
+fn grpc_request<T>(message: T, metadata: MetadataMap) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    *request.metadata_mut() = metadata;
+    request
+}
+
+async fn export_http(
+    client: HttpClient,
+    endpoint: &Uri,
+    headers: HeaderMap,
+    gzip: bool,
+    export: &ExportRequest,
+) -> Result<(), OpentelemetryError> {
+    let uri = http_uri(endpoint, export.http_path());
+    let mut body = export.encode_to_vec();
+
+    let mut builder = Request::post(uri).header(CONTENT_TYPE, "application/x-protobuf");
+    if gzip {
+        let mut encoder = GzEncoder::new(Vec::with_capacity(body.len()), Compression::default());
+        body = encoder
+            .write_all(&body)
+            .and_then(|_| encoder.finish())
+            .expect("Writing to Vec can't fail");
+        builder = builder.header(CONTENT_ENCODING, "gzip");
+    }
+    let mut request = builder
+        .body(hyper::Body::from(Bytes::from(body)))
+        .expect("Invalid request");
+    request.headers_mut().extend(headers);
+
+    let response = client.send(request).await.context(HttpSnafu)?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(OpentelemetryError::HttpStatus { status })
+    }
+}
+
+/// The URI at `path` under the path of `endpoint`.
+fn http_uri(endpoint: &Uri, path: &str) -> String {
+    let endpoint = endpoint.to_string();
+    format!("{}/{}", endpoint.trim_end_matches('/'), path)
+}
+
+#[derive(Debug, Clone)]
+pub struct OpentelemetryRetryLogic;
+
+impl RetryLogic for OpentelemetryRetryLogic {
+    type Error = OpentelemetryError;
+    type Response = OpentelemetryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        // The retryable codes and statuses of the OTLP specification, see
+        // <https://opentelemetry.io/docs/specs/otlp/#failures>.
+        match error {
+            OpentelemetryError::Grpc { source } => matches!(
+                source.code(),
+                Code::Cancelled
+                    | Code::DeadlineExceeded
+                    | Code::ResourceExhausted
+                    | Code::Aborted
+                    | Code::OutOfRange
+                    | Code::Unavailable
+                    | Code::DataLoss
+            ),
+            OpentelemetryError::Http { .. } => true,
+            OpentelemetryError::HttpStatus { status } => matches!(
+                *status,
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_signal_paths() {
+        let logs = ExportRequest::Logs(ExportLogsServiceRequest::default());
+        for endpoint in ["http://localhost:4318", "http://localhost:4318/"] {
+            let endpoint = endpoint.parse::<Uri>().unwrap();
+            assert_eq!(
+                http_uri(&endpoint, logs.http_path()),
+                "http://localhost:4318/v1/logs"
+            );
+        }
+        let endpoint = "https://otlp.example.com/otlp".parse::<Uri>().unwrap();
+        assert_eq!(
+            http_uri(&endpoint, "v1/traces"),
+            "https://otlp.example.com/otlp/v1/traces"
+        );
+    }
+
+    #[test]
+    fn retries_throttled_requests() {
+        let retry_logic = OpentelemetryRetryLogic;
+        assert!(retry_logic.is_retriable_error(&OpentelemetryError::Grpc {
+            source: Status::unavailable("unavailable")
+        }));
+        assert!(!retry_logic.is_retriable_error(&OpentelemetryError::Grpc {
+            source: Status::invalid_argument("invalid")
+        }));
+        assert!(
+            retry_logic.is_retriable_error(&OpentelemetryError::HttpStatus {
+                status: StatusCode::TOO_MANY_REQUESTS
+            })
+        );
+        assert!(
+            !retry_logic.is_retriable_error(&OpentelemetryError::HttpStatus {
+                status: StatusCode::BAD_REQUEST
+            })
+        );
+    }
+}
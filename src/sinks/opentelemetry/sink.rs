@@ -0,0 +1,103 @@
+use std::num::NonZeroUsize;
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use opentelemetry_proto::{
+    encode::{encode_logs, encode_metrics, encode_spans},
+    proto::collector::{
+        logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+        trace::v1::ExportTraceServiceRequest,
+    },
+};
+use tower::ServiceBuilder;
+use vector_core::{
+    event::{Event, Finalizable},
+    partition::Partitioner,
+    stream::BatcherSettings,
+};
+
+use super::service::{
+    ExportRequest, OpentelemetryClient, OpentelemetryRequest, OpentelemetryRetryLogic,
+    OpentelemetryService,
+};
+use crate::sinks::util::{
+    metadata::RequestMetadataBuilder, ServiceBuilderExt, SinkBuilderExt, StreamSink,
+    TowerRequestSettings,
+};
+
+/// The OTLP signal, and so the service, events are exported as.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Signal {
+    Logs,
+    Metrics,
+    Traces,
+}
+
+struct SignalPartitioner;
+
+impl Partitioner for SignalPartitioner {
+    type Item = Event;
+    type Key = Signal;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        match item {
+            Event::Log(_) => Signal::Logs,
+            Event::Metric(_) => Signal::Metrics,
+            Event::Trace(_) => Signal::Traces,
+        }
+    }
+}
+
+pub struct OpentelemetrySink {
+    pub service: OpentelemetryService,
+    pub batch_settings: BatcherSettings,
+    pub request: TowerRequestSettings,
+}
+
+#[async_trait]
+impl StreamSink<Event> for OpentelemetrySink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let protocol = match self.service.client {
+            OpentelemetryClient::Grpc { .. } => "grpc",
+            OpentelemetryClient::Http { .. } => "http",
+        };
+        let service = ServiceBuilder::new()
+            .settings(self.request, OpentelemetryRetryLogic)
+            .service(self.service);
+
+        input
+            .batched_partitioned(SignalPartitioner, self.batch_settings)
+            .map(|(signal, mut events)| {
+                let finalizers = events.take_finalizers();
+                let builder = RequestMetadataBuilder::from_events(&events);
+                let export = match signal {
+                    Signal::Logs => ExportRequest::Logs(ExportLogsServiceRequest {
+                        resource_logs: encode_logs(events.into_iter().map(Event::into_log)),
+                    }),
+                    Signal::Metrics => ExportRequest::Metrics(ExportMetricsServiceRequest {
+                        resource_metrics: encode_metrics(
+                            events.into_iter().map(Event::into_metric),
+                        ),
+                    }),
+                    Signal::Traces => ExportRequest::Traces(ExportTraceServiceRequest {
+                        resource_spans: encode_spans(events.into_iter().map(Event::into_trace)),
+                    }),
+                };
+                // Requests of events without any fields are empty.
+                let metadata = builder.with_request_size(
+                    NonZeroUsize::new(export.encoded_len().max(1))
+                        .expect("size should be at least one"),
+                );
+
+                OpentelemetryRequest {
+                    export,
+                    finalizers,
+                    metadata,
+                }
+            })
+            .into_driver(service)
+            .protocol(protocol)
+            .run()
+            .await
+    }
+}
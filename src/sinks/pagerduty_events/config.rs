@@ -0,0 +1,167 @@
+use futures::FutureExt;
+use http::Uri;
+use vector_config::configurable_component;
+
+use super::{
+    encoder::PagerdutyEventsEncoder, service::PagerdutyEventsService, sink::PagerdutyEventsSink,
+};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::HttpClient,
+    sinks::{util::TowerRequestConfig, Healthcheck, VectorSink},
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Configuration for the `pagerduty_events` sink.
+#[configurable_component(sink("pagerduty_events"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PagerdutyEventsConfig {
+    /// The endpoint of the Events API.
+    #[serde(default = "default_endpoint")]
+    #[configurable(metadata(docs::examples = "https://events.eu.pagerduty.com/v2/enqueue"))]
+    pub endpoint: String,
+
+    /// The integration key of the service, or of the ruleset, events are routed to.
+    #[configurable(metadata(docs::examples = "R0123456789ABCDEF0123456789ABCDE"))]
+    #[configurable(metadata(docs::examples = "{{ pagerduty.routing_key }}"))]
+    pub routing_key: Template,
+
+    /// The action of events, which must render to `trigger`, `acknowledge`, or `resolve`.
+    ///
+    /// Events with other actions are dropped.
+    #[serde(default = "default_event_action")]
+    #[configurable(metadata(docs::examples = "{{ action }}"))]
+    pub event_action: Template,
+
+    /// The key identifying the alert of events.
+    ///
+    /// Triggers with the key of an open alert are grouped into it, and acknowledgements and
+    /// resolutions apply to the alert of their key, which they require. Triggers without a key
+    /// open an alert each.
+    #[configurable(metadata(docs::examples = "{{ host }}-{{ check }}"))]
+    pub dedup_key: Option<Template>,
+
+    /// The summary of the alerts triggered by events.
+    ///
+    /// Summaries are truncated to 1024 characters.
+    #[serde(default = "default_summary")]
+    #[configurable(metadata(docs::examples = "{{ check }} is failing on {{ host }}"))]
+    pub summary: Template,
+
+    /// The source of the alerts triggered by events, such as the host they're about.
+    ///
+    /// Defaults to the host field of events, or to the hostname of Vector when they have none.
+    #[configurable(metadata(docs::examples = "{{ kubernetes.pod_name }}"))]
+    pub source: Option<Template>,
+
+    /// The field holding the severity of events.
+    ///
+    /// Severities are mapped to those of PagerDuty: `critical`, `error`, `warning`, and `info`.
+    /// Events without a severity, or with a severity PagerDuty doesn't know, are errors.
+    #[serde(default = "default_severity_key")]
+    pub severity_key: String,
+
+    /// The component of the source the alerts triggered by events are about.
+    #[configurable(metadata(docs::examples = "postgres"))]
+    pub component: Option<Template>,
+
+    /// The logical group of the components the alerts triggered by events are about.
+    #[configurable(metadata(docs::examples = "{{ cluster }}"))]
+    pub group: Option<Template>,
+
+    /// The class, or type, of the alerts triggered by events.
+    #[configurable(metadata(docs::examples = "disk_usage"))]
+    pub class: Option<Template>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_endpoint() -> String {
+    "https://events.pagerduty.com/v2/enqueue".to_owned()
+}
+
+fn default_event_action() -> Template {
+    Template::try_from("trigger").expect("couldn't build default event action template")
+}
+
+fn default_summary() -> Template {
+    Template::try_from("{{ message }}").expect("couldn't build default summary template")
+}
+
+fn default_severity_key() -> String {
+    "severity".to_owned()
+}
+
+impl GenerateConfig for PagerdutyEventsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"routing_key = "R0123456789ABCDEF0123456789ABCDE"
+            dedup_key = "{{ host }}-{{ check }}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for PagerdutyEventsConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let endpoint: Uri = self.endpoint.parse()?;
+        let protocol = endpoint.scheme_str().unwrap_or("https").to_owned();
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+
+        let sink = PagerdutyEventsSink {
+            service: PagerdutyEventsService { client, endpoint },
+            protocol,
+            encoder: PagerdutyEventsEncoder {
+                routing_key: self.routing_key.clone(),
+                event_action: self.event_action.clone(),
+                dedup_key: self.dedup_key.clone(),
+                summary: self.summary.clone(),
+                source: self.source.clone(),
+                severity_key: self.severity_key.clone(),
+                component: self.component.clone(),
+                group: self.group.clone(),
+                class: self.class.clone(),
+                transformer: self.encoding.clone(),
+            },
+            request: self.request.unwrap_with(&TowerRequestConfig::default()),
+        };
+
+        // The Events API has no endpoint to check routing keys with, without sending them events.
+        let healthcheck = futures::future::ok(()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
@@ -0,0 +1,259 @@
+use chrono::SecondsFormat;
+use serde_json::{json, Map, Value as JsonValue};
+use vector_core::{
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+};
+
+use crate::{
+    codecs::Transformer,
+    emit,
+    internal_events::{PagerdutyInvalidEventAction, TemplateRenderingError},
+    template::Template,
+};
+
+/// The longest summary PagerDuty accepts, in characters.
+const MAX_SUMMARY_LENGTH: usize = 1024;
+
+/// The actions of the Events API, which open, acknowledge, or resolve the alert of a dedup key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventAction {
+    Trigger,
+    Acknowledge,
+    Resolve,
+}
+
+impl EventAction {
+    fn parse(action: &str) -> Option<Self> {
+        match action.trim().to_lowercase().as_str() {
+            "trigger" => Some(Self::Trigger),
+            "acknowledge" => Some(Self::Acknowledge),
+            "resolve" => Some(Self::Resolve),
+            _ => None,
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Trigger => "trigger",
+            Self::Acknowledge => "acknowledge",
+            Self::Resolve => "resolve",
+        }
+    }
+}
+
+/// Maps the severity of an event to one of those of PagerDuty, which defaults to `error`.
+fn severity(value: Option<&Value>) -> &'static str {
+    let value = match value {
+        Some(value) => value.to_string_lossy().to_lowercase(),
+        None => return "error",
+    };
+    match value.as_str() {
+        "critical" | "crit" | "fatal" | "alert" | "emerg" | "emergency" | "panic" => "critical",
+        "warning" | "warn" => "warning",
+        "info" | "informational" | "notice" | "debug" | "trace" => "info",
+        _ => "error",
+    }
+}
+
+/// Truncates `summary` to the length PagerDuty accepts.
+fn truncate(mut summary: String) -> String {
+    if let Some((index, _)) = summary.char_indices().nth(MAX_SUMMARY_LENGTH) {
+        summary.truncate(index);
+    }
+    summary
+}
+
+/// Renders log events as the events of the Events API.
+#[derive(Clone, Debug)]
+pub struct PagerdutyEventsEncoder {
+    pub routing_key: Template,
+    pub event_action: Template,
+    pub dedup_key: Option<Template>,
+    pub summary: Template,
+    pub source: Option<Template>,
+    pub severity_key: String,
+    pub component: Option<Template>,
+    pub group: Option<Template>,
+    pub class: Option<Template>,
+    pub transformer: Transformer,
+}
+
+fn render(template: &Template, log: &LogEvent, field: &'static str) -> Option<String> {
+    template
+        .render_string(log)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: true,
+            })
+        })
+        .ok()
+}
+
+impl PagerdutyEventsEncoder {
+    /// Encodes `log`, or returns `None` when its templates fail to render, or its action isn't
+    /// one of the Events API.
+    pub fn encode(&self, log: LogEvent) -> Option<JsonValue> {
+        let routing_key = render(&self.routing_key, &log, "routing_key")?;
+        let action = render(&self.event_action, &log, "event_action")?;
+        let action = match EventAction::parse(&action) {
+            Some(action) => action,
+            None => {
+                emit!(PagerdutyInvalidEventAction { action: &action });
+                return None;
+            }
+        };
+
+        let mut event = Map::new();
+        event.insert("routing_key".into(), routing_key.into());
+        event.insert("event_action".into(), action.as_str().into());
+        if let Some(template) = &self.dedup_key {
+            event.insert(
+                "dedup_key".into(),
+                render(template, &log, "dedup_key")?.into(),
+            );
+        }
+        event.insert("client".into(), "Vector".into());
+
+        // Acknowledgements and resolutions only refer to the alert of their dedup key.
+        if action == EventAction::Trigger {
+            event.insert("payload".into(), self.payload(log)?);
+        }
+        Some(event.into())
+    }
+
+    fn payload(&self, log: LogEvent) -> Option<JsonValue> {
+        let summary = render(&self.summary, &log, "summary")?;
+        let source = match &self.source {
+            Some(template) => render(template, &log, "source")?,
+            None => match log.get(log_schema().host_key()) {
+                Some(host) => host.to_string_lossy().into_owned(),
+                None => crate::get_hostname().unwrap_or_else(|_| "vector".to_owned()),
+            },
+        };
+
+        let mut payload = json!({
+            "summary": truncate(summary),
+            "source": source,
+            "severity": severity(log.get(self.severity_key.as_str())),
+        });
+        if let Some(Value::Timestamp(timestamp)) = log.get(log_schema().timestamp_key()) {
+            payload["timestamp"] = timestamp
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                .into();
+        }
+        let fields = [
+            ("component", self.component.as_ref()),
+            ("group", self.group.as_ref()),
+            ("class", self.class.as_ref()),
+        ];
+        for (field, template) in fields {
+            if let Some(template) = template {
+                payload[field] = render(template, &log, field)?.into();
+            }
+        }
+
+        let mut event = Event::from(log);
+        self.transformer.transform(&mut event);
+        let (details, _) = event.into_log().into_parts();
+        payload["custom_details"] =
+            serde_json::to_value(details).expect("details should serialize");
+        Some(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn encoder() -> PagerdutyEventsEncoder {
+        PagerdutyEventsEncoder {
+            routing_key: Template::try_from("{{ team }}-key").unwrap(),
+            event_action: Template::try_from("{{ action }}").unwrap(),
+            dedup_key: Some(Template::try_from("{{ check }}").unwrap()),
+            summary: Template::try_from("{{ message }}").unwrap(),
+            source: None,
+            severity_key: "severity".to_owned(),
+            component: None,
+            group: None,
+            class: Some(Template::try_from("{{ check }}").unwrap()),
+            transformer: Transformer::default(),
+        }
+    }
+
+    fn log(action: &str) -> LogEvent {
+        let mut log = LogEvent::from("Disk almost full");
+        log.insert("timestamp", Utc.ymd(2023, 1, 2).and_hms(3, 4, 5));
+        log.insert("host", "db-1");
+        log.insert("team", "storage");
+        log.insert("action", action);
+        log.insert("check", "disk_usage");
+        log.insert("severity", "WARN");
+        log
+    }
+
+    #[test]
+    fn encodes_triggers() {
+        let event = encoder().encode(log("trigger")).unwrap();
+
+        assert_eq!(
+            event,
+            json!({
+                "routing_key": "storage-key",
+                "event_action": "trigger",
+                "dedup_key": "disk_usage",
+                "client": "Vector",
+                "payload": {
+                    "summary": "Disk almost full",
+                    "source": "db-1",
+                    "severity": "warning",
+                    "timestamp": "2023-01-02T03:04:05Z",
+                    "class": "disk_usage",
+                    "custom_details": {
+                        "message": "Disk almost full",
+                        "timestamp": "2023-01-02T03:04:05Z",
+                        "host": "db-1",
+                        "team": "storage",
+                        "action": "trigger",
+                        "check": "disk_usage",
+                        "severity": "WARN",
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn encodes_resolutions() {
+        let event = encoder().encode(log("Resolve")).unwrap();
+
+        assert_eq!(
+            event,
+            json!({
+                "routing_key": "storage-key",
+                "event_action": "resolve",
+                "dedup_key": "disk_usage",
+                "client": "Vector",
+            })
+        );
+    }
+
+    #[test]
+    fn drops_invalid_events() {
+        assert_eq!(encoder().encode(log("escalate")), None);
+
+        let mut log = log("trigger");
+        log.remove("check");
+        assert_eq!(encoder().encode(log), None);
+    }
+
+    #[test]
+    fn truncates_summaries() {
+        let summary = truncate("é".repeat(MAX_SUMMARY_LENGTH + 10));
+        assert_eq!(summary.chars().count(), MAX_SUMMARY_LENGTH);
+    }
+}
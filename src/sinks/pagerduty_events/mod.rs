@@ -0,0 +1,88 @@
+//! The `pagerduty_events` sink.
+//!
+//! Sends log events to the PagerDuty Events API v2, as triggers, acknowledgements, or
+//! resolutions of the alerts of their dedup keys, with their routing keys and other fields
+//! rendered from templates.
+
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+pub use self::config::PagerdutyEventsConfig;
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use http::{Response, StatusCode};
+    use hyper::Body;
+    use serde_json::Value as JsonValue;
+    use vector_core::event::{BatchNotifier, BatchStatus, Event, LogEvent};
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        sinks::util::test::build_test_server_generic,
+        test_util::{
+            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+            next_addr,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PagerdutyEventsConfig>();
+    }
+
+    #[tokio::test]
+    async fn sends_events() {
+        let addr = next_addr();
+        let config: PagerdutyEventsConfig = toml::from_str(&format!(
+            r#"endpoint = "http://{}/v2/enqueue"
+            routing_key = "{{{{ team }}}}-key"
+            event_action = "{{{{ action }}}}"
+            dedup_key = "{{{{ check }}}}""#,
+            addr
+        ))
+        .unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (mut rx, trigger, server) = build_test_server_generic(addr, || {
+            Response::builder()
+                .status(StatusCode::ACCEPTED)
+                .body(Body::from(
+                    r#"{"status":"success","message":"Event processed"}"#,
+                ))
+                .unwrap()
+        });
+        tokio::spawn(server);
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let events: Vec<_> = ["trigger", "resolve"]
+            .into_iter()
+            .map(|action| {
+                let mut log = LogEvent::from("Disk almost full").with_batch_notifier(&batch);
+                log.insert("team", "storage");
+                log.insert("action", action);
+                log.insert("check", "disk_usage");
+                Event::from(log)
+            })
+            .collect();
+        drop(batch);
+        run_and_assert_sink_compliance(sink, futures::stream::iter(events), &HTTP_SINK_TAGS).await;
+        drop(trigger);
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let mut actions = Vec::new();
+        for _ in 0..2 {
+            let (parts, body) = rx.next().await.unwrap();
+            assert_eq!(parts.uri.path(), "/v2/enqueue");
+            let event: JsonValue = serde_json::from_slice(&body).unwrap();
+            assert_eq!(event["routing_key"], "storage-key");
+            assert_eq!(event["dedup_key"], "disk_usage");
+            actions.push(event["event_action"].as_str().unwrap().to_owned());
+        }
+        actions.sort();
+        assert_eq!(actions, ["resolve", "trigger"]);
+    }
+}
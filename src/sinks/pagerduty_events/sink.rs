@@ -0,0 +1,65 @@
+use std::num::NonZeroUsize;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{stream::BoxStream, StreamExt};
+use tower::ServiceBuilder;
+use vector_core::event::{Event, EventStatus, Finalizable};
+
+use super::{
+    encoder::PagerdutyEventsEncoder,
+    service::{PagerdutyEventsRequest, PagerdutyEventsRetryLogic, PagerdutyEventsService},
+};
+use crate::sinks::util::{
+    metadata::RequestMetadataBuilder, ServiceBuilderExt, SinkBuilderExt, StreamSink,
+    TowerRequestSettings,
+};
+
+pub struct PagerdutyEventsSink {
+    pub service: PagerdutyEventsService,
+    pub protocol: String,
+    pub encoder: PagerdutyEventsEncoder,
+    pub request: TowerRequestSettings,
+}
+
+#[async_trait]
+impl StreamSink<Event> for PagerdutyEventsSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let sink = *self;
+        let service = ServiceBuilder::new()
+            .settings(sink.request, PagerdutyEventsRetryLogic)
+            .service(sink.service);
+        let encoder = sink.encoder;
+
+        // The Events API takes a single event per request.
+        input
+            .filter_map(move |mut event| {
+                let finalizers = event.take_finalizers();
+                let builder = RequestMetadataBuilder::from_events(&event);
+                let request = match encoder.encode(event.into_log()) {
+                    Some(body) => {
+                        let body = Bytes::from(
+                            serde_json::to_vec(&body).expect("events should serialize"),
+                        );
+                        let metadata = builder.with_request_size(
+                            NonZeroUsize::new(body.len()).expect("events should not be empty"),
+                        );
+                        Some(PagerdutyEventsRequest {
+                            body,
+                            finalizers,
+                            metadata,
+                        })
+                    }
+                    None => {
+                        finalizers.update_status(EventStatus::Rejected);
+                        None
+                    }
+                };
+                futures::future::ready(request)
+            })
+            .into_driver(service)
+            .protocol(sink.protocol)
+            .run()
+            .await
+    }
+}
@@ -0,0 +1,11 @@
+//! The `postgres` sink.
+//!
+//! Batches of events are written as rows of a table of a PostgreSQL server, such as a TimescaleDB
+//! hypertable, with `INSERT` statements, which can upsert them, or with `COPY` statements.
+
+mod config;
+mod pool;
+mod service;
+mod sink;
+
+pub use self::config::PostgresConfig;
@@ -0,0 +1,131 @@
+use std::{
+    ops::Deref,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use openssl::{
+    error::ErrorStack,
+    ssl::{SslConnector, SslMethod},
+};
+use postgres_openssl::MakeTlsConnector;
+use snafu::{ResultExt, Snafu};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio_postgres::{Client, Config, Error as PgError, NoTls};
+use vector_config::configurable_component;
+
+/// Configuration of TLS when connecting to PostgreSQL.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PostgresTlsConfig {
+    /// Absolute path to an additional CA certificate file.
+    ///
+    /// The certificate must be in the DER or PEM (X.509) format.
+    pub ca_file: PathBuf,
+}
+
+#[derive(Debug, Snafu)]
+pub enum ConnectError {
+    #[snafu(display("failed to create tls connector: {}", source))]
+    TlsFailed { source: ErrorStack },
+    #[snafu(display("failed to connect: {}", source))]
+    ConnectionFailed { source: PgError },
+}
+
+/// A pool of connections to the server, opened as they're needed, up to its size.
+pub struct Pool {
+    config: Config,
+    tls: Option<PostgresTlsConfig>,
+    idle: Mutex<Vec<Client>>,
+    permits: Arc<Semaphore>,
+}
+
+impl Pool {
+    pub fn new(config: Config, tls: Option<PostgresTlsConfig>, size: usize) -> Self {
+        Self {
+            config,
+            tls,
+            idle: Mutex::new(Vec::new()),
+            permits: Arc::new(Semaphore::new(size)),
+        }
+    }
+
+    /// Waits for a connection to be available, reusing an idle one or opening a new one.
+    pub async fn get(self: &Arc<Self>) -> Result<PooledClient, ConnectError> {
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .expect("the semaphore should never be closed");
+
+        let idle = self.idle.lock().expect("poisoned lock").pop();
+        let client = match idle.filter(|client| !client.is_closed()) {
+            Some(client) => client,
+            None => self.connect().await?,
+        };
+
+        Ok(PooledClient {
+            client: Some(client),
+            pool: Arc::clone(self),
+            _permit: permit,
+        })
+    }
+
+    async fn connect(&self) -> Result<Client, ConnectError> {
+        let client = match &self.tls {
+            Some(tls) => {
+                let mut builder =
+                    SslConnector::builder(SslMethod::tls_client()).context(TlsFailedSnafu)?;
+                builder
+                    .set_ca_file(tls.ca_file.clone())
+                    .context(TlsFailedSnafu)?;
+                let connector = MakeTlsConnector::new(builder.build());
+
+                let (client, connection) = self
+                    .config
+                    .connect(connector)
+                    .await
+                    .context(ConnectionFailedSnafu)?;
+                tokio::spawn(connection);
+                client
+            }
+            None => {
+                let (client, connection) = self
+                    .config
+                    .connect(NoTls)
+                    .await
+                    .context(ConnectionFailedSnafu)?;
+                tokio::spawn(connection);
+                client
+            }
+        };
+        Ok(client)
+    }
+}
+
+/// A connection of the pool, which is returned to it when dropped, unless it was closed.
+pub struct PooledClient {
+    client: Option<Client>,
+    pool: Arc<Pool>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl Deref for PooledClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        self.client
+            .as_ref()
+            .expect("the client is only taken when dropped")
+    }
+}
+
+impl Drop for PooledClient {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            if !client.is_closed() {
+                self.pool.idle.lock().expect("poisoned lock").push(client);
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::{future::BoxFuture, pin_mut, SinkExt};
+use snafu::{ResultExt, Snafu};
+use tokio_postgres::Error as PgError;
+use tower::Service;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    stream::DriverResponse,
+};
+
+use super::{
+    config::{ConflictAction, InsertMethod, OnConflict},
+    pool::{ConnectError, Pool},
+};
+use crate::sinks::util::retries::RetryLogic;
+
+#[derive(Debug, Snafu)]
+pub enum PostgresError {
+    #[snafu(display("{}", source))]
+    Connect { source: ConnectError },
+    #[snafu(display("Failed to write rows: {}", source))]
+    Write { source: PgError },
+}
+
+/// Quotes `identifier`, so that it's used as is, whatever its case or characters.
+pub fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn column_list(columns: &[String]) -> String {
+    columns
+        .iter()
+        .map(|column| quote_identifier(column))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Builds the statement inserting the rows of batches, which are passed as a JSON array of
+/// objects, converted to the types of the columns of the table by `json_populate_recordset`.
+pub fn insert_statement(
+    table: &str,
+    columns: &[String],
+    on_conflict: Option<&OnConflict>,
+) -> String {
+    let columns_list = column_list(columns);
+    let mut statement = format!(
+        "INSERT INTO {table} ({columns}) SELECT {columns} FROM json_populate_recordset(NULL::{table}, $1::text::json)",
+        table = table,
+        columns = columns_list,
+    );
+    if let Some(on_conflict) = on_conflict {
+        statement.push_str(&format!(
+            " ON CONFLICT ({}) DO ",
+            column_list(&on_conflict.columns)
+        ));
+        let updates: Vec<_> = columns
+            .iter()
+            .filter(|column| !on_conflict.columns.contains(column))
+            .map(|column| {
+                let column = quote_identifier(column);
+                format!("{} = EXCLUDED.{}", column, column)
+            })
+            .collect();
+        match on_conflict.action {
+            ConflictAction::Update if !updates.is_empty() => {
+                statement.push_str("UPDATE SET ");
+                statement.push_str(&updates.join(", "));
+            }
+            _ => statement.push_str("NOTHING"),
+        }
+    }
+    statement
+}
+
+/// Builds the statement copying the rows of batches, which are passed as CSV.
+pub fn copy_statement(table: &str, columns: &[String]) -> String {
+    format!(
+        "COPY {} ({}) FROM STDIN WITH (FORMAT csv)",
+        table,
+        column_list(columns)
+    )
+}
+
+/// A batch of rows, encoded for the insert method of the sink.
+#[derive(Clone)]
+pub struct PostgresRequest {
+    pub rows: Bytes,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for PostgresRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for PostgresRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata.clone()
+    }
+}
+
+pub struct PostgresResponse;
+
+impl DriverResponse for PostgresResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+}
+
+#[derive(Clone)]
+pub struct PostgresService {
+    pub pool: Arc<Pool>,
+    pub method: InsertMethod,
+    pub statement: Arc<str>,
+}
+
+impl Service<PostgresRequest> for PostgresService {
+    type Response = PostgresResponse;
+    type Error = PostgresError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: PostgresRequest) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move {
+            let client = service.pool.get().await.context(ConnectSnafu)?;
+            match service.method {
+                InsertMethod::Insert => {
+                    let rows = std::str::from_utf8(&request.rows).expect("rows should be JSON");
+                    client
+                        .execute(service.statement.as_ref(), &[&rows])
+                        .await
+                        .context(WriteSnafu)?;
+                }
+                InsertMethod::Copy => {
+                    let sink = client
+                        .copy_in::<_, Bytes>(service.statement.as_ref())
+                        .await
+                        .context(WriteSnafu)?;
+                    pin_mut!(sink);
+                    sink.send(request.rows.clone()).await.context(WriteSnafu)?;
+                    sink.as_mut().finish().await.context(WriteSnafu)?;
+                }
+            }
+            Ok(PostgresResponse)
+        })
+    }
+}
+
+/// Whether writes failing with `error` are retried, which are those failing because of the
+/// connection or of the load of the server, rather than because of the rows themselves.
+fn is_retriable(error: &PgError) -> bool {
+    match error.code() {
+        Some(state) => {
+            let code = state.code();
+            // Connection exceptions, transaction rollbacks such as serialization failures and
+            // deadlocks, insufficient resources, and the server shutting down.
+            code.starts_with("08")
+                || code.starts_with("40")
+                || code.starts_with("53")
+                || code.starts_with("57P")
+        }
+        // Errors without a state are those of the connection.
+        None => true,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PostgresRetryLogic;
+
+impl RetryLogic for PostgresRetryLogic {
+    type Error = PostgresError;
+    type Response = PostgresResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            PostgresError::Connect {
+                source: ConnectError::TlsFailed { .. },
+            } => false,
+            PostgresError::Connect {
+                source: ConnectError::ConnectionFailed { source },
+            }
+            | PostgresError::Write { source } => is_retriable(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns() -> Vec<String> {
+        vec!["id".to_owned(), "message".to_owned(), "Host".to_owned()]
+    }
+
+    #[test]
+    fn builds_insert_statement() {
+        assert_eq!(
+            insert_statement("public.events", &columns(), None),
+            r#"INSERT INTO public.events ("id", "message", "Host") SELECT "id", "message", "Host" FROM json_populate_recordset(NULL::public.events, $1::text::json)"#
+        );
+    }
+
+    #[test]
+    fn builds_upsert_statement() {
+        let on_conflict = OnConflict {
+            columns: vec!["id".to_owned()],
+            action: ConflictAction::Update,
+        };
+
+        assert!(insert_statement("events", &columns(), Some(&on_conflict)).ends_with(
+            r#" ON CONFLICT ("id") DO UPDATE SET "message" = EXCLUDED."message", "Host" = EXCLUDED."Host""#
+        ));
+
+        let on_conflict = OnConflict {
+            action: ConflictAction::Nothing,
+            ..on_conflict
+        };
+        assert!(insert_statement("events", &columns(), Some(&on_conflict))
+            .ends_with(r#" ON CONFLICT ("id") DO NOTHING"#));
+    }
+
+    #[test]
+    fn builds_copy_statement() {
+        assert_eq!(
+            copy_statement("events", &columns()),
+            r#"COPY events ("id", "message", "Host") FROM STDIN WITH (FORMAT csv)"#
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use std::{num::NonZeroUsize, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::SecondsFormat;
+use futures::{stream::BoxStream, StreamExt};
+use serde_json::{Map, Value as JsonValue};
+use tower::ServiceBuilder;
+use vector_core::{
+    event::{EventFinalizers, Finalizable, LogEvent, Value},
+    stream::BatcherSettings,
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use super::{
+    config::{InsertMethod, OnConflict},
+    pool::Pool,
+    service::{
+        copy_statement, insert_statement, PostgresError, PostgresRequest, PostgresRetryLogic,
+        PostgresService,
+    },
+};
+use crate::{
+    codecs::Transformer,
+    event::Event,
+    sinks::util::{
+        metadata::RequestMetadataBuilder, retries::RetryLogic, ServiceBuilderExt, SinkBuilderExt,
+        StreamSink, TowerRequestSettings,
+    },
+};
+
+/// How long to wait before loading the columns of the table again, when they can't be loaded on
+/// start up.
+const LOAD_COLUMNS_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The columns of a table that values can be inserted into, which excludes the identity columns
+/// generated by the server.
+const COLUMNS_QUERY: &str = "SELECT attname::text FROM pg_attribute \
+    WHERE attrelid = $1::text::regclass AND attnum > 0 AND NOT attisdropped AND attidentity <> 'a' \
+    ORDER BY attnum";
+
+/// An event, encoded as a row, with the sizes it had before being transformed.
+struct EncodedRow {
+    row: Vec<u8>,
+    finalizers: EventFinalizers,
+    byte_size: usize,
+    json_size: usize,
+}
+
+impl ByteSizeOf for EncodedRow {
+    fn size_of(&self) -> usize {
+        self.row.len()
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
+/// The columns of the table, with the fields of events whose values they hold.
+struct ColumnMapping {
+    columns: Vec<String>,
+    fields: Vec<String>,
+}
+
+impl ColumnMapping {
+    fn values<'a>(
+        &'a self,
+        log: &'a LogEvent,
+    ) -> impl Iterator<Item = (&'a String, Option<&'a Value>)> {
+        self.columns
+            .iter()
+            .zip(&self.fields)
+            .map(move |(column, field)| (column, log.get(field.as_str())))
+    }
+
+    /// Encodes `log` as a JSON object, for inserts.
+    fn encode_json(&self, log: &LogEvent) -> Vec<u8> {
+        let row: Map<String, JsonValue> = self
+            .values(log)
+            .map(|(column, value)| {
+                let value = value
+                    .and_then(|value| serde_json::to_value(value).ok())
+                    .unwrap_or(JsonValue::Null);
+                (column.clone(), value)
+            })
+            .collect();
+        serde_json::to_vec(&row).expect("rows should serialize")
+    }
+
+    /// Encodes `log` as a line of CSV, for copies.
+    fn encode_csv(&self, log: &LogEvent) -> Vec<u8> {
+        let mut line = String::new();
+        for (index, (_, value)) in self.values(log).enumerate() {
+            if index > 0 {
+                line.push(',');
+            }
+            // Unquoted empty values are nulls, while quoted ones are empty strings.
+            let text = match value {
+                None | Some(Value::Null) => continue,
+                Some(Value::Bytes(bytes)) => String::from_utf8_lossy(bytes).into_owned(),
+                Some(Value::Timestamp(timestamp)) => {
+                    timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                }
+                Some(value @ (Value::Object(_) | Value::Array(_))) => {
+                    serde_json::to_string(value).expect("values should serialize")
+                }
+                Some(value) => value.to_string_lossy().into_owned(),
+            };
+            line.push('"');
+            line.push_str(&text.replace('"', "\"\""));
+            line.push('"');
+        }
+        line.push('\n');
+        line.into_bytes()
+    }
+}
+
+pub struct PostgresSink {
+    pub pool: Arc<Pool>,
+    pub table: String,
+    /// The columns of the table mapped to the fields of events, or all of them if this is empty.
+    pub columns: Vec<(String, String)>,
+    pub method: InsertMethod,
+    pub on_conflict: Option<OnConflict>,
+    pub transformer: Transformer,
+    pub batch_settings: BatcherSettings,
+    pub request: TowerRequestSettings,
+}
+
+impl PostgresSink {
+    /// Loads the columns of the table, waiting for the server to be reachable, when they aren't
+    /// mapped to fields.
+    async fn column_mapping(&self) -> Result<ColumnMapping, PostgresError> {
+        if !self.columns.is_empty() {
+            let (columns, fields) = self.columns.iter().cloned().unzip();
+            return Ok(ColumnMapping { columns, fields });
+        }
+
+        loop {
+            let result = match self.pool.get().await {
+                Ok(client) => client
+                    .query(COLUMNS_QUERY, &[&self.table.as_str()])
+                    .await
+                    .map_err(|source| PostgresError::Write { source }),
+                Err(source) => Err(PostgresError::Connect { source }),
+            };
+            match result {
+                Ok(rows) => {
+                    let columns: Vec<String> = rows.iter().map(|row| row.get(0)).collect();
+                    return Ok(ColumnMapping {
+                        fields: columns.clone(),
+                        columns,
+                    });
+                }
+                Err(error) if PostgresRetryLogic.is_retriable_error(&error) => {
+                    error!(message = "Failed to load the columns of the table, retrying.", %error);
+                    tokio::time::sleep(LOAD_COLUMNS_RETRY_INTERVAL).await;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for PostgresSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mapping = match self.column_mapping().await {
+            Ok(mapping) => mapping,
+            Err(error) => {
+                error!(message = "Failed to load the columns of the table.", %error);
+                return Err(());
+            }
+        };
+
+        let statement = match self.method {
+            InsertMethod::Insert => {
+                insert_statement(&self.table, &mapping.columns, self.on_conflict.as_ref())
+            }
+            InsertMethod::Copy => copy_statement(&self.table, &mapping.columns),
+        };
+        let service = ServiceBuilder::new()
+            .settings(self.request, PostgresRetryLogic)
+            .service(PostgresService {
+                pool: Arc::clone(&self.pool),
+                method: self.method,
+                statement: statement.into(),
+            });
+
+        let method = self.method;
+        let transformer = self.transformer;
+        input
+            .map(|mut event| {
+                let byte_size = event.size_of();
+                let json_size = event.estimated_json_encoded_size_of();
+                transformer.transform(&mut event);
+                let row = match method {
+                    InsertMethod::Insert => mapping.encode_json(event.as_log()),
+                    InsertMethod::Copy => mapping.encode_csv(event.as_log()),
+                };
+                EncodedRow {
+                    row,
+                    finalizers: event.take_finalizers(),
+                    byte_size,
+                    json_size,
+                }
+            })
+            .batched(self.batch_settings.into_byte_size_config())
+            .map(|rows| {
+                let count = rows.len();
+                let mut byte_size = 0;
+                let mut json_size = 0;
+                let mut finalizers = EventFinalizers::default();
+                let mut body = Vec::new();
+                if method == InsertMethod::Insert {
+                    body.push(b'[');
+                }
+                for (index, row) in rows.into_iter().enumerate() {
+                    byte_size += row.byte_size;
+                    json_size += row.json_size;
+                    finalizers.merge(row.finalizers);
+                    if method == InsertMethod::Insert && index > 0 {
+                        body.push(b',');
+                    }
+                    body.extend_from_slice(&row.row);
+                }
+                if method == InsertMethod::Insert {
+                    body.push(b']');
+                }
+                let metadata = RequestMetadataBuilder::new(count, byte_size, json_size)
+                    .with_request_size(
+                        NonZeroUsize::new(body.len()).expect("batches should not be empty"),
+                    );
+
+                PostgresRequest {
+                    rows: Bytes::from(body),
+                    finalizers,
+                    metadata,
+                }
+            })
+            .into_driver(service)
+            .protocol("postgres")
+            .run()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn mapping() -> ColumnMapping {
+        ColumnMapping {
+            columns: vec![
+                "message".to_owned(),
+                "status".to_owned(),
+                "time".to_owned(),
+                "tags".to_owned(),
+                "user".to_owned(),
+            ],
+            fields: vec![
+                "message".to_owned(),
+                "http.status".to_owned(),
+                "timestamp".to_owned(),
+                "tags".to_owned(),
+                "user".to_owned(),
+            ],
+        }
+    }
+
+    fn log() -> LogEvent {
+        let mut log = LogEvent::from("say \"hello\"");
+        log.insert("http.status", 200);
+        log.insert("timestamp", Utc.ymd(2023, 1, 2).and_hms(3, 4, 5));
+        log.insert("tags", Value::Array(vec!["a".into(), "b".into()]));
+        log
+    }
+
+    #[test]
+    fn encodes_json_rows() {
+        let row: JsonValue = serde_json::from_slice(&mapping().encode_json(&log())).unwrap();
+
+        assert_eq!(
+            row,
+            serde_json::json!({
+                "message": "say \"hello\"",
+                "status": 200,
+                "time": "2023-01-02T03:04:05Z",
+                "tags": ["a", "b"],
+                "user": null,
+            })
+        );
+    }
+
+    #[test]
+    fn encodes_csv_rows() {
+        let row = String::from_utf8(mapping().encode_csv(&log())).unwrap();
+
+        assert_eq!(
+            row,
+            "\"say \"\"hello\"\"\",\"200\",\"2023-01-02T03:04:05Z\",\"[\"\"a\"\",\"\"b\"\"]\",\n"
+        );
+    }
+}
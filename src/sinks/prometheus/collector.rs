@@ -2,15 +2,19 @@ use std::{collections::BTreeMap, fmt::Write as _};
 
 use chrono::Utc;
 use indexmap::map::IndexMap;
+use lookup::path;
 use prometheus_parser::{proto, METRIC_NAME_LABEL};
-use vector_core::event::metric::{samples_to_buckets, MetricSketch, MetricTags, Quantile};
+use vector_core::event::metric::{samples_to_buckets, MetricSketch, MetricTags, Quantile, Sample};
 
 use crate::{
-    event::metric::{Metric, MetricKind, MetricValue, StatisticKind},
+    event::{
+        metric::{Metric, MetricKind, MetricValue, StatisticKind},
+        Value,
+    },
     sinks::util::{encode_namespace, statistic::DistributionStatistic},
 };
 
-pub(super) trait MetricCollector {
+pub(crate) trait MetricCollector {
     type Output;
 
     fn new() -> Self;
@@ -316,13 +320,43 @@ impl StringCollector {
 
 type Labels = Vec<proto::Label>;
 
+/// The metadata key under which the exemplars, help, and unit of metrics are read from events, as
+/// attached by the `prometheus_remote_write` source.
+const REMOTE_WRITE_METADATA_KEY: &str = "prometheus_remote_write";
+
+/// The schema of native histograms, whose bucket boundaries grow by a factor of 2^(2^-3), so
+/// that values are approximated to within about 4.3%.
+const NATIVE_HISTOGRAM_SCHEMA: i32 = 3;
+
+/// The breadth of the zero bucket of native histograms, which is the default of Prometheus.
+const NATIVE_HISTOGRAM_ZERO_THRESHOLD: f64 = 2.938_735_877_055_719e-39;
+
+#[derive(Default)]
+struct Series {
+    samples: Vec<proto::Sample>,
+    exemplars: Vec<proto::Exemplar>,
+    histograms: Vec<proto::Histogram>,
+}
+
 pub(super) struct TimeSeries {
-    buffer: IndexMap<Labels, Vec<proto::Sample>>,
+    buffer: IndexMap<Labels, Series>,
     metadata: IndexMap<String, proto::MetricMetadata>,
     timestamp: Option<i64>,
+    send_exemplars: bool,
+    native_histograms: bool,
 }
 
 impl TimeSeries {
+    /// Creates a collector which also encodes the exemplars of metrics if `send_exemplars` is
+    /// set, and their distributions as native histograms if `native_histograms` is set.
+    pub(super) fn with_options(send_exemplars: bool, native_histograms: bool) -> Self {
+        Self {
+            send_exemplars,
+            native_histograms,
+            ..Self::new()
+        }
+    }
+
     fn make_labels(
         tags: Option<&MetricTags>,
         name: &str,
@@ -354,6 +388,113 @@ impl TimeSeries {
             .timestamp
             .get_or_insert_with(|| Utc::now().timestamp_millis())
     }
+
+    /// Encodes `metric`, along with the help, unit, and exemplars it was annotated with.
+    pub(super) fn encode_annotated_metric(
+        &mut self,
+        default_namespace: Option<&str>,
+        buckets: &[f64],
+        quantiles: &[f64],
+        metric: &Metric,
+    ) {
+        if metric.kind() != MetricKind::Absolute {
+            return;
+        }
+
+        let native = self.native_histograms
+            && matches!(
+                metric.value(),
+                MetricValue::Distribution {
+                    statistic: StatisticKind::Histogram,
+                    ..
+                }
+            );
+        if native {
+            self.encode_native_histogram(default_namespace, metric);
+        } else {
+            self.encode_metric(default_namespace, buckets, quantiles, metric);
+        }
+
+        let annotations = metric.metadata().value();
+        if let Some(metadata) = self.metadata.get_mut(metric.name()) {
+            let help = annotations.get(path!(REMOTE_WRITE_METADATA_KEY, "help"));
+            if let Some(help) = help.and_then(Value::as_str) {
+                metadata.help = help.into_owned();
+            }
+            let unit = annotations.get(path!(REMOTE_WRITE_METADATA_KEY, "unit"));
+            if let Some(unit) = unit.and_then(Value::as_str) {
+                metadata.unit = unit.into_owned();
+            }
+        }
+
+        if !self.send_exemplars {
+            return;
+        }
+        let exemplars = match annotations
+            .get(path!(REMOTE_WRITE_METADATA_KEY, "exemplars"))
+            .and_then(Value::as_array)
+        {
+            Some(exemplars) => exemplars,
+            None => return,
+        };
+        let name = encode_namespace(metric.namespace().or(default_namespace), '_', metric.name());
+        let timestamp = metric
+            .timestamp()
+            .map(|t| t.timestamp_millis())
+            .unwrap_or_else(|| self.default_timestamp());
+        for exemplar in exemplars {
+            let exemplar = match decode_exemplar(exemplar, timestamp) {
+                Some(exemplar) => exemplar,
+                None => continue,
+            };
+            // Exemplars of classic histograms belong to the bucket of their value, while those of
+            // summaries can't be sent.
+            let extra = match metric.value() {
+                _ if native => None,
+                MetricValue::Counter { .. } | MetricValue::Gauge { .. } => None,
+                MetricValue::Distribution {
+                    statistic: StatisticKind::Histogram,
+                    ..
+                } => Some(bucket_bound(buckets.iter().copied(), exemplar.value)),
+                MetricValue::AggregatedHistogram { buckets, .. } => Some(bucket_bound(
+                    buckets.iter().map(|bucket| bucket.upper_limit),
+                    exemplar.value,
+                )),
+                _ => break,
+            };
+            let (suffix, extra) = match extra {
+                Some(le) => ("_bucket", Some(("le", le))),
+                None => ("", None),
+            };
+            if let Some(series) =
+                self.buffer
+                    .get_mut(&Self::make_labels(metric.tags(), &name, suffix, extra))
+            {
+                series.exemplars.push(exemplar);
+            }
+        }
+    }
+
+    /// Encodes the samples of a distribution as a native histogram, with exponential buckets.
+    fn encode_native_histogram(&mut self, default_namespace: Option<&str>, metric: &Metric) {
+        let samples = match metric.value() {
+            MetricValue::Distribution { samples, .. } => samples,
+            _ => return,
+        };
+        let name = encode_namespace(metric.namespace().or(default_namespace), '_', metric.name());
+        self.emit_metadata(metric.name(), &name, metric.value());
+
+        let timestamp = metric
+            .timestamp()
+            .map(|t| t.timestamp_millis())
+            .unwrap_or_else(|| self.default_timestamp());
+        let histogram = native_histogram(samples, timestamp);
+        self.buffer
+            .entry(Self::make_labels(metric.tags(), &name, "", None))
+            .or_default()
+            .histograms
+            .push(histogram);
+    }
 }
 
 impl MetricCollector for TimeSeries {
@@ -364,6 +505,8 @@ impl MetricCollector for TimeSeries {
             buffer: Default::default(),
             metadata: Default::default(),
             timestamp: None,
+            send_exemplars: false,
+            native_histograms: false,
         }
     }
 
@@ -393,6 +536,7 @@ impl MetricCollector for TimeSeries {
         self.buffer
             .entry(Self::make_labels(tags, name, suffix, extra))
             .or_default()
+            .samples
             .push(proto::Sample { value, timestamp });
     }
 
@@ -400,7 +544,12 @@ impl MetricCollector for TimeSeries {
         let timeseries = self
             .buffer
             .into_iter()
-            .map(|(labels, samples)| proto::TimeSeries { labels, samples })
+            .map(|(labels, series)| proto::TimeSeries {
+                labels,
+                samples: series.samples,
+                exemplars: series.exemplars,
+                histograms: series.histograms,
+            })
             .collect::<Vec<_>>();
         let metadata = self
             .metadata
@@ -414,6 +563,120 @@ impl MetricCollector for TimeSeries {
     }
 }
 
+/// Decodes an exemplar attached to a metric by the `prometheus_remote_write` source, or with the
+/// same layout, defaulting to the timestamp of the metric.
+fn decode_exemplar(exemplar: &Value, default_timestamp: i64) -> Option<proto::Exemplar> {
+    let value = match exemplar.get("value")? {
+        Value::Float(value) => value.into_inner(),
+        Value::Integer(value) => *value as f64,
+        _ => return None,
+    };
+    let labels = exemplar
+        .get("labels")
+        .and_then(Value::as_object)
+        .map(|labels| {
+            labels
+                .iter()
+                .map(|(name, value)| proto::Label {
+                    name: name.clone(),
+                    value: value.to_string_lossy().into_owned(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let timestamp = match exemplar.get("timestamp") {
+        Some(Value::Timestamp(timestamp)) => timestamp.timestamp_millis(),
+        _ => default_timestamp,
+    };
+    Some(proto::Exemplar {
+        labels,
+        value,
+        timestamp,
+    })
+}
+
+/// The `le` label of the bucket holding `value`, out of the ascending upper limits of the buckets
+/// of a histogram.
+fn bucket_bound(upper_limits: impl Iterator<Item = f64>, value: f64) -> String {
+    upper_limits
+        .filter(|upper_limit| upper_limit.is_finite())
+        .find(|upper_limit| value <= *upper_limit)
+        .map_or_else(|| "+Inf".to_string(), |upper_limit| upper_limit.to_string())
+}
+
+fn native_histogram(samples: &[Sample], timestamp: i64) -> proto::Histogram {
+    let mut count = 0;
+    let mut sum = 0.0;
+    let mut zero_count = 0;
+    let mut positive = BTreeMap::new();
+    let mut negative = BTreeMap::new();
+    for sample in samples {
+        let rate = u64::from(sample.rate);
+        count += rate;
+        sum += sample.value * rate as f64;
+        // Infinite and NaN values are only counted, as they don't belong to any bucket.
+        if !sample.value.is_finite() {
+            continue;
+        }
+        if sample.value.abs() <= NATIVE_HISTOGRAM_ZERO_THRESHOLD {
+            zero_count += rate;
+        } else if sample.value > 0.0 {
+            *positive.entry(bucket_index(sample.value)).or_insert(0) += rate;
+        } else {
+            *negative.entry(bucket_index(-sample.value)).or_insert(0) += rate;
+        }
+    }
+
+    let (positive_spans, positive_deltas) = encode_buckets(&positive);
+    let (negative_spans, negative_deltas) = encode_buckets(&negative);
+    proto::Histogram {
+        count: Some(proto::histogram::Count::CountInt(count)),
+        sum,
+        schema: NATIVE_HISTOGRAM_SCHEMA,
+        zero_threshold: NATIVE_HISTOGRAM_ZERO_THRESHOLD,
+        zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(zero_count)),
+        negative_spans,
+        negative_deltas,
+        negative_counts: Vec::new(),
+        positive_spans,
+        positive_deltas,
+        positive_counts: Vec::new(),
+        reset_hint: proto::histogram::ResetHint::Unknown as i32,
+        timestamp,
+    }
+}
+
+/// The index of the bucket of a native histogram holding the positive `value`, the bucket `i`
+/// holding the values in (base^(i-1), base^i].
+fn bucket_index(value: f64) -> i32 {
+    (value.log2() * f64::from(1 << NATIVE_HISTOGRAM_SCHEMA)).ceil() as i32
+}
+
+/// Encodes the counts of the buckets of a native histogram, by index, as the spans of consecutive
+/// buckets, and the difference of the count of each bucket with that of the previous one.
+fn encode_buckets(buckets: &BTreeMap<i32, u64>) -> (Vec<proto::BucketSpan>, Vec<i64>) {
+    let mut spans: Vec<proto::BucketSpan> = Vec::new();
+    let mut deltas = Vec::with_capacity(buckets.len());
+    let mut previous: Option<(i32, i64)> = None;
+    for (&index, &count) in buckets {
+        let count = count as i64;
+        match (previous, spans.last_mut()) {
+            (Some((previous, _)), Some(span)) if index == previous + 1 => span.length += 1,
+            (Some((previous, _)), _) => spans.push(proto::BucketSpan {
+                offset: index - previous - 1,
+                length: 1,
+            }),
+            (None, _) => spans.push(proto::BucketSpan {
+                offset: index,
+                length: 1,
+            }),
+        }
+        deltas.push(count - previous.map_or(0, |(_, count)| count));
+        previous = Some((index, count));
+    }
+    (spans, deltas)
+}
+
 const fn prometheus_metric_type(metric_value: &MetricValue) -> proto::MetricType {
     use proto::MetricType;
     match metric_value {
@@ -439,6 +702,7 @@ mod tests {
 
     use chrono::{DateTime, TimeZone};
     use indoc::indoc;
+    use ordered_float::NotNan;
     use similar_asserts::assert_eq;
     use vector_core::metric_tags;
 
@@ -490,6 +754,8 @@ mod tests {
                                 value: $svalue,
                                 timestamp: $timestamp,
                             }],
+                            exemplars: Vec::new(),
+                            histograms: Vec::new(),
                         },
                     )*
                 ],
@@ -938,4 +1204,129 @@ mod tests {
             "#}
         );
     }
+
+    #[test]
+    fn encodes_native_histogram_request() {
+        let metric = Metric::new(
+            "requests".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Distribution {
+                samples: vector_core::samples![1.0 => 3, 2.0 => 3, 3.0 => 2, 0.0 => 1],
+                statistic: StatisticKind::Histogram,
+            },
+        )
+        .with_timestamp(Some(timestamp()));
+        let mut time_series = TimeSeries::with_options(false, true);
+        time_series.encode_annotated_metric(Some("vector"), &[0.0, 2.5, 5.0], &[], &metric);
+        let request = time_series.finish();
+
+        assert_eq!(request.timeseries.len(), 1);
+        let series = &request.timeseries[0];
+        assert!(series.samples.is_empty());
+        assert_eq!(
+            series.histograms,
+            vec![proto::Histogram {
+                count: Some(proto::histogram::Count::CountInt(9)),
+                sum: 15.0,
+                schema: 3,
+                zero_threshold: NATIVE_HISTOGRAM_ZERO_THRESHOLD,
+                zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(1)),
+                negative_spans: Vec::new(),
+                negative_deltas: Vec::new(),
+                negative_counts: Vec::new(),
+                // The buckets of 1, 2, and 3, which are (2^(-1/8), 1], (2^(7/8), 2], and
+                // (2^(12/8), 2^(13/8)].
+                positive_spans: vec![
+                    proto::BucketSpan {
+                        offset: 0,
+                        length: 1
+                    },
+                    proto::BucketSpan {
+                        offset: 7,
+                        length: 1
+                    },
+                    proto::BucketSpan {
+                        offset: 4,
+                        length: 1
+                    },
+                ],
+                positive_deltas: vec![3, 0, -1],
+                positive_counts: Vec::new(),
+                reset_hint: 0,
+                timestamp: 1612325106789,
+            }]
+        );
+        assert_eq!(
+            request.metadata[0].r#type,
+            proto::MetricType::Histogram as i32
+        );
+    }
+
+    #[test]
+    fn encodes_annotations_request() {
+        let mut metric = Metric::new(
+            "requests".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vector_core::buckets![0.1 => 1, 1.0 => 2],
+                count: 4,
+                sum: 4.5,
+            },
+        )
+        .with_timestamp(Some(timestamp()));
+        let value = metric.metadata_mut().value_mut();
+        value.insert(path!(REMOTE_WRITE_METADATA_KEY, "help"), "Request latency");
+        value.insert(path!(REMOTE_WRITE_METADATA_KEY, "unit"), "seconds");
+        value.insert(
+            path!(REMOTE_WRITE_METADATA_KEY, "exemplars"),
+            Value::Array(vec![exemplar("abc", 0.05), exemplar("def", 2.5)]),
+        );
+
+        let mut time_series = TimeSeries::with_options(true, false);
+        time_series.encode_annotated_metric(None, &[], &[], &metric);
+        let request = time_series.finish();
+
+        let exemplars = request
+            .timeseries
+            .iter()
+            .map(|series| {
+                let labels = series
+                    .labels
+                    .iter()
+                    .map(|label| label.value.as_str())
+                    .collect::<Vec<_>>();
+                let trace_ids = series
+                    .exemplars
+                    .iter()
+                    .map(|exemplar| exemplar.labels[0].value.as_str())
+                    .collect::<Vec<_>>();
+                (labels, trace_ids)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            exemplars,
+            vec![
+                (vec!["requests_bucket", "0.1"], vec!["abc"]),
+                (vec!["requests_bucket", "1"], vec![]),
+                (vec!["requests_bucket", "+Inf"], vec!["def"]),
+                (vec!["requests_sum"], vec![]),
+                (vec!["requests_count"], vec![]),
+            ]
+        );
+        assert_eq!(request.timeseries[0].exemplars[0].timestamp, 1612325106789);
+        assert_eq!(request.metadata[0].help, "Request latency");
+        assert_eq!(request.metadata[0].unit, "seconds");
+    }
+
+    fn exemplar(trace_id: &str, value: f64) -> Value {
+        let mut labels = BTreeMap::new();
+        labels.insert("trace_id".to_string(), Value::from(trace_id));
+        let mut exemplar = BTreeMap::new();
+        exemplar.insert("labels".to_string(), Value::from(labels));
+        exemplar.insert(
+            "value".to_string(),
+            Value::Float(NotNan::new(value).unwrap()),
+        );
+        Value::from(exemplar)
+    }
 }
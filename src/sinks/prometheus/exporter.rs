@@ -35,7 +35,7 @@ use crate::{
         metric::{Metric, MetricData, MetricKind, MetricSeries, MetricValue},
         Event, EventStatus, Finalizable,
     },
-    http::Auth,
+    http::{Auth, MaybeAuth},
     internal_events::{PrometheusNormalizationError, PrometheusServerRequestComplete},
     sinks::{
         util::{
@@ -189,6 +189,7 @@ impl SinkConfig for PrometheusExporterConfig {
         }
 
         validate_quantiles(&self.quantiles)?;
+        self.auth.ensure_static()?;
 
         let sink = PrometheusExporter::new(self.clone());
         let healthcheck = future::ok(()).boxed();
@@ -356,6 +357,8 @@ fn authorized(req: &Request<Body>, auth: &Option<Auth>) -> bool {
                 Auth::Bearer { token } => {
                     HeaderValue::from_str(format!("Bearer {}", token.inner()).as_str())
                 }
+                // Access tokens can't be validated without the authorization server.
+                Auth::OAuth2(_) => return false,
             };
 
             if let Ok(encoded_credentials) = encoded_credentials {
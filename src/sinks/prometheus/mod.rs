@@ -2,13 +2,13 @@ use vector_common::sensitive_string::SensitiveString;
 #[cfg(test)]
 use vector_core::event::Metric;
 
-mod collector;
+pub(crate) mod collector;
 pub(crate) mod exporter;
 pub(crate) mod remote_write;
 
 use vector_config::configurable_component;
 
-use crate::aws::AwsAuthentication;
+use crate::{aws::AwsAuthentication, http::OAuth2Config};
 
 /// Authentication strategies.
 #[configurable_component]
@@ -32,17 +32,24 @@ pub enum PrometheusRemoteWriteAuth {
         token: SensitiveString,
     },
 
+    /// OAuth2 authentication, with the client credentials grant.
+    ///
+    /// Access tokens are requested from the token endpoint with the credentials of the client, and
+    /// passed as bearer tokens. They're cached, and refreshed before they expire.
+    #[serde(rename = "oauth2")]
+    OAuth2(#[configurable(derived)] OAuth2Config),
+
     /// Amazon Prometheus Service-specific authentication.
     Aws(#[configurable(derived)] AwsAuthentication),
 }
 
-fn default_histogram_buckets() -> Vec<f64> {
+pub(crate) fn default_histogram_buckets() -> Vec<f64> {
     vec![
         0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
     ]
 }
 
-fn default_summary_quantiles() -> Vec<f64> {
+pub(crate) fn default_summary_quantiles() -> Vec<f64> {
     vec![0.5, 0.75, 0.9, 0.95, 0.99]
 }
 
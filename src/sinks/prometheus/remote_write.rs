@@ -17,7 +17,7 @@ use crate::{
     aws::RegionOrEndpoint,
     config::{self, AcknowledgementsConfig, Input, SinkConfig},
     event::{Event, Metric},
-    http::{Auth, HttpClient},
+    http::{build_authenticator, Auth, HttpAuthenticator, HttpClient},
     internal_events::{EndpointBytesSent, TemplateRenderingError},
     sinks::{
         self,
@@ -81,6 +81,25 @@ pub struct RemoteWriteConfig {
     #[serde(default = "super::default_summary_quantiles")]
     pub quantiles: Vec<f64>,
 
+    /// Whether to send the exemplars of metrics.
+    ///
+    /// Exemplars are read from the metadata of metric events, where the `prometheus_remote_write`
+    /// source attaches those it receives, and are sent with the series of counters, gauges, and
+    /// the buckets of histograms. Prometheus only stores them with its exemplar storage enabled.
+    #[serde(default)]
+    pub send_exemplars: bool,
+
+    /// Whether to send [distribution][dist_metric_docs] metrics aggregated into histograms as
+    /// native histograms.
+    ///
+    /// Native histograms have exponential buckets, rather than the buckets set by `buckets`, and
+    /// are only accepted by Prometheus with its native histograms feature enabled. Aggregated
+    /// histograms are still sent with their own buckets.
+    ///
+    /// [dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+    #[serde(default)]
+    pub native_histograms: bool,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: BatchConfig<PrometheusRemoteWriteDefaultBatchSettings>,
@@ -149,6 +168,9 @@ impl SinkConfig for RemoteWriteConfig {
                 None,
                 None,
             ),
+            Some(PrometheusRemoteWriteAuth::OAuth2(oauth2)) => {
+                (Some(Auth::OAuth2(oauth2.clone())), None, None)
+            }
             Some(PrometheusRemoteWriteAuth::Aws(aws_auth)) => {
                 let region = self
                     .aws
@@ -165,6 +187,7 @@ impl SinkConfig for RemoteWriteConfig {
             }
             None => (None, None, None),
         };
+        let http_auth = build_authenticator(http_auth.as_ref(), &client).await?;
 
         let http_request_builder = Arc::new(HttpRequestBuilder {
             endpoint: endpoint.clone(),
@@ -179,6 +202,8 @@ impl SinkConfig for RemoteWriteConfig {
             client,
             buckets,
             quantiles,
+            send_exemplars: self.send_exemplars,
+            native_histograms: self.native_histograms,
             http_request_builder,
         };
 
@@ -263,14 +288,17 @@ struct RemoteWriteService {
     client: HttpClient,
     buckets: Vec<f64>,
     quantiles: Vec<f64>,
+    send_exemplars: bool,
+    native_histograms: bool,
     http_request_builder: Arc<HttpRequestBuilder>,
 }
 
 impl RemoteWriteService {
     fn encode_events(&self, metrics: Vec<Metric>) -> Bytes {
-        let mut time_series = collector::TimeSeries::new();
+        let mut time_series =
+            collector::TimeSeries::with_options(self.send_exemplars, self.native_histograms);
         for metric in metrics {
-            time_series.encode_metric(
+            time_series.encode_annotated_metric(
                 self.default_namespace.as_deref(),
                 &self.buckets,
                 &self.quantiles,
@@ -329,7 +357,7 @@ impl Service<PartitionInnerBuffer<Vec<Metric>, PartitionKey>> for RemoteWriteSer
 pub struct HttpRequestBuilder {
     pub endpoint: Uri,
     pub aws_region: Option<Region>,
-    pub http_auth: Option<Auth>,
+    pub http_auth: Option<HttpAuthenticator>,
     pub credentials_provider: Option<SharedCredentialsProvider>,
 }
 
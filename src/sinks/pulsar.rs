@@ -8,8 +8,9 @@ use crate::{
     codecs::{Encoder, EncodingConfig, Transformer},
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     event::{Event, EventFinalizers, EventStatus, Finalizable},
-    internal_events::PulsarSendingError,
+    internal_events::{PulsarSendingError, TemplateRenderingError},
     sinks::util::metadata::RequestMetadataBuilder,
+    template::Template,
 };
 use bytes::BytesMut;
 use codecs::{encoding::SerializerConfig, TextSerializerConfig};
@@ -38,6 +39,16 @@ use vector_core::config::log_schema;
 enum BuildError {
     #[snafu(display("creating pulsar producer failed: {}", source))]
     CreatePulsarSink { source: PulsarError },
+    #[snafu(display("`partition_key` and `partition_key_field` can't both be set"))]
+    PartitionKeyConflict,
+    #[snafu(display("a `{}` schema requires the `{}` codec", schema_type, schema_type))]
+    SchemaCodecMismatch { schema_type: &'static str },
+    #[snafu(display("`schema.definition` is required for `json` schemas"))]
+    MissingSchemaDefinition,
+    #[snafu(display("`schema.definition` must be the schema of the `avro` codec"))]
+    SchemaDefinitionMismatch,
+    #[snafu(display("invalid schema definition: {}", source))]
+    InvalidSchemaDefinition { source: serde_json::Error },
 }
 
 /// Configuration for the `pulsar` sink.
@@ -70,6 +81,60 @@ pub struct PulsarSinkConfig {
 
     /// Log field to use as Pulsar message key.
     partition_key_field: Option<String>,
+
+    /// The key of messages, which routes them to the partitions of the topic.
+    ///
+    /// Messages with the same key are routed to the same partition. Messages whose key can't be
+    /// rendered are sent without a key.
+    #[configurable(metadata(docs::examples = "{{ host }}"))]
+    #[configurable(metadata(docs::examples = "{{ tenant }}-{{ service }}"))]
+    partition_key: Option<Template>,
+
+    #[configurable(derived)]
+    schema: Option<SchemaConfig>,
+}
+
+/// The type of a schema.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum SchemaType {
+    /// Messages are Avro records, encoded with the `avro` codec.
+    Avro,
+
+    /// Messages are JSON documents, encoded with the `json` codec.
+    Json,
+}
+
+impl SchemaType {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Avro => "avro",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// The schema of messages, declared to the schema registry of the broker.
+///
+/// The broker checks that the schema is compatible with the schema of the topic when the producer
+/// connects, and the sink fails to start otherwise. By default, the schema of the `avro` codec is
+/// declared when encoding with it.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct SchemaConfig {
+    #[configurable(derived)]
+    #[serde(rename = "type")]
+    schema_type: SchemaType,
+
+    /// The definition of the schema, as an Avro schema in JSON, which describes JSON documents too.
+    ///
+    /// Defaults to the schema of the `avro` codec, for `avro` schemas.
+    #[configurable(metadata(
+        docs::examples = r#"{ "type": "record", "name": "log", "fields": [{ "name": "message", "type": "string" }] }"#
+    ))]
+    definition: Option<String>,
 }
 
 /// Authentication configuration.
@@ -130,10 +195,16 @@ enum PulsarSinkState {
     ),
 }
 
+/// Where the key of messages comes from.
+enum PartitionKey {
+    Field(String),
+    Template(Template),
+}
+
 struct PulsarSink {
     transformer: Transformer,
     encoder: Encoder<()>,
-    partition_key_field: Option<String>,
+    partition_key: Option<PartitionKey>,
     state: PulsarSinkState,
     in_flight: FuturesUnordered<
         BoxFuture<
@@ -159,6 +230,8 @@ impl GenerateConfig for PulsarSinkConfig {
             auth: None,
             acknowledgements: Default::default(),
             producer_name: None,
+            partition_key: None,
+            schema: None,
         })
         .unwrap()
     }
@@ -170,8 +243,10 @@ impl SinkConfig for PulsarSinkConfig {
         &self,
         _cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let partition_key = self.partition_key()?;
+        let schema = self.schema()?;
         let producer = self
-            .create_pulsar_producer(false)
+            .create_pulsar_producer(false, schema.clone())
             .await
             .context(CreatePulsarSinkSnafu)?;
 
@@ -179,15 +254,10 @@ impl SinkConfig for PulsarSinkConfig {
         let serializer = self.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
 
-        let sink = PulsarSink::new(
-            producer,
-            transformer,
-            encoder,
-            self.partition_key_field.clone(),
-        )?;
+        let sink = PulsarSink::new(producer, transformer, encoder, partition_key)?;
 
         let producer = self
-            .create_pulsar_producer(true)
+            .create_pulsar_producer(true, schema)
             .await
             .context(CreatePulsarSinkSnafu)?;
         let healthcheck = healthcheck(producer).boxed();
@@ -205,9 +275,71 @@ impl SinkConfig for PulsarSinkConfig {
 }
 
 impl PulsarSinkConfig {
+    fn partition_key(&self) -> Result<Option<PartitionKey>, BuildError> {
+        match (&self.partition_key, &self.partition_key_field) {
+            (Some(_), Some(_)) => Err(BuildError::PartitionKeyConflict),
+            (Some(template), None) => Ok(Some(PartitionKey::Template(template.clone()))),
+            (None, Some(field)) => Ok(Some(PartitionKey::Field(field.clone()))),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Returns the schema declared by the producer, which must describe the messages of the codec.
+    fn schema(&self) -> Result<Option<proto::Schema>, BuildError> {
+        let avro_schema = match self.encoding.config() {
+            SerializerConfig::Avro { avro } => Some(avro.schema.as_str()),
+            _ => None,
+        };
+
+        let (schema_type, definition) = match (&self.schema, avro_schema) {
+            (None, None) => return Ok(None),
+            (None, Some(avro_schema)) => (SchemaType::Avro, avro_schema.to_owned()),
+            (Some(schema), avro_schema) => {
+                let codec_matches = match schema.schema_type {
+                    SchemaType::Avro => avro_schema.is_some(),
+                    SchemaType::Json => matches!(self.encoding.config(), SerializerConfig::Json),
+                };
+                if !codec_matches {
+                    return Err(BuildError::SchemaCodecMismatch {
+                        schema_type: schema.schema_type.as_str(),
+                    });
+                }
+
+                let definition = match (&schema.definition, avro_schema) {
+                    (Some(definition), avro_schema) => {
+                        let parsed: serde_json::Value = serde_json::from_str(definition)
+                            .context(InvalidSchemaDefinitionSnafu)?;
+                        if let Some(avro_schema) = avro_schema {
+                            if serde_json::from_str::<serde_json::Value>(avro_schema).ok()
+                                != Some(parsed)
+                            {
+                                return Err(BuildError::SchemaDefinitionMismatch);
+                            }
+                        }
+                        definition.clone()
+                    }
+                    (None, Some(avro_schema)) => avro_schema.to_owned(),
+                    (None, None) => return Err(BuildError::MissingSchemaDefinition),
+                };
+                (schema.schema_type, definition)
+            }
+        };
+
+        let r#type = match schema_type {
+            SchemaType::Avro => proto::schema::Type::Avro,
+            SchemaType::Json => proto::schema::Type::Json,
+        };
+        Ok(Some(proto::Schema {
+            schema_data: definition.into_bytes(),
+            r#type: r#type as i32,
+            ..Default::default()
+        }))
+    }
+
     async fn create_pulsar_producer(
         &self,
         is_healthcheck: bool,
+        schema: Option<proto::Schema>,
     ) -> Result<PulsarProducer, PulsarError> {
         let mut builder = Pulsar::builder(&self.endpoint, TokioExecutor);
         if let Some(auth) = &self.auth {
@@ -246,13 +378,9 @@ impl PulsarSinkConfig {
             });
         }
 
-        if let SerializerConfig::Avro { avro } = self.encoding.config() {
+        if schema.is_some() {
             pulsar_builder = pulsar_builder.with_options(pulsar::producer::ProducerOptions {
-                schema: Some(proto::Schema {
-                    schema_data: avro.schema.as_bytes().into(),
-                    r#type: proto::schema::Type::Avro as i32,
-                    ..Default::default()
-                }),
+                schema,
                 ..Default::default()
             });
         }
@@ -270,7 +398,7 @@ impl PulsarSink {
         producer: PulsarProducer,
         transformer: Transformer,
         encoder: Encoder<()>,
-        partition_key_field: Option<String>,
+        partition_key: Option<PartitionKey>,
     ) -> crate::Result<Self> {
         Ok(Self {
             transformer,
@@ -279,7 +407,7 @@ impl PulsarSink {
             in_flight: FuturesUnordered::new(),
             bytes_sent: register!(BytesSent::from(Protocol::TCP)),
             events_sent: register!(EventsSent::from(Output(None))),
-            partition_key_field,
+            partition_key,
         })
     }
 
@@ -315,11 +443,23 @@ impl Sink<Event> for PulsarSink {
             "Expected `poll_ready` to be called first."
         );
 
-        let key_value: Option<String> = match (event.maybe_as_log(), &self.partition_key_field) {
-            (Some(log), Some(field)) => log.get(field.as_str()).map(|x| match x {
-                Value::Bytes(x) => String::from_utf8_lossy(x).to_string(),
-                x => x.to_string(),
-            }),
+        let key_value: Option<String> = match (event.maybe_as_log(), &self.partition_key) {
+            (Some(log), Some(PartitionKey::Field(field))) => {
+                log.get(field.as_str()).map(|x| match x {
+                    Value::Bytes(x) => String::from_utf8_lossy(x).to_string(),
+                    x => x.to_string(),
+                })
+            }
+            (_, Some(PartitionKey::Template(template))) => template
+                .render_string(&event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("partition_key"),
+                        drop_event: false,
+                    })
+                })
+                .ok(),
             _ => None,
         };
 
@@ -419,6 +559,81 @@ mod tests {
     fn generate_config() {
         crate::test_util::test_generate_config::<PulsarSinkConfig>();
     }
+
+    fn config_with(extra: &str) -> PulsarSinkConfig {
+        toml::from_str(&format!(
+            r#"endpoint = "pulsar://127.0.0.1:6650"
+            topic = "logs"
+            {}"#,
+            extra
+        ))
+        .unwrap()
+    }
+
+    const AVRO_SCHEMA: &str = r#"{ "type": "record", "name": "log", "fields": [{ "name": "message", "type": "string" }] }"#;
+
+    #[test]
+    fn declares_avro_codec_schema() {
+        let config = config_with(&format!(
+            "encoding.codec = \"avro\"\nencoding.avro.schema = '{}'",
+            AVRO_SCHEMA
+        ));
+
+        let schema = config.schema().unwrap().unwrap();
+        assert_eq!(schema.r#type, proto::schema::Type::Avro as i32);
+        assert_eq!(schema.schema_data, AVRO_SCHEMA.as_bytes());
+    }
+
+    #[test]
+    fn declares_json_schema() {
+        let config = config_with(&format!(
+            "encoding.codec = \"json\"\nschema.type = \"json\"\nschema.definition = '{}'",
+            AVRO_SCHEMA
+        ));
+
+        let schema = config.schema().unwrap().unwrap();
+        assert_eq!(schema.r#type, proto::schema::Type::Json as i32);
+        assert_eq!(schema.schema_data, AVRO_SCHEMA.as_bytes());
+
+        assert!(config_with("encoding.codec = \"text\"")
+            .schema()
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_schemas() {
+        assert!(matches!(
+            config_with("encoding.codec = \"text\"\nschema.type = \"json\"").schema(),
+            Err(BuildError::SchemaCodecMismatch {
+                schema_type: "json"
+            })
+        ));
+        assert!(matches!(
+            config_with("encoding.codec = \"json\"\nschema.type = \"json\"").schema(),
+            Err(BuildError::MissingSchemaDefinition)
+        ));
+        assert!(matches!(
+            config_with(&format!(
+                "encoding.codec = \"avro\"\nencoding.avro.schema = '{}'\nschema.type = \"avro\"\nschema.definition = '{{}}'",
+                AVRO_SCHEMA
+            ))
+            .schema(),
+            Err(BuildError::SchemaDefinitionMismatch)
+        ));
+    }
+
+    #[test]
+    fn rejects_conflicting_partition_keys() {
+        let config = config_with(
+            "encoding.codec = \"text\"\npartition_key = \"{{ host }}\"\npartition_key_field = \"host\"",
+        );
+
+        assert!(matches!(
+            config.partition_key(),
+            Err(BuildError::PartitionKeyConflict)
+        ));
+    }
 }
 
 #[cfg(feature = "pulsar-integration-tests")]
@@ -454,6 +669,8 @@ mod integration_tests {
             auth: None,
             acknowledgements: Default::default(),
             partition_key_field: Some("message".to_string()),
+            partition_key: None,
+            schema: None,
         };
 
         let pulsar = Pulsar::<TokioExecutor>::builder(&cnf.endpoint, TokioExecutor)
@@ -474,14 +691,18 @@ mod integration_tests {
             .await
             .unwrap();
 
-        let producer = cnf.create_pulsar_producer(false).await.unwrap();
+        let producer = cnf
+            .create_pulsar_producer(false, cnf.schema().unwrap())
+            .await
+            .unwrap();
         let transformer = cnf.encoding.transformer();
         let serializer = cnf.encoding.build().unwrap();
         let encoder = Encoder::<()>::new(serializer);
 
         assert_sink_compliance(&SINK_TAGS, async move {
             let sink =
-                PulsarSink::new(producer, transformer, encoder, cnf.partition_key_field).unwrap();
+                PulsarSink::new(producer, transformer, encoder, cnf.partition_key().unwrap())
+                    .unwrap();
             VectorSink::from_event_sink(sink).run(events).await
         })
         .await
@@ -0,0 +1,285 @@
+use crate::{
+    codecs::Transformer,
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+    internal_events::{QuestDbEncodingError, TemplateRenderingError},
+    sinks::util::http::HttpEventEncoder,
+    template::Template,
+};
+use bytes::{BufMut, BytesMut};
+
+/// Encodes events as rows of the InfluxDB Line Protocol dialect understood by QuestDB.
+///
+/// Errors are emitted by the encoder itself, and the events that can't be encoded are dropped.
+#[derive(Clone, Debug)]
+pub(super) struct IlpEncoder {
+    pub(super) table: Template,
+    pub(super) symbols: Vec<String>,
+    pub(super) columns: Option<Vec<String>>,
+    pub(super) timestamp_field: Option<String>,
+    pub(super) transformer: Transformer,
+}
+
+impl IlpEncoder {
+    /// Writes the row of `event` to `buffer`, returning whether the event could be encoded.
+    pub(super) fn encode_row(&self, mut event: Event, buffer: &mut BytesMut) -> bool {
+        let table = match self.table.render_string(&event) {
+            Ok(table) => table,
+            Err(error) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("table"),
+                    drop_event: true,
+                });
+                return false;
+            }
+        };
+
+        self.transformer.transform(&mut event);
+        match self.encode_log(&table, event.into_log(), buffer) {
+            Ok(()) => true,
+            Err(error_message) => {
+                emit!(QuestDbEncodingError {
+                    error_message,
+                    count: 1,
+                });
+                false
+            }
+        }
+    }
+
+    fn encode_log(
+        &self,
+        table: &str,
+        mut log: LogEvent,
+        buffer: &mut BytesMut,
+    ) -> Result<(), &'static str> {
+        let timestamp = match log.remove(
+            self.timestamp_field
+                .as_deref()
+                .unwrap_or_else(|| log_schema().timestamp_key()),
+        ) {
+            None | Some(Value::Null) => None,
+            Some(Value::Timestamp(timestamp)) => Some(timestamp.timestamp_nanos()),
+            Some(_) => return Err("designated timestamp field must be a timestamp"),
+        };
+
+        let mut line = BytesMut::new();
+        encode_name(table, &mut line);
+
+        for field in &self.symbols {
+            if let Some(value) = log.remove(field.as_str()).filter(|value| !value.is_null()) {
+                line.put_u8(b',');
+                encode_name(&column_name(field), &mut line);
+                line.put_u8(b'=');
+                encode_name(&value.to_string_lossy(), &mut line);
+            }
+        }
+
+        let columns: Vec<(String, Value)> = match &self.columns {
+            Some(columns) => columns
+                .iter()
+                .filter_map(|field| {
+                    log.remove(field.as_str())
+                        .map(|value| (column_name(field), value))
+                })
+                .collect(),
+            None => log
+                .as_map()
+                .map(|fields| {
+                    fields
+                        .iter()
+                        .map(|(name, value)| (name.clone(), value.clone()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+
+        let mut separator = b' ';
+        for (name, value) in columns.iter().filter(|(_, value)| !value.is_null()) {
+            line.put_u8(separator);
+            separator = b',';
+            encode_name(name, &mut line);
+            line.put_u8(b'=');
+            encode_value(value, &mut line);
+        }
+        if separator == b' ' {
+            return Err("rows must have at least one column");
+        }
+
+        if let Some(timestamp) = timestamp {
+            line.put_u8(b' ');
+            line.put_slice(timestamp.to_string().as_bytes());
+        }
+        line.put_u8(b'\n');
+
+        buffer.put(line);
+        Ok(())
+    }
+}
+
+impl tokio_util::codec::Encoder<Event> for IlpEncoder {
+    type Error = codecs::encoding::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        if self.encode_row(event, buffer) {
+            Ok(())
+        } else {
+            Err(Self::Error::SerializingError(
+                "Failed to encode event.".into(),
+            ))
+        }
+    }
+}
+
+impl HttpEventEncoder<BytesMut> for IlpEncoder {
+    fn encode_event(&mut self, event: Event) -> Option<BytesMut> {
+        let mut buffer = BytesMut::new();
+        self.encode_row(event, &mut buffer).then_some(buffer)
+    }
+}
+
+/// Column names can't contain dots, so those of nested fields are used with underscores instead.
+fn column_name(field: &str) -> String {
+    field.replace('.', "_")
+}
+
+/// Writes a table name, column name or symbol value, escaping the characters separating the
+/// parts of rows.
+fn encode_name(name: &str, output: &mut BytesMut) {
+    for c in name.chars() {
+        if " ,=\\\n\r".contains(c) {
+            output.put_u8(b'\\');
+        }
+        let mut c_buffer: [u8; 4] = [0; 4];
+        output.put_slice(c.encode_utf8(&mut c_buffer).as_bytes());
+    }
+}
+
+fn encode_value(value: &Value, output: &mut BytesMut) {
+    match value {
+        Value::Integer(integer) => {
+            output.put_slice(integer.to_string().as_bytes());
+            output.put_u8(b'i');
+        }
+        Value::Float(float) => output.put_slice(float.to_string().as_bytes()),
+        Value::Boolean(boolean) => output.put_u8(if *boolean { b't' } else { b'f' }),
+        // Timestamp columns have a microsecond precision.
+        Value::Timestamp(timestamp) => {
+            output.put_slice(timestamp.timestamp_micros().to_string().as_bytes());
+            output.put_u8(b't');
+        }
+        Value::Bytes(bytes) => encode_string(&String::from_utf8_lossy(bytes), output),
+        Value::Object(_) | Value::Array(_) => encode_string(
+            &serde_json::to_string(value).expect("values should be valid json"),
+            output,
+        ),
+        value => encode_string(&value.to_string_lossy(), output),
+    }
+}
+
+fn encode_string(string: &str, output: &mut BytesMut) {
+    output.put_u8(b'"');
+    for c in string.chars() {
+        if "\\\"\n\r".contains(c) {
+            output.put_u8(b'\\');
+        }
+        let mut c_buffer: [u8; 4] = [0; 4];
+        output.put_slice(c.encode_utf8(&mut c_buffer).as_bytes());
+    }
+    output.put_u8(b'"');
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn encoder(columns: Option<Vec<&str>>) -> IlpEncoder {
+        IlpEncoder {
+            table: Template::try_from("{{ application }} logs").unwrap(),
+            symbols: vec!["host".into(), "region".into()],
+            columns: columns.map(|columns| columns.into_iter().map(Into::into).collect()),
+            timestamp_field: None,
+            transformer: Transformer::default(),
+        }
+    }
+
+    fn event() -> Event {
+        let mut log = LogEvent::from("GET /index.html \"200\"");
+        log.insert("application", "web");
+        log.insert("host", "host a");
+        log.insert("status", 200);
+        log.insert("duration", 0.25);
+        log.insert("cached", false);
+        log.insert("request.path", "/index.html");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2023, 1, 10).and_hms_micro(12, 30, 0, 123_456),
+        );
+        log.into()
+    }
+
+    fn encode(encoder: &IlpEncoder, event: Event) -> Option<String> {
+        let mut buffer = BytesMut::new();
+        encoder
+            .encode_row(event, &mut buffer)
+            .then(|| String::from_utf8(buffer.to_vec()).unwrap())
+    }
+
+    #[test]
+    fn encodes_every_field_as_column_by_default() {
+        assert_eq!(
+            encode(&encoder(None), event()).unwrap(),
+            "web\\ logs,host=host\\ a application=\"web\",cached=f,duration=0.25,\
+            message=\"GET /index.html \\\"200\\\"\",request=\"{\\\"path\\\":\\\"/index.html\\\"}\",\
+            status=200i 1673353800123456000\n"
+        );
+    }
+
+    #[test]
+    fn encodes_configured_columns() {
+        let mut event = event();
+        event
+            .as_mut_log()
+            .insert("seen_at", Utc.ymd(2023, 1, 10).and_hms(12, 0, 0));
+
+        assert_eq!(
+            encode(
+                &encoder(Some(vec!["request.path", "seen_at", "missing"])),
+                event
+            )
+            .unwrap(),
+            "web\\ logs,host=host\\ a request_path=\"/index.html\",seen_at=1673352000000000t \
+            1673353800123456000\n"
+        );
+    }
+
+    #[test]
+    fn uses_configured_timestamp_field() {
+        let mut encoder = encoder(Some(vec!["status"]));
+        encoder.timestamp_field = Some("seen_at".into());
+
+        // Rows without a designated timestamp are timestamped by QuestDB.
+        assert_eq!(
+            encode(&encoder, event()).unwrap(),
+            "web\\ logs,host=host\\ a status=200i\n"
+        );
+
+        let mut event = event();
+        event.as_mut_log().insert("seen_at", "yesterday");
+        assert!(encode(&encoder, event).is_none());
+    }
+
+    #[test]
+    fn drops_events_that_cant_be_encoded() {
+        // Rows must have at least one column.
+        assert!(encode(&encoder(Some(vec!["missing"])), event()).is_none());
+
+        // The table must be rendered.
+        let mut event = event();
+        event.as_mut_log().remove("application");
+        assert!(encode(&encoder(None), event).is_none());
+    }
+}
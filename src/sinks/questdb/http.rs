@@ -0,0 +1,192 @@
+use bytes::{Bytes, BytesMut};
+use futures::{FutureExt, SinkExt};
+use http::{Request, Uri};
+use hyper::Body;
+use vector_config::configurable_component;
+
+use super::encoder::IlpEncoder;
+use crate::{
+    config::SinkContext,
+    http::{Auth, HttpClient, MaybeAuth},
+    sinks::{
+        util::{
+            http::{BatchedHttpSink, HttpSink},
+            BatchConfig, Buffer, Compression, RealtimeSizeBasedDefaultBatchSettings,
+            TowerRequestConfig, UriSerde,
+        },
+        Healthcheck, HealthcheckError, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Configuration for the `http` mode of the `questdb` sink.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HttpConfig {
+    /// The endpoint of the HTTP server of QuestDB.
+    ///
+    /// Rows are sent to the `/write` path of this endpoint.
+    #[configurable(metadata(docs::examples = "http://localhost:9000"))]
+    endpoint: UriSerde,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+}
+
+impl HttpConfig {
+    pub(super) fn build(
+        &self,
+        cx: SinkContext,
+        encoder: IlpEncoder,
+    ) -> crate::Result<(VectorSink, Healthcheck)> {
+        let batch = self.batch.into_batch_settings()?;
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+
+        let auth = self.auth.choose_one(&self.endpoint.auth)?;
+        auth.ensure_static()?;
+        let endpoint = self.endpoint.with_default_parts();
+        let sink = IlpHttpSink {
+            uri: endpoint.append_path("write")?.uri,
+            auth: auth.clone(),
+            encoder,
+        };
+
+        let healthcheck = healthcheck(client.clone(), endpoint.append_path("ping")?.uri, auth);
+
+        let sink = BatchedHttpSink::new(
+            sink,
+            Buffer::new(batch.size, Compression::None),
+            request,
+            batch.timeout,
+            client,
+        )
+        .sink_map_err(|error| error!(message = "Fatal questdb sink error.", %error));
+
+        Ok((VectorSink::from_event_sink(sink), healthcheck.boxed()))
+    }
+}
+
+struct IlpHttpSink {
+    uri: Uri,
+    auth: Option<Auth>,
+    encoder: IlpEncoder,
+}
+
+#[async_trait::async_trait]
+impl HttpSink for IlpHttpSink {
+    type Input = BytesMut;
+    type Output = BytesMut;
+    type Encoder = IlpEncoder;
+
+    fn build_encoder(&self) -> Self::Encoder {
+        self.encoder.clone()
+    }
+
+    async fn build_request(&self, rows: Self::Output) -> crate::Result<Request<Bytes>> {
+        let mut request = Request::post(&self.uri)
+            .header("Content-Type", "text/plain; charset=utf-8")
+            .body(rows.freeze())?;
+
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        Ok(request)
+    }
+}
+
+async fn healthcheck(client: HttpClient, uri: Uri, auth: Option<Auth>) -> crate::Result<()> {
+    let mut request = Request::get(uri).body(Body::empty())?;
+    if let Some(auth) = &auth {
+        auth.apply(&mut request);
+    }
+
+    let response = client.send(request).await?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(HealthcheckError::UnexpectedStatus { status }.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use futures::stream;
+    use vector_core::event::{BatchNotifier, BatchStatus, Event, LogEvent};
+
+    use super::super::QuestDbConfig;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        test_util::{
+            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+            http::spawn_blackhole_http_server,
+        },
+    };
+
+    #[tokio::test]
+    async fn sends_batches_to_write_endpoint() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let endpoint = spawn_blackhole_http_server(move |request| {
+            let tx = tx.clone();
+            async move {
+                let path = request.uri().path().to_string();
+                let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                tx.send((path, String::from_utf8(body.to_vec()).unwrap()))
+                    .unwrap();
+                Ok::<_, Infallible>(
+                    hyper::Response::builder()
+                        .status(204)
+                        .body(hyper::Body::empty())
+                        .unwrap(),
+                )
+            }
+        })
+        .await;
+
+        let config: QuestDbConfig = toml::from_str(&format!(
+            r#"mode = "http"
+            endpoint = "{}"
+            table = "{{{{ table }}}}"
+            columns = ["message"]"#,
+            endpoint
+        ))
+        .unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let events: Vec<Event> = ["trades", "quotes"]
+            .into_iter()
+            .map(|table| {
+                let mut log = LogEvent::from("hello").with_batch_notifier(&batch);
+                log.insert("table", table);
+                log.remove(crate::config::log_schema().timestamp_key());
+                log.into()
+            })
+            .collect();
+        drop(batch);
+
+        run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS).await;
+
+        let (path, body) = rx.recv().await.unwrap();
+        assert_eq!(path, "/write");
+        assert_eq!(body, "trades message=\"hello\"\nquotes message=\"hello\"\n");
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+    }
+}
@@ -0,0 +1,179 @@
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{util::tcp::TcpSinkConfig, Healthcheck, VectorSink},
+    template::Template,
+};
+
+mod encoder;
+mod http;
+
+use self::encoder::IlpEncoder;
+
+/// Configuration for the `questdb` sink.
+#[configurable_component(sink("questdb"))]
+#[derive(Clone, Debug)]
+pub struct QuestDbConfig {
+    #[serde(flatten)]
+    mode: Mode,
+
+    /// The table rows are written into.
+    ///
+    /// QuestDB creates tables, and adds their missing columns, when rows are written into them.
+    #[configurable(metadata(docs::examples = "logs"))]
+    #[configurable(metadata(docs::examples = "{{ application }}_logs"))]
+    table: Template,
+
+    /// The fields written as symbol columns.
+    ///
+    /// Symbols are stored once and referenced by the rows holding them, which suits values repeated
+    /// across many rows, such as host or service names.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "host"))]
+    symbols: Vec<String>,
+
+    /// The fields written as columns.
+    ///
+    /// By default, every top-level field that isn't a symbol or the designated timestamp is written
+    /// as a column. Objects and arrays are written as JSON strings, and the dots in the paths of
+    /// nested fields are replaced with underscores in the names of their columns.
+    #[configurable(metadata(docs::examples = "message"))]
+    #[configurable(metadata(docs::examples = "request.status"))]
+    columns: Option<Vec<String>>,
+
+    /// The field holding the designated timestamp of rows.
+    ///
+    /// By default, the timestamp field of the [global log schema][global_log_schema] is used. Rows
+    /// of events without this field are timestamped by QuestDB when they are written.
+    ///
+    /// [global_log_schema]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.timestamp_key
+    #[configurable(metadata(docs::examples = "received_at"))]
+    timestamp_field: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// How rows are sent to QuestDB.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)] // just used for configuration
+pub enum Mode {
+    /// Stream rows to the InfluxDB Line Protocol TCP port of QuestDB.
+    ///
+    /// Rows QuestDB can't parse are dropped by it, without telling Vector about it.
+    Tcp(#[configurable(derived)] TcpSinkConfig),
+
+    /// Send batches of rows to the InfluxDB Line Protocol HTTP endpoint of QuestDB.
+    ///
+    /// Each batch is acknowledged once QuestDB has committed it.
+    Http(#[configurable(derived)] http::HttpConfig),
+}
+
+impl GenerateConfig for QuestDbConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"mode = "http"
+            endpoint = "http://localhost:9000"
+            table = "logs"
+            symbols = ["host"]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for QuestDbConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let encoder = IlpEncoder {
+            table: self.table.clone(),
+            symbols: self.symbols.clone(),
+            columns: self.columns.clone(),
+            timestamp_field: self.timestamp_field.clone(),
+            transformer: self.encoding.clone(),
+        };
+
+        match &self.mode {
+            Mode::Tcp(config) => config.build(Transformer::default(), encoder),
+            Mode::Http(config) => config.build(cx, encoder),
+        }
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{future::ready, stream};
+    use tokio::{io::AsyncReadExt, net::TcpListener};
+    use vector_core::event::{Event, LogEvent};
+
+    use super::*;
+    use crate::test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        next_addr,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<QuestDbConfig>();
+    }
+
+    fn event() -> Event {
+        let mut log = LogEvent::from("hello");
+        log.insert("host", "example.com");
+        log.remove(crate::config::log_schema().timestamp_key());
+        log.into()
+    }
+
+    #[tokio::test]
+    async fn tcp_writes_rows() {
+        let address = next_addr();
+        let listener = TcpListener::bind(address).await.unwrap();
+
+        let config: QuestDbConfig = toml::from_str(&format!(
+            r#"mode = "tcp"
+            address = "{}"
+            table = "logs"
+            symbols = ["host"]"#,
+            address
+        ))
+        .unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let receive = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut rows = String::new();
+            socket.read_to_string(&mut rows).await.unwrap();
+            rows
+        });
+
+        run_and_assert_sink_compliance(sink, stream::once(ready(event())), &SINK_TAGS).await;
+
+        assert_eq!(
+            receive.await.unwrap(),
+            "logs,host=example.com message=\"hello\"\n"
+        );
+    }
+}
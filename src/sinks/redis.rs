@@ -1,8 +1,17 @@
-use std::task::{Context, Poll};
+use std::{
+    io,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
 
 use bytes::{Bytes, BytesMut};
 use futures::{future::BoxFuture, stream, FutureExt, SinkExt, StreamExt};
-use redis::{aio::ConnectionManager, RedisError, RedisResult};
+use redis::{
+    aio::ConnectionManager,
+    cluster::{cluster_pipe, ClusterClient, ClusterConnection},
+    streams::StreamMaxlen,
+    Cmd, FromRedisValue, RedisError, RedisResult, Value,
+};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::Encoder as _;
 use tower::{Service, ServiceBuilder};
@@ -53,6 +62,12 @@ pub enum DataTypeConfig {
     ///
     /// Redis channels function in a pub/sub fashion, allowing many-to-many broadcasting and receiving.
     Channel,
+
+    /// The Redis `stream` type.
+    ///
+    /// Messages are appended to the stream with `XADD`, which can trim it to a maximum length as
+    /// they are added.
+    Stream,
 }
 
 /// List-specific options.
@@ -64,7 +79,37 @@ pub struct ListOption {
     method: Method,
 }
 
-#[derive(Clone, Copy, Debug, Derivative)]
+/// Stream-specific options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub struct StreamOption {
+    /// The field of the stream entries to store messages in.
+    #[serde(default = "default_stream_field")]
+    #[derivative(Default(value = "default_stream_field()"))]
+    field: String,
+
+    /// The maximum length to trim the stream to as messages are added.
+    ///
+    /// If not set, the stream is not trimmed.
+    #[configurable(metadata(docs::examples = 10000))]
+    maxlen: Option<usize>,
+
+    /// Whether to trim the stream approximately.
+    ///
+    /// Approximate trimming (`MAXLEN ~`) only removes whole nodes of the stream, which is much more
+    /// efficient, but can leave the stream with slightly more entries than `maxlen`.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    approximate: bool,
+}
+
+fn default_stream_field() -> String {
+    "message".to_owned()
+}
+
+#[derive(Clone, Debug, Derivative)]
 #[derivative(Default)]
 pub enum DataType {
     /// The Redis `list` type.
@@ -77,6 +122,9 @@ pub enum DataType {
     ///
     /// Redis channels function in a pub/sub fashion, allowing many-to-many broadcasting and receiving.
     Channel,
+
+    /// The Redis `stream` type.
+    Stream(StreamOption),
 }
 
 /// Method for pushing messages into a `list`.
@@ -124,12 +172,23 @@ pub struct RedisSinkConfig {
     #[serde(alias = "list")]
     list_option: Option<ListOption>,
 
+    #[configurable(derived)]
+    #[serde(alias = "stream")]
+    stream_option: Option<StreamOption>,
+
     /// The Redis URL to connect to.
     ///
     /// The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
     /// `redis` or `rediss` for connections secured via TLS.
     url: String,
 
+    /// Whether `url` is the address of a node of a Redis Cluster.
+    ///
+    /// The topology of the cluster is then discovered from that node, and each message is sent to
+    /// the node serving the slot of its key.
+    #[serde(default)]
+    cluster: bool,
+
     /// The Redis key to publish messages to.
     #[configurable(validation(length(min = 1)))]
     key: Template,
@@ -176,7 +235,10 @@ impl SinkConfig for RedisSinkConfig {
         if self.key.is_empty() {
             return Err("`key` cannot be empty.".into());
         }
-        let conn = self.build_client().await.context(RedisCreateFailedSnafu)?;
+        let conn = self
+            .build_connection()
+            .await
+            .context(RedisCreateFailedSnafu)?;
         let healthcheck = RedisSinkConfig::healthcheck(conn.clone()).boxed();
         let sink = self.new(conn)?;
         Ok((sink, healthcheck))
@@ -192,7 +254,7 @@ impl SinkConfig for RedisSinkConfig {
 }
 
 impl RedisSinkConfig {
-    pub fn new(&self, conn: ConnectionManager) -> crate::Result<super::VectorSink> {
+    pub fn new(&self, conn: RedisConnection) -> crate::Result<super::VectorSink> {
         let request = self.request.unwrap_with(&TowerRequestConfig {
             concurrency: Concurrency::Fixed(1),
             ..Default::default()
@@ -209,6 +271,9 @@ impl RedisSinkConfig {
         let data_type = match self.data_type {
             DataTypeConfig::Channel => DataType::Channel,
             DataTypeConfig::List => DataType::List(method.unwrap_or_default()),
+            DataTypeConfig::Stream => {
+                DataType::Stream(self.stream_option.clone().unwrap_or_default())
+            }
         };
 
         let batch = self.batch.into_batch_settings()?;
@@ -245,11 +310,105 @@ impl RedisSinkConfig {
         conn
     }
 
-    async fn healthcheck(mut conn: ConnectionManager) -> crate::Result<()> {
-        redis::cmd("PING")
-            .query_async(&mut conn)
-            .await
-            .map_err(Into::into)
+    async fn build_connection(&self) -> RedisResult<RedisConnection> {
+        if !self.cluster {
+            return self.build_client().await.map(RedisConnection::Single);
+        }
+
+        trace!("Open Redis cluster client.");
+        let client = ClusterClient::new(vec![self.url.as_str()])?;
+        trace!("Get Redis cluster connection.");
+        let conn = blocking(move || client.get_connection()).await?;
+        trace!("Get Redis cluster connection success.");
+        Ok(RedisConnection::Cluster(Arc::new(Mutex::new(conn))))
+    }
+
+    async fn healthcheck(conn: RedisConnection) -> crate::Result<()> {
+        let result = match conn {
+            RedisConnection::Single(mut conn) => redis::cmd("PING").query_async(&mut conn).await,
+            RedisConnection::Cluster(conn) => {
+                blocking(move || redis::cmd("PING").query(&mut *lock(&conn))).await
+            }
+        };
+        result.map_err(Into::into)
+    }
+}
+
+/// Connection to either a single Redis node or a Redis Cluster.
+#[derive(Clone)]
+pub enum RedisConnection {
+    Single(ConnectionManager),
+
+    /// The cluster connection of `redis-rs` is blocking, so it's only used from blocking tasks.
+    Cluster(Arc<Mutex<ClusterConnection>>),
+}
+
+impl RedisConnection {
+    /// Sends `cmds` in a single pipeline, which is atomic if it's sent to a single node.
+    ///
+    /// Pipelines sent to a cluster can't be atomic, as their keys may be served by different nodes.
+    async fn query(self, cmds: Vec<Cmd>) -> RedisResult<Vec<Value>> {
+        match self {
+            Self::Single(mut conn) => {
+                let mut pipe = redis::pipe();
+                if cmds.len() > 1 {
+                    pipe.atomic();
+                }
+                for cmd in cmds {
+                    pipe.add_command(cmd);
+                }
+                pipe.query_async(&mut conn).await
+            }
+            Self::Cluster(conn) => {
+                let mut pipe = cluster_pipe();
+                for cmd in cmds {
+                    pipe.add_command(cmd);
+                }
+                blocking(move || pipe.query(&mut *lock(&conn))).await
+            }
+        }
+    }
+}
+
+fn lock(conn: &Mutex<ClusterConnection>) -> std::sync::MutexGuard<'_, ClusterConnection> {
+    conn.lock().expect("Redis cluster connection lock poisoned")
+}
+
+async fn blocking<T, F>(f: F) -> RedisResult<T>
+where
+    F: FnOnce() -> RedisResult<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .unwrap_or_else(|error| Err(io::Error::new(io::ErrorKind::Other, error).into()))
+}
+
+/// Builds the command sending `value` to `key` for the given data type.
+fn command(data_type: &DataType, key: String, value: &[u8]) -> Cmd {
+    match data_type {
+        DataType::List(Method::LPush) => Cmd::lpush(key, value),
+        DataType::List(Method::RPush) => Cmd::rpush(key, value),
+        DataType::Channel => Cmd::publish(key, value),
+        DataType::Stream(option) => {
+            let items = [(option.field.as_str(), value)];
+            match option.maxlen {
+                Some(maxlen) if option.approximate => {
+                    Cmd::xadd_maxlen(key, StreamMaxlen::Approx(maxlen), "*", &items)
+                }
+                Some(maxlen) => Cmd::xadd_maxlen(key, StreamMaxlen::Equals(maxlen), "*", &items),
+                None => Cmd::xadd(key, "*", &items),
+            }
+        }
+    }
+}
+
+/// Whether `value` is the response of a successful command for the given data type.
+fn is_successful(data_type: &DataType, value: &Value) -> RedisResult<bool> {
+    match data_type {
+        // `XADD` responds with the ID of the added entry.
+        DataType::Stream(_) => Ok(*value != Value::Nil),
+        _ => bool::from_redis_value(value),
     }
 }
 
@@ -325,7 +484,7 @@ impl RetryLogic for RedisRetryLogic {
 
 #[derive(Clone)]
 pub struct RedisSink {
-    conn: ConnectionManager,
+    conn: RedisConnection,
     data_type: DataType,
     bytes_sent: Registered<BytesSent>,
 }
@@ -342,44 +501,26 @@ impl Service<Vec<RedisKvEntry>> for RedisSink {
 
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, kvs: Vec<RedisKvEntry>) -> Self::Future {
-        let count = kvs.len();
         let mut byte_size = 0;
 
-        let mut conn = self.conn.clone();
-        let mut pipe = redis::pipe();
-
-        for kv in kvs {
-            byte_size += kv.encoded_length();
-            match self.data_type {
-                DataType::List(method) => match method {
-                    Method::LPush => {
-                        if count > 1 {
-                            pipe.atomic().lpush(kv.key, kv.value.as_ref());
-                        } else {
-                            pipe.lpush(kv.key, kv.value.as_ref());
-                        }
-                    }
-                    Method::RPush => {
-                        if count > 1 {
-                            pipe.atomic().rpush(kv.key, kv.value.as_ref());
-                        } else {
-                            pipe.rpush(kv.key, kv.value.as_ref());
-                        }
-                    }
-                },
-                DataType::Channel => {
-                    if count > 1 {
-                        pipe.atomic().publish(kv.key, kv.value.as_ref());
-                    } else {
-                        pipe.publish(kv.key, kv.value.as_ref());
-                    }
-                }
-            }
-        }
+        let conn = self.conn.clone();
+        let data_type = self.data_type.clone();
+        let cmds = kvs
+            .into_iter()
+            .map(|kv| {
+                byte_size += kv.encoded_length();
+                command(&data_type, kv.key, kv.value.as_ref())
+            })
+            .collect();
 
         let bytes_sent = self.bytes_sent.clone();
         Box::pin(async move {
-            let result: RedisPipeResult = pipe.query_async(&mut conn).await;
+            let result: RedisPipeResult = conn.query(cmds).await.and_then(|values| {
+                values
+                    .iter()
+                    .map(|value| is_successful(&data_type, value))
+                    .collect()
+            });
             if let Ok(res) = &result {
                 if res.is_successful() {
                     bytes_sent.emit(ByteSize(byte_size));
@@ -460,6 +601,84 @@ mod tests {
         let map: HashMap<String, String> = serde_json::from_slice(&result[..]).unwrap();
         assert!(!map.contains_key("key"));
     }
+
+    #[test]
+    fn parses_stream_option() {
+        let config: RedisSinkConfig = toml::from_str(
+            r#"
+            url = "redis://127.0.0.1:6379/0"
+            key = "vector"
+            data_type = "stream"
+            stream.maxlen = 1000
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.stream_option,
+            Some(StreamOption {
+                field: "message".to_owned(),
+                maxlen: Some(1000),
+                approximate: true,
+            })
+        );
+        assert!(!config.cluster);
+    }
+
+    #[test]
+    fn stream_command_trims_stream() {
+        let mut option = StreamOption {
+            maxlen: Some(1000),
+            ..Default::default()
+        };
+        let xadd = |option: &StreamOption| {
+            command(
+                &DataType::Stream(option.clone()),
+                "key".to_owned(),
+                b"value",
+            )
+            .get_packed_command()
+        };
+
+        assert_eq!(
+            xadd(&option),
+            redis::cmd("XADD")
+                .arg("key")
+                .arg("MAXLEN")
+                .arg("~")
+                .arg(1000)
+                .arg("*")
+                .arg("message")
+                .arg("value")
+                .get_packed_command()
+        );
+
+        option.approximate = false;
+        assert_eq!(
+            xadd(&option),
+            redis::cmd("XADD")
+                .arg("key")
+                .arg("MAXLEN")
+                .arg("=")
+                .arg(1000)
+                .arg("*")
+                .arg("message")
+                .arg("value")
+                .get_packed_command()
+        );
+
+        option.maxlen = None;
+        assert_eq!(
+            xadd(&option),
+            redis::cmd("XADD")
+                .arg("key")
+                .arg("*")
+                .arg("message")
+                .arg("value")
+                .get_packed_command()
+        );
+    }
 }
 
 #[cfg(feature = "redis-integration-tests")]
@@ -500,6 +719,8 @@ mod integration_tests {
             list_option: Some(ListOption {
                 method: Method::LPush,
             }),
+            stream_option: None,
+            cluster: false,
             batch: BatchConfig::default(),
             request: TowerRequestConfig {
                 rate_limit_num: Option::from(u64::MAX),
@@ -519,7 +740,7 @@ mod integration_tests {
         // Publish events.
         let cnf2 = cnf.clone();
         assert_sink_compliance(&SINK_TAGS, async move {
-            let conn = cnf2.build_client().await.unwrap();
+            let conn = cnf2.build_connection().await.unwrap();
             cnf2.new(conn).unwrap().run(input).await
         })
         .await
@@ -563,6 +784,8 @@ mod integration_tests {
             list_option: Some(ListOption {
                 method: Method::RPush,
             }),
+            stream_option: None,
+            cluster: false,
             batch: BatchConfig::default(),
             request: TowerRequestConfig {
                 rate_limit_num: Option::from(u64::MAX),
@@ -582,7 +805,7 @@ mod integration_tests {
         // Publish events.
         let cnf2 = cnf.clone();
         assert_sink_compliance(&SINK_TAGS, async move {
-            let conn = cnf2.build_client().await.unwrap();
+            let conn = cnf2.build_connection().await.unwrap();
             cnf2.new(conn).unwrap().run(input).await
         })
         .await
@@ -607,6 +830,51 @@ mod integration_tests {
         }
     }
 
+    #[tokio::test]
+    async fn redis_sink_stream() {
+        trace_init();
+
+        let key = Template::try_from(format!("test-{}", random_string(10)))
+            .expect("should not fail to create key template");
+        debug!("Test key name: {}.", key);
+        let num_events = 1000;
+
+        let cnf = RedisSinkConfig {
+            url: redis_server(),
+            key: key.clone(),
+            encoding: JsonSerializerConfig::new().into(),
+            data_type: DataTypeConfig::Stream,
+            list_option: None,
+            stream_option: Some(StreamOption {
+                maxlen: Some(100),
+                approximate: false,
+                ..Default::default()
+            }),
+            cluster: false,
+            batch: BatchConfig::default(),
+            request: TowerRequestConfig {
+                rate_limit_num: Option::from(u64::MAX),
+                ..Default::default()
+            },
+            acknowledgements: Default::default(),
+        };
+
+        let cnf2 = cnf.clone();
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let conn = cnf2.build_connection().await.unwrap();
+            let sink = cnf2.new(conn).unwrap();
+            let (_input, events) = random_lines_with_stream(100, num_events, None);
+            sink.run(events).await
+        })
+        .await
+        .expect("Running sink failed");
+
+        let mut conn = cnf.build_client().await.unwrap();
+        let xlen: usize = conn.xlen(key.to_string()).await.unwrap();
+        debug!("Test key: {} len: {}.", key, xlen);
+        assert_eq!(xlen, 100);
+    }
+
     #[tokio::test]
     async fn redis_sink_channel() {
         trace_init();
@@ -640,6 +908,8 @@ mod integration_tests {
             encoding: JsonSerializerConfig::new().into(),
             data_type: DataTypeConfig::Channel,
             list_option: None,
+            stream_option: None,
+            cluster: false,
             batch: BatchConfig::default(),
             request: TowerRequestConfig {
                 rate_limit_num: Option::from(u64::MAX),
@@ -650,7 +920,7 @@ mod integration_tests {
 
         // Publish events.
         assert_sink_compliance(&SINK_TAGS, async move {
-            let conn = cnf.build_client().await.unwrap();
+            let conn = cnf.build_connection().await.unwrap();
             let sink = cnf.new(conn).unwrap();
             let (_input, events) = random_lines_with_stream(100, num_events, None);
             sink.run(events).await
@@ -1,17 +1,20 @@
-use std::collections::BTreeMap;
+use std::{
+    collections::BTreeMap,
+    num::{NonZeroU32, NonZeroUsize},
+};
 
 use aws_sdk_s3::{
-    error::PutObjectError,
-    model::{ObjectCannedAcl, ServerSideEncryption, StorageClass},
+    model::{ObjectCannedAcl, ObjectLockMode, ServerSideEncryption, StorageClass},
     Client as S3Client,
 };
 use aws_smithy_client::SdkError;
+use chrono::{DateTime, Duration, Utc};
 use futures::FutureExt;
 use http::StatusCode;
 use snafu::Snafu;
 use vector_config::configurable_component;
 
-use super::service::{S3Response, S3Service};
+use super::service::{S3Response, S3Service, S3UploadError};
 use crate::{
     aws::{create_client, is_retriable_error, AwsAuthentication, RegionOrEndpoint},
     common::s3::S3ClientBuilder,
@@ -80,8 +83,20 @@ pub struct S3Options {
     pub storage_class: Option<S3StorageClass>,
 
     /// The tag-set for the object.
+    ///
+    /// The values of tags are templates, and events are batched by the values they render.
+    #[configurable(metadata(
+        docs::additional_props_description = "The template of the value of the tag."
+    ))]
     pub tags: Option<BTreeMap<String, String>>,
 
+    /// The algorithm of the checksum of each object, calculated by Vector and verified by S3 on
+    /// upload.
+    pub checksum_algorithm: Option<S3ChecksumAlgorithm>,
+
+    #[configurable(derived)]
+    pub object_lock: Option<S3ObjectLock>,
+
     /// Specifies what content encoding has been applied to the object.
     ///
     /// Directly comparable to the `Content-Encoding` HTTP header.
@@ -97,6 +112,102 @@ pub struct S3Options {
     pub content_type: Option<String>,
 }
 
+/// The minimum size of the parts of multipart uploads, except for their last one.
+pub const MIN_PART_SIZE_BYTES: usize = 5 * 1024 * 1024;
+
+/// Multipart upload of large objects.
+///
+/// Objects larger than the part size are uploaded in parts with the [multipart upload
+/// API][multipart_upload], rather than in a single request, which can't upload objects larger than
+/// 5 GB. If any part fails to upload, the upload is aborted and retried as a whole.
+///
+/// [multipart_upload]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/mpuoverview.html
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct S3MultipartUploadConfig {
+    /// The size of the parts objects are uploaded in, in bytes.
+    ///
+    /// Parts must be at least 5 MiB large. Parts are made larger if objects would otherwise be
+    /// uploaded in more than 10,000 parts, the most S3 allows.
+    #[serde(default = "default_part_size_bytes")]
+    #[configurable(metadata(docs::examples = 104857600))]
+    pub part_size_bytes: usize,
+
+    /// The number of parts of each object uploaded concurrently.
+    #[serde(default = "default_part_concurrency")]
+    pub concurrency: NonZeroUsize,
+}
+
+const fn default_part_size_bytes() -> usize {
+    16 * 1024 * 1024
+}
+
+fn default_part_concurrency() -> NonZeroUsize {
+    NonZeroUsize::new(4).expect("concurrency should be non-zero")
+}
+
+/// Checksum algorithms of objects.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum S3ChecksumAlgorithm {
+    /// SHA-256.
+    Sha256,
+}
+
+/// [Object Lock][object_lock] retention of the created objects, which can't be overwritten or
+/// deleted until their retention period expires.
+///
+/// The bucket must have Object Lock enabled.
+///
+/// [object_lock]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/object-lock.html
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct S3ObjectLock {
+    #[configurable(derived)]
+    pub mode: S3ObjectLockMode,
+
+    /// The number of days objects are retained for, from their creation.
+    #[configurable(metadata(docs::examples = 365))]
+    pub retention_days: NonZeroU32,
+}
+
+impl S3ObjectLock {
+    /// The date until which objects created at `now` are retained.
+    pub fn retain_until(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now + Duration::days(i64::from(self.retention_days.get()))
+    }
+}
+
+/// Object Lock retention modes.
+///
+/// More information on each mode can be found in the [AWS documentation][aws_docs].
+///
+/// [aws_docs]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/object-lock-overview.html#object-lock-retention-modes
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum S3ObjectLockMode {
+    /// Objects can't be overwritten or deleted by any user until their retention period expires,
+    /// nor can their retention mode be changed or their retention period be shortened.
+    Compliance,
+
+    /// Objects can't be overwritten or deleted, nor can their retention settings be changed,
+    /// except by users granted the `s3:BypassGovernanceRetention` permission.
+    Governance,
+}
+
+impl From<S3ObjectLockMode> for ObjectLockMode {
+    fn from(mode: S3ObjectLockMode) -> Self {
+        match mode {
+            S3ObjectLockMode::Compliance => Self::Compliance,
+            S3ObjectLockMode::Governance => Self::Governance,
+        }
+    }
+}
+
 /// S3 storage classes.
 ///
 /// More information on each storage class can be found in the [AWS documentation][aws_docs].
@@ -270,11 +381,17 @@ impl From<S3CannedAcl> for ObjectCannedAcl {
 pub struct S3RetryLogic;
 
 impl RetryLogic for S3RetryLogic {
-    type Error = SdkError<PutObjectError>;
+    type Error = S3UploadError;
     type Response = S3Response;
 
     fn is_retriable_error(&self, error: &Self::Error) -> bool {
-        is_retriable_error(error)
+        match error {
+            S3UploadError::PutObject { source } => is_retriable_error(source),
+            S3UploadError::CreateMultipartUpload { source } => is_retriable_error(source),
+            S3UploadError::MissingUploadId => false,
+            S3UploadError::UploadPart { source, .. } => is_retriable_error(source),
+            S3UploadError::CompleteMultipartUpload { source } => is_retriable_error(source),
+        }
     }
 }
 
@@ -329,7 +446,10 @@ pub async fn create_service(
 
 #[cfg(test)]
 mod tests {
-    use super::S3StorageClass;
+    use chrono::{TimeZone, Utc};
+    use indoc::indoc;
+
+    use super::{S3ObjectLock, S3ObjectLockMode, S3StorageClass};
     use crate::serde::json::to_string;
 
     #[test]
@@ -351,4 +471,18 @@ mod tests {
             assert_eq!(result, storage_class);
         }
     }
+
+    #[test]
+    fn object_lock_retention() {
+        let object_lock: S3ObjectLock = toml::from_str(indoc! {r#"
+            mode = "COMPLIANCE"
+            retention_days = 30
+        "#})
+        .unwrap();
+        assert_eq!(object_lock.mode, S3ObjectLockMode::Compliance);
+        assert_eq!(
+            object_lock.retain_until(Utc.ymd(2023, 1, 15).and_hms(12, 0, 0)),
+            Utc.ymd(2023, 2, 14).and_hms(12, 0, 0)
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use vector_core::{event::Event, partition::Partitioner};
 
 use crate::{internal_events::TemplateRenderingError, template::Template};
@@ -6,26 +8,80 @@ use crate::{internal_events::TemplateRenderingError, template::Template};
 pub struct S3PartitionKey {
     pub key_prefix: String,
     pub ssekms_key_id: Option<String>,
+    pub tags: Option<BTreeMap<String, String>>,
 }
 
+/// The partition Hive writes rows to when their partition values are null or empty.
+const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
 /// Partitions items based on the generated key for the given event.
-pub struct S3KeyPartitioner(Template, Option<Template>);
+pub struct S3KeyPartitioner(
+    Template,
+    Option<Template>,
+    Vec<(String, Template)>,
+    Vec<(String, Template)>,
+);
 
 impl S3KeyPartitioner {
     pub const fn new(
         key_prefix_template: Template,
         ssekms_key_id_template: Option<Template>,
     ) -> Self {
-        Self(key_prefix_template, ssekms_key_id_template)
+        Self(
+            key_prefix_template,
+            ssekms_key_id_template,
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    /// Appends Hive-style `name=value/` directories to the key prefix, with the values rendered
+    /// from the templates of `partitions`.
+    pub fn with_hive_partitions(mut self, partitions: Vec<(String, Template)>) -> Self {
+        self.2 = partitions;
+        self
+    }
+
+    /// Sets the tags of objects to the values rendered from the templates of `tags`.
+    pub fn with_tags(mut self, tags: Vec<(String, Template)>) -> Self {
+        self.3 = tags;
+        self
     }
 }
 
+/// Escapes `value` the way Hive escapes the values of partitions in their paths.
+fn escape_partition_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '\u{01}'..='\u{1F}'
+            | '"'
+            | '#'
+            | '%'
+            | '\''
+            | '*'
+            | '/'
+            | ':'
+            | '='
+            | '?'
+            | '\\'
+            | '\u{7F}'
+            | '{'
+            | '['
+            | ']'
+            | '^' => escaped.push_str(&format!("%{:02X}", character as u32)),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
 impl Partitioner for S3KeyPartitioner {
     type Item = Event;
     type Key = Option<S3PartitionKey>;
 
     fn partition(&self, item: &Self::Item) -> Self::Key {
-        let key_prefix = self
+        let mut key_prefix = self
             .0
             .render_string(item)
             .map_err(|error| {
@@ -36,6 +92,20 @@ impl Partitioner for S3KeyPartitioner {
                 });
             })
             .ok()?;
+        for (name, template) in &self.2 {
+            // Missing fields don't drop events here, as Hive has a partition for missing values.
+            let value = template
+                .render_string(item)
+                .ok()
+                .filter(|value| !value.is_empty());
+            key_prefix.push_str(name);
+            key_prefix.push('=');
+            match value {
+                Some(value) => key_prefix.push_str(&escape_partition_value(&value)),
+                None => key_prefix.push_str(HIVE_DEFAULT_PARTITION),
+            }
+            key_prefix.push('/');
+        }
         let ssekms_key_id = self
             .1
             .as_ref()
@@ -50,9 +120,95 @@ impl Partitioner for S3KeyPartitioner {
             })
             .transpose()
             .ok()?;
+        let tags = if self.3.is_empty() {
+            None
+        } else {
+            let tags = self
+                .3
+                .iter()
+                .map(|(name, template)| {
+                    template
+                        .render_string(item)
+                        .map(|value| (name.clone(), value))
+                })
+                .collect::<Result<BTreeMap<_, _>, _>>()
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("tags"),
+                        drop_event: true,
+                    });
+                })
+                .ok()?;
+            Some(tags)
+        };
         Some(S3PartitionKey {
             key_prefix,
             ssekms_key_id,
+            tags,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn partitioner(key_prefix: &str) -> S3KeyPartitioner {
+        S3KeyPartitioner::new(Template::try_from(key_prefix).unwrap(), None).with_hive_partitions(
+            vec![
+                ("region".into(), Template::try_from("{{ region }}").unwrap()),
+                ("path".into(), Template::try_from("{{ path }}").unwrap()),
+            ],
+        )
+    }
+
+    #[test]
+    fn renders_hive_partitions() {
+        let mut log = LogEvent::default();
+        log.insert("region", "us-east-1");
+        log.insert("path", "/var/log/app=1.log");
+
+        let key = partitioner("logs/").partition(&log.into()).unwrap();
+        assert_eq!(
+            key.key_prefix,
+            "logs/region=us-east-1/path=%2Fvar%2Flog%2Fapp%3D1.log/"
+        );
+    }
+
+    #[test]
+    fn renders_missing_hive_partitions() {
+        let mut log = LogEvent::default();
+        log.insert("path", "");
+
+        let key = partitioner("").partition(&log.into()).unwrap();
+        assert_eq!(
+            key.key_prefix,
+            "region=__HIVE_DEFAULT_PARTITION__/path=__HIVE_DEFAULT_PARTITION__/"
+        );
+    }
+
+    #[test]
+    fn renders_tags() {
+        let partitioner = S3KeyPartitioner::new(Template::try_from("logs/").unwrap(), None)
+            .with_tags(vec![
+                ("retention".into(), Template::try_from("archive").unwrap()),
+                ("app".into(), Template::try_from("{{ app }}").unwrap()),
+            ]);
+
+        let mut log = LogEvent::default();
+        log.insert("app", "billing");
+        let key = partitioner.partition(&log.into()).unwrap();
+        assert_eq!(
+            key.tags,
+            Some(BTreeMap::from([
+                ("app".to_owned(), "billing".to_owned()),
+                ("retention".to_owned(), "archive".to_owned()),
+            ]))
+        );
+
+        assert_eq!(partitioner.partition(&LogEvent::default().into()), None);
+    }
+}
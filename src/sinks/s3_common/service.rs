@@ -1,23 +1,29 @@
 use std::task::{Context, Poll};
 
 use aws_sdk_s3::{
-    error::PutObjectError,
-    types::{ByteStream, SdkError},
+    error::{
+        CompleteMultipartUploadError, CreateMultipartUploadError, PutObjectError, UploadPartError,
+    },
+    model::{ChecksumAlgorithm, CompletedMultipartUpload, CompletedPart},
+    types::{ByteStream, DateTime, SdkError},
     Client as S3Client,
 };
 use bytes::Bytes;
-use futures::future::BoxFuture;
+use chrono::Utc;
+use futures::{future::BoxFuture, stream, StreamExt, TryStreamExt};
 use md5::Digest;
+use snafu::{OptionExt, ResultExt, Snafu};
 use tower::Service;
 use tracing::Instrument;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_core::{
     event::{EventFinalizers, EventStatus, Finalizable},
-    internal_event::CountByteSize,
     stream::DriverResponse,
 };
 
-use super::config::S3Options;
+use crate::sinks::util::adaptive_concurrency::ConcurrencyPartition;
+
+use super::config::{S3ChecksumAlgorithm, S3MultipartUploadConfig, S3Options};
 use super::partitioner::S3PartitionKey;
 
 #[derive(Debug, Clone)]
@@ -36,9 +42,15 @@ impl Finalizable for S3Request {
     }
 }
 
+impl ConcurrencyPartition for S3Request {
+    fn concurrency_partition(&self) -> Option<String> {
+        Some(self.metadata.partition_key.key_prefix.clone())
+    }
+}
+
 impl MetaDescriptive for S3Request {
     fn get_metadata(&self) -> RequestMetadata {
-        self.request_metadata
+        self.request_metadata.clone()
     }
 }
 
@@ -49,20 +61,34 @@ pub struct S3Metadata {
     pub finalizers: EventFinalizers,
 }
 
-#[derive(Debug)]
-pub struct S3Response {
-    count: usize,
-    events_byte_size: usize,
+#[derive(Debug, Snafu)]
+pub enum S3UploadError {
+    #[snafu(display("Failed to put object: {}", source))]
+    PutObject { source: SdkError<PutObjectError> },
+    #[snafu(display("Failed to create multipart upload: {}", source))]
+    CreateMultipartUpload {
+        source: SdkError<CreateMultipartUploadError>,
+    },
+    #[snafu(display("Multipart upload was created without an upload ID"))]
+    MissingUploadId,
+    #[snafu(display("Failed to upload part {}: {}", part_number, source))]
+    UploadPart {
+        part_number: i32,
+        source: SdkError<UploadPartError>,
+    },
+    #[snafu(display("Failed to complete multipart upload: {}", source))]
+    CompleteMultipartUpload {
+        source: SdkError<CompleteMultipartUploadError>,
+    },
 }
 
+#[derive(Debug)]
+pub struct S3Response;
+
 impl DriverResponse for S3Response {
     fn event_status(&self) -> EventStatus {
         EventStatus::Delivered
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(self.count, self.events_byte_size)
-    }
 }
 
 /// Wrapper for the AWS SDK S3 client.
@@ -74,11 +100,24 @@ impl DriverResponse for S3Response {
 #[derive(Clone)]
 pub struct S3Service {
     client: S3Client,
+    multipart_upload: Option<S3MultipartUploadConfig>,
 }
 
 impl S3Service {
     pub const fn new(client: S3Client) -> S3Service {
-        S3Service { client }
+        S3Service {
+            client,
+            multipart_upload: None,
+        }
+    }
+
+    /// Uploads objects larger than the part size of `multipart_upload` in parts.
+    pub const fn with_multipart_upload(
+        mut self,
+        multipart_upload: Option<S3MultipartUploadConfig>,
+    ) -> S3Service {
+        self.multipart_upload = multipart_upload;
+        self
     }
 
     pub fn client(&self) -> S3Client {
@@ -86,9 +125,18 @@ impl S3Service {
     }
 }
 
+/// The options of an object, set by the request creating it.
+struct ObjectOptions {
+    content_encoding: Option<String>,
+    content_type: Option<String>,
+    tagging: Option<String>,
+    object_lock_retain_until_date: Option<DateTime>,
+    options: S3Options,
+}
+
 impl Service<S3Request> for S3Service {
     type Response = S3Response;
-    type Error = SdkError<PutObjectError>;
+    type Error = S3UploadError;
     type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
     // Emission of an internal event in case of errors is handled upstream by the caller.
@@ -98,60 +146,254 @@ impl Service<S3Request> for S3Service {
 
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, request: S3Request) -> Self::Future {
-        let count = request.get_metadata().event_count();
-        let events_byte_size = request.get_metadata().events_byte_size();
-
         let options = request.options;
 
         let content_encoding = request.content_encoding;
         let content_encoding = options
             .content_encoding
+            .clone()
             .or_else(|| content_encoding.map(|ce| ce.to_string()));
         let content_type = options
             .content_type
+            .clone()
             .or_else(|| Some("text/x-log".to_owned()));
 
-        let content_md5 = base64::encode(md5::Md5::digest(&request.body));
+        let object_lock_retain_until_date = options.object_lock.map(|object_lock| {
+            DateTime::from_secs(object_lock.retain_until(Utc::now()).timestamp())
+        });
 
-        let tagging = options.tags.map(|tags| {
+        let tagging = options.tags.as_ref().map(|tags| {
             let mut tagging = url::form_urlencoded::Serializer::new(String::new());
-            for (p, v) in &tags {
+            for (p, v) in tags {
                 tagging.append_pair(p, v);
             }
             tagging.finish()
         });
 
+        let object = ObjectOptions {
+            content_encoding,
+            content_type,
+            tagging,
+            object_lock_retain_until_date,
+            options,
+        };
         let client = self.client.clone();
+        let multipart_upload = self
+            .multipart_upload
+            .filter(|multipart_upload| request.body.len() > multipart_upload.part_size_bytes);
 
         Box::pin(async move {
+            let bucket = request.bucket;
+            let key = request.metadata.s3_key;
+            match multipart_upload {
+                None => put_object(client, bucket, key, request.body, object).await?,
+                Some(multipart_upload) => {
+                    upload_multipart(client, bucket, key, request.body, object, multipart_upload)
+                        .await?
+                }
+            }
+
+            Ok(S3Response)
+        })
+    }
+}
+
+fn checksum_sha256(algorithm: Option<S3ChecksumAlgorithm>, body: &[u8]) -> Option<String> {
+    algorithm.map(|S3ChecksumAlgorithm::Sha256| base64::encode(sha2::Sha256::digest(body)))
+}
+
+async fn put_object(
+    client: S3Client,
+    bucket: String,
+    key: String,
+    body: Bytes,
+    object: ObjectOptions,
+) -> Result<(), S3UploadError> {
+    let options = object.options;
+    let content_md5 = base64::encode(md5::Md5::digest(&body));
+    let checksum_sha256 = checksum_sha256(options.checksum_algorithm, &body);
+
+    client
+        .put_object()
+        .body(bytes_to_bytestream(body))
+        .bucket(bucket)
+        .key(key)
+        .set_content_encoding(object.content_encoding)
+        .set_content_type(object.content_type)
+        .set_acl(options.acl.map(Into::into))
+        .set_grant_full_control(options.grant_full_control)
+        .set_grant_read(options.grant_read)
+        .set_grant_read_acp(options.grant_read_acp)
+        .set_grant_write_acp(options.grant_write_acp)
+        .set_server_side_encryption(options.server_side_encryption.map(Into::into))
+        .set_ssekms_key_id(options.ssekms_key_id)
+        .set_storage_class(options.storage_class.map(Into::into))
+        .set_tagging(object.tagging)
+        .set_object_lock_mode(
+            options
+                .object_lock
+                .map(|object_lock| object_lock.mode.into()),
+        )
+        .set_object_lock_retain_until_date(object.object_lock_retain_until_date)
+        .set_checksum_sha256(checksum_sha256)
+        .content_md5(content_md5)
+        .send()
+        .in_current_span()
+        .await
+        .context(PutObjectSnafu)?;
+
+    Ok(())
+}
+
+/// Uploads `body` in parts of at least `part_size_bytes` bytes, concurrently.
+///
+/// Uploads failing to complete are aborted, so that S3 discards the parts already uploaded.
+async fn upload_multipart(
+    client: S3Client,
+    bucket: String,
+    key: String,
+    body: Bytes,
+    object: ObjectOptions,
+    multipart_upload: S3MultipartUploadConfig,
+) -> Result<(), S3UploadError> {
+    let options = object.options;
+    let upload = client
+        .create_multipart_upload()
+        .bucket(&bucket)
+        .key(&key)
+        .set_content_encoding(object.content_encoding)
+        .set_content_type(object.content_type)
+        .set_acl(options.acl.map(Into::into))
+        .set_grant_full_control(options.grant_full_control)
+        .set_grant_read(options.grant_read)
+        .set_grant_read_acp(options.grant_read_acp)
+        .set_grant_write_acp(options.grant_write_acp)
+        .set_server_side_encryption(options.server_side_encryption.map(Into::into))
+        .set_ssekms_key_id(options.ssekms_key_id)
+        .set_storage_class(options.storage_class.map(Into::into))
+        .set_tagging(object.tagging)
+        .set_object_lock_mode(
+            options
+                .object_lock
+                .map(|object_lock| object_lock.mode.into()),
+        )
+        .set_object_lock_retain_until_date(object.object_lock_retain_until_date)
+        .set_checksum_algorithm(
+            options
+                .checksum_algorithm
+                .map(|S3ChecksumAlgorithm::Sha256| ChecksumAlgorithm::Sha256),
+        )
+        .send()
+        .in_current_span()
+        .await
+        .context(CreateMultipartUploadSnafu)?;
+    let upload_id = upload.upload_id().context(MissingUploadIdSnafu)?.to_owned();
+
+    let parts = part_ranges(body.len(), multipart_upload.part_size_bytes)
+        .into_iter()
+        .zip(1..)
+        .map(|((start, end), part_number)| {
+            let part = body.slice(start..end);
+            let content_md5 = base64::encode(md5::Md5::digest(&part));
+            let checksum_sha256 = checksum_sha256(options.checksum_algorithm, &part);
             let request = client
-                .put_object()
-                .body(bytes_to_bytestream(request.body))
-                .bucket(request.bucket)
-                .key(request.metadata.s3_key)
-                .set_content_encoding(content_encoding)
-                .set_content_type(content_type)
-                .set_acl(options.acl.map(Into::into))
-                .set_grant_full_control(options.grant_full_control)
-                .set_grant_read(options.grant_read)
-                .set_grant_read_acp(options.grant_read_acp)
-                .set_grant_write_acp(options.grant_write_acp)
-                .set_server_side_encryption(options.server_side_encryption.map(Into::into))
-                .set_ssekms_key_id(options.ssekms_key_id)
-                .set_storage_class(options.storage_class.map(Into::into))
-                .set_tagging(tagging)
+                .upload_part()
+                .bucket(&bucket)
+                .key(&key)
+                .upload_id(&upload_id)
+                .part_number(part_number)
+                .body(bytes_to_bytestream(part))
+                .set_checksum_sha256(checksum_sha256.clone())
                 .content_md5(content_md5);
+            async move {
+                request
+                    .send()
+                    .in_current_span()
+                    .await
+                    .map(|output| {
+                        CompletedPart::builder()
+                            .set_e_tag(output.e_tag().map(Into::into))
+                            .set_checksum_sha256(checksum_sha256)
+                            .part_number(part_number)
+                            .build()
+                    })
+                    .context(UploadPartSnafu { part_number })
+            }
+        });
+    let result = stream::iter(parts)
+        .buffered(multipart_upload.concurrency.get())
+        .try_collect::<Vec<_>>()
+        .await;
+    let result = match result {
+        Ok(parts) => client
+            .complete_multipart_upload()
+            .bucket(&bucket)
+            .key(&key)
+            .upload_id(&upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts))
+                    .build(),
+            )
+            .send()
+            .in_current_span()
+            .await
+            .map(|_| ())
+            .context(CompleteMultipartUploadSnafu),
+        Err(error) => Err(error),
+    };
 
-            let result = request.send().in_current_span().await;
-
-            result.map(|_| S3Response {
-                count,
-                events_byte_size,
-            })
-        })
+    if result.is_err() {
+        if let Err(error) = client
+            .abort_multipart_upload()
+            .bucket(bucket)
+            .key(key)
+            .upload_id(&upload_id)
+            .send()
+            .in_current_span()
+            .await
+        {
+            warn!(message = "Failed to abort multipart upload.", %upload_id, %error);
+        }
     }
+    result
+}
+
+/// The byte ranges of the parts `len` bytes are uploaded in.
+///
+/// Parts are `part_size` bytes long, except for the last one, or longer if `len` bytes would
+/// otherwise be split in more parts than S3 allows.
+fn part_ranges(len: usize, part_size: usize) -> Vec<(usize, usize)> {
+    let part_size = part_size.max((len + MAX_PARTS - 1) / MAX_PARTS);
+    (0..len)
+        .step_by(part_size)
+        .map(|start| (start, len.min(start + part_size)))
+        .collect()
 }
 
+/// The maximum number of parts of a multipart upload.
+const MAX_PARTS: usize = 10_000;
+
 fn bytes_to_bytestream(buf: Bytes) -> ByteStream {
     ByteStream::from(buf)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{part_ranges, MAX_PARTS};
+
+    #[test]
+    fn splits_parts() {
+        assert_eq!(part_ranges(10, 4), vec![(0, 4), (4, 8), (8, 10)]);
+        assert_eq!(part_ranges(8, 4), vec![(0, 4), (4, 8)]);
+    }
+
+    #[test]
+    fn grows_parts_beyond_the_parts_limit() {
+        let len = MAX_PARTS * 4 + 1;
+        let ranges = part_ranges(len, 2);
+        assert!(ranges.len() <= MAX_PARTS);
+        assert_eq!(ranges[0], (0, 5));
+        assert_eq!(ranges.last(), Some(&(len - 1, len)));
+    }
+}
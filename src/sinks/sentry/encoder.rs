@@ -0,0 +1,236 @@
+use bytes::Bytes;
+use chrono::{SecondsFormat, Utc};
+use serde_json::{json, Map, Value as JsonValue};
+use uuid::Uuid;
+use vector_core::{
+    config::log_schema,
+    event::{LogEvent, Value},
+};
+
+use super::stacktrace;
+
+/// Converts log events to Sentry events, wrapped in envelopes.
+#[derive(Clone, Debug)]
+pub struct EventEncoder {
+    pub level_key: String,
+    pub stack_trace_key: String,
+    pub fingerprint_key: String,
+    pub tags: Vec<String>,
+    pub environment: Option<String>,
+    pub release: Option<String>,
+}
+
+/// Normalizes the level of a log to one of those of Sentry, which defaults to `error`.
+fn level(value: Option<&Value>) -> &'static str {
+    let value = match value {
+        Some(value) => value.to_string_lossy().to_lowercase(),
+        None => return "error",
+    };
+    match value.as_str() {
+        "fatal" | "critical" | "crit" | "alert" | "emerg" | "emergency" | "panic" => "fatal",
+        "warning" | "warn" => "warning",
+        "info" | "informational" | "notice" => "info",
+        "debug" | "trace" => "debug",
+        _ => "error",
+    }
+}
+
+fn string(value: Value) -> String {
+    match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        value @ (Value::Object(_) | Value::Array(_)) => {
+            serde_json::to_string(&value).expect("values should serialize")
+        }
+        value => value.to_string_lossy().into_owned(),
+    }
+}
+
+impl EventEncoder {
+    /// Encodes `log` as a Sentry event, returning its ID with it.
+    pub fn encode_event(&self, mut log: LogEvent) -> (String, JsonValue) {
+        let event_id = Uuid::new_v4().simple().to_string();
+        let mut event = Map::new();
+        event.insert("event_id".into(), event_id.clone().into());
+        event.insert("platform".into(), "other".into());
+
+        let timestamp = match log.remove(log_schema().timestamp_key()) {
+            Some(Value::Timestamp(timestamp)) => timestamp,
+            _ => Utc::now(),
+        };
+        event.insert(
+            "timestamp".into(),
+            timestamp
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                .into(),
+        );
+        event.insert(
+            "level".into(),
+            level(log.remove(self.level_key.as_str()).as_ref()).into(),
+        );
+
+        if let Some(message) = log.remove(log_schema().message_key()) {
+            event.insert("logentry".into(), json!({ "formatted": string(message) }));
+        }
+        if let Some(host) = log.remove(log_schema().host_key()) {
+            event.insert("server_name".into(), string(host).into());
+        }
+
+        if let Some(trace) = log.remove(self.stack_trace_key.as_str()) {
+            let values: Vec<_> = stacktrace::parse(&string(trace))
+                .into_iter()
+                .map(|exception| {
+                    let mut value = json!({ "type": exception.ty });
+                    if let Some(message) = exception.value {
+                        value["value"] = message.into();
+                    }
+                    if !exception.frames.is_empty() {
+                        value["stacktrace"] = json!({ "frames": exception.frames });
+                    }
+                    value
+                })
+                .collect();
+            if !values.is_empty() {
+                event.insert("exception".into(), json!({ "values": values }));
+            }
+        }
+
+        match log.remove(self.fingerprint_key.as_str()) {
+            Some(Value::Array(parts)) => {
+                let parts: Vec<_> = parts.into_iter().map(string).collect();
+                event.insert("fingerprint".into(), parts.into());
+            }
+            Some(part) => {
+                event.insert("fingerprint".into(), vec![string(part)].into());
+            }
+            None => {}
+        }
+
+        let tags: Map<_, _> = self
+            .tags
+            .iter()
+            .filter_map(|field| {
+                let value = log.remove(field.as_str())?;
+                Some((field.clone(), string(value).into()))
+            })
+            .collect();
+        if !tags.is_empty() {
+            event.insert("tags".into(), tags.into());
+        }
+
+        if let Some(environment) = &self.environment {
+            event.insert("environment".into(), environment.clone().into());
+        }
+        if let Some(release) = &self.release {
+            event.insert("release".into(), release.clone().into());
+        }
+
+        // Whatever fields are left are set as the extra data of the event.
+        let (value, _) = log.into_parts();
+        match serde_json::to_value(value) {
+            Ok(JsonValue::Object(extra)) if !extra.is_empty() => {
+                event.insert("extra".into(), extra.into());
+            }
+            _ => {}
+        }
+
+        (event_id, event.into())
+    }
+
+    /// Encodes `log` as an envelope holding its Sentry event.
+    pub fn encode_envelope(&self, log: LogEvent) -> Bytes {
+        let (event_id, event) = self.encode_event(log);
+        let payload = serde_json::to_vec(&event).expect("events should serialize");
+        let headers = json!({
+            "event_id": event_id,
+            "sent_at": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        });
+        let item_headers = json!({
+            "type": "event",
+            "length": payload.len(),
+            "content_type": "application/json",
+        });
+
+        let mut envelope = Vec::with_capacity(payload.len() + 128);
+        serde_json::to_writer(&mut envelope, &headers).expect("headers should serialize");
+        envelope.push(b'\n');
+        serde_json::to_writer(&mut envelope, &item_headers).expect("headers should serialize");
+        envelope.push(b'\n');
+        envelope.extend_from_slice(&payload);
+        envelope.push(b'\n');
+        envelope.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn encoder() -> EventEncoder {
+        EventEncoder {
+            level_key: "level".to_owned(),
+            stack_trace_key: "stack_trace".to_owned(),
+            fingerprint_key: "fingerprint".to_owned(),
+            tags: vec!["service".to_owned()],
+            environment: Some("production".to_owned()),
+            release: None,
+        }
+    }
+
+    #[test]
+    fn encodes_error_logs() {
+        let mut log = LogEvent::from("Failed to process order");
+        log.insert("timestamp", Utc.ymd(2023, 1, 2).and_hms(3, 4, 5));
+        log.insert("level", "WARN");
+        log.insert("host", "web-1");
+        log.insert("service", "orders");
+        log.insert("fingerprint", "order-failures");
+        log.insert("order_id", 42);
+        log.insert(
+            "stack_trace",
+            "TypeError: undefined is not a function\n    at process (/app/orders.js:10:3)",
+        );
+
+        let (event_id, mut event) = encoder().encode_event(log);
+        assert_eq!(event_id.len(), 32);
+        event.as_object_mut().unwrap().remove("event_id");
+        assert_eq!(
+            event,
+            json!({
+                "platform": "other",
+                "timestamp": "2023-01-02T03:04:05Z",
+                "level": "warning",
+                "logentry": { "formatted": "Failed to process order" },
+                "server_name": "web-1",
+                "exception": { "values": [{
+                    "type": "TypeError",
+                    "value": "undefined is not a function",
+                    "stacktrace": { "frames": [{
+                        "function": "process",
+                        "filename": "/app/orders.js",
+                        "lineno": 10,
+                        "colno": 3,
+                    }] },
+                }] },
+                "fingerprint": ["order-failures"],
+                "tags": { "service": "orders" },
+                "environment": "production",
+                "extra": { "order_id": 42 },
+            })
+        );
+    }
+
+    #[test]
+    fn encodes_envelopes() {
+        let envelope = encoder().encode_envelope(LogEvent::from("boom"));
+        let lines: Vec<_> = envelope.split(|byte| *byte == b'\n').collect();
+
+        assert_eq!(lines.len(), 4);
+        let item_headers: JsonValue = serde_json::from_slice(lines[1]).unwrap();
+        assert_eq!(item_headers["type"], "event");
+        assert_eq!(item_headers["length"], lines[2].len());
+        let event: JsonValue = serde_json::from_slice(lines[2]).unwrap();
+        assert_eq!(event["level"], "error");
+    }
+}
@@ -0,0 +1,83 @@
+//! The `sentry` sink.
+//!
+//! Converts error logs to Sentry events, with the exceptions parsed from their stack traces, and
+//! sends them to the envelope endpoint of a project, holding requests back while it's rate
+//! limited.
+
+mod config;
+mod encoder;
+mod service;
+mod sink;
+mod stacktrace;
+
+pub use self::config::SentryConfig;
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use http::{Response, StatusCode};
+    use hyper::Body;
+    use serde_json::Value as JsonValue;
+    use vector_core::event::{BatchNotifier, BatchStatus, Event, LogEvent};
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        sinks::util::test::build_test_server_generic,
+        test_util::{
+            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+            next_addr,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SentryConfig>();
+    }
+
+    #[tokio::test]
+    async fn sends_envelopes() {
+        let addr = next_addr();
+        let config: SentryConfig = toml::from_str(&format!(
+            r#"dsn = "http://abcdef@{}/42"
+            tags = ["service"]"#,
+            addr
+        ))
+        .unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (mut rx, trigger, server) = build_test_server_generic(addr, || {
+            Response::builder()
+                .status(StatusCode::OK)
+                .body(Body::empty())
+                .unwrap()
+        });
+        tokio::spawn(server);
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let mut log = LogEvent::from("Failed to process order").with_batch_notifier(&batch);
+        log.insert("level", "error");
+        log.insert("service", "orders");
+        drop(batch);
+        run_and_assert_sink_compliance(
+            sink,
+            futures::stream::iter([Event::from(log)]),
+            &HTTP_SINK_TAGS,
+        )
+        .await;
+        drop(trigger);
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let (parts, body) = rx.next().await.unwrap();
+        assert_eq!(parts.uri.path(), "/api/42/envelope/");
+        assert!(parts.headers["x-sentry-auth"]
+            .to_str()
+            .unwrap()
+            .starts_with("Sentry sentry_version=7, sentry_key=abcdef, sentry_client=vector/"));
+
+        let event: JsonValue =
+            serde_json::from_slice(body.split(|byte| *byte == b'\n').nth(2).unwrap()).unwrap();
+        assert_eq!(event["logentry"]["formatted"], "Failed to process order");
+        assert_eq!(event["tags"]["service"], "orders");
+    }
+}
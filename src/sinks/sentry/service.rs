@@ -0,0 +1,234 @@
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use http::{
+    header::{CONTENT_TYPE, RETRY_AFTER},
+    HeaderMap, Request, StatusCode,
+};
+use snafu::{ResultExt, Snafu};
+use tower::Service;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    stream::DriverResponse,
+};
+
+use super::config::Dsn;
+use crate::{
+    http::{HttpClient, HttpError},
+    sinks::util::retries::RetryLogic,
+};
+
+/// How long requests are held back after being rate limited, when Sentry doesn't tell.
+const DEFAULT_RATE_LIMIT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Snafu)]
+pub enum SentryError {
+    #[snafu(display("Request failed: {}", source))]
+    Http { source: HttpError },
+    #[snafu(display("Rate limited by Sentry for {:?}", retry_after))]
+    RateLimited { retry_after: Duration },
+    #[snafu(display("Request failed with status {}", status))]
+    Status { status: StatusCode },
+}
+
+/// An envelope holding a single event.
+#[derive(Clone)]
+pub struct SentryRequest {
+    pub envelope: Bytes,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for SentryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for SentryRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata.clone()
+    }
+}
+
+pub struct SentryResponse;
+
+impl DriverResponse for SentryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+}
+
+#[derive(Clone)]
+pub struct SentryService {
+    pub client: HttpClient,
+    pub dsn: Dsn,
+    /// When the rate limits of the project end, shared by all requests so that none of them are
+    /// sent before then.
+    pub rate_limited_until: Arc<Mutex<Option<Instant>>>,
+}
+
+impl SentryService {
+    fn auth_header(&self) -> String {
+        format!(
+            "Sentry sentry_version=7, sentry_key={}, sentry_client=vector/{}",
+            self.dsn.public_key,
+            crate::get_version()
+        )
+    }
+}
+
+impl Service<SentryRequest> for SentryService {
+    type Response = SentryResponse;
+    type Error = SentryError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: SentryRequest) -> Self::Future {
+        let client = self.client.clone();
+        let rate_limited_until = Arc::clone(&self.rate_limited_until);
+        let http_request = Request::post(&self.dsn.envelope_uri)
+            .header(CONTENT_TYPE, "application/x-sentry-envelope")
+            .header("X-Sentry-Auth", self.auth_header())
+            .body(hyper::Body::from(request.envelope))
+            .expect("Invalid request");
+
+        Box::pin(async move {
+            let until = *rate_limited_until.lock().expect("poisoned lock");
+            if let Some(until) = until {
+                tokio::time::sleep_until(until.into()).await;
+            }
+
+            let response = client.send(http_request).await.context(HttpSnafu)?;
+            let status = response.status();
+            if let Some(retry_after) = rate_limit(status, response.headers()) {
+                let until = Instant::now() + retry_after;
+                let mut rate_limited_until = rate_limited_until.lock().expect("poisoned lock");
+                if rate_limited_until.map_or(true, |current| current < until) {
+                    *rate_limited_until = Some(until);
+                }
+                if status == StatusCode::TOO_MANY_REQUESTS {
+                    return Err(SentryError::RateLimited { retry_after });
+                }
+            }
+            if !status.is_success() {
+                return Err(SentryError::Status { status });
+            }
+
+            Ok(SentryResponse)
+        })
+    }
+}
+
+/// How long the project is rate limited for errors, according to the [headers][rate_limits] of a
+/// response.
+///
+/// [rate_limits]: https://develop.sentry.dev/sdk/rate-limiting/
+fn rate_limit(status: StatusCode, headers: &HeaderMap) -> Option<Duration> {
+    if let Some(limits) = headers
+        .get("X-Sentry-Rate-Limits")
+        .and_then(|value| value.to_str().ok())
+    {
+        // Limits are listed as `<seconds>:<categories>:<scope>...`, where the categories are
+        // separated by semicolons, and limits without categories apply to all of them.
+        let limit = limits
+            .split(',')
+            .filter_map(|limit| {
+                let mut parts = limit.trim().split(':');
+                let seconds: f64 = parts.next()?.parse().ok()?;
+                let categories = parts.next().unwrap_or("");
+                (categories.is_empty()
+                    || categories
+                        .split(';')
+                        .any(|category| matches!(category, "error" | "default")))
+                .then(|| Duration::from_secs_f64(seconds.max(0.0)))
+            })
+            .max();
+        if limit.is_some() {
+            return limit;
+        }
+    }
+
+    if status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+    let retry_after = headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .map(|seconds| Duration::from_secs_f64(seconds.max(0.0)));
+    Some(retry_after.unwrap_or(DEFAULT_RATE_LIMIT))
+}
+
+#[derive(Debug, Clone)]
+pub struct SentryRetryLogic;
+
+impl RetryLogic for SentryRetryLogic {
+    type Error = SentryError;
+    type Response = SentryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            SentryError::Http { .. } | SentryError::RateLimited { .. } => true,
+            SentryError::Status { status } => status.is_server_error(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| {
+                (
+                    http::header::HeaderName::from_static(name),
+                    value.parse().unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_rate_limits() {
+        let limits = headers(&[(
+            "x-sentry-rate-limits",
+            "120:transaction:key, 30:error;security:organization",
+        )]);
+        assert_eq!(
+            rate_limit(StatusCode::TOO_MANY_REQUESTS, &limits),
+            Some(Duration::from_secs(30))
+        );
+
+        let limits = headers(&[("x-sentry-rate-limits", "60::organization")]);
+        assert_eq!(
+            rate_limit(StatusCode::OK, &limits),
+            Some(Duration::from_secs(60))
+        );
+
+        let limits = headers(&[("x-sentry-rate-limits", "60:transaction:key")]);
+        assert_eq!(rate_limit(StatusCode::OK, &limits), None);
+
+        let limits = headers(&[("retry-after", "5")]);
+        assert_eq!(
+            rate_limit(StatusCode::TOO_MANY_REQUESTS, &limits),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            rate_limit(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new()),
+            Some(DEFAULT_RATE_LIMIT)
+        );
+        assert_eq!(rate_limit(StatusCode::OK, &HeaderMap::new()), None);
+    }
+}
@@ -0,0 +1,60 @@
+use std::num::NonZeroUsize;
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::ServiceBuilder;
+use vector_core::event::{Event, Finalizable};
+
+use super::{
+    encoder::EventEncoder,
+    service::{SentryRequest, SentryRetryLogic, SentryService},
+};
+use crate::{
+    codecs::Transformer,
+    sinks::util::{
+        metadata::RequestMetadataBuilder, ServiceBuilderExt, SinkBuilderExt, StreamSink,
+        TowerRequestSettings,
+    },
+};
+
+pub struct SentrySink {
+    pub service: SentryService,
+    pub protocol: String,
+    pub encoder: EventEncoder,
+    pub transformer: Transformer,
+    pub request: TowerRequestSettings,
+}
+
+#[async_trait]
+impl StreamSink<Event> for SentrySink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let sink = *self;
+        let service = ServiceBuilder::new()
+            .settings(sink.request, SentryRetryLogic)
+            .service(sink.service);
+        let encoder = sink.encoder;
+        let transformer = sink.transformer;
+
+        // Envelopes hold a single event, so that Sentry rate limits and drops events one by one.
+        input
+            .map(move |mut event| {
+                let finalizers = event.take_finalizers();
+                let builder = RequestMetadataBuilder::from_events(&event);
+                transformer.transform(&mut event);
+                let envelope = encoder.encode_envelope(event.into_log());
+                let metadata = builder.with_request_size(
+                    NonZeroUsize::new(envelope.len()).expect("envelopes should not be empty"),
+                );
+
+                SentryRequest {
+                    envelope,
+                    finalizers,
+                    metadata,
+                }
+            })
+            .into_driver(service)
+            .protocol(sink.protocol)
+            .run()
+            .await
+    }
+}
@@ -0,0 +1,251 @@
+//! Parsing of the stack traces of Python, Java, and JavaScript exceptions.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+static PYTHON_FRAME: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*File "(?P<filename>[^"]+)", line (?P<lineno>\d+)(?:, in (?P<function>.+))?$"#)
+        .unwrap()
+});
+
+static JAVASCRIPT_FRAME: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*at\s+(?:(?P<function>.+?)\s+\()?(?P<filename>[^()\s]+?):(?P<lineno>\d+)(?::(?P<colno>\d+))?\)?$",
+    )
+    .unwrap()
+});
+
+static JAVA_FRAME: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*at\s+(?P<function>[^\s(]+)\((?P<location>[^)]*)\)(?:\s.*)?$").unwrap()
+});
+
+/// A frame of a stack trace, in the shape of the frames of Sentry events.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Frame {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lineno: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub colno: Option<u64>,
+}
+
+/// An exception of a stack trace, with its frames ordered from the oldest to the most recent call.
+#[derive(Debug, PartialEq)]
+pub struct Exception {
+    pub ty: String,
+    pub value: Option<String>,
+    pub frames: Vec<Frame>,
+}
+
+impl Exception {
+    /// Parses the line naming an exception, such as `ValueError: invalid literal`.
+    fn from_header(line: &str) -> Self {
+        let (ty, value) = match line.split_once(": ") {
+            Some((ty, value)) if !ty.contains(char::is_whitespace) => (ty, Some(value)),
+            _ if !line.contains(char::is_whitespace) => (line, None),
+            _ => ("Error", Some(line)),
+        };
+        Self {
+            ty: ty.to_owned(),
+            value: value.map(Into::into),
+            frames: Vec::new(),
+        }
+    }
+}
+
+/// Parses the exceptions of a stack trace, ordered from the oldest cause to the exception that
+/// was raised last, as Sentry expects them.
+pub fn parse(trace: &str) -> Vec<Exception> {
+    let lines: Vec<&str> = trace
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+
+    match lines.first() {
+        Some(line) if line.starts_with("Traceback ") => parse_python(&lines),
+        Some(_) => parse_java_like(&lines),
+        None => Vec::new(),
+    }
+}
+
+/// Python prints the frames of tracebacks from the oldest call, and chained exceptions from their
+/// oldest cause.
+fn parse_python(lines: &[&str]) -> Vec<Exception> {
+    let mut exceptions = Vec::new();
+    let mut frames = Vec::new();
+    for line in lines {
+        if let Some(captures) = PYTHON_FRAME.captures(line) {
+            frames.push(Frame {
+                function: captures.name("function").map(|m| m.as_str().to_owned()),
+                filename: Some(captures["filename"].to_owned()),
+                lineno: captures["lineno"].parse().ok(),
+                ..Default::default()
+            });
+        } else if line.starts_with(char::is_whitespace)
+            || line.starts_with("Traceback ")
+            || line.starts_with("During handling of the above exception")
+            || line.starts_with("The above exception was the direct cause")
+        {
+            // Lines of source code, and the headers of tracebacks.
+        } else {
+            let mut exception = Exception::from_header(line);
+            exception.frames = std::mem::take(&mut frames);
+            exceptions.push(exception);
+        }
+    }
+    exceptions
+}
+
+/// Java and JavaScript print the frames of stack traces from the most recent call, and chained
+/// exceptions from the one raised last, with their causes following them.
+fn parse_java_like(lines: &[&str]) -> Vec<Exception> {
+    let mut exceptions: Vec<Exception> = Vec::new();
+    for line in lines {
+        if let Some(frame) = javascript_frame(line).or_else(|| java_frame(line)) {
+            if let Some(exception) = exceptions.last_mut() {
+                exception.frames.push(frame);
+            }
+            continue;
+        }
+
+        let line = line.trim();
+        if let Some(cause) = line.strip_prefix("Caused by: ") {
+            exceptions.push(Exception::from_header(cause));
+        } else if exceptions.is_empty() {
+            exceptions.push(Exception::from_header(line));
+        }
+        // Other lines, such as the `... 5 more` of elided frames, are ignored.
+    }
+
+    for exception in &mut exceptions {
+        exception.frames.reverse();
+    }
+    exceptions.reverse();
+    exceptions
+}
+
+fn javascript_frame(line: &str) -> Option<Frame> {
+    let captures = JAVASCRIPT_FRAME.captures(line)?;
+    Some(Frame {
+        function: captures.name("function").map(|m| m.as_str().to_owned()),
+        filename: Some(captures["filename"].to_owned()),
+        lineno: captures["lineno"].parse().ok(),
+        colno: captures.name("colno").and_then(|m| m.as_str().parse().ok()),
+        ..Default::default()
+    })
+}
+
+/// Java frames name the methods of classes, such as `com.example.Main.run(Main.java:12)`.
+fn java_frame(line: &str) -> Option<Frame> {
+    let captures = JAVA_FRAME.captures(line)?;
+    let (module, function) = match captures["function"].rsplit_once('.') {
+        Some((class, method)) => (Some(class.to_owned()), method.to_owned()),
+        None => (None, captures["function"].to_owned()),
+    };
+    let (filename, lineno) = match captures["location"].split_once(':') {
+        Some((filename, lineno)) => (Some(filename.to_owned()), lineno.parse().ok()),
+        None => (None, None),
+    };
+    Some(Frame {
+        function: Some(function),
+        module,
+        filename,
+        lineno,
+        colno: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(function: &str, filename: &str, lineno: u64) -> Frame {
+        Frame {
+            function: Some(function.to_owned()),
+            filename: Some(filename.to_owned()),
+            lineno: Some(lineno),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_python_tracebacks() {
+        let trace = r#"Traceback (most recent call last):
+  File "app.py", line 10, in <module>
+    main()
+  File "app.py", line 5, in main
+    int("x")
+ValueError: invalid literal for int() with base 10: 'x'"#;
+
+        assert_eq!(
+            parse(trace),
+            vec![Exception {
+                ty: "ValueError".to_owned(),
+                value: Some("invalid literal for int() with base 10: 'x'".to_owned()),
+                frames: vec![frame("<module>", "app.py", 10), frame("main", "app.py", 5)],
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_java_stack_traces() {
+        let trace = "java.lang.IllegalStateException: failed to start
+\tat com.example.App.start(App.java:20)
+\tat com.example.App.main(App.java:8)
+Caused by: java.io.IOException: disk full
+\tat com.example.Store.open(Store.java:42)
+\t... 2 more";
+
+        let exceptions = parse(trace);
+        assert_eq!(exceptions.len(), 2);
+        assert_eq!(exceptions[0].ty, "java.io.IOException");
+        assert_eq!(exceptions[0].value.as_deref(), Some("disk full"));
+        assert_eq!(exceptions[1].ty, "java.lang.IllegalStateException");
+        assert_eq!(
+            exceptions[1].frames,
+            vec![
+                Frame {
+                    module: Some("com.example.App".to_owned()),
+                    ..frame("main", "App.java", 8)
+                },
+                Frame {
+                    module: Some("com.example.App".to_owned()),
+                    ..frame("start", "App.java", 20)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_javascript_stack_traces() {
+        let trace = "TypeError: Cannot read properties of undefined
+    at handler (/app/server.js:12:7)
+    at /app/router.js:30:5";
+
+        assert_eq!(
+            parse(trace),
+            vec![Exception {
+                ty: "TypeError".to_owned(),
+                value: Some("Cannot read properties of undefined".to_owned()),
+                frames: vec![
+                    Frame {
+                        filename: Some("/app/router.js".to_owned()),
+                        lineno: Some(30),
+                        colno: Some(5),
+                        ..Default::default()
+                    },
+                    Frame {
+                        colno: Some(7),
+                        ..frame("handler", "/app/server.js", 12)
+                    },
+                ],
+            }]
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use std::path::PathBuf;
+
+use futures::FutureExt;
+use lettre::{
+    message::Mailbox,
+    transport::smtp::{
+        authentication::Credentials,
+        client::{Certificate, Tls, TlsParameters},
+    },
+    AsyncSmtpTransport, Tokio1Executor,
+};
+use snafu::{ResultExt, Snafu};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use super::{message::EmailTemplates, service::SmtpService, sink::SmtpSink};
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{
+        util::{BatchConfig, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid `from` address: {}", source))]
+    InvalidFrom {
+        source: lettre::address::AddressError,
+    },
+    #[snafu(display("Could not read CA file {:?}: {}", path, source))]
+    ReadCaFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Invalid TLS settings: {}", source))]
+    InvalidTls {
+        source: lettre::transport::smtp::Error,
+    },
+}
+
+#[derive(Debug, Snafu)]
+enum HealthcheckError {
+    #[snafu(display("Could not connect to the SMTP server: {}", source))]
+    Connect {
+        source: lettre::transport::smtp::Error,
+    },
+    #[snafu(display("The SMTP server did not accept the connection"))]
+    Refused,
+}
+
+/// How connections to the SMTP server are secured.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpTlsMode {
+    /// Connections are upgraded to TLS with `STARTTLS`, which the server must support.
+    #[default]
+    Starttls,
+
+    /// Connections use TLS from the start, as with SMTPS.
+    Implicit,
+
+    /// Connections aren't encrypted, which should only be used with servers on the same host or
+    /// network.
+    None,
+}
+
+impl SmtpTlsMode {
+    const fn default_port(self) -> u16 {
+        match self {
+            Self::Starttls => 587,
+            Self::Implicit => 465,
+            Self::None => 25,
+        }
+    }
+}
+
+/// TLS configuration of connections to the SMTP server.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SmtpTlsConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: SmtpTlsMode,
+
+    /// Whether to verify the certificate of the server.
+    ///
+    /// Do NOT set this to `false` unless you understand the risks of not verifying the validity of
+    /// certificates.
+    pub verify_certificate: Option<bool>,
+
+    /// Whether to verify that the certificate of the server is for its host.
+    ///
+    /// Do NOT set this to `false` unless you understand the risks of not verifying the remote
+    /// hostname.
+    pub verify_hostname: Option<bool>,
+
+    /// Absolute path to an additional CA certificate file, in PEM format, the certificate of the
+    /// server is verified with.
+    #[configurable(metadata(docs::examples = "/path/to/certificate_authority.crt"))]
+    pub ca_file: Option<PathBuf>,
+}
+
+/// The credentials emails are sent with.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SmtpAuth {
+    /// The username to authenticate with.
+    #[configurable(metadata(docs::examples = "vector@example.com"))]
+    pub username: String,
+
+    /// The password to authenticate with.
+    #[configurable(metadata(docs::examples = "${SMTP_PASSWORD}"))]
+    pub password: SensitiveString,
+}
+
+/// Configuration for the `smtp` sink.
+#[configurable_component(sink("smtp"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SmtpConfig {
+    /// The host of the SMTP server.
+    #[configurable(metadata(docs::examples = "smtp.example.com"))]
+    pub host: String,
+
+    /// The port of the SMTP server.
+    ///
+    /// Defaults to `587` with `STARTTLS`, `465` with implicit TLS, and `25` without TLS.
+    #[configurable(metadata(docs::examples = 2525))]
+    pub port: Option<u16>,
+
+    #[configurable(derived)]
+    pub auth: Option<SmtpAuth>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tls: SmtpTlsConfig,
+
+    /// The sender of emails, as an address, optionally with a name.
+    #[configurable(metadata(docs::examples = "Vector <vector@example.com>"))]
+    pub from: String,
+
+    /// The recipients of emails, as addresses separated by commas.
+    ///
+    /// Events are batched by their recipients, and each batch is sent as a single email to them.
+    /// Events whose recipients aren't valid addresses are dropped.
+    #[configurable(metadata(docs::examples = "oncall@example.com"))]
+    #[configurable(metadata(docs::examples = "{{ team }}@example.com, ops@example.com"))]
+    pub to: Template,
+
+    /// The template of the subject of emails.
+    ///
+    /// Emails of batches have the subject of their first event.
+    #[configurable(metadata(docs::examples = "[{{ level }}] {{ host }}"))]
+    pub subject: Template,
+
+    /// The template of the plain-text body of emails.
+    ///
+    /// The bodies of the events of batches are sent as the lines of their email.
+    #[serde(default = "default_body")]
+    #[configurable(metadata(docs::examples = "{{ timestamp }} {{ host }}: {{ message }}"))]
+    pub body: Template,
+
+    /// The template of the HTML body of emails.
+    ///
+    /// When set, emails have both a plain-text and an HTML body, and clients show the one they
+    /// prefer.
+    #[configurable(metadata(docs::examples = "<p><b>{{ host }}</b>: {{ message }}</p>"))]
+    pub html_body: Option<Template>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<SmtpDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_body() -> Template {
+    Template::try_from("{{ message }}").expect("couldn't build default body template")
+}
+
+/// The window events are collected in before being sent as a single email.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SmtpDefaultBatchSettings;
+
+impl SinkBatchSettings for SmtpDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    const MAX_BYTES: Option<usize> = Some(1_000_000);
+    const TIMEOUT_SECS: f64 = 60.0;
+}
+
+impl GenerateConfig for SmtpConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"host = "smtp.example.com"
+            from = "Vector <vector@example.com>"
+            to = "oncall@example.com"
+            subject = "[{{ level }}] {{ host }}""#,
+        )
+        .unwrap()
+    }
+}
+
+impl SmtpConfig {
+    fn tls(&self) -> crate::Result<Tls> {
+        if self.tls.mode == SmtpTlsMode::None {
+            return Ok(Tls::None);
+        }
+
+        let mut parameters = TlsParameters::builder(self.host.clone())
+            .dangerous_accept_invalid_certs(!self.tls.verify_certificate.unwrap_or(true))
+            .dangerous_accept_invalid_hostnames(!self.tls.verify_hostname.unwrap_or(true));
+        if let Some(path) = &self.tls.ca_file {
+            let pem = std::fs::read(path).context(ReadCaFileSnafu { path })?;
+            let certificate = Certificate::from_pem(&pem).context(InvalidTlsSnafu)?;
+            parameters = parameters.add_root_certificate(certificate);
+        }
+        let parameters = parameters.build().context(InvalidTlsSnafu)?;
+
+        Ok(match self.tls.mode {
+            SmtpTlsMode::Implicit => Tls::Wrapper(parameters),
+            _ => Tls::Required(parameters),
+        })
+    }
+
+    fn transport(&self) -> crate::Result<AsyncSmtpTransport<Tokio1Executor>> {
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.host)
+            .port(self.port.unwrap_or_else(|| self.tls.mode.default_port()))
+            .tls(self.tls()?)
+            .timeout(Some(request.timeout));
+        if let Some(auth) = &self.auth {
+            builder = builder.credentials(Credentials::new(
+                auth.username.clone(),
+                auth.password.inner().to_owned(),
+            ));
+        }
+        Ok(builder.build())
+    }
+}
+
+async fn healthcheck(transport: AsyncSmtpTransport<Tokio1Executor>) -> crate::Result<()> {
+    match transport.test_connection().await.context(ConnectSnafu)? {
+        true => Ok(()),
+        false => Err(HealthcheckError::Refused.into()),
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for SmtpConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let from: Mailbox = self.from.parse().context(InvalidFromSnafu)?;
+        let transport = self.transport()?;
+        let healthcheck = healthcheck(transport.clone()).boxed();
+
+        let sink = SmtpSink {
+            service: SmtpService { transport },
+            from,
+            templates: EmailTemplates {
+                to: self.to.clone(),
+                subject: self.subject.clone(),
+                body: self.body.clone(),
+                html_body: self.html_body.clone(),
+            },
+            batch_settings: self.batch.into_batcher_settings()?,
+            // Emails are read by people, so bursts of events send at most one every few seconds.
+            request: self.request.unwrap_with(&TowerRequestConfig {
+                rate_limit_num: Some(10),
+                rate_limit_duration_secs: Some(60),
+                ..Default::default()
+            }),
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
@@ -0,0 +1,240 @@
+use std::num::NonZeroUsize;
+
+use bytes::Bytes;
+use lettre::{
+    message::{header::ContentType, Mailbox, MultiPart},
+    Message,
+};
+use vector_core::{
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use super::service::SmtpRequest;
+use crate::{
+    emit,
+    internal_events::{SmtpInvalidAddress, TemplateRenderingError},
+    sinks::util::metadata::RequestMetadataBuilder,
+    template::Template,
+};
+
+/// An event, rendered as the recipients, subject, and bodies of its email.
+pub struct RenderedEmail {
+    /// The addresses of the recipients, sorted so that events with the same recipients are
+    /// batched together, whatever their order.
+    pub recipients: Vec<String>,
+    pub subject: String,
+    pub text: String,
+    pub html: Option<String>,
+    pub finalizers: EventFinalizers,
+    events_byte_size: usize,
+    events_estimated_json_encoded_byte_size: usize,
+}
+
+impl ByteSizeOf for RenderedEmail {
+    fn size_of(&self) -> usize {
+        // The batcher limits batches by the size of their bodies, rather than that of events.
+        self.text.len() + self.html.as_ref().map_or(0, String::len)
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
+/// The templates events are rendered as emails with.
+#[derive(Clone, Debug)]
+pub struct EmailTemplates {
+    pub to: Template,
+    pub subject: Template,
+    pub body: Template,
+    pub html_body: Option<Template>,
+}
+
+fn render(template: &Template, event: &Event, field: &'static str) -> Option<String> {
+    template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: true,
+            })
+        })
+        .ok()
+}
+
+/// Parses the comma-separated addresses of `recipients`.
+fn parse_recipients(recipients: &str) -> Option<Vec<String>> {
+    let mut addresses = recipients
+        .split(',')
+        .map(str::trim)
+        .filter(|address| !address.is_empty())
+        .map(|address| match address.parse::<Mailbox>() {
+            Ok(mailbox) => Some(mailbox.to_string()),
+            Err(error) => {
+                emit!(SmtpInvalidAddress { address, error });
+                None
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    addresses.sort();
+    addresses.dedup();
+    (!addresses.is_empty()).then(|| addresses)
+}
+
+impl EmailTemplates {
+    fn render_parts(&self, event: &Event) -> Option<(Vec<String>, String, String, Option<String>)> {
+        let recipients = parse_recipients(&render(&self.to, event, "to")?)?;
+        let subject = render(&self.subject, event, "subject")?;
+        let text = render(&self.body, event, "body")?;
+        let html = match &self.html_body {
+            Some(template) => Some(render(template, event, "html_body")?),
+            None => None,
+        };
+        Some((recipients, subject, text, html))
+    }
+
+    /// Renders `event`, or returns `None` after rejecting it when its templates fail to render, or
+    /// its recipients aren't valid addresses.
+    pub fn render(&self, mut event: Event) -> Option<RenderedEmail> {
+        let finalizers = event.take_finalizers();
+        match self.render_parts(&event) {
+            Some((recipients, subject, text, html)) => Some(RenderedEmail {
+                recipients,
+                subject,
+                text,
+                html,
+                finalizers,
+                events_byte_size: event.size_of(),
+                events_estimated_json_encoded_byte_size: event.estimated_json_encoded_size_of(),
+            }),
+            None => {
+                finalizers.update_status(EventStatus::Rejected);
+                None
+            }
+        }
+    }
+}
+
+/// Builds a single email out of the events of a batch, which share their recipients.
+///
+/// The email has the subject of the first event, and the bodies of all events as its lines.
+pub fn build_email(
+    from: &Mailbox,
+    recipients: &[String],
+    emails: Vec<RenderedEmail>,
+) -> Result<SmtpRequest, lettre::error::Error> {
+    let event_count = emails.len();
+    let mut events_byte_size = 0;
+    let mut events_estimated_json_encoded_byte_size = 0;
+    let mut finalizers = EventFinalizers::default();
+    let mut subject = None;
+    let mut texts = Vec::with_capacity(event_count);
+    let mut htmls = Vec::new();
+    for email in emails {
+        events_byte_size += email.events_byte_size;
+        events_estimated_json_encoded_byte_size += email.events_estimated_json_encoded_byte_size;
+        finalizers.merge(email.finalizers);
+        subject.get_or_insert(email.subject);
+        texts.push(email.text);
+        htmls.extend(email.html);
+    }
+
+    let mut message = Message::builder()
+        .from(from.clone())
+        .subject(subject.unwrap_or_default());
+    for recipient in recipients {
+        message = message.to(recipient
+            .parse()
+            .expect("recipients should have been validated"));
+    }
+    let text = texts.join("\n");
+    let message = if htmls.is_empty() {
+        message.header(ContentType::TEXT_PLAIN).body(text)
+    } else {
+        message.multipart(MultiPart::alternative_plain_html(text, htmls.join("\n")))
+    };
+    let message = match message {
+        Ok(message) => message,
+        Err(error) => {
+            finalizers.update_status(EventStatus::Rejected);
+            return Err(error);
+        }
+    };
+
+    let body = Bytes::from(message.formatted());
+    let builder = RequestMetadataBuilder::new(
+        event_count,
+        events_byte_size,
+        events_estimated_json_encoded_byte_size,
+    );
+    let metadata = builder
+        .with_request_size(NonZeroUsize::new(body.len()).expect("emails should not be empty"));
+    Ok(SmtpRequest {
+        envelope: message.envelope().clone(),
+        body,
+        finalizers,
+        metadata,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn templates(html_body: Option<&str>) -> EmailTemplates {
+        EmailTemplates {
+            to: Template::try_from("{{ recipients }}").unwrap(),
+            subject: Template::try_from("Alert from {{ host }}").unwrap(),
+            body: Template::try_from("{{ message }}").unwrap(),
+            html_body: html_body.map(|html| Template::try_from(html).unwrap()),
+        }
+    }
+
+    fn event(host: &str, recipients: &str) -> Event {
+        let mut log = LogEvent::from("Disk almost full");
+        log.insert("host", host);
+        log.insert("recipients", recipients);
+        log.into()
+    }
+
+    #[test]
+    fn renders_recipients() {
+        let email = templates(None)
+            .render(event("db-1", "b@example.com, a@example.com,b@example.com"))
+            .unwrap();
+        assert_eq!(email.recipients, vec!["a@example.com", "b@example.com"]);
+        assert_eq!(email.subject, "Alert from db-1");
+
+        assert!(templates(None)
+            .render(event("db-1", "not an address"))
+            .is_none());
+        assert!(templates(None).render(event("db-1", " , ")).is_none());
+    }
+
+    #[test]
+    fn builds_emails_of_batches() {
+        let templates = templates(Some("<p>{{ message }} on {{ host }}</p>"));
+        let emails = vec![
+            templates
+                .render(event("db-1", "oncall@example.com"))
+                .unwrap(),
+            templates
+                .render(event("db-2", "oncall@example.com"))
+                .unwrap(),
+        ];
+        let from = "Vector <vector@example.com>".parse().unwrap();
+
+        let request = build_email(&from, &["oncall@example.com".to_owned()], emails).unwrap();
+        let body = String::from_utf8(request.body.to_vec()).unwrap();
+        assert_eq!(request.metadata.event_count(), 2);
+        assert_eq!(request.envelope.to().len(), 1);
+        assert!(body.contains("Subject: Alert from db-1\r\n"));
+        assert!(body.contains("multipart/alternative"));
+        assert!(body.contains("Disk almost full\r\nDisk almost full"));
+        assert!(body.contains("<p>Disk almost full on db-1</p>\r\n<p>Disk almost full on db-2</p>"));
+    }
+}
@@ -0,0 +1,119 @@
+//! The `smtp` sink.
+//!
+//! Sends events rendered by templates as emails, through an SMTP server, where the events of each
+//! batch with the same recipients make up a single email.
+
+mod config;
+mod message;
+mod service;
+mod sink;
+
+pub use self::config::SmtpConfig;
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+        sync::mpsc,
+    };
+    use vector_core::event::{BatchNotifier, BatchStatus, Event, LogEvent};
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        test_util::{
+            components::{run_and_assert_sink_compliance, SINK_TAGS},
+            next_addr,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SmtpConfig>();
+    }
+
+    /// Accepts a single connection, answering commands the way SMTP servers do, and sends the
+    /// recipients and data of the emails it receives.
+    async fn serve(addr: SocketAddr) -> mpsc::UnboundedReceiver<(Vec<String>, String)> {
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = socket.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            writer.write_all(b"220 localhost\r\n").await.unwrap();
+
+            let mut recipients = Vec::new();
+            while let Some(line) = lines.next_line().await.unwrap() {
+                let command = line.to_uppercase();
+                let reply: &[u8] = if command.starts_with("EHLO") {
+                    b"250 localhost\r\n"
+                } else if command.starts_with("RCPT TO:") {
+                    recipients.push(line[8..].trim().to_owned());
+                    b"250 OK\r\n"
+                } else if command.starts_with("DATA") {
+                    writer.write_all(b"354 Go ahead\r\n").await.unwrap();
+                    let mut data = String::new();
+                    while let Some(line) = lines.next_line().await.unwrap() {
+                        if line == "." {
+                            break;
+                        }
+                        data.push_str(&line);
+                        data.push('\n');
+                    }
+                    tx.send((std::mem::take(&mut recipients), data)).unwrap();
+                    b"250 OK\r\n"
+                } else if command.starts_with("QUIT") {
+                    writer.write_all(b"221 Bye\r\n").await.unwrap();
+                    break;
+                } else {
+                    b"250 OK\r\n"
+                };
+                writer.write_all(reply).await.unwrap();
+            }
+        });
+        rx
+    }
+
+    #[tokio::test]
+    async fn sends_batches_by_recipients() {
+        let addr = next_addr();
+        let mut rx = serve(addr).await;
+        let config: SmtpConfig = toml::from_str(&format!(
+            r#"host = "{}"
+            port = {}
+            tls.mode = "none"
+            from = "Vector <vector@example.com>"
+            to = "{{{{ team }}}}@example.com"
+            subject = "Alerts of {{{{ team }}}}"
+            body = "{{{{ host }}}}: {{{{ message }}}}"
+            batch.timeout_secs = 0.1"#,
+            addr.ip(),
+            addr.port()
+        ))
+        .unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let events: Vec<_> = ["db-1", "db-2"]
+            .into_iter()
+            .map(|host| {
+                let mut log = LogEvent::from("Disk almost full").with_batch_notifier(&batch);
+                log.insert("host", host);
+                log.insert("team", "storage");
+                Event::from(log)
+            })
+            .collect();
+        drop(batch);
+        run_and_assert_sink_compliance(sink, futures::stream::iter(events), &SINK_TAGS).await;
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let (recipients, data) = rx.recv().await.unwrap();
+        assert_eq!(recipients, vec!["<storage@example.com>"]);
+        assert!(data.contains("Subject: Alerts of storage\n"));
+        assert!(data.contains("db-1: Disk almost full\ndb-2: Disk almost full\n"));
+    }
+}
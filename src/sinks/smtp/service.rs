@@ -0,0 +1,95 @@
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use lettre::{address::Envelope, AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+use snafu::{ResultExt, Snafu};
+use tower::Service;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    stream::DriverResponse,
+};
+
+use crate::sinks::util::retries::RetryLogic;
+
+#[derive(Debug, Snafu)]
+pub enum SmtpError {
+    #[snafu(display("Sending email failed: {}", source))]
+    Send {
+        source: lettre::transport::smtp::Error,
+    },
+}
+
+/// An email, formatted as the message sent to the server.
+#[derive(Clone)]
+pub struct SmtpRequest {
+    pub envelope: Envelope,
+    pub body: Bytes,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for SmtpRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for SmtpRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata.clone()
+    }
+}
+
+pub struct SmtpResponse;
+
+impl DriverResponse for SmtpResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+}
+
+#[derive(Clone)]
+pub struct SmtpService {
+    pub transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl Service<SmtpRequest> for SmtpService {
+    type Response = SmtpResponse;
+    type Error = SmtpError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: SmtpRequest) -> Self::Future {
+        let transport = self.transport.clone();
+
+        Box::pin(async move {
+            transport
+                .send_raw(&request.envelope, &request.body)
+                .await
+                .context(SendSnafu)?;
+
+            Ok(SmtpResponse)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpRetryLogic;
+
+impl RetryLogic for SmtpRetryLogic {
+    type Error = SmtpError;
+    type Response = SmtpResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            // Permanent errors are the `5xx` replies of servers, such as unknown recipients, and
+            // client errors are those of emails themselves, so neither goes away when retried.
+            SmtpError::Send { source } => !(source.is_permanent() || source.is_client()),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use lettre::message::Mailbox;
+use tower::ServiceBuilder;
+use vector_core::{event::Event, partition::Partitioner, stream::BatcherSettings};
+
+use super::{
+    message::{build_email, EmailTemplates, RenderedEmail},
+    service::{SmtpRetryLogic, SmtpService},
+};
+use crate::{
+    emit,
+    internal_events::SinkRequestBuildError,
+    sinks::util::{ServiceBuilderExt, SinkBuilderExt, StreamSink, TowerRequestSettings},
+};
+
+/// Batches emails by their recipients, so that each batch is sent as a single email.
+struct RecipientsPartitioner;
+
+impl Partitioner for RecipientsPartitioner {
+    type Item = RenderedEmail;
+    type Key = Vec<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        item.recipients.clone()
+    }
+}
+
+pub struct SmtpSink {
+    pub service: SmtpService,
+    pub from: Mailbox,
+    pub templates: EmailTemplates,
+    pub batch_settings: BatcherSettings,
+    pub request: TowerRequestSettings,
+}
+
+#[async_trait]
+impl StreamSink<Event> for SmtpSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let sink = *self;
+        let service = ServiceBuilder::new()
+            .settings(sink.request, SmtpRetryLogic)
+            .service(sink.service);
+        let templates = sink.templates;
+        let from = sink.from;
+
+        input
+            .filter_map(move |event| futures::future::ready(templates.render(event)))
+            .batched_partitioned(RecipientsPartitioner, sink.batch_settings)
+            .filter_map(move |(recipients, emails)| {
+                let request = build_email(&from, &recipients, emails)
+                    .map_err(|error| emit!(SinkRequestBuildError { error }))
+                    .ok();
+                futures::future::ready(request)
+            })
+            .into_driver(service)
+            .protocol("smtp")
+            .run()
+            .await
+    }
+}
@@ -0,0 +1,190 @@
+//! Requests to the Snowpipe Streaming REST API, authenticated with scoped tokens.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use http::{header, Method, Request, StatusCode};
+use hyper::Body;
+use serde::{de::DeserializeOwned, Deserialize};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Mutex;
+
+use super::auth::KeyPairAuth;
+use crate::http::{HttpClient, HttpError};
+
+/// How long scoped tokens are used for before being exchanged again, which is less than the hour
+/// they're valid for.
+const SESSION_TTL: Duration = Duration::from_secs(50 * 60);
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(super)))]
+pub enum SnowflakeError {
+    #[snafu(display("Failed to build the request: {}", source))]
+    BuildRequest { source: http::Error },
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("Failed to read the response: {}", source))]
+    ReadResponse { source: hyper::Error },
+    #[snafu(display("Failed to sign the token: {}", source))]
+    Sign { source: openssl::error::ErrorStack },
+    #[snafu(display("Unexpected status {}: {}", status, body))]
+    UnexpectedStatus { status: StatusCode, body: String },
+    #[snafu(display("Invalid response: {}", source))]
+    InvalidResponse { source: serde_json::Error },
+    #[snafu(display("Rows weren't committed within {:?}", timeout))]
+    CommitTimeout { timeout: Duration },
+}
+
+impl SnowflakeError {
+    pub fn is_retriable(&self) -> bool {
+        match self {
+            Self::Request { .. } | Self::ReadResponse { .. } | Self::CommitTimeout { .. } => true,
+            Self::UnexpectedStatus { status, .. } => {
+                status.is_server_error()
+                    || *status == StatusCode::TOO_MANY_REQUESTS
+                    || *status == StatusCode::REQUEST_TIMEOUT
+                    || *status == StatusCode::UNAUTHORIZED
+            }
+            Self::BuildRequest { .. } | Self::Sign { .. } | Self::InvalidResponse { .. } => false,
+        }
+    }
+}
+
+/// The ingest host of the account, and the token scoped to it.
+struct Session {
+    host: String,
+    token: String,
+    created_at: Instant,
+}
+
+pub struct SnowflakeClient {
+    http: HttpClient,
+    auth: KeyPairAuth,
+    /// The URL of the account, such as `https://myorg-myaccount.snowflakecomputing.com`.
+    account_url: String,
+    session: Mutex<Option<Session>>,
+}
+
+impl SnowflakeClient {
+    pub fn new(http: HttpClient, auth: KeyPairAuth, account_url: String) -> Self {
+        Self {
+            http,
+            auth,
+            account_url,
+            session: Mutex::new(None),
+        }
+    }
+
+    async fn send(&self, request: Request<Body>) -> Result<Bytes, SnowflakeError> {
+        let response = self.http.send(request).await.context(RequestSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadResponseSnafu)?;
+        if !status.is_success() {
+            return Err(SnowflakeError::UnexpectedStatus {
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            });
+        }
+        Ok(body)
+    }
+
+    /// Returns the ingest host and scoped token, exchanging a new token for them if they haven't
+    /// been, or have expired.
+    async fn session(&self) -> Result<(String, String), SnowflakeError> {
+        let mut session = self.session.lock().await;
+        if let Some(session) = session.as_ref() {
+            if session.created_at.elapsed() < SESSION_TTL {
+                return Ok((session.host.clone(), session.token.clone()));
+            }
+        }
+
+        let jwt = self.auth.token().context(SignSnafu)?;
+        let request = Request::get(format!("{}/v2/streaming/hostname", self.account_url))
+            .header(header::AUTHORIZATION, format!("Bearer {}", jwt))
+            .header("X-Snowflake-Authorization-Token-Type", "KEYPAIR_JWT")
+            .body(Body::empty())
+            .context(BuildRequestSnafu)?;
+        let host = self.send(request).await?;
+        let host = String::from_utf8_lossy(&host).trim().to_owned();
+
+        let form = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer")
+            .append_pair("scope", &host)
+            .append_pair("assertion", &jwt)
+            .finish();
+        let request = Request::post(format!("{}/oauth/token", self.account_url))
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(form))
+            .context(BuildRequestSnafu)?;
+        let token = self.send(request).await?;
+        let token = String::from_utf8_lossy(&token).trim().to_owned();
+
+        *session = Some(Session {
+            host: host.clone(),
+            token: token.clone(),
+            created_at: Instant::now(),
+        });
+        Ok((host, token))
+    }
+
+    /// Sends a request to `path_and_query` of the ingest host, parsing its JSON response.
+    pub async fn call<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path_and_query: &str,
+        content_type: &str,
+        body: Body,
+    ) -> Result<T, SnowflakeError> {
+        let (host, token) = self.session().await?;
+        let request = Request::builder()
+            .method(method)
+            .uri(format!("https://{}{}", host, path_and_query))
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .header(header::ACCEPT, "application/json")
+            .header(header::CONTENT_TYPE, content_type)
+            .body(body)
+            .context(BuildRequestSnafu)?;
+
+        let body = match self.send(request).await {
+            Err(SnowflakeError::UnexpectedStatus { status, body })
+                if status == StatusCode::UNAUTHORIZED =>
+            {
+                // The token was revoked or has expired early, so it's exchanged again on retry.
+                *self.session.lock().await = None;
+                return Err(SnowflakeError::UnexpectedStatus { status, body });
+            }
+            result => result?,
+        };
+        serde_json::from_slice(&body).context(InvalidResponseSnafu)
+    }
+}
+
+/// The status of a channel, with the offset token of the last rows committed to the table.
+#[derive(Debug, Default, Deserialize)]
+pub struct ChannelStatus {
+    #[serde(default, alias = "committed_offset_token")]
+    pub last_committed_offset_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OpenChannelResponse {
+    pub next_continuation_token: String,
+    #[serde(default)]
+    pub channel_status: ChannelStatus,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AppendRowsResponse {
+    pub next_continuation_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkChannelStatusResponse {
+    #[serde(default)]
+    pub channel_statuses: HashMap<String, ChannelStatus>,
+}
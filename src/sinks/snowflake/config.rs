@@ -0,0 +1,247 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use futures::FutureExt;
+use indoc::indoc;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use super::{
+    auth::KeyPairAuth,
+    client::SnowflakeClient,
+    service::{Channel, ChannelPath},
+    sink::SnowflakeSink,
+};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::HttpClient,
+    sinks::{
+        util::{BatchConfig, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// The maximum size of the rows of a request to append rows to a channel.
+const MAX_APPEND_BYTES: usize = 16_000_000;
+
+/// Configuration for the `snowflake` sink.
+#[configurable_component(sink("snowflake"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnowflakeConfig {
+    /// The identifier of the account.
+    #[configurable(metadata(docs::examples = "myorg-myaccount"))]
+    pub account: String,
+
+    /// The URL of the account.
+    ///
+    /// By default, this is `https://<account>.snowflakecomputing.com`.
+    #[configurable(metadata(docs::examples = "https://myorg-myaccount.snowflakecomputing.com"))]
+    pub endpoint: Option<String>,
+
+    /// The user to authenticate as, with key pair authentication.
+    #[configurable(metadata(docs::examples = "VECTOR"))]
+    pub user: String,
+
+    /// The PEM-encoded private key of the user.
+    #[configurable(metadata(docs::examples = "${SNOWFLAKE_PRIVATE_KEY}"))]
+    pub private_key: SensitiveString,
+
+    /// The passphrase the private key is encrypted with, if it is.
+    #[configurable(metadata(docs::examples = "${SNOWFLAKE_PRIVATE_KEY_PASSPHRASE}"))]
+    pub private_key_passphrase: Option<SensitiveString>,
+
+    /// The database of the table.
+    #[configurable(metadata(docs::examples = "LOGS"))]
+    pub database: String,
+
+    /// The schema of the table.
+    #[configurable(metadata(docs::examples = "PUBLIC"))]
+    pub schema: String,
+
+    /// The table to write events to.
+    ///
+    /// The table must already exist.
+    #[configurable(metadata(docs::examples = "EVENTS"))]
+    pub table: String,
+
+    /// The pipe that rows are streamed through.
+    ///
+    /// By default, this is the default pipe of the table, `<table>-STREAMING`, which loads the
+    /// fields of rows into the columns of the same names.
+    #[configurable(metadata(docs::examples = "EVENTS_PIPE"))]
+    pub pipe: Option<String>,
+
+    /// The name of the channel of the pipe that rows are appended to.
+    ///
+    /// Channels can only be written to by one client at a time, so each Vector instance writing to
+    /// the same pipe must use a channel of its own.
+    #[serde(default = "default_channel")]
+    pub channel: String,
+
+    /// A map of the columns of rows to the fields of events whose values they hold.
+    ///
+    /// Columns of fields missing from events are null. By default, the top-level fields of events
+    /// are sent as the columns of the same names.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "The field of the column."))]
+    pub columns: BTreeMap<String, String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<SnowflakeDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_channel() -> String {
+    "vector".to_owned()
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SnowflakeDefaultBatchSettings;
+
+impl SinkBatchSettings for SnowflakeDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(10_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+impl GenerateConfig for SnowflakeConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            account = "myorg-myaccount"
+            user = "VECTOR"
+            private_key = "${SNOWFLAKE_PRIVATE_KEY}"
+            database = "LOGS"
+            schema = "PUBLIC"
+            table = "EVENTS"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for SnowflakeConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let auth = KeyPairAuth::new(
+            &self.account,
+            &self.user,
+            self.private_key.inner(),
+            self.private_key_passphrase
+                .as_ref()
+                .map(SensitiveString::inner),
+        )?;
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = Arc::new(SnowflakeClient::new(
+            HttpClient::new(tls, &cx.proxy)?,
+            auth,
+            self.account_url(),
+        ));
+
+        let channel = Arc::new(Channel::new(
+            client,
+            ChannelPath {
+                database: self.database.clone(),
+                schema: self.schema.clone(),
+                pipe: self
+                    .pipe
+                    .clone()
+                    .unwrap_or_else(|| format!("{}-STREAMING", self.table)),
+                channel: self.channel.clone(),
+            },
+        ));
+
+        let healthcheck = {
+            let channel = Arc::clone(&channel);
+            async move {
+                channel.open().await?;
+                Ok(())
+            }
+            .boxed()
+        };
+
+        let sink = SnowflakeSink {
+            channel,
+            columns: self.columns.clone(),
+            transformer: self.encoding.clone(),
+            batch_settings: self
+                .batch
+                .validate()?
+                .limit_max_bytes(MAX_APPEND_BYTES)?
+                .into_batcher_settings()?,
+            request: self.request.unwrap_with(&TowerRequestConfig::default()),
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl SnowflakeConfig {
+    fn account_url(&self) -> String {
+        match &self.endpoint {
+            Some(endpoint) => endpoint.trim_end_matches('/').to_owned(),
+            None => format!("https://{}.snowflakecomputing.com", self.account),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SnowflakeConfig>();
+    }
+
+    #[test]
+    fn parses_columns() {
+        let config: SnowflakeConfig = toml::from_str(indoc! {r#"
+            account = "myorg-myaccount"
+            user = "VECTOR"
+            private_key = "key"
+            database = "LOGS"
+            schema = "PUBLIC"
+            table = "EVENTS"
+            columns.MESSAGE = "message"
+        "#})
+        .unwrap();
+
+        assert_eq!(config.channel, "vector");
+        assert_eq!(config.columns["MESSAGE"], "message");
+        assert_eq!(
+            config.account_url(),
+            "https://myorg-myaccount.snowflakecomputing.com"
+        );
+    }
+}
@@ -0,0 +1,15 @@
+//! The `snowflake` sink.
+//!
+//! Batches of events are streamed as rows into tables of [Snowflake][snowflake], through a channel
+//! of a pipe of the [Snowpipe Streaming][snowpipe_streaming] REST API.
+//!
+//! [snowflake]: https://www.snowflake.com/
+//! [snowpipe_streaming]: https://docs.snowflake.com/en/user-guide/data-load-snowpipe-streaming-overview
+
+mod auth;
+mod client;
+mod config;
+mod service;
+mod sink;
+
+pub use self::config::SnowflakeConfig;
@@ -0,0 +1,297 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use http::Method;
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde_json::json;
+use tokio::sync::Mutex;
+use tower::Service;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    stream::DriverResponse,
+};
+
+use super::client::{
+    AppendRowsResponse, BulkChannelStatusResponse, ChannelStatus, OpenChannelResponse,
+    SnowflakeClient, SnowflakeError,
+};
+use crate::sinks::util::retries::RetryLogic;
+
+/// How often the status of the channel is polled, while waiting for rows to be committed.
+const COMMIT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long to wait for rows to be committed, before retrying the request.
+const COMMIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The pipe and channel that rows are appended to.
+pub struct ChannelPath {
+    pub database: String,
+    pub schema: String,
+    pub pipe: String,
+    pub channel: String,
+}
+
+impl ChannelPath {
+    fn pipe_path(&self) -> String {
+        format!(
+            "/v2/streaming/databases/{}/schemas/{}/pipes/{}",
+            encode(&self.database),
+            encode(&self.schema),
+            encode(&self.pipe)
+        )
+    }
+
+    fn channel_path(&self) -> String {
+        format!("{}/channels/{}", self.pipe_path(), encode(&self.channel))
+    }
+
+    fn rows_path(&self) -> String {
+        format!(
+            "/v2/streaming/data/databases/{}/schemas/{}/pipes/{}/channels/{}/rows",
+            encode(&self.database),
+            encode(&self.schema),
+            encode(&self.pipe),
+            encode(&self.channel)
+        )
+    }
+}
+
+fn encode(segment: &str) -> String {
+    utf8_percent_encode(segment, NON_ALPHANUMERIC).to_string()
+}
+
+/// Parses offset tokens, which are the sequence numbers of batches, ignoring those written by
+/// other clients.
+fn parse_offset(status: &ChannelStatus) -> u64 {
+    status
+        .last_committed_offset_token
+        .as_deref()
+        .and_then(|token| token.parse().ok())
+        .unwrap_or(0)
+}
+
+/// The state of the open channel.
+#[derive(Default)]
+struct ChannelState {
+    /// The token the next rows are appended with, unset until the channel is (re)opened.
+    continuation_token: Option<String>,
+    /// The offset token of the last rows committed to the table.
+    committed_offset: u64,
+    /// The offset token of the next batch.
+    next_offset: u64,
+}
+
+/// A channel of the pipe, which batches are appended to one at a time.
+///
+/// Each batch is appended with its sequence number as offset token, and is only acknowledged
+/// once it's committed to the table. When a request fails, the channel is reopened, and its retry
+/// is skipped if the channel's last committed offset token shows that it was committed after all,
+/// so that batches are written exactly once.
+pub struct Channel {
+    pub client: Arc<SnowflakeClient>,
+    pub path: ChannelPath,
+    state: Mutex<ChannelState>,
+}
+
+impl Channel {
+    pub fn new(client: Arc<SnowflakeClient>, path: ChannelPath) -> Self {
+        Self {
+            client,
+            path,
+            state: Mutex::new(ChannelState::default()),
+        }
+    }
+
+    /// Opens the channel, invalidating the previous opening of it by this or other clients.
+    pub async fn open(&self) -> Result<OpenChannelResponse, SnowflakeError> {
+        self.client
+            .call(
+                Method::PUT,
+                &self.path.channel_path(),
+                "application/json",
+                Body::from(json!({}).to_string()),
+            )
+            .await
+    }
+
+    async fn committed_offset(&self) -> Result<u64, SnowflakeError> {
+        let body = json!({ "channel_names": [self.path.channel] });
+        let response: BulkChannelStatusResponse = self
+            .client
+            .call(
+                Method::POST,
+                &format!("{}:bulk-channel-status", self.path.pipe_path()),
+                "application/json",
+                Body::from(body.to_string()),
+            )
+            .await?;
+        Ok(response
+            .channel_statuses
+            .get(&self.path.channel)
+            .map(parse_offset)
+            .unwrap_or(0))
+    }
+
+    async fn write(&self, request: &SnowflakeRequest) -> Result<(), SnowflakeError> {
+        let mut state = self.state.lock().await;
+
+        let continuation_token = match state.continuation_token.take() {
+            Some(token) => token,
+            None => {
+                let response = self.open().await?;
+                state.committed_offset = parse_offset(&response.channel_status);
+                state.next_offset = state.next_offset.max(state.committed_offset + 1);
+                response.next_continuation_token
+            }
+        };
+
+        // Offsets are assigned once, on the first attempt, so that retries are recognized.
+        let mut offset = request.offset.load(Ordering::Acquire);
+        if offset == 0 {
+            offset = state.next_offset;
+            state.next_offset += 1;
+            request.offset.store(offset, Ordering::Release);
+        } else if offset <= state.committed_offset {
+            state.continuation_token = Some(continuation_token);
+            return Ok(());
+        }
+
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("continuationToken", &continuation_token)
+            .append_pair("offsetToken", &offset.to_string())
+            .finish();
+        let response: AppendRowsResponse = self
+            .client
+            .call(
+                Method::POST,
+                &format!("{}?{}", self.path.rows_path(), query),
+                "application/x-ndjson",
+                Body::from(request.rows.clone()),
+            )
+            .await?;
+
+        let started = Instant::now();
+        while state.committed_offset < offset {
+            if started.elapsed() > COMMIT_TIMEOUT {
+                return Err(SnowflakeError::CommitTimeout {
+                    timeout: COMMIT_TIMEOUT,
+                });
+            }
+            tokio::time::sleep(COMMIT_POLL_INTERVAL).await;
+            state.committed_offset = self.committed_offset().await?;
+        }
+
+        state.continuation_token = Some(response.next_continuation_token);
+        Ok(())
+    }
+}
+
+/// A batch of rows, encoded as JSON lines.
+#[derive(Clone)]
+pub struct SnowflakeRequest {
+    pub rows: Bytes,
+    /// The offset token of the batch, shared by its retries, which is zero until it's assigned.
+    pub offset: Arc<AtomicU64>,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for SnowflakeRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for SnowflakeRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata.clone()
+    }
+}
+
+pub struct SnowflakeResponse;
+
+impl DriverResponse for SnowflakeResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+}
+
+#[derive(Clone)]
+pub struct SnowflakeService {
+    pub channel: Arc<Channel>,
+}
+
+impl Service<SnowflakeRequest> for SnowflakeService {
+    type Response = SnowflakeResponse;
+    type Error = SnowflakeError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: SnowflakeRequest) -> Self::Future {
+        let channel = Arc::clone(&self.channel);
+        Box::pin(async move {
+            channel.write(&request).await?;
+            Ok(SnowflakeResponse)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SnowflakeRetryLogic;
+
+impl RetryLogic for SnowflakeRetryLogic {
+    type Error = SnowflakeError;
+    type Response = SnowflakeResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_paths() {
+        let path = ChannelPath {
+            database: "LOGS".to_owned(),
+            schema: "PUBLIC".to_owned(),
+            pipe: "EVENTS-STREAMING".to_owned(),
+            channel: "vector".to_owned(),
+        };
+
+        assert_eq!(
+            path.channel_path(),
+            "/v2/streaming/databases/LOGS/schemas/PUBLIC/pipes/EVENTS%2DSTREAMING/channels/vector"
+        );
+        assert_eq!(
+            path.rows_path(),
+            "/v2/streaming/data/databases/LOGS/schemas/PUBLIC/pipes/EVENTS%2DSTREAMING/channels/vector/rows"
+        );
+    }
+
+    #[test]
+    fn parses_offsets() {
+        let status = |token: Option<&str>| ChannelStatus {
+            last_committed_offset_token: token.map(ToOwned::to_owned),
+        };
+
+        assert_eq!(parse_offset(&status(Some("42"))), 42);
+        assert_eq!(parse_offset(&status(Some("other"))), 0);
+        assert_eq!(parse_offset(&status(None)), 0);
+    }
+}
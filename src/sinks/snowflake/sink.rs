@@ -0,0 +1,158 @@
+use std::{collections::BTreeMap, num::NonZeroUsize, sync::Arc};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{stream::BoxStream, StreamExt};
+use serde_json::{Map, Value as JsonValue};
+use tower::ServiceBuilder;
+use vector_core::{
+    event::{EventFinalizers, Finalizable, LogEvent},
+    stream::BatcherSettings,
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use super::service::{Channel, SnowflakeRequest, SnowflakeRetryLogic, SnowflakeService};
+use crate::{
+    codecs::Transformer,
+    event::Event,
+    sinks::util::{
+        metadata::RequestMetadataBuilder, ServiceBuilderExt, SinkBuilderExt, StreamSink,
+        TowerRequestSettings,
+    },
+};
+
+/// An event, encoded as a row in a line of JSON, with the sizes it had before being transformed.
+///
+/// Rows are batched by their encoded size, which is what the size of requests is limited by.
+struct EncodedRow {
+    line: Vec<u8>,
+    finalizers: EventFinalizers,
+    byte_size: usize,
+    json_size: usize,
+}
+
+impl ByteSizeOf for EncodedRow {
+    fn size_of(&self) -> usize {
+        self.line.len()
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
+/// Encodes `log` as a row, whose columns hold the values of the fields they're mapped to, or
+/// the values of its top-level fields if no columns are mapped.
+fn encode_row(log: &LogEvent, columns: &BTreeMap<String, String>) -> Vec<u8> {
+    let mut line = if columns.is_empty() {
+        serde_json::to_vec(log)
+    } else {
+        let row: Map<String, JsonValue> = columns
+            .iter()
+            .map(|(column, field)| {
+                let value = log
+                    .get(field.as_str())
+                    .and_then(|value| serde_json::to_value(value).ok())
+                    .unwrap_or(JsonValue::Null);
+                (column.clone(), value)
+            })
+            .collect();
+        serde_json::to_vec(&row)
+    }
+    .expect("rows should serialize");
+    line.push(b'\n');
+    line
+}
+
+pub struct SnowflakeSink {
+    pub channel: Arc<Channel>,
+    pub columns: BTreeMap<String, String>,
+    pub transformer: Transformer,
+    pub batch_settings: BatcherSettings,
+    pub request: TowerRequestSettings,
+}
+
+#[async_trait]
+impl StreamSink<Event> for SnowflakeSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let service = ServiceBuilder::new()
+            .settings(self.request, SnowflakeRetryLogic)
+            .service(SnowflakeService {
+                channel: self.channel,
+            });
+
+        let transformer = self.transformer;
+        let columns = self.columns;
+        input
+            .map(|mut event| {
+                let byte_size = event.size_of();
+                let json_size = event.estimated_json_encoded_size_of();
+                transformer.transform(&mut event);
+                EncodedRow {
+                    line: encode_row(event.as_log(), &columns),
+                    finalizers: event.take_finalizers(),
+                    byte_size,
+                    json_size,
+                }
+            })
+            .batched(self.batch_settings.into_byte_size_config())
+            .map(|rows| {
+                let count = rows.len();
+                let mut byte_size = 0;
+                let mut json_size = 0;
+                let mut finalizers = EventFinalizers::default();
+                let mut body = Vec::new();
+                for row in rows {
+                    byte_size += row.byte_size;
+                    json_size += row.json_size;
+                    finalizers.merge(row.finalizers);
+                    body.extend_from_slice(&row.line);
+                }
+                let metadata = RequestMetadataBuilder::new(count, byte_size, json_size)
+                    .with_request_size(
+                        NonZeroUsize::new(body.len()).expect("rows should not be empty"),
+                    );
+
+                SnowflakeRequest {
+                    rows: Bytes::from(body),
+                    offset: Default::default(),
+                    finalizers,
+                    metadata,
+                }
+            })
+            .into_driver(service)
+            .protocol("https")
+            .run()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_mapped_columns() {
+        let mut log = LogEvent::from("hello");
+        log.insert("host", "web-1");
+        log.insert("http.status", 200);
+        let columns = BTreeMap::from([
+            ("HOST".to_owned(), "host".to_owned()),
+            ("MESSAGE".to_owned(), "message".to_owned()),
+            ("STATUS".to_owned(), "http.status".to_owned()),
+            ("USER".to_owned(), "user".to_owned()),
+        ]);
+
+        let row: JsonValue = serde_json::from_slice(&encode_row(&log, &columns)).unwrap();
+
+        assert_eq!(
+            row,
+            serde_json::json!({
+                "HOST": "web-1",
+                "MESSAGE": "hello",
+                "STATUS": 200,
+                "USER": null,
+            })
+        );
+    }
+}
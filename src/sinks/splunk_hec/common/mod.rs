@@ -1,6 +1,7 @@
 pub mod acknowledgements;
 pub mod request;
 pub mod response;
+pub mod routing;
 pub mod service;
 pub mod util;
 
@@ -41,6 +41,6 @@ impl Finalizable for HecRequest {
 
 impl MetaDescriptive for HecRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
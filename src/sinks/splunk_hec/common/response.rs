@@ -1,10 +1,7 @@
-use vector_core::internal_event::CountByteSize;
 use vector_core::{event::EventStatus, stream::DriverResponse};
 
 pub struct HecResponse {
     pub event_status: EventStatus,
-    pub events_count: usize,
-    pub events_byte_size: usize,
 }
 
 impl AsRef<EventStatus> for HecResponse {
@@ -17,8 +14,4 @@ impl DriverResponse for HecResponse {
     fn event_status(&self) -> EventStatus {
         self.event_status
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(self.events_count, self.events_byte_size)
-    }
 }
@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use futures_util::{stream::BoxStream, StreamExt};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use vector_core::{
+    event::EventArray,
+    sink::{StreamSink, VectorSink},
+};
+
+/// Routes metrics to a sink, and the other events to another one, so that a single sink sends
+/// each of them to their endpoint.
+pub struct HecRoutingSink {
+    pub events: VectorSink,
+    pub metrics: VectorSink,
+}
+
+#[async_trait]
+impl StreamSink<EventArray> for HecRoutingSink {
+    async fn run(self: Box<Self>, mut input: BoxStream<'_, EventArray>) -> Result<(), ()> {
+        let Self { events, metrics } = *self;
+        let (events_tx, events_rx) = mpsc::channel(1);
+        let (metrics_tx, metrics_rx) = mpsc::channel(1);
+
+        // The streams of both sinks end when the senders are dropped, once the input ends or
+        // either of the sinks stops.
+        let route = async move {
+            while let Some(events) = input.next().await {
+                let tx = match events {
+                    EventArray::Metrics(_) => &metrics_tx,
+                    _ => &events_tx,
+                };
+                if tx.send(events).await.is_err() {
+                    break;
+                }
+            }
+        };
+
+        let ((), events, metrics) = tokio::join!(
+            route,
+            events.run(ReceiverStream::new(events_rx)),
+            metrics.run(ReceiverStream::new(metrics_rx)),
+        );
+        events.and(metrics)
+    }
+}
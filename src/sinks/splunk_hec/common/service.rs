@@ -13,7 +13,6 @@ use tokio::sync::{mpsc, oneshot, OwnedSemaphorePermit, Semaphore};
 use tokio_util::sync::PollSemaphore;
 use tower::Service;
 use uuid::Uuid;
-use vector_common::request_metadata::MetaDescriptive;
 use vector_core::event::EventStatus;
 
 use super::{
@@ -113,8 +112,6 @@ where
         let ack_finalizer_tx = self.ack_finalizer_tx.clone();
         let ack_slot = self.current_ack_slot.take();
 
-        let events_count = req.get_metadata().event_count();
-        let events_byte_size = req.get_metadata().events_byte_size();
         let response = self.inner.call(req);
 
         Box::pin(async move {
@@ -158,11 +155,7 @@ where
                 EventStatus::Rejected
             };
 
-            Ok(HecResponse {
-                event_status,
-                events_count,
-                events_byte_size,
-            })
+            Ok(HecResponse { event_status })
         })
     }
 }
@@ -13,11 +13,15 @@ use crate::{
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     http::HttpClient,
     sinks::{
-        splunk_hec::common::{
-            acknowledgements::HecClientAcknowledgementsConfig,
-            build_healthcheck, build_http_batch_service, create_client, host_key,
-            service::{HecService, HttpRequestBuilder},
-            timestamp_key, EndpointTarget, SplunkHecDefaultBatchSettings,
+        splunk_hec::{
+            common::{
+                acknowledgements::HecClientAcknowledgementsConfig,
+                build_healthcheck, build_http_batch_service, create_client, host_key,
+                routing::HecRoutingSink,
+                service::{HecService, HttpRequestBuilder},
+                timestamp_key, EndpointTarget, SplunkHecDefaultBatchSettings,
+            },
+            metrics::config::HecMetricsSinkConfig,
         },
         util::{
             http::HttpRetryLogic, BatchConfig, Compression, ServiceBuilderExt, TowerRequestConfig,
@@ -119,6 +123,40 @@ pub struct HecLogsSinkConfig {
     #[configurable(derived)]
     #[serde(default = "default_endpoint_target")]
     pub endpoint_target: EndpointTarget,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub metrics: HecLogsMetricsConfig,
+}
+
+/// Sending of metrics.
+///
+/// When enabled, the sink also accepts metrics, which are sent to the event endpoint in the
+/// [metric JSON format][metric_format_docs], whatever `endpoint_target` is, with the same
+/// token, index, source, sourcetype, and host as logs.
+///
+/// [metric_format_docs]: https://docs.splunk.com/Documentation/Splunk/8.0.0/Metrics/GetMetricsInOther#The_metrics_data_format
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(default)]
+pub struct HecLogsMetricsConfig {
+    /// Whether or not metrics are accepted by the sink.
+    pub enabled: bool,
+
+    /// Sets the default namespace for any metrics sent.
+    ///
+    /// This namespace is only used if a metric has no existing namespace. When a namespace is
+    /// present, it is used as a prefix to the metric name, and separated with a period (`.`).
+    pub default_namespace: Option<String>,
+
+    /// Whether or not to send metrics in the [multiple-metric JSON format][multi_metric_docs].
+    ///
+    /// Metrics sharing their timestamp, dimensions, and metadata are then sent as a single event,
+    /// with a `metric_name:<name>` field holding the value of each. This requires Splunk 8.0 or
+    /// later.
+    ///
+    /// [multi_metric_docs]: https://docs.splunk.com/Documentation/Splunk/8.0.0/Metrics/GetMetricsInOther#The_multiple-metric_JSON_format
+    pub multi_metric: bool,
 }
 
 const fn default_endpoint_target() -> EndpointTarget {
@@ -145,6 +183,7 @@ impl GenerateConfig for HecLogsSinkConfig {
             timestamp_key: timestamp_key(),
             auto_extract_timestamp: None,
             endpoint_target: EndpointTarget::Event,
+            metrics: Default::default(),
         })
         .unwrap()
     }
@@ -170,7 +209,11 @@ impl SinkConfig for HecLogsSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().input_type() & DataType::Log)
+        let mut data_type = self.encoding.config().input_type() & DataType::Log;
+        if self.metrics.enabled {
+            data_type |= DataType::Metric;
+        }
+        Input::new(data_type)
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -184,6 +227,36 @@ impl HecLogsSinkConfig {
         client: HttpClient,
         cx: SinkContext,
     ) -> crate::Result<VectorSink> {
+        if self.metrics.enabled {
+            let metrics = HecMetricsSinkConfig {
+                default_namespace: self.metrics.default_namespace.clone(),
+                default_token: self.default_token.clone(),
+                endpoint: self.endpoint.clone(),
+                host_key: self.host_key.clone(),
+                index: self.index.clone(),
+                sourcetype: self.sourcetype.clone(),
+                source: self.source.clone(),
+                multi_metric: self.metrics.multi_metric,
+                compression: self.compression,
+                batch: self.batch,
+                request: self.request,
+                tls: self.tls.clone(),
+                acknowledgements: self.acknowledgements.clone(),
+            }
+            .build_processor(client.clone(), cx.clone())?;
+
+            let logs = HecLogsSinkConfig {
+                metrics: HecLogsMetricsConfig::default(),
+                ..self.clone()
+            }
+            .build_processor(client, cx)?;
+
+            return Ok(VectorSink::Stream(Box::new(HecRoutingSink {
+                events: logs,
+                metrics,
+            })));
+        }
+
         let ack_client = if self.acknowledgements.indexer_acknowledgements_enabled {
             Some(client.clone())
         } else {
@@ -201,6 +274,7 @@ impl HecLogsSinkConfig {
         let request_builder = HecLogsRequestBuilder {
             encoder,
             compression: self.compression,
+            endpoint: self.endpoint.as_str().into(),
         };
 
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
@@ -19,6 +19,7 @@ use crate::sinks::{
 pub struct HecLogsRequestBuilder {
     pub encoder: HecLogsEncoder,
     pub compression: Compression,
+    pub endpoint: Arc<str>,
 }
 
 #[derive(Debug, Clone)]
@@ -85,7 +86,7 @@ impl RequestBuilder<(Option<Partitioned>, Vec<HecProcessedEvent>)> for HecLogsRe
             sourcetype: hec_metadata.sourcetype,
             index: hec_metadata.index,
             host: hec_metadata.host,
-            metadata,
+            metadata: metadata.with_endpoint(Arc::clone(&self.endpoint)),
         }
     }
 }
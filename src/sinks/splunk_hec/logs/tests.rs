@@ -6,7 +6,7 @@ use futures_util::StreamExt;
 use serde::Deserialize;
 use vector_core::{
     config::log_schema,
-    event::{Event, LogEvent, Value},
+    event::{Event, LogEvent, Metric, MetricKind, MetricValue, Value},
 };
 
 use super::sink::HecProcessedEvent;
@@ -206,6 +206,7 @@ async fn splunk_passthrough_token() {
         timestamp_key: log_schema().timestamp_key().into(),
         auto_extract_timestamp: None,
         endpoint_target: EndpointTarget::Event,
+        metrics: Default::default(),
     };
     let cx = SinkContext::new_test();
 
@@ -268,3 +269,51 @@ fn splunk_encode_log_event_json_timestamps() {
     let hec_data = get_hec_data_for_timestamp_test(None, &timestamp_key());
     assert_eq!(hec_data.time, None);
 }
+
+#[tokio::test]
+async fn splunk_routes_metrics_to_event_endpoint() {
+    let addr = next_addr();
+    let config = toml::from_str::<HecLogsSinkConfig>(&format!(
+        r#"
+            endpoint = "http://{}"
+            default_token = "token"
+            encoding.codec = "text"
+            compression = "none"
+            endpoint_target = "raw"
+            metrics.enabled = true
+        "#,
+        addr
+    ))
+    .unwrap();
+    let cx = SinkContext::new_test();
+
+    let (sink, _) = config.build(cx).await.unwrap();
+
+    let (rx, _trigger, server) = build_test_server(addr);
+    tokio::spawn(server);
+
+    let metric = Metric::new(
+        "requests",
+        MetricKind::Absolute,
+        MetricValue::Counter { value: 42.0 },
+    );
+    let events = vec![Event::Log(LogEvent::from("hello")), Event::from(metric)];
+
+    sink.run_events(events).await.unwrap();
+
+    let mut requests = rx
+        .take(2)
+        .map(|(parts, body)| (parts.uri.path().to_string(), body))
+        .collect::<Vec<_>>()
+        .await;
+    requests.sort_by(|a, b| a.0.cmp(&b.0));
+
+    assert_eq!(requests[0].0, "/services/collector/event");
+    let metric = serde_json::from_slice::<serde_json::Value>(&requests[0].1).unwrap();
+    assert_eq!(metric["event"], "metric");
+    assert_eq!(metric["fields"]["metric_name"], "requests");
+    assert_eq!(metric["fields"]["_value"], 42.0);
+
+    assert_eq!(requests[1].0, "/services/collector/raw");
+    assert_eq!(&requests[1].1[..], b"hello");
+}
@@ -6,7 +6,9 @@ use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 use vector_core::sink::VectorSink;
 
-use super::{request_builder::HecMetricsRequestBuilder, sink::HecMetricsSink};
+use super::{
+    encoder::HecMetricsEncoder, request_builder::HecMetricsRequestBuilder, sink::HecMetricsSink,
+};
 use crate::{
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     http::HttpClient,
@@ -71,6 +73,16 @@ pub struct HecMetricsSinkConfig {
     /// If unset, the Splunk collector will set it.
     pub source: Option<Template>,
 
+    /// Whether or not to send metrics in the [multiple-metric JSON format][multi_metric_docs].
+    ///
+    /// Metrics sharing their timestamp, dimensions, and metadata are then sent as a single event,
+    /// with a `metric_name:<name>` field holding the value of each. This requires Splunk 8.0 or
+    /// later.
+    ///
+    /// [multi_metric_docs]: https://docs.splunk.com/Documentation/Splunk/8.0.0/Metrics/GetMetricsInOther#The_multiple-metric_JSON_format
+    #[serde(default)]
+    pub multi_metric: bool,
+
     #[configurable(derived)]
     #[serde(default)]
     pub compression: Compression,
@@ -101,6 +113,7 @@ impl GenerateConfig for HecMetricsSinkConfig {
             index: None,
             sourcetype: None,
             source: None,
+            multi_metric: false,
             compression: Compression::default(),
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
@@ -148,6 +161,10 @@ impl HecMetricsSinkConfig {
 
         let request_builder = HecMetricsRequestBuilder {
             compression: self.compression,
+            encoder: HecMetricsEncoder {
+                multi_metric: self.multi_metric,
+            },
+            endpoint: self.endpoint.as_str().into(),
         };
 
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
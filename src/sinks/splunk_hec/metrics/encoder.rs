@@ -1,5 +1,6 @@
-use std::{collections::BTreeMap, iter};
+use std::{borrow::Cow, collections::BTreeMap};
 
+use indexmap::IndexMap;
 use serde::Serialize;
 
 use super::sink::HecProcessedEvent;
@@ -12,7 +13,7 @@ pub enum HecFieldValue<'a> {
     Str(&'a str),
 }
 
-pub type HecFieldMap<'a> = BTreeMap<&'a str, HecFieldValue<'a>>;
+pub type HecFieldMap<'a> = BTreeMap<Cow<'a, str>, HecFieldValue<'a>>;
 
 #[derive(Serialize, Debug)]
 struct HecData<'a> {
@@ -43,12 +44,18 @@ impl<'a> HecData<'a> {
     }
 }
 
-pub struct HecMetricsEncoder;
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HecMetricsEncoder {
+    /// Whether metrics sharing their timestamp, dimensions and metadata are encoded as a single
+    /// event, in the multiple-metric JSON format.
+    pub multi_metric: bool,
+}
 
 impl HecMetricsEncoder {
-    pub fn encode_event(processed_event: HecProcessedEvent) -> Option<Vec<u8>> {
-        let metadata = processed_event.metadata;
-        let metric = processed_event.event;
+    /// Builds the event of `processed_event`, with its dimensions but without its value.
+    fn hec_data(processed_event: &HecProcessedEvent) -> HecData<'_> {
+        let metadata = &processed_event.metadata;
+        let metric = &processed_event.event;
 
         let fields = metric
             .tags()
@@ -56,15 +63,7 @@ impl HecMetricsEncoder {
             .flat_map(|tags| tags.iter_single())
             // skip the metric tags used for templating
             .filter(|(k, _)| !metadata.templated_field_keys.iter().any(|f| f == k))
-            .map(|(k, v)| (k, HecFieldValue::Str(v)))
-            .chain(iter::once((
-                "metric_name",
-                HecFieldValue::Str(metadata.metric_name.as_str()),
-            )))
-            .chain(iter::once((
-                "_value",
-                HecFieldValue::Float(metadata.metric_value),
-            )))
+            .map(|(k, v)| (Cow::Borrowed(k), HecFieldValue::Str(v)))
             .collect::<HecFieldMap>();
         let time = metric
             .timestamp()
@@ -73,12 +72,15 @@ impl HecMetricsEncoder {
             / 1000f64;
         let mut hec_data = HecData::new(fields, time);
 
-        hec_data.host = metadata.host;
-        hec_data.index = metadata.index;
-        hec_data.source = metadata.source;
-        hec_data.sourcetype = metadata.sourcetype;
+        hec_data.host = metadata.host.clone();
+        hec_data.index = metadata.index.clone();
+        hec_data.source = metadata.source.clone();
+        hec_data.sourcetype = metadata.sourcetype.clone();
+        hec_data
+    }
 
-        match serde_json::to_vec(&hec_data) {
+    fn to_vec(hec_data: &HecData) -> Option<Vec<u8>> {
+        match serde_json::to_vec(hec_data) {
             Ok(value) => Some(value),
             Err(error) => {
                 emit!(SplunkEventEncodeError {
@@ -88,6 +90,45 @@ impl HecMetricsEncoder {
             }
         }
     }
+
+    pub fn encode_event(processed_event: HecProcessedEvent) -> Option<Vec<u8>> {
+        let mut hec_data = Self::hec_data(&processed_event);
+        let metadata = &processed_event.metadata;
+        hec_data.fields.insert(
+            Cow::Borrowed("metric_name"),
+            HecFieldValue::Str(metadata.metric_name.as_str()),
+        );
+        hec_data.fields.insert(
+            Cow::Borrowed("_value"),
+            HecFieldValue::Float(metadata.metric_value),
+        );
+
+        Self::to_vec(&hec_data)
+    }
+
+    /// Encodes `processed_events` in the [multiple-metric JSON format][multi_metric_docs], where
+    /// metrics sharing their timestamp, dimensions and metadata are sent as a single event, with a
+    /// `metric_name:<name>` field holding the value of each.
+    ///
+    /// [multi_metric_docs]: https://docs.splunk.com/Documentation/Splunk/8.0.0/Metrics/GetMetricsInOther#The_multiple-metric_JSON_format
+    pub fn encode_multi_metric(processed_events: &[HecProcessedEvent]) -> Vec<u8> {
+        let mut events: IndexMap<Vec<u8>, HecData> = IndexMap::new();
+        for processed_event in processed_events {
+            let hec_data = Self::hec_data(processed_event);
+            let key = match Self::to_vec(&hec_data) {
+                Some(key) => key,
+                None => continue,
+            };
+
+            let metadata = &processed_event.metadata;
+            events.entry(key).or_insert(hec_data).fields.insert(
+                Cow::Owned(format!("metric_name:{}", metadata.metric_name)),
+                HecFieldValue::Float(metadata.metric_value),
+            );
+        }
+
+        events.values().filter_map(Self::to_vec).flatten().collect()
+    }
 }
 
 impl Encoder<Vec<HecProcessedEvent>> for HecMetricsEncoder {
@@ -96,11 +137,15 @@ impl Encoder<Vec<HecProcessedEvent>> for HecMetricsEncoder {
         input: Vec<HecProcessedEvent>,
         writer: &mut dyn std::io::Write,
     ) -> std::io::Result<usize> {
-        let encoded_input: Vec<u8> = input
-            .into_iter()
-            .filter_map(Self::encode_event)
-            .flatten()
-            .collect();
+        let encoded_input: Vec<u8> = if self.multi_metric {
+            Self::encode_multi_metric(&input)
+        } else {
+            input
+                .into_iter()
+                .filter_map(Self::encode_event)
+                .flatten()
+                .collect()
+        };
         let encoded_size = encoded_input.len();
         writer.write_all(encoded_input.as_slice())?;
         Ok(encoded_size)
@@ -34,6 +34,7 @@ async fn config() -> HecMetricsSinkConfig {
         index: None,
         sourcetype: None,
         source: None,
+        multi_metric: false,
         compression: Compression::None,
         batch,
         request: TowerRequestConfig::default(),
@@ -14,7 +14,9 @@ use crate::sinks::{
 };
 
 pub struct HecMetricsRequestBuilder {
-    pub(super) compression: Compression,
+    pub(crate) compression: Compression,
+    pub(crate) encoder: HecMetricsEncoder,
+    pub(crate) endpoint: Arc<str>,
 }
 
 impl RequestBuilder<(Option<Arc<str>>, Vec<HecProcessedEvent>)> for HecMetricsRequestBuilder {
@@ -30,7 +32,7 @@ impl RequestBuilder<(Option<Arc<str>>, Vec<HecProcessedEvent>)> for HecMetricsRe
     }
 
     fn encoder(&self) -> &Self::Encoder {
-        &HecMetricsEncoder
+        &self.encoder
     }
 
     fn split_input(
@@ -60,7 +62,7 @@ impl RequestBuilder<(Option<Arc<str>>, Vec<HecProcessedEvent>)> for HecMetricsRe
             source: None,
             sourcetype: None,
             host: None,
-            metadata,
+            metadata: metadata.with_endpoint(Arc::clone(&self.endpoint)),
         }
     }
 }
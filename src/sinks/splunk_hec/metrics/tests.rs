@@ -313,6 +313,52 @@ fn test_encode_event_gauge_overridden_namespace_returns_expected_json() {
     assert_eq!(expected, actual);
 }
 
+#[test]
+fn test_encode_multi_metric_groups_metrics() {
+    let counter = get_processed_event(get_counter(), None, None, None, None);
+    let other_counter = get_processed_event(
+        get_counter().with_name("other-counter"),
+        None,
+        None,
+        None,
+        None,
+    );
+    let gauge = get_processed_event(get_gauge(None), None, None, None, None);
+
+    let encoded = HecMetricsEncoder::encode_multi_metric(&[counter, gauge, other_counter]);
+    let actual = serde_json::Deserializer::from_slice(&encoded)
+        .into_iter::<JsonValue>()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let expected = vec![
+        json!({
+            "time": 1134396775.123,
+            "host": "host_value",
+            "fields": {
+                "host": "host_value",
+                "tag_one": "tag_one_value",
+                "tag_two": "tag_two_value",
+                "template_index": "index_value",
+                "template_source": "source_value",
+                "template_sourcetype": "sourcetype_value",
+                "metric_name:example-counter": 26.8,
+                "metric_name:other-counter": 26.8,
+            },
+            "event": "metric",
+        }),
+        json!({
+            "time": 1134396775.123,
+            "fields": {
+                "metric_name:example-gauge": 26.8,
+            },
+            "event": "metric",
+        }),
+    ];
+
+    assert_eq!(expected, actual);
+}
+
 #[tokio::test]
 async fn splunk_passthrough_token() {
     let addr = next_addr();
@@ -323,6 +369,7 @@ async fn splunk_passthrough_token() {
         index: None,
         sourcetype: None,
         source: None,
+        multi_metric: false,
         compression: Compression::None,
         batch: Default::default(),
         request: Default::default(),
@@ -0,0 +1,109 @@
+//! The `sql` sink.
+//!
+//! Executes a parameterized statement for each event of batches, in a transaction per batch,
+//! against a PostgreSQL, MySQL, or SQLite database.
+
+mod config;
+mod parameter;
+mod pool;
+mod service;
+mod sink;
+
+pub use self::config::SqlConfig;
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+    use vector_core::event::{BatchNotifier, BatchStatus, Event, LogEvent};
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        test_util::components::{run_and_assert_sink_compliance, SINK_TAGS},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SqlConfig>();
+    }
+
+    fn config(path: &std::path::Path) -> SqlConfig {
+        toml::from_str(&format!(
+            r#"endpoint = "sqlite://{}"
+            statement = "INSERT INTO events (id, message, status) VALUES (?, ?, ?) ON CONFLICT (id) DO UPDATE SET message = excluded.message, status = excluded.status"
+            parameters = ["id", "message", "http.status"]"#,
+            path.display()
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn upserts_rows_into_sqlite() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("events.db");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE events (id INTEGER PRIMARY KEY, message TEXT, status INTEGER);
+                INSERT INTO events VALUES (1, 'old', 500);",
+            )
+            .unwrap();
+
+        let (sink, healthcheck) = config(&path).build(SinkContext::new_test()).await.unwrap();
+        healthcheck.await.unwrap();
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let events: Vec<_> = (1..=3)
+            .map(|id| {
+                let mut log = LogEvent::from(format!("message {}", id)).with_batch_notifier(&batch);
+                log.insert("id", id);
+                if id != 2 {
+                    log.insert("http.status", 200);
+                }
+                Event::from(log)
+            })
+            .collect();
+        drop(batch);
+        run_and_assert_sink_compliance(sink, futures::stream::iter(events), &SINK_TAGS).await;
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let connection = Connection::open(&path).unwrap();
+        let mut statement = connection
+            .prepare("SELECT id, message, status FROM events ORDER BY id")
+            .unwrap();
+        let rows: Vec<(i64, String, Option<i64>)> = statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                (1, "message 1".to_owned(), Some(200)),
+                (2, "message 2".to_owned(), None),
+                (3, "message 3".to_owned(), Some(200)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn checks_parameter_count() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("events.db");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE events (id INTEGER PRIMARY KEY, message TEXT, status INTEGER);",
+            )
+            .unwrap();
+
+        let mut config = config(&path);
+        config.parameters.pop();
+        let (_, healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+
+        assert_eq!(
+            healthcheck.await.unwrap_err().to_string(),
+            "statement takes 3 parameters, but 2 fields are mapped to them"
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use std::error::Error;
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, Datelike, SecondsFormat, Timelike, Utc};
+use mysql_async::Value as MysqlValue;
+use rusqlite::types::{ToSqlOutput, Value as SqliteValue, ValueRef};
+use tokio_postgres::types::{to_sql_checked, IsNull, ToSql, Type};
+use vector_core::event::Value;
+
+type BoxError = Box<dyn Error + Sync + Send>;
+
+/// The value of a field of an event, bound to a parameter of the statement.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Parameter {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    Text(String),
+    /// Objects and arrays, encoded as JSON.
+    Json(String),
+    Timestamp(DateTime<Utc>),
+}
+
+impl Parameter {
+    pub fn from_value(value: Option<&Value>) -> Self {
+        match value {
+            None | Some(Value::Null) => Self::Null,
+            Some(Value::Boolean(value)) => Self::Boolean(*value),
+            Some(Value::Integer(value)) => Self::Integer(*value),
+            Some(Value::Float(value)) => Self::Float(value.into_inner()),
+            Some(Value::Bytes(bytes)) => Self::Text(String::from_utf8_lossy(bytes).into_owned()),
+            Some(Value::Regex(regex)) => Self::Text(regex.as_str().to_owned()),
+            Some(Value::Timestamp(timestamp)) => Self::Timestamp(*timestamp),
+            Some(value @ (Value::Object(_) | Value::Array(_))) => {
+                Self::Json(serde_json::to_string(value).expect("values should serialize"))
+            }
+        }
+    }
+
+    /// The approximate size of the value once sent to the server.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Null => 0,
+            Self::Boolean(_) => 1,
+            Self::Integer(_) | Self::Float(_) | Self::Timestamp(_) => 8,
+            Self::Text(text) | Self::Json(text) => text.len(),
+        }
+    }
+
+    const fn kind(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Boolean(_) => "boolean",
+            Self::Integer(_) => "integer",
+            Self::Float(_) => "float",
+            Self::Text(_) => "text",
+            Self::Json(_) => "object",
+            Self::Timestamp(_) => "timestamp",
+        }
+    }
+
+    fn text(&self) -> String {
+        match self {
+            Self::Null => String::new(),
+            Self::Boolean(value) => value.to_string(),
+            Self::Integer(value) => value.to_string(),
+            Self::Float(value) => value.to_string(),
+            Self::Text(text) | Self::Json(text) => text.clone(),
+            Self::Timestamp(timestamp) => timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        }
+    }
+
+    fn json(&self) -> String {
+        match self {
+            Self::Text(_) | Self::Timestamp(_) => {
+                serde_json::to_string(&self.text()).expect("strings should serialize")
+            }
+            _ => self.text(),
+        }
+    }
+
+    fn mismatch(&self, ty: &Type) -> BoxError {
+        format!("can't convert {} to {}", self.kind(), ty).into()
+    }
+
+    fn integer(&self, ty: &Type) -> Result<i64, BoxError> {
+        match self {
+            Self::Integer(value) => Ok(*value),
+            Self::Text(text) => Ok(text.parse()?),
+            _ => Err(self.mismatch(ty)),
+        }
+    }
+
+    fn float(&self, ty: &Type) -> Result<f64, BoxError> {
+        match self {
+            Self::Integer(value) => Ok(*value as f64),
+            Self::Float(value) => Ok(*value),
+            Self::Text(text) => Ok(text.parse()?),
+            _ => Err(self.mismatch(ty)),
+        }
+    }
+
+    fn timestamp(&self, ty: &Type) -> Result<DateTime<Utc>, BoxError> {
+        match self {
+            Self::Timestamp(timestamp) => Ok(*timestamp),
+            Self::Text(text) => Ok(DateTime::parse_from_rfc3339(text)?.with_timezone(&Utc)),
+            _ => Err(self.mismatch(ty)),
+        }
+    }
+
+    pub fn into_mysql(self) -> MysqlValue {
+        match self {
+            Self::Null => MysqlValue::NULL,
+            Self::Boolean(value) => MysqlValue::Int(value.into()),
+            Self::Integer(value) => MysqlValue::Int(value),
+            Self::Float(value) => MysqlValue::Double(value),
+            Self::Text(text) | Self::Json(text) => MysqlValue::Bytes(text.into_bytes()),
+            Self::Timestamp(timestamp) => MysqlValue::Date(
+                timestamp.year() as u16,
+                timestamp.month() as u8,
+                timestamp.day() as u8,
+                timestamp.hour() as u8,
+                timestamp.minute() as u8,
+                timestamp.second() as u8,
+                timestamp.timestamp_subsec_micros(),
+            ),
+        }
+    }
+}
+
+/// Parameters are converted to the types the server infers for them, or sent as text, which is the
+/// binary format of the textual types.
+impl ToSql for Parameter {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        if *self == Self::Null {
+            return Ok(IsNull::Yes);
+        }
+
+        match *ty {
+            Type::BOOL => match self {
+                Self::Boolean(value) => value.to_sql(ty, out),
+                _ => Err(self.mismatch(ty)),
+            },
+            Type::INT2 => i16::try_from(self.integer(ty)?)?.to_sql(ty, out),
+            Type::INT4 => i32::try_from(self.integer(ty)?)?.to_sql(ty, out),
+            Type::INT8 => self.integer(ty)?.to_sql(ty, out),
+            Type::FLOAT4 => (self.float(ty)? as f32).to_sql(ty, out),
+            Type::FLOAT8 => self.float(ty)?.to_sql(ty, out),
+            Type::TIMESTAMPTZ => self.timestamp(ty)?.to_sql(ty, out),
+            Type::TIMESTAMP => self.timestamp(ty)?.naive_utc().to_sql(ty, out),
+            Type::JSON => {
+                out.put_slice(self.json().as_bytes());
+                Ok(IsNull::No)
+            }
+            Type::JSONB => {
+                // The version of the binary format of `jsonb`.
+                out.put_u8(1);
+                out.put_slice(self.json().as_bytes());
+                Ok(IsNull::No)
+            }
+            _ => {
+                out.put_slice(self.text().as_bytes());
+                Ok(IsNull::No)
+            }
+        }
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+impl rusqlite::ToSql for Parameter {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(match self {
+            Self::Null => ToSqlOutput::Owned(SqliteValue::Null),
+            Self::Boolean(value) => ToSqlOutput::Owned(SqliteValue::Integer((*value).into())),
+            Self::Integer(value) => ToSqlOutput::Owned(SqliteValue::Integer(*value)),
+            Self::Float(value) => ToSqlOutput::Owned(SqliteValue::Real(*value)),
+            Self::Text(text) | Self::Json(text) => {
+                ToSqlOutput::Borrowed(ValueRef::Text(text.as_bytes()))
+            }
+            Self::Timestamp(_) => ToSqlOutput::Owned(SqliteValue::Text(self.text())),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_common::btreemap;
+
+    use super::*;
+
+    fn postgres_bytes(parameter: &Parameter, ty: &Type) -> Result<Vec<u8>, BoxError> {
+        let mut out = BytesMut::new();
+        parameter.to_sql(ty, &mut out)?;
+        Ok(out.to_vec())
+    }
+
+    #[test]
+    fn converts_values() {
+        let timestamp = Utc.ymd(2023, 1, 2).and_hms(3, 4, 5);
+        let object = Value::from(btreemap! { "a" => 1 });
+
+        assert_eq!(Parameter::from_value(None), Parameter::Null);
+        assert_eq!(
+            Parameter::from_value(Some(&Value::from("text"))),
+            Parameter::Text("text".to_owned())
+        );
+        assert_eq!(
+            Parameter::from_value(Some(&Value::Timestamp(timestamp))),
+            Parameter::Timestamp(timestamp)
+        );
+        assert_eq!(
+            Parameter::from_value(Some(&object)),
+            Parameter::Json(r#"{"a":1}"#.to_owned())
+        );
+    }
+
+    #[test]
+    fn converts_postgres_parameters_to_their_types() {
+        assert_eq!(
+            postgres_bytes(&Parameter::Integer(7), &Type::INT4).unwrap(),
+            vec![0, 0, 0, 7]
+        );
+        assert_eq!(
+            postgres_bytes(&Parameter::Text("7".to_owned()), &Type::INT2).unwrap(),
+            vec![0, 7]
+        );
+        assert_eq!(
+            postgres_bytes(&Parameter::Integer(7), &Type::TEXT).unwrap(),
+            b"7".to_vec()
+        );
+        assert_eq!(
+            postgres_bytes(&Parameter::Text("a".to_owned()), &Type::JSONB).unwrap(),
+            b"\x01\"a\"".to_vec()
+        );
+        assert!(postgres_bytes(&Parameter::Integer(100_000), &Type::INT2).is_err());
+        assert!(postgres_bytes(&Parameter::Boolean(true), &Type::INT8).is_err());
+    }
+}
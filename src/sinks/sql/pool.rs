@@ -0,0 +1,298 @@
+use std::{
+    ops::{Deref, DerefMut},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use futures::future::try_join_all;
+use mysql_async::{
+    prelude::Queryable, Opts, OptsBuilder, Params, PoolConstraints, PoolOpts, TxOpts,
+};
+use openssl::ssl::{SslConnector, SslMethod};
+use postgres_openssl::MakeTlsConnector;
+use rusqlite::Connection;
+use snafu::{ResultExt, Snafu};
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio_postgres::{config::SslMode, Client, Config, NoTls};
+
+use super::{
+    parameter::Parameter,
+    service::{MysqlSnafu, PostgresSnafu, SqlError, SqliteSnafu, SqliteTaskSnafu},
+};
+
+#[derive(Debug, Snafu)]
+pub enum EndpointError {
+    #[snafu(display("invalid PostgreSQL endpoint: {}", source))]
+    InvalidPostgresEndpoint { source: tokio_postgres::Error },
+    #[snafu(display("invalid MySQL endpoint: {}", source))]
+    InvalidMysqlEndpoint { source: mysql_async::UrlError },
+    #[snafu(display(
+        "unsupported endpoint scheme, expected `postgresql://`, `mysql://`, or `sqlite://`"
+    ))]
+    UnsupportedScheme,
+    #[snafu(display("failed to create tls connector: {}", source))]
+    TlsConnector { source: openssl::error::ErrorStack },
+    #[snafu(display("failed to open SQLite database: {}", source))]
+    OpenSqlite { source: rusqlite::Error },
+}
+
+/// The database to write to, as parsed from the endpoint.
+#[derive(Debug)]
+pub enum Database {
+    Postgres(Config),
+    Mysql(Opts),
+    Sqlite(PathBuf),
+}
+
+impl Database {
+    pub fn from_endpoint(endpoint: &str) -> Result<Self, EndpointError> {
+        let scheme = endpoint.split_once("://").map(|(scheme, _)| scheme);
+        match scheme {
+            Some("postgres" | "postgresql") => endpoint
+                .parse()
+                .map(Self::Postgres)
+                .context(InvalidPostgresEndpointSnafu),
+            Some("mysql") => Opts::from_url(endpoint)
+                .map(Self::Mysql)
+                .context(InvalidMysqlEndpointSnafu),
+            Some("sqlite") => Ok(Self::Sqlite(PathBuf::from(&endpoint["sqlite://".len()..]))),
+            _ => Err(EndpointError::UnsupportedScheme),
+        }
+    }
+
+    pub const fn protocol(&self) -> &'static str {
+        match self {
+            Self::Postgres(_) => "postgres",
+            Self::Mysql(_) => "mysql",
+            Self::Sqlite(_) => "sqlite",
+        }
+    }
+}
+
+/// The connections to the database, opened as they're needed, up to the size of the pool.
+///
+/// SQLite databases are written through a single connection, as their writes are serialized
+/// anyway.
+pub enum Pool {
+    Postgres(PostgresPool),
+    Mysql(mysql_async::Pool),
+    Sqlite(Arc<Mutex<Connection>>),
+}
+
+impl Pool {
+    pub fn new(database: Database, size: usize) -> Result<Self, EndpointError> {
+        Ok(match database {
+            Database::Postgres(config) => {
+                let builder =
+                    SslConnector::builder(SslMethod::tls_client()).context(TlsConnectorSnafu)?;
+                Self::Postgres(PostgresPool {
+                    config,
+                    tls: MakeTlsConnector::new(builder.build()),
+                    idle: Mutex::new(Vec::new()),
+                    permits: Semaphore::new(size),
+                })
+            }
+            Database::Mysql(opts) => {
+                let constraints =
+                    PoolConstraints::new(0, size).expect("the pool size should not be zero");
+                let opts = OptsBuilder::from_opts(opts)
+                    .pool_opts(PoolOpts::default().with_constraints(constraints));
+                Self::Mysql(mysql_async::Pool::new(opts))
+            }
+            Database::Sqlite(path) => {
+                let connection = Connection::open(path).context(OpenSqliteSnafu)?;
+                Self::Sqlite(Arc::new(Mutex::new(connection)))
+            }
+        })
+    }
+
+    /// Prepares `statement`, returning the number of its parameters.
+    pub async fn check(&self, statement: &str) -> Result<usize, SqlError> {
+        match self {
+            Self::Postgres(pool) => {
+                let client = pool.get().await?;
+                let statement = client.prepare(statement).await.context(PostgresSnafu)?;
+                Ok(statement.params().len())
+            }
+            Self::Mysql(pool) => {
+                let mut connection = pool.get_conn().await.context(MysqlSnafu)?;
+                let statement = connection.prep(statement).await.context(MysqlSnafu)?;
+                Ok(statement.num_params().into())
+            }
+            Self::Sqlite(connection) => {
+                let connection = Arc::clone(connection);
+                let statement = statement.to_owned();
+                tokio::task::spawn_blocking(move || {
+                    let connection = connection.lock().expect("poisoned lock");
+                    let statement = connection.prepare_cached(&statement)?;
+                    Ok::<_, rusqlite::Error>(statement.parameter_count())
+                })
+                .await
+                .context(SqliteTaskSnafu)?
+                .context(SqliteSnafu)
+            }
+        }
+    }
+
+    /// Executes `statement` once per row, in a single transaction.
+    pub async fn write(&self, statement: &str, rows: Vec<Vec<Parameter>>) -> Result<(), SqlError> {
+        match self {
+            Self::Postgres(pool) => {
+                let mut client = pool.get().await?;
+                let transaction = client.transaction().await.context(PostgresSnafu)?;
+                let statement = transaction
+                    .prepare(statement)
+                    .await
+                    .context(PostgresSnafu)?;
+                // The rows are pipelined, rather than waiting for each of them to be written.
+                try_join_all(
+                    rows.iter()
+                        .map(|row| transaction.execute_raw(&statement, row.iter())),
+                )
+                .await
+                .context(PostgresSnafu)?;
+                transaction.commit().await.context(PostgresSnafu)
+            }
+            Self::Mysql(pool) => {
+                let mut connection = pool.get_conn().await.context(MysqlSnafu)?;
+                let mut transaction = connection
+                    .start_transaction(TxOpts::default())
+                    .await
+                    .context(MysqlSnafu)?;
+                let params = rows.into_iter().map(|row| {
+                    Params::Positional(row.into_iter().map(Parameter::into_mysql).collect())
+                });
+                transaction
+                    .exec_batch(statement, params)
+                    .await
+                    .context(MysqlSnafu)?;
+                transaction.commit().await.context(MysqlSnafu)
+            }
+            Self::Sqlite(connection) => {
+                let connection = Arc::clone(connection);
+                let statement = statement.to_owned();
+                tokio::task::spawn_blocking(move || {
+                    let mut connection = connection.lock().expect("poisoned lock");
+                    let transaction = connection.transaction()?;
+                    {
+                        let mut statement = transaction.prepare_cached(&statement)?;
+                        for row in &rows {
+                            statement.execute(rusqlite::params_from_iter(row))?;
+                        }
+                    }
+                    transaction.commit()
+                })
+                .await
+                .context(SqliteTaskSnafu)?
+                .context(SqliteSnafu)
+            }
+        }
+    }
+}
+
+/// A pool of connections to a PostgreSQL server.
+///
+/// Connections use TLS as the `sslmode` of the endpoint requires, with the certificates of the
+/// system.
+pub struct PostgresPool {
+    config: Config,
+    tls: MakeTlsConnector,
+    idle: Mutex<Vec<Client>>,
+    permits: Semaphore,
+}
+
+impl PostgresPool {
+    /// Waits for a connection to be available, reusing an idle one or opening a new one.
+    async fn get(&self) -> Result<PooledClient<'_>, SqlError> {
+        let permit = self
+            .permits
+            .acquire()
+            .await
+            .expect("the semaphore should never be closed");
+
+        let idle = self.idle.lock().expect("poisoned lock").pop();
+        let client = match idle.filter(|client| !client.is_closed()) {
+            Some(client) => client,
+            None => self.connect().await.context(PostgresSnafu)?,
+        };
+
+        Ok(PooledClient {
+            client: Some(client),
+            pool: self,
+            _permit: permit,
+        })
+    }
+
+    async fn connect(&self) -> Result<Client, tokio_postgres::Error> {
+        let client = if self.config.get_ssl_mode() == SslMode::Disable {
+            let (client, connection) = self.config.connect(NoTls).await?;
+            tokio::spawn(connection);
+            client
+        } else {
+            let (client, connection) = self.config.connect(self.tls.clone()).await?;
+            tokio::spawn(connection);
+            client
+        };
+        Ok(client)
+    }
+}
+
+/// A connection of the pool, which is returned to it when dropped, unless it was closed.
+struct PooledClient<'a> {
+    client: Option<Client>,
+    pool: &'a PostgresPool,
+    _permit: SemaphorePermit<'a>,
+}
+
+impl Deref for PooledClient<'_> {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        self.client
+            .as_ref()
+            .expect("the client is only taken when dropped")
+    }
+}
+
+impl DerefMut for PooledClient<'_> {
+    fn deref_mut(&mut self) -> &mut Client {
+        self.client
+            .as_mut()
+            .expect("the client is only taken when dropped")
+    }
+}
+
+impl Drop for PooledClient<'_> {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            if !client.is_closed() {
+                self.pool.idle.lock().expect("poisoned lock").push(client);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_endpoints() {
+        assert!(matches!(
+            Database::from_endpoint("postgresql://vector@localhost:5432/app"),
+            Ok(Database::Postgres(_))
+        ));
+        assert!(matches!(
+            Database::from_endpoint("mysql://vector@localhost:3306/app"),
+            Ok(Database::Mysql(_))
+        ));
+        assert!(matches!(
+            Database::from_endpoint("sqlite:///var/lib/app.db"),
+            Ok(Database::Sqlite(path)) if path == PathBuf::from("/var/lib/app.db")
+        ));
+        assert!(matches!(
+            Database::from_endpoint("mssql://localhost/app"),
+            Err(EndpointError::UnsupportedScheme)
+        ));
+    }
+}
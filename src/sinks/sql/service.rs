@@ -0,0 +1,159 @@
+use std::{
+    error::Error as _,
+    io,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::future::BoxFuture;
+use mysql_async::DriverError;
+use rusqlite::ErrorCode;
+use snafu::Snafu;
+use tokio::task::JoinError;
+use tower::Service;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    stream::DriverResponse,
+};
+
+use super::{parameter::Parameter, pool::Pool};
+use crate::sinks::util::retries::RetryLogic;
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(super)))]
+pub enum SqlError {
+    #[snafu(display("PostgreSQL request failed: {}", source))]
+    Postgres { source: tokio_postgres::Error },
+    #[snafu(display("MySQL request failed: {}", source))]
+    Mysql { source: mysql_async::Error },
+    #[snafu(display("SQLite request failed: {}", source))]
+    Sqlite { source: rusqlite::Error },
+    #[snafu(display("SQLite request failed: {}", source))]
+    SqliteTask { source: JoinError },
+}
+
+/// A batch of rows, each holding the parameters the statement is executed with.
+#[derive(Clone)]
+pub struct SqlRequest {
+    pub rows: Vec<Vec<Parameter>>,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for SqlRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for SqlRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata.clone()
+    }
+}
+
+pub struct SqlResponse;
+
+impl DriverResponse for SqlResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+}
+
+#[derive(Clone)]
+pub struct SqlService {
+    pub pool: Arc<Pool>,
+    pub statement: Arc<str>,
+}
+
+impl Service<SqlRequest> for SqlService {
+    type Response = SqlResponse;
+    type Error = SqlError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: SqlRequest) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move {
+            service.pool.write(&service.statement, request.rows).await?;
+            Ok(SqlResponse)
+        })
+    }
+}
+
+/// Whether requests failing with `error` are retried, which are those failing because of the
+/// connection or of the load of the server, rather than because of the rows themselves.
+fn is_retriable(error: &SqlError) -> bool {
+    match error {
+        SqlError::Postgres { source } => match source.code() {
+            Some(state) => {
+                let code = state.code();
+                // Connection exceptions, transaction rollbacks such as serialization failures and
+                // deadlocks, insufficient resources, and the server shutting down.
+                code.starts_with("08")
+                    || code.starts_with("40")
+                    || code.starts_with("53")
+                    || code.starts_with("57P")
+            }
+            // Errors without a state are those of the connection, or of the conversion of
+            // parameters.
+            None => {
+                source.is_closed()
+                    || source
+                        .source()
+                        .map_or(false, |source| source.is::<io::Error>())
+            }
+        },
+        SqlError::Mysql { source } => match source {
+            mysql_async::Error::Io(_) => true,
+            mysql_async::Error::Driver(DriverError::ConnectionClosed) => true,
+            // Too many connections, the server shutting down, lock wait timeouts, and deadlocks.
+            mysql_async::Error::Server(error) => matches!(error.code, 1040 | 1053 | 1205 | 1213),
+            _ => false,
+        },
+        SqlError::Sqlite {
+            source: rusqlite::Error::SqliteFailure(error, _),
+        } => matches!(
+            error.code,
+            ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked
+        ),
+        SqlError::Sqlite { .. } => false,
+        SqlError::SqliteTask { .. } => false,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SqlRetryLogic;
+
+impl RetryLogic for SqlRetryLogic {
+    type Error = SqlError;
+    type Response = SqlResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        is_retriable(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_busy_sqlite_databases() {
+        let busy = rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+            None,
+        );
+        let constraint = rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CONSTRAINT),
+            None,
+        );
+
+        assert!(SqlRetryLogic.is_retriable_error(&SqlError::Sqlite { source: busy }));
+        assert!(!SqlRetryLogic.is_retriable_error(&SqlError::Sqlite { source: constraint }));
+    }
+}
@@ -0,0 +1,117 @@
+use std::{num::NonZeroUsize, sync::Arc};
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::ServiceBuilder;
+use vector_core::{
+    event::{EventFinalizers, Finalizable},
+    stream::BatcherSettings,
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use super::{
+    parameter::Parameter,
+    pool::Pool,
+    service::{SqlRequest, SqlRetryLogic, SqlService},
+};
+use crate::{
+    codecs::Transformer,
+    event::Event,
+    sinks::util::{
+        metadata::RequestMetadataBuilder, ServiceBuilderExt, SinkBuilderExt, StreamSink,
+        TowerRequestSettings,
+    },
+};
+
+/// An event, as the parameters of the statement, with the sizes it had before being transformed.
+struct EncodedRow {
+    parameters: Vec<Parameter>,
+    finalizers: EventFinalizers,
+    byte_size: usize,
+    json_size: usize,
+}
+
+impl ByteSizeOf for EncodedRow {
+    fn size_of(&self) -> usize {
+        self.parameters.iter().map(Parameter::size).sum()
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
+pub struct SqlSink {
+    pub pool: Arc<Pool>,
+    pub protocol: &'static str,
+    pub statement: String,
+    pub parameters: Vec<String>,
+    pub transformer: Transformer,
+    pub batch_settings: BatcherSettings,
+    pub request: TowerRequestSettings,
+}
+
+#[async_trait]
+impl StreamSink<Event> for SqlSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let service = ServiceBuilder::new()
+            .settings(self.request, SqlRetryLogic)
+            .service(SqlService {
+                pool: Arc::clone(&self.pool),
+                statement: self.statement.as_str().into(),
+            });
+
+        let parameters = self.parameters;
+        let transformer = self.transformer;
+        input
+            .map(|mut event| {
+                let byte_size = event.size_of();
+                let json_size = event.estimated_json_encoded_size_of();
+                transformer.transform(&mut event);
+                let log = event.as_log();
+                EncodedRow {
+                    parameters: parameters
+                        .iter()
+                        .map(|field| Parameter::from_value(log.get(field.as_str())))
+                        .collect(),
+                    finalizers: event.take_finalizers(),
+                    byte_size,
+                    json_size,
+                }
+            })
+            .batched(self.batch_settings.into_byte_size_config())
+            .map(|rows| {
+                let count = rows.len();
+                let mut byte_size = 0;
+                let mut json_size = 0;
+                let mut request_size = 0;
+                let mut finalizers = EventFinalizers::default();
+                let rows: Vec<_> = rows
+                    .into_iter()
+                    .map(|row| {
+                        byte_size += row.byte_size;
+                        json_size += row.json_size;
+                        request_size += row.size_of();
+                        finalizers.merge(row.finalizers);
+                        row.parameters
+                    })
+                    .collect();
+                // Rows of events without any of the fields of the parameters are empty.
+                let metadata = RequestMetadataBuilder::new(count, byte_size, json_size)
+                    .with_request_size(
+                        NonZeroUsize::new(request_size.max(1))
+                            .expect("size should be at least one"),
+                    );
+
+                SqlRequest {
+                    rows,
+                    finalizers,
+                    metadata,
+                }
+            })
+            .into_driver(service)
+            .protocol(self.protocol)
+            .run()
+            .await
+    }
+}
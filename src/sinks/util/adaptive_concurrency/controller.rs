@@ -17,7 +17,10 @@ use crate::{
         AdaptiveConcurrencyAveragedRtt, AdaptiveConcurrencyInFlight, AdaptiveConcurrencyLimit,
         AdaptiveConcurrencyLimitData, AdaptiveConcurrencyObservedRtt,
     },
-    sinks::util::retries::{RetryAction, RetryLogic},
+    sinks::util::{
+        retries::{RetryAction, RetryLogic},
+        service::CircuitBreakerOpen,
+    },
     stats::{EwmaVar, Mean, MeanVariance},
 };
 
@@ -284,6 +287,9 @@ where
                 } else if error.downcast_ref::<HttpError>().is_some() {
                     // HTTP protocol-level errors are not backpressure
                     false
+                } else if error.downcast_ref::<CircuitBreakerOpen>().is_some() {
+                    // Requests failed by the circuit breaker were never sent
+                    false
                 } else {
                     warn!(
                         message = "Unhandled error response.",
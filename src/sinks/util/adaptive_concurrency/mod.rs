@@ -3,6 +3,7 @@
 mod controller;
 mod future;
 mod layer;
+mod partitioned;
 mod semaphore;
 mod service;
 
@@ -12,6 +13,10 @@ pub mod tests;
 pub(super) const MAX_CONCURRENCY: usize = 200;
 
 pub(crate) use layer::AdaptiveConcurrencyLimitLayer;
+pub(crate) use partitioned::{
+    ConcurrencyPartition, PartitionedAdaptiveConcurrencyLimit,
+    PartitionedAdaptiveConcurrencyLimitLayer,
+};
 pub(crate) use service::AdaptiveConcurrencyLimit;
 use vector_config::configurable_component;
 
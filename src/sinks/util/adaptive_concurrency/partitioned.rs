@@ -0,0 +1,348 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    mem,
+    sync::{Arc, Mutex},
+    task::{ready, Context, Poll},
+    time::{Duration, Instant},
+};
+
+use futures::future::BoxFuture;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tower::{Layer, Service};
+
+use super::{
+    controller::Controller, future::ResponseFuture, instant_now, AdaptiveConcurrencySettings,
+    MAX_CONCURRENCY,
+};
+use crate::sinks::util::retries::RetryLogic;
+
+/// The time after which the controllers of partitions without requests are forgotten.
+const PARTITION_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Requests that belong to a partition of the service they're sent to, such as a tenant or a
+/// key prefix, which has its own adaptive concurrency limit.
+pub trait ConcurrencyPartition {
+    /// The partition of the request, or `None` for requests that share the limit of the service.
+    fn concurrency_partition(&self) -> Option<String>;
+}
+
+/// Enforces a limit on the concurrent number of requests the underlying
+/// service can handle, separately for each partition of its requests.
+///
+/// Each partition has its own controller, which expands and contracts its limit depending on
+/// the observed response behavior of its requests only, so that a slow partition doesn't throttle
+/// the others. As the partition of a request is only known once it's called, requests wait for a
+/// permit of their partition in the returned future, and only then for a slot of the service, so
+/// that the number of requests in flight is bounded by the maximum concurrency. The service is
+/// ready while it has a free slot.
+///
+/// A fixed `concurrency` isn't adjusted, and is shared by all partitions.
+pub struct PartitionedAdaptiveConcurrencyLimit<S, L> {
+    inner: S,
+    concurrency: Option<usize>,
+    options: AdaptiveConcurrencySettings,
+    logic: L,
+    partitions: Arc<Mutex<HashMap<Option<String>, Partition<L>>>>,
+    semaphore: Arc<Semaphore>,
+    state: State,
+}
+
+struct Partition<L> {
+    controller: Arc<Controller<L>>,
+    last_used: Instant,
+}
+
+enum State {
+    Waiting(BoxFuture<'static, OwnedSemaphorePermit>),
+    Ready(OwnedSemaphorePermit),
+    Empty,
+}
+
+impl<S, L> PartitionedAdaptiveConcurrencyLimit<S, L> {
+    /// Create a new automated concurrency limiter, with a limit per partition.
+    pub(crate) fn new(
+        inner: S,
+        logic: L,
+        concurrency: Option<usize>,
+        options: AdaptiveConcurrencySettings,
+    ) -> Self {
+        Self {
+            inner,
+            concurrency,
+            options,
+            logic,
+            partitions: Arc::new(Mutex::new(HashMap::new())),
+            semaphore: Arc::new(Semaphore::new(concurrency.unwrap_or(MAX_CONCURRENCY))),
+            state: State::Empty,
+        }
+    }
+
+    /// The number of partitions with a controller.
+    #[cfg(test)]
+    fn partition_count(&self) -> usize {
+        self.partitions
+            .lock()
+            .expect("Partitions mutex is poisoned")
+            .len()
+    }
+}
+
+impl<S, L: Clone> PartitionedAdaptiveConcurrencyLimit<S, L> {
+    /// Returns the controller of `partition`, creating it for partitions that don't have one.
+    fn controller(&self, partition: Option<String>) -> Arc<Controller<L>> {
+        // A fixed limit applies to the whole service.
+        let partition = partition.filter(|_| self.concurrency.is_none());
+        let now = instant_now();
+        let mut partitions = self
+            .partitions
+            .lock()
+            .expect("Partitions mutex is poisoned");
+
+        if !partitions.contains_key(&partition) {
+            // Controllers without requests in flight are only referenced by the map.
+            partitions.retain(|_, idle| {
+                Arc::strong_count(&idle.controller) > 1
+                    || now.duration_since(idle.last_used) < PARTITION_IDLE_TIMEOUT
+            });
+        }
+        let partition = partitions.entry(partition).or_insert_with(|| Partition {
+            controller: Arc::new(Controller::new(
+                self.concurrency,
+                self.options,
+                self.logic.clone(),
+            )),
+            last_used: now,
+        });
+        partition.last_used = now;
+        Arc::clone(&partition.controller)
+    }
+}
+
+impl<S, L, Request> Service<Request> for PartitionedAdaptiveConcurrencyLimit<S, L>
+where
+    S: Service<Request> + Clone + Send + 'static,
+    S::Error: Into<crate::Error>,
+    S::Future: Send + 'static,
+    L: RetryLogic<Response = S::Response>,
+    Request: ConcurrencyPartition + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        loop {
+            self.state = match self.state {
+                State::Ready(_) => return self.inner.poll_ready(cx).map_err(Into::into),
+                State::Waiting(ref mut fut) => {
+                    tokio::pin!(fut);
+                    let permit = ready!(fut.poll(cx));
+                    State::Ready(permit)
+                }
+                State::Empty => {
+                    let semaphore = Arc::clone(&self.semaphore);
+                    State::Waiting(Box::pin(async move {
+                        semaphore
+                            .acquire_owned()
+                            .await
+                            .expect("Semaphore has been closed")
+                    }))
+                }
+            };
+        }
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Make sure the service had a free slot. It's only taken once the partition has a permit,
+        // so that requests waiting for a slow partition don't hold back the others.
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Ready(_permit) => {}
+            _ => panic!("Maximum requests in-flight; poll_ready must be called first"),
+        }
+
+        let controller = self.controller(request.concurrency_partition());
+
+        // The inner service is ready, and is only called once the partition has a permit.
+        let clone = self.inner.clone();
+        let mut inner = mem::replace(&mut self.inner, clone);
+        let semaphore = Arc::clone(&self.semaphore);
+
+        Box::pin(async move {
+            let partition_permit = Arc::clone(&controller).acquire().await;
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .expect("Semaphore has been closed");
+            controller.start_request();
+
+            let future = inner.call(request);
+            ResponseFuture::new(future, partition_permit, controller).await
+        })
+    }
+}
+
+impl<S, L> Clone for PartitionedAdaptiveConcurrencyLimit<S, L>
+where
+    S: Clone,
+    L: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            concurrency: self.concurrency,
+            options: self.options,
+            logic: self.logic.clone(),
+            partitions: Arc::clone(&self.partitions),
+            semaphore: Arc::clone(&self.semaphore),
+            state: State::Empty,
+        }
+    }
+}
+
+/// Enforces a limit on the concurrent number of requests the underlying
+/// service can handle, separately for each partition of its requests.
+#[derive(Debug, Clone)]
+pub struct PartitionedAdaptiveConcurrencyLimitLayer<L> {
+    concurrency: Option<usize>,
+    options: AdaptiveConcurrencySettings,
+    logic: L,
+}
+
+impl<L> PartitionedAdaptiveConcurrencyLimitLayer<L> {
+    /// Create a new partitioned concurrency limit layer.
+    pub const fn new(
+        concurrency: Option<usize>,
+        options: AdaptiveConcurrencySettings,
+        logic: L,
+    ) -> Self {
+        Self {
+            concurrency,
+            options,
+            logic,
+        }
+    }
+}
+
+impl<S, L: RetryLogic> Layer<S> for PartitionedAdaptiveConcurrencyLimitLayer<L> {
+    type Service = PartitionedAdaptiveConcurrencyLimit<S, L>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        PartitionedAdaptiveConcurrencyLimit::new(
+            service,
+            self.logic.clone(),
+            self.concurrency,
+            self.options,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_test::{assert_pending, assert_ready_ok, task};
+    use tower_test::{assert_request_eq, mock};
+
+    use super::*;
+
+    #[derive(Clone, Copy, Debug)]
+    struct TestRetryLogic;
+    impl RetryLogic for TestRetryLogic {
+        type Error = std::io::Error;
+        type Response = String;
+        fn is_retriable_error(&self, _error: &Self::Error) -> bool {
+            true
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct TestRequest(&'static str, usize);
+
+    impl ConcurrencyPartition for TestRequest {
+        fn concurrency_partition(&self) -> Option<String> {
+            Some(self.0.to_owned())
+        }
+    }
+
+    #[tokio::test]
+    async fn limits_partitions_separately() {
+        let layer =
+            PartitionedAdaptiveConcurrencyLimitLayer::new(None, Default::default(), TestRetryLogic);
+        let (mut service, mut handle) = mock::spawn_layer::<TestRequest, String, _>(layer);
+
+        // Concurrency starts at 1 for each partition.
+        assert_ready_ok!(service.poll_ready());
+        let mut slow = task::spawn(service.call(TestRequest("slow", 1)));
+        assert_pending!(slow.poll());
+        let slow_response = assert_request_eq!(handle, TestRequest("slow", 1));
+
+        assert_ready_ok!(service.poll_ready());
+        let mut queued = task::spawn(service.call(TestRequest("slow", 2)));
+        assert_pending!(queued.poll());
+
+        // Requests of other partitions aren't held back by the slow one.
+        assert_ready_ok!(service.poll_ready());
+        let mut fast = task::spawn(service.call(TestRequest("fast", 1)));
+        assert_pending!(fast.poll());
+        assert_request_eq!(handle, TestRequest("fast", 1)).send_response("fast #1".into());
+        assert_eq!(assert_ready_ok!(fast.poll()), "fast #1");
+        assert_eq!(service.get_ref().partition_count(), 2);
+
+        // Queued requests are sent once their partition has a permit.
+        slow_response.send_response("slow #1".into());
+        assert_eq!(assert_ready_ok!(slow.poll()), "slow #1");
+        assert_pending!(queued.poll());
+        assert_request_eq!(handle, TestRequest("slow", 2)).send_response("slow #2".into());
+        assert_eq!(assert_ready_ok!(queued.poll()), "slow #2");
+    }
+
+    #[tokio::test]
+    async fn queued_requests_dont_hold_back_other_partitions() {
+        let layer =
+            PartitionedAdaptiveConcurrencyLimitLayer::new(None, Default::default(), TestRetryLogic);
+        let (mut service, mut handle) = mock::spawn_layer::<TestRequest, String, _>(layer);
+
+        assert_ready_ok!(service.poll_ready());
+        let mut slow = task::spawn(service.call(TestRequest("slow", 0)));
+        assert_pending!(slow.poll());
+        let _slow_response = assert_request_eq!(handle, TestRequest("slow", 0));
+
+        // More requests than the maximum concurrency wait for a permit of the slow partition.
+        let _queued = (1..=MAX_CONCURRENCY)
+            .map(|i| {
+                assert_ready_ok!(service.poll_ready());
+                let mut queued = task::spawn(service.call(TestRequest("slow", i)));
+                assert_pending!(queued.poll());
+                queued
+            })
+            .collect::<Vec<_>>();
+
+        assert_ready_ok!(service.poll_ready());
+        let mut fast = task::spawn(service.call(TestRequest("fast", 1)));
+        assert_pending!(fast.poll());
+        assert_request_eq!(handle, TestRequest("fast", 1)).send_response("fast #1".into());
+        assert_eq!(assert_ready_ok!(fast.poll()), "fast #1");
+    }
+
+    #[tokio::test]
+    async fn shares_fixed_concurrency() {
+        let layer = PartitionedAdaptiveConcurrencyLimitLayer::new(
+            Some(1),
+            Default::default(),
+            TestRetryLogic,
+        );
+        let (mut service, mut handle) = mock::spawn_layer::<TestRequest, String, _>(layer);
+
+        assert_ready_ok!(service.poll_ready());
+        let mut first = task::spawn(service.call(TestRequest("first", 1)));
+        assert_pending!(first.poll());
+        let response = assert_request_eq!(handle, TestRequest("first", 1));
+
+        // The fixed limit applies to all partitions together.
+        assert_pending!(service.poll_ready());
+        assert_eq!(service.get_ref().partition_count(), 1);
+
+        response.send_response("first #1".into());
+        assert_eq!(assert_ready_ok!(first.poll()), "first #1");
+        assert_ready_ok!(service.poll_ready());
+    }
+}
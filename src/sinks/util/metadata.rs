@@ -1,9 +1,9 @@
 use std::num::NonZeroUsize;
 
 use vector_buffers::EventCount;
-use vector_core::{ByteSizeOf, EstimatedJsonEncodedSizeOf};
+use vector_core::{ByteSizeOf, EstimatedJsonEncodedSizeOf, GroupedSizeOf};
 
-use vector_common::request_metadata::RequestMetadata;
+use vector_common::request_metadata::{GroupedCountByteSize, RequestMetadata};
 
 use super::request_builder::EncodeResult;
 
@@ -12,21 +12,23 @@ pub struct RequestMetadataBuilder {
     event_count: usize,
     events_byte_size: usize,
     events_estimated_json_encoded_byte_size: usize,
+    grouped_sizes: GroupedCountByteSize,
 }
 
 impl RequestMetadataBuilder {
     pub fn from_events<E>(events: E) -> Self
     where
-        E: ByteSizeOf + EventCount + EstimatedJsonEncodedSizeOf,
+        E: ByteSizeOf + EventCount + EstimatedJsonEncodedSizeOf + GroupedSizeOf,
     {
         Self {
             event_count: events.event_count(),
             events_byte_size: events.size_of(),
             events_estimated_json_encoded_byte_size: events.estimated_json_encoded_size_of(),
+            grouped_sizes: events.grouped_size_of(),
         }
     }
 
-    pub const fn new(
+    pub fn new(
         event_count: usize,
         events_byte_size: usize,
         events_estimated_json_encoded_byte_size: usize,
@@ -35,6 +37,7 @@ impl RequestMetadataBuilder {
             event_count,
             events_byte_size,
             events_estimated_json_encoded_byte_size,
+            grouped_sizes: GroupedCountByteSize::default(),
         }
     }
 
@@ -43,27 +46,46 @@ impl RequestMetadataBuilder {
         self.events_byte_size += events_byte_size;
     }
 
-    pub fn with_request_size(&self, size: NonZeroUsize) -> RequestMetadata {
-        let size = size.get();
+    /// Adds an event to the request, as it's encoded into its payload.
+    pub fn track_event<E>(&mut self, event: E)
+    where
+        E: ByteSizeOf + EstimatedJsonEncodedSizeOf + GroupedSizeOf,
+    {
+        self.event_count += 1;
+        self.events_byte_size += event.size_of();
+        self.events_estimated_json_encoded_byte_size += event.estimated_json_encoded_size_of();
+        event.add_grouped_sizes(&mut self.grouped_sizes);
+    }
 
+    /// Builds the metadata of a request whose payload is `request_encoded_size` bytes before
+    /// compression, and `request_wire_size` bytes after.
+    pub fn with_request_sizes(
+        &self,
+        request_encoded_size: usize,
+        request_wire_size: usize,
+    ) -> RequestMetadata {
         RequestMetadata::new(
             self.event_count,
             self.events_byte_size,
-            size,
-            size,
+            request_encoded_size,
+            request_wire_size,
             self.events_estimated_json_encoded_byte_size,
         )
+        .with_grouped_sizes(self.grouped_sizes.clone())
+    }
+
+    pub fn with_request_size(&self, size: NonZeroUsize) -> RequestMetadata {
+        let size = size.get();
+
+        self.with_request_sizes(size, size)
     }
 
     pub fn build<T>(&self, result: &EncodeResult<T>) -> RequestMetadata {
-        RequestMetadata::new(
-            self.event_count,
-            self.events_byte_size,
+        self.with_request_sizes(
             result.uncompressed_byte_size,
             result
                 .compressed_byte_size
                 .unwrap_or(result.uncompressed_byte_size),
-            self.events_estimated_json_encoded_byte_size,
         )
     }
 }
@@ -1,7 +1,8 @@
 use serde::Serialize;
+use vector_common::request_metadata::GroupedCountByteSize;
 use vector_core::{
     event::{EventFinalizers, Finalizable, LogEvent, MaybeAsLogMut},
-    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+    ByteSizeOf, EstimatedJsonEncodedSizeOf, GroupedSizeOf,
 };
 
 /// An event alongside metadata from preprocessing. This is useful for sinks
@@ -48,3 +49,12 @@ where
         self.event.estimated_json_encoded_size_of()
     }
 }
+
+impl<E, M> GroupedSizeOf for ProcessedEvent<E, M>
+where
+    E: GroupedSizeOf,
+{
+    fn add_grouped_sizes(&self, grouped_sizes: &mut GroupedCountByteSize) {
+        self.event.add_grouped_sizes(grouped_sizes);
+    }
+}
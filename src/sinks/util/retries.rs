@@ -11,7 +11,7 @@ use futures::FutureExt;
 use tokio::time::{sleep, Sleep};
 use tower::{retry::Policy, timeout::error::Elapsed};
 
-use crate::Error;
+use crate::{sinks::util::service::CircuitBreakerOpen, Error};
 
 pub enum RetryAction {
     /// Indicate that this request should be retried with a reason
@@ -146,6 +146,13 @@ where
                         internal_log_rate_limit = true
                     );
                     Some(self.build_retry())
+                } else if error.downcast_ref::<CircuitBreakerOpen>().is_some() {
+                    warn!(
+                        message =
+                            "Circuit breaker is open; retrying the request after backing off.",
+                        internal_log_rate_limit = true
+                    );
+                    Some(self.build_retry())
                 } else {
                     error!(
                         message = "Unexpected error type; dropping the request.",
@@ -361,6 +368,34 @@ mod tests {
         assert_eq!(fut.await.unwrap(), "world");
     }
 
+    #[tokio::test]
+    async fn circuit_breaker_open_retry() {
+        trace_init();
+
+        time::pause();
+
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        );
+
+        let (mut svc, mut handle) = mock::spawn_layer(RetryLayer::new(policy));
+
+        assert_ready_ok!(svc.poll_ready());
+
+        let mut fut = task::spawn(svc.call("hello"));
+        assert_request_eq!(handle, "hello").send_error(CircuitBreakerOpen);
+        assert_pending!(fut.poll());
+
+        time::advance(Duration::from_secs(2)).await;
+        assert_pending!(fut.poll());
+
+        assert_request_eq!(handle, "hello").send_response("world");
+        assert_eq!(fut.await.unwrap(), "world");
+    }
+
     #[test]
     fn backoff_grows_to_max() {
         let mut policy = FixedRetryPolicy::new(
@@ -14,6 +14,7 @@ use tower::{
 use vector_config::configurable_component;
 
 pub use crate::sinks::util::service::{
+    circuit_breaker::{CircuitBreakerConfig, CircuitBreakerOpen},
     concurrency::{concurrency_is_none, Concurrency},
     health::{HealthConfig, HealthLogic, HealthService},
     map::Map,
@@ -23,21 +24,35 @@ use crate::{
     sinks::util::{
         adaptive_concurrency::{
             AdaptiveConcurrencyLimit, AdaptiveConcurrencyLimitLayer, AdaptiveConcurrencySettings,
+            ConcurrencyPartition, PartitionedAdaptiveConcurrencyLimit,
+            PartitionedAdaptiveConcurrencyLimitLayer,
         },
         retries::{FixedRetryPolicy, RetryLogic},
-        service::map::MapLayer,
+        service::{
+            circuit_breaker::{CircuitBreaker, CircuitBreakerLayer},
+            map::MapLayer,
+        },
         sink::Response,
         Batch, BatchSink, Partition, PartitionBatchSink,
     },
 };
 
+mod circuit_breaker;
 mod concurrency;
 mod health;
 mod map;
 
-pub type Svc<S, L> = RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>;
+pub type Svc<S, L> = RateLimit<
+    AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, CircuitBreaker<Timeout<S>, L>>, L>,
+>;
 pub type TowerBatchedSink<S, B, RL> = BatchSink<Svc<S, RL>, B>;
 pub type TowerPartitionSink<S, B, RL, K> = PartitionBatchSink<Svc<S, RL>, B, K>;
+pub type PartitionedSvc<S, L> = RateLimit<
+    PartitionedAdaptiveConcurrencyLimit<
+        Retry<FixedRetryPolicy<L>, CircuitBreaker<Timeout<S>, L>>,
+        L,
+    >,
+>;
 
 // Distributed service types
 pub type DistributedService<S, RL, HL, K, Req> = RateLimit<
@@ -46,7 +61,7 @@ pub type DistributedService<S, RL, HL, K, Req> = RateLimit<
 pub type DiscoveryService<S, RL, HL, K> =
     BoxStream<'static, Result<Change<K, SingleDistributedService<S, RL, HL>>, crate::Error>>;
 pub type SingleDistributedService<S, RL, HL> =
-    AdaptiveConcurrencyLimit<HealthService<Timeout<S>, HL>, RL>;
+    AdaptiveConcurrencyLimit<HealthService<CircuitBreaker<Timeout<S>, RL>, HL>, RL>;
 
 pub trait ServiceBuilderExt<L> {
     fn map<R1, R2, F>(self, f: F) -> ServiceBuilder<Stack<MapLayer<R1, R2>, L>>
@@ -58,6 +73,14 @@ pub trait ServiceBuilderExt<L> {
         settings: TowerRequestSettings,
         retry_logic: RL,
     ) -> ServiceBuilder<Stack<TowerRequestLayer<RL, Request>, L>>;
+
+    /// Like `settings`, with adaptive concurrency limits tracked separately for each partition of
+    /// the requests, as given by `ConcurrencyPartition`.
+    fn partitioned_settings<RL, Request>(
+        self,
+        settings: TowerRequestSettings,
+        retry_logic: RL,
+    ) -> ServiceBuilder<Stack<TowerPartitionedRequestLayer<RL, Request>, L>>;
 }
 
 impl<L> ServiceBuilderExt<L> for ServiceBuilder<L> {
@@ -79,6 +102,18 @@ impl<L> ServiceBuilderExt<L> for ServiceBuilder<L> {
             _pd: std::marker::PhantomData,
         })
     }
+
+    fn partitioned_settings<RL, Request>(
+        self,
+        settings: TowerRequestSettings,
+        retry_logic: RL,
+    ) -> ServiceBuilder<Stack<TowerPartitionedRequestLayer<RL, Request>, L>> {
+        self.layer(TowerPartitionedRequestLayer {
+            settings,
+            retry_logic,
+            _pd: std::marker::PhantomData,
+        })
+    }
 }
 
 /// Middleware settings for outbound requests.
@@ -120,6 +155,10 @@ pub struct TowerRequestConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerConfig,
 }
 
 pub const CONCURRENCY_DEFAULT: Concurrency = Concurrency::None;
@@ -147,6 +186,7 @@ impl TowerRequestConfig {
             retry_max_duration_secs: Some(RETRY_MAX_DURATION_SECONDS_DEFAULT),
             retry_initial_backoff_secs: Some(RETRY_INITIAL_BACKOFF_SECONDS_DEFAULT),
             adaptive_concurrency: AdaptiveConcurrencySettings::const_default(),
+            circuit_breaker: CircuitBreakerConfig::const_default(),
         }
     }
 
@@ -216,6 +256,7 @@ impl TowerRequestConfig {
                     .unwrap_or(RETRY_INITIAL_BACKOFF_SECONDS_DEFAULT),
             ),
             adaptive_concurrency: self.adaptive_concurrency,
+            circuit_breaker: self.circuit_breaker,
         }
     }
 }
@@ -230,6 +271,7 @@ pub struct TowerRequestSettings {
     pub retry_max_duration_secs: Duration,
     pub retry_initial_backoff_secs: Duration,
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
+    pub circuit_breaker: CircuitBreakerConfig,
 }
 
 impl TowerRequestSettings {
@@ -327,6 +369,10 @@ impl TowerRequestSettings {
                         health_config.build(
                             health_logic.clone(),
                             ServiceBuilder::new()
+                                .layer(CircuitBreakerLayer::new(
+                                    settings.circuit_breaker,
+                                    retry_logic.clone(),
+                                ))
                                 .timeout(settings.timeout)
                                 .service(inner),
                             open.clone(),
@@ -380,6 +426,50 @@ where
                 self.retry_logic.clone(),
             ))
             .retry(policy)
+            .layer(CircuitBreakerLayer::new(
+                self.settings.circuit_breaker,
+                self.retry_logic.clone(),
+            ))
+            .timeout(self.settings.timeout)
+            .service(inner)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TowerPartitionedRequestLayer<L, Request> {
+    settings: TowerRequestSettings,
+    retry_logic: L,
+    _pd: PhantomData<Request>,
+}
+
+impl<S, RL, Request> Layer<S> for TowerPartitionedRequestLayer<RL, Request>
+where
+    S: Service<Request> + Clone + Send + 'static,
+    S::Response: Send + 'static,
+    S::Error: Into<crate::Error> + Send + Sync + 'static,
+    S::Future: Send + 'static,
+    RL: RetryLogic<Response = S::Response> + Send + 'static,
+    Request: ConcurrencyPartition + Clone + Send + 'static,
+{
+    type Service = PartitionedSvc<S, RL>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        let policy = self.settings.retry_policy(self.retry_logic.clone());
+        ServiceBuilder::new()
+            .rate_limit(
+                self.settings.rate_limit_num,
+                self.settings.rate_limit_duration,
+            )
+            .layer(PartitionedAdaptiveConcurrencyLimitLayer::new(
+                self.settings.concurrency,
+                self.settings.adaptive_concurrency,
+                self.retry_logic.clone(),
+            ))
+            .retry(policy)
+            .layer(CircuitBreakerLayer::new(
+                self.settings.circuit_breaker,
+                self.retry_logic.clone(),
+            ))
             .timeout(self.settings.timeout)
             .service(inner)
     }
@@ -388,12 +478,13 @@ where
 #[cfg(test)]
 mod tests {
     use std::sync::{
-        atomic::{AtomicBool, Ordering::AcqRel},
+        atomic::{AtomicBool, AtomicUsize, Ordering::AcqRel},
         Arc, Mutex,
     };
 
     use futures::{future, stream, FutureExt, SinkExt, StreamExt};
     use tokio::time::Duration;
+    use tower::ServiceExt;
 
     use super::*;
     use crate::sinks::util::{
@@ -490,6 +581,60 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn distributed_service_breaks_circuit_of_failing_endpoint() {
+        let cfg = TowerRequestConfig {
+            retry_attempts: Some(0),
+            circuit_breaker: CircuitBreakerConfig {
+                enabled: true,
+                minimum_requests: 2,
+                ..CircuitBreakerConfig::default()
+            },
+            ..TowerRequestConfig::default()
+        };
+        let settings = cfg.unwrap_with(&TowerRequestConfig::default());
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let svc = {
+            let calls = Arc::clone(&calls);
+            tower::service_fn(move |_: ()| {
+                calls.fetch_add(1, AcqRel);
+                future::err::<(), _>(std::io::Error::new(std::io::ErrorKind::Other, ""))
+            })
+        };
+
+        let mut service = settings.distributed_service(
+            RetryAlways,
+            vec![("endpoint".to_owned(), svc)],
+            HealthConfig::default(),
+            UnknownHealth,
+        );
+
+        let mut errors = Vec::new();
+        for _ in 0..4 {
+            let error = service.ready().await.unwrap().call(()).await.unwrap_err();
+            errors.push(error);
+        }
+
+        // Once the endpoint failed enough requests, its circuit is open and requests aren't sent.
+        assert_eq!(calls.load(AcqRel), 2);
+        assert!(errors[1].downcast_ref::<std::io::Error>().is_some());
+        assert!(errors[2].downcast_ref::<CircuitBreakerOpen>().is_some());
+        assert!(errors[3].downcast_ref::<CircuitBreakerOpen>().is_some());
+    }
+
+    #[derive(Clone, Debug, Copy)]
+    struct UnknownHealth;
+
+    impl HealthLogic for UnknownHealth {
+        type Error = crate::Error;
+        type Response = ();
+
+        fn is_healthy(&self, _response: &Result<Self::Response, Self::Error>) -> Option<bool> {
+            None
+        }
+    }
+
     #[derive(Clone, Debug, Copy)]
     struct RetryAlways;
 
@@ -0,0 +1,444 @@
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use futures::future::{self, BoxFuture};
+use serde::{de, Deserialize, Deserializer};
+use snafu::Snafu;
+use tokio::time::{Duration, Instant};
+use tower::{timeout::error::Elapsed, Layer, Service};
+use vector_config::configurable_component;
+
+use crate::{
+    emit,
+    http::HttpError,
+    internal_events::{
+        CircuitBreakerRequestRejected, CircuitBreakerState, CircuitBreakerStateChanged,
+    },
+    sinks::util::retries::{RetryAction, RetryLogic},
+};
+
+/// Configuration of the circuit breaker of outbound requests.
+///
+/// While the circuit is open, requests aren't sent to the service that keeps failing, and are
+/// retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+/// passed, a single request is sent to probe the service, and the circuit is closed again if it
+/// succeeds.
+#[configurable_component]
+#[derive(Clone, Copy, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct CircuitBreakerConfig {
+    /// Whether or not the circuit breaker is enabled.
+    pub enabled: bool,
+
+    /// The rate of failed requests at which the circuit is opened.
+    ///
+    /// Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+    /// time out, fail to connect, or have an error or response which would be retried.
+    #[configurable(validation(range(max = 1.0)))]
+    #[serde(deserialize_with = "deserialize_error_rate_threshold")]
+    pub error_rate_threshold: f64,
+
+    /// The minimum number of requests within `window_secs` for the circuit to be opened.
+    pub minimum_requests: u64,
+
+    /// The time window, in seconds, over which the rate of failed requests is measured.
+    ///
+    /// It must be greater than `0`.
+    #[configurable(validation(range(min = 1)))]
+    #[serde(deserialize_with = "deserialize_window_secs")]
+    pub window_secs: u64,
+
+    /// The time, in seconds, the circuit stays open before a request is sent to probe the service.
+    pub open_duration_secs: u64,
+}
+
+impl CircuitBreakerConfig {
+    pub const fn const_default() -> Self {
+        Self {
+            enabled: false,
+            error_rate_threshold: 0.5,
+            minimum_requests: 20,
+            window_secs: 60,
+            open_duration_secs: 30,
+        }
+    }
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+fn deserialize_error_rate_threshold<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let threshold = f64::deserialize(deserializer)?;
+    if threshold > 0.0 && threshold <= 1.0 {
+        Ok(threshold)
+    } else {
+        Err(de::Error::custom(
+            "must be greater than 0 and less than or equal to 1",
+        ))
+    }
+}
+
+fn deserialize_window_secs<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match u64::deserialize(deserializer)? {
+        0 => Err(de::Error::custom("must be greater than 0")),
+        window_secs => Ok(window_secs),
+    }
+}
+
+/// The error of requests that aren't sent, as the circuit is open.
+#[derive(Debug, Snafu)]
+#[snafu(display("Circuit breaker is open; the request was not sent."))]
+pub struct CircuitBreakerOpen;
+
+enum State {
+    Closed {
+        window_start: Instant,
+        requests: u64,
+        failures: u64,
+    },
+    Open {
+        until: Instant,
+    },
+    HalfOpen {
+        probe_started: Option<Instant>,
+    },
+}
+
+struct Breaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<State>,
+}
+
+impl Breaker {
+    fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State::Closed {
+                window_start: Instant::now(),
+                requests: 0,
+                failures: 0,
+            }),
+        }
+    }
+
+    fn open_duration(&self) -> Duration {
+        Duration::from_secs(self.config.open_duration_secs)
+    }
+
+    /// Returns whether a request can be sent, and whether it's a probe, or `None` if the circuit
+    /// is open.
+    fn admit(&self) -> Option<bool> {
+        let now = Instant::now();
+        let mut state = self
+            .state
+            .lock()
+            .expect("Circuit breaker mutex is poisoned");
+        match *state {
+            State::Closed { .. } => Some(false),
+            State::Open { until } if now >= until => {
+                *state = State::HalfOpen {
+                    probe_started: Some(now),
+                };
+                emit!(CircuitBreakerStateChanged {
+                    state: CircuitBreakerState::HalfOpen,
+                    error_rate: None,
+                });
+                Some(true)
+            }
+            State::Open { .. } => None,
+            // Probes that never complete, such as those that are dropped, are replaced.
+            State::HalfOpen {
+                ref mut probe_started,
+            } => match *probe_started {
+                Some(started) if now.duration_since(started) < self.open_duration() => None,
+                _ => {
+                    *probe_started = Some(now);
+                    Some(true)
+                }
+            },
+        }
+    }
+
+    fn record(&self, failed: bool, probe: bool) {
+        let now = Instant::now();
+        let mut state = self
+            .state
+            .lock()
+            .expect("Circuit breaker mutex is poisoned");
+        match *state {
+            State::Closed {
+                ref mut window_start,
+                ref mut requests,
+                ref mut failures,
+            } => {
+                if now.duration_since(*window_start) >= Duration::from_secs(self.config.window_secs)
+                {
+                    *window_start = now;
+                    *requests = 0;
+                    *failures = 0;
+                }
+                *requests += 1;
+                *failures += u64::from(failed);
+
+                let error_rate = *failures as f64 / *requests as f64;
+                if *requests >= self.config.minimum_requests
+                    && error_rate >= self.config.error_rate_threshold
+                {
+                    *state = State::Open {
+                        until: now + self.open_duration(),
+                    };
+                    emit!(CircuitBreakerStateChanged {
+                        state: CircuitBreakerState::Open,
+                        error_rate: Some(error_rate),
+                    });
+                }
+            }
+            // Only the outcome of probes decides whether the service recovered.
+            State::HalfOpen { .. } if probe => {
+                if failed {
+                    *state = State::Open {
+                        until: now + self.open_duration(),
+                    };
+                    emit!(CircuitBreakerStateChanged {
+                        state: CircuitBreakerState::Open,
+                        error_rate: None,
+                    });
+                } else {
+                    *state = State::Closed {
+                        window_start: now,
+                        requests: 0,
+                        failures: 0,
+                    };
+                    emit!(CircuitBreakerStateChanged {
+                        state: CircuitBreakerState::Closed,
+                        error_rate: None,
+                    });
+                }
+            }
+            State::HalfOpen { .. } | State::Open { .. } => {}
+        }
+    }
+}
+
+/// Returns whether the request failed because of the service, as opposed to the request itself.
+fn is_failure<L: RetryLogic>(logic: &L, result: &Result<L::Response, crate::Error>) -> bool {
+    match result {
+        Ok(response) => matches!(logic.should_retry_response(response), RetryAction::Retry(_)),
+        Err(error) => {
+            if let Some(error) = error.downcast_ref::<L::Error>() {
+                logic.is_retriable_error(error)
+            } else {
+                error.downcast_ref::<Elapsed>().is_some()
+                    || error.downcast_ref::<HttpError>().is_some()
+            }
+        }
+    }
+}
+
+/// Fails requests without sending them while the underlying service keeps failing.
+///
+/// The state of the circuit is shared by all clones of the service.
+#[derive(Clone)]
+pub struct CircuitBreaker<S, L> {
+    inner: S,
+    logic: L,
+    breaker: Option<Arc<Breaker>>,
+}
+
+impl<S, L, Request> Service<Request> for CircuitBreaker<S, L>
+where
+    S: Service<Request> + 'static,
+    S::Response: Send + 'static,
+    S::Error: Into<crate::Error>,
+    S::Future: Send + 'static,
+    L: RetryLogic<Response = S::Response>,
+    Request: 'static,
+{
+    type Response = S::Response;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let breaker = match &self.breaker {
+            Some(breaker) => Arc::clone(breaker),
+            None => {
+                let future = self.inner.call(request);
+                return Box::pin(async move {
+                    let result: Result<S::Response, crate::Error> =
+                        future.await.map_err(Into::into);
+                    result
+                });
+            }
+        };
+        let probe = match breaker.admit() {
+            Some(probe) => probe,
+            None => {
+                emit!(CircuitBreakerRequestRejected);
+                let error: crate::Error = CircuitBreakerOpen.into();
+                return Box::pin(future::err(error));
+            }
+        };
+
+        let logic = self.logic.clone();
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let result = future.await.map_err(Into::into);
+            breaker.record(is_failure(&logic, &result), probe);
+            result
+        })
+    }
+}
+
+/// Fails requests without sending them while the underlying service keeps failing.
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerLayer<L> {
+    config: CircuitBreakerConfig,
+    logic: L,
+}
+
+impl<L> CircuitBreakerLayer<L> {
+    pub const fn new(config: CircuitBreakerConfig, logic: L) -> Self {
+        Self { config, logic }
+    }
+}
+
+impl<S, L: RetryLogic> Layer<S> for CircuitBreakerLayer<L> {
+    type Service = CircuitBreaker<S, L>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        // The state of the circuit is shared by the clones of the service, which are made for
+        // each retry and for each partition of a sink.
+        let breaker = self
+            .config
+            .enabled
+            .then(|| Arc::new(Breaker::new(self.config)));
+        CircuitBreaker {
+            inner,
+            logic: self.logic.clone(),
+            breaker,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::time::{advance, pause};
+    use tokio_test::assert_ready_ok;
+    use tower_test::{assert_request_eq, mock};
+
+    use super::*;
+    use crate::assert_downcast_matches;
+
+    #[derive(Clone, Copy, Debug)]
+    struct TestRetryLogic;
+    impl RetryLogic for TestRetryLogic {
+        type Error = std::io::Error;
+        type Response = String;
+        fn is_retriable_error(&self, _error: &Self::Error) -> bool {
+            true
+        }
+    }
+
+    fn layer() -> CircuitBreakerLayer<TestRetryLogic> {
+        CircuitBreakerLayer::new(
+            CircuitBreakerConfig {
+                enabled: true,
+                error_rate_threshold: 0.5,
+                minimum_requests: 2,
+                window_secs: 60,
+                open_duration_secs: 10,
+            },
+            TestRetryLogic,
+        )
+    }
+
+    fn error() -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::Other, "unavailable")
+    }
+
+    #[tokio::test]
+    async fn opens_on_error_rate() {
+        pause();
+        let (mut service, mut handle) = mock::spawn_layer::<String, String, _>(layer());
+
+        for _ in 0..2 {
+            assert_ready_ok!(service.poll_ready());
+            let response = service.call("request".into());
+            assert_request_eq!(handle, "request").send_error(error());
+            assert!(response.await.is_err());
+        }
+
+        // Requests fail without being sent while the circuit is open.
+        assert_ready_ok!(service.poll_ready());
+        assert_downcast_matches!(
+            service.call("request".into()).await.unwrap_err(),
+            CircuitBreakerOpen,
+            CircuitBreakerOpen
+        );
+
+        // Once the circuit half-opens, a successful probe closes it again.
+        advance(Duration::from_secs(10)).await;
+        assert_ready_ok!(service.poll_ready());
+        let probe = service.call("probe".into());
+        assert_ready_ok!(service.poll_ready());
+        assert_downcast_matches!(
+            service.call("request".into()).await.unwrap_err(),
+            CircuitBreakerOpen,
+            CircuitBreakerOpen
+        );
+        assert_request_eq!(handle, "probe").send_response("ok".into());
+        assert_eq!(probe.await.unwrap(), "ok");
+
+        assert_ready_ok!(service.poll_ready());
+        let response = service.call("request".into());
+        assert_request_eq!(handle, "request").send_response("ok".into());
+        assert_eq!(response.await.unwrap(), "ok");
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        for config in [
+            "error_rate_threshold = 0.0",
+            "error_rate_threshold = 1.5",
+            "window_secs = 0",
+        ] {
+            assert!(
+                toml::from_str::<CircuitBreakerConfig>(config).is_err(),
+                "{}",
+                config
+            );
+        }
+        let config: CircuitBreakerConfig =
+            toml::from_str("error_rate_threshold = 1.0\nwindow_secs = 1").unwrap();
+        assert_eq!(config.window_secs, 1);
+    }
+
+    #[tokio::test]
+    async fn disabled_passes_requests() {
+        let (mut service, mut handle) = mock::spawn_layer::<String, String, _>(
+            CircuitBreakerLayer::new(CircuitBreakerConfig::default(), TestRetryLogic),
+        );
+
+        for _ in 0..30 {
+            assert_ready_ok!(service.poll_ready());
+            let response = service.call("request".into());
+            assert_request_eq!(handle, "request").send_error(error());
+            assert!(response.await.is_err());
+        }
+    }
+}
@@ -40,13 +40,9 @@ pub struct VectorConfig {
     /// The address _must_ include a port.
     address: String,
 
-    /// Whether or not to compress requests.
-    ///
-    /// If set to `true`, requests will be compressed with [`gzip`][gzip_docs].
-    ///
-    /// [gzip_docs]: https://www.gzip.org/
+    #[configurable(derived)]
     #[serde(default)]
-    compression: bool,
+    compression: VectorCompression,
 
     #[configurable(derived)]
     #[serde(default)]
@@ -69,6 +65,64 @@ pub struct VectorConfig {
     pub(in crate::sinks::vector) acknowledgements: AcknowledgementsConfig,
 }
 
+/// Compression of the requests sent to the downstream Vector instance.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum VectorCompression {
+    /// Whether or not to compress requests.
+    ///
+    /// If set to `true`, requests will be compressed with [`gzip`][gzip_docs].
+    ///
+    /// [gzip_docs]: https://www.gzip.org/
+    Enabled(bool),
+
+    /// The algorithm to compress requests with.
+    Algorithm(#[configurable(derived)] VectorCompressionAlgorithm),
+}
+
+impl Default for VectorCompression {
+    fn default() -> Self {
+        Self::Enabled(false)
+    }
+}
+
+impl VectorCompression {
+    const fn algorithm(self) -> Option<VectorCompressionAlgorithm> {
+        match self {
+            Self::Enabled(false) | Self::Algorithm(VectorCompressionAlgorithm::None) => None,
+            Self::Enabled(true) | Self::Algorithm(VectorCompressionAlgorithm::Gzip) => {
+                Some(VectorCompressionAlgorithm::Gzip)
+            }
+            Self::Algorithm(VectorCompressionAlgorithm::Zstd) => {
+                Some(VectorCompressionAlgorithm::Zstd)
+            }
+        }
+    }
+}
+
+/// Compression algorithm.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum VectorCompressionAlgorithm {
+    /// No compression.
+    None,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// The downstream Vector instance must be running a version of Vector supporting it: requests are compressed
+    /// with gzip instead if it doesn't.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
 impl VectorConfig {
     /// Creates a `VectorConfig` with the given address.
     pub fn from_address(addr: Uri) -> Self {
@@ -87,7 +141,7 @@ fn default_config(address: &str) -> VectorConfig {
     VectorConfig {
         version: None,
         address: address.to_owned(),
-        compression: false,
+        compression: VectorCompression::default(),
         batch: BatchConfig::default(),
         request: TowerRequestConfig::default(),
         tls: None,
@@ -109,9 +163,9 @@ impl SinkConfig for VectorConfig {
             .clone()
             .map(|uri| uri.uri)
             .unwrap_or_else(|| uri.clone());
-        let healthcheck_client = VectorService::new(client.clone(), healthcheck_uri, false);
+        let healthcheck_client = VectorService::new(client.clone(), healthcheck_uri, None);
         let healthcheck = healthcheck(healthcheck_client, cx.healthcheck);
-        let service = VectorService::new(client, uri, self.compression);
+        let service = VectorService::new(client, uri, self.compression.algorithm());
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batcher_settings()?;
 
@@ -1,7 +1,19 @@
-use std::task::{Context, Poll};
+use std::{
+    convert::Infallible,
+    io::{self, Write},
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
 
+use bytes::{BufMut, Bytes, BytesMut};
+use flate2::write::GzEncoder;
 use futures::{future::BoxFuture, TryFutureExt};
-use http::Uri;
+use http::{header::HeaderValue, request::Parts, Uri};
+use http_body::Body as _;
 use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
@@ -9,9 +21,9 @@ use prost::Message;
 use tonic::{body::BoxBody, IntoRequest};
 use tower::Service;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
-use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
+use vector_core::stream::DriverResponse;
 
-use super::VectorSinkError;
+use super::{config::VectorCompressionAlgorithm, VectorSinkError};
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     internal_events::EndpointBytesSent,
@@ -27,19 +39,12 @@ pub struct VectorService {
     pub endpoint: String,
 }
 
-pub struct VectorResponse {
-    events_count: usize,
-    events_byte_size: usize,
-}
+pub struct VectorResponse;
 
 impl DriverResponse for VectorResponse {
     fn event_status(&self) -> EventStatus {
         EventStatus::Delivered
     }
-
-    fn events_sent(&self) -> CountByteSize {
-        CountByteSize(self.events_count, self.events_byte_size)
-    }
 }
 
 #[derive(Clone, Default)]
@@ -57,7 +62,7 @@ impl Finalizable for VectorRequest {
 
 impl MetaDescriptive for VectorRequest {
     fn get_metadata(&self) -> RequestMetadata {
-        self.metadata
+        self.metadata.clone()
     }
 }
 
@@ -65,17 +70,15 @@ impl VectorService {
     pub fn new(
         hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
         uri: Uri,
-        compression: bool,
+        compression: Option<VectorCompressionAlgorithm>,
     ) -> Self {
         let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
-        let mut proto_client = proto_vector::Client::new(HyperSvc {
+        let proto_client = proto_vector::Client::new(HyperSvc {
             uri,
             client: hyper_client,
+            compression: compression.map(RequestCompression::new),
         });
 
-        if compression {
-            proto_client = proto_client.send_compressed(tonic::codec::CompressionEncoding::Gzip);
-        }
         Self {
             client: proto_client,
             protocol,
@@ -103,8 +106,6 @@ impl Service<VectorRequest> for VectorService {
     fn call(&mut self, list: VectorRequest) -> Self::Future {
         let mut service = self.clone();
         let byte_size = list.request.encoded_len();
-        let events_count = list.get_metadata().event_count();
-        let events_byte_size = list.get_metadata().events_byte_size();
 
         let future = async move {
             service
@@ -116,10 +117,7 @@ impl Service<VectorRequest> for VectorService {
                         protocol: &service.protocol,
                         endpoint: &service.endpoint,
                     });
-                    VectorResponse {
-                        events_count,
-                        events_byte_size,
-                    }
+                    VectorResponse
                 })
                 .map_err(|source| VectorSinkError::Request { source }.into())
                 .await
@@ -133,11 +131,12 @@ impl Service<VectorRequest> for VectorService {
 pub struct HyperSvc {
     uri: Uri,
     client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+    compression: Option<RequestCompression>,
 }
 
 impl Service<hyper::Request<BoxBody>> for HyperSvc {
     type Response = hyper::Response<hyper::Body>;
-    type Error = hyper::Error;
+    type Error = Error;
     type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
     // Emission of an internal event in case of errors is handled upstream by the caller.
@@ -156,6 +155,270 @@ impl Service<hyper::Request<BoxBody>> for HyperSvc {
 
         *req.uri_mut() = uri;
 
-        Box::pin(self.client.request(req))
+        match self.compression.clone() {
+            None => Box::pin(self.client.request(req).err_into()),
+            Some(compression) => Box::pin(compression.send(self.client.clone(), req)),
+        }
+    }
+}
+
+// Every gRPC message has a five byte header:
+// - a compressed flag (u8, 0/1 for compressed/decompressed)
+// - a length prefix, indicating the number of remaining bytes to read (u32)
+const GRPC_MESSAGE_HEADER_LEN: usize = mem::size_of::<u8>() + mem::size_of::<u32>();
+const GRPC_STATUS_HEADER: &str = "grpc-status";
+const GRPC_ENCODING_HEADER: &str = "grpc-encoding";
+const GRPC_ACCEPT_ENCODING_HEADER: &str = "grpc-accept-encoding";
+const GRPC_STATUS_UNIMPLEMENTED: &str = "12";
+
+/// Compresses the gRPC messages of the requests.
+///
+/// `tonic` only supports gzip, so the messages are compressed here instead. A downstream Vector instance not
+/// supporting zstd rejects the requests compressed with it, listing the schemes it supports: the request is then sent
+/// again compressed with gzip, as are all the following ones.
+#[derive(Clone, Debug)]
+struct RequestCompression {
+    algorithm: VectorCompressionAlgorithm,
+    zstd_unsupported: Arc<AtomicBool>,
+}
+
+impl RequestCompression {
+    fn new(algorithm: VectorCompressionAlgorithm) -> Self {
+        Self {
+            algorithm,
+            zstd_unsupported: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn algorithm(&self) -> VectorCompressionAlgorithm {
+        match self.algorithm {
+            VectorCompressionAlgorithm::Zstd if self.zstd_unsupported.load(Ordering::Relaxed) => {
+                VectorCompressionAlgorithm::Gzip
+            }
+            algorithm => algorithm,
+        }
+    }
+
+    async fn send(
+        self,
+        client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+        req: hyper::Request<BoxBody>,
+    ) -> Result<hyper::Response<hyper::Body>, Error> {
+        let (parts, body) = req.into_parts();
+        let body = hyper::body::to_bytes(body).await?;
+
+        let algorithm = self.algorithm();
+        let response = client
+            .request(compressed_request(&parts, &body, algorithm)?)
+            .await?;
+
+        if algorithm == VectorCompressionAlgorithm::Zstd && rejects_zstd(&response) {
+            warn!(
+                message = "Downstream Vector instance does not support zstd compression, falling back to gzip.",
+                internal_log_rate_limit = true
+            );
+            self.zstd_unsupported.store(true, Ordering::Relaxed);
+
+            let request = compressed_request(&parts, &body, VectorCompressionAlgorithm::Gzip)?;
+            return Ok(client.request(request).await?);
+        }
+
+        Ok(response)
+    }
+}
+
+/// Whether `response` rejects a request because it was compressed with zstd.
+fn rejects_zstd(response: &hyper::Response<hyper::Body>) -> bool {
+    let headers = response.headers();
+    let unimplemented = headers
+        .get(GRPC_STATUS_HEADER)
+        .map_or(false, |status| status == GRPC_STATUS_UNIMPLEMENTED);
+    let accepts_zstd = headers
+        .get(GRPC_ACCEPT_ENCODING_HEADER)
+        .and_then(|encodings| encodings.to_str().ok())
+        .map_or(true, |encodings| {
+            encodings
+                .split(',')
+                .any(|encoding| encoding.trim() == "zstd")
+        });
+
+    unimplemented && !accepts_zstd
+}
+
+fn compressed_request(
+    parts: &Parts,
+    body: &Bytes,
+    algorithm: VectorCompressionAlgorithm,
+) -> io::Result<hyper::Request<BoxBody>> {
+    let body = http_body::Full::new(compress_messages(body, algorithm)?)
+        .map_err(|never: Infallible| -> tonic::Status { match never {} })
+        .boxed_unsync();
+
+    let mut request = hyper::Request::new(body);
+    *request.method_mut() = parts.method.clone();
+    *request.uri_mut() = parts.uri.clone();
+    *request.version_mut() = parts.version;
+    *request.headers_mut() = parts.headers.clone();
+    if let Some(encoding) = algorithm.grpc_encoding() {
+        request
+            .headers_mut()
+            .insert(GRPC_ENCODING_HEADER, HeaderValue::from_static(encoding));
+    }
+
+    Ok(request)
+}
+
+/// Compresses each gRPC message of `body` with `algorithm`.
+fn compress_messages(body: &Bytes, algorithm: VectorCompressionAlgorithm) -> io::Result<Bytes> {
+    if algorithm.grpc_encoding().is_none() {
+        return Ok(body.clone());
+    }
+
+    let mut compressed = BytesMut::with_capacity(body.len());
+    let mut remaining = &body[..];
+    while !remaining.is_empty() {
+        if remaining.len() < GRPC_MESSAGE_HEADER_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "truncated gRPC message header",
+            ));
+        }
+        let (header, rest) = remaining.split_at(GRPC_MESSAGE_HEADER_LEN);
+        let message_len = header[1..]
+            .try_into()
+            .map(u32::from_be_bytes)
+            .expect("there must be four bytes remaining in the header slice")
+            as usize;
+        if rest.len() < message_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "truncated gRPC message",
+            ));
+        }
+        let (message, rest) = rest.split_at(message_len);
+
+        let message = algorithm.compress(message)?;
+        let message_len = u32::try_from(message.len()).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "messages greater than 4GB are not supported",
+            )
+        })?;
+        compressed.put_u8(1);
+        compressed.put_u32(message_len);
+        compressed.put_slice(&message);
+
+        remaining = rest;
+    }
+
+    Ok(compressed.freeze())
+}
+
+impl VectorCompressionAlgorithm {
+    const fn grpc_encoding(self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::Gzip => Some("gzip"),
+            Self::Zstd => Some("zstd"),
+        }
+    }
+
+    fn compress(self, message: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(message.to_vec()),
+            Self::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(message)?;
+                encoder.finish()
+            }
+            Self::Zstd => zstd::stream::encode_all(message, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+
+    fn message(payload: &[u8]) -> Vec<u8> {
+        let mut message = vec![0];
+        message.extend((payload.len() as u32).to_be_bytes());
+        message.extend(payload);
+        message
+    }
+
+    fn decompressed_messages(
+        mut body: &[u8],
+        decompress: impl Fn(&[u8]) -> Vec<u8>,
+    ) -> Vec<Vec<u8>> {
+        let mut messages = Vec::new();
+        while !body.is_empty() {
+            assert_eq!(body[0], 1);
+            let len = u32::from_be_bytes(body[1..GRPC_MESSAGE_HEADER_LEN].try_into().unwrap());
+            let end = GRPC_MESSAGE_HEADER_LEN + len as usize;
+            messages.push(decompress(&body[GRPC_MESSAGE_HEADER_LEN..end]));
+            body = &body[end..];
+        }
+        messages
+    }
+
+    #[test]
+    fn compresses_messages_with_gzip() {
+        let body = Bytes::from([message(b"first"), message(b"second")].concat());
+
+        let compressed = compress_messages(&body, VectorCompressionAlgorithm::Gzip).unwrap();
+        let messages = decompressed_messages(&compressed, |message| {
+            let mut decompressed = Vec::new();
+            GzDecoder::new(message)
+                .read_to_end(&mut decompressed)
+                .unwrap();
+            decompressed
+        });
+
+        assert_eq!(messages, vec![b"first".to_vec(), b"second".to_vec()]);
+    }
+
+    #[test]
+    fn compresses_messages_with_zstd() {
+        let body = Bytes::from([message(b"first"), message(b"second")].concat());
+
+        let compressed = compress_messages(&body, VectorCompressionAlgorithm::Zstd).unwrap();
+        let messages = decompressed_messages(&compressed, |message| {
+            zstd::stream::decode_all(message).unwrap()
+        });
+
+        assert_eq!(messages, vec![b"first".to_vec(), b"second".to_vec()]);
+    }
+
+    #[test]
+    fn rejects_truncated_messages() {
+        let mut body = message(b"first");
+        body.truncate(body.len() - 1);
+
+        assert!(compress_messages(&Bytes::from(body), VectorCompressionAlgorithm::Zstd).is_err());
+    }
+
+    #[test]
+    fn detects_zstd_rejection() {
+        let response = |encodings: &str| {
+            hyper::Response::builder()
+                .header(GRPC_STATUS_HEADER, GRPC_STATUS_UNIMPLEMENTED)
+                .header(GRPC_ACCEPT_ENCODING_HEADER, encodings)
+                .body(hyper::Body::empty())
+                .unwrap()
+        };
+
+        assert!(rejects_zstd(&response("gzip,identity")));
+        assert!(!rejects_zstd(&response("gzip,zstd,identity")));
+
+        let ok = hyper::Response::builder()
+            .header(GRPC_STATUS_HEADER, "0")
+            .body(hyper::Body::empty())
+            .unwrap();
+        assert!(!rejects_zstd(&ok));
     }
 }
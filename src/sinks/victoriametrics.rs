@@ -0,0 +1,468 @@
+//! The `victoriametrics` sink.
+//!
+//! Batches of metrics are sent to the JSON line import endpoint of VictoriaMetrics, which ingests
+//! them with much less overhead than Prometheus remote write requests.
+
+use std::io::Write as _;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use chrono::Utc;
+use futures::{FutureExt, SinkExt};
+use http::{uri::PathAndQuery, Request, Uri};
+use hyper::Body;
+use indexmap::IndexMap;
+use vector_config::configurable_component;
+use vector_core::event::metric::{MetricTags, MetricValue};
+
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    event::Event,
+    http::{Auth, HttpClient, MaybeAuth},
+    sinks::{
+        prometheus::{
+            collector::MetricCollector, default_histogram_buckets, default_summary_quantiles,
+            remote_write::PrometheusMetricNormalize,
+        },
+        util::{
+            buffer::metrics::MetricNormalizer,
+            http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
+            BatchConfig, Buffer, Compression, SinkBatchSettings, TowerRequestConfig, UriSerde,
+        },
+        Healthcheck, HealthcheckError, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Compression of the batches sent to VictoriaMetrics.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VictoriaMetricsCompression {
+    /// Batches are not compressed.
+    None,
+
+    /// Batches are compressed with [Gzip][gzip].
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// Batches are compressed with [Zstandard][zstd], which compresses them better than Gzip, at a
+    /// lower cost.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    #[default]
+    Zstd,
+}
+
+impl VictoriaMetricsCompression {
+    const fn content_encoding(self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::Gzip => Some("gzip"),
+            Self::Zstd => Some("zstd"),
+        }
+    }
+
+    fn compress(self, body: Bytes) -> std::io::Result<Bytes> {
+        match self {
+            Self::None => Ok(body),
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(&body)?;
+                encoder.finish().map(Into::into)
+            }
+            Self::Zstd => zstd::encode_all(body.as_ref(), 0).map(Into::into),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VictoriaMetricsDefaultBatchSettings;
+
+impl SinkBatchSettings for VictoriaMetricsDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(10_000);
+    const MAX_BYTES: Option<usize> = Some(10_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `victoriametrics` sink.
+#[configurable_component(sink("victoriametrics"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct VictoriaMetricsConfig {
+    /// The endpoint of VictoriaMetrics.
+    ///
+    /// Metrics are sent to the `/api/v1/import` path of this endpoint, which is the address of a
+    /// single-node VictoriaMetrics, or the `/insert/<account_id>/prometheus` path of `vminsert`
+    /// for a cluster.
+    #[configurable(metadata(docs::examples = "http://localhost:8428"))]
+    #[configurable(metadata(docs::examples = "http://vminsert:8480/insert/0/prometheus"))]
+    pub endpoint: UriSerde,
+
+    /// The default namespace for any metrics sent.
+    ///
+    /// This namespace is only used if a metric has no existing namespace. When a namespace is
+    /// present, it is used as a prefix to the metric name, and separated with an underscore (`_`).
+    #[configurable(metadata(docs::examples = "service"))]
+    pub default_namespace: Option<String>,
+
+    /// Default buckets to use for aggregating [distribution][dist_metric_docs] metrics into histograms.
+    ///
+    /// [dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+    #[serde(default = "default_histogram_buckets")]
+    pub buckets: Vec<f64>,
+
+    /// Quantiles to use for aggregating [distribution][dist_metric_docs] metrics into a summary.
+    ///
+    /// [dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+    #[serde(default = "default_summary_quantiles")]
+    pub quantiles: Vec<f64>,
+
+    /// Labels added to every metric by VictoriaMetrics, as `extra_label` query parameters.
+    ///
+    /// They override the tags of metrics of the same names.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "The value of the label."))]
+    pub extra_labels: IndexMap<String, String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: VictoriaMetricsCompression,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<VictoriaMetricsDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for VictoriaMetricsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://localhost:8428""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for VictoriaMetricsConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let batch = self.batch.into_batch_settings()?;
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+
+        let auth = self.auth.choose_one(&self.endpoint.auth)?;
+        auth.ensure_static()?;
+        let endpoint = self.endpoint.with_default_parts();
+        let sink = VictoriaMetricsSink {
+            uri: import_uri(&endpoint, &self.extra_labels)?,
+            auth: auth.clone(),
+            compression: self.compression,
+            default_namespace: self.default_namespace.clone(),
+            buckets: self.buckets.clone(),
+            quantiles: self.quantiles.clone(),
+        };
+
+        let healthcheck = healthcheck(client.clone(), health_uri(&endpoint.uri)?, auth);
+
+        let sink = BatchedHttpSink::new(
+            sink,
+            Buffer::new(batch.size, Compression::None),
+            request,
+            batch.timeout,
+            client,
+        )
+        .sink_map_err(|error| error!(message = "Fatal victoriametrics sink error.", %error));
+
+        Ok((VectorSink::from_event_sink(sink), healthcheck.boxed()))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Builds the URI of the import endpoint, with the extra labels as query parameters.
+fn import_uri(endpoint: &UriSerde, extra_labels: &IndexMap<String, String>) -> crate::Result<Uri> {
+    let uri = endpoint.append_path("api/v1/import")?.uri;
+    if extra_labels.is_empty() {
+        return Ok(uri);
+    }
+
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    for (name, value) in extra_labels {
+        query.append_pair("extra_label", &format!("{}={}", name, value));
+    }
+    Ok(format!("{}?{}", uri, query.finish()).parse()?)
+}
+
+/// Builds the URI of the health endpoint, which is served at the root of the server, whatever the
+/// path of the endpoint.
+fn health_uri(endpoint: &Uri) -> crate::Result<Uri> {
+    let mut parts = endpoint.clone().into_parts();
+    parts.path_and_query = Some(PathAndQuery::from_static("/health"));
+    Ok(Uri::from_parts(parts)?)
+}
+
+struct VictoriaMetricsSink {
+    uri: Uri,
+    auth: Option<Auth>,
+    compression: VictoriaMetricsCompression,
+    default_namespace: Option<String>,
+    buckets: Vec<f64>,
+    quantiles: Vec<f64>,
+}
+
+#[async_trait::async_trait]
+impl HttpSink for VictoriaMetricsSink {
+    type Input = BytesMut;
+    type Output = BytesMut;
+    type Encoder = VictoriaMetricsEncoder;
+
+    fn build_encoder(&self) -> Self::Encoder {
+        VictoriaMetricsEncoder {
+            default_namespace: self.default_namespace.clone(),
+            buckets: self.buckets.clone(),
+            quantiles: self.quantiles.clone(),
+            normalizer: MetricNormalizer::default(),
+        }
+    }
+
+    async fn build_request(&self, lines: Self::Output) -> crate::Result<Request<Bytes>> {
+        let mut builder = Request::post(&self.uri).header("Content-Type", "application/json");
+        if let Some(content_encoding) = self.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", content_encoding);
+        }
+        let mut request = builder.body(self.compression.compress(lines.freeze())?)?;
+
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        Ok(request)
+    }
+}
+
+/// Encodes metrics as lines of the JSON import format, after making them absolute, as
+/// VictoriaMetrics expects counters to be cumulative.
+struct VictoriaMetricsEncoder {
+    default_namespace: Option<String>,
+    buckets: Vec<f64>,
+    quantiles: Vec<f64>,
+    normalizer: MetricNormalizer<PrometheusMetricNormalize>,
+}
+
+impl HttpEventEncoder<BytesMut> for VictoriaMetricsEncoder {
+    fn encode_event(&mut self, event: Event) -> Option<BytesMut> {
+        let metric = self.normalizer.normalize(event.into_metric())?;
+        let mut lines = JsonLines::new();
+        lines.encode_metric(
+            self.default_namespace.as_deref(),
+            &self.buckets,
+            &self.quantiles,
+            &metric,
+        );
+        Some(lines.finish())
+    }
+}
+
+/// Collects the samples of metrics as lines of the JSON import format, each holding a single
+/// sample of a series.
+struct JsonLines {
+    lines: BytesMut,
+}
+
+impl MetricCollector for JsonLines {
+    type Output = BytesMut;
+
+    fn new() -> Self {
+        Self {
+            lines: BytesMut::new(),
+        }
+    }
+
+    fn emit_metadata(&mut self, _name: &str, _fullname: &str, _value: &MetricValue) {}
+
+    fn emit_value(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        suffix: &str,
+        value: f64,
+        tags: Option<&MetricTags>,
+        extra: Option<(&str, String)>,
+    ) {
+        let mut labels = serde_json::Map::new();
+        labels.insert("__name__".into(), format!("{}{}", name, suffix).into());
+        for (key, value) in tags.into_iter().flat_map(MetricTags::iter_single) {
+            labels.insert(key.into(), value.into());
+        }
+        if let Some((key, value)) = extra {
+            labels.insert(key.into(), value.into());
+        }
+
+        let line = serde_json::json!({
+            "metric": labels,
+            "values": [value],
+            "timestamps": [timestamp_millis.unwrap_or_else(|| Utc::now().timestamp_millis())],
+        });
+        serde_json::to_writer((&mut self.lines).writer(), &line).expect("lines should serialize");
+        self.lines.put_u8(b'\n');
+    }
+
+    fn finish(self) -> BytesMut {
+        self.lines
+    }
+}
+
+async fn healthcheck(client: HttpClient, uri: Uri, auth: Option<Auth>) -> crate::Result<()> {
+    let mut request = Request::get(uri).body(Body::empty())?;
+    if let Some(auth) = &auth {
+        auth.apply(&mut request);
+    }
+
+    let response = client.send(request).await?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(HealthcheckError::UnexpectedStatus { status }.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use chrono::TimeZone;
+    use futures::stream;
+    use vector_core::{
+        event::{Metric, MetricKind},
+        metric_tags,
+    };
+
+    use super::*;
+    use crate::test_util::{
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+        http::spawn_blackhole_http_server,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<VictoriaMetricsConfig>();
+    }
+
+    fn counter(value: f64) -> Event {
+        Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value },
+        )
+        .with_namespace(Some("app"))
+        .with_tags(Some(metric_tags!("host" => "example.com")))
+        .with_timestamp(Some(Utc.ymd(2023, 1, 2).and_hms(3, 4, 5)))
+        .into()
+    }
+
+    #[test]
+    fn encodes_absolute_counters() {
+        let mut encoder = VictoriaMetricsSink {
+            uri: Uri::from_static("http://localhost:8428/api/v1/import"),
+            auth: None,
+            compression: VictoriaMetricsCompression::None,
+            default_namespace: None,
+            buckets: default_histogram_buckets(),
+            quantiles: default_summary_quantiles(),
+        }
+        .build_encoder();
+
+        let _ = encoder.encode_event(counter(1.0));
+        let line = encoder.encode_event(counter(2.0)).unwrap();
+
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&line).unwrap(),
+            serde_json::json!({
+                "metric": {"__name__": "app_requests", "host": "example.com"},
+                "values": [3.0],
+                "timestamps": [1_672_628_645_000_i64],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_oauth2_auth() {
+        let config: VictoriaMetricsConfig = toml::from_str(
+            r#"endpoint = "http://localhost:8428"
+            auth.strategy = "oauth2"
+            auth.token_endpoint = "http://localhost:8080/token"
+            auth.client_id = "vector"
+            auth.client_secret = "secret""#,
+        )
+        .unwrap();
+
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn sends_compressed_batches_with_extra_labels() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let endpoint = spawn_blackhole_http_server(move |request| {
+            let tx = tx.clone();
+            async move {
+                let uri = request.uri().clone();
+                let encoding = request.headers()["Content-Encoding"].clone();
+                let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                tx.send((uri, encoding, body)).unwrap();
+                Ok::<_, Infallible>(
+                    hyper::Response::builder()
+                        .status(204)
+                        .body(hyper::Body::empty())
+                        .unwrap(),
+                )
+            }
+        })
+        .await;
+
+        let config: VictoriaMetricsConfig = toml::from_str(&format!(
+            r#"endpoint = "{}"
+            extra_labels.env = "prod""#,
+            endpoint
+        ))
+        .unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        run_and_assert_sink_compliance(
+            sink,
+            stream::iter(vec![counter(1.0), counter(2.0)]),
+            &HTTP_SINK_TAGS,
+        )
+        .await;
+
+        let (uri, encoding, body) = rx.recv().await.unwrap();
+        assert_eq!(uri.path(), "/api/v1/import");
+        assert_eq!(uri.query(), Some("extra_label=env%3Dprod"));
+        assert_eq!(encoding, "zstd");
+        let lines = zstd::decode_all(body.as_ref()).unwrap();
+        assert_eq!(String::from_utf8(lines).unwrap().lines().count(), 2);
+    }
+}
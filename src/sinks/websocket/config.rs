@@ -5,7 +5,7 @@ use vector_config::configurable_component;
 use crate::{
     codecs::EncodingConfig,
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
-    http::Auth,
+    http::{Auth, MaybeAuth},
     sinks::{
         websocket::sink::{ConnectSnafu, WebSocketConnector, WebSocketError, WebSocketSink},
         Healthcheck, VectorSink,
@@ -66,6 +66,7 @@ impl GenerateConfig for WebSocketSinkConfig {
 #[async_trait::async_trait]
 impl SinkConfig for WebSocketSinkConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        self.auth.ensure_static()?;
         let connector = self.build_connector()?;
         let ws_sink = WebSocketSink::new(self, connector.clone())?;
 
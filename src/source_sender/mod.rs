@@ -46,6 +46,17 @@ impl Builder {
         }
     }
 
+    /// Disables the histogram of the lag time of events, which is only meaningful for the events
+    /// of sources.
+    // https://github.com/rust-lang/rust/issues/73255
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn without_lag_time(self) -> Self {
+        Self {
+            lag_time: None,
+            ..self
+        }
+    }
+
     pub fn add_output(&mut self, output: Output) -> LimitedReceiver<EventArray> {
         match output.port {
             None => {
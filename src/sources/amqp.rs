@@ -19,7 +19,13 @@ use chrono::{TimeZone, Utc};
 use codecs::decoding::{DeserializerConfig, FramingConfig};
 use futures::{FutureExt, StreamExt};
 use futures_util::Stream;
-use lapin::{acker::Acker, message::Delivery, Channel};
+use lapin::{
+    acker::Acker,
+    message::Delivery,
+    options::{BasicQosOptions, BasicRejectOptions},
+    types::AMQPValue,
+    Channel,
+};
 use lookup::{lookup_v2::OptionalValuePath, metadata_path, owned_value_path, path, PathPrefix};
 use snafu::Snafu;
 use std::{io::Cursor, pin::Pin};
@@ -44,8 +50,13 @@ enum BuildError {
     },
     #[snafu(display("Could not subscribe to AMQP queue: {}", source))]
     AmqpSubscribeError { source: lapin::Error },
+    #[snafu(display("Could not set AMQP prefetch count: {}", source))]
+    AmqpQosError { source: lapin::Error },
 }
 
+/// The header in which quorum queues count the previous deliveries of a message.
+const DELIVERY_COUNT_HEADER: &str = "x-delivery-count";
+
 /// Configuration for the `amqp` source.
 ///
 /// Supports AMQP version 0.9.1
@@ -98,6 +109,22 @@ pub struct AmqpSourceConfig {
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     pub(crate) acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The maximum number of unacknowledged messages the server delivers to the consumer.
+    ///
+    /// The limit applies to this consumer only, as quorum queues don't support limits shared by the whole channel. By default, there is no limit.
+    pub(crate) prefetch_count: Option<u16>,
+
+    /// Whether to requeue the messages whose events couldn't be delivered.
+    ///
+    /// By default, these messages are rejected, which routes them to the dead letter exchange of the queue if it has one, and drops them otherwise. Messages whose events were rejected by a sink are always rejected, as they would fail again.
+    #[serde(default)]
+    pub(crate) requeue_on_error: bool,
+
+    /// The maximum number of times a message can be requeued before it is rejected instead.
+    ///
+    /// The number of previous deliveries is read from the `x-delivery-count` header, which is set by quorum queues. For other queues, messages that were already redelivered are considered requeued once. By default, messages are requeued indefinitely.
+    pub(crate) max_requeues: Option<u32>,
 }
 
 fn default_queue() -> String {
@@ -186,16 +213,41 @@ impl SourceConfig for AmqpSourceConfig {
 #[derive(Debug)]
 struct FinalizerEntry {
     acker: Acker,
+    /// The number of times the message was previously delivered, if known.
+    requeues: u32,
 }
 
 impl From<Delivery> for FinalizerEntry {
     fn from(delivery: Delivery) -> Self {
+        let requeues = delivery_count(&delivery).unwrap_or(u32::from(delivery.redelivered));
         Self {
             acker: delivery.acker,
+            requeues,
         }
     }
 }
 
+/// Reads the delivery count header set by quorum queues.
+fn delivery_count(delivery: &Delivery) -> Option<u32> {
+    let headers = delivery.properties.headers().as_ref()?;
+    let (_, value) = headers
+        .inner()
+        .iter()
+        .find(|(key, _)| key.as_str() == DELIVERY_COUNT_HEADER)?;
+
+    let count = match value {
+        AMQPValue::ShortShortUInt(count) => u64::from(*count),
+        AMQPValue::ShortUInt(count) => u64::from(*count),
+        AMQPValue::LongUInt(count) => u64::from(*count),
+        AMQPValue::ShortShortInt(count) => u64::try_from(*count).ok()?,
+        AMQPValue::ShortInt(count) => u64::try_from(*count).ok()?,
+        AMQPValue::LongInt(count) => u64::try_from(*count).ok()?,
+        AMQPValue::LongLongInt(count) => u64::try_from(*count).ok()?,
+        _ => return None,
+    };
+    Some(u32::try_from(count).unwrap_or(u32::MAX))
+}
+
 pub(crate) async fn amqp_source(
     config: &AmqpSourceConfig,
     shutdown: ShutdownSignal,
@@ -210,6 +262,13 @@ pub(crate) async fn amqp_source(
         .await
         .map_err(|source| BuildError::AmqpCreateError { source })?;
 
+    if let Some(prefetch_count) = config.prefetch_count {
+        channel
+            .basic_qos(prefetch_count, BasicQosOptions::default())
+            .await
+            .map_err(|source| BuildError::AmqpQosError { source })?;
+    }
+
     Ok(Box::pin(run_amqp_source(
         config,
         shutdown,
@@ -435,7 +494,7 @@ async fn run_amqp_source(
             _ = &mut shutdown => break,
             entry = ack_stream.next() => {
                 if let Some((status, entry)) = entry {
-                    handle_ack(status, entry).await;
+                    handle_ack(&config, status, entry).await;
                 }
             },
             opt_m = consumer.next() => {
@@ -459,7 +518,7 @@ async fn run_amqp_source(
     Ok(())
 }
 
-async fn handle_ack(status: BatchStatus, entry: FinalizerEntry) {
+async fn handle_ack(config: &AmqpSourceConfig, status: BatchStatus, entry: FinalizerEntry) {
     match status {
         BatchStatus::Delivered => {
             let ack_options = lapin::options::BasicAckOptions::default();
@@ -468,13 +527,15 @@ async fn handle_ack(status: BatchStatus, entry: FinalizerEntry) {
             }
         }
         BatchStatus::Errored => {
-            let ack_options = lapin::options::BasicRejectOptions::default();
+            let ack_options = BasicRejectOptions {
+                requeue: should_requeue(config, entry.requeues),
+            };
             if let Err(error) = entry.acker.reject(ack_options).await {
                 emit!(AmqpRejectError { error });
             }
         }
         BatchStatus::Rejected => {
-            let ack_options = lapin::options::BasicRejectOptions::default();
+            let ack_options = BasicRejectOptions::default();
             if let Err(error) = entry.acker.reject(ack_options).await {
                 emit!(AmqpRejectError { error });
             }
@@ -482,6 +543,15 @@ async fn handle_ack(status: BatchStatus, entry: FinalizerEntry) {
     }
 }
 
+/// Whether a message whose events couldn't be delivered should be requeued, rather than
+/// rejected to the dead letter exchange.
+fn should_requeue(config: &AmqpSourceConfig, requeues: u32) -> bool {
+    config.requeue_on_error
+        && config
+            .max_requeues
+            .map_or(true, |max_requeues| requeues < max_requeues)
+}
+
 #[cfg(test)]
 pub mod test {
     use lookup::LookupBuf;
@@ -508,6 +578,20 @@ pub mod test {
         config
     }
 
+    #[test]
+    fn requeue_on_error() {
+        let mut config = AmqpSourceConfig::default();
+        assert!(!should_requeue(&config, 0));
+
+        config.requeue_on_error = true;
+        assert!(should_requeue(&config, 0));
+        assert!(should_requeue(&config, 100));
+
+        config.max_requeues = Some(3);
+        assert!(should_requeue(&config, 2));
+        assert!(!should_requeue(&config, 3));
+    }
+
     #[test]
     fn output_schema_definition_vector_namespace() {
         let config = AmqpSourceConfig {
@@ -94,6 +94,16 @@ pub(super) struct Config {
     /// consumption rate affects the S3 object retrieval rate.
     pub(super) client_concurrency: Option<NonZeroUsize>,
 
+    /// The `detail-type` values of EventBridge notifications that reference objects to process.
+    ///
+    /// Only relevant when bucket notifications are delivered to the queue through [Amazon EventBridge][eventbridge].
+    /// Notifications with any other `detail-type` are ignored.
+    ///
+    /// [eventbridge]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/EventBridge.html
+    #[serde(default = "default_eventbridge_detail_types")]
+    #[derivative(Default(value = "default_eventbridge_detail_types()"))]
+    pub(super) eventbridge_detail_types: Vec<String>,
+
     #[configurable(derived)]
     #[serde(default)]
     #[derivative(Default)]
@@ -112,6 +122,10 @@ const fn default_true() -> bool {
     true
 }
 
+fn default_eventbridge_detail_types() -> Vec<String> {
+    vec!["Object Created".to_owned()]
+}
+
 #[derive(Debug, Snafu)]
 pub(super) enum IngestorNewError {
     #[snafu(display("Invalid visibility timeout {}: {}", timeout, source))]
@@ -182,6 +196,7 @@ pub struct State {
     client_concurrency: usize,
     visibility_timeout_secs: i32,
     delete_message: bool,
+    eventbridge_detail_types: Vec<String>,
 }
 
 pub(super) struct Ingestor {
@@ -214,6 +229,7 @@ impl Ingestor {
                 .unwrap_or_else(crate::num_threads),
             visibility_timeout_secs: config.visibility_timeout_secs as i32,
             delete_message: config.delete_message,
+            eventbridge_detail_types: config.eventbridge_detail_types,
         });
 
         Ok(Ingestor { state })
@@ -385,20 +401,32 @@ impl IngestorProcess {
     }
 
     async fn handle_sqs_message(&mut self, message: Message) -> Result<(), ProcessingError> {
-        let s3_event: Event = serde_json::from_str(message.body.unwrap_or_default().as_ref())
-            .context(InvalidSqsMessageSnafu {
+        let event = parse_notification(message.body.as_deref().unwrap_or_default()).context(
+            InvalidSqsMessageSnafu {
                 message_id: message
                     .message_id
                     .clone()
                     .unwrap_or_else(|| "<empty>".to_owned()),
-            })?;
+            },
+        )?;
 
-        match s3_event {
+        match event {
             Event::TestEvent(_s3_test_event) => {
                 debug!(?message.message_id, message = "Found S3 Test Event.");
                 Ok(())
             }
             Event::Event(s3_event) => self.handle_s3_event(s3_event).await,
+            Event::EventBridge(eventbridge_event) => {
+                self.handle_eventbridge_event(eventbridge_event).await
+            }
+            Event::SnsNotification(notification) => {
+                debug!(
+                    ?message.message_id,
+                    notification_type = %notification.r#type,
+                    message = "Ignoring SNS message that does not carry an S3 notification."
+                );
+                Ok(())
+            }
         }
     }
 
@@ -432,13 +460,57 @@ impl IngestorProcess {
             return Ok(());
         }
 
+        self.handle_s3_object(
+            s3_event.s3.bucket.name,
+            s3_event.s3.object.key,
+            s3_event.aws_region,
+            log_namespace,
+        )
+        .await
+    }
+
+    async fn handle_eventbridge_event(
+        &mut self,
+        event: EventBridgeEvent,
+    ) -> Result<(), ProcessingError> {
+        if !self
+            .state
+            .eventbridge_detail_types
+            .iter()
+            .any(|detail_type| *detail_type == event.detail_type)
+        {
+            emit!(SqsS3EventRecordInvalidEventIgnored {
+                bucket: &event.detail.bucket.name,
+                key: &event.detail.object.key,
+                kind: &event.source,
+                name: &event.detail_type,
+            });
+            return Ok(());
+        }
+
+        self.handle_s3_object(
+            event.detail.bucket.name,
+            event.detail.object.key,
+            event.region,
+            self.log_namespace,
+        )
+        .await
+    }
+
+    async fn handle_s3_object(
+        &mut self,
+        bucket: String,
+        key: String,
+        region: String,
+        log_namespace: LogNamespace,
+    ) -> Result<(), ProcessingError> {
         // S3 has to send notifications to a queue in the same region so I don't think this will
         // actually ever be hit unless messages are being forwarded from one queue to another
-        if self.state.region.as_ref() != s3_event.aws_region.as_str() {
+        if self.state.region.as_ref() != region.as_str() {
             return Err(ProcessingError::WrongRegion {
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
-                region: s3_event.aws_region,
+                bucket,
+                key,
+                region,
             });
         }
 
@@ -446,13 +518,13 @@ impl IngestorProcess {
             .state
             .s3_client
             .get_object()
-            .bucket(s3_event.s3.bucket.name.clone())
-            .key(s3_event.s3.object.key.clone())
+            .bucket(bucket.clone())
+            .key(key.clone())
             .send()
             .await
             .context(GetObjectSnafu {
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
+                bucket: bucket.clone(),
+                key: key.clone(),
             });
 
         let object = object_result?;
@@ -466,7 +538,7 @@ impl IngestorProcess {
         let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
         let object_reader = super::s3_object_decoder(
             self.state.compression,
-            &s3_event.s3.object.key,
+            &key,
             object.content_encoding.as_deref(),
             object.content_type.as_deref(),
             object.body,
@@ -526,21 +598,21 @@ impl IngestorProcess {
                 &mut log,
                 Some(LegacyKey::Overwrite(path!("bucket"))),
                 path!("bucket"),
-                Bytes::from(s3_event.s3.bucket.name.as_bytes().to_vec()),
+                Bytes::from(bucket.as_bytes().to_vec()),
             );
             log_namespace.insert_source_metadata(
                 AwsS3Config::NAME,
                 &mut log,
                 Some(LegacyKey::Overwrite(path!("object"))),
                 path!("object"),
-                Bytes::from(s3_event.s3.object.key.as_bytes().to_vec()),
+                Bytes::from(key.as_bytes().to_vec()),
             );
             log_namespace.insert_source_metadata(
                 AwsS3Config::NAME,
                 &mut log,
                 Some(LegacyKey::Overwrite(path!("region"))),
                 path!("region"),
-                Bytes::from(s3_event.aws_region.as_bytes().to_vec()),
+                Bytes::from(region.as_bytes().to_vec()),
             );
 
             if let Some(metadata) = &metadata {
@@ -602,14 +674,14 @@ impl IngestorProcess {
         if let Some(error) = read_error {
             Err(ProcessingError::ReadObject {
                 source: error,
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
+                bucket,
+                key,
             })
         } else if let Some(error) = send_error {
             Err(ProcessingError::PipelineSend {
                 source: error,
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
+                bucket,
+                key,
             })
         } else {
             match receiver {
@@ -654,12 +726,52 @@ impl IngestorProcess {
     }
 }
 
+/// Parses the body of an SQS message as a bucket notification.
+///
+/// Notifications delivered through an SNS topic without raw message delivery are wrapped in an SNS
+/// envelope, so the envelope is unwrapped, once, before the notification itself is parsed.
+fn parse_notification(body: &str) -> Result<Event, serde_json::Error> {
+    match serde_json::from_str(body)? {
+        Event::SnsNotification(notification) if notification.r#type == "Notification" => {
+            serde_json::from_str(&notification.message)
+        }
+        event => Ok(event),
+    }
+}
+
 // https://docs.aws.amazon.com/AmazonS3/latest/userguide/how-to-enable-disable-notification-intro.html
 #[derive(Clone, Debug, Deserialize)]
 #[serde(untagged)]
 enum Event {
     Event(S3Event),
     TestEvent(S3TestEvent),
+    SnsNotification(SnsNotification),
+    EventBridge(EventBridgeEvent),
+}
+
+// https://docs.aws.amazon.com/sns/latest/dg/sns-message-and-json-formats.html
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SnsNotification {
+    pub r#type: String,
+    pub message: String,
+}
+
+// https://docs.aws.amazon.com/AmazonS3/latest/userguide/ev-events.html
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EventBridgeEvent {
+    pub detail_type: String,
+    pub source: String,
+    pub region: String,
+    pub detail: EventBridgeDetail,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventBridgeDetail {
+    pub bucket: S3Bucket,
+    pub object: S3Object,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -881,3 +993,74 @@ fn test_s3_testevent() {
     assert_eq!(value.event.kind, "s3".to_string());
     assert_eq!(value.event.name, "TestEvent".to_string());
 }
+
+#[test]
+fn test_sns_wrapped_s3_event() {
+    let inner = r#"{"Records":[{"eventVersion":"2.1","eventSource":"aws:s3","awsRegion":"us-east-1","eventName":"ObjectCreated:Put","s3":{"bucket":{"name":"bucketname"},"object":{"key":"some+key"}}}]}"#;
+    let body = serde_json::json!({
+        "Type": "Notification",
+        "MessageId": "22b80b92-fdea-4c2c-8f9d-bdfb0c7bf324",
+        "TopicArn": "arn:aws:sns:us-east-1:123456789012:bucket-notifications",
+        "Subject": "Amazon S3 Notification",
+        "Message": inner,
+        "Timestamp": "2022-12-01T12:00:00.000Z",
+    })
+    .to_string();
+
+    match parse_notification(&body).unwrap() {
+        Event::Event(event) => {
+            assert_eq!(event.records.len(), 1);
+            assert_eq!(event.records[0].s3.bucket.name, "bucketname");
+            assert_eq!(event.records[0].s3.object.key, "some key");
+        }
+        event => panic!("unexpected event: {:?}", event),
+    }
+}
+
+#[test]
+fn test_sns_subscription_confirmation_is_not_unwrapped() {
+    let body = r#"{
+        "Type": "SubscriptionConfirmation",
+        "MessageId": "165545c9-2a5c-472c-8df2-7ff2be2b3b1b",
+        "Message": "You have chosen to subscribe to the topic.",
+        "TopicArn": "arn:aws:sns:us-east-1:123456789012:bucket-notifications"
+    }"#;
+
+    assert!(matches!(
+        parse_notification(body).unwrap(),
+        Event::SnsNotification(_)
+    ));
+}
+
+#[test]
+fn test_eventbridge_event() {
+    let body = r#"{
+        "version": "0",
+        "id": "17793124-05d4-b198-2fde-7ededc63b103",
+        "detail-type": "Object Created",
+        "source": "aws.s3",
+        "account": "123456789012",
+        "time": "2021-11-12T00:00:00Z",
+        "region": "ca-central-1",
+        "resources": ["arn:aws:s3:::bucketname"],
+        "detail": {
+            "version": "0",
+            "bucket": {"name": "bucketname"},
+            "object": {"key": "my%2Bkey", "size": 5, "etag": "b1946ac92492d2347c6235b4d2611184"},
+            "request-id": "N4N7GDK58NMKJ12R",
+            "requester": "123456789012",
+            "reason": "PutObject"
+        }
+    }"#;
+
+    match parse_notification(body).unwrap() {
+        Event::EventBridge(event) => {
+            assert_eq!(event.detail_type, "Object Created");
+            assert_eq!(event.source, "aws.s3");
+            assert_eq!(event.region, "ca-central-1");
+            assert_eq!(event.detail.bucket.name, "bucketname");
+            assert_eq!(event.detail.object.key, "my+key");
+        }
+        event => panic!("unexpected event: {:?}", event),
+    }
+}
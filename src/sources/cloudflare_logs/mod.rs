@@ -0,0 +1,258 @@
+use std::io::Read;
+
+use bytes::{Buf, Bytes};
+use chrono::{DateTime, Utc};
+use flate2::read::MultiGzDecoder;
+use lookup::{lookup_v2::parse_value_path, owned_value_path, path};
+use value::{kind::Collection, Kind};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+};
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, Resource, SourceAcknowledgementsConfig,
+        SourceConfig, SourceContext,
+    },
+    event::Event,
+    internal_events::CloudflareLogsParseError,
+    serde::bool_or_struct,
+};
+
+mod pull;
+mod push;
+
+/// The fields holding the time of an event, in the datasets that have one.
+const TIMESTAMP_FIELDS: [&str; 3] = ["EdgeStartTimestamp", "Datetime", "Timestamp"];
+
+/// Configuration for the `cloudflare_logs` source.
+#[configurable_component(source("cloudflare_logs"))]
+#[derive(Clone, Debug)]
+pub struct CloudflareLogsConfig {
+    #[serde(flatten)]
+    mode: Mode,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+/// How logs are collected from Cloudflare.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)] // just used for configuration
+pub enum Mode {
+    /// Pull the HTTP request logs of a zone from the Logpull API.
+    Pull(#[configurable(derived)] pull::PullConfig),
+
+    /// Receive the logs pushed by Logpush jobs with an HTTP destination.
+    Push(#[configurable(derived)] push::PushConfig),
+}
+
+impl GenerateConfig for CloudflareLogsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"mode = "pull"
+            zone_id = "023e105f4ecef8ad9ca31a8372d0c353"
+            token = "${CLOUDFLARE_API_TOKEN}""#,
+        )
+        .unwrap()
+    }
+}
+
+impl CloudflareLogsConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        Definition::new_with_default_metadata(Kind::object(Collection::any()), [log_namespace])
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("zone"))),
+                &owned_value_path!("zone"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                parse_value_path(log_schema().timestamp_key())
+                    .ok()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp().or_undefined(),
+                Some("timestamp"),
+            )
+            .with_standard_vector_source_metadata()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for CloudflareLogsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        match &self.mode {
+            Mode::Pull(config) => config.build(cx, self.acknowledgements, log_namespace),
+            Mode::Push(config) => config.build(cx, self.acknowledgements, log_namespace),
+        }
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        match &self.mode {
+            Mode::Pull(_) => Vec::new(),
+            Mode::Push(config) => vec![Resource::tcp(config.address)],
+        }
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// Parses a body of newline delimited JSON logs, as sent by both the Logpull API and Logpush
+/// jobs, into events.
+///
+/// Gzip compressed bodies are decompressed first, since Logpush compresses its requests without
+/// always saying so. Lines that can't be parsed are dropped.
+fn body_to_events(body: Bytes, zone: Option<&str>, log_namespace: LogNamespace) -> Vec<Event> {
+    let body = if body.starts_with(&[0x1f, 0x8b]) {
+        let mut decoded = Vec::new();
+        match MultiGzDecoder::new(body.reader()).read_to_end(&mut decoded) {
+            Ok(_) => Bytes::from(decoded),
+            Err(error) => {
+                emit!(CloudflareLogsParseError { error: &error });
+                return Vec::new();
+            }
+        }
+    } else {
+        body
+    };
+
+    let now = Utc::now();
+    body.split(|byte| *byte == b'\n')
+        .filter(|line| !line.iter().all(u8::is_ascii_whitespace))
+        .filter_map(|line| {
+            match serde_json::from_slice::<serde_json::Map<String, serde_json::Value>>(line) {
+                Ok(entry) => Some(entry),
+                Err(error) => {
+                    emit!(CloudflareLogsParseError { error: &error });
+                    None
+                }
+            }
+        })
+        .filter(|entry| !is_test_message(entry))
+        .map(|entry| {
+            let timestamp = TIMESTAMP_FIELDS.iter().find_map(|field| {
+                entry
+                    .get(*field)
+                    .and_then(|value| value.as_str())
+                    .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+                    .map(|timestamp| timestamp.with_timezone(&Utc))
+            });
+
+            let mut log =
+                log_namespace.new_log_from_data(value::Value::from(serde_json::Value::from(entry)));
+            if let Some(zone) = zone {
+                log_namespace.insert_source_metadata(
+                    CloudflareLogsConfig::NAME,
+                    &mut log,
+                    Some(LegacyKey::InsertIfEmpty(path!("zone"))),
+                    path!("zone"),
+                    zone.to_owned(),
+                );
+            }
+            if let Some(timestamp) = timestamp {
+                log_namespace.insert_source_metadata(
+                    CloudflareLogsConfig::NAME,
+                    &mut log,
+                    Some(LegacyKey::Overwrite(path!(log_schema().timestamp_key()))),
+                    path!("timestamp"),
+                    timestamp,
+                );
+            }
+            log_namespace.insert_standard_vector_source_metadata(
+                &mut log,
+                CloudflareLogsConfig::NAME,
+                now,
+            );
+            Event::from(log)
+        })
+        .collect()
+}
+
+/// Whether or not `entry` is the message sent by Logpush to check a destination is reachable
+/// when a job is created.
+fn is_test_message(entry: &serde_json::Map<String, serde_json::Value>) -> bool {
+    entry.len() == 2 && entry.contains_key("content") && entry.contains_key("filename")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use chrono::TimeZone;
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<CloudflareLogsConfig>();
+    }
+
+    const BODY: &str = r#"{"RayID":"7a1b2c3d4e5f6a7b","ClientRequestHost":"example.com","EdgeStartTimestamp":"2023-01-10T12:30:00Z"}
+not json
+{"content":"tests","filename":"test.txt"}
+
+{"RayID":"7a1b2c3d4e5f6a7c","ClientRequestHost":"example.com","EdgeStartTimestamp":1673353800000000000}
+"#;
+
+    #[test]
+    fn parses_ndjson_body() {
+        let events = body_to_events(Bytes::from(BODY), Some("example.com"), LogNamespace::Legacy);
+        assert_eq!(events.len(), 2);
+
+        let log = events[0].as_log();
+        assert_eq!(log["RayID"], "7a1b2c3d4e5f6a7b".into());
+        assert_eq!(log["zone"], "example.com".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            value::Value::from(Utc.ymd(2023, 1, 10).and_hms(12, 30, 0))
+        );
+        assert_eq!(
+            log[log_schema().source_type_key()],
+            "cloudflare_logs".into()
+        );
+
+        // Timestamps that aren't RFC 3339 are left as they are, and ingest time is used instead.
+        let log = events[1].as_log();
+        assert_eq!(
+            log["EdgeStartTimestamp"],
+            1_673_353_800_000_000_000_i64.into()
+        );
+        assert!(log[log_schema().timestamp_key()].as_timestamp().is_some());
+    }
+
+    #[test]
+    fn parses_gzip_body() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(BODY.as_bytes()).unwrap();
+        let body = Bytes::from(encoder.finish().unwrap());
+
+        let events = body_to_events(body, None, LogNamespace::Legacy);
+        assert_eq!(events.len(), 2);
+        assert!(!events[0].as_log().contains("zone"));
+    }
+}
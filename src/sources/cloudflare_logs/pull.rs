@@ -0,0 +1,546 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use http::{header, Request, StatusCode};
+use hyper::Body;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio::fs;
+use tokio_stream::wrappers::IntervalStream;
+use vector_common::{
+    internal_event::{CountByteSize, InternalEventHandle as _, Registered},
+    sensitive_string::SensitiveString,
+};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, EstimatedJsonEncodedSizeOf};
+
+use super::body_to_events;
+use crate::{
+    config::{SourceAcknowledgementsConfig, SourceContext},
+    event::{BatchNotifier, BatchStatus},
+    http::{Auth, HttpClient, HttpError},
+    internal_events::{
+        CloudflareLogsCheckpointError, CloudflareLogsReadError, CloudflareLogsRetentionExceeded,
+        EndpointBytesReceived, EventsReceived, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    tls::{TlsConfig, TlsSettings},
+    SourceSender,
+};
+
+const CHECKPOINT_FILENAME: &str = "checkpoint.json";
+const TMP_CHECKPOINT_FILENAME: &str = "checkpoint.new.json";
+
+/// The longest window the Logpull API can be queried for.
+const MAX_WINDOW_SECS: u64 = 60 * 60;
+/// How far back in time the end of a window must be.
+const MIN_DELAY_SECS: u64 = 60;
+/// How long logs are retained for the Logpull API.
+const RETENTION_SECS: i64 = 7 * 24 * 60 * 60;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid endpoint {:?}: {}", endpoint, source))]
+    InvalidEndpoint {
+        endpoint: String,
+        source: url::ParseError,
+    },
+    #[snafu(display(
+        "`window_secs` must be between 1 and {}, got {}",
+        MAX_WINDOW_SECS,
+        window_secs
+    ))]
+    InvalidWindow { window_secs: u64 },
+    #[snafu(display("`delay_secs` must be at least {}, got {}", MIN_DELAY_SECS, delay_secs))]
+    DelayTooShort { delay_secs: u64 },
+}
+
+#[derive(Debug, Snafu)]
+enum ReadError {
+    #[snafu(display("HTTP request failed: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("failed to read response body: {}", source))]
+    Body { source: hyper::Error },
+    #[snafu(display("unexpected status {} from {}: {}", status, url, body))]
+    Status {
+        status: StatusCode,
+        url: String,
+        body: String,
+    },
+    #[snafu(display("source output closed"))]
+    OutputClosed,
+}
+
+/// Configuration for the `pull` mode of the `cloudflare_logs` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PullConfig {
+    /// The base URL of the Cloudflare API.
+    #[serde(default = "default_endpoint")]
+    endpoint: String,
+
+    /// The identifier of the zone whose logs are pulled.
+    #[configurable(metadata(docs::examples = "023e105f4ecef8ad9ca31a8372d0c353"))]
+    zone_id: String,
+
+    /// The API token used to authenticate with the Cloudflare API.
+    ///
+    /// It must have the `Zone Logs: Read` permission for the zone.
+    #[configurable(metadata(docs::examples = "${CLOUDFLARE_API_TOKEN}"))]
+    token: SensitiveString,
+
+    /// The fields of the logs to pull.
+    ///
+    /// By default, the fields selected by the Logpull API are pulled.
+    #[configurable(metadata(docs::examples = "ClientIP"))]
+    #[configurable(metadata(docs::examples = "ClientRequestHost"))]
+    #[configurable(metadata(docs::examples = "EdgeStartTimestamp"))]
+    #[serde(default)]
+    fields: Vec<String>,
+
+    /// The length of the time windows logs are pulled for, in seconds.
+    ///
+    /// It can be at most one hour.
+    #[serde(default = "default_window_secs")]
+    window_secs: u64,
+
+    /// How far back in time the end of a window must be before its logs are pulled, in seconds.
+    ///
+    /// Cloudflare takes time to make logs available, and rejects windows ending less than a
+    /// minute ago.
+    #[serde(default = "default_delay_secs")]
+    delay_secs: u64,
+
+    /// The interval between polls of the Logpull API, in seconds.
+    #[serde(default = "default_poll_interval_secs")]
+    poll_interval_secs: u64,
+
+    /// How far back to pull logs from when no checkpoint is persisted, in seconds.
+    ///
+    /// Logs are retained for seven days at most. By default, only the logs of the requests
+    /// handled after Vector starts are pulled.
+    #[serde(default)]
+    backfill_secs: u64,
+
+    /// The directory used to persist the end of the last window pulled.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    #[serde(default)]
+    data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+}
+
+fn default_endpoint() -> String {
+    "https://api.cloudflare.com/client/v4".to_string()
+}
+
+const fn default_window_secs() -> u64 {
+    60
+}
+
+const fn default_delay_secs() -> u64 {
+    300
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    60
+}
+
+impl PullConfig {
+    pub(super) fn build(
+        &self,
+        cx: SourceContext,
+        acknowledgements: SourceAcknowledgementsConfig,
+        log_namespace: LogNamespace,
+    ) -> crate::Result<crate::sources::Source> {
+        if self.window_secs == 0 || self.window_secs > MAX_WINDOW_SECS {
+            return Err(BuildError::InvalidWindow {
+                window_secs: self.window_secs,
+            }
+            .into());
+        }
+        if self.delay_secs < MIN_DELAY_SECS {
+            return Err(BuildError::DelayTooShort {
+                delay_secs: self.delay_secs,
+            }
+            .into());
+        }
+
+        let logs_url = logs_url(&self.endpoint, &self.zone_id)?;
+        let data_dir = cx
+            .globals
+            // source are only global, name can be used for subdir
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, &cx.proxy)?;
+
+        let reader = LogpullReader {
+            client,
+            logs_url,
+            auth: Auth::Bearer {
+                token: self.token.clone(),
+            },
+            zone_id: self.zone_id.clone(),
+            fields: self.fields.clone(),
+            window: chrono::Duration::seconds(self.window_secs as i64),
+            delay: chrono::Duration::seconds(self.delay_secs as i64),
+            backfill: chrono::Duration::seconds(
+                self.backfill_secs.min(RETENTION_SECS as u64) as i64
+            ),
+            checkpointer: Checkpointer::new(&data_dir),
+            acknowledgements: cx.do_acknowledgements(acknowledgements),
+            log_namespace,
+            events_received: register!(EventsReceived),
+            out: cx.out,
+            shutdown: cx.shutdown,
+        };
+        Ok(Box::pin(
+            reader.run(Duration::from_secs(self.poll_interval_secs)),
+        ))
+    }
+}
+
+/// Builds the URL of the logs of the requests received by `zone_id`.
+fn logs_url(endpoint: &str, zone_id: &str) -> Result<url::Url, BuildError> {
+    let url = format!(
+        "{}/zones/{}/logs/received",
+        endpoint.trim_end_matches('/'),
+        zone_id
+    );
+    url::Url::parse(&url).context(InvalidEndpointSnafu { endpoint })
+}
+
+/// Builds the URL pulling the logs of the requests received between `start`, inclusive, and
+/// `end`, exclusive.
+fn window_url(
+    logs_url: &url::Url,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    fields: &[String],
+) -> url::Url {
+    let mut url = logs_url.clone();
+    {
+        let mut query = url.query_pairs_mut();
+        query
+            .append_pair("start", &start.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+            .append_pair("end", &end.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+            .append_pair("timestamps", "rfc3339");
+        if !fields.is_empty() {
+            query.append_pair("fields", &fields.join(","));
+        }
+    }
+    url
+}
+
+/// The end of the last window pulled, persisted as JSON in the data directory.
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct Checkpoint {
+    end: DateTime<Utc>,
+}
+
+struct Checkpointer {
+    path: PathBuf,
+    tmp_path: PathBuf,
+}
+
+impl Checkpointer {
+    fn new(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(CHECKPOINT_FILENAME),
+            tmp_path: data_dir.join(TMP_CHECKPOINT_FILENAME),
+        }
+    }
+
+    /// Loads the end of the last window pulled, if any.
+    async fn load(&self) -> io::Result<Option<DateTime<Utc>>> {
+        match fs::read(&self.path).await {
+            Ok(contents) => serde_json::from_slice::<Checkpoint>(&contents)
+                .map(|checkpoint| Some(checkpoint.end))
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Persists `end`, through a temporary file so a crash never leaves a partially written file
+    /// behind.
+    async fn set(&self, end: DateTime<Utc>) -> io::Result<()> {
+        let contents = serde_json::to_vec(&Checkpoint { end })
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(&self.tmp_path, contents).await?;
+        fs::rename(&self.tmp_path, &self.path).await
+    }
+}
+
+struct LogpullReader {
+    client: HttpClient,
+    logs_url: url::Url,
+    auth: Auth,
+    zone_id: String,
+    fields: Vec<String>,
+    window: chrono::Duration,
+    delay: chrono::Duration,
+    backfill: chrono::Duration,
+    checkpointer: Checkpointer,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    events_received: Registered<EventsReceived>,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+}
+
+impl LogpullReader {
+    async fn run(mut self, interval: Duration) -> Result<(), ()> {
+        let mut start = self
+            .checkpointer
+            .load()
+            .await
+            .map_err(|error| {
+                emit!(CloudflareLogsCheckpointError {
+                    path: &self.checkpointer.path,
+                    error,
+                })
+            })?
+            .unwrap_or_else(|| truncate_to_secs(Utc::now() - self.delay - self.backfill));
+
+        let mut ticks =
+            IntervalStream::new(tokio::time::interval(interval)).take_until(self.shutdown.clone());
+        while ticks.next().await.is_some() {
+            match self.poll(&mut start).await {
+                Ok(()) => {}
+                Err(ReadError::OutputClosed) => break,
+                Err(error) => emit!(CloudflareLogsReadError { error }),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pulls the logs of every complete window since `start`.
+    async fn poll(&mut self, start: &mut DateTime<Utc>) -> Result<(), ReadError> {
+        loop {
+            let now = Utc::now();
+            // Windows are only pulled once they are complete, so they never need to be pulled
+            // again.
+            let end = *start + self.window;
+            if end > now - self.delay {
+                return Ok(());
+            }
+
+            let oldest = truncate_to_secs(now) - chrono::Duration::seconds(RETENTION_SECS)
+                + chrono::Duration::seconds(MIN_DELAY_SECS as i64);
+            if *start < oldest {
+                emit!(CloudflareLogsRetentionExceeded {
+                    start: *start,
+                    end: oldest,
+                });
+                *start = oldest;
+                continue;
+            }
+
+            let url = window_url(&self.logs_url, *start, end, &self.fields);
+            let body = self.request(url).await?;
+            if !self.send(body).await? {
+                // The logs weren't delivered, so the window is pulled again on the next poll.
+                return Ok(());
+            }
+
+            *start = end;
+            if let Err(error) = self.checkpointer.set(end).await {
+                emit!(CloudflareLogsCheckpointError {
+                    path: &self.checkpointer.path,
+                    error,
+                });
+            }
+        }
+    }
+
+    async fn request(&self, url: url::Url) -> Result<Bytes, ReadError> {
+        let mut request = Request::get(url.as_str())
+            .header(header::ACCEPT, "application/json")
+            .body(Body::empty())
+            .expect("error creating request");
+        self.auth.apply(&mut request);
+
+        let response = self.client.send(request).await.context(RequestSnafu)?;
+        let (parts, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body).await.context(BodySnafu)?;
+        emit!(EndpointBytesReceived {
+            byte_size: body.len(),
+            protocol: "http",
+            endpoint: self.logs_url.as_str(),
+        });
+
+        if parts.status.is_success() {
+            Ok(body)
+        } else {
+            Err(ReadError::Status {
+                status: parts.status,
+                url: url.to_string(),
+                body: String::from_utf8_lossy(&body).into_owned(),
+            })
+        }
+    }
+
+    /// Sends the logs in `body` downstream.
+    ///
+    /// Returns whether or not the logs were delivered.
+    async fn send(&mut self, body: Bytes) -> Result<bool, ReadError> {
+        let mut events = body_to_events(body, Some(&self.zone_id), self.log_namespace);
+        if events.is_empty() {
+            return Ok(true);
+        }
+
+        let count = events.len();
+        self.events_received.emit(CountByteSize(
+            count,
+            events.estimated_json_encoded_size_of(),
+        ));
+
+        let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+        if let Err(error) = self.out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            return Err(ReadError::OutputClosed);
+        }
+        match receiver {
+            Some(receiver) => Ok(receiver.await == BatchStatus::Delivered),
+            None => Ok(true),
+        }
+    }
+}
+
+/// The Logpull API only has a resolution of seconds.
+fn truncate_to_secs(timestamp: DateTime<Utc>) -> DateTime<Utc> {
+    timestamp - chrono::Duration::nanoseconds(timestamp.timestamp_subsec_nanos() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use chrono::TimeZone;
+    use warp::Filter;
+
+    use super::*;
+    use crate::{
+        config::log_schema,
+        sources::cloudflare_logs::CloudflareLogsConfig,
+        test_util::{
+            components::{run_and_assert_source_compliance, HTTP_PULL_SOURCE_TAGS},
+            next_addr, wait_for_tcp,
+        },
+    };
+
+    #[test]
+    fn builds_window_url() {
+        let url = logs_url(
+            "https://api.cloudflare.com/client/v4/",
+            "023e105f4ecef8ad9ca31a8372d0c353",
+        )
+        .unwrap();
+        let url = window_url(
+            &url,
+            Utc.ymd(2023, 1, 10).and_hms(12, 30, 0),
+            Utc.ymd(2023, 1, 10).and_hms(12, 31, 0),
+            &["ClientIP".to_string(), "RayID".to_string()],
+        );
+        assert_eq!(
+            url.as_str(),
+            "https://api.cloudflare.com/client/v4/zones/023e105f4ecef8ad9ca31a8372d0c353/logs/received\
+             ?start=2023-01-10T12%3A30%3A00Z&end=2023-01-10T12%3A31%3A00Z&timestamps=rfc3339\
+             &fields=ClientIP%2CRayID"
+        );
+    }
+
+    #[tokio::test]
+    async fn persists_checkpoint() {
+        let data_dir = tempfile::tempdir().unwrap();
+
+        let checkpointer = Checkpointer::new(data_dir.path());
+        assert_eq!(checkpointer.load().await.unwrap(), None);
+
+        let end = Utc.ymd(2023, 1, 10).and_hms(12, 31, 0);
+        checkpointer.set(end).await.unwrap();
+        assert_eq!(
+            Checkpointer::new(data_dir.path()).load().await.unwrap(),
+            Some(end)
+        );
+    }
+
+    fn logpull_server(addr: SocketAddr) {
+        let logs = warp::path!("zones" / "zone" / "logs" / "received")
+            .and(warp::header::exact("Authorization", "Bearer token"))
+            .and(warp::query::<std::collections::HashMap<String, String>>())
+            .map(|query: std::collections::HashMap<String, String>| {
+                format!(
+                    "{{\"RayID\":\"{}\",\"EdgeStartTimestamp\":\"{}\"}}\n",
+                    query["end"], query["start"]
+                )
+            });
+        tokio::spawn(warp::serve(logs).run(addr));
+    }
+
+    #[tokio::test]
+    async fn pulls_complete_windows() {
+        let addr = next_addr();
+        logpull_server(addr);
+        wait_for_tcp(addr).await;
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let config: CloudflareLogsConfig = toml::from_str(&format!(
+            r#"mode = "pull"
+            endpoint = "http://{}"
+            zone_id = "zone"
+            token = "token"
+            window_secs = 60
+            delay_secs = 60
+            backfill_secs = 180
+            poll_interval_secs = 1
+            data_dir = "{}""#,
+            addr,
+            data_dir.path().display(),
+        ))
+        .unwrap();
+
+        let events = run_and_assert_source_compliance(
+            config,
+            Duration::from_secs(3),
+            &HTTP_PULL_SOURCE_TAGS,
+        )
+        .await;
+
+        // The three complete windows since the backfill are pulled once each.
+        assert_eq!(events.len(), 3);
+        for window in events.windows(2) {
+            assert_eq!(
+                window[0].as_log()["RayID"],
+                window[1].as_log()[log_schema().timestamp_key()]
+                    .as_timestamp()
+                    .unwrap()
+                    .format("%Y-%m-%dT%H:%M:%SZ")
+                    .to_string()
+                    .into()
+            );
+        }
+        assert_eq!(events[0].as_log()["zone"], "zone".into());
+
+        let end = Checkpointer::new(data_dir.path())
+            .load()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            events[2].as_log()["RayID"],
+            end.format("%Y-%m-%dT%H:%M:%SZ").to_string().into()
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use std::{collections::HashMap, net::SocketAddr};
+
+use bytes::Bytes;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+use warp::http::HeaderMap;
+
+use super::body_to_events;
+use crate::{
+    config::{SourceAcknowledgementsConfig, SourceContext},
+    event::Event,
+    sources::util::{http::HttpMethod, ErrorMessage, HttpSource, HttpSourceAuthConfig},
+    tls::TlsEnableableConfig,
+};
+
+/// The query parameter holding the zone of the logs pushed.
+const ZONE_QUERY_PARAMETER: &str = "zone";
+
+/// Configuration for the `push` mode of the `cloudflare_logs` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PushConfig {
+    /// The address to listen for Logpush requests on.
+    ///
+    /// Logpush jobs can be pointed at any path on this address.
+    #[configurable(metadata(docs::examples = "0.0.0.0:443"))]
+    pub(super) address: SocketAddr,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    auth: Option<HttpSourceAuthConfig>,
+}
+
+impl PushConfig {
+    pub(super) fn build(
+        &self,
+        cx: SourceContext,
+        acknowledgements: SourceAcknowledgementsConfig,
+        log_namespace: LogNamespace,
+    ) -> crate::Result<crate::sources::Source> {
+        let source = LogpushSource { log_namespace };
+        source.run(
+            self.address,
+            "",
+            HttpMethod::Post,
+            false,
+            &self.tls,
+            &self.auth,
+            cx,
+            acknowledgements,
+        )
+    }
+}
+
+#[derive(Clone)]
+struct LogpushSource {
+    log_namespace: LogNamespace,
+}
+
+impl HttpSource for LogpushSource {
+    fn build_events(
+        &self,
+        body: Bytes,
+        _header_map: HeaderMap,
+        query_parameters: HashMap<String, String>,
+        _full_path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        let zone = query_parameters.get(ZONE_QUERY_PARAMETER);
+        Ok(body_to_events(
+            body,
+            zone.map(String::as_str),
+            self.log_namespace,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+    use futures::Stream;
+    use vector_core::event::EventStatus;
+
+    use super::*;
+    use crate::{
+        config::SourceConfig,
+        sources::cloudflare_logs::CloudflareLogsConfig,
+        test_util::{
+            components::{assert_source_compliance, HTTP_PUSH_SOURCE_TAGS},
+            next_addr, spawn_collect_n, wait_for_tcp,
+        },
+        SourceSender,
+    };
+
+    async fn source() -> (impl Stream<Item = Event> + Unpin, SocketAddr) {
+        let (sender, recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let context = SourceContext::new_test(sender, None);
+        let config: CloudflareLogsConfig = toml::from_str(&format!(
+            r#"mode = "push"
+            address = "{}""#,
+            address
+        ))
+        .unwrap();
+        tokio::spawn(async move { config.build(context).await.unwrap().await.unwrap() });
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
+
+    async fn send(address: SocketAddr, body: &str) -> u16 {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body.as_bytes()).unwrap();
+
+        reqwest::Client::new()
+            .post(&format!(
+                "http://{}/logpush/http_requests?zone=example.com",
+                address
+            ))
+            .header("Content-Encoding", "gzip")
+            .body(encoder.finish().unwrap())
+            .send()
+            .await
+            .unwrap()
+            .status()
+            .as_u16()
+    }
+
+    #[tokio::test]
+    async fn receives_logpush_requests() {
+        assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
+            let (rx, addr) = source().await;
+
+            let events = spawn_collect_n(
+                async move {
+                    assert_eq!(
+                        200,
+                        send(addr, r#"{"content":"tests","filename":"test.txt"}"#).await
+                    );
+                    assert_eq!(
+                        200,
+                        send(
+                            addr,
+                            "{\"RayID\":\"7a1b2c3d4e5f6a7b\"}\n{\"RayID\":\"7a1b2c3d4e5f6a7c\"}\n"
+                        )
+                        .await
+                    );
+                },
+                rx,
+                2,
+            )
+            .await;
+
+            assert_eq!(events[0].as_log()["RayID"], "7a1b2c3d4e5f6a7b".into());
+            assert_eq!(events[1].as_log()["RayID"], "7a1b2c3d4e5f6a7c".into());
+            assert_eq!(events[1].as_log()["zone"], "example.com".into());
+        })
+        .await;
+    }
+}
@@ -0,0 +1,594 @@
+//! Collection of container logs written in the CRI log format.
+//!
+//! Hosts running containerd or CRI-O instead of `dockerd` don't expose the Docker API, but the CRI runtime (or the
+//! `kubelet`) writes the output of every container to a log file. This module tails those files and turns them into
+//! events shaped like the ones produced from the Docker API.
+//!
+//! See <https://github.com/kubernetes/design-proposals-archive/blob/main/node/kubelet-cri-logging.md> for the format.
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use codecs::BytesDeserializer;
+use file_source::{
+    paths_provider::{
+        glob::{Glob, MatchOptions},
+        PathsProvider,
+    },
+    Checkpointer, FileServer, FingerprintStrategy, Fingerprinter, Line, ReadFrom,
+};
+use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
+use lookup::{metadata_path, path, PathPrefix};
+use tokio::task::spawn_blocking;
+use vector_common::internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::config::{LegacyKey, LogNamespace};
+
+use super::{
+    add_hostname, is_self, line_agg_adapter, merge_partial_event, DockerLogsConfig, CONTAINER,
+    NAME, STDERR, STDOUT, STREAM,
+};
+use crate::{
+    config::{log_schema, SourceContext},
+    event::{merge_state::LogEventMergeState, EstimatedJsonEncodedSizeOf, LogEvent},
+    internal_events::{
+        DockerLogsEventsReceived, DockerLogsTimestampParseError, FileOpen,
+        FileSourceInternalEventsEmitter, ParserMatchError, StreamClosedError,
+    },
+    line_agg,
+    sources::Source,
+};
+
+/// Well-known locations of CRI runtime sockets, probed in order.
+const CRI_SOCKET_PATHS: &[&str] = &[
+    "/run/containerd/containerd.sock",
+    "/var/run/containerd/containerd.sock",
+    "/run/k3s/containerd/containerd.sock",
+    "/var/snap/microk8s/common/run/containerd.sock",
+    "/run/crio/crio.sock",
+    "/var/run/crio/crio.sock",
+];
+
+/// The default location of the Docker socket on Unix platforms.
+const DOCKER_SOCKET_PATH: &str = "/var/run/docker.sock";
+
+/// The length of a full container ID, as used in the CRI log file names.
+const CONTAINER_ID_LENGTH: usize = 64;
+
+/// The container runtime to collect logs from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerRuntime {
+    /// Collect logs through the Docker API.
+    #[derivative(Default)]
+    Docker,
+
+    /// Collect logs written in the CRI log format by a CRI runtime, such as containerd or CRI-O.
+    Cri,
+
+    /// Collect logs through the Docker API if the Docker daemon is available, otherwise fall back to a CRI runtime.
+    ///
+    /// The Docker daemon is considered available if `docker_host` or the `DOCKER_HOST` environment variable is set, or
+    /// if the default Docker socket exists. A CRI runtime is detected by probing for the containerd and CRI-O sockets.
+    Auto,
+}
+
+/// Configuration for collecting logs from a CRI runtime.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct CriConfig {
+    /// The directory containing the container log files written by the CRI runtime.
+    ///
+    /// Log files are expected to be named `<name>-<container ID>.log`, as done by the `kubelet` in
+    /// `/var/log/containers`. Files not following this convention are skipped.
+    pub log_directory: PathBuf,
+
+    /// The path of the CRI runtime socket.
+    ///
+    /// Used to detect the CRI runtime when `runtime` is set to `auto`. If not set, the default locations of the
+    /// containerd and CRI-O sockets are probed.
+    pub socket_path: Option<PathBuf>,
+
+    /// The directory used to persist file checkpoint positions.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    pub data_dir: Option<PathBuf>,
+
+    /// Delay between discovering new log files, in milliseconds.
+    pub glob_minimum_cooldown_ms: u64,
+}
+
+impl Default for CriConfig {
+    fn default() -> Self {
+        Self {
+            log_directory: PathBuf::from("/var/log/containers"),
+            socket_path: None,
+            data_dir: None,
+            glob_minimum_cooldown_ms: 1_000,
+        }
+    }
+}
+
+impl CriConfig {
+    /// Returns the path of the CRI runtime socket, if one can be found.
+    fn discover_socket(&self) -> Option<PathBuf> {
+        match &self.socket_path {
+            Some(path) => path.exists().then(|| path.clone()),
+            None => CRI_SOCKET_PATHS
+                .iter()
+                .map(PathBuf::from)
+                .find(|path| path.exists()),
+        }
+    }
+}
+
+/// Resolves the runtime to collect logs from, returning either `Docker` or `Cri`.
+pub(super) fn resolve_runtime(config: &DockerLogsConfig) -> crate::Result<ContainerRuntime> {
+    match config.runtime {
+        ContainerRuntime::Auto => {
+            let docker_available = config.docker_host.is_some()
+                || std::env::var_os("DOCKER_HOST").is_some()
+                || Path::new(DOCKER_SOCKET_PATH).exists();
+
+            if docker_available {
+                Ok(ContainerRuntime::Docker)
+            } else if let Some(socket) = config.cri.discover_socket() {
+                info!(message = "Found CRI runtime socket.", socket = %socket.display());
+                Ok(ContainerRuntime::Cri)
+            } else {
+                Err("Neither a Docker daemon nor a CRI runtime socket could be found.".into())
+            }
+        }
+        runtime => Ok(runtime),
+    }
+}
+
+/// Builds the source reading CRI log files.
+pub(super) fn build(
+    config: DockerLogsConfig,
+    cx: SourceContext,
+    log_namespace: LogNamespace,
+) -> crate::Result<Source> {
+    if config.include_labels.is_some() || config.include_images.is_some() {
+        warn!(
+            message = "The `include_labels` and `include_images` options are not supported by the CRI runtime and will be ignored."
+        );
+    }
+
+    let data_dir = cx
+        .globals
+        .resolve_and_make_data_subdir(config.cri.data_dir.as_ref(), cx.key.id())?;
+
+    let line_agg_config = config
+        .multiline
+        .as_ref()
+        .map(line_agg::Config::try_from)
+        .transpose()?;
+
+    let hostname = crate::get_hostname().ok();
+    let include = config.cri.log_directory.join("*.log");
+    let paths_provider = CriPathsProvider {
+        glob: Glob::new(
+            &[include.clone()],
+            &[],
+            MatchOptions::default(),
+            FileSourceInternalEventsEmitter,
+        )
+        .ok_or("The CRI log directory is not a valid path.")?,
+        config: config.clone(),
+        hostname: hostname.clone(),
+    };
+
+    // Same as the `kubernetes_logs` source, the CRI format caps lines at 16KiB and splits longer ones into partial
+    // lines, so this leaves room for the metadata.
+    let max_line_bytes = 32 * 1024;
+
+    let checkpointer = Checkpointer::new(&data_dir);
+    let file_server = FileServer {
+        paths_provider,
+        max_read_bytes: 2048,
+        ignore_checkpoints: false,
+        read_from: ReadFrom::End,
+        ignore_before: None,
+        max_line_bytes,
+        line_delimiter: Bytes::from("\n"),
+        data_dir,
+        glob_minimum_cooldown: Duration::from_millis(config.cri.glob_minimum_cooldown_ms),
+        fingerprinter: Fingerprinter {
+            strategy: FingerprintStrategy::FirstLinesChecksum {
+                ignored_header_bytes: 0,
+                lines: 1,
+            },
+            max_line_length: max_line_bytes,
+            ignore_not_found: true,
+        },
+        oldest_first: true,
+        // The log files are owned by the runtime, which is responsible for removing them.
+        remove_after: None,
+        emitter: FileSourceInternalEventsEmitter,
+        handle: tokio::runtime::Handle::current(),
+    };
+
+    let shutdown = cx.shutdown;
+    let mut out = cx.out;
+    let checkpoints = checkpointer.view();
+
+    Ok(Box::pin(async move {
+        info!(message = "Starting CRI log file server.", include = %include.display());
+
+        let (tx, rx) = futures::channel::mpsc::channel::<Vec<Line>>(2);
+
+        let bytes_received = register!(BytesReceived::from(Protocol::from("file")));
+        let mut parser = CriParser::new(config.clone(), log_namespace);
+        let events = rx.flat_map(futures::stream::iter).filter_map(move |line| {
+            bytes_received.emit(ByteSize(line.text.len()));
+            let event = parser.parse(&line);
+            checkpoints.update(line.file_id, line.end_offset);
+            futures::future::ready(event)
+        });
+
+        let events: Box<dyn Stream<Item = LogEvent> + Unpin + Send> =
+            if let Some(line_agg_config) = line_agg_config {
+                Box::new(line_agg_adapter(
+                    events,
+                    line_agg::Logic::new(line_agg_config),
+                    log_namespace,
+                ))
+            } else {
+                Box::new(events)
+            };
+
+        let host_key = config.host_key.clone();
+        let mut events =
+            events.map(move |log| add_hostname(log, &host_key, &hostname, log_namespace));
+
+        tokio::spawn(async move {
+            if let Err(error) = out.send_event_stream(&mut events).await {
+                let (count, _) = events.size_hint();
+                emit!(StreamClosedError { error, count });
+            }
+        });
+
+        let span = info_span!("file_server");
+        let shutdown_checkpointer = shutdown.clone().map(|_| ()).boxed();
+        spawn_blocking(move || {
+            let _enter = span.enter();
+            let result = file_server.run(tx, shutdown, shutdown_checkpointer, checkpointer);
+            emit!(FileOpen { count: 0 });
+            // Panic if we encounter any error originating from the file server.
+            result.unwrap();
+        })
+        .map_err(|error| error!(message = "File server unexpectedly stopped.", %error))
+        .await
+    }))
+}
+
+/// A paths provider listing the CRI log files of the containers to collect logs from.
+struct CriPathsProvider {
+    glob: Glob<FileSourceInternalEventsEmitter>,
+    config: DockerLogsConfig,
+    hostname: Option<String>,
+}
+
+impl PathsProvider for CriPathsProvider {
+    type IntoIter = Vec<PathBuf>;
+
+    fn paths(&self) -> Self::IntoIter {
+        self.glob
+            .paths()
+            .into_iter()
+            .filter(|path| {
+                let included = path
+                    .to_str()
+                    .and_then(parse_log_file_path)
+                    .map(|(name, id)| {
+                        !is_self(&self.hostname, id)
+                            && self.config.container_name_or_id_included(id, [name])
+                    })
+                    .unwrap_or(false);
+                if !included {
+                    trace!(message = "Skipping CRI log file.", path = %path.display());
+                }
+                included
+            })
+            .collect()
+    }
+}
+
+/// Extracts the container name and ID from a CRI log file path of the form `<name>-<container ID>.log`.
+fn parse_log_file_path(path: &str) -> Option<(&str, &str)> {
+    let file_name = path.rsplit('/').next()?;
+    let (name, id) = file_name.strip_suffix(".log")?.rsplit_once('-')?;
+
+    (!name.is_empty()
+        && id.len() == CONTAINER_ID_LENGTH
+        && id.bytes().all(|b| b.is_ascii_hexdigit()))
+    .then(|| (name, id))
+}
+
+/// A single line of a CRI log file.
+#[derive(Debug, PartialEq)]
+struct CriLine<'a> {
+    timestamp: &'a str,
+    stream: Bytes,
+    partial: bool,
+    message: Bytes,
+}
+
+/// Splits a CRI log line, `<timestamp> <stream> <P|F> <message>`, into its parts.
+fn parse_cri_line(line: &Bytes) -> Option<CriLine<'_>> {
+    let mut parts = line.splitn(4, |&b| b == b' ');
+    let timestamp = parts.next()?;
+    let stream = parts.next()?;
+    let tag = parts.next()?;
+
+    let stream_value = match stream {
+        b"stdout" => STDOUT.clone(),
+        b"stderr" => STDERR.clone(),
+        _ => return None,
+    };
+    let partial = match tag {
+        b"P" => true,
+        b"F" => false,
+        _ => return None,
+    };
+
+    let start = (timestamp.len() + stream.len() + tag.len() + 3).min(line.len());
+    let mut end = line.len();
+    if end > start && line.ends_with(b"\r") {
+        end -= 1;
+    }
+
+    Some(CriLine {
+        timestamp: std::str::from_utf8(timestamp).ok()?,
+        stream: stream_value,
+        partial,
+        message: line.slice(start..end),
+    })
+}
+
+/// Turns the lines read from the CRI log files into events.
+struct CriParser {
+    config: DockerLogsConfig,
+    log_namespace: LogNamespace,
+    /// Partial events waiting for their final line, by file.
+    partial_event_merge_states: HashMap<String, LogEventMergeState>,
+}
+
+impl CriParser {
+    fn new(config: DockerLogsConfig, log_namespace: LogNamespace) -> Self {
+        Self {
+            config,
+            log_namespace,
+            partial_event_merge_states: HashMap::new(),
+        }
+    }
+
+    fn parse(&mut self, line: &Line) -> Option<LogEvent> {
+        let (name, id) = parse_log_file_path(&line.filename)?;
+
+        let cri_line = match parse_cri_line(&line.text) {
+            Some(cri_line) => cri_line,
+            None => {
+                emit!(ParserMatchError { value: &line.text });
+                return None;
+            }
+        };
+
+        let timestamp = match DateTime::parse_from_rfc3339(cri_line.timestamp) {
+            Ok(timestamp) => Some(timestamp.with_timezone(&Utc)),
+            Err(error) => {
+                emit!(DockerLogsTimestampParseError {
+                    error,
+                    container_id: id
+                });
+                None
+            }
+        };
+
+        let log_namespace = self.log_namespace;
+        let deserializer = BytesDeserializer::new();
+        let mut log = deserializer.parse_single(cri_line.message, log_namespace);
+
+        log_namespace.insert_source_metadata(
+            DockerLogsConfig::NAME,
+            &mut log,
+            Some(LegacyKey::Overwrite(path!(CONTAINER))),
+            path!(CONTAINER),
+            id.to_owned(),
+        );
+        log_namespace.insert_source_metadata(
+            DockerLogsConfig::NAME,
+            &mut log,
+            Some(LegacyKey::Overwrite(path!(NAME))),
+            path!(NAME),
+            name.to_owned(),
+        );
+        log_namespace.insert_source_metadata(
+            DockerLogsConfig::NAME,
+            &mut log,
+            Some(LegacyKey::Overwrite(path!(STREAM))),
+            path!(STREAM),
+            cri_line.stream,
+        );
+        log_namespace.insert_vector_metadata(
+            &mut log,
+            path!(log_schema().source_type_key()),
+            path!("source_type"),
+            Bytes::from_static(DockerLogsConfig::NAME.as_bytes()),
+        );
+
+        match log_namespace {
+            LogNamespace::Vector => {
+                if let Some(timestamp) = timestamp {
+                    log.insert(
+                        metadata_path!(DockerLogsConfig::NAME, "timestamp"),
+                        timestamp,
+                    );
+                }
+
+                log.insert(metadata_path!("vector", "ingest_timestamp"), Utc::now());
+            }
+            LogNamespace::Legacy => {
+                if let Some(timestamp) = timestamp {
+                    log.try_insert((PathPrefix::Event, log_schema().timestamp_key()), timestamp);
+                }
+            }
+        };
+
+        let mut partial_event_merge_state = self.partial_event_merge_states.remove(&line.filename);
+        let log = merge_partial_event(
+            log,
+            cri_line.partial,
+            self.config.auto_partial_merge,
+            self.config.partial_event_marker_field.clone(),
+            &mut partial_event_merge_state,
+            log_namespace,
+        );
+        if let Some(state) = partial_event_merge_state {
+            self.partial_event_merge_states
+                .insert(line.filename.clone(), state);
+        }
+
+        let log = log?;
+        emit!(DockerLogsEventsReceived {
+            byte_size: log.estimated_json_encoded_size_of(),
+            container_id: id,
+            container_name: name,
+        });
+
+        Some(log)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "8d8b0cd2a3eb3423a3e2f3c91ef0d3e1bc4a1f0146b1ff24d7a4b0c0f0e2c1d3";
+
+    fn line(filename: &str, text: &'static str) -> Line {
+        Line {
+            text: Bytes::from_static(text.as_bytes()),
+            filename: filename.to_owned(),
+            file_id: file_source::FileFingerprint::FirstLinesChecksum(0),
+            start_offset: 0,
+            end_offset: 0,
+        }
+    }
+
+    #[test]
+    fn parses_log_file_path() {
+        let path = format!("/var/log/containers/web-6b9f_default_nginx-{}.log", ID);
+        assert_eq!(
+            parse_log_file_path(&path),
+            Some(("web-6b9f_default_nginx", ID))
+        );
+
+        assert_eq!(parse_log_file_path("/var/log/containers/nginx.log"), None);
+        assert_eq!(
+            parse_log_file_path("/var/log/containers/nginx-1234.log"),
+            None
+        );
+        assert_eq!(
+            parse_log_file_path(&format!("/var/log/containers/nginx-{}.txt", ID)),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_cri_line() {
+        let text = Bytes::from_static(b"2023-01-02T03:04:05.123456789Z stdout F hello world");
+        assert_eq!(
+            parse_cri_line(&text),
+            Some(CriLine {
+                timestamp: "2023-01-02T03:04:05.123456789Z",
+                stream: STDOUT.clone(),
+                partial: false,
+                message: Bytes::from_static(b"hello world"),
+            })
+        );
+
+        let text = Bytes::from_static(b"2023-01-02T03:04:05Z stderr P ");
+        let cri_line = parse_cri_line(&text).unwrap();
+        assert!(cri_line.partial);
+        assert_eq!(cri_line.stream, STDERR.clone());
+        assert!(cri_line.message.is_empty());
+
+        assert_eq!(
+            parse_cri_line(&Bytes::from_static(b"2023-01-02T03:04:05Z stdin F hello")),
+            None
+        );
+        assert_eq!(parse_cri_line(&Bytes::from_static(b"hello")), None);
+    }
+
+    #[test]
+    fn creates_events() {
+        let config = DockerLogsConfig::default();
+        let filename = format!("/var/log/containers/nginx-{}.log", ID);
+        let mut parser = CriParser::new(config, LogNamespace::Legacy);
+
+        let log = parser
+            .parse(&line(&filename, "2023-01-02T03:04:05Z stdout F hello"))
+            .unwrap();
+        assert_eq!(log[log_schema().message_key()], "hello".into());
+        assert_eq!(log[CONTAINER], ID.into());
+        assert_eq!(log[NAME], "nginx".into());
+        assert_eq!(log[STREAM], "stdout".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            DateTime::parse_from_rfc3339("2023-01-02T03:04:05Z")
+                .unwrap()
+                .with_timezone(&Utc)
+                .into()
+        );
+
+        assert!(parser
+            .parse(&line(&filename, "2023-01-02T03:04:06Z stdout P hello "))
+            .is_none());
+        let log = parser
+            .parse(&line(&filename, "2023-01-02T03:04:07Z stdout F world"))
+            .unwrap();
+        assert_eq!(log[log_schema().message_key()], "hello world".into());
+    }
+
+    #[test]
+    fn marks_partial_events_without_merging() {
+        let config = DockerLogsConfig {
+            auto_partial_merge: false,
+            ..Default::default()
+        };
+        let filename = format!("/var/log/containers/nginx-{}.log", ID);
+        let mut parser = CriParser::new(config, LogNamespace::Legacy);
+
+        let log = parser
+            .parse(&line(&filename, "2023-01-02T03:04:06Z stdout P hello "))
+            .unwrap();
+        assert_eq!(log[crate::event::PARTIAL], true.into());
+    }
+
+    #[test]
+    fn resolves_runtime() {
+        let config = DockerLogsConfig {
+            runtime: ContainerRuntime::Auto,
+            docker_host: Some("unix:///var/run/docker.sock".into()),
+            ..Default::default()
+        };
+        assert_eq!(resolve_runtime(&config).unwrap(), ContainerRuntime::Docker);
+
+        let config = DockerLogsConfig {
+            runtime: ContainerRuntime::Cri,
+            ..Default::default()
+        };
+        assert_eq!(resolve_runtime(&config).unwrap(), ContainerRuntime::Cri);
+    }
+}
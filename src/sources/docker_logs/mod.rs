@@ -43,9 +43,12 @@ use crate::{
     SourceSender,
 };
 
+mod cri;
 #[cfg(test)]
 mod tests;
 
+use self::cri::{ContainerRuntime, CriConfig};
+
 const IMAGE: &str = "image";
 const CREATED_AT: &str = "container_created_at";
 const NAME: &str = "container_name";
@@ -136,6 +139,12 @@ pub struct DockerLogsConfig {
     #[configurable(derived)]
     tls: Option<DockerTlsConfig>,
 
+    #[configurable(derived)]
+    runtime: ContainerRuntime,
+
+    #[configurable(derived)]
+    cri: CriConfig,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[serde(default)]
     #[configurable(metadata(docs::hidden))]
@@ -156,6 +165,8 @@ impl Default for DockerLogsConfig {
             auto_partial_merge: true,
             multiline: None,
             retry_backoff_secs: 2,
+            runtime: ContainerRuntime::default(),
+            cri: CriConfig::default(),
             log_namespace: None,
         }
     }
@@ -207,6 +218,15 @@ impl_generate_config_from_default!(DockerLogsConfig);
 impl SourceConfig for DockerLogsConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
+
+        if cri::resolve_runtime(self)? == ContainerRuntime::Cri {
+            return cri::build(
+                self.clone().with_empty_partial_event_marker_field_as_none(),
+                cx,
+                log_namespace,
+            );
+        }
+
         let source = DockerLogsSource::new(
             self.clone().with_empty_partial_event_marker_field_as_none(),
             cx.out,
@@ -239,6 +259,16 @@ impl SourceConfig for DockerLogsConfig {
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
         let log_namespace = global_log_namespace.merge(self.log_namespace);
+
+        // The CRI log files don't carry the image, creation time or labels of the containers.
+        let docker_only = |kind: Kind| {
+            if self.runtime == ContainerRuntime::Docker {
+                kind
+            } else {
+                kind.or_undefined()
+            }
+        };
+
         let schema_definition = BytesDeserializerConfig
             .schema_definition(log_namespace)
             .with_source_metadata(
@@ -261,7 +291,7 @@ impl SourceConfig for DockerLogsConfig {
                 Self::NAME,
                 Some(LegacyKey::Overwrite(owned_value_path!(IMAGE))),
                 &owned_value_path!(IMAGE),
-                Kind::bytes(),
+                docker_only(Kind::bytes()),
                 None,
             )
             .with_source_metadata(
@@ -275,7 +305,7 @@ impl SourceConfig for DockerLogsConfig {
                 Self::NAME,
                 Some(LegacyKey::Overwrite(owned_value_path!(CREATED_AT))),
                 &owned_value_path!(CREATED_AT),
-                Kind::timestamp(),
+                docker_only(Kind::timestamp()),
                 None,
             )
             .with_source_metadata(
@@ -626,13 +656,18 @@ impl DockerLogsSource {
     }
 
     fn exclude_self(&self, id: &str) -> bool {
-        self.hostname
-            .as_ref()
-            .map(|hostname| id.starts_with(hostname) && hostname.len() >= MIN_HOSTNAME_LENGTH)
-            .unwrap_or(false)
+        is_self(&self.hostname, id)
     }
 }
 
+/// Returns `true` if the container ID matches the hostname, that is, if it's the container Vector is running in.
+fn is_self(hostname: &Option<String>, id: &str) -> bool {
+    hostname
+        .as_ref()
+        .map(|hostname| id.starts_with(hostname) && hostname.len() >= MIN_HOSTNAME_LENGTH)
+        .unwrap_or(false)
+}
+
 /// Used to construct and start event stream futures
 #[derive(Clone)]
 struct EventStreamBuilder {
@@ -1109,74 +1144,14 @@ impl ContainerLogInfo {
             }
         };
 
-        // If automatic partial event merging is requested - perform the
-        // merging.
-        // Otherwise mark partial events and return all the events with no
-        // merging.
-        let log = if auto_partial_merge {
-            // Partial event events merging logic.
-
-            // If event is partial, stash it and return `None`.
-            if is_partial {
-                // If we already have a partial event merge state, the current
-                // message has to be merged into that existing state.
-                // Otherwise, create a new partial event merge state with the
-                // current message being the initial one.
-                if let Some(partial_event_merge_state) = partial_event_merge_state {
-                    // Depending on the log namespace the actual contents of the log "message" will be
-                    // found in either the root of the event ("."), or at the globally configured "message_key".
-                    match log_namespace {
-                        LogNamespace::Vector => {
-                            partial_event_merge_state.merge_in_next_event(log, &["."]);
-                        }
-                        LogNamespace::Legacy => {
-                            partial_event_merge_state.merge_in_next_event(
-                                log,
-                                &[log_schema().message_key().to_string()],
-                            );
-                        }
-                    }
-                } else {
-                    *partial_event_merge_state = Some(LogEventMergeState::new(log));
-                };
-                return None;
-            };
-
-            // This is not a partial event. If we have a partial event merge
-            // state from before, the current event must be a final event, that
-            // would give us a merged event we can return.
-            // Otherwise it's just a regular event that we return as-is.
-            match partial_event_merge_state.take() {
-                // Depending on the log namespace the actual contents of the log "message" will be
-                // found in either the root of the event ("."), or at the globally configured "message_key".
-                Some(partial_event_merge_state) => match log_namespace {
-                    LogNamespace::Vector => {
-                        partial_event_merge_state.merge_in_final_event(log, &["."])
-                    }
-                    LogNamespace::Legacy => partial_event_merge_state
-                        .merge_in_final_event(log, &[log_schema().message_key().to_string()]),
-                },
-                None => log,
-            }
-        } else {
-            // If the event is partial, just set the partial event marker field.
-            if is_partial {
-                // Only add partial event marker field if it's requested.
-                if let Some(partial_event_marker_field) = partial_event_marker_field {
-                    log_namespace.insert_source_metadata(
-                        DockerLogsConfig::NAME,
-                        &mut log,
-                        Some(LegacyKey::Overwrite(path!(
-                            partial_event_marker_field.as_str()
-                        ))),
-                        path!(event::PARTIAL),
-                        true,
-                    );
-                }
-            }
-            // Return the log event as is, partial or not. No merging here.
-            log
-        };
+        let log = merge_partial_event(
+            log,
+            is_partial,
+            auto_partial_merge,
+            partial_event_marker_field,
+            partial_event_merge_state,
+            log_namespace,
+        )?;
 
         // Partial or not partial - we return the event we got here, because all
         // other cases were handled earlier.
@@ -1190,6 +1165,83 @@ impl ContainerLogInfo {
     }
 }
 
+/// Merges partial events if `auto_partial_merge` is enabled, otherwise marks them with the partial event marker field.
+///
+/// Returns `None` while a partial event is being stashed, waiting for the rest of its lines.
+fn merge_partial_event(
+    mut log: LogEvent,
+    is_partial: bool,
+    auto_partial_merge: bool,
+    partial_event_marker_field: Option<String>,
+    partial_event_merge_state: &mut Option<LogEventMergeState>,
+    log_namespace: LogNamespace,
+) -> Option<LogEvent> {
+    // If automatic partial event merging is requested - perform the
+    // merging.
+    // Otherwise mark partial events and return all the events with no
+    // merging.
+    if auto_partial_merge {
+        // Partial event events merging logic.
+
+        // If event is partial, stash it and return `None`.
+        if is_partial {
+            // If we already have a partial event merge state, the current
+            // message has to be merged into that existing state.
+            // Otherwise, create a new partial event merge state with the
+            // current message being the initial one.
+            if let Some(partial_event_merge_state) = partial_event_merge_state {
+                // Depending on the log namespace the actual contents of the log "message" will be
+                // found in either the root of the event ("."), or at the globally configured "message_key".
+                match log_namespace {
+                    LogNamespace::Vector => {
+                        partial_event_merge_state.merge_in_next_event(log, &["."]);
+                    }
+                    LogNamespace::Legacy => {
+                        partial_event_merge_state
+                            .merge_in_next_event(log, &[log_schema().message_key().to_string()]);
+                    }
+                }
+            } else {
+                *partial_event_merge_state = Some(LogEventMergeState::new(log));
+            };
+            return None;
+        };
+
+        // This is not a partial event. If we have a partial event merge
+        // state from before, the current event must be a final event, that
+        // would give us a merged event we can return.
+        // Otherwise it's just a regular event that we return as-is.
+        Some(match partial_event_merge_state.take() {
+            // Depending on the log namespace the actual contents of the log "message" will be
+            // found in either the root of the event ("."), or at the globally configured "message_key".
+            Some(partial_event_merge_state) => match log_namespace {
+                LogNamespace::Vector => partial_event_merge_state.merge_in_final_event(log, &["."]),
+                LogNamespace::Legacy => partial_event_merge_state
+                    .merge_in_final_event(log, &[log_schema().message_key().to_string()]),
+            },
+            None => log,
+        })
+    } else {
+        // If the event is partial, just set the partial event marker field.
+        if is_partial {
+            // Only add partial event marker field if it's requested.
+            if let Some(partial_event_marker_field) = partial_event_marker_field {
+                log_namespace.insert_source_metadata(
+                    DockerLogsConfig::NAME,
+                    &mut log,
+                    Some(LegacyKey::Overwrite(path!(
+                        partial_event_marker_field.as_str()
+                    ))),
+                    path!(event::PARTIAL),
+                    true,
+                );
+            }
+        }
+        // Return the log event as is, partial or not. No merging here.
+        Some(log)
+    }
+}
+
 struct ContainerMetadata {
     /// label.key -> String
     labels: HashMap<String, String>,
@@ -10,13 +10,14 @@ use codecs::{
     StreamDecodingError,
 };
 use futures::StreamExt;
+use rand::{thread_rng, Rng};
 use smallvec::SmallVec;
 use snafu::Snafu;
 use tokio::{
     io::{AsyncRead, BufReader},
     process::Command,
     sync::mpsc::{channel, Sender},
-    time::{self, sleep, Duration, Instant},
+    time::{self, sleep, Duration, Instant, MissedTickBehavior},
 };
 use tokio_stream::wrappers::IntervalStream;
 use tokio_util::codec::FramedRead;
@@ -35,6 +36,7 @@ use crate::{
     },
     serde::default_decoding,
     shutdown::ShutdownSignal,
+    source_sender::ClosedError,
     SourceSender,
 };
 use lookup::{owned_value_path, path};
@@ -66,6 +68,12 @@ pub struct ExecConfig {
     #[serde(default = "default_include_stderr")]
     pub include_stderr: bool,
 
+    /// Whether or not to send the events generated from stderr to a separate output.
+    ///
+    /// When enabled, these events are sent to the `stderr` output instead of the default one, which other components can consume as `<source_id>.stderr`. Has no effect unless `include_stderr` is enabled.
+    #[serde(default)]
+    pub separate_stderr_output: bool,
+
     /// The maximum buffer size allowed before a log event will be generated.
     #[serde(default = "default_maximum_buffer_size")]
     pub maximum_buffer_size_bytes: usize,
@@ -102,9 +110,26 @@ pub enum Mode {
 pub struct ScheduledConfig {
     /// The interval, in seconds, between scheduled command runs.
     ///
-    /// If the command takes longer than `exec_interval_secs` to run, it will be killed.
+    /// If the command takes longer than `exec_interval_secs` to run, it will be killed, unless `timeout_secs` is set.
     #[serde(default = "default_exec_interval_secs")]
     exec_interval_secs: u64,
+
+    /// The maximum amount of time, in seconds, that a scheduled command run can take.
+    ///
+    /// If the command takes longer to run, it will be killed. Defaults to `exec_interval_secs`. Runs that take longer than `exec_interval_secs` delay the next run rather than overlapping with it.
+    timeout_secs: Option<u64>,
+
+    /// The maximum amount of time, in seconds, to randomly delay each scheduled command run by.
+    ///
+    /// This spreads out the runs of a command scheduled on many hosts at once. The delay doesn't count towards the timeout of the run.
+    #[serde(default)]
+    jitter_secs: u64,
+
+    /// Whether or not to add the exit code of the command to the events of each run.
+    ///
+    /// When enabled, the events of a run are held until the command exits, and are then sent with an `exit_code` field. The field is absent if the command was killed, either because it timed out or by a signal.
+    #[serde(default)]
+    include_exit_code: bool,
 }
 
 /// Configuration options for streaming commands.
@@ -127,6 +152,8 @@ pub enum ExecConfigError {
     CommandEmpty,
     #[snafu(display("The maximum buffer size must be greater than zero"))]
     ZeroBuffer,
+    #[snafu(display("The timeout must be greater than zero"))]
+    ZeroTimeout,
 }
 
 impl Default for ExecConfig {
@@ -135,11 +162,15 @@ impl Default for ExecConfig {
             mode: Mode::Scheduled,
             scheduled: Some(ScheduledConfig {
                 exec_interval_secs: default_exec_interval_secs(),
+                timeout_secs: None,
+                jitter_secs: 0,
+                include_exit_code: false,
             }),
             streaming: None,
             command: vec!["echo".to_owned(), "Hello World!".to_owned()],
             working_directory: None,
             include_stderr: default_include_stderr(),
+            separate_stderr_output: false,
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
             framing: None,
             decoding: default_decoding(),
@@ -178,6 +209,7 @@ const STDERR: &str = "stderr";
 const STREAM_KEY: &str = "stream";
 const PID_KEY: &str = "pid";
 const COMMAND_KEY: &str = "command";
+const EXIT_CODE_KEY: &str = "exit_code";
 
 impl_generate_config_from_default!(ExecConfig);
 
@@ -187,6 +219,8 @@ impl ExecConfig {
             Err(ExecConfigError::CommandEmpty)
         } else if self.maximum_buffer_size_bytes == 0 {
             Err(ExecConfigError::ZeroBuffer)
+        } else if matches!(&self.scheduled, Some(config) if config.timeout_secs == Some(0)) {
+            Err(ExecConfigError::ZeroTimeout)
         } else {
             Ok(())
         }
@@ -203,6 +237,31 @@ impl ExecConfig {
         }
     }
 
+    fn timeout_secs_or_default(&self) -> u64 {
+        match &self.scheduled {
+            None => default_exec_interval_secs(),
+            Some(config) => config.timeout_secs.unwrap_or(config.exec_interval_secs),
+        }
+    }
+
+    const fn jitter_secs_or_default(&self) -> u64 {
+        match &self.scheduled {
+            None => 0,
+            Some(config) => config.jitter_secs,
+        }
+    }
+
+    const fn include_exit_code(&self) -> bool {
+        match (&self.mode, &self.scheduled) {
+            (Mode::Scheduled, Some(config)) => config.include_exit_code,
+            _ => false,
+        }
+    }
+
+    const fn separate_stderr_output(&self) -> bool {
+        self.include_stderr && self.separate_stderr_output
+    }
+
     const fn respawn_on_exit_or_default(&self) -> bool {
         match &self.streaming {
             None => default_respawn_on_exit(),
@@ -300,7 +359,28 @@ impl SourceConfig for ExecConfig {
                 None,
             );
 
-        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+        let schema_definition = if self.include_exit_code() {
+            schema_definition.with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(EXIT_CODE_KEY))),
+                &owned_value_path!(EXIT_CODE_KEY),
+                Kind::integer().or_undefined(),
+                None,
+            )
+        } else {
+            schema_definition
+        };
+
+        let mut outputs = vec![Output::default(self.decoding.output_type())
+            .with_schema_definition(schema_definition.clone())];
+        if self.separate_stderr_output() {
+            outputs.push(
+                Output::default(self.decoding.output_type())
+                    .with_port(STDERR)
+                    .with_schema_definition(schema_definition),
+            );
+        }
+        outputs
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -319,40 +399,42 @@ async fn run_scheduled(
 ) -> Result<(), ()> {
     debug!("Starting scheduled exec runs.");
     let schedule = Duration::from_secs(exec_interval_secs);
+    let jitter_secs = config.jitter_secs_or_default();
+    let options = RunOptions {
+        timeout: Some(Duration::from_secs(config.timeout_secs_or_default())),
+        include_exit_code: config.include_exit_code(),
+    };
 
-    let mut interval = IntervalStream::new(time::interval(schedule)).take_until(shutdown.clone());
+    // Runs that take longer than the interval delay the next one instead of being followed by a burst of runs.
+    let mut interval = time::interval(schedule);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut interval = IntervalStream::new(interval).take_until(shutdown.clone());
 
     while interval.next().await.is_some() {
-        // Wait for our task to finish, wrapping it in a timeout
-        let timeout = tokio::time::timeout(
-            schedule,
-            run_command(
-                config.clone(),
-                hostname.clone(),
-                decoder.clone(),
-                shutdown.clone(),
-                out.clone(),
-                log_namespace,
-            ),
+        if jitter_secs > 0 {
+            let jitter = Duration::from_millis(thread_rng().gen_range(0..=jitter_secs * 1000));
+            tokio::select! {
+                _ = shutdown.clone() => break,
+                _ = sleep(jitter) => {}
+            }
+        }
+
+        let output = run_command(
+            config.clone(),
+            hostname.clone(),
+            decoder.clone(),
+            shutdown.clone(),
+            out.clone(),
+            log_namespace,
+            options,
         )
         .await;
 
-        match timeout {
-            Ok(output) => {
-                if let Err(command_error) = output {
-                    emit!(ExecFailedError {
-                        command: config.command_line().as_str(),
-                        error: command_error,
-                    });
-                }
-            }
-            Err(error) => {
-                emit!(ExecTimeoutError {
-                    command: config.command_line().as_str(),
-                    elapsed_seconds: schedule.as_secs(),
-                    error,
-                });
-            }
+        if let Err(command_error) = output {
+            emit!(ExecFailedError {
+                command: config.command_line().as_str(),
+                error: command_error,
+            });
         }
     }
 
@@ -383,6 +465,7 @@ async fn run_streaming(
                 shutdown.clone(),
                 out.clone(),
                 log_namespace,
+                RunOptions::default(),
             )
             .await;
 
@@ -407,6 +490,7 @@ async fn run_streaming(
             shutdown,
             out,
             log_namespace,
+            RunOptions::default(),
         )
         .await;
 
@@ -421,6 +505,17 @@ async fn run_streaming(
     Ok(())
 }
 
+/// Options that apply to a single run of the command.
+#[derive(Clone, Copy, Debug, Default)]
+struct RunOptions {
+    /// The maximum duration of the run, after which the command is killed.
+    timeout: Option<Duration>,
+
+    /// Whether or not to hold the events until the command exits, to add its exit code to them.
+    include_exit_code: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn run_command(
     config: ExecConfig,
     hostname: Option<String>,
@@ -428,6 +523,7 @@ async fn run_command(
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
     log_namespace: LogNamespace,
+    options: RunOptions,
 ) -> Result<Option<ExitStatus>, Error> {
     debug!("Starting command run.");
     let mut command = build_command(&config);
@@ -467,6 +563,16 @@ async fn run_command(
 
     let bytes_received = register!(BytesReceived::from(Protocol::NONE));
 
+    let timeout = async {
+        match options.timeout {
+            Some(timeout) => sleep(timeout).await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(timeout);
+    let mut timed_out = false;
+    let mut held_events = Vec::new();
+
     'outer: loop {
         tokio::select! {
             _ = &mut shutdown => {
@@ -474,6 +580,11 @@ async fn run_command(
                         break 'outer; // couldn't signal, exit early
                 }
             }
+            _ = &mut timeout => {
+                kill_timed_out_child(&mut child, &config, start);
+                timed_out = true;
+                break 'outer;
+            }
             v = receiver.recv() => {
                 match v {
                     None => break 'outer,
@@ -490,7 +601,9 @@ async fn run_command(
                         for event in &mut events {
                             handle_event(&config, &hostname, &Some(stream.to_string()), pid, event, log_namespace);
                         }
-                        if let Err(error) = out.send_batch(events).await {
+                        if options.include_exit_code {
+                            held_events.extend(events.into_iter().map(|event| (stream, event)));
+                        } else if let Err(error) = send_events(&mut out, &config, stream, events).await {
                             emit!(StreamClosedError { count, error });
                             break;
                         }
@@ -500,9 +613,23 @@ async fn run_command(
         }
     }
 
+    let exit_status = if options.include_exit_code && !timed_out {
+        // The output of the command can be closed before it exits, so wait for its exit code.
+        let exit_status = tokio::select! {
+            exit_status = child.wait() => Some(exit_status.map(Some)),
+            _ = &mut timeout => None,
+        };
+        exit_status.unwrap_or_else(|| {
+            kill_timed_out_child(&mut child, &config, start);
+            Ok(None)
+        })
+    } else {
+        child.try_wait()
+    };
+
     let elapsed = start.elapsed();
 
-    let result = match child.try_wait() {
+    let result = match exit_status {
         Ok(Some(exit_status)) => {
             handle_exit_status(&config, exit_status.code(), elapsed);
             Ok(Some(exit_status))
@@ -519,11 +646,81 @@ async fn run_command(
         }
     };
 
+    if !held_events.is_empty() {
+        let exit_code = result
+            .as_ref()
+            .ok()
+            .and_then(|exit_status| exit_status.and_then(|exit_status| exit_status.code()));
+        send_held_events(&mut out, &config, held_events, exit_code, log_namespace).await;
+    }
+
     debug!("Finished command run.");
 
     result
 }
 
+fn kill_timed_out_child(child: &mut tokio::process::Child, config: &ExecConfig, start: Instant) {
+    emit!(ExecTimeoutError {
+        command: config.command_line().as_str(),
+        elapsed_seconds: start.elapsed().as_secs(),
+    });
+
+    if let Err(error) = child.start_kill() {
+        error!(message = "Unable to kill timed out command.", %error);
+    }
+}
+
+async fn send_events(
+    out: &mut SourceSender,
+    config: &ExecConfig,
+    stream: &str,
+    events: impl IntoIterator<Item = Event>,
+) -> Result<(), ClosedError> {
+    if stream == STDERR && config.separate_stderr_output() {
+        out.send_batch_named(STDERR, events).await
+    } else {
+        out.send_batch(events).await
+    }
+}
+
+/// Sends the events held until the command exited, adding its exit code to them.
+async fn send_held_events(
+    out: &mut SourceSender,
+    config: &ExecConfig,
+    held_events: Vec<(&'static str, Event)>,
+    exit_code: Option<i32>,
+    log_namespace: LogNamespace,
+) {
+    let (stderr, stdout): (Vec<_>, Vec<_>) = held_events
+        .into_iter()
+        .map(|(stream, mut event)| {
+            if let (Event::Log(log), Some(exit_code)) = (&mut event, exit_code) {
+                log_namespace.insert_source_metadata(
+                    ExecConfig::NAME,
+                    log,
+                    Some(LegacyKey::InsertIfEmpty(path!(EXIT_CODE_KEY))),
+                    path!(EXIT_CODE_KEY),
+                    exit_code,
+                );
+            }
+            (stream, event)
+        })
+        .partition(|(stream, _)| *stream == STDERR && config.separate_stderr_output());
+
+    for (stream, events) in [(STDOUT, stdout), (STDERR, stderr)] {
+        if events.is_empty() {
+            continue;
+        }
+
+        let count = events.len();
+        let events = events.into_iter().map(|(_, event)| event);
+        if let Err(error) = send_events(out, config, stream, events).await {
+            emit!(StreamClosedError { count, error });
+            return;
+        }
+    }
+}
+
 fn handle_exit_status(config: &ExecConfig, exit_status: Option<i32>, exec_duration: Duration) {
     emit!(ExecCommandExecuted {
         command: config.command_line().as_str(),
@@ -714,7 +911,7 @@ fn spawn_reader_thread<R: 'static + AsyncRead + Unpin + std::marker::Send>(
 mod tests {
     use bytes::Bytes;
     use std::io::Cursor;
-    use vector_core::event::EventMetadata;
+    use vector_core::event::{EventContainer, EventMetadata, EventStatus};
 
     #[cfg(unix)]
     use futures::task::Poll;
@@ -893,6 +1090,7 @@ mod tests {
             command: vec!["./runner".to_owned(), "arg1".to_owned(), "arg2".to_owned()],
             working_directory: Some(PathBuf::from("/tmp")),
             include_stderr: default_include_stderr(),
+            separate_stderr_output: false,
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
             framing: None,
             decoding: default_decoding(),
@@ -970,6 +1168,7 @@ mod tests {
                 shutdown,
                 tx,
                 LogNamespace::Legacy,
+                RunOptions::default(),
             ),
         );
 
@@ -1005,6 +1204,7 @@ mod tests {
                         shutdown,
                         tx,
                         LogNamespace::Legacy,
+                        RunOptions::default(),
                     ),
                 )
                 .await;
@@ -1058,6 +1258,7 @@ mod tests {
             shutdown,
             tx,
             LogNamespace::Legacy,
+            RunOptions::default(),
         ));
 
         tokio::time::sleep(Duration::from_secs(1)).await; // let the source start the command
@@ -1090,6 +1291,148 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_zero_timeout() {
+        let config: ExecConfig = toml::from_str(
+            r#"
+            mode = "scheduled"
+            command = ["echo", "Hello World!"]
+            scheduled.timeout_secs = 0
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.validate(), Err(ExecConfigError::ZeroTimeout));
+    }
+
+    #[test]
+    fn test_separate_stderr_output() {
+        let mut config = standard_scheduled_test_config();
+        assert_eq!(config.outputs(LogNamespace::Legacy).len(), 1);
+
+        config.separate_stderr_output = true;
+        let outputs = config.outputs(LogNamespace::Legacy);
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[1].port.as_deref(), Some(STDERR));
+
+        config.include_stderr = false;
+        assert_eq!(config.outputs(LogNamespace::Legacy).len(), 1);
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_run_command_separate_stderr_output() {
+        let mut config = standard_scheduled_test_config();
+        config.command = vec![
+            String::from("sh"),
+            String::from("-c"),
+            String::from("echo out; echo err >&2"),
+        ];
+        config.separate_stderr_output = true;
+
+        let (mut tx, rx) = SourceSender::new_test();
+        let stderr_rx = tx.add_outputs(EventStatus::Delivered, STDERR.to_string());
+
+        run_command(
+            config,
+            None,
+            Default::default(),
+            ShutdownSignal::noop(),
+            tx,
+            LogNamespace::Legacy,
+            RunOptions::default(),
+        )
+        .await
+        .expect("command error");
+
+        let events = rx.collect::<Vec<_>>().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "out".into());
+
+        let events = stderr_rx
+            .flat_map(|events| futures::stream::iter(events.into_events()))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "err".into());
+        assert_eq!(events[0].as_log()[STREAM_KEY], STDERR.into());
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_run_command_include_exit_code() {
+        let mut config = standard_scheduled_test_config();
+        config.command = vec![
+            String::from("sh"),
+            String::from("-c"),
+            String::from("echo failing; exit 3"),
+        ];
+
+        let (tx, rx) = SourceSender::new_test();
+        let exit_status = run_command(
+            config,
+            None,
+            Default::default(),
+            ShutdownSignal::noop(),
+            tx,
+            LogNamespace::Legacy,
+            RunOptions {
+                timeout: Some(Duration::from_secs(5)),
+                include_exit_code: true,
+            },
+        )
+        .await
+        .expect("command error");
+        assert_eq!(Some(3), exit_status.and_then(|status| status.code()));
+
+        let events = rx.collect::<Vec<_>>().await;
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "failing".into());
+        assert_eq!(log[EXIT_CODE_KEY], 3.into());
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_run_command_timeout() {
+        let mut config = standard_scheduled_test_config();
+        config.command = vec![
+            String::from("sh"),
+            String::from("-c"),
+            String::from("echo started; sleep 30"),
+        ];
+
+        let (tx, rx) = SourceSender::new_test();
+        let exit_status = tokio::time::timeout(
+            Duration::from_secs(10),
+            run_command(
+                config,
+                None,
+                Default::default(),
+                ShutdownSignal::noop(),
+                tx,
+                LogNamespace::Legacy,
+                RunOptions {
+                    timeout: Some(Duration::from_secs(1)),
+                    include_exit_code: true,
+                },
+            ),
+        )
+        .await
+        .expect("command was not killed")
+        .expect("command error");
+        assert!(exit_status.is_none());
+
+        // The events of the run are still sent, without an exit code.
+        let events = rx.collect::<Vec<_>>().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "started".into()
+        );
+        assert!(events[0].as_log().get(EXIT_CODE_KEY).is_none());
+    }
+
     fn standard_scheduled_test_config() -> ExecConfig {
         Default::default()
     }
@@ -1105,6 +1448,7 @@ mod tests {
             command: vec!["yes".to_owned()],
             working_directory: None,
             include_stderr: default_include_stderr(),
+            separate_stderr_output: false,
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
             framing: None,
             decoding: default_decoding(),
@@ -0,0 +1,125 @@
+use std::{
+    collections::BTreeSet,
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+const CHECKPOINT_FILENAME: &str = "checkpoint.json";
+const TMP_CHECKPOINT_FILENAME: &str = "checkpoint.new.json";
+
+/// The position in the audit log following the last event read.
+///
+/// The audit log can only be searched by time, so the identifiers of the events read with the
+/// latest timestamp are kept to skip them when searching from that timestamp again.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Cursor {
+    /// The timestamp of the latest event read, in milliseconds since the Unix epoch.
+    pub timestamp_ms: i64,
+    pub document_ids: BTreeSet<String>,
+}
+
+impl Cursor {
+    pub fn new(timestamp_ms: i64) -> Self {
+        Self {
+            timestamp_ms,
+            document_ids: BTreeSet::new(),
+        }
+    }
+
+    /// Whether or not the event was already read.
+    pub fn is_read(&self, timestamp_ms: i64, document_id: &str) -> bool {
+        timestamp_ms < self.timestamp_ms
+            || (timestamp_ms == self.timestamp_ms && self.document_ids.contains(document_id))
+    }
+
+    pub fn advance(&mut self, timestamp_ms: i64, document_id: String) {
+        if timestamp_ms > self.timestamp_ms {
+            self.timestamp_ms = timestamp_ms;
+            self.document_ids.clear();
+        }
+        if timestamp_ms == self.timestamp_ms {
+            self.document_ids.insert(document_id);
+        }
+    }
+}
+
+/// The cursor persisted as JSON in the data directory.
+pub struct Checkpointer {
+    path: PathBuf,
+    tmp_path: PathBuf,
+}
+
+impl Checkpointer {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(CHECKPOINT_FILENAME),
+            tmp_path: data_dir.join(TMP_CHECKPOINT_FILENAME),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the cursor previously persisted, if any.
+    pub async fn load(&self) -> io::Result<Option<Cursor>> {
+        match fs::read(&self.path).await {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map(Some)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Persists `cursor`.
+    ///
+    /// The cursor is written to a temporary file first, so a crash never leaves a partially
+    /// written file behind.
+    pub async fn set(&self, cursor: &Cursor) -> io::Result<()> {
+        let contents = serde_json::to_vec(cursor)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(&self.tmp_path, contents).await?;
+        fs::rename(&self.tmp_path, &self.path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_events_read() {
+        let mut cursor = Cursor::new(1_000);
+        assert!(cursor.is_read(999, "a"));
+        assert!(!cursor.is_read(1_000, "a"));
+
+        cursor.advance(1_000, "a".into());
+        assert!(cursor.is_read(1_000, "a"));
+        assert!(!cursor.is_read(1_000, "b"));
+
+        cursor.advance(1_001, "b".into());
+        assert_eq!(cursor.timestamp_ms, 1_001);
+        assert!(cursor.is_read(1_000, "b"));
+        assert!(cursor.is_read(1_001, "b"));
+        assert!(!cursor.is_read(1_001, "a"));
+    }
+
+    #[tokio::test]
+    async fn persists_cursor() {
+        let data_dir = tempfile::tempdir().unwrap();
+
+        let checkpointer = Checkpointer::new(data_dir.path());
+        assert_eq!(checkpointer.load().await.unwrap(), None);
+
+        let mut cursor = Cursor::new(1_673_353_800_000);
+        cursor.advance(1_673_353_800_000, "LwW2vpJZCDS-WUmo9Z-ifw".into());
+        checkpointer.set(&cursor).await.unwrap();
+
+        let checkpointer = Checkpointer::new(data_dir.path());
+        assert_eq!(checkpointer.load().await.unwrap(), Some(cursor));
+    }
+}
@@ -0,0 +1,696 @@
+use std::{path::PathBuf, time::Duration};
+
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::StreamExt;
+use http::{header, response::Parts, HeaderMap, Request, StatusCode};
+use hyper::Body;
+use lookup::{lookup_v2::parse_value_path, owned_value_path, path};
+use snafu::{ResultExt, Snafu};
+use tokio_stream::wrappers::IntervalStream;
+use value::{kind::Collection, Kind};
+use vector_common::{
+    internal_event::{CountByteSize, InternalEventHandle as _, Registered},
+    sensitive_string::SensitiveString,
+};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext,
+    },
+    event::{BatchNotifier, BatchStatus, Event},
+    http::{Auth, HttpClient, HttpError},
+    internal_events::{
+        EndpointBytesReceived, EventsReceived, GithubAuditCheckpointError, GithubAuditRateLimited,
+        GithubAuditReadError, StreamClosedError,
+    },
+    serde::bool_or_struct,
+    shutdown::ShutdownSignal,
+    tls::{TlsConfig, TlsSettings},
+    SourceSender,
+};
+
+mod checkpoint;
+
+use self::checkpoint::{Checkpointer, Cursor};
+
+const API_VERSION: &str = "2022-11-28";
+const PAGE_SIZE: &str = "100";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid endpoint {:?}: {}", endpoint, source))]
+    InvalidEndpoint {
+        endpoint: String,
+        source: url::ParseError,
+    },
+}
+
+#[derive(Debug, Snafu)]
+enum ReadError {
+    #[snafu(display("HTTP request failed: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("failed to read response body: {}", source))]
+    Body { source: hyper::Error },
+    #[snafu(display("unexpected status {} from {}", status, url))]
+    Status { status: StatusCode, url: String },
+    #[snafu(display("invalid audit log entries: {}", source))]
+    InvalidEntries { source: serde_json::Error },
+    #[snafu(display("source output closed"))]
+    OutputClosed,
+    #[snafu(display("source shut down"))]
+    Shutdown,
+}
+
+/// Configuration for the `github_audit` source.
+#[configurable_component(source("github_audit"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GithubAuditConfig {
+    /// The base URL of the GitHub REST API.
+    ///
+    /// For GitHub Enterprise Server, this is `https://<hostname>/api/v3`.
+    #[configurable(metadata(docs::examples = "https://github.example.com/api/v3"))]
+    #[serde(default = "default_endpoint")]
+    endpoint: String,
+
+    /// The slug of the enterprise whose audit log is read.
+    #[configurable(metadata(docs::examples = "acme"))]
+    enterprise: String,
+
+    /// The token used to authenticate with the GitHub API.
+    ///
+    /// It must have the `read:audit_log` scope, and belong to an enterprise owner.
+    #[configurable(metadata(docs::examples = "${GITHUB_TOKEN}"))]
+    token: SensitiveString,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    include: AuditLogInclude,
+
+    /// A search phrase filtering the events read.
+    ///
+    /// The `created` qualifier is set by Vector, and must not be part of the phrase.
+    #[configurable(metadata(docs::examples = "action:repo.destroy"))]
+    #[serde(default)]
+    phrase: Option<String>,
+
+    /// The interval between polls of the audit log, in seconds.
+    #[serde(default = "default_poll_interval_secs")]
+    poll_interval_secs: u64,
+
+    /// How far back to read events from when no cursor is persisted, in seconds.
+    ///
+    /// By default, only the events created after Vector starts are read.
+    #[serde(default)]
+    backfill_secs: u64,
+
+    /// The directory used to persist the position in the audit log.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    #[serde(default)]
+    data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+/// The kinds of events read from the audit log.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditLogInclude {
+    /// Web (non-Git) events.
+    #[default]
+    Web,
+
+    /// Git events.
+    Git,
+
+    /// Both web and Git events.
+    All,
+}
+
+impl AuditLogInclude {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Web => "web",
+            Self::Git => "git",
+            Self::All => "all",
+        }
+    }
+}
+
+fn default_endpoint() -> String {
+    "https://api.github.com".to_string()
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    60
+}
+
+impl GenerateConfig for GithubAuditConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"enterprise = "acme"
+            token = "${GITHUB_TOKEN}""#,
+        )
+        .unwrap()
+    }
+}
+
+impl GithubAuditConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        Definition::new_with_default_metadata(Kind::object(Collection::any()), [log_namespace])
+            .with_event_field(&owned_value_path!("action"), Kind::bytes(), None)
+            .with_event_field(
+                &owned_value_path!("actor"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(&owned_value_path!("_document_id"), Kind::bytes(), None)
+            .with_event_field(&owned_value_path!("@timestamp"), Kind::integer(), None)
+            .with_source_metadata(
+                Self::NAME,
+                parse_value_path(log_schema().timestamp_key())
+                    .ok()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some("timestamp"),
+            )
+            .with_standard_vector_source_metadata()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for GithubAuditConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let audit_log_url = audit_log_url(&self.endpoint, &self.enterprise)?;
+        let data_dir = cx
+            .globals
+            // source are only global, name can be used for subdir
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, &cx.proxy)?;
+
+        let reader = AuditLogReader {
+            client,
+            audit_log_url,
+            auth: Auth::Bearer {
+                token: self.token.clone(),
+            },
+            include: self.include,
+            phrase: self.phrase.clone(),
+            backfill: Duration::from_secs(self.backfill_secs),
+            checkpointer: Checkpointer::new(&data_dir),
+            acknowledgements: cx.do_acknowledgements(self.acknowledgements),
+            log_namespace: cx.log_namespace(self.log_namespace),
+            events_received: register!(EventsReceived),
+            out: cx.out,
+            shutdown: cx.shutdown,
+        };
+        Ok(Box::pin(
+            reader.run(Duration::from_secs(self.poll_interval_secs)),
+        ))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// Builds the URL of the audit log of `enterprise`.
+fn audit_log_url(endpoint: &str, enterprise: &str) -> Result<url::Url, BuildError> {
+    let url = format!(
+        "{}/enterprises/{}/audit-log",
+        endpoint.trim_end_matches('/'),
+        enterprise
+    );
+    url::Url::parse(&url).context(InvalidEndpointSnafu { endpoint })
+}
+
+/// Builds the URL searching the events created since `cursor`, oldest first.
+fn search_url(
+    audit_log_url: &url::Url,
+    cursor: &Cursor,
+    include: AuditLogInclude,
+    phrase: Option<&str>,
+) -> url::Url {
+    let created = Utc
+        .timestamp_millis_opt(cursor.timestamp_ms)
+        .single()
+        .unwrap_or_else(Utc::now);
+    // The search only has a resolution of seconds, the events already read in the same second
+    // are skipped with the cursor.
+    let mut search = format!("created:>={}", created.format("%Y-%m-%dT%H:%M:%SZ"));
+    if let Some(phrase) = phrase {
+        search.push(' ');
+        search.push_str(phrase);
+    }
+
+    let mut url = audit_log_url.clone();
+    url.query_pairs_mut()
+        .append_pair("phrase", &search)
+        .append_pair("include", include.as_str())
+        .append_pair("order", "asc")
+        .append_pair("per_page", PAGE_SIZE);
+    url
+}
+
+/// Finds the URL of the next page in a `Link` header.
+fn next_link(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(header::LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|link| {
+            let mut parts = link.split(';').map(str::trim);
+            let url = parts.next()?.strip_prefix('<')?.strip_suffix('>')?;
+            parts
+                .any(|param| param == "rel=\"next\"")
+                .then(|| url.to_string())
+        })
+}
+
+/// How long to wait before sending the next request, if the rate limit is exhausted.
+fn rate_limit_wait(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let header_u64 = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok())
+    };
+
+    if let Some(secs) = header_u64("retry-after") {
+        return Some(Duration::from_secs(secs));
+    }
+    if header_u64("x-ratelimit-remaining") == Some(0) {
+        let reset = header_u64("x-ratelimit-reset")?;
+        let wait = reset.saturating_sub(now.timestamp().max(0) as u64);
+        // The limit is reset at the start of the second, so wait for one more.
+        return Some(Duration::from_secs(wait + 1));
+    }
+    None
+}
+
+struct AuditLogReader {
+    client: HttpClient,
+    audit_log_url: url::Url,
+    auth: Auth,
+    include: AuditLogInclude,
+    phrase: Option<String>,
+    backfill: Duration,
+    checkpointer: Checkpointer,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    events_received: Registered<EventsReceived>,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+}
+
+impl AuditLogReader {
+    async fn run(mut self, interval: Duration) -> Result<(), ()> {
+        let mut cursor = self
+            .checkpointer
+            .load()
+            .await
+            .map_err(|error| {
+                emit!(GithubAuditCheckpointError {
+                    path: self.checkpointer.path(),
+                    error,
+                })
+            })?
+            .unwrap_or_else(|| {
+                let backfill = chrono::Duration::from_std(self.backfill)
+                    .unwrap_or_else(|_| chrono::Duration::zero());
+                Cursor::new((Utc::now() - backfill).timestamp_millis())
+            });
+
+        let mut ticks =
+            IntervalStream::new(tokio::time::interval(interval)).take_until(self.shutdown.clone());
+        while ticks.next().await.is_some() {
+            match self.poll(&mut cursor).await {
+                Ok(()) => {}
+                Err(ReadError::OutputClosed | ReadError::Shutdown) => break,
+                Err(error) => emit!(GithubAuditReadError { error }),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the events created since the cursor, following the pages of the search results.
+    async fn poll(&mut self, cursor: &mut Cursor) -> Result<(), ReadError> {
+        let url = search_url(
+            &self.audit_log_url,
+            cursor,
+            self.include,
+            self.phrase.as_deref(),
+        );
+        let mut next = Some(url.to_string());
+        while let Some(url) = next.take() {
+            let (parts, body) = self.request(&url).await?;
+            next = next_link(&parts.headers);
+
+            let entries = serde_json::from_slice::<Vec<serde_json::Value>>(&body)
+                .context(InvalidEntriesSnafu)?;
+            if !self.read_entries(entries, cursor).await? {
+                // The events weren't delivered, so they're read again on the next poll.
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends a request to `url`, waiting for the rate limit to be reset when it's exhausted.
+    async fn request(&self, url: &str) -> Result<(Parts, Bytes), ReadError> {
+        loop {
+            let mut request = Request::get(url)
+                .header(header::ACCEPT, "application/vnd.github+json")
+                .header("X-GitHub-Api-Version", API_VERSION)
+                .body(Body::empty())
+                .expect("error creating request");
+            self.auth.apply(&mut request);
+
+            let response = self.client.send(request).await.context(RequestSnafu)?;
+            let (parts, body) = response.into_parts();
+            let body = hyper::body::to_bytes(body).await.context(BodySnafu)?;
+            emit!(EndpointBytesReceived {
+                byte_size: body.len(),
+                protocol: "http",
+                endpoint: self.audit_log_url.as_str(),
+            });
+
+            let wait = rate_limit_wait(&parts.headers, Utc::now());
+            if parts.status.is_success() {
+                if let Some(wait) = wait {
+                    self.wait(wait).await?;
+                }
+                return Ok((parts, body));
+            }
+            match wait {
+                Some(wait)
+                    if matches!(
+                        parts.status,
+                        StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+                    ) =>
+                {
+                    emit!(GithubAuditRateLimited { wait });
+                    self.wait(wait).await?;
+                }
+                _ => {
+                    return Err(ReadError::Status {
+                        status: parts.status,
+                        url: url.to_string(),
+                    })
+                }
+            }
+        }
+    }
+
+    async fn wait(&self, duration: Duration) -> Result<(), ReadError> {
+        tokio::select! {
+            _ = tokio::time::sleep(duration) => Ok(()),
+            _ = self.shutdown.clone() => Err(ReadError::Shutdown),
+        }
+    }
+
+    /// Sends the events of `entries` not read yet, and advances the cursor past them.
+    ///
+    /// Returns whether or not the events were delivered.
+    async fn read_entries(
+        &mut self,
+        entries: Vec<serde_json::Value>,
+        cursor: &mut Cursor,
+    ) -> Result<bool, ReadError> {
+        let mut advanced = cursor.clone();
+        let now = Utc::now();
+        let mut events = Vec::new();
+        for entry in entries {
+            let timestamp_ms = entry.get("@timestamp").and_then(|value| value.as_i64());
+            let document_id = entry
+                .get("_document_id")
+                .and_then(|value| value.as_str())
+                .map(ToOwned::to_owned);
+            if let (Some(timestamp_ms), Some(document_id)) = (timestamp_ms, document_id.as_ref()) {
+                if advanced.is_read(timestamp_ms, document_id) {
+                    continue;
+                }
+            }
+
+            let mut log = self
+                .log_namespace
+                .new_log_from_data(value::Value::from(entry));
+            if let Some(timestamp) = timestamp_ms
+                .and_then(|timestamp_ms| Utc.timestamp_millis_opt(timestamp_ms).single())
+            {
+                self.log_namespace.insert_source_metadata(
+                    GithubAuditConfig::NAME,
+                    &mut log,
+                    Some(LegacyKey::Overwrite(path!(log_schema().timestamp_key()))),
+                    path!("timestamp"),
+                    timestamp,
+                );
+            }
+            self.log_namespace.insert_standard_vector_source_metadata(
+                &mut log,
+                GithubAuditConfig::NAME,
+                now,
+            );
+            events.push(Event::from(log));
+
+            if let (Some(timestamp_ms), Some(document_id)) = (timestamp_ms, document_id) {
+                advanced.advance(timestamp_ms, document_id);
+            }
+        }
+
+        if !events.is_empty() {
+            let count = events.len();
+            self.events_received.emit(CountByteSize(
+                count,
+                events.estimated_json_encoded_size_of(),
+            ));
+
+            let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+            if let Err(error) = self.out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return Err(ReadError::OutputClosed);
+            }
+            if let Some(receiver) = receiver {
+                if receiver.await != BatchStatus::Delivered {
+                    return Ok(false);
+                }
+            }
+        }
+
+        if advanced != *cursor {
+            *cursor = advanced;
+            if let Err(error) = self.checkpointer.set(cursor).await {
+                emit!(GithubAuditCheckpointError {
+                    path: self.checkpointer.path(),
+                    error,
+                });
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use http::HeaderValue;
+    use warp::Filter;
+
+    use super::*;
+    use crate::test_util::{
+        components::{run_and_assert_source_compliance, HTTP_PULL_SOURCE_TAGS},
+        next_addr, wait_for_tcp,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GithubAuditConfig>();
+    }
+
+    #[test]
+    fn finds_next_link() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::LINK,
+            HeaderValue::from_static(
+                "<https://api.github.com/enterprises/acme/audit-log?after=MS42&before=>; rel=\"next\", \
+                 <https://api.github.com/enterprises/acme/audit-log?after=&before=MS40>; rel=\"prev\"",
+            ),
+        );
+        assert_eq!(
+            next_link(&headers).as_deref(),
+            Some("https://api.github.com/enterprises/acme/audit-log?after=MS42&before=")
+        );
+
+        headers.insert(
+            header::LINK,
+            HeaderValue::from_static(
+                "<https://api.github.com/enterprises/acme/audit-log?after=&before=MS40>; rel=\"prev\"",
+            ),
+        );
+        assert_eq!(next_link(&headers), None);
+    }
+
+    #[test]
+    fn computes_rate_limit_wait() {
+        let now = Utc.timestamp(1_673_353_800, 0);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("12"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1673353860"));
+        assert_eq!(rate_limit_wait(&headers, now), None);
+
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        assert_eq!(
+            rate_limit_wait(&headers, now),
+            Some(Duration::from_secs(61))
+        );
+
+        headers.insert("retry-after", HeaderValue::from_static("30"));
+        assert_eq!(
+            rate_limit_wait(&headers, now),
+            Some(Duration::from_secs(30))
+        );
+    }
+
+    #[test]
+    fn builds_search_url() {
+        let config: GithubAuditConfig = toml::from_str(
+            r#"endpoint = "https://github.example.com/api/v3/"
+            enterprise = "acme"
+            token = "token"
+            include = "all"
+            phrase = "action:repo.destroy""#,
+        )
+        .unwrap();
+        let url = audit_log_url(&config.endpoint, &config.enterprise).unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://github.example.com/api/v3/enterprises/acme/audit-log"
+        );
+
+        let url = search_url(
+            &url,
+            &Cursor::new(1_673_353_800_123),
+            config.include,
+            config.phrase.as_deref(),
+        );
+        assert_eq!(
+            url.as_str(),
+            "https://github.example.com/api/v3/enterprises/acme/audit-log\
+             ?phrase=created%3A%3E%3D2023-01-10T12%3A30%3A00Z+action%3Arepo.destroy\
+             &include=all&order=asc&per_page=100"
+        );
+    }
+
+    fn audit_log_server(addr: SocketAddr) {
+        let entries = warp::path!("enterprises" / "acme" / "audit-log")
+            .and(warp::header::exact("Authorization", "Bearer token"))
+            .and(warp::query::raw().or(warp::any().map(String::new)).unify())
+            .map(move |query: String| {
+                if query.contains("after=page2") {
+                    warp::http::Response::builder()
+                        .body(
+                            r#"[{"@timestamp":1673353800002,"_document_id":"c","action":"repo.destroy","actor":"octocat"}]"#
+                                .to_string(),
+                        )
+                        .unwrap()
+                } else {
+                    warp::http::Response::builder()
+                        .header(
+                            "Link",
+                            format!(
+                                "<http://{}/enterprises/acme/audit-log?after=page2>; rel=\"next\"",
+                                addr
+                            ),
+                        )
+                        .body(
+                            r#"[{"@timestamp":1673353800000,"_document_id":"a","action":"org.add_member","actor":"octocat"},
+                                {"@timestamp":1673353800001,"_document_id":"b","action":"team.create","actor":"octocat"}]"#
+                                .to_string(),
+                        )
+                        .unwrap()
+                }
+            });
+        tokio::spawn(warp::serve(entries).run(addr));
+    }
+
+    #[tokio::test]
+    async fn reads_audit_log_pages() {
+        let addr = next_addr();
+        audit_log_server(addr);
+        wait_for_tcp(addr).await;
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let config: GithubAuditConfig = toml::from_str(&format!(
+            r#"endpoint = "http://{}"
+            enterprise = "acme"
+            token = "token"
+            backfill_secs = {}
+            poll_interval_secs = 1
+            data_dir = "{}""#,
+            addr,
+            Utc::now().timestamp() - 1_673_353_800 + 60,
+            data_dir.path().display(),
+        ))
+        .unwrap();
+
+        let events = run_and_assert_source_compliance(
+            config,
+            Duration::from_secs(3),
+            &HTTP_PULL_SOURCE_TAGS,
+        )
+        .await;
+
+        // The same events are returned on every poll, but only read once.
+        let actions = events
+            .iter()
+            .map(|event| event.as_log()["action"].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(actions, ["org.add_member", "team.create", "repo.destroy"]);
+        assert_eq!(
+            events[0].as_log()[log_schema().timestamp_key()],
+            value::Value::from(Utc.timestamp_millis(1_673_353_800_000))
+        );
+
+        let cursor = Checkpointer::new(data_dir.path())
+            .load()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cursor.timestamp_ms, 1_673_353_800_002);
+    }
+}
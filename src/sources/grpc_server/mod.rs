@@ -0,0 +1,396 @@
+use std::{convert::Infallible, net::SocketAddr, path::PathBuf};
+
+use futures::FutureExt;
+use hyper::{server::accept, service::make_service_fn, Server};
+use snafu::Snafu;
+use tower::Layer;
+use tracing::Instrument;
+use value::{kind::Collection, Kind};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, schema::Definition};
+
+use self::service::MethodService;
+use crate::{
+    config::{
+        DataType, GenerateConfig, Output, Resource, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext,
+    },
+    grpc::{find_method, load_descriptor_pool},
+    serde::bool_or_struct,
+    sources::{util::grpc::DecompressionAndMetricsLayer, Source},
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+mod service;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Method {:?} streams its responses, which is not supported", method))]
+    ServerStreaming { method: String },
+}
+
+/// Configuration for the `grpc_server` source.
+#[configurable_component(source("grpc_server"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcServerConfig {
+    /// The address to listen for connections on.
+    ///
+    /// It _must_ include a port.
+    address: SocketAddr,
+
+    /// The path to the file descriptor set defining the served method and the messages it uses.
+    ///
+    /// The descriptor set can be generated with `protoc --include_imports --descriptor_set_out=<path>`.
+    #[configurable(metadata(docs::examples = "/etc/vector/telemetry.desc"))]
+    descriptor_set_file: PathBuf,
+
+    /// The fully qualified name of the served method, in the form `package.Service/Method`.
+    ///
+    /// Unary and client streaming methods are supported. Each request message is turned into a log event,
+    /// and the response is the default value of the method's output message.
+    #[configurable(metadata(docs::examples = "telemetry.v1.TelemetryService/Push"))]
+    method: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[serde(default)]
+    #[configurable(metadata(docs::hidden))]
+    log_namespace: Option<bool>,
+}
+
+impl GenerateConfig for GrpcServerConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "0.0.0.0:50051"
+            descriptor_set_file = "/etc/vector/telemetry.desc"
+            method = "telemetry.v1.TelemetryService/Push""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for GrpcServerConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
+        let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        let pool = load_descriptor_pool(&self.descriptor_set_file)?;
+        let method = find_method(&pool, &self.method)?;
+        if method.is_server_streaming() {
+            return Err(BuildError::ServerStreaming {
+                method: self.method.clone(),
+            }
+            .into());
+        }
+
+        let service = DecompressionAndMetricsLayer::default().layer(MethodService::new(
+            &method,
+            cx.out,
+            acknowledgements,
+            log_namespace,
+        ));
+
+        let address = self.address;
+        let shutdown = cx.shutdown;
+        Ok(Box::pin(async move {
+            let listener = tls_settings.bind(&address).await.map_err(|error| {
+                error!(message = "Failed to bind to listener socket.", %error);
+            })?;
+
+            info!(%address, "Building gRPC server.");
+
+            Server::builder(accept::from_stream(listener.accept_stream()))
+                .http2_only(true)
+                .serve(make_service_fn(move |_| {
+                    let service = service.clone();
+                    async move { Ok::<_, Infallible>(service) }
+                }))
+                .with_graceful_shutdown(shutdown.map(|_| ()))
+                .in_current_span()
+                .await
+                .map_err(|error| {
+                    error!(message = "Source future failed.", %error);
+                })
+        }))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+
+        let schema_definition =
+            Definition::new_with_default_metadata(Kind::object(Collection::any()), [log_namespace])
+                .with_standard_vector_source_metadata();
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, io::Write};
+
+    use futures::Stream;
+    use http::uri::PathAndQuery;
+    use prost::Message;
+    use prost_reflect::{DescriptorPool, DynamicMessage, MapKey, Value as ReflectValue};
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+        MessageOptions, MethodDescriptorProto, ServiceDescriptorProto,
+    };
+    use tonic::{client::Grpc, transport::Channel, Code};
+    use vector_core::config::log_schema;
+
+    use super::*;
+    use crate::{
+        event::Event,
+        grpc::DynamicCodec,
+        test_util::{
+            self,
+            components::{assert_source_compliance, SOURCE_TAGS},
+        },
+        SourceSender,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GrpcServerConfig>();
+    }
+
+    fn field(name: &str, number: i32, label: Label, r#type: Type) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.into()),
+            number: Some(number),
+            label: Some(label.into()),
+            r#type: Some(r#type.into()),
+            json_name: Some(name.into()),
+            ..Default::default()
+        }
+    }
+
+    fn descriptor_set() -> Vec<u8> {
+        let mut attributes = field("attributes", 3, Label::Repeated, Type::Message);
+        attributes.type_name = Some(".telemetry.v1.Telemetry.AttributesEntry".into());
+
+        let method = |name: &str, client_streaming: bool| MethodDescriptorProto {
+            name: Some(name.into()),
+            input_type: Some(".telemetry.v1.Telemetry".into()),
+            output_type: Some(".telemetry.v1.PushResponse".into()),
+            client_streaming: Some(client_streaming),
+            ..Default::default()
+        };
+
+        FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("telemetry.proto".into()),
+                package: Some("telemetry.v1".into()),
+                syntax: Some("proto3".into()),
+                message_type: vec![
+                    DescriptorProto {
+                        name: Some("Telemetry".into()),
+                        field: vec![
+                            field("message", 1, Label::Optional, Type::String),
+                            field("count", 2, Label::Optional, Type::Int64),
+                            attributes,
+                        ],
+                        nested_type: vec![DescriptorProto {
+                            name: Some("AttributesEntry".into()),
+                            field: vec![
+                                field("key", 1, Label::Optional, Type::String),
+                                field("value", 2, Label::Optional, Type::String),
+                            ],
+                            options: Some(MessageOptions {
+                                map_entry: Some(true),
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    },
+                    DescriptorProto {
+                        name: Some("PushResponse".into()),
+                        ..Default::default()
+                    },
+                ],
+                service: vec![ServiceDescriptorProto {
+                    name: Some("TelemetryService".into()),
+                    method: vec![method("Push", false), method("PushStream", true)],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+        .encode_to_vec()
+    }
+
+    fn config(address: SocketAddr, method: &str) -> (GrpcServerConfig, tempfile::NamedTempFile) {
+        let mut descriptor_set_file = tempfile::NamedTempFile::new().unwrap();
+        descriptor_set_file.write_all(&descriptor_set()).unwrap();
+
+        let config = toml::from_str(&format!(
+            r#"address = "{}"
+            descriptor_set_file = "{}"
+            method = "{}""#,
+            address,
+            descriptor_set_file.path().display(),
+            method
+        ))
+        .unwrap();
+
+        (config, descriptor_set_file)
+    }
+
+    async fn run_source(method: &str) -> (Channel, impl Stream<Item = Event> + Unpin) {
+        let address = test_util::next_addr();
+        let (config, _descriptor_set_file) = config(address, method);
+
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        test_util::wait_for_tcp(address).await;
+
+        let channel = Channel::from_shared(format!("http://{}", address))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+
+        (channel, rx)
+    }
+
+    fn telemetry(message: &str) -> DynamicMessage {
+        let pool = DescriptorPool::decode(descriptor_set().as_slice()).unwrap();
+        let mut telemetry =
+            DynamicMessage::new(pool.get_message_by_name("telemetry.v1.Telemetry").unwrap());
+        telemetry.set_field_by_name("message", ReflectValue::String(message.into()));
+        telemetry.set_field_by_name("count", ReflectValue::I64(3));
+        telemetry.set_field_by_name(
+            "attributes",
+            ReflectValue::Map(HashMap::from([(
+                MapKey::String("env".into()),
+                ReflectValue::String("prod".into()),
+            )])),
+        );
+        telemetry
+    }
+
+    fn response_codec() -> DynamicCodec {
+        let pool = DescriptorPool::decode(descriptor_set().as_slice()).unwrap();
+        DynamicCodec::new(
+            pool.get_message_by_name("telemetry.v1.PushResponse")
+                .unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn receives_unary_requests() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let (channel, rx) = run_source("telemetry.v1.TelemetryService/Push").await;
+
+            let mut client = Grpc::new(channel);
+            client.ready().await.unwrap();
+            client
+                .unary(
+                    tonic::Request::new(telemetry("hello")),
+                    PathAndQuery::from_static("/telemetry.v1.TelemetryService/Push"),
+                    response_codec(),
+                )
+                .await
+                .unwrap();
+
+            let events = test_util::collect_ready(rx).await;
+            assert_eq!(events.len(), 1);
+            let log = events[0].as_log();
+            assert_eq!(log["message"], "hello".into());
+            assert_eq!(log["count"], 3.into());
+            assert_eq!(log["attributes.env"], "prod".into());
+            assert_eq!(log[log_schema().source_type_key()], "grpc_server".into());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn receives_client_streaming_requests() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let (channel, rx) = run_source("telemetry.v1.TelemetryService/PushStream").await;
+
+            let mut client = Grpc::new(channel);
+            client.ready().await.unwrap();
+            client
+                .client_streaming(
+                    tonic::Request::new(futures::stream::iter(vec![
+                        telemetry("first"),
+                        telemetry("second"),
+                    ])),
+                    PathAndQuery::from_static("/telemetry.v1.TelemetryService/PushStream"),
+                    response_codec(),
+                )
+                .await
+                .unwrap();
+
+            let events = test_util::collect_ready(rx).await;
+            let messages = events
+                .iter()
+                .map(|event| event.as_log()["message"].to_string_lossy())
+                .collect::<Vec<_>>();
+            assert_eq!(messages, vec!["first", "second"]);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn rejects_other_methods() {
+        let (channel, _rx) = run_source("telemetry.v1.TelemetryService/Push").await;
+
+        let mut client = Grpc::new(channel);
+        client.ready().await.unwrap();
+        let status = client
+            .unary(
+                tonic::Request::new(telemetry("hello")),
+                PathAndQuery::from_static("/telemetry.v1.TelemetryService/PushStream"),
+                response_codec(),
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), Code::Unimplemented);
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_methods() {
+        let (config, _descriptor_set_file) =
+            config(test_util::next_addr(), "telemetry.v1.TelemetryService/Pull");
+        let (tx, _rx) = SourceSender::new_test();
+
+        let error = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"Method "Pull" is not defined by service "telemetry.v1.TelemetryService""#
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use chrono::Utc;
+use futures::future::{self, BoxFuture};
+use http::{Request, Response};
+use hyper::Body;
+use ordered_float::NotNan;
+use prost_reflect::{DynamicMessage, Kind, MapKey, MessageDescriptor, MethodDescriptor};
+use tonic::{
+    body::BoxBody,
+    server::{ClientStreamingService, Grpc, UnaryService},
+    Status, Streaming,
+};
+use tower::Service;
+use value::Value;
+use vector_common::internal_event::{CountByteSize, InternalEventHandle as _};
+use vector_config::NamedComponent;
+use vector_core::{
+    config::LogNamespace,
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
+    EstimatedJsonEncodedSizeOf,
+};
+
+use super::GrpcServerConfig;
+use crate::{
+    grpc::{method_path, DynamicCodec},
+    internal_events::{EventsReceived, StreamClosedError},
+    SourceSender,
+};
+
+/// Serves the configured method, rejecting calls to any other one.
+#[derive(Clone)]
+pub(super) struct MethodService {
+    path: String,
+    input: MessageDescriptor,
+    output: MessageDescriptor,
+    client_streaming: bool,
+    handler: MessageHandler,
+}
+
+impl MethodService {
+    pub(super) fn new(
+        method: &MethodDescriptor,
+        pipeline: SourceSender,
+        acknowledgements: bool,
+        log_namespace: LogNamespace,
+    ) -> Self {
+        Self {
+            path: method_path(method),
+            input: method.input(),
+            output: method.output(),
+            client_streaming: method.is_client_streaming(),
+            handler: MessageHandler {
+                output: method.output(),
+                pipeline,
+                acknowledgements,
+                log_namespace,
+            },
+        }
+    }
+}
+
+impl Service<Request<Body>> for MethodService {
+    type Response = Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        if req.uri().path() != self.path {
+            let status = Status::unimplemented(format!("Unknown method {:?}", req.uri().path()));
+            return Box::pin(future::ok(status.to_http()));
+        }
+
+        let mut grpc = Grpc::new(DynamicCodec::new(self.input.clone()));
+        let handler = self.handler.clone();
+        let client_streaming = self.client_streaming;
+        Box::pin(async move {
+            let response = if client_streaming {
+                grpc.client_streaming(handler, req).await
+            } else {
+                grpc.unary(handler, req).await
+            };
+            Ok(response)
+        })
+    }
+}
+
+/// Turns the request messages into events and sends them on.
+#[derive(Clone)]
+struct MessageHandler {
+    output: MessageDescriptor,
+    pipeline: SourceSender,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+}
+
+impl MessageHandler {
+    async fn handle_message(&self, message: DynamicMessage) -> Result<(), Status> {
+        let mut log = self
+            .log_namespace
+            .new_log_from_data(message_to_value(&message));
+        self.log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            GrpcServerConfig::NAME,
+            Utc::now(),
+        );
+        let mut events = vec![Event::from(log)];
+
+        let count = events.len();
+        let byte_size = events.estimated_json_encoded_size_of();
+        register!(EventsReceived).emit(CountByteSize(count, byte_size));
+
+        let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+
+        self.pipeline
+            .clone()
+            .send_batch(events)
+            .await
+            .map_err(|error| {
+                let message = error.to_string();
+                emit!(StreamClosedError { error, count });
+                Status::unavailable(message)
+            })?;
+
+        handle_batch_status(receiver).await
+    }
+
+    fn response(&self) -> tonic::Response<DynamicMessage> {
+        tonic::Response::new(DynamicMessage::new(self.output.clone()))
+    }
+}
+
+impl UnaryService<DynamicMessage> for MessageHandler {
+    type Response = DynamicMessage;
+    type Future = BoxFuture<'static, Result<tonic::Response<Self::Response>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<DynamicMessage>) -> Self::Future {
+        let handler = self.clone();
+        Box::pin(async move {
+            handler.handle_message(request.into_inner()).await?;
+            Ok(handler.response())
+        })
+    }
+}
+
+impl ClientStreamingService<DynamicMessage> for MessageHandler {
+    type Response = DynamicMessage;
+    type Future = BoxFuture<'static, Result<tonic::Response<Self::Response>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<Streaming<DynamicMessage>>) -> Self::Future {
+        let handler = self.clone();
+        Box::pin(async move {
+            let mut messages = request.into_inner();
+            while let Some(message) = messages.message().await? {
+                handler.handle_message(message).await?;
+            }
+            Ok(handler.response())
+        })
+    }
+}
+
+async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Status> {
+    let status = match receiver {
+        Some(receiver) => receiver.await,
+        None => BatchStatus::Delivered,
+    };
+
+    match status {
+        BatchStatus::Errored => Err(Status::internal("Delivery error")),
+        BatchStatus::Rejected => Err(Status::data_loss("Delivery failed")),
+        BatchStatus::Delivered => Ok(()),
+    }
+}
+
+/// Converts `message` to an object with the fields set in it.
+///
+/// Enumerations are converted to the name of their value, if it is known, and maps to objects.
+fn message_to_value(message: &DynamicMessage) -> Value {
+    Value::Object(
+        message
+            .fields()
+            .map(|(field, value)| (field.name().to_string(), to_value(&field.kind(), value)))
+            .collect(),
+    )
+}
+
+fn to_value(kind: &Kind, value: &prost_reflect::Value) -> Value {
+    use prost_reflect::Value as ReflectValue;
+
+    match value {
+        ReflectValue::Bool(value) => Value::from(*value),
+        ReflectValue::I32(value) => Value::from(i64::from(*value)),
+        ReflectValue::I64(value) => Value::from(*value),
+        ReflectValue::U32(value) => Value::from(i64::from(*value)),
+        ReflectValue::U64(value) => i64::try_from(*value)
+            .map(Value::from)
+            .unwrap_or_else(|_| float_to_value(*value as f64)),
+        ReflectValue::F32(value) => float_to_value(f64::from(*value)),
+        ReflectValue::F64(value) => float_to_value(*value),
+        ReflectValue::String(value) => Value::from(value.as_str()),
+        ReflectValue::Bytes(value) => Value::Bytes(Bytes::clone(value)),
+        ReflectValue::EnumNumber(number) => kind
+            .as_enum()
+            .and_then(|enum_descriptor| enum_descriptor.get_value(*number))
+            .map(|enum_value| Value::from(enum_value.name()))
+            .unwrap_or_else(|| Value::from(i64::from(*number))),
+        ReflectValue::Message(message) => message_to_value(message),
+        ReflectValue::List(values) => {
+            Value::Array(values.iter().map(|value| to_value(kind, value)).collect())
+        }
+        ReflectValue::Map(entries) => {
+            let value_kind = kind
+                .as_message()
+                .map(|entry| entry.map_entry_value_field().kind())
+                .unwrap_or_else(|| kind.clone());
+            Value::Object(
+                entries
+                    .iter()
+                    .map(|(key, value)| (map_key_to_string(key), to_value(&value_kind, value)))
+                    .collect::<BTreeMap<_, _>>(),
+            )
+        }
+    }
+}
+
+fn float_to_value(value: f64) -> Value {
+    NotNan::new(value).map(Value::Float).unwrap_or(Value::Null)
+}
+
+fn map_key_to_string(key: &MapKey) -> String {
+    match key {
+        MapKey::Bool(key) => key.to_string(),
+        MapKey::I32(key) => key.to_string(),
+        MapKey::I64(key) => key.to_string(),
+        MapKey::U32(key) => key.to_string(),
+        MapKey::U64(key) => key.to_string(),
+        MapKey::String(key) => key.clone(),
+    }
+}
@@ -11,6 +11,7 @@ use codecs::{
     decoding::{DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
+use enrichment::{Case, Condition, IndexHandle, TableRegistry, TableSearch};
 use lookup::{lookup_v2::parse_value_path, owned_value_path, path};
 use smallvec::SmallVec;
 use tokio_util::codec::Decoder as _;
@@ -58,6 +59,19 @@ pub struct LogplexConfig {
     #[configurable(derived)]
     auth: Option<HttpSourceAuthConfig>,
 
+    /// A mapping of drain tokens to tenants.
+    ///
+    /// When set, or when `drain_token_table` is set, only the requests whose `Logplex-Drain-Token` header is a known
+    /// drain token are accepted. The tenant of the drain token is added to their events as `tenant`, so that a single
+    /// endpoint can serve the drains of many applications.
+    #[configurable(metadata(
+        docs::additional_props_description = "The tenant of the drain token."
+    ))]
+    drain_tokens: Option<HashMap<String, String>>,
+
+    #[configurable(derived)]
+    drain_token_table: Option<DrainTokenTableConfig>,
+
     #[configurable(derived)]
     #[serde(default = "default_framing_message_based")]
     framing: FramingConfig,
@@ -76,7 +90,40 @@ pub struct LogplexConfig {
     log_namespace: Option<bool>,
 }
 
+/// Configuration for looking up the tenants of drain tokens in an enrichment table.
+///
+/// The drain tokens found in `drain_tokens` aren't looked up.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DrainTokenTableConfig {
+    /// The name of the enrichment table.
+    #[configurable(metadata(docs::examples = "drains"))]
+    table: String,
+
+    /// The field of the table holding the drain tokens.
+    #[serde(default = "default_token_field")]
+    token_field: String,
+
+    /// The field of the table holding the tenants.
+    #[serde(default = "default_tenant_field")]
+    tenant_field: String,
+}
+
+fn default_token_field() -> String {
+    "token".to_string()
+}
+
+fn default_tenant_field() -> String {
+    "tenant".to_string()
+}
+
 impl LogplexConfig {
+    /// Whether or not drain tokens are validated, and the tenants of their drains added to events.
+    fn validates_drain_tokens(&self) -> bool {
+        self.drain_tokens.is_some() || self.drain_token_table.is_some()
+    }
+
     /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
     fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
         let mut schema_definition = self
@@ -120,6 +167,16 @@ impl LogplexConfig {
                 None,
             );
 
+        if self.validates_drain_tokens() {
+            schema_definition = schema_definition.with_source_metadata(
+                LogplexConfig::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("tenant"))),
+                &owned_value_path!("tenant"),
+                Kind::bytes(),
+                None,
+            );
+        }
+
         // for metadata that is added to the events dynamically from config options
         if log_namespace == LogNamespace::Legacy {
             schema_definition = schema_definition.unknown_fields(Kind::bytes());
@@ -136,6 +193,8 @@ impl Default for LogplexConfig {
             query_parameters: Vec::new(),
             tls: None,
             auth: None,
+            drain_tokens: None,
+            drain_token_table: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             acknowledgements: SourceAcknowledgementsConfig::default(),
@@ -162,10 +221,25 @@ impl SourceConfig for LogplexConfig {
 
         let log_namespace = cx.log_namespace(self.log_namespace);
 
+        let drain_tenants = if self.validates_drain_tokens() {
+            let table = self
+                .drain_token_table
+                .as_ref()
+                .map(|config| DrainTokenTable::new(config, &cx.enrichment_tables))
+                .transpose()?;
+            Some(DrainTenants {
+                tokens: self.drain_tokens.clone().unwrap_or_default(),
+                table,
+            })
+        } else {
+            None
+        };
+
         let source = LogplexSource {
             query_parameters: self.query_parameters.clone(),
             decoder,
             log_namespace,
+            drain_tenants,
         };
 
         source.run(
@@ -196,11 +270,83 @@ impl SourceConfig for LogplexConfig {
     }
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone)]
 struct LogplexSource {
     query_parameters: Vec<String>,
     decoder: Decoder,
     log_namespace: LogNamespace,
+    drain_tenants: Option<DrainTenants>,
+}
+
+/// Resolves the tenants of drain tokens.
+#[derive(Clone)]
+struct DrainTenants {
+    tokens: HashMap<String, String>,
+    table: Option<DrainTokenTable>,
+}
+
+impl DrainTenants {
+    /// Returns the tenant of `drain_token`, if it's a known drain token.
+    fn tenant(&self, drain_token: &str) -> Option<String> {
+        self.tokens.get(drain_token).cloned().or_else(|| {
+            self.table
+                .as_ref()
+                .and_then(|table| table.tenant(drain_token))
+        })
+    }
+}
+
+/// An enrichment table holding the tenants of drain tokens.
+#[derive(Clone)]
+struct DrainTokenTable {
+    tables: TableSearch,
+    index: IndexHandle,
+    table: String,
+    token_field: String,
+    tenant_field: String,
+}
+
+impl DrainTokenTable {
+    fn new(
+        config: &DrainTokenTableConfig,
+        enrichment_tables: &TableRegistry,
+    ) -> crate::Result<Self> {
+        if !enrichment_tables.table_ids().contains(&config.table) {
+            return Err(format!("Enrichment table {:?} does not exist.", config.table).into());
+        }
+        let index = enrichment_tables.clone().add_index(
+            &config.table,
+            Case::Sensitive,
+            &[config.token_field.as_str()],
+        )?;
+
+        Ok(Self {
+            tables: enrichment_tables.as_readonly(),
+            index,
+            table: config.table.clone(),
+            token_field: config.token_field.clone(),
+            tenant_field: config.tenant_field.clone(),
+        })
+    }
+
+    fn tenant(&self, drain_token: &str) -> Option<String> {
+        let condition = [Condition::Equals {
+            field: &self.token_field,
+            value: drain_token.into(),
+        }];
+        let row = self
+            .tables
+            .find_table_row(
+                &self.table,
+                Case::Sensitive,
+                &condition,
+                Some(std::slice::from_ref(&self.tenant_field)),
+                Some(self.index),
+            )
+            .ok()?;
+        row.get(&self.tenant_field)
+            .map(|tenant| tenant.to_string_lossy().into_owned())
+    }
 }
 
 impl LogplexSource {
@@ -217,6 +363,13 @@ impl LogplexSource {
         let frame_id = get_header(&header_map, "Logplex-Frame-Id")?;
         let drain_token = get_header(&header_map, "Logplex-Drain-Token")?;
 
+        let tenant = match &self.drain_tenants {
+            Some(drain_tenants) => Some(drain_tenants.tenant(drain_token).ok_or_else(|| {
+                ErrorMessage::new(StatusCode::UNAUTHORIZED, "Unknown drain token".to_string())
+            })?),
+            None => None,
+        };
+
         emit!(HerokuLogplexRequestReceived {
             msg_count,
             frame_id,
@@ -224,7 +377,7 @@ impl LogplexSource {
         });
 
         // Deal with body
-        let events = self.body_to_events(body);
+        let mut events = self.body_to_events(body);
 
         if events.len() != msg_count {
             let error_msg = format!(
@@ -239,6 +392,20 @@ impl LogplexSource {
             return Err(header_error_message("Logplex-Msg-Count", &error_msg));
         }
 
+        if let Some(tenant) = tenant {
+            for event in &mut events {
+                if let Event::Log(log) = event {
+                    self.log_namespace.insert_source_metadata(
+                        LogplexConfig::NAME,
+                        log,
+                        Some(LegacyKey::Overwrite(path!("tenant"))),
+                        path!("tenant"),
+                        tenant.clone(),
+                    );
+                }
+            }
+        }
+
         Ok(events)
     }
 
@@ -390,9 +557,13 @@ fn line_to_events(
 
 #[cfg(test)]
 mod tests {
-    use std::net::SocketAddr;
+    use std::{
+        collections::{BTreeMap, HashMap},
+        net::SocketAddr,
+    };
 
     use chrono::{DateTime, Utc};
+    use enrichment::{Case, Condition, IndexHandle, Table, TableRegistry};
     use futures::Stream;
     use lookup::{owned_value_path, LookupBuf};
     use similar_asserts::assert_eq;
@@ -404,7 +575,10 @@ mod tests {
         schema::Definition,
     };
 
-    use super::{HttpSourceAuthConfig, LogplexConfig};
+    use super::{
+        default_tenant_field, default_token_field, DrainTokenTableConfig, HttpSourceAuthConfig,
+        LogplexConfig,
+    };
     use crate::{
         config::{log_schema, SourceConfig, SourceContext},
         serde::{default_decoding, default_framing_message_based},
@@ -426,26 +600,32 @@ mod tests {
         status: EventStatus,
         acknowledgements: bool,
     ) -> (impl Stream<Item = Event> + Unpin, SocketAddr) {
-        let (sender, recv) = SourceSender::new_test_finalize(status);
-        let address = next_addr();
-        let context = SourceContext::new_test(sender, None);
-        tokio::spawn(async move {
+        source_with_config(
             LogplexConfig {
-                address,
                 query_parameters,
-                tls: None,
                 auth,
                 framing: default_framing_message_based(),
                 decoding: default_decoding(),
                 acknowledgements: acknowledgements.into(),
-                log_namespace: None,
-            }
-            .build(context)
-            .await
-            .unwrap()
-            .await
-            .unwrap()
-        });
+                ..Default::default()
+            },
+            status,
+            TableRegistry::default(),
+        )
+        .await
+    }
+
+    async fn source_with_config(
+        mut config: LogplexConfig,
+        status: EventStatus,
+        enrichment_tables: TableRegistry,
+    ) -> (impl Stream<Item = Event> + Unpin, SocketAddr) {
+        let (sender, recv) = SourceSender::new_test_finalize(status);
+        let address = next_addr();
+        config.address = address;
+        let mut context = SourceContext::new_test(sender, None);
+        context.enrichment_tables = enrichment_tables;
+        tokio::spawn(async move { config.build(context).await.unwrap().await.unwrap() });
         wait_for_tcp(address).await;
         (recv, address)
     }
@@ -455,6 +635,16 @@ mod tests {
         body: &str,
         auth: Option<HttpSourceAuthConfig>,
         query: &str,
+    ) -> u16 {
+        send_with_drain_token(address, body, auth, query, "drain-bar").await
+    }
+
+    async fn send_with_drain_token(
+        address: SocketAddr,
+        body: &str,
+        auth: Option<HttpSourceAuthConfig>,
+        query: &str,
+        drain_token: &str,
     ) -> u16 {
         let len = body.lines().count();
         let mut req = reqwest::Client::new().post(&format!("http://{}/events?{}", address, query));
@@ -463,7 +653,7 @@ mod tests {
         }
         req.header("Logplex-Msg-Count", len)
             .header("Logplex-Frame-Id", "frame-foo")
-            .header("Logplex-Drain-Token", "drain-bar")
+            .header("Logplex-Drain-Token", drain_token)
             .body(body.to_owned())
             .send()
             .await
@@ -588,6 +778,125 @@ mod tests {
         );
     }
 
+    /// An enrichment table of drain tokens, and the tenants of their drains.
+    #[derive(Clone)]
+    struct DrainTable(HashMap<String, String>);
+
+    impl Table for DrainTable {
+        fn find_table_row<'a>(
+            &self,
+            _case: Case,
+            condition: &'a [Condition<'a>],
+            select: Option<&[String]>,
+            _index: Option<IndexHandle>,
+        ) -> Result<BTreeMap<String, Value>, String> {
+            assert_eq!(select, Some(&["tenant".to_string()][..]));
+            match condition {
+                [Condition::Equals {
+                    field: "token",
+                    value,
+                }] => self
+                    .0
+                    .get(value.to_string_lossy().as_ref())
+                    .map(|tenant| BTreeMap::from([("tenant".to_string(), tenant.as_str().into())]))
+                    .ok_or_else(|| "no rows found".to_string()),
+                _ => Err("unexpected condition".to_string()),
+            }
+        }
+
+        fn find_table_rows<'a>(
+            &self,
+            case: Case,
+            condition: &'a [Condition<'a>],
+            select: Option<&[String]>,
+            index: Option<IndexHandle>,
+        ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+            self.find_table_row(case, condition, select, index)
+                .map(|row| vec![row])
+        }
+
+        fn add_index(&mut self, _case: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+            assert_eq!(fields, ["token"]);
+            Ok(IndexHandle(0))
+        }
+
+        fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+            Vec::new()
+        }
+
+        fn needs_reload(&self) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn logplex_routes_drain_tokens_to_tenants() {
+        let enrichment_tables = TableRegistry::default();
+        let mut tables = HashMap::<String, Box<dyn Table + Send + Sync>>::new();
+        tables.insert(
+            "drains".to_string(),
+            Box::new(DrainTable(HashMap::from([(
+                "drain-table".to_string(),
+                "tenant-b".to_string(),
+            )]))),
+        );
+        enrichment_tables.load(tables);
+
+        let config = LogplexConfig {
+            drain_tokens: Some(HashMap::from([(
+                "drain-map".to_string(),
+                "tenant-a".to_string(),
+            )])),
+            drain_token_table: Some(DrainTokenTableConfig {
+                table: "drains".to_string(),
+                token_field: default_token_field(),
+                tenant_field: default_tenant_field(),
+            }),
+            ..Default::default()
+        };
+        let (rx, addr) =
+            source_with_config(config, EventStatus::Delivered, enrichment_tables.clone()).await;
+        enrichment_tables.finish_load();
+
+        let events = spawn_collect_n(
+            async move {
+                for (drain_token, status) in [
+                    ("drain-map", 200),
+                    ("drain-table", 200),
+                    ("drain-unknown", 401),
+                ] {
+                    assert_eq!(
+                        status,
+                        send_with_drain_token(addr, SAMPLE_BODY, None, "", drain_token).await
+                    );
+                }
+            },
+            rx,
+            2,
+        )
+        .await;
+
+        assert_eq!(events[0].as_log()["tenant"], "tenant-a".into());
+        assert_eq!(events[1].as_log()["tenant"], "tenant-b".into());
+    }
+
+    #[tokio::test]
+    async fn logplex_rejects_missing_drain_token_table() {
+        let config = LogplexConfig {
+            drain_token_table: Some(DrainTokenTableConfig {
+                table: "drains".to_string(),
+                token_field: default_token_field(),
+                tenant_field: default_tenant_field(),
+            }),
+            ..Default::default()
+        };
+        let (sender, _recv) = SourceSender::new_test();
+        assert!(config
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .is_err());
+    }
+
     #[test]
     fn logplex_handles_normal_lines() {
         let log_namespace = LogNamespace::Legacy;
@@ -13,7 +13,7 @@ use crate::sources::util::http_client;
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{SourceConfig, SourceContext},
-    http::Auth,
+    http::{Auth, MaybeAuth},
     serde::{default_decoding, default_framing_message_based},
     sources,
     sources::util::{
@@ -114,6 +114,8 @@ impl_generate_config_from_default!(HttpClientConfig);
 #[async_trait::async_trait]
 impl SourceConfig for HttpClientConfig {
     async fn build(&self, cx: SourceContext) -> Result<sources::Source> {
+        self.auth.ensure_static()?;
+
         // build the url
         let endpoints = vec![self.endpoint.clone()];
         let urls = endpoints
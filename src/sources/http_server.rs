@@ -1,4 +1,7 @@
-use std::{collections::HashMap, net::SocketAddr};
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+};
 
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
@@ -31,7 +34,10 @@ use crate::{
     event::{Event, Value},
     serde::{bool_or_struct, default_decoding},
     sources::util::{
-        http::{add_query_parameters, HttpMethod},
+        http::{
+            add_query_parameters, HttpMethod, HttpSignatureConfig, HttpSignatureVerifier,
+            SignatureFailureAction,
+        },
         Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
     },
     tls::TlsEnableableConfig,
@@ -96,6 +102,9 @@ pub struct SimpleHttpConfig {
     #[configurable(derived)]
     auth: Option<HttpSourceAuthConfig>,
 
+    #[configurable(derived)]
+    signature: Option<HttpSignatureConfig>,
+
     /// Whether or not to treat the configured `path` as an absolute path.
     ///
     /// If set to `true`, only requests using the exact URL path specified in `path` will be accepted. Otherwise,
@@ -118,6 +127,14 @@ pub struct SimpleHttpConfig {
     #[serde(default = "default_http_method")]
     method: HttpMethod,
 
+    /// Additional routes to serve on the same listener.
+    ///
+    /// Each route has its own decoding and header/query parameter capture rules, and sends its events to a named
+    /// output instead of the default one. Requests are handled by the route with the most specific matching `path`,
+    /// falling back to the top-level `path` and the default output.
+    #[serde(default)]
+    routes: Vec<HttpServerRouteConfig>,
+
     #[configurable(derived)]
     tls: Option<TlsEnableableConfig>,
 
@@ -137,12 +154,69 @@ pub struct SimpleHttpConfig {
     log_namespace: Option<bool>,
 }
 
+/// Configuration for an additional route served by the `http_server` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HttpServerRouteConfig {
+    /// The name of the route.
+    ///
+    /// Events received on this route are sent to the output of the same name, which other components can consume
+    /// as `<source_id>.<name>`.
+    name: String,
+
+    /// The URL path on which requests for this route are accepted.
+    path: String,
+
+    /// Whether or not to treat the configured `path` as an absolute path.
+    ///
+    /// If set to `true`, only requests using the exact URL path specified in `path` are handled by this route.
+    /// Otherwise, requests sent to a URL path that starts with the value of `path` are handled by this route.
+    #[serde(default = "crate::serde::default_true")]
+    strict_path: bool,
+
+    /// A list of HTTP headers to include in the log event.
+    ///
+    /// These will override any values included in the JSON payload with conflicting names.
+    #[serde(default)]
+    headers: Vec<String>,
+
+    /// A list of URL query parameters to include in the log event.
+    ///
+    /// These will override any values included in the body with conflicting names.
+    #[serde(default)]
+    query_parameters: Vec<String>,
+
+    #[configurable(derived)]
+    signature: Option<HttpSignatureConfig>,
+
+    #[configurable(derived)]
+    framing: Option<FramingConfig>,
+
+    #[configurable(derived)]
+    decoding: Option<DeserializerConfig>,
+}
+
+impl HttpServerRouteConfig {
+    fn get_decoding_config(&self, log_namespace: LogNamespace) -> DecodingConfig {
+        let decoding = self.decoding.clone().unwrap_or_else(default_decoding);
+        let framing = self
+            .framing
+            .clone()
+            .unwrap_or_else(|| decoding.default_stream_framing());
+        DecodingConfig::new(framing, decoding, log_namespace)
+    }
+}
+
 impl SimpleHttpConfig {
     /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
-    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
-        let mut schema_definition = self
-            .decoding
-            .as_ref()
+    fn schema_definition(
+        &self,
+        decoding: Option<&DeserializerConfig>,
+        signature: Option<&HttpSignatureConfig>,
+        log_namespace: LogNamespace,
+    ) -> Definition {
+        let mut schema_definition = decoding
             .unwrap_or(&default_decoding())
             .schema_definition(log_namespace)
             .with_source_metadata(
@@ -170,6 +244,20 @@ impl SimpleHttpConfig {
             )
             .with_standard_vector_source_metadata();
 
+        if signature.map_or(false, |signature| {
+            signature.on_failure == SignatureFailureAction::Tag
+        }) {
+            schema_definition = schema_definition.with_source_metadata(
+                SimpleHttpConfig::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!(
+                    "signature_verified"
+                ))),
+                &owned_value_path!("signature_verified"),
+                Kind::boolean(),
+                None,
+            );
+        }
+
         // for metadata that is added to the events dynamically from config options
         if log_namespace == LogNamespace::Legacy {
             schema_definition = schema_definition.unknown_fields(Kind::bytes());
@@ -213,6 +301,19 @@ impl SimpleHttpConfig {
 
         Ok(DecodingConfig::new(framing, decoding, LogNamespace::Legacy))
     }
+
+    fn validate_routes(&self) -> crate::Result<()> {
+        let mut names = HashSet::new();
+        for route in &self.routes {
+            if route.name.is_empty() {
+                return Err("Route names must not be empty.".into());
+            }
+            if !names.insert(route.name.as_str()) {
+                return Err(format!("Duplicate route name `{}`.", route.name).into());
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for SimpleHttpConfig {
@@ -224,9 +325,11 @@ impl Default for SimpleHttpConfig {
             query_parameters: Vec::new(),
             tls: None,
             auth: None,
+            signature: None,
             path: default_path(),
             path_key: default_path_key(),
             method: default_http_method(),
+            routes: Vec::new(),
             strict_path: true,
             framing: None,
             decoding: Some(default_decoding()),
@@ -315,21 +418,67 @@ fn remove_duplicates(mut list: Vec<String>, list_name: &str) -> Vec<String> {
 #[async_trait::async_trait]
 impl SourceConfig for SimpleHttpConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        self.validate_routes()?;
+
         let decoder = self.get_decoding_config()?.build();
         let log_namespace = cx.log_namespace(self.log_namespace);
 
-        let source = SimpleHttpSource {
+        let mut routes = self
+            .routes
+            .iter()
+            .map(|route| {
+                Ok(HttpRoute {
+                    output: Some(route.name.clone()),
+                    path: route.path.clone(),
+                    strict_path: route.strict_path,
+                    headers: remove_duplicates(route.headers.clone(), "headers"),
+                    query_parameters: remove_duplicates(
+                        route.query_parameters.clone(),
+                        "query_parameters",
+                    ),
+                    signature: route
+                        .signature
+                        .as_ref()
+                        .map(HttpSignatureVerifier::new)
+                        .transpose()?,
+                    decoder: route.get_decoding_config(log_namespace).build(),
+                })
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        // Check the most specific paths first, so that nested route paths take precedence.
+        routes.sort_by_key(|route| std::cmp::Reverse(route.path_segments().count()));
+        routes.push(HttpRoute {
+            output: None,
+            path: self.path.clone(),
+            strict_path: self.strict_path,
             headers: remove_duplicates(self.headers.clone(), "headers"),
             query_parameters: remove_duplicates(self.query_parameters.clone(), "query_parameters"),
-            path_key: self.path_key.clone(),
+            signature: self
+                .signature
+                .as_ref()
+                .map(HttpSignatureVerifier::new)
+                .transpose()?,
             decoder,
+        });
+
+        // With additional routes, every request is accepted by the server and matched against the
+        // routes when being handled.
+        let (path, strict_path) = if self.routes.is_empty() {
+            (self.path.as_str(), self.strict_path)
+        } else {
+            ("/", false)
+        };
+
+        let source = SimpleHttpSource {
+            routes,
+            path_key: self.path_key.clone(),
             log_namespace,
         };
         source.run(
             self.address,
-            self.path.as_str(),
+            path,
             self.method,
-            self.strict_path,
+            strict_path,
             &self.tls,
             &self.auth,
             cx,
@@ -342,15 +491,26 @@ impl SourceConfig for SimpleHttpConfig {
         // The source config overrides the global setting and is merged here.
         let log_namespace = global_log_namespace.merge(self.log_namespace);
 
-        let schema_definition = self.schema_definition(log_namespace);
+        let output_type = |decoding: Option<&DeserializerConfig>| {
+            decoding.map(|d| d.output_type()).unwrap_or(DataType::Log)
+        };
 
-        vec![Output::default(
-            self.decoding
-                .as_ref()
-                .map(|d| d.output_type())
-                .unwrap_or(DataType::Log),
-        )
-        .with_schema_definition(schema_definition)]
+        let mut outputs = vec![Output::default(output_type(self.decoding.as_ref()))
+            .with_schema_definition(self.schema_definition(
+                self.decoding.as_ref(),
+                self.signature.as_ref(),
+                log_namespace,
+            ))];
+        outputs.extend(self.routes.iter().map(|route| {
+            Output::default(output_type(route.decoding.as_ref()))
+                .with_port(route.name.clone())
+                .with_schema_definition(self.schema_definition(
+                    route.decoding.as_ref(),
+                    route.signature.as_ref(),
+                    log_namespace,
+                ))
+        }));
+        outputs
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -363,18 +523,56 @@ impl SourceConfig for SimpleHttpConfig {
 }
 
 #[derive(Clone)]
-struct SimpleHttpSource {
+struct HttpRoute {
+    /// The named output events are sent to, or `None` for the default output.
+    output: Option<String>,
+    path: String,
+    strict_path: bool,
     headers: Vec<String>,
     query_parameters: Vec<String>,
-    path_key: OptionalValuePath,
+    signature: Option<HttpSignatureVerifier>,
     decoder: Decoder,
+}
+
+impl HttpRoute {
+    fn path_segments(&self) -> impl Iterator<Item = &str> {
+        self.path.split('/').filter(|segment| !segment.is_empty())
+    }
+
+    /// Whether a request sent to `request_path` is handled by this route.
+    ///
+    /// This mirrors the segment-wise matching done by the server's path filter.
+    fn matches(&self, request_path: &str) -> bool {
+        let mut request_segments = request_path
+            .split('/')
+            .filter(|segment| !segment.is_empty());
+        for segment in self.path_segments() {
+            if request_segments.next() != Some(segment) {
+                return false;
+            }
+        }
+        !self.strict_path || request_segments.next().is_none()
+    }
+}
+
+#[derive(Clone)]
+struct SimpleHttpSource {
+    /// The routes requests are matched against, in order. The last one is always the route for
+    /// the top-level `path`, which sends to the default output.
+    routes: Vec<HttpRoute>,
+    path_key: OptionalValuePath,
     log_namespace: LogNamespace,
 }
 
 impl SimpleHttpSource {
+    fn route(&self, request_path: &str) -> Option<&HttpRoute> {
+        self.routes.iter().find(|route| route.matches(request_path))
+    }
+
     /// Enriches the passed in events with metadata for the `request_path` and for each of the headers.
     fn enrich_events(
         &self,
+        route: &HttpRoute,
         events: &mut [Event],
         request_path: &str,
         headers_config: HeaderMap,
@@ -393,7 +591,7 @@ impl SimpleHttpSource {
             );
 
             // add each header to each event
-            for header_name in &self.headers {
+            for header_name in &route.headers {
                 let value = headers_config.get(header_name).map(HeaderValue::as_bytes);
 
                 self.log_namespace.insert_source_metadata(
@@ -408,7 +606,7 @@ impl SimpleHttpSource {
 
         add_query_parameters(
             events,
-            &self.query_parameters,
+            &route.query_parameters,
             query_parameters,
             self.log_namespace,
             SimpleHttpConfig::NAME,
@@ -435,7 +633,27 @@ impl HttpSource for SimpleHttpSource {
         query_parameters: HashMap<String, String>,
         request_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        let mut decoder = self.decoder.clone();
+        let route = self
+            .route(request_path)
+            .ok_or_else(|| ErrorMessage::new(StatusCode::NOT_FOUND, "Not found".to_string()))?;
+
+        // Only tagged events record the outcome of the verification, rejected requests never
+        // make it into events.
+        let signature_verified = match &route.signature {
+            None => None,
+            Some(verifier) => match (verifier.verify(&header_map, &body), verifier.on_failure()) {
+                (Ok(()), SignatureFailureAction::Reject) => None,
+                (Err(error), SignatureFailureAction::Reject) => {
+                    return Err(ErrorMessage::new(
+                        StatusCode::UNAUTHORIZED,
+                        format!("Signature verification failed: {}", error),
+                    ));
+                }
+                (result, SignatureFailureAction::Tag) => Some(result.is_ok()),
+            },
+        };
+
+        let mut decoder = route.decoder.clone();
         let mut events = Vec::new();
         let mut bytes = BytesMut::new();
         bytes.extend_from_slice(&body);
@@ -457,10 +675,33 @@ impl HttpSource for SimpleHttpSource {
             }
         }
 
-        self.enrich_events(&mut events, request_path, header_map, query_parameters);
+        self.enrich_events(
+            route,
+            &mut events,
+            request_path,
+            header_map,
+            query_parameters,
+        );
+
+        if let Some(verified) = signature_verified {
+            for event in events.iter_mut() {
+                self.log_namespace.insert_source_metadata(
+                    SimpleHttpConfig::NAME,
+                    event.as_mut_log(),
+                    Some(LegacyKey::Overwrite(path!("signature_verified"))),
+                    path!("signature_verified"),
+                    verified,
+                );
+            }
+        }
 
         Ok(events)
     }
+
+    fn output(&self, request_path: &str) -> Option<String> {
+        self.route(request_path)
+            .and_then(|route| route.output.clone())
+    }
 }
 
 #[cfg(test)]
@@ -472,7 +713,7 @@ mod tests {
     use value::Kind;
     use vector_config::NamedComponent;
     use vector_core::config::LogNamespace;
-    use vector_core::event::LogEvent;
+    use vector_core::event::{into_event_stream, LogEvent};
     use vector_core::schema::Definition;
 
     use codecs::{
@@ -483,12 +724,12 @@ mod tests {
         write::{GzEncoder, ZlibEncoder},
         Compression,
     };
-    use futures::Stream;
+    use futures::{Stream, StreamExt};
     use http::{HeaderMap, Method};
     use lookup::lookup_v2::OptionalValuePath;
     use similar_asserts::assert_eq;
 
-    use super::{remove_duplicates, SimpleHttpConfig};
+    use super::{remove_duplicates, HttpServerRouteConfig, SimpleHttpConfig};
     use crate::sources::http_server::HttpMethod;
     use crate::{
         config::{log_schema, SourceConfig, SourceContext},
@@ -537,10 +778,12 @@ mod tests {
                 query_parameters,
                 tls: None,
                 auth: None,
+                signature: None,
                 strict_path,
                 path_key,
                 path,
                 method,
+                routes: Vec::new(),
                 framing,
                 decoding,
                 acknowledgements: acknowledgements.into(),
@@ -1284,6 +1527,228 @@ mod tests {
         assert_eq!(200, send_request(addr, "GET", "", "/").await);
     }
 
+    #[tokio::test]
+    async fn http_routes() {
+        let (mut sender, default_rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let route_rx = sender
+            .add_outputs(EventStatus::Delivered, "json".to_string())
+            .flat_map(into_event_stream);
+        let address = next_addr();
+        let context = SourceContext::new_test(sender, None);
+
+        tokio::spawn(async move {
+            SimpleHttpConfig {
+                address,
+                routes: vec![HttpServerRouteConfig {
+                    name: "json".to_string(),
+                    path: "/json".to_string(),
+                    strict_path: true,
+                    headers: vec![],
+                    query_parameters: vec!["source".to_string()],
+                    signature: None,
+                    framing: None,
+                    decoding: Some(JsonDeserializerConfig::new().into()),
+                }],
+                ..Default::default()
+            }
+            .build(context)
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+        });
+        wait_for_tcp(address).await;
+
+        let mut events = spawn_ok_collect_n(
+            send_with_path(address, r#"{"key":"value"}"#, "/json?source=webhook"),
+            route_rx,
+            1,
+        )
+        .await;
+        {
+            let event = events.remove(0);
+            let log = event.as_log();
+            assert_eq!(log["key"], "value".into());
+            assert_eq!(log["source"], "webhook".into());
+            assert_eq!(log["path"], "/json".into());
+        }
+
+        let mut events =
+            spawn_ok_collect_n(send(address, r#"{"key":"value"}"#), default_rx, 1).await;
+        {
+            let event = events.remove(0);
+            let log = event.as_log();
+            assert_eq!(log[log_schema().message_key()], r#"{"key":"value"}"#.into());
+            assert_eq!(log["path"], "/".into());
+        }
+
+        assert_eq!(404, send_with_path(address, "body", "/json/nested").await);
+        assert_eq!(404, send_with_path(address, "body", "/other").await);
+    }
+
+    async fn signed_source(on_failure: &str) -> (impl Stream<Item = Event> + Unpin, SocketAddr) {
+        let (sender, recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let context = SourceContext::new_test(sender, None);
+        let signature = toml::from_str(&format!(
+            r#"
+            provider = "github"
+            secret = "It's a Secret to Everybody"
+            on_failure = "{}"
+            "#,
+            on_failure
+        ))
+        .unwrap();
+
+        tokio::spawn(async move {
+            SimpleHttpConfig {
+                address,
+                signature: Some(signature),
+                ..Default::default()
+            }
+            .build(context)
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+        });
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
+
+    fn github_signature(signature: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-hub-signature-256", signature.parse().unwrap());
+        headers
+    }
+
+    #[tokio::test]
+    async fn http_signature_reject() {
+        let (rx, addr) = signed_source("reject").await;
+
+        assert_eq!(
+            401,
+            send_with_headers(
+                addr,
+                "Hello, World!",
+                github_signature(
+                    "sha256=0000000000000000000000000000000000000000000000000000000000000000"
+                )
+            )
+            .await
+        );
+        assert_eq!(401, send(addr, "Hello, World!").await);
+
+        let mut events = spawn_ok_collect_n(
+            send_with_headers(
+                addr,
+                "Hello, World!",
+                github_signature(
+                    "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17",
+                ),
+            ),
+            rx,
+            1,
+        )
+        .await;
+
+        let log = events.remove(0).into_log();
+        assert_eq!(log[log_schema().message_key()], "Hello, World!".into());
+        assert!(log.get("signature_verified").is_none());
+    }
+
+    #[tokio::test]
+    async fn http_signature_tag() {
+        let (rx, addr) = signed_source("tag").await;
+
+        let mut events = spawn_ok_collect_n(send(addr, "Hello, World!"), rx, 1).await;
+
+        let log = events.remove(0).into_log();
+        assert_eq!(log[log_schema().message_key()], "Hello, World!".into());
+        assert_eq!(log["signature_verified"], false.into());
+    }
+
+    #[tokio::test]
+    async fn http_signature_tag_valid() {
+        let (rx, addr) = signed_source("tag").await;
+
+        let mut events = spawn_ok_collect_n(
+            send_with_headers(
+                addr,
+                "Hello, World!",
+                github_signature(
+                    "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17",
+                ),
+            ),
+            rx,
+            1,
+        )
+        .await;
+
+        let log = events.remove(0).into_log();
+        assert_eq!(log["signature_verified"], true.into());
+    }
+
+    #[tokio::test]
+    async fn http_signature_reject_tampered_body() {
+        let (_rx, addr) = signed_source("reject").await;
+
+        // The signature of "Hello, World!", sent with another body.
+        assert_eq!(
+            401,
+            send_with_headers(
+                addr,
+                "Hello, World?",
+                github_signature(
+                    "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17",
+                ),
+            )
+            .await
+        );
+    }
+
+    #[test]
+    fn outputs_include_routes() {
+        let config = SimpleHttpConfig {
+            routes: vec![HttpServerRouteConfig {
+                name: "json".to_string(),
+                path: "/json".to_string(),
+                strict_path: true,
+                headers: vec![],
+                query_parameters: vec![],
+                signature: None,
+                framing: None,
+                decoding: Some(JsonDeserializerConfig::new().into()),
+            }],
+            ..Default::default()
+        };
+
+        let outputs = config.outputs(LogNamespace::Legacy);
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].port, None);
+        assert_eq!(outputs[1].port, Some("json".to_string()));
+    }
+
+    #[test]
+    fn duplicate_route_names_are_rejected() {
+        let route = HttpServerRouteConfig {
+            name: "json".to_string(),
+            path: "/json".to_string(),
+            strict_path: true,
+            headers: vec![],
+            query_parameters: vec![],
+            signature: None,
+            framing: None,
+            decoding: None,
+        };
+        let config = SimpleHttpConfig {
+            routes: vec![route.clone(), route],
+            ..Default::default()
+        };
+
+        assert!(config.validate_routes().is_err());
+    }
+
     #[test]
     fn output_schema_definition_vector_namespace() {
         let config = SimpleHttpConfig {
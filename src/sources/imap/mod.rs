@@ -0,0 +1,489 @@
+use std::{net::SocketAddr, time::Duration};
+
+use async_imap::{types::Fetch, Client, Session};
+use chrono::Utc;
+use futures::{StreamExt, TryStreamExt};
+use lookup::{owned_value_path, path};
+use snafu::{ResultExt, Snafu};
+use tokio::net::TcpStream;
+use tokio_stream::wrappers::IntervalStream;
+use value::{kind::Collection, Kind};
+use vector_common::{
+    internal_event::{
+        ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol, Registered,
+    },
+    sensitive_string::SensitiveString,
+};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Output, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
+    },
+    dns,
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{EventsReceived, ImapParseError, ImapReadError, StreamClosedError},
+    serde::bool_or_struct,
+    shutdown::ShutdownSignal,
+    tls::{MaybeTlsSettings, MaybeTlsStream, TlsEnableableConfig},
+    SourceSender,
+};
+
+mod parse;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid address: {}", source))]
+    InvalidAddress { source: http::uri::InvalidUri },
+    #[snafu(display("missing host in address field"))]
+    MissingHost,
+    #[snafu(display("missing port in address field"))]
+    MissingPort,
+}
+
+#[derive(Debug, Snafu)]
+enum ReadError {
+    #[snafu(display("DNS error: {}", source))]
+    Dns { source: dns::DnsError },
+    #[snafu(display("no addresses found for {}", host))]
+    NoAddresses { host: String },
+    #[snafu(display("failed to connect: {}", source))]
+    Connect { source: crate::tls::TlsError },
+    #[snafu(display("server closed the connection before its greeting"))]
+    NoGreeting,
+    #[snafu(display("IMAP error: {}", source))]
+    Imap { source: async_imap::error::Error },
+    #[snafu(display("source output closed"))]
+    OutputClosed,
+}
+
+/// Configuration for the `imap` source.
+#[configurable_component(source("imap"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ImapConfig {
+    /// The IMAP server to read messages from, as `host:port`.
+    #[configurable(metadata(docs::examples = "imap.example.com:993"))]
+    address: String,
+
+    /// The user to log in as.
+    #[configurable(metadata(docs::examples = "alerts@example.com"))]
+    username: String,
+
+    /// The password of the user.
+    #[configurable(metadata(docs::examples = "${IMAP_PASSWORD}"))]
+    password: SensitiveString,
+
+    /// The mailbox to read messages from.
+    #[serde(default = "default_mailbox")]
+    mailbox: String,
+
+    /// The IMAP search criteria selecting the messages to read.
+    ///
+    /// The default only selects messages without the `\Seen` flag, which works with every
+    /// `after_read` action.
+    #[configurable(metadata(docs::examples = "UNSEEN FROM \"abuse@example.com\""))]
+    #[serde(default = "default_search")]
+    search: String,
+
+    /// The interval between polls of the mailbox, in seconds.
+    #[serde(default = "default_poll_interval_secs")]
+    poll_interval_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    after_read: AfterRead,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+/// What to do with a message once it has been read.
+///
+/// When acknowledgements are enabled, this happens once the event read from the message has been
+/// delivered.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "action")]
+pub enum AfterRead {
+    /// Add the `\Seen` flag to the message.
+    #[default]
+    MarkSeen,
+
+    /// Move the message to another mailbox.
+    ///
+    /// The server must support the `MOVE` extension.
+    Move {
+        /// The mailbox to move the message to.
+        #[configurable(metadata(docs::examples = "Processed"))]
+        mailbox: String,
+    },
+
+    /// Delete the message.
+    Delete,
+}
+
+fn default_mailbox() -> String {
+    "INBOX".to_string()
+}
+
+fn default_search() -> String {
+    "UNSEEN".to_string()
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    60
+}
+
+impl GenerateConfig for ImapConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "imap.example.com:993"
+            username = "alerts@example.com"
+            password = "${IMAP_PASSWORD}"
+            tls.enabled = true"#,
+        )
+        .unwrap()
+    }
+}
+
+impl ImapConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        let attachment = Kind::object(Collection::from_unknown(Kind::bytes().or_integer()));
+
+        Definition::new_with_default_metadata(Kind::object(Collection::empty()), [log_namespace])
+            .with_event_field(
+                &owned_value_path!("message"),
+                Kind::bytes(),
+                Some("message"),
+            )
+            .with_event_field(
+                &owned_value_path!("html"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("subject"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("from"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(&owned_value_path!("to"), Kind::bytes().or_undefined(), None)
+            .with_event_field(&owned_value_path!("cc"), Kind::bytes().or_undefined(), None)
+            .with_event_field(
+                &owned_value_path!("message_id"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("date"),
+                Kind::timestamp().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("headers"),
+                Kind::object(Collection::from_unknown(
+                    Kind::bytes().or_array(Collection::from_unknown(Kind::bytes())),
+                )),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("attachments"),
+                Kind::array(Collection::from_unknown(attachment)),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("mailbox"))),
+                &owned_value_path!("mailbox"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("uid"))),
+                &owned_value_path!("uid"),
+                Kind::integer(),
+                None,
+            )
+            .with_standard_vector_source_metadata()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for ImapConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let uri = self
+            .address
+            .parse::<http::Uri>()
+            .context(InvalidAddressSnafu)?;
+        let host = uri.host().ok_or(BuildError::MissingHost)?.to_string();
+        let port = uri.port_u16().ok_or(BuildError::MissingPort)?;
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+
+        let reader = MailboxReader {
+            host,
+            port,
+            tls,
+            username: self.username.clone(),
+            password: self.password.clone(),
+            mailbox: self.mailbox.clone(),
+            search: self.search.clone(),
+            after_read: self.after_read.clone(),
+            acknowledgements: cx.do_acknowledgements(self.acknowledgements),
+            log_namespace: cx.log_namespace(self.log_namespace),
+            bytes_received: register!(BytesReceived::from(Protocol::from("imap"))),
+            events_received: register!(EventsReceived),
+            out: cx.out,
+        };
+        Ok(Box::pin(reader.run(
+            Duration::from_secs(self.poll_interval_secs),
+            cx.shutdown,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+struct MailboxReader {
+    host: String,
+    port: u16,
+    tls: MaybeTlsSettings,
+    username: String,
+    password: SensitiveString,
+    mailbox: String,
+    search: String,
+    after_read: AfterRead,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+    out: SourceSender,
+}
+
+impl MailboxReader {
+    async fn run(mut self, interval: Duration, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let mut ticks = IntervalStream::new(tokio::time::interval(interval)).take_until(shutdown);
+        while ticks.next().await.is_some() {
+            match self.poll().await {
+                Ok(()) => {}
+                Err(ReadError::OutputClosed) => break,
+                Err(error) => emit!(ImapReadError { error }),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn connect(&self) -> Result<Session<MaybeTlsStream<TcpStream>>, ReadError> {
+        let ip = dns::Resolver
+            .lookup_ip(self.host.clone())
+            .await
+            .context(DnsSnafu)?
+            .next()
+            .ok_or_else(|| ReadError::NoAddresses {
+                host: self.host.clone(),
+            })?;
+        let stream = self
+            .tls
+            .connect(&self.host, &SocketAddr::new(ip, self.port))
+            .await
+            .context(ConnectSnafu)?;
+
+        let mut client = Client::new(stream);
+        match client.read_response().await {
+            Some(Ok(_greeting)) => {}
+            _ => return Err(ReadError::NoGreeting),
+        }
+
+        client
+            .login(&self.username, self.password.inner())
+            .await
+            .map_err(|(source, _client)| ReadError::Imap { source })
+    }
+
+    /// Reads the messages matching the search criteria, one at a time.
+    async fn poll(&mut self) -> Result<(), ReadError> {
+        let mut session = self.connect().await?;
+        session.select(&self.mailbox).await.context(ImapSnafu)?;
+
+        let mut uids = session
+            .uid_search(&self.search)
+            .await
+            .context(ImapSnafu)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        uids.sort_unstable();
+
+        for uid in uids {
+            // `BODY.PEEK[]` doesn't set the `\Seen` flag, so messages whose events aren't
+            // delivered are read again.
+            let fetches: Vec<Fetch> = session
+                .uid_fetch(uid.to_string(), "(UID BODY.PEEK[])")
+                .await
+                .context(ImapSnafu)?
+                .try_collect()
+                .await
+                .context(ImapSnafu)?;
+
+            for fetch in &fetches {
+                if let Some(body) = fetch.body() {
+                    if self.read_message(uid, body).await? {
+                        self.apply_after_read(&mut session, uid).await?;
+                    }
+                }
+            }
+        }
+
+        session.logout().await.context(ImapSnafu)
+    }
+
+    /// Sends the event parsed from `body`.
+    ///
+    /// Returns whether or not the `after_read` action must be applied, which is not the case if
+    /// the event was not delivered.
+    async fn read_message(&mut self, uid: u32, body: &[u8]) -> Result<bool, ReadError> {
+        self.bytes_received.emit(ByteSize(body.len()));
+
+        let value = match parse::message_to_value(body) {
+            Ok(value) => value,
+            Err(error) => {
+                // The message is still processed, so it isn't read again on every poll.
+                emit!(ImapParseError { uid, error });
+                return Ok(true);
+            }
+        };
+
+        let mut log = self.log_namespace.new_log_from_data(value);
+        self.log_namespace.insert_source_metadata(
+            ImapConfig::NAME,
+            &mut log,
+            Some(LegacyKey::InsertIfEmpty(path!("mailbox"))),
+            path!("mailbox"),
+            self.mailbox.clone(),
+        );
+        self.log_namespace.insert_source_metadata(
+            ImapConfig::NAME,
+            &mut log,
+            Some(LegacyKey::InsertIfEmpty(path!("uid"))),
+            path!("uid"),
+            i64::from(uid),
+        );
+        self.log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            ImapConfig::NAME,
+            Utc::now(),
+        );
+
+        let mut events = vec![Event::from(log)];
+        self.events_received
+            .emit(CountByteSize(1, events.estimated_json_encoded_size_of()));
+
+        let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+        if let Err(error) = self.out.send_batch(events).await {
+            emit!(StreamClosedError { error, count: 1 });
+            return Err(ReadError::OutputClosed);
+        }
+
+        Ok(match receiver {
+            Some(receiver) => receiver.await == BatchStatus::Delivered,
+            None => true,
+        })
+    }
+
+    async fn apply_after_read(
+        &self,
+        session: &mut Session<MaybeTlsStream<TcpStream>>,
+        uid: u32,
+    ) -> Result<(), ReadError> {
+        let uid = uid.to_string();
+        match &self.after_read {
+            AfterRead::MarkSeen => {
+                session
+                    .uid_store(&uid, "+FLAGS.SILENT (\\Seen)")
+                    .await
+                    .context(ImapSnafu)?
+                    .try_collect::<Vec<_>>()
+                    .await
+                    .context(ImapSnafu)?;
+            }
+            AfterRead::Move { mailbox } => {
+                session.uid_mv(&uid, mailbox).await.context(ImapSnafu)?;
+            }
+            AfterRead::Delete => {
+                session
+                    .uid_store(&uid, "+FLAGS.SILENT (\\Deleted)")
+                    .await
+                    .context(ImapSnafu)?
+                    .try_collect::<Vec<_>>()
+                    .await
+                    .context(ImapSnafu)?;
+                session
+                    .expunge()
+                    .await
+                    .context(ImapSnafu)?
+                    .try_collect::<Vec<_>>()
+                    .await
+                    .context(ImapSnafu)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ImapConfig>();
+    }
+
+    #[test]
+    fn parses_after_read() {
+        let config: ImapConfig = toml::from_str(
+            r#"address = "imap.example.com:993"
+            username = "alerts@example.com"
+            password = "secret"
+            after_read.action = "move"
+            after_read.mailbox = "Processed""#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            config.after_read,
+            AfterRead::Move { mailbox } if mailbox == "Processed"
+        ));
+        assert_eq!(config.mailbox, "INBOX");
+        assert_eq!(config.search, "UNSEEN");
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+
+use chrono::{TimeZone, Utc};
+use mailparse::{DispositionType, MailParseError, ParsedMail};
+use value::Value;
+
+/// The parts of a message relevant to the event built from it.
+#[derive(Default)]
+struct Parts {
+    text: Option<String>,
+    html: Option<String>,
+    attachments: Vec<Value>,
+}
+
+/// Parses a raw RFC 5322 message into an object with its headers, bodies, and the metadata of its
+/// attachments.
+///
+/// The `text/plain` body is used as the `message`, falling back to the `text/html` body. The
+/// content of attachments is not included, only their file name, content type, and size.
+pub fn message_to_value(raw: &[u8]) -> Result<Value, MailParseError> {
+    let mail = mailparse::parse_mail(raw)?;
+
+    let mut parts = Parts::default();
+    collect_parts(&mail, &mut parts)?;
+
+    let mut headers = BTreeMap::<String, Value>::new();
+    for header in &mail.headers {
+        let key = header.get_key().to_lowercase();
+        let value = Value::from(header.get_value());
+        match headers.get_mut(&key) {
+            Some(Value::Array(values)) => values.push(value),
+            Some(existing) => *existing = Value::Array(vec![existing.clone(), value]),
+            None => {
+                headers.insert(key, value);
+            }
+        }
+    }
+
+    let mut object = BTreeMap::new();
+    for field in ["subject", "from", "to", "cc", "message-id"] {
+        if let Some(value) = mail
+            .headers
+            .iter()
+            .find(|header| header.get_key().eq_ignore_ascii_case(field))
+        {
+            object.insert(field.replace('-', "_"), Value::from(value.get_value()));
+        }
+    }
+    if let Some(date) = headers
+        .get("date")
+        .and_then(|date| date.as_str())
+        .and_then(|date| mailparse::dateparse(&date).ok())
+        .and_then(|date| Utc.timestamp_opt(date, 0).single())
+    {
+        object.insert("date".to_string(), Value::from(date));
+    }
+
+    let message = parts
+        .text
+        .clone()
+        .or_else(|| parts.html.clone())
+        .unwrap_or_default();
+    object.insert("message".to_string(), Value::from(message));
+    if let Some(html) = parts.html {
+        object.insert("html".to_string(), Value::from(html));
+    }
+    object.insert("attachments".to_string(), Value::Array(parts.attachments));
+    object.insert("headers".to_string(), Value::Object(headers));
+
+    Ok(Value::Object(object))
+}
+
+fn collect_parts(part: &ParsedMail<'_>, parts: &mut Parts) -> Result<(), MailParseError> {
+    let disposition = part.get_content_disposition();
+    let filename = disposition
+        .params
+        .get("filename")
+        .or_else(|| part.ctype.params.get("name"))
+        .cloned();
+
+    if disposition.disposition == DispositionType::Attachment || filename.is_some() {
+        let mut attachment = BTreeMap::new();
+        if let Some(filename) = filename {
+            attachment.insert("filename".to_string(), Value::from(filename));
+        }
+        attachment.insert(
+            "content_type".to_string(),
+            Value::from(part.ctype.mimetype.clone()),
+        );
+        attachment.insert(
+            "size".to_string(),
+            Value::from(part.get_body_raw()?.len() as i64),
+        );
+        parts.attachments.push(Value::Object(attachment));
+    } else if !part.subparts.is_empty() {
+        for subpart in &part.subparts {
+            collect_parts(subpart, parts)?;
+        }
+    } else if part.ctype.mimetype.eq_ignore_ascii_case("text/plain") {
+        if parts.text.is_none() {
+            parts.text = Some(part.get_body()?);
+        }
+    } else if part.ctype.mimetype.eq_ignore_ascii_case("text/html") && parts.html.is_none() {
+        parts.html = Some(part.get_body()?);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = "From: Alerts <alerts@example.com>\r\n\
+        To: ops@example.com\r\n\
+        Subject: =?utf-8?q?Disk_usage_above_90=25?=\r\n\
+        Date: Tue, 10 Jan 2023 12:30:00 +0000\r\n\
+        Received: from mx1.example.com\r\n\
+        Received: from mx2.example.com\r\n\
+        MIME-Version: 1.0\r\n\
+        Content-Type: multipart/mixed; boundary=\"outer\"\r\n\
+        \r\n\
+        --outer\r\n\
+        Content-Type: multipart/alternative; boundary=\"inner\"\r\n\
+        \r\n\
+        --inner\r\n\
+        Content-Type: text/plain; charset=utf-8\r\n\
+        \r\n\
+        Disk usage on db-1 is 93%.\r\n\
+        --inner\r\n\
+        Content-Type: text/html; charset=utf-8\r\n\
+        \r\n\
+        <p>Disk usage on db-1 is 93%.</p>\r\n\
+        --inner--\r\n\
+        --outer\r\n\
+        Content-Type: text/csv; name=\"usage.csv\"\r\n\
+        Content-Disposition: attachment; filename=\"usage.csv\"\r\n\
+        \r\n\
+        mount,usage\r\n\
+        /,93\r\n\
+        --outer--\r\n";
+
+    #[test]
+    fn parses_multipart_message() {
+        let value = message_to_value(MESSAGE.as_bytes()).unwrap();
+
+        assert_eq!(
+            value.get("subject"),
+            Some(&Value::from("Disk usage above 90%"))
+        );
+        assert_eq!(
+            value.get("from"),
+            Some(&Value::from("Alerts <alerts@example.com>"))
+        );
+        assert_eq!(value.get("to"), Some(&Value::from("ops@example.com")));
+        assert_eq!(
+            value.get("date"),
+            Some(&Value::from(Utc.ymd(2023, 1, 10).and_hms(12, 30, 0)))
+        );
+        assert_eq!(
+            value.get("message"),
+            Some(&Value::from("Disk usage on db-1 is 93%.\r\n"))
+        );
+        assert_eq!(
+            value.get("html"),
+            Some(&Value::from("<p>Disk usage on db-1 is 93%.</p>\r\n"))
+        );
+        assert_eq!(
+            value.get("headers.received"),
+            Some(&Value::Array(vec![
+                Value::from("from mx1.example.com"),
+                Value::from("from mx2.example.com"),
+            ]))
+        );
+
+        let attachments = value.get("attachments").unwrap().as_array().unwrap();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(
+            attachments[0].get("filename"),
+            Some(&Value::from("usage.csv"))
+        );
+        assert_eq!(
+            attachments[0].get("content_type"),
+            Some(&Value::from("text/csv"))
+        );
+        assert_eq!(attachments[0].get("size"), Some(&Value::from(19)));
+    }
+
+    #[test]
+    fn falls_back_to_html_body() {
+        let message = "Subject: Report\r\n\
+            Content-Type: text/html\r\n\
+            \r\n\
+            <p>Abuse report</p>\r\n";
+        let value = message_to_value(message.as_bytes()).unwrap();
+
+        assert_eq!(
+            value.get("message"),
+            Some(&Value::from("<p>Abuse report</p>\r\n"))
+        );
+        assert_eq!(value.get("attachments"), Some(&Value::Array(Vec::new())));
+    }
+}
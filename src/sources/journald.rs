@@ -12,7 +12,9 @@ use bytes::Bytes;
 use chrono::{TimeZone, Utc};
 use codecs::{decoding::BoxedFramingError, CharacterDelimitedDecoder};
 use futures::{poll, stream::BoxStream, task::Poll, StreamExt};
-use lookup::{lookup_v2::parse_value_path, metadata_path, owned_value_path, path, PathPrefix};
+use lookup::{
+    event_path, lookup_v2::parse_value_path, metadata_path, owned_value_path, path, PathPrefix,
+};
 use nix::{
     sys::signal::{kill, Signal},
     unistd::Pid,
@@ -28,7 +30,10 @@ use tokio::{
     time::sleep,
 };
 use tokio_util::codec::FramedRead;
-use value::{kind::Collection, Kind, Value};
+use value::{
+    kind::{Collection, Field},
+    Kind, Value,
+};
 use vector_common::{
     finalizer::OrderedFinalizer,
     internal_event::{
@@ -67,6 +72,7 @@ const MESSAGE: &str = "MESSAGE";
 const SYSTEMD_UNIT: &str = "_SYSTEMD_UNIT";
 const SOURCE_TIMESTAMP: &str = "_SOURCE_REALTIME_TIMESTAMP";
 const RECEIVED_TIMESTAMP: &str = "__REALTIME_TIMESTAMP";
+const TRUSTED_FIELDS: &str = "trusted";
 
 const BACKOFF_DURATION: Duration = Duration::from_secs(1);
 
@@ -89,6 +95,8 @@ enum BuildError {
         value,
     ))]
     DuplicatedMatches { field: String, value: String },
+    #[snafu(display("Cannot use both `current_boot_only` and `boot_id`"))]
+    BothCurrentBootOnlyAndBootId,
 }
 
 type Matches = HashMap<String, HashSet<String>>;
@@ -104,6 +112,13 @@ pub struct JournaldConfig {
     /// Only include entries that occurred after the current boot of the system.
     pub current_boot_only: Option<bool>,
 
+    /// Only include entries from the boot with the given ID.
+    ///
+    /// The ID can be an absolute boot ID, as listed by `journalctl --list-boots`, or a relative offset such as `-1` for the previous boot. Cannot be used when `current_boot_only` is explicitly enabled.
+    #[configurable(metadata(docs::examples = "5ab4e7c1f0040b57a4b9f0093b1f85f0"))]
+    #[configurable(metadata(docs::examples = "-1"))]
+    pub boot_id: Option<String>,
+
     /// The list of unit names to monitor.
     ///
     /// If empty or not present, all units are accepted. Unit names lacking a "." will have ".service" appended to make them a valid service unit name.
@@ -149,6 +164,21 @@ pub struct JournaldConfig {
     /// If not set, `journalctl` will use the default system journal paths.
     pub journal_directory: Option<PathBuf>,
 
+    /// The journal namespace to read from.
+    ///
+    /// Journal namespaces allow services to log into separate journals, such as one per tenant. If not set, only the default namespace is read.
+    ///
+    /// The value `*` reads from all namespaces, and `+<name>` reads from the given namespace interleaved with the default one.
+    #[configurable(metadata(docs::examples = "tenant-a"))]
+    pub journal_namespace: Option<String>,
+
+    /// Whether or not to group the trusted journal fields into a single object.
+    ///
+    /// Trusted fields are those added by `journald` itself, prefixed with a single underscore, such as `_PID`, `_UID` or `_SYSTEMD_UNIT`. When enabled, they are placed under a `trusted` object with the leading underscore removed, instead of alongside the fields supplied by the logging process.
+    ///
+    /// The `_HOSTNAME` and `_SOURCE_REALTIME_TIMESTAMP` fields are still used to set the host and timestamp of the event.
+    pub group_trusted_fields: bool,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -193,6 +223,14 @@ impl JournaldConfig {
         matches
     }
 
+    fn current_boot_only(&self) -> crate::Result<bool> {
+        match (self.current_boot_only, &self.boot_id) {
+            (Some(true), Some(_)) => Err(BuildError::BothCurrentBootOnlyAndBootId.into()),
+            (_, Some(_)) => Ok(false),
+            (current_boot_only, None) => Ok(current_boot_only.unwrap_or(true)),
+        }
+    }
+
     /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
     fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
         let schema_definition = match log_namespace {
@@ -213,7 +251,7 @@ impl JournaldConfig {
                 JournaldConfig::NAME,
                 None,
                 &owned_value_path!("metadata"),
-                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                Kind::object(self.record_fields_collection()).or_undefined(),
                 None,
             )
             .with_source_metadata(
@@ -235,11 +273,31 @@ impl JournaldConfig {
 
         // for metadata that is added to the events dynamically through the Record
         if log_namespace == LogNamespace::Legacy {
+            if self.group_trusted_fields {
+                schema_definition = schema_definition.with_event_field(
+                    &owned_value_path!(TRUSTED_FIELDS),
+                    trusted_fields_kind().or_undefined(),
+                    None,
+                );
+            }
             schema_definition = schema_definition.unknown_fields(Kind::bytes());
         }
 
         schema_definition
     }
+
+    fn record_fields_collection(&self) -> Collection<Field> {
+        let collection = Collection::empty().with_unknown(Kind::bytes());
+        if self.group_trusted_fields {
+            collection.with_known(TRUSTED_FIELDS, trusted_fields_kind().or_undefined())
+        } else {
+            collection
+        }
+    }
+}
+
+fn trusted_fields_kind() -> Kind {
+    Kind::object(Collection::empty().with_unknown(Kind::bytes()))
 }
 
 impl_generate_config_from_default!(JournaldConfig);
@@ -285,7 +343,9 @@ impl SourceConfig for JournaldConfig {
         let starter = StartJournalctl::new(
             journalctl_path,
             self.journal_directory.clone(),
-            self.current_boot_only.unwrap_or(true),
+            self.journal_namespace.clone(),
+            self.current_boot_only()?,
+            self.boot_id.clone(),
             self.since_now.unwrap_or(false),
         );
 
@@ -300,6 +360,7 @@ impl SourceConfig for JournaldConfig {
                 checkpoint_path,
                 batch_size,
                 remap_priority: self.remap_priority,
+                group_trusted_fields: self.group_trusted_fields,
                 out: cx.out,
                 acknowledgements,
                 starter,
@@ -327,6 +388,7 @@ struct JournaldSource {
     checkpoint_path: PathBuf,
     batch_size: usize,
     remap_priority: bool,
+    group_trusted_fields: bool,
     out: SourceSender,
     acknowledgements: bool,
     starter: StartJournalctl,
@@ -496,6 +558,7 @@ impl<'a> Batch<'a> {
                                 record,
                                 &self.batch,
                                 self.source.log_namespace,
+                                self.source.group_trusted_fields,
                             );
 
                             enrich_log_event(&mut event, self.source.log_namespace);
@@ -554,7 +617,9 @@ type JournalStream = BoxStream<'static, Result<Bytes, BoxedFramingError>>;
 struct StartJournalctl {
     path: PathBuf,
     journal_dir: Option<PathBuf>,
+    journal_namespace: Option<String>,
     current_boot_only: bool,
+    boot_id: Option<String>,
     since_now: bool,
 }
 
@@ -562,13 +627,17 @@ impl StartJournalctl {
     const fn new(
         path: PathBuf,
         journal_dir: Option<PathBuf>,
+        journal_namespace: Option<String>,
         current_boot_only: bool,
+        boot_id: Option<String>,
         since_now: bool,
     ) -> Self {
         Self {
             path,
             journal_dir,
+            journal_namespace,
             current_boot_only,
+            boot_id,
             since_now,
         }
     }
@@ -585,7 +654,13 @@ impl StartJournalctl {
             command.arg(format!("--directory={}", dir.display()));
         }
 
-        if self.current_boot_only {
+        if let Some(namespace) = &self.journal_namespace {
+            command.arg(format!("--namespace={}", namespace));
+        }
+
+        if let Some(boot_id) = &self.boot_id {
+            command.arg(format!("--boot={}", boot_id));
+        } else if self.current_boot_only {
             command.arg("--boot");
         }
 
@@ -684,8 +759,15 @@ fn create_log_event_from_record(
     mut record: Record,
     batch: &Option<BatchNotifier>,
     log_namespace: LogNamespace,
+    group_trusted_fields: bool,
 ) -> LogEvent {
-    match log_namespace {
+    let trusted_fields = if group_trusted_fields {
+        take_trusted_fields(&mut record)
+    } else {
+        Vec::new()
+    };
+
+    let mut log = match log_namespace {
         LogNamespace::Vector => {
             let message_value = record
                 .remove(MESSAGE)
@@ -712,7 +794,54 @@ fn create_log_event_from_record(
 
             log
         }
+    };
+
+    for (key, value) in trusted_fields {
+        match log_namespace {
+            LogNamespace::Vector => {
+                log.metadata_mut().value_mut().insert(
+                    path!(
+                        JournaldConfig::NAME,
+                        "metadata",
+                        TRUSTED_FIELDS,
+                        key.as_str()
+                    ),
+                    value,
+                );
+            }
+            LogNamespace::Legacy => {
+                log.insert(event_path!(TRUSTED_FIELDS, key.as_str()), value);
+            }
+        }
     }
+
+    log
+}
+
+/// Removes the trusted fields from the record, returning them with their leading underscore stripped.
+///
+/// The host and timestamp fields are left in place since they are handled by `enrich_log_event`.
+fn take_trusted_fields(record: &mut Record) -> Vec<(String, String)> {
+    let keys = record
+        .keys()
+        .filter(|key| is_trusted_field(key))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            record
+                .remove(&key)
+                .map(|value| (key[1..].to_string(), value))
+        })
+        .collect()
+}
+
+fn is_trusted_field(field: &str) -> bool {
+    field.starts_with('_')
+        && !field.starts_with("__")
+        && field != HOSTNAME
+        && field != SOURCE_TIMESTAMP
 }
 
 /// Map the given unit name into a valid systemd unit
@@ -1363,6 +1492,78 @@ mod tests {
         assert!(cmd_line.contains("--directory=/tmp/journal-dir"));
         assert!(cmd_line.contains("--boot"));
         assert!(cmd_line.contains("--after-cursor="));
+        assert!(!cmd_line.contains("--namespace="));
+    }
+
+    #[test]
+    fn command_options_namespace_and_boot_id() {
+        let command = StartJournalctl::new(
+            PathBuf::from("journalctl"),
+            None,
+            Some("tenant-a".into()),
+            false,
+            Some("5ab4e7c1f0040b57a4b9f0093b1f85f0".into()),
+            false,
+        )
+        .make_command(None);
+        let cmd_line = format!("{:?}", command);
+        assert!(cmd_line.contains("--namespace=tenant-a"));
+        assert!(cmd_line.contains("--boot=5ab4e7c1f0040b57a4b9f0093b1f85f0"));
+    }
+
+    #[test]
+    fn boot_id_and_current_boot_only() {
+        let mut config = JournaldConfig {
+            boot_id: Some("-1".into()),
+            ..Default::default()
+        };
+        assert!(!config.current_boot_only().unwrap());
+
+        config.current_boot_only = Some(false);
+        assert!(!config.current_boot_only().unwrap());
+
+        config.current_boot_only = Some(true);
+        assert!(config.current_boot_only().is_err());
+
+        config.boot_id = None;
+        assert!(config.current_boot_only().unwrap());
+    }
+
+    #[test]
+    fn groups_trusted_fields() {
+        let record: Record = [
+            ("MESSAGE", "hello"),
+            ("PRIORITY", "6"),
+            ("_PID", "2156"),
+            ("_SYSTEMD_UNIT", "ntpd.service"),
+            ("_HOSTNAME", "my-host.local"),
+            ("__MONOTONIC_TIMESTAMP", "98694000446"),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+
+        let log = create_log_event_from_record(record.clone(), &None, LogNamespace::Legacy, true);
+        assert_eq!(log["trusted.PID"], "2156".into());
+        assert_eq!(log["trusted.SYSTEMD_UNIT"], "ntpd.service".into());
+        assert_eq!(log["PRIORITY"], "6".into());
+        assert_eq!(log["_HOSTNAME"], "my-host.local".into());
+        assert_eq!(log["__MONOTONIC_TIMESTAMP"], "98694000446".into());
+        assert!(!log.contains("_PID"));
+
+        let log = create_log_event_from_record(record, &None, LogNamespace::Vector, true);
+        let metadata = log.metadata().value();
+        assert_eq!(
+            metadata.get(path!(JournaldConfig::NAME, "metadata", "trusted", "PID")),
+            Some(&Value::from("2156"))
+        );
+        assert_eq!(
+            metadata.get(path!(JournaldConfig::NAME, "metadata", "PRIORITY")),
+            Some(&Value::from("6"))
+        );
+        assert!(metadata
+            .get(path!(JournaldConfig::NAME, "metadata", "_PID"))
+            .is_none());
     }
 
     fn create_command(
@@ -1372,8 +1573,15 @@ mod tests {
         since_now: bool,
         cursor: Option<&str>,
     ) -> Command {
-        StartJournalctl::new(path.into(), journal_dir, current_boot_only, since_now)
-            .make_command(cursor)
+        StartJournalctl::new(
+            path.into(),
+            journal_dir,
+            None,
+            current_boot_only,
+            None,
+            since_now,
+        )
+        .make_command(cursor)
     }
 
     fn message(event: &Event) -> Value {
@@ -1498,4 +1706,25 @@ mod tests {
 
         matches_schema(&config, LogNamespace::Legacy)
     }
+
+    #[test]
+    fn output_schema_definition_grouped_trusted_fields() {
+        let config = JournaldConfig {
+            group_trusted_fields: true,
+            ..Default::default()
+        };
+
+        let definition = config.outputs(LogNamespace::Legacy)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+
+        let mut event = Event::from(LogEvent::from("message"));
+        event.as_mut_log().insert("timestamp", chrono::Utc::now());
+        event.as_mut_log().insert("source_type", "journald");
+        event.as_mut_log().insert("trusted.PID", "2156");
+        event.as_mut_log().insert("PRIORITY", "6");
+
+        definition.assert_valid_for_event(&event)
+    }
 }
@@ -8,7 +8,7 @@ use file_source::paths_provider::PathsProvider;
 use k8s_openapi::api::core::v1::{Namespace, Pod};
 use kube::runtime::reflector::{store::Store, ObjectRef};
 
-use super::path_helpers::build_pod_logs_directory;
+use super::{path_helpers::build_pod_logs_directory, pod_selector::PodFilter};
 use crate::kubernetes::pod_manager_logic::extract_static_pod_config_hashsum;
 
 /// A paths provider implementation that uses the state obtained from the
@@ -17,6 +17,7 @@ pub struct K8sPathsProvider {
     pod_state: Store<Pod>,
     namespace_state: Store<Namespace>,
     exclude_paths: Vec<glob::Pattern>,
+    pod_filter: PodFilter,
 }
 
 impl K8sPathsProvider {
@@ -25,11 +26,13 @@ impl K8sPathsProvider {
         pod_state: Store<Pod>,
         namespace_state: Store<Namespace>,
         exclude_paths: Vec<glob::Pattern>,
+        pod_filter: PodFilter,
     ) -> Self {
         Self {
             pod_state,
             namespace_state,
             exclude_paths,
+            pod_filter,
         }
     }
 }
@@ -54,6 +57,14 @@ impl PathsProvider for K8sPathsProvider {
                     false
                 }
             })
+            // filter out pods rejected by the selectors the API can't evaluate for us
+            .filter(|pod| {
+                let included = self.pod_filter.matches(pod);
+                if !included {
+                    trace!(message = "Skipping pod excluded by selectors.", pod = ?pod.metadata.name);
+                }
+                included
+            })
             .flat_map(|pod| {
                 trace!(message = "Providing log paths for pod.", pod = ?pod.metadata.name);
                 let paths_iter = list_pod_log_paths(real_glob, pod.as_ref());
@@ -56,6 +56,7 @@ mod parser;
 mod partial_events_merger;
 mod path_helpers;
 mod pod_metadata_annotator;
+mod pod_selector;
 mod transform_utils;
 mod util;
 
@@ -68,6 +69,7 @@ use futures::{future::FutureExt, stream::StreamExt};
 use k8s_paths_provider::K8sPathsProvider;
 use lifecycle::Lifecycle;
 use lookup::{owned_value_path, path, PathPrefix};
+use pod_selector::PodFilter;
 use value::{kind::Collection, Kind};
 use vector_core::config::LogNamespace;
 
@@ -96,6 +98,24 @@ pub struct Config {
     /// Specifies the field selector to filter `Pod`s with, to be used in addition to the built-in `Node` filter.
     extra_field_selector: String,
 
+    /// Specifies the annotation selector to filter `Pod`s with.
+    ///
+    /// Uses the same syntax as label selectors, both equality-based (`=`, `==`, `!=`) and set-based (`in`, `notin`, `key`, `!key`). As the Kubernetes API does not support filtering on annotations, this selector is evaluated by Vector before it starts reading the logs of a `Pod`.
+    #[configurable(metadata(docs::examples = "example.com/logs=enabled"))]
+    extra_annotation_selector: String,
+
+    /// Specifies the label selector of `Pod`s to exclude.
+    ///
+    /// `Pod`s matching all of the requirements of this selector are not collected. If empty, no `Pod`s are excluded.
+    #[configurable(metadata(docs::examples = "tier in (debug, canary)"))]
+    exclude_label_selector: String,
+
+    /// Specifies the annotation selector of `Pod`s to exclude.
+    ///
+    /// `Pod`s matching all of the requirements of this selector are not collected. If empty, no `Pod`s are excluded.
+    #[configurable(metadata(docs::examples = "example.com/noisy=true"))]
+    exclude_annotation_selector: String,
+
     /// Whether or not to automatically merge partial events.
     auto_partial_merge: bool,
 
@@ -179,6 +199,9 @@ impl Default for Config {
             extra_namespace_label_selector: "".to_string(),
             self_node_name: default_self_node_name_env_template(),
             extra_field_selector: "".to_string(),
+            extra_annotation_selector: "".to_string(),
+            exclude_label_selector: "".to_string(),
+            exclude_annotation_selector: "".to_string(),
             auto_partial_merge: true,
             data_dir: None,
             pod_annotation_fields: pod_metadata_annotator::FieldsSpec::default(),
@@ -430,6 +453,7 @@ struct Source {
     label_selector: String,
     namespace_label_selector: String,
     node_selector: String,
+    pod_filter: PodFilter,
     self_node_name: String,
     exclude_paths: Vec<glob::Pattern>,
     max_read_bytes: usize,
@@ -464,6 +488,11 @@ impl Source {
         let namespace_label_selector =
             prepare_label_selector(config.extra_namespace_label_selector.as_ref());
         let node_selector = prepare_node_selector(self_node_name.as_str())?;
+        let pod_filter = PodFilter::new(
+            &config.extra_annotation_selector,
+            &config.exclude_label_selector,
+            &config.exclude_annotation_selector,
+        )?;
 
         // If the user passed a custom Kubeconfig use it, otherwise
         // we attempt to load the local kubec-config, followed by the
@@ -507,6 +536,7 @@ impl Source {
             label_selector,
             namespace_label_selector,
             node_selector,
+            pod_filter,
             self_node_name,
             exclude_paths,
             max_read_bytes: config.max_read_bytes,
@@ -535,6 +565,7 @@ impl Source {
             label_selector,
             namespace_label_selector,
             node_selector,
+            pod_filter,
             self_node_name,
             exclude_paths,
             max_read_bytes,
@@ -610,8 +641,12 @@ impl Source {
             delay_deletion,
         )));
 
-        let paths_provider =
-            K8sPathsProvider::new(pod_state.clone(), ns_state.clone(), exclude_paths);
+        let paths_provider = K8sPathsProvider::new(
+            pod_state.clone(),
+            ns_state.clone(),
+            exclude_paths,
+            pod_filter,
+        );
         let annotator = PodMetadataAnnotator::new(pod_state, pod_fields_spec, log_namespace);
         let ns_annotator =
             NamespaceMetadataAnnotator::new(ns_state, namespace_fields_spec, log_namespace);
@@ -0,0 +1,392 @@
+//! Client-side evaluation of selector expressions against `Pod` metadata.
+//!
+//! The Kubernetes API only supports filtering by labels and a small set of
+//! fields, so annotation filtering and exclusion selectors are evaluated here,
+//! before the log paths of a `Pod` are handed to the file server.
+//!
+//! The syntax follows the `kubectl` label selector syntax, see
+//! <https://kubernetes.io/docs/concepts/overview/working-with-objects/labels/#label-selectors>.
+
+#![deny(missing_docs)]
+
+use std::{collections::BTreeMap, collections::BTreeSet, str::FromStr};
+
+use k8s_openapi::api::core::v1::Pod;
+use snafu::Snafu;
+
+/// Errors that can occur while parsing a selector expression.
+#[derive(Debug, PartialEq, Eq, Snafu)]
+pub enum SelectorParseError {
+    /// A requirement has no key.
+    #[snafu(display("Selector requirement {:?} is missing a key", requirement))]
+    MissingKey {
+        /// The offending requirement.
+        requirement: String,
+    },
+
+    /// A set-based requirement is not enclosed in parentheses.
+    #[snafu(display(
+        "Selector requirement {:?} must list its values in parentheses",
+        requirement
+    ))]
+    InvalidSet {
+        /// The offending requirement.
+        requirement: String,
+    },
+
+    /// The parentheses in the selector are not balanced.
+    #[snafu(display("Selector {:?} has unbalanced parentheses", selector))]
+    UnbalancedParentheses {
+        /// The offending selector.
+        selector: String,
+    },
+}
+
+/// The operator of a single selector requirement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Operator {
+    Equals(String),
+    NotEquals(String),
+    In(BTreeSet<String>),
+    NotIn(BTreeSet<String>),
+    Exists,
+    DoesNotExist,
+}
+
+/// A single requirement of a selector, such as `app=web` or `tier in (frontend, backend)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Requirement {
+    key: String,
+    operator: Operator,
+}
+
+impl Requirement {
+    fn matches(&self, map: Option<&BTreeMap<String, String>>) -> bool {
+        let value = map.and_then(|map| map.get(&self.key));
+        match (&self.operator, value) {
+            (Operator::Equals(expected), Some(value)) => value == expected,
+            (Operator::NotEquals(expected), Some(value)) => value != expected,
+            (Operator::In(values), Some(value)) => values.contains(value),
+            (Operator::NotIn(values), Some(value)) => !values.contains(value),
+            (Operator::Exists, value) => value.is_some(),
+            (Operator::DoesNotExist, value) => value.is_none(),
+            // As with `kubectl`, the negative operators match when the key is absent.
+            (Operator::NotEquals(_) | Operator::NotIn(_), None) => true,
+            (Operator::Equals(_) | Operator::In(_), None) => false,
+        }
+    }
+}
+
+impl FromStr for Requirement {
+    type Err = SelectorParseError;
+
+    fn from_str(requirement: &str) -> Result<Self, Self::Err> {
+        let missing_key = || SelectorParseError::MissingKey {
+            requirement: requirement.to_string(),
+        };
+
+        if let Some(key) = requirement.strip_prefix('!') {
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(missing_key());
+            }
+            return Ok(Self {
+                key: key.to_string(),
+                operator: Operator::DoesNotExist,
+            });
+        }
+
+        let (key, operator) = if let Some((key, value)) = requirement.split_once("!=") {
+            (key, Operator::NotEquals(value.trim().to_string()))
+        } else if let Some((key, value)) = requirement.split_once("==") {
+            (key, Operator::Equals(value.trim().to_string()))
+        } else if let Some((key, value)) = requirement.split_once('=') {
+            (key, Operator::Equals(value.trim().to_string()))
+        } else if let Some((key, values)) = split_set_operator(requirement, " notin ") {
+            (key, Operator::NotIn(parse_set(requirement, values)?))
+        } else if let Some((key, values)) = split_set_operator(requirement, " in ") {
+            (key, Operator::In(parse_set(requirement, values)?))
+        } else {
+            (requirement, Operator::Exists)
+        };
+
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(missing_key());
+        }
+
+        Ok(Self {
+            key: key.to_string(),
+            operator,
+        })
+    }
+}
+
+fn split_set_operator<'a>(requirement: &'a str, operator: &str) -> Option<(&'a str, &'a str)> {
+    requirement.find(operator).map(|index| {
+        (
+            &requirement[..index],
+            &requirement[index + operator.len()..],
+        )
+    })
+}
+
+fn parse_set(requirement: &str, values: &str) -> Result<BTreeSet<String>, SelectorParseError> {
+    values
+        .trim()
+        .strip_prefix('(')
+        .and_then(|values| values.strip_suffix(')'))
+        .map(|values| {
+            values
+                .split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToString::to_string)
+                .collect()
+        })
+        .ok_or_else(|| SelectorParseError::InvalidSet {
+            requirement: requirement.to_string(),
+        })
+}
+
+/// A selector made of a list of requirements, all of which must match.
+///
+/// An empty selector matches everything.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Selector {
+    requirements: Vec<Requirement>,
+}
+
+impl Selector {
+    /// Returns `true` if the selector has no requirements.
+    pub fn is_empty(&self) -> bool {
+        self.requirements.is_empty()
+    }
+
+    /// Evaluates the selector against the given labels or annotations.
+    pub fn matches(&self, map: Option<&BTreeMap<String, String>>) -> bool {
+        self.requirements
+            .iter()
+            .all(|requirement| requirement.matches(map))
+    }
+}
+
+impl FromStr for Selector {
+    type Err = SelectorParseError;
+
+    fn from_str(selector: &str) -> Result<Self, Self::Err> {
+        let mut requirements = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+
+        for (index, c) in selector.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth = depth.checked_sub(1).ok_or_else(|| {
+                        SelectorParseError::UnbalancedParentheses {
+                            selector: selector.to_string(),
+                        }
+                    })?
+                }
+                ',' if depth == 0 => {
+                    requirements.push(&selector[start..index]);
+                    start = index + 1;
+                }
+                _ => {}
+            }
+        }
+        if depth != 0 {
+            return Err(SelectorParseError::UnbalancedParentheses {
+                selector: selector.to_string(),
+            });
+        }
+        requirements.push(&selector[start..]);
+
+        let requirements = requirements
+            .into_iter()
+            .map(str::trim)
+            .filter(|requirement| !requirement.is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { requirements })
+    }
+}
+
+/// Decides which `Pod`s to collect logs from, based on the selectors that
+/// can't be pushed down to the Kubernetes API.
+#[derive(Clone, Debug, Default)]
+pub struct PodFilter {
+    include_annotations: Selector,
+    exclude_labels: Selector,
+    exclude_annotations: Selector,
+}
+
+impl PodFilter {
+    /// Creates a new [`PodFilter`] from the configured selector expressions.
+    pub fn new(
+        include_annotations: &str,
+        exclude_labels: &str,
+        exclude_annotations: &str,
+    ) -> Result<Self, SelectorParseError> {
+        Ok(Self {
+            include_annotations: include_annotations.parse()?,
+            exclude_labels: exclude_labels.parse()?,
+            exclude_annotations: exclude_annotations.parse()?,
+        })
+    }
+
+    /// Returns `true` if logs should be collected from the given `Pod`.
+    ///
+    /// Exclusion selectors only take effect when they have at least one requirement.
+    pub fn matches(&self, pod: &Pod) -> bool {
+        let labels = pod.metadata.labels.as_ref();
+        let annotations = pod.metadata.annotations.as_ref();
+
+        let excluded = (!self.exclude_labels.is_empty() && self.exclude_labels.matches(labels))
+            || (!self.exclude_annotations.is_empty()
+                && self.exclude_annotations.matches(annotations));
+
+        !excluded && self.include_annotations.matches(annotations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+    use super::*;
+
+    fn map(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn pod(labels: &[(&str, &str)], annotations: &[(&str, &str)]) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                labels: Some(map(labels)),
+                annotations: Some(map(annotations)),
+                ..ObjectMeta::default()
+            },
+            ..Pod::default()
+        }
+    }
+
+    #[test]
+    fn parse_selector() {
+        let selector: Selector = "app=web, tier in (frontend, backend),env!=dev,!canary,team"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            selector.requirements,
+            vec![
+                Requirement {
+                    key: "app".into(),
+                    operator: Operator::Equals("web".into()),
+                },
+                Requirement {
+                    key: "tier".into(),
+                    operator: Operator::In(["frontend".into(), "backend".into()].into()),
+                },
+                Requirement {
+                    key: "env".into(),
+                    operator: Operator::NotEquals("dev".into()),
+                },
+                Requirement {
+                    key: "canary".into(),
+                    operator: Operator::DoesNotExist,
+                },
+                Requirement {
+                    key: "team".into(),
+                    operator: Operator::Exists,
+                },
+            ]
+        );
+
+        assert!("".parse::<Selector>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_selector_errors() {
+        assert_eq!(
+            "=web".parse::<Selector>(),
+            Err(SelectorParseError::MissingKey {
+                requirement: "=web".into()
+            })
+        );
+        assert_eq!(
+            "tier in frontend".parse::<Selector>(),
+            Err(SelectorParseError::InvalidSet {
+                requirement: "tier in frontend".into()
+            })
+        );
+        assert_eq!(
+            "tier in (frontend".parse::<Selector>(),
+            Err(SelectorParseError::UnbalancedParentheses {
+                selector: "tier in (frontend".into()
+            })
+        );
+    }
+
+    #[test]
+    fn selector_matches() {
+        let labels = map(&[("app", "web"), ("tier", "frontend")]);
+        let cases = [
+            ("app=web", true),
+            ("app==web", true),
+            ("app=api", false),
+            ("app!=api", true),
+            ("missing!=api", true),
+            ("tier in (frontend, backend)", true),
+            ("tier notin (frontend, backend)", false),
+            ("missing notin (frontend)", true),
+            ("missing in (frontend)", false),
+            ("app", true),
+            ("!app", false),
+            ("!missing", true),
+            ("app=web,tier=backend", false),
+        ];
+
+        for (selector, expected) in cases {
+            let selector: Selector = selector.parse().unwrap();
+            assert_eq!(selector.matches(Some(&labels)), expected, "{:?}", selector);
+        }
+
+        let selector: Selector = "!app".parse().unwrap();
+        assert!(selector.matches(None));
+    }
+
+    #[test]
+    fn pod_filter_matches() {
+        let filter = PodFilter::new(
+            "example.com/logs=enabled",
+            "tier=debug",
+            "example.com/noisy",
+        )
+        .unwrap();
+
+        assert!(filter.matches(&pod(
+            &[("tier", "frontend")],
+            &[("example.com/logs", "enabled")]
+        )));
+        assert!(!filter.matches(&pod(&[("tier", "frontend")], &[])));
+        assert!(!filter.matches(&pod(
+            &[("tier", "debug")],
+            &[("example.com/logs", "enabled")]
+        )));
+        assert!(!filter.matches(&pod(
+            &[("tier", "frontend")],
+            &[
+                ("example.com/logs", "enabled"),
+                ("example.com/noisy", "true")
+            ]
+        )));
+
+        let filter = PodFilter::default();
+        assert!(filter.matches(&pod(&[], &[])));
+    }
+}
@@ -13,6 +13,8 @@ pub mod aws_kinesis_firehose;
 pub mod aws_s3;
 #[cfg(feature = "sources-aws_sqs")]
 pub mod aws_sqs;
+#[cfg(feature = "sources-cloudflare_logs")]
+pub mod cloudflare_logs;
 #[cfg(any(feature = "sources-datadog_agent"))]
 pub mod datadog_agent;
 #[cfg(feature = "sources-demo_logs")]
@@ -36,6 +38,10 @@ pub mod file_descriptors;
 pub mod fluent;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub mod gcp_pubsub;
+#[cfg(feature = "sources-github_audit")]
+pub mod github_audit;
+#[cfg(feature = "sources-grpc_server")]
+pub mod grpc_server;
 #[cfg(feature = "sources-heroku_logs")]
 pub mod heroku_logs;
 #[cfg(feature = "sources-host_metrics")]
@@ -44,6 +50,8 @@ pub mod host_metrics;
 pub mod http_client;
 #[cfg(feature = "sources-http_server")]
 pub mod http_server;
+#[cfg(feature = "sources-imap")]
+pub mod imap;
 #[cfg(feature = "sources-internal_logs")]
 pub mod internal_logs;
 #[cfg(feature = "sources-internal_metrics")]
@@ -56,20 +64,30 @@ pub mod kafka;
 pub mod kubernetes_logs;
 #[cfg(all(feature = "sources-logstash"))]
 pub mod logstash;
+#[cfg(feature = "sources-mongodb_change_stream")]
+pub mod mongodb_change_stream;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub mod mongodb_metrics;
+#[cfg(feature = "sources-mysql_cdc")]
+pub mod mysql_cdc;
 #[cfg(all(feature = "sources-nats"))]
 pub mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 pub mod nginx_metrics;
+#[cfg(feature = "sources-okta_system_log")]
+pub mod okta_system_log;
 #[cfg(feature = "sources-opentelemetry")]
 pub mod opentelemetry;
+#[cfg(feature = "sources-postgres_cdc")]
+pub mod postgres_cdc;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub mod postgresql_metrics;
 #[cfg(feature = "sources-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sources-redis")]
 pub mod redis;
+#[cfg(feature = "sources-sftp")]
+pub mod sftp;
 #[cfg(feature = "sources-socket")]
 pub mod socket;
 #[cfg(feature = "sources-splunk_hec")]
@@ -80,6 +98,8 @@ pub mod statsd;
 pub mod syslog;
 #[cfg(feature = "sources-vector")]
 pub mod vector;
+#[cfg(all(windows, feature = "sources-windows_event_log"))]
+pub mod windows_event_log;
 
 pub mod util;
 
@@ -130,6 +150,10 @@ pub enum Sources {
     #[cfg(feature = "sources-aws_sqs")]
     AwsSqs(#[configurable(derived)] aws_sqs::AwsSqsConfig),
 
+    /// Cloudflare logs.
+    #[cfg(feature = "sources-cloudflare_logs")]
+    CloudflareLogs(#[configurable(derived)] cloudflare_logs::CloudflareLogsConfig),
+
     /// Datadog Agent.
     #[cfg(feature = "sources-datadog_agent")]
     DatadogAgent(#[configurable(derived)] datadog_agent::DatadogAgentConfig),
@@ -172,6 +196,14 @@ pub enum Sources {
     #[cfg(feature = "sources-gcp_pubsub")]
     GcpPubsub(#[configurable(derived)] gcp_pubsub::PubsubConfig),
 
+    /// GitHub audit log.
+    #[cfg(feature = "sources-github_audit")]
+    GithubAudit(#[configurable(derived)] github_audit::GithubAuditConfig),
+
+    /// gRPC server.
+    #[cfg(feature = "sources-grpc_server")]
+    GrpcServer(#[configurable(derived)] grpc_server::GrpcServerConfig),
+
     /// Heroku Logs.
     #[cfg(feature = "sources-heroku_logs")]
     HerokuLogs(#[configurable(derived)] heroku_logs::LogplexConfig),
@@ -192,6 +224,10 @@ pub enum Sources {
     #[cfg(feature = "sources-http_server")]
     HttpServer(#[configurable(derived)] http_server::SimpleHttpConfig),
 
+    /// IMAP.
+    #[cfg(feature = "sources-imap")]
+    Imap(#[configurable(derived)] imap::ImapConfig),
+
     /// Internal Logs.
     #[cfg(feature = "sources-internal_logs")]
     InternalLogs(#[configurable(derived)] internal_logs::InternalLogsConfig),
@@ -216,10 +252,18 @@ pub enum Sources {
     #[cfg(all(feature = "sources-logstash"))]
     Logstash(#[configurable(derived)] logstash::LogstashConfig),
 
+    /// MongoDB Change Stream.
+    #[cfg(feature = "sources-mongodb_change_stream")]
+    MongodbChangeStream(#[configurable(derived)] mongodb_change_stream::MongoDbChangeStreamConfig),
+
     /// MongoDB Metrics.
     #[cfg(feature = "sources-mongodb_metrics")]
     MongodbMetrics(#[configurable(derived)] mongodb_metrics::MongoDbMetricsConfig),
 
+    /// MySQL change data capture.
+    #[cfg(feature = "sources-mysql_cdc")]
+    MysqlCdc(#[configurable(derived)] mysql_cdc::MysqlCdcConfig),
+
     /// NATS.
     #[cfg(all(feature = "sources-nats"))]
     Nats(#[configurable(derived)] nats::NatsSourceConfig),
@@ -228,10 +272,18 @@ pub enum Sources {
     #[cfg(feature = "sources-nginx_metrics")]
     NginxMetrics(#[configurable(derived)] nginx_metrics::NginxMetricsConfig),
 
+    /// Okta System Log.
+    #[cfg(feature = "sources-okta_system_log")]
+    OktaSystemLog(#[configurable(derived)] okta_system_log::OktaSystemLogConfig),
+
     /// OpenTelemetry.
     #[cfg(feature = "sources-opentelemetry")]
     Opentelemetry(#[configurable(derived)] opentelemetry::OpentelemetryConfig),
 
+    /// PostgreSQL change data capture.
+    #[cfg(feature = "sources-postgres_cdc")]
+    PostgresCdc(#[configurable(derived)] postgres_cdc::PostgresCdcConfig),
+
     /// PostgreSQL Metrics.
     #[cfg(feature = "sources-postgresql_metrics")]
     PostgresqlMetrics(#[configurable(derived)] postgresql_metrics::PostgresqlMetricsConfig),
@@ -248,6 +300,10 @@ pub enum Sources {
     #[cfg(feature = "sources-redis")]
     Redis(#[configurable(derived)] redis::RedisSourceConfig),
 
+    /// SFTP.
+    #[cfg(feature = "sources-sftp")]
+    Sftp(#[configurable(derived)] sftp::SftpConfig),
+
     /// Test (backpressure).
     #[cfg(test)]
     TestBackpressure(
@@ -299,6 +355,10 @@ pub enum Sources {
     /// Vector.
     #[cfg(feature = "sources-vector")]
     Vector(#[configurable(derived)] vector::VectorConfig),
+
+    /// Windows Event Log.
+    #[cfg(all(windows, feature = "sources-windows_event_log"))]
+    WindowsEventLog(#[configurable(derived)] windows_event_log::WindowsEventLogConfig),
 }
 
 // We can't use `enum_dispatch` here because it doesn't support associated constants.
@@ -319,6 +379,8 @@ impl NamedComponent for Sources {
             Self::AwsS3(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_sqs")]
             Self::AwsSqs(config) => config.get_component_name(),
+            #[cfg(feature = "sources-cloudflare_logs")]
+            Self::CloudflareLogs(config) => config.get_component_name(),
             #[cfg(feature = "sources-datadog_agent")]
             Self::DatadogAgent(config) => config.get_component_name(),
             #[cfg(feature = "sources-demo_logs")]
@@ -339,6 +401,10 @@ impl NamedComponent for Sources {
             Self::Fluent(config) => config.get_component_name(),
             #[cfg(feature = "sources-gcp_pubsub")]
             Self::GcpPubsub(config) => config.get_component_name(),
+            #[cfg(feature = "sources-github_audit")]
+            Self::GithubAudit(config) => config.get_component_name(),
+            #[cfg(feature = "sources-grpc_server")]
+            Self::GrpcServer(config) => config.get_component_name(),
             #[cfg(feature = "sources-heroku_logs")]
             Self::HerokuLogs(config) => config.get_component_name(),
             #[cfg(feature = "sources-host_metrics")]
@@ -349,6 +415,8 @@ impl NamedComponent for Sources {
             Self::HttpClient(config) => config.get_component_name(),
             #[cfg(feature = "sources-http_server")]
             Self::HttpServer(config) => config.get_component_name(),
+            #[cfg(feature = "sources-imap")]
+            Self::Imap(config) => config.get_component_name(),
             #[cfg(feature = "sources-internal_logs")]
             Self::InternalLogs(config) => config.get_component_name(),
             #[cfg(feature = "sources-internal_metrics")]
@@ -361,14 +429,22 @@ impl NamedComponent for Sources {
             Self::KubernetesLogs(config) => config.get_component_name(),
             #[cfg(all(feature = "sources-logstash"))]
             Self::Logstash(config) => config.get_component_name(),
+            #[cfg(feature = "sources-mongodb_change_stream")]
+            Self::MongodbChangeStream(config) => config.get_component_name(),
             #[cfg(feature = "sources-mongodb_metrics")]
             Self::MongodbMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sources-mysql_cdc")]
+            Self::MysqlCdc(config) => config.get_component_name(),
             #[cfg(all(feature = "sources-nats"))]
             Self::Nats(config) => config.get_component_name(),
             #[cfg(feature = "sources-nginx_metrics")]
             Self::NginxMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sources-okta_system_log")]
+            Self::OktaSystemLog(config) => config.get_component_name(),
             #[cfg(feature = "sources-opentelemetry")]
             Self::Opentelemetry(config) => config.get_component_name(),
+            #[cfg(feature = "sources-postgres_cdc")]
+            Self::PostgresCdc(config) => config.get_component_name(),
             #[cfg(feature = "sources-postgresql_metrics")]
             Self::PostgresqlMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-prometheus")]
@@ -377,6 +453,8 @@ impl NamedComponent for Sources {
             Self::PrometheusRemoteWrite(config) => config.get_component_name(),
             #[cfg(feature = "sources-redis")]
             Self::Redis(config) => config.get_component_name(),
+            #[cfg(feature = "sources-sftp")]
+            Self::Sftp(config) => config.get_component_name(),
             #[cfg(test)]
             Self::TestBackpressure(config) => config.get_component_name(),
             #[cfg(test)]
@@ -401,6 +479,8 @@ impl NamedComponent for Sources {
             Self::UnitTestStream(config) => config.get_component_name(),
             #[cfg(feature = "sources-vector")]
             Self::Vector(config) => config.get_component_name(),
+            #[cfg(all(windows, feature = "sources-windows_event_log"))]
+            Self::WindowsEventLog(config) => config.get_component_name(),
         }
     }
 }
@@ -0,0 +1,82 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use mongodb::{bson, change_stream::event::ResumeToken};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+const CHECKPOINT_FILENAME: &str = "resume_token.bson";
+const TMP_CHECKPOINT_FILENAME: &str = "resume_token.new.bson";
+
+#[derive(Deserialize, Serialize)]
+struct Checkpoint {
+    resume_token: ResumeToken,
+}
+
+/// The resume token of the last change processed, persisted as BSON in the data directory.
+pub struct Checkpointer {
+    path: PathBuf,
+    tmp_path: PathBuf,
+}
+
+impl Checkpointer {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(CHECKPOINT_FILENAME),
+            tmp_path: data_dir.join(TMP_CHECKPOINT_FILENAME),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the resume token previously persisted, if any.
+    pub async fn load(&self) -> io::Result<Option<ResumeToken>> {
+        match fs::read(&self.path).await {
+            Ok(contents) => bson::from_slice::<Checkpoint>(&contents)
+                .map(|checkpoint| Some(checkpoint.resume_token))
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Persists `resume_token`.
+    ///
+    /// The token is written to a temporary file first, so a crash never leaves a partially
+    /// written file behind.
+    pub async fn set(&self, resume_token: ResumeToken) -> io::Result<()> {
+        let contents = bson::to_vec(&Checkpoint { resume_token })
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(&self.tmp_path, contents).await?;
+        fs::rename(&self.tmp_path, &self.path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::doc;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn persists_resume_token() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let token = doc! { "_data": "8263A1B2C3000000012B022C0100296E5A1004" };
+
+        let checkpointer = Checkpointer::new(data_dir.path());
+        assert!(checkpointer.load().await.unwrap().is_none());
+
+        checkpointer
+            .set(bson::from_document(token.clone()).unwrap())
+            .await
+            .unwrap();
+
+        let checkpointer = Checkpointer::new(data_dir.path());
+        let loaded = checkpointer.load().await.unwrap().unwrap();
+        assert_eq!(bson::to_bson(&loaded).unwrap(), bson::Bson::Document(token));
+    }
+}
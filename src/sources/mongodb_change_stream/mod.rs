@@ -0,0 +1,681 @@
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc, time::Duration};
+
+use chrono::{TimeZone, Utc};
+use futures::StreamExt;
+use lookup::{lookup_v2::parse_value_path, owned_value_path, path};
+use mongodb::{
+    bson::{self, Bson, Document},
+    change_stream::{
+        event::{ChangeStreamEvent, ResumeToken},
+        ChangeStream,
+    },
+    error::Error as MongoError,
+    options::{ChangeStreamOptions, ClientOptions, FullDocumentBeforeChangeType, FullDocumentType},
+    Client,
+};
+use ordered_float::NotNan;
+use snafu::{ResultExt, Snafu};
+use value::{kind::Collection, Kind};
+use vector_common::{
+    finalizer::OrderedFinalizer,
+    internal_event::{CountByteSize, InternalEventHandle as _, Registered},
+};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext,
+    },
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent, Value},
+    internal_events::{
+        EventsReceived, MongoDbChangeStreamCheckpointError, MongoDbChangeStreamError,
+        StreamClosedError,
+    },
+    serde::bool_or_struct,
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod checkpoint;
+
+use self::checkpoint::Checkpointer;
+
+const BACKOFF_DURATION: Duration = Duration::from_secs(5);
+
+/// The maximum number of changes sent at once, when many are available.
+const MAX_BATCH_SIZE: usize = 100;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid endpoint: {}", source))]
+    InvalidEndpoint { source: MongoError },
+    #[snafu(display("invalid client options: {}", source))]
+    InvalidClientOptions { source: MongoError },
+    #[snafu(display("`collection` requires `database` to be set"))]
+    CollectionWithoutDatabase,
+}
+
+/// What the change events include of the document after an update.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FullDocument {
+    /// Only inserts and replaces include the document.
+    #[default]
+    Default,
+
+    /// Updates include the current version of the document, looked up when the change is read.
+    ///
+    /// The document may include later changes, or be missing if it was deleted since.
+    UpdateLookup,
+
+    /// Updates include the document as it was right after the change, when available.
+    ///
+    /// This requires MongoDB 6.0 and `changeStreamPreAndPostImages` to be enabled on the collections.
+    WhenAvailable,
+
+    /// Like `when_available`, but reading fails when the document isn't available.
+    Required,
+}
+
+/// What the change events include of the document before an update, replace, or delete.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FullDocumentBeforeChange {
+    /// The document before the change isn't included.
+    #[default]
+    Off,
+
+    /// The document before the change is included, when available.
+    ///
+    /// This requires MongoDB 6.0 and `changeStreamPreAndPostImages` to be enabled on the collections.
+    WhenAvailable,
+
+    /// Like `when_available`, but reading fails when the document isn't available.
+    Required,
+}
+
+/// Configuration for the `mongodb_change_stream` source.
+#[configurable_component(source("mongodb_change_stream"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MongoDbChangeStreamConfig {
+    /// The MongoDB deployment to watch, in the [Connection String URI
+    /// format](https://www.mongodb.com/docs/manual/reference/connection-string/).
+    ///
+    /// Change streams are only available on replica sets and sharded clusters.
+    #[configurable(metadata(docs::examples = "mongodb://localhost:27017/?replicaSet=rs0"))]
+    endpoint: String,
+
+    /// The database to watch.
+    ///
+    /// By default, all databases of the deployment are watched.
+    #[configurable(metadata(docs::examples = "app"))]
+    database: Option<String>,
+
+    /// The collection of `database` to watch.
+    ///
+    /// By default, all collections of the database are watched.
+    #[configurable(metadata(docs::examples = "users"))]
+    collection: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    full_document: FullDocument,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    full_document_before_change: FullDocumentBeforeChange,
+
+    /// The directory used to persist the resume token of the last change processed.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    #[serde(default)]
+    data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+impl GenerateConfig for MongoDbChangeStreamConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "mongodb://localhost:27017/?replicaSet=rs0""#).unwrap()
+    }
+}
+
+impl MongoDbChangeStreamConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        let document = Kind::object(Collection::any()).or_undefined();
+
+        Definition::new_with_default_metadata(Kind::object(Collection::empty()), [log_namespace])
+            .with_event_field(&owned_value_path!("operation_type"), Kind::bytes(), None)
+            .with_event_field(
+                &owned_value_path!("database"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("collection"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(&owned_value_path!("document_key"), document.clone(), None)
+            .with_event_field(&owned_value_path!("full_document"), document.clone(), None)
+            .with_event_field(
+                &owned_value_path!("full_document_before_change"),
+                document.clone(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("update_description"),
+                document.clone(),
+                None,
+            )
+            .with_event_field(&owned_value_path!("to"), document, None)
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("resume_token"))),
+                &owned_value_path!("resume_token"),
+                Kind::any(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                parse_value_path(log_schema().timestamp_key())
+                    .ok()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp().or_undefined(),
+                Some("timestamp"),
+            )
+            .with_standard_vector_source_metadata()
+    }
+
+    fn change_stream_options(&self, resume_token: Option<ResumeToken>) -> ChangeStreamOptions {
+        let full_document = match self.full_document {
+            FullDocument::Default => None,
+            FullDocument::UpdateLookup => Some(FullDocumentType::UpdateLookup),
+            FullDocument::WhenAvailable => Some(FullDocumentType::WhenAvailable),
+            FullDocument::Required => Some(FullDocumentType::Required),
+        };
+        let full_document_before_change = match self.full_document_before_change {
+            FullDocumentBeforeChange::Off => None,
+            FullDocumentBeforeChange::WhenAvailable => {
+                Some(FullDocumentBeforeChangeType::WhenAvailable)
+            }
+            FullDocumentBeforeChange::Required => Some(FullDocumentBeforeChangeType::Required),
+        };
+
+        // Starting after the token, rather than resuming after it, also works once the stream
+        // has been invalidated, such as when the watched collection is dropped.
+        ChangeStreamOptions::builder()
+            .full_document(full_document)
+            .full_document_before_change(full_document_before_change)
+            .start_after(resume_token)
+            .build()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for MongoDbChangeStreamConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.collection.is_some() && self.database.is_none() {
+            return Err(BuildError::CollectionWithoutDatabase.into());
+        }
+        let client_options = ClientOptions::parse(&self.endpoint)
+            .await
+            .context(InvalidEndpointSnafu)?;
+        let client = Client::with_options(client_options).context(InvalidClientOptionsSnafu)?;
+
+        let data_dir = cx
+            .globals
+            // source are only global, name can be used for subdir
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+        let checkpointer = Arc::new(Checkpointer::new(&data_dir));
+        let watcher = Watcher {
+            config: self.clone(),
+            client,
+            resume_token: None,
+            finalizer: Finalizer::new(acknowledgements, checkpointer.clone(), cx.shutdown.clone()),
+            checkpointer,
+            log_namespace: cx.log_namespace(self.log_namespace),
+            events_received: register!(EventsReceived),
+            out: cx.out,
+            shutdown: cx.shutdown,
+        };
+        Ok(Box::pin(watcher.run()))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+enum Finalizer {
+    Sync(Arc<Checkpointer>),
+    Async(OrderedFinalizer<ResumeToken>),
+}
+
+impl Finalizer {
+    fn new(
+        acknowledgements: bool,
+        checkpointer: Arc<Checkpointer>,
+        shutdown: ShutdownSignal,
+    ) -> Self {
+        if acknowledgements {
+            let (finalizer, mut ack_stream) = OrderedFinalizer::new(shutdown);
+            tokio::spawn(async move {
+                while let Some((status, resume_token)) = ack_stream.next().await {
+                    if status == BatchStatus::Delivered {
+                        set_checkpoint(&checkpointer, resume_token).await;
+                    }
+                }
+            });
+            Self::Async(finalizer)
+        } else {
+            Self::Sync(checkpointer)
+        }
+    }
+
+    async fn finalize(&self, resume_token: ResumeToken, receiver: Option<BatchStatusReceiver>) {
+        match (self, receiver) {
+            (Self::Sync(checkpointer), None) => set_checkpoint(checkpointer, resume_token).await,
+            (Self::Async(finalizer), Some(receiver)) => finalizer.add(resume_token, receiver),
+            _ => {
+                unreachable!(
+                    "Cannot have async finalization without a receiver in mongodb_change_stream source"
+                )
+            }
+        }
+    }
+}
+
+async fn set_checkpoint(checkpointer: &Checkpointer, resume_token: ResumeToken) {
+    if let Err(error) = checkpointer.set(resume_token).await {
+        emit!(MongoDbChangeStreamCheckpointError {
+            path: checkpointer.path(),
+            error,
+        });
+    }
+}
+
+struct Watcher {
+    config: MongoDbChangeStreamConfig,
+    client: Client,
+    /// The resume token of the last change read, to resume from after reconnecting.
+    resume_token: Option<ResumeToken>,
+    finalizer: Finalizer,
+    checkpointer: Arc<Checkpointer>,
+    log_namespace: LogNamespace,
+    events_received: Registered<EventsReceived>,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+}
+
+impl Watcher {
+    async fn run(mut self) -> Result<(), ()> {
+        self.resume_token = self.checkpointer.load().await.map_err(|error| {
+            emit!(MongoDbChangeStreamCheckpointError {
+                path: self.checkpointer.path(),
+                error,
+            })
+        })?;
+
+        loop {
+            let stream = match self.watch().await {
+                Ok(stream) => stream,
+                Err(error) => {
+                    emit!(MongoDbChangeStreamError { error });
+                    if !self.sleep(BACKOFF_DURATION).await {
+                        break;
+                    }
+                    continue;
+                }
+            };
+
+            match self.read(stream).await {
+                Ok(true) => {
+                    // The stream was invalidated, so watch again from the last change.
+                }
+                Ok(false) => break,
+                Err(error) => {
+                    emit!(MongoDbChangeStreamError { error });
+                    if !self.sleep(BACKOFF_DURATION).await {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn watch(&self) -> Result<ChangeStream<ChangeStreamEvent<Document>>, MongoError> {
+        let options = self.config.change_stream_options(self.resume_token.clone());
+        match (&self.config.database, &self.config.collection) {
+            (Some(database), Some(collection)) => {
+                self.client
+                    .database(database)
+                    .collection::<Document>(collection)
+                    .watch(None, options)
+                    .await
+            }
+            (Some(database), None) => self.client.database(database).watch(None, options).await,
+            _ => self.client.watch(None, options).await,
+        }
+    }
+
+    /// Reads changes until the stream ends, returning `false` if the source is stopping.
+    async fn read(
+        &mut self,
+        stream: ChangeStream<ChangeStreamEvent<Document>>,
+    ) -> Result<bool, MongoError> {
+        let mut chunks = stream.ready_chunks(MAX_BATCH_SIZE);
+        loop {
+            let chunk = tokio::select! {
+                chunk = chunks.next() => match chunk {
+                    Some(chunk) => chunk,
+                    None => return Ok(true),
+                },
+                _ = &mut self.shutdown => return Ok(false),
+            };
+
+            let mut changes = Vec::with_capacity(chunk.len());
+            let mut error = None;
+            for change in chunk {
+                match change {
+                    Ok(change) => changes.push(change),
+                    Err(change_error) => {
+                        error = Some(change_error);
+                        break;
+                    }
+                }
+            }
+
+            if !self.send(changes).await {
+                return Ok(false);
+            }
+            if let Some(error) = error {
+                return Err(error);
+            }
+        }
+    }
+
+    /// Sends a batch of changes, returning `false` if the output is closed.
+    async fn send(&mut self, changes: Vec<ChangeStreamEvent<Document>>) -> bool {
+        let resume_token = match changes.last() {
+            Some(change) => change.id.clone(),
+            None => return true,
+        };
+
+        let (batch, receiver) =
+            BatchNotifier::maybe_new_with_receiver(matches!(self.finalizer, Finalizer::Async(_)));
+        let events = changes
+            .into_iter()
+            .map(|change| {
+                let log = change_event(change, self.log_namespace);
+                Event::from(log).with_batch_notifier_option(&batch)
+            })
+            .collect::<Vec<_>>();
+        drop(batch); // Drop last reference to batch acknowledgement finalizer
+
+        let count = events.len();
+        self.events_received.emit(CountByteSize(
+            count,
+            events.estimated_json_encoded_size_of(),
+        ));
+        if let Err(error) = self.out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            return false;
+        }
+
+        self.resume_token = Some(resume_token.clone());
+        self.finalizer.finalize(resume_token, receiver).await;
+        true
+    }
+
+    /// Sleeps for `duration`, returning `false` if the source is shutting down.
+    async fn sleep(&mut self, duration: Duration) -> bool {
+        tokio::select! {
+            _ = tokio::time::sleep(duration) => true,
+            _ = &mut self.shutdown => false,
+        }
+    }
+}
+
+fn change_event(change: ChangeStreamEvent<Document>, log_namespace: LogNamespace) -> LogEvent {
+    let mut data = BTreeMap::new();
+    let operation_type = match bson::to_bson(&change.operation_type) {
+        Ok(Bson::String(operation_type)) => operation_type,
+        _ => "unknown".to_string(),
+    };
+    data.insert("operation_type".to_string(), Value::from(operation_type));
+    if let Some(ns) = change.ns {
+        data.insert("database".to_string(), Value::from(ns.db));
+        if let Some(coll) = ns.coll {
+            data.insert("collection".to_string(), Value::from(coll));
+        }
+    }
+    if let Some(to) = change.to {
+        let mut target = BTreeMap::new();
+        target.insert("database".to_string(), Value::from(to.db));
+        if let Some(coll) = to.coll {
+            target.insert("collection".to_string(), Value::from(coll));
+        }
+        data.insert("to".to_string(), Value::from(target));
+    }
+    if let Some(document_key) = change.document_key {
+        data.insert("document_key".to_string(), document_value(document_key));
+    }
+    if let Some(update) = change.update_description {
+        let mut description = BTreeMap::new();
+        description.insert(
+            "updated_fields".to_string(),
+            document_value(update.updated_fields),
+        );
+        description.insert(
+            "removed_fields".to_string(),
+            Value::Array(update.removed_fields.into_iter().map(Value::from).collect()),
+        );
+        data.insert("update_description".to_string(), Value::from(description));
+    }
+    if let Some(document) = change.full_document {
+        data.insert("full_document".to_string(), document_value(document));
+    }
+    if let Some(document) = change.full_document_before_change {
+        data.insert(
+            "full_document_before_change".to_string(),
+            document_value(document),
+        );
+    }
+
+    let mut log = log_namespace.new_log_from_data(data);
+    let resume_token = bson::to_bson(&change.id).map_or(Value::Null, bson_value);
+    log_namespace.insert_source_metadata(
+        MongoDbChangeStreamConfig::NAME,
+        &mut log,
+        Some(LegacyKey::Overwrite(path!("resume_token"))),
+        path!("resume_token"),
+        resume_token,
+    );
+    if let Some(cluster_time) = change.cluster_time {
+        log_namespace.insert_source_metadata(
+            MongoDbChangeStreamConfig::NAME,
+            &mut log,
+            Some(LegacyKey::Overwrite(path!(log_schema().timestamp_key()))),
+            path!("timestamp"),
+            Utc.timestamp(i64::from(cluster_time.time), 0),
+        );
+    }
+    log_namespace.insert_standard_vector_source_metadata(
+        &mut log,
+        MongoDbChangeStreamConfig::NAME,
+        Utc::now(),
+    );
+    log
+}
+
+fn document_value(document: Document) -> Value {
+    Value::from(
+        document
+            .into_iter()
+            .map(|(key, value)| (key, bson_value(value)))
+            .collect::<BTreeMap<_, _>>(),
+    )
+}
+
+/// Converts a BSON value, keeping the types without an equivalent in their extended JSON
+/// representation.
+fn bson_value(value: Bson) -> Value {
+    match value {
+        Bson::Null | Bson::Undefined => Value::Null,
+        Bson::Boolean(value) => Value::Boolean(value),
+        Bson::Int32(value) => Value::Integer(value.into()),
+        Bson::Int64(value) => Value::Integer(value),
+        Bson::Double(value) => NotNan::new(value).map_or(Value::Null, Value::Float),
+        Bson::String(value) => Value::from(value),
+        Bson::ObjectId(id) => Value::from(id.to_hex()),
+        Bson::DateTime(datetime) => Utc
+            .timestamp_millis_opt(datetime.timestamp_millis())
+            .single()
+            .map_or(Value::Null, Value::Timestamp),
+        Bson::Timestamp(timestamp) => Value::Timestamp(Utc.timestamp(i64::from(timestamp.time), 0)),
+        Bson::Binary(binary) => Value::Bytes(binary.bytes.into()),
+        Bson::Array(values) => Value::Array(values.into_iter().map(bson_value).collect()),
+        Bson::Document(document) => document_value(document),
+        other => Value::from(other.into_relaxed_extjson()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::{doc, oid::ObjectId, DateTime};
+
+    use super::*;
+
+    fn config(log_namespace: Option<bool>) -> MongoDbChangeStreamConfig {
+        MongoDbChangeStreamConfig {
+            log_namespace,
+            ..toml::from_str(r#"endpoint = "mongodb://localhost:27017/?replicaSet=rs0""#).unwrap()
+        }
+    }
+
+    fn change() -> ChangeStreamEvent<Document> {
+        bson::from_document(doc! {
+            "_id": { "_data": "8263A1B2C3000000012B022C0100296E5A1004" },
+            "operationType": "update",
+            "clusterTime": bson::Timestamp { time: 1_670_000_000, increment: 1 },
+            "ns": { "db": "app", "coll": "users" },
+            "documentKey": { "_id": 1 },
+            "updateDescription": {
+                "updatedFields": { "name": "bob" },
+                "removedFields": ["nickname"],
+            },
+            "fullDocument": { "_id": 1, "name": "bob" },
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MongoDbChangeStreamConfig>();
+    }
+
+    #[test]
+    fn parses_config_defaults() {
+        let config = config(None);
+
+        assert_eq!(config.database, None);
+        assert_eq!(config.full_document, FullDocument::Default);
+        assert_eq!(
+            config.full_document_before_change,
+            FullDocumentBeforeChange::Off
+        );
+    }
+
+    #[test]
+    fn converts_bson_values() {
+        let id = ObjectId::parse_str("63a1b2c3d4e5f60718293a4b").unwrap();
+        let value = document_value(doc! {
+            "id": id,
+            "count": 3_i32,
+            "ratio": 0.5,
+            "created_at": DateTime::from_millis(1_670_000_000_123),
+            "tags": ["a", "b"],
+        });
+
+        let object = value.as_object().unwrap();
+        assert_eq!(object["id"], Value::from("63a1b2c3d4e5f60718293a4b"));
+        assert_eq!(object["count"], Value::Integer(3));
+        assert_eq!(object["ratio"], Value::Float(NotNan::new(0.5).unwrap()));
+        assert_eq!(
+            object["created_at"],
+            Value::Timestamp(Utc.timestamp_millis(1_670_000_000_123))
+        );
+        assert_eq!(
+            object["tags"],
+            Value::Array(vec![Value::from("a"), Value::from("b")])
+        );
+    }
+
+    #[test]
+    fn converts_change() {
+        let log = change_event(change(), LogNamespace::Legacy);
+
+        assert_eq!(log["operation_type"], Value::from("update"));
+        assert_eq!(log["database"], Value::from("app"));
+        assert_eq!(log["collection"], Value::from("users"));
+        assert_eq!(log["document_key._id"], Value::Integer(1));
+        assert_eq!(
+            log["update_description.updated_fields.name"],
+            Value::from("bob")
+        );
+        assert_eq!(log["full_document.name"], Value::from("bob"));
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Value::Timestamp(Utc.timestamp(1_670_000_000, 0))
+        );
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let definition = config(Some(true)).outputs(LogNamespace::Vector)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+
+        definition.assert_valid_for_event(&change_event(change(), LogNamespace::Vector).into());
+    }
+
+    #[test]
+    fn output_schema_definition_legacy_namespace() {
+        let definition = config(None).outputs(LogNamespace::Legacy)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+
+        definition.assert_valid_for_event(&change_event(change(), LogNamespace::Legacy).into());
+    }
+}
@@ -0,0 +1,84 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+const CHECKPOINT_FILENAME: &str = "checkpoint.json";
+const TMP_CHECKPOINT_FILENAME: &str = "checkpoint.new.json";
+
+/// The position in the binary log following the last transaction processed.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Position {
+    pub file: String,
+    pub position: u64,
+    /// The transactions executed up to the position, when GTIDs are used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gtid_set: Option<String>,
+}
+
+/// The position persisted as JSON in the data directory.
+pub struct Checkpointer {
+    path: PathBuf,
+    tmp_path: PathBuf,
+}
+
+impl Checkpointer {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(CHECKPOINT_FILENAME),
+            tmp_path: data_dir.join(TMP_CHECKPOINT_FILENAME),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the position previously persisted, if any.
+    pub async fn load(&self) -> io::Result<Option<Position>> {
+        match fs::read(&self.path).await {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map(Some)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Persists `position`.
+    ///
+    /// The position is written to a temporary file first, so a crash never leaves a partially
+    /// written file behind.
+    pub async fn set(&self, position: &Position) -> io::Result<()> {
+        let contents = serde_json::to_vec(position)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(&self.tmp_path, contents).await?;
+        fs::rename(&self.tmp_path, &self.path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn persists_position() {
+        let data_dir = tempfile::tempdir().unwrap();
+
+        let checkpointer = Checkpointer::new(data_dir.path());
+        assert_eq!(checkpointer.load().await.unwrap(), None);
+
+        let position = Position {
+            file: "binlog.000042".into(),
+            position: 1337,
+            gtid_set: Some("3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5".into()),
+        };
+        checkpointer.set(&position).await.unwrap();
+
+        let checkpointer = Checkpointer::new(data_dir.path());
+        assert_eq!(checkpointer.load().await.unwrap(), Some(position));
+    }
+}
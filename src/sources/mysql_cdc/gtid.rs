@@ -0,0 +1,184 @@
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use mysql_async::{GnoInterval, Sid};
+use snafu::Snafu;
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq, Eq, Snafu)]
+pub enum ParseGtidSetError {
+    #[snafu(display("invalid source ID {:?}", source_id))]
+    InvalidSourceId { source_id: String },
+    #[snafu(display("invalid transaction interval {:?}", interval))]
+    InvalidInterval { interval: String },
+}
+
+/// A set of global transaction identifiers, such as found in `gtid_executed`.
+///
+/// The transactions of each source server are kept as sorted, non-overlapping, inclusive
+/// intervals of transaction numbers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GtidSet {
+    sources: BTreeMap<Uuid, Vec<(u64, u64)>>,
+}
+
+impl GtidSet {
+    /// Adds the transaction `gno` of the source server `sid`.
+    pub fn add(&mut self, sid: Uuid, gno: u64) {
+        self.add_interval(sid, gno, gno);
+    }
+
+    /// Adds the transactions `start` to `end` of the source server `sid`, merging the
+    /// intervals that overlap or are adjacent.
+    fn add_interval(&mut self, sid: Uuid, start: u64, end: u64) {
+        let intervals = self.sources.entry(sid).or_default();
+        intervals.push((start, end));
+        intervals.sort_unstable();
+
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(intervals.len());
+        for &(start, end) in intervals.iter() {
+            match merged.last_mut() {
+                Some((_, last_end)) if start <= last_end.saturating_add(1) => {
+                    *last_end = (*last_end).max(end);
+                }
+                _ => merged.push((start, end)),
+            }
+        }
+        *intervals = merged;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Converts the set to the identifiers sent to the server to start replicating from it.
+    pub fn to_sids(&self) -> Vec<Sid<'static>> {
+        self.sources
+            .iter()
+            .map(|(uuid, intervals)| {
+                intervals
+                    .iter()
+                    .fold(Sid::new(*uuid.as_bytes()), |sid, &(start, end)| {
+                        // The end of the intervals the server expects is exclusive.
+                        sid.with_interval(GnoInterval::new(start, end + 1))
+                    })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for GtidSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (uuid, intervals)) in self.sources.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", uuid)?;
+            for (start, end) in intervals {
+                if start == end {
+                    write!(f, ":{}", start)?;
+                } else {
+                    write!(f, ":{}-{}", start, end)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GtidSet {
+    type Err = ParseGtidSetError;
+
+    /// Parses a set formatted as by MySQL, such as `3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5:7`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = Self::default();
+        for source in s
+            .split(',')
+            .map(str::trim)
+            .filter(|source| !source.is_empty())
+        {
+            let mut parts = source.split(':');
+            let source_id = parts.next().unwrap_or_default();
+            let uuid =
+                Uuid::parse_str(source_id).map_err(|_| ParseGtidSetError::InvalidSourceId {
+                    source_id: source_id.to_string(),
+                })?;
+
+            for interval in parts {
+                let invalid = || ParseGtidSetError::InvalidInterval {
+                    interval: interval.to_string(),
+                };
+                let (start, end) = match interval.split_once('-') {
+                    Some((start, end)) => (
+                        start.parse::<u64>().map_err(|_| invalid())?,
+                        end.parse::<u64>().map_err(|_| invalid())?,
+                    ),
+                    None => {
+                        let gno = interval.parse::<u64>().map_err(|_| invalid())?;
+                        (gno, gno)
+                    }
+                };
+                if start == 0 || start > end {
+                    return Err(invalid());
+                }
+                set.add_interval(uuid, start, end);
+            }
+        }
+        Ok(set)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SID: &str = "3e11fa47-71ca-11e1-9e33-c80aa9429562";
+
+    #[test]
+    fn adds_transactions() {
+        let sid = Uuid::parse_str(SID).unwrap();
+        let mut set = GtidSet::default();
+        for gno in [1, 2, 3, 7, 5, 9] {
+            set.add(sid, gno);
+        }
+        assert_eq!(set.to_string(), format!("{}:1-3:5:7:9", SID));
+
+        set.add(sid, 6);
+        set.add(sid, 8);
+        assert_eq!(set.to_string(), format!("{}:1-3:5-9", SID));
+
+        set.add(sid, 4);
+        assert_eq!(set.to_string(), format!("{}:1-9", SID));
+    }
+
+    #[test]
+    fn parses_and_formats_sets() {
+        let executed = format!(
+            "{}:1-5:7,\n8a94f357-aab4-11df-86ab-c80aa9429562:1-12",
+            SID.to_uppercase()
+        );
+        let set = executed.parse::<GtidSet>().unwrap();
+        assert_eq!(
+            set.to_string(),
+            format!("{}:1-5:7,8a94f357-aab4-11df-86ab-c80aa9429562:1-12", SID)
+        );
+        assert_eq!(set.to_sids().len(), 2);
+
+        assert!("".parse::<GtidSet>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_sets() {
+        assert_eq!(
+            "nope:1-5".parse::<GtidSet>(),
+            Err(ParseGtidSetError::InvalidSourceId {
+                source_id: "nope".into()
+            })
+        );
+        assert_eq!(
+            format!("{}:5-1", SID).parse::<GtidSet>(),
+            Err(ParseGtidSetError::InvalidInterval {
+                interval: "5-1".into()
+            })
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+
+use mysql_async::{prelude::Queryable, Conn};
+
+const COLUMNS_QUERY: &str = "SELECT COLUMN_NAME FROM information_schema.COLUMNS \
+    WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? ORDER BY ORDINAL_POSITION";
+
+/// The column names of the tables whose rows were read.
+///
+/// Row events only describe the types of the columns, so their names are looked up once per
+/// table, until the schema changes.
+#[derive(Debug, Default)]
+pub struct SchemaCache {
+    tables: HashMap<(String, String), Vec<String>>,
+}
+
+impl SchemaCache {
+    /// Returns the column names of `database.table`, in order.
+    pub async fn columns(
+        &mut self,
+        conn: &mut Conn,
+        database: &str,
+        table: &str,
+    ) -> mysql_async::Result<&[String]> {
+        let key = (database.to_string(), table.to_string());
+        if !self.tables.contains_key(&key) {
+            let columns: Vec<String> = conn.exec(COLUMNS_QUERY, (database, table)).await?;
+            self.tables.insert(key.clone(), columns);
+        }
+        Ok(&self.tables[&key])
+    }
+
+    /// Forgets all column names, to be looked up again after a schema change.
+    pub fn clear(&mut self) {
+        self.tables.clear();
+    }
+}
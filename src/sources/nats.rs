@@ -1,21 +1,29 @@
+use std::{io, sync::Arc, time::Duration};
+
 use chrono::Utc;
 use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
 use futures::{pin_mut, stream, Stream, StreamExt};
+use nats::jetstream::{AckKind, BatchOptions, PullSubscribeOptions, PullSubscription};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
-use vector_common::internal_event::{
-    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+use tracing_futures::Instrument;
+use vector_common::{
+    finalizer::UnorderedFinalizer,
+    internal_event::{
+        ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+        Registered,
+    },
 };
 use vector_config::{configurable_component, NamedComponent};
 use vector_core::{config::LogNamespace, EstimatedJsonEncodedSizeOf};
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
-    config::{GenerateConfig, Output, SourceConfig, SourceContext},
-    event::Event,
-    internal_events::StreamClosedError,
+    config::{GenerateConfig, Output, SourceAcknowledgementsConfig, SourceConfig, SourceContext},
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{NatsJetStreamAckError, NatsJetStreamFetchError, StreamClosedError},
     nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
     tls::TlsEnableableConfig,
     SourceSender,
@@ -29,6 +37,8 @@ enum BuildError {
     Connect { source: std::io::Error },
     #[snafu(display("NATS Subscribe Error: {}", source))]
     Subscribe { source: std::io::Error },
+    #[snafu(display("NATS Config Error: `queue` can't be used with `jetstream`"))]
+    QueueWithJetStream,
 }
 
 /// Configuration for the `nats` source.
@@ -51,8 +61,13 @@ pub struct NatsSourceConfig {
     subject: String,
 
     /// NATS Queue Group to join.
+    ///
+    /// Not supported with `jetstream`, where messages are shared by the clients reading with the same consumer.
     queue: Option<String>,
 
+    #[configurable(derived)]
+    jetstream: Option<JetStreamConfig>,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -73,6 +88,41 @@ pub struct NatsSourceConfig {
     #[serde(default = "default_decoding")]
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+}
+
+/// Configuration for reading messages with a JetStream consumer.
+///
+/// When set, messages are read from the JetStream stream bound to `subject`, with a durable pull
+/// consumer. Each message is acknowledged once its events have been processed, or negatively
+/// acknowledged to be redelivered if they couldn't be delivered.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JetStreamConfig {
+    /// The name of the durable consumer to read messages with.
+    ///
+    /// The consumer is created if it doesn't exist yet.
+    durable_name: String,
+
+    /// The maximum number of messages to fetch at once.
+    #[serde(default = "default_batch_size")]
+    batch_size: usize,
+
+    /// The maximum amount of time, in milliseconds, to wait for a batch of messages to be fetched.
+    #[serde(default = "default_fetch_timeout_ms")]
+    fetch_timeout_ms: u64,
+}
+
+const fn default_batch_size() -> usize {
+    100
+}
+
+const fn default_fetch_timeout_ms() -> u64 {
+    5000
 }
 
 impl GenerateConfig for NatsSourceConfig {
@@ -91,10 +141,25 @@ impl GenerateConfig for NatsSourceConfig {
 impl SourceConfig for NatsSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
-        let (connection, subscription) = create_subscription(self).await?;
         let decoder =
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
 
+        if let Some(jetstream) = &self.jetstream {
+            let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+            let subscription = create_pull_subscription(self, jetstream).await?;
+
+            return Ok(Box::pin(jetstream_source(
+                subscription,
+                jetstream.clone(),
+                decoder,
+                log_namespace,
+                acknowledgements,
+                cx.shutdown,
+                cx.out,
+            )));
+        }
+
+        let (connection, subscription) = create_subscription(self).await?;
         Ok(Box::pin(nats_source(
             connection,
             subscription,
@@ -116,7 +181,7 @@ impl SourceConfig for NatsSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        self.jetstream.is_some()
     }
 }
 
@@ -158,42 +223,193 @@ async fn nats_source(
     let bytes_received = register!(BytesReceived::from(Protocol::TCP));
     while let Some(msg) = stream.next().await {
         bytes_received.emit(ByteSize(msg.data.len()));
-        let mut stream = FramedRead::new(msg.data.as_ref(), decoder.clone());
-        while let Some(next) = stream.next().await {
-            match next {
-                Ok((events, _byte_size)) => {
-                    let count = events.len();
-                    let byte_size = events.estimated_json_encoded_size_of();
-                    events_received.emit(CountByteSize(count, byte_size));
-
-                    let now = Utc::now();
-
-                    let events = events.into_iter().map(|mut event| {
-                        if let Event::Log(ref mut log) = event {
-                            log_namespace.insert_standard_vector_source_metadata(
-                                log,
-                                NatsSourceConfig::NAME,
-                                now,
-                            );
-                        }
-                        event
-                    });
-
-                    out.send_batch(events).await.map_err(|error| {
-                        emit!(StreamClosedError { error, count });
-                    })?;
+        let events = decode_message(&msg.data, &decoder, log_namespace, &events_received).await;
+        if events.is_empty() {
+            continue;
+        }
+
+        let count = events.len();
+        out.send_batch(events).await.map_err(|error| {
+            emit!(StreamClosedError { error, count });
+        })?;
+    }
+    Ok(())
+}
+
+type Finalizer = UnorderedFinalizer<nats::Message>;
+
+async fn jetstream_source(
+    subscription: PullSubscription,
+    config: JetStreamConfig,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let subscription = Arc::new(subscription);
+    let fetch_timeout = Duration::from_millis(config.fetch_timeout_ms);
+
+    let (finalizer, mut ack_stream) = Finalizer::maybe_new(acknowledgements, shutdown.clone());
+    if finalizer.is_some() {
+        tokio::spawn(
+            async move {
+                while let Some((status, message)) = ack_stream.next().await {
+                    ack_message(message, status).await;
                 }
-                Err(error) => {
-                    // Error is logged by `crate::codecs`, no further
-                    // handling is needed here.
-                    if !error.can_continue() {
-                        break;
+            }
+            .in_current_span(),
+        );
+    }
+
+    let events_received = register!(EventsReceived);
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+    loop {
+        let messages = tokio::select! {
+            messages = fetch_messages(Arc::clone(&subscription), config.batch_size, fetch_timeout) => messages,
+            _ = &mut shutdown => break,
+        };
+        let messages = match messages {
+            Ok(messages) => messages,
+            Err(error) => {
+                emit!(NatsJetStreamFetchError { error });
+                continue;
+            }
+        };
+
+        for message in messages {
+            bytes_received.emit(ByteSize(message.data.len()));
+
+            let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(finalizer.is_some());
+            let events = decode_message(&message.data, &decoder, log_namespace, &events_received)
+                .await
+                .into_iter()
+                .map(|event| event.with_batch_notifier_option(&batch))
+                .collect::<Vec<_>>();
+            drop(batch); // Drop last reference to batch acknowledgement finalizer
+
+            let count = events.len();
+            out.send_batch(events).await.map_err(|error| {
+                emit!(StreamClosedError { error, count });
+            })?;
+
+            match receiver {
+                Some(receiver) => finalizer
+                    .as_ref()
+                    .expect("Finalizer must exist for the batch receiver to be created")
+                    .add(message, receiver),
+                None => ack_message(message, BatchStatus::Delivered).await,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Decodes the events of a message, adding the standard source metadata to them.
+async fn decode_message(
+    data: &[u8],
+    decoder: &Decoder,
+    log_namespace: LogNamespace,
+    events_received: &Registered<EventsReceived>,
+) -> Vec<Event> {
+    let mut decoded = Vec::new();
+    let mut stream = FramedRead::new(data, decoder.clone());
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((events, _byte_size)) => {
+                let count = events.len();
+                let byte_size = events.estimated_json_encoded_size_of();
+                events_received.emit(CountByteSize(count, byte_size));
+
+                let now = Utc::now();
+
+                decoded.extend(events.into_iter().map(|mut event| {
+                    if let Event::Log(ref mut log) = event {
+                        log_namespace.insert_standard_vector_source_metadata(
+                            log,
+                            NatsSourceConfig::NAME,
+                            now,
+                        );
                     }
+                    event
+                }));
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
                 }
             }
         }
     }
-    Ok(())
+    decoded
+}
+
+/// Fetches the next batch of messages, waiting up to `timeout` for them.
+///
+/// The JetStream API of the client is blocking, so it is run on a blocking thread.
+async fn fetch_messages(
+    subscription: Arc<PullSubscription>,
+    batch_size: usize,
+    timeout: Duration,
+) -> io::Result<Vec<nats::Message>> {
+    tokio::task::spawn_blocking(move || -> io::Result<Vec<nats::Message>> {
+        let batch = subscription.fetch(BatchOptions {
+            batch: batch_size,
+            expires: Some(timeout.as_nanos() as usize),
+            no_wait: false,
+        })?;
+        Ok(batch.collect())
+    })
+    .await
+    .unwrap_or_else(|error| Err(io::Error::new(io::ErrorKind::Other, error)))
+}
+
+/// Acknowledges a message according to the delivery status of its events.
+///
+/// Messages whose events errored are redelivered, while the ones whose events were rejected
+/// are terminated, as they would be rejected again.
+async fn ack_message(message: nats::Message, status: BatchStatus) {
+    let kind = match status {
+        BatchStatus::Delivered => AckKind::Ack,
+        BatchStatus::Errored => AckKind::Nak,
+        BatchStatus::Rejected => AckKind::Term,
+    };
+
+    let result = tokio::task::spawn_blocking(move || message.ack_kind(kind))
+        .await
+        .unwrap_or_else(|error| Err(io::Error::new(io::ErrorKind::Other, error)));
+    if let Err(error) = result {
+        emit!(NatsJetStreamAckError { error });
+    }
+}
+
+async fn create_pull_subscription(
+    config: &NatsSourceConfig,
+    jetstream: &JetStreamConfig,
+) -> crate::Result<PullSubscription> {
+    if config.queue.is_some() {
+        return Err(BuildError::QueueWithJetStream.into());
+    }
+
+    let options: nats::Options =
+        from_tls_auth_config(&config.connection_name, &config.auth, &config.tls)
+            .context(ConfigSnafu)?;
+    let url = config.url.clone();
+    let subject = config.subject.clone();
+    let subscribe_options =
+        PullSubscribeOptions::new().durable_name(jetstream.durable_name.clone());
+
+    let subscription = tokio::task::spawn_blocking(move || -> Result<_, BuildError> {
+        let connection = options.connect(&url).context(ConnectSnafu)?;
+        nats::jetstream::new(connection)
+            .pull_subscribe_with_options(&subject, &subscribe_options)
+            .context(SubscribeSnafu)
+    })
+    .await??;
+
+    Ok(subscription)
 }
 
 async fn create_subscription(
@@ -226,6 +442,33 @@ mod tests {
         crate::test_util::test_generate_config::<NatsSourceConfig>();
     }
 
+    #[tokio::test]
+    async fn jetstream_rejects_queue() {
+        let config: NatsSourceConfig = toml::from_str(
+            r#"
+            connection_name = "vector"
+            subject = "from.vector"
+            url = "nats://127.0.0.1:4222"
+            queue = "vector"
+            jetstream.durable_name = "vector"
+            "#,
+        )
+        .unwrap();
+
+        let jetstream = config.jetstream.as_ref().unwrap();
+        assert_eq!(jetstream.batch_size, 100);
+        assert_eq!(jetstream.fetch_timeout_ms, 5000);
+        assert!(config.can_acknowledge());
+
+        let error = create_pull_subscription(&config, jetstream)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<BuildError>(),
+            Some(BuildError::QueueWithJetStream)
+        ));
+    }
+
     #[test]
     fn output_schema_definition_vector_namespace() {
         let config = NatsSourceConfig {
@@ -340,6 +583,8 @@ mod integration_tests {
             tls: None,
             auth: None,
             log_namespace: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -371,6 +616,8 @@ mod integration_tests {
                 },
             }),
             log_namespace: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -402,6 +649,8 @@ mod integration_tests {
                 },
             }),
             log_namespace: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -432,6 +681,8 @@ mod integration_tests {
                 },
             }),
             log_namespace: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -462,6 +713,8 @@ mod integration_tests {
                 },
             }),
             log_namespace: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -493,6 +746,8 @@ mod integration_tests {
                 },
             }),
             log_namespace: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -524,6 +779,8 @@ mod integration_tests {
                 },
             }),
             log_namespace: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -556,6 +813,8 @@ mod integration_tests {
             }),
             auth: None,
             log_namespace: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -582,6 +841,8 @@ mod integration_tests {
             tls: None,
             auth: None,
             log_namespace: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -616,6 +877,8 @@ mod integration_tests {
             }),
             auth: None,
             log_namespace: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -648,6 +911,8 @@ mod integration_tests {
             }),
             auth: None,
             log_namespace: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -684,6 +949,8 @@ mod integration_tests {
                 },
             }),
             log_namespace: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -720,6 +987,8 @@ mod integration_tests {
                 },
             }),
             log_namespace: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -14,7 +14,7 @@ use vector_core::{metric_tags, EstimatedJsonEncodedSizeOf};
 use crate::{
     config::{DataType, Output, SourceConfig, SourceContext},
     event::metric::{Metric, MetricKind, MetricTags, MetricValue},
-    http::{Auth, HttpClient},
+    http::{Auth, HttpClient, MaybeAuth},
     internal_events::{
         CollectionCompleted, EndpointBytesReceived, NginxMetricsEventsReceived,
         NginxMetricsRequestError, NginxMetricsStubStatusParseError, StreamClosedError,
@@ -97,6 +97,8 @@ impl_generate_config_from_default!(NginxMetricsConfig);
 #[async_trait::async_trait]
 impl SourceConfig for NginxMetricsConfig {
     async fn build(&self, mut cx: SourceContext) -> crate::Result<super::Source> {
+        self.auth.ensure_static()?;
+
         let tls = TlsSettings::from_options(&self.tls)?;
         let http_client = HttpClient::new(tls, &cx.proxy)?;
 
@@ -0,0 +1,675 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use http::{header, response::Parts, HeaderMap, HeaderValue, Request, StatusCode};
+use hyper::Body;
+use lookup::{lookup_v2::parse_value_path, owned_value_path, path};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio::fs;
+use tokio_stream::wrappers::IntervalStream;
+use value::{kind::Collection, Kind};
+use vector_common::{
+    internal_event::{CountByteSize, InternalEventHandle as _, Registered},
+    sensitive_string::SensitiveString,
+};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext,
+    },
+    event::{BatchNotifier, BatchStatus, Event},
+    http::{HttpClient, HttpError},
+    internal_events::{
+        EndpointBytesReceived, EventsReceived, OktaSystemLogCheckpointError,
+        OktaSystemLogRateLimited, OktaSystemLogReadError, StreamClosedError,
+    },
+    serde::bool_or_struct,
+    shutdown::ShutdownSignal,
+    tls::{TlsConfig, TlsSettings},
+    SourceSender,
+};
+
+const CHECKPOINT_FILENAME: &str = "checkpoint.json";
+const TMP_CHECKPOINT_FILENAME: &str = "checkpoint.new.json";
+
+const PAGE_SIZE: &str = "1000";
+/// How long the System Log is retained for.
+const RETENTION_SECS: u64 = 90 * 24 * 60 * 60;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid endpoint {:?}: {}", endpoint, source))]
+    InvalidEndpoint {
+        endpoint: String,
+        source: url::ParseError,
+    },
+    #[snafu(display("invalid API token"))]
+    InvalidToken {
+        source: http::header::InvalidHeaderValue,
+    },
+}
+
+#[derive(Debug, Snafu)]
+enum ReadError {
+    #[snafu(display("HTTP request failed: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("failed to read response body: {}", source))]
+    Body { source: hyper::Error },
+    #[snafu(display("unexpected status {} from {}", status, url))]
+    Status { status: StatusCode, url: String },
+    #[snafu(display("invalid System Log events: {}", source))]
+    InvalidEvents { source: serde_json::Error },
+    #[snafu(display("source output closed"))]
+    OutputClosed,
+    #[snafu(display("source shut down"))]
+    Shutdown,
+}
+
+/// Configuration for the `okta_system_log` source.
+#[configurable_component(source("okta_system_log"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OktaSystemLogConfig {
+    /// The URL of the Okta organization.
+    #[configurable(metadata(docs::examples = "https://example.okta.com"))]
+    endpoint: String,
+
+    /// The API token used to authenticate with the Okta API.
+    ///
+    /// It must belong to an administrator allowed to read the System Log.
+    #[configurable(metadata(docs::examples = "${OKTA_API_TOKEN}"))]
+    token: SensitiveString,
+
+    /// The types of the events to read.
+    ///
+    /// By default, events of all types are read.
+    #[configurable(metadata(docs::examples = "user.session.start"))]
+    #[configurable(metadata(docs::examples = "user.account.lock"))]
+    #[serde(default)]
+    event_types: Vec<String>,
+
+    /// The interval between polls of the System Log, in seconds.
+    #[serde(default = "default_poll_interval_secs")]
+    poll_interval_secs: u64,
+
+    /// How far back to read events from when no cursor is persisted, in seconds.
+    ///
+    /// Events are retained for 90 days at most. By default, only the events published after
+    /// Vector starts are read.
+    #[serde(default)]
+    backfill_secs: u64,
+
+    /// The directory used to persist the position in the System Log.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    #[serde(default)]
+    data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    60
+}
+
+impl GenerateConfig for OktaSystemLogConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "https://example.okta.com"
+            token = "${OKTA_API_TOKEN}""#,
+        )
+        .unwrap()
+    }
+}
+
+impl OktaSystemLogConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        Definition::new_with_default_metadata(Kind::object(Collection::any()), [log_namespace])
+            .with_event_field(&owned_value_path!("uuid"), Kind::bytes(), None)
+            .with_event_field(&owned_value_path!("eventType"), Kind::bytes(), None)
+            .with_event_field(&owned_value_path!("published"), Kind::bytes(), None)
+            .with_source_metadata(
+                Self::NAME,
+                parse_value_path(log_schema().timestamp_key())
+                    .ok()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some("timestamp"),
+            )
+            .with_standard_vector_source_metadata()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for OktaSystemLogConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let logs_url = logs_url(&self.endpoint)?;
+        let authorization = HeaderValue::from_str(&format!("SSWS {}", self.token.inner()))
+            .context(InvalidTokenSnafu)?;
+        let data_dir = cx
+            .globals
+            // source are only global, name can be used for subdir
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, &cx.proxy)?;
+
+        let reader = SystemLogReader {
+            client,
+            logs_url,
+            authorization,
+            filter: event_types_filter(&self.event_types),
+            backfill: Duration::from_secs(self.backfill_secs.min(RETENTION_SECS)),
+            checkpointer: Checkpointer::new(&data_dir),
+            acknowledgements: cx.do_acknowledgements(self.acknowledgements),
+            log_namespace: cx.log_namespace(self.log_namespace),
+            events_received: register!(EventsReceived),
+            out: cx.out,
+            shutdown: cx.shutdown,
+        };
+        Ok(Box::pin(
+            reader.run(Duration::from_secs(self.poll_interval_secs)),
+        ))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// Builds the URL of the System Log of the organization at `endpoint`.
+fn logs_url(endpoint: &str) -> Result<url::Url, BuildError> {
+    let url = format!("{}/api/v1/logs", endpoint.trim_end_matches('/'));
+    url::Url::parse(&url).context(InvalidEndpointSnafu { endpoint })
+}
+
+/// Builds the filter expression matching events of any of `event_types`.
+fn event_types_filter(event_types: &[String]) -> Option<String> {
+    (!event_types.is_empty()).then(|| {
+        event_types
+            .iter()
+            .map(|event_type| format!("eventType eq {:?}", event_type))
+            .collect::<Vec<_>>()
+            .join(" or ")
+    })
+}
+
+/// Builds the URL of the first page read, starting at `cursor`, oldest first.
+fn page_url(logs_url: &url::Url, cursor: &Cursor, filter: Option<&str>) -> url::Url {
+    let mut url = logs_url.clone();
+    {
+        let mut query = url.query_pairs_mut();
+        match cursor {
+            Cursor::Since(since) => query.append_pair(
+                "since",
+                &since.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            ),
+            Cursor::After(after) => query.append_pair("after", after),
+        };
+        query
+            .append_pair("sortOrder", "ASCENDING")
+            .append_pair("limit", PAGE_SIZE);
+        if let Some(filter) = filter {
+            query.append_pair("filter", filter);
+        }
+    }
+    url
+}
+
+/// Finds the URL of the next page in the `Link` headers.
+fn next_link(headers: &HeaderMap) -> Option<url::Url> {
+    headers
+        .get_all(header::LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|link| {
+            let mut parts = link.split(';').map(str::trim);
+            let url = parts.next()?.strip_prefix('<')?.strip_suffix('>')?;
+            if parts.any(|param| param == "rel=\"next\"") {
+                url::Url::parse(url).ok()
+            } else {
+                None
+            }
+        })
+}
+
+/// How long to wait before sending the next request, if the rate limit is exhausted.
+fn rate_limit_wait(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let header_u64 = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok())
+    };
+
+    if header_u64("x-rate-limit-remaining") == Some(0) {
+        let reset = header_u64("x-rate-limit-reset")?;
+        let wait = reset.saturating_sub(now.timestamp().max(0) as u64);
+        // The limit is reset at the start of the second, so wait for one more.
+        return Some(Duration::from_secs(wait + 1));
+    }
+    None
+}
+
+/// The position in the System Log following the last event read.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Cursor {
+    /// The time from which to read events, when no page has been read yet.
+    Since(DateTime<Utc>),
+    /// The opaque `after` parameter of the link to the page following the last one read.
+    After(String),
+}
+
+/// The cursor persisted as JSON in the data directory.
+struct Checkpointer {
+    path: PathBuf,
+    tmp_path: PathBuf,
+}
+
+impl Checkpointer {
+    fn new(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(CHECKPOINT_FILENAME),
+            tmp_path: data_dir.join(TMP_CHECKPOINT_FILENAME),
+        }
+    }
+
+    /// Loads the cursor previously persisted, if any.
+    async fn load(&self) -> io::Result<Option<Cursor>> {
+        match fs::read(&self.path).await {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map(Some)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Persists `cursor`, through a temporary file so a crash never leaves a partially written
+    /// file behind.
+    async fn set(&self, cursor: &Cursor) -> io::Result<()> {
+        let contents = serde_json::to_vec(cursor)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(&self.tmp_path, contents).await?;
+        fs::rename(&self.tmp_path, &self.path).await
+    }
+}
+
+struct SystemLogReader {
+    client: HttpClient,
+    logs_url: url::Url,
+    authorization: HeaderValue,
+    filter: Option<String>,
+    backfill: Duration,
+    checkpointer: Checkpointer,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    events_received: Registered<EventsReceived>,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+}
+
+impl SystemLogReader {
+    async fn run(mut self, interval: Duration) -> Result<(), ()> {
+        let mut cursor = self
+            .checkpointer
+            .load()
+            .await
+            .map_err(|error| {
+                emit!(OktaSystemLogCheckpointError {
+                    path: &self.checkpointer.path,
+                    error,
+                })
+            })?
+            .unwrap_or_else(|| {
+                let backfill = chrono::Duration::from_std(self.backfill)
+                    .unwrap_or_else(|_| chrono::Duration::zero());
+                Cursor::Since(Utc::now() - backfill)
+            });
+
+        let mut ticks =
+            IntervalStream::new(tokio::time::interval(interval)).take_until(self.shutdown.clone());
+        while ticks.next().await.is_some() {
+            match self.poll(&mut cursor).await {
+                Ok(()) => {}
+                Err(ReadError::OutputClosed | ReadError::Shutdown) => break,
+                Err(error) => emit!(OktaSystemLogReadError { error }),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the events published since the cursor, following the pages until an empty one.
+    ///
+    /// Pages read in ascending order always link to a next page, which holds the events published
+    /// after the last one, so its `after` parameter is kept as the cursor.
+    async fn poll(&mut self, cursor: &mut Cursor) -> Result<(), ReadError> {
+        let mut url = page_url(&self.logs_url, cursor, self.filter.as_deref());
+        loop {
+            let (parts, body) = self.request(&url).await?;
+            let entries = serde_json::from_slice::<Vec<serde_json::Value>>(&body)
+                .context(InvalidEventsSnafu)?;
+            let is_empty = entries.is_empty();
+            if !self.send(entries).await? {
+                // The events weren't delivered, so they're read again on the next poll.
+                return Ok(());
+            }
+
+            let next = next_link(&parts.headers);
+            let after = next.as_ref().and_then(|next| {
+                next.query_pairs()
+                    .find(|(name, _)| name == "after")
+                    .map(|(_, value)| value.into_owned())
+            });
+            if let Some(after) = after {
+                let advanced = Cursor::After(after);
+                if advanced != *cursor {
+                    *cursor = advanced;
+                    if let Err(error) = self.checkpointer.set(cursor).await {
+                        emit!(OktaSystemLogCheckpointError {
+                            path: &self.checkpointer.path,
+                            error,
+                        });
+                    }
+                }
+            }
+
+            match next {
+                Some(next) if !is_empty => url = next,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Sends a request to `url`, waiting for the rate limit to be reset when it's exhausted.
+    async fn request(&self, url: &url::Url) -> Result<(Parts, Bytes), ReadError> {
+        loop {
+            let request = Request::get(url.as_str())
+                .header(header::ACCEPT, "application/json")
+                .header(header::AUTHORIZATION, self.authorization.clone())
+                .body(Body::empty())
+                .expect("error creating request");
+
+            let response = self.client.send(request).await.context(RequestSnafu)?;
+            let (parts, body) = response.into_parts();
+            let body = hyper::body::to_bytes(body).await.context(BodySnafu)?;
+            emit!(EndpointBytesReceived {
+                byte_size: body.len(),
+                protocol: "http",
+                endpoint: self.logs_url.as_str(),
+            });
+
+            let wait = rate_limit_wait(&parts.headers, Utc::now());
+            if parts.status.is_success() {
+                if let Some(wait) = wait {
+                    self.wait(wait).await?;
+                }
+                return Ok((parts, body));
+            }
+            match wait {
+                Some(wait) if parts.status == StatusCode::TOO_MANY_REQUESTS => {
+                    emit!(OktaSystemLogRateLimited { wait });
+                    self.wait(wait).await?;
+                }
+                _ => {
+                    return Err(ReadError::Status {
+                        status: parts.status,
+                        url: url.to_string(),
+                    })
+                }
+            }
+        }
+    }
+
+    async fn wait(&self, duration: Duration) -> Result<(), ReadError> {
+        tokio::select! {
+            _ = tokio::time::sleep(duration) => Ok(()),
+            _ = self.shutdown.clone() => Err(ReadError::Shutdown),
+        }
+    }
+
+    /// Sends the events of `entries` downstream.
+    ///
+    /// Returns whether or not the events were delivered.
+    async fn send(&mut self, entries: Vec<serde_json::Value>) -> Result<bool, ReadError> {
+        if entries.is_empty() {
+            return Ok(true);
+        }
+
+        let now = Utc::now();
+        let mut events = entries
+            .into_iter()
+            .map(|entry| {
+                let published = entry
+                    .get("published")
+                    .and_then(|value| value.as_str())
+                    .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+                    .map(|published| published.with_timezone(&Utc));
+
+                let mut log = self
+                    .log_namespace
+                    .new_log_from_data(value::Value::from(entry));
+                if let Some(published) = published {
+                    self.log_namespace.insert_source_metadata(
+                        OktaSystemLogConfig::NAME,
+                        &mut log,
+                        Some(LegacyKey::Overwrite(path!(log_schema().timestamp_key()))),
+                        path!("timestamp"),
+                        published,
+                    );
+                }
+                self.log_namespace.insert_standard_vector_source_metadata(
+                    &mut log,
+                    OktaSystemLogConfig::NAME,
+                    now,
+                );
+                Event::from(log)
+            })
+            .collect::<Vec<_>>();
+
+        let count = events.len();
+        self.events_received.emit(CountByteSize(
+            count,
+            events.estimated_json_encoded_size_of(),
+        ));
+
+        let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+        if let Err(error) = self.out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            return Err(ReadError::OutputClosed);
+        }
+        match receiver {
+            Some(receiver) => Ok(receiver.await == BatchStatus::Delivered),
+            None => Ok(true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use chrono::TimeZone;
+    use warp::Filter;
+
+    use super::*;
+    use crate::test_util::{
+        components::{run_and_assert_source_compliance, HTTP_PULL_SOURCE_TAGS},
+        next_addr, wait_for_tcp,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OktaSystemLogConfig>();
+    }
+
+    #[test]
+    fn builds_page_url() {
+        let url = logs_url("https://example.okta.com/").unwrap();
+        let filter = event_types_filter(&[
+            "user.session.start".to_string(),
+            "user.account.lock".to_string(),
+        ]);
+
+        assert_eq!(
+            page_url(
+                &url,
+                &Cursor::Since(Utc.ymd(2023, 1, 10).and_hms(12, 30, 0)),
+                filter.as_deref()
+            )
+            .as_str(),
+            "https://example.okta.com/api/v1/logs?since=2023-01-10T12%3A30%3A00.000Z\
+             &sortOrder=ASCENDING&limit=1000\
+             &filter=eventType+eq+%22user.session.start%22+or+eventType+eq+%22user.account.lock%22"
+        );
+        assert_eq!(
+            page_url(&url, &Cursor::After("1673353800000_1".to_string()), None).as_str(),
+            "https://example.okta.com/api/v1/logs?after=1673353800000_1&sortOrder=ASCENDING&limit=1000"
+        );
+    }
+
+    #[test]
+    fn finds_next_link() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            header::LINK,
+            HeaderValue::from_static(
+                "<https://example.okta.com/api/v1/logs?limit=1000>; rel=\"self\"",
+            ),
+        );
+        assert_eq!(next_link(&headers), None);
+
+        headers.append(
+            header::LINK,
+            HeaderValue::from_static(
+                "<https://example.okta.com/api/v1/logs?limit=1000&after=1673353800000_1>; rel=\"next\"",
+            ),
+        );
+        assert_eq!(
+            next_link(&headers).unwrap().as_str(),
+            "https://example.okta.com/api/v1/logs?limit=1000&after=1673353800000_1"
+        );
+    }
+
+    #[test]
+    fn computes_rate_limit_wait() {
+        let now = Utc.timestamp(1_673_353_800, 0);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-rate-limit-remaining", HeaderValue::from_static("12"));
+        headers.insert("x-rate-limit-reset", HeaderValue::from_static("1673353830"));
+        assert_eq!(rate_limit_wait(&headers, now), None);
+
+        headers.insert("x-rate-limit-remaining", HeaderValue::from_static("0"));
+        assert_eq!(
+            rate_limit_wait(&headers, now),
+            Some(Duration::from_secs(31))
+        );
+    }
+
+    fn system_log_server(addr: SocketAddr) {
+        let logs = warp::path!("api" / "v1" / "logs")
+            .and(warp::header::exact("Authorization", "SSWS token"))
+            .and(warp::query::<std::collections::HashMap<String, String>>())
+            .map(move |query: std::collections::HashMap<String, String>| {
+                let (body, after) = match query.get("after").map(String::as_str) {
+                    None => (
+                        r#"[{"uuid":"a","eventType":"user.session.start","published":"2023-01-10T12:30:00.000Z"},
+                            {"uuid":"b","eventType":"user.session.start","published":"2023-01-10T12:30:01.000Z"}]"#,
+                        "page2",
+                    ),
+                    Some("page2") => (
+                        r#"[{"uuid":"c","eventType":"user.account.lock","published":"2023-01-10T12:30:02.000Z"}]"#,
+                        "page3",
+                    ),
+                    Some(_) => ("[]", "page3"),
+                };
+                warp::http::Response::builder()
+                    .header(
+                        "Link",
+                        format!("<http://{}/api/v1/logs?limit=1000&after={}>; rel=\"next\"", addr, after),
+                    )
+                    .body(body)
+                    .unwrap()
+            });
+        tokio::spawn(warp::serve(logs).run(addr));
+    }
+
+    #[tokio::test]
+    async fn reads_system_log_pages() {
+        let addr = next_addr();
+        system_log_server(addr);
+        wait_for_tcp(addr).await;
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let config: OktaSystemLogConfig = toml::from_str(&format!(
+            r#"endpoint = "http://{}"
+            token = "token"
+            poll_interval_secs = 1
+            data_dir = "{}""#,
+            addr,
+            data_dir.path().display(),
+        ))
+        .unwrap();
+
+        let events = run_and_assert_source_compliance(
+            config,
+            Duration::from_secs(3),
+            &HTTP_PULL_SOURCE_TAGS,
+        )
+        .await;
+
+        // Later polls start from the last page, so the events are only read once.
+        let uuids = events
+            .iter()
+            .map(|event| event.as_log()["uuid"].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(uuids, ["a", "b", "c"]);
+        assert_eq!(
+            events[0].as_log()[log_schema().timestamp_key()],
+            value::Value::from(Utc.ymd(2023, 1, 10).and_hms(12, 30, 0))
+        );
+
+        let cursor = Checkpointer::new(data_dir.path())
+            .load()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cursor, Cursor::After("page3".to_string()));
+    }
+}
@@ -3,8 +3,10 @@ use std::net::SocketAddr;
 use bytes::Bytes;
 use futures_util::FutureExt;
 use http::StatusCode;
-use opentelemetry_proto::proto::collector::logs::v1::{
-    ExportLogsServiceRequest, ExportLogsServiceResponse,
+use opentelemetry_proto::proto::collector::{
+    logs::v1::{ExportLogsServiceRequest, ExportLogsServiceResponse},
+    metrics::v1::{ExportMetricsServiceRequest, ExportMetricsServiceResponse},
+    trace::v1::{ExportTraceServiceRequest, ExportTraceServiceResponse},
 };
 use prost::Message;
 use snafu::Snafu;
@@ -64,26 +66,115 @@ pub(crate) fn build_warp_filter(
     bytes_received: Registered<BytesReceived>,
     events_received: Registered<EventsReceived>,
 ) -> BoxedFilter<(Response,)> {
+    let log_filters = build_warp_log_filter(
+        acknowledgements,
+        out.clone(),
+        bytes_received.clone(),
+        events_received.clone(),
+    );
+    let metric_filters = build_warp_metric_filter(
+        acknowledgements,
+        out.clone(),
+        bytes_received.clone(),
+        events_received.clone(),
+    );
+    let trace_filters =
+        build_warp_trace_filter(acknowledgements, out, bytes_received, events_received);
+    log_filters
+        .or(metric_filters)
+        .unify()
+        .or(trace_filters)
+        .unify()
+        .boxed()
+}
+
+fn protobuf_request(path: &'static str) -> BoxedFilter<(Option<String>, Bytes)> {
     warp::post()
-        .and(warp::path!("v1" / "logs"))
+        .and(warp::path("v1"))
+        .and(warp::path(path))
+        .and(warp::path::end())
         .and(warp::header::exact_ignore_case(
             "content-type",
             "application/x-protobuf",
         ))
         .and(warp::header::optional::<String>("content-encoding"))
         .and(warp::body::bytes())
+        .boxed()
+}
+
+fn build_warp_log_filter(
+    acknowledgements: bool,
+    out: SourceSender,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+) -> BoxedFilter<(Response,)> {
+    protobuf_request("logs")
+        .and_then(move |encoding_header: Option<String>, body: Bytes| {
+            let events = decode(&encoding_header, body).and_then(|body| {
+                bytes_received.emit(ByteSize(body.len()));
+                decode_log_body(body, &events_received)
+            });
+
+            handle_request(
+                events,
+                acknowledgements,
+                out.clone(),
+                super::LOGS,
+                ExportLogsServiceResponse {},
+            )
+        })
+        .boxed()
+}
+
+fn build_warp_metric_filter(
+    acknowledgements: bool,
+    out: SourceSender,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+) -> BoxedFilter<(Response,)> {
+    protobuf_request("metrics")
         .and_then(move |encoding_header: Option<String>, body: Bytes| {
             let events = decode(&encoding_header, body).and_then(|body| {
                 bytes_received.emit(ByteSize(body.len()));
-                decode_body(body, &events_received)
+                decode_metric_body(body, &events_received)
             });
 
-            handle_request(events, acknowledgements, out.clone(), super::LOGS)
+            handle_request(
+                events,
+                acknowledgements,
+                out.clone(),
+                super::METRICS,
+                ExportMetricsServiceResponse {},
+            )
         })
         .boxed()
 }
 
-fn decode_body(
+fn build_warp_trace_filter(
+    acknowledgements: bool,
+    out: SourceSender,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+) -> BoxedFilter<(Response,)> {
+    protobuf_request("traces")
+        .and_then(move |encoding_header: Option<String>, body: Bytes| {
+            let events = decode(&encoding_header, body).and_then(|body| {
+                bytes_received.emit(ByteSize(body.len()));
+                decode_trace_body(body, &events_received)
+            });
+
+            handle_request(
+                events,
+                acknowledgements,
+                out.clone(),
+                super::TRACES,
+                ExportTraceServiceResponse {},
+            )
+        })
+        .boxed()
+}
+
+fn decode_log_body(
     body: Bytes,
     events_received: &Registered<EventsReceived>,
 ) -> Result<Vec<Event>, ErrorMessage> {
@@ -108,11 +199,62 @@ fn decode_body(
     Ok(events)
 }
 
+fn decode_metric_body(
+    body: Bytes,
+    events_received: &Registered<EventsReceived>,
+) -> Result<Vec<Event>, ErrorMessage> {
+    let request = ExportMetricsServiceRequest::decode(body).map_err(|error| {
+        ErrorMessage::new(
+            StatusCode::BAD_REQUEST,
+            format!("Could not decode request: {}", error),
+        )
+    })?;
+
+    let events: Vec<Event> = request
+        .resource_metrics
+        .into_iter()
+        .flat_map(|v| v.into_iter())
+        .collect();
+
+    events_received.emit(CountByteSize(
+        events.len(),
+        events.estimated_json_encoded_size_of(),
+    ));
+
+    Ok(events)
+}
+
+fn decode_trace_body(
+    body: Bytes,
+    events_received: &Registered<EventsReceived>,
+) -> Result<Vec<Event>, ErrorMessage> {
+    let request = ExportTraceServiceRequest::decode(body).map_err(|error| {
+        ErrorMessage::new(
+            StatusCode::BAD_REQUEST,
+            format!("Could not decode request: {}", error),
+        )
+    })?;
+
+    let events: Vec<Event> = request
+        .resource_spans
+        .into_iter()
+        .flat_map(|v| v.into_iter())
+        .collect();
+
+    events_received.emit(CountByteSize(
+        events.len(),
+        events.estimated_json_encoded_size_of(),
+    ));
+
+    Ok(events)
+}
+
 async fn handle_request(
     events: Result<Vec<Event>, ErrorMessage>,
     acknowledgements: bool,
     mut out: SourceSender,
     output: &str,
+    response: impl Message,
 ) -> Result<Response, Rejection> {
     match events {
         Ok(mut events) => {
@@ -127,11 +269,9 @@ async fn handle_request(
                 })?;
 
             match receiver {
-                None => Ok(protobuf(ExportLogsServiceResponse {}).into_response()),
+                None => Ok(protobuf(response).into_response()),
                 Some(receiver) => match receiver.await {
-                    BatchStatus::Delivered => {
-                        Ok(protobuf(ExportLogsServiceResponse {}).into_response())
-                    }
+                    BatchStatus::Delivered => Ok(protobuf(response).into_response()),
                     BatchStatus::Errored => Err(warp::reject::custom(Status {
                         code: 2, // UNKNOWN - OTLP doesn't require use of status.code, but we can't encode a None here
                         message: "Error delivering contents to sink".into(),
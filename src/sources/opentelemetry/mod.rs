@@ -11,7 +11,11 @@ mod status;
 use std::net::SocketAddr;
 
 use futures::{future::join, FutureExt, TryFutureExt};
-use opentelemetry_proto::proto::collector::logs::v1::logs_service_server::LogsServiceServer;
+use opentelemetry_proto::proto::collector::{
+    logs::v1::logs_service_server::LogsServiceServer,
+    metrics::v1::metrics_service_server::MetricsServiceServer,
+    trace::v1::trace_service_server::TraceServiceServer,
+};
 use vector_common::internal_event::{BytesReceived, EventsReceived, Protocol};
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
@@ -26,11 +30,13 @@ use crate::{
         SourceContext,
     },
     serde::bool_or_struct,
-    sources::{util::grpc::run_grpc_server, Source},
+    sources::{util::grpc::run_grpc_server_with_routes, Source},
     tls::{MaybeTlsSettings, TlsEnableableConfig},
 };
 
 pub const LOGS: &str = "logs";
+pub const METRICS: &str = "metrics";
+pub const TRACES: &str = "traces";
 
 /// Configuration for the `opentelemetry` source.
 #[configurable_component(source("opentelemetry"))]
@@ -102,16 +108,26 @@ impl SourceConfig for OpentelemetryConfig {
         let events_received = register!(EventsReceived);
 
         let grpc_tls_settings = MaybeTlsSettings::from_config(&self.grpc.tls, true)?;
-        let grpc_service = LogsServiceServer::new(Service {
+        let service = Service {
             pipeline: cx.out.clone(),
             acknowledgements,
             events_received: events_received.clone(),
-        })
-        .accept_compressed(tonic::codec::CompressionEncoding::Gzip);
-        let grpc_source = run_grpc_server(
+        };
+        let log_service = LogsServiceServer::new(service.clone())
+            .accept_compressed(tonic::codec::CompressionEncoding::Gzip);
+        let metrics_service = MetricsServiceServer::new(service.clone())
+            .accept_compressed(tonic::codec::CompressionEncoding::Gzip);
+        let trace_service = TraceServiceServer::new(service)
+            .accept_compressed(tonic::codec::CompressionEncoding::Gzip);
+        let grpc_source = run_grpc_server_with_routes(
             self.grpc.address,
             grpc_tls_settings,
-            grpc_service,
+            |server| {
+                server
+                    .add_service(log_service)
+                    .add_service(metrics_service)
+                    .add_service(trace_service)
+            },
             cx.shutdown.clone(),
         )
         .map_err(|error| {
@@ -129,7 +145,11 @@ impl SourceConfig for OpentelemetryConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(DataType::Log).with_port(LOGS)]
+        vec![
+            Output::default(DataType::Log).with_port(LOGS),
+            Output::default(DataType::Metric).with_port(METRICS),
+            Output::default(DataType::Trace).with_port(TRACES),
+        ]
     }
 
     fn resources(&self) -> Vec<Resource> {
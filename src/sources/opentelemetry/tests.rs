@@ -1,7 +1,11 @@
 use crate::{
     config::{SourceConfig, SourceContext},
-    event::{into_event_stream, Event, EventStatus, LogEvent, Value},
-    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS},
+    event::{
+        into_event_stream,
+        metric::{Bucket, Metric, MetricKind, MetricValue},
+        Event, EventStatus, LogEvent, Value,
+    },
+    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS, METRICS, TRACES},
     test_util::{
         self,
         components::{assert_source_compliance, SOURCE_TAGS},
@@ -13,13 +17,24 @@ use chrono::{TimeZone, Utc};
 use futures::Stream;
 use futures_util::StreamExt;
 use opentelemetry_proto::proto::{
-    collector::logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+    collector::{
+        logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+        metrics::v1::{metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest},
+        trace::v1::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+    },
     common::v1::{any_value, AnyValue, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+    metrics::v1::{
+        exponential_histogram_data_point::Buckets, metric::Data, number_data_point,
+        AggregationTemporality, ExponentialHistogram, ExponentialHistogramDataPoint,
+        Metric as OtelMetric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum,
+    },
     resource::v1::Resource as OtelResource,
+    trace::v1::{span, ResourceSpans, ScopeSpans, Span, Status as SpanStatus},
 };
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, num::NonZeroU32};
 use tonic::Request;
+use vector_core::metric_tags;
 
 #[test]
 fn generate_config() {
@@ -43,7 +58,7 @@ async fn receive_grpc_logs() {
             },
             acknowledgements: Default::default(),
         };
-        let (sender, logs_output, _) = new_source(EventStatus::Delivered);
+        let (sender, logs_output, _, _, _) = new_source(EventStatus::Delivered);
         let server = source
             .build(SourceContext::new_test(sender, None))
             .await
@@ -124,18 +139,294 @@ async fn receive_grpc_logs() {
     .await;
 }
 
+#[tokio::test]
+async fn receive_grpc_traces() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let grpc_addr = next_addr();
+        let http_addr = next_addr();
+
+        let source = OpentelemetryConfig {
+            grpc: GrpcConfig {
+                address: grpc_addr,
+                tls: Default::default(),
+            },
+            http: HttpConfig {
+                address: http_addr,
+                tls: Default::default(),
+            },
+            acknowledgements: Default::default(),
+        };
+        let (sender, _, _, traces_output, _) = new_source(EventStatus::Delivered);
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(grpc_addr).await;
+
+        // send request via grpc client
+        let mut client = TraceServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        let req = Request::new(ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Some(OtelResource {
+                    attributes: vec![KeyValue {
+                        key: "res_key".into(),
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue("res_val".into())),
+                        }),
+                    }],
+                    dropped_attributes_count: 0,
+                }),
+                scope_spans: vec![ScopeSpans {
+                    scope: None,
+                    spans: vec![Span {
+                        trace_id: str_into_hex_bytes("4ac52aadf321c2e531db005df08792f5"),
+                        span_id: str_into_hex_bytes("0b9e4bda2a55530d"),
+                        trace_state: "".into(),
+                        parent_span_id: str_into_hex_bytes("b5d2d4a9c2b1e3f7"),
+                        name: "GET /users".into(),
+                        kind: span::SpanKind::Server as i32,
+                        start_time_unix_nano: 1,
+                        end_time_unix_nano: 2,
+                        attributes: vec![KeyValue {
+                            key: "attr_key".into(),
+                            value: Some(AnyValue {
+                                value: Some(any_value::Value::StringValue("attr_val".into())),
+                            }),
+                        }],
+                        dropped_attributes_count: 3,
+                        events: vec![],
+                        dropped_events_count: 0,
+                        links: vec![],
+                        dropped_links_count: 0,
+                        status: Some(SpanStatus {
+                            message: "".into(),
+                            code: 1,
+                        }),
+                    }],
+                    schema_url: "v1".into(),
+                }],
+                schema_url: "v1".into(),
+            }],
+        });
+        let _ = client.export(req).await;
+        let mut output = test_util::collect_ready(traces_output).await;
+        // we just send one, so only one output
+        assert_eq!(output.len(), 1);
+        let actual_event = output.pop().unwrap();
+        let trace = actual_event.as_trace();
+        assert!(trace.get("ingest_timestamp").is_some());
+
+        let expected = vec_into_btmap(vec![
+            (
+                "attributes",
+                Value::Object(vec_into_btmap(vec![("attr_key", "attr_val".into())])),
+            ),
+            (
+                "resources",
+                Value::Object(vec_into_btmap(vec![("res_key", "res_val".into())])),
+            ),
+            ("trace_id", "4ac52aadf321c2e531db005df08792f5".into()),
+            ("span_id", "0b9e4bda2a55530d".into()),
+            ("parent_span_id", "b5d2d4a9c2b1e3f7".into()),
+            ("name", "GET /users".into()),
+            ("kind", 2.into()),
+            ("start_time_unix_nano", Utc.timestamp_nanos(1).into()),
+            ("end_time_unix_nano", Utc.timestamp_nanos(2).into()),
+            ("dropped_attributes_count", 3.into()),
+            ("dropped_events_count", 0.into()),
+            ("dropped_links_count", 0.into()),
+            (
+                "status",
+                Value::Object(vec_into_btmap(vec![
+                    ("message", "".into()),
+                    ("code", 1.into()),
+                ])),
+            ),
+            (
+                "ingest_timestamp",
+                trace.get("ingest_timestamp").unwrap().clone(),
+            ),
+            ("source_type", "opentelemetry".into()),
+        ]);
+        assert_eq!(trace.as_map(), &expected);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn receive_grpc_metrics() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let grpc_addr = next_addr();
+        let http_addr = next_addr();
+
+        let source = OpentelemetryConfig {
+            grpc: GrpcConfig {
+                address: grpc_addr,
+                tls: Default::default(),
+            },
+            http: HttpConfig {
+                address: http_addr,
+                tls: Default::default(),
+            },
+            acknowledgements: Default::default(),
+        };
+        let (sender, _, metrics_output, _, _) = new_source(EventStatus::Delivered);
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(grpc_addr).await;
+
+        // send request via grpc client
+        let mut client = MetricsServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        let attributes = vec![KeyValue {
+            key: "attr_key".into(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::IntValue(42)),
+            }),
+        }];
+        let req = Request::new(ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(OtelResource {
+                    attributes: vec![KeyValue {
+                        key: "res_key".into(),
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue("res_val".into())),
+                        }),
+                    }],
+                    dropped_attributes_count: 0,
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: None,
+                    metrics: vec![
+                        OtelMetric {
+                            name: "requests".into(),
+                            description: "".into(),
+                            unit: "1".into(),
+                            data: Some(Data::Sum(Sum {
+                                data_points: vec![NumberDataPoint {
+                                    attributes: attributes.clone(),
+                                    start_time_unix_nano: 1_000_000_000,
+                                    time_unix_nano: 11_000_000_000,
+                                    value: Some(number_data_point::Value::AsInt(3)),
+                                    exemplars: vec![],
+                                    flags: 0,
+                                }],
+                                aggregation_temporality: AggregationTemporality::Delta as i32,
+                                is_monotonic: true,
+                            })),
+                        },
+                        OtelMetric {
+                            name: "latency".into(),
+                            description: "".into(),
+                            unit: "s".into(),
+                            data: Some(Data::ExponentialHistogram(ExponentialHistogram {
+                                data_points: vec![ExponentialHistogramDataPoint {
+                                    attributes,
+                                    start_time_unix_nano: 1_000_000_000,
+                                    time_unix_nano: 11_000_000_000,
+                                    count: 6,
+                                    sum: Some(7.5),
+                                    scale: 0,
+                                    zero_count: 1,
+                                    positive: Some(Buckets {
+                                        offset: 0,
+                                        bucket_counts: vec![2, 3],
+                                    }),
+                                    negative: None,
+                                    flags: 0,
+                                    exemplars: vec![],
+                                    min: None,
+                                    max: None,
+                                }],
+                                aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                            })),
+                        },
+                    ],
+                    schema_url: "v1".into(),
+                }],
+                schema_url: "v1".into(),
+            }],
+        });
+        let _ = client.export(req).await;
+        let output = test_util::collect_ready(metrics_output).await;
+
+        let tags = metric_tags!(
+            "resource.res_key" => "res_val",
+            "attr_key" => "42",
+        );
+        let timestamp = Some(Utc.timestamp(11, 0));
+        let expected = vec![
+            Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 3.0 },
+            )
+            .with_tags(Some(tags.clone()))
+            .with_timestamp(timestamp)
+            .with_interval_ms(NonZeroU32::new(10_000)),
+            Metric::new(
+                "latency",
+                MetricKind::Absolute,
+                MetricValue::AggregatedHistogram {
+                    buckets: vec![
+                        Bucket {
+                            upper_limit: 0.0,
+                            count: 1,
+                        },
+                        Bucket {
+                            upper_limit: 2.0,
+                            count: 2,
+                        },
+                        Bucket {
+                            upper_limit: 4.0,
+                            count: 3,
+                        },
+                    ],
+                    count: 6,
+                    sum: 7.5,
+                },
+            )
+            .with_tags(Some(tags))
+            .with_timestamp(timestamp),
+        ];
+        assert_eq!(
+            output
+                .into_iter()
+                .map(Event::into_metric)
+                .collect::<Vec<_>>(),
+            expected
+        );
+    })
+    .await;
+}
+
 fn new_source(
     status: EventStatus,
 ) -> (
     SourceSender,
     impl Stream<Item = Event>,
     impl Stream<Item = Event>,
+    impl Stream<Item = Event>,
+    impl Stream<Item = Event>,
 ) {
     let (mut sender, recv) = SourceSender::new_test_finalize(status);
     let logs_output = sender
         .add_outputs(status, LOGS.to_string())
         .flat_map(into_event_stream);
-    (sender, logs_output, recv)
+    let metrics_output = sender
+        .add_outputs(status, METRICS.to_string())
+        .flat_map(into_event_stream);
+    let traces_output = sender
+        .add_outputs(status, TRACES.to_string())
+        .flat_map(into_event_stream);
+    (sender, logs_output, metrics_output, traces_output, recv)
 }
 
 fn str_into_hex_bytes(s: &str) -> Vec<u8> {
@@ -0,0 +1,420 @@
+//! Decoding of the messages of the `pgoutput` logical decoding plugin.
+//!
+//! The messages follow the [logical replication message
+//! formats](https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html), version 1.
+
+use std::collections::{BTreeMap, HashMap};
+
+use bytes::Buf;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ordered_float::NotNan;
+use snafu::Snafu;
+
+use super::{Change, Operation, Transaction};
+use crate::event::Value;
+
+const BOOL_OID: u32 = 16;
+const INT8_OID: u32 = 20;
+const INT2_OID: u32 = 21;
+const INT4_OID: u32 = 23;
+const OID_OID: u32 = 26;
+const FLOAT4_OID: u32 = 700;
+const FLOAT8_OID: u32 = 701;
+
+#[derive(Debug, PartialEq, Eq, Snafu)]
+pub enum DecodeError {
+    #[snafu(display("message ended unexpectedly"))]
+    UnexpectedEnd,
+    #[snafu(display("string is not valid UTF-8"))]
+    InvalidString,
+    #[snafu(display("unknown message type {:?}", tag as char))]
+    UnknownMessage { tag: u8 },
+    #[snafu(display("unknown tuple type {:?}", tag as char))]
+    UnknownTuple { tag: u8 },
+    #[snafu(display("unknown column value type {:?}", tag as char))]
+    UnknownColumnValue { tag: u8 },
+    #[snafu(display("change of unknown relation {}", id))]
+    UnknownRelation { id: u32 },
+    #[snafu(display("change outside of a transaction"))]
+    NoTransaction,
+}
+
+#[derive(Debug)]
+struct Column {
+    name: String,
+    type_oid: u32,
+}
+
+#[derive(Debug)]
+struct Relation {
+    schema: String,
+    table: String,
+    columns: Vec<Column>,
+}
+
+/// Decodes the messages of a slot into transactions.
+///
+/// Relation messages describing the tables are sent before the first change of each table, so
+/// they are kept for the changes that follow.
+#[derive(Debug, Default)]
+pub struct Decoder {
+    relations: HashMap<u32, Relation>,
+    transaction: Option<Transaction>,
+}
+
+impl Decoder {
+    /// Decodes a message read at `lsn`, returning the transaction it completes, if any.
+    pub fn decode(
+        &mut self,
+        lsn: u64,
+        mut data: &[u8],
+    ) -> Result<Option<Transaction>, DecodeError> {
+        let tag = get_u8(&mut data)?;
+        match tag {
+            b'B' => {
+                let _final_lsn = get_u64(&mut data)?;
+                let timestamp = get_timestamp(&mut data)?;
+                let xid = get_u32(&mut data)?;
+                self.transaction = Some(Transaction {
+                    xid: Some(xid),
+                    timestamp: Some(timestamp),
+                    lsn,
+                    changes: Vec::new(),
+                });
+            }
+            b'C' => {
+                let _flags = get_u8(&mut data)?;
+                let _commit_lsn = get_u64(&mut data)?;
+                let end_lsn = get_u64(&mut data)?;
+                let mut transaction = self.transaction.take().ok_or(DecodeError::NoTransaction)?;
+                transaction.lsn = end_lsn;
+                return Ok(Some(transaction));
+            }
+            b'R' => {
+                let id = get_u32(&mut data)?;
+                let schema = get_string(&mut data)?;
+                let table = get_string(&mut data)?;
+                let _replica_identity = get_u8(&mut data)?;
+                let count = get_u16(&mut data)?;
+                let columns = (0..count)
+                    .map(|_| {
+                        let _flags = get_u8(&mut data)?;
+                        let name = get_string(&mut data)?;
+                        let type_oid = get_u32(&mut data)?;
+                        let _type_modifier = get_u32(&mut data)?;
+                        Ok(Column { name, type_oid })
+                    })
+                    .collect::<Result<_, _>>()?;
+                self.relations.insert(
+                    id,
+                    Relation {
+                        schema,
+                        table,
+                        columns,
+                    },
+                );
+            }
+            b'I' => {
+                let relation = self.relation(get_u32(&mut data)?)?;
+                let after = match get_u8(&mut data)? {
+                    b'N' => get_tuple(&mut data, relation)?,
+                    tag => return Err(DecodeError::UnknownTuple { tag }),
+                };
+                let change = relation.change(Operation::Insert, None, Some(after));
+                self.push(change)?;
+            }
+            b'U' => {
+                let relation = self.relation(get_u32(&mut data)?)?;
+                let (before, after) = match get_u8(&mut data)? {
+                    b'K' | b'O' => {
+                        let before = get_tuple(&mut data, relation)?;
+                        match get_u8(&mut data)? {
+                            b'N' => (Some(before), get_tuple(&mut data, relation)?),
+                            tag => return Err(DecodeError::UnknownTuple { tag }),
+                        }
+                    }
+                    b'N' => (None, get_tuple(&mut data, relation)?),
+                    tag => return Err(DecodeError::UnknownTuple { tag }),
+                };
+                let change = relation.change(Operation::Update, before, Some(after));
+                self.push(change)?;
+            }
+            b'D' => {
+                let relation = self.relation(get_u32(&mut data)?)?;
+                let before = match get_u8(&mut data)? {
+                    b'K' | b'O' => get_tuple(&mut data, relation)?,
+                    tag => return Err(DecodeError::UnknownTuple { tag }),
+                };
+                let change = relation.change(Operation::Delete, Some(before), None);
+                self.push(change)?;
+            }
+            b'T' => {
+                let count = get_u32(&mut data)?;
+                let _options = get_u8(&mut data)?;
+                let changes = (0..count)
+                    .map(|_| {
+                        let relation = self.relation(get_u32(&mut data)?)?;
+                        Ok(relation.change(Operation::Truncate, None, None))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                for change in changes {
+                    self.push(change)?;
+                }
+            }
+            // Origin, type and logical decoding messages carry nothing that is emitted.
+            b'O' | b'Y' | b'M' => {}
+            tag => return Err(DecodeError::UnknownMessage { tag }),
+        }
+        Ok(None)
+    }
+
+    fn relation(&self, id: u32) -> Result<&Relation, DecodeError> {
+        self.relations
+            .get(&id)
+            .ok_or(DecodeError::UnknownRelation { id })
+    }
+
+    fn push(&mut self, change: Change) -> Result<(), DecodeError> {
+        self.transaction
+            .as_mut()
+            .ok_or(DecodeError::NoTransaction)?
+            .changes
+            .push(change);
+        Ok(())
+    }
+}
+
+impl Relation {
+    fn change(
+        &self,
+        operation: Operation,
+        before: Option<BTreeMap<String, Value>>,
+        after: Option<BTreeMap<String, Value>>,
+    ) -> Change {
+        Change {
+            operation,
+            schema: self.schema.clone(),
+            table: self.table.clone(),
+            before,
+            after,
+        }
+    }
+}
+
+/// Reads the values of a row, leaving out the unchanged TOASTed values that aren't sent.
+fn get_tuple(
+    data: &mut &[u8],
+    relation: &Relation,
+) -> Result<BTreeMap<String, Value>, DecodeError> {
+    let count = get_u16(data)?;
+    let mut values = BTreeMap::new();
+    for index in 0..usize::from(count) {
+        let value = match get_u8(data)? {
+            b'n' => Some(Value::Null),
+            b'u' => None,
+            b't' => {
+                let len = get_u32(data)? as usize;
+                if data.remaining() < len {
+                    return Err(DecodeError::UnexpectedEnd);
+                }
+                let text =
+                    std::str::from_utf8(&data[..len]).map_err(|_| DecodeError::InvalidString)?;
+                let value = relation
+                    .columns
+                    .get(index)
+                    .map_or_else(|| text.into(), |column| text_value(column.type_oid, text));
+                data.advance(len);
+                Some(value)
+            }
+            tag => return Err(DecodeError::UnknownColumnValue { tag }),
+        };
+
+        if let (Some(value), Some(column)) = (value, relation.columns.get(index)) {
+            values.insert(column.name.clone(), value);
+        }
+    }
+    Ok(values)
+}
+
+/// Converts the text representation of a value, keeping the types that have no exact
+/// equivalent, like `numeric`, as text.
+fn text_value(type_oid: u32, text: &str) -> Value {
+    match type_oid {
+        BOOL_OID => match text {
+            "t" => Value::Boolean(true),
+            "f" => Value::Boolean(false),
+            _ => text.into(),
+        },
+        INT2_OID | INT4_OID | INT8_OID | OID_OID => text
+            .parse::<i64>()
+            .map(Value::Integer)
+            .unwrap_or_else(|_| text.into()),
+        FLOAT4_OID | FLOAT8_OID => text
+            .parse::<f64>()
+            .ok()
+            .and_then(|value| NotNan::new(value).ok())
+            .map(Value::Float)
+            .unwrap_or_else(|| text.into()),
+        _ => text.into(),
+    }
+}
+
+fn get_u8(data: &mut &[u8]) -> Result<u8, DecodeError> {
+    if data.remaining() < 1 {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    Ok(data.get_u8())
+}
+
+fn get_u16(data: &mut &[u8]) -> Result<u16, DecodeError> {
+    if data.remaining() < 2 {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    Ok(data.get_u16())
+}
+
+fn get_u32(data: &mut &[u8]) -> Result<u32, DecodeError> {
+    if data.remaining() < 4 {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    Ok(data.get_u32())
+}
+
+fn get_u64(data: &mut &[u8]) -> Result<u64, DecodeError> {
+    if data.remaining() < 8 {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    Ok(data.get_u64())
+}
+
+fn get_string(data: &mut &[u8]) -> Result<String, DecodeError> {
+    let len = data
+        .iter()
+        .position(|&byte| byte == 0)
+        .ok_or(DecodeError::UnexpectedEnd)?;
+    let string = std::str::from_utf8(&data[..len])
+        .map_err(|_| DecodeError::InvalidString)?
+        .to_string();
+    data.advance(len + 1);
+    Ok(string)
+}
+
+/// Reads a timestamp, sent as microseconds since the PostgreSQL epoch.
+fn get_timestamp(data: &mut &[u8]) -> Result<DateTime<Utc>, DecodeError> {
+    if data.remaining() < 8 {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    let micros = data.get_i64();
+    Ok(Utc.ymd(2000, 1, 1).and_hms(0, 0, 0) + Duration::microseconds(micros))
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BufMut;
+
+    use super::*;
+
+    fn relation_message() -> Vec<u8> {
+        let mut data = vec![b'R'];
+        data.put_u32(16384);
+        data.put_slice(b"public\0users\0");
+        data.put_u8(b'd');
+        data.put_u16(3);
+        for (name, type_oid) in [("id", INT4_OID), ("name", 25), ("active", BOOL_OID)] {
+            data.put_u8(1);
+            data.put_slice(name.as_bytes());
+            data.put_u8(0);
+            data.put_u32(type_oid);
+            data.put_u32(u32::MAX);
+        }
+        data
+    }
+
+    fn put_text(data: &mut Vec<u8>, text: &str) {
+        data.put_u8(b't');
+        data.put_u32(text.len() as u32);
+        data.put_slice(text.as_bytes());
+    }
+
+    #[test]
+    fn decodes_transaction() {
+        let mut decoder = Decoder::default();
+
+        let mut begin = vec![b'B'];
+        begin.put_u64(0x16B3748);
+        begin.put_i64(1_000_000);
+        begin.put_u32(731);
+        assert_eq!(decoder.decode(0x16B3700, &begin), Ok(None));
+        assert_eq!(decoder.decode(0x16B3700, &relation_message()), Ok(None));
+
+        let mut insert = vec![b'I'];
+        insert.put_u32(16384);
+        insert.put_u8(b'N');
+        insert.put_u16(3);
+        put_text(&mut insert, "1");
+        put_text(&mut insert, "alice");
+        put_text(&mut insert, "t");
+        assert_eq!(decoder.decode(0x16B3710, &insert), Ok(None));
+
+        let mut delete = vec![b'D'];
+        delete.put_u32(16384);
+        delete.put_u8(b'K');
+        delete.put_u16(3);
+        put_text(&mut delete, "2");
+        delete.put_u8(b'n');
+        delete.put_u8(b'n');
+        assert_eq!(decoder.decode(0x16B3720, &delete), Ok(None));
+
+        let mut commit = vec![b'C'];
+        commit.put_u8(0);
+        commit.put_u64(0x16B3740);
+        commit.put_u64(0x16B3778);
+        commit.put_i64(1_000_000);
+        let transaction = decoder.decode(0x16B3740, &commit).unwrap().unwrap();
+
+        assert_eq!(transaction.xid, Some(731));
+        assert_eq!(transaction.lsn, 0x16B3778);
+        assert_eq!(
+            transaction.timestamp,
+            Some(Utc.ymd(2000, 1, 1).and_hms(0, 0, 1))
+        );
+        assert_eq!(transaction.changes.len(), 2);
+
+        let insert = &transaction.changes[0];
+        assert_eq!(insert.operation, Operation::Insert);
+        assert_eq!(insert.table, "users");
+        assert_eq!(insert.before, None);
+        let after = insert.after.as_ref().unwrap();
+        assert_eq!(after["id"], Value::Integer(1));
+        assert_eq!(after["name"], Value::from("alice"));
+        assert_eq!(after["active"], Value::Boolean(true));
+
+        let delete = &transaction.changes[1];
+        assert_eq!(delete.operation, Operation::Delete);
+        assert_eq!(delete.before.as_ref().unwrap()["id"], Value::Integer(2));
+        assert_eq!(delete.after, None);
+    }
+
+    #[test]
+    fn rejects_change_of_unknown_relation() {
+        let mut decoder = Decoder::default();
+
+        let mut insert = vec![b'I'];
+        insert.put_u32(42);
+        insert.put_u8(b'N');
+        insert.put_u16(0);
+        assert_eq!(
+            decoder.decode(0, &insert),
+            Err(DecodeError::UnknownRelation { id: 42 })
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_message() {
+        let mut decoder = Decoder::default();
+        assert_eq!(
+            decoder.decode(0, &[b'B', 0, 0]),
+            Err(DecodeError::UnexpectedEnd)
+        );
+    }
+}
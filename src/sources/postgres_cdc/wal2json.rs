@@ -0,0 +1,175 @@
+//! Decoding of the messages of the `wal2json` logical decoding plugin, in format version 2.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+
+use super::{Change, Operation, Transaction};
+use crate::event::Value;
+
+#[derive(Debug, Snafu)]
+pub enum DecodeError {
+    #[snafu(display("invalid message: {}", source))]
+    InvalidMessage { source: serde_json::Error },
+    #[snafu(display("unknown action {:?}", action))]
+    UnknownAction { action: String },
+    #[snafu(display("change outside of a transaction"))]
+    NoTransaction,
+}
+
+#[derive(Debug, Deserialize)]
+struct Message {
+    action: String,
+    xid: Option<u32>,
+    timestamp: Option<String>,
+    schema: Option<String>,
+    table: Option<String>,
+    #[serde(default)]
+    columns: Vec<Column>,
+    #[serde(default)]
+    identity: Vec<Column>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Column {
+    name: String,
+    value: serde_json::Value,
+}
+
+/// Decodes the messages of a slot into transactions.
+#[derive(Debug, Default)]
+pub struct Decoder {
+    transaction: Option<Transaction>,
+}
+
+impl Decoder {
+    /// Decodes a message read at `lsn`, returning the transaction it completes, if any.
+    pub fn decode(&mut self, lsn: u64, data: &str) -> Result<Option<Transaction>, DecodeError> {
+        let message: Message = serde_json::from_str(data).context(InvalidMessageSnafu)?;
+        let operation = match message.action.as_str() {
+            "B" => {
+                self.transaction = Some(Transaction {
+                    xid: message.xid,
+                    timestamp: message.timestamp.as_deref().and_then(parse_timestamp),
+                    lsn,
+                    changes: Vec::new(),
+                });
+                return Ok(None);
+            }
+            "C" => {
+                let mut transaction = self.transaction.take().ok_or(DecodeError::NoTransaction)?;
+                // The commit is read at the end of its record.
+                transaction.lsn = lsn;
+                return Ok(Some(transaction));
+            }
+            "I" => Operation::Insert,
+            "U" => Operation::Update,
+            "D" => Operation::Delete,
+            "T" => Operation::Truncate,
+            // Logical decoding messages carry nothing that is emitted.
+            "M" => return Ok(None),
+            _ => {
+                return Err(DecodeError::UnknownAction {
+                    action: message.action,
+                })
+            }
+        };
+
+        let (before, after) = match operation {
+            Operation::Insert => (None, Some(values(message.columns))),
+            Operation::Update => (
+                (!message.identity.is_empty()).then(|| values(message.identity)),
+                Some(values(message.columns)),
+            ),
+            Operation::Delete => (Some(values(message.identity)), None),
+            Operation::Truncate => (None, None),
+        };
+
+        self.transaction
+            .as_mut()
+            .ok_or(DecodeError::NoTransaction)?
+            .changes
+            .push(Change {
+                operation,
+                schema: message.schema.unwrap_or_default(),
+                table: message.table.unwrap_or_default(),
+                before,
+                after,
+            });
+        Ok(None)
+    }
+}
+
+fn values(columns: Vec<Column>) -> BTreeMap<String, Value> {
+    columns
+        .into_iter()
+        .map(|column| (column.name, column.value.into()))
+        .collect()
+}
+
+/// Parses a timestamp as formatted by PostgreSQL, such as `2022-11-03 10:41:22.58834+00`.
+fn parse_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S%.f%#z")
+        .ok()
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn decodes_transaction() {
+        let mut decoder = Decoder::default();
+
+        let messages = [
+            r#"{"action":"B","xid":731,"timestamp":"2022-11-03 10:41:22.58834+00"}"#,
+            r#"{"action":"I","schema":"public","table":"users","columns":[{"name":"id","type":"integer","value":1},{"name":"name","type":"text","value":"alice"}]}"#,
+            r#"{"action":"U","schema":"public","table":"users","columns":[{"name":"id","type":"integer","value":1},{"name":"name","type":"text","value":"bob"}],"identity":[{"name":"id","type":"integer","value":1}]}"#,
+            r#"{"action":"D","schema":"public","table":"users","identity":[{"name":"id","type":"integer","value":1}]}"#,
+        ];
+        for message in messages {
+            assert!(decoder.decode(0x16B3700, message).unwrap().is_none());
+        }
+        let transaction = decoder
+            .decode(0x16B3740, r#"{"action":"C","xid":731}"#)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(transaction.xid, Some(731));
+        assert_eq!(transaction.lsn, 0x16B3740);
+        assert_eq!(
+            transaction.timestamp,
+            Some(Utc.ymd(2022, 11, 3).and_hms_micro(10, 41, 22, 588340))
+        );
+
+        let operations = transaction
+            .changes
+            .iter()
+            .map(|change| change.operation)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            operations,
+            [Operation::Insert, Operation::Update, Operation::Delete]
+        );
+
+        let update = &transaction.changes[1];
+        assert_eq!(update.schema, "public");
+        assert_eq!(update.table, "users");
+        assert_eq!(update.before.as_ref().unwrap()["id"], Value::Integer(1));
+        assert_eq!(update.after.as_ref().unwrap()["name"], Value::from("bob"));
+    }
+
+    #[test]
+    fn rejects_change_outside_of_transaction() {
+        let mut decoder = Decoder::default();
+        assert!(matches!(
+            decoder.decode(0, r#"{"action":"D","schema":"public","table":"users"}"#),
+            Err(DecodeError::NoTransaction)
+        ));
+    }
+}
@@ -1,4 +1,5 @@
 pub(crate) mod parser;
+mod relabel;
 mod remote_write;
 mod scrape;
 
@@ -1,13 +1,22 @@
-use std::cmp::Ordering;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+};
 
 use chrono::{DateTime, TimeZone, Utc};
-use prometheus_parser::{proto, GroupKind, MetricGroup, ParserError};
+use lookup::path;
+use ordered_float::NotNan;
+use prometheus_parser::{proto, GroupKind, MetricGroup, ParserError, METRIC_NAME_LABEL};
 
 use crate::event::{
     metric::{Bucket, Metric, MetricKind, MetricTags, MetricValue, Quantile},
-    Event,
+    Event, Value,
 };
 
+/// The metadata key under which the exemplars and metric metadata of remote write requests are
+/// attached to metric events.
+const REMOTE_WRITE_METADATA_KEY: &str = "prometheus_remote_write";
+
 fn utc_timestamp(timestamp: Option<i64>, default: DateTime<Utc>) -> DateTime<Utc> {
     timestamp
         .and_then(|timestamp| {
@@ -21,8 +30,143 @@ pub(super) fn parse_text(packet: &str) -> Result<Vec<Event>, ParserError> {
     prometheus_parser::parse_text(packet).map(reparse_groups)
 }
 
-pub(super) fn parse_request(request: proto::WriteRequest) -> Result<Vec<Event>, ParserError> {
-    prometheus_parser::parse_request(request).map(reparse_groups)
+pub(super) fn parse_request(mut request: proto::WriteRequest) -> Result<Vec<Event>, ParserError> {
+    let annotations = Annotations::take(&mut request);
+    prometheus_parser::parse_request(request).map(|groups| {
+        let mut events = reparse_groups(groups);
+        if !annotations.is_empty() {
+            for event in &mut events {
+                annotations.apply(event.as_mut_metric());
+            }
+        }
+        events
+    })
+}
+
+type SeriesKey = (String, BTreeMap<String, String>);
+
+/// The exemplars and metric metadata of a remote write request, which don't fit in the metric
+/// values and are attached to the metadata of the matching metric events instead.
+#[derive(Default)]
+struct Annotations {
+    /// Exemplars, by the name and labels of their series.
+    exemplars: HashMap<SeriesKey, Vec<proto::Exemplar>>,
+    /// Exemplars of histogram buckets, by the name and labels of their histogram.
+    bucket_exemplars: HashMap<SeriesKey, Vec<proto::Exemplar>>,
+    /// Help and unit, by metric family name.
+    metadata: HashMap<String, (String, String)>,
+}
+
+impl Annotations {
+    fn take(request: &mut proto::WriteRequest) -> Self {
+        let mut annotations = Self::default();
+
+        for metadata in &request.metadata {
+            if !metadata.help.is_empty() || !metadata.unit.is_empty() {
+                annotations.metadata.insert(
+                    metadata.metric_family_name.clone(),
+                    (metadata.help.clone(), metadata.unit.clone()),
+                );
+            }
+        }
+
+        for timeseries in &mut request.timeseries {
+            if timeseries.exemplars.is_empty() {
+                continue;
+            }
+            let exemplars = std::mem::take(&mut timeseries.exemplars);
+            let mut labels: BTreeMap<String, String> = timeseries
+                .labels
+                .iter()
+                .map(|label| (label.name.clone(), label.value.clone()))
+                .collect();
+            let name = match labels.remove(METRIC_NAME_LABEL) {
+                Some(name) => name,
+                None => continue,
+            };
+
+            // Bucket series are grouped into a single histogram, if typed as such, so their
+            // exemplars are attached to it.
+            if let Some(basename) = name.strip_suffix("_bucket") {
+                let mut labels = labels.clone();
+                if labels.remove("le").is_some() {
+                    annotations
+                        .bucket_exemplars
+                        .entry((basename.to_string(), labels))
+                        .or_default()
+                        .extend(exemplars.iter().cloned());
+                }
+            }
+            annotations
+                .exemplars
+                .entry((name, labels))
+                .or_default()
+                .extend(exemplars);
+        }
+
+        annotations
+    }
+
+    fn is_empty(&self) -> bool {
+        self.exemplars.is_empty() && self.metadata.is_empty()
+    }
+
+    fn apply(&self, metric: &mut Metric) {
+        let exemplars = if self.exemplars.is_empty() {
+            None
+        } else {
+            let key = (
+                metric.name().to_string(),
+                metric
+                    .tags()
+                    .map(|tags| {
+                        tags.iter_single()
+                            .map(|(name, value)| (name.to_string(), value.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            );
+            match metric.value() {
+                MetricValue::AggregatedHistogram { .. } => self.bucket_exemplars.get(&key),
+                _ => self.exemplars.get(&key),
+            }
+        };
+        let metadata = self.metadata.get(metric.name());
+
+        let value = metric.metadata_mut().value_mut();
+        if let Some(exemplars) = exemplars {
+            value.insert(
+                path!(REMOTE_WRITE_METADATA_KEY, "exemplars"),
+                Value::Array(exemplars.iter().map(exemplar_value).collect()),
+            );
+        }
+        if let Some((help, unit)) = metadata {
+            if !help.is_empty() {
+                value.insert(path!(REMOTE_WRITE_METADATA_KEY, "help"), help.as_str());
+            }
+            if !unit.is_empty() {
+                value.insert(path!(REMOTE_WRITE_METADATA_KEY, "unit"), unit.as_str());
+            }
+        }
+    }
+}
+
+fn exemplar_value(exemplar: &proto::Exemplar) -> Value {
+    let labels = exemplar
+        .labels
+        .iter()
+        .map(|label| (label.name.clone(), Value::from(label.value.as_str())))
+        .collect::<BTreeMap<_, _>>();
+    let mut value = BTreeMap::new();
+    value.insert("labels".to_string(), Value::from(labels));
+    value.insert(
+        "value".to_string(),
+        NotNan::new(exemplar.value).map_or(Value::Null, Value::Float),
+    );
+    if let Some(timestamp) = Utc.timestamp_millis_opt(exemplar.timestamp).single() {
+        value.insert("timestamp".to_string(), Value::Timestamp(timestamp));
+    }
+    Value::from(value)
 }
 
 fn reparse_groups(groups: Vec<MetricGroup>) -> Vec<Event> {
@@ -1011,4 +1155,107 @@ mod test {
             ]
         );
     }
+
+    fn label(name: &str, value: &str) -> proto::Label {
+        proto::Label {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    fn exemplar(trace_id: &str, value: f64) -> proto::Exemplar {
+        proto::Exemplar {
+            labels: vec![label("trace_id", trace_id)],
+            value,
+            timestamp: 1612411506789,
+        }
+    }
+
+    #[test]
+    fn test_request_exemplars_and_metadata() {
+        let request = proto::WriteRequest {
+            metadata: vec![proto::MetricMetadata {
+                r#type: proto::MetricType::Histogram as i32,
+                metric_family_name: "latency".into(),
+                help: "Request latency".into(),
+                unit: "seconds".into(),
+            }],
+            timeseries: vec![
+                proto::TimeSeries {
+                    labels: vec![label("__name__", "requests_total"), label("code", "200")],
+                    samples: vec![proto::Sample {
+                        value: 10.0,
+                        timestamp: 1612411506789,
+                    }],
+                    exemplars: vec![exemplar("abc", 1.0)],
+                    histograms: Vec::new(),
+                },
+                proto::TimeSeries {
+                    labels: vec![label("__name__", "latency_bucket"), label("le", "0.1")],
+                    samples: vec![proto::Sample {
+                        value: 3.0,
+                        timestamp: 1612411506789,
+                    }],
+                    exemplars: vec![exemplar("def", 0.05)],
+                    histograms: Vec::new(),
+                },
+                proto::TimeSeries {
+                    labels: vec![label("__name__", "latency_bucket"), label("le", "+Inf")],
+                    samples: vec![proto::Sample {
+                        value: 4.0,
+                        timestamp: 1612411506789,
+                    }],
+                    exemplars: vec![exemplar("ghi", 2.5)],
+                    histograms: Vec::new(),
+                },
+            ],
+        };
+
+        let metrics = super::parse_request(request)
+            .unwrap()
+            .into_iter()
+            .map(Event::into_metric)
+            .collect::<Vec<_>>();
+        assert_eq!(metrics.len(), 2);
+
+        let latency = metrics.iter().find(|m| m.name() == "latency").unwrap();
+        let metadata = latency.metadata().value();
+        assert_eq!(
+            metadata.get("prometheus_remote_write.help"),
+            Some(&Value::from("Request latency"))
+        );
+        assert_eq!(
+            metadata.get("prometheus_remote_write.unit"),
+            Some(&Value::from("seconds"))
+        );
+        let exemplars = metadata
+            .get("prometheus_remote_write.exemplars")
+            .and_then(Value::as_array)
+            .unwrap();
+        assert_eq!(exemplars.len(), 2);
+        assert_eq!(
+            exemplars[0].get("labels.trace_id"),
+            Some(&Value::from("def"))
+        );
+        assert_eq!(
+            exemplars[0].get("timestamp"),
+            Some(&Value::Timestamp(*TIMESTAMP))
+        );
+
+        let requests = metrics
+            .iter()
+            .find(|m| m.name() == "requests_total")
+            .unwrap();
+        let exemplars = requests
+            .metadata()
+            .value()
+            .get("prometheus_remote_write.exemplars")
+            .and_then(Value::as_array)
+            .unwrap();
+        assert_eq!(exemplars.len(), 1);
+        assert_eq!(
+            exemplars[0].get("value"),
+            Some(&Value::Float(NotNan::new(1.0).unwrap()))
+        );
+    }
 }
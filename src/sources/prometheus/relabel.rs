@@ -0,0 +1,341 @@
+use std::convert::TryFrom;
+
+use regex::Regex;
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+use vector_core::event::Metric;
+
+/// The name of the label holding the metric name, as in Prometheus.
+const METRIC_NAME_LABEL: &str = "__name__";
+
+/// Action to perform when relabeling a metric.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RelabelAction {
+    /// Replaces `target_label` with `replacement` when `regex` matches the source labels.
+    ///
+    /// The label is removed if the replacement is empty.
+    #[default]
+    Replace,
+
+    /// Drops metrics whose source labels don't match `regex`.
+    Keep,
+
+    /// Drops metrics whose source labels match `regex`.
+    Drop,
+
+    /// Copies the labels whose name matches `regex` to the label named after `replacement`.
+    Labelmap,
+}
+
+/// A relabeling rule applied to scraped metrics.
+///
+/// This matches Prometheus’ `metric_relabel_configs` configuration, where the `__name__` label refers to the
+/// name of the metric.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RelabelConfig {
+    /// The labels whose values are concatenated, with `separator`, to be matched against `regex`.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "__name__"))]
+    pub source_labels: Vec<String>,
+
+    /// The separator placed between the values of the source labels.
+    #[serde(default = "default_separator")]
+    pub separator: String,
+
+    /// The regular expression matched against the source labels, or the label names for `labelmap`.
+    ///
+    /// The expression is anchored at both ends.
+    #[serde(default = "default_regex")]
+    #[configurable(metadata(docs::examples = "go_.*"))]
+    pub regex: String,
+
+    /// The label set by `replace`.
+    ///
+    /// The capture groups of `regex` can be referenced, as in `replacement`.
+    #[configurable(metadata(docs::examples = "job"))]
+    pub target_label: Option<String>,
+
+    /// The value of the label set by `replace`, or the name of the labels copied by `labelmap`.
+    ///
+    /// The capture groups of `regex` can be referenced with `$1`, `${1}`, or `${name}`.
+    #[serde(default = "default_replacement")]
+    pub replacement: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub action: RelabelAction,
+}
+
+fn default_separator() -> String {
+    ";".to_string()
+}
+
+fn default_regex() -> String {
+    "(.*)".to_string()
+}
+
+fn default_replacement() -> String {
+    "$1".to_string()
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("unable to parse relabeling regex from {:?}: {}", regex, source))]
+    InvalidRegex { regex: String, source: regex::Error },
+    #[snafu(display("the `replace` relabeling action requires `target_label` to be set"))]
+    MissingTargetLabel,
+}
+
+/// A compiled relabeling rule.
+#[derive(Clone, Debug)]
+pub struct Relabeler {
+    source_labels: Vec<String>,
+    separator: String,
+    regex: Regex,
+    target_label: String,
+    replacement: String,
+    action: RelabelAction,
+}
+
+impl TryFrom<&RelabelConfig> for Relabeler {
+    type Error = Error;
+
+    fn try_from(config: &RelabelConfig) -> Result<Self, Self::Error> {
+        let regex =
+            Regex::new(&format!("^(?:{})$", config.regex)).with_context(|_| InvalidRegexSnafu {
+                regex: config.regex.clone(),
+            })?;
+        let target_label = match (config.action, &config.target_label) {
+            (_, Some(target_label)) => target_label.clone(),
+            (RelabelAction::Replace, None) => return Err(Error::MissingTargetLabel),
+            (_, None) => String::new(),
+        };
+
+        Ok(Self {
+            source_labels: config.source_labels.clone(),
+            separator: config.separator.clone(),
+            regex,
+            target_label,
+            replacement: config.replacement.clone(),
+            action: config.action,
+        })
+    }
+}
+
+impl Relabeler {
+    /// Applies the rule to `metric`, returning `None` if it is dropped.
+    pub fn apply(&self, metric: Metric) -> Option<Metric> {
+        match self.action {
+            RelabelAction::Keep => self
+                .regex
+                .is_match(&self.source_value(&metric))
+                .then_some(metric),
+            RelabelAction::Drop => {
+                (!self.regex.is_match(&self.source_value(&metric))).then_some(metric)
+            }
+            RelabelAction::Replace => Some(self.replace(metric)),
+            RelabelAction::Labelmap => Some(self.labelmap(metric)),
+        }
+    }
+
+    fn source_value(&self, metric: &Metric) -> String {
+        self.source_labels
+            .iter()
+            .map(|label| match label.as_str() {
+                METRIC_NAME_LABEL => metric.name().to_string(),
+                label => metric.tag_value(label).unwrap_or_default(),
+            })
+            .collect::<Vec<_>>()
+            .join(&self.separator)
+    }
+
+    fn replace(&self, mut metric: Metric) -> Metric {
+        let source_value = self.source_value(&metric);
+        let captures = match self.regex.captures(&source_value) {
+            Some(captures) => captures,
+            None => return metric,
+        };
+
+        let mut target_label = String::new();
+        captures.expand(&self.target_label, &mut target_label);
+        let mut value = String::new();
+        captures.expand(&self.replacement, &mut value);
+
+        match (target_label.as_str(), value.is_empty()) {
+            ("", _) => {}
+            (METRIC_NAME_LABEL, true) => {}
+            (METRIC_NAME_LABEL, false) => metric = metric.with_name(value),
+            (_, true) => {
+                metric.remove_tag(&target_label);
+            }
+            (_, false) => {
+                metric.replace_tag(target_label, value);
+            }
+        }
+        metric
+    }
+
+    fn labelmap(&self, mut metric: Metric) -> Metric {
+        let mapped = metric
+            .tags()
+            .map(|tags| {
+                tags.iter_single()
+                    .filter_map(|(name, value)| {
+                        let captures = self.regex.captures(name)?;
+                        let mut target_label = String::new();
+                        captures.expand(&self.replacement, &mut target_label);
+                        (!target_label.is_empty()).then(|| (target_label, value.to_string()))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        for (name, value) in mapped {
+            metric.replace_tag(name, value);
+        }
+        metric
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::{MetricKind, MetricTags, MetricValue};
+
+    use super::*;
+
+    fn metric(name: &str, tags: &[(&str, &str)]) -> Metric {
+        Metric::new(
+            name,
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.0 },
+        )
+        .with_tags(Some(
+            tags.iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect::<MetricTags>(),
+        ))
+    }
+
+    fn relabeler(config: &str) -> Relabeler {
+        let config: RelabelConfig = toml::from_str(config).unwrap();
+        Relabeler::try_from(&config).unwrap()
+    }
+
+    #[test]
+    fn keeps_matching_metrics() {
+        let relabeler = relabeler(
+            r#"
+            action = "keep"
+            source_labels = ["__name__"]
+            regex = "http_.*"
+            "#,
+        );
+
+        assert!(relabeler.apply(metric("http_requests", &[])).is_some());
+        assert!(relabeler.apply(metric("go_goroutines", &[])).is_none());
+        // The regex is anchored.
+        assert!(relabeler.apply(metric("go_http_requests", &[])).is_none());
+    }
+
+    #[test]
+    fn drops_matching_metrics() {
+        let relabeler = relabeler(
+            r#"
+            action = "drop"
+            source_labels = ["__name__", "code"]
+            regex = "http_requests;5.."
+            "#,
+        );
+
+        assert!(relabeler
+            .apply(metric("http_requests", &[("code", "503")]))
+            .is_none());
+        assert!(relabeler
+            .apply(metric("http_requests", &[("code", "200")]))
+            .is_some());
+    }
+
+    #[test]
+    fn replaces_labels() {
+        let relabeler = relabeler(
+            r#"
+            source_labels = ["instance"]
+            regex = "([^:]+):\\d+"
+            target_label = "host"
+            "#,
+        );
+
+        let relabeled = relabeler
+            .apply(metric("up", &[("instance", "localhost:9090")]))
+            .unwrap();
+        assert_eq!(relabeled.tag_value("host"), Some("localhost".to_string()));
+
+        let unchanged = relabeler
+            .apply(metric("up", &[("instance", "localhost")]))
+            .unwrap();
+        assert_eq!(unchanged.tag_value("host"), None);
+    }
+
+    #[test]
+    fn removes_labels_replaced_with_empty_values() {
+        let relabeler = relabeler(
+            r#"
+            regex = ".*"
+            replacement = ""
+            target_label = "pod_uid"
+            "#,
+        );
+
+        let relabeled = relabeler
+            .apply(metric("up", &[("pod_uid", "d2a3a4")]))
+            .unwrap();
+        assert_eq!(relabeled.tag_value("pod_uid"), None);
+    }
+
+    #[test]
+    fn replaces_metric_names() {
+        let relabeler = relabeler(
+            r#"
+            source_labels = ["__name__"]
+            regex = "legacy_(.*)"
+            target_label = "__name__"
+            "#,
+        );
+
+        let relabeled = relabeler.apply(metric("legacy_up", &[])).unwrap();
+        assert_eq!(relabeled.name(), "up");
+    }
+
+    #[test]
+    fn maps_labels() {
+        let relabeler = relabeler(
+            r#"
+            action = "labelmap"
+            regex = "__meta_(.+)"
+            "#,
+        );
+
+        let relabeled = relabeler
+            .apply(metric("up", &[("__meta_zone", "eu-west-1a")]))
+            .unwrap();
+        assert_eq!(relabeled.tag_value("zone"), Some("eu-west-1a".to_string()));
+        assert_eq!(
+            relabeled.tag_value("__meta_zone"),
+            Some("eu-west-1a".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_replace_without_target_label() {
+        let config: RelabelConfig = toml::from_str(r#"source_labels = ["job"]"#).unwrap();
+
+        assert!(matches!(
+            Relabeler::try_from(&config),
+            Err(Error::MissingTargetLabel)
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, convert::TryFrom, sync::Arc};
 
 use bytes::Bytes;
 use futures_util::FutureExt;
@@ -8,11 +8,14 @@ use snafu::{ResultExt, Snafu};
 use vector_config::configurable_component;
 use vector_core::{config::LogNamespace, event::Event};
 
-use super::parser;
+use super::{
+    parser,
+    relabel::{RelabelConfig, Relabeler},
+};
 use crate::sources::util::http::HttpMethod;
 use crate::{
     config::{self, GenerateConfig, Output, SourceConfig, SourceContext},
-    http::Auth,
+    http::{Auth, MaybeAuth},
     internal_events::PrometheusParseError,
     sources::{
         self,
@@ -82,6 +85,15 @@ pub struct PrometheusScrapeConfig {
     #[serde(default)]
     query: HashMap<String, Vec<String>>,
 
+    /// Relabeling rules applied, in order, to the scraped metrics.
+    ///
+    /// The rules are applied after the instance and endpoint tags are added. Metrics dropped by a rule are discarded
+    /// before being sent downstream, which allows high-cardinality series to be filtered out at the source.
+    ///
+    /// This matches Prometheus’ `metric_relabel_configs` configuration.
+    #[serde(default)]
+    relabel_configs: Vec<RelabelConfig>,
+
     #[configurable(derived)]
     tls: Option<TlsConfig>,
 
@@ -98,6 +110,7 @@ impl GenerateConfig for PrometheusScrapeConfig {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
             query: HashMap::new(),
+            relabel_configs: Vec::new(),
             tls: None,
             auth: None,
         })
@@ -108,6 +121,8 @@ impl GenerateConfig for PrometheusScrapeConfig {
 #[async_trait::async_trait]
 impl SourceConfig for PrometheusScrapeConfig {
     async fn build(&self, cx: SourceContext) -> Result<sources::Source> {
+        self.auth.ensure_static()?;
+
         let urls = self
             .endpoints
             .iter()
@@ -115,11 +130,17 @@ impl SourceConfig for PrometheusScrapeConfig {
             .map(|r| r.map(|uri| build_url(&uri, &self.query)))
             .collect::<std::result::Result<Vec<Uri>, sources::BuildError>>()?;
         let tls = TlsSettings::from_options(&self.tls)?;
+        let relabelers = self
+            .relabel_configs
+            .iter()
+            .map(Relabeler::try_from)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
 
         let builder = PrometheusScrapeBuilder {
             honor_labels: self.honor_labels,
             instance_tag: self.instance_tag.clone(),
             endpoint_tag: self.endpoint_tag.clone(),
+            relabelers: Arc::new(relabelers),
         };
 
         let inputs = GenericHttpClientInputs {
@@ -171,6 +192,7 @@ struct PrometheusScrapeBuilder {
     honor_labels: bool,
     instance_tag: Option<String>,
     endpoint_tag: Option<String>,
+    relabelers: Arc<Vec<Relabeler>>,
 }
 
 impl HttpClientBuilder for PrometheusScrapeBuilder {
@@ -202,6 +224,7 @@ impl HttpClientBuilder for PrometheusScrapeBuilder {
         PrometheusScrapeContext {
             instance_info,
             endpoint_info,
+            relabelers: Arc::clone(&self.relabelers),
         }
     }
 }
@@ -210,6 +233,7 @@ impl HttpClientBuilder for PrometheusScrapeBuilder {
 struct PrometheusScrapeContext {
     instance_info: Option<InstanceInfo>,
     endpoint_info: Option<EndpointInfo>,
+    relabelers: Arc<Vec<Relabeler>>,
 }
 
 impl HttpClientContext for PrometheusScrapeContext {
@@ -256,6 +280,19 @@ impl HttpClientContext for PrometheusScrapeContext {
                         }
                     }
                 }
+                if !self.relabelers.is_empty() {
+                    events = events
+                        .into_iter()
+                        .filter_map(|event| {
+                            self.relabelers
+                                .iter()
+                                .try_fold(event.into_metric(), |metric, relabeler| {
+                                    relabeler.apply(metric)
+                                })
+                                .map(Event::Metric)
+                        })
+                        .collect();
+                }
                 Some(events)
             }
             Err(error) => {
@@ -333,6 +370,7 @@ mod test {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
             query: HashMap::new(),
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
@@ -366,6 +404,7 @@ mod test {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
             query: HashMap::new(),
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
@@ -397,6 +436,64 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_prometheus_relabel_configs() {
+        let in_addr = next_addr();
+
+        let dummy_endpoint = warp::path!("metrics").map(|| {
+            r#"
+                    promhttp_metric_handler_requests_total{code="200"} 100 1612411516789
+                    go_goroutines 12 1612411516789
+                "#
+        });
+
+        tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+        wait_for_tcp(in_addr).await;
+
+        let config = PrometheusScrapeConfig {
+            endpoints: vec![format!("http://{}/metrics", in_addr)],
+            scrape_interval_secs: 1,
+            instance_tag: Some("instance".to_string()),
+            endpoint_tag: None,
+            honor_labels: false,
+            query: HashMap::new(),
+            relabel_configs: vec![
+                toml::from_str(
+                    r#"
+                    action = "drop"
+                    source_labels = ["__name__"]
+                    regex = "go_.*"
+                    "#,
+                )
+                .unwrap(),
+                toml::from_str(
+                    r#"
+                    source_labels = ["instance"]
+                    regex = "([^:]+):.*"
+                    target_label = "host"
+                    "#,
+                )
+                .unwrap(),
+            ],
+            auth: None,
+            tls: None,
+        };
+
+        let events = run_and_assert_source_compliance(
+            config,
+            Duration::from_secs(3),
+            &HTTP_PULL_SOURCE_TAGS,
+        )
+        .await;
+        assert!(!events.is_empty());
+
+        for event in events {
+            let metric = event.into_metric();
+            assert_eq!(metric.name(), "promhttp_metric_handler_requests_total");
+            assert_eq!(metric.tag_value("host"), Some(in_addr.ip().to_string()));
+        }
+    }
+
     #[tokio::test]
     async fn test_prometheus_do_not_honor_labels() {
         let in_addr = next_addr();
@@ -417,6 +514,7 @@ mod test {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
             query: HashMap::new(),
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
@@ -487,6 +585,7 @@ mod test {
                     vec!["val1".to_string(), "val2".to_string()],
                 ),
             ]),
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
@@ -589,6 +688,7 @@ mod test {
                 endpoint_tag: None,
                 honor_labels: false,
                 query: HashMap::new(),
+                relabel_configs: Vec::new(),
                 scrape_interval_secs: 1,
                 tls: None,
                 auth: None,
@@ -679,6 +779,7 @@ mod integration_tests {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
             query: HashMap::new(),
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
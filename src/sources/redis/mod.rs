@@ -18,14 +18,18 @@ use vector_core::EstimatedJsonEncodedSizeOf;
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
-    config::{log_schema, GenerateConfig, Output, SourceConfig, SourceContext},
+    config::{
+        log_schema, GenerateConfig, Output, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext,
+    },
     event::Event,
     internal_events::{EventsReceived, StreamClosedError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
 };
 
 mod channel;
 mod list;
+mod stream;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -47,6 +51,11 @@ pub enum DataTypeConfig {
     ///
     /// This is based on Redis' Pub/Sub capabilities.
     Channel,
+
+    /// The `stream` data type.
+    ///
+    /// Messages are read from the stream with a consumer group, and are acknowledged once they have been processed.
+    Stream,
 }
 
 /// Options for the Redis `list` data type.
@@ -72,6 +81,78 @@ pub enum Method {
     Rpop,
 }
 
+/// Options for the Redis `stream` data type.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The name of the consumer group to read messages with.
+    ///
+    /// The consumer group, and the stream itself, are created if they don't exist yet.
+    #[serde(default = "default_group")]
+    #[derivative(Default(value = "default_group()"))]
+    group: String,
+
+    /// The name of the consumer within the consumer group.
+    ///
+    /// Each Vector instance reading with the same consumer group must use a distinct name. By default, the hostname is used.
+    consumer: Option<String>,
+
+    /// The ID of the first message to read when the consumer group is created.
+    ///
+    /// By default, only the messages added to the stream after the consumer group is created are read. Use `0` to read the whole stream.
+    #[serde(default = "default_start_id")]
+    #[derivative(Default(value = "default_start_id()"))]
+    start_id: String,
+
+    /// The name of the message field to decode events from.
+    ///
+    /// Messages without this field are acknowledged and discarded.
+    #[serde(default = "default_field")]
+    #[derivative(Default(value = "default_field()"))]
+    field: String,
+
+    /// Sets the name of the log field to use to add the message ID to each event.
+    ///
+    /// By default, this is not set and the field will not be automatically added.
+    id_key: Option<String>,
+
+    /// The maximum number of messages to read at once.
+    #[serde(default = "default_batch_size")]
+    #[derivative(Default(value = "default_batch_size()"))]
+    batch_size: usize,
+
+    /// The minimum time, in milliseconds, a message must have been pending for before it is claimed.
+    ///
+    /// Messages that were delivered to a consumer of the group but never acknowledged, for example because the consumer stopped, or because the events could not be delivered, are claimed and read again by this consumer once they have been pending for this long. Pending messages are checked for at the same interval.
+    ///
+    /// Set to `0` to never claim pending messages.
+    #[serde(default = "default_claim_min_idle_ms")]
+    #[derivative(Default(value = "default_claim_min_idle_ms()"))]
+    claim_min_idle_ms: u64,
+}
+
+fn default_group() -> String {
+    "vector".into()
+}
+
+fn default_start_id() -> String {
+    "$".into()
+}
+
+fn default_field() -> String {
+    "message".into()
+}
+
+const fn default_batch_size() -> usize {
+    100
+}
+
+const fn default_claim_min_idle_ms() -> u64 {
+    60_000
+}
+
 pub struct ConnectionInfo {
     protocol: &'static str,
     endpoint: String,
@@ -96,13 +177,16 @@ impl From<&redis::ConnectionInfo> for ConnectionInfo {
 #[derive(Clone, Debug, Derivative)]
 #[serde(deny_unknown_fields)]
 pub struct RedisSourceConfig {
-    /// The Redis data type (`list` or `channel`) to use.
+    /// The Redis data type (`list`, `channel` or `stream`) to use.
     #[serde(default)]
     data_type: DataTypeConfig,
 
     #[configurable(derived)]
     list: Option<ListOption>,
 
+    #[configurable(derived)]
+    stream: Option<StreamOption>,
+
     /// The Redis URL to connect to.
     ///
     /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be `redis` or `rediss` for connections secured via TLS.
@@ -128,6 +212,10 @@ pub struct RedisSourceConfig {
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -153,8 +241,9 @@ impl GenerateConfig for RedisSourceConfig {
 impl SourceConfig for RedisSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
 
-        // A key must be specified to actually query i.e. the list to pop from, or the channel to subscribe to.
+        // A key must be specified to actually query i.e. the list to pop from, the channel to subscribe to, or the stream to read.
         if self.key.is_empty() {
             return Err("`key` cannot be empty.".into());
         }
@@ -185,6 +274,10 @@ impl SourceConfig for RedisSourceConfig {
                 handler.watch(method).await
             }
             DataTypeConfig::Channel => handler.subscribe(connection_info).await,
+            DataTypeConfig::Stream => {
+                let options = self.stream.clone().unwrap_or_default();
+                handler.consume(options, acknowledgements).await
+            }
         }
     }
 
@@ -197,7 +290,7 @@ impl SourceConfig for RedisSourceConfig {
             .map(|x| owned_value_path!(x))
             .map(LegacyKey::InsertIfEmpty);
 
-        let schema_definition = self
+        let mut schema_definition = self
             .decoding
             .schema_definition(log_namespace)
             .with_source_metadata(
@@ -209,11 +302,28 @@ impl SourceConfig for RedisSourceConfig {
             )
             .with_standard_vector_source_metadata();
 
+        if let DataTypeConfig::Stream = self.data_type {
+            let id_key_path = self
+                .stream
+                .as_ref()
+                .and_then(|stream| stream.id_key.as_ref())
+                .map(|x| owned_value_path!(x))
+                .map(LegacyKey::InsertIfEmpty);
+
+            schema_definition = schema_definition.with_source_metadata(
+                Self::NAME,
+                id_key_path,
+                &owned_value_path!("id"),
+                Kind::bytes(),
+                None,
+            );
+        }
+
         vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        matches!(self.data_type, DataTypeConfig::Stream)
     }
 }
 
@@ -230,11 +340,27 @@ pub(self) struct InputHandler {
 
 impl InputHandler {
     async fn handle_line(&mut self, line: String) -> Result<(), ()> {
+        let events = self.decode_line(&line).await;
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let count = events.len();
+        if let Err(error) = self.cx.out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            return Err(());
+        }
+        Ok(())
+    }
+
+    /// Decodes the events of a message, adding the Redis metadata to them.
+    async fn decode_line(&mut self, line: &str) -> Vec<Event> {
         let now = Utc::now();
 
         self.bytes_received.emit(ByteSize(line.len()));
 
-        let mut stream = FramedRead::new(line.as_ref(), self.decoder.clone());
+        let mut decoded = Vec::new();
+        let mut stream = FramedRead::new(line.as_bytes(), self.decoder.clone());
         while let Some(next) = stream.next().await {
             match next {
                 Ok((events, _byte_size)) => {
@@ -242,7 +368,7 @@ impl InputHandler {
                     let byte_size = events.estimated_json_encoded_size_of();
                     self.events_received.emit(CountByteSize(count, byte_size));
 
-                    let events = events.into_iter().map(|mut event| {
+                    decoded.extend(events.into_iter().map(|mut event| {
                         if let Event::Log(ref mut log) = event {
                             self.log_namespace.insert_vector_metadata(
                                 log,
@@ -272,12 +398,7 @@ impl InputHandler {
                         };
 
                         event
-                    });
-
-                    if let Err(error) = self.cx.out.send_batch(events).await {
-                        emit!(StreamClosedError { error, count });
-                        return Err(());
-                    }
+                    }));
                 }
                 Err(error) => {
                     // Error is logged by `crate::codecs::Decoder`, no further
@@ -288,7 +409,7 @@ impl InputHandler {
                 }
             }
         }
-        Ok(())
+        decoded
     }
 }
 
@@ -335,11 +456,13 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
             log_namespace: Some(false),
         };
 
@@ -367,11 +490,13 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: Some("remapped_key".into()),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
             log_namespace: Some(true),
         };
 
@@ -408,11 +533,51 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Lpop,
             }),
+            stream: None,
+            url: REDIS_SERVER.to_owned(),
+            key: key.clone(),
+            redis_key: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            acknowledgements: Default::default(),
+            log_namespace: Some(false),
+        };
+
+        let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
+
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "1".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "2".into());
+        assert_eq!(events[2].as_log()[log_schema().message_key()], "3".into());
+    }
+
+    #[tokio::test]
+    async fn redis_source_stream_consumer_group() {
+        // Add some test data to a stream which we'll read from.
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_tokio_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        debug!("Test key name: {}.", key);
+
+        for value in ["1", "2", "3"] {
+            let _: String = conn.xadd(&key, "*", &[("message", value)]).await.unwrap();
+        }
+
+        let config = RedisSourceConfig {
+            data_type: DataTypeConfig::Stream,
+            list: None,
+            stream: Some(StreamOption {
+                consumer: Some("consumer-1".into()),
+                start_id: "0".into(),
+                id_key: Some("redis_id".into()),
+                ..Default::default()
+            }),
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: true.into(),
             log_namespace: Some(false),
         };
 
@@ -421,6 +586,7 @@ mod integration_test {
         assert_eq!(events[0].as_log()[log_schema().message_key()], "1".into());
         assert_eq!(events[1].as_log()[log_schema().message_key()], "2".into());
         assert_eq!(events[2].as_log()[log_schema().message_key()], "3".into());
+        assert!(events[0].as_log().get("redis_id").is_some());
     }
 
     #[tokio::test]
@@ -432,11 +598,13 @@ mod integration_test {
         let config = RedisSourceConfig {
             data_type: DataTypeConfig::Channel,
             list: None,
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
             log_namespace: Some(false),
         };
 
@@ -0,0 +1,291 @@
+use std::time::{Duration, Instant};
+
+use futures::StreamExt;
+use lookup::{lookup_v2::BorrowedSegment, path};
+use redis::{
+    aio::ConnectionManager,
+    streams::{
+        StreamClaimReply, StreamId, StreamPendingCountReply, StreamReadOptions, StreamReadReply,
+    },
+    AsyncCommands, RedisResult,
+};
+use snafu::{ResultExt, Snafu};
+use tracing_futures::Instrument;
+use vector_common::finalizer::UnorderedFinalizer;
+use vector_config::NamedComponent;
+use vector_core::config::LegacyKey;
+
+use super::{InputHandler, RedisSourceConfig, StreamOption};
+use crate::{
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{
+        RedisReceiveEventError, RedisStreamAckError, RedisStreamFieldMissingError,
+        StreamClosedError,
+    },
+    sources::Source,
+};
+
+/// How long to wait for new messages, in milliseconds, before checking for pending messages to claim.
+const READ_BLOCK_MS: usize = 1000;
+
+type Finalizer = UnorderedFinalizer<Vec<String>>;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed to create connection: {}", source))]
+    Connection { source: redis::RedisError },
+    #[snafu(display("Failed to create consumer group: {}", source))]
+    CreateGroup { source: redis::RedisError },
+}
+
+impl InputHandler {
+    pub(super) async fn consume(
+        mut self,
+        options: StreamOption,
+        acknowledgements: bool,
+    ) -> crate::Result<Source> {
+        let mut conn = self
+            .client
+            .get_tokio_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+        // Reading blocks the connection until messages are available, so messages are
+        // acknowledged on a separate one.
+        let ack_conn = self
+            .client
+            .get_tokio_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+
+        create_group(&mut conn, &self.key, &options)
+            .await
+            .context(CreateGroupSnafu {})?;
+
+        let consumer = match &options.consumer {
+            Some(consumer) => consumer.clone(),
+            None => crate::get_hostname()?,
+        };
+        let claim_interval = (options.claim_min_idle_ms > 0)
+            .then(|| Duration::from_millis(options.claim_min_idle_ms));
+
+        Ok(Box::pin(async move {
+            let mut shutdown = self.cx.shutdown.clone();
+            let acker = Acker {
+                conn: ack_conn,
+                key: self.key.clone(),
+                group: options.group.clone(),
+            };
+
+            let (finalizer, mut ack_stream) =
+                Finalizer::maybe_new(acknowledgements, shutdown.clone());
+            if finalizer.is_some() {
+                let acker = acker.clone();
+                tokio::spawn(
+                    async move {
+                        // Messages of batches that weren't delivered are left pending, to be
+                        // claimed and read again.
+                        while let Some((status, ids)) = ack_stream.next().await {
+                            if status == BatchStatus::Delivered {
+                                acker.ack(ids).await;
+                            }
+                        }
+                    }
+                    .in_current_span(),
+                );
+            }
+
+            let mut last_claim: Option<Instant> = None;
+            loop {
+                let claim_due = claim_interval.map_or(false, |interval| {
+                    last_claim.map_or(true, |last_claim| last_claim.elapsed() >= interval)
+                });
+
+                let res = if claim_due {
+                    tokio::select! {
+                        res = claim(&mut conn, &self.key, &options, &consumer) => {
+                            // Keep claiming while full batches of pending messages are returned.
+                            last_claim = match &res {
+                                Ok(messages) if messages.len() >= options.batch_size => None,
+                                _ => Some(Instant::now()),
+                            };
+                            res
+                        },
+                        _ = &mut shutdown => break
+                    }
+                } else {
+                    tokio::select! {
+                        res = read(&mut conn, &self.key, &options, &consumer) => res,
+                        _ = &mut shutdown => break
+                    }
+                };
+
+                match res {
+                    Err(error) => emit!(RedisReceiveEventError::from(error)),
+                    Ok(messages) => {
+                        if let Err(()) = self
+                            .handle_messages(messages, &options, finalizer.as_ref(), &acker)
+                            .await
+                        {
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }))
+    }
+
+    async fn handle_messages(
+        &mut self,
+        messages: Vec<StreamId>,
+        options: &StreamOption,
+        finalizer: Option<&Finalizer>,
+        acker: &Acker,
+    ) -> Result<(), ()> {
+        if messages.is_empty() {
+            return Ok(());
+        }
+
+        let id_key_path = options
+            .id_key
+            .as_deref()
+            .map(|x| [BorrowedSegment::from(x)]);
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(finalizer.is_some());
+        let mut events = Vec::new();
+        let mut ids = Vec::with_capacity(messages.len());
+        for message in messages {
+            match message.get::<String>(&options.field) {
+                Some(line) => {
+                    let decoded = self.decode_line(&line).await;
+                    events.extend(decoded.into_iter().map(|mut event| {
+                        if let Event::Log(ref mut log) = event {
+                            self.log_namespace.insert_source_metadata(
+                                RedisSourceConfig::NAME,
+                                log,
+                                id_key_path.as_ref().map(LegacyKey::InsertIfEmpty),
+                                path!("id"),
+                                message.id.as_str(),
+                            );
+                        }
+                        event.with_batch_notifier_option(&batch)
+                    }));
+                }
+                None => emit!(RedisStreamFieldMissingError {
+                    id: &message.id,
+                    field: &options.field,
+                }),
+            }
+            ids.push(message.id);
+        }
+        drop(batch); // Drop last reference to batch acknowledgement finalizer
+
+        let count = events.len();
+        if let Err(error) = self.cx.out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            return Err(());
+        }
+
+        match receiver {
+            Some(receiver) => finalizer
+                .expect("Finalizer must exist for the batch receiver to be created")
+                .add(ids, receiver),
+            None => acker.ack(ids).await,
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+struct Acker {
+    conn: ConnectionManager,
+    key: String,
+    group: String,
+}
+
+impl Acker {
+    async fn ack(&self, ids: Vec<String>) {
+        if ids.is_empty() {
+            return;
+        }
+
+        let mut conn = self.conn.clone();
+        let result: RedisResult<usize> = conn.xack(&self.key, &self.group, &ids).await;
+        if let Err(error) = result {
+            emit!(RedisStreamAckError {
+                error,
+                count: ids.len()
+            });
+        }
+    }
+}
+
+/// Creates the consumer group, along with the stream, unless the group already exists.
+async fn create_group(
+    conn: &mut ConnectionManager,
+    key: &str,
+    options: &StreamOption,
+) -> RedisResult<()> {
+    let result: RedisResult<()> = conn
+        .xgroup_create_mkstream(key, &options.group, &options.start_id)
+        .await;
+    match result {
+        Err(error) if error.code() == Some("BUSYGROUP") => Ok(()),
+        result => result,
+    }
+}
+
+/// Reads messages that were never delivered to any consumer of the group.
+async fn read(
+    conn: &mut ConnectionManager,
+    key: &str,
+    options: &StreamOption,
+    consumer: &str,
+) -> RedisResult<Vec<StreamId>> {
+    let read_options = StreamReadOptions::default()
+        .group(&options.group, consumer)
+        .count(options.batch_size)
+        .block(READ_BLOCK_MS);
+
+    // The reply is nil when no messages arrived before the block timeout.
+    let reply: Option<StreamReadReply> = conn.xread_options(&[key], &[">"], &read_options).await?;
+    Ok(reply
+        .into_iter()
+        .flat_map(|reply| reply.keys)
+        .flat_map(|key| key.ids)
+        .collect())
+}
+
+/// Claims the messages that have been pending for too long, including the ones previously
+/// delivered to this consumer.
+async fn claim(
+    conn: &mut ConnectionManager,
+    key: &str,
+    options: &StreamOption,
+    consumer: &str,
+) -> RedisResult<Vec<StreamId>> {
+    let pending: StreamPendingCountReply = conn
+        .xpending_count(key, &options.group, "-", "+", options.batch_size)
+        .await?;
+
+    let ids = pending
+        .ids
+        .into_iter()
+        .filter(|pending| pending.last_delivered_ms as u64 >= options.claim_min_idle_ms)
+        .map(|pending| pending.id)
+        .collect::<Vec<_>>();
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let reply: StreamClaimReply = conn
+        .xclaim(
+            key,
+            &options.group,
+            consumer,
+            options.claim_min_idle_ms,
+            &ids,
+        )
+        .await?;
+    Ok(reply.ids)
+}
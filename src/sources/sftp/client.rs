@@ -0,0 +1,188 @@
+use std::{
+    io::Read,
+    net::TcpStream,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use snafu::{ResultExt, Snafu};
+use ssh2::{CheckResult, FileStat, KnownHostFileKind, Session, Sftp};
+
+use super::SftpAuth;
+
+#[derive(Debug, Snafu)]
+pub enum ClientError {
+    #[snafu(display("failed to connect to {}:{}: {}", host, port, source))]
+    Connect {
+        host: String,
+        port: u16,
+        source: std::io::Error,
+    },
+    #[snafu(display("SSH handshake failed: {}", source))]
+    Handshake { source: ssh2::Error },
+    #[snafu(display("failed to read known hosts file {:?}: {}", path, source))]
+    KnownHosts { path: PathBuf, source: ssh2::Error },
+    #[snafu(display("host key of {} does not match the known hosts file", host))]
+    HostKeyMismatch { host: String },
+    #[snafu(display("authentication failed: {}", source))]
+    Authentication { source: ssh2::Error },
+    #[snafu(display("SFTP error on {:?}: {}", path, source))]
+    Sftp { path: PathBuf, source: ssh2::Error },
+    #[snafu(display("failed to read {:?}: {}", path, source))]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// The settings used to connect to the SFTP server.
+#[derive(Debug)]
+pub struct ConnectionSettings {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub auth: SftpAuth,
+    pub known_hosts_file: Option<PathBuf>,
+}
+
+struct Connection {
+    // The session must outlive the SFTP channel opened on it.
+    _session: Session,
+    sftp: Sftp,
+}
+
+/// A connection to an SFTP server.
+///
+/// The underlying `libssh2` calls block, so they are run on the blocking thread pool.
+#[derive(Clone)]
+pub struct SftpClient {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SftpClient {
+    pub async fn connect(settings: Arc<ConnectionSettings>) -> Result<Self, ClientError> {
+        let connection = tokio::task::spawn_blocking(move || connect(&settings))
+            .await
+            .expect("SFTP connection task panicked")?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// Lists the entries of `directory`.
+    pub async fn list(&self, directory: PathBuf) -> Result<Vec<(PathBuf, FileStat)>, ClientError> {
+        self.blocking(move |sftp| {
+            sftp.readdir(&directory)
+                .context(SftpSnafu { path: directory })
+        })
+        .await
+    }
+
+    /// Reads the whole file at `path`.
+    pub async fn read(&self, path: PathBuf) -> Result<Vec<u8>, ClientError> {
+        self.blocking(move |sftp| {
+            let mut file = sftp
+                .open(&path)
+                .with_context(|_| SftpSnafu { path: &path })?;
+            let mut contents = Vec::new();
+            file.read_to_end(&mut contents)
+                .context(ReadSnafu { path })?;
+            Ok(contents)
+        })
+        .await
+    }
+
+    pub async fn delete(&self, path: PathBuf) -> Result<(), ClientError> {
+        self.blocking(move |sftp| sftp.unlink(&path).context(SftpSnafu { path }))
+            .await
+    }
+
+    pub async fn rename(&self, from: PathBuf, to: PathBuf) -> Result<(), ClientError> {
+        self.blocking(move |sftp| {
+            sftp.rename(&from, &to, None)
+                .context(SftpSnafu { path: from })
+        })
+        .await
+    }
+
+    async fn blocking<T, F>(&self, f: F) -> Result<T, ClientError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Sftp) -> Result<T, ClientError> + Send + 'static,
+    {
+        let connection = Arc::clone(&self.connection);
+        tokio::task::spawn_blocking(move || {
+            let connection = connection.lock().expect("SFTP connection mutex poisoned");
+            f(&connection.sftp)
+        })
+        .await
+        .expect("SFTP task panicked")
+    }
+}
+
+fn connect(settings: &ConnectionSettings) -> Result<Connection, ClientError> {
+    let tcp = TcpStream::connect((settings.host.as_str(), settings.port)).with_context(|_| {
+        ConnectSnafu {
+            host: settings.host.clone(),
+            port: settings.port,
+        }
+    })?;
+
+    let mut session = Session::new().context(HandshakeSnafu)?;
+    session.set_tcp_stream(tcp);
+    session.handshake().context(HandshakeSnafu)?;
+
+    if let Some(path) = &settings.known_hosts_file {
+        verify_host_key(&session, settings, path)?;
+    }
+
+    match &settings.auth {
+        SftpAuth::Password { password } => {
+            session.userauth_password(&settings.username, password.inner())
+        }
+        SftpAuth::Key {
+            private_key_file,
+            passphrase,
+        } => session.userauth_pubkey_file(
+            &settings.username,
+            None,
+            private_key_file,
+            passphrase.as_ref().map(|passphrase| passphrase.inner()),
+        ),
+    }
+    .context(AuthenticationSnafu)?;
+
+    let sftp = session.sftp().context(SftpSnafu { path: "/" })?;
+    Ok(Connection {
+        _session: session,
+        sftp,
+    })
+}
+
+fn verify_host_key(
+    session: &Session,
+    settings: &ConnectionSettings,
+    path: &Path,
+) -> Result<(), ClientError> {
+    let mut known_hosts = session
+        .known_hosts()
+        .with_context(|_| KnownHostsSnafu { path })?;
+    known_hosts
+        .read_file(path, KnownHostFileKind::OpenSSH)
+        .with_context(|_| KnownHostsSnafu { path })?;
+
+    let matches = session.host_key().map_or(false, |(key, _)| {
+        matches!(
+            known_hosts.check_port(&settings.host, settings.port, key),
+            CheckResult::Match
+        )
+    });
+    if matches {
+        Ok(())
+    } else {
+        Err(ClientError::HostKeyMismatch {
+            host: settings.host.clone(),
+        })
+    }
+}
@@ -0,0 +1,626 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chrono::Utc;
+use codecs::{
+    decoding::{DeserializerConfig, FramingConfig},
+    StreamDecodingError,
+};
+use futures::StreamExt;
+use lookup::{owned_value_path, path};
+use snafu::Snafu;
+use tokio_stream::wrappers::IntervalStream;
+use tokio_util::codec::FramedRead;
+use value::Kind;
+use vector_common::{
+    internal_event::{
+        ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol, Registered,
+    },
+    sensitive_string::SensitiveString,
+};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{GenerateConfig, Output, SourceAcknowledgementsConfig, SourceConfig, SourceContext},
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{EventsReceived, SftpCheckpointError, SftpReadError, StreamClosedError},
+    serde::{bool_or_struct, default_decoding},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod client;
+mod state;
+
+use self::{
+    client::{ConnectionSettings, SftpClient},
+    state::{Checkpointer, FileState, State},
+};
+
+const DEFAULT_PORT: u16 = 22;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid endpoint {:?}: expected `host` or `host:port`", endpoint))]
+    InvalidEndpoint { endpoint: String },
+    #[snafu(display("invalid include pattern {:?}: {}", pattern, reason))]
+    InvalidPattern { pattern: String, reason: String },
+}
+
+/// Configuration for the `sftp` source.
+#[configurable_component(source("sftp"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SftpConfig {
+    /// The SFTP server to download files from, as `host` or `host:port`.
+    ///
+    /// The port defaults to `22`.
+    #[configurable(metadata(docs::examples = "sftp.example.com:22"))]
+    endpoint: String,
+
+    /// The user to log in as.
+    #[configurable(metadata(docs::examples = "vector"))]
+    username: String,
+
+    #[configurable(derived)]
+    auth: SftpAuth,
+
+    /// The path of an OpenSSH `known_hosts` file used to verify the host key of the server.
+    ///
+    /// If not set, the host key of the server is not verified.
+    #[configurable(metadata(docs::examples = "/etc/vector/known_hosts"))]
+    #[serde(default)]
+    known_hosts_file: Option<PathBuf>,
+
+    /// Patterns of the remote files to download.
+    ///
+    /// Only the file name may contain wildcards, such as `*` and `?`. The directory of each
+    /// pattern is listed on every poll.
+    #[configurable(metadata(docs::examples = "/outgoing/*.log"))]
+    include: Vec<String>,
+
+    /// The interval between polls of the SFTP server, in seconds.
+    #[serde(default = "default_poll_interval_secs")]
+    poll_interval_secs: u64,
+
+    /// The minimum time since a file was last modified before it is downloaded, in seconds.
+    ///
+    /// This prevents downloading files which are still being uploaded.
+    #[serde(default)]
+    min_file_age_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    after_read: AfterRead,
+
+    /// The directory used to persist which files were already read.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    #[serde(default)]
+    data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    framing: Option<FramingConfig>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+/// How to authenticate with the SFTP server.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+pub enum SftpAuth {
+    /// Authenticate with a password.
+    Password {
+        /// The password of the user.
+        #[configurable(metadata(docs::examples = "${SFTP_PASSWORD}"))]
+        password: SensitiveString,
+    },
+
+    /// Authenticate with a private key.
+    Key {
+        /// The path of the private key file.
+        #[configurable(metadata(docs::examples = "/etc/vector/id_ed25519"))]
+        private_key_file: PathBuf,
+
+        /// The passphrase of the private key, if it is encrypted.
+        #[configurable(metadata(docs::examples = "${SFTP_KEY_PASSPHRASE}"))]
+        #[serde(default)]
+        passphrase: Option<SensitiveString>,
+    },
+}
+
+/// What to do with a file once it has been read.
+///
+/// When acknowledgements are enabled, this happens once the events read from the file have been
+/// delivered.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "action")]
+pub enum AfterRead {
+    /// Leave the file on the server.
+    ///
+    /// The file is read again only if its size or modification time changes.
+    #[default]
+    Keep,
+
+    /// Delete the file from the server.
+    Delete,
+
+    /// Rename the file on the server by appending a suffix to its name.
+    ///
+    /// Renamed files are never read again, even if they match an `include` pattern.
+    Rename {
+        /// The suffix appended to the name of the file.
+        #[serde(default = "default_rename_suffix")]
+        suffix: String,
+    },
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    60
+}
+
+fn default_rename_suffix() -> String {
+    ".processed".to_string()
+}
+
+impl GenerateConfig for SftpConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "sftp.example.com:22"
+            username = "vector"
+            auth.strategy = "password"
+            auth.password = "${SFTP_PASSWORD}"
+            include = ["/outgoing/*.log"]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for SftpConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let (host, port) = parse_endpoint(&self.endpoint)?;
+        let patterns = self
+            .include
+            .iter()
+            .map(|pattern| IncludePattern::parse(pattern))
+            .collect::<Result<Vec<_>, _>>()?;
+        let data_dir = cx
+            .globals
+            // source are only global, name can be used for subdir
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+
+        if self.known_hosts_file.is_none() {
+            warn!(
+                message = "No `known_hosts_file` configured, the host key of the SFTP server will not be verified.",
+                endpoint = %self.endpoint,
+            );
+        }
+
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let framing = self
+            .framing
+            .clone()
+            .unwrap_or_else(|| self.decoding.default_stream_framing());
+        let decoder = DecodingConfig::new(framing, self.decoding.clone(), log_namespace).build();
+
+        let poller = Poller {
+            settings: Arc::new(ConnectionSettings {
+                host: host.clone(),
+                port,
+                username: self.username.clone(),
+                auth: self.auth.clone(),
+                known_hosts_file: self.known_hosts_file.clone(),
+            }),
+            host,
+            patterns,
+            min_file_age: Duration::from_secs(self.min_file_age_secs),
+            after_read: self.after_read.clone(),
+            checkpointer: Checkpointer::new(&data_dir),
+            decoder,
+            acknowledgements: cx.do_acknowledgements(self.acknowledgements),
+            log_namespace,
+            bytes_received: register!(BytesReceived::from(Protocol::from("sftp"))),
+            events_received: register!(EventsReceived),
+            out: cx.out,
+        };
+        Ok(Box::pin(poller.run(
+            Duration::from_secs(self.poll_interval_secs),
+            cx.shutdown,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("host"))),
+                &owned_value_path!("host"),
+                Kind::bytes(),
+                Some("host"),
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("path"))),
+                &owned_value_path!("path"),
+                Kind::bytes(),
+                None,
+            );
+
+        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// Splits `endpoint` into a host and a port.
+fn parse_endpoint(endpoint: &str) -> Result<(String, u16), BuildError> {
+    let invalid = || BuildError::InvalidEndpoint {
+        endpoint: endpoint.to_string(),
+    };
+
+    let (host, port) = match endpoint.rsplit_once(':') {
+        // A bracketed IPv6 address, with an optional port.
+        _ if endpoint.starts_with('[') => match endpoint[1..].split_once(']') {
+            Some((host, "")) => (host, DEFAULT_PORT),
+            Some((host, port)) => (
+                host,
+                port.strip_prefix(':')
+                    .and_then(|port| port.parse().ok())
+                    .ok_or_else(invalid)?,
+            ),
+            None => return Err(invalid()),
+        },
+        Some((host, port)) if !host.contains(':') => (host, port.parse().map_err(|_| invalid())?),
+        _ => (endpoint, DEFAULT_PORT),
+    };
+
+    if host.is_empty() {
+        Err(invalid())
+    } else {
+        Ok((host.to_string(), port))
+    }
+}
+
+/// A remote directory and a pattern matching the names of the files to read in it.
+#[derive(Debug)]
+struct IncludePattern {
+    directory: PathBuf,
+    file_name: glob::Pattern,
+}
+
+impl IncludePattern {
+    fn parse(pattern: &str) -> Result<Self, BuildError> {
+        let invalid = |reason: &str| BuildError::InvalidPattern {
+            pattern: pattern.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let (directory, file_name) = pattern
+            .rsplit_once('/')
+            .ok_or_else(|| invalid("must be an absolute path"))?;
+        if !pattern.starts_with('/') {
+            return Err(invalid("must be an absolute path"));
+        }
+        if directory.contains(|c| matches!(c, '*' | '?' | '[')) {
+            return Err(invalid("only the file name may contain wildcards"));
+        }
+        if file_name.is_empty() {
+            return Err(invalid("must match file names"));
+        }
+
+        Ok(Self {
+            directory: PathBuf::from(if directory.is_empty() { "/" } else { directory }),
+            file_name: glob::Pattern::new(file_name)
+                .map_err(|error| invalid(&error.to_string()))?,
+        })
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .map_or(false, |name| self.file_name.matches(name))
+    }
+}
+
+/// The path a file is renamed to after it is read.
+fn renamed_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut renamed = path.as_os_str().to_owned();
+    renamed.push(suffix);
+    PathBuf::from(renamed)
+}
+
+/// A remote file to read.
+struct RemoteFile {
+    path: PathBuf,
+    path_str: String,
+    state: FileState,
+}
+
+struct Poller {
+    settings: Arc<ConnectionSettings>,
+    host: String,
+    patterns: Vec<IncludePattern>,
+    min_file_age: Duration,
+    after_read: AfterRead,
+    checkpointer: Checkpointer,
+    decoder: Decoder,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+    out: SourceSender,
+}
+
+impl Poller {
+    async fn run(mut self, interval: Duration, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let mut state = self.checkpointer.load().await.map_err(|error| {
+            emit!(SftpCheckpointError {
+                path: self.checkpointer.path(),
+                error,
+            })
+        })?;
+
+        let mut ticks = IntervalStream::new(tokio::time::interval(interval)).take_until(shutdown);
+        while ticks.next().await.is_some() {
+            match self.poll(&mut state).await {
+                Ok(()) => {}
+                Err(PollError::OutputClosed) => break,
+                Err(PollError::Client(error)) => emit!(SftpReadError { error }),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the files matching the include patterns which were not read yet.
+    async fn poll(&mut self, state: &mut State) -> Result<(), PollError> {
+        let client = SftpClient::connect(Arc::clone(&self.settings)).await?;
+
+        let mut listed = HashSet::new();
+        let mut files = Vec::new();
+        let now = SystemTime::now();
+        for pattern in &self.patterns {
+            for (path, stat) in client.list(pattern.directory.clone()).await? {
+                if !stat.is_file() || !pattern.matches(&path) || self.is_renamed(&path) {
+                    continue;
+                }
+                let path_str = path.to_string_lossy().into_owned();
+                let file = FileState {
+                    size: stat.size.unwrap_or(0),
+                    mtime: stat.mtime.unwrap_or(0),
+                };
+                listed.insert(path_str.clone());
+                if state.is_read(&path_str, &file) || !self.is_old_enough(&file, now) {
+                    continue;
+                }
+                files.push(RemoteFile {
+                    path,
+                    path_str,
+                    state: file,
+                });
+            }
+        }
+
+        for file in files {
+            if self.read_file(&client, &file).await? {
+                // Files deleted or renamed are forgotten on the next poll, as they are no longer
+                // listed.
+                state.mark_read(file.path_str, file.state);
+                self.persist(state).await;
+            }
+        }
+
+        state.retain(|path| listed.contains(path));
+        self.persist(state).await;
+        Ok(())
+    }
+
+    /// Whether or not `path` is the result of renaming a file after reading it.
+    fn is_renamed(&self, path: &Path) -> bool {
+        match &self.after_read {
+            AfterRead::Rename { suffix } => path.to_string_lossy().ends_with(suffix.as_str()),
+            _ => false,
+        }
+    }
+
+    fn is_old_enough(&self, file: &FileState, now: SystemTime) -> bool {
+        let modified = UNIX_EPOCH + Duration::from_secs(file.mtime);
+        now.duration_since(modified)
+            .map_or(false, |age| age >= self.min_file_age)
+    }
+
+    /// Sends the events decoded from `file` and applies the `after_read` action once they are
+    /// delivered.
+    ///
+    /// Returns whether or not the file was read, as it must be read again if its events were not
+    /// delivered.
+    async fn read_file(
+        &mut self,
+        client: &SftpClient,
+        file: &RemoteFile,
+    ) -> Result<bool, PollError> {
+        let contents = client.read(file.path.clone()).await?;
+        self.bytes_received.emit(ByteSize(contents.len()));
+
+        let mut events = self.decode(&contents, &file.path_str).await;
+        if !events.is_empty() {
+            let count = events.len();
+            self.events_received.emit(CountByteSize(
+                count,
+                events.estimated_json_encoded_size_of(),
+            ));
+
+            let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+            if let Err(error) = self.out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return Err(PollError::OutputClosed);
+            }
+            if let Some(receiver) = receiver {
+                if receiver.await != BatchStatus::Delivered {
+                    return Ok(false);
+                }
+            }
+        }
+
+        let result = match &self.after_read {
+            AfterRead::Keep => Ok(()),
+            AfterRead::Delete => client.delete(file.path.clone()).await,
+            AfterRead::Rename { suffix } => {
+                client
+                    .rename(file.path.clone(), renamed_path(&file.path, suffix))
+                    .await
+            }
+        };
+        // The file was read even if the action failed, so it is not read again while it exists.
+        if let Err(error) = result {
+            emit!(SftpReadError { error });
+        }
+        Ok(true)
+    }
+
+    async fn decode(&self, contents: &[u8], path: &str) -> Vec<Event> {
+        let now = Utc::now();
+        let mut events = Vec::new();
+        let mut stream = FramedRead::new(contents, self.decoder.clone());
+        while let Some(result) = stream.next().await {
+            match result {
+                Ok((decoded, _byte_size)) => {
+                    for mut event in decoded {
+                        if let Event::Log(log) = &mut event {
+                            self.log_namespace.insert_standard_vector_source_metadata(
+                                log,
+                                SftpConfig::NAME,
+                                now,
+                            );
+                            self.log_namespace.insert_source_metadata(
+                                SftpConfig::NAME,
+                                log,
+                                Some(LegacyKey::InsertIfEmpty(path!("host"))),
+                                path!("host"),
+                                self.host.clone(),
+                            );
+                            self.log_namespace.insert_source_metadata(
+                                SftpConfig::NAME,
+                                log,
+                                Some(LegacyKey::InsertIfEmpty(path!("path"))),
+                                path!("path"),
+                                path,
+                            );
+                        }
+                        events.push(event);
+                    }
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs::Decoder`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    async fn persist(&self, state: &State) {
+        if let Err(error) = self.checkpointer.set(state).await {
+            emit!(SftpCheckpointError {
+                path: self.checkpointer.path(),
+                error,
+            });
+        }
+    }
+}
+
+enum PollError {
+    Client(client::ClientError),
+    OutputClosed,
+}
+
+impl From<client::ClientError> for PollError {
+    fn from(error: client::ClientError) -> Self {
+        Self::Client(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SftpConfig>();
+    }
+
+    #[test]
+    fn parses_endpoints() {
+        assert_eq!(
+            parse_endpoint("sftp.example.com").unwrap(),
+            ("sftp.example.com".to_string(), 22)
+        );
+        assert_eq!(
+            parse_endpoint("sftp.example.com:2222").unwrap(),
+            ("sftp.example.com".to_string(), 2222)
+        );
+        assert_eq!(
+            parse_endpoint("[::1]:2222").unwrap(),
+            ("::1".to_string(), 2222)
+        );
+        assert_eq!(parse_endpoint("::1").unwrap(), ("::1".to_string(), 22));
+        assert!(parse_endpoint("sftp.example.com:ssh").is_err());
+        assert!(parse_endpoint(":22").is_err());
+    }
+
+    #[test]
+    fn parses_include_patterns() {
+        let pattern = IncludePattern::parse("/outgoing/*.log").unwrap();
+        assert_eq!(pattern.directory, PathBuf::from("/outgoing"));
+        assert!(pattern.matches(Path::new("/outgoing/app.log")));
+        assert!(!pattern.matches(Path::new("/outgoing/app.log.processed")));
+
+        let pattern = IncludePattern::parse("/*.csv").unwrap();
+        assert_eq!(pattern.directory, PathBuf::from("/"));
+        assert!(pattern.matches(Path::new("/export.csv")));
+
+        assert!(IncludePattern::parse("outgoing/*.log").is_err());
+        assert!(IncludePattern::parse("/outgoing/*/app.log").is_err());
+        assert!(IncludePattern::parse("/outgoing/").is_err());
+    }
+
+    #[test]
+    fn appends_rename_suffix() {
+        assert_eq!(
+            renamed_path(Path::new("/outgoing/app.log"), ".processed"),
+            PathBuf::from("/outgoing/app.log.processed")
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+const STATE_FILENAME: &str = "state.json";
+const TMP_STATE_FILENAME: &str = "state.new.json";
+
+/// The size and modification time of a remote file.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FileState {
+    pub size: u64,
+    pub mtime: u64,
+}
+
+/// The remote files already read, with the state they had when they were.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct State {
+    files: BTreeMap<String, FileState>,
+}
+
+impl State {
+    /// Whether or not the file at `path` was already read as it is now.
+    pub fn is_read(&self, path: &str, file: &FileState) -> bool {
+        self.files.get(path) == Some(file)
+    }
+
+    pub fn mark_read(&mut self, path: String, file: FileState) {
+        self.files.insert(path, file);
+    }
+
+    /// Forgets the files for which `listed` returns `false`, as they no longer exist.
+    pub fn retain(&mut self, mut listed: impl FnMut(&str) -> bool) {
+        self.files.retain(|path, _| listed(path));
+    }
+}
+
+/// The state persisted as JSON in the data directory.
+pub struct Checkpointer {
+    path: PathBuf,
+    tmp_path: PathBuf,
+}
+
+impl Checkpointer {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(STATE_FILENAME),
+            tmp_path: data_dir.join(TMP_STATE_FILENAME),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the state previously persisted, if any.
+    pub async fn load(&self) -> io::Result<State> {
+        match fs::read(&self.path).await {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(State::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Persists `state`.
+    ///
+    /// The state is written to a temporary file first, so a crash never leaves a partially
+    /// written file behind.
+    pub async fn set(&self, state: &State) -> io::Result<()> {
+        let contents = serde_json::to_vec(state)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(&self.tmp_path, contents).await?;
+        fs::rename(&self.tmp_path, &self.path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: FileState = FileState {
+        size: 1024,
+        mtime: 1_672_531_200,
+    };
+
+    #[test]
+    fn tracks_read_files() {
+        let mut state = State::default();
+        assert!(!state.is_read("/outgoing/app.log", &FILE));
+
+        state.mark_read("/outgoing/app.log".into(), FILE);
+        assert!(state.is_read("/outgoing/app.log", &FILE));
+
+        let modified = FileState { size: 2048, ..FILE };
+        assert!(!state.is_read("/outgoing/app.log", &modified));
+
+        state.retain(|path| path != "/outgoing/app.log");
+        assert!(!state.is_read("/outgoing/app.log", &FILE));
+    }
+
+    #[tokio::test]
+    async fn persists_state() {
+        let data_dir = tempfile::tempdir().unwrap();
+
+        let checkpointer = Checkpointer::new(data_dir.path());
+        assert_eq!(checkpointer.load().await.unwrap(), State::default());
+
+        let mut state = State::default();
+        state.mark_read("/outgoing/app.log".into(), FILE);
+        checkpointer.set(&state).await.unwrap();
+
+        let checkpointer = Checkpointer::new(data_dir.path());
+        assert_eq!(checkpointer.load().await.unwrap(), state);
+    }
+}
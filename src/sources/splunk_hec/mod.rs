@@ -1281,6 +1281,7 @@ mod tests {
             timestamp_key: timestamp_key(),
             auto_extract_timestamp: None,
             endpoint_target: Default::default(),
+            metrics: Default::default(),
         }
         .build(SinkContext::new_test())
         .await
@@ -33,7 +33,7 @@ pub mod parser;
 #[cfg(unix)]
 mod unix;
 
-use parser::parse;
+use parser::parse_packet;
 #[cfg(unix)]
 use unix::{statsd_unix, UnixConfig};
 use vector_core::config::LogNamespace;
@@ -167,7 +167,9 @@ impl SourceConfig for StatsdConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(config::DataType::Metric)]
+        vec![Output::default(
+            config::DataType::Metric | config::DataType::Log,
+        )]
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -233,10 +235,9 @@ impl decoding::format::Deserializer for StatsdDeserializer {
 
         match std::str::from_utf8(&bytes)
             .map_err(ParseError::InvalidUtf8)
-            .and_then(parse)
+            .and_then(parse_packet)
         {
-            Ok(metric) => {
-                let event = Event::Metric(metric);
+            Ok(event) => {
                 if let Some(er) = &self.events_received {
                     let byte_size = event.estimated_json_encoded_size_of();
                     er.emit(CountByteSize(1, byte_size));
@@ -1,17 +1,39 @@
 use std::{
+    collections::BTreeMap,
     error, fmt,
     num::{ParseFloatError, ParseIntError},
     str::Utf8Error,
 };
 
+use chrono::{DateTime, TimeZone, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use vector_config::NamedComponent;
+use vector_core::config::{log_schema, LogNamespace};
 
-use crate::event::metric::{Metric, MetricKind, MetricTags, MetricValue, StatisticKind};
+use super::StatsdConfig;
+use crate::event::{
+    metric::{Metric, MetricKind, MetricTags, MetricValue, Sample, StatisticKind},
+    Event, LogEvent, Value,
+};
+
+/// The tag, or field, holding the container ID of DogStatsD packets.
+const CONTAINER_ID_KEY: &str = "container_id";
 
 static WHITESPACE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
 static NONALPHANUM: Lazy<Regex> = Lazy::new(|| Regex::new(r"[^a-zA-Z_\-0-9\.]").unwrap());
 
+/// Parses a packet, which is either a metric or, in the DogStatsD dialect, an event or a service check.
+pub fn parse_packet(packet: &str) -> Result<Event, ParseError> {
+    if packet.starts_with("_e{") {
+        parse_dogstatsd_event(packet).map(Event::Log)
+    } else if packet.starts_with("_sc|") {
+        parse_service_check(packet).map(Event::Log)
+    } else {
+        parse(packet).map(Event::Metric)
+    }
+}
+
 pub fn parse(packet: &str) -> Result<Metric, ParseError> {
     // https://docs.datadoghq.com/developers/dogstatsd/datagram_shell/#datagram-format
     let key_and_body = packet.splitn(2, ':').collect::<Vec<_>>();
@@ -30,28 +52,18 @@ pub fn parse(packet: &str) -> Result<Metric, ParseError> {
     }
 
     let name = sanitize_key(key);
+    // DogStatsD allows packing several values of the same metric in a single packet
+    let values = parts[0].split(':').collect::<Vec<_>>();
     let metric_type = parts[1];
-
-    // sampling part is optional and comes after metric type part
-    let sampling = parts.get(2).filter(|s| s.starts_with('@'));
-    let sample_rate = if let Some(s) = sampling {
-        1.0 / sanitize_sampling(parse_sampling(s)?)
-    } else {
-        1.0
-    };
-
-    // tags are optional and could be found either after sampling of after metric type part
-    let tags = if sampling.is_none() {
-        parts.get(2)
-    } else {
-        parts.get(3)
-    };
-    let tags = tags.filter(|s| s.starts_with('#'));
-    let tags = tags.map(parse_tags).transpose()?;
+    let extensions = MetricExtensions::parse(&parts[2..])?;
+    let sample_rate = extensions.sample_rate;
 
     let metric = match metric_type {
         "c" => {
-            let val: f64 = parts[0].parse()?;
+            let val = values
+                .iter()
+                .map(|value| value.parse::<f64>())
+                .sum::<Result<f64, _>>()?;
             Metric::new(
                 name,
                 MetricKind::Incremental,
@@ -59,53 +71,243 @@ pub fn parse(packet: &str) -> Result<Metric, ParseError> {
                     value: val * sample_rate,
                 },
             )
-            .with_tags(tags)
         }
         unit @ "h" | unit @ "ms" | unit @ "d" => {
-            let val: f64 = parts[0].parse()?;
+            let samples = values
+                .iter()
+                .map(|value| {
+                    value.parse().map(|value| Sample {
+                        value: convert_to_base_units(unit, value),
+                        rate: sample_rate as u32,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
             Metric::new(
-                name, MetricKind::Incremental, MetricValue::Distribution {
-                    samples: vector_core::samples![convert_to_base_units(unit, val) => sample_rate as u32],
+                name,
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples,
                     statistic: convert_to_statistic(unit),
                 },
-            ).with_tags(tags)
+            )
         }
         "g" => {
-            let value = if parts[0]
+            // Only the last of packed gauge values is relevant.
+            let last = values[values.len() - 1];
+            let value = if last
                 .chars()
                 .next()
                 .map(|c| c.is_ascii_digit())
                 .ok_or(ParseError::Malformed("empty first body component"))?
             {
-                parts[0].parse()?
+                last.parse()?
             } else {
-                parts[0][1..].parse()?
+                last[1..].parse()?
             };
 
-            match parse_direction(parts[0])? {
-                None => Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
-                    .with_tags(tags),
+            match parse_direction(last)? {
+                None => Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value }),
                 Some(sign) => Metric::new(
                     name,
                     MetricKind::Incremental,
                     MetricValue::Gauge {
                         value: value * sign,
                     },
-                )
-                .with_tags(tags),
+                ),
             }
         }
         "s" => Metric::new(
             name,
             MetricKind::Incremental,
             MetricValue::Set {
-                values: vec![parts[0].into()].into_iter().collect(),
+                values: values.into_iter().map(Into::into).collect(),
             },
-        )
-        .with_tags(tags),
+        ),
         other => return Err(ParseError::UnknownMetricType(other.into())),
     };
-    Ok(metric)
+    Ok(metric
+        .with_tags(extensions.tags)
+        .with_timestamp(extensions.timestamp))
+}
+
+/// The optional components following the type of a metric.
+#[derive(Default)]
+struct MetricExtensions {
+    sample_rate: f64,
+    tags: Option<MetricTags>,
+    timestamp: Option<DateTime<Utc>>,
+}
+
+impl MetricExtensions {
+    fn parse(parts: &[&str]) -> Result<Self, ParseError> {
+        let mut extensions = Self {
+            sample_rate: 1.0,
+            ..Default::default()
+        };
+        let mut container_id = None;
+
+        // Unknown components are ignored, as DogStatsD recommends for forward compatibility.
+        for part in parts {
+            if part.starts_with('@') {
+                extensions.sample_rate = 1.0 / sanitize_sampling(parse_sampling(part)?);
+            } else if part.starts_with('#') {
+                extensions.tags = Some(parse_tags(part)?);
+            } else if let Some(id) = part.strip_prefix("c:") {
+                container_id = Some(id);
+            } else if let Some(timestamp) = part.strip_prefix('T') {
+                extensions.timestamp = Some(parse_timestamp(timestamp)?);
+            }
+        }
+
+        if let Some(container_id) = container_id {
+            extensions
+                .tags
+                .get_or_insert_with(MetricTags::default)
+                .replace(CONTAINER_ID_KEY.to_owned(), container_id.to_owned());
+        }
+        Ok(extensions)
+    }
+}
+
+/// Parses a DogStatsD event into a log event.
+///
+/// `_e{<TITLE_LENGTH>,<TEXT_LENGTH>}:<TITLE>|<TEXT>|d:<TIMESTAMP>|h:<HOSTNAME>|k:<AGGREGATION_KEY>|p:<PRIORITY>|s:<SOURCE_TYPE_NAME>|t:<ALERT_TYPE>|#<TAGS>`
+fn parse_dogstatsd_event(packet: &str) -> Result<LogEvent, ParseError> {
+    // https://docs.datadoghq.com/developers/dogstatsd/datagram_shell/?tab=events#the-dogstatsd-protocol
+    let (lengths, rest) = packet["_e{".len()..]
+        .split_once("}:")
+        .ok_or(ParseError::Malformed(
+            "event should start with title and text lengths",
+        ))?;
+    let (title_length, text_length) = lengths.split_once(',').ok_or(ParseError::Malformed(
+        "event lengths should be ',' separated",
+    ))?;
+    let (title_length, text_length) = (
+        title_length.parse::<usize>()?,
+        text_length.parse::<usize>()?,
+    );
+
+    let title = rest.get(..title_length).ok_or(ParseError::Malformed(
+        "event title is shorter than its length",
+    ))?;
+    let rest = rest[title_length..]
+        .strip_prefix('|')
+        .ok_or(ParseError::Malformed(
+            "event title and text should be '|' separated",
+        ))?;
+    let text = rest.get(..text_length).ok_or(ParseError::Malformed(
+        "event text is shorter than its length",
+    ))?;
+
+    let mut log = LogEvent::default();
+    log.insert("title", title);
+    // Newlines are escaped in the text of events.
+    log.insert(log_schema().message_key(), text.replace("\\n", "\n"));
+
+    let mut timestamp = None;
+    for part in split_fields(&rest[text_length..])? {
+        if let Some(value) = part.strip_prefix("d:") {
+            timestamp = Some(parse_timestamp(value)?);
+        } else if let Some(host) = part.strip_prefix("h:") {
+            log.insert(log_schema().host_key(), host);
+        } else if let Some(key) = part.strip_prefix("k:") {
+            log.insert("aggregation_key", key);
+        } else if let Some(priority) = part.strip_prefix("p:") {
+            log.insert("priority", priority);
+        } else if let Some(source_type_name) = part.strip_prefix("s:") {
+            log.insert("source_type_name", source_type_name);
+        } else if let Some(alert_type) = part.strip_prefix("t:") {
+            log.insert("alert_type", alert_type);
+        } else if let Some(container_id) = part.strip_prefix("c:") {
+            log.insert(CONTAINER_ID_KEY, container_id);
+        } else if part.starts_with('#') {
+            log.insert("tags", tags_into_value(parse_tags(part)?));
+        }
+    }
+
+    insert_source_metadata(&mut log, timestamp);
+    Ok(log)
+}
+
+/// Parses a DogStatsD service check into a log event.
+///
+/// `_sc|<NAME>|<STATUS>|d:<TIMESTAMP>|h:<HOSTNAME>|#<TAGS>|m:<MESSAGE>`
+fn parse_service_check(packet: &str) -> Result<LogEvent, ParseError> {
+    // https://docs.datadoghq.com/developers/dogstatsd/datagram_shell/?tab=servicechecks#the-dogstatsd-protocol
+    // The message is always last, and is kept as is even if it contains separators.
+    let (packet, message) = match packet.split_once("|m:") {
+        Some((packet, message)) => (packet, Some(message)),
+        None => (packet, None),
+    };
+
+    let parts = packet.split('|').collect::<Vec<_>>();
+    if parts.len() < 3 {
+        return Err(ParseError::Malformed(
+            "service check should have a name and a status",
+        ));
+    }
+    let status = parts[2].parse::<u8>()?;
+    if status > 3 {
+        return Err(ParseError::Malformed(
+            "service check status should be between 0 and 3",
+        ));
+    }
+
+    let mut log = LogEvent::default();
+    log.insert("name", parts[1]);
+    log.insert("status", status);
+    if let Some(message) = message {
+        log.insert(log_schema().message_key(), message);
+    }
+
+    let mut timestamp = None;
+    for part in &parts[3..] {
+        if let Some(value) = part.strip_prefix("d:") {
+            timestamp = Some(parse_timestamp(value)?);
+        } else if let Some(host) = part.strip_prefix("h:") {
+            log.insert(log_schema().host_key(), host);
+        } else if let Some(container_id) = part.strip_prefix("c:") {
+            log.insert(CONTAINER_ID_KEY, container_id);
+        } else if part.starts_with('#') {
+            log.insert("tags", tags_into_value(parse_tags(part)?));
+        }
+    }
+
+    insert_source_metadata(&mut log, timestamp);
+    Ok(log)
+}
+
+fn split_fields(input: &str) -> Result<Vec<&str>, ParseError> {
+    if input.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    input
+        .strip_prefix('|')
+        .map(|fields| fields.split('|').collect())
+        .ok_or(ParseError::Malformed("fields should be '|' separated"))
+}
+
+fn insert_source_metadata(log: &mut LogEvent, timestamp: Option<DateTime<Utc>>) {
+    LogNamespace::Legacy.insert_standard_vector_source_metadata(
+        log,
+        StatsdConfig::NAME,
+        timestamp.unwrap_or_else(Utc::now),
+    );
+}
+
+fn tags_into_value(tags: MetricTags) -> Value {
+    Value::Object(
+        tags.into_iter_single()
+            .map(|(key, value)| (key, value.into()))
+            .collect::<BTreeMap<_, _>>(),
+    )
+}
+
+fn parse_timestamp(input: &str) -> Result<DateTime<Utc>, ParseError> {
+    Utc.timestamp_opt(input.parse()?, 0)
+        .single()
+        .ok_or(ParseError::Malformed("timestamp is out of range"))
 }
 
 fn parse_sampling(input: &str) -> Result<f64, ParseError> {
@@ -123,7 +325,7 @@ fn parse_sampling(input: &str) -> Result<f64, ParseError> {
     }
 }
 
-fn parse_tags(input: &&str) -> Result<MetricTags, ParseError> {
+fn parse_tags(input: &str) -> Result<MetricTags, ParseError> {
     if !input.starts_with('#') || input.len() < 2 {
         return Err(ParseError::Malformed(
             "expected non empty '#'-prefixed tags component",
@@ -219,11 +421,17 @@ impl From<ParseFloatError> for ParseError {
 
 #[cfg(test)]
 mod test {
-    use vector_common::assert_event_data_eq;
+    use vector_common::{assert_event_data_eq, btreemap};
     use vector_core::metric_tags;
 
-    use super::{parse, sanitize_key, sanitize_sampling};
-    use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
+    use chrono::{TimeZone, Utc};
+    use vector_core::config::log_schema;
+
+    use super::{parse, parse_packet, sanitize_key, sanitize_sampling, ParseError};
+    use crate::event::{
+        metric::{Metric, MetricKind, MetricValue, StatisticKind},
+        Value,
+    };
 
     #[test]
     fn basic_counter() {
@@ -378,6 +586,109 @@ mod test {
         );
     }
 
+    #[test]
+    fn packed_distribution() {
+        assert_event_data_eq!(
+            parse("glork:320:160|d|@0.5"),
+            Ok(Metric::new(
+                "glork",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: vector_core::samples![320.0 => 2, 160.0 => 2],
+                    statistic: StatisticKind::Summary
+                },
+            )),
+        );
+    }
+
+    #[test]
+    fn packed_counter() {
+        assert_event_data_eq!(
+            parse("foo:1:2|c"),
+            Ok(Metric::new(
+                "foo",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 3.0 },
+            )),
+        );
+    }
+
+    #[test]
+    fn counter_with_timestamp_and_container_id() {
+        assert_event_data_eq!(
+            parse("foo:1|c|#tag1|c:83c0a99c0a54|T1656581400"),
+            Ok(Metric::new(
+                "foo",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(metric_tags!(
+                "tag1" => "true",
+                "container_id" => "83c0a99c0a54",
+            )))
+            .with_timestamp(Some(Utc.timestamp(1656581400, 0)))),
+        );
+    }
+
+    #[test]
+    fn dogstatsd_event() {
+        let event = parse_packet(
+            "_e{5,11}:Alert|First\\nline|d:1656581400|h:web-1|p:low|t:warning|#env:prod",
+        )
+        .unwrap();
+        let log = event.as_log();
+
+        assert_eq!(log["title"], "Alert".into());
+        assert_eq!(log[log_schema().message_key()], "First\nline".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1656581400, 0).into()
+        );
+        assert_eq!(log[log_schema().host_key()], "web-1".into());
+        assert_eq!(log["priority"], "low".into());
+        assert_eq!(log["alert_type"], "warning".into());
+        assert_eq!(log["tags"], Value::from(btreemap! { "env" => "prod" }));
+        assert_eq!(log[log_schema().source_type_key()], "statsd".into());
+    }
+
+    #[test]
+    fn dogstatsd_event_with_invalid_lengths() {
+        assert_eq!(
+            parse_packet("_e{10,4}:Alert|text").map(|_| ()),
+            Err(ParseError::Malformed(
+                "event title is shorter than its length"
+            )),
+        );
+    }
+
+    #[test]
+    fn service_check() {
+        let event = parse_packet(
+            "_sc|redis.can_connect|2|h:db-1|#env:prod|m:Connection refused | retrying",
+        )
+        .unwrap();
+        let log = event.as_log();
+
+        assert_eq!(log["name"], "redis.can_connect".into());
+        assert_eq!(log["status"], 2.into());
+        assert_eq!(log[log_schema().host_key()], "db-1".into());
+        assert_eq!(
+            log[log_schema().message_key()],
+            "Connection refused | retrying".into()
+        );
+        assert_eq!(log["tags"], Value::from(btreemap! { "env" => "prod" }));
+    }
+
+    #[test]
+    fn service_check_with_invalid_status() {
+        assert_eq!(
+            parse_packet("_sc|redis.can_connect|4").map(|_| ()),
+            Err(ParseError::Malformed(
+                "service check status should be between 0 and 3"
+            )),
+        );
+    }
+
     #[test]
     fn sanitizing_keys() {
         assert_eq!("foo-bar-baz", sanitize_key("foo/bar/baz"));
@@ -1,6 +1,9 @@
-use std::net::SocketAddr;
 #[cfg(unix)]
 use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::SocketAddr,
+};
 
 use bytes::Bytes;
 use chrono::Utc;
@@ -30,7 +33,7 @@ use crate::{
     shutdown::ShutdownSignal,
     sources::util::net::{try_bind_udp_socket, SocketListenAddr, TcpNullAcker, TcpSource},
     tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsSettings, TlsSourceConfig},
+    tls::{CertificateMetadata, MaybeTlsSettings, TlsSourceConfig},
     udp, SourceSender,
 };
 
@@ -86,6 +89,18 @@ pub enum Mode {
 
         /// The maximum number of TCP connections that will be allowed at any given time.
         connection_limit: Option<u32>,
+
+        /// A mapping of client certificate identities to tenants.
+        ///
+        /// When set, only the clients presenting a certificate whose common name, or one of whose subject alternative
+        /// names, is mapped are allowed to connect. The tenant they're mapped to is added to the TLS client metadata of
+        /// their events, as `tenant`, to route them per tenant.
+        ///
+        /// This requires TLS to be enabled, with `tls.verify_certificate`.
+        #[configurable(metadata(
+            docs::additional_props_description = "The tenant of the client certificate identity."
+        ))]
+        client_certificate_tenants: Option<HashMap<String, String>>,
     },
 
     /// Listen on UDP.
@@ -136,6 +151,7 @@ impl Default for SyslogConfig {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                client_certificate_tenants: None,
             },
             host_key: None,
             max_length: crate::serde::default_max_length(),
@@ -166,11 +182,25 @@ impl SourceConfig for SyslogConfig {
                 tls,
                 receive_buffer_bytes,
                 connection_limit,
+                client_certificate_tenants,
             } => {
+                if client_certificate_tenants.is_some()
+                    && !tls.as_ref().map_or(false, |tls| {
+                        tls.tls_config.enabled == Some(true)
+                            && tls.tls_config.options.verify_certificate == Some(true)
+                    })
+                {
+                    return Err(
+                        "`client_certificate_tenants` requires `tls.enabled` and `tls.verify_certificate`."
+                            .into(),
+                    );
+                }
+
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
                     host_key,
                     log_namespace,
+                    client_certificate_tenants,
                 };
                 let shutdown_secs = 30;
                 let tls_config = tls.as_ref().map(|tls| tls.tls_config.clone());
@@ -257,6 +287,7 @@ struct SyslogTcpSource {
     max_length: usize,
     host_key: Option<OwnedValuePath>,
     log_namespace: LogNamespace,
+    client_certificate_tenants: Option<HashMap<String, String>>,
 }
 
 impl TcpSource for SyslogTcpSource {
@@ -283,6 +314,34 @@ impl TcpSource for SyslogTcpSource {
         );
     }
 
+    fn client_metadata(
+        &self,
+        certificate_metadata: &CertificateMetadata,
+    ) -> Option<BTreeMap<String, value::Value>> {
+        let mut metadata =
+            BTreeMap::from([("subject".to_string(), certificate_metadata.subject().into())]);
+        if let Some(common_name) = &certificate_metadata.common_name {
+            metadata.insert("common_name".to_string(), common_name.as_str().into());
+        }
+        if !certificate_metadata.subject_alt_names.is_empty() {
+            metadata.insert(
+                "subject_alt_names".to_string(),
+                certificate_metadata.subject_alt_names.join(",").into(),
+            );
+        }
+
+        if let Some(tenants) = &self.client_certificate_tenants {
+            let tenant = certificate_metadata
+                .common_name
+                .iter()
+                .chain(&certificate_metadata.subject_alt_names)
+                .find_map(|identity| tenants.get(identity))?;
+            metadata.insert("tenant".to_string(), tenant.as_str().into());
+        }
+
+        Some(metadata)
+    }
+
     fn build_acker(&self, _: &[Self::Item]) -> Self::Acker {
         TcpNullAcker
     }
@@ -449,9 +508,10 @@ mod test {
         event::{Event, LogEvent},
         test_util::{
             components::{assert_source_compliance, SOCKET_PUSH_SOURCE_TAGS},
-            next_addr, random_maps, random_string, send_encodable, send_lines, wait_for_tcp,
-            CountReceiver,
+            next_addr, random_maps, random_string, send_encodable, send_lines, send_lines_tls,
+            wait_for_tcp, CountReceiver,
         },
+        tls::{self, TlsConfig, TlsEnableableConfig},
     };
 
     fn event_from_bytes(
@@ -1007,6 +1067,7 @@ mod test {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                client_certificate_tenants: None,
             });
 
             let key = ComponentKey::from("in");
@@ -1150,6 +1211,7 @@ mod test {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                client_certificate_tenants: None,
             });
 
             let key = ComponentKey::from("in");
@@ -1214,6 +1276,106 @@ mod test {
         .await;
     }
 
+    fn tls_mode(address: SocketAddr, tenants: &[(&str, &str)]) -> Mode {
+        Mode::Tcp {
+            address: address.into(),
+            keepalive: None,
+            tls: Some(TlsSourceConfig {
+                tls_config: TlsEnableableConfig {
+                    enabled: Some(true),
+                    options: TlsConfig {
+                        verify_certificate: Some(true),
+                        crt_file: Some(tls::TEST_PEM_CRT_PATH.into()),
+                        key_file: Some(tls::TEST_PEM_KEY_PATH.into()),
+                        ca_file: Some(tls::TEST_PEM_CA_PATH.into()),
+                        ..Default::default()
+                    },
+                },
+                client_metadata_key: Some("tls_peer".into()),
+            }),
+            receive_buffer_bytes: None,
+            connection_limit: None,
+            client_certificate_tenants: Some(
+                tenants
+                    .iter()
+                    .map(|(identity, tenant)| (identity.to_string(), tenant.to_string()))
+                    .collect(),
+            ),
+        }
+    }
+
+    async fn send_tls_message(address: SocketAddr) {
+        let message =
+            "<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 - 'su root' failed";
+        send_lines_tls(
+            address,
+            "localhost".into(),
+            std::iter::once(message.to_owned()),
+            std::path::Path::new(tls::TEST_PEM_CA_PATH),
+            std::path::Path::new(tls::TEST_PEM_CLIENT_CRT_PATH),
+            std::path::Path::new(tls::TEST_PEM_CLIENT_KEY_PATH),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_tls_client_certificate_tenants() {
+        assert_source_compliance(&SOCKET_PUSH_SOURCE_TAGS, async {
+            let in_addr = next_addr();
+            let config = SyslogConfig::from_mode(tls_mode(in_addr, &[("localhost", "tenant-a")]));
+            let (tx, mut rx) = SourceSender::new_test();
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            wait_for_tcp(in_addr).await;
+
+            send_tls_message(in_addr).await;
+
+            let event = rx.next().await.unwrap();
+            let log = event.as_log();
+            assert_eq!(log[log_schema().message_key()], "'su root' failed".into());
+            assert_eq!(log["tls_peer.common_name"], "localhost".into());
+            assert_eq!(log["tls_peer.tenant"], "tenant-a".into());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_tls_unauthorized_client_certificate() {
+        let in_addr = next_addr();
+        let config = SyslogConfig::from_mode(tls_mode(in_addr, &[("example.com", "tenant-a")]));
+        let (tx, mut rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(in_addr).await;
+
+        send_tls_message(in_addr).await;
+
+        assert!(tokio::time::timeout(Duration::from_millis(500), rx.next())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn client_certificate_tenants_require_verified_tls() {
+        let mut mode = tls_mode(next_addr(), &[("localhost", "tenant-a")]);
+        if let Mode::Tcp { tls, .. } = &mut mode {
+            tls.as_mut().unwrap().tls_config.options.verify_certificate = None;
+        }
+
+        let (tx, _rx) = SourceSender::new_test();
+        assert!(SyslogConfig::from_mode(mode)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .is_err());
+    }
+
     #[derive(Deserialize, PartialEq, Clone, Debug)]
     struct SyslogMessageRfc5424 {
         msgid: String,
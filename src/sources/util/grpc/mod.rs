@@ -8,9 +8,12 @@ use hyper::Body;
 use std::{convert::Infallible, net::SocketAddr};
 use tonic::{
     body::BoxBody,
-    transport::server::{NamedService, Server},
+    transport::server::{NamedService, Router, Server},
+};
+use tower::{
+    layer::util::{Identity, Stack},
+    Service,
 };
-use tower::Service;
 use tracing::{Instrument, Span};
 
 mod decompression;
@@ -29,6 +32,29 @@ where
         + Send
         + 'static,
     S::Future: Send + 'static,
+{
+    run_grpc_server_with_routes(
+        address,
+        tls_settings,
+        |server| server.add_service(service),
+        shutdown,
+    )
+    .await
+}
+
+/// The layers applied to the services of gRPC servers.
+pub type GrpcServerLayer = Stack<DecompressionAndMetricsLayer, Identity>;
+
+/// Runs a gRPC server with the services added to it by `routes`, for sources serving several services on the
+/// same address.
+pub async fn run_grpc_server_with_routes<F>(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    routes: F,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()>
+where
+    F: FnOnce(&mut Server<GrpcServerLayer>) -> Router<GrpcServerLayer>,
 {
     let span = Span::current();
     let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
@@ -37,7 +63,7 @@ where
 
     info!(%address, "Building gRPC server.");
 
-    Server::builder()
+    let mut server = Server::builder()
         .trace_fn(move |_| span.clone())
         // This layer explicitly decompresses payloads, if compressed, and reports the number of message bytes we've
         // received if the message is processed successfully, aka `BytesReceived`. We do this because otherwise the only
@@ -48,8 +74,9 @@ where
         // use independent `tower` layers when the request body itself (the body type, not the actual bytes) must be
         // modified or wrapped.. so instead of a cleaner design, we're opting here to bake it all together until the
         // crates are sufficiently flexible for us to craft a better design.
-        .layer(DecompressionAndMetricsLayer::default())
-        .add_service(service)
+        .layer(DecompressionAndMetricsLayer::default());
+
+    routes(&mut server)
         .serve_with_incoming_shutdown(stream, shutdown.map(|token| tx.send(token).unwrap()))
         .in_current_span()
         .await?;
@@ -13,6 +13,8 @@ mod prelude;
     feature = "sources-utils-http-query"
 ))]
 mod query;
+#[cfg(feature = "sources-utils-http-signature")]
+mod signature;
 
 #[cfg(feature = "sources-utils-http-auth")]
 pub use auth::{HttpSourceAuth, HttpSourceAuthConfig};
@@ -25,3 +27,5 @@ pub use method::HttpMethod;
 pub use prelude::HttpSource;
 #[cfg(feature = "sources-utils-http-query")]
 pub use query::add_query_parameters;
+#[cfg(feature = "sources-utils-http-signature")]
+pub use signature::{HttpSignatureConfig, HttpSignatureVerifier, SignatureFailureAction};
@@ -2,7 +2,7 @@ use std::{collections::HashMap, convert::TryFrom, fmt, net::SocketAddr};
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use futures::{FutureExt, TryFutureExt};
+use futures::FutureExt;
 use tracing::Span;
 use vector_core::{
     config::SourceAcknowledgementsConfig,
@@ -45,6 +45,13 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         path: &str,
     ) -> Result<Vec<Event>, ErrorMessage>;
 
+    /// Returns the name of the output that events received on `path` are sent to.
+    ///
+    /// Defaults to `None`, which sends all events to the default output.
+    fn output(&self, _path: &str) -> Option<String> {
+        None
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn run(
         self,
@@ -115,6 +122,7 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                             protocol,
                         });
 
+                        let output = self.output(http_path);
                         let events = auth
                             .is_valid(&auth_header)
                             .and_then(|()| decode(&encoding_header, body))
@@ -131,7 +139,7 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                                 events
                             });
 
-                        handle_request(events, acknowledgements, cx.out.clone())
+                        handle_request(events, output, acknowledgements, cx.out.clone())
                     },
                 )
                 .with(warp::trace(move |_info| span.clone()));
@@ -176,6 +184,7 @@ impl warp::reject::Reject for RejectShuttingDown {}
 
 async fn handle_request(
     events: Result<Vec<Event>, ErrorMessage>,
+    output: Option<String>,
     acknowledgements: bool,
     mut out: SourceSender,
 ) -> Result<impl warp::Reply, Rejection> {
@@ -184,15 +193,19 @@ async fn handle_request(
             let receiver = BatchNotifier::maybe_apply_to(acknowledgements, &mut events);
 
             let count = events.len();
-            out.send_batch(events)
-                .map_err(move |error: crate::source_sender::ClosedError| {
+            let sent = match output {
+                Some(output) => out.send_batch_named(&output, events).await,
+                None => out.send_batch(events).await,
+            };
+            match sent {
+                Ok(()) => handle_batch_status(receiver).await,
+                Err(error) => {
                     // can only fail if receiving end disconnected, so we are shutting down,
                     // probably not gracefully.
                     emit!(StreamClosedError { error, count });
-                    warp::reject::custom(RejectShuttingDown)
-                })
-                .and_then(|_| handle_batch_status(receiver))
-                .await
+                    Err(warp::reject::custom(RejectShuttingDown))
+                }
+            }
         }
         Err(error) => {
             emit!(HttpBadRequest::new(error.code(), error.message()));
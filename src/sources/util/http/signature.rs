@@ -0,0 +1,400 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use openssl::{
+    error::ErrorStack,
+    memcmp,
+    pkey::{PKey, Private},
+    sign::Signer,
+};
+use snafu::Snafu;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use warp::http::HeaderMap;
+
+use crate::http::{HmacAlgorithm, SignatureEncoding};
+
+const GITHUB_SIGNATURE_HEADER: &str = "X-Hub-Signature-256";
+const STRIPE_SIGNATURE_HEADER: &str = "Stripe-Signature";
+const SLACK_SIGNATURE_HEADER: &str = "X-Slack-Signature";
+const SLACK_TIMESTAMP_HEADER: &str = "X-Slack-Request-Timestamp";
+
+/// Webhook signature verification configuration.
+///
+/// The signature is computed over the request body, after it has been decompressed according to its
+/// `Content-Encoding` header.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HttpSignatureConfig {
+    #[configurable(derived)]
+    #[serde(flatten)]
+    pub provider: SignatureProvider,
+
+    /// The shared secret used to sign requests.
+    pub secret: SensitiveString,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_failure: SignatureFailureAction,
+
+    /// The maximum age of a signed request, in seconds.
+    ///
+    /// Only relevant for providers that include a timestamp in the signature, where it protects against replayed
+    /// requests.
+    #[serde(default = "default_timestamp_tolerance_secs")]
+    pub timestamp_tolerance_secs: u64,
+}
+
+const fn default_timestamp_tolerance_secs() -> u64 {
+    300
+}
+
+/// The scheme used to sign requests.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "provider", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SignatureProvider {
+    /// Signatures sent by GitHub webhooks in the `X-Hub-Signature-256` header.
+    Github,
+
+    /// Signatures sent by Stripe webhooks in the `Stripe-Signature` header.
+    Stripe,
+
+    /// Signatures sent by Slack in the `X-Slack-Signature` header.
+    Slack,
+
+    /// An HMAC of the request body, sent in a configurable header.
+    Generic {
+        /// The name of the header carrying the signature.
+        header: String,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        algorithm: HmacAlgorithm,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        encoding: SignatureEncoding,
+
+        /// A prefix to strip from the header value before decoding the signature, such as `sha256=`.
+        prefix: Option<String>,
+    },
+}
+
+/// What to do with requests whose signature can't be verified.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureFailureAction {
+    /// Reject the request with a `401 Unauthorized` response.
+    #[derivative(Default)]
+    Reject,
+
+    /// Accept the request, recording the outcome of the verification in the `signature_verified` field of each
+    /// event.
+    Tag,
+}
+
+#[derive(Debug, Snafu)]
+pub enum SignatureError {
+    #[snafu(display("Missing {} header.", header))]
+    MissingHeader { header: String },
+    #[snafu(display("Malformed {} header.", header))]
+    MalformedHeader { header: String },
+    #[snafu(display("Request timestamp is outside of the accepted tolerance."))]
+    StaleTimestamp,
+    #[snafu(display("Signature mismatch."))]
+    Mismatch,
+    #[snafu(display("Failed to compute signature: {}", source))]
+    Compute { source: ErrorStack },
+}
+
+impl From<ErrorStack> for SignatureError {
+    fn from(source: ErrorStack) -> Self {
+        Self::Compute { source }
+    }
+}
+
+#[derive(Clone)]
+pub struct HttpSignatureVerifier {
+    provider: SignatureProvider,
+    key: PKey<Private>,
+    on_failure: SignatureFailureAction,
+    timestamp_tolerance_secs: u64,
+}
+
+impl HttpSignatureVerifier {
+    pub fn new(config: &HttpSignatureConfig) -> crate::Result<Self> {
+        if config.secret.inner().is_empty() {
+            return Err("Signature secret must not be empty.".into());
+        }
+
+        Ok(Self {
+            provider: config.provider.clone(),
+            key: PKey::hmac(config.secret.inner().as_bytes())?,
+            on_failure: config.on_failure,
+            timestamp_tolerance_secs: config.timestamp_tolerance_secs,
+        })
+    }
+
+    pub const fn on_failure(&self) -> SignatureFailureAction {
+        self.on_failure
+    }
+
+    /// Verifies the signature of the request made up of `headers` and `body`.
+    pub fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), SignatureError> {
+        match &self.provider {
+            SignatureProvider::Github => {
+                let signature = header(headers, GITHUB_SIGNATURE_HEADER)?;
+                let signature = signature
+                    .strip_prefix("sha256=")
+                    .and_then(|signature| hex::decode(signature).ok())
+                    .ok_or_else(|| malformed(GITHUB_SIGNATURE_HEADER))?;
+                self.check(HmacAlgorithm::Sha256, &[body], &signature)
+            }
+            SignatureProvider::Stripe => {
+                // The header looks like `t=1492774577,v1=5257a869...,v0=6ffbb59b...`, and may carry
+                // several `v1` signatures while the endpoint secret is being rolled.
+                let value = header(headers, STRIPE_SIGNATURE_HEADER)?;
+                let mut timestamp = None;
+                let mut signatures = Vec::new();
+                for (key, value) in value.split(',').filter_map(|pair| pair.split_once('=')) {
+                    match key.trim() {
+                        "t" => timestamp = Some(value.trim()),
+                        "v1" => signatures.extend(hex::decode(value.trim()).ok()),
+                        _ => {}
+                    }
+                }
+                let timestamp = timestamp.ok_or_else(|| malformed(STRIPE_SIGNATURE_HEADER))?;
+                self.check_timestamp(timestamp, STRIPE_SIGNATURE_HEADER)?;
+
+                let expected =
+                    self.sign(HmacAlgorithm::Sha256, &[timestamp.as_bytes(), b".", body])?;
+                if signatures
+                    .iter()
+                    .any(|signature| constant_time_eq(&expected, signature))
+                {
+                    Ok(())
+                } else {
+                    Err(SignatureError::Mismatch)
+                }
+            }
+            SignatureProvider::Slack => {
+                let timestamp = header(headers, SLACK_TIMESTAMP_HEADER)?;
+                self.check_timestamp(timestamp, SLACK_TIMESTAMP_HEADER)?;
+
+                let signature = header(headers, SLACK_SIGNATURE_HEADER)?;
+                let signature = signature
+                    .strip_prefix("v0=")
+                    .and_then(|signature| hex::decode(signature).ok())
+                    .ok_or_else(|| malformed(SLACK_SIGNATURE_HEADER))?;
+                self.check(
+                    HmacAlgorithm::Sha256,
+                    &[b"v0:", timestamp.as_bytes(), b":", body],
+                    &signature,
+                )
+            }
+            SignatureProvider::Generic {
+                header: name,
+                algorithm,
+                encoding,
+                prefix,
+            } => {
+                let value = header(headers, name)?;
+                let value = match prefix {
+                    Some(prefix) => value.strip_prefix(prefix.as_str()),
+                    None => Some(value),
+                };
+                let signature = value
+                    .and_then(|value| match encoding {
+                        SignatureEncoding::Hex => hex::decode(value).ok(),
+                        SignatureEncoding::Base64 => base64::decode(value).ok(),
+                    })
+                    .ok_or_else(|| malformed(name))?;
+                self.check(*algorithm, &[body], &signature)
+            }
+        }
+    }
+
+    fn sign(&self, algorithm: HmacAlgorithm, parts: &[&[u8]]) -> Result<Vec<u8>, ErrorStack> {
+        let mut signer = Signer::new(algorithm.message_digest(), &self.key)?;
+        for part in parts {
+            signer.update(part)?;
+        }
+        signer.sign_to_vec()
+    }
+
+    fn check(
+        &self,
+        algorithm: HmacAlgorithm,
+        parts: &[&[u8]],
+        signature: &[u8],
+    ) -> Result<(), SignatureError> {
+        let expected = self.sign(algorithm, parts)?;
+        if constant_time_eq(&expected, signature) {
+            Ok(())
+        } else {
+            Err(SignatureError::Mismatch)
+        }
+    }
+
+    fn check_timestamp(&self, timestamp: &str, header: &str) -> Result<(), SignatureError> {
+        let timestamp = timestamp.parse::<u64>().map_err(|_| malformed(header))?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_secs())
+            .unwrap_or_default();
+        if now.abs_diff(timestamp) <= self.timestamp_tolerance_secs {
+            Ok(())
+        } else {
+            Err(SignatureError::StaleTimestamp)
+        }
+    }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Result<&'a str, SignatureError> {
+    headers
+        .get(name)
+        .ok_or_else(|| SignatureError::MissingHeader {
+            header: name.to_owned(),
+        })?
+        .to_str()
+        .map_err(|_| malformed(name))
+}
+
+fn malformed(header: &str) -> SignatureError {
+    SignatureError::MalformedHeader {
+        header: header.to_owned(),
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    // `memcmp::eq` panics on inputs of different lengths.
+    a.len() == b.len() && memcmp::eq(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use warp::http::{HeaderName, HeaderValue};
+
+    use super::*;
+
+    fn verifier(provider: SignatureProvider) -> HttpSignatureVerifier {
+        HttpSignatureVerifier::new(&HttpSignatureConfig {
+            provider,
+            secret: "It's a Secret to Everybody".to_string().into(),
+            on_failure: SignatureFailureAction::Reject,
+            timestamp_tolerance_secs: default_timestamp_tolerance_secs(),
+        })
+        .unwrap()
+    }
+
+    fn headers(pairs: &[(&'static str, String)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+        headers
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[test]
+    fn github() {
+        // Test vector from https://docs.github.com/en/webhooks/using-webhooks/validating-webhook-deliveries
+        let verifier = verifier(SignatureProvider::Github);
+        let valid = headers(&[(
+            GITHUB_SIGNATURE_HEADER,
+            "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17".to_string(),
+        )]);
+
+        assert!(verifier.verify(&valid, b"Hello, World!").is_ok());
+        assert!(matches!(
+            verifier.verify(&valid, b"Goodbye, World!"),
+            Err(SignatureError::Mismatch)
+        ));
+        assert!(matches!(
+            verifier.verify(&HeaderMap::new(), b"Hello, World!"),
+            Err(SignatureError::MissingHeader { .. })
+        ));
+    }
+
+    #[test]
+    fn stripe() {
+        let verifier = verifier(SignatureProvider::Stripe);
+        let timestamp = now().to_string();
+        let signature = hex::encode(
+            verifier
+                .sign(
+                    HmacAlgorithm::Sha256,
+                    &[timestamp.as_bytes(), b".", b"{\"id\":\"evt_1\"}"],
+                )
+                .unwrap(),
+        );
+
+        let valid = headers(&[(
+            STRIPE_SIGNATURE_HEADER,
+            format!("t={},v1=00ff,v1={}", timestamp, signature),
+        )]);
+        assert!(verifier.verify(&valid, b"{\"id\":\"evt_1\"}").is_ok());
+
+        let stale = headers(&[(
+            STRIPE_SIGNATURE_HEADER,
+            format!("t={},v1={}", now() - 3600, signature),
+        )]);
+        assert!(matches!(
+            verifier.verify(&stale, b"{\"id\":\"evt_1\"}"),
+            Err(SignatureError::StaleTimestamp)
+        ));
+    }
+
+    #[test]
+    fn slack() {
+        let verifier = verifier(SignatureProvider::Slack);
+        let timestamp = now().to_string();
+        let signature = hex::encode(
+            verifier
+                .sign(
+                    HmacAlgorithm::Sha256,
+                    &[b"v0:", timestamp.as_bytes(), b":", b"token=abc"],
+                )
+                .unwrap(),
+        );
+
+        let valid = headers(&[
+            (SLACK_TIMESTAMP_HEADER, timestamp),
+            (SLACK_SIGNATURE_HEADER, format!("v0={}", signature)),
+        ]);
+        assert!(verifier.verify(&valid, b"token=abc").is_ok());
+        assert!(verifier.verify(&valid, b"token=abd").is_err());
+    }
+
+    #[test]
+    fn generic() {
+        let verifier = verifier(SignatureProvider::Generic {
+            header: "X-Signature".to_string(),
+            algorithm: HmacAlgorithm::Sha512,
+            encoding: SignatureEncoding::Base64,
+            prefix: Some("sha512=".to_string()),
+        });
+        let signature = base64::encode(verifier.sign(HmacAlgorithm::Sha512, &[b"body"]).unwrap());
+
+        let valid = headers(&[("X-Signature", format!("sha512={}", signature))]);
+        assert!(verifier.verify(&valid, b"body").is_ok());
+
+        let unprefixed = headers(&[("X-Signature", signature)]);
+        assert!(matches!(
+            verifier.verify(&unprefixed, b"body"),
+            Err(SignatureError::MalformedHeader { .. })
+        ));
+    }
+}
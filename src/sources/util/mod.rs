@@ -3,14 +3,19 @@ mod body_decoding;
 mod encoding_config;
 #[cfg(all(unix, feature = "sources-dnstap"))]
 pub mod framestream;
-#[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
+#[cfg(any(
+    feature = "sources-grpc_server",
+    feature = "sources-opentelemetry",
+    feature = "sources-vector"
+))]
 pub mod grpc;
 #[cfg(any(
     feature = "sources-utils-http-auth",
     feature = "sources-utils-http-encoding",
     feature = "sources-utils-http-error",
     feature = "sources-utils-http-prelude",
-    feature = "sources-utils-http-query"
+    feature = "sources-utils-http-query",
+    feature = "sources-utils-http-signature"
 ))]
 pub mod http;
 #[cfg(any(feature = "sources-http_client", feature = "sources-prometheus"))]
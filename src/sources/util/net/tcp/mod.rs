@@ -31,7 +31,7 @@ use crate::{
     internal_events::{
         ConnectionOpen, DecoderFramingError, OpenGauge, SocketBindError, SocketEventsReceived,
         SocketMode, SocketReceiveError, StreamClosedError, TcpBytesReceived, TcpSendAckError,
-        TcpSocketTlsConnectionError,
+        TcpSocketClientUnauthorized, TcpSocketTlsConnectionError,
     },
     shutdown::ShutdownSignal,
     sources::util::AfterReadExt,
@@ -101,6 +101,19 @@ where
 
     fn handle_events(&self, _events: &mut [Event], _host: std::net::SocketAddr) {}
 
+    /// Builds the metadata added to the events of a client from its TLS certificate.
+    ///
+    /// Returns `None` if the client isn't authorized, in which case its connection is closed.
+    fn client_metadata(
+        &self,
+        certificate_metadata: &CertificateMetadata,
+    ) -> Option<BTreeMap<String, value::Value>> {
+        Some(BTreeMap::from([(
+            "subject".to_string(),
+            certificate_metadata.subject().into(),
+        )]))
+    }
+
     fn build_acker(&self, item: &[Self::Item]) -> Self::Acker;
 
     #[allow(clippy::too_many_arguments)]
@@ -281,6 +294,19 @@ async fn handle_stream<T>(
         .ssl_stream()
         .and_then(|stream| stream.ssl().peer_certificate())
         .map(CertificateMetadata::from);
+    let client_metadata = match certificate_metadata {
+        Some(certificate_metadata) => match source.client_metadata(&certificate_metadata) {
+            Some(client_metadata) => Some(client_metadata),
+            None => {
+                emit!(TcpSocketClientUnauthorized {
+                    peer_addr,
+                    subject: &certificate_metadata.subject(),
+                });
+                return;
+            }
+        },
+        None => None,
+    };
 
     let reader = FramedRead::new(socket, source.decoder());
     let mut reader = ReadyFrames::new(reader);
@@ -345,9 +371,7 @@ async fn handle_stream<T>(
                         }
 
 
-                        if let Some(certificate_metadata) = &certificate_metadata {
-                            let mut metadata: BTreeMap<String, value::Value> = BTreeMap::new();
-                            metadata.insert("subject".to_string(), certificate_metadata.subject().into());
+                        if let Some(metadata) = &client_metadata {
                             for event in &mut events {
                                 let log = event.as_mut_log();
 
@@ -1,14 +1,19 @@
-use std::net::SocketAddr;
+use std::{future::Future, net::SocketAddr, num::NonZeroU32, sync::Arc, time::Duration};
 
 use chrono::Utc;
 use codecs::NativeDeserializerConfig;
 use futures::TryFutureExt;
+use governor::{DefaultKeyedRateLimiter, Quota, RateLimiter};
+use openssl::x509::X509;
+use serde_with::serde_as;
+use snafu::Snafu;
 use tonic::{Request, Response, Status};
 use vector_common::internal_event::{CountByteSize, InternalEventHandle as _};
 use vector_config::{configurable_component, NamedComponent};
 use vector_core::{
     config::LogNamespace,
     event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
+    tls::{CertificateMetadata, MaybeTlsConnectInfo},
     EstimatedJsonEncodedSizeOf,
 };
 
@@ -17,7 +22,7 @@ use crate::{
         DataType, GenerateConfig, Output, Resource, SourceAcknowledgementsConfig, SourceConfig,
         SourceContext,
     },
-    internal_events::{EventsReceived, StreamClosedError},
+    internal_events::{EventsReceived, GrpcClientRateLimited, StreamClosedError},
     proto::vector as proto,
     serde::bool_or_struct,
     sources::{util::grpc::run_grpc_server, Source},
@@ -34,11 +39,101 @@ enum VectorConfigVersion {
     V2,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`rate_limit.threshold` and `rate_limit.window_secs` must be non-zero"))]
+    RateLimitNonZero,
+}
+
+/// Per-client rate limiting configuration.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// The number of requests allowed for a given client per configured `window_secs`.
+    ///
+    /// Clients are identified by the subject of their TLS certificate, if they presented one, and by their IP
+    /// address otherwise.
+    pub threshold: u32,
+
+    /// The time window in which the configured `threshold` is applied, in seconds.
+    #[serde_as(as = "serde_with::DurationSeconds<f64>")]
+    pub window_secs: Duration,
+}
+
+impl RateLimitConfig {
+    fn build(&self) -> Result<DefaultKeyedRateLimiter<String>, BuildError> {
+        let threshold = NonZeroU32::new(self.threshold).ok_or(BuildError::RateLimitNonZero)?;
+        let quota = Quota::with_period(Duration::from_secs_f64(
+            self.window_secs.as_secs_f64() / f64::from(threshold.get()),
+        ))
+        .ok_or(BuildError::RateLimitNonZero)?
+        .allow_burst(threshold);
+
+        Ok(RateLimiter::dashmap(quota))
+    }
+}
+
+#[derive(Clone)]
 pub struct Service {
     pipeline: SourceSender,
     acknowledgements: bool,
     log_namespace: LogNamespace,
+    rate_limiter: Option<Arc<DefaultKeyedRateLimiter<String>>>,
+}
+
+impl Service {
+    fn check_rate_limit<T>(&self, request: &Request<T>) -> Result<(), Status> {
+        let rate_limiter = match &self.rate_limiter {
+            Some(rate_limiter) => rate_limiter,
+            None => return Ok(()),
+        };
+        let client = match client_identity(request) {
+            Some(client) => client,
+            None => return Ok(()),
+        };
+
+        rate_limiter.check_key(&client).map_err(|_| {
+            emit!(GrpcClientRateLimited { client: &client });
+            Status::resource_exhausted("Rate limit exceeded")
+        })
+    }
+}
+
+/// Runs `source` while periodically forgetting the clients whose rate limit has been fully replenished, as the
+/// rate limiter would otherwise keep the state of every client it ever saw.
+async fn forget_idle_clients(
+    source: impl Future<Output = Result<(), ()>>,
+    rate_limiter: Arc<DefaultKeyedRateLimiter<String>>,
+    interval: Duration,
+) -> Result<(), ()> {
+    let mut interval = tokio::time::interval(interval);
+    tokio::pin!(source);
+    loop {
+        tokio::select! {
+            result = &mut source => return result,
+            _ = interval.tick() => {
+                rate_limiter.retain_recent();
+                rate_limiter.shrink_to_fit();
+            }
+        }
+    }
+}
+
+/// Returns the identity of the client that sent `request`: the subject of its TLS certificate if it presented one,
+/// and its IP address otherwise.
+fn client_identity<T>(request: &Request<T>) -> Option<String> {
+    let connect_info = request.extensions().get::<MaybeTlsConnectInfo>()?;
+
+    let subject = connect_info
+        .peer_certificate
+        .as_ref()
+        .and_then(|cert| X509::from_pem(cert.get_ref()).ok())
+        .map(|cert| CertificateMetadata::from(cert).subject())
+        .filter(|subject| !subject.is_empty());
+
+    Some(subject.unwrap_or_else(|| connect_info.remote_addr.ip().to_string()))
 }
 
 #[tonic::async_trait]
@@ -47,6 +142,8 @@ impl proto::Service for Service {
         &self,
         request: Request<proto::PushEventsRequest>,
     ) -> Result<Response<proto::PushEventsResponse>, Status> {
+        self.check_rate_limit(&request)?;
+
         let mut events: Vec<Event> = request
             .into_inner()
             .events
@@ -133,6 +230,13 @@ pub struct VectorConfig {
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
 
+    /// Limits the rate at which each client can send requests.
+    ///
+    /// Requests exceeding the limit are rejected, and retried by the `vector` sink of the client.
+    #[configurable(derived)]
+    #[serde(default)]
+    rate_limit: Option<RateLimitConfig>,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[serde(default)]
     #[configurable(metadata(docs::hidden))]
@@ -156,6 +260,7 @@ impl Default for VectorConfig {
             address: "0.0.0.0:6000".parse().unwrap(),
             tls: None,
             acknowledgements: Default::default(),
+            rate_limit: None,
             log_namespace: None,
         }
     }
@@ -173,11 +278,17 @@ impl SourceConfig for VectorConfig {
         let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
         let log_namespace = cx.log_namespace(self.log_namespace);
+        let rate_limiter = self
+            .rate_limit
+            .as_ref()
+            .map(|rate_limit| rate_limit.build().map(Arc::new))
+            .transpose()?;
 
         let service = proto::Server::new(Service {
             pipeline: cx.out,
             acknowledgements,
             log_namespace,
+            rate_limiter: rate_limiter.clone(),
         })
         .accept_compressed(tonic::codec::CompressionEncoding::Gzip);
 
@@ -186,7 +297,14 @@ impl SourceConfig for VectorConfig {
                 error!(message = "Source future failed.", %error);
             });
 
-        Ok(Box::pin(source))
+        Ok(match (rate_limiter, &self.rate_limit) {
+            (Some(rate_limiter), Some(rate_limit)) => Box::pin(forget_idle_clients(
+                source,
+                rate_limiter,
+                rate_limit.window_secs * 2,
+            )),
+            _ => Box::pin(source),
+        })
     }
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
@@ -266,6 +384,7 @@ mod test {
 #[cfg(feature = "sinks-vector")]
 #[cfg(test)]
 mod tests {
+    use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity};
     use vector_common::assert_event_data_eq;
     use vector_core::config::log_schema;
 
@@ -277,7 +396,7 @@ mod tests {
             self,
             components::{assert_source_compliance, SOURCE_TAGS},
         },
-        SourceSender,
+        tls, SourceSender,
     };
 
     async fn run_test(vector_source_config_str: &str, addr: SocketAddr) {
@@ -337,4 +456,139 @@ mod tests {
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn receive_zstd_compressed_message() {
+        let addr = test_util::next_addr();
+
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let config = format!(
+                r#"address = "{}"
+            compression = "zstd""#,
+                addr
+            );
+            run_test(&config, addr).await;
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn rate_limits_clients() {
+        let addr = test_util::next_addr();
+        let config = format!(
+            r#"address = "{}"
+            rate_limit.threshold = 1
+            rate_limit.window_secs = 60"#,
+            addr
+        );
+        let source: VectorConfig = toml::from_str(&config).unwrap();
+
+        let (tx, _rx) = SourceSender::new_test();
+        let server = source
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(addr).await;
+
+        let mut client = proto::Client::connect(format!("http://{}", addr))
+            .await
+            .unwrap();
+        client
+            .push_events(proto::PushEventsRequest { events: vec![] })
+            .await
+            .unwrap();
+        let status = client
+            .push_events(proto::PushEventsRequest { events: vec![] })
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+    }
+
+    async fn tls_client(
+        addr: SocketAddr,
+        crt_path: &str,
+        key_path: &str,
+    ) -> proto::Client<Channel> {
+        let tls_config = ClientTlsConfig::new()
+            .domain_name("localhost")
+            .ca_certificate(Certificate::from_pem(
+                std::fs::read(tls::TEST_PEM_CA_PATH).unwrap(),
+            ))
+            .identity(Identity::from_pem(
+                std::fs::read(crt_path).unwrap(),
+                std::fs::read(key_path).unwrap(),
+            ));
+        let channel = Endpoint::from_shared(format!("https://{}", addr))
+            .unwrap()
+            .tls_config(tls_config)
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        proto::Client::new(channel)
+    }
+
+    #[tokio::test]
+    async fn rate_limits_clients_by_certificate() {
+        let addr = test_util::next_addr();
+        let config = format!(
+            r#"address = "{}"
+            tls.enabled = true
+            tls.crt_file = "{}"
+            tls.key_file = "{}"
+            tls.ca_file = "{}"
+            tls.verify_certificate = true
+            rate_limit.threshold = 1
+            rate_limit.window_secs = 60"#,
+            addr,
+            tls::TEST_PEM_CRT_PATH,
+            tls::TEST_PEM_KEY_PATH,
+            tls::TEST_PEM_CA_PATH,
+        );
+        let source: VectorConfig = toml::from_str(&config).unwrap();
+
+        let (tx, _rx) = SourceSender::new_test();
+        let server = source
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(addr).await;
+
+        // Both clients connect from the same address, but present different certificates.
+        let mut first = tls_client(
+            addr,
+            tls::TEST_PEM_CLIENT_CRT_PATH,
+            tls::TEST_PEM_CLIENT_KEY_PATH,
+        )
+        .await;
+        let mut second = tls_client(
+            addr,
+            "tests/data/ca/intermediate_client/certs/vector-client-chain.cert.pem",
+            "tests/data/ca/intermediate_client/private/vector-client.key.pem",
+        )
+        .await;
+
+        first
+            .push_events(proto::PushEventsRequest { events: vec![] })
+            .await
+            .unwrap();
+        second
+            .push_events(proto::PushEventsRequest { events: vec![] })
+            .await
+            .unwrap();
+
+        let status = first
+            .push_events(proto::PushEventsRequest { events: vec![] })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+        let status = second
+            .push_events(proto::PushEventsRequest { events: vec![] })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+    }
 }
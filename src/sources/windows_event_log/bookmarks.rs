@@ -0,0 +1,86 @@
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+};
+
+use tokio::fs;
+
+const BOOKMARKS_FILENAME: &str = "bookmarks.json";
+const TMP_BOOKMARKS_FILENAME: &str = "bookmarks.new.json";
+
+/// The bookmarks of each subscribed channel, persisted as JSON in the data directory.
+pub struct Bookmarks {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    bookmarks: HashMap<String, String>,
+}
+
+impl Bookmarks {
+    /// Loads the bookmarks previously persisted in `data_dir`, if any.
+    pub async fn load(data_dir: &Path) -> io::Result<Self> {
+        let path = data_dir.join(BOOKMARKS_FILENAME);
+        let bookmarks = match fs::read(&path).await {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(error) => return Err(error),
+        };
+
+        Ok(Self {
+            path,
+            tmp_path: data_dir.join(TMP_BOOKMARKS_FILENAME),
+            bookmarks,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, channel: &str) -> Option<&str> {
+        self.bookmarks.get(channel).map(String::as_str)
+    }
+
+    /// Updates the bookmark of `channel` and persists all bookmarks.
+    ///
+    /// The bookmarks are written to a temporary file first, so a crash never leaves a partially
+    /// written file behind.
+    pub async fn update(&mut self, channel: &str, bookmark: String) -> io::Result<()> {
+        self.bookmarks.insert(channel.to_string(), bookmark);
+
+        let contents = serde_json::to_vec(&self.bookmarks)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(&self.tmp_path, contents).await?;
+        fs::rename(&self.tmp_path, &self.path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn persists_bookmarks() {
+        let data_dir = tempfile::tempdir().unwrap();
+
+        let mut bookmarks = Bookmarks::load(data_dir.path()).await.unwrap();
+        assert_eq!(bookmarks.get("System"), None);
+
+        bookmarks
+            .update("System", "<BookmarkList/>".into())
+            .await
+            .unwrap();
+        bookmarks
+            .update("Application", "<BookmarkList Direction='backward'/>".into())
+            .await
+            .unwrap();
+
+        let bookmarks = Bookmarks::load(data_dir.path()).await.unwrap();
+        assert_eq!(bookmarks.get("System"), Some("<BookmarkList/>"));
+        assert_eq!(
+            bookmarks.get("Application"),
+            Some("<BookmarkList Direction='backward'/>")
+        );
+    }
+}
@@ -0,0 +1,508 @@
+use std::{
+    io,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use lookup::{lookup_v2::parse_value_path, owned_value_path};
+use snafu::Snafu;
+use tokio::{sync::mpsc, task::spawn_blocking};
+use value::{kind::Collection, Kind};
+use vector_common::internal_event::{CountByteSize, InternalEventHandle as _};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    config::{log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext},
+    internal_events::{
+        EventsReceived, StreamClosedError, WindowsEventLogBookmarkError, WindowsEventLogParseError,
+        WindowsEventLogReadError, WindowsEventLogSubscribeError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod bookmarks;
+mod parser;
+mod subscription;
+
+use self::{
+    bookmarks::Bookmarks,
+    subscription::{RawEvent, Subscription},
+};
+
+/// How long a reader waits for new events before checking whether the source is shutting down.
+const WAIT_TIMEOUT: Duration = Duration::from_millis(500);
+const BACKOFF_DURATION: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one channel must be configured"))]
+    NoChannels,
+    #[snafu(display("`batch_size` must be greater than zero"))]
+    ZeroBatchSize,
+}
+
+/// Configuration for the `windows_event_log` source.
+#[configurable_component(source("windows_event_log"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WindowsEventLogConfig {
+    /// The event log channels to read from.
+    ///
+    /// Channels can be classic event logs, such as `System` or `Application`, or the channels of event providers, such as `Microsoft-Windows-Sysmon/Operational`. The channels available on a host are listed by `wevtutil el`.
+    #[configurable(metadata(docs::examples = "System"))]
+    #[configurable(metadata(docs::examples = "Application"))]
+    #[configurable(metadata(docs::examples = "Microsoft-Windows-Sysmon/Operational"))]
+    pub channels: Vec<String>,
+
+    /// The XPath query used to select the events to read from each channel.
+    ///
+    /// The query follows the same syntax as the filters of the Event Viewer, which only supports a subset of XPath 1.0. By default, all events are read.
+    #[serde(default = "default_query")]
+    #[configurable(metadata(docs::examples = "*[System[(Level=1 or Level=2 or Level=3)]]"))]
+    #[configurable(metadata(docs::examples = "*[System[EventID=4624]]"))]
+    pub query: String,
+
+    /// Whether or not to read the events already present in a channel, when no bookmark has been persisted for it yet.
+    ///
+    /// By default, only the events published after Vector starts are read. Once a bookmark has been persisted for a channel, reading always resumes after it.
+    #[serde(default)]
+    pub read_existing_events: bool,
+
+    /// Whether or not to render the message of each event, using the message resources of its provider.
+    ///
+    /// When disabled, or when the provider has no message resources installed on the host, the events have no message.
+    #[serde(default = "crate::serde::default_true")]
+    pub render_message: bool,
+
+    /// The maximum number of events read from a channel at once.
+    ///
+    /// The bookmark of a channel is persisted after each batch.
+    #[serde(default = "default_batch_size")]
+    pub batch_size: u32,
+
+    /// The directory used to persist the channel bookmarks.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    #[serde(default)]
+    pub data_dir: Option<PathBuf>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_query() -> String {
+    "*".to_string()
+}
+
+const fn default_batch_size() -> u32 {
+    10
+}
+
+impl GenerateConfig for WindowsEventLogConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"channels = ["Application", "System"]"#).unwrap()
+    }
+}
+
+impl WindowsEventLogConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        let definition = match log_namespace {
+            LogNamespace::Vector => Definition::new_with_default_metadata(
+                Kind::bytes().or_null(),
+                [LogNamespace::Vector],
+            ),
+            LogNamespace::Legacy => Definition::new_with_default_metadata(
+                Kind::object(Collection::empty()),
+                [LogNamespace::Legacy],
+            )
+            .with_event_field(
+                &parse_value_path(log_schema().message_key()).expect("valid message key"),
+                Kind::bytes().or_undefined(),
+                Some("message"),
+            ),
+        };
+
+        let fields = [
+            ("provider_name", Kind::bytes(), None),
+            ("provider_guid", Kind::bytes(), None),
+            ("event_id", Kind::integer(), None),
+            ("version", Kind::integer(), None),
+            ("level", Kind::bytes(), Some("severity")),
+            ("task", Kind::integer(), None),
+            ("opcode", Kind::integer(), None),
+            ("keywords", Kind::bytes(), None),
+            ("record_id", Kind::integer(), None),
+            ("process_id", Kind::integer(), None),
+            ("thread_id", Kind::integer(), None),
+            ("channel", Kind::bytes(), None),
+            ("user_id", Kind::bytes(), None),
+            (
+                "event_data",
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())),
+                None,
+            ),
+            (
+                "user_data",
+                Kind::bytes().or_object(Collection::any()),
+                None,
+            ),
+        ];
+
+        fields
+            .into_iter()
+            .fold(definition, |definition, (field, kind, meaning)| {
+                definition.with_source_metadata(
+                    Self::NAME,
+                    Some(LegacyKey::Overwrite(owned_value_path!(field))),
+                    &owned_value_path!(field),
+                    kind.or_undefined(),
+                    meaning,
+                )
+            })
+            .with_source_metadata(
+                Self::NAME,
+                parse_value_path(log_schema().host_key())
+                    .ok()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("host"),
+                Kind::bytes().or_undefined(),
+                Some("host"),
+            )
+            .with_source_metadata(
+                Self::NAME,
+                parse_value_path(log_schema().timestamp_key())
+                    .ok()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp().or_undefined(),
+                Some("timestamp"),
+            )
+            .with_standard_vector_source_metadata()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for WindowsEventLogConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.channels.is_empty() {
+            return Err(BuildError::NoChannels.into());
+        }
+        if self.batch_size == 0 {
+            return Err(BuildError::ZeroBatchSize.into());
+        }
+
+        let data_dir = cx
+            .globals
+            // source are only global, name can be used for subdir
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        Ok(Box::pin(run(
+            self.clone(),
+            data_dir,
+            log_namespace,
+            cx.out,
+            cx.shutdown,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// A batch of events read from a channel, along with the bookmark of its last event.
+struct Batch {
+    channel: String,
+    events: Vec<RawEvent>,
+    bookmark: String,
+}
+
+async fn run(
+    config: WindowsEventLogConfig,
+    data_dir: PathBuf,
+    log_namespace: LogNamespace,
+    mut out: SourceSender,
+    mut shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let mut bookmarks = Bookmarks::load(&data_dir).await.map_err(|error| {
+        emit!(WindowsEventLogBookmarkError {
+            path: &data_dir,
+            error,
+        })
+    })?;
+
+    let (sender, mut receiver) = mpsc::channel(config.channels.len());
+    let stop = Arc::new(AtomicBool::new(false));
+
+    // The subscription API is blocking, so each channel is read from its own thread.
+    let readers = config
+        .channels
+        .iter()
+        .map(|channel| {
+            let reader = ChannelReader {
+                channel: channel.clone(),
+                query: config.query.clone(),
+                bookmark: bookmarks.get(channel).map(ToString::to_string),
+                read_existing_events: config.read_existing_events,
+                render_message: config.render_message,
+                batch_size: config.batch_size,
+                sender: sender.clone(),
+                stop: Arc::clone(&stop),
+            };
+            spawn_blocking(move || reader.run())
+        })
+        .collect::<Vec<_>>();
+    drop(sender);
+
+    let events_received = register!(EventsReceived);
+
+    loop {
+        let batch = tokio::select! {
+            _ = &mut shutdown => break,
+            batch = receiver.recv() => match batch {
+                Some(batch) => batch,
+                None => break,
+            },
+        };
+
+        let events = batch
+            .events
+            .into_iter()
+            .filter_map(|event| {
+                parser::parse_event(&event.xml, event.message, log_namespace)
+                    .map_err(|error| {
+                        emit!(WindowsEventLogParseError {
+                            channel: &batch.channel,
+                            error,
+                        })
+                    })
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+
+        if !events.is_empty() {
+            let count = events.len();
+            events_received.emit(CountByteSize(
+                count,
+                events.estimated_json_encoded_size_of(),
+            ));
+
+            if let Err(error) = out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                break;
+            }
+        }
+
+        if let Err(error) = bookmarks.update(&batch.channel, batch.bookmark).await {
+            emit!(WindowsEventLogBookmarkError {
+                path: bookmarks.path(),
+                error,
+            });
+        }
+    }
+
+    // Wait for the readers to close their subscriptions before returning.
+    stop.store(true, Ordering::Relaxed);
+    drop(receiver);
+    for reader in readers {
+        _ = reader.await;
+    }
+
+    Ok(())
+}
+
+struct ChannelReader {
+    channel: String,
+    query: String,
+    bookmark: Option<String>,
+    read_existing_events: bool,
+    render_message: bool,
+    batch_size: u32,
+    sender: mpsc::Sender<Batch>,
+    stop: Arc<AtomicBool>,
+}
+
+impl ChannelReader {
+    fn run(mut self) {
+        while !self.stopped() {
+            let subscription = Subscription::new(
+                &self.channel,
+                &self.query,
+                self.bookmark.as_deref(),
+                self.read_existing_events,
+                self.render_message,
+            );
+
+            match subscription {
+                Ok(subscription) => {
+                    if !self.read(subscription) {
+                        return;
+                    }
+                }
+                Err(error) => emit!(WindowsEventLogSubscribeError {
+                    channel: &self.channel,
+                    error,
+                }),
+            }
+
+            self.sleep(BACKOFF_DURATION);
+        }
+    }
+
+    /// Reads events until the source stops or the subscription fails, returning `false` if the
+    /// source is stopping.
+    fn read(&mut self, mut subscription: Subscription) -> bool {
+        while !self.stopped() {
+            if !subscription.wait(WAIT_TIMEOUT) {
+                continue;
+            }
+
+            match self.next_batch(&mut subscription) {
+                Ok(None) => {}
+                Ok(Some(batch)) => {
+                    // Resume from the last event read if the subscription has to be recreated.
+                    self.bookmark = Some(batch.bookmark.clone());
+
+                    if self.sender.blocking_send(batch).is_err() {
+                        return false;
+                    }
+                }
+                Err(error) => {
+                    emit!(WindowsEventLogReadError {
+                        channel: &self.channel,
+                        error,
+                    });
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn next_batch(&self, subscription: &mut Subscription) -> io::Result<Option<Batch>> {
+        let events = subscription.next_batch(self.batch_size)?;
+        if events.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Batch {
+            channel: self.channel.clone(),
+            events,
+            bookmark: subscription.bookmark()?,
+        }))
+    }
+
+    fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    /// Sleeps for `duration`, waking up early if the source is stopping.
+    fn sleep(&self, duration: Duration) {
+        let mut remaining = duration;
+        while !remaining.is_zero() && !self.stopped() {
+            let step = remaining.min(WAIT_TIMEOUT);
+            std::thread::sleep(step);
+            remaining -= step;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT: &str = r#"<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+  <System>
+    <Provider Name="Service Control Manager" Guid="{555908d1-a6d7-4695-8e1e-26931d2012f4}"/>
+    <EventID Qualifiers="16384">7036</EventID>
+    <Version>0</Version>
+    <Level>4</Level>
+    <Task>0</Task>
+    <Opcode>0</Opcode>
+    <Keywords>0x8080000000000000</Keywords>
+    <TimeCreated SystemTime="2022-12-01T10:15:30.1234567Z"/>
+    <EventRecordID>52311</EventRecordID>
+    <Execution ProcessID="700" ThreadID="6104"/>
+    <Channel>System</Channel>
+    <Computer>web-01.example.com</Computer>
+    <Security UserID="S-1-5-18"/>
+  </System>
+  <EventData>
+    <Data Name="param1">Windows Update</Data>
+    <Data Name="param2">running</Data>
+  </EventData>
+</Event>"#;
+
+    fn config(log_namespace: Option<bool>) -> WindowsEventLogConfig {
+        WindowsEventLogConfig {
+            log_namespace,
+            ..toml::from_str(r#"channels = ["System"]"#).unwrap()
+        }
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WindowsEventLogConfig>();
+    }
+
+    #[test]
+    fn parses_config_defaults() {
+        let config = config(None);
+
+        assert_eq!(config.channels, vec!["System"]);
+        assert_eq!(config.query, "*");
+        assert!(!config.read_existing_events);
+        assert!(config.render_message);
+        assert_eq!(config.batch_size, 10);
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let definition = config(Some(true)).outputs(LogNamespace::Vector)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+
+        let log = parser::parse_event(
+            EVENT,
+            Some("The Windows Update service entered the running state.".into()),
+            LogNamespace::Vector,
+        )
+        .unwrap();
+
+        definition.assert_valid_for_event(&log.into());
+    }
+
+    #[test]
+    fn output_schema_definition_legacy_namespace() {
+        let definition = config(None).outputs(LogNamespace::Legacy)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+
+        let log = parser::parse_event(EVENT, None, LogNamespace::Legacy).unwrap();
+
+        definition.assert_valid_for_event(&log.into());
+    }
+}
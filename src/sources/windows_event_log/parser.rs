@@ -0,0 +1,345 @@
+//! Conversion of rendered event XML into log events.
+//!
+//! Events are rendered with `EvtRenderEventXml`, which produces documents following the
+//! [event schema](https://learn.microsoft.com/en-us/windows/win32/wes/eventschema-schema).
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use lookup::{lookup_v2::parse_value_path, path};
+use roxmltree::{Document, Node};
+use vector_config::NamedComponent;
+use vector_core::config::{LegacyKey, LogNamespace};
+
+use super::WindowsEventLogConfig;
+use crate::{
+    config::log_schema,
+    event::{LogEvent, Value},
+};
+
+/// Returns the name of the provider that published the event, used to look up its message strings.
+pub fn provider_name(xml: &str) -> Option<String> {
+    let document = Document::parse(xml).ok()?;
+    let system = child(document.root_element(), "System")?;
+    child(system, "Provider")?
+        .attribute("Name")
+        .map(ToString::to_string)
+}
+
+/// Builds a log event from the rendered XML of an event and its optional rendered message.
+pub fn parse_event(
+    xml: &str,
+    message: Option<String>,
+    log_namespace: LogNamespace,
+) -> Result<LogEvent, roxmltree::Error> {
+    let document = Document::parse(xml)?;
+    let root = document.root_element();
+
+    let mut log = match log_namespace {
+        LogNamespace::Vector => LogEvent::from(message.map(Value::from).unwrap_or(Value::Null)),
+        LogNamespace::Legacy => {
+            let mut log = LogEvent::default();
+            if let Some(message) = message {
+                log.insert(log_schema().message_key(), message);
+            }
+            log
+        }
+    };
+
+    let mut fields = BTreeMap::new();
+    let mut host = None;
+    let mut timestamp = None;
+
+    if let Some(system) = child(root, "System") {
+        for node in system.children().filter(Node::is_element) {
+            match node.tag_name().name() {
+                "Provider" => {
+                    insert_attribute(&mut fields, "provider_name", node, "Name");
+                    insert_attribute(&mut fields, "provider_guid", node, "Guid");
+                }
+                "EventID" => insert_integer(&mut fields, "event_id", node.text()),
+                "Version" => insert_integer(&mut fields, "version", node.text()),
+                "Level" => {
+                    if let Some(level) = node.text().and_then(|level| level.parse().ok()) {
+                        fields.insert("level", level_name(level).into());
+                    }
+                }
+                "Task" => insert_integer(&mut fields, "task", node.text()),
+                "Opcode" => insert_integer(&mut fields, "opcode", node.text()),
+                "Keywords" => insert_text(&mut fields, "keywords", node.text()),
+                "TimeCreated" => {
+                    timestamp = node
+                        .attribute("SystemTime")
+                        .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                        .map(|time| time.with_timezone(&Utc));
+                }
+                "EventRecordID" => insert_integer(&mut fields, "record_id", node.text()),
+                "Execution" => {
+                    insert_integer(&mut fields, "process_id", node.attribute("ProcessID"));
+                    insert_integer(&mut fields, "thread_id", node.attribute("ThreadID"));
+                }
+                "Channel" => insert_text(&mut fields, "channel", node.text()),
+                "Computer" => host = node.text().map(ToString::to_string),
+                "Security" => insert_attribute(&mut fields, "user_id", node, "UserID"),
+                _ => {}
+            }
+        }
+    }
+
+    if let Some(event_data) = child(root, "EventData") {
+        fields.insert("event_data", Value::Object(event_data_fields(event_data)));
+    }
+
+    if let Some(user_data) = child(root, "UserData").and_then(|node| node.first_element_child()) {
+        fields.insert("user_data", element_value(user_data));
+    }
+
+    for (key, value) in fields {
+        log_namespace.insert_source_metadata(
+            WindowsEventLogConfig::NAME,
+            &mut log,
+            Some(LegacyKey::Overwrite(path!(key))),
+            path!(key),
+            value,
+        );
+    }
+
+    if let Some(host) = host {
+        log_namespace.insert_source_metadata(
+            WindowsEventLogConfig::NAME,
+            &mut log,
+            parse_value_path(log_schema().host_key())
+                .ok()
+                .as_ref()
+                .map(LegacyKey::Overwrite),
+            path!("host"),
+            host,
+        );
+    }
+
+    if let Some(timestamp) = timestamp {
+        log_namespace.insert_source_metadata(
+            WindowsEventLogConfig::NAME,
+            &mut log,
+            parse_value_path(log_schema().timestamp_key())
+                .ok()
+                .as_ref()
+                .map(LegacyKey::Overwrite),
+            path!("timestamp"),
+            timestamp,
+        );
+    }
+
+    log_namespace.insert_standard_vector_source_metadata(
+        &mut log,
+        WindowsEventLogConfig::NAME,
+        Utc::now(),
+    );
+
+    Ok(log)
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name(name))
+}
+
+/// Maps the standard event levels to their names, as displayed by the Event Viewer.
+fn level_name(level: u8) -> String {
+    match level {
+        0 | 4 => "information".to_string(),
+        1 => "critical".to_string(),
+        2 => "error".to_string(),
+        3 => "warning".to_string(),
+        5 => "verbose".to_string(),
+        level => level.to_string(),
+    }
+}
+
+fn insert_integer(
+    fields: &mut BTreeMap<&'static str, Value>,
+    key: &'static str,
+    text: Option<&str>,
+) {
+    if let Some(value) = text.and_then(|text| text.trim().parse::<i64>().ok()) {
+        fields.insert(key, value.into());
+    }
+}
+
+fn insert_text(fields: &mut BTreeMap<&'static str, Value>, key: &'static str, text: Option<&str>) {
+    if let Some(text) = text {
+        fields.insert(key, text.into());
+    }
+}
+
+fn insert_attribute(
+    fields: &mut BTreeMap<&'static str, Value>,
+    key: &'static str,
+    node: Node,
+    attribute: &str,
+) {
+    insert_text(fields, key, node.attribute(attribute));
+}
+
+/// Collects the `Data` elements of `EventData`, naming the unnamed ones `param1`, `param2` and so on.
+fn event_data_fields(event_data: Node) -> BTreeMap<String, Value> {
+    event_data
+        .children()
+        .filter(|node| node.has_tag_name("Data"))
+        .enumerate()
+        .map(|(index, node)| {
+            let name = node
+                .attribute("Name")
+                .map(ToString::to_string)
+                .unwrap_or_else(|| format!("param{}", index + 1));
+            (name, node.text().unwrap_or_default().into())
+        })
+        .collect()
+}
+
+/// Converts an element to an object of its child elements, or to its text if it has none.
+fn element_value(node: Node) -> Value {
+    if node.first_element_child().is_none() {
+        return node.text().unwrap_or_default().into();
+    }
+
+    Value::Object(
+        node.children()
+            .filter(Node::is_element)
+            .map(|child| (child.tag_name().name().to_string(), element_value(child)))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use lookup::{event_path, metadata_path};
+
+    use super::*;
+
+    const SECURITY_EVENT: &str = r#"<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+  <System>
+    <Provider Name="Microsoft-Windows-Security-Auditing" Guid="{54849625-5478-4994-a5ba-3e3b0328c30d}"/>
+    <EventID>4624</EventID>
+    <Version>2</Version>
+    <Level>0</Level>
+    <Task>12544</Task>
+    <Opcode>0</Opcode>
+    <Keywords>0x8020000000000000</Keywords>
+    <TimeCreated SystemTime="2022-12-01T10:15:30.1234567Z"/>
+    <EventRecordID>184021</EventRecordID>
+    <Correlation/>
+    <Execution ProcessID="748" ThreadID="5272"/>
+    <Channel>Security</Channel>
+    <Computer>web-01.example.com</Computer>
+    <Security/>
+  </System>
+  <EventData>
+    <Data Name="TargetUserName">alice</Data>
+    <Data Name="LogonType">2</Data>
+  </EventData>
+</Event>"#;
+
+    const USER_DATA_EVENT: &str = r#"<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+  <System>
+    <Provider Name="Microsoft-Windows-Eventlog"/>
+    <EventID>1102</EventID>
+    <Level>4</Level>
+    <Channel>Security</Channel>
+    <Security UserID="S-1-5-18"/>
+  </System>
+  <UserData>
+    <LogFileCleared xmlns="http://manifests.microsoft.com/win/2004/08/windows/eventlog">
+      <SubjectUserName>bob</SubjectUserName>
+      <SubjectDomainName>EXAMPLE</SubjectDomainName>
+    </LogFileCleared>
+  </UserData>
+</Event>"#;
+
+    #[test]
+    fn parses_provider_name() {
+        assert_eq!(
+            provider_name(SECURITY_EVENT).as_deref(),
+            Some("Microsoft-Windows-Security-Auditing")
+        );
+        assert_eq!(provider_name("<Event/>"), None);
+    }
+
+    #[test]
+    fn parses_legacy_event() {
+        let log = parse_event(
+            SECURITY_EVENT,
+            Some("An account was successfully logged on.".into()),
+            LogNamespace::Legacy,
+        )
+        .unwrap();
+
+        assert_eq!(
+            log[log_schema().message_key()],
+            "An account was successfully logged on.".into()
+        );
+        assert_eq!(log[log_schema().host_key()], "web-01.example.com".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            DateTime::parse_from_rfc3339("2022-12-01T10:15:30.1234567Z")
+                .unwrap()
+                .with_timezone(&Utc)
+                .into()
+        );
+        assert_eq!(
+            log["provider_name"],
+            "Microsoft-Windows-Security-Auditing".into()
+        );
+        assert_eq!(log["event_id"], 4624.into());
+        assert_eq!(log["level"], "information".into());
+        assert_eq!(log["keywords"], "0x8020000000000000".into());
+        assert_eq!(log["record_id"], 184021.into());
+        assert_eq!(log["process_id"], 748.into());
+        assert_eq!(log["channel"], "Security".into());
+        assert_eq!(log["event_data.TargetUserName"], "alice".into());
+        assert_eq!(log["event_data.LogonType"], "2".into());
+        assert_eq!(log["source_type"], "windows_event_log".into());
+        assert!(log.get("user_id").is_none());
+    }
+
+    #[test]
+    fn parses_vector_namespace_event() {
+        let log = parse_event(USER_DATA_EVENT, None, LogNamespace::Vector).unwrap();
+
+        assert_eq!(log.value(), &Value::Null);
+        assert_eq!(
+            log.get(metadata_path!("windows_event_log", "event_id")),
+            Some(&1102.into())
+        );
+        assert_eq!(
+            log.get(metadata_path!("windows_event_log", "user_id")),
+            Some(&"S-1-5-18".into())
+        );
+        assert_eq!(
+            log.get(metadata_path!(
+                "windows_event_log",
+                "user_data",
+                "SubjectUserName"
+            )),
+            Some(&"bob".into())
+        );
+        assert_eq!(
+            log.get(metadata_path!("vector", "source_type")),
+            Some(&"windows_event_log".into())
+        );
+        assert!(log.get(event_path!("event_id")).is_none());
+    }
+
+    #[test]
+    fn names_unnamed_event_data() {
+        let xml = r#"<Event><System><EventID>1</EventID></System><EventData><Data>first</Data><Data>second</Data></EventData></Event>"#;
+        let log = parse_event(xml, None, LogNamespace::Legacy).unwrap();
+
+        assert_eq!(log["event_data.param1"], "first".into());
+        assert_eq!(log["event_data.param2"], "second".into());
+    }
+
+    #[test]
+    fn rejects_invalid_xml() {
+        assert!(parse_event("<Event>", None, LogNamespace::Legacy).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+//! A wrapper around the Windows Event Log subscription API.
+//!
+//! Subscriptions are created in pull mode: the system signals an event object whenever new
+//! events match the query, and they are then read in batches with `EvtNext`.
+
+use std::{collections::HashMap, ffi::OsStr, io, os::windows::ffi::OsStrExt, ptr, time::Duration};
+
+use windows_sys::Win32::{
+    Foundation::{
+        CloseHandle, ERROR_INSUFFICIENT_BUFFER, ERROR_NO_MORE_ITEMS, HANDLE, WAIT_OBJECT_0,
+    },
+    System::{
+        EventLog::{
+            EvtClose, EvtCreateBookmark, EvtFormatMessage, EvtFormatMessageEvent, EvtNext,
+            EvtOpenPublisherMetadata, EvtRender, EvtRenderBookmark, EvtRenderEventXml,
+            EvtSubscribe, EvtSubscribeStartAfterBookmark, EvtSubscribeStartAtOldestRecord,
+            EvtSubscribeToFutureEvents, EvtUpdateBookmark, EVT_HANDLE,
+        },
+        Threading::{CreateEventW, ResetEvent, WaitForSingleObject},
+    },
+};
+
+use super::parser;
+
+/// An event read from a subscription, before parsing.
+pub struct RawEvent {
+    pub xml: String,
+    pub message: Option<String>,
+}
+
+/// An owned handle returned by the `Evt*` functions.
+struct EvtHandle(EVT_HANDLE);
+
+impl EvtHandle {
+    fn new(handle: EVT_HANDLE) -> io::Result<Self> {
+        if handle == 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(Self(handle))
+        }
+    }
+}
+
+impl Drop for EvtHandle {
+    fn drop(&mut self) {
+        unsafe {
+            EvtClose(self.0);
+        }
+    }
+}
+
+/// An owned event object, signaled when new events are available.
+struct SignalEvent(HANDLE);
+
+impl Drop for SignalEvent {
+    fn drop(&mut self) {
+        unsafe {
+            CloseHandle(self.0);
+        }
+    }
+}
+
+/// A pull subscription to a single channel.
+pub struct Subscription {
+    // The subscription must be closed before the event object it signals.
+    handle: EvtHandle,
+    signal: SignalEvent,
+    bookmark: EvtHandle,
+    render_message: bool,
+    /// Publisher metadata used to render messages, or `None` if the publisher can't be opened.
+    publishers: HashMap<String, Option<EvtHandle>>,
+}
+
+impl Subscription {
+    /// Subscribes to the events of `channel` matching the XPath `query`.
+    ///
+    /// When a bookmark is given, reading resumes after the bookmarked event. Otherwise, reading
+    /// starts with the oldest event still in the channel if `read_existing_events` is set, or
+    /// with the events published from now on.
+    pub fn new(
+        channel: &str,
+        query: &str,
+        bookmark: Option<&str>,
+        read_existing_events: bool,
+        render_message: bool,
+    ) -> io::Result<Self> {
+        let signal = unsafe { CreateEventW(ptr::null(), 1, 1, ptr::null()) };
+        if signal == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let signal = SignalEvent(signal);
+
+        let bookmark_xml = bookmark.map(to_wide);
+        let bookmark_handle = EvtHandle::new(unsafe {
+            EvtCreateBookmark(
+                bookmark_xml
+                    .as_ref()
+                    .map_or(ptr::null(), |bookmark| bookmark.as_ptr()),
+            )
+        })?;
+
+        let flags = match (bookmark, read_existing_events) {
+            (Some(_), _) => EvtSubscribeStartAfterBookmark,
+            (None, true) => EvtSubscribeStartAtOldestRecord,
+            (None, false) => EvtSubscribeToFutureEvents,
+        };
+
+        let channel = to_wide(channel);
+        let query = to_wide(query);
+        let handle = EvtHandle::new(unsafe {
+            EvtSubscribe(
+                0,
+                signal.0,
+                channel.as_ptr(),
+                query.as_ptr(),
+                if bookmark.is_some() {
+                    bookmark_handle.0
+                } else {
+                    0
+                },
+                ptr::null(),
+                None,
+                flags,
+            )
+        })?;
+
+        Ok(Self {
+            handle,
+            signal,
+            bookmark: bookmark_handle,
+            render_message,
+            publishers: HashMap::new(),
+        })
+    }
+
+    /// Waits up to `timeout` for new events to be signaled, returning `true` if there are any.
+    pub fn wait(&self, timeout: Duration) -> bool {
+        let timeout = timeout.as_millis().try_into().unwrap_or(u32::MAX);
+        unsafe { WaitForSingleObject(self.signal.0, timeout) == WAIT_OBJECT_0 }
+    }
+
+    /// Reads up to `batch_size` events, advancing the bookmark past them.
+    ///
+    /// An empty batch means that all available events have been read.
+    pub fn next_batch(&mut self, batch_size: u32) -> io::Result<Vec<RawEvent>> {
+        let mut handles = vec![0; batch_size as usize];
+        let mut returned = 0;
+        let result = unsafe {
+            EvtNext(
+                self.handle.0,
+                batch_size,
+                handles.as_mut_ptr(),
+                0,
+                0,
+                &mut returned,
+            )
+        };
+        if result == 0 {
+            let error = io::Error::last_os_error();
+            if error.raw_os_error() == Some(ERROR_NO_MORE_ITEMS as i32) {
+                unsafe {
+                    ResetEvent(self.signal.0);
+                }
+                return Ok(Vec::new());
+            }
+            return Err(error);
+        }
+
+        // Take ownership of all the returned handles first, so they get closed even on errors.
+        let events = handles[..returned as usize]
+            .iter()
+            .map(|&handle| EvtHandle(handle))
+            .collect::<Vec<_>>();
+
+        events
+            .iter()
+            .map(|event| {
+                let xml = render(event.0, EvtRenderEventXml)?;
+                let message = if self.render_message {
+                    parser::provider_name(&xml)
+                        .and_then(|provider| self.format_message(&provider, event.0))
+                } else {
+                    None
+                };
+
+                if unsafe { EvtUpdateBookmark(self.bookmark.0, event.0) } == 0 {
+                    return Err(io::Error::last_os_error());
+                }
+
+                Ok(RawEvent { xml, message })
+            })
+            .collect()
+    }
+
+    /// Renders the bookmark of the last event read as XML.
+    pub fn bookmark(&self) -> io::Result<String> {
+        render(self.bookmark.0, EvtRenderBookmark)
+    }
+
+    fn format_message(&mut self, provider: &str, event: EVT_HANDLE) -> Option<String> {
+        let metadata = self
+            .publishers
+            .entry(provider.to_string())
+            .or_insert_with(|| {
+                let provider = to_wide(provider);
+                EvtHandle::new(unsafe {
+                    EvtOpenPublisherMetadata(0, provider.as_ptr(), ptr::null(), 0, 0)
+                })
+                .ok()
+            })
+            .as_ref()?;
+
+        let mut used = 0;
+        let result = unsafe {
+            EvtFormatMessage(
+                metadata.0,
+                event,
+                0,
+                0,
+                ptr::null(),
+                EvtFormatMessageEvent,
+                0,
+                ptr::null_mut(),
+                &mut used,
+            )
+        };
+        if result == 0 && !is_insufficient_buffer() {
+            return None;
+        }
+
+        // The size used by `EvtFormatMessage` is in characters.
+        let mut buffer = vec![0u16; used as usize];
+        let result = unsafe {
+            EvtFormatMessage(
+                metadata.0,
+                event,
+                0,
+                0,
+                ptr::null(),
+                EvtFormatMessageEvent,
+                buffer.len() as u32,
+                buffer.as_mut_ptr(),
+                &mut used,
+            )
+        };
+        (result != 0).then(|| from_wide(&buffer))
+    }
+}
+
+/// Renders an event or a bookmark as XML.
+fn render(handle: EVT_HANDLE, flags: u32) -> io::Result<String> {
+    let mut used = 0;
+    let mut properties = 0;
+    let result = unsafe {
+        EvtRender(
+            0,
+            handle,
+            flags,
+            0,
+            ptr::null_mut(),
+            &mut used,
+            &mut properties,
+        )
+    };
+    if result == 0 && !is_insufficient_buffer() {
+        return Err(io::Error::last_os_error());
+    }
+
+    // The size used by `EvtRender` is in bytes.
+    let mut buffer = vec![0u16; (used as usize + 1) / 2];
+    let result = unsafe {
+        EvtRender(
+            0,
+            handle,
+            flags,
+            (buffer.len() * 2) as u32,
+            buffer.as_mut_ptr().cast(),
+            &mut used,
+            &mut properties,
+        )
+    };
+    if result == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(from_wide(&buffer))
+}
+
+fn is_insufficient_buffer() -> bool {
+    io::Error::last_os_error().raw_os_error() == Some(ERROR_INSUFFICIENT_BUFFER as i32)
+}
+
+fn to_wide(value: &str) -> Vec<u16> {
+    OsStr::new(value).encode_wide().chain(Some(0)).collect()
+}
+
+fn from_wide(buffer: &[u16]) -> String {
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..len])
+}
@@ -59,7 +59,7 @@ pub(crate) async fn cmd(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode
             .collect()
     };
 
-    let formatter = EventFormatter::new(opts.meta, opts.format);
+    let formatter = EventFormatter::new(opts.meta || opts.delivery_status, opts.format);
 
     loop {
         tokio::select! {
@@ -103,6 +103,7 @@ async fn run(
             opts.format,
             opts.limit as i64,
             opts.interval as i64,
+            opts.delivery_status,
         );
     };
 
@@ -116,13 +117,13 @@ async fn run(
                 for tap_event in d.output_events_by_component_id_patterns.iter() {
                     match tap_event {
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::Log(ev) => {
-                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()));
+                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.delivery_status.as_ref().map(|status| status.as_str()), ev.string.as_ref()));
                         },
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::Metric(ev) => {
-                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()));
+                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.delivery_status.as_ref().map(|status| status.as_str()), ev.string.as_ref()));
                         },
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::Trace(ev) => {
-                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()));
+                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.delivery_status.as_ref().map(|status| status.as_str()), ev.string.as_ref()));
                         },
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::EventNotification(ev) => {
                             if !opts.quiet {
@@ -145,6 +146,7 @@ struct EventFormatter {
     component_id_label: ColoredString,
     component_kind_label: ColoredString,
     component_type_label: ColoredString,
+    delivery_status_label: ColoredString,
 }
 
 impl EventFormatter {
@@ -155,6 +157,7 @@ impl EventFormatter {
             component_id_label: "component_id".green(),
             component_kind_label: "component_kind".green(),
             component_type_label: "component_type".green(),
+            delivery_status_label: "delivery_status".green(),
         }
     }
 
@@ -163,50 +166,71 @@ impl EventFormatter {
         component_id: &str,
         component_kind: &str,
         component_type: &str,
+        delivery_status: Option<&str>,
         event: &'a str,
     ) -> Cow<'a, str> {
         if self.meta {
             match self.format {
-                TapEncodingFormat::Json => format!(
-                    r#"{{"{}":"{}","{}":"{}","{}":"{}","event":{}}}"#,
-                    self.component_id_label,
-                    component_id.green(),
-                    self.component_kind_label,
-                    component_kind.green(),
-                    self.component_type_label,
-                    component_type.green(),
-                    event
-                )
-                .into(),
+                TapEncodingFormat::Json => {
+                    let delivery_status = delivery_status
+                        .map(|status| {
+                            format!(r#""{}":"{}","#, self.delivery_status_label, status.green())
+                        })
+                        .unwrap_or_default();
+                    format!(
+                        r#"{{"{}":"{}","{}":"{}","{}":"{}",{}"event":{}}}"#,
+                        self.component_id_label,
+                        component_id.green(),
+                        self.component_kind_label,
+                        component_kind.green(),
+                        self.component_type_label,
+                        component_type.green(),
+                        delivery_status,
+                        event
+                    )
+                    .into()
+                }
                 TapEncodingFormat::Yaml => {
                     let mut value: BTreeMap<String, serde_yaml::Value> = BTreeMap::new();
                     value.insert("event".to_string(), serde_yaml::from_str(event).unwrap());
+                    let delivery_status = delivery_status
+                        .map(|status| {
+                            format!("{}: {}\n", self.delivery_status_label, status.green())
+                        })
+                        .unwrap_or_default();
                     // We interpolate to include component_id rather than
                     // include it in the map to correctly preserve color
                     // formatting
                     format!(
-                        "{}{}: {}\n{}: {}\n{}: {}\n",
+                        "{}{}: {}\n{}: {}\n{}: {}\n{}",
                         serde_yaml::to_string(&value).unwrap(),
                         self.component_id_label,
                         component_id.green(),
                         self.component_kind_label,
                         component_kind.green(),
                         self.component_type_label,
-                        component_type.green()
+                        component_type.green(),
+                        delivery_status
+                    )
+                    .into()
+                }
+                TapEncodingFormat::Logfmt => {
+                    let delivery_status = delivery_status
+                        .map(|status| format!("{}={} ", self.delivery_status_label, status.green()))
+                        .unwrap_or_default();
+                    format!(
+                        "{}={} {}={} {}={} {}{}",
+                        self.component_id_label,
+                        component_id.green(),
+                        self.component_kind_label,
+                        component_kind.green(),
+                        self.component_type_label,
+                        component_type.green(),
+                        delivery_status,
+                        event
                     )
                     .into()
                 }
-                TapEncodingFormat::Logfmt => format!(
-                    "{}={} {}={} {}={} {}",
-                    self.component_id_label,
-                    component_id.green(),
-                    self.component_kind_label,
-                    component_kind.green(),
-                    self.component_type_label,
-                    component_type.green(),
-                    event
-                )
-                .into(),
             }
         } else {
             event.into()
@@ -44,6 +44,12 @@ pub struct Opts {
     #[arg(short, long)]
     meta: bool,
 
+    /// Hold events until their final delivery status is known, and include it in their metadata.
+    /// Implies `--meta`; the status of events is only tracked when acknowledgements are enabled
+    /// for the sinks they're sent to.
+    #[arg(short, long)]
+    delivery_status: bool,
+
     /// Whether to reconnect if the underlying Vector API connection drops. By default, tap will attempt to reconnect if the connection drops.
     #[arg(short, long)]
     no_reconnect: bool,
@@ -146,11 +146,29 @@ impl Template {
         if self.is_static {
             Ok(self.src.clone())
         } else {
-            self.render_event(event.into())
+            self.render_event(event.into(), |value| Cow::Borrowed(value))
         }
     }
 
-    fn render_event(&self, event: EventRef<'_>) -> Result<String, TemplateRenderingError> {
+    /// Renders the template like `render_string`, with the values of the fields it references
+    /// passed through `escape`, such as to encode them for where they're rendered.
+    pub fn render_string_escaped<'a>(
+        &self,
+        event: impl Into<EventRef<'a>>,
+        escape: impl Fn(&str) -> Cow<'_, str>,
+    ) -> Result<String, TemplateRenderingError> {
+        if self.is_static {
+            Ok(self.src.clone())
+        } else {
+            self.render_event(event.into(), escape)
+        }
+    }
+
+    fn render_event(
+        &self,
+        event: EventRef<'_>,
+        escape: impl Fn(&str) -> Cow<'_, str>,
+    ) -> Result<String, TemplateRenderingError> {
         let mut missing_keys = Vec::new();
         let mut out = String::with_capacity(self.reserve_size);
         for part in &self.parts {
@@ -158,19 +176,17 @@ impl Template {
                 Part::Literal(lit) => out.push_str(lit),
                 Part::Strftime(items) => out.push_str(&render_timestamp(items, event)),
                 Part::Reference(key) => {
-                    out.push_str(
-                        &match event {
-                            EventRef::Log(log) => log.get(&**key).map(Value::to_string_lossy),
-                            EventRef::Metric(metric) => {
-                                render_metric_field(key, metric).map(Cow::Borrowed)
-                            }
-                            EventRef::Trace(trace) => trace.get(&key).map(Value::to_string_lossy),
+                    let value = match event {
+                        EventRef::Log(log) => log.get(&**key).map(Value::to_string_lossy),
+                        EventRef::Metric(metric) => {
+                            render_metric_field(key, metric).map(Cow::Borrowed)
                         }
-                        .unwrap_or_else(|| {
-                            missing_keys.push(key.to_owned());
-                            Cow::Borrowed("")
-                        }),
-                    );
+                        EventRef::Trace(trace) => trace.get(&key).map(Value::to_string_lossy),
+                    };
+                    match value {
+                        Some(value) => out.push_str(&escape(&*value)),
+                        None => missing_keys.push(key.to_owned()),
+                    }
                 }
             }
         }
@@ -431,6 +447,18 @@ mod tests {
         assert_eq!(Ok(Bytes::from("stream-abcd")), template.render(&event))
     }
 
+    #[test]
+    fn render_log_dynamic_escaped() {
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("log_stream", "stream");
+        let template = Template::try_from("abcd-{{log_stream}}").unwrap();
+
+        assert_eq!(
+            Ok("abcd-STREAM".to_owned()),
+            template.render_string_escaped(&event, |value| Cow::Owned(value.to_uppercase()))
+        )
+    }
+
     #[test]
     fn render_log_dynamic_missing_key() {
         let event = Event::Log(LogEvent::from("hello world"));
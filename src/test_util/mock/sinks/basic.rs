@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use futures_util::{stream::BoxStream, FutureExt, StreamExt};
 use snafu::Snafu;
 use tokio::sync::oneshot;
-use vector_common::finalization::Finalizable;
+use vector_common::finalization::{EventStatus, Finalizable};
 use vector_config::configurable_component;
 use vector_core::{
     config::{AcknowledgementsConfig, Input},
@@ -26,6 +26,9 @@ pub struct BasicSinkConfig {
     #[serde(skip)]
     healthy: bool,
 
+    #[serde(skip)]
+    status: Option<EventStatus>,
+
     /// Dummy field used for generating unique configurations to trigger reloads.
     data: Option<String>,
 }
@@ -50,6 +53,7 @@ impl BasicSinkConfig {
         Self {
             sink: Mode::Normal(sink),
             healthy,
+            status: None,
             data: None,
         }
     }
@@ -58,9 +62,16 @@ impl BasicSinkConfig {
         Self {
             sink: Mode::Normal(sink),
             healthy,
+            status: None,
             data: Some(data.into()),
         }
     }
+
+    /// Finalizes the events the sink forwards with `status`.
+    pub const fn with_status(mut self, status: EventStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
 }
 
 #[derive(Debug, Snafu)]
@@ -87,6 +98,7 @@ impl SinkConfig for BasicSinkConfig {
         let sink = MockSink {
             sink: self.sink.clone(),
             health_tx,
+            status: self.status,
         };
 
         let healthcheck = async move { rx.await.unwrap() };
@@ -106,6 +118,7 @@ impl SinkConfig for BasicSinkConfig {
 struct MockSink {
     sink: Mode,
     health_tx: Option<oneshot::Sender<crate::Result<()>>>,
+    status: Option<EventStatus>,
 }
 
 #[async_trait]
@@ -123,6 +136,9 @@ impl StreamSink<Event> for MockSink {
                     if let Err(error) = sink.send_event(event).await {
                         error!(message = "Ingesting an event failed at mock sink.", %error);
                     }
+                    if let Some(status) = self.status {
+                        finalizers.update_status(status);
+                    }
                     drop(finalizers);
                 }
             }
@@ -6,7 +6,7 @@ use std::{
     time::Instant,
 };
 
-use futures::{stream::FuturesOrdered, FutureExt, StreamExt, TryStreamExt};
+use futures::{future, stream::FuturesOrdered, FutureExt, StreamExt, TryStreamExt};
 use futures_util::stream::FuturesUnordered;
 use once_cell::sync::Lazy;
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
@@ -34,6 +34,7 @@ use vector_core::{
 };
 
 use super::{
+    dead_letter::DeadLetters,
     fanout::{self, Fanout},
     schema,
     task::{Task, TaskOutput, TaskResult},
@@ -189,14 +190,21 @@ pub async fn build_pieces(
         let mut controls = HashMap::new();
         let mut schema_definitions = HashMap::with_capacity(source_outputs.len());
 
+        let source_id: Arc<str> = Arc::from(key.id());
         for output in source_outputs {
             let mut rx = builder.add_output(output.clone());
 
             let (mut fanout, control) = Fanout::new();
+            let source_id = Arc::clone(&source_id);
             let pump = async move {
                 debug!("Source pump starting.");
 
-                while let Some(array) = rx.next().await {
+                while let Some(mut array) = rx.next().await {
+                    // Events are grouped by the source they were received by when they're sent.
+                    for mut event in array.iter_events_mut() {
+                        event.metadata_mut().set_source_id(&source_id);
+                    }
+
                     fanout.send(array).await.map_err(|e| {
                         debug!("Source pump finished with an error.");
                         TaskError::wrapped(e)
@@ -263,6 +271,7 @@ pub async fn build_pieces(
         let context = SourceContext {
             key: key.clone(),
             globals: config.global.clone(),
+            enrichment_tables: enrichment_tables.clone(),
             shutdown: shutdown_signal,
             out: pipeline,
             proxy: ProxyConfig::merge_with_env(&config.global.proxy, &source.proxy),
@@ -461,11 +470,51 @@ pub async fn build_pieces(
             }
         };
 
+        // Events sent to the outputs of the sink are pumped into their fanouts alongside the sink.
+        let sink_outputs = sink.outputs();
+        let mut pumps = Vec::with_capacity(sink_outputs.len());
+        let out = if sink_outputs.is_empty() {
+            None
+        } else {
+            let mut builder = SourceSender::builder()
+                .with_buffer(*SOURCE_SENDER_BUFFER_SIZE)
+                .without_lag_time();
+            for output in sink_outputs {
+                let mut rx = builder.add_output(output.clone());
+
+                let (mut fanout, control) = Fanout::new();
+                pumps.push(async move {
+                    while let Some(array) = rx.next().await {
+                        fanout.send(array).await.map_err(TaskError::wrapped)?;
+                    }
+                    Ok::<(), TaskError>(())
+                });
+                outputs.insert(
+                    OutputId {
+                        component: key.clone(),
+                        port: output.port,
+                    },
+                    control,
+                );
+            }
+            Some(builder.build())
+        };
+
+        // Events the sink fails to deliver are sent to its `dead_letter` output once finalized.
+        let (dead_letters, dead_letter_task) = match (&sink.dead_letter, &out) {
+            (Some(_), Some(out)) => {
+                let (dead_letters, task) = DeadLetters::new(key.clone(), typetag, out.clone());
+                (Some(dead_letters), Some(task))
+            }
+            _ => (None, None),
+        };
+
         let cx = SinkContext {
             healthcheck,
             globals: config.global.clone(),
             proxy: ProxyConfig::merge_with_env(&config.global.proxy, sink.proxy()),
             schema: config.schema,
+            out,
         };
 
         let (sink, healthcheck) = match sink.inner.build(cx).await {
@@ -496,7 +545,7 @@ pub async fn build_pieces(
             let mut rx = wrap(rx);
 
             let events_received = register!(EventsReceived);
-            sink.run(
+            let run = sink.run(
                 rx.by_ref()
                     .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
                     .inspect(|events| {
@@ -505,17 +554,45 @@ pub async fn build_pieces(
                             events.estimated_json_encoded_size_of(),
                         ))
                     })
+                    .then(move |events| {
+                        let dead_letters = dead_letters.clone();
+                        async move {
+                            match dead_letters {
+                                Some(dead_letters) => dead_letters.track(events).await,
+                                None => events,
+                            }
+                        }
+                    })
                     .take_until_if(tripwire),
-            )
-            .await
-            .map(|_| {
-                debug!("Sink finished normally.");
-                TaskOutput::Sink(rx)
-            })
-            .map_err(|_| {
-                debug!("Sink finished with an error.");
-                TaskError::Opaque
-            })
+            );
+            // The pumps finish once the sink, and the task sending the events it fails to deliver,
+            // drop the senders of its outputs.
+            let dead_letter_task = async move {
+                match dead_letter_task {
+                    Some(task) => task.await,
+                    None => Ok(()),
+                }
+            };
+            let (result, pumped, dead_lettered) =
+                future::join3(run, future::try_join_all(pumps), dead_letter_task).await;
+            if let Err(error) = pumped {
+                debug!("Sink output pump finished with an error.");
+                return Err(error);
+            }
+            if let Err(error) = dead_lettered {
+                debug!("Sink dead-letter task finished with an error.");
+                return Err(error);
+            }
+
+            result
+                .map(|_| {
+                    debug!("Sink finished normally.");
+                    TaskOutput::Sink(rx)
+                })
+                .map_err(|_| {
+                    debug!("Sink finished with an error.");
+                    TaskError::Opaque
+                })
         };
 
         let task = Task::new(key.clone(), typetag, sink);
@@ -0,0 +1,149 @@
+//! Routing of the events sinks fail to deliver to their dead-letter component.
+//!
+//! Each event sent to a sink with a dead-letter component is given a finalizer of its own, while a
+//! copy of it keeps its original finalizers until the sink finalizes it. Copies of the events the
+//! sink rejects, or gives up retrying, are then sent to the `dead_letter` output of the sink, so
+//! that their sources are only acknowledged once they are delivered downstream.
+//!
+//! At most `MAX_TRACKED_EVENTS` events are awaited at once, after which sending events to the sink
+//! waits for the oldest of them to be finalized.
+
+use futures::{Future, StreamExt};
+use lookup::{metadata_path, path, PathPrefix};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use vector_core::{
+    config::{log_schema, LogNamespace},
+    event::{
+        BatchNotifier, BatchStatus, BatchStatusReceiver, Event, EventArray, EventFinalizer,
+        EventFinalizers, EventMutRef, LogEvent,
+    },
+};
+
+use super::task::TaskError;
+use crate::{
+    config::{ComponentKey, DEAD_LETTER},
+    SourceSender,
+};
+
+/// The number of events sent to a sink whose finalization is awaited at once.
+const MAX_TRACKED_EVENTS: usize = 10_000;
+
+/// An event sent to a sink, whose copy is sent to the dead-letter output if the sink fails to
+/// deliver it.
+struct Tracked {
+    event: Event,
+    finalizers: EventFinalizers,
+    status: BatchStatusReceiver,
+}
+
+/// Tracks the delivery of the events sent to a sink.
+#[derive(Clone)]
+pub(super) struct DeadLetters {
+    tracked: mpsc::Sender<Tracked>,
+}
+
+impl DeadLetters {
+    /// Creates the tracker of the events sent to the sink `key`, with the task sending those it
+    /// fails to deliver to `out`.
+    ///
+    /// The task finishes once the tracker is dropped and all tracked events are finalized.
+    pub(super) fn new(
+        key: ComponentKey,
+        typetag: &'static str,
+        mut out: SourceSender,
+    ) -> (Self, impl Future<Output = Result<(), TaskError>>) {
+        let (tracked, receiver) = mpsc::channel::<Tracked>(MAX_TRACKED_EVENTS);
+
+        let task = async move {
+            // Events are finalized in any order, so their statuses are awaited concurrently.
+            let mut statuses =
+                ReceiverStream::new(receiver)
+                    .map(|tracked| async move {
+                        (tracked.status.await, tracked.event, tracked.finalizers)
+                    })
+                    .buffer_unordered(MAX_TRACKED_EVENTS);
+
+            while let Some((status, mut event, finalizers)) = statuses.next().await {
+                if status == BatchStatus::Delivered {
+                    continue;
+                }
+
+                annotate(&mut event, &key, typetag, status);
+                event.metadata_mut().merge_finalizers(finalizers);
+                out.send_batch_named(DEAD_LETTER, std::iter::once(event))
+                    .await
+                    .map_err(|error| TaskError::wrapped(Box::new(error)))?;
+            }
+            Ok(())
+        };
+
+        (Self { tracked }, task)
+    }
+
+    /// Tracks the delivery of `events`, which are given finalizers of their own, waiting for room
+    /// when `MAX_TRACKED_EVENTS` events are already tracked.
+    pub(super) async fn track(&self, mut events: EventArray) -> EventArray {
+        for mut event in events.iter_events_mut() {
+            let finalizers = event.metadata_mut().take_finalizers();
+            let copy = match &event {
+                EventMutRef::Log(log) => Event::Log((*log).clone()),
+                EventMutRef::Metric(metric) => Event::Metric((*metric).clone()),
+                EventMutRef::Trace(trace) => Event::Trace((*trace).clone()),
+            };
+
+            let (batch, status) = BatchNotifier::new_with_receiver();
+            event
+                .metadata_mut()
+                .add_finalizer(EventFinalizer::new(batch));
+
+            // The task only stops receiving once the tracker is dropped.
+            let _ = self
+                .tracked
+                .send(Tracked {
+                    event: copy,
+                    finalizers,
+                    status,
+                })
+                .await;
+        }
+        events
+    }
+}
+
+/// Annotates `event` with the details of the failure of `typetag` sink `key` to deliver it.
+fn annotate(event: &mut Event, key: &ComponentKey, typetag: &'static str, status: BatchStatus) {
+    let status = match status {
+        BatchStatus::Delivered => "delivered",
+        BatchStatus::Errored => "errored",
+        BatchStatus::Rejected => "rejected",
+    };
+
+    match event {
+        Event::Log(log) => annotate_log(log, key, typetag, status),
+        Event::Trace(trace) => annotate_log(trace.as_mut(), key, typetag, status),
+        Event::Metric(metric) => {
+            metric.replace_tag("dead_letter_component_id".into(), key.id().into());
+            metric.replace_tag("dead_letter_component_type".into(), typetag.into());
+            metric.replace_tag("dead_letter_status".into(), status.into());
+        }
+    }
+}
+
+fn annotate_log(log: &mut LogEvent, key: &ComponentKey, typetag: &'static str, status: &str) {
+    let details = vector_common::btreemap! {
+        "component_id" => key.id(),
+        "component_type" => typetag,
+        "status" => status,
+    };
+    match log.namespace() {
+        LogNamespace::Legacy => log.insert(
+            (
+                PathPrefix::Event,
+                log_schema().metadata_key().concat(path!("dead_letter")),
+            ),
+            details,
+        ),
+        LogNamespace::Vector => log.insert(metadata_path!("vector", "dead_letter"), details),
+    };
+}
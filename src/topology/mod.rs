@@ -10,6 +10,7 @@ pub(super) use vector_core::fanout;
 pub mod schema;
 
 pub mod builder;
+mod dead_letter;
 mod ready_arrays;
 mod running;
 mod task;
@@ -106,6 +106,11 @@ pub fn merged_definition(
                 ));
             }
         }
+        // If the input is a sink, its outputs are events it received and failed to deliver, so the
+        // definition is the merged definition of its own inputs.
+        if let Some(inputs) = config.sink_inputs(key) {
+            definition = definition.merge(merged_definition(inputs, config, cache));
+        }
     }
     definition
 }
@@ -205,6 +210,11 @@ pub(super) fn expanded_definitions(
                 // pipeline definitions.
                 definitions.append(&mut expanded_definitions);
             }
+
+        // A sink outputs events it received, so each of its inputs is expanded like those of
+        // transforms without a schema definition of their own.
+        } else if let Some(inputs) = config.sink_inputs(key) {
+            definitions.append(&mut expanded_definitions(inputs, config, cache));
         }
     }
 
@@ -266,6 +276,12 @@ pub trait ComponentContainer {
         merged_definition: &Definition,
     ) -> Option<Vec<Output>>;
 
+    /// Gets the inputs of the sink with the given key, whose outputs carry the events it
+    /// received.
+    fn sink_inputs(&self, _key: &ComponentKey) -> Option<&[OutputId]> {
+        None
+    }
+
     /// Gets the transform output for the given port.
     ///
     /// Returns Err(()) if there is no transform with the given key
@@ -331,6 +347,10 @@ impl ComponentContainer for Config {
                 .outputs(merged_definition, self.schema.log_namespace())
         })
     }
+
+    fn sink_inputs(&self, key: &ComponentKey) -> Option<&[OutputId]> {
+        self.sink(key).map(|sink| &sink.inputs[..])
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,82 @@
+use futures::StreamExt;
+use tokio::time::{sleep, Duration};
+
+use crate::{
+    config::{Config, SinkOuter},
+    event::{BatchNotifier, BatchStatus, Event, EventStatus, LogEvent, Value},
+    sinks::Sinks,
+    test_util::{
+        mock::{basic_sink, basic_source, sinks::BasicSinkConfig},
+        start_topology, trace_init,
+    },
+    SourceSender,
+};
+
+/// Builds a topology whose `in` source sends events to a sink rejecting them, with `dead_letter`
+/// as its dead-letter component.
+fn config(dead_letter: impl Into<Sinks>) -> (SourceSender, Config) {
+    trace_init();
+
+    let (source_tx, source) = basic_source();
+    let (_, rejecting) = basic_sink(10);
+    let mut out = SinkOuter::new(
+        vec!["in".to_owned()],
+        rejecting.with_status(EventStatus::Rejected),
+    );
+    out.dead_letter = Some("dlq".into());
+
+    let mut config = Config::builder();
+    config.add_source("in", source);
+    config.add_sink_outer("out", out);
+    config.add_sink("dlq", &[], dead_letter);
+    (source_tx, config.build().unwrap())
+}
+
+#[tokio::test]
+async fn dead_letter_receives_failed_events() {
+    let (mut dlq_rx, dlq) = basic_sink(10);
+    let (mut source_tx, config) = config(dlq);
+    let (topology, _) = start_topology(config, false).await;
+
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let event = LogEvent::from("rejected").with_batch_notifier(&batch);
+    drop(batch);
+    source_tx.send_event(event).await.unwrap();
+
+    let events = dlq_rx.next().await.unwrap();
+    let event = events.into_events().next().map(Event::into_log).unwrap();
+    assert_eq!(event["message"], Value::from("rejected"));
+    assert_eq!(
+        event["metadata.dead_letter.component_id"],
+        Value::from("out")
+    );
+    assert_eq!(
+        event["metadata.dead_letter.component_type"],
+        Value::from("test_basic")
+    );
+    assert_eq!(
+        event["metadata.dead_letter.status"],
+        Value::from("rejected")
+    );
+
+    // The source is acknowledged with the status of the event in the dead-letter sink, rather
+    // than with the rejection by the failing sink.
+    assert_eq!(receiver.await, BatchStatus::Delivered);
+
+    topology.stop().await;
+}
+
+#[tokio::test]
+async fn dead_letter_delays_acknowledgements() {
+    // This sink never consumes its input, so the events sent to it are never finalized.
+    let (mut source_tx, config) = config(BasicSinkConfig::default());
+    let (_topology, _) = start_topology(config, false).await;
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let event = LogEvent::from("rejected").with_batch_notifier(&batch);
+    drop(batch);
+    source_tx.send_event(event).await.unwrap();
+
+    sleep(Duration::from_millis(100)).await;
+    assert!(receiver.try_recv().is_err());
+}
@@ -1,22 +1,97 @@
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, VecDeque},
     pin::Pin,
     time::Duration,
 };
 
 use async_stream::stream;
+use chrono::Utc;
 use futures::{Stream, StreamExt};
+use ordered_float::NotNan;
+use snafu::Snafu;
 use vector_config::configurable_component;
-use vector_core::config::LogNamespace;
+use vector_core::config::{log_schema, LogNamespace};
 
 use crate::{
     config::{DataType, Input, Output, TransformConfig, TransformContext},
-    event::{metric, Event, EventMetadata},
-    internal_events::{AggregateEventRecorded, AggregateFlushed, AggregateUpdateFailed},
+    event::{metric, Event, EventMetadata, LogEvent, Value},
+    internal_events::{
+        AggregateEventRecorded, AggregateFlushed, AggregateUpdateFailed, TemplateRenderingError,
+    },
     schema,
+    template::Template,
     transforms::{TaskTransform, Transform},
 };
 
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Aggregation `{}` must have a `field` for `{:?}`", name, function))]
+    MissingField {
+        name: String,
+        function: AggregationFunction,
+    },
+    #[snafu(display("`group_by` can only be used along with `aggregations`"))]
+    GroupByWithoutAggregations,
+    #[snafu(display("`window_ms` can only be used along with `aggregations`"))]
+    WindowWithoutAggregations,
+    #[snafu(display("`window_ms` must be a non-zero multiple of `interval_ms`"))]
+    InvalidWindow,
+}
+
+/// A function aggregating the values of a field over the events of a group.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationFunction {
+    /// The sum of the values.
+    Sum,
+
+    /// The smallest value.
+    Min,
+
+    /// The largest value.
+    Max,
+
+    /// The mean of the values.
+    Mean,
+
+    /// The number of events with the field, or of all events without a field.
+    Count,
+
+    /// The 95th percentile of the values.
+    P95,
+
+    /// The value of the last event.
+    Last,
+
+    /// The sum of the values per second over the window, or the number of events per second
+    /// without a field.
+    Rate,
+
+    /// The estimated number of distinct values.
+    ///
+    /// Values are counted with a HyperLogLog sketch, whose estimates have a standard error of
+    /// about 1.6%.
+    DistinctCount,
+}
+
+/// An aggregation of the events of each group.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Aggregation {
+    /// The field whose values are aggregated.
+    ///
+    /// Required by all functions but `count` and `rate`, which count all events of groups without
+    /// it. Values that aren't numbers are ignored by all functions but `count`, `last`, and
+    /// `distinct_count`.
+    #[configurable(metadata(docs::examples = "duration_ms"))]
+    pub field: Option<String>,
+
+    #[configurable(derived)]
+    pub function: AggregationFunction,
+}
+
 /// Configuration for the `aggregate` transform.
 #[configurable_component(transform("aggregate"))]
 #[derive(Clone, Debug, Default)]
@@ -27,6 +102,35 @@ pub struct AggregateConfig {
     /// Over this period metrics with the same series data (name, namespace, tags, …) will be aggregated.
     #[serde(default = "default_interval_ms")]
     pub interval_ms: u64,
+
+    /// The fields of the events emitted for each group, and the templates rendering them.
+    ///
+    /// Log events are grouped by the values these templates render, and log events whose templates
+    /// fail to render are dropped. Without any, all log events make up a single group.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "A template rendering the field."
+    ))]
+    pub group_by: HashMap<String, Template>,
+
+    /// The fields of the events emitted for each group, and the aggregations computing them.
+    ///
+    /// When set, log events are aggregated instead of metrics, and one log event is emitted per
+    /// group with events in the window ending at each interval, holding the fields of `group_by`
+    /// and of the aggregations.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "An aggregation computing the field."
+    ))]
+    pub aggregations: HashMap<String, Aggregation>,
+
+    /// The length of the windows log events are aggregated over, in milliseconds.
+    ///
+    /// It must be a multiple of `interval_ms`. Windows longer than the interval slide by it, so
+    /// that log events are aggregated into the events emitted for each window they fall in. By
+    /// default, windows are as long as the interval, so that they don't overlap.
+    #[configurable(metadata(docs::examples = 60000))]
+    pub window_ms: Option<u64>,
 }
 
 const fn default_interval_ms() -> u64 {
@@ -35,6 +139,12 @@ const fn default_interval_ms() -> u64 {
 
 impl_generate_config_from_default!(AggregateConfig);
 
+impl AggregateConfig {
+    fn aggregates_logs(&self) -> bool {
+        !self.aggregations.is_empty()
+    }
+}
+
 #[async_trait::async_trait]
 impl TransformConfig for AggregateConfig {
     async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
@@ -42,31 +152,394 @@ impl TransformConfig for AggregateConfig {
     }
 
     fn input(&self) -> Input {
-        Input::metric()
+        if self.aggregates_logs() {
+            Input::log()
+        } else {
+            Input::metric()
+        }
     }
 
     fn outputs(&self, _: &schema::Definition, _: LogNamespace) -> Vec<Output> {
-        vec![Output::default(DataType::Metric)]
+        if self.aggregates_logs() {
+            vec![Output::default(DataType::Log)]
+        } else {
+            vec![Output::default(DataType::Metric)]
+        }
+    }
+}
+
+/// The running state of an aggregation over the events of a group.
+#[derive(Debug)]
+enum Accumulator {
+    Sum { sum: f64, integer: bool },
+    Min { min: Option<f64>, integer: bool },
+    Max { max: Option<f64>, integer: bool },
+    Mean { sum: f64, count: u64 },
+    Count(i64),
+    P95(Vec<NotNan<f64>>),
+    Last(Option<Value>),
+    Rate { sum: f64, count_events: bool },
+    DistinctCount(HyperLogLog),
+}
+
+/// The number of bits of hashes indexing the registers of HyperLogLog sketches.
+const HLL_PRECISION: u32 = 12;
+
+/// A HyperLogLog sketch estimating the number of distinct values added to it.
+///
+/// Registers are only allocated once a value is added, as most sketches of sliding windows are of
+/// intervals without any.
+#[derive(Debug, Default)]
+struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    fn add(&mut self, value: &Value) {
+        if self.registers.is_empty() {
+            self.registers = vec![0; 1 << HLL_PRECISION];
+        }
+        let hash = seahash::hash(value.to_string_lossy().as_bytes());
+        let index = (hash >> (64 - HLL_PRECISION)) as usize;
+        // The position of the first set bit of the rest of the hash, which is bounded by setting
+        // the bit right after it.
+        let rank = ((hash << HLL_PRECISION) | (1 << (HLL_PRECISION - 1))).leading_zeros() + 1;
+        self.registers[index] = self.registers[index].max(rank as u8);
+    }
+
+    fn merge(&mut self, other: &Self) {
+        if self.registers.is_empty() {
+            self.registers = other.registers.clone();
+        } else if !other.registers.is_empty() {
+            for (register, other) in self.registers.iter_mut().zip(&other.registers) {
+                *register = (*register).max(*other);
+            }
+        }
+    }
+
+    fn estimate(&self) -> i64 {
+        if self.registers.is_empty() {
+            return 0;
+        }
+        let registers = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / registers);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|register| 2f64.powi(-i32::from(*register)))
+            .sum();
+        let estimate = alpha * registers * registers / sum;
+        let zeros = self
+            .registers
+            .iter()
+            .filter(|register| **register == 0)
+            .count();
+        // Small numbers of distinct values are estimated much more closely by linear counting.
+        let estimate = if estimate <= 2.5 * registers && zeros > 0 {
+            registers * (registers / zeros as f64).ln()
+        } else {
+            estimate
+        };
+        estimate.round() as i64
     }
 }
 
+/// The number of `value`, and whether it's an integer, or `None` when it isn't a number.
+fn number(value: &Value) -> Option<(f64, bool)> {
+    match value {
+        Value::Integer(value) => Some((*value as f64, true)),
+        Value::Float(value) => Some((value.into_inner(), false)),
+        _ => None,
+    }
+}
+
+fn float_value(number: f64) -> Value {
+    // Sums of infinite values of both signs aren't numbers.
+    NotNan::new(number).map_or(Value::Null, Value::Float)
+}
+
+fn number_value(number: f64, integer: bool) -> Value {
+    if integer {
+        Value::Integer(number as i64)
+    } else {
+        float_value(number)
+    }
+}
+
+impl Accumulator {
+    fn new(aggregation: &Aggregation) -> Self {
+        match aggregation.function {
+            AggregationFunction::Sum => Self::Sum {
+                sum: 0.0,
+                integer: true,
+            },
+            AggregationFunction::Min => Self::Min {
+                min: None,
+                integer: true,
+            },
+            AggregationFunction::Max => Self::Max {
+                max: None,
+                integer: true,
+            },
+            AggregationFunction::Mean => Self::Mean { sum: 0.0, count: 0 },
+            AggregationFunction::Count => Self::Count(0),
+            AggregationFunction::P95 => Self::P95(Vec::new()),
+            AggregationFunction::Last => Self::Last(None),
+            AggregationFunction::Rate => Self::Rate {
+                sum: 0.0,
+                count_events: aggregation.field.is_none(),
+            },
+            AggregationFunction::DistinctCount => Self::DistinctCount(HyperLogLog::default()),
+        }
+    }
+
+    /// Adds the value of the field of an event, which is `None` when the event doesn't have it, or
+    /// the whole event for counts without a field.
+    fn add(&mut self, value: Option<&Value>) {
+        if let Self::Count(count) = self {
+            *count += i64::from(value.is_some());
+            return;
+        }
+        if let Self::Last(last) = self {
+            if let Some(value) = value {
+                *last = Some(value.clone());
+            }
+            return;
+        }
+        if let Self::DistinctCount(sketch) = self {
+            if let Some(value) = value {
+                sketch.add(value);
+            }
+            return;
+        }
+        if let Self::Rate {
+            sum,
+            count_events: true,
+        } = self
+        {
+            *sum += f64::from(u8::from(value.is_some()));
+            return;
+        }
+
+        let (number, is_integer) = match value.and_then(number) {
+            Some(number) => number,
+            None => return,
+        };
+        match self {
+            Self::Sum { sum, integer } => {
+                *sum += number;
+                *integer &= is_integer;
+            }
+            Self::Min { min, integer } => {
+                if min.map_or(true, |min| number < min) {
+                    *min = Some(number);
+                    *integer = is_integer;
+                }
+            }
+            Self::Max { max, integer } => {
+                if max.map_or(true, |max| number > max) {
+                    *max = Some(number);
+                    *integer = is_integer;
+                }
+            }
+            Self::Mean { sum, count } => {
+                *sum += number;
+                *count += 1;
+            }
+            Self::P95(values) => values.extend(NotNan::new(number).ok()),
+            Self::Rate { sum, .. } => *sum += number,
+            Self::Count(_) | Self::Last(_) | Self::DistinctCount(_) => {
+                unreachable!("handled above")
+            }
+        }
+    }
+
+    /// Merges the state of `other`, of the same aggregation over later events, into this one.
+    fn merge(&mut self, other: &Self) {
+        match (self, other) {
+            (
+                Self::Sum { sum, integer },
+                Self::Sum {
+                    sum: other_sum,
+                    integer: other_integer,
+                },
+            ) => {
+                *sum += other_sum;
+                *integer &= other_integer;
+            }
+            (
+                Self::Min { min, integer },
+                Self::Min {
+                    min: Some(other_min),
+                    integer: other_integer,
+                },
+            ) => {
+                if min.map_or(true, |min| *other_min < min) {
+                    *min = Some(*other_min);
+                    *integer = *other_integer;
+                }
+            }
+            (
+                Self::Max { max, integer },
+                Self::Max {
+                    max: Some(other_max),
+                    integer: other_integer,
+                },
+            ) => {
+                if max.map_or(true, |max| *other_max > max) {
+                    *max = Some(*other_max);
+                    *integer = *other_integer;
+                }
+            }
+            (
+                Self::Mean { sum, count },
+                Self::Mean {
+                    sum: other_sum,
+                    count: other_count,
+                },
+            ) => {
+                *sum += other_sum;
+                *count += other_count;
+            }
+            (Self::Count(count), Self::Count(other_count)) => *count += other_count,
+            (Self::P95(values), Self::P95(other_values)) => values.extend_from_slice(other_values),
+            (Self::Last(last), Self::Last(Some(other_last))) => *last = Some(other_last.clone()),
+            (Self::Rate { sum, .. }, Self::Rate { sum: other_sum, .. }) => *sum += other_sum,
+            (Self::DistinctCount(sketch), Self::DistinctCount(other_sketch)) => {
+                sketch.merge(other_sketch)
+            }
+            // The other accumulator has no values, or is of another aggregation.
+            _ => {}
+        }
+    }
+
+    /// The value of the aggregation over a window of length `window`, or `None` when no event of
+    /// the group had a value for it.
+    fn finish(self, window: Duration) -> Option<Value> {
+        match self {
+            Self::Sum { sum, integer } => Some(number_value(sum, integer)),
+            Self::Min { min, integer } => min.map(|min| number_value(min, integer)),
+            Self::Max { max, integer } => max.map(|max| number_value(max, integer)),
+            Self::Mean { sum, count } => (count > 0).then(|| float_value(sum / count as f64)),
+            Self::Count(count) => Some(Value::Integer(count)),
+            Self::P95(mut values) => {
+                values.sort_unstable();
+                // The nearest-rank percentile: the smallest value that's larger than or equal to
+                // 95% of the values.
+                let rank = (values.len() as f64 * 0.95).ceil() as usize;
+                values
+                    .get(rank.saturating_sub(1))
+                    .map(|value| Value::Float(*value))
+            }
+            Self::Last(last) => last,
+            Self::Rate { sum, .. } => Some(float_value(sum / window.as_secs_f64())),
+            Self::DistinctCount(sketch) => Some(Value::Integer(sketch.estimate())),
+        }
+    }
+}
+
+/// The aggregations of the events of a group over an interval.
+#[derive(Debug)]
+struct Bucket {
+    accumulators: Vec<Accumulator>,
+    events: u64,
+}
+
+impl Bucket {
+    fn new(aggregations: &[(String, Aggregation)]) -> Self {
+        Self {
+            accumulators: aggregations
+                .iter()
+                .map(|(_, aggregation)| Accumulator::new(aggregation))
+                .collect(),
+            events: 0,
+        }
+    }
+}
+
+/// The aggregations of the events of a group over each interval of its window, oldest first,
+/// with the merged metadata of the events not emitted yet.
+#[derive(Debug)]
+struct Group {
+    buckets: VecDeque<Bucket>,
+    metadata: EventMetadata,
+}
+
 type MetricEntry = (metric::MetricData, EventMetadata);
 
 #[derive(Debug)]
 pub struct Aggregate {
     interval: Duration,
     map: HashMap<metric::MetricSeries, MetricEntry>,
+    /// The fields of groups and their templates, sorted by field so that keys are stable.
+    group_by: Vec<(String, Template)>,
+    aggregations: Vec<(String, Aggregation)>,
+    groups: HashMap<Vec<String>, Group>,
+    /// The number of intervals in windows.
+    window_intervals: u32,
+    /// The number of flushes so far, up to `window_intervals`, as windows are shorter before.
+    flushes: u32,
 }
 
 impl Aggregate {
     pub fn new(config: &AggregateConfig) -> crate::Result<Self> {
+        if !config.aggregates_logs() && !config.group_by.is_empty() {
+            return Err(BuildError::GroupByWithoutAggregations.into());
+        }
+        if !config.aggregates_logs() && config.window_ms.is_some() {
+            return Err(BuildError::WindowWithoutAggregations.into());
+        }
+        let window_ms = config.window_ms.unwrap_or(config.interval_ms);
+        if config.interval_ms == 0 || window_ms == 0 || window_ms % config.interval_ms != 0 {
+            return Err(BuildError::InvalidWindow.into());
+        }
+        let window_intervals =
+            u32::try_from(window_ms / config.interval_ms).map_err(|_| BuildError::InvalidWindow)?;
+        for (name, aggregation) in &config.aggregations {
+            if aggregation.field.is_none()
+                && !matches!(
+                    aggregation.function,
+                    AggregationFunction::Count | AggregationFunction::Rate
+                )
+            {
+                return Err(BuildError::MissingField {
+                    name: name.clone(),
+                    function: aggregation.function,
+                }
+                .into());
+            }
+        }
+
+        let mut group_by: Vec<_> = config
+            .group_by
+            .iter()
+            .map(|(field, template)| (field.clone(), template.clone()))
+            .collect();
+        group_by.sort_by(|a, b| a.0.cmp(&b.0));
+
         Ok(Self {
             interval: Duration::from_millis(config.interval_ms),
             map: Default::default(),
+            group_by,
+            aggregations: config
+                .aggregations
+                .iter()
+                .map(|(field, aggregation)| (field.clone(), aggregation.clone()))
+                .collect(),
+            groups: Default::default(),
+            window_intervals,
+            flushes: 0,
         })
     }
 
     fn record(&mut self, event: Event) {
+        match event {
+            Event::Log(log) => self.record_log(log),
+            event => self.record_metric(event),
+        }
+    }
+
+    fn record_metric(&mut self, event: Event) {
         let (series, data, metadata) = event.into_metric().into_parts();
 
         match data.kind {
@@ -94,6 +567,42 @@ impl Aggregate {
         emit!(AggregateEventRecorded);
     }
 
+    fn record_log(&mut self, log: LogEvent) {
+        let mut key = Vec::with_capacity(self.group_by.len());
+        for (field, template) in &self.group_by {
+            match template.render_string(&log) {
+                Ok(value) => key.push(value),
+                Err(error) => {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some(field.as_str()),
+                        drop_event: true,
+                    });
+                    return;
+                }
+            }
+        }
+
+        let aggregations = &self.aggregations;
+        let group = self.groups.entry(key).or_insert_with(|| Group {
+            buckets: VecDeque::from([Bucket::new(aggregations)]),
+            metadata: EventMetadata::default(),
+        });
+        let bucket = group.buckets.back_mut().expect("groups have a bucket");
+        bucket.events += 1;
+        for ((_, aggregation), accumulator) in aggregations.iter().zip(&mut bucket.accumulators) {
+            let value = match &aggregation.field {
+                Some(field) => log.get(field.as_str()),
+                None => Some(log.value()),
+            };
+            accumulator.add(value);
+        }
+        let (_, metadata) = log.into_parts();
+        group.metadata.merge(metadata);
+
+        emit!(AggregateEventRecorded);
+    }
+
     fn flush_into(&mut self, output: &mut Vec<Event>) {
         let map = std::mem::take(&mut self.map);
         for (series, entry) in map.into_iter() {
@@ -101,6 +610,48 @@ impl Aggregate {
             output.push(Event::Metric(metric));
         }
 
+        let timestamp = Utc::now();
+        self.flushes = (self.flushes + 1).min(self.window_intervals);
+        let window = self.interval * self.flushes;
+        let (group_by, aggregations) = (&self.group_by, &self.aggregations);
+        let window_intervals = self.window_intervals as usize;
+        self.groups.retain(|key, group| {
+            if group.buckets.iter().any(|bucket| bucket.events > 0) {
+                // The metadata of events goes along with the first event they're aggregated into.
+                let metadata = std::mem::take(&mut group.metadata);
+                let mut log = LogEvent::new_with_metadata(metadata);
+                for ((field, _), value) in group_by.iter().zip(key) {
+                    log.insert(field.as_str(), value.clone());
+                }
+                let mut window_bucket = Bucket::new(aggregations);
+                for bucket in &group.buckets {
+                    for (accumulator, other) in window_bucket
+                        .accumulators
+                        .iter_mut()
+                        .zip(&bucket.accumulators)
+                    {
+                        accumulator.merge(other);
+                    }
+                }
+                for ((field, _), accumulator) in aggregations.iter().zip(window_bucket.accumulators)
+                {
+                    if let Some(value) = accumulator.finish(window) {
+                        log.insert(field.as_str(), value);
+                    }
+                }
+                log.insert(log_schema().timestamp_key(), timestamp);
+                output.push(Event::Log(log));
+            }
+
+            // The window slides to the next interval, and groups are dropped once none of its
+            // intervals have events.
+            group.buckets.push_back(Bucket::new(aggregations));
+            if group.buckets.len() > window_intervals {
+                group.buckets.pop_front();
+            }
+            group.buckets.iter().any(|bucket| bucket.events > 0)
+        });
+
         emit!(AggregateFlushed);
     }
 }
@@ -173,6 +724,7 @@ mod tests {
     fn incremental() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -243,6 +795,7 @@ mod tests {
     fn absolute() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -308,6 +861,7 @@ mod tests {
     fn conflicting_value_type() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -365,6 +919,7 @@ mod tests {
     fn conflicting_kinds() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -415,6 +970,165 @@ mod tests {
         assert_eq!(&summed, &out[0]);
     }
 
+    fn make_log(service: &str, duration_ms: Value) -> Event {
+        let mut log = LogEvent::from("request");
+        log.insert("service", service);
+        log.insert("duration_ms", duration_ms);
+        Event::Log(log)
+    }
+
+    #[test]
+    fn aggregates_logs_by_group() {
+        let config = toml::from_str::<AggregateConfig>(
+            r#"
+group_by.service = "{{ service }}"
+aggregations.requests = { function = "count" }
+aggregations.total_ms = { field = "duration_ms", function = "sum" }
+aggregations.min_ms = { field = "duration_ms", function = "min" }
+aggregations.max_ms = { field = "duration_ms", function = "max" }
+aggregations.mean_ms = { field = "duration_ms", function = "mean" }
+aggregations.p95_ms = { field = "duration_ms", function = "p95" }
+aggregations.last_ms = { field = "duration_ms", function = "last" }
+"#,
+        )
+        .unwrap();
+        let mut agg = Aggregate::new(&config).unwrap();
+
+        for duration in 1..=20 {
+            agg.record(make_log("api", Value::from(duration)));
+        }
+        agg.record(make_log("web", Value::from(1.5)));
+        agg.record(make_log("web", Value::from("slow")));
+        let mut missing = LogEvent::from("request");
+        missing.insert("duration_ms", 1);
+        agg.record(Event::Log(missing));
+
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert_eq!(2, out.len());
+        out.sort_by_key(|event| event.as_log()["service"].to_string_lossy().into_owned());
+
+        let api = out[0].as_log();
+        assert_eq!(api["service"], "api".into());
+        assert_eq!(api["requests"], Value::from(20));
+        assert_eq!(api["total_ms"], Value::from(210));
+        assert_eq!(api["min_ms"], Value::from(1));
+        assert_eq!(api["max_ms"], Value::from(20));
+        assert_eq!(api["mean_ms"], Value::from(10.5));
+        assert_eq!(api["p95_ms"], Value::from(19.0));
+        assert_eq!(api["last_ms"], Value::from(20));
+        assert!(api.contains(log_schema().timestamp_key()));
+
+        let web = out[1].as_log();
+        assert_eq!(web["requests"], Value::from(2));
+        assert_eq!(web["total_ms"], Value::from(1.5));
+        assert_eq!(web["last_ms"], Value::from("slow"));
+    }
+
+    #[test]
+    fn validates_aggregations() {
+        let config =
+            toml::from_str::<AggregateConfig>(r#"aggregations.total = { function = "sum" }"#)
+                .unwrap();
+        assert!(Aggregate::new(&config).is_err());
+
+        let config =
+            toml::from_str::<AggregateConfig>(r#"group_by.service = "{{ service }}""#).unwrap();
+        assert!(Aggregate::new(&config).is_err());
+    }
+
+    #[test]
+    fn aggregates_logs_over_sliding_windows() {
+        let config = toml::from_str::<AggregateConfig>(
+            r#"
+interval_ms = 1000
+window_ms = 3000
+aggregations.requests = { function = "count" }
+aggregations.rps = { function = "rate" }
+aggregations.bytes_per_second = { field = "bytes", function = "rate" }
+aggregations.last_ms = { field = "duration_ms", function = "last" }
+"#,
+        )
+        .unwrap();
+        let mut agg = Aggregate::new(&config).unwrap();
+        let flush = |agg: &mut Aggregate| {
+            let mut out = vec![];
+            agg.flush_into(&mut out);
+            out
+        };
+
+        for duration in 1..=3 {
+            let mut log = make_log("api", Value::from(duration)).into_log();
+            log.insert("bytes", 100);
+            agg.record(Event::Log(log));
+        }
+        let out = flush(&mut agg);
+        let log = out[0].as_log();
+        assert_eq!(log["requests"], Value::from(3));
+        // The window only spans the intervals since the transform started.
+        assert_eq!(log["rps"], Value::from(3.0));
+        assert_eq!(log["bytes_per_second"], Value::from(300.0));
+
+        agg.record(make_log("api", Value::from(4)));
+        let out = flush(&mut agg);
+        let log = out[0].as_log();
+        assert_eq!(log["requests"], Value::from(4));
+        assert_eq!(log["rps"], Value::from(2.0));
+        assert_eq!(log["last_ms"], Value::from(4));
+
+        let out = flush(&mut agg);
+        assert_eq!(out[0].as_log()["requests"], Value::from(4));
+        assert_eq!(out[0].as_log()["rps"], Value::from(4.0 / 3.0));
+
+        // The first interval slides out of the window, and then the second one.
+        let out = flush(&mut agg);
+        assert_eq!(out[0].as_log()["requests"], Value::from(1));
+        assert_eq!(out[0].as_log()["bytes_per_second"], Value::from(0.0));
+        assert!(flush(&mut agg).is_empty());
+        assert!(agg.groups.is_empty());
+    }
+
+    #[test]
+    fn counts_distinct_values() {
+        let config = toml::from_str::<AggregateConfig>(
+            r#"aggregations.users = { field = "user", function = "distinct_count" }"#,
+        )
+        .unwrap();
+        let mut agg = Aggregate::new(&config).unwrap();
+        for user in 0..10_000 {
+            let mut log = LogEvent::from("login");
+            log.insert("user", format!("user-{}", user % 5_000));
+            agg.record(Event::Log(log));
+        }
+
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        let users = match out[0].as_log()["users"] {
+            Value::Integer(users) => users,
+            ref value => panic!("unexpected distinct count {:?}", value),
+        };
+        assert!((4_750..=5_250).contains(&users), "{}", users);
+
+        let mut sketch = HyperLogLog::default();
+        for user in ["a", "b", "c", "a"] {
+            sketch.add(&Value::from(user));
+        }
+        assert_eq!(sketch.estimate(), 3);
+    }
+
+    #[test]
+    fn validates_windows() {
+        for config in [
+            "window_ms = 1000",
+            "interval_ms = 1000\nwindow_ms = 1500\naggregations.requests = { function = \"count\" }",
+            "interval_ms = 1000\nwindow_ms = 0\naggregations.requests = { function = \"count\" }",
+            "aggregations.users = { function = \"distinct_count\" }",
+        ] {
+            let config = toml::from_str::<AggregateConfig>(config).unwrap();
+            assert!(Aggregate::new(&config).is_err());
+        }
+    }
+
     #[tokio::test]
     async fn transform_shutdown() {
         let agg = toml::from_str::<AggregateConfig>(
@@ -0,0 +1,297 @@
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use async_stream::stream;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{
+        metric::{Metric, MetricKind, MetricSeries, MetricValue},
+        Event,
+    },
+    internal_events::{CounterDeltaReset, CounterDeltaSeriesTracked},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// What absolute counters are converted into.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CounterDeltaMode {
+    /// Incremental counters of the increase since the previous value of the series.
+    #[derivative(Default)]
+    Delta,
+
+    /// Absolute gauges of the increase per second since the previous value of the series.
+    Rate,
+}
+
+/// Configuration for the `counter_delta` transform.
+#[configurable_component(transform("counter_delta"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CounterDeltaConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: CounterDeltaMode,
+
+    /// The time, in seconds, after which series that haven't been seen are forgotten.
+    ///
+    /// The next value of forgotten series is taken as a new reference, like the first value of
+    /// any series, rather than converted from a value that may predate a restart of their source.
+    #[serde(default = "default_stale_after_secs")]
+    pub stale_after_secs: u64,
+}
+
+const fn default_stale_after_secs() -> u64 {
+    300
+}
+
+impl GenerateConfig for CounterDeltaConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"mode = "delta""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for CounterDeltaConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if self.stale_after_secs == 0 {
+            return Err("`stale_after_secs` must be greater than zero.".into());
+        }
+        Ok(Transform::event_task(CounterDelta::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn outputs(&self, _: &schema::Definition, _: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+}
+
+/// The previous value of a series.
+#[derive(Debug)]
+struct Reference {
+    value: f64,
+    timestamp: Option<DateTime<Utc>>,
+    seen: Instant,
+}
+
+#[derive(Debug)]
+pub struct CounterDelta {
+    mode: CounterDeltaMode,
+    stale_after: Duration,
+    references: HashMap<MetricSeries, Reference>,
+}
+
+impl CounterDelta {
+    fn new(config: &CounterDeltaConfig) -> Self {
+        Self {
+            mode: config.mode,
+            stale_after: Duration::from_secs(config.stale_after_secs),
+            references: HashMap::new(),
+        }
+    }
+
+    /// Converts absolute counters, passing through all other metrics.
+    ///
+    /// Nothing is emitted for the first value of series, which only becomes their reference, as
+    /// converting it would count everything since their source started again.
+    fn process(&mut self, metric: Metric, now: Instant) -> Option<Metric> {
+        let value = match (metric.kind(), metric.value()) {
+            (MetricKind::Absolute, MetricValue::Counter { value }) => *value,
+            _ => return Some(metric),
+        };
+        let reference = Reference {
+            value,
+            timestamp: metric.timestamp(),
+            seen: now,
+        };
+
+        let previous = match self.references.get_mut(metric.series()) {
+            Some(previous) if now.duration_since(previous.seen) < self.stale_after => previous,
+            _ => {
+                self.references.insert(metric.series().clone(), reference);
+                emit!(CounterDeltaSeriesTracked {
+                    count: self.references.len()
+                });
+                return None;
+            }
+        };
+
+        let delta = if value < previous.value {
+            // Counters only decrease when their source restarts, counting from zero again.
+            emit!(CounterDeltaReset {
+                series: metric.series()
+            });
+            value
+        } else {
+            value - previous.value
+        };
+        let elapsed = match (reference.timestamp, previous.timestamp) {
+            (Some(current), Some(last)) => (current - last).to_std().ok(),
+            _ => Some(now.duration_since(previous.seen)),
+        };
+
+        match self.mode {
+            CounterDeltaMode::Delta => {
+                *previous = reference;
+                Some(
+                    metric
+                        .with_value(MetricValue::Counter { value: delta })
+                        .into_incremental(),
+                )
+            }
+            CounterDeltaMode::Rate => match elapsed.filter(|elapsed| !elapsed.is_zero()) {
+                Some(elapsed) => {
+                    *previous = reference;
+                    Some(
+                        metric
+                            .with_value(MetricValue::Gauge {
+                                value: delta / elapsed.as_secs_f64(),
+                            })
+                            .into_absolute(),
+                    )
+                }
+                // Values no later than the reference don't have a rate, and are dropped.
+                None => None,
+            },
+        }
+    }
+
+    /// Forgets the series that haven't been seen for too long.
+    fn evict_stale(&mut self, now: Instant) {
+        let stale_after = self.stale_after;
+        self.references
+            .retain(|_, reference| now.duration_since(reference.seen) < stale_after);
+        emit!(CounterDeltaSeriesTracked {
+            count: self.references.len()
+        });
+    }
+}
+
+impl TaskTransform<Event> for CounterDelta {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut evict_stream = tokio::time::interval(self.stale_after);
+
+        Box::pin(stream! {
+            let mut done = false;
+            while !done {
+                let mut output = None;
+                tokio::select! {
+                    _ = evict_stream.tick() => {
+                        self.evict_stale(Instant::now());
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => done = true,
+                            Some(event) => {
+                                output = self.process(event.into_metric(), Instant::now());
+                            }
+                        }
+                    }
+                };
+                if let Some(metric) = output {
+                    yield Event::from(metric);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<CounterDeltaConfig>();
+    }
+
+    fn counter_delta(mode: CounterDeltaMode) -> CounterDelta {
+        CounterDelta::new(&CounterDeltaConfig {
+            mode,
+            stale_after_secs: default_stale_after_secs(),
+        })
+    }
+
+    fn counter(value: f64, secs: i64) -> Metric {
+        Metric::new(
+            "requests_total",
+            MetricKind::Absolute,
+            MetricValue::Counter { value },
+        )
+        .with_timestamp(Some(Utc.timestamp(secs, 0)))
+    }
+
+    #[test]
+    fn converts_counters_to_deltas() {
+        let mut transform = counter_delta(CounterDeltaMode::Delta);
+        let now = Instant::now();
+
+        assert_eq!(transform.process(counter(10.0, 0), now), None);
+        let delta = transform.process(counter(15.0, 10), now).unwrap();
+        assert_eq!(delta.kind(), MetricKind::Incremental);
+        assert_eq!(delta.value(), &MetricValue::Counter { value: 5.0 });
+
+        // Counters that decreased were reset, and count from zero.
+        let delta = transform.process(counter(3.0, 20), now).unwrap();
+        assert_eq!(delta.value(), &MetricValue::Counter { value: 3.0 });
+
+        let gauge = Metric::new(
+            "temperature",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 21.0 },
+        );
+        assert_eq!(transform.process(gauge.clone(), now), Some(gauge));
+    }
+
+    #[test]
+    fn converts_counters_to_rates() {
+        let mut transform = counter_delta(CounterDeltaMode::Rate);
+        let now = Instant::now();
+
+        assert_eq!(transform.process(counter(10.0, 0), now), None);
+        let rate = transform.process(counter(30.0, 10), now).unwrap();
+        assert_eq!(rate.kind(), MetricKind::Absolute);
+        assert_eq!(rate.value(), &MetricValue::Gauge { value: 2.0 });
+
+        // Values at the same time as the reference don't have a rate.
+        assert_eq!(transform.process(counter(40.0, 10), now), None);
+        let rate = transform.process(counter(40.0, 15), now).unwrap();
+        assert_eq!(rate.value(), &MetricValue::Gauge { value: 2.0 });
+    }
+
+    #[test]
+    fn forgets_stale_series() {
+        let mut transform = counter_delta(CounterDeltaMode::Delta);
+        let now = Instant::now();
+        let later = now + Duration::from_secs(default_stale_after_secs());
+
+        assert_eq!(transform.process(counter(10.0, 0), now), None);
+        assert_eq!(transform.process(counter(20.0, 400), later), None);
+        assert!(transform.process(counter(25.0, 410), later).is_some());
+
+        transform.evict_stale(later + Duration::from_secs(default_stale_after_secs()));
+        assert!(transform.references.is_empty());
+    }
+}
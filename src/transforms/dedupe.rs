@@ -1,8 +1,16 @@
-use std::{future::ready, num::NonZeroUsize, pin::Pin};
+use std::{
+    future::ready,
+    num::{NonZeroU64, NonZeroUsize},
+    pin::Pin,
+    time::Duration,
+};
 
+use async_stream::stream;
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
 use lru::LruCache;
+use snafu::Snafu;
+use tokio::time::Instant;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -11,7 +19,7 @@ use crate::{
         log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
     },
     event::{Event, Value},
-    internal_events::DedupeEventsDropped,
+    internal_events::{DedupeCacheLookup, DedupeEventsDropped},
     schema,
     transforms::{TaskTransform, Transform},
 };
@@ -60,6 +68,26 @@ pub enum FieldMatchConfig {
     ),
 }
 
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`duplicate_count_field` can only be used along with `cache.ttl_secs`"))]
+    DuplicateCountWithoutTtl,
+}
+
+/// The policy evicting events from the cache once it's full.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionPolicy {
+    /// Evicts the event that was least recently matched, so that events that keep being
+    /// duplicated stay in the cache.
+    #[default]
+    Lru,
+
+    /// Evicts the event that was first cached, whether it was matched since or not.
+    Fifo,
+}
+
 /// Caching configuration for deduplication.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -67,6 +95,19 @@ pub enum FieldMatchConfig {
 pub struct CacheConfig {
     /// Number of events to cache and use for comparing incoming events to previously seen events.
     pub num_events: NonZeroUsize,
+
+    /// The time window, in seconds, events are deduplicated within.
+    ///
+    /// Events are only considered duplicates of cached events that were first seen less than this
+    /// long ago, after which the same event starts a new window. By default, events are
+    /// deduplicated for as long as they're cached.
+    #[configurable(metadata(docs::examples = 60))]
+    #[serde(default)]
+    pub ttl_secs: Option<NonZeroU64>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub eviction: EvictionPolicy,
 }
 
 /// Configuration for the `dedupe` transform.
@@ -81,11 +122,22 @@ pub struct DedupeConfig {
     #[configurable(derived)]
     #[serde(default = "default_cache_config")]
     pub cache: CacheConfig,
+
+    /// The field the number of duplicates of retained events is added to.
+    ///
+    /// When set, the first event of each time window is retained until its window ends, or until
+    /// it's evicted from the cache, and then emitted with the number of duplicates that were
+    /// dropped in the meantime. Requires `cache.ttl_secs`.
+    #[configurable(metadata(docs::examples = "duplicate_count"))]
+    #[serde(default)]
+    pub duplicate_count_field: Option<String>,
 }
 
 fn default_cache_config() -> CacheConfig {
     CacheConfig {
         num_events: NonZeroUsize::new(5000).expect("static non-zero number"),
+        ttl_secs: None,
+        eviction: EvictionPolicy::default(),
     }
 }
 
@@ -125,9 +177,25 @@ impl DedupeConfig {
     }
 }
 
+/// A cached event, with the start of its time window and the duplicates dropped since.
+struct CacheValue {
+    seen_at: Instant,
+    duplicates: u64,
+    /// The event, when it's retained until it's emitted with its number of duplicates.
+    retained: Option<Event>,
+}
+
+/// Whether the time window of `value` has ended.
+fn is_expired(ttl: Option<Duration>, value: &CacheValue, now: Instant) -> bool {
+    ttl.map_or(false, |ttl| now.duration_since(value.seen_at) >= ttl)
+}
+
 pub struct Dedupe {
     fields: FieldMatchConfig,
-    cache: LruCache<CacheEntry, bool>,
+    cache: LruCache<CacheEntry, CacheValue>,
+    ttl: Option<Duration>,
+    eviction: EvictionPolicy,
+    duplicate_count_field: Option<String>,
 }
 
 impl GenerateConfig for DedupeConfig {
@@ -135,6 +203,7 @@ impl GenerateConfig for DedupeConfig {
         toml::Value::try_from(Self {
             fields: None,
             cache: default_cache_config(),
+            duplicate_count_field: None,
         })
         .unwrap()
     }
@@ -143,6 +212,9 @@ impl GenerateConfig for DedupeConfig {
 #[async_trait::async_trait]
 impl TransformConfig for DedupeConfig {
     async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if self.duplicate_count_field.is_some() && self.cache.ttl_secs.is_none() {
+            return Err(BuildError::DuplicateCountWithoutTtl.into());
+        }
         Ok(Transform::event_task(Dedupe::new(self.clone())))
     }
 
@@ -180,7 +252,7 @@ type TypeId = u8;
 /// iterating over the fields of the incoming Events, we know that the
 /// CacheEntries for 2 equivalent events will always contain the fields in the
 /// same order.
-#[derive(PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 enum CacheEntry {
     Match(Vec<Option<(TypeId, Bytes)>>),
     Ignore(Vec<(String, TypeId, Bytes)>),
@@ -208,16 +280,90 @@ impl Dedupe {
         Self {
             fields,
             cache: LruCache::new(num_entries),
+            ttl: config
+                .cache
+                .ttl_secs
+                .map(|ttl_secs| Duration::from_secs(ttl_secs.get())),
+            eviction: config.cache.eviction,
+            duplicate_count_field: config.duplicate_count_field,
         }
     }
 
-    fn transform_one(&mut self, event: Event) -> Option<Event> {
+    /// Emits the event retained by `value`, if any, with its number of duplicates.
+    fn emit_retained(&self, value: CacheValue, output: &mut Vec<Event>) {
+        if let (Some(mut event), Some(field)) = (value.retained, &self.duplicate_count_field) {
+            event
+                .as_mut_log()
+                .insert(field.as_str(), value.duplicates as i64);
+            output.push(event);
+        }
+    }
+
+    fn transform_one(&mut self, event: Event, output: &mut Vec<Event>) {
         let cache_entry = build_cache_entry(&event, &self.fields);
-        if self.cache.put(cache_entry, true).is_some() {
+        let now = Instant::now();
+
+        let ttl = self.ttl;
+        let cached = match self.eviction {
+            EvictionPolicy::Lru => self.cache.get_mut(&cache_entry),
+            EvictionPolicy::Fifo => self.cache.peek_mut(&cache_entry),
+        };
+        if let Some(value) = cached.filter(|value| !is_expired(ttl, value, now)) {
+            value.duplicates += 1;
+            if let Some(retained) = &mut value.retained {
+                // The retained event is only delivered along with the duplicates it stands for.
+                let (_, metadata) = event.into_log().into_parts();
+                retained.metadata_mut().merge(metadata);
+            }
+            emit!(DedupeCacheLookup {
+                hit: true,
+                cache_size: self.cache.len(),
+            });
             emit!(DedupeEventsDropped { count: 1 });
-            None
-        } else {
+            return;
+        }
+
+        let retained = if self.duplicate_count_field.is_some() {
             Some(event)
+        } else {
+            output.push(event);
+            None
+        };
+        let value = CacheValue {
+            seen_at: now,
+            duplicates: 0,
+            retained,
+        };
+        // Either the replaced value of an expired event, or that of the evicted event.
+        if let Some((_, previous)) = self.cache.push(cache_entry, value) {
+            self.emit_retained(previous, output);
+        }
+        emit!(DedupeCacheLookup {
+            hit: false,
+            cache_size: self.cache.len(),
+        });
+    }
+
+    /// Emits the retained events whose time window has ended.
+    fn flush_expired(&mut self, output: &mut Vec<Event>) {
+        let now = Instant::now();
+        let expired: Vec<_> = self
+            .cache
+            .iter()
+            .filter(|(_, value)| value.retained.is_some() && is_expired(self.ttl, value, now))
+            .map(|(entry, _)| entry.clone())
+            .collect();
+        for entry in expired {
+            if let Some(value) = self.cache.pop(&entry) {
+                self.emit_retained(value, output);
+            }
+        }
+    }
+
+    /// Emits all retained events, once the input has ended.
+    fn flush_all(&mut self, output: &mut Vec<Event>) {
+        while let Some((_, value)) = self.cache.pop_lru() {
+            self.emit_retained(value, output);
         }
     }
 }
@@ -267,13 +413,45 @@ impl TaskTransform<Event> for Dedupe {
         Self: 'static,
     {
         let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+        if inner.duplicate_count_field.is_none() {
+            return Box::pin(task.filter_map(move |event| {
+                let mut output = Vec::with_capacity(1);
+                inner.transform_one(event, &mut output);
+                ready(output.pop())
+            }));
+        }
+
+        let mut input_rx = task;
+        let mut flush_stream = tokio::time::interval(Duration::from_secs(1));
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        inner.flush_expired(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                inner.flush_all(&mut output);
+                                done = true;
+                            }
+                            Some(event) => inner.transform_one(event, &mut output),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeMap;
+    use std::{collections::BTreeMap, num::NonZeroU64, time::Duration};
 
     use tokio::sync::mpsc;
     use tokio_stream::wrappers::ReceiverStream;
@@ -282,7 +460,7 @@ mod tests {
         event::{Event, LogEvent, Value},
         test_util::components::assert_transform_compliance,
         transforms::{
-            dedupe::{CacheConfig, DedupeConfig, FieldMatchConfig},
+            dedupe::{CacheConfig, Dedupe, DedupeConfig, EvictionPolicy, FieldMatchConfig},
             test::create_topology,
         },
     };
@@ -296,8 +474,11 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                ttl_secs: None,
+                eviction: EvictionPolicy::Lru,
             },
             fields: Some(FieldMatchConfig::MatchFields(fields)),
+            duplicate_count_field: None,
         }
     }
 
@@ -309,8 +490,11 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                ttl_secs: None,
+                eviction: EvictionPolicy::Lru,
             },
             fields: Some(FieldMatchConfig::IgnoreFields(fields)),
+            duplicate_count_field: None,
         }
     }
 
@@ -643,4 +827,76 @@ mod tests {
         })
         .await;
     }
+
+    fn make_event(matched: &str) -> Event {
+        let mut event = Event::Log(LogEvent::from("message"));
+        event.as_mut_log().insert("matched", matched);
+        event
+    }
+
+    /// Sends events with the given values of "matched", and returns those of the emitted ones.
+    fn send(dedupe: &mut Dedupe, values: &[&str]) -> Vec<Event> {
+        let mut output = Vec::new();
+        for value in values {
+            dedupe.transform_one(make_event(value), &mut output);
+        }
+        output
+    }
+
+    #[tokio::test]
+    async fn dedupe_ttl_window() {
+        tokio::time::pause();
+        let mut config = make_match_transform_config(5, vec!["matched".into()]);
+        config.cache.ttl_secs = NonZeroU64::new(10);
+        let mut dedupe = Dedupe::new(config);
+
+        assert_eq!(send(&mut dedupe, &["a", "a"]).len(), 1);
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert!(send(&mut dedupe, &["a"]).is_empty());
+
+        // The window starts with the first event, so later duplicates don't extend it.
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert_eq!(send(&mut dedupe, &["a", "a"]).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn dedupe_eviction_policies() {
+        let mut config = make_match_transform_config(2, vec!["matched".into()]);
+
+        // Matching "a" again keeps it cached, so caching "c" evicts "b".
+        let mut dedupe = Dedupe::new(config.clone());
+        assert_eq!(send(&mut dedupe, &["a", "b", "a", "c", "a", "b"]).len(), 4);
+
+        // Matching "a" again doesn't keep it cached, so caching "c" evicts it.
+        config.cache.eviction = EvictionPolicy::Fifo;
+        let mut dedupe = Dedupe::new(config);
+        assert_eq!(send(&mut dedupe, &["a", "b", "a", "c", "a", "b"]).len(), 5);
+    }
+
+    #[tokio::test]
+    async fn dedupe_duplicate_count() {
+        tokio::time::pause();
+        let mut config = make_match_transform_config(5, vec!["matched".into()]);
+        config.cache.ttl_secs = NonZeroU64::new(10);
+        config.duplicate_count_field = Some("duplicate_count".into());
+        let mut dedupe = Dedupe::new(config);
+
+        // Events are retained until their window ends.
+        assert!(send(&mut dedupe, &["a", "a", "a"]).is_empty());
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert!(send(&mut dedupe, &["b"]).is_empty());
+
+        let mut output = Vec::new();
+        tokio::time::advance(Duration::from_secs(5)).await;
+        dedupe.flush_expired(&mut output);
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].as_log()["matched"], "a".into());
+        assert_eq!(output[0].as_log()["duplicate_count"], Value::from(2));
+
+        output.clear();
+        dedupe.flush_all(&mut output);
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].as_log()["matched"], "b".into());
+        assert_eq!(output[0].as_log()["duplicate_count"], Value::from(0));
+    }
 }
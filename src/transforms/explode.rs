@@ -0,0 +1,294 @@
+use lookup::lookup_v2::parse_value_path;
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    emit,
+    event::{Event, LogEvent, Value},
+    internal_events::ExplodeEmptyArray,
+    schema::Definition,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Configuration for the `explode` transform.
+#[configurable_component(transform("explode"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ExplodeConfig {
+    /// The array field whose elements an event is emitted for each.
+    ///
+    /// Logs without the field, or with a value that isn't an array, are emitted unchanged.
+    #[configurable(metadata(docs::examples = "records", docs::examples = "payload.items"))]
+    pub field: String,
+
+    /// The field of the emitted events the elements are inserted into.
+    ///
+    /// By default, elements are inserted into `field`.
+    #[configurable(metadata(docs::examples = "record"))]
+    pub target_field: Option<String>,
+
+    /// The fields of logs copied to the events emitted for their elements.
+    ///
+    /// By default, all fields of logs are copied.
+    #[configurable(metadata(docs::examples = "host", docs::examples = "timestamp"))]
+    pub copy_fields: Option<Vec<String>>,
+
+    /// The fields of logs moved to the events emitted for their elements.
+    ///
+    /// They're copied as with `copy_fields`, and removed from logs that are kept with
+    /// `keep_parent`.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "request_id"))]
+    pub move_fields: Vec<String>,
+
+    /// The field of the emitted events the indexes of their elements are inserted into.
+    #[configurable(metadata(docs::examples = "record_index"))]
+    pub index_field: Option<String>,
+
+    /// Whether logs are emitted as well, after the events of their elements, without `field`.
+    #[serde(default)]
+    pub keep_parent: bool,
+}
+
+impl GenerateConfig for ExplodeConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"field = "records""#).unwrap()
+    }
+}
+
+impl ExplodeConfig {
+    fn target_field(&self) -> &str {
+        self.target_field.as_deref().unwrap_or(&self.field)
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for ExplodeConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        let fields = std::iter::once(&self.field)
+            .chain(&self.target_field)
+            .chain(&self.index_field)
+            .chain(self.copy_fields.iter().flatten())
+            .chain(&self.move_fields);
+        for field in fields {
+            if parse_value_path(field).is_err() {
+                return Err(format!("Invalid field path {:?}.", field).into());
+            }
+        }
+
+        Ok(Transform::function(Explode {
+            field: self.field.clone(),
+            target_field: self.target_field().to_owned(),
+            copy_fields: self.copy_fields.clone(),
+            move_fields: self.move_fields.clone(),
+            index_field: self.index_field.clone(),
+            keep_parent: self.keep_parent,
+        }))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, merged_definition: &Definition, _: LogNamespace) -> Vec<Output> {
+        let mut definition = if self.copy_fields.is_some() {
+            // Only the selected fields are copied, which may be any of those of the inputs.
+            Definition::default_for_namespace(merged_definition.log_namespaces())
+        } else {
+            merged_definition.clone()
+        };
+        if let Ok(path) = parse_value_path(self.target_field()) {
+            definition = definition.optional_field(&path, Kind::any(), None);
+        }
+        if let Some(Ok(path)) = self.index_field.as_deref().map(parse_value_path) {
+            definition = definition.optional_field(&path, Kind::integer(), None);
+        }
+        vec![Output::default(DataType::Log).with_schema_definition(definition)]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+pub struct Explode {
+    field: String,
+    target_field: String,
+    copy_fields: Option<Vec<String>>,
+    move_fields: Vec<String>,
+    index_field: Option<String>,
+    keep_parent: bool,
+}
+
+impl Explode {
+    /// The log holding the fields copied and moved from `parent` to the events of its elements.
+    fn child_template(&self, parent: &LogEvent) -> LogEvent {
+        match &self.copy_fields {
+            None => parent.clone(),
+            Some(copy_fields) => {
+                // Children share the finalizers of their parent, so that it's only acknowledged
+                // once all of them are.
+                let mut template = LogEvent::new_with_metadata(parent.metadata().clone());
+                for field in copy_fields.iter().chain(&self.move_fields) {
+                    if let Some(value) = parent.get(field.as_str()) {
+                        template.insert(field.as_str(), value.clone());
+                    }
+                }
+                template
+            }
+        }
+    }
+}
+
+impl FunctionTransform for Explode {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        let mut log = event.into_log();
+        let elements = match log.remove(self.field.as_str()) {
+            Some(Value::Array(elements)) => elements,
+            Some(value) => {
+                log.insert(self.field.as_str(), value);
+                output.push(Event::from(log));
+                return;
+            }
+            None => {
+                output.push(Event::from(log));
+                return;
+            }
+        };
+
+        if elements.is_empty() && !self.keep_parent {
+            emit!(ExplodeEmptyArray);
+            return;
+        }
+
+        let template = self.child_template(&log);
+        for (index, element) in elements.into_iter().enumerate() {
+            let mut child = template.clone();
+            child.insert(self.target_field.as_str(), element);
+            if let Some(index_field) = &self.index_field {
+                child.insert(index_field.as_str(), index as i64);
+            }
+            output.push(Event::from(child));
+        }
+
+        if self.keep_parent {
+            for field in &self.move_fields {
+                log.remove(field.as_str());
+            }
+            output.push(Event::from(log));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::event::{BatchNotifier, BatchStatus, EventFinalizers, Finalizable};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ExplodeConfig>();
+    }
+
+    fn explode(config: &str, log: LogEvent) -> Vec<Event> {
+        let config: ExplodeConfig = toml::from_str(config).unwrap();
+        let mut transform = Explode {
+            field: config.field.clone(),
+            target_field: config.target_field().to_owned(),
+            copy_fields: config.copy_fields,
+            move_fields: config.move_fields,
+            index_field: config.index_field,
+            keep_parent: config.keep_parent,
+        };
+        let mut output = OutputBuffer::default();
+        transform.transform(&mut output, Event::from(log));
+        output.into_events().collect()
+    }
+
+    fn values(events: &[Event]) -> Vec<Value> {
+        events
+            .iter()
+            .map(|event| event.as_log().value().clone())
+            .collect()
+    }
+
+    fn request() -> LogEvent {
+        LogEvent::from(Value::from(json!({
+            "host": "web-1",
+            "request_id": "abc",
+            "records": [{"id": 1}, {"id": 2}],
+        })))
+    }
+
+    #[test]
+    fn explodes_arrays() {
+        let events = explode(r#"field = "records""#, request());
+        assert_eq!(
+            values(&events),
+            vec![
+                Value::from(json!({"host": "web-1", "request_id": "abc", "records": {"id": 1}})),
+                Value::from(json!({"host": "web-1", "request_id": "abc", "records": {"id": 2}})),
+            ]
+        );
+
+        let mut log = request();
+        log.insert("records", "not an array");
+        let events = explode(r#"field = "records""#, log.clone());
+        assert_eq!(values(&events), vec![log.value().clone()]);
+
+        log.insert("records", Vec::<Value>::new());
+        assert!(explode(r#"field = "records""#, log).is_empty());
+    }
+
+    #[test]
+    fn copies_and_moves_fields() {
+        let config = r#"
+            field = "records"
+            target_field = "record"
+            copy_fields = ["host"]
+            move_fields = ["request_id"]
+            index_field = "index"
+            keep_parent = true
+        "#;
+        let events = explode(config, request());
+        assert_eq!(
+            values(&events),
+            vec![
+                Value::from(
+                    json!({"host": "web-1", "request_id": "abc", "record": {"id": 1}, "index": 0})
+                ),
+                Value::from(
+                    json!({"host": "web-1", "request_id": "abc", "record": {"id": 2}, "index": 1})
+                ),
+                Value::from(json!({"host": "web-1"})),
+            ]
+        );
+    }
+
+    #[test]
+    fn acknowledges_parents_once_all_children_are() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let mut events = explode(
+            r#"field = "records""#,
+            request().with_batch_notifier(&batch),
+        );
+        drop(batch);
+        assert_eq!(events.len(), 2);
+
+        let finalizers: Vec<EventFinalizers> = events
+            .iter_mut()
+            .map(|event| event.take_finalizers())
+            .collect();
+        drop(events);
+        let mut finalizers = finalizers.into_iter();
+        drop(finalizers.next());
+        assert!(receiver.try_recv().is_err());
+        drop(finalizers.next());
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    }
+}
@@ -1,4 +1,7 @@
-use std::num::ParseFloatError;
+use std::{
+    collections::{HashMap, HashSet},
+    num::{NonZeroUsize, ParseFloatError},
+};
 
 use chrono::Utc;
 use indexmap::IndexMap;
@@ -10,11 +13,15 @@ use crate::{
         log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
     },
     event::{
-        metric::{Metric, MetricKind, MetricTags, MetricValue, StatisticKind, TagValue},
+        metric::{
+            samples_to_buckets, Metric, MetricKind, MetricSeries, MetricTags, MetricValue,
+            StatisticKind, TagValue,
+        },
         Event, Value,
     },
     internal_events::{
-        LogToMetricFieldNullError, LogToMetricParseFloatError, ParserMissingFieldError, DROP_EVENT,
+        LogToMetricCardinalityLimitExceeded, LogToMetricFieldNullError, LogToMetricParseFloatError,
+        ParserMissingFieldError, DROP_EVENT,
     },
     schema,
     template::{Template, TemplateRenderingError},
@@ -27,7 +34,15 @@ use crate::{
 #[serde(deny_unknown_fields)]
 pub struct LogToMetricConfig {
     /// A list of metrics to generate.
+    #[serde(default)]
     pub metrics: Vec<MetricConfig>,
+
+    /// Converts logs encoding whole metrics, as output by the `metric_to_log` transform, back
+    /// into those metrics, rather than deriving the metrics of `metrics`.
+    ///
+    /// Logs that don't encode a metric are dropped.
+    #[serde(default)]
+    pub all_metrics: bool,
 }
 
 /// Specification of a counter derived from a log event.
@@ -43,6 +58,60 @@ pub struct CounterConfig {
     pub kind: MetricKind,
 }
 
+/// Specification of a histogram derived from a log event.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct HistogramConfig {
+    /// The upper limits of the buckets of the histogram, in increasing order.
+    ///
+    /// When set, the histogram is emitted with these buckets rather than as a distribution of
+    /// samples, so that it is aggregated the same way whatever the sink. Values greater than the
+    /// last limit are only counted in the sum and count of the histogram.
+    #[serde(default)]
+    pub buckets: Vec<f64>,
+}
+
+/// What to do with the metrics of new series once the cardinality limit of a metric is reached.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowAction {
+    /// Drop the metrics of new series.
+    #[default]
+    Drop,
+
+    /// Replace the values of all tags of the metrics of new series with `overflow`, so that they
+    /// are aggregated into a single series.
+    Aggregate,
+}
+
+impl OverflowAction {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Drop => "drop",
+            Self::Aggregate => "aggregate",
+        }
+    }
+}
+
+/// Limits the number of series of a metric, as distinct sets of name, namespace, and tags.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CardinalityLimitConfig {
+    /// The maximum number of series of the metric.
+    #[configurable(metadata(docs::examples = 1000))]
+    pub max_series: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub overflow_action: OverflowAction,
+}
+
+/// The value tags of the metrics of new series are set to, once the cardinality limit is reached
+/// with the `aggregate` action.
+const OVERFLOW_TAG_VALUE: &str = "overflow";
+
 /// Specification of a metric derived from a log event.
 // TODO: While we're resolving the schema for this enum somewhat reasonably (in
 // `generate-components-docs.rb`), we have a problem where an overlapping field (overlap between two
@@ -66,6 +135,9 @@ pub struct MetricConfig {
     /// Tags to apply to the metric.
     pub tags: Option<IndexMap<String, TagConfig>>,
 
+    #[configurable(derived)]
+    pub cardinality_limit: Option<CardinalityLimitConfig>,
+
     #[configurable(derived)]
     #[serde(flatten)]
     pub metric: MetricTypeConfig,
@@ -93,7 +165,7 @@ pub enum MetricTypeConfig {
     Counter(#[configurable(derived)] CounterConfig),
 
     /// A histogram.
-    Histogram,
+    Histogram(#[configurable(derived)] HistogramConfig),
 
     /// A gauge.
     Gauge,
@@ -122,6 +194,8 @@ const fn default_kind() -> MetricKind {
 #[derive(Debug, Clone)]
 pub struct LogToMetric {
     config: LogToMetricConfig,
+    /// The series seen of each metric with a cardinality limit, in the order of `config.metrics`.
+    series: Vec<HashSet<MetricSeries>>,
 }
 
 impl GenerateConfig for LogToMetricConfig {
@@ -132,11 +206,13 @@ impl GenerateConfig for LogToMetricConfig {
                 name: None,
                 namespace: None,
                 tags: None,
+                cardinality_limit: None,
                 metric: MetricTypeConfig::Counter(CounterConfig {
                     increment_by_value: false,
                     kind: MetricKind::Incremental,
                 }),
             }],
+            all_metrics: false,
         })
         .unwrap()
     }
@@ -145,6 +221,24 @@ impl GenerateConfig for LogToMetricConfig {
 #[async_trait::async_trait]
 impl TransformConfig for LogToMetricConfig {
     async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if self.all_metrics && !self.metrics.is_empty() {
+            return Err("`metrics` can't be set with `all_metrics`.".into());
+        }
+        for metric in &self.metrics {
+            if let MetricTypeConfig::Histogram(histogram) = &metric.metric {
+                if histogram
+                    .buckets
+                    .windows(2)
+                    .any(|limits| limits[0] >= limits[1])
+                {
+                    return Err(format!(
+                        "The buckets of the histogram of {:?} must be in increasing order.",
+                        metric.field()
+                    )
+                    .into());
+                }
+            }
+        }
         Ok(Transform::function(LogToMetric::new(self.clone())))
     }
 
@@ -162,8 +256,42 @@ impl TransformConfig for LogToMetricConfig {
 }
 
 impl LogToMetric {
-    pub const fn new(config: LogToMetricConfig) -> Self {
-        LogToMetric { config }
+    pub fn new(config: LogToMetricConfig) -> Self {
+        let series = vec![HashSet::new(); config.metrics.len()];
+        LogToMetric { config, series }
+    }
+}
+
+/// Applies the cardinality limit of a metric to `metric`, returning it unless it is of a new
+/// series beyond the limit, which is dropped.
+fn limit_cardinality(
+    limit: &CardinalityLimitConfig,
+    series: &mut HashSet<MetricSeries>,
+    mut metric: Metric,
+) -> Option<Metric> {
+    if series.contains(metric.series()) {
+        return Some(metric);
+    }
+    if series.len() < limit.max_series.get() {
+        series.insert(metric.series().clone());
+        return Some(metric);
+    }
+
+    emit!(LogToMetricCardinalityLimitExceeded {
+        name: metric.name(),
+        action: limit.overflow_action.as_str(),
+    });
+    match limit.overflow_action {
+        OverflowAction::Drop => None,
+        OverflowAction::Aggregate => {
+            if let Some(tags) = metric.tags_mut() {
+                let names: Vec<_> = tags.keys().map(str::to_owned).collect();
+                for name in names {
+                    tags.replace(name, OVERFLOW_TAG_VALUE.to_owned());
+                }
+            }
+            Some(metric)
+        }
     }
 }
 
@@ -284,21 +412,29 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
 
             (counter.kind, MetricValue::Counter { value })
         }
-        MetricTypeConfig::Histogram => {
+        MetricTypeConfig::Histogram(histogram) => {
             let value = value.to_string_lossy().parse().map_err(|error| {
                 TransformError::ParseFloatError {
                     field: field.to_string(),
                     error,
                 }
             })?;
+            let samples = vector_core::samples![value => 1];
 
-            (
-                MetricKind::Incremental,
+            let value = if histogram.buckets.is_empty() {
                 MetricValue::Distribution {
-                    samples: vector_core::samples![value => 1],
+                    samples,
                     statistic: StatisticKind::Histogram,
-                },
-            )
+                }
+            } else {
+                let (buckets, count, sum) = samples_to_buckets(&samples, &histogram.buckets);
+                MetricValue::AggregatedHistogram {
+                    buckets,
+                    count,
+                    sum,
+                }
+            };
+            (MetricKind::Incremental, value)
         }
         MetricTypeConfig::Summary => {
             let value = value.to_string_lossy().parse().map_err(|error| {
@@ -343,15 +479,180 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
         .with_timestamp(timestamp))
 }
 
+/// The encodings of non-finite floats in logs encoding metrics, which JSON has no representation
+/// of.
+const NON_FINITE_FLOATS: [(&str, f64); 3] = [
+    ("inf", f64::INFINITY),
+    ("-inf", f64::NEG_INFINITY),
+    ("NaN", f64::NAN),
+];
+
+/// The fields of metric values holding floats, in their serialization.
+const FLOAT_FIELDS: [&str; 4] = ["value", "upper_limit", "quantile", "sum"];
+
+/// Calls `f` with each float of `value`, along with the JSON pointer of the float in the
+/// serialization of `value`.
+fn visit_floats(value: &mut MetricValue, mut f: impl FnMut(String, &mut f64)) {
+    match value {
+        MetricValue::Counter { value } => f("/counter/value".to_owned(), value),
+        MetricValue::Gauge { value } => f("/gauge/value".to_owned(), value),
+        MetricValue::Distribution { samples, .. } => {
+            for (i, sample) in samples.iter_mut().enumerate() {
+                f(
+                    format!("/distribution/samples/{i}/value"),
+                    &mut sample.value,
+                );
+            }
+        }
+        MetricValue::AggregatedHistogram { buckets, sum, .. } => {
+            for (i, bucket) in buckets.iter_mut().enumerate() {
+                f(
+                    format!("/aggregated_histogram/buckets/{i}/upper_limit"),
+                    &mut bucket.upper_limit,
+                );
+            }
+            f("/aggregated_histogram/sum".to_owned(), sum);
+        }
+        MetricValue::AggregatedSummary { quantiles, sum, .. } => {
+            for (i, quantile) in quantiles.iter_mut().enumerate() {
+                f(
+                    format!("/aggregated_summary/quantiles/{i}/quantile"),
+                    &mut quantile.quantile,
+                );
+                f(
+                    format!("/aggregated_summary/quantiles/{i}/value"),
+                    &mut quantile.value,
+                );
+            }
+            f("/aggregated_summary/sum".to_owned(), sum);
+        }
+        // The statistics of sketches are finite, even when they are empty.
+        MetricValue::Set { .. } | MetricValue::Sketch { .. } => {}
+    }
+}
+
+/// Encodes the non-finite floats of `metric` in `json`, its serialization, where they are `null`.
+pub(crate) fn encode_non_finite_floats(metric: &mut Metric, json: &mut serde_json::Value) {
+    visit_floats(metric.value_mut(), |pointer, value| {
+        if value.is_finite() {
+            return;
+        }
+        let encoding = NON_FINITE_FLOATS
+            .iter()
+            .find(|(_, float)| *float == *value || (float.is_nan() && value.is_nan()))
+            .map(|(encoding, _)| *encoding);
+        if let (Some(encoding), Some(field)) = (encoding, json.pointer_mut(&pointer)) {
+            *field = serde_json::Value::from(encoding);
+        }
+    });
+}
+
+/// Replaces the encodings of non-finite floats in the metric values of `json` with `0.0`,
+/// collecting them by their JSON pointer.
+fn take_non_finite_floats(
+    json: &mut serde_json::Value,
+    pointer: String,
+    floats: &mut HashMap<String, f64>,
+) {
+    let float = match json {
+        serde_json::Value::Object(object) => {
+            for (key, value) in object {
+                take_non_finite_floats(value, format!("{pointer}/{key}"), floats);
+            }
+            None
+        }
+        serde_json::Value::Array(array) => {
+            for (i, value) in array.iter_mut().enumerate() {
+                take_non_finite_floats(value, format!("{pointer}/{i}"), floats);
+            }
+            None
+        }
+        serde_json::Value::String(string) => NON_FINITE_FLOATS
+            .iter()
+            .find(|(encoding, _)| *encoding == string.as_str())
+            .map(|(_, float)| *float),
+        _ => None,
+    };
+
+    let is_float_field = pointer
+        .rsplit('/')
+        .next()
+        .map_or(false, |field| FLOAT_FIELDS.contains(&field));
+    if let (Some(float), true) = (float, is_float_field) {
+        floats.insert(pointer, float);
+        *json = serde_json::Value::from(0.0);
+    }
+}
+
+/// Decodes the metric encoded by `event`, as output by the `metric_to_log` transform.
+fn to_any_metric(event: Event) -> Result<Metric, serde_json::Error> {
+    let (value, metadata) = event.into_log().into_parts();
+    let mut json = serde_json::to_value(&value)?;
+
+    let mut floats = HashMap::new();
+    if let Some(object) = json.as_object_mut() {
+        // Logs of the legacy namespace have the timestamp and host of metrics in the fields of
+        // the log schema.
+        let timestamp_key = log_schema().timestamp_key();
+        if timestamp_key != "timestamp" {
+            if let Some(timestamp) = object.remove(timestamp_key) {
+                object.insert("timestamp".to_owned(), timestamp);
+            }
+        }
+        let host_key = log_schema().host_key();
+        if let Some(host) = object.remove(host_key) {
+            let tags = object
+                .entry("tags")
+                .or_insert_with(|| serde_json::Value::Object(Default::default()));
+            if let Some(tags) = tags.as_object_mut() {
+                tags.entry(host_key).or_insert(host);
+            }
+        }
+
+        for kind in [
+            "counter",
+            "gauge",
+            "distribution",
+            "aggregated_histogram",
+            "aggregated_summary",
+        ] {
+            if let Some(value) = object.get_mut(kind) {
+                take_non_finite_floats(value, format!("/{kind}"), &mut floats);
+            }
+        }
+    }
+
+    let mut metric: Metric = serde_json::from_value(json)?;
+    visit_floats(metric.value_mut(), |pointer, value| {
+        if let Some(float) = floats.remove(&pointer) {
+            *value = float;
+        }
+    });
+    let (series, data, _) = metric.into_parts();
+    Ok(Metric::from_parts(series, data, metadata))
+}
+
 impl FunctionTransform for LogToMetric {
     fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        if self.config.all_metrics {
+            match to_any_metric(event) {
+                Ok(metric) => output.push(Event::Metric(metric)),
+                Err(error) => emit!(LogToMetricDeserializeError { error }),
+            }
+            return;
+        }
+
         // Metrics are "all or none" for a specific log. If a single fails, none are produced.
         let mut buffer = Vec::with_capacity(self.config.metrics.len());
 
-        for config in self.config.metrics.iter() {
+        for (config, series) in self.config.metrics.iter().zip(self.series.iter_mut()) {
             match to_metric(config, &event) {
                 Ok(metric) => {
-                    buffer.push(Event::Metric(metric));
+                    let metric = match &config.cardinality_limit {
+                        Some(limit) => limit_cardinality(limit, series, metric),
+                        None => Some(metric),
+                    };
+                    buffer.extend(metric.map(Event::Metric));
                 }
                 Err(err) => {
                     match err {
@@ -956,4 +1257,106 @@ mod tests {
             .with_timestamp(Some(ts()))
         );
     }
+
+    #[tokio::test]
+    async fn response_time_histogram_with_buckets() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "histogram"
+            field = "response_time"
+            buckets = [1.0, 2.5, 5.0]
+            "#,
+        );
+
+        let event = create_event("response_time", "2.5");
+        let metadata = event.metadata().clone();
+        let metric = do_transform(config, event).await.unwrap();
+
+        assert_eq!(
+            metric.into_metric(),
+            Metric::new_with_metadata(
+                "response_time",
+                MetricKind::Incremental,
+                MetricValue::AggregatedHistogram {
+                    buckets: vector_core::buckets![1.0 => 0, 2.5 => 1, 5.0 => 0],
+                    count: 1,
+                    sum: 2.5,
+                },
+                metadata
+            )
+            .with_timestamp(Some(ts()))
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_unsorted_buckets() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "histogram"
+            field = "response_time"
+            buckets = [1.0, 5.0, 2.5]
+            "#,
+        );
+
+        assert!(config.build(&TransformContext::default()).await.is_err());
+    }
+
+    fn transform_paths(config: &str, paths: &[&str]) -> Vec<Metric> {
+        let mut transform = LogToMetric::new(parse_config(config));
+        paths
+            .iter()
+            .flat_map(|path| {
+                let mut buf = OutputBuffer::with_capacity(1);
+                transform.transform(&mut buf, create_event("path", *path));
+                buf.into_events()
+                    .map(Event::into_metric)
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn drops_series_beyond_cardinality_limit() {
+        let metrics = transform_paths(
+            r#"
+            [[metrics]]
+            type = "counter"
+            field = "path"
+            name = "requests"
+            tags.path = "{{ path }}"
+            cardinality_limit.max_series = 2
+            "#,
+            &["/a", "/b", "/c", "/a"],
+        );
+
+        let paths: Vec<_> = metrics
+            .iter()
+            .map(|metric| metric.tag_value("path").unwrap())
+            .collect();
+        assert_eq!(paths, vec!["/a", "/b", "/a"]);
+    }
+
+    #[test]
+    fn aggregates_series_beyond_cardinality_limit() {
+        let metrics = transform_paths(
+            r#"
+            [[metrics]]
+            type = "counter"
+            field = "path"
+            name = "requests"
+            tags.path = "{{ path }}"
+            cardinality_limit.max_series = 1
+            cardinality_limit.overflow_action = "aggregate"
+            "#,
+            &["/a", "/b", "/c"],
+        );
+
+        let paths: Vec<_> = metrics
+            .iter()
+            .map(|metric| metric.tag_value("path").unwrap())
+            .collect();
+        assert_eq!(paths, vec!["/a", "overflow", "overflow"]);
+    }
 }
@@ -1,5 +1,6 @@
 pub mod v1;
 pub mod v2;
+pub mod v3;
 
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
@@ -61,6 +62,30 @@ pub struct LuaConfigV2 {
     config: v2::LuaConfig,
 }
 
+/// Marker type for the version three of the configuration for the `lua` transform.
+#[configurable_component]
+#[derive(Clone, Debug)]
+enum V3 {
+    /// Lua transform API version 3.
+    ///
+    /// Scripts have the same API as with version two, but run on dedicated threads.
+    #[serde(rename = "3")]
+    V3,
+}
+
+/// Configuration for the version three of the `lua` transform.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct LuaConfigV3 {
+    /// Transform API version.
+    ///
+    /// Specifying this version ensures that Vector does not break backward compatibility.
+    version: V3,
+
+    #[serde(flatten)]
+    config: v3::LuaConfig,
+}
+
 /// Configuration for the `lua` transform.
 #[configurable_component(transform("lua"))]
 #[derive(Clone, Debug)]
@@ -71,6 +96,9 @@ pub enum LuaConfig {
 
     /// Configuration for version two.
     V2(#[configurable(derived)] LuaConfigV2),
+
+    /// Configuration for version three.
+    V3(#[configurable(derived)] LuaConfigV3),
 }
 
 impl GenerateConfig for LuaConfig {
@@ -89,6 +117,7 @@ impl TransformConfig for LuaConfig {
         match self {
             LuaConfig::V1(v1) => v1.config.build(),
             LuaConfig::V2(v2) => v2.config.build(),
+            LuaConfig::V3(v3) => v3.config.build(),
         }
     }
 
@@ -96,6 +125,7 @@ impl TransformConfig for LuaConfig {
         match self {
             LuaConfig::V1(v1) => v1.config.input(),
             LuaConfig::V2(v2) => v2.config.input(),
+            LuaConfig::V3(v3) => v3.config.input(),
         }
     }
 
@@ -103,6 +133,7 @@ impl TransformConfig for LuaConfig {
         match self {
             LuaConfig::V1(v1) => v1.config.outputs(merged_definition),
             LuaConfig::V2(v2) => v2.config.outputs(merged_definition),
+            LuaConfig::V3(v3) => v3.config.outputs(merged_definition),
         }
     }
 }
@@ -282,6 +282,11 @@ impl Lua {
         })
     }
 
+    /// The Lua state scripts run in, so that runtimes can limit it.
+    pub(super) const fn lua(&self) -> &mlua::Lua {
+        &self.lua
+    }
+
     #[cfg(test)]
     fn process(&mut self, event: Event, output: &mut Vec<Event>) -> Result<(), mlua::Error> {
         let lua = &self.lua;
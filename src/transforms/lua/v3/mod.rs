@@ -0,0 +1,466 @@
+use std::{
+    num::NonZeroUsize,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use async_stream::stream;
+use futures::{stream, Stream, StreamExt};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio_stream::wrappers::IntervalStream;
+use tracing::{Instrument, Span};
+use vector_config::configurable_component;
+use vector_core::transform::runtime_transform::{RuntimeTransform, Timer};
+
+use super::v2;
+use crate::{
+    config::{Input, Output},
+    event::Event,
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Cannot set the memory limit of the Lua runtime: {}", source))]
+    InvalidMemoryLimit { source: mlua::Error },
+    #[snafu(display("Cannot set the timeout of the Lua runtime: {}", source))]
+    InvalidTimeout { source: mlua::Error },
+    #[snafu(display("Cannot start Lua runtime thread: {}", source))]
+    SpawnThread { source: std::io::Error },
+}
+
+/// Configuration for the version three of the `lua` transform.
+///
+/// Scripts have the same API as with version two, but run on dedicated threads rather than on the
+/// threads of the topology.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct LuaConfig {
+    #[serde(flatten)]
+    script: v2::LuaConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    runtime: RuntimeConfig,
+}
+
+/// The runtime scripts run in.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct RuntimeConfig {
+    /// The number of threads running the script, each with its own Lua runtime.
+    ///
+    /// Events are processed by whichever thread has room for them, so with more than one thread
+    /// the order of events isn't kept, and the global variables of scripts aren't shared between
+    /// events processed by different threads. The `init` and `shutdown` hooks, and the timer
+    /// handlers, are called on each thread.
+    #[serde(default = "default_threads")]
+    threads: NonZeroUsize,
+
+    /// The number of events each thread can have waiting to be processed.
+    ///
+    /// Once all threads have this many, the transform stops taking events, applying backpressure
+    /// to its inputs.
+    #[serde(default = "default_queue_size")]
+    queue_size: NonZeroUsize,
+
+    /// The longest, in milliseconds, a single call of a hook or timer handler can run for before
+    /// it's aborted with an error.
+    ///
+    /// The time is checked every thousand Lua instructions, so calls blocked in Lua functions
+    /// implemented in C, such as those reading files, aren't aborted until they return.
+    #[configurable(metadata(docs::examples = 100))]
+    timeout_ms: Option<u64>,
+
+    /// The most memory, in bytes, the Lua runtime of each thread can allocate.
+    ///
+    /// Allocations beyond it fail with an error, aborting the call of the hook or timer handler
+    /// making them.
+    #[configurable(metadata(docs::examples = 67108864))]
+    memory_limit: Option<usize>,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self {
+            threads: default_threads(),
+            queue_size: default_queue_size(),
+            timeout_ms: None,
+            memory_limit: None,
+        }
+    }
+}
+
+fn default_threads() -> NonZeroUsize {
+    NonZeroUsize::new(1).expect("static non-zero number")
+}
+
+fn default_queue_size() -> NonZeroUsize {
+    NonZeroUsize::new(100).expect("static non-zero number")
+}
+
+/// The number of Lua instructions between checks of the timeout of calls.
+const INSTRUCTIONS_PER_TIMEOUT_CHECK: u32 = 1000;
+
+impl LuaConfig {
+    pub fn build(&self) -> crate::Result<Transform> {
+        let runtimes = (0..self.runtime.threads.get())
+            .map(|_| Runtime::new(&self.script, &self.runtime))
+            .collect::<crate::Result<_>>()?;
+
+        Ok(Transform::event_task(LuaPool {
+            runtimes,
+            queue_size: self.runtime.queue_size.get(),
+        }))
+    }
+
+    pub fn input(&self) -> Input {
+        self.script.input()
+    }
+
+    pub fn outputs(&self, merged_definition: &schema::Definition) -> Vec<Output> {
+        self.script.outputs(merged_definition)
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+enum Message {
+    Init,
+    Process(Event),
+    Shutdown,
+    Timer(Timer),
+}
+
+/// A Lua runtime, along with the deadline of the call running in it.
+struct Runtime {
+    lua: v2::Lua,
+    timeout: Option<Duration>,
+    deadline: Arc<Mutex<Option<Instant>>>,
+}
+
+impl Runtime {
+    fn new(script: &v2::LuaConfig, config: &RuntimeConfig) -> crate::Result<Self> {
+        let lua = v2::Lua::new(script)?;
+        if let Some(memory_limit) = config.memory_limit {
+            lua.lua()
+                .set_memory_limit(memory_limit)
+                .context(InvalidMemoryLimitSnafu)?;
+        }
+
+        let deadline = Arc::new(Mutex::new(None));
+        let timeout = config.timeout_ms.map(Duration::from_millis);
+        if timeout.is_some() {
+            let deadline = Arc::clone(&deadline);
+            let triggers = mlua::HookTriggers {
+                every_nth_instruction: Some(INSTRUCTIONS_PER_TIMEOUT_CHECK),
+                ..Default::default()
+            };
+            lua.lua()
+                .set_hook(triggers, move |_, _| {
+                    match *deadline.lock().expect("deadline mutex poisoned") {
+                        Some(deadline) if Instant::now() > deadline => {
+                            Err(mlua::Error::RuntimeError("the call timed out".to_string()))
+                        }
+                        _ => Ok(()),
+                    }
+                })
+                .context(InvalidTimeoutSnafu)?;
+        }
+
+        Ok(Self {
+            lua,
+            timeout,
+            deadline,
+        })
+    }
+
+    /// Handles `message`, returning the events emitted by the script.
+    fn handle(&mut self, message: Message) -> Vec<Event> {
+        let mut events = Vec::new();
+        let emit = |event: Event| events.push(event);
+        *self.deadline.lock().expect("deadline mutex poisoned") =
+            self.timeout.map(|timeout| Instant::now() + timeout);
+        match message {
+            Message::Init => self.lua.hook_init(emit),
+            Message::Process(event) => self.lua.hook_process(event, emit),
+            Message::Shutdown => self.lua.hook_shutdown(emit),
+            Message::Timer(timer) => self.lua.timer_handler(timer, emit),
+        }
+        events
+    }
+
+    /// Handles messages until there are no more, or the events it emits are no longer received.
+    fn run(mut self, mut messages: mpsc::Receiver<Message>, output: mpsc::Sender<Event>) {
+        while let Some(message) = messages.blocking_recv() {
+            for event in self.handle(message) {
+                if output.blocking_send(event).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Runs scripts on a pool of threads, each with its own Lua runtime, so that slow scripts don't
+/// block the threads of the topology.
+pub struct LuaPool {
+    runtimes: Vec<Runtime>,
+    queue_size: usize,
+}
+
+/// Sends `message` to all runtimes, returning whether they all still handle messages.
+async fn broadcast(runtimes: &[mpsc::Sender<Message>], message: impl Fn() -> Message) -> bool {
+    for runtime in runtimes {
+        if runtime.send(message()).await.is_err() {
+            return false;
+        }
+    }
+    true
+}
+
+/// Sends `event` to the next runtime with room for it, waiting for room in the next one only
+/// once all of them are full, and returns whether they still handle messages.
+async fn send_event(runtimes: &[mpsc::Sender<Message>], next: &mut usize, event: Event) -> bool {
+    let mut message = Message::Process(event);
+    for offset in 0..runtimes.len() {
+        let index = (*next + offset) % runtimes.len();
+        match runtimes[index].try_send(message) {
+            Ok(()) => {
+                *next = (index + 1) % runtimes.len();
+                return true;
+            }
+            Err(TrySendError::Full(unsent)) => message = unsent,
+            Err(TrySendError::Closed(_)) => return false,
+        }
+    }
+    let sent = runtimes[*next].send(message).await.is_ok();
+    *next = (*next + 1) % runtimes.len();
+    sent
+}
+
+/// Sends the events of `input` to the runtimes, along with the messages of their lifecycle.
+async fn dispatch(
+    input: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    runtimes: Vec<mpsc::Sender<Message>>,
+    timers: Vec<Timer>,
+) {
+    let mut input = input.fuse();
+    // As with version two, the `init` hook is called once the first event comes.
+    let mut first = input.next().await;
+    if !broadcast(&runtimes, || Message::Init).await {
+        return;
+    }
+
+    let mut timer_ticks = stream::select_all(timers.into_iter().map(|timer| {
+        IntervalStream::new(tokio::time::interval(timer.interval)).map(move |_| timer)
+    }))
+    .chain(stream::pending());
+    let mut next = 0;
+    loop {
+        let event = match first.take() {
+            Some(event) => event,
+            None => tokio::select! {
+                maybe_event = input.next() => match maybe_event {
+                    Some(event) => event,
+                    None => break,
+                },
+                Some(timer) = timer_ticks.next() => {
+                    if !broadcast(&runtimes, || Message::Timer(timer)).await {
+                        return;
+                    }
+                    continue;
+                }
+            },
+        };
+        if !send_event(&runtimes, &mut next, event).await {
+            return;
+        }
+    }
+
+    broadcast(&runtimes, || Message::Shutdown).await;
+}
+
+impl TaskTransform<Event> for LuaPool {
+    fn transform(
+        self: Box<Self>,
+        input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let LuaPool {
+            runtimes,
+            queue_size,
+        } = *self;
+        let timers = runtimes
+            .first()
+            .map(|runtime| runtime.lua.timers())
+            .unwrap_or_default();
+
+        Box::pin(stream! {
+            let (output_tx, mut output_rx) = mpsc::channel(queue_size * runtimes.len());
+            let mut senders = Vec::with_capacity(runtimes.len());
+            for (id, runtime) in runtimes.into_iter().enumerate() {
+                let (tx, rx) = mpsc::channel(queue_size);
+                let output_tx = output_tx.clone();
+                // Events emitted by runtimes are tagged with the component, as they would be on
+                // the threads of the topology.
+                let span = Span::current();
+                let spawned = thread::Builder::new()
+                    .name(format!("vector-lua-{}", id))
+                    .spawn(move || {
+                        let _entered = span.enter();
+                        runtime.run(rx, output_tx);
+                    })
+                    .context(SpawnThreadSnafu);
+                match spawned {
+                    Ok(_) => senders.push(tx),
+                    Err(error) => error!(message = "Lua runtime thread not started.", %error),
+                }
+            }
+            // The output ends once all runtimes have stopped, which they do once the input ends.
+            drop(output_tx);
+            if !senders.is_empty() {
+                tokio::spawn(dispatch(input_rx, senders, timers).in_current_span());
+            }
+
+            while let Some(event) = output_rx.recv().await {
+                yield event;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::super::LuaConfig;
+    use crate::{
+        event::{Event, LogEvent},
+        test_util::{components::assert_transform_compliance, trace_init},
+        transforms::test::create_topology,
+    };
+
+    async fn collect(config: &str, events: Vec<Event>, expected: usize) -> Vec<Event> {
+        assert_transform_compliance(async move {
+            let config: LuaConfig = toml::from_str(config).unwrap();
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            for event in events {
+                tx.send(event).await.unwrap();
+            }
+            let mut output = Vec::with_capacity(expected);
+            for _ in 0..expected {
+                output.push(out.recv().await.unwrap());
+            }
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+            output
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn lua_processes_events_on_threads() {
+        trace_init();
+
+        let events = (0..20)
+            .map(|i| Event::Log(LogEvent::from(i.to_string())))
+            .collect();
+        let mut output = collect(
+            r#"
+            version = "3"
+            runtime.threads = 4
+            runtime.queue_size = 2
+            hooks.process = """function (event, emit)
+                event.log.thread = "lua"
+                emit(event)
+            end
+            """
+            "#,
+            events,
+            20,
+        )
+        .await;
+
+        output.sort_by_key(|event| {
+            event.as_log()["message"]
+                .to_string_lossy()
+                .parse::<u32>()
+                .unwrap()
+        });
+        for (i, event) in output.iter().enumerate() {
+            assert_eq!(event.as_log()["message"], i.to_string().into());
+            assert_eq!(event.as_log()["thread"], "lua".into());
+        }
+    }
+
+    #[tokio::test]
+    async fn lua_aborts_slow_calls() {
+        trace_init();
+
+        let events = vec![
+            Event::Log(LogEvent::from("slow")),
+            Event::Log(LogEvent::from("fast")),
+        ];
+        let output = collect(
+            r#"
+            version = "3"
+            runtime.timeout_ms = 50
+            hooks.process = """function (event, emit)
+                if event.log.message == "slow" then
+                    while true do end
+                end
+                emit(event)
+            end
+            """
+            "#,
+            events,
+            1,
+        )
+        .await;
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].as_log()["message"], "fast".into());
+    }
+
+    #[tokio::test]
+    async fn lua_limits_memory() {
+        trace_init();
+
+        let events = vec![
+            Event::Log(LogEvent::from("large")),
+            Event::Log(LogEvent::from("small")),
+        ];
+        let output = collect(
+            r#"
+            version = "3"
+            runtime.memory_limit = 4194304
+            hooks.process = """function (event, emit)
+                if event.log.message == "large" then
+                    event.log.message = string.rep("x", 8388608)
+                end
+                emit(event)
+            end
+            """
+            "#,
+            events,
+            1,
+        )
+        .await;
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].as_log()["message"], "small".into());
+    }
+}
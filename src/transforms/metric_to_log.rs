@@ -18,7 +18,9 @@ use crate::{
     event::{self, Event, LogEvent, Metric},
     internal_events::MetricToLogSerializeError,
     schema,
-    transforms::{FunctionTransform, OutputBuffer, Transform},
+    transforms::{
+        log_to_metric::encode_non_finite_floats, FunctionTransform, OutputBuffer, Transform,
+    },
     types::Conversion,
 };
 
@@ -96,13 +98,13 @@ impl TransformConfig for MetricToLogConfig {
                 .with_event_field(&owned_value_path!("kind"), Kind::bytes(), None)
                 .with_event_field(
                     &owned_value_path!("counter"),
-                    Kind::object(Collection::empty().with_known("value", Kind::float()))
+                    Kind::object(Collection::empty().with_known("value", Kind::float().or_bytes()))
                         .or_undefined(),
                     None,
                 )
                 .with_event_field(
                     &owned_value_path!("gauge"),
-                    Kind::object(Collection::empty().with_known("value", Kind::float()))
+                    Kind::object(Collection::empty().with_known("value", Kind::float().or_bytes()))
                         .or_undefined(),
                     None,
                 )
@@ -124,7 +126,7 @@ impl TransformConfig for MetricToLogConfig {
                                 Kind::array(
                                     Collection::empty().with_unknown(Kind::object(
                                         Collection::empty()
-                                            .with_known("value", Kind::float())
+                                            .with_known("value", Kind::float().or_bytes())
                                             .with_known("rate", Kind::integer()),
                                     )),
                                 ),
@@ -143,13 +145,13 @@ impl TransformConfig for MetricToLogConfig {
                                 Kind::array(
                                     Collection::empty().with_unknown(Kind::object(
                                         Collection::empty()
-                                            .with_known("upper_limit", Kind::float())
+                                            .with_known("upper_limit", Kind::float().or_bytes())
                                             .with_known("count", Kind::integer()),
                                     )),
                                 ),
                             )
                             .with_known("count", Kind::integer())
-                            .with_known("sum", Kind::float()),
+                            .with_known("sum", Kind::float().or_bytes()),
                     )
                     .or_undefined(),
                     None,
@@ -163,13 +165,13 @@ impl TransformConfig for MetricToLogConfig {
                                 Kind::array(
                                     Collection::empty().with_unknown(Kind::object(
                                         Collection::empty()
-                                            .with_known("quantile", Kind::float())
-                                            .with_known("value", Kind::float()),
+                                            .with_known("quantile", Kind::float().or_bytes())
+                                            .with_known("value", Kind::float().or_bytes()),
                                     )),
                                 ),
                             )
                             .with_known("count", Kind::integer())
-                            .with_known("sum", Kind::float()),
+                            .with_known("sum", Kind::float().or_bytes()),
                     )
                     .or_undefined(),
                     None,
@@ -238,10 +240,15 @@ impl MetricToLog {
         }
     }
 
-    pub fn transform_one(&self, metric: Metric) -> Option<LogEvent> {
+    pub fn transform_one(&self, mut metric: Metric) -> Option<LogEvent> {
         serde_json::to_value(&metric)
             .map_err(|error| emit!(MetricToLogSerializeError { error }))
             .ok()
+            .map(|mut value| {
+                // JSON has no representation of non-finite floats, which are encoded as strings.
+                encode_non_finite_floats(&mut metric, &mut value);
+                value
+            })
             .and_then(|value| match value {
                 Value::Object(object) => {
                     let (_, _, metadata) = metric.into_parts();
@@ -568,4 +575,66 @@ mod tests {
         );
         assert_eq!(log.metadata(), &metadata);
     }
+
+    #[test]
+    fn round_trips_through_log_to_metric() {
+        use vector_core::{buckets, metrics::AgentDDSketch, quantiles, samples};
+
+        use crate::transforms::log_to_metric::{LogToMetric, LogToMetricConfig};
+
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        sketch.insert_many(&[1.0, 2.0, 4.0]);
+        let values = vec![
+            MetricValue::Counter { value: 1.5 },
+            MetricValue::Gauge {
+                value: f64::NEG_INFINITY,
+            },
+            MetricValue::Set {
+                values: vec!["inf".into(), "a".into()].into_iter().collect(),
+            },
+            MetricValue::Distribution {
+                samples: samples![1.0 => 2, f64::INFINITY => 1],
+                statistic: StatisticKind::Summary,
+            },
+            MetricValue::AggregatedHistogram {
+                buckets: buckets![1.0 => 1, 2.0 => 3, f64::INFINITY => 2],
+                count: 6,
+                sum: 12.5,
+            },
+            MetricValue::AggregatedSummary {
+                quantiles: quantiles![0.5 => 1.0, 0.99 => 3.0],
+                count: 6,
+                sum: 12.5,
+            },
+            sketch.into(),
+        ];
+
+        let mut log_to_metric = LogToMetric::new(LogToMetricConfig {
+            metrics: Vec::new(),
+            all_metrics: true,
+        });
+        for log_namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let metric_to_log = MetricToLog::new(
+                Some("host".into()),
+                vector_common::TimeZone::default(),
+                log_namespace,
+            );
+            for value in values.clone() {
+                let metric = Metric::new("metric", MetricKind::Absolute, value)
+                    .with_namespace(Some("namespace"))
+                    .with_tags(Some(tags()))
+                    .with_timestamp(Some(ts()))
+                    .with_interval_ms(std::num::NonZeroU32::new(10_000));
+
+                let log = metric_to_log.transform_one(metric.clone()).unwrap();
+                let mut buf = OutputBuffer::with_capacity(1);
+                log_to_metric.transform(&mut buf, log.into());
+                let events: Vec<_> = buf.into_events().collect();
+                assert_eq!(events.len(), 1);
+                let decoded = events[0].as_metric();
+                assert_eq!(decoded.series(), metric.series());
+                assert_eq!(decoded.data(), metric.data());
+            }
+        }
+    }
 }
@@ -8,8 +8,12 @@ use snafu::Snafu;
 pub mod aggregate;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 pub mod aws_ec2_metadata;
+#[cfg(feature = "transforms-counter_delta")]
+pub mod counter_delta;
 #[cfg(feature = "transforms-dedupe")]
 pub mod dedupe;
+#[cfg(feature = "transforms-explode")]
+pub mod explode;
 #[cfg(feature = "transforms-filter")]
 pub mod filter;
 pub mod log_to_metric;
@@ -17,20 +21,32 @@ pub mod log_to_metric;
 pub mod lua;
 #[cfg(feature = "transforms-metric_to_log")]
 pub mod metric_to_log;
+#[cfg(feature = "transforms-pii_redaction")]
+pub mod pii_redaction;
 #[cfg(feature = "transforms-pipelines")]
 pub mod pipelines;
 #[cfg(feature = "transforms-reduce")]
 pub mod reduce;
 #[cfg(feature = "transforms-remap")]
 pub mod remap;
+#[cfg(feature = "transforms-remote_lookup")]
+pub mod remote_lookup;
 #[cfg(feature = "transforms-route")]
 pub mod route;
 #[cfg(feature = "transforms-sample")]
 pub mod sample;
+#[cfg(feature = "transforms-schema_enforcement")]
+pub mod schema_enforcement;
+#[cfg(feature = "transforms-span_metrics")]
+pub mod span_metrics;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
+#[cfg(feature = "transforms-tail_sampling")]
+pub mod tail_sampling;
 #[cfg(feature = "transforms-throttle")]
 pub mod throttle;
+#[cfg(feature = "transforms-wasm")]
+pub mod wasm;
 
 use vector_common::config::ComponentKey;
 use vector_config::{configurable_component, NamedComponent};
@@ -82,10 +98,18 @@ pub enum Transforms {
     #[cfg(feature = "transforms-aws_ec2_metadata")]
     AwsEc2Metadata(#[configurable(derived)] aws_ec2_metadata::Ec2Metadata),
 
+    /// Counter delta.
+    #[cfg(feature = "transforms-counter_delta")]
+    CounterDelta(#[configurable(derived)] counter_delta::CounterDeltaConfig),
+
     /// Dedupe.
     #[cfg(feature = "transforms-dedupe")]
     Dedupe(#[configurable(derived)] dedupe::DedupeConfig),
 
+    /// Explode.
+    #[cfg(feature = "transforms-explode")]
+    Explode(#[configurable(derived)] explode::ExplodeConfig),
+
     /// Filter.
     #[cfg(feature = "transforms-filter")]
     Filter(#[configurable(derived)] filter::FilterConfig),
@@ -101,6 +125,10 @@ pub enum Transforms {
     #[cfg(feature = "transforms-metric_to_log")]
     MetricToLog(#[configurable(derived)] metric_to_log::MetricToLogConfig),
 
+    /// PII redaction.
+    #[cfg(feature = "transforms-pii_redaction")]
+    PiiRedaction(#[configurable(derived)] pii_redaction::PiiRedactionConfig),
+
     /// Pipelines. (inner)
     #[cfg(feature = "transforms-pipelines")]
     #[configurable(metadata(skip_docs))]
@@ -118,6 +146,10 @@ pub enum Transforms {
     #[cfg(feature = "transforms-remap")]
     Remap(#[configurable(derived)] remap::RemapConfig),
 
+    /// Remote lookup.
+    #[cfg(feature = "transforms-remote_lookup")]
+    RemoteLookup(#[configurable(derived)] remote_lookup::RemoteLookupConfig),
+
     /// Route.
     #[cfg(feature = "transforms-route")]
     Route(#[configurable(derived)] route::RouteConfig),
@@ -126,10 +158,22 @@ pub enum Transforms {
     #[cfg(feature = "transforms-sample")]
     Sample(#[configurable(derived)] sample::SampleConfig),
 
+    /// Schema enforcement.
+    #[cfg(feature = "transforms-schema_enforcement")]
+    SchemaEnforcement(#[configurable(derived)] schema_enforcement::SchemaEnforcementConfig),
+
+    /// Span metrics.
+    #[cfg(feature = "transforms-span_metrics")]
+    SpanMetrics(#[configurable(derived)] span_metrics::SpanMetricsConfig),
+
     /// Tag cardinality limit.
     #[cfg(feature = "transforms-tag_cardinality_limit")]
     TagCardinalityLimit(#[configurable(derived)] tag_cardinality_limit::TagCardinalityLimitConfig),
 
+    /// Tail sampling.
+    #[cfg(feature = "transforms-tail_sampling")]
+    TailSampling(#[configurable(derived)] tail_sampling::TailSamplingConfig),
+
     /// Test (basic).
     #[cfg(test)]
     TestBasic(#[configurable(derived)] crate::test_util::mock::transforms::BasicTransformConfig),
@@ -141,6 +185,10 @@ pub enum Transforms {
     /// Throttle.
     #[cfg(feature = "transforms-throttle")]
     Throttle(#[configurable(derived)] throttle::ThrottleConfig),
+
+    /// WebAssembly.
+    #[cfg(feature = "transforms-wasm")]
+    Wasm(#[configurable(derived)] wasm::WasmConfig),
 }
 
 // We can't use `enum_dispatch` here because it doesn't support associated constants.
@@ -153,8 +201,12 @@ impl NamedComponent for Transforms {
             Transforms::Aggregate(config) => config.get_component_name(),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
             Transforms::AwsEc2Metadata(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-counter_delta")]
+            Transforms::CounterDelta(config) => config.get_component_name(),
             #[cfg(feature = "transforms-dedupe")]
             Transforms::Dedupe(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-explode")]
+            Transforms::Explode(config) => config.get_component_name(),
             #[cfg(feature = "transforms-filter")]
             Transforms::Filter(config) => config.get_component_name(),
             Transforms::LogToMetric(config) => config.get_component_name(),
@@ -162,6 +214,8 @@ impl NamedComponent for Transforms {
             Transforms::Lua(config) => config.get_component_name(),
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-pii_redaction")]
+            Transforms::PiiRedaction(config) => config.get_component_name(),
             #[cfg(feature = "transforms-pipelines")]
             Transforms::Pipeline(config) => config.get_component_name(),
             #[cfg(feature = "transforms-pipelines")]
@@ -170,18 +224,28 @@ impl NamedComponent for Transforms {
             Transforms::Reduce(config) => config.get_component_name(),
             #[cfg(feature = "transforms-remap")]
             Transforms::Remap(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-remote_lookup")]
+            Transforms::RemoteLookup(config) => config.get_component_name(),
             #[cfg(feature = "transforms-route")]
             Transforms::Route(config) => config.get_component_name(),
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-schema_enforcement")]
+            Transforms::SchemaEnforcement(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-span_metrics")]
+            Transforms::SpanMetrics(config) => config.get_component_name(),
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-tail_sampling")]
+            Transforms::TailSampling(config) => config.get_component_name(),
             #[cfg(test)]
             Transforms::TestBasic(config) => config.get_component_name(),
             #[cfg(test)]
             Transforms::TestNoop(config) => config.get_component_name(),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-wasm")]
+            Transforms::Wasm(config) => config.get_component_name(),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 use std::{
     collections::{hash_map, HashMap},
+    num::NonZeroUsize,
     pin::Pin,
     time::{Duration, Instant},
 };
@@ -17,7 +18,7 @@ use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Input, Output, TransformConfig, TransformContext},
     event::{discriminant::Discriminant, Event, EventMetadata, LogEvent},
-    internal_events::ReduceStaleEventFlushed,
+    internal_events::{ReduceBufferedBytes, ReduceGroupFlushed, ReduceStaleEventFlushed},
     schema,
     transforms::{TaskTransform, Transform},
 };
@@ -28,7 +29,7 @@ use crate::event::Value;
 pub use merge_strategy::*;
 use value::kind::Collection;
 use value::Kind;
-use vector_core::config::LogNamespace;
+use vector_core::{config::LogNamespace, ByteSizeOf};
 
 /// Configuration for the `reduce` transform.
 #[serde_as]
@@ -91,6 +92,20 @@ pub struct ReduceConfig {
     /// If this condition resolves to `true` for an event, the previous transaction is flushed
     /// (without this event) and a new transaction is started.
     pub starts_when: Option<AnyCondition>,
+
+    /// Whether to flush the group of the previous event when an event of another group is received.
+    ///
+    /// Suits streams where the events of each group are consecutive, such as the lines of
+    /// multi-line messages, as at most one group is then reduced at a time.
+    #[serde(default)]
+    pub flush_on_group_change: bool,
+
+    /// The maximum size of the events of a group, in bytes, after which the group is flushed.
+    ///
+    /// Groups are flushed with the event that makes them reach this size, so that the memory held
+    /// by each group is bounded. Sizes are those of events in memory.
+    #[configurable(metadata(docs::examples = 1048576))]
+    pub max_bytes: Option<NonZeroUsize>,
 }
 
 const fn default_expire_after_ms() -> Duration {
@@ -206,6 +221,8 @@ struct ReduceState {
     fields: HashMap<String, Box<dyn ReduceValueMerger>>,
     stale_since: Instant,
     metadata: EventMetadata,
+    /// The size of the events reduced so far.
+    byte_size: usize,
 }
 
 impl ReduceState {
@@ -237,6 +254,7 @@ impl ReduceState {
             stale_since: Instant::now(),
             fields,
             metadata,
+            byte_size: 0,
         }
     }
 
@@ -296,6 +314,12 @@ pub struct Reduce {
     reduce_merge_states: HashMap<Discriminant, ReduceState>,
     ends_when: Option<Condition>,
     starts_when: Option<Condition>,
+    flush_on_group_change: bool,
+    max_bytes: Option<usize>,
+    /// The group of the previous event, when flushing on group changes.
+    last_discriminant: Option<Discriminant>,
+    /// The size of the events of all groups.
+    buffered_bytes: usize,
 }
 
 impl Reduce {
@@ -327,9 +351,20 @@ impl Reduce {
             reduce_merge_states: HashMap::new(),
             ends_when,
             starts_when,
+            flush_on_group_change: config.flush_on_group_change,
+            max_bytes: config.max_bytes.map(NonZeroUsize::get),
+            last_discriminant: None,
+            buffered_bytes: 0,
         })
     }
 
+    /// Removes the state of the group of `discriminant`, to flush it.
+    fn remove_state(&mut self, discriminant: &Discriminant) -> Option<ReduceState> {
+        let state = self.reduce_merge_states.remove(discriminant)?;
+        self.buffered_bytes -= state.byte_size;
+        Some(state)
+    }
+
     fn flush_into(&mut self, output: &mut Vec<Event>) {
         let mut flush_discriminants = Vec::new();
         for (k, t) in &self.reduce_merge_states {
@@ -338,26 +373,52 @@ impl Reduce {
             }
         }
         for k in &flush_discriminants {
-            if let Some(t) = self.reduce_merge_states.remove(k) {
+            if let Some(t) = self.remove_state(k) {
                 emit!(ReduceStaleEventFlushed);
                 output.push(Event::from(t.flush()));
             }
         }
+        emit!(ReduceBufferedBytes {
+            byte_size: self.buffered_bytes,
+        });
     }
 
     fn flush_all_into(&mut self, output: &mut Vec<Event>) {
         self.reduce_merge_states
             .drain()
             .for_each(|(_, s)| output.push(Event::from(s.flush())));
+        self.buffered_bytes = 0;
     }
 
-    fn push_or_new_reduce_state(&mut self, event: LogEvent, discriminant: Discriminant) {
-        match self.reduce_merge_states.entry(discriminant) {
+    fn push_or_new_reduce_state(
+        &mut self,
+        output: &mut Vec<Event>,
+        event: LogEvent,
+        discriminant: Discriminant,
+    ) {
+        let byte_size = event.size_of();
+        self.buffered_bytes += byte_size;
+        let state = match self.reduce_merge_states.entry(discriminant.clone()) {
             hash_map::Entry::Vacant(entry) => {
-                entry.insert(ReduceState::new(event, &self.merge_strategies));
+                entry.insert(ReduceState::new(event, &self.merge_strategies))
             }
-            hash_map::Entry::Occupied(mut entry) => {
-                entry.get_mut().add_event(event, &self.merge_strategies);
+            hash_map::Entry::Occupied(entry) => {
+                let state = entry.into_mut();
+                state.add_event(event, &self.merge_strategies);
+                state
+            }
+        };
+        state.byte_size += byte_size;
+
+        if self
+            .max_bytes
+            .map_or(false, |max_bytes| state.byte_size >= max_bytes)
+        {
+            if let Some(state) = self.remove_state(&discriminant) {
+                emit!(ReduceGroupFlushed {
+                    reason: "size_limit",
+                });
+                output.push(state.flush().into());
             }
         }
     }
@@ -376,14 +437,26 @@ impl Reduce {
         let event = event.into_log();
         let discriminant = Discriminant::from_log_event(&event, &self.group_by);
 
+        if self.flush_on_group_change {
+            let previous = self.last_discriminant.replace(discriminant.clone());
+            if let Some(previous) = previous.filter(|previous| *previous != discriminant) {
+                if let Some(state) = self.remove_state(&previous) {
+                    emit!(ReduceGroupFlushed {
+                        reason: "group_change",
+                    });
+                    output.push(state.flush().into());
+                }
+            }
+        }
+
         if starts_here {
-            if let Some(state) = self.reduce_merge_states.remove(&discriminant) {
+            if let Some(state) = self.remove_state(&discriminant) {
                 output.push(state.flush().into());
             }
 
-            self.push_or_new_reduce_state(event, discriminant)
+            self.push_or_new_reduce_state(output, event, discriminant)
         } else if ends_here {
-            output.push(match self.reduce_merge_states.remove(&discriminant) {
+            output.push(match self.remove_state(&discriminant) {
                 Some(mut state) => {
                     state.add_event(event, &self.merge_strategies);
                     state.flush().into()
@@ -393,7 +466,7 @@ impl Reduce {
                     .into(),
             })
         } else {
-            self.push_or_new_reduce_state(event, discriminant)
+            self.push_or_new_reduce_state(output, event, discriminant)
         }
 
         self.flush_into(output);
@@ -750,4 +823,63 @@ merge_strategies.bar = "concat"
         })
         .await;
     }
+
+    fn reduce(config: &str) -> Reduce {
+        let config = toml::from_str::<ReduceConfig>(config).unwrap();
+        Reduce::new(&config, &enrichment::TableRegistry::default()).unwrap()
+    }
+
+    fn event(message: &str, request_id: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert("request_id", request_id);
+        log.into()
+    }
+
+    #[test]
+    fn flushes_on_group_change() {
+        let mut reduce = reduce(
+            r#"
+group_by = [ "request_id" ]
+flush_on_group_change = true
+"#,
+        );
+
+        let mut output = Vec::new();
+        reduce.transform_one(&mut output, event("line 1", "1"));
+        reduce.transform_one(&mut output, event("line 2", "1"));
+        assert!(output.is_empty());
+
+        reduce.transform_one(&mut output, event("line 3", "2"));
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].as_log()["message"], "line 1".into());
+        assert_eq!(reduce.reduce_merge_states.len(), 1);
+
+        reduce.flush_all_into(&mut output);
+        assert_eq!(output.len(), 2);
+        assert_eq!(output[1].as_log()["message"], "line 3".into());
+        assert_eq!(reduce.buffered_bytes, 0);
+    }
+
+    #[test]
+    fn flushes_groups_reaching_max_bytes() {
+        let max_bytes = event("line 1", "1").into_log().size_of() * 2;
+        let mut reduce = reduce(&format!(
+            r#"
+group_by = [ "request_id" ]
+max_bytes = {}
+"#,
+            max_bytes
+        ));
+
+        let mut output = Vec::new();
+        reduce.transform_one(&mut output, event("line 1", "1"));
+        reduce.transform_one(&mut output, event("line 1", "2"));
+        assert!(output.is_empty());
+        assert!(reduce.buffered_bytes >= max_bytes);
+
+        reduce.transform_one(&mut output, event("line 2", "1"));
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].as_log()["request_id"], "1".into());
+        assert_eq!(reduce.reduce_merge_states.len(), 1);
+    }
 }
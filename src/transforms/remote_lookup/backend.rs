@@ -0,0 +1,246 @@
+use bytes::Bytes;
+use http::{header::HeaderName, HeaderValue, Method, Request, StatusCode, Uri};
+use hyper::Body;
+use indexmap::IndexMap;
+use redis::{aio::ConnectionManager, AsyncCommands};
+use vector_config::configurable_component;
+
+use crate::{
+    config::ProxyConfig,
+    emit,
+    event::{LogEvent, Value},
+    http::{build_authenticator, Auth, HttpAuthenticator, HttpClient},
+    internal_events::TemplateRenderingError,
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Where events are looked up.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LookupBackendConfig {
+    /// Events are looked up with HTTP requests.
+    Http(#[configurable(derived)] HttpBackendConfig),
+
+    /// Events are looked up in Redis strings.
+    Redis(#[configurable(derived)] RedisBackendConfig),
+}
+
+/// The HTTP method of lookup requests.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LookupMethod {
+    /// GET.
+    #[default]
+    Get,
+
+    /// POST.
+    Post,
+}
+
+/// Configuration of the HTTP backend.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HttpBackendConfig {
+    /// The template of the URI looked up for events.
+    ///
+    /// Values of events are inserted as is, so fields that may hold characters that aren't
+    /// allowed in URIs must be encoded beforehand, such as with the `encode_percent` function of
+    /// the `remap` transform.
+    #[configurable(metadata(
+        docs::examples = "https://users.example.com/api/users/{{ user_id }}"
+    ))]
+    pub uri: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub method: LookupMethod,
+
+    /// The template of the body of requests.
+    #[configurable(metadata(docs::examples = "{\"ip\": \"{{ client_ip }}\"}"))]
+    pub body: Option<Template>,
+
+    /// Headers sent with requests.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "An HTTP request header."))]
+    pub headers: IndexMap<String, String>,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+/// Configuration of the Redis backend.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RedisBackendConfig {
+    /// The URL of the Redis server.
+    #[configurable(metadata(docs::examples = "redis://127.0.0.1:6379/0"))]
+    pub url: String,
+
+    /// The template of the key looked up for events.
+    #[configurable(metadata(docs::examples = "users:{{ user_id }}"))]
+    pub key: Template,
+}
+
+/// The lookup rendered for an event.
+pub enum Rendered {
+    Http { uri: String, body: Option<String> },
+    Redis { key: String },
+}
+
+impl Rendered {
+    /// The key the result of the lookup is cached with.
+    pub fn cache_key(&self) -> String {
+        match self {
+            Self::Http { uri, body: None } => uri.clone(),
+            Self::Http {
+                uri,
+                body: Some(body),
+            } => format!("{uri}\n{body}"),
+            Self::Redis { key } => key.clone(),
+        }
+    }
+}
+
+fn render(template: &Template, log: &LogEvent, field: &'static str) -> Option<String> {
+    template
+        .render_string(log)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: false,
+            })
+        })
+        .ok()
+}
+
+/// Parses `bytes` as JSON, or keeps them as a string when they aren't JSON.
+fn parse_result(bytes: Bytes) -> Value {
+    match serde_json::from_slice::<serde_json::Value>(&bytes) {
+        Ok(json) => Value::from(json),
+        Err(_) => Value::Bytes(bytes),
+    }
+}
+
+#[derive(Clone)]
+pub enum LookupBackend {
+    Http {
+        client: HttpClient,
+        authenticator: Option<HttpAuthenticator>,
+        method: Method,
+        uri: Template,
+        body: Option<Template>,
+        headers: Vec<(HeaderName, HeaderValue)>,
+    },
+    Redis {
+        connection: ConnectionManager,
+        key: Template,
+    },
+}
+
+impl LookupBackend {
+    pub async fn new(config: &LookupBackendConfig) -> crate::Result<Self> {
+        Ok(match config {
+            LookupBackendConfig::Http(config) => {
+                let tls = TlsSettings::from_options(&config.tls)?;
+                let client = HttpClient::new(tls, &ProxyConfig::from_env())?;
+                let headers = config
+                    .headers
+                    .iter()
+                    .map(|(name, value)| {
+                        Ok((
+                            HeaderName::from_bytes(name.as_bytes())?,
+                            HeaderValue::from_str(value)?,
+                        ))
+                    })
+                    .collect::<crate::Result<_>>()?;
+                Self::Http {
+                    authenticator: build_authenticator(config.auth.as_ref(), &client).await?,
+                    client,
+                    method: match config.method {
+                        LookupMethod::Get => Method::GET,
+                        LookupMethod::Post => Method::POST,
+                    },
+                    uri: config.uri.clone(),
+                    body: config.body.clone(),
+                    headers,
+                }
+            }
+            LookupBackendConfig::Redis(config) => {
+                let client = redis::Client::open(config.url.as_str())?;
+                Self::Redis {
+                    connection: client.get_tokio_connection_manager().await?,
+                    key: config.key.clone(),
+                }
+            }
+        })
+    }
+
+    /// Renders the lookup of `log`, or returns `None` when its templates fail to render.
+    pub fn render(&self, log: &LogEvent) -> Option<Rendered> {
+        match self {
+            Self::Http { uri, body, .. } => Some(Rendered::Http {
+                uri: render(uri, log, "uri")?,
+                body: match body {
+                    Some(body) => Some(render(body, log, "body")?),
+                    None => None,
+                },
+            }),
+            Self::Redis { key, .. } => Some(Rendered::Redis {
+                key: render(key, log, "key")?,
+            }),
+        }
+    }
+
+    /// Looks up `rendered`, returning `None` when nothing is found: when requests are answered
+    /// with `404 Not Found`, or Redis keys don't exist.
+    pub async fn lookup(&self, rendered: Rendered) -> crate::Result<Option<Value>> {
+        match (self, rendered) {
+            (
+                Self::Http {
+                    client,
+                    authenticator,
+                    method,
+                    headers,
+                    ..
+                },
+                Rendered::Http { uri, body },
+            ) => {
+                let uri: Uri = uri.parse()?;
+                let mut request = Request::builder().method(method.clone()).uri(uri);
+                for (name, value) in headers {
+                    request = request.header(name, value);
+                }
+                let mut request = request.body(body.map_or_else(Body::empty, Body::from))?;
+                if let Some(authenticator) = authenticator {
+                    authenticator.apply(&mut request);
+                }
+
+                let response = client.send(request).await?;
+                let status = response.status();
+                if status == StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+                if !status.is_success() {
+                    return Err(format!("Lookup request failed with status {}.", status).into());
+                }
+                let body = hyper::body::to_bytes(response.into_body()).await?;
+                Ok(Some(parse_result(body)))
+            }
+            (Self::Redis { connection, .. }, Rendered::Redis { key }) => {
+                let mut connection = connection.clone();
+                let value: Option<Vec<u8>> = connection.get(key).await?;
+                Ok(value.map(|value| parse_result(Bytes::from(value))))
+            }
+            _ => unreachable!("lookups are rendered by their backend"),
+        }
+    }
+}
@@ -0,0 +1,412 @@
+//! The `remote_lookup` transform.
+//!
+//! Enriches events with the results of lookups of HTTP endpoints or Redis keys, rendered from
+//! templates, for data that changes too often to be kept in enrichment tables.
+
+mod backend;
+
+use std::{
+    num::NonZeroUsize,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures::{future, Stream, StreamExt};
+use lookup::lookup_v2::parse_value_path;
+use lru::LruCache;
+use serde_with::serde_as;
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+pub use self::backend::{HttpBackendConfig, LookupBackendConfig, LookupMethod, RedisBackendConfig};
+use self::backend::{LookupBackend, Rendered};
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    emit,
+    event::{Event, LogEvent, Value},
+    internal_events::{
+        RemoteLookupCacheHit, RemoteLookupCacheMiss, RemoteLookupEventDropped, RemoteLookupFailed,
+    },
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// What is done with events whose lookup fails or times out.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OnError {
+    /// Events are passed without the result, or with `fallback` when it's set.
+    #[default]
+    Pass,
+
+    /// Events are dropped.
+    Drop,
+}
+
+/// Configuration of the cache of the results of lookups.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CacheConfig {
+    /// How long results are cached for, in seconds.
+    ///
+    /// Lookups finding nothing are cached as well, while failed lookups aren't.
+    #[serde(default = "default_ttl_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<f64>")]
+    pub ttl_secs: Duration,
+
+    /// The maximum number of results cached, after which the least recently used ones are
+    /// evicted.
+    #[serde(default = "default_max_entries")]
+    pub max_entries: NonZeroUsize,
+}
+
+const fn default_ttl_secs() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_max_entries() -> NonZeroUsize {
+    NonZeroUsize::new(10_000).expect("static non-zero number")
+}
+
+/// Configuration for the `remote_lookup` transform.
+#[serde_as]
+#[configurable_component(transform("remote_lookup"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteLookupConfig {
+    #[configurable(derived)]
+    pub backend: LookupBackendConfig,
+
+    /// The field the results of lookups are inserted into.
+    ///
+    /// Results are parsed as JSON, and inserted as strings when they aren't JSON. Events whose
+    /// lookup finds nothing are passed without the field.
+    #[serde(default = "default_target_field")]
+    #[configurable(metadata(docs::examples = "user"))]
+    pub target_field: String,
+
+    /// The maximum number of lookups in flight at once.
+    ///
+    /// Events are emitted in the order they were received, so a slow lookup holds back those of
+    /// the events after it.
+    #[serde(default = "default_concurrency")]
+    pub concurrency: NonZeroUsize,
+
+    /// How long lookups are waited for before failing, in seconds.
+    #[serde(default = "default_timeout_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<f64>")]
+    pub timeout_secs: Duration,
+
+    #[configurable(derived)]
+    pub cache: Option<CacheConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_error: OnError,
+
+    /// The value inserted into `target_field` when lookups fail or time out, and `on_error` is
+    /// `pass`.
+    #[configurable(metadata(docs::examples = "unknown"))]
+    pub fallback: Option<String>,
+}
+
+fn default_target_field() -> String {
+    "lookup".to_owned()
+}
+
+fn default_concurrency() -> NonZeroUsize {
+    NonZeroUsize::new(10).expect("static non-zero number")
+}
+
+const fn default_timeout_secs() -> Duration {
+    Duration::from_secs(1)
+}
+
+impl GenerateConfig for RemoteLookupConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"backend.type = "http"
+            backend.uri = "https://users.example.com/api/users/{{ user_id }}"
+            target_field = "user"
+            cache.ttl_secs = 60"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for RemoteLookupConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if parse_value_path(&self.target_field).is_err() {
+            return Err(format!("Invalid `target_field` {:?}.", self.target_field).into());
+        }
+
+        Ok(Transform::event_task(RemoteLookup {
+            backend: LookupBackend::new(&self.backend).await?,
+            target_field: self.target_field.clone(),
+            concurrency: self.concurrency.get(),
+            timeout: self.timeout_secs,
+            cache: self.cache.as_ref().map(Cache::new),
+            on_error: self.on_error,
+            fallback: self.fallback.clone(),
+        }))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, merged_definition: &schema::Definition, _: LogNamespace) -> Vec<Output> {
+        let definition = match parse_value_path(&self.target_field) {
+            Ok(path) => merged_definition
+                .clone()
+                .optional_field(&path, Kind::json(), None),
+            Err(_) => merged_definition.clone(),
+        };
+        vec![Output::default(DataType::Log).with_schema_definition(definition)]
+    }
+}
+
+/// The results of lookups, by their rendered request, along with when they were looked up.
+#[derive(Clone)]
+struct Cache {
+    entries: Arc<Mutex<LruCache<String, (Instant, Option<Value>)>>>,
+    ttl: Duration,
+}
+
+impl Cache {
+    fn new(config: &CacheConfig) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(LruCache::new(config.max_entries))),
+            ttl: config.ttl_secs,
+        }
+    }
+
+    /// Gets the result cached for `key`, if it was looked up within the TTL.
+    fn get(&self, key: &str) -> Option<Option<Value>> {
+        let mut entries = self.entries.lock().expect("cache lock poisoned");
+        let expired = entries
+            .peek(key)
+            .map(|(looked_up, _)| looked_up.elapsed() >= self.ttl)?;
+        if expired {
+            entries.pop(key);
+            None
+        } else {
+            entries.get(key).map(|(_, result)| result.clone())
+        }
+    }
+
+    fn insert(&self, key: String, result: Option<Value>) {
+        let mut entries = self.entries.lock().expect("cache lock poisoned");
+        entries.put(key, (Instant::now(), result));
+    }
+}
+
+#[derive(Clone)]
+pub struct RemoteLookup {
+    backend: LookupBackend,
+    target_field: String,
+    concurrency: usize,
+    timeout: Duration,
+    cache: Option<Cache>,
+    on_error: OnError,
+    fallback: Option<String>,
+}
+
+impl RemoteLookup {
+    /// Looks up the result of `rendered`, from the cache when it's there.
+    async fn result(&self, rendered: Rendered) -> Result<Option<Value>, RemoteLookupFailed> {
+        let key = self.cache.as_ref().map(|_| rendered.cache_key());
+        if let (Some(cache), Some(key)) = (&self.cache, &key) {
+            if let Some(result) = cache.get(key) {
+                emit!(RemoteLookupCacheHit);
+                return Ok(result);
+            }
+            emit!(RemoteLookupCacheMiss);
+        }
+
+        let result = match tokio::time::timeout(self.timeout, self.backend.lookup(rendered)).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(error)) => return Err(RemoteLookupFailed::Error(error)),
+            Err(_) => return Err(RemoteLookupFailed::TimedOut),
+        };
+        if let (Some(cache), Some(key)) = (&self.cache, key) {
+            cache.insert(key, result.clone());
+        }
+        Ok(result)
+    }
+
+    async fn enrich(self: Arc<Self>, event: Event) -> Option<Event> {
+        let mut log = event.into_log();
+        // Events whose templates fail to render are handled as failed lookups, with the error
+        // of their rendering having been reported already.
+        let result = match self.backend.render(&log) {
+            Some(rendered) => self.result(rendered).await.map_err(|error| emit!(error)),
+            None => Err(()),
+        };
+        match result {
+            Ok(Some(value)) => {
+                log.insert(self.target_field.as_str(), value);
+            }
+            Ok(None) => {}
+            Err(()) => return self.fall_back(log),
+        }
+        Some(Event::from(log))
+    }
+
+    fn fall_back(&self, mut log: LogEvent) -> Option<Event> {
+        match self.on_error {
+            OnError::Drop => {
+                emit!(RemoteLookupEventDropped);
+                None
+            }
+            OnError::Pass => {
+                if let Some(fallback) = &self.fallback {
+                    log.insert(self.target_field.as_str(), fallback.clone());
+                }
+                Some(Event::from(log))
+            }
+        }
+    }
+}
+
+impl TaskTransform<Event> for RemoteLookup {
+    fn transform(
+        self: Box<Self>,
+        input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>> {
+        let concurrency = self.concurrency;
+        let lookup = Arc::new(*self);
+        Box::pin(
+            input_rx
+                .map(move |event| Arc::clone(&lookup).enrich(event))
+                .buffered(concurrency)
+                .filter_map(future::ready),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        net::SocketAddr,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body, Response, Server, StatusCode,
+    };
+
+    use super::*;
+    use crate::test_util::next_addr;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RemoteLookupConfig>();
+    }
+
+    /// Serves users by the path of requests, counting requests, and answering slowly for
+    /// `/slow`.
+    fn serve(addr: SocketAddr) -> Arc<AtomicUsize> {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        let make_service = make_service_fn(move |_| {
+            let counter = Arc::clone(&counter);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: hyper::Request<Body>| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    async move {
+                        let response = match request.uri().path() {
+                            "/users/1" => Response::new(Body::from(r#"{"name": "Jane"}"#)),
+                            "/slow" => {
+                                tokio::time::sleep(Duration::from_secs(5)).await;
+                                Response::new(Body::empty())
+                            }
+                            _ => {
+                                let mut response = Response::new(Body::empty());
+                                *response.status_mut() = StatusCode::NOT_FOUND;
+                                response
+                            }
+                        };
+                        Ok::<_, Infallible>(response)
+                    }
+                }))
+            }
+        });
+        tokio::spawn(Server::bind(&addr).serve(make_service));
+        requests
+    }
+
+    async fn enrich(config: &str, user_ids: &[&str]) -> Vec<Event> {
+        let config: RemoteLookupConfig = toml::from_str(config).unwrap();
+        let transform = config
+            .build(&TransformContext::default())
+            .await
+            .unwrap()
+            .into_task();
+        let events = user_ids.iter().map(|user_id| {
+            let mut log = LogEvent::from("login");
+            log.insert("user_id", *user_id);
+            Event::from(log)
+        });
+        transform
+            .transform_events(Box::pin(futures::stream::iter(events.collect::<Vec<_>>())))
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn enriches_events_with_cached_results() {
+        let addr = next_addr();
+        let requests = serve(addr);
+        let config = format!(
+            r#"backend.type = "http"
+            backend.uri = "http://{addr}/users/{{{{ user_id }}}}"
+            target_field = "user"
+            concurrency = 1
+            cache.ttl_secs = 60"#
+        );
+
+        let events = enrich(&config, &["1", "2", "1"]).await;
+        let users: Vec<_> = events
+            .into_iter()
+            .map(|event| event.into_log().remove("user"))
+            .collect();
+        assert_eq!(
+            users,
+            vec![
+                Some(Value::from(serde_json::json!({"name": "Jane"}))),
+                None,
+                Some(Value::from(serde_json::json!({"name": "Jane"}))),
+            ]
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn falls_back_on_timeouts() {
+        let addr = next_addr();
+        serve(addr);
+        let config = format!(
+            r#"backend.type = "http"
+            backend.uri = "http://{addr}/{{{{ user_id }}}}"
+            timeout_secs = 0.1
+            fallback = "unknown""#
+        );
+        let events = enrich(&config, &["slow"]).await;
+        assert_eq!(
+            events[0].as_log().get("lookup"),
+            Some(&Value::from("unknown"))
+        );
+
+        let events = enrich(&format!("{config}\non_error = \"drop\""), &["slow"]).await;
+        assert!(events.is_empty());
+    }
+}
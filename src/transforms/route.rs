@@ -1,3 +1,6 @@
+use std::{collections::HashSet, num::NonZeroU64};
+
+use enrichment::{Case, Condition as TableCondition, IndexHandle, TableRegistry, TableSearch};
 use indexmap::IndexMap;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
@@ -6,16 +9,128 @@ use vector_core::transform::SyncTransform;
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
-    event::Event,
+    event::{Event, Value},
     schema,
     transforms::Transform,
 };
 
 pub(crate) const UNMATCHED_ROUTE: &str = "_unmatched";
 
+/// The outputs events are split across, each with the sum of its weight and those of the outputs
+/// before it.
+#[derive(Clone)]
+struct Split {
+    outputs: Vec<(String, u64)>,
+    total_weight: u64,
+    key_field: Option<String>,
+}
+
+impl Split {
+    fn new(config: &SplitConfig) -> Self {
+        let mut total_weight = 0;
+        let outputs = config
+            .weights
+            .iter()
+            .map(|(output_name, weight)| {
+                total_weight += weight.get();
+                (output_name.clone(), total_weight)
+            })
+            .collect();
+        Self {
+            outputs,
+            total_weight,
+            key_field: config.key_field.clone(),
+        }
+    }
+
+    /// Picks the output of `event`, by the hash of its key when it has one, so that events with
+    /// the same key are always sent to the same output, and at random otherwise.
+    fn output_of(&self, event: &Event) -> &str {
+        let key = self
+            .key_field
+            .as_deref()
+            .and_then(|key_field| field_of(event, key_field));
+        let point = match key {
+            Some(key) => seahash::hash(key.to_string_lossy().as_bytes()),
+            None => rand::random::<u64>(),
+        } % self.total_weight;
+        let index = self
+            .outputs
+            .partition_point(|(_, cumulative_weight)| *cumulative_weight <= point);
+        &self.outputs[index].0
+    }
+}
+
+/// Gets the value of the field of logs and traces, or of the tag of metrics, named `field`.
+fn field_of(event: &Event, field: &str) -> Option<Value> {
+    match event {
+        Event::Log(log) => log.get(field).cloned(),
+        Event::Metric(metric) => metric.tag_value(field).map(Value::from),
+        Event::Trace(trace) => trace.get(field).cloned(),
+    }
+}
+
+/// An enrichment table holding the outputs events are routed to.
+#[derive(Clone)]
+struct RouteTable {
+    tables: TableSearch,
+    index: IndexHandle,
+    table: String,
+    event_field: String,
+    key_field: String,
+    output_field: String,
+    outputs: HashSet<String>,
+}
+
+impl RouteTable {
+    fn new(config: &RouteTableConfig, enrichment_tables: &TableRegistry) -> crate::Result<Self> {
+        if !enrichment_tables.table_ids().contains(&config.table) {
+            return Err(format!("Enrichment table {:?} does not exist.", config.table).into());
+        }
+        let index = enrichment_tables.clone().add_index(
+            &config.table,
+            Case::Sensitive,
+            &[config.key_field.as_str()],
+        )?;
+
+        Ok(Self {
+            tables: enrichment_tables.as_readonly(),
+            index,
+            table: config.table.clone(),
+            event_field: config.event_field.clone(),
+            key_field: config.key_field.clone(),
+            output_field: config.output_field.clone(),
+            outputs: config.outputs.iter().cloned().collect(),
+        })
+    }
+
+    /// Looks up the output of `event`, which must be one of the declared outputs.
+    fn output_of(&self, event: &Event) -> Option<String> {
+        let key = field_of(event, &self.event_field)?;
+        let condition = [TableCondition::Equals {
+            field: &self.key_field,
+            value: key,
+        }];
+        let row = self
+            .tables
+            .find_table_row(
+                &self.table,
+                Case::Sensitive,
+                &condition,
+                Some(std::slice::from_ref(&self.output_field)),
+                Some(self.index),
+            )
+            .ok()?;
+        let output = row.get(&self.output_field)?.to_string_lossy().into_owned();
+        self.outputs.contains(output.as_str()).then(|| output)
+    }
+}
+
 #[derive(Clone)]
 pub struct Route {
     conditions: Vec<(String, Condition)>,
+    split: Option<Split>,
+    route_table: Option<RouteTable>,
 }
 
 impl Route {
@@ -25,7 +140,15 @@ impl Route {
             let condition = condition.build(&context.enrichment_tables)?;
             conditions.push((output_name.clone(), condition));
         }
-        Ok(Self { conditions })
+        Ok(Self {
+            conditions,
+            split: config.split.as_ref().map(Split::new),
+            route_table: config
+                .route_table
+                .as_ref()
+                .map(|route_table| RouteTable::new(route_table, &context.enrichment_tables))
+                .transpose()?,
+        })
     }
 }
 
@@ -35,6 +158,11 @@ impl SyncTransform for Route {
         event: Event,
         output: &mut vector_core::transform::TransformOutputsBuf,
     ) {
+        if let Some(split) = &self.split {
+            output.push_named(split.output_of(&event), event);
+            return;
+        }
+
         let mut check_failed: usize = 0;
         for (output_name, condition) in &self.conditions {
             let (result, event) = condition.check(event.clone());
@@ -44,12 +172,83 @@ impl SyncTransform for Route {
                 check_failed += 1;
             }
         }
-        if check_failed == self.conditions.len() {
-            output.push_named(UNMATCHED_ROUTE, event);
+        let table_output = self
+            .route_table
+            .as_ref()
+            .and_then(|route_table| route_table.output_of(&event));
+        match table_output {
+            Some(output_name) => output.push_named(&output_name, event),
+            None if check_failed == self.conditions.len() => {
+                output.push_named(UNMATCHED_ROUTE, event)
+            }
+            None => {}
         }
     }
 }
 
+/// Configuration for splitting events across outputs by weight.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SplitConfig {
+    /// A table of output names to their weights.
+    ///
+    /// Each event is sent to a single output, each output receiving its weight's share of the sum
+    /// of all weights. For example, weights of `5` and `95` send 5% of events to the first output.
+    #[configurable(metadata(docs::additional_props_description = "The weight of an output."))]
+    pub weights: IndexMap<String, NonZeroU64>,
+
+    /// The name of the field events are assigned to outputs by.
+    ///
+    /// Events with the same value of this field are always sent to the same output. Events without
+    /// it, or all events when this is not set, are assigned at random.
+    #[configurable(metadata(docs::examples = "user_id"))]
+    pub key_field: Option<String>,
+}
+
+/// Configuration for looking up the outputs of events in an enrichment table.
+///
+/// As the table can be reloaded without reloading the configuration, events of new keys, such as
+/// new tenants, can be routed by adding rows to the table.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RouteTableConfig {
+    /// The name of the enrichment table.
+    #[configurable(metadata(docs::examples = "tenant_routes"))]
+    pub table: String,
+
+    /// The name of the field whose value is looked up in the table.
+    ///
+    /// For metrics, this is the name of a tag.
+    #[configurable(metadata(docs::examples = "tenant"))]
+    pub event_field: String,
+
+    /// The field of the table matched against the value of `event_field`.
+    #[serde(default = "default_table_key_field")]
+    pub key_field: String,
+
+    /// The field of the table holding the names of outputs.
+    #[serde(default = "default_table_output_field")]
+    pub output_field: String,
+
+    /// The outputs events can be routed to by the table.
+    ///
+    /// Outputs are referenced by other components, so they can't be added by the table. Events
+    /// whose output isn't found in the table, or isn't one of these, are handled as events matching
+    /// no route.
+    #[configurable(metadata(docs::examples = "tenant_a", docs::examples = "tenant_b"))]
+    pub outputs: Vec<String>,
+}
+
+fn default_table_key_field() -> String {
+    "key".to_string()
+}
+
+fn default_table_output_field() -> String {
+    "output".to_string()
+}
+
 /// Configuration for the `route` transform.
 #[configurable_component(transform("route"))]
 #[derive(Clone, Debug)]
@@ -63,13 +262,32 @@ pub struct RouteConfig {
     ///
     /// Both `_unmatched`, as well as `_default`, are reserved output names and cannot be used as a
     /// route name.
+    #[serde(default)]
     route: IndexMap<String, AnyCondition>,
+
+    /// Splits events across outputs by weight, rather than routing them by conditions.
+    ///
+    /// Each output can then be referenced as an input by other components with the name
+    /// `<transform_name>.<output_name>`, such as to send a share of events to a new sink.
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    split: Option<SplitConfig>,
+
+    /// Routes events to outputs looked up in an enrichment table, in addition to the routes of
+    /// `route`.
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    route_table: Option<RouteTableConfig>,
 }
 
 #[cfg(feature = "transforms-pipelines")]
 impl RouteConfig {
     pub(crate) const fn new(route: IndexMap<String, AnyCondition>) -> Self {
-        Self { route }
+        Self {
+            route,
+            split: None,
+            route_table: None,
+        }
     }
 }
 
@@ -77,11 +295,26 @@ impl GenerateConfig for RouteConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             route: IndexMap::new(),
+            split: None,
+            route_table: None,
         })
         .unwrap()
     }
 }
 
+impl RouteConfig {
+    fn output_names(&self) -> impl Iterator<Item = &String> {
+        self.route
+            .keys()
+            .chain(self.split.iter().flat_map(|split| split.weights.keys()))
+            .chain(
+                self.route_table
+                    .iter()
+                    .flat_map(|route_table| route_table.outputs.iter()),
+            )
+    }
+}
+
 #[async_trait::async_trait]
 impl TransformConfig for RouteConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
@@ -94,19 +327,39 @@ impl TransformConfig for RouteConfig {
     }
 
     fn validate(&self, _: &schema::Definition) -> Result<(), Vec<String>> {
-        if self.route.contains_key(UNMATCHED_ROUTE) {
-            Err(vec![format!(
+        let mut errors = Vec::new();
+        if self.output_names().any(|name| name == UNMATCHED_ROUTE) {
+            errors.push(format!(
                 "cannot have a named output with reserved name: `{UNMATCHED_ROUTE}`"
-            )])
-        } else {
+            ));
+        }
+        let mut output_names = HashSet::new();
+        for name in self.output_names() {
+            if !output_names.insert(name) {
+                errors.push(format!("output `{name}` is declared more than once"));
+            }
+        }
+        if let Some(split) = &self.split {
+            if !self.route.is_empty() || self.route_table.is_some() {
+                errors.push(
+                    "only one of `split` and `route` or `route_table` can be provided".to_owned(),
+                );
+            }
+            if split.weights.is_empty() {
+                errors.push("`split.weights` must have at least one output".to_owned());
+            }
+        }
+
+        if errors.is_empty() {
             Ok(())
+        } else {
+            Err(errors)
         }
     }
 
     fn outputs(&self, merged_definition: &schema::Definition, _: LogNamespace) -> Vec<Output> {
         let mut result: Vec<Output> = self
-            .route
-            .keys()
+            .output_names()
             .map(|output_name| {
                 Output::default(DataType::all())
                     .with_schema_definition(merged_definition.clone())
@@ -128,6 +381,9 @@ impl TransformConfig for RouteConfig {
 
 #[cfg(test)]
 mod test {
+    use std::collections::{BTreeMap, HashMap};
+
+    use enrichment::Table;
     use indoc::indoc;
     use vector_core::transform::TransformOutputsBuf;
 
@@ -284,6 +540,186 @@ mod test {
         }
     }
 
+    #[test]
+    fn split_by_weights() {
+        let output_names = vec!["canary", "stable", UNMATCHED_ROUTE];
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            split.weights.canary = 1
+            split.weights.stable = 3
+            split.key_field = "user_id"
+        "#,
+        )
+        .unwrap();
+        assert!(config.validate(&schema::Definition::any()).is_ok());
+
+        let mut transform = Route::new(&config, &Default::default()).unwrap();
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            output_names
+                .iter()
+                .map(|output_name| {
+                    Output::default(DataType::all()).with_port(output_name.to_owned())
+                })
+                .collect(),
+            1,
+        );
+
+        for user_id in 0..1000 {
+            for _ in 0..2 {
+                let event =
+                    Event::try_from(serde_json::json!({ "user_id": user_id.to_string() })).unwrap();
+                transform.transform(event, &mut outputs);
+            }
+        }
+        let canary: Vec<_> = outputs.drain_named("canary").collect();
+        let stable: Vec<_> = outputs.drain_named("stable").collect();
+        assert_eq!(canary.len() + stable.len(), 2000);
+        assert_eq!(outputs.drain_named(UNMATCHED_ROUTE).count(), 0);
+        assert!((400..600).contains(&canary.len()), "{}", canary.len());
+
+        // Both events of each user are sent to the same output.
+        for events in [canary, stable] {
+            for pair in events.chunks(2) {
+                assert_eq!(pair[0], pair[1]);
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct RoutesTable(HashMap<String, String>);
+
+    impl Table for RoutesTable {
+        fn find_table_row<'a>(
+            &self,
+            _case: Case,
+            condition: &'a [TableCondition<'a>],
+            select: Option<&[String]>,
+            _index: Option<IndexHandle>,
+        ) -> Result<BTreeMap<String, Value>, String> {
+            assert_eq!(select, Some(&["output".to_string()][..]));
+            match condition {
+                [TableCondition::Equals {
+                    field: "key",
+                    value,
+                }] => self
+                    .0
+                    .get(value.to_string_lossy().as_ref())
+                    .map(|output| {
+                        BTreeMap::from([("output".to_string(), Value::from(output.as_str()))])
+                    })
+                    .ok_or_else(|| "no rows found".to_string()),
+                _ => Err("unexpected condition".to_string()),
+            }
+        }
+
+        fn find_table_rows<'a>(
+            &self,
+            case: Case,
+            condition: &'a [TableCondition<'a>],
+            select: Option<&[String]>,
+            index: Option<IndexHandle>,
+        ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+            self.find_table_row(case, condition, select, index)
+                .map(|row| vec![row])
+        }
+
+        fn add_index(&mut self, _case: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+            assert_eq!(fields, ["key"]);
+            Ok(IndexHandle(0))
+        }
+
+        fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+            Vec::new()
+        }
+
+        fn needs_reload(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn route_by_table() {
+        let output_names = vec!["errors", "tenant_a", "tenant_b", UNMATCHED_ROUTE];
+        let enrichment_tables = TableRegistry::default();
+        let mut tables = HashMap::<String, Box<dyn Table + Send + Sync>>::new();
+        tables.insert(
+            "tenant_routes".to_string(),
+            Box::new(RoutesTable(HashMap::from([
+                ("a".to_string(), "tenant_a".to_string()),
+                ("c".to_string(), "tenant_c".to_string()),
+            ]))),
+        );
+        enrichment_tables.load(tables);
+
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.errors.type = "vrl"
+            route.errors.source = '.level == "error"'
+
+            route_table.table = "tenant_routes"
+            route_table.event_field = "tenant"
+            route_table.outputs = ["tenant_a", "tenant_b"]
+        "#,
+        )
+        .unwrap();
+        assert!(config.validate(&schema::Definition::any()).is_ok());
+
+        let context = TransformContext {
+            enrichment_tables: enrichment_tables.clone(),
+            ..Default::default()
+        };
+        let mut transform = Route::new(&config, &context).unwrap();
+        enrichment_tables.finish_load();
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            output_names
+                .iter()
+                .map(|output_name| {
+                    Output::default(DataType::all()).with_port(output_name.to_owned())
+                })
+                .collect(),
+            1,
+        );
+
+        let mut route_of = |tenant: &str, level: &str| {
+            let event =
+                Event::try_from(serde_json::json!({ "tenant": tenant, "level": level })).unwrap();
+            transform.transform(event, &mut outputs);
+            let routed: Vec<_> = output_names
+                .iter()
+                .filter(|output_name| outputs.drain_named(output_name).count() == 1)
+                .copied()
+                .collect();
+            routed
+        };
+        assert_eq!(route_of("a", "info"), vec!["tenant_a"]);
+        assert_eq!(route_of("a", "error"), vec!["errors", "tenant_a"]);
+        // Outputs that aren't declared, and keys without rows, aren't routed to.
+        assert_eq!(route_of("b", "info"), vec![UNMATCHED_ROUTE]);
+        assert_eq!(route_of("c", "info"), vec![UNMATCHED_ROUTE]);
+        assert_eq!(route_of("c", "error"), vec!["errors"]);
+    }
+
+    #[test]
+    fn split_excludes_route() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.first.type = "vrl"
+            route.first.source = "true"
+            split.weights._unmatched = 1
+        "#,
+        )
+        .unwrap();
+
+        let errors = config.validate(&schema::Definition::any()).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                "cannot have a named output with reserved name: `_unmatched`",
+                "only one of `split` and `route` or `route_table` can be provided",
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn route_metrics_with_output_tag() {
         init_test();
@@ -1,10 +1,11 @@
+use enrichment::{Case, Condition as TableCondition, IndexHandle, TableRegistry, TableSearch};
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
-    event::Event,
+    event::{Event, Value},
     internal_events::SampleEventDiscarded,
     schema,
     transforms::{FunctionTransform, OutputBuffer, Transform},
@@ -24,21 +25,67 @@ pub struct SampleConfig {
     /// The name of the log field whose value will be hashed to determine if the event should be
     /// passed.
     ///
-    /// Consistently samples the same events. Actual rate of sampling may differ from the configured
-    /// one if values in the field are not uniformly distributed. If left unspecified, or if the
-    /// event doesn’t have `key_field`, events will be count rated.
-    #[configurable(metadata(docs::examples = "message",))]
+    /// Consistently samples the same events, so that all events with the same value, such as those
+    /// of a trace or a session, are either passed or dropped together. Events passed at a rate are
+    /// also passed at all lower rates. Actual rate of sampling may differ from the configured one
+    /// if values in the field are not uniformly distributed. If left unspecified, or if the event
+    /// doesn’t have `key_field`, events will be count rated.
+    #[configurable(metadata(docs::examples = "message", docs::examples = "trace_id"))]
     pub key_field: Option<String>,
 
+    /// The name of the log field holding the rate events are sampled at.
+    ///
+    /// Events without the field, or whose value isn't a positive integer, are sampled at the rate
+    /// of `rate_table`, or at `rate`.
+    #[configurable(metadata(docs::examples = "sample_rate"))]
+    pub rate_field: Option<String>,
+
+    #[configurable(derived)]
+    pub rate_table: Option<RateTableConfig>,
+
     /// A logical condition used to exclude events from sampling.
     pub exclude: Option<AnyCondition>,
 }
 
+/// Configuration for looking up the rates events are sampled at in an enrichment table.
+///
+/// Events whose rate isn't found in the table are sampled at `rate`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RateTableConfig {
+    /// The name of the enrichment table.
+    #[configurable(metadata(docs::examples = "sample_rates"))]
+    pub table: String,
+
+    /// The name of the log field whose value is looked up in the table.
+    #[configurable(metadata(docs::examples = "service"))]
+    pub event_field: String,
+
+    /// The field of the table matched against the value of `event_field`.
+    #[serde(default = "default_table_key_field")]
+    pub key_field: String,
+
+    /// The field of the table holding the rates.
+    #[serde(default = "default_table_rate_field")]
+    pub rate_field: String,
+}
+
+fn default_table_key_field() -> String {
+    "key".to_string()
+}
+
+fn default_table_rate_field() -> String {
+    "rate".to_string()
+}
+
 impl GenerateConfig for SampleConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             rate: 10,
             key_field: None,
+            rate_field: None,
+            rate_table: None,
             exclude: None::<AnyCondition>,
         })
         .unwrap()
@@ -48,14 +95,21 @@ impl GenerateConfig for SampleConfig {
 #[async_trait::async_trait]
 impl TransformConfig for SampleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Sample::new(
+        let mut sample = Sample::new(
             self.rate,
             self.key_field.clone(),
             self.exclude
                 .as_ref()
                 .map(|condition| condition.build(&context.enrichment_tables))
                 .transpose()?,
-        )))
+        );
+        sample.rate_field = self.rate_field.clone();
+        sample.rate_table = self
+            .rate_table
+            .as_ref()
+            .map(|config| RateTable::new(config, &context.enrichment_tables))
+            .transpose()?;
+        Ok(Transform::function(sample))
     }
 
     fn input(&self) -> Input {
@@ -68,10 +122,82 @@ impl TransformConfig for SampleConfig {
     }
 }
 
+/// An enrichment table holding the rates events are sampled at.
+#[derive(Clone)]
+struct RateTable {
+    tables: TableSearch,
+    index: IndexHandle,
+    table: String,
+    event_field: String,
+    key_field: String,
+    rate_field: String,
+}
+
+impl RateTable {
+    fn new(config: &RateTableConfig, enrichment_tables: &TableRegistry) -> crate::Result<Self> {
+        if !enrichment_tables.table_ids().contains(&config.table) {
+            return Err(format!("Enrichment table {:?} does not exist.", config.table).into());
+        }
+        let index = enrichment_tables.clone().add_index(
+            &config.table,
+            Case::Sensitive,
+            &[config.key_field.as_str()],
+        )?;
+
+        Ok(Self {
+            tables: enrichment_tables.as_readonly(),
+            index,
+            table: config.table.clone(),
+            event_field: config.event_field.clone(),
+            key_field: config.key_field.clone(),
+            rate_field: config.rate_field.clone(),
+        })
+    }
+
+    fn rate(&self, event: &Event) -> Option<u64> {
+        let key = event_field(event, &self.event_field)?;
+        let condition = [TableCondition::Equals {
+            field: &self.key_field,
+            value: key.clone(),
+        }];
+        let row = self
+            .tables
+            .find_table_row(
+                &self.table,
+                Case::Sensitive,
+                &condition,
+                Some(std::slice::from_ref(&self.rate_field)),
+                Some(self.index),
+            )
+            .ok()?;
+        row.get(&self.rate_field).and_then(parse_rate)
+    }
+}
+
+fn event_field<'a>(event: &'a Event, field: &str) -> Option<&'a Value> {
+    match event {
+        Event::Log(event) => event.get(field),
+        Event::Trace(event) => event.get(field),
+        Event::Metric(_) => panic!("component can never receive metric events"),
+    }
+}
+
+/// Parses `value` as a rate, which must be a positive integer, or a string of one.
+fn parse_rate(value: &Value) -> Option<u64> {
+    let rate = match value {
+        Value::Integer(rate) => u64::try_from(*rate).ok()?,
+        Value::Bytes(rate) => std::str::from_utf8(rate).ok()?.trim().parse().ok()?,
+        _ => return None,
+    };
+    (rate > 0).then(|| rate)
+}
+
 #[derive(Clone)]
 pub struct Sample {
     rate: u64,
     key_field: Option<String>,
+    rate_field: Option<String>,
+    rate_table: Option<RateTable>,
     exclude: Option<Condition>,
     count: u64,
 }
@@ -81,10 +207,27 @@ impl Sample {
         Self {
             rate,
             key_field,
+            rate_field: None,
+            rate_table: None,
             exclude,
             count: 0,
         }
     }
+
+    /// The rate `event` is sampled at, from its `rate_field`, then from the rate table, and
+    /// finally from the configured rate.
+    fn rate(&self, event: &Event) -> u64 {
+        self.rate_field
+            .as_ref()
+            .and_then(|rate_field| event_field(event, rate_field))
+            .and_then(parse_rate)
+            .or_else(|| {
+                self.rate_table
+                    .as_ref()
+                    .and_then(|rate_table| rate_table.rate(event))
+            })
+            .unwrap_or(self.rate)
+    }
 }
 
 impl FunctionTransform for Sample {
@@ -103,28 +246,27 @@ impl FunctionTransform for Sample {
             }
         };
 
+        let rate = self.rate(&event);
         let value = self
             .key_field
             .as_ref()
-            .and_then(|key_field| match &event {
-                Event::Log(event) => event.get(key_field.as_str()),
-                Event::Trace(event) => event.get(key_field.as_str()),
-                Event::Metric(_) => panic!("component can never receive metric events"),
-            })
+            .and_then(|key_field| event_field(&event, key_field))
             .map(|v| v.to_string_lossy());
 
-        let num = if let Some(value) = value {
-            seahash::hash(value.as_bytes())
+        let passes = if let Some(value) = value {
+            // Comparing hashes to a threshold, rather than taking their remainder, passes the
+            // events of a key at all rates lower than the highest one it's passed at.
+            seahash::hash(value.as_bytes()) <= u64::MAX / rate
         } else {
-            self.count
+            self.count % rate == 0
         };
 
-        self.count = (self.count + 1) % self.rate;
+        self.count = self.count.wrapping_add(1);
 
-        if num % self.rate == 0 {
+        if passes {
             match event {
-                Event::Log(ref mut event) => event.insert("sample_rate", self.rate.to_string()),
-                Event::Trace(ref mut event) => event.insert("sample_rate", self.rate.to_string()),
+                Event::Log(ref mut event) => event.insert("sample_rate", rate.to_string()),
+                Event::Trace(ref mut event) => event.insert("sample_rate", rate.to_string()),
                 Event::Metric(_) => panic!("component can never receive metric events"),
             };
             output.push(event);
@@ -136,7 +278,10 @@ impl FunctionTransform for Sample {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
     use approx::assert_relative_eq;
+    use enrichment::Table;
 
     use super::*;
     use crate::{
@@ -339,12 +484,136 @@ mod tests {
         assert_eq!(total_passed, 1);
     }
 
+    #[test]
+    fn hash_samples_the_same_keys_at_lower_rates() {
+        let events = random_events(1000);
+        let passed_at = |rate| {
+            let mut sampler = Sample::new(rate, Some(log_schema().message_key().into()), None);
+            events
+                .iter()
+                .filter(|event| transform_one(&mut sampler, (*event).clone()).is_some())
+                .map(|event| event.as_log()[log_schema().message_key()].clone())
+                .collect::<Vec<_>>()
+        };
+
+        let passed_at_2 = passed_at(2);
+        let passed_at_4 = passed_at(4);
+        assert!(passed_at_4.len() < passed_at_2.len());
+        assert!(passed_at_4.iter().all(|value| passed_at_2.contains(value)));
+    }
+
+    #[test]
+    fn samples_at_the_rate_of_rate_field() {
+        let mut sampler = Sample::new(1, None, None);
+        sampler.rate_field = Some("rate".into());
+
+        let events = (0..10).map(|_| {
+            let mut log = LogEvent::from("message");
+            log.insert("rate", 5);
+            Event::Log(log)
+        });
+        let passed = events
+            .filter_map(|event| transform_one(&mut sampler, event))
+            .collect::<Vec<_>>();
+        assert_eq!(passed.len(), 2);
+        assert_eq!(passed[0].as_log()["sample_rate"], "5".into());
+
+        // Events without a valid rate are sampled at the configured one.
+        let mut log = LogEvent::from("message");
+        log.insert("rate", "zero");
+        let passed = transform_one(&mut sampler, Event::Log(log)).unwrap();
+        assert_eq!(passed.as_log()["sample_rate"], "1".into());
+    }
+
+    /// An enrichment table of the rates of services.
+    #[derive(Clone)]
+    struct RatesTable(HashMap<String, i64>);
+
+    impl Table for RatesTable {
+        fn find_table_row<'a>(
+            &self,
+            _case: Case,
+            condition: &'a [TableCondition<'a>],
+            select: Option<&[String]>,
+            _index: Option<IndexHandle>,
+        ) -> Result<BTreeMap<String, Value>, String> {
+            assert_eq!(select, Some(&["rate".to_string()][..]));
+            match condition {
+                [TableCondition::Equals {
+                    field: "key",
+                    value,
+                }] => self
+                    .0
+                    .get(value.to_string_lossy().as_ref())
+                    .map(|rate| BTreeMap::from([("rate".to_string(), Value::from(*rate))]))
+                    .ok_or_else(|| "no rows found".to_string()),
+                _ => Err("unexpected condition".to_string()),
+            }
+        }
+
+        fn find_table_rows<'a>(
+            &self,
+            case: Case,
+            condition: &'a [TableCondition<'a>],
+            select: Option<&[String]>,
+            index: Option<IndexHandle>,
+        ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+            self.find_table_row(case, condition, select, index)
+                .map(|row| vec![row])
+        }
+
+        fn add_index(&mut self, _case: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+            assert_eq!(fields, ["key"]);
+            Ok(IndexHandle(0))
+        }
+
+        fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+            Vec::new()
+        }
+
+        fn needs_reload(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn looks_up_rates_in_table() {
+        let enrichment_tables = TableRegistry::default();
+        let mut tables = HashMap::<String, Box<dyn Table + Send + Sync>>::new();
+        tables.insert(
+            "sample_rates".to_string(),
+            Box::new(RatesTable(HashMap::from([("api".to_string(), 4)]))),
+        );
+        enrichment_tables.load(tables);
+
+        let config = RateTableConfig {
+            table: "sample_rates".to_string(),
+            event_field: "service".to_string(),
+            key_field: default_table_key_field(),
+            rate_field: default_table_rate_field(),
+        };
+        let mut sampler = Sample::new(1, None, None);
+        sampler.rate_table = Some(RateTable::new(&config, &enrichment_tables).unwrap());
+        enrichment_tables.finish_load();
+
+        let rate_of = |sampler: &mut Sample, service: &str| {
+            let mut log = LogEvent::from("message");
+            log.insert("service", service);
+            let passed = transform_one(sampler, Event::Log(log)).unwrap();
+            passed.as_log()["sample_rate"].clone()
+        };
+        assert_eq!(rate_of(&mut sampler, "api"), "4".into());
+        assert_eq!(rate_of(&mut sampler, "web"), "1".into());
+    }
+
     #[tokio::test]
     async fn emits_internal_events() {
         assert_transform_compliance(async move {
             let config = SampleConfig {
                 rate: 1,
                 key_field: None,
+                rate_field: None,
+                rate_table: None,
                 exclude: None,
             };
             let (tx, rx) = mpsc::channel(1);
@@ -0,0 +1,355 @@
+//! The `schema_enforcement` transform.
+//!
+//! Validates logs against a JSON Schema, and the schema definition of the inputs of the
+//! transform, sending those that don't conform to them to the `rejected` output, along with their
+//! violations.
+
+mod schema;
+
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+
+use lookup::lookup_v2::parse_value_path;
+use snafu::{ResultExt, Snafu};
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, transform::SyncTransform};
+
+use self::schema::{Schema, SchemaError, Violation};
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    emit,
+    event::{Event, LogEvent, Value},
+    internal_events::SchemaEnforcementEventRejected,
+    schema::Definition,
+    transforms::{Transform, TransformOutputsBuf},
+};
+
+/// The output logs not conforming to the schema are sent to.
+pub(crate) const REJECTED: &str = "rejected";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("One of `schema` and `schema_file` must be provided"))]
+    MissingSchema,
+    #[snafu(display("Only one of `schema` and `schema_file` can be provided"))]
+    ConflictingSchemas,
+    #[snafu(display("Could not read schema file {:?}: {}", path, source))]
+    ReadSchemaFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Schema is not valid JSON: {}", source))]
+    ParseSchema { source: serde_json::Error },
+    #[snafu(display("Invalid schema: {}", source))]
+    InvalidSchema { source: SchemaError },
+    #[snafu(display("Invalid `violations_field` {:?}", field))]
+    InvalidViolationsField { field: String },
+}
+
+/// Configuration for the `schema_enforcement` transform.
+#[configurable_component(transform("schema_enforcement"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaEnforcementConfig {
+    /// The JSON Schema logs are validated against, as JSON.
+    ///
+    /// The `type`, `properties`, `required`, `additionalProperties`, `items`, `enum`, `minimum`,
+    /// `maximum`, `minLength`, `maxLength`, and `pattern` keywords are supported, and other
+    /// keywords are ignored. Timestamps are validated as strings.
+    #[configurable(metadata(
+        docs::examples = r#"{"type": "object", "required": ["message"], "properties": {"message": {"type": "string"}}}"#
+    ))]
+    pub schema: Option<String>,
+
+    /// The path of a file holding the JSON Schema logs are validated against.
+    ///
+    /// Only one of `schema` and `schema_file` can be provided.
+    #[configurable(metadata(docs::examples = "/etc/vector/schemas/access_log.json"))]
+    pub schema_file: Option<PathBuf>,
+
+    /// Whether logs are also validated against the schema definition of the inputs of the
+    /// transform.
+    ///
+    /// Logs whose fields don't have the types the definition has for them are rejected. This only
+    /// has an effect with inputs whose schema definitions are known, such as those of sources
+    /// decoding events with `json` and the `vector` log namespace.
+    #[serde(default)]
+    pub validate_input_definition: bool,
+
+    /// Whether values of other types than those of the schema are coerced to its types.
+    ///
+    /// Strings of integers, numbers and booleans are coerced to them, and integers, numbers and
+    /// booleans are coerced to strings. Logs whose values can't be coerced are rejected, without
+    /// any of their values being coerced.
+    #[serde(default)]
+    pub coerce: bool,
+
+    /// The field the violations of rejected logs are inserted into.
+    ///
+    /// Violations are objects with the `path` of the value violating the schema, and a `message`
+    /// describing the violation.
+    #[serde(default = "default_violations_field")]
+    #[configurable(metadata(docs::examples = "_violations"))]
+    pub violations_field: String,
+}
+
+fn default_violations_field() -> String {
+    "schema_violations".to_owned()
+}
+
+impl GenerateConfig for SchemaEnforcementConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"schema = '{"type": "object", "required": ["message"], "properties": {"message": {"type": "string"}}}'"#,
+        )
+        .unwrap()
+    }
+}
+
+impl SchemaEnforcementConfig {
+    fn compile_schema(&self) -> crate::Result<Schema> {
+        let json = match (&self.schema, &self.schema_file) {
+            (Some(schema), None) => schema.clone(),
+            (None, Some(path)) => {
+                std::fs::read_to_string(path).context(ReadSchemaFileSnafu { path })?
+            }
+            (None, None) => return Err(BuildError::MissingSchema.into()),
+            (Some(_), Some(_)) => return Err(BuildError::ConflictingSchemas.into()),
+        };
+        let json = serde_json::from_str(&json).context(ParseSchemaSnafu)?;
+        Ok(Schema::parse(&json, "").context(InvalidSchemaSnafu)?)
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for SchemaEnforcementConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::synchronous(SchemaEnforcement::new(
+            self, context,
+        )?))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, merged_definition: &Definition, _: LogNamespace) -> Vec<Output> {
+        let rejected_definition = match parse_value_path(&self.violations_field) {
+            Ok(path) => merged_definition.clone().with_event_field(
+                &path,
+                Kind::array(Kind::object(BTreeMap::from([
+                    ("path".into(), Kind::bytes()),
+                    ("message".into(), Kind::bytes()),
+                ]))),
+                None,
+            ),
+            Err(_) => merged_definition.clone(),
+        };
+
+        vec![
+            Output::default(DataType::Log).with_schema_definition(merged_definition.clone()),
+            Output::default(DataType::Log)
+                .with_schema_definition(rejected_definition)
+                .with_port(REJECTED),
+        ]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+pub struct SchemaEnforcement {
+    schema: Arc<Schema>,
+    input_kind: Option<Kind>,
+    coerce: bool,
+    violations_field: String,
+}
+
+impl SchemaEnforcement {
+    fn new(config: &SchemaEnforcementConfig, context: &TransformContext) -> crate::Result<Self> {
+        if parse_value_path(&config.violations_field).is_err() {
+            return Err(BuildError::InvalidViolationsField {
+                field: config.violations_field.clone(),
+            }
+            .into());
+        }
+
+        Ok(Self {
+            schema: Arc::new(config.compile_schema()?),
+            input_kind: config
+                .validate_input_definition
+                .then(|| context.merged_schema_definition.event_kind().clone()),
+            coerce: config.coerce,
+            violations_field: config.violations_field.clone(),
+        })
+    }
+
+    /// Checks that `value` has the type of the schema definition of the inputs, when enabled.
+    fn input_violation(&self, value: &Value) -> Option<Violation> {
+        let path = self
+            .input_kind
+            .as_ref()?
+            .is_superset(&Kind::from(value))
+            .err()?;
+        Some(Violation {
+            path: format!(".{path}"),
+            message: "doesn't have the type of the schema definition of the input".to_owned(),
+        })
+    }
+
+    /// Validates `log`, coercing its values when enabled and needed, and returns its violations.
+    fn enforce(&self, log: &mut LogEvent) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        self.schema
+            .validate(log.value_mut(), "", false, &mut violations);
+        if !violations.is_empty() && self.coerce {
+            // Coercing is only attempted once the log is known not to conform as is, and on a copy
+            // of it, so that rejected logs keep their original values.
+            let mut value = log.value().clone();
+            violations.clear();
+            self.schema.validate(&mut value, "", true, &mut violations);
+            if violations.is_empty() && self.input_violation(&value).is_none() {
+                *log.value_mut() = value;
+                return violations;
+            }
+        }
+        violations.extend(self.input_violation(log.value()));
+        violations
+    }
+}
+
+impl SyncTransform for SchemaEnforcement {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let mut log = event.into_log();
+        let violations = self.enforce(&mut log);
+        if violations.is_empty() {
+            output.push(Event::from(log));
+        } else {
+            emit!(SchemaEnforcementEventRejected {
+                violations: violations.len(),
+            });
+            let violations: Vec<Value> = violations.into_iter().map(Value::from).collect();
+            log.insert(self.violations_field.as_str(), violations);
+            output.push_named(REJECTED, Event::from(log));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SchemaEnforcementConfig>();
+    }
+
+    fn config(schema: serde_json::Value, coerce: bool) -> SchemaEnforcementConfig {
+        SchemaEnforcementConfig {
+            schema: Some(schema.to_string()),
+            schema_file: None,
+            validate_input_definition: false,
+            coerce,
+            violations_field: default_violations_field(),
+        }
+    }
+
+    fn transform(
+        config: &SchemaEnforcementConfig,
+        log: serde_json::Value,
+    ) -> (Option<Event>, Option<Event>) {
+        let mut transform = SchemaEnforcement::new(config, &TransformContext::default()).unwrap();
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            config.outputs(&Definition::any(), LogNamespace::Legacy),
+            1,
+        );
+        let log = LogEvent::from(Value::from(log));
+        transform.transform(Event::from(log), &mut outputs);
+        let passed = outputs.drain().next();
+        let rejected = outputs.drain_named(REJECTED).next();
+        (passed, rejected)
+    }
+
+    fn access_log_schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "required": ["message", "status"],
+            "properties": {
+                "message": {"type": "string"},
+                "status": {"type": "integer", "minimum": 100},
+            },
+        })
+    }
+
+    #[test]
+    fn passes_conforming_logs() {
+        let config = config(access_log_schema(), false);
+        let log = json!({"message": "GET /", "status": 200});
+        let (passed, rejected) = transform(&config, log.clone());
+        assert_eq!(passed.unwrap().into_log().value(), &Value::from(log));
+        assert!(rejected.is_none());
+    }
+
+    #[test]
+    fn rejects_violating_logs() {
+        let config = config(access_log_schema(), false);
+        let (passed, rejected) = transform(&config, json!({"status": 42}));
+        assert!(passed.is_none());
+        assert_eq!(
+            rejected.unwrap().into_log().value(),
+            &Value::from(json!({
+                "status": 42,
+                "schema_violations": [
+                    {"path": ".", "message": "is missing the required field \"message\""},
+                    {"path": ".status", "message": "42 is less than the minimum of 100"},
+                ],
+            }))
+        );
+    }
+
+    #[test]
+    fn coerces_values() {
+        let config = config(access_log_schema(), true);
+        let (passed, _) = transform(&config, json!({"message": "GET /", "status": "200"}));
+        assert_eq!(
+            passed.unwrap().into_log().value(),
+            &Value::from(json!({"message": "GET /", "status": 200}))
+        );
+
+        // Rejected logs keep their original values, even those which could be coerced.
+        let (_, rejected) = transform(&config, json!({"message": 1, "status": "two"}));
+        let rejected = rejected.unwrap().into_log();
+        assert_eq!(rejected.get("message"), Some(&Value::from(1)));
+        assert_eq!(
+            rejected.get("schema_violations"),
+            Some(&Value::from(json!([
+                {"path": ".status", "message": "expected integer, got string"},
+            ])))
+        );
+    }
+
+    #[test]
+    fn reads_schema_files() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "{}", access_log_schema()).unwrap();
+        let config = SchemaEnforcementConfig {
+            schema: None,
+            schema_file: Some(file.path().to_owned()),
+            ..config(json!({}), false)
+        };
+        let (_, rejected) = transform(&config, json!({"message": "GET /"}));
+        assert!(rejected.is_some());
+
+        let config = SchemaEnforcementConfig {
+            schema_file: Some(file.path().to_owned()),
+            ..config
+        };
+        assert!(SchemaEnforcement::new(&config, &TransformContext::default()).is_err());
+    }
+}
@@ -0,0 +1,520 @@
+//! The subset of JSON Schema events are validated against.
+
+use std::collections::BTreeMap;
+
+use ordered_float::NotNan;
+use regex::Regex;
+use serde_json::Value as JsonValue;
+use snafu::Snafu;
+
+use crate::event::Value;
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum SchemaError {
+    #[snafu(display("Invalid `{}` keyword at {:?}: {}", keyword, path, reason))]
+    InvalidKeyword {
+        keyword: &'static str,
+        path: String,
+        reason: String,
+    },
+}
+
+/// The types of JSON Schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Type {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl Type {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "null" => Self::Null,
+            "boolean" => Self::Boolean,
+            "integer" => Self::Integer,
+            "number" => Self::Number,
+            "string" => Self::String,
+            "array" => Self::Array,
+            "object" => Self::Object,
+            _ => return None,
+        })
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Object => "object",
+        }
+    }
+
+    /// Whether `value` is of this type, where timestamps are strings, and integers are numbers.
+    const fn matches(self, value: &Value) -> bool {
+        matches!(
+            (self, value),
+            (Self::Null, Value::Null)
+                | (Self::Boolean, Value::Boolean(_))
+                | (Self::Integer, Value::Integer(_))
+                | (Self::Number, Value::Integer(_) | Value::Float(_))
+                | (
+                    Self::String,
+                    Value::Bytes(_) | Value::Timestamp(_) | Value::Regex(_)
+                )
+                | (Self::Array, Value::Array(_))
+                | (Self::Object, Value::Object(_))
+        )
+    }
+
+    /// Converts `value` to this type, if it has a lossless representation of it.
+    fn coerce(self, value: &Value) -> Option<Value> {
+        match (self, value) {
+            (Self::Integer, Value::Bytes(bytes)) => std::str::from_utf8(bytes)
+                .ok()?
+                .trim()
+                .parse::<i64>()
+                .ok()
+                .map(Value::from),
+            (Self::Integer, Value::Float(float)) if float.fract() == 0.0 => {
+                let integer = float.into_inner() as i64;
+                (integer as f64 == float.into_inner()).then(|| Value::from(integer))
+            }
+            (Self::Number, Value::Bytes(bytes)) => {
+                let float = std::str::from_utf8(bytes)
+                    .ok()?
+                    .trim()
+                    .parse::<f64>()
+                    .ok()?;
+                NotNan::new(float).ok().map(Value::Float)
+            }
+            (Self::Boolean, Value::Bytes(bytes)) => match bytes.as_ref() {
+                b"true" => Some(Value::Boolean(true)),
+                b"false" => Some(Value::Boolean(false)),
+                _ => None,
+            },
+            (Self::String, Value::Integer(_) | Value::Float(_) | Value::Boolean(_)) => {
+                Some(Value::from(value.to_string_lossy().into_owned()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A violation of the schema by an event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// The path of the value violating the schema, in the syntax of VRL.
+    pub path: String,
+    pub message: String,
+}
+
+impl From<Violation> for Value {
+    fn from(violation: Violation) -> Self {
+        Value::from(BTreeMap::from([
+            ("path".to_owned(), Value::from(violation.path)),
+            ("message".to_owned(), Value::from(violation.message)),
+        ]))
+    }
+}
+
+/// What values of objects not listed in `properties` must conform to.
+#[derive(Debug)]
+enum AdditionalProperties {
+    Allowed,
+    Denied,
+    Schema(Box<Schema>),
+}
+
+/// A compiled JSON Schema, supporting its type, object, array, enumeration, numeric, and string
+/// keywords. Other keywords, such as annotations, are ignored.
+#[derive(Debug)]
+pub struct Schema {
+    types: Vec<Type>,
+    properties: BTreeMap<String, Schema>,
+    required: Vec<String>,
+    additional_properties: AdditionalProperties,
+    items: Option<Box<Schema>>,
+    enumeration: Option<Vec<JsonValue>>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    pattern: Option<Regex>,
+}
+
+fn invalid(keyword: &'static str, path: &str, reason: impl Into<String>) -> SchemaError {
+    SchemaError::InvalidKeyword {
+        keyword,
+        path: path.to_owned(),
+        reason: reason.into(),
+    }
+}
+
+fn parse_length(
+    json: &JsonValue,
+    keyword: &'static str,
+    path: &str,
+) -> Result<Option<usize>, SchemaError> {
+    json.get(keyword)
+        .map(|length| {
+            length
+                .as_u64()
+                .map(|length| length as usize)
+                .ok_or_else(|| invalid(keyword, path, "must be a non-negative integer"))
+        })
+        .transpose()
+}
+
+fn parse_bound(
+    json: &JsonValue,
+    keyword: &'static str,
+    path: &str,
+) -> Result<Option<f64>, SchemaError> {
+    json.get(keyword)
+        .map(|bound| {
+            bound
+                .as_f64()
+                .ok_or_else(|| invalid(keyword, path, "must be a number"))
+        })
+        .transpose()
+}
+
+impl Schema {
+    /// Compiles the JSON Schema `json`, whose path in the schema, for errors, is `path`.
+    pub fn parse(json: &JsonValue, path: &str) -> Result<Self, SchemaError> {
+        if !json.is_object() {
+            return Err(invalid("schema", path, "must be an object"));
+        }
+
+        let types = match json.get("type") {
+            None => Vec::new(),
+            Some(JsonValue::String(name)) => vec![name.as_str()],
+            Some(JsonValue::Array(names)) => names
+                .iter()
+                .map(|name| {
+                    name.as_str()
+                        .ok_or_else(|| invalid("type", path, "must be a string or strings"))
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err(invalid("type", path, "must be a string or strings")),
+        }
+        .into_iter()
+        .map(|name| {
+            Type::parse(name).ok_or_else(|| invalid("type", path, format!("unknown type {name:?}")))
+        })
+        .collect::<Result<_, _>>()?;
+
+        let properties = match json.get("properties") {
+            None => BTreeMap::new(),
+            Some(JsonValue::Object(properties)) => properties
+                .iter()
+                .map(|(name, property)| {
+                    Schema::parse(property, &format!("{path}/properties/{name}"))
+                        .map(|property| (name.clone(), property))
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err(invalid("properties", path, "must be an object")),
+        };
+
+        let required = match json.get("required") {
+            None => Vec::new(),
+            Some(JsonValue::Array(names)) => names
+                .iter()
+                .map(|name| {
+                    name.as_str()
+                        .map(str::to_owned)
+                        .ok_or_else(|| invalid("required", path, "must be an array of strings"))
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err(invalid("required", path, "must be an array of strings")),
+        };
+
+        let additional_properties = match json.get("additionalProperties") {
+            None | Some(JsonValue::Bool(true)) => AdditionalProperties::Allowed,
+            Some(JsonValue::Bool(false)) => AdditionalProperties::Denied,
+            Some(schema) => AdditionalProperties::Schema(Box::new(Schema::parse(
+                schema,
+                &format!("{path}/additionalProperties"),
+            )?)),
+        };
+
+        let items = json
+            .get("items")
+            .map(|items| Schema::parse(items, &format!("{path}/items")).map(Box::new))
+            .transpose()?;
+
+        let enumeration = match json.get("enum") {
+            None => None,
+            Some(JsonValue::Array(values)) => Some(values.clone()),
+            Some(_) => return Err(invalid("enum", path, "must be an array")),
+        };
+
+        let pattern = match json.get("pattern") {
+            None => None,
+            Some(JsonValue::String(pattern)) => Some(
+                Regex::new(pattern).map_err(|error| invalid("pattern", path, error.to_string()))?,
+            ),
+            Some(_) => return Err(invalid("pattern", path, "must be a string")),
+        };
+
+        Ok(Self {
+            types,
+            properties,
+            required,
+            additional_properties,
+            items,
+            enumeration,
+            minimum: parse_bound(json, "minimum", path)?,
+            maximum: parse_bound(json, "maximum", path)?,
+            min_length: parse_length(json, "minLength", path)?,
+            max_length: parse_length(json, "maxLength", path)?,
+            pattern,
+        })
+    }
+
+    /// Validates `value`, at `path` in its event, collecting its violations of the schema.
+    ///
+    /// With `coerce`, values of other types than those of the schema are converted to them when
+    /// they can be, such as strings of numbers to numbers.
+    pub fn validate(
+        &self,
+        value: &mut Value,
+        path: &str,
+        coerce: bool,
+        violations: &mut Vec<Violation>,
+    ) {
+        let mut violate = |message: String| {
+            violations.push(Violation {
+                path: if path.is_empty() {
+                    ".".to_owned()
+                } else {
+                    path.to_owned()
+                },
+                message,
+            })
+        };
+
+        if !self.types.is_empty() && !self.types.iter().any(|kind| kind.matches(value)) {
+            let coerced = coerce
+                .then(|| self.types.iter().find_map(|kind| kind.coerce(value)))
+                .flatten();
+            match coerced {
+                Some(coerced) => *value = coerced,
+                None => {
+                    let types: Vec<_> = self.types.iter().map(|kind| kind.as_str()).collect();
+                    violate(format!(
+                        "expected {}, got {}",
+                        types.join(" or "),
+                        value.kind_str()
+                    ));
+                    return;
+                }
+            }
+        }
+
+        if let Some(enumeration) = &self.enumeration {
+            let json = serde_json::to_value(&*value).unwrap_or(JsonValue::Null);
+            if !enumeration.contains(&json) {
+                violate(format!("{json} is not one of the allowed values"));
+            }
+        }
+
+        match value {
+            Value::Integer(_) | Value::Float(_) => {
+                let number = match value {
+                    Value::Integer(integer) => *integer as f64,
+                    Value::Float(float) => float.into_inner(),
+                    _ => unreachable!("value is a number"),
+                };
+                match (self.minimum, self.maximum) {
+                    (Some(minimum), _) if number < minimum => {
+                        violate(format!("{number} is less than the minimum of {minimum}"))
+                    }
+                    (_, Some(maximum)) if number > maximum => {
+                        violate(format!("{number} is greater than the maximum of {maximum}"))
+                    }
+                    _ => {}
+                }
+            }
+            Value::Bytes(_) | Value::Timestamp(_) | Value::Regex(_) => {
+                let string = value.to_string_lossy();
+                let length = string.chars().count();
+                match (self.min_length, self.max_length) {
+                    (Some(min_length), _) if length < min_length => {
+                        violate(format!("is shorter than {min_length} characters"))
+                    }
+                    (_, Some(max_length)) if length > max_length => {
+                        violate(format!("is longer than {max_length} characters"))
+                    }
+                    _ => {}
+                }
+                if let Some(pattern) = &self.pattern {
+                    if !pattern.is_match(&string) {
+                        violate(format!("doesn't match the pattern {:?}", pattern.as_str()));
+                    }
+                }
+            }
+            Value::Object(object) => {
+                for name in &self.required {
+                    if !object.contains_key(name) {
+                        violate(format!("is missing the required field {name:?}"));
+                    }
+                }
+                for (name, value) in object.iter_mut() {
+                    let field_path = format!("{path}.{}", field_segment(name));
+                    match self.properties.get(name) {
+                        Some(property) => property.validate(value, &field_path, coerce, violations),
+                        None => match &self.additional_properties {
+                            AdditionalProperties::Allowed => {}
+                            AdditionalProperties::Denied => violations.push(Violation {
+                                path: field_path,
+                                message: "is not an allowed field".to_owned(),
+                            }),
+                            AdditionalProperties::Schema(schema) => {
+                                schema.validate(value, &field_path, coerce, violations)
+                            }
+                        },
+                    }
+                }
+            }
+            Value::Array(array) => {
+                if let Some(items) = &self.items {
+                    let path = if path.is_empty() { "." } else { path };
+                    for (i, item) in array.iter_mut().enumerate() {
+                        items.validate(item, &format!("{path}[{i}]"), coerce, violations);
+                    }
+                }
+            }
+            Value::Boolean(_) | Value::Null => {}
+        }
+    }
+}
+
+/// Formats `name` as a segment of a VRL path, quoting it when it isn't a plain identifier.
+fn field_segment(name: &str) -> String {
+    let plain = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '@');
+    if plain {
+        name.to_owned()
+    } else {
+        format!("{name:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn validate(schema: JsonValue, value: JsonValue, coerce: bool) -> (Value, Vec<Violation>) {
+        let schema = Schema::parse(&schema, "").unwrap();
+        let mut value = Value::from(value);
+        let mut violations = Vec::new();
+        schema.validate(&mut value, "", coerce, &mut violations);
+        (value, violations)
+    }
+
+    fn violation(path: &str, message: &str) -> Violation {
+        Violation {
+            path: path.to_owned(),
+            message: message.to_owned(),
+        }
+    }
+
+    #[test]
+    fn validates_objects() {
+        let schema = json!({
+            "type": "object",
+            "required": ["message", "status"],
+            "properties": {
+                "message": {"type": "string", "minLength": 1},
+                "status": {"type": "integer", "minimum": 100, "maximum": 599},
+                "tags": {"type": "array", "items": {"type": "string", "pattern": "^[a-z]+$"}},
+                "level": {"enum": ["info", "error"]},
+            },
+            "additionalProperties": false,
+        });
+
+        let (_, violations) = validate(
+            schema.clone(),
+            json!({"message": "ok", "status": 200, "tags": ["a"], "level": "info"}),
+            false,
+        );
+        assert!(violations.is_empty());
+
+        let (_, violations) = validate(
+            schema,
+            json!({"message": "", "status": 700, "tags": ["a", "B"], "level": "debug", "user id": 1}),
+            false,
+        );
+        assert_eq!(
+            violations,
+            vec![
+                violation(".level", "\"debug\" is not one of the allowed values"),
+                violation(".message", "is shorter than 1 characters"),
+                violation(".status", "700 is greater than the maximum of 599"),
+                violation(".tags[1]", "doesn't match the pattern \"^[a-z]+$\""),
+                violation(".\"user id\"", "is not an allowed field"),
+            ]
+        );
+
+        let (_, violations) = validate(
+            json!({"type": "object", "required": ["message"]}),
+            json!([]),
+            false,
+        );
+        assert_eq!(
+            violations,
+            vec![violation(".", "expected object, got array")]
+        );
+    }
+
+    #[test]
+    fn coerces_types() {
+        let schema = json!({
+            "properties": {
+                "status": {"type": "integer"},
+                "duration": {"type": "number"},
+                "ok": {"type": "boolean"},
+                "id": {"type": "string"},
+            },
+        });
+        let event = json!({"status": "200", "duration": "1.5", "ok": "true", "id": 42});
+
+        let (_, violations) = validate(schema.clone(), event.clone(), false);
+        assert_eq!(violations.len(), 4);
+
+        let (value, violations) = validate(schema, event, true);
+        assert!(violations.is_empty());
+        assert_eq!(
+            value,
+            Value::from(json!({"status": 200, "duration": 1.5, "ok": true, "id": "42"}))
+        );
+
+        let (_, violations) = validate(json!({"type": "integer"}), json!("two"), true);
+        assert_eq!(
+            violations,
+            vec![violation(".", "expected integer, got string")]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_schemas() {
+        assert_eq!(
+            Schema::parse(&json!({"properties": {"a": {"type": "text"}}}), "").unwrap_err(),
+            invalid("type", "/properties/a", "unknown type \"text\"")
+        );
+        assert!(Schema::parse(&json!({"pattern": "("}), "").is_err());
+    }
+}
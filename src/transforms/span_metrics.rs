@@ -0,0 +1,412 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use chrono::Utc;
+use futures::{Stream, StreamExt};
+use snafu::Snafu;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{
+        metric::{Bucket, Metric, MetricKind, MetricTags, MetricValue},
+        Event, EventMetadata, TraceEvent, Value,
+    },
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// The status code of spans that failed, in OpenTelemetry.
+const STATUS_CODE_ERROR: i64 = 2;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`flush_interval_secs` must be greater than zero"))]
+    InvalidFlushInterval,
+    #[snafu(display("`buckets` must be sorted, finite, upper limits"))]
+    InvalidBuckets,
+    #[snafu(display("Dimension `{}` conflicts with the `service` or `operation` tag", tag))]
+    ReservedDimension { tag: String },
+}
+
+/// Configuration for the `span_metrics` transform.
+#[configurable_component(transform("span_metrics"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SpanMetricsConfig {
+    /// The field of spans holding the name of their service, as the `service` tag of metrics.
+    #[serde(default = "default_service_field")]
+    pub service_field: String,
+
+    /// The field of spans holding the name of their operation, as the `operation` tag of
+    /// metrics.
+    #[serde(default = "default_operation_field")]
+    pub operation_field: String,
+
+    /// Additional tags of metrics, and the fields of spans holding their values.
+    ///
+    /// Spans without a field don't have its tag.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The field of spans holding the value of the tag."
+    ))]
+    pub dimensions: HashMap<String, String>,
+
+    /// The upper limits of the buckets of the histogram of the duration of spans, in seconds.
+    #[serde(default = "default_buckets")]
+    pub buckets: Vec<f64>,
+
+    /// The namespace of metrics.
+    ///
+    /// By default, `traces` is used. Set to an empty string to not use a namespace.
+    #[serde(default = "default_namespace")]
+    pub namespace: Option<String>,
+
+    /// The interval between the emissions of metrics, in seconds.
+    ///
+    /// Metrics are incremental, counting the spans seen since the previous emission.
+    #[serde(default = "default_flush_interval_secs")]
+    pub flush_interval_secs: u64,
+}
+
+fn default_service_field() -> String {
+    r#"resources."service.name""#.to_string()
+}
+
+fn default_operation_field() -> String {
+    "name".to_string()
+}
+
+fn default_namespace() -> Option<String> {
+    Some("traces".to_string())
+}
+
+fn default_buckets() -> Vec<f64> {
+    vec![
+        0.002, 0.004, 0.006, 0.008, 0.01, 0.05, 0.1, 0.2, 0.4, 0.8, 1.0, 1.4, 2.0, 5.0, 10.0, 15.0,
+    ]
+}
+
+const fn default_flush_interval_secs() -> u64 {
+    15
+}
+
+impl GenerateConfig for SpanMetricsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"dimensions.http_method = "attributes.\"http.method\"""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for SpanMetricsConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        SpanMetrics::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::trace()
+    }
+
+    fn outputs(&self, _: &schema::Definition, _: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+}
+
+/// The spans of a series since the previous flush.
+#[derive(Debug)]
+struct Series {
+    calls: u64,
+    errors: u64,
+    /// The number of durations in each bucket, not including those of the buckets before.
+    bucket_counts: Vec<u64>,
+    duration_count: u64,
+    duration_sum: f64,
+    metadata: EventMetadata,
+}
+
+pub struct SpanMetrics {
+    service_field: String,
+    operation_field: String,
+    /// The dimensions, sorted by tag so that series keys are stable.
+    dimensions: Vec<(String, String)>,
+    buckets: Vec<f64>,
+    namespace: Option<String>,
+    flush_interval: Duration,
+    series: HashMap<BTreeMap<String, String>, Series>,
+}
+
+impl SpanMetrics {
+    fn new(config: &SpanMetricsConfig) -> crate::Result<Self> {
+        if config.flush_interval_secs == 0 {
+            return Err(BuildError::InvalidFlushInterval.into());
+        }
+        let sorted = config.buckets.windows(2).all(|pair| pair[0] < pair[1]);
+        if !sorted || config.buckets.iter().any(|limit| !limit.is_finite()) {
+            return Err(BuildError::InvalidBuckets.into());
+        }
+        if let Some(tag) = config
+            .dimensions
+            .keys()
+            .find(|tag| *tag == "service" || *tag == "operation")
+        {
+            return Err(BuildError::ReservedDimension { tag: tag.clone() }.into());
+        }
+
+        let mut dimensions: Vec<_> = config
+            .dimensions
+            .iter()
+            .map(|(tag, field)| (tag.clone(), field.clone()))
+            .collect();
+        dimensions.sort();
+        Ok(Self {
+            service_field: config.service_field.clone(),
+            operation_field: config.operation_field.clone(),
+            dimensions,
+            buckets: config.buckets.clone(),
+            namespace: config
+                .namespace
+                .clone()
+                .filter(|namespace| !namespace.is_empty()),
+            flush_interval: Duration::from_secs(config.flush_interval_secs),
+            series: HashMap::new(),
+        })
+    }
+
+    fn record(&mut self, span: TraceEvent) {
+        let tag = |field: &str| {
+            span.get(field)
+                .map(|value| value.to_string_lossy().into_owned())
+        };
+        let mut key = BTreeMap::new();
+        key.extend(tag(&self.service_field).map(|value| ("service".to_string(), value)));
+        key.extend(tag(&self.operation_field).map(|value| ("operation".to_string(), value)));
+        for (name, field) in &self.dimensions {
+            key.extend(tag(field).map(|value| (name.clone(), value)));
+        }
+
+        let error = span.get("status.code") == Some(&Value::Integer(STATUS_CODE_ERROR));
+        let duration = match (
+            span.get("start_time_unix_nano")
+                .and_then(Value::as_timestamp),
+            span.get("end_time_unix_nano").and_then(Value::as_timestamp),
+        ) {
+            (Some(start), Some(end)) => (*end - *start).to_std().ok(),
+            _ => None,
+        };
+
+        let buckets = self.buckets.len();
+        let series = self.series.entry(key).or_insert_with(|| Series {
+            calls: 0,
+            errors: 0,
+            bucket_counts: vec![0; buckets],
+            duration_count: 0,
+            duration_sum: 0.0,
+            metadata: EventMetadata::default(),
+        });
+        series.calls += 1;
+        if error {
+            series.errors += 1;
+        }
+        if let Some(duration) = duration {
+            let secs = duration.as_secs_f64();
+            // Durations above the last bucket are only counted in the total.
+            if let Some(index) = self.buckets.iter().position(|limit| secs <= *limit) {
+                series.bucket_counts[index] += 1;
+            }
+            series.duration_count += 1;
+            series.duration_sum += secs;
+        }
+        let (_, metadata) = span.into_parts();
+        series.metadata.merge(metadata);
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        let now = Utc::now();
+        for (key, series) in self.series.drain() {
+            let metric = |name: &str, value: MetricValue, metadata: EventMetadata| {
+                let metric =
+                    Metric::new_with_metadata(name, MetricKind::Incremental, value, metadata)
+                        .with_namespace(self.namespace.clone())
+                        .with_tags(Some(MetricTags::from(key.clone())))
+                        .with_timestamp(Some(now));
+                Event::from(metric)
+            };
+
+            // The spans are acknowledged along with the first metric of their series.
+            output.push(metric(
+                "span_calls_total",
+                MetricValue::Counter {
+                    value: series.calls as f64,
+                },
+                series.metadata,
+            ));
+            output.push(metric(
+                "span_errors_total",
+                MetricValue::Counter {
+                    value: series.errors as f64,
+                },
+                EventMetadata::default(),
+            ));
+            if series.duration_count > 0 {
+                let buckets = self
+                    .buckets
+                    .iter()
+                    .zip(series.bucket_counts)
+                    .map(|(upper_limit, count)| Bucket {
+                        upper_limit: *upper_limit,
+                        count,
+                    })
+                    .collect();
+                output.push(metric(
+                    "span_duration_seconds",
+                    MetricValue::AggregatedHistogram {
+                        buckets,
+                        count: series.duration_count,
+                        sum: series.duration_sum,
+                    },
+                    EventMetadata::default(),
+                ));
+            }
+        }
+    }
+}
+
+impl TaskTransform<Event> for SpanMetrics {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream = tokio::time::interval(self.flush_interval);
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_into(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => self.record(event.into_trace()),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SpanMetricsConfig>();
+    }
+
+    fn config(config: &str) -> SpanMetricsConfig {
+        toml::from_str(config).unwrap()
+    }
+
+    fn span(operation: &str, duration_ms: i64, status_code: i64) -> TraceEvent {
+        let mut span = TraceEvent::default();
+        span.insert("name", operation);
+        span.insert(r#"resources."service.name""#, "checkout");
+        span.insert(r#"attributes."http.method""#, "GET");
+        span.insert("status.code", status_code);
+        span.insert("start_time_unix_nano", Utc.timestamp_millis(0));
+        span.insert("end_time_unix_nano", Utc.timestamp_millis(duration_ms));
+        span
+    }
+
+    fn find<'a>(metrics: &'a [Event], name: &str, operation: &str) -> &'a Metric {
+        metrics
+            .iter()
+            .map(Event::as_metric)
+            .find(|metric| {
+                metric.name() == name && metric.tag_value("operation").as_deref() == Some(operation)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn aggregates_spans_by_service_and_operation() {
+        let mut transform = SpanMetrics::new(&config(
+            r#"
+                buckets = [0.25, 1.0]
+                dimensions.http_method = "attributes.\"http.method\""
+            "#,
+        ))
+        .unwrap();
+        transform.record(span("GET /cart", 125, 1));
+        transform.record(span("GET /cart", 500, STATUS_CODE_ERROR));
+        transform.record(span("GET /cart", 5000, 1));
+        transform.record(span("POST /order", 125, 1));
+
+        let mut output = Vec::new();
+        transform.flush_into(&mut output);
+        assert_eq!(output.len(), 6);
+
+        let calls = find(&output, "span_calls_total", "GET /cart");
+        assert_eq!(calls.kind(), MetricKind::Incremental);
+        assert_eq!(calls.value(), &MetricValue::Counter { value: 3.0 });
+        assert_eq!(calls.tag_value("service").as_deref(), Some("checkout"));
+        assert_eq!(calls.tag_value("http_method").as_deref(), Some("GET"));
+
+        let errors = find(&output, "span_errors_total", "GET /cart");
+        assert_eq!(errors.value(), &MetricValue::Counter { value: 1.0 });
+
+        let durations = find(&output, "span_duration_seconds", "GET /cart");
+        assert_eq!(
+            durations.value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 0.25,
+                        count: 1
+                    },
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 1
+                    },
+                ],
+                count: 3,
+                sum: 5.625,
+            }
+        );
+
+        let errors = find(&output, "span_errors_total", "POST /order");
+        assert_eq!(errors.value(), &MetricValue::Counter { value: 0.0 });
+
+        // Series are emitted for the spans since the previous flush.
+        let mut output = Vec::new();
+        transform.flush_into(&mut output);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn validates_config() {
+        assert!(SpanMetrics::new(&config("buckets = [1.0, 0.1]")).is_err());
+        assert!(SpanMetrics::new(&config("flush_interval_secs = 0")).is_err());
+        assert!(SpanMetrics::new(&config(r#"dimensions.service = "service""#)).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext};
 use crate::schema;
 use crate::transforms::tag_cardinality_limit::TagCardinalityLimit;
@@ -17,10 +19,53 @@ pub struct TagCardinalityLimitConfig {
     #[serde(default = "default_limit_exceeded_action")]
     pub limit_exceeded_action: LimitExceededAction,
 
+    /// How many distinct values to accept for specific keys, overriding `value_limit`.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "How many distinct values to accept for the key."
+    ))]
+    pub tag_value_limits: HashMap<String, u32>,
+
+    /// Overrides of the limits for the metrics with specific names.
+    ///
+    /// The values of the tags of these metrics are tracked separately from those of other
+    /// metrics, so that they neither count towards, nor are rejected by, the limits of others.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The overrides of the limits for the metric."
+    ))]
+    pub per_metric_limits: HashMap<String, PerMetricConfig>,
+
+    /// The value tags are given by the `replace_tag` action.
+    #[serde(default = "default_overflow_value")]
+    pub overflow_value: String,
+
     #[serde(flatten)]
     pub mode: Mode,
 }
 
+/// Overrides of the limits for the metrics with a name.
+///
+/// Limits that aren't overridden are those of all metrics.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PerMetricConfig {
+    /// How many distinct values to accept for any given key of the metrics.
+    pub value_limit: Option<u32>,
+
+    /// The action to take when an event arrives that would exceed the cardinality limit for one
+    /// or more of its tags.
+    pub limit_exceeded_action: Option<LimitExceededAction>,
+
+    /// How many distinct values to accept for specific keys of the metrics.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "How many distinct values to accept for the key."
+    ))]
+    pub tag_value_limits: HashMap<String, u32>,
+}
+
 /// Controls the approach taken for tracking tag cardinality.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -64,6 +109,13 @@ pub enum LimitExceededAction {
 
     /// Drop the entire event itself.
     DropEvent,
+
+    /// Replace the value of the tag(s) that would exceed the configured limit with
+    /// `overflow_value`.
+    ///
+    /// Metrics whose tags are replaced are aggregated into the same series, by sinks, as the
+    /// others whose values exceeded the limit.
+    ReplaceTag,
 }
 
 const fn default_limit_exceeded_action() -> LimitExceededAction {
@@ -74,6 +126,10 @@ const fn default_value_limit() -> u32 {
     500
 }
 
+fn default_overflow_value() -> String {
+    "other".to_string()
+}
+
 pub(crate) const fn default_cache_size() -> usize {
     5000 * 1024 // 5KB
 }
@@ -84,6 +140,9 @@ impl GenerateConfig for TagCardinalityLimitConfig {
             mode: Mode::Exact,
             value_limit: default_value_limit(),
             limit_exceeded_action: default_limit_exceeded_action(),
+            tag_value_limits: HashMap::new(),
+            per_metric_limits: HashMap::new(),
+            overflow_value: default_overflow_value(),
         })
         .unwrap()
     }
@@ -2,7 +2,7 @@ use futures::{Stream, StreamExt};
 use hashbrown::HashMap;
 use std::{future::ready, pin::Pin};
 
-use crate::transforms::tag_cardinality_limit::config::LimitExceededAction;
+use crate::transforms::tag_cardinality_limit::config::{LimitExceededAction, Mode};
 use crate::{
     event::Event,
     internal_events::{
@@ -22,32 +22,37 @@ use crate::event::metric::TagValueSet;
 pub use config::TagCardinalityLimitConfig;
 use tag_value_set::AcceptedTagValueSet;
 
+/// The limits of the tags of metrics, with the values accepted so far for each key.
 #[derive(Debug)]
-pub struct TagCardinalityLimit {
-    config: TagCardinalityLimitConfig,
+struct TagLimits {
+    value_limit: u32,
+    tag_value_limits: std::collections::HashMap<String, u32>,
+    limit_exceeded_action: LimitExceededAction,
     accepted_tags: HashMap<String, AcceptedTagValueSet>,
 }
 
-impl TagCardinalityLimit {
-    fn new(config: TagCardinalityLimitConfig) -> Self {
-        Self {
-            config,
-            accepted_tags: HashMap::new(),
-        }
+impl TagLimits {
+    fn value_limit(&self, key: &str) -> u32 {
+        self.tag_value_limits
+            .get(key)
+            .copied()
+            .unwrap_or(self.value_limit)
     }
 
     /// Takes in key and a value corresponding to a tag on an incoming Metric
     /// Event.  If that value is already part of set of accepted values for that
     /// key, then simply returns true.  If that value is not yet part of the
-    /// accepted values for that key, checks whether we have hit the value_limit
+    /// accepted values for that key, checks whether we have hit the value limit
     /// for that key yet and if not adds the value to the set of accepted values
     /// for the key and returns true, otherwise returns false.  A false return
     /// value indicates to the caller that the value is not accepted for this
     /// key, and the configured limit_exceeded_action should be taken.
-    fn try_accept_tag(&mut self, key: &str, value: &TagValueSet) -> bool {
-        let tag_value_set = self.accepted_tags.entry_ref(key).or_insert_with(|| {
-            AcceptedTagValueSet::new(self.config.value_limit, &self.config.mode)
-        });
+    fn try_accept_tag(&mut self, key: &str, value: &TagValueSet, mode: &Mode) -> bool {
+        let value_limit = self.value_limit(key);
+        let tag_value_set = self
+            .accepted_tags
+            .entry_ref(key)
+            .or_insert_with(|| AcceptedTagValueSet::new(value_limit, mode));
 
         if tag_value_set.contains(value) {
             // Tag value has already been accepted, nothing more to do.
@@ -55,11 +60,11 @@ impl TagCardinalityLimit {
         }
 
         // Tag value not yet part of the accepted set.
-        if tag_value_set.len() < self.config.value_limit as usize {
+        if tag_value_set.len() < value_limit as usize {
             // accept the new value
             tag_value_set.insert(value.clone());
 
-            if tag_value_set.len() == self.config.value_limit as usize {
+            if tag_value_set.len() == value_limit as usize {
                 emit!(TagCardinalityValueLimitReached { key });
             }
 
@@ -76,29 +81,82 @@ impl TagCardinalityLimit {
         self.accepted_tags
             .get(key)
             .map(|value_set| {
-                !value_set.contains(value) && value_set.len() >= self.config.value_limit as usize
+                !value_set.contains(value) && value_set.len() >= self.value_limit(key) as usize
             })
             .unwrap_or(false)
     }
 
     /// Record a key and value corresponding to a tag on an incoming Metric.
-    fn record_tag_value(&mut self, key: &str, value: &TagValueSet) {
+    fn record_tag_value(&mut self, key: &str, value: &TagValueSet, mode: &Mode) {
+        let value_limit = self.value_limit(key);
         self.accepted_tags
             .entry_ref(key)
-            .or_insert_with(|| AcceptedTagValueSet::new(self.config.value_limit, &self.config.mode))
+            .or_insert_with(|| AcceptedTagValueSet::new(value_limit, mode))
             .insert(value.clone());
     }
+}
+
+#[derive(Debug)]
+pub struct TagCardinalityLimit {
+    mode: Mode,
+    overflow_value: String,
+    /// `overflow_value`, as the values of tags replaced with it.
+    overflow_set: TagValueSet,
+    limits: TagLimits,
+    /// The limits of metrics whose names have overrides, tracked separately.
+    per_metric_limits: HashMap<String, TagLimits>,
+}
+
+impl TagCardinalityLimit {
+    fn new(config: TagCardinalityLimitConfig) -> Self {
+        let per_metric_limits = config
+            .per_metric_limits
+            .iter()
+            .map(|(name, overrides)| {
+                let mut tag_value_limits = config.tag_value_limits.clone();
+                tag_value_limits.extend(overrides.tag_value_limits.clone());
+                let limits = TagLimits {
+                    value_limit: overrides.value_limit.unwrap_or(config.value_limit),
+                    tag_value_limits,
+                    limit_exceeded_action: overrides
+                        .limit_exceeded_action
+                        .clone()
+                        .unwrap_or_else(|| config.limit_exceeded_action.clone()),
+                    accepted_tags: HashMap::new(),
+                };
+                (name.clone(), limits)
+            })
+            .collect();
+
+        Self {
+            overflow_set: TagValueSet::from([config.overflow_value.clone()]),
+            overflow_value: config.overflow_value,
+            limits: TagLimits {
+                value_limit: config.value_limit,
+                tag_value_limits: config.tag_value_limits,
+                limit_exceeded_action: config.limit_exceeded_action,
+                accepted_tags: HashMap::new(),
+            },
+            per_metric_limits,
+            mode: config.mode,
+        }
+    }
 
     fn transform_one(&mut self, mut event: Event) -> Option<Event> {
         let metric = event.as_mut_metric();
+        let limits = match self.per_metric_limits.get_mut(metric.name()) {
+            Some(limits) => limits,
+            None => &mut self.limits,
+        };
+        let mode = &self.mode;
         if let Some(tags_map) = metric.tags_mut() {
-            match self.config.limit_exceeded_action {
+            match limits.limit_exceeded_action {
                 LimitExceededAction::DropEvent => {
                     // This needs to check all the tags, to ensure that the ordering of tag names
                     // doesn't change the behavior of the check.
 
                     for (key, value) in tags_map.iter_sets() {
-                        if self.tag_limit_exceeded(key, value) {
+                        if limits.tag_limit_exceeded(key, value) {
                             emit!(TagCardinalityLimitRejectingEvent {
                                 tag_key: key,
                                 tag_value: &value.to_string(),
@@ -107,12 +165,12 @@ impl TagCardinalityLimit {
                         }
                     }
                     for (key, value) in tags_map.iter_sets() {
-                        self.record_tag_value(key, value);
+                        limits.record_tag_value(key, value, mode);
                     }
                 }
                 LimitExceededAction::DropTag => {
                     tags_map.retain(|key, value| {
-                        if self.try_accept_tag(key, value) {
+                        if limits.try_accept_tag(key, value, mode) {
                             true
                         } else {
                             emit!(TagCardinalityLimitRejectingTag {
@@ -123,6 +181,27 @@ impl TagCardinalityLimit {
                         }
                     });
                 }
+                LimitExceededAction::ReplaceTag => {
+                    // Values are already replaced, so they're always accepted without counting
+                    // towards the limit.
+                    let overflow_set = &self.overflow_set;
+                    let rejected: Vec<String> = tags_map
+                        .iter_sets()
+                        .filter(|(key, value)| {
+                            *value != overflow_set && !limits.try_accept_tag(key, value, mode)
+                        })
+                        .map(|(key, value)| {
+                            emit!(TagCardinalityLimitRejectingTag {
+                                tag_key: key,
+                                tag_value: &value.to_string(),
+                            });
+                            key.to_string()
+                        })
+                        .collect();
+                    for key in rejected {
+                        tags_map.replace(key, self.overflow_value.clone());
+                    }
+                }
             }
         }
         Some(event)
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use vector_core::metric_tags;
 
 use super::*;
@@ -5,7 +7,7 @@ use crate::event::metric::TagValue;
 use crate::event::{metric, Event, Metric, MetricTags};
 use crate::test_util::components::assert_transform_compliance;
 use crate::transforms::tag_cardinality_limit::config::{
-    default_cache_size, BloomFilterConfig, Mode,
+    default_cache_size, BloomFilterConfig, Mode, PerMetricConfig,
 };
 use crate::transforms::test::create_topology;
 use tokio::sync::mpsc;
@@ -27,27 +29,29 @@ fn make_metric(tags: MetricTags) -> Event {
     )
 }
 
-const fn make_transform_hashset(
+fn make_transform_hashset(
     value_limit: u32,
     limit_exceeded_action: LimitExceededAction,
 ) -> TagCardinalityLimitConfig {
     TagCardinalityLimitConfig {
         value_limit,
         limit_exceeded_action,
+        tag_value_limits: HashMap::new(),
+        per_metric_limits: HashMap::new(),
+        overflow_value: "other".to_string(),
         mode: Mode::Exact,
     }
 }
 
-const fn make_transform_bloom(
+fn make_transform_bloom(
     value_limit: u32,
     limit_exceeded_action: LimitExceededAction,
 ) -> TagCardinalityLimitConfig {
     TagCardinalityLimitConfig {
-        value_limit,
-        limit_exceeded_action,
         mode: Mode::Probabilistic(BloomFilterConfig {
             cache_size_per_key: default_cache_size(),
         }),
+        ..make_transform_hashset(value_limit, limit_exceeded_action)
     }
 }
 
@@ -279,3 +283,48 @@ fn drop_event_checks_all_tags(make_tags: impl Fn(&str, &str) -> MetricTags) {
     assert_eq!(new_event3, None);
     assert_eq!(new_event4, Some(event4));
 }
+
+#[test]
+fn replace_tag() {
+    let config = make_transform_hashset(1, LimitExceededAction::ReplaceTag);
+    let mut transform = TagCardinalityLimit::new(config);
+
+    let event1 = make_metric(metric_tags!("tag1" => "val1"));
+    let event2 = make_metric(metric_tags!("tag1" => "val2"));
+    let event3 = make_metric(metric_tags!("tag1" => "val3"));
+
+    assert_eq!(transform.transform_one(event1.clone()), Some(event1));
+    // Values over the limit are replaced, and the replacements don't count towards it.
+    let other = make_metric(metric_tags!("tag1" => "other"));
+    assert_eq!(transform.transform_one(event2), Some(other.clone()));
+    assert_eq!(transform.transform_one(event3), Some(other));
+}
+
+#[test]
+fn per_tag_and_per_metric_limits() {
+    let mut config = make_transform_hashset(1, LimitExceededAction::DropEvent);
+    config.tag_value_limits.insert("tag2".to_string(), 2);
+    config.per_metric_limits.insert(
+        "noisy".to_string(),
+        PerMetricConfig {
+            value_limit: Some(3),
+            limit_exceeded_action: None,
+            tag_value_limits: HashMap::new(),
+        },
+    );
+    let mut transform = TagCardinalityLimit::new(config);
+
+    let tagged = |tag: &'static str, value: &str| make_metric(metric_tags!(tag => value));
+    assert!(transform.transform_one(tagged("tag1", "val1")).is_some());
+    assert!(transform.transform_one(tagged("tag1", "val2")).is_none());
+    assert!(transform.transform_one(tagged("tag2", "val1")).is_some());
+    assert!(transform.transform_one(tagged("tag2", "val2")).is_some());
+    assert!(transform.transform_one(tagged("tag2", "val3")).is_none());
+
+    // Metrics with overrides have their own limits, and values.
+    let noisy = |value: &str| Event::Metric(tagged("tag1", value).into_metric().with_name("noisy"));
+    assert!(transform.transform_one(noisy("val2")).is_some());
+    assert!(transform.transform_one(noisy("val3")).is_some());
+    assert!(transform.transform_one(noisy("val4")).is_some());
+    assert!(transform.transform_one(noisy("val5")).is_none());
+}
@@ -0,0 +1,513 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    num::{NonZeroU64, NonZeroUsize},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use snafu::Snafu;
+use tokio::time::Instant;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    conditions::{AnyCondition, Condition},
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{Event, Value},
+    internal_events::{
+        TailSamplingLateSpanDropped, TailSamplingTraceDropped, TailSamplingTraceKept,
+    },
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// The status code of spans that failed, in OpenTelemetry.
+const STATUS_CODE_ERROR: i64 = 2;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one sampling policy is required"))]
+    NoPolicies,
+}
+
+/// Configuration of the `latency` policy.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LatencyPolicyConfig {
+    /// The duration of traces, from the start of their first span to the end of their last span,
+    /// from which they're kept, in milliseconds.
+    #[configurable(metadata(docs::examples = 5000))]
+    threshold_ms: u64,
+}
+
+/// Configuration of the `probabilistic` policy.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ProbabilisticPolicyConfig {
+    /// The rate at which traces are kept, expressed as `1/N`.
+    ///
+    /// Traces are kept by the hash of their trace ID, so that Vector instances receiving spans of
+    /// the same traces make the same decisions.
+    #[configurable(metadata(docs::examples = 10))]
+    rate: NonZeroU64,
+}
+
+/// Configuration of the `condition` policy.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ConditionPolicyConfig {
+    /// A logical condition, traces with a span matching it are kept.
+    condition: AnyCondition,
+}
+
+/// A policy deciding whether traces are kept.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SamplingPolicyConfig {
+    /// Keeps traces with a span whose `status.code` is that of errors.
+    Error,
+
+    /// Keeps traces that lasted at least `threshold_ms`.
+    Latency(#[configurable(derived)] LatencyPolicyConfig),
+
+    /// Keeps a share of traces.
+    Probabilistic(#[configurable(derived)] ProbabilisticPolicyConfig),
+
+    /// Keeps traces with a span matching a condition.
+    Condition(#[configurable(derived)] ConditionPolicyConfig),
+}
+
+/// Configuration for the `tail_sampling` transform.
+#[configurable_component(transform("tail_sampling"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TailSamplingConfig {
+    /// The policies deciding whether traces are kept.
+    ///
+    /// Traces are kept when any policy keeps them, and dropped otherwise.
+    policies: Vec<SamplingPolicyConfig>,
+
+    /// The name of the field of spans holding their trace ID.
+    ///
+    /// Spans without this field are passed through.
+    #[serde(default = "default_trace_id_field")]
+    trace_id_field: String,
+
+    /// The time spans of traces are buffered for, from their first span, before deciding whether
+    /// they're kept, in seconds.
+    ///
+    /// Spans of traces arriving after their decision follow it, for as long again.
+    #[serde(default = "default_decision_wait_secs")]
+    decision_wait_secs: NonZeroU64,
+
+    /// The maximum number of traces buffered.
+    ///
+    /// Once reached, the oldest traces are decided before the end of their window.
+    #[serde(default = "default_max_traces")]
+    max_traces: NonZeroUsize,
+}
+
+fn default_trace_id_field() -> String {
+    "trace_id".to_string()
+}
+
+fn default_decision_wait_secs() -> NonZeroU64 {
+    NonZeroU64::new(30).expect("static non-zero number")
+}
+
+fn default_max_traces() -> NonZeroUsize {
+    NonZeroUsize::new(50_000).expect("static non-zero number")
+}
+
+impl GenerateConfig for TailSamplingConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"policies = [
+                { type = "error" },
+                { type = "latency", threshold_ms = 5000 },
+                { type = "probabilistic", rate = 10 },
+            ]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for TailSamplingConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        TailSampling::new(self, context).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::trace()
+    }
+
+    fn outputs(&self, merged_definition: &schema::Definition, _: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Trace).with_schema_definition(merged_definition.clone())]
+    }
+}
+
+enum SamplingPolicy {
+    Error,
+    Latency(chrono::Duration),
+    Probabilistic(NonZeroU64),
+    Condition(Condition),
+}
+
+impl SamplingPolicy {
+    fn new(config: &SamplingPolicyConfig, context: &TransformContext) -> crate::Result<Self> {
+        Ok(match config {
+            SamplingPolicyConfig::Error => Self::Error,
+            SamplingPolicyConfig::Latency(config) => {
+                Self::Latency(chrono::Duration::milliseconds(config.threshold_ms as i64))
+            }
+            SamplingPolicyConfig::Probabilistic(config) => Self::Probabilistic(config.rate),
+            SamplingPolicyConfig::Condition(config) => {
+                Self::Condition(config.condition.build(&context.enrichment_tables)?)
+            }
+        })
+    }
+
+    /// Whether the trace of `spans` is kept.
+    fn keeps(&self, trace_id: &str, spans: &mut Vec<Event>) -> bool {
+        match self {
+            Self::Error => spans.iter().any(|span| {
+                span.as_trace().get("status.code") == Some(&Value::Integer(STATUS_CODE_ERROR))
+            }),
+            Self::Latency(threshold) => {
+                let times = |field: &'static str| {
+                    spans
+                        .iter()
+                        .filter_map(move |span: &Event| span.as_trace().get(field))
+                        .filter_map(Value::as_timestamp)
+                };
+                match (
+                    times("start_time_unix_nano").min(),
+                    times("end_time_unix_nano").max(),
+                ) {
+                    (Some(start), Some(end)) => end.signed_duration_since(*start) >= *threshold,
+                    _ => false,
+                }
+            }
+            Self::Probabilistic(rate) => {
+                seahash::hash(trace_id.as_bytes()) <= u64::MAX / rate.get()
+            }
+            Self::Condition(condition) => {
+                let mut keeps = false;
+                *spans = std::mem::take(spans)
+                    .into_iter()
+                    .map(|span| {
+                        if keeps {
+                            return span;
+                        }
+                        let (result, span) = condition.check(span);
+                        keeps = result;
+                        span
+                    })
+                    .collect();
+                keeps
+            }
+        }
+    }
+}
+
+pub struct TailSampling {
+    policies: Vec<SamplingPolicy>,
+    trace_id_field: String,
+    decision_wait: Duration,
+    max_traces: usize,
+    /// The spans of traces waiting for their decision.
+    pending: HashMap<String, Vec<Event>>,
+    /// The traces waiting for their decision, with the end of their window, oldest first.
+    deadlines: VecDeque<(String, Instant)>,
+    /// Whether decided traces were kept, until late spans stop following their decision.
+    decisions: HashMap<String, (bool, Instant)>,
+}
+
+impl TailSampling {
+    pub fn new(config: &TailSamplingConfig, context: &TransformContext) -> crate::Result<Self> {
+        if config.policies.is_empty() {
+            return Err(BuildError::NoPolicies.into());
+        }
+        let policies = config
+            .policies
+            .iter()
+            .map(|policy| SamplingPolicy::new(policy, context))
+            .collect::<crate::Result<_>>()?;
+
+        Ok(Self {
+            policies,
+            trace_id_field: config.trace_id_field.clone(),
+            decision_wait: Duration::from_secs(config.decision_wait_secs.get()),
+            max_traces: config.max_traces.get(),
+            pending: HashMap::new(),
+            deadlines: VecDeque::new(),
+            decisions: HashMap::new(),
+        })
+    }
+
+    fn record(&mut self, event: Event, output: &mut Vec<Event>) {
+        let trace_id = match event.as_trace().get(&self.trace_id_field) {
+            Some(trace_id) => trace_id.to_string_lossy().into_owned(),
+            None => {
+                output.push(event);
+                return;
+            }
+        };
+
+        if let Some((kept, _)) = self.decisions.get(&trace_id) {
+            if *kept {
+                output.push(event);
+            } else {
+                emit!(TailSamplingLateSpanDropped);
+            }
+            return;
+        }
+
+        let now = Instant::now();
+        let decision_wait = self.decision_wait;
+        let deadlines = &mut self.deadlines;
+        self.pending
+            .entry(trace_id)
+            .or_insert_with_key(|trace_id| {
+                deadlines.push_back((trace_id.clone(), now + decision_wait));
+                Vec::new()
+            })
+            .push(event);
+
+        while self.pending.len() > self.max_traces {
+            match self.deadlines.pop_front() {
+                Some((trace_id, _)) => self.decide(trace_id, now, output),
+                None => break,
+            }
+        }
+    }
+
+    /// Decides whether the pending trace `trace_id` is kept, emitting its spans if it is.
+    fn decide(&mut self, trace_id: String, now: Instant, output: &mut Vec<Event>) {
+        let mut spans = match self.pending.remove(&trace_id) {
+            Some(spans) => spans,
+            None => return,
+        };
+        let kept = self
+            .policies
+            .iter()
+            .any(|policy| policy.keeps(&trace_id, &mut spans));
+        if kept {
+            emit!(TailSamplingTraceKept { spans: spans.len() });
+            output.extend(spans);
+        } else {
+            emit!(TailSamplingTraceDropped { spans: spans.len() });
+        }
+        self.decisions
+            .insert(trace_id, (kept, now + self.decision_wait));
+    }
+
+    /// Decides the traces whose window has ended.
+    fn flush_expired(&mut self, output: &mut Vec<Event>) {
+        let now = Instant::now();
+        while self
+            .deadlines
+            .front()
+            .map_or(false, |(_, deadline)| *deadline <= now)
+        {
+            if let Some((trace_id, _)) = self.deadlines.pop_front() {
+                self.decide(trace_id, now, output);
+            }
+        }
+        self.decisions.retain(|_, (_, expiry)| *expiry > now);
+    }
+
+    /// Decides all pending traces, once the input has ended.
+    fn flush_all(&mut self, output: &mut Vec<Event>) {
+        let now = Instant::now();
+        while let Some((trace_id, _)) = self.deadlines.pop_front() {
+            self.decide(trace_id, now, output);
+        }
+    }
+}
+
+impl TaskTransform<Event> for TailSampling {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream = tokio::time::interval(Duration::from_secs(1));
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_expired(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_all(&mut output);
+                                done = true;
+                            }
+                            Some(event) => self.record(event, &mut output),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        event::{LogEvent, TraceEvent},
+        test_util::components::assert_transform_compliance,
+        transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TailSamplingConfig>();
+    }
+
+    fn span(trace_id: &str, start_ms: i64, end_ms: i64, status_code: i64) -> Event {
+        let mut span = TraceEvent::from(LogEvent::default());
+        span.insert("trace_id", trace_id);
+        span.insert("start_time_unix_nano", Utc.timestamp_millis(start_ms));
+        span.insert("end_time_unix_nano", Utc.timestamp_millis(end_ms));
+        span.insert("status.code", status_code);
+        Event::Trace(span)
+    }
+
+    fn sampler(policies: &str) -> TailSampling {
+        let config = toml::from_str::<TailSamplingConfig>(&format!(
+            "decision_wait_secs = 10\npolicies = {}",
+            policies
+        ))
+        .unwrap();
+        TailSampling::new(&config, &TransformContext::default()).unwrap()
+    }
+
+    fn trace_ids(output: &[Event]) -> Vec<String> {
+        output
+            .iter()
+            .map(|span| {
+                span.as_trace()
+                    .get("trace_id")
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn keeps_traces_matching_policies() {
+        tokio::time::pause();
+        let mut sampler =
+            sampler(r#"[{ type = "error" }, { type = "latency", threshold_ms = 1000 }]"#);
+
+        let mut output = Vec::new();
+        for span in [
+            span("failed", 0, 10, 1),
+            span("failed", 10, 20, STATUS_CODE_ERROR),
+            span("slow", 0, 500, 1),
+            span("slow", 600, 1200, 1),
+            span("fast", 0, 500, 1),
+        ] {
+            sampler.record(span, &mut output);
+        }
+        assert!(output.is_empty());
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        sampler.flush_expired(&mut output);
+        let mut kept = trace_ids(&output);
+        kept.sort();
+        assert_eq!(kept, vec!["failed", "failed", "slow", "slow"]);
+
+        // Late spans follow the decision of their trace.
+        output.clear();
+        sampler.record(span("slow", 1200, 1300, 1), &mut output);
+        sampler.record(span("fast", 500, 600, 1), &mut output);
+        assert_eq!(trace_ids(&output), vec!["slow"]);
+    }
+
+    #[tokio::test]
+    async fn decides_oldest_traces_when_full() {
+        let config = toml::from_str::<TailSamplingConfig>(
+            r#"
+max_traces = 1
+policies = [{ type = "error" }]
+"#,
+        )
+        .unwrap();
+        let mut sampler = TailSampling::new(&config, &TransformContext::default()).unwrap();
+
+        let mut output = Vec::new();
+        sampler.record(span("first", 0, 10, STATUS_CODE_ERROR), &mut output);
+        sampler.record(span("second", 0, 10, STATUS_CODE_ERROR), &mut output);
+        assert_eq!(trace_ids(&output), vec!["first"]);
+
+        output.clear();
+        sampler.flush_all(&mut output);
+        assert_eq!(trace_ids(&output), vec!["second"]);
+    }
+
+    #[test]
+    fn probabilistic_policy_keeps_consistent_traces() {
+        let policy = SamplingPolicy::Probabilistic(NonZeroU64::new(4).unwrap());
+        let kept = (0..1000)
+            .filter(|id| policy.keeps(&id.to_string(), &mut Vec::new()))
+            .count();
+        assert!((150..350).contains(&kept));
+        assert!(
+            (0..1000).all(|id| policy.keeps(&id.to_string(), &mut Vec::new())
+                == policy.keeps(&id.to_string(), &mut Vec::new()))
+        );
+    }
+
+    #[test]
+    fn requires_policies() {
+        let config = toml::from_str::<TailSamplingConfig>("policies = []").unwrap();
+        assert!(TailSampling::new(&config, &TransformContext::default()).is_err());
+    }
+
+    #[tokio::test]
+    async fn emits_internal_events() {
+        assert_transform_compliance(async move {
+            let config = toml::from_str::<TailSamplingConfig>(
+                r#"policies = [{ type = "probabilistic", rate = 1 }]"#,
+            )
+            .unwrap();
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            tx.send(span("trace", 0, 10, 1)).await.unwrap();
+
+            // The trace is decided once the input ends.
+            drop(tx);
+            _ = out.recv().await;
+
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await
+    }
+}
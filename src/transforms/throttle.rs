@@ -1,10 +1,15 @@
-use std::{num::NonZeroU32, pin::Pin, time::Duration};
+use std::{
+    num::NonZeroU32,
+    pin::Pin,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use async_stream::stream;
 use futures::{Stream, StreamExt};
 use governor::{clock, Quota, RateLimiter};
+use redis::{aio::ConnectionManager, RedisError};
 use serde_with::serde_as;
-use snafu::Snafu;
+use snafu::{ResultExt, Snafu};
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -12,7 +17,7 @@ use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Input, Output, TransformConfig, TransformContext},
     event::Event,
-    internal_events::{TemplateRenderingError, ThrottleEventDiscarded},
+    internal_events::{TemplateRenderingError, ThrottleBackendError, ThrottleEventDiscarded},
     schema,
     template::Template,
     transforms::{TaskTransform, Transform},
@@ -44,14 +49,58 @@ pub struct ThrottleConfig {
 
     /// A logical condition used to exclude events from sampling.
     exclude: Option<AnyCondition>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    backend: ThrottleBackend,
 }
 
 impl_generate_config_from_default!(ThrottleConfig);
 
+/// Where the number of events allowed for each bucket is kept.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ThrottleBackend {
+    /// Events are counted in memory, so each Vector instance allows the full `threshold`.
+    #[default]
+    Local,
+
+    /// Events are counted in Redis, so that all Vector instances sharing it share the `threshold`.
+    Redis(#[configurable(derived)] RedisBackendConfig),
+}
+
+/// Configuration of the Redis backend.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RedisBackendConfig {
+    /// The URL of the Redis server.
+    #[configurable(metadata(docs::examples = "redis://127.0.0.1:6379/0"))]
+    url: String,
+
+    /// The prefix of the Redis keys events are counted in.
+    ///
+    /// Vector instances enforcing the same rate limit must use the same prefix, and those
+    /// enforcing different ones must use different prefixes.
+    #[serde(default = "default_key_prefix")]
+    #[configurable(metadata(docs::examples = "vector:throttle:api_logs"))]
+    key_prefix: String,
+}
+
+fn default_key_prefix() -> String {
+    "vector:throttle".to_owned()
+}
+
 #[async_trait::async_trait]
 impl TransformConfig for ThrottleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Throttle::new(self, context, clock::MonotonicClock).map(Transform::event_task)
+        let mut throttle = Throttle::new(self, context, clock::MonotonicClock)?;
+        if let ThrottleBackend::Redis(config) = &self.backend {
+            throttle.redis =
+                Some(RedisLimiter::new(config, self.threshold, self.window_secs).await?);
+        }
+        Ok(Transform::event_task(throttle))
     }
 
     fn input(&self) -> Input {
@@ -71,6 +120,72 @@ pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
     key_field: Option<Template>,
     exclude: Option<Condition>,
     clock: C,
+    redis: Option<RedisLimiter>,
+}
+
+/// Counts the events of buckets in Redis, in fixed windows of `window_secs` shared by all Vector
+/// instances through their wall clocks.
+#[derive(Clone)]
+struct RedisLimiter {
+    connection: ConnectionManager,
+    key_prefix: String,
+    threshold: u32,
+    window: Duration,
+}
+
+impl RedisLimiter {
+    async fn new(
+        config: &RedisBackendConfig,
+        threshold: u32,
+        window: Duration,
+    ) -> crate::Result<Self> {
+        let client = redis::Client::open(config.url.as_str()).context(RedisConnectSnafu)?;
+        let connection = client
+            .get_tokio_connection_manager()
+            .await
+            .context(RedisConnectSnafu)?;
+        Ok(Self {
+            connection,
+            key_prefix: config.key_prefix.clone(),
+            threshold,
+            window,
+        })
+    }
+
+    /// The Redis key the events of `key` are counted in during the window of `now`.
+    fn redis_key(&self, key: Option<&str>, now: Duration) -> String {
+        let window = now.as_millis() / self.window.as_millis().max(1);
+        match key {
+            Some(key) => format!("{}:{}:{}", self.key_prefix, window, key),
+            None => format!("{}:{}", self.key_prefix, window),
+        }
+    }
+
+    /// Counts an event of `key`, and returns whether it's within the threshold.
+    ///
+    /// Events are allowed when Redis can't be reached, so that an outage of Redis doesn't drop all
+    /// events.
+    async fn check(&mut self, key: Option<&str>) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let redis_key = self.redis_key(key, now);
+        // Keys outlive their window, so that instances with slightly skewed clocks still share it.
+        let expiry_ms = (self.window.as_millis() * 2) as usize;
+        let result: Result<(u64, bool), RedisError> = redis::pipe()
+            .atomic()
+            .incr(&redis_key, 1)
+            .pexpire(&redis_key, expiry_ms)
+            .query_async(&mut self.connection)
+            .await;
+        match result {
+            Ok((count, _)) => count <= u64::from(self.threshold),
+            Err(error) => {
+                emit!(ThrottleBackendError { error });
+                true
+            }
+        }
+    }
 }
 
 impl<C, I> Throttle<C, I>
@@ -108,6 +223,7 @@ where
             flush_keys_interval,
             key_field: config.key_field.clone(),
             exclude,
+            redis: None,
         })
     }
 }
@@ -127,6 +243,7 @@ where
         let mut flush_keys = tokio::time::interval(self.flush_keys_interval * 2);
 
         let limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
+        let mut redis = self.redis.clone();
 
         Box::pin(stream! {
           loop {
@@ -157,11 +274,15 @@ where
                                         .ok()
                                 });
 
-                                match limiter.check_key(&key) {
-                                    Ok(()) => {
+                                let allowed = match redis.as_mut() {
+                                    Some(redis) => redis.check(key.as_deref()).await,
+                                    None => limiter.check_key(&key).is_ok(),
+                                };
+                                match allowed {
+                                    true => {
                                         Some(event)
                                     }
-                                    _ => {
+                                    false => {
                                         if let Some(key) = key {
                                             emit!(ThrottleEventDiscarded{key})
                                         } else {
@@ -195,6 +316,8 @@ where
 pub enum ConfigError {
     #[snafu(display("`threshold`, and `window_secs` must be non-zero"))]
     NonZero,
+    #[snafu(display("Failed to connect to Redis: {}", source))]
+    RedisConnect { source: RedisError },
 }
 
 #[cfg(test)]
@@ -413,6 +536,7 @@ key_field = "{{ bucket }}"
                 window_secs: Duration::from_secs_f64(1.0),
                 key_field: None,
                 exclude: None,
+                backend: ThrottleBackend::Local,
             };
             let (tx, rx) = mpsc::channel(1);
             let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
@@ -428,4 +552,22 @@ key_field = "{{ bucket }}"
         })
         .await
     }
+
+    #[test]
+    fn parses_redis_backend() {
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 2
+window_secs = 5
+backend.type = "redis"
+backend.url = "redis://127.0.0.1:6379/0"
+"#,
+        )
+        .unwrap();
+
+        match config.backend {
+            ThrottleBackend::Redis(redis) => assert_eq!(redis.key_prefix, "vector:throttle"),
+            ThrottleBackend::Local => panic!("Expected the Redis backend"),
+        }
+    }
 }
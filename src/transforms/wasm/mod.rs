@@ -0,0 +1,202 @@
+//! The `wasm` transform.
+//!
+//! Processes logs with WebAssembly modules implementing its guest ABI, which can read, set, and
+//! remove the fields of logs, emit new logs, and drop logs.
+
+mod runtime;
+
+use std::{path::PathBuf, pin::Pin};
+
+use futures::{stream, Stream, StreamExt};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use self::runtime::Runtime;
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    emit,
+    event::Event,
+    internal_events::WasmExecutionError,
+    schema::Definition,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `wasm` transform.
+#[configurable_component(transform("wasm"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WasmConfig {
+    /// The path of the WebAssembly module logs are processed with.
+    ///
+    /// The module must implement the guest ABI of the transform, and can be in either the binary or
+    /// the text format.
+    #[configurable(metadata(docs::examples = "/etc/vector/modules/enrich.wasm"))]
+    pub module: PathBuf,
+
+    /// The most fuel the module can consume processing each log.
+    ///
+    /// Each WebAssembly instruction run consumes fuel, so this bounds how long logs are processed
+    /// for. Logs whose processing runs out of fuel are dropped.
+    #[serde(default = "default_fuel")]
+    pub fuel: u64,
+
+    /// The most memory, in bytes, the module can use.
+    ///
+    /// Modules whose initial memory is larger fail to load, and attempts to grow memory beyond it
+    /// fail.
+    #[serde(default = "default_memory_limit")]
+    pub memory_limit: usize,
+}
+
+const fn default_fuel() -> u64 {
+    10_000_000
+}
+
+const fn default_memory_limit() -> usize {
+    64 * 1024 * 1024
+}
+
+impl GenerateConfig for WasmConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"module = "/etc/vector/modules/enrich.wasm""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for WasmConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        let runtime =
+            Runtime::new(&self.module, self.fuel, self.memory_limit).map_err(|error| {
+                format!(
+                    "Could not load WebAssembly module {:?}: {:#}",
+                    self.module, error
+                )
+            })?;
+        Ok(Transform::event_task(runtime))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, merged_definition: &Definition, _: LogNamespace) -> Vec<Output> {
+        // Modules can change logs in any way.
+        let definition = Definition::default_for_namespace(merged_definition.log_namespaces());
+        vec![Output::default(DataType::Log).with_schema_definition(definition)]
+    }
+}
+
+impl TaskTransform<Event> for Runtime {
+    fn transform(
+        mut self: Box<Self>,
+        input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        Box::pin(input_rx.flat_map(move |event| {
+            let mut output = Vec::new();
+            if let Err(error) = self.process(event.into_log(), &mut output) {
+                emit!(WasmExecutionError { error });
+            }
+            stream::iter(output)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::event::{LogEvent, Value};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WasmConfig>();
+    }
+
+    /// A module setting `processed` on logs and emitting a new log for each of them, dropping
+    /// those with a `drop` field, and looping forever on those with a `loop` field.
+    const MODULE: &str = r#"
+        (module
+          (import "vector" "get_field" (func $get_field (param i32 i32) (result i64)))
+          (import "vector" "set_field" (func $set_field (param i32 i32 i32 i32) (result i32)))
+          (import "vector" "emit" (func $emit (param i32 i32) (result i32)))
+          (memory (export "memory") 1)
+          (global $next (mut i32) (i32.const 1024))
+          (data (i32.const 0) "processed")
+          (data (i32.const 16) "true")
+          (data (i32.const 32) "drop")
+          (data (i32.const 48) "loop")
+          (data (i32.const 64) "{\"message\":\"emitted\"}")
+          (func (export "vector_alloc") (param $len i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr (global.get $next))
+            (global.set $next (i32.add (global.get $next) (local.get $len)))
+            (local.get $ptr))
+          (func (export "vector_process") (result i32)
+            (if (i64.ge_s (call $get_field (i32.const 48) (i32.const 4)) (i64.const 0))
+              (then (loop $forever (br $forever))))
+            (if (i64.ge_s (call $get_field (i32.const 32) (i32.const 4)) (i64.const 0))
+              (then (return (i32.const 0))))
+            (drop (call $set_field (i32.const 0) (i32.const 9) (i32.const 16) (i32.const 4)))
+            (drop (call $emit (i32.const 64) (i32.const 21)))
+            (i32.const 1)))
+    "#;
+
+    fn runtime(module: &str, memory_limit: usize) -> anyhow::Result<Runtime> {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "{}", module).unwrap();
+        Runtime::new(file.path(), default_fuel(), memory_limit)
+    }
+
+    fn log(field: &str) -> LogEvent {
+        let mut log = LogEvent::from("original");
+        log.insert(field, true);
+        log
+    }
+
+    #[test]
+    fn processes_logs() {
+        let mut runtime = runtime(MODULE, default_memory_limit()).unwrap();
+
+        let mut output = Vec::new();
+        runtime.process(log("keep"), &mut output).unwrap();
+        assert_eq!(output.len(), 2);
+        assert_eq!(output[0].as_log()["message"], "original".into());
+        assert_eq!(output[0].as_log()["processed"], Value::Boolean(true));
+        assert_eq!(output[1].as_log()["message"], "emitted".into());
+
+        let mut output = Vec::new();
+        runtime.process(log("drop"), &mut output).unwrap();
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn limits_fuel() {
+        let mut runtime = runtime(MODULE, default_memory_limit()).unwrap();
+
+        let mut output = Vec::new();
+        assert!(runtime.process(log("loop"), &mut output).is_err());
+        assert!(output.is_empty());
+
+        // Logs after one running out of fuel get fuel again.
+        runtime.process(log("keep"), &mut output).unwrap();
+        assert_eq!(output.len(), 2);
+    }
+
+    #[test]
+    fn limits_memory() {
+        assert!(runtime(MODULE, 32 * 1024).is_err());
+    }
+
+    #[test]
+    fn requires_guest_exports() {
+        assert!(runtime(
+            r#"(module (memory (export "memory") 1))"#,
+            default_memory_limit()
+        )
+        .is_err());
+    }
+}
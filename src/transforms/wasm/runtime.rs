@@ -0,0 +1,232 @@
+//! The host side of the guest ABI, running modules with wasmtime.
+
+use std::path::Path;
+
+use lookup::lookup_v2::parse_value_path;
+use wasmtime::{
+    Caller, Config, Engine, Extern, InstancePre, Linker, Memory, Module, Store, StoreLimits,
+    StoreLimitsBuilder, TypedFunc,
+};
+
+use crate::event::{Event, LogEvent, Value};
+
+/// The module the functions provided to guests are imported from.
+const HOST_MODULE: &str = "vector";
+
+/// The state of an instance of a module, along with the event it's processing.
+struct State {
+    log: Option<LogEvent>,
+    emitted: Vec<Event>,
+    limits: StoreLimits,
+}
+
+/// An instance of a module, with the function processing events it exports.
+struct Instance {
+    store: Store<State>,
+    process: TypedFunc<(), i32>,
+}
+
+/// Runs a module processing events, within limits of fuel and memory.
+pub(super) struct Runtime {
+    engine: Engine,
+    module: InstancePre<State>,
+    fuel: u64,
+    memory_limit: usize,
+    instance: Instance,
+}
+
+impl Runtime {
+    pub(super) fn new(path: &Path, fuel: u64, memory_limit: usize) -> anyhow::Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+
+        let mut linker = Linker::new(&engine);
+        linker.func_wrap(HOST_MODULE, "get_field", get_field)?;
+        linker.func_wrap(HOST_MODULE, "set_field", set_field)?;
+        linker.func_wrap(HOST_MODULE, "remove_field", remove_field)?;
+        linker.func_wrap(HOST_MODULE, "emit", emit)?;
+        let module = linker.instantiate_pre(&Module::from_file(&engine, path)?)?;
+
+        let instance = instantiate(&engine, &module, fuel, memory_limit)?;
+        Ok(Self {
+            engine,
+            module,
+            fuel,
+            memory_limit,
+            instance,
+        })
+    }
+
+    /// Processes `log` with the module, pushing the events it keeps and emits to `output`.
+    pub(super) fn process(&mut self, log: LogEvent, output: &mut Vec<Event>) -> anyhow::Result<()> {
+        let store = &mut self.instance.store;
+        // Each event gets the same fuel, however much was left by the previous one.
+        let remaining = store.consume_fuel(0)?;
+        store.add_fuel(self.fuel.saturating_sub(remaining))?;
+        store.data_mut().log = Some(log);
+
+        let result = self.instance.process.call(&mut *store, ());
+        let state = store.data_mut();
+        let log = state.log.take();
+        match result {
+            Ok(keep) => {
+                if keep != 0 {
+                    output.extend(log.map(Event::from));
+                }
+                output.append(&mut state.emitted);
+                Ok(())
+            }
+            Err(error) => {
+                state.emitted.clear();
+                // Modules trapping may have been left in any state, so the next events are
+                // processed by a new instance.
+                if let Ok(instance) =
+                    instantiate(&self.engine, &self.module, self.fuel, self.memory_limit)
+                {
+                    self.instance = instance;
+                }
+                Err(error)
+            }
+        }
+    }
+}
+
+fn instantiate(
+    engine: &Engine,
+    module: &InstancePre<State>,
+    fuel: u64,
+    memory_limit: usize,
+) -> anyhow::Result<Instance> {
+    let state = State {
+        log: None,
+        emitted: Vec::new(),
+        limits: StoreLimitsBuilder::new().memory_size(memory_limit).build(),
+    };
+    let mut store = Store::new(engine, state);
+    store.limiter(|state| &mut state.limits);
+    store.add_fuel(fuel)?;
+
+    let instance = module.instantiate(&mut store)?;
+    // Checked here, rather than when events are processed.
+    instance.get_typed_func::<i32, i32>(&mut store, "vector_alloc")?;
+    let process = instance.get_typed_func::<(), i32>(&mut store, "vector_process")?;
+    if let Some(init) = instance.get_func(&mut store, "vector_init") {
+        init.typed::<(), ()>(&store)?.call(&mut store, ())?;
+    }
+    Ok(Instance { store, process })
+}
+
+fn memory(caller: &mut Caller<'_, State>) -> anyhow::Result<Memory> {
+    caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| anyhow::Error::msg("module doesn't export `memory`"))
+}
+
+/// Reads the `len` bytes at `ptr` of the memory of the guest.
+fn read(caller: &mut Caller<'_, State>, ptr: i32, len: i32) -> anyhow::Result<Vec<u8>> {
+    let memory = memory(caller)?;
+    let mut bytes = vec![0; len as u32 as usize];
+    memory.read(&*caller, ptr as u32 as usize, &mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_string(caller: &mut Caller<'_, State>, ptr: i32, len: i32) -> anyhow::Result<String> {
+    Ok(String::from_utf8(read(caller, ptr, len)?)?)
+}
+
+/// Reads a JSON value from the memory of the guest, or `None` when it isn't valid JSON.
+fn read_json(
+    caller: &mut Caller<'_, State>,
+    ptr: i32,
+    len: i32,
+) -> anyhow::Result<Option<serde_json::Value>> {
+    Ok(serde_json::from_slice(&read(caller, ptr, len)?).ok())
+}
+
+/// Writes `bytes` to memory allocated by the `vector_alloc` function of the guest, returning
+/// where.
+fn write(caller: &mut Caller<'_, State>, bytes: &[u8]) -> anyhow::Result<i32> {
+    let alloc = caller
+        .get_export("vector_alloc")
+        .and_then(Extern::into_func)
+        .ok_or_else(|| anyhow::Error::msg("module doesn't export `vector_alloc`"))?
+        .typed::<i32, i32>(&*caller)?;
+    let ptr = alloc.call(&mut *caller, i32::try_from(bytes.len())?)?;
+    memory(caller)?.write(&mut *caller, ptr as u32 as usize, bytes)?;
+    Ok(ptr)
+}
+
+/// `get_field(path_ptr, path_len) -> i64`: the JSON of the value of the field, in memory
+/// allocated with `vector_alloc`, with its pointer in the upper 32 bits and its length in the
+/// lower ones, or -1 when the event doesn't have the field.
+fn get_field(mut caller: Caller<'_, State>, path_ptr: i32, path_len: i32) -> anyhow::Result<i64> {
+    let path = read_string(&mut caller, path_ptr, path_len)?;
+    let json = match caller
+        .data()
+        .log
+        .as_ref()
+        .and_then(|log| log.get(path.as_str()))
+    {
+        Some(value) => serde_json::to_vec(value)?,
+        None => return Ok(-1),
+    };
+    let ptr = write(&mut caller, &json)?;
+    Ok((i64::from(ptr as u32) << 32) | json.len() as i64)
+}
+
+/// `set_field(path_ptr, path_len, value_ptr, value_len) -> i32`: sets the field to the JSON value,
+/// returning 0, or -1 when the path or the JSON isn't valid.
+fn set_field(
+    mut caller: Caller<'_, State>,
+    path_ptr: i32,
+    path_len: i32,
+    value_ptr: i32,
+    value_len: i32,
+) -> anyhow::Result<i32> {
+    let path = read_string(&mut caller, path_ptr, path_len)?;
+    let value = read_json(&mut caller, value_ptr, value_len)?;
+    match (caller.data_mut().log.as_mut(), value) {
+        (Some(log), Some(value)) if parse_value_path(&path).is_ok() => {
+            log.insert(path.as_str(), Value::from(value));
+            Ok(0)
+        }
+        _ => Ok(-1),
+    }
+}
+
+/// `remove_field(path_ptr, path_len) -> i32`: removes the field, returning 0, or -1 when the
+/// event doesn't have it.
+fn remove_field(
+    mut caller: Caller<'_, State>,
+    path_ptr: i32,
+    path_len: i32,
+) -> anyhow::Result<i32> {
+    let path = read_string(&mut caller, path_ptr, path_len)?;
+    let removed = caller
+        .data_mut()
+        .log
+        .as_mut()
+        .and_then(|log| log.remove(path.as_str()));
+    Ok(if removed.is_some() { 0 } else { -1 })
+}
+
+/// `emit(value_ptr, value_len) -> i32`: emits a new event with the JSON object as its fields,
+/// returning 0, or -1 when the JSON isn't a valid object.
+fn emit(mut caller: Caller<'_, State>, value_ptr: i32, value_len: i32) -> anyhow::Result<i32> {
+    let value = read_json(&mut caller, value_ptr, value_len)?;
+    let state = caller.data_mut();
+    match (value, &state.log) {
+        (Some(value @ serde_json::Value::Object(_)), Some(log)) => {
+            // New events are acknowledged along with the event they're emitted for.
+            let metadata = log.metadata().clone();
+            state.emitted.push(Event::from(LogEvent::from_parts(
+                Value::from(value),
+                metadata,
+            )));
+            Ok(0)
+        }
+        _ => Ok(-1),
+    }
+}
@@ -285,6 +285,16 @@ cli: {
 					_short:      "m"
 					description: "Event output includes the associated component_id as metadata. The actual event is nested under an `event` key."
 				}
+				"delivery-status": {
+					_short: "d"
+					description: """
+						Events are held until their final delivery status is known, which is included in
+						their metadata. Implies `--meta`. The status of events is only tracked when
+						acknowledgements are enabled for the sinks they're sent to. Sinks whose
+						destinations don't acknowledge events, such as the `console`, `socket`, and
+						`websocket` sinks, report events as delivered once they're sent.
+						"""
+				}
 				"no-reconnect": {
 					_short:      "n"
 					description: "Whether to reconnect if the underlying Vector API connection drops. By default, tap will attempt to reconnect if the connection drops."
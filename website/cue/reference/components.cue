@@ -140,6 +140,11 @@ components: {
 			outputs: #Outputs
 		}
 
+		if Kind == "sink" {
+			// Sinks only have outputs for the events they fail to deliver.
+			outputs?: [#Output, ...#Output]
+		}
+
 		// `support` communicates the varying levels of support of the component.
 		support: #Support & {_args: kind: Kind}
 
@@ -842,6 +847,20 @@ components: {
 				description: "Configures the authentication strategy."
 				required:    false
 				type: object: options: {
+					client_id: {
+						description: "The identifier of the client, for OAuth2 authentication."
+						required:    true
+						type: string: {
+							examples: ["vector"]
+						}
+					}
+					client_secret: {
+						description: "The secret of the client, for OAuth2 authentication."
+						required:    true
+						type: string: {
+							examples: ["${OAUTH2_CLIENT_SECRET}"]
+						}
+					}
 					password: {
 						description: "The basic authentication password."
 						required:    true
@@ -849,6 +868,24 @@ components: {
 							examples: [Args.password_example, "password"]
 						}
 					}
+					refresh_before_secs: {
+						common:      false
+						description: "How long before they expire OAuth2 access tokens are refreshed, in seconds."
+						required:    false
+						type: uint: {
+							default: 60
+							unit:    "seconds"
+						}
+					}
+					scopes: {
+						common:      false
+						description: "The scopes to request OAuth2 access tokens for."
+						required:    false
+						type: array: {
+							default: []
+							items: type: string: examples: ["logs:write"]
+						}
+					}
 					strategy: {
 						description: "The authentication strategy to use."
 						required:    true
@@ -856,6 +893,7 @@ components: {
 							enum: {
 								basic:  "The [basic authentication strategy](\(urls.basic_auth))."
 								bearer: "The bearer token authentication strategy."
+								oauth2: "The OAuth2 client credentials authentication strategy, with access tokens refreshed before they expire."
 							}
 						}
 					}
@@ -866,6 +904,13 @@ components: {
 							examples: ["${API_TOKEN}", "xyz123"]
 						}
 					}
+					token_endpoint: {
+						description: "The token endpoint of the authorization server, for OAuth2 authentication."
+						required:    true
+						type: string: {
+							examples: ["https://auth.example.com/oauth2/token"]
+						}
+					}
 					user: {
 						description: "The basic authentication user name."
 						required:    true
@@ -331,6 +331,22 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		dead_letter: {
+			common:      false
+			description: """
+				The component the events this sink fails to deliver are sent to.
+
+				Events the sink rejects, or gives up retrying to deliver, are sent to this transform or
+				sink through the `dead_letter` output of the sink, annotated with the details of the
+				failure. Their sources are then acknowledged once they are delivered by this component.
+				"""
+			required:    false
+			type: string: {
+				default: null
+				examples: ["my_dlq_sink"]
+			}
+		}
+
 		if features.healthcheck != _|_ {
 			if features.healthcheck.enabled {
 				healthcheck: {
@@ -590,6 +606,27 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		dead_letters: {
+			title: "Dead letters"
+			body: """
+				Events this sink fails to deliver, because they are rejected or exhaust the retries
+				of their requests, are dropped by default. When the [`dead_letter`](#dead_letter)
+				option names a transform or sink, they are instead sent to that component through the
+				`dead_letter` output of this sink, such as `foo.dead_letter` for a sink named `foo`,
+				which is added to the inputs of that component.
+
+				Dead-lettered logs and traces are annotated with a `dead_letter` object, holding the
+				`component_id` and `component_type` of this sink and the delivery `status`, either
+				`errored` or `rejected`. It is written under the `metadata_key` of the
+				[log schema](\(urls.vector_log_schema)), or in the `vector` metadata of logs with the
+				`vector` log namespace. Metrics are tagged with `dead_letter_component_id`,
+				`dead_letter_component_type` and `dead_letter_status` instead.
+
+				When acknowledgements are enabled, the sources of dead-lettered events are only
+				acknowledged once the dead-letter component delivers them.
+				"""
+		}
+
 		if features.healthcheck.enabled {
 			healthchecks: {
 				title: "Health checks"
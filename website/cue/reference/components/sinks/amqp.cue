@@ -13,7 +13,7 @@ components: sinks: amqp: {
 	}
 
 	features: {
-		acknowledgements: false
+		acknowledgements: true
 		buffer: enabled:      true
 		healthcheck: enabled: true
 		send: {
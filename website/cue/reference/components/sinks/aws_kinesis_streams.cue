@@ -137,6 +137,27 @@ components: sinks: aws_kinesis_streams: components._aws & {
 				},
 			]
 		}
+		aggregation: {
+			title: "Record aggregation"
+			body:  """
+				When `aggregate` is enabled, Vector packs the events of each batch into records in
+				the [aggregation format](\(urls.aws_kinesis_aggregation)) of the Kinesis Producer
+				Library, of up to 1 MB each. Kinesis bills and limits streams by their records, so
+				aggregating many small events into few records lowers both the cost and the number of
+				shards a stream needs.
+
+				Consumers must deaggregate the records. The Kinesis Client Library does so on its
+				own, while AWS Lambda functions and other consumers need one of the deaggregation
+				libraries of the Kinesis Producer Library.
+
+				Each event keeps its partition key inside the record, but the record is routed to a
+				shard by the partition key of its first event. When `partition_key_field` is set,
+				batches hold the events of a single partition key, so the events of a key still
+				land on the same shard, in order. Without it, the events of a record share that
+				key. Since aggregated batches are sent in a handful of records, the `batch.max_events`
+				limit of 500 records a request no longer applies, and it can be raised.
+				"""
+		}
 	}
 
 	permissions: iam: [
@@ -254,6 +254,16 @@ components: sinks: aws_s3: components._aws & {
 	}
 
 	how_it_works: {
+		adaptive_concurrency_per_key_prefix: {
+			title: "Adaptive Concurrency Per Key Prefix"
+			body: """
+				With adaptive `request.concurrency`, the concurrency limit is
+				adjusted separately for each rendered `key_prefix`, so that a
+				prefix whose uploads slow down doesn't throttle the uploads of
+				the others. A fixed concurrency limit is shared by all prefixes.
+				"""
+		}
+
 		cross_account: {
 			title: "Cross account object writing"
 			body:  """
@@ -266,6 +276,21 @@ components: sinks: aws_s3: components._aws & {
 				"""
 		}
 
+		multipart_upload: {
+			title: "Multipart uploads"
+			body:  """
+				A single request can't upload objects larger than 5 GB. To write larger batches, or to
+				upload objects over slow links in smaller requests, set the `multipart_upload` option:
+				objects larger than `multipart_upload.part_size_bytes` are then uploaded in parts, of
+				which `multipart_upload.concurrency` are uploaded at once. Objects are only as large as
+				their batches, so `batch.max_bytes` must also be raised for objects to be uploaded in
+				parts.
+
+				Uploads failing to complete are aborted, so that S3 discards their parts, and are
+				retried as a whole.
+				"""
+		}
+
 		object_acl: {
 			title: "Object Access Control List (ACL)"
 			body:  """
@@ -287,6 +312,19 @@ components: sinks: aws_s3: components._aws & {
 			]
 		}
 
+		object_lock: {
+			title: "Object Lock retention"
+			body:  """
+				Objects can be archived in WORM (write once, read many) storage by setting the
+				`object_lock` option, which places each object under
+				[Object Lock](\(urls.aws_s3_object_lock)) retention from its creation until
+				`object_lock.retention_days` days later. The bucket must have Object Lock enabled,
+				which requires uploads to carry a checksum: Vector always sends the MD5 checksum of
+				objects, and also sends their SHA-256 checksum if `checksum_algorithm` is set to
+				`SHA256`.
+				"""
+		}
+
 		object_naming: {
 			title: "Object naming"
 			body:  """
@@ -335,6 +373,36 @@ components: sinks: aws_s3: components._aws & {
 				"""
 		}
 
+		partitioned_parquet: {
+			title: "Partitioned Parquet datasets"
+			body:  """
+				Vector can write objects laid out as partitioned datasets, which query engines such
+				as Athena, Spark or Trino read directly. Each entry of
+				[`partition_by`](#partition_by) adds a Hive-style `name=value/` directory to the
+				keys of objects, after the [`key_prefix`](#key_prefix), which is empty by default
+				then:
+
+				```text
+				dt=2023-01-10/region=eu-west-1/1673353800-fddd7a0e-fad9-4f7e-9bce-00ae5debc563.parquet
+				```
+
+				Values are escaped the way Hive escapes them, and events whose values are empty, or
+				are missing the fields they are rendered from, are written to the
+				`__HIVE_DEFAULT_PARTITION__` partition.
+
+				Setting [`batch_encoding.codec`](#batch_encoding.codec) to `parquet` writes each
+				batch as an [Apache Parquet](\(urls.apache_parquet)) file, with the columns of
+				[`batch_encoding.schema`](#batch_encoding.schema). Parquet files are compressed
+				internally, so [`compression`](#compression) isn't applied to them. Parquet output is only
+				available in builds of Vector with the `codecs-parquet` feature, which the default builds
+				don't include.
+
+				Each partition is batched on its own, so a new object is written to it whenever its
+				batch reaches [`batch.max_bytes`](#batch.max_bytes) or
+				[`batch.timeout_secs`](#batch.timeout_secs).
+				"""
+		}
+
 		server_side_encryption: {
 			title: "Server-Side Encryption (SSE)"
 			body:  """
@@ -99,6 +99,36 @@ components: sinks: aws_sqs: components._aws & {
 				syntax: "template"
 			}
 		}
+		content_based_deduplication: {
+			common:      false
+			description: """
+			Whether to deduplicate messages by their body. When enabled, the deduplication ID of messages is the
+			SHA-256 hash of their body, as with the content-based deduplication of FIFO queues. Can be applied only
+			to FIFO queues, and not along with `message_deduplication_id`.
+			"""
+			required:    false
+			type: bool: default: false
+		}
+	}
+
+	how_it_works: {
+		fifo_queues: {
+			title: "FIFO queues"
+			body:  """
+				Queues whose URL ends with `.fifo` are [FIFO queues](\(urls.aws_sqs_fifo)), which
+				require the `message_group_id` option. Messages of the same group are delivered in the
+				order they were sent, so the group ID template should render the same value for
+				events whose order matters, such as the name of their application. Events whose group
+				ID or deduplication ID template fails to render are dropped, instead of being sent
+				without them.
+
+				FIFO queues also drop messages whose deduplication ID was already sent in the last
+				five minutes. The deduplication ID is either rendered from the
+				`message_deduplication_id` template, the SHA-256 hash of the message body when
+				`content_based_deduplication` is enabled, or left to the queue, which rejects messages
+				without a deduplication ID unless content-based deduplication is enabled on it.
+				"""
+		}
 	}
 
 	input: {
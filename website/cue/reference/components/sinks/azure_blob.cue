@@ -166,6 +166,21 @@ components: sinks: azure_blob: {
 				[`blob_time_format`](#blob_time_format), and [`blob_append_uuid`](#blob_append_uuid) options.
 				"""
 		}
+		append_blobs: {
+			title: "Append blobs"
+			body:  """
+				Writing each batch to a new block blob creates many small blobs at high throughput. With
+				[`blob_type`](#blob_type) set to `append`, Vector instead appends batches to an append
+				blob per blob prefix, named like block blobs are when it's created, and rolls over to a
+				new blob once the current one would exceed [`append.max_bytes`](#append.max_bytes), or
+				reached the age of [`append.max_age_secs`](#append.max_age_secs).
+
+				Batches are appended to each blob in order. Batches larger than 4 MiB are appended in
+				several blocks, so readers can observe them partially appended, and retried requests can
+				append some of their blocks twice. With `gzip` compression, each batch is appended as a
+				separate gzip member, which gzip decompresses as a single stream.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
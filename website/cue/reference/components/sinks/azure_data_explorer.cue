@@ -0,0 +1,122 @@
+package metadata
+
+components: sinks: azure_data_explorer: {
+	title: "Azure Data Explorer"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    100_000_000
+				timeout_secs: 300.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.azure_data_explorer
+
+				interface: {
+					socket: {
+						api: {
+							title: "Azure Data Explorer ingestion"
+							url:   urls.azure_data_explorer_ingestion
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The table must already exist, and the identity Vector authenticates as must have the
+				`Database Ingestor` role on its database.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.azure_data_explorer.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		rows: {
+			title: "Rows"
+			body: """
+				Each event is ingested as a row of the table, from a line of JSON. Without a `mapping`, the
+				values of the top-level fields of events are ingested into the columns of the same names.
+				With one, the row is built with the [JSON ingestion mapping](\(urls.azure_data_explorer_ingestion_mappings))
+				of the table of that name.
+				"""
+		}
+		ingestion_modes: {
+			title: "Ingestion modes"
+			body: """
+				With queued ingestion, the default, each batch is uploaded as a gzipped blob to the
+				temporary storage of the cluster, and a message referencing it is posted to one of its
+				ingestion queues. The cluster then ingests queued blobs in the background, aggregating
+				them as set by the ingestion batching policy of the table, so events become available
+				after a few minutes. Events are acknowledged once their message is queued.
+
+				With [streaming ingestion](\(urls.azure_data_explorer_streaming_ingestion)), each
+				batch is sent to the engine of the cluster, which ingests it before responding, so events
+				are available within seconds. Streaming ingestion must be enabled on the cluster and the
+				table, and batches are limited to 4MB of uncompressed data.
+				"""
+		}
+		authentication: {
+			title: "Authentication"
+			body: """
+				Requests are authenticated with tokens from Azure Active Directory. By default, the
+				credentials of the environment are used, such as those of managed identities, of the
+				`AZURE_CLIENT_ID`, `AZURE_CLIENT_SECRET` and `AZURE_TENANT_ID` environment variables, or of
+				the Azure CLI. Set `auth.strategy` to `client_secret` to authenticate as an application
+				with its client secret instead.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
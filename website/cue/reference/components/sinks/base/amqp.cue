@@ -176,6 +176,13 @@ base: components: sinks: amqp: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					pretty: """
+						Encodes an event as human-readable text, writing each field on its own line, with aligned
+						keys and colorized values.
+
+						Events are separated by an empty line. This encoding is meant for reading events while
+						developing, such as in a terminal, not for being parsed.
+						"""
 					raw_message: """
 						No encoding.
 
@@ -206,6 +213,30 @@ base: components: sinks: amqp: configuration: {
 				required:    false
 				type: array: items: type: string: syntax: "literal"
 			}
+			pretty: {
+				description:   "Pretty-specific encoder options."
+				relevant_when: "codec = \"pretty\""
+				required:      false
+				type: object: options: {
+					colors: {
+						description: """
+							Whether keys and values are colorized with ANSI escape codes.
+
+							Pagers such as `less` show colors with their `-R` option.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					timezone: {
+						description: "The timezone timestamps are shown in."
+						required:    false
+						type: string: {
+							default: "local"
+							examples: ["local", "America/New_York", "EST5EDT"]
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
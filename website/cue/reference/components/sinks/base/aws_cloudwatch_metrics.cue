@@ -217,6 +217,62 @@ base: components: sinks: aws_cloudwatch_metrics: configuration: {
 					}
 				}
 			}
+			circuit_breaker: {
+				description: """
+					Configuration of the circuit breaker of outbound requests.
+
+					While the circuit is open, requests aren't sent to the service that keeps failing, and are
+					retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+					passed, a single request is sent to probe the service, and the circuit is closed again if it
+					succeeds.
+					"""
+				required: false
+				type: object: {
+					default: {
+						enabled:              false
+						error_rate_threshold: 0.5
+						minimum_requests:     20
+						open_duration_secs:   30
+						window_secs:          60
+					}
+					options: {
+						enabled: {
+							description: "Whether or not the circuit breaker is enabled."
+							required:    false
+							type: bool: default: false
+						}
+						error_rate_threshold: {
+							description: """
+																The rate of failed requests at which the circuit is opened.
+
+																Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																time out, fail to connect, or have an error or response which would be retried.
+																"""
+							required: false
+							type: float: default: 0.5
+						}
+						minimum_requests: {
+							description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+							required:    false
+							type: uint: default: 20
+						}
+						open_duration_secs: {
+							description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+							required:    false
+							type: uint: default: 30
+						}
+						window_secs: {
+							description: """
+																The time window, in seconds, over which the rate of failed requests is measured.
+
+																It must be greater than `0`.
+																"""
+							required: false
+							type: uint: default: 60
+						}
+					}
+				}
+			}
 			concurrency: {
 				description: "Configuration for outbound request concurrency."
 				required:    false
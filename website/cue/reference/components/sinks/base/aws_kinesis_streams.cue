@@ -27,6 +27,24 @@ base: components: sinks: aws_kinesis_streams: configuration: {
 			type: bool: {}
 		}
 	}
+	aggregate: {
+		description: """
+			Whether to aggregate events into records in the [aggregation format][kpl_aggregation] of
+			the Kinesis Producer Library.
+
+			Aggregation packs many events into each record, of up to 1 MB, which lowers the number of
+			records, and so the cost, of streams with many small events. Consumers must deaggregate
+			records, which the Kinesis Client Library does on its own, and AWS Lambda functions can do
+			with the deaggregation libraries of the Kinesis Producer Library.
+
+			The partition keys of events are kept in the records, but the records themselves are
+			routed to shards by the partition key of their first event.
+
+			[kpl_aggregation]: https://github.com/awslabs/amazon-kinesis-producer/blob/master/aggregation-format.md
+			"""
+		required: false
+		type: bool: default: false
+	}
 	auth: {
 		description: "Configuration of the authentication strategy for interacting with AWS services."
 		required:    false
@@ -172,6 +190,13 @@ base: components: sinks: aws_kinesis_streams: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					pretty: """
+						Encodes an event as human-readable text, writing each field on its own line, with aligned
+						keys and colorized values.
+
+						Events are separated by an empty line. This encoding is meant for reading events while
+						developing, such as in a terminal, not for being parsed.
+						"""
 					raw_message: """
 						No encoding.
 
@@ -202,6 +227,30 @@ base: components: sinks: aws_kinesis_streams: configuration: {
 				required:    false
 				type: array: items: type: string: syntax: "literal"
 			}
+			pretty: {
+				description:   "Pretty-specific encoder options."
+				relevant_when: "codec = \"pretty\""
+				required:      false
+				type: object: options: {
+					colors: {
+						description: """
+							Whether keys and values are colorized with ANSI escape codes.
+
+							Pagers such as `less` show colors with their `-R` option.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					timezone: {
+						description: "The timezone timestamps are shown in."
+						required:    false
+						type: string: {
+							default: "local"
+							examples: ["local", "America/New_York", "EST5EDT"]
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -296,6 +345,62 @@ base: components: sinks: aws_kinesis_streams: configuration: {
 					}
 				}
 			}
+			circuit_breaker: {
+				description: """
+					Configuration of the circuit breaker of outbound requests.
+
+					While the circuit is open, requests aren't sent to the service that keeps failing, and are
+					retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+					passed, a single request is sent to probe the service, and the circuit is closed again if it
+					succeeds.
+					"""
+				required: false
+				type: object: {
+					default: {
+						enabled:              false
+						error_rate_threshold: 0.5
+						minimum_requests:     20
+						open_duration_secs:   30
+						window_secs:          60
+					}
+					options: {
+						enabled: {
+							description: "Whether or not the circuit breaker is enabled."
+							required:    false
+							type: bool: default: false
+						}
+						error_rate_threshold: {
+							description: """
+																The rate of failed requests at which the circuit is opened.
+
+																Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																time out, fail to connect, or have an error or response which would be retried.
+																"""
+							required: false
+							type: float: default: 0.5
+						}
+						minimum_requests: {
+							description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+							required:    false
+							type: uint: default: 20
+						}
+						open_duration_secs: {
+							description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+							required:    false
+							type: uint: default: 30
+						}
+						window_secs: {
+							description: """
+																The time window, in seconds, over which the rate of failed requests is measured.
+
+																It must be greater than `0`.
+																"""
+							required: false
+							type: uint: default: 60
+						}
+					}
+				}
+			}
 			concurrency: {
 				description: "Configuration for outbound request concurrency."
 				required:    false
@@ -170,6 +170,72 @@ base: components: sinks: aws_s3: configuration: {
 			}
 		}
 	}
+	batch_encoding: {
+		description: """
+			Encoding applied to whole batches of events, instead of to each event.
+
+			When set, objects are encoded with it rather than with `encoding.codec` and `framing`, and are
+			not compressed with `compression`. The other `encoding` options still apply to events.
+
+			Batch encodings are only available in builds of Vector with the `codecs-parquet` feature.
+			"""
+		required: false
+		type: object: options: {
+			codec: {
+				required: true
+				type: string: enum: parquet: """
+					Batches are encoded as [Apache Parquet][apache_parquet] files.
+
+					[apache_parquet]: https://parquet.apache.org/
+					"""
+			}
+			compression: {
+				description:   "Compression codec applied to the pages of Parquet files."
+				relevant_when: "codec = \"parquet\""
+				required:      false
+				type: string: {
+					default: "snappy"
+					enum: {
+						gzip: """
+							[Gzip][gzip] compression.
+
+							[gzip]: https://www.gzip.org/
+							"""
+						none: "No compression."
+						snappy: """
+							[Snappy][snappy] compression.
+
+							[snappy]: https://github.com/google/snappy
+							"""
+					}
+				}
+			}
+			schema: {
+				description: """
+					The columns of the files, by name, with the types of their values.
+
+					Columns hold the values of the fields of events at the same paths, and are ordered by name.
+					Values that don't fit the types of their columns are written as nulls.
+					"""
+				relevant_when: "codec = \"parquet\""
+				required:      true
+				type: object: options: "*": {
+					description: "The type of the values of a column."
+					required:    true
+					type: string: enum: {
+						boolean: "Booleans."
+						double:  "64-bit floating point numbers, which integers are converted to."
+						int64:   "64-bit signed integers."
+						string:  "UTF-8 strings, which other values are converted to, with objects and arrays encoded as JSON."
+						timestamp: """
+							Timestamps, in microseconds since the Unix epoch in UTC, which RFC 3339 strings are parsed
+							as.
+							"""
+					}
+				}
+			}
+		}
+	}
 	bucket: {
 		description: """
 			The S3 bucket name.
@@ -179,6 +245,14 @@ base: components: sinks: aws_s3: configuration: {
 		required: true
 		type: string: syntax: "literal"
 	}
+	checksum_algorithm: {
+		description: """
+			The algorithm of the checksum of each object, calculated by Vector and verified by S3 on
+			upload.
+			"""
+		required: false
+		type: string: enum: SHA256: "SHA-256."
+	}
 	compression: {
 		description: """
 			Compression configuration.
@@ -274,6 +348,13 @@ base: components: sinks: aws_s3: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					pretty: """
+						Encodes an event as human-readable text, writing each field on its own line, with aligned
+						keys and colorized values.
+
+						Events are separated by an empty line. This encoding is meant for reading events while
+						developing, such as in a terminal, not for being parsed.
+						"""
 					raw_message: """
 						No encoding.
 
@@ -304,6 +385,30 @@ base: components: sinks: aws_s3: configuration: {
 				required:    false
 				type: array: items: type: string: syntax: "literal"
 			}
+			pretty: {
+				description:   "Pretty-specific encoder options."
+				relevant_when: "codec = \"pretty\""
+				required:      false
+				type: object: options: {
+					colors: {
+						description: """
+							Whether keys and values are colorized with ANSI escape codes.
+
+							Pagers such as `less` show colors with their `-R` option.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					timezone: {
+						description: "The timezone timestamps are shown in."
+						required:    false
+						type: string: {
+							default: "local"
+							examples: ["local", "America/New_York", "EST5EDT"]
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -441,10 +546,117 @@ base: components: sinks: aws_s3: configuration: {
 			Prefixes are useful for partitioning objects, such as by creating an object key that
 			stores objects under a particular "directory". If using a prefix for this purpose, it must end
 			in `/` in order to act as a directory path: Vector will **not** add a trailing `/` automatically.
+
+			When `partition_by` is set, no prefix is applied by default.
 			"""
 		required: false
 		type: string: syntax: "template"
 	}
+	multipart_upload: {
+		description: """
+			Multipart upload of large objects.
+
+			Objects larger than the part size are uploaded in parts with the [multipart upload
+			API][multipart_upload], rather than in a single request, which can't upload objects larger than
+			5 GB. If any part fails to upload, the upload is aborted and retried as a whole.
+
+			[multipart_upload]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/mpuoverview.html
+			"""
+		required: false
+		type: object: options: {
+			concurrency: {
+				description: "The number of parts of each object uploaded concurrently."
+				required:    false
+				type: uint: default: 4
+			}
+			part_size_bytes: {
+				description: """
+					The size of the parts objects are uploaded in, in bytes.
+
+					Parts must be at least 5 MiB large. Parts are made larger if objects would otherwise be
+					uploaded in more than 10,000 parts, the most S3 allows.
+					"""
+				required: false
+				type: uint: {
+					default: 16777216
+					examples: [104857600]
+				}
+			}
+		}
+	}
+	object_lock: {
+		description: """
+			[Object Lock][object_lock] retention of the created objects, which can't be overwritten or
+			deleted until their retention period expires.
+
+			The bucket must have Object Lock enabled.
+
+			[object_lock]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/object-lock.html
+			"""
+		required: false
+		type: object: options: {
+			mode: {
+				description: """
+					Object Lock retention modes.
+
+					More information on each mode can be found in the [AWS documentation][aws_docs].
+
+					[aws_docs]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/object-lock-overview.html#object-lock-retention-modes
+					"""
+				required: true
+				type: string: enum: {
+					COMPLIANCE: """
+						Objects can't be overwritten or deleted by any user until their retention period expires,
+						nor can their retention mode be changed or their retention period be shortened.
+						"""
+					GOVERNANCE: """
+						Objects can't be overwritten or deleted, nor can their retention settings be changed,
+						except by users granted the `s3:BypassGovernanceRetention` permission.
+						"""
+				}
+			}
+			retention_days: {
+				description: "The number of days objects are retained for, from their creation."
+				required:    true
+				type: uint: examples: [365]
+			}
+		}
+	}
+	partition_by: {
+		description: """
+			Hive-style partitions of the object keys, rendered as `name=value/` directories after the
+			key prefix, in order.
+
+			Values are escaped the way Hive escapes the values of partitions. Events whose values are
+			empty, or are missing the fields they are rendered from, are written to the
+			`__HIVE_DEFAULT_PARTITION__` partition instead of being dropped.
+
+			Each partition is batched on its own, so objects are written to each of them whenever
+			their batch reaches `batch.max_bytes` or `batch.timeout_secs`.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				name: {
+					description: "The name of the partition."
+					required:    true
+					type: string: {
+						examples: ["dt", "region"]
+						syntax: "literal"
+					}
+				}
+				value: {
+					description: "The value of the partition."
+					required:    true
+					type: string: {
+						examples: ["%Y-%m-%d", "{{ region }}"]
+						syntax: "template"
+					}
+				}
+			}
+		}
+	}
 	region: {
 		description: "The AWS region to use."
 		required:    false
@@ -515,6 +727,62 @@ base: components: sinks: aws_s3: configuration: {
 					}
 				}
 			}
+			circuit_breaker: {
+				description: """
+					Configuration of the circuit breaker of outbound requests.
+
+					While the circuit is open, requests aren't sent to the service that keeps failing, and are
+					retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+					passed, a single request is sent to probe the service, and the circuit is closed again if it
+					succeeds.
+					"""
+				required: false
+				type: object: {
+					default: {
+						enabled:              false
+						error_rate_threshold: 0.5
+						minimum_requests:     20
+						open_duration_secs:   30
+						window_secs:          60
+					}
+					options: {
+						enabled: {
+							description: "Whether or not the circuit breaker is enabled."
+							required:    false
+							type: bool: default: false
+						}
+						error_rate_threshold: {
+							description: """
+																The rate of failed requests at which the circuit is opened.
+
+																Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																time out, fail to connect, or have an error or response which would be retried.
+																"""
+							required: false
+							type: float: default: 0.5
+						}
+						minimum_requests: {
+							description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+							required:    false
+							type: uint: default: 20
+						}
+						open_duration_secs: {
+							description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+							required:    false
+							type: uint: default: 30
+						}
+						window_secs: {
+							description: """
+																The time window, in seconds, over which the rate of failed requests is measured.
+
+																It must be greater than `0`.
+																"""
+							required: false
+							type: uint: default: 60
+						}
+					}
+				}
+			}
 			concurrency: {
 				description: "Configuration for outbound request concurrency."
 				required:    false
@@ -625,12 +893,16 @@ base: components: sinks: aws_s3: configuration: {
 		}
 	}
 	tags: {
-		description: "The tag-set for the object."
-		required:    false
+		description: """
+			The tag-set for the object.
+
+			The values of tags are templates, and events are batched by the values they render.
+			"""
+		required: false
 		type: object: options: "*": {
-			description: "The tag-set for the object."
+			description: "The template of the value of the tag."
 			required:    true
-			type: string: syntax: "literal"
+			type: string: syntax: "template"
 		}
 	}
 	tls: {
@@ -82,6 +82,19 @@ base: components: sinks: aws_sqs: configuration: {
 			}
 		}
 	}
+	content_based_deduplication: {
+		description: """
+			Whether to deduplicate messages by their body.
+
+			When enabled, the deduplication ID of messages is the SHA-256 hash of their body, as with
+			the content-based deduplication of FIFO queues, so that queues without it, or whose
+			attributes can't be changed, deduplicate messages the same way.
+
+			Can be applied only to FIFO queues, and not along with `message_deduplication_id`.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	encoding: {
 		description: "Encoding configuration."
 		required:    true
@@ -131,6 +144,13 @@ base: components: sinks: aws_sqs: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					pretty: """
+						Encodes an event as human-readable text, writing each field on its own line, with aligned
+						keys and colorized values.
+
+						Events are separated by an empty line. This encoding is meant for reading events while
+						developing, such as in a terminal, not for being parsed.
+						"""
 					raw_message: """
 						No encoding.
 
@@ -161,6 +181,30 @@ base: components: sinks: aws_sqs: configuration: {
 				required:    false
 				type: array: items: type: string: syntax: "literal"
 			}
+			pretty: {
+				description:   "Pretty-specific encoder options."
+				relevant_when: "codec = \"pretty\""
+				required:      false
+				type: object: options: {
+					colors: {
+						description: """
+							Whether keys and values are colorized with ANSI escape codes.
+
+							Pagers such as `less` show colors with their `-R` option.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					timezone: {
+						description: "The timezone timestamps are shown in."
+						required:    false
+						type: string: {
+							default: "local"
+							examples: ["local", "America/New_York", "EST5EDT"]
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -184,18 +228,29 @@ base: components: sinks: aws_sqs: configuration: {
 			documentation][deduplication_id_docs] for more about how AWS does message deduplication.
 
 			[deduplication_id_docs]: https://docs.aws.amazon.com/AWSSimpleQueueService/latest/SQSDeveloperGuide/using-messagededuplicationid-property.html
+
+			Can be applied only to FIFO queues.
 			"""
 		required: false
-		type: string: syntax: "literal"
+		type: string: {
+			examples: ["{{ transaction_id }}"]
+			syntax: "literal"
+		}
 	}
 	message_group_id: {
 		description: """
 			The tag that specifies that a message belongs to a specific message group.
 
+			This value is a template, and is required for FIFO queues, which keep the order of the
+			messages of each group. Events whose group ID fails to render are dropped.
+
 			Can be applied only to FIFO queues.
 			"""
 		required: false
-		type: string: syntax: "literal"
+		type: string: {
+			examples: ["{{ application }}"]
+			syntax: "literal"
+		}
 	}
 	queue_url: {
 		description: "The URL of the Amazon SQS queue to which messages are sent."
@@ -272,6 +327,62 @@ base: components: sinks: aws_sqs: configuration: {
 					}
 				}
 			}
+			circuit_breaker: {
+				description: """
+					Configuration of the circuit breaker of outbound requests.
+
+					While the circuit is open, requests aren't sent to the service that keeps failing, and are
+					retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+					passed, a single request is sent to probe the service, and the circuit is closed again if it
+					succeeds.
+					"""
+				required: false
+				type: object: {
+					default: {
+						enabled:              false
+						error_rate_threshold: 0.5
+						minimum_requests:     20
+						open_duration_secs:   30
+						window_secs:          60
+					}
+					options: {
+						enabled: {
+							description: "Whether or not the circuit breaker is enabled."
+							required:    false
+							type: bool: default: false
+						}
+						error_rate_threshold: {
+							description: """
+																The rate of failed requests at which the circuit is opened.
+
+																Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																time out, fail to connect, or have an error or response which would be retried.
+																"""
+							required: false
+							type: float: default: 0.5
+						}
+						minimum_requests: {
+							description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+							required:    false
+							type: uint: default: 20
+						}
+						open_duration_secs: {
+							description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+							required:    false
+							type: uint: default: 30
+						}
+						window_secs: {
+							description: """
+																The time window, in seconds, over which the rate of failed requests is measured.
+
+																It must be greater than `0`.
+																"""
+							required: false
+							type: uint: default: 60
+						}
+					}
+				}
+			}
 			concurrency: {
 				description: "Configuration for outbound request concurrency."
 				required:    false
@@ -27,6 +27,32 @@ base: components: sinks: azure_blob: configuration: {
 			type: bool: {}
 		}
 	}
+	append: {
+		description: "Rollover options for append blobs."
+		required:    false
+		type: object: options: {
+			max_age_secs: {
+				description: """
+					The maximum age of each append blob, in seconds.
+
+					Batches sent once an append blob reached this age are appended to a new blob.
+					"""
+				required: false
+				type: uint: default: 3600
+			}
+			max_bytes: {
+				description: """
+					The maximum size of each append blob, in bytes.
+
+					Batches that would make an append blob exceed this size are appended to a new blob.
+					Append blobs can hold at most 50,000 blocks of up to 4 MiB, which also makes batches roll
+					over to a new blob.
+					"""
+				required: false
+				type: uint: default: 268435456
+			}
+		}
+	}
 	batch: {
 		description: "Event batching behavior."
 		required:    false
@@ -101,6 +127,23 @@ base: components: sinks: azure_blob: configuration: {
 		required: false
 		type: string: syntax: "literal"
 	}
+	blob_type: {
+		description: "The type of the blobs batches are written to."
+		required:    false
+		type: string: {
+			default: "block"
+			enum: {
+				append: """
+					Batches are appended to append blobs, which are rolled over to new blobs according to the
+					`append` options.
+
+					The blob prefix of a batch selects the append blob it is appended to, so events of
+					different blob prefixes are appended to different blobs.
+					"""
+				block: "Each batch is written to a new block blob."
+			}
+		}
+	}
 	compression: {
 		description: """
 			Compression configuration.
@@ -190,6 +233,13 @@ base: components: sinks: azure_blob: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					pretty: """
+						Encodes an event as human-readable text, writing each field on its own line, with aligned
+						keys and colorized values.
+
+						Events are separated by an empty line. This encoding is meant for reading events while
+						developing, such as in a terminal, not for being parsed.
+						"""
 					raw_message: """
 						No encoding.
 
@@ -220,6 +270,30 @@ base: components: sinks: azure_blob: configuration: {
 				required:    false
 				type: array: items: type: string: syntax: "literal"
 			}
+			pretty: {
+				description:   "Pretty-specific encoder options."
+				relevant_when: "codec = \"pretty\""
+				required:      false
+				type: object: options: {
+					colors: {
+						description: """
+							Whether keys and values are colorized with ANSI escape codes.
+
+							Pagers such as `less` show colors with their `-R` option.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					timezone: {
+						description: "The timezone timestamps are shown in."
+						required:    false
+						type: string: {
+							default: "local"
+							examples: ["local", "America/New_York", "EST5EDT"]
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -324,6 +398,62 @@ base: components: sinks: azure_blob: configuration: {
 					}
 				}
 			}
+			circuit_breaker: {
+				description: """
+					Configuration of the circuit breaker of outbound requests.
+
+					While the circuit is open, requests aren't sent to the service that keeps failing, and are
+					retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+					passed, a single request is sent to probe the service, and the circuit is closed again if it
+					succeeds.
+					"""
+				required: false
+				type: object: {
+					default: {
+						enabled:              false
+						error_rate_threshold: 0.5
+						minimum_requests:     20
+						open_duration_secs:   30
+						window_secs:          60
+					}
+					options: {
+						enabled: {
+							description: "Whether or not the circuit breaker is enabled."
+							required:    false
+							type: bool: default: false
+						}
+						error_rate_threshold: {
+							description: """
+																The rate of failed requests at which the circuit is opened.
+
+																Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																time out, fail to connect, or have an error or response which would be retried.
+																"""
+							required: false
+							type: float: default: 0.5
+						}
+						minimum_requests: {
+							description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+							required:    false
+							type: uint: default: 20
+						}
+						open_duration_secs: {
+							description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+							required:    false
+							type: uint: default: 30
+						}
+						window_secs: {
+							description: """
+																The time window, in seconds, over which the rate of failed requests is measured.
+
+																It must be greater than `0`.
+																"""
+							required: false
+							type: uint: default: 60
+						}
+					}
+				}
+			}
 			concurrency: {
 				description: "Configuration for outbound request concurrency."
 				required:    false
@@ -45,4 +45,305 @@ base: components: sinks: blackhole: configuration: {
 		required: false
 		type: uint: {}
 	}
+	simulate: {
+		description: """
+			Simulated downstream service options.
+
+			When set, batches of events are sent as requests to a simulated service, instead of being
+			consumed as soon as they are received.
+			"""
+		required: false
+		type: object: options: {
+			batch: {
+				description: "Event batching behavior."
+				required:    false
+				type: object: options: {
+					max_bytes: {
+						description: """
+							The maximum size of a batch that will be processed by a sink.
+
+							This is based on the uncompressed size of the batched events, before they are
+							serialized / compressed.
+							"""
+						required: false
+						type: uint: {}
+					}
+					max_events: {
+						description: "The maximum size of a batch, in events, before it is flushed."
+						required:    false
+						type: uint: {}
+					}
+					timeout_secs: {
+						description: "The maximum age of a batch, in seconds, before it is flushed."
+						required:    false
+						type: float: {}
+					}
+				}
+			}
+			error_rate: {
+				description: "The fraction of requests, between `0` and `1`, that fail with an error that is retried."
+				required:    false
+				type: float: default: 0.0
+			}
+			latency: {
+				description: "The distribution of the latency of simulated requests."
+				required:    false
+				type: object: options: {
+					distribution: {
+						required: true
+						type: string: enum: {
+							constant:    "Requests take a constant time."
+							exponential: "Requests take an exponentially distributed time."
+							normal:      "Requests take a normally distributed time, which is never negative."
+							uniform:     "Requests take a time uniformly distributed between a minimum and a maximum."
+						}
+					}
+					max_ms: {
+						description:   "The maximum latency, in milliseconds."
+						relevant_when: "distribution = \"uniform\""
+						required:      true
+						type: float: {}
+					}
+					mean_ms: {
+						description:   "The mean latency, in milliseconds."
+						relevant_when: "distribution = \"normal\" or distribution = \"exponential\""
+						required:      true
+						type: float: {}
+					}
+					min_ms: {
+						description:   "The minimum latency, in milliseconds."
+						relevant_when: "distribution = \"uniform\""
+						required:      true
+						type: float: {}
+					}
+					ms: {
+						description:   "The latency, in milliseconds."
+						relevant_when: "distribution = \"constant\""
+						required:      true
+						type: float: {}
+					}
+					std_dev_ms: {
+						description:   "The standard deviation of the latency, in milliseconds."
+						relevant_when: "distribution = \"normal\""
+						required:      true
+						type: float: {}
+					}
+				}
+			}
+			max_bytes_per_sec: {
+				description: """
+					The number of bytes, per second, that the simulated service is able to handle.
+
+					The bytes of requests are the estimated size of their events encoded as JSON. Requests wait
+					for the service to have handled the bytes of the previous ones before taking their latency.
+					By default, there is no limit.
+					"""
+				required: false
+				type: uint: {}
+			}
+			max_events_per_sec: {
+				description: """
+					The number of events, per second, that the simulated service is able to handle.
+
+					Requests wait for the service to have handled the events of the previous ones before
+					taking their latency. By default, there is no limit.
+					"""
+				required: false
+				type: uint: {}
+			}
+			reject_rate: {
+				description: """
+					The fraction of requests, between `0` and `1`, whose events are rejected without being
+					retried.
+					"""
+				required: false
+				type: float: default: 0.0
+			}
+			request: {
+				description: """
+					Middleware settings for outbound requests.
+
+					Various settings can be configured, such as concurrency and rate limits, timeouts, etc.
+					"""
+				required: false
+				type: object: options: {
+					adaptive_concurrency: {
+						description: """
+							Configuration of adaptive concurrency parameters.
+
+							These parameters typically do not require changes from the default, and incorrect values can lead to meta-stable or
+							unstable performance and sink behavior. Proceed with caution.
+							"""
+						required: false
+						type: object: {
+							default: {
+								decrease_ratio:      0.9
+								ewma_alpha:          0.4
+								rtt_deviation_scale: 2.5
+							}
+							options: {
+								decrease_ratio: {
+									description: """
+																		The fraction of the current value to set the new concurrency limit when decreasing the limit.
+
+																		Valid values are greater than `0` and less than `1`. Smaller values cause the algorithm to scale back rapidly
+																		when latency increases.
+
+																		Note that the new limit is rounded down after applying this ratio.
+																		"""
+									required: false
+									type: float: default: 0.9
+								}
+								ewma_alpha: {
+									description: """
+																		The weighting of new measurements compared to older measurements.
+
+																		Valid values are greater than `0` and less than `1`.
+
+																		ARC uses an exponentially weighted moving average (EWMA) of past RTT measurements as a reference to compare with
+																		the current RTT. Smaller values cause this reference to adjust more slowly, which may be useful if a service has
+																		unusually high response variability.
+																		"""
+									required: false
+									type: float: default: 0.4
+								}
+								rtt_deviation_scale: {
+									description: """
+																		Scale of RTT deviations which are not considered anomalous.
+
+																		Valid values are greater than or equal to `0`, and we expect reasonable values to range from `1.0` to `3.0`.
+
+																		When calculating the past RTT average, we also compute a secondary “deviation” value that indicates how variable
+																		those values are. We use that deviation when comparing the past RTT average to the current measurements, so we
+																		can ignore increases in RTT that are within an expected range. This factor is used to scale up the deviation to
+																		an appropriate range.  Larger values cause the algorithm to ignore larger increases in the RTT.
+																		"""
+									required: false
+									type: float: default: 2.5
+								}
+							}
+						}
+					}
+					circuit_breaker: {
+						description: """
+							Configuration of the circuit breaker of outbound requests.
+
+							While the circuit is open, requests aren't sent to the service that keeps failing, and are
+							retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+							passed, a single request is sent to probe the service, and the circuit is closed again if it
+							succeeds.
+							"""
+						required: false
+						type: object: {
+							default: {
+								enabled:              false
+								error_rate_threshold: 0.5
+								minimum_requests:     20
+								open_duration_secs:   30
+								window_secs:          60
+							}
+							options: {
+								enabled: {
+									description: "Whether or not the circuit breaker is enabled."
+									required:    false
+									type: bool: default: false
+								}
+								error_rate_threshold: {
+									description: """
+																		The rate of failed requests at which the circuit is opened.
+
+																		Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																		time out, fail to connect, or have an error or response which would be retried.
+																		"""
+									required: false
+									type: float: default: 0.5
+								}
+								minimum_requests: {
+									description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+									required:    false
+									type: uint: default: 20
+								}
+								open_duration_secs: {
+									description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+									required:    false
+									type: uint: default: 30
+								}
+								window_secs: {
+									description: """
+																		The time window, in seconds, over which the rate of failed requests is measured.
+
+																		It must be greater than `0`.
+																		"""
+									required: false
+									type: uint: default: 60
+								}
+							}
+						}
+					}
+					concurrency: {
+						description: "Configuration for outbound request concurrency."
+						required:    false
+						type: {
+							string: {
+								const:   "adaptive"
+								default: "none"
+							}
+							uint: {}
+						}
+					}
+					rate_limit_duration_secs: {
+						description: "The time window, in seconds, used for the `rate_limit_num` option."
+						required:    false
+						type: uint: default: 1
+					}
+					rate_limit_num: {
+						description: "The maximum number of requests allowed within the `rate_limit_duration_secs` time window."
+						required:    false
+						type: uint: default: 9223372036854775807
+					}
+					retry_attempts: {
+						description: """
+							The maximum number of retries to make for failed requests.
+
+							The default, for all intents and purposes, represents an infinite number of retries.
+							"""
+						required: false
+						type: uint: default: 9223372036854775807
+					}
+					retry_initial_backoff_secs: {
+						description: """
+							The amount of time to wait before attempting the first retry for a failed request.
+
+							After the first retry has failed, the fibonacci sequence will be used to select future backoffs.
+							"""
+						required: false
+						type: uint: default: 1
+					}
+					retry_max_duration_secs: {
+						description: "The maximum amount of time, in seconds, to wait between retries."
+						required:    false
+						type: uint: default: 3600
+					}
+					timeout_secs: {
+						description: """
+							The maximum time a request can take before being aborted.
+
+							It is highly recommended that you do not lower this value below the service’s internal timeout, as this could
+							create orphaned requests, pile on retries, and result in duplicate data downstream.
+							"""
+						required: false
+						type: uint: default: 60
+					}
+				}
+			}
+			throttle_rate: {
+				description: """
+					The fraction of requests, between `0` and `1`, that are throttled, as with an HTTP `429 Too
+					Many Requests` response, and retried.
+					"""
+				required: false
+				type: float: default: 0.0
+			}
+		}
+	}
 }
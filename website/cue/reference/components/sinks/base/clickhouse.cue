@@ -27,6 +27,15 @@ base: components: sinks: clickhouse: configuration: {
 			type: bool: {}
 		}
 	}
+	async_insert: {
+		description: """
+			Sets `async_insert`, having Clickhouse buffer inserted data and write it in larger parts.
+
+			Inserts are still only acknowledged once their data is written.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	auth: {
 		description: """
 			Configuration of the authentication strategy for HTTP requests.
@@ -36,12 +45,46 @@ base: components: sinks: clickhouse: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			client_id: {
+				description:   "The identifier of the client."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: syntax: "literal"
+			}
+			client_secret: {
+				description:   "The secret of the client."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: syntax: "literal"
+			}
 			password: {
 				description:   "The password to send."
 				relevant_when: "strategy = \"basic\""
 				required:      true
 				type: string: syntax: "literal"
 			}
+			refresh_before_secs: {
+				description: """
+					How long before they expire access tokens are refreshed, in seconds.
+
+					Access tokens expiring sooner than this are refreshed halfway through their lifetime.
+					"""
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: uint: default: 60
+			}
+			scopes: {
+				description:   "The scopes to request access tokens for."
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: array: {
+					default: []
+					items: type: string: {
+						examples: ["logs:write"]
+						syntax: "literal"
+					}
+				}
+			}
 			strategy: {
 				required: true
 				type: string: enum: {
@@ -57,6 +100,15 @@ base: components: sinks: clickhouse: configuration: {
 
 						The bearer token value (OAuth2, JWT, etc) is passed as-is.
 						"""
+					oauth2: """
+						OAuth2 authentication, with the client credentials grant.
+
+						Access tokens are requested from the token endpoint with the credentials of the client, and
+						passed as bearer tokens. They're cached, and refreshed before they expire.
+
+						This is only supported by the `http`, `loki`, and `prometheus_remote_write` sinks, and the
+						`remote_lookup` transform. Other components fail to build with it.
+						"""
 				}
 			}
 			token: {
@@ -65,6 +117,15 @@ base: components: sinks: clickhouse: configuration: {
 				required:      true
 				type: string: syntax: "literal"
 			}
+			token_endpoint: {
+				description:   "The token endpoint of the authorization server, which access tokens are requested from."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: {
+					examples: ["https://auth.example.com/oauth2/token"]
+					syntax: "literal"
+				}
+			}
 			user: {
 				description:   "The username to send."
 				relevant_when: "strategy = \"basic\""
@@ -153,10 +214,34 @@ base: components: sinks: clickhouse: configuration: {
 		}
 	}
 	endpoint: {
-		description: "The endpoint of the Clickhouse server."
-		required:    true
+		description: """
+			The endpoint of the Clickhouse server.
+
+			With the `native` protocol, this is the address of its TCP interface, such as
+			`tcp://localhost:9000`.
+			"""
+		required: true
 		type: string: syntax: "literal"
 	}
+	protocol: {
+		description: "The protocol used to insert data into Clickhouse."
+		required:    false
+		type: string: {
+			default: "http"
+			enum: {
+				http: "Insert rows encoded as `JSONEachRow` through the HTTP interface."
+				native: """
+					Insert blocks of columns through the native TCP interface.
+
+					The fields of events are converted to the types of the columns of the table, which is
+					cheaper for both Vector and Clickhouse than parsing JSON. Blocks are compressed with LZ4
+					unless `compression` is `none`.
+
+					TLS and bearer authentication are not supported with this protocol.
+					"""
+			}
+		}
+	}
 	request: {
 		description: """
 			Middleware settings for outbound requests.
@@ -222,6 +307,62 @@ base: components: sinks: clickhouse: configuration: {
 					}
 				}
 			}
+			circuit_breaker: {
+				description: """
+					Configuration of the circuit breaker of outbound requests.
+
+					While the circuit is open, requests aren't sent to the service that keeps failing, and are
+					retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+					passed, a single request is sent to probe the service, and the circuit is closed again if it
+					succeeds.
+					"""
+				required: false
+				type: object: {
+					default: {
+						enabled:              false
+						error_rate_threshold: 0.5
+						minimum_requests:     20
+						open_duration_secs:   30
+						window_secs:          60
+					}
+					options: {
+						enabled: {
+							description: "Whether or not the circuit breaker is enabled."
+							required:    false
+							type: bool: default: false
+						}
+						error_rate_threshold: {
+							description: """
+																The rate of failed requests at which the circuit is opened.
+
+																Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																time out, fail to connect, or have an error or response which would be retried.
+																"""
+							required: false
+							type: float: default: 0.5
+						}
+						minimum_requests: {
+							description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+							required:    false
+							type: uint: default: 20
+						}
+						open_duration_secs: {
+							description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+							required:    false
+							type: uint: default: 30
+						}
+						window_secs: {
+							description: """
+																The time window, in seconds, over which the rate of failed requests is measured.
+
+																It must be greater than `0`.
+																"""
+							required: false
+							type: uint: default: 60
+						}
+					}
+				}
+			}
 			concurrency: {
 				description: "Configuration for outbound request concurrency."
 				required:    false
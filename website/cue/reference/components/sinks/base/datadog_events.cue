@@ -27,6 +27,20 @@ base: components: sinks: datadog_events: configuration: {
 			type: bool: {}
 		}
 	}
+	alert_type: {
+		description: """
+			The template of the alert type of events.
+
+			It must render to one of `error`, `warning`, `info`, `success`, `user_update`,
+			`recommendation`, or `snapshot`. By default, the `alert_type` field of events is used, and
+			Datadog considers events without one as `info`.
+			"""
+		required: false
+		type: string: {
+			examples: ["{{ level }}"]
+			syntax: "template"
+		}
+	}
 	default_api_key: {
 		description: """
 			The default Datadog [API key][api_key] to send events with.
@@ -121,6 +135,62 @@ base: components: sinks: datadog_events: configuration: {
 					}
 				}
 			}
+			circuit_breaker: {
+				description: """
+					Configuration of the circuit breaker of outbound requests.
+
+					While the circuit is open, requests aren't sent to the service that keeps failing, and are
+					retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+					passed, a single request is sent to probe the service, and the circuit is closed again if it
+					succeeds.
+					"""
+				required: false
+				type: object: {
+					default: {
+						enabled:              false
+						error_rate_threshold: 0.5
+						minimum_requests:     20
+						open_duration_secs:   30
+						window_secs:          60
+					}
+					options: {
+						enabled: {
+							description: "Whether or not the circuit breaker is enabled."
+							required:    false
+							type: bool: default: false
+						}
+						error_rate_threshold: {
+							description: """
+																The rate of failed requests at which the circuit is opened.
+
+																Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																time out, fail to connect, or have an error or response which would be retried.
+																"""
+							required: false
+							type: float: default: 0.5
+						}
+						minimum_requests: {
+							description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+							required:    false
+							type: uint: default: 20
+						}
+						open_duration_secs: {
+							description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+							required:    false
+							type: uint: default: 30
+						}
+						window_secs: {
+							description: """
+																The time window, in seconds, over which the rate of failed requests is measured.
+
+																It must be greater than `0`.
+																"""
+							required: false
+							type: uint: default: 60
+						}
+					}
+				}
+			}
 			concurrency: {
 				description: "Configuration for outbound request concurrency."
 				required:    false
@@ -186,6 +256,42 @@ base: components: sinks: datadog_events: configuration: {
 		required: false
 		type: string: syntax: "literal"
 	}
+	tags: {
+		description: """
+			The templates of the tags added to events.
+
+			Tags are added to those of the `tags` field of events.
+			"""
+		required: false
+		type: array: items: type: string: {
+			examples: ["env:{{ environment }}"]
+			syntax: "template"
+		}
+	}
+	text: {
+		description: """
+			The template of the text of events.
+
+			By default, the `text` field of events is used, or their message if they don't have one.
+			"""
+		required: false
+		type: string: {
+			examples: ["{{ message }}"]
+			syntax: "template"
+		}
+	}
+	title: {
+		description: """
+			The template of the title of events.
+
+			By default, the `title` field of events is used, and events without one are dropped.
+			"""
+		required: false
+		type: string: {
+			examples: ["{{ service }} is unhealthy"]
+			syntax: "template"
+		}
+	}
 	tls: {
 		description: "Configures the TLS options for incoming/outgoing connections."
 		required:    false
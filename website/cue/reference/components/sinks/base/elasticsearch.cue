@@ -382,6 +382,28 @@ base: components: sinks: elasticsearch: configuration: {
 			}
 		}
 	}
+	opensearch_service_type: {
+		description: "The type of Amazon OpenSearch service written to."
+		required:    false
+		type: string: {
+			default: "managed"
+			enum: {
+				ingestion: """
+					Amazon OpenSearch Ingestion pipelines.
+
+					Documents are sent as JSON arrays to the path of the HTTP source of the pipeline, which
+					must be part of the endpoint, and requests are signed for the `osis` service, which
+					requires AWS authentication.
+					"""
+				managed: "Elasticsearch or OpenSearch clusters, including Amazon OpenSearch Service domains."
+				serverless: """
+					Amazon OpenSearch Serverless collections.
+
+					Requests are signed for the `aoss` service, which requires AWS authentication.
+					"""
+			}
+		}
+	}
 	pipeline: {
 		description: "The name of the pipeline to apply."
 		required:    false
@@ -457,6 +479,62 @@ base: components: sinks: elasticsearch: configuration: {
 					}
 				}
 			}
+			circuit_breaker: {
+				description: """
+					Configuration of the circuit breaker of outbound requests.
+
+					While the circuit is open, requests aren't sent to the service that keeps failing, and are
+					retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+					passed, a single request is sent to probe the service, and the circuit is closed again if it
+					succeeds.
+					"""
+				required: false
+				type: object: {
+					default: {
+						enabled:              false
+						error_rate_threshold: 0.5
+						minimum_requests:     20
+						open_duration_secs:   30
+						window_secs:          60
+					}
+					options: {
+						enabled: {
+							description: "Whether or not the circuit breaker is enabled."
+							required:    false
+							type: bool: default: false
+						}
+						error_rate_threshold: {
+							description: """
+																The rate of failed requests at which the circuit is opened.
+
+																Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																time out, fail to connect, or have an error or response which would be retried.
+																"""
+							required: false
+							type: float: default: 0.5
+						}
+						minimum_requests: {
+							description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+							required:    false
+							type: uint: default: 20
+						}
+						open_duration_secs: {
+							description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+							required:    false
+							type: uint: default: 30
+						}
+						window_secs: {
+							description: """
+																The time window, in seconds, over which the rate of failed requests is measured.
+
+																It must be greater than `0`.
+																"""
+							required: false
+							type: uint: default: 60
+						}
+					}
+				}
+			}
 			concurrency: {
 				description: "Configuration for outbound request concurrency."
 				required:    false
@@ -529,11 +607,26 @@ base: components: sinks: elasticsearch: configuration: {
 		description: """
 			Whether or not to retry successful requests containing partial failures.
 
+			Only the documents that failed with retriable errors, which are backpressure and server
+			errors, are written again.
+
 			To avoid duplicates in Elasticsearch, please use option `id_key`.
 			"""
 		required: false
 		type: bool: default: false
 	}
+	reroute_rejected: {
+		description: """
+			Reroutes the documents rejected by Elasticsearch to a named output instead of dropping them.
+
+			Documents can be rejected one by one within successful requests, for instance because of
+			mapping conflicts. When `reroute_rejected` is set to `true`, the original events of these
+			documents are forwarded to a specially-named output, `rejected`, annotated with the status
+			and error returned by Elasticsearch.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	suppress_type_name: {
 		description: """
 			Whether or not to send the `type` field to Elasticsearch.
@@ -88,6 +88,13 @@ base: components: sinks: file: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					pretty: """
+						Encodes an event as human-readable text, writing each field on its own line, with aligned
+						keys and colorized values.
+
+						Events are separated by an empty line. This encoding is meant for reading events while
+						developing, such as in a terminal, not for being parsed.
+						"""
 					raw_message: """
 						No encoding.
 
@@ -118,6 +125,30 @@ base: components: sinks: file: configuration: {
 				required:    false
 				type: array: items: type: string: syntax: "literal"
 			}
+			pretty: {
+				description:   "Pretty-specific encoder options."
+				relevant_when: "codec = \"pretty\""
+				required:      false
+				type: object: options: {
+					colors: {
+						description: """
+							Whether keys and values are colorized with ANSI escape codes.
+
+							Pagers such as `less` show colors with their `-R` option.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					timezone: {
+						description: "The timezone timestamps are shown in."
+						required:    false
+						type: string: {
+							default: "local"
+							examples: ["local", "America/New_York", "EST5EDT"]
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -171,4 +202,71 @@ base: components: sinks: file: configuration: {
 		required:    true
 		type: string: syntax: "template"
 	}
+	rotate: {
+		description: """
+			Rotation options for the written files.
+
+			Files are rotated by renaming them, appending the time of their rotation to their name, such as
+			`/var/log/vector.log.20221014-120000.000000`. Events are then written to a new file.
+			"""
+		required: false
+		type: object: options: {
+			compression: {
+				description: """
+					The compression rotated files are compressed with once rotated.
+
+					The extension of the compression is appended to the names of compressed files. Only
+					uncompressed files written with `compression` set to `none` can be compressed once rotated.
+					"""
+				required: false
+				type: string: {
+					default: "none"
+					enum: {
+						gzip: "Gzip compression."
+						none: "No compression."
+						zstd: "Zstandard compression."
+					}
+				}
+			}
+			max_age_secs: {
+				description: """
+					The age, in seconds, written files are rotated at.
+
+					Files are rotated once an event is written to them after they reached this age, which is
+					counted from their creation, or from when Vector opened them if the filesystem does not
+					record the creation of files.
+					"""
+				required: false
+				type: uint: {}
+			}
+			max_bytes: {
+				description: """
+					The size, in bytes, written files are rotated at.
+
+					Files are rotated once writing an event makes them reach this size, which includes the size
+					of files written before they were opened by Vector.
+					"""
+				required: false
+				type: uint: {}
+			}
+			max_files: {
+				description: """
+					The maximum number of rotated files kept for each written file.
+
+					The oldest rotated files are deleted once there are more of them.
+					"""
+				required: false
+				type: uint: {}
+			}
+			max_total_bytes: {
+				description: """
+					The maximum total size, in bytes, of the rotated files kept for each written file.
+
+					The oldest rotated files are deleted once their total size is larger.
+					"""
+				required: false
+				type: uint: {}
+			}
+		}
+	}
 }
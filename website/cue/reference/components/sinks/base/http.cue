@@ -36,12 +36,46 @@ base: components: sinks: http: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			client_id: {
+				description:   "The identifier of the client."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: syntax: "literal"
+			}
+			client_secret: {
+				description:   "The secret of the client."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: syntax: "literal"
+			}
 			password: {
 				description:   "The password to send."
 				relevant_when: "strategy = \"basic\""
 				required:      true
 				type: string: syntax: "literal"
 			}
+			refresh_before_secs: {
+				description: """
+					How long before they expire access tokens are refreshed, in seconds.
+
+					Access tokens expiring sooner than this are refreshed halfway through their lifetime.
+					"""
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: uint: default: 60
+			}
+			scopes: {
+				description:   "The scopes to request access tokens for."
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: array: {
+					default: []
+					items: type: string: {
+						examples: ["logs:write"]
+						syntax: "literal"
+					}
+				}
+			}
 			strategy: {
 				required: true
 				type: string: enum: {
@@ -57,6 +91,15 @@ base: components: sinks: http: configuration: {
 
 						The bearer token value (OAuth2, JWT, etc) is passed as-is.
 						"""
+					oauth2: """
+						OAuth2 authentication, with the client credentials grant.
+
+						Access tokens are requested from the token endpoint with the credentials of the client, and
+						passed as bearer tokens. They're cached, and refreshed before they expire.
+
+						This is only supported by the `http`, `loki`, and `prometheus_remote_write` sinks, and the
+						`remote_lookup` transform. Other components fail to build with it.
+						"""
 				}
 			}
 			token: {
@@ -65,6 +108,15 @@ base: components: sinks: http: configuration: {
 				required:      true
 				type: string: syntax: "literal"
 			}
+			token_endpoint: {
+				description:   "The token endpoint of the authorization server, which access tokens are requested from."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: {
+					examples: ["https://auth.example.com/oauth2/token"]
+					syntax: "literal"
+				}
+			}
 			user: {
 				description:   "The username to send."
 				relevant_when: "strategy = \"basic\""
@@ -172,6 +224,13 @@ base: components: sinks: http: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					pretty: """
+						Encodes an event as human-readable text, writing each field on its own line, with aligned
+						keys and colorized values.
+
+						Events are separated by an empty line. This encoding is meant for reading events while
+						developing, such as in a terminal, not for being parsed.
+						"""
 					raw_message: """
 						No encoding.
 
@@ -202,6 +261,30 @@ base: components: sinks: http: configuration: {
 				required:    false
 				type: array: items: type: string: syntax: "literal"
 			}
+			pretty: {
+				description:   "Pretty-specific encoder options."
+				relevant_when: "codec = \"pretty\""
+				required:      false
+				type: object: options: {
+					colors: {
+						description: """
+							Whether keys and values are colorized with ANSI escape codes.
+
+							Pagers such as `less` show colors with their `-R` option.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					timezone: {
+						description: "The timezone timestamps are shown in."
+						required:    false
+						type: string: {
+							default: "local"
+							examples: ["local", "America/New_York", "EST5EDT"]
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -329,6 +412,62 @@ base: components: sinks: http: configuration: {
 					}
 				}
 			}
+			circuit_breaker: {
+				description: """
+					Configuration of the circuit breaker of outbound requests.
+
+					While the circuit is open, requests aren't sent to the service that keeps failing, and are
+					retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+					passed, a single request is sent to probe the service, and the circuit is closed again if it
+					succeeds.
+					"""
+				required: false
+				type: object: {
+					default: {
+						enabled:              false
+						error_rate_threshold: 0.5
+						minimum_requests:     20
+						open_duration_secs:   30
+						window_secs:          60
+					}
+					options: {
+						enabled: {
+							description: "Whether or not the circuit breaker is enabled."
+							required:    false
+							type: bool: default: false
+						}
+						error_rate_threshold: {
+							description: """
+																The rate of failed requests at which the circuit is opened.
+
+																Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																time out, fail to connect, or have an error or response which would be retried.
+																"""
+							required: false
+							type: float: default: 0.5
+						}
+						minimum_requests: {
+							description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+							required:    false
+							type: uint: default: 20
+						}
+						open_duration_secs: {
+							description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+							required:    false
+							type: uint: default: 30
+						}
+						window_secs: {
+							description: """
+																The time window, in seconds, over which the rate of failed requests is measured.
+
+																It must be greater than `0`.
+																"""
+							required: false
+							type: uint: default: 60
+						}
+					}
+				}
+			}
 			concurrency: {
 				description: "Configuration for outbound request concurrency."
 				required:    false
@@ -397,6 +536,74 @@ base: components: sinks: http: configuration: {
 			}
 		}
 	}
+	signing: {
+		description: """
+			Request signing configuration.
+
+			Requests are signed with an HMAC of the values of the signed headers, each followed by a
+			newline, and of the body, as it's sent after compression. The signature is sent in its own
+			header, so that webhooks can verify where requests come from.
+			"""
+		required: false
+		type: object: options: {
+			algorithm: {
+				description: "The hash algorithm used to compute the HMAC."
+				required:    false
+				type: string: {
+					default: "sha256"
+					enum: {
+						sha1:   "SHA-1."
+						sha256: "SHA-256."
+						sha512: "SHA-512."
+					}
+				}
+			}
+			encoding: {
+				description: "The encoding of the signature in the header value."
+				required:    false
+				type: string: {
+					default: "hex"
+					enum: {
+						base64: "Base64."
+						hex:    "Hexadecimal."
+					}
+				}
+			}
+			header: {
+				description: "The name of the header carrying the signature."
+				required:    true
+				type: string: {
+					examples: ["X-Signature"]
+					syntax: "literal"
+				}
+			}
+			headers: {
+				description: """
+					The headers whose values are signed along with the body, in order.
+
+					Headers missing from requests are signed as empty values.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: {
+						examples: ["X-Timestamp"]
+						syntax: "literal"
+					}
+				}
+			}
+			prefix: {
+				description: "A prefix to add to the signature in the header value, such as `sha256=`."
+				required:    false
+				type: string: syntax: "literal"
+			}
+			secret: {
+				description: "The shared secret used to sign requests."
+				required:    true
+				type: string: syntax: "literal"
+			}
+		}
+	}
 	tls: {
 		description: "TLS configuration."
 		required:    false
@@ -487,8 +694,15 @@ base: components: sinks: http: configuration: {
 			The full URI to make HTTP requests to.
 
 			This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
+
+			The path and query can be templated from the fields of events, in which case events are
+			batched per rendered URI. The values of fields are percent-encoded. Events for which the
+			URI can't be rendered, or is invalid, are dropped.
 			"""
 		required: true
-		type: string: syntax: "literal"
+		type: string: {
+			examples: ["https://10.22.212.22:9000/endpoint", "https://example.com/api/{{ tenant }}/logs"]
+			syntax: "template"
+		}
 	}
 }
@@ -0,0 +1,535 @@
+package metadata
+
+base: components: sinks: iceberg: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled for this sink.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how Vector handles event acknowledgement.
+
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: """
+				Whether or not end-to-end acknowledgements are enabled.
+
+				When enabled for a sink, any source connected to that sink, where the source supports
+				end-to-end acknowledgements as well, will wait for events to be acknowledged by the sink
+				before acknowledging them at the source.
+
+				Enabling or disabling acknowledgements at the sink level takes precedence over any global
+				[`acknowledgements`][global_acks] configuration.
+
+				[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+				"""
+			required: false
+			type: bool: {}
+		}
+	}
+	auth: {
+		description: "Configuration of the authentication strategy for interacting with AWS services."
+		required:    false
+		type: object: options: {
+			access_key_id: {
+				description: "The AWS access key ID."
+				required:    true
+				type: string: syntax: "literal"
+			}
+			assume_role: {
+				description: "The ARN of the role to assume."
+				required:    true
+				type: string: syntax: "literal"
+			}
+			credentials_file: {
+				description: "Path to the credentials file."
+				required:    true
+				type: string: syntax: "literal"
+			}
+			load_timeout_secs: {
+				description: "Timeout for successfully loading any credentials, in seconds."
+				required:    false
+				type: uint: {}
+			}
+			profile: {
+				description: "The credentials profile to use."
+				required:    false
+				type: string: syntax: "literal"
+			}
+			region: {
+				description: """
+					The AWS region to send STS requests to.
+
+					If not set, this will default to the configured region
+					for the service itself.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			secret_access_key: {
+				description: "The AWS secret access key."
+				required:    true
+				type: string: syntax: "literal"
+			}
+		}
+	}
+	batch: {
+		description: "Event batching behavior."
+		required:    false
+		type: object: options: {
+			max_bytes: {
+				description: """
+					The maximum size of a batch that will be processed by a sink.
+
+					This is based on the uncompressed size of the batched events, before they are
+					serialized / compressed.
+					"""
+				required: false
+				type: uint: {}
+			}
+			max_events: {
+				description: "The maximum size of a batch, in events, before it is flushed."
+				required:    false
+				type: uint: {}
+			}
+			timeout_secs: {
+				description: "The maximum age of a batch, in seconds, before it is flushed."
+				required:    false
+				type: float: {}
+			}
+		}
+	}
+	catalog: {
+		description: "The catalog tracking the table."
+		required:    true
+		type: object: options: {
+			catalog_id: {
+				description: """
+					The ID of the Data Catalog, which is the ID of the AWS account owning it.
+
+					By default, the Data Catalog of the account Vector authenticates as is used.
+					"""
+				relevant_when: "type = \"glue\""
+				required:      false
+				type: string: {
+					examples: ["123456789012"]
+					syntax: "literal"
+				}
+			}
+			endpoint: {
+				description:   "A custom endpoint for the Glue API."
+				relevant_when: "type = \"glue\""
+				required:      false
+				type: string: {
+					examples: ["http://127.0.0.0:5000/path/to/service"]
+					syntax: "literal"
+				}
+			}
+			token: {
+				description:   "The bearer token requests to the catalog are authenticated with."
+				relevant_when: "type = \"rest\""
+				required:      false
+				type: string: {
+					examples: ["${ICEBERG_CATALOG_TOKEN}"]
+					syntax: "literal"
+				}
+			}
+			type: {
+				required: true
+				type: string: enum: {
+					glue: """
+						The [AWS Glue Data Catalog][glue_catalog], in the region of the sink.
+
+						[glue_catalog]: https://docs.aws.amazon.com/glue/latest/dg/components-overview.html#data-catalog-intro
+						"""
+					rest: """
+						An [Iceberg REST catalog][rest_catalog].
+
+						[rest_catalog]: https://iceberg.apache.org/concepts/catalog/#decoupling-using-the-rest-catalog
+						"""
+				}
+			}
+			uri: {
+				description:   "The URI of the catalog."
+				relevant_when: "type = \"rest\""
+				required:      true
+				type: string: {
+					examples: ["http://localhost:8181"]
+					syntax: "literal"
+				}
+			}
+			warehouse: {
+				description:   "The warehouse of the table, for catalogs serving several warehouses."
+				relevant_when: "type = \"rest\""
+				required:      false
+				type: string: {
+					examples: ["s3://warehouse"]
+					syntax: "literal"
+				}
+			}
+		}
+	}
+	column_templates: {
+		description: """
+			Templates rendering the values of columns, by the names of the columns.
+
+			By default, columns are written with the values of the top-level fields of events with
+			the same names. Rendered values are parsed as values of the types of their columns, which
+			makes templates suited to deriving the source columns of partition fields from events.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: """
+				Templates rendering the values of columns, by the names of the columns.
+
+				By default, columns are written with the values of the top-level fields of events with
+				the same names. Rendered values are parsed as values of the types of their columns, which
+				makes templates suited to deriving the source columns of partition fields from events.
+				"""
+			required: true
+			type: string: syntax: "template"
+		}
+	}
+	compression: {
+		description: "Compression codec applied to the pages of data files."
+		required:    false
+		type: string: {
+			default: "snappy"
+			enum: {
+				gzip: """
+					[Gzip][gzip] compression.
+
+					[gzip]: https://www.gzip.org/
+					"""
+				none: "No compression."
+				snappy: """
+					[Snappy][snappy] compression.
+
+					[snappy]: https://github.com/google/snappy
+					"""
+			}
+		}
+	}
+	encoding: {
+		description: "Transformations to prepare an event for serialization."
+		required:    false
+		type: object: options: {
+			except_fields: {
+				description: "List of fields that will be excluded from the encoded event."
+				required:    false
+				type: array: items: type: string: syntax: "literal"
+			}
+			only_fields: {
+				description: "List of fields that will be included in the encoded event."
+				required:    false
+				type: array: items: type: string: syntax: "literal"
+			}
+			timestamp_format: {
+				description: "Format used for timestamp fields."
+				required:    false
+				type: string: enum: {
+					rfc3339: "Represent the timestamp as a RFC 3339 timestamp."
+					unix:    "Represent the timestamp as a Unix timestamp."
+				}
+			}
+		}
+	}
+	endpoint: {
+		description: "The API endpoint of the service."
+		required:    false
+		type: string: syntax: "literal"
+	}
+	namespace: {
+		description: """
+			The namespace of the table.
+
+			The levels of nested namespaces are separated by dots. With the `glue` catalog, this is the
+			name of the database of the table.
+			"""
+		required: true
+		type: string: {
+			examples: ["logs", "analytics.logs"]
+			syntax: "literal"
+		}
+	}
+	region: {
+		description: "The AWS region to use."
+		required:    false
+		type: string: syntax: "literal"
+	}
+	request: {
+		description: """
+			Middleware settings for outbound requests.
+
+			Various settings can be configured, such as concurrency and rate limits, timeouts, etc.
+			"""
+		required: false
+		type: object: options: {
+			adaptive_concurrency: {
+				description: """
+					Configuration of adaptive concurrency parameters.
+
+					These parameters typically do not require changes from the default, and incorrect values can lead to meta-stable or
+					unstable performance and sink behavior. Proceed with caution.
+					"""
+				required: false
+				type: object: {
+					default: {
+						decrease_ratio:      0.9
+						ewma_alpha:          0.4
+						rtt_deviation_scale: 2.5
+					}
+					options: {
+						decrease_ratio: {
+							description: """
+																The fraction of the current value to set the new concurrency limit when decreasing the limit.
+
+																Valid values are greater than `0` and less than `1`. Smaller values cause the algorithm to scale back rapidly
+																when latency increases.
+
+																Note that the new limit is rounded down after applying this ratio.
+																"""
+							required: false
+							type: float: default: 0.9
+						}
+						ewma_alpha: {
+							description: """
+																The weighting of new measurements compared to older measurements.
+
+																Valid values are greater than `0` and less than `1`.
+
+																ARC uses an exponentially weighted moving average (EWMA) of past RTT measurements as a reference to compare with
+																the current RTT. Smaller values cause this reference to adjust more slowly, which may be useful if a service has
+																unusually high response variability.
+																"""
+							required: false
+							type: float: default: 0.4
+						}
+						rtt_deviation_scale: {
+							description: """
+																Scale of RTT deviations which are not considered anomalous.
+
+																Valid values are greater than or equal to `0`, and we expect reasonable values to range from `1.0` to `3.0`.
+
+																When calculating the past RTT average, we also compute a secondary “deviation” value that indicates how variable
+																those values are. We use that deviation when comparing the past RTT average to the current measurements, so we
+																can ignore increases in RTT that are within an expected range. This factor is used to scale up the deviation to
+																an appropriate range.  Larger values cause the algorithm to ignore larger increases in the RTT.
+																"""
+							required: false
+							type: float: default: 2.5
+						}
+					}
+				}
+			}
+			circuit_breaker: {
+				description: """
+					Configuration of the circuit breaker of outbound requests.
+
+					While the circuit is open, requests aren't sent to the service that keeps failing, and are
+					retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+					passed, a single request is sent to probe the service, and the circuit is closed again if it
+					succeeds.
+					"""
+				required: false
+				type: object: {
+					default: {
+						enabled:              false
+						error_rate_threshold: 0.5
+						minimum_requests:     20
+						open_duration_secs:   30
+						window_secs:          60
+					}
+					options: {
+						enabled: {
+							description: "Whether or not the circuit breaker is enabled."
+							required:    false
+							type: bool: default: false
+						}
+						error_rate_threshold: {
+							description: """
+																The rate of failed requests at which the circuit is opened.
+
+																Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																time out, fail to connect, or have an error or response which would be retried.
+																"""
+							required: false
+							type: float: default: 0.5
+						}
+						minimum_requests: {
+							description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+							required:    false
+							type: uint: default: 20
+						}
+						open_duration_secs: {
+							description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+							required:    false
+							type: uint: default: 30
+						}
+						window_secs: {
+							description: """
+																The time window, in seconds, over which the rate of failed requests is measured.
+
+																It must be greater than `0`.
+																"""
+							required: false
+							type: uint: default: 60
+						}
+					}
+				}
+			}
+			concurrency: {
+				description: "Configuration for outbound request concurrency."
+				required:    false
+				type: {
+					string: {
+						const:   "adaptive"
+						default: "none"
+					}
+					uint: {}
+				}
+			}
+			rate_limit_duration_secs: {
+				description: "The time window, in seconds, used for the `rate_limit_num` option."
+				required:    false
+				type: uint: default: 1
+			}
+			rate_limit_num: {
+				description: "The maximum number of requests allowed within the `rate_limit_duration_secs` time window."
+				required:    false
+				type: uint: default: 9223372036854775807
+			}
+			retry_attempts: {
+				description: """
+					The maximum number of retries to make for failed requests.
+
+					The default, for all intents and purposes, represents an infinite number of retries.
+					"""
+				required: false
+				type: uint: default: 9223372036854775807
+			}
+			retry_initial_backoff_secs: {
+				description: """
+					The amount of time to wait before attempting the first retry for a failed request.
+
+					After the first retry has failed, the fibonacci sequence will be used to select future backoffs.
+					"""
+				required: false
+				type: uint: default: 1
+			}
+			retry_max_duration_secs: {
+				description: "The maximum amount of time, in seconds, to wait between retries."
+				required:    false
+				type: uint: default: 3600
+			}
+			timeout_secs: {
+				description: """
+					The maximum time a request can take before being aborted.
+
+					It is highly recommended that you do not lower this value below the service’s internal timeout, as this could
+					create orphaned requests, pile on retries, and result in duplicate data downstream.
+					"""
+				required: false
+				type: uint: default: 60
+			}
+		}
+	}
+	table: {
+		description: """
+			The name of the table.
+
+			The table must already exist. Its schema and partition spec are loaded when the sink
+			starts, and the sink must be reloaded to write with new ones.
+			"""
+		required: true
+		type: string: {
+			examples: ["events"]
+			syntax: "literal"
+		}
+	}
+	tls: {
+		description: "TLS configuration."
+		required:    false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. Prioritized in the order
+					they are defined.
+					"""
+				required: false
+				type: array: items: type: string: syntax: "literal"
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					If enabled, certificates must be valid in terms of not being expired, as well as being issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that not only the leaf certificate (the
+					certificate presented by the client/server) is valid, but also that the issuer of that certificate is valid, and
+					so on until reaching a root certificate.
+
+					Relevant for both incoming and outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+}
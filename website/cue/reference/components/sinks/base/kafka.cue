@@ -125,6 +125,13 @@ base: components: sinks: kafka: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					pretty: """
+						Encodes an event as human-readable text, writing each field on its own line, with aligned
+						keys and colorized values.
+
+						Events are separated by an empty line. This encoding is meant for reading events while
+						developing, such as in a terminal, not for being parsed.
+						"""
 					raw_message: """
 						No encoding.
 
@@ -155,6 +162,30 @@ base: components: sinks: kafka: configuration: {
 				required:    false
 				type: array: items: type: string: syntax: "literal"
 			}
+			pretty: {
+				description:   "Pretty-specific encoder options."
+				relevant_when: "codec = \"pretty\""
+				required:      false
+				type: object: options: {
+					colors: {
+						description: """
+							Whether keys and values are colorized with ANSI escape codes.
+
+							Pagers such as `less` show colors with their `-R` option.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					timezone: {
+						description: "The timezone timestamps are shown in."
+						required:    false
+						type: string: {
+							default: "local"
+							examples: ["local", "America/New_York", "EST5EDT"]
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -165,6 +196,30 @@ base: components: sinks: kafka: configuration: {
 			}
 		}
 	}
+	forward_source_headers: {
+		description: """
+			Whether the headers of the records read by the `kafka` source are written to records.
+
+			This requires the `vector` log namespace, where the source keeps them in the
+			`%kafka.headers` metadata of events, while with the legacy namespace, they're written with
+			`headers_key`.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	headers: {
+		description: """
+			A map of the headers of records to the templates rendering their values for each event.
+
+			These headers take precedence over those of `headers_key` and of the `kafka` source.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "The template of the value of the header."
+			required:    true
+			type: string: syntax: "template"
+		}
+	}
 	headers_key: {
 		description: """
 			The log field name to use for the Kafka headers.
@@ -174,6 +229,16 @@ base: components: sinks: kafka: configuration: {
 		required: false
 		type: string: syntax: "literal"
 	}
+	idempotence: {
+		description: """
+			Whether the producer is idempotent, which keeps it from writing duplicate records when it
+			retries sending them.
+
+			Idempotence is always enabled for transactional producers.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	key_field: {
 		description: """
 			The log field name or tags key to use for the topic key.
@@ -352,4 +417,42 @@ base: components: sinks: kafka: configuration: {
 		required:    true
 		type: string: syntax: "template"
 	}
+	transaction: {
+		description: """
+			Transactional production of records, which writes them exactly once.
+
+			Records are written in transactions, committed at an interval, and events are acknowledged once
+			the transaction holding their records is committed. Consumers must read with the
+			`read_committed` isolation level to skip the records of aborted transactions.
+			"""
+		required: false
+		type: object: options: {
+			commit_interval_ms: {
+				description: "The interval, in milliseconds, at which transactions are committed."
+				required:    false
+				type: uint: default: 1000
+			}
+			id: {
+				description: """
+					The transactional identifier of the producer.
+
+					It must be unique to the sink, and stay the same when Vector restarts, so that the
+					transaction left open by the previous producer is aborted before new records are written.
+					"""
+				required: true
+				type: string: {
+					examples: ["vector-kafka-sink"]
+					syntax: "literal"
+				}
+			}
+			timeout_ms: {
+				description: """
+					The timeout, in milliseconds, after which the broker aborts transactions that aren't
+					committed.
+					"""
+				required: false
+				type: uint: default: 60000
+			}
+		}
+	}
 }
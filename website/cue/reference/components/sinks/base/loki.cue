@@ -36,12 +36,46 @@ base: components: sinks: loki: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			client_id: {
+				description:   "The identifier of the client."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: syntax: "literal"
+			}
+			client_secret: {
+				description:   "The secret of the client."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: syntax: "literal"
+			}
 			password: {
 				description:   "The password to send."
 				relevant_when: "strategy = \"basic\""
 				required:      true
 				type: string: syntax: "literal"
 			}
+			refresh_before_secs: {
+				description: """
+					How long before they expire access tokens are refreshed, in seconds.
+
+					Access tokens expiring sooner than this are refreshed halfway through their lifetime.
+					"""
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: uint: default: 60
+			}
+			scopes: {
+				description:   "The scopes to request access tokens for."
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: array: {
+					default: []
+					items: type: string: {
+						examples: ["logs:write"]
+						syntax: "literal"
+					}
+				}
+			}
 			strategy: {
 				required: true
 				type: string: enum: {
@@ -57,6 +91,15 @@ base: components: sinks: loki: configuration: {
 
 						The bearer token value (OAuth2, JWT, etc) is passed as-is.
 						"""
+					oauth2: """
+						OAuth2 authentication, with the client credentials grant.
+
+						Access tokens are requested from the token endpoint with the credentials of the client, and
+						passed as bearer tokens. They're cached, and refreshed before they expire.
+
+						This is only supported by the `http`, `loki`, and `prometheus_remote_write` sinks, and the
+						`remote_lookup` transform. Other components fail to build with it.
+						"""
 				}
 			}
 			token: {
@@ -65,6 +108,15 @@ base: components: sinks: loki: configuration: {
 				required:      true
 				type: string: syntax: "literal"
 			}
+			token_endpoint: {
+				description:   "The token endpoint of the authorization server, which access tokens are requested from."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: {
+					examples: ["https://auth.example.com/oauth2/token"]
+					syntax: "literal"
+				}
+			}
 			user: {
 				description:   "The username to send."
 				relevant_when: "strategy = \"basic\""
@@ -173,6 +225,13 @@ base: components: sinks: loki: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					pretty: """
+						Encodes an event as human-readable text, writing each field on its own line, with aligned
+						keys and colorized values.
+
+						Events are separated by an empty line. This encoding is meant for reading events while
+						developing, such as in a terminal, not for being parsed.
+						"""
 					raw_message: """
 						No encoding.
 
@@ -203,6 +262,30 @@ base: components: sinks: loki: configuration: {
 				required:    false
 				type: array: items: type: string: syntax: "literal"
 			}
+			pretty: {
+				description:   "Pretty-specific encoder options."
+				relevant_when: "codec = \"pretty\""
+				required:      false
+				type: object: options: {
+					colors: {
+						description: """
+							Whether keys and values are colorized with ANSI escape codes.
+
+							Pagers such as `less` show colors with their `-R` option.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					timezone: {
+						description: "The timezone timestamps are shown in."
+						required:    false
+						type: string: {
+							default: "local"
+							examples: ["local", "America/New_York", "EST5EDT"]
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -282,6 +365,18 @@ base: components: sinks: loki: configuration: {
 			}
 		}
 	}
+	out_of_order_window_secs: {
+		description: """
+			How far behind the latest timestamp of its stream, in seconds, the timestamp of an event can
+			be and still be sent as is.
+
+			Events further behind are handled according to `out_of_order_action`. Loki 2.4.0 and newer
+			accept events out of order within half of its `max_chunk_age`, which is an hour by default,
+			but reject those further behind with `400` responses.
+			"""
+		required: false
+		type: uint: default: 0
+	}
 	path: {
 		description: """
 			The path to use in the URL of the Loki instance.
@@ -299,6 +394,11 @@ base: components: sinks: loki: configuration: {
 		required:    false
 		type: bool: default: false
 	}
+	remove_structured_metadata_fields: {
+		description: "Whether or not to delete fields from the event when they are used as structured metadata."
+		required:    false
+		type: bool: default: false
+	}
 	remove_timestamp: {
 		description: """
 			Whether or not to remove the timestamp from the event payload.
@@ -373,6 +473,62 @@ base: components: sinks: loki: configuration: {
 					}
 				}
 			}
+			circuit_breaker: {
+				description: """
+					Configuration of the circuit breaker of outbound requests.
+
+					While the circuit is open, requests aren't sent to the service that keeps failing, and are
+					retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+					passed, a single request is sent to probe the service, and the circuit is closed again if it
+					succeeds.
+					"""
+				required: false
+				type: object: {
+					default: {
+						enabled:              false
+						error_rate_threshold: 0.5
+						minimum_requests:     20
+						open_duration_secs:   30
+						window_secs:          60
+					}
+					options: {
+						enabled: {
+							description: "Whether or not the circuit breaker is enabled."
+							required:    false
+							type: bool: default: false
+						}
+						error_rate_threshold: {
+							description: """
+																The rate of failed requests at which the circuit is opened.
+
+																Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																time out, fail to connect, or have an error or response which would be retried.
+																"""
+							required: false
+							type: float: default: 0.5
+						}
+						minimum_requests: {
+							description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+							required:    false
+							type: uint: default: 20
+						}
+						open_duration_secs: {
+							description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+							required:    false
+							type: uint: default: 30
+						}
+						window_secs: {
+							description: """
+																The time window, in seconds, over which the rate of failed requests is measured.
+
+																It must be greater than `0`.
+																"""
+							required: false
+							type: uint: default: 60
+						}
+					}
+				}
+			}
 			concurrency: {
 				description: "Configuration for outbound request concurrency."
 				required:    false
@@ -429,6 +585,74 @@ base: components: sinks: loki: configuration: {
 			}
 		}
 	}
+	shard_streams: {
+		description: """
+			Automatic sharding of streams.
+
+			Loki limits the rate at which each stream, which is each unique set of labels, can be written
+			to, and rejects pushes exceeding it with `429` responses. When enabled, streams whose rate
+			exceeds `rate_limit_bytes` are split into several streams, with a label holding the shard of
+			each event.
+			"""
+		required: false
+		type: object: options: {
+			enabled: {
+				description: "Whether or not to shard streams whose rate exceeds `rate_limit_bytes`."
+				required:    false
+				type: bool: default: false
+			}
+			label: {
+				description: "The name of the label holding the shard of events."
+				required:    false
+				type: string: default: "__stream_shard__"
+			}
+			max_shards: {
+				description: "The maximum number of shards that a stream is split into."
+				required:    false
+				type: uint: default: 16
+			}
+			rate_limit_bytes: {
+				description: """
+					The rate of a stream, in bytes per second, above which it is sharded.
+
+					This should be below the `per_stream_rate_limit` of Loki, which is 3MB by default.
+					"""
+				required: false
+				type: uint: default: 1572864
+			}
+		}
+	}
+	structured_metadata: {
+		description: """
+			A set of structured metadata that is attached to each event.
+
+			Structured metadata are key/value pairs that aren't indexed by Loki, which makes them a
+			better fit than labels for high cardinality attributes, such as trace IDs.
+
+			Both keys and values are templateable, and keys can be suffixed with a “*” to allow the
+			expansion of objects into multiple pairs, as with `labels`.
+
+			Note: Structured metadata are only supported by Loki 2.9 or later, and must be enabled
+			with `allow_structured_metadata` in its limits.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: """
+				A set of structured metadata that is attached to each event.
+
+				Structured metadata are key/value pairs that aren't indexed by Loki, which makes them a
+				better fit than labels for high cardinality attributes, such as trace IDs.
+
+				Both keys and values are templateable, and keys can be suffixed with a “*” to allow the
+				expansion of objects into multiple pairs, as with `labels`.
+
+				Note: Structured metadata are only supported by Loki 2.9 or later, and must be enabled
+				with `allow_structured_metadata` in its limits.
+				"""
+			required: true
+			type: string: syntax: "template"
+		}
+	}
 	tenant_id: {
 		description: """
 			The tenant ID to send.
@@ -173,6 +173,13 @@ base: components: sinks: nats: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					pretty: """
+						Encodes an event as human-readable text, writing each field on its own line, with aligned
+						keys and colorized values.
+
+						Events are separated by an empty line. This encoding is meant for reading events while
+						developing, such as in a terminal, not for being parsed.
+						"""
 					raw_message: """
 						No encoding.
 
@@ -203,6 +210,30 @@ base: components: sinks: nats: configuration: {
 				required:    false
 				type: array: items: type: string: syntax: "literal"
 			}
+			pretty: {
+				description:   "Pretty-specific encoder options."
+				relevant_when: "codec = \"pretty\""
+				required:      false
+				type: object: options: {
+					colors: {
+						description: """
+							Whether keys and values are colorized with ANSI escape codes.
+
+							Pagers such as `less` show colors with their `-R` option.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					timezone: {
+						description: "The timezone timestamps are shown in."
+						required:    false
+						type: string: {
+							default: "local"
+							examples: ["local", "America/New_York", "EST5EDT"]
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -213,6 +244,57 @@ base: components: sinks: nats: configuration: {
 			}
 		}
 	}
+	jetstream: {
+		description: """
+			Configuration for publishing messages to JetStream.
+
+			When set, messages are published to the JetStream stream bound to `subject`, and events are
+			only acknowledged once the stream has acknowledged their message. Publishing is retried when
+			the acknowledgement isn't received, so messages should be given an ID for the stream to discard
+			their duplicates.
+			"""
+		required: false
+		type: object: options: {
+			ack_timeout_ms: {
+				description: "The amount of time, in milliseconds, to wait for JetStream to acknowledge a message."
+				required:    false
+				type: uint: default: 5000
+			}
+			expected_stream: {
+				description: """
+					The name of the stream that messages are expected to be stored in.
+
+					Messages published to a subject bound to another stream are rejected by JetStream, and the
+					healthcheck fails if the stream doesn't exist.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			message_id: {
+				description: """
+					The ID of messages, sent in their `Nats-Msg-Id` header.
+
+					JetStream discards the messages with the same ID as one it stored within the duplicate
+					window of the stream.
+					"""
+				required: false
+				type: string: {
+					examples: ["{{ id }}"]
+					syntax: "template"
+				}
+			}
+			retry_attempts: {
+				description: "The number of times publishing a message is retried when it isn't acknowledged."
+				required:    false
+				type: uint: default: 5
+			}
+			retry_backoff_ms: {
+				description: "The amount of time, in milliseconds, to wait before retrying to publish a message."
+				required:    false
+				type: uint: default: 1000
+			}
+		}
+	}
 	subject: {
 		description: "The NATS subject to publish messages to."
 		required:    true
@@ -48,12 +48,46 @@ base: components: sinks: prometheus_exporter: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			client_id: {
+				description:   "The identifier of the client."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: syntax: "literal"
+			}
+			client_secret: {
+				description:   "The secret of the client."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: syntax: "literal"
+			}
 			password: {
 				description:   "The password to send."
 				relevant_when: "strategy = \"basic\""
 				required:      true
 				type: string: syntax: "literal"
 			}
+			refresh_before_secs: {
+				description: """
+					How long before they expire access tokens are refreshed, in seconds.
+
+					Access tokens expiring sooner than this are refreshed halfway through their lifetime.
+					"""
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: uint: default: 60
+			}
+			scopes: {
+				description:   "The scopes to request access tokens for."
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: array: {
+					default: []
+					items: type: string: {
+						examples: ["logs:write"]
+						syntax: "literal"
+					}
+				}
+			}
 			strategy: {
 				required: true
 				type: string: enum: {
@@ -69,6 +103,15 @@ base: components: sinks: prometheus_exporter: configuration: {
 
 						The bearer token value (OAuth2, JWT, etc) is passed as-is.
 						"""
+					oauth2: """
+						OAuth2 authentication, with the client credentials grant.
+
+						Access tokens are requested from the token endpoint with the credentials of the client, and
+						passed as bearer tokens. They're cached, and refreshed before they expire.
+
+						This is only supported by the `http`, `loki`, and `prometheus_remote_write` sinks, and the
+						`remote_lookup` transform. Other components fail to build with it.
+						"""
 				}
 			}
 			token: {
@@ -77,6 +120,15 @@ base: components: sinks: prometheus_exporter: configuration: {
 				required:      true
 				type: string: syntax: "literal"
 			}
+			token_endpoint: {
+				description:   "The token endpoint of the authorization server, which access tokens are requested from."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: {
+					examples: ["https://auth.example.com/oauth2/token"]
+					syntax: "literal"
+				}
+			}
 			user: {
 				description:   "The username to send."
 				relevant_when: "strategy = \"basic\""
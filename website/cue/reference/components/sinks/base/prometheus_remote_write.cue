@@ -43,6 +43,18 @@ base: components: sinks: prometheus_remote_write: configuration: {
 				required:      true
 				type: string: syntax: "literal"
 			}
+			client_id: {
+				description:   "The identifier of the client."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: syntax: "literal"
+			}
+			client_secret: {
+				description:   "The secret of the client."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: syntax: "literal"
+			}
 			credentials_file: {
 				description:   "Path to the credentials file."
 				relevant_when: "strategy = \"aws\""
@@ -67,6 +79,16 @@ base: components: sinks: prometheus_remote_write: configuration: {
 				required:      false
 				type: string: syntax: "literal"
 			}
+			refresh_before_secs: {
+				description: """
+					How long before they expire access tokens are refreshed, in seconds.
+
+					Access tokens expiring sooner than this are refreshed halfway through their lifetime.
+					"""
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: uint: default: 60
+			}
 			region: {
 				description: """
 					The AWS region to send STS requests to.
@@ -78,6 +100,18 @@ base: components: sinks: prometheus_remote_write: configuration: {
 				required:      false
 				type: string: syntax: "literal"
 			}
+			scopes: {
+				description:   "The scopes to request access tokens for."
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: array: {
+					default: []
+					items: type: string: {
+						examples: ["logs:write"]
+						syntax: "literal"
+					}
+				}
+			}
 			secret_access_key: {
 				description:   "The AWS secret access key."
 				relevant_when: "strategy = \"aws\""
@@ -94,6 +128,12 @@ base: components: sinks: prometheus_remote_write: configuration: {
 
 						A bearer token (OAuth2, JWT, etc) is passed as-is.
 						"""
+					oauth2: """
+						OAuth2 authentication, with the client credentials grant.
+
+						Access tokens are requested from the token endpoint with the credentials of the client, and
+						passed as bearer tokens. They're cached, and refreshed before they expire.
+						"""
 				}
 			}
 			token: {
@@ -102,6 +142,15 @@ base: components: sinks: prometheus_remote_write: configuration: {
 				required:      true
 				type: string: syntax: "literal"
 			}
+			token_endpoint: {
+				description:   "The token endpoint of the authorization server, which access tokens are requested from."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: {
+					examples: ["https://auth.example.com/oauth2/token"]
+					syntax: "literal"
+				}
+			}
 			user: {
 				description:   "Basic authentication username."
 				relevant_when: "strategy = \"basic\""
@@ -183,6 +232,20 @@ base: components: sinks: prometheus_remote_write: configuration: {
 		required:    true
 		type: string: syntax: "literal"
 	}
+	native_histograms: {
+		description: """
+			Whether to send [distribution][dist_metric_docs] metrics aggregated into histograms as
+			native histograms.
+
+			Native histograms have exponential buckets, rather than the buckets set by `buckets`, and
+			are only accepted by Prometheus with its native histograms feature enabled. Aggregated
+			histograms are still sent with their own buckets.
+
+			[dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+			"""
+		required: false
+		type: bool: default: false
+	}
 	quantiles: {
 		description: """
 			Quantiles to use for aggregating [distribution][dist_metric_docs] metrics into a summary.
@@ -260,6 +323,62 @@ base: components: sinks: prometheus_remote_write: configuration: {
 					}
 				}
 			}
+			circuit_breaker: {
+				description: """
+					Configuration of the circuit breaker of outbound requests.
+
+					While the circuit is open, requests aren't sent to the service that keeps failing, and are
+					retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+					passed, a single request is sent to probe the service, and the circuit is closed again if it
+					succeeds.
+					"""
+				required: false
+				type: object: {
+					default: {
+						enabled:              false
+						error_rate_threshold: 0.5
+						minimum_requests:     20
+						open_duration_secs:   30
+						window_secs:          60
+					}
+					options: {
+						enabled: {
+							description: "Whether or not the circuit breaker is enabled."
+							required:    false
+							type: bool: default: false
+						}
+						error_rate_threshold: {
+							description: """
+																The rate of failed requests at which the circuit is opened.
+
+																Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																time out, fail to connect, or have an error or response which would be retried.
+																"""
+							required: false
+							type: float: default: 0.5
+						}
+						minimum_requests: {
+							description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+							required:    false
+							type: uint: default: 20
+						}
+						open_duration_secs: {
+							description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+							required:    false
+							type: uint: default: 30
+						}
+						window_secs: {
+							description: """
+																The time window, in seconds, over which the rate of failed requests is measured.
+
+																It must be greater than `0`.
+																"""
+							required: false
+							type: uint: default: 60
+						}
+					}
+				}
+			}
 			concurrency: {
 				description: "Configuration for outbound request concurrency."
 				required:    false
@@ -316,6 +435,17 @@ base: components: sinks: prometheus_remote_write: configuration: {
 			}
 		}
 	}
+	send_exemplars: {
+		description: """
+			Whether to send the exemplars of metrics.
+
+			Exemplars are read from the metadata of metric events, where the `prometheus_remote_write`
+			source attaches those it receives, and are sent with the series of counters, gauges, and
+			the buckets of histograms. Prometheus only stores them with its exemplar storage enabled.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	tenant_id: {
 		description: """
 			The tenant ID to send.
@@ -132,6 +132,13 @@ base: components: sinks: pulsar: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					pretty: """
+						Encodes an event as human-readable text, writing each field on its own line, with aligned
+						keys and colorized values.
+
+						Events are separated by an empty line. This encoding is meant for reading events while
+						developing, such as in a terminal, not for being parsed.
+						"""
 					raw_message: """
 						No encoding.
 
@@ -162,6 +169,30 @@ base: components: sinks: pulsar: configuration: {
 				required:    false
 				type: array: items: type: string: syntax: "literal"
 			}
+			pretty: {
+				description:   "Pretty-specific encoder options."
+				relevant_when: "codec = \"pretty\""
+				required:      false
+				type: object: options: {
+					colors: {
+						description: """
+							Whether keys and values are colorized with ANSI escape codes.
+
+							Pagers such as `less` show colors with their `-R` option.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					timezone: {
+						description: "The timezone timestamps are shown in."
+						required:    false
+						type: string: {
+							default: "local"
+							examples: ["local", "America/New_York", "EST5EDT"]
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -177,6 +208,19 @@ base: components: sinks: pulsar: configuration: {
 		required:    true
 		type: string: syntax: "literal"
 	}
+	partition_key: {
+		description: """
+			The key of messages, which routes them to the partitions of the topic.
+
+			Messages with the same key are routed to the same partition. Messages whose key can't be
+			rendered are sent without a key.
+			"""
+		required: false
+		type: string: {
+			examples: ["{{ host }}", "{{ tenant }}-{{ service }}"]
+			syntax: "template"
+		}
+	}
 	partition_key_field: {
 		description: "Log field to use as Pulsar message key."
 		required:    false
@@ -187,6 +231,38 @@ base: components: sinks: pulsar: configuration: {
 		required:    false
 		type: string: syntax: "literal"
 	}
+	schema: {
+		description: """
+			The schema of messages, declared to the schema registry of the broker.
+
+			The broker checks that the schema is compatible with the schema of the topic when the producer
+			connects, and the sink fails to start otherwise. By default, the schema of the `avro` codec is
+			declared when encoding with it.
+			"""
+		required: false
+		type: object: options: {
+			definition: {
+				description: """
+					The definition of the schema, as an Avro schema in JSON, which describes JSON documents too.
+
+					Defaults to the schema of the `avro` codec, for `avro` schemas.
+					"""
+				required: false
+				type: string: {
+					examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					syntax: "literal"
+				}
+			}
+			type: {
+				description: "The type of a schema."
+				required:    true
+				type: string: enum: {
+					avro: "Messages are Avro records, encoded with the `avro` codec."
+					json: "Messages are JSON documents, encoded with the `json` codec."
+				}
+			}
+		}
+	}
 	topic: {
 		description: "The Pulsar topic name to write events to."
 		required:    true
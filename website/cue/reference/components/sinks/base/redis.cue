@@ -53,6 +53,16 @@ base: components: sinks: redis: configuration: {
 			}
 		}
 	}
+	cluster: {
+		description: """
+			Whether `url` is the address of a node of a Redis Cluster.
+
+			The topology of the cluster is then discovered from that node, and each message is sent to
+			the node serving the slot of its key.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	data_type: {
 		description: "Redis data type to store messages in."
 		required:    false
@@ -71,6 +81,12 @@ base: components: sinks: redis: configuration: {
 
 					This is the default.
 					"""
+				stream: """
+					The Redis `stream` type.
+
+					Messages are appended to the stream with `XADD`, which can trim it to a maximum length as
+					they are added.
+					"""
 			}
 		}
 	}
@@ -123,6 +139,13 @@ base: components: sinks: redis: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					pretty: """
+						Encodes an event as human-readable text, writing each field on its own line, with aligned
+						keys and colorized values.
+
+						Events are separated by an empty line. This encoding is meant for reading events while
+						developing, such as in a terminal, not for being parsed.
+						"""
 					raw_message: """
 						No encoding.
 
@@ -153,6 +176,30 @@ base: components: sinks: redis: configuration: {
 				required:    false
 				type: array: items: type: string: syntax: "literal"
 			}
+			pretty: {
+				description:   "Pretty-specific encoder options."
+				relevant_when: "codec = \"pretty\""
+				required:      false
+				type: object: options: {
+					colors: {
+						description: """
+							Whether keys and values are colorized with ANSI escape codes.
+
+							Pagers such as `less` show colors with their `-R` option.
+							"""
+						required: false
+						type: bool: default: true
+					}
+					timezone: {
+						description: "The timezone timestamps are shown in."
+						required:    false
+						type: string: {
+							default: "local"
+							examples: ["local", "America/New_York", "EST5EDT"]
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -255,6 +302,62 @@ base: components: sinks: redis: configuration: {
 					}
 				}
 			}
+			circuit_breaker: {
+				description: """
+					Configuration of the circuit breaker of outbound requests.
+
+					While the circuit is open, requests aren't sent to the service that keeps failing, and are
+					retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+					passed, a single request is sent to probe the service, and the circuit is closed again if it
+					succeeds.
+					"""
+				required: false
+				type: object: {
+					default: {
+						enabled:              false
+						error_rate_threshold: 0.5
+						minimum_requests:     20
+						open_duration_secs:   30
+						window_secs:          60
+					}
+					options: {
+						enabled: {
+							description: "Whether or not the circuit breaker is enabled."
+							required:    false
+							type: bool: default: false
+						}
+						error_rate_threshold: {
+							description: """
+																The rate of failed requests at which the circuit is opened.
+
+																Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																time out, fail to connect, or have an error or response which would be retried.
+																"""
+							required: false
+							type: float: default: 0.5
+						}
+						minimum_requests: {
+							description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+							required:    false
+							type: uint: default: 20
+						}
+						open_duration_secs: {
+							description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+							required:    false
+							type: uint: default: 30
+						}
+						window_secs: {
+							description: """
+																The time window, in seconds, over which the rate of failed requests is measured.
+
+																It must be greater than `0`.
+																"""
+							required: false
+							type: uint: default: 60
+						}
+					}
+				}
+			}
 			concurrency: {
 				description: "Configuration for outbound request concurrency."
 				required:    false
@@ -311,6 +414,36 @@ base: components: sinks: redis: configuration: {
 			}
 		}
 	}
+	stream_option: {
+		description: "Stream-specific options."
+		required:    false
+		type: object: options: {
+			approximate: {
+				description: """
+					Whether to trim the stream approximately.
+
+					Approximate trimming (`MAXLEN ~`) only removes whole nodes of the stream, which is much more
+					efficient, but can leave the stream with slightly more entries than `maxlen`.
+					"""
+				required: false
+				type: bool: default: true
+			}
+			field: {
+				description: "The field of the stream entries to store messages in."
+				required:    false
+				type: string: default: "message"
+			}
+			maxlen: {
+				description: """
+					The maximum length to trim the stream to as messages are added.
+
+					If not set, the stream is not trimmed.
+					"""
+				required: false
+				type: uint: examples: [10000]
+			}
+		}
+	}
 	url: {
 		description: """
 			The Redis URL to connect to.
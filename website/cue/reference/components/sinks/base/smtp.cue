@@ -0,0 +1,383 @@
+package metadata
+
+base: components: sinks: smtp: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled for this sink.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how Vector handles event acknowledgement.
+
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: """
+				Whether or not end-to-end acknowledgements are enabled.
+
+				When enabled for a sink, any source connected to that sink, where the source supports
+				end-to-end acknowledgements as well, will wait for events to be acknowledged by the sink
+				before acknowledging them at the source.
+
+				Enabling or disabling acknowledgements at the sink level takes precedence over any global
+				[`acknowledgements`][global_acks] configuration.
+
+				[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+				"""
+			required: false
+			type: bool: {}
+		}
+	}
+	auth: {
+		description: "The credentials emails are sent with."
+		required:    false
+		type: object: options: {
+			password: {
+				description: "The password to authenticate with."
+				required:    true
+				type: string: {
+					examples: ["${SMTP_PASSWORD}"]
+					syntax: "literal"
+				}
+			}
+			username: {
+				description: "The username to authenticate with."
+				required:    true
+				type: string: {
+					examples: ["vector@example.com"]
+					syntax: "literal"
+				}
+			}
+		}
+	}
+	batch: {
+		description: "Event batching behavior."
+		required:    false
+		type: object: options: {
+			max_bytes: {
+				description: """
+					The maximum size of a batch that will be processed by a sink.
+
+					This is based on the uncompressed size of the batched events, before they are
+					serialized / compressed.
+					"""
+				required: false
+				type: uint: {}
+			}
+			max_events: {
+				description: "The maximum size of a batch, in events, before it is flushed."
+				required:    false
+				type: uint: {}
+			}
+			timeout_secs: {
+				description: "The maximum age of a batch, in seconds, before it is flushed."
+				required:    false
+				type: float: {}
+			}
+		}
+	}
+	body: {
+		description: """
+			The template of the plain-text body of emails.
+
+			The bodies of the events of batches are sent as the lines of their email.
+			"""
+		required: false
+		type: string: {
+			default: "{{ message }}"
+			examples: ["{{ timestamp }} {{ host }}: {{ message }}"]
+			syntax: "template"
+		}
+	}
+	from: {
+		description: "The sender of emails, as an address, optionally with a name."
+		required:    true
+		type: string: {
+			examples: ["Vector <vector@example.com>"]
+			syntax: "literal"
+		}
+	}
+	host: {
+		description: "The host of the SMTP server."
+		required:    true
+		type: string: {
+			examples: ["smtp.example.com"]
+			syntax: "literal"
+		}
+	}
+	html_body: {
+		description: """
+			The template of the HTML body of emails.
+
+			When set, emails have both a plain-text and an HTML body, and clients show the one they
+			prefer.
+			"""
+		required: false
+		type: string: {
+			examples: ["<p><b>{{ host }}</b>: {{ message }}</p>"]
+			syntax: "template"
+		}
+	}
+	port: {
+		description: """
+			The port of the SMTP server.
+
+			Defaults to `587` with `STARTTLS`, `465` with implicit TLS, and `25` without TLS.
+			"""
+		required: false
+		type: uint: examples: [2525]
+	}
+	request: {
+		description: """
+			Middleware settings for outbound requests.
+
+			Various settings can be configured, such as concurrency and rate limits, timeouts, etc.
+			"""
+		required: false
+		type: object: options: {
+			adaptive_concurrency: {
+				description: """
+					Configuration of adaptive concurrency parameters.
+
+					These parameters typically do not require changes from the default, and incorrect values can lead to meta-stable or
+					unstable performance and sink behavior. Proceed with caution.
+					"""
+				required: false
+				type: object: {
+					default: {
+						decrease_ratio:      0.9
+						ewma_alpha:          0.4
+						rtt_deviation_scale: 2.5
+					}
+					options: {
+						decrease_ratio: {
+							description: """
+																The fraction of the current value to set the new concurrency limit when decreasing the limit.
+
+																Valid values are greater than `0` and less than `1`. Smaller values cause the algorithm to scale back rapidly
+																when latency increases.
+
+																Note that the new limit is rounded down after applying this ratio.
+																"""
+							required: false
+							type: float: default: 0.9
+						}
+						ewma_alpha: {
+							description: """
+																The weighting of new measurements compared to older measurements.
+
+																Valid values are greater than `0` and less than `1`.
+
+																ARC uses an exponentially weighted moving average (EWMA) of past RTT measurements as a reference to compare with
+																the current RTT. Smaller values cause this reference to adjust more slowly, which may be useful if a service has
+																unusually high response variability.
+																"""
+							required: false
+							type: float: default: 0.4
+						}
+						rtt_deviation_scale: {
+							description: """
+																Scale of RTT deviations which are not considered anomalous.
+
+																Valid values are greater than or equal to `0`, and we expect reasonable values to range from `1.0` to `3.0`.
+
+																When calculating the past RTT average, we also compute a secondary “deviation” value that indicates how variable
+																those values are. We use that deviation when comparing the past RTT average to the current measurements, so we
+																can ignore increases in RTT that are within an expected range. This factor is used to scale up the deviation to
+																an appropriate range.  Larger values cause the algorithm to ignore larger increases in the RTT.
+																"""
+							required: false
+							type: float: default: 2.5
+						}
+					}
+				}
+			}
+			circuit_breaker: {
+				description: """
+					Configuration of the circuit breaker of outbound requests.
+
+					While the circuit is open, requests aren't sent to the service that keeps failing, and are
+					retried after backing off instead, like requests that failed. Once `open_duration_secs` have
+					passed, a single request is sent to probe the service, and the circuit is closed again if it
+					succeeds.
+					"""
+				required: false
+				type: object: {
+					default: {
+						enabled:              false
+						error_rate_threshold: 0.5
+						minimum_requests:     20
+						open_duration_secs:   30
+						window_secs:          60
+					}
+					options: {
+						enabled: {
+							description: "Whether or not the circuit breaker is enabled."
+							required:    false
+							type: bool: default: false
+						}
+						error_rate_threshold: {
+							description: """
+																The rate of failed requests at which the circuit is opened.
+
+																Valid values are greater than `0` and less than or equal to `1`. Requests fail when they
+																time out, fail to connect, or have an error or response which would be retried.
+																"""
+							required: false
+							type: float: default: 0.5
+						}
+						minimum_requests: {
+							description: "The minimum number of requests within `window_secs` for the circuit to be opened."
+							required:    false
+							type: uint: default: 20
+						}
+						open_duration_secs: {
+							description: "The time, in seconds, the circuit stays open before a request is sent to probe the service."
+							required:    false
+							type: uint: default: 30
+						}
+						window_secs: {
+							description: """
+																The time window, in seconds, over which the rate of failed requests is measured.
+
+																It must be greater than `0`.
+																"""
+							required: false
+							type: uint: default: 60
+						}
+					}
+				}
+			}
+			concurrency: {
+				description: "Configuration for outbound request concurrency."
+				required:    false
+				type: {
+					string: {
+						const:   "adaptive"
+						default: "none"
+					}
+					uint: {}
+				}
+			}
+			rate_limit_duration_secs: {
+				description: "The time window, in seconds, used for the `rate_limit_num` option."
+				required:    false
+				type: uint: default: 1
+			}
+			rate_limit_num: {
+				description: "The maximum number of requests allowed within the `rate_limit_duration_secs` time window."
+				required:    false
+				type: uint: default: 9223372036854775807
+			}
+			retry_attempts: {
+				description: """
+					The maximum number of retries to make for failed requests.
+
+					The default, for all intents and purposes, represents an infinite number of retries.
+					"""
+				required: false
+				type: uint: default: 9223372036854775807
+			}
+			retry_initial_backoff_secs: {
+				description: """
+					The amount of time to wait before attempting the first retry for a failed request.
+
+					After the first retry has failed, the fibonacci sequence will be used to select future backoffs.
+					"""
+				required: false
+				type: uint: default: 1
+			}
+			retry_max_duration_secs: {
+				description: "The maximum amount of time, in seconds, to wait between retries."
+				required:    false
+				type: uint: default: 3600
+			}
+			timeout_secs: {
+				description: """
+					The maximum time a request can take before being aborted.
+
+					It is highly recommended that you do not lower this value below the service’s internal timeout, as this could
+					create orphaned requests, pile on retries, and result in duplicate data downstream.
+					"""
+				required: false
+				type: uint: default: 60
+			}
+		}
+	}
+	subject: {
+		description: """
+			The template of the subject of emails.
+
+			Emails of batches have the subject of their first event.
+			"""
+		required: true
+		type: string: {
+			examples: ["[{{ level }}] {{ host }}"]
+			syntax: "template"
+		}
+	}
+	tls: {
+		description: "TLS configuration of connections to the SMTP server."
+		required:    false
+		type: object: options: {
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file, in PEM format, the certificate of the
+					server is verified with.
+					"""
+				required: false
+				type: string: {
+					examples: ["/path/to/certificate_authority.crt"]
+					syntax: "literal"
+				}
+			}
+			mode: {
+				description: "How connections to the SMTP server are secured."
+				required:    false
+				type: string: {
+					default: "starttls"
+					enum: {
+						implicit: "Connections use TLS from the start, as with SMTPS."
+						none: """
+							Connections aren't encrypted, which should only be used with servers on the same host or
+							network.
+							"""
+						starttls: "Connections are upgraded to TLS with `STARTTLS`, which the server must support."
+					}
+				}
+			}
+			verify_certificate: {
+				description: """
+					Whether to verify the certificate of the server.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of
+					certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Whether to verify that the certificate of the server is for its host.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote
+					hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+	to: {
+		description: """
+			The recipients of emails, as addresses separated by commas.
+
+			Events are batched by their recipients, and each batch is sent as a single email to them.
+			Events whose recipients aren't valid addresses are dropped.
+			"""
+		required: true
+		type: string: {
+			examples: ["oncall@example.com", "{{ team }}@example.com, ops@example.com"]
+			syntax: "template"
+		}
+	}
+}
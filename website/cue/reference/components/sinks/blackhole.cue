@@ -52,6 +52,29 @@ components: sinks: blackhole: {
 		}
 	}
 
+	how_it_works: {
+		simulation: {
+			title: "Simulated downstream services"
+			body: """
+				By default, this sink consumes events as soon as it receives them, at most at the `rate`
+				it is configured with. To load test adaptive request concurrency, retries, and buffering
+				without a real downstream service, the `simulate` option sends batches of events as
+				requests to a simulated service, through the same request stack as the other sinks,
+				configured by `simulate.batch` and `simulate.request`.
+
+				Simulated requests take a latency drawn from the `simulate.latency` distribution, after
+				waiting for the service to be able to handle them when `simulate.max_events_per_sec` or
+				`simulate.max_bytes_per_sec` cap its throughput. Requests then randomly fail with an
+				error, are throttled, or have their events rejected, at the `simulate.error_rate`,
+				`simulate.throttle_rate` and `simulate.reject_rate`. Failed and throttled requests are
+				retried, which adaptive request concurrency treats as back pressure, as it does the
+				latency of requests.
+
+				The `rate` option can't be set along with `simulate`.
+				"""
+		}
+	}
+
 	input: {
 		logs: true
 		metrics: {
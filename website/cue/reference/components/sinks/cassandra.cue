@@ -0,0 +1,109 @@
+package metadata
+
+components: sinks: cassandra: {
+	title: "Apache Cassandra"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				max_events:   100
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: enabled: false
+			to: {
+				service: services.cassandra
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The keyspaces and tables must already exist, and the user must be allowed to modify
+				them.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.cassandra.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		rows: {
+			title: "Rows"
+			body: """
+				Each event is written as a row of the table rendered by the `keyspace` and `table`
+				templates, whose columns hold the values of the fields they're mapped to with `columns`.
+				Values are converted to the types of their columns, so timestamps can be written to
+				`timestamp` columns, and arrays to `list` or `set` columns. Events whose values can't be
+				converted are dropped.
+
+				Rows are inserted with prepared statements, which are prepared once for each table, as
+				events are first written to it.
+				"""
+		}
+		batching: {
+			title: "Token-aware batching"
+			body: """
+				Rows are batched by partition, so that each [batch](\(urls.cassandra_batch)) only holds
+				rows of a single partition. Batches are then sent to a replica of their partition, which
+				applies them at once, rather than to a coordinator having to forward their rows to other
+				nodes. Writes are made with the `consistency` level, and batches are `unlogged` unless
+				`batch_type` is `logged`.
+				"""
+		}
+		scylladb: {
+			title: "ScyllaDB"
+			body: """
+				The sink uses the CQL protocol, so it writes to [ScyllaDB](\(urls.scylladb)) clusters as
+				well as to Cassandra ones.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -0,0 +1,109 @@
+package metadata
+
+components: sinks: chat_webhook: {
+	title: "Chat Webhook"
+
+	description: """
+		Posts events as the messages of [Slack](\(urls.slack_webhooks)),
+		[Mattermost](\(urls.mattermost_webhooks)), or [Microsoft Teams](\(urls.teams_webhooks))
+		incoming webhooks, rendered with a template, so that alerts reach channels without a bridge
+		between Vector and the chat platform.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Slack", "Mattermost", "Microsoft"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    100_000
+				max_events:   50
+				timeout_secs: 5.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			proxy: enabled:       true
+			request: {
+				enabled:        true
+				rate_limit_num: 1
+				headers:        false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.chat_webhook
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.chat_webhook.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		messages: {
+			title: "Messages"
+			body: """
+				The `text` template renders the text of messages from the fields of events, in the markup
+				of the platform: the [`mrkdwn`](\(urls.slack_mrkdwn)) of Slack, or the Markdown of
+				Mattermost and Microsoft Teams. Slack and Mattermost messages are sent as their `text`,
+				while Microsoft Teams messages are sent as Adaptive Cards holding a text block. Texts
+				longer than the platforms show are truncated.
+
+				Events are sent as messages of their own by default. With `mode` set to `aggregated`, the
+				texts of the events of batches are sent as the lines of a single message, so that bursts
+				of alerts are posted together. Incoming webhooks don't tell the messages they post, so
+				messages can't be posted as the replies of threads.
+				"""
+		}
+		rate_limits: {
+			title: "Rate limits"
+			body: """
+				Incoming webhooks accept about one message per second, so the sink sends one request per
+				second by default, which `request.rate_limit_num` raises. Messages rate limited, or
+				failing because of the platform, are retried, while those rejected, such as because
+				their webhook was removed, are dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -22,7 +22,7 @@ components: sinks: console: {
 				codec: {
 					enabled: true
 					framing: true
-					enum: ["json", "text"]
+					enum: ["json", "pretty", "text"]
 				}
 			}
 			request: enabled: false
@@ -68,6 +68,22 @@ components: sinks: console: {
 		traces: true
 	}
 
+	how_it_works: {
+		pretty: {
+			title: "Pretty output"
+			body: """
+				With the `pretty` codec, the fields of each log event are written on their own line,
+				with their keys aligned, their values colorized, and timestamps shown in the
+				timezone set by `encoding.pretty.timezone`, so that events can be read while
+				developing. The shown fields can be selected with `encoding.only_fields`, or
+				`encoding.except_fields`.
+
+				Set `encoding.pretty.colors` to `false` when writing to a pager that does not show
+				colors, or use `less -R`.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
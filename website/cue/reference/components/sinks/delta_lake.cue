@@ -0,0 +1,106 @@
+package metadata
+
+components: sinks: delta_lake: {
+	title: "Delta Lake"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure", "GCP"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    100_000_000
+				timeout_secs: 300.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: enabled: false
+			to: {
+				service: services.delta_lake
+
+				interface: {
+					socket: {
+						api: {
+							title: "Delta Lake transaction log protocol"
+							url:   urls.delta_lake_protocol
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The table must already exist, and be stored in Azure Data Lake Storage or Google Cloud
+				Storage.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.delta_lake.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		rows: {
+			title: "Rows"
+			body: """
+				Each event is written as a row of the table, with the values of its top-level fields named
+				after the columns of the table. Timestamps are written to `timestamp` and `date` columns,
+				and to `string` columns as RFC 3339 strings. Values that don't fit the types of their
+				columns are left null, and objects and arrays are written to columns of nested types.
+
+				With `schema_evolution` set to `merge`, columns are added to the table for the fields of
+				events it has no columns for, in a commit of its own before the batch is written.
+				"""
+		}
+		commits: {
+			title: "Commits"
+			body: """
+				Each batch is written to [Parquet](\(urls.apache_parquet)) data files, one for each
+				partition of the table, and the files are appended to the table in a single commit to its
+				[transaction log](\(urls.delta_lake_protocol)). Events are acknowledged once their commit
+				succeeds.
+
+				When other writers commit to the table first, the table is updated and the commit is
+				attempted again, up to `max_commit_attempts` times.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
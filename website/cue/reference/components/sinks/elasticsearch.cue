@@ -368,11 +368,23 @@ components: sinks: elasticsearch: {
 			description: """
 				Whether or not to retry successful requests containing partial failures.
 
+				Only the documents that failed with retriable errors, which are backpressure and server
+				errors, are written again.
+
 				To avoid duplicates in Elasticsearch, please use option `id_key`.
 				"""
 			required: false
 			type: bool: default: false
 		}
+		reroute_rejected: {
+			common: false
+			description: """
+				Reroutes the documents rejected by Elasticsearch to the `rejected` output instead of
+				dropping them.
+				"""
+			required: false
+			type: bool: default: false
+		}
 	}
 
 	input: {
@@ -381,6 +393,21 @@ components: sinks: elasticsearch: {
 		traces:  false
 	}
 
+	outputs: [
+		{
+			name: "rejected"
+			description: """
+				This sink implements a `rejected` output when `reroute_rejected` is set to
+				`true`. Documents rejected by Elasticsearch, such as those with mapping
+				conflicts, are sent to this output instead of being dropped. For a sink
+				component named `foo`, this output can be accessed by specifying
+				`foo.rejected` as the input to another component. Events sent to this output
+				are in their original form, annotated with the index of the document and the
+				status, error type and reason returned by Elasticsearch.
+				"""
+		},
+	]
+
 	how_it_works: {
 		conflicts: {
 			title: "Conflicts"
@@ -426,12 +453,33 @@ components: sinks: elasticsearch: {
 				typed. To change this behavior, refer to the Elasticsearch [`ignore_malformed`
 				setting](\(urls.elasticsearch_ignore_malformed)).
 
+				The response of each request is checked item by item, so that each document is handled on its own.
+				Documents rejected by Elasticsearch are dropped, unless `reroute_rejected` is set, in which case they
+				are sent to the `rejected` output of the sink, annotated with the error returned by Elasticsearch.
+
 				By default, partial failures are not retried. To enable retries, set `request_retry_partial`. Once enabled it will
-				retry whole partially failed requests. As such it is advised to use `id_key` to avoid duplicates.
+				retry only the documents that failed with retriable errors, such as backpressure or server errors. As such it is
+				advised to use `id_key` to avoid duplicates.
 				"""
 		}
 
 		aws_authentication: components._aws.how_it_works.aws_authentication
+
+		opensearch_serverless: {
+			title: "Amazon OpenSearch Serverless and Ingestion"
+			body:  """
+				Amazon OpenSearch Serverless collections and OpenSearch Ingestion pipelines can be written
+				to directly, with AWS authentication, by setting `opensearch_service_type` to `serverless` or
+				`ingestion`. Requests are then signed for the `aoss` or `osis` service, and the region of the
+				endpoint is used when `aws.region` isn't set.
+
+				As neither of them expose the version or the health of a cluster, the API version isn't
+				detected and health checks always pass. Ingestion pipelines receive documents as JSON
+				arrays at the path of their HTTP source, which must be part of the endpoint, such as
+				`https://my-pipeline-abc123.us-east-1.osis.amazonaws.com/log/ingest`, and are routed
+				to their sinks by the pipelines themselves.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
@@ -90,6 +90,21 @@ components: sinks: file: {
 				disk before acknowledging the events.
 				"""
 		}
+
+		rotation: {
+			title: "Rotation"
+			body: """
+				With the `rotate` options, Vector rotates files once they reach a size or an age,
+				renaming them by appending the time of their rotation to their name, and writing
+				the following events to a new file. Rotated files can be compressed, and the oldest
+				of them deleted once there are more of them, or they are larger in total, than kept.
+
+				Rotated files are compressed, and old ones deleted, as part of the rotation, which
+				delays the events written by the sink meanwhile. Rotated files are recognized, and
+				ordered, by the timestamps in their names, so files of other programs should not be
+				named like them.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
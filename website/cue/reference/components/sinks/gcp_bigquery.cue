@@ -0,0 +1,144 @@
+package metadata
+
+components: sinks: gcp_bigquery: {
+	title: "GCP BigQuery"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["GCP"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.gcp_bigquery
+
+				interface: {
+					socket: {
+						api: {
+							title: "BigQuery Storage Write API"
+							url:   urls.gcp_bigquery_storage_write_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The table must already exist. Its schema is loaded when the sink starts, so the sink must be
+				restarted to write to columns added to the table later on.
+				""",
+		]
+		warnings: [
+			"""
+				With `write_mode` set to `default`, the rows of batches that are retried may be written more than
+				once. Set it to `pending` for rows to be written exactly once.
+				""",
+		]
+		notices: []
+	}
+
+	configuration: base.components.sinks.gcp_bigquery.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		rows: {
+			title: "Rows"
+			body: """
+				Each event is written as a row of the table, with the values of its top-level fields named
+				after the columns of the table, encoded as Protocol Buffers messages built from the schema
+				of the table. Objects are written to `STRUCT` columns, arrays to `REPEATED` columns, and
+				any value to `JSON` columns. Timestamps and RFC 3339 strings are written to `TIMESTAMP`
+				columns, and timestamps are also written to `DATE`, `DATETIME` and `TIME` columns, in UTC.
+
+				Values that don't fit the types of their columns are left null, and batches with rows
+				that BigQuery rejects, such as rows missing `REQUIRED` columns, are dropped.
+				"""
+		}
+		write_modes: {
+			title: "Write modes"
+			body: """
+				With `write_mode` set to `default`, batches are appended to the default stream of the
+				table, and their rows are available as soon as they are appended.
+
+				With `write_mode` set to `pending`, each batch is appended to a pending stream of its
+				own, at offsets starting from zero, and the stream is committed once the whole batch is
+				appended. Retries append to the same stream, at the same offsets, so rows appended by
+				previous attempts aren't appended again, and each batch is written exactly once.
+				"""
+		}
+		retries: {
+			title: "Retries"
+			body: """
+				Requests that exceed the quotas of the [Storage Write API](\(urls.gcp_bigquery_storage_write_api)),
+				or fail because of transient errors, are retried, following the `request` settings.
+				Batches larger than the 10MB requests of the API are split in several requests.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform: "gcp"
+			_service: "bigquery"
+
+			policies: [
+				{
+					_action: "tables.get"
+					required_for: ["healthcheck", "operation"]
+				},
+				{
+					_action: "tables.updateData"
+					required_for: ["operation"]
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
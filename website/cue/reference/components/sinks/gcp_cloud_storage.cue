@@ -178,6 +178,15 @@ components: sinks: gcp_cloud_storage: {
 	}
 
 	how_it_works: {
+		encryption: {
+			title: "Customer-managed encryption keys"
+			body:  """
+					Objects are encrypted with the default key of their bucket, unless the
+					`kms_key_name` option names a [Cloud KMS key](\(urls.gcs_cmek)) to encrypt
+					them with. The service account of the Cloud Storage service agent of the
+					project must be allowed to use the key.
+					"""
+		}
 		object_access_control_list: {
 			title: "Object access control list (ACL)"
 			body:  """
@@ -244,6 +253,11 @@ components: sinks: gcp_cloud_storage: {
 					Vector supports adding [custom metadata](\(urls.gcs_custom_metadata)) to
 					created objects. These metadata items are a way of associating extra
 					data items with the object that are not part of the uploaded data.
+
+					The values of metadata, and the custom time set with the `custom_time`
+					option, are templates rendered for each object from the events of its
+					batch. Lifecycle rules can use the custom time of objects, such as a
+					date read from their events, instead of the time they were created.
 					"""
 		}
 	}
@@ -0,0 +1,121 @@
+package metadata
+
+components: sinks: grpc: {
+	title: "gRPC"
+
+	description: """
+		Calls a gRPC method defined by a user-provided descriptor set, with events encoded as the
+		messages it takes. Events can be sent to internal services directly, without an HTTP shim.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    4_000_000
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.grpc
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.grpc.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		descriptor_sets: {
+			title: "Descriptor sets"
+			body: """
+				The called method, and the messages it takes, are read from a
+				[file descriptor set][urls.protobuf_descriptor_set] when the sink starts. It must include the
+				definitions imported by the file defining the method, which `protoc` does with its
+				`--include_imports` flag:
+
+				```shell
+				protoc --include_imports --descriptor_set_out=telemetry.desc telemetry.proto
+				```
+				"""
+		}
+		message_encoding: {
+			title: "Message encoding"
+			body: """
+				Each event is encoded as a message of `message_type`, or of the input type of the method,
+				whose fields hold the values of the top-level fields of the event of the same names. Objects
+				are encoded as nested messages or maps, arrays as repeated fields, and enumerations from the
+				names or numbers of their values. Timestamps are encoded as `google.protobuf.Timestamp`
+				messages, or as RFC 3339 strings. Values that don't fit the types of their fields are left
+				out, so their fields are left unset.
+				"""
+		}
+		calls: {
+			title: "Calls"
+			body: """
+				Client streaming methods are called once per batch, streaming the messages of its events.
+				Unary methods taking a message with a single repeated field of `message_type` are called
+				once per batch too, with the messages of its events set as that field, while those taking
+				`message_type` itself are called once per event. Server streaming methods are rejected.
+
+				Calls failing with a status other than `NOT_FOUND`, `INVALID_ARGUMENT`, `ALREADY_EXISTS`,
+				`PERMISSION_DENIED`, `OUT_OF_RANGE`, `UNIMPLEMENTED` or `UNAUTHENTICATED` are retried.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -81,11 +81,13 @@ components: sinks: http: {
 		uri: {
 			description: """
 				The full URI to make HTTP requests to. This should include the protocol and host,
-				but can also include the port, path, and any other valid part of a URI.
+				but can also include the port, path, and any other valid part of a URI. The path and
+				query can be templated from the fields of events, whose values are percent-encoded.
 				"""
 			required: true
 			type: string: {
-				examples: ["https://10.22.212.22:9000/endpoint"]
+				examples: ["https://10.22.212.22:9000/endpoint", "https://example.com/api/{{ tenant }}/logs"]
+				syntax: "template"
 			}
 		}
 		method: {
@@ -127,6 +129,33 @@ components: sinks: http: {
 		traces: true
 	}
 
+	how_it_works: {
+		signing: {
+			title: "Request signing"
+			body: """
+				Requests can be signed for webhooks that verify where requests come from, with `signing`.
+				The signature is an HMAC, keyed by the shared `signing.secret`, of the values of the
+				`signing.headers`, each followed by a newline, and of the body of the request, as it's
+				sent after compression. It's sent in the `signing.header` header, encoded as configured
+				and prefixed with `signing.prefix`, such as `X-Signature: sha256=<hex signature>`.
+				"""
+		}
+		templated_uri: {
+			title: "Templated URIs"
+			body: """
+				The path and query of the `uri` can be templated from the fields of events, so that a
+				single sink can send events to several endpoints of an API, such as
+				`https://example.com/api/{{ tenant }}/logs` for multi-tenant APIs. Events are batched
+				per rendered URI, and each batch is sent to its own URI.
+
+				The values of fields are percent-encoded, so that they can't add path segments or query
+				parameters, while the scheme and host of the `uri` can't be templated. Events for which
+				the URI can't be rendered, because of missing fields, or for which the rendered URI is
+				invalid, are dropped.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
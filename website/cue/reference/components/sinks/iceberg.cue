@@ -0,0 +1,159 @@
+package metadata
+
+components: sinks: iceberg: {
+	title: "Apache Iceberg"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["AWS"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    100_000_000
+				timeout_secs: 300.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.iceberg
+
+				interface: {
+					socket: {
+						api: {
+							title: "Iceberg REST catalog API"
+							url:   urls.iceberg_rest_catalog
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The table must already exist, with its data stored in S3 or a service compatible with the S3
+				API.
+				""",
+		]
+		warnings: [
+			"""
+				Each batch is committed as a snapshot of the table. Tables written to often should have their
+				snapshots expired and their data files compacted regularly.
+				""",
+		]
+		notices: []
+	}
+
+	configuration: base.components.sinks.iceberg.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		rows: {
+			title: "Rows"
+			body: """
+				Each event is written as a row of the table, with the values of its top-level fields named
+				after the columns of the table, or rendered from `column_templates`. Values are converted to
+				the types of their columns, and strings are parsed as numbers, booleans, dates and timestamps
+				when their columns have those types.
+
+				Optional columns of nested types are left out of data files, and read as nulls. Events missing
+				the value of a required column, or whose values can't be converted to the types of their
+				columns, are dropped.
+				"""
+		}
+		commits: {
+			title: "Commits"
+			body: """
+				Batches are split by the partitions of the table, whose values are computed with the
+				`identity`, `year`, `month`, `day`, `hour`, `truncate` and `void` transforms. Each partition
+				is written to a [Parquet](\(urls.apache_parquet)) data file under the `data` directory of the
+				table, and the files of a batch are appended to the table in a single snapshot.
+
+				Snapshots are committed to the catalog with optimistic concurrency, and are retried on top of
+				the latest snapshot of the table when other writers commit first. Events are acknowledged
+				once their snapshot is committed.
+				"""
+		}
+		catalogs: {
+			title: "Catalogs"
+			body: """
+				With the `rest` catalog, snapshots are committed through the
+				[REST catalog API](\(urls.iceberg_rest_catalog)), which writes the metadata of the table.
+
+				With the `glue` catalog, the metadata of the table is written next to its previous
+				version, and the table is pointed to it in the [AWS Glue Data Catalog](\(urls.aws_glue_data_catalog)).
+				The AWS credentials of the sink are used for Glue as well as for S3.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform: "aws"
+			_service: "s3"
+
+			policies: [
+				{
+					_action: "GetObject"
+				},
+				{
+					_action: "PutObject"
+				},
+			]
+		},
+		{
+			platform: "aws"
+			_service: "glue"
+
+			policies: [
+				{
+					_action: "GetTable"
+				},
+				{
+					_action: "UpdateTable"
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -148,7 +148,35 @@ components: sinks: kafka: {
 		traces: false
 	}
 
-	how_it_works: components._kafka.how_it_works
+	how_it_works: components._kafka.how_it_works & {
+		headers: {
+			title: "Record headers"
+			body: """
+				Records are written with the headers of the `headers` map, whose values are rendered from
+				templates for each event, such as `headers.trace-id = "{{ trace_id }}"`, so that consumers
+				can route records and propagate tracing context without decoding them. Headers of the
+				object field named by `headers_key`, and, with `forward_source_headers`, those of the
+				records read by the `kafka` source, are written too, unless templated headers of the same
+				names replace them.
+				"""
+		}
+		transactions: {
+			title: "Exactly-once production"
+			body: """
+				With `transaction.id` set, records are written in transactions, which are committed every
+				`transaction.commit_interval_ms`, and events are only acknowledged once the transaction
+				holding their records is committed. When a transaction fails to commit, or the producer
+				refuses one of its records, it's aborted, and its events are errored, so that sources
+				supporting end-to-end acknowledgements deliver them again. Events whose records are
+				refused are rejected.
+
+				When Vector restarts, the producer with the same transactional identifier aborts the
+				transaction left open by the previous one before writing records. Consumers reading with
+				the `read_committed` isolation level therefore read the records of acknowledged events
+				exactly once.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		component_sent_events_total:         components.sources.internal_metrics.output.metrics.component_sent_events_total
@@ -178,6 +178,16 @@ components: sinks: loki: {
 	}
 
 	how_it_works: {
+		adaptive_concurrency_per_tenant: {
+			title: "Adaptive Concurrency Per Tenant"
+			body: """
+				With adaptive `request.concurrency`, the concurrency limit is
+				adjusted separately for each `tenant_id`, so that a tenant
+				whose requests slow down doesn't throttle the requests of the
+				others. A fixed concurrency limit is shared by all tenants.
+				"""
+		}
+
 		decentralized_deployments: {
 			title: "Decentralized Deployments"
 			body: """
@@ -228,6 +238,36 @@ components: sinks: loki: {
 				"""
 		}
 
+		stream_sharding: {
+			title: "Stream Sharding"
+			body: """
+				Loki limits the rate at which each stream can be written to,
+				and rejects pushes exceeding it with `429` responses. With
+				`shard_streams.enabled`, the sink measures the rate of each
+				stream, and splits those exceeding `shard_streams.rate_limit_bytes`
+				into up to `shard_streams.max_shards` streams, adding a
+				`shard_streams.label` label holding the shard of each event.
+				Events are distributed between the shards of their stream in
+				turn, and the number of shards is updated every second.
+				"""
+		}
+
+		structured_metadata: {
+			title: "Structured Metadata"
+			body: """
+				Loki 2.9 and later can attach structured metadata to log
+				entries, which are key/value pairs that aren't indexed as
+				labels. They are a better fit for high cardinality attributes,
+				such as trace IDs or user IDs, which would otherwise have to
+				be added to labels or to the log line itself.
+
+				The `structured_metadata` option takes templated keys and
+				values, like `labels`, including the expansion of objects
+				with keys suffixed with "*". Loki must be configured with
+				`allow_structured_metadata` enabled for them to be accepted.
+				"""
+		}
+
 		request_encoding: {
 			title: "Request Encoding"
 			body: """
@@ -0,0 +1,105 @@
+package metadata
+
+components: sinks: mqtt: {
+	title: "MQTT"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: {
+				enabled:                true
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
+			to: {
+				service: services.mqtt
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				When TLS is enabled, the certificate of the broker is only trusted if it's signed by one
+				of the authorities of `tls.ca_file`, rather than by those of the system.
+				""",
+		]
+		notices: []
+	}
+
+	configuration: base.components.sinks.mqtt.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		quality_of_service: {
+			title: "Quality of service"
+			body: """
+				Each event is published as a message to the topic rendered for it, with the
+				`quality_of_service` of the [MQTT specification](\(urls.mqtt_specification)). Events
+				published `at_least_once` or `exactly_once` are only acknowledged once the broker
+				acknowledges their messages, while those published `at_most_once` are acknowledged as
+				soon as they're sent.
+
+				The connection to the broker is opened again whenever it's lost. With `clean_session`
+				disabled and a fixed `client_id`, messages that were sent but not acknowledged are
+				published again once it is, within the session the broker kept for the client.
+				"""
+		}
+		retained_messages: {
+			title: "Retained messages"
+			body: """
+				With `retain` enabled, the broker keeps the last message published to each topic, and
+				delivers it to clients subscribing to the topic afterwards, which suits topics holding
+				the latest state of devices.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
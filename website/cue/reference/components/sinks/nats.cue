@@ -60,7 +60,23 @@ components: sinks: nats: {
 		traces:  false
 	}
 
-	how_it_works: components._nats.how_it_works
+	how_it_works: components._nats.how_it_works & {
+		jetstream: {
+			title: "JetStream"
+			body: """
+				When [`jetstream`](#jetstream) is set, each message is published to the JetStream stream bound to
+				`subject`, and its events are only acknowledged once the stream has stored it, which makes the
+				delivery of events at least once. Messages that aren't acknowledged within
+				`jetstream.ack_timeout_ms`, or fail to be published because of the connection, are published again,
+				up to `jetstream.retry_attempts` times, while messages JetStream refuses, such as those published to a
+				subject bound to another stream than `jetstream.expected_stream`, are rejected.
+
+				As retried messages may have been stored already, `jetstream.message_id` should be set for them to be
+				sent with a `Nats-Msg-Id` header, making the stream discard those it already stored within its
+				duplicate window.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		events_discarded_total:  components.sources.internal_metrics.output.metrics.events_discarded_total
@@ -0,0 +1,123 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Exports logs, metrics, and traces to receivers of the
+		[OpenTelemetry protocol (OTLP)][urls.opentelemetry_protocol], over gRPC or HTTP, such as the
+		OpenTelemetry Collector and the many backends accepting it.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    4_000_000
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			proxy: enabled:       true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.opentelemetry.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	how_it_works: {
+		events: {
+			title: "Events"
+			body: """
+				Events are exported in the shape the `opentelemetry` source decodes them in, so events it
+				received are exported unchanged. The `message` of logs is set as the body of their records,
+				their `resources` field as their resource, and their `attributes` field, along with any
+				other fields, as their attributes. Batches hold a single kind of events, each sent to the
+				service of its signal.
+				"""
+		}
+		metrics: {
+			title: "Metrics"
+			body: """
+				The tags of metrics prefixed with `resource.` are set as the attributes of their resource,
+				and all others as the attributes of their data points. Counters are exported as monotonic
+				sums, incremental gauges as non-monotonic ones, and absolute gauges and sets as gauges.
+				Aggregated histograms and summaries are exported as histograms and summaries, while
+				distributions are exported as exponential histograms, and sketches as summaries of their
+				main quantiles.
+				"""
+		}
+		requests: {
+			title: "Requests"
+			body: """
+				With the `http` protocol, requests are posted to `/v1/logs`, `/v1/metrics`, and
+				`/v1/traces` under the path of the endpoint. Requests are compressed with gzip, unless
+				`compression` is set to `none`. Those failing with the statuses the specification deems
+				retryable, such as `UNAVAILABLE` or `429 Too Many Requests`, are retried.
+
+				Only the gRPC endpoint is health checked, as OTLP/HTTP receivers can't be without sending
+				them events.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
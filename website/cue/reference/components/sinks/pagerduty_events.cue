@@ -0,0 +1,114 @@
+package metadata
+
+components: sinks: pagerduty_events: {
+	title: "PagerDuty Events"
+
+	description: """
+		Sends events to the [PagerDuty Events API v2](\(urls.pagerduty_events_api)), which trigger,
+		acknowledge, or resolve alerts, so that alert pipelines built with VRL can page the teams
+		they route them to.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: ["PagerDuty"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			batch: enabled:       false
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.pagerduty
+
+				interface: {
+					socket: {
+						api: {
+							title: "PagerDuty Events API v2"
+							url:   urls.pagerduty_events_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The routing keys of events must be the integration keys of Events API v2 integrations of
+				services, or of event rulesets.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.pagerduty_events.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		alerts: {
+			title: "Alerts"
+			body: """
+				Each event is sent as an event of its own. Triggers open an alert for their `dedup_key`,
+				or are grouped into the alert already open for it, while acknowledgements and resolutions
+				apply to the alert of their `dedup_key`. The `event_action` template selects the action
+				from the fields of events, so that a single sink can open and resolve alerts:
+
+				```vrl
+				.action = if .status == "ok" { "resolve" } else { "trigger" }
+				```
+
+				Acknowledgements and resolutions only send the routing key, action, and dedup key of
+				events, while triggers send their summary, source, severity, and timestamp, with all their
+				fields as the custom details of alerts, which `encoding` can restrict.
+				"""
+		}
+		retries: {
+			title: "Retries"
+			body: """
+				Events rejected by PagerDuty because they're invalid, such as acknowledgements without a
+				dedup key, are dropped, while those rate limited, or failing because of PagerDuty, are
+				retried. Triggers retried after their alert was resolved open a new alert.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -0,0 +1,107 @@
+package metadata
+
+components: sinks: postgres: {
+	title: "PostgreSQL"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: enabled: false
+			to: {
+				service: services.postgresql
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The table must already exist, and the user must be allowed to insert rows into it, and
+				to update them when upserting them.
+				""",
+		]
+		warnings: [
+			"""
+				PostgreSQL can't upsert the same row twice in one statement, so batches holding several
+				events with the same `on_conflict` columns fail, and are dropped.
+				""",
+		]
+		notices: []
+	}
+
+	configuration: base.components.sinks.postgres.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		rows: {
+			title: "Rows"
+			body: """
+				Each event is written as a row of the table, whose columns hold the values of the fields
+				they're mapped to with `columns`, or of the top-level fields of the same names. Values are
+				converted to the types of their columns by PostgreSQL, so timestamps can be written to
+				`timestamptz` columns, and objects and arrays to `jsonb` columns. Tables of extensions
+				such as TimescaleDB hypertables are written to like any other table.
+				"""
+		}
+		methods: {
+			title: "Insert methods"
+			body: """
+				With the `insert` method, each batch is written with a single `INSERT` statement, which
+				upserts its rows with `on_conflict`, as an [`ON CONFLICT`](\(urls.postgresql_upsert))
+				clause updating the conflicting rows, or ignoring the new ones. With the `copy` method,
+				batches are loaded with [`COPY`](\(urls.postgresql_copy)) statements instead, which is
+				faster for large batches, but can't upsert rows.
+
+				Batches are written concurrently over up to `pool_size` connections to the server, which
+				are opened as they're needed and reused by the following batches.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -180,6 +180,38 @@ components: sinks: prometheus_remote_write: {
 		}
 	}
 
+	how_it_works: {
+		metadata: {
+			title: "Metadata"
+			body: """
+				The type of each metric is sent as metric metadata, along with its help text and unit when they were
+				received by the `prometheus_remote_write` source, which attaches them to the metadata of metric events.
+				Otherwise, the help text is the name of the metric.
+				"""
+		}
+
+		exemplars: {
+			title: "Exemplars"
+			body: """
+				With `send_exemplars` enabled, the exemplars received by the `prometheus_remote_write` source, which
+				correlate metrics with traces, are sent with the series of their metrics. The exemplars of histograms
+				are sent with the series of the bucket their value falls in, while those of summaries are dropped, as
+				they aren't supported by Prometheus.
+				"""
+		}
+
+		native_histograms: {
+			title: "Native histograms"
+			body: """
+				With `native_histograms` enabled, distributions are sent as native histograms, whose exponential
+				buckets have boundaries growing by a factor of 2^(1/8), so that each value is known to within about
+				4.3%, and which replace the `_bucket`, `_sum`, and `_count` series of classic histograms. Aggregated
+				histograms, whose samples are already counted into their own buckets, are still sent as classic
+				histograms. Prometheus only accepts native histograms with its `native-histograms` feature enabled.
+				"""
+		}
+	}
+
 	input: {
 		logs: false
 		metrics: {
@@ -46,7 +46,12 @@ components: sinks: pulsar: {
 
 	support: {
 		requirements: []
-		warnings: []
+		warnings: [
+			"""
+				Messages are not split into chunks, so events encoded to more than the maximum message
+				size of the broker, which is 5 MB by default, are rejected.
+				""",
+		]
 		notices: []
 	}
 
@@ -144,6 +149,43 @@ components: sinks: pulsar: {
 				examples: ["message", "my_field"]
 			}
 		}
+		partition_key: {
+			common:      false
+			description: "The key of messages, which routes them to the partitions of the topic. Can't be set along with `partition_key_field`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ host }}", "{{ tenant }}-{{ service }}"]
+				syntax: "template"
+			}
+		}
+		schema: {
+			common:      false
+			description: "The schema of messages, declared to the schema registry of the broker."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					type: {
+						description: "The type of the schema, which must match the codec."
+						required:    true
+						type: string: enum: {
+							avro: "Messages are Avro records, encoded with the `avro` codec."
+							json: "Messages are JSON documents, encoded with the `json` codec."
+						}
+					}
+					definition: {
+						common:      false
+						description: "The definition of the schema, as an Avro schema in JSON. Defaults to the schema of the `avro` codec, for `avro` schemas."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+						}
+					}
+				}
+			}
+		}
 	}
 
 	input: {
@@ -152,6 +194,28 @@ components: sinks: pulsar: {
 		traces:  false
 	}
 
+	how_it_works: {
+		schemas: {
+			title: "Schemas"
+			body: """
+				With `schema`, the producer declares the schema of its messages to the schema registry of
+				the broker, which checks that it's compatible with the schema of the topic, according to
+				its compatibility strategy, or registers it for topics that have none. The sink fails to
+				start if it isn't, rather than sending messages consumers can't decode. Avro schemas are
+				declared by default when encoding with the `avro` codec, and JSON schemas describe the
+				documents of the `json` codec with an Avro schema, as Pulsar does.
+				"""
+		}
+		partitioning: {
+			title: "Partitioning"
+			body: """
+				Messages are routed to the partitions of partitioned topics by their key, which is
+				rendered for each event from the `partition_key` template, so that events with the same
+				key are consumed in order. Messages without a key are distributed across partitions.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
 		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
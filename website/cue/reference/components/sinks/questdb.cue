@@ -0,0 +1,114 @@
+package metadata
+
+components: sinks: questdb: {
+	title: "QuestDB"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			keepalive: enabled: true
+			proxy: enabled:     true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.questdb
+
+				interface: {
+					socket: {
+						api: {
+							title: "QuestDB InfluxDB Line Protocol"
+							url:   urls.questdb_ilp
+						}
+						direction: "outgoing"
+						protocols: ["http", "tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The `http` mode requires QuestDB `>= 7.4.0`, which added the InfluxDB Line Protocol HTTP endpoint.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.questdb.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		rows: {
+			title: "Rows"
+			body: """
+				Each event is written as a row of the [InfluxDB Line Protocol](\(urls.questdb_ilp)) into the
+				table rendered from `table`. The fields listed in `symbols` are written as symbol columns,
+				and the fields listed in `columns`, or every other top-level field by default, are written as
+				columns typed after their values: strings, integers, floats, booleans and timestamps, with
+				objects and arrays written as JSON strings.
+
+				The designated timestamp of rows is taken from `timestamp_field`, or from the timestamp of the
+				global log schema by default, with a nanosecond precision. Events that can't be written as
+				rows, because their timestamp isn't one or they have no columns, are dropped.
+				"""
+		}
+		modes: {
+			title: "TCP and HTTP"
+			body: """
+				In the `tcp` mode, rows are streamed to the TCP port of QuestDB, `9009` by default. This is
+				the fastest way of writing rows, but QuestDB doesn't report the rows it couldn't write, so
+				events are acknowledged once they are sent.
+
+				In the `http` mode, batches of rows are sent to the `/write` path of the HTTP server of
+				QuestDB, `http://localhost:9000` by default. Batches are acknowledged once QuestDB has
+				committed them, and the batches QuestDB rejects are reported and not retried.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -122,6 +122,25 @@ components: sinks: redis: {
 				API.
 				"""
 		}
+
+		streams: {
+			title: "Streams"
+			body: """
+				With `data_type` set to `stream`, each message is appended to the stream at `key` with `XADD`, as the
+				`stream_option.field` field of an entry whose ID is generated by Redis. Setting `stream_option.maxlen`
+				trims the stream as messages are added, approximately unless `stream_option.approximate` is `false`.
+				"""
+		}
+
+		cluster: {
+			title: "Redis Cluster"
+			body: """
+				With `cluster` enabled, the topology of the cluster is discovered from the node at `url`, and each
+				message is sent to the node serving the slot of its key. Batches of messages are then sent in
+				pipelines that are not atomic, as their keys may be served by different nodes, whereas batches sent
+				to a single node are wrapped in a `MULTI`/`EXEC` transaction.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
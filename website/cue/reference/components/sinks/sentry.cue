@@ -0,0 +1,116 @@
+package metadata
+
+components: sinks: sentry: {
+	title: "Sentry"
+
+	description: """
+		Sends error logs to [Sentry](\(urls.sentry)) as events, with their level, fingerprint, tags,
+		and the exceptions parsed from their stack traces, so that they're grouped and alerted on
+		like the errors reported by the Sentry SDKs.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: ["Sentry"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			batch: enabled:       false
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.sentry
+
+				interface: {
+					socket: {
+						api: {
+							title: "Sentry envelope API"
+							url:   urls.sentry_envelopes
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.sentry.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		events: {
+			title: "Events"
+			body: """
+				Each log is sent as a Sentry event of its own. The `message` field is the message of the
+				event, the host field its server name, and the `timestamp` field its timestamp. The field
+				named by `level_key` is mapped to the levels of Sentry, so that `WARN` and `warning` are
+				warnings, and `critical` is fatal, while events without a level are errors.
+
+				The fields listed in `tags` are the tags of events, which Sentry indexes and searches by,
+				and the field named by `fingerprint_key` their fingerprint, which Sentry groups them by.
+				All other fields are set as the extra data of events.
+				"""
+		}
+		stack_traces: {
+			title: "Stack traces"
+			body: """
+				The field named by `stack_trace_key` is parsed into the exceptions of events, with their
+				types, messages, and frames. Python tracebacks, and the stack traces of Java and
+				JavaScript exceptions are understood, including the chained exceptions of Python and the
+				`Caused by:` causes of Java. Other stack traces are sent as the message of an exception
+				of type `Error`.
+				"""
+		}
+		rate_limits: {
+			title: "Rate limits"
+			body: """
+				Events are sent with the public key of the DSN to the [envelope endpoint](\(urls.sentry_envelopes))
+				of the project. When Sentry [rate limits](\(urls.sentry_rate_limits)) the project, because
+				of its quotas or its spike protection, no events are sent until the limit ends, and the
+				rate limited events are retried then.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
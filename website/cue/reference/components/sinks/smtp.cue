@@ -0,0 +1,111 @@
+package metadata
+
+components: sinks: smtp: {
+	title: "SMTP"
+
+	description: """
+		Sends events as emails through an [SMTP](\(urls.smtp)) server, rendered with templates, so
+		that low-volume reports and alerts reach people straight from a pipeline.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				max_events:   1000
+				timeout_secs: 60.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			proxy: enabled:       false
+			request: {
+				enabled:        true
+				rate_limit_num: 10
+				headers:        false
+			}
+			tls: enabled: false
+			to: {
+				service: services.smtp
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.smtp.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		emails: {
+			title: "Emails"
+			body: """
+				The `to`, `subject`, `body`, and `html_body` templates render the emails of events from
+				their fields. Events are collected in batches for up to `batch.timeout_secs`, a minute by
+				default, and the events of each batch with the same recipients are sent as a single
+				email, whose subject is that of its first event, and whose body holds the bodies of all
+				of its events as lines. Setting `batch.max_events` to `1` sends an email per event.
+
+				With `html_body` set, emails have both a plain-text and an HTML body, and email clients
+				show the one they prefer. Events whose templates fail to render, or whose recipients
+				aren't valid addresses, are dropped.
+				"""
+		}
+		tls_and_authentication: {
+			title: "TLS and authentication"
+			body: """
+				Connections are upgraded to TLS with [`STARTTLS`](\(urls.smtp_starttls)) by default, on
+				port `587`. With `tls.mode` set to `implicit`, connections use TLS from the start, on
+				port `465`, while `none` leaves them unencrypted, on port `25`, which should only be
+				used with relays on the same host or network. The `auth` credentials are sent once
+				connections are encrypted, with the `PLAIN` or `LOGIN` mechanisms.
+				"""
+		}
+		throttling: {
+			title: "Throttling"
+			body: """
+				Emails are read by people, and email providers limit how many they accept, so the sink
+				sends at most 10 emails per minute by default, which `request.rate_limit_num` and
+				`request.rate_limit_duration_secs` change. Emails failing with temporary errors, such as
+				`4xx` replies or lost connections, are retried, while those rejected with permanent
+				errors, such as unknown recipients, are dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
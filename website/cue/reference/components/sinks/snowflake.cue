@@ -0,0 +1,113 @@
+package metadata
+
+components: sinks: snowflake: {
+	title: "Snowflake"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Snowflake"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.snowflake
+
+				interface: {
+					socket: {
+						api: {
+							title: "Snowpipe Streaming REST API"
+							url:   urls.snowflake_snowpipe_streaming
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The table must already exist, and the user must be set up for
+				[key pair authentication](\(urls.snowflake_key_pair_auth)), with a role allowed to write to
+				the pipe.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.snowflake.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		rows: {
+			title: "Rows"
+			body: """
+				Each event is sent as a row, in a line of JSON. By default, the row holds the top-level
+				fields of the event, which the default pipe of the table loads into the columns of the same
+				names. With `columns` set, the row holds the columns it maps to the fields of events, so
+				that rows match the columns of the table, or of the pipe's transformation.
+				"""
+		}
+		channels: {
+			title: "Channels and offset tokens"
+			body: """
+				Rows are appended to a channel of the pipe, opened when the sink starts, through the
+				[Snowpipe Streaming](\(urls.snowflake_snowpipe_streaming)) REST API. Batches are appended
+				one at a time, each with its sequence number as offset token, and events are acknowledged
+				once the channel reports that their batch is committed to the table.
+
+				When a batch fails, the channel is reopened before it's retried, and the retry is skipped
+				if the last offset token committed by the channel shows that the batch was committed
+				after all, so that batches are written to the table exactly once. Since batches are
+				appended one at a time, throughput is limited by how quickly they're committed, which
+				larger batches or more channels, with several sinks, make up for.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
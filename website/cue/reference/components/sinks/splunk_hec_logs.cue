@@ -180,9 +180,16 @@ components: sinks: splunk_hec_logs: {
 		}
 	}
 	input: {
-		logs:    true
-		metrics: null
-		traces:  false
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: false
+			gauge:        true
+			histogram:    false
+			set:          false
+			summary:      false
+		}
+		traces: false
 	}
 
 	telemetry: metrics: {
@@ -197,5 +204,20 @@ components: sinks: splunk_hec_logs: {
 		requests_received_total:          components.sources.internal_metrics.output.metrics.requests_received_total
 	}
 
-	how_it_works: sinks._splunk_hec.how_it_works
+	how_it_works: sinks._splunk_hec.how_it_works & {
+		metrics: {
+			title: "Metrics"
+			body: """
+				When `metrics.enabled` is set, the sink also accepts counter
+				and gauge metrics, so that a single sink can be used in
+				pipelines mixing logs and metrics. Metrics are sent to the
+				event endpoint in the metric JSON format, whatever
+				`endpoint_target` is, while logs are sent to the configured
+				endpoint. With `metrics.multi_metric`, metrics sharing their
+				timestamp, dimensions, and metadata are sent as a single
+				event in the multiple-metric JSON format, which requires
+				Splunk 8.0 or later.
+				"""
+		}
+	}
 }
@@ -0,0 +1,116 @@
+package metadata
+
+components: sinks: sql: {
+	title: "SQL"
+
+	description: """
+		Executes a parameterized statement, such as an `INSERT` or an upsert, for each event of
+		batches, against a PostgreSQL, MySQL, or SQLite database. It suits small operational
+		integrations that keep a table up to date, when a dedicated analytics store isn't needed.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: enabled: false
+			to: {
+				service: services.sql
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The tables the statement writes to must already exist, and the user must be allowed to
+				execute it.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.sql.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		parameters: {
+			title: "Parameters"
+			body: """
+				The statement is executed once per event, with the values of the fields listed in
+				`parameters` bound to its placeholders in order. The health check prepares the statement,
+				so statements that are invalid, or whose number of placeholders doesn't match the number of
+				`parameters`, are reported when the sink starts.
+
+				PostgreSQL converts parameters to the types it infers for them, such as integers,
+				timestamps, or `jsonb` for objects and arrays, and other types, such as `uuid`, can be
+				cast from text in the statement: `$1::text::uuid`. MySQL and SQLite convert parameters to
+				the types of their columns themselves. Upserts are written with the syntax of each
+				database, such as [`ON CONFLICT`](\(urls.postgresql_upsert)) for PostgreSQL and
+				[SQLite](\(urls.sqlite_upsert)), or `ON DUPLICATE KEY UPDATE` for MySQL.
+				"""
+		}
+		transactions: {
+			title: "Transactions"
+			body: """
+				Each batch is written in its own transaction, so either all of its events are written, or
+				none of them are. Batches failing because of the connection or of the load of the
+				database, such as deadlocks, are retried as a whole, while those failing because of their
+				rows, such as constraint violations, are dropped.
+
+				Batches are written concurrently over up to `pool_size` connections, which are opened as
+				they're needed and reused by the following batches. SQLite databases are written through
+				a single connection, as their writes are serialized anyway. PostgreSQL connections use TLS
+				as the `sslmode` parameter of the endpoint requires, while MySQL connections aren't
+				encrypted.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -83,10 +83,20 @@ components: sinks: vector: {
 			}
 		}
 		compression: {
-			description: "Enable gRPC compression with gzip."
-			common:      true
-			required:    false
-			type: bool: default: false
+			description: """
+				Enable gRPC compression. `true` compresses requests with gzip, as does `"gzip"`. `"zstd"` compresses
+				them with Zstandard, falling back to gzip if the downstream Vector instance doesn't support it.
+				"""
+			common:   true
+			required: false
+			type: {
+				bool: default: false
+				string: enum: {
+					none: "No compression."
+					gzip: "Gzip compression."
+					zstd: "Zstandard compression."
+				}
+			}
 		}
 	}
 
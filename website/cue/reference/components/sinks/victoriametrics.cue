@@ -0,0 +1,117 @@
+package metadata
+
+components: sinks: victoriametrics: {
+	title: "VictoriaMetrics"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				max_events:   10_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			proxy: enabled:       true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.victoriametrics
+
+				interface: {
+					socket: {
+						api: {
+							title: "VictoriaMetrics JSON line import"
+							url:   urls.victoriametrics_json_import
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.victoriametrics.configuration
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		json_import: {
+			title: "JSON line import"
+			body: """
+				Batches of metrics are sent to the [JSON line import
+				endpoint](\(urls.victoriametrics_json_import)) of VictoriaMetrics, which parses them with much
+				less overhead than Prometheus remote write requests. Each line holds a sample of a series,
+				named and labeled as they are by the `prometheus_remote_write` sink: incremental metrics are
+				made absolute, distributions are aggregated into histograms or summaries with `buckets` and
+				`quantiles`, and the series of histograms and summaries are suffixed with `_bucket`, `_sum`
+				and `_count`.
+
+				The native import format of VictoriaMetrics is only meant for data exported from it, so the
+				JSON line format is used instead.
+				"""
+		}
+		extra_labels: {
+			title: "Extra labels"
+			body: """
+				The labels of `extra_labels` are added by VictoriaMetrics to every metric it imports from the
+				sink, as `extra_label` query parameters, so they aren't repeated on each line of the batches.
+				"""
+		}
+		compression: {
+			title: "Compression"
+			body: """
+				Batches are compressed with Zstandard by default, which VictoriaMetrics decompresses faster
+				than Gzip. Set `compression` to `gzip` for older versions lacking support for it.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -95,6 +95,35 @@ components: sources: amqp: {
 				syntax: "literal"
 			}
 		}
+		prefetch_count: {
+			common:      false
+			description: "The maximum number of unacknowledged messages the server delivers to this consumer."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: null
+				examples: [100]
+				unit: null
+			}
+		}
+		requeue_on_error: {
+			common:      false
+			description: "Requeue the messages whose events couldn't be delivered, instead of rejecting them to the dead letter exchange of the queue."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		max_requeues: {
+			common:      false
+			description: "The maximum number of times a message can be requeued before it is rejected instead, based on the `x-delivery-count` header set by quorum queues."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: null
+				examples: [5]
+				unit: null
+			}
+		}
 	}
 
 	output: logs: record: {
@@ -138,5 +167,20 @@ components: sources: amqp: {
 		processed_events_total:               components.sources.internal_metrics.output.metrics.processed_events_total
 	}
 
-	how_it_works: components._amqp.how_it_works
+	how_it_works: components._amqp.how_it_works & {
+		failed_messages: {
+			title: "Failed messages"
+			body: """
+				With end-to-end acknowledgements enabled, messages are acknowledged once their events have been
+				delivered. Messages whose events couldn't be delivered are rejected without being requeued by default,
+				so that the server routes them to the dead letter exchange of the queue, if it has one, instead of
+				delivering them again and again.
+
+				Setting [`requeue_on_error`](#requeue_on_error) requeues these messages instead, while
+				[`max_requeues`](#max_requeues) bounds how many times a message is requeued before it is rejected,
+				which relies on the `x-delivery-count` header maintained by quorum queues. Messages whose events were
+				rejected by a sink are always rejected.
+				"""
+		}
+	}
 }
@@ -195,6 +195,20 @@ components: sources: aws_s3: components._aws & {
 				```
 				"""
 		}
+		notification_formats: {
+			title: "Bucket notification formats"
+			body:  """
+				Bucket notifications can reach the SQS queue in one of three formats, all of which are
+				detected automatically:
+
+				* Notifications sent directly from S3 to the queue.
+				* Notifications published to an SNS topic that the queue is subscribed to. The SNS
+				  envelope is unwrapped before the notification is processed, so raw message delivery
+				  doesn't need to be enabled on the subscription.
+				* Notifications routed through Amazon EventBridge. Only events whose `detail-type` is
+				  listed in `sqs.eventbridge_detail_types` are processed; all others are ignored.
+				"""
+		}
 	}
 
 	permissions: iam: [
@@ -253,6 +253,15 @@ base: components: sources: amqp: configuration: {
 			}
 		}
 	}
+	max_requeues: {
+		description: """
+			The maximum number of times a message can be requeued before it is rejected instead.
+
+			The number of previous deliveries is read from the `x-delivery-count` header, which is set by quorum queues. For other queues, messages that were already redelivered are considered requeued once. By default, messages are requeued indefinitely.
+			"""
+		required: false
+		type: uint: {}
+	}
 	offset_key: {
 		description: "The `AMQP` offset key."
 		required:    false
@@ -261,6 +270,15 @@ base: components: sources: amqp: configuration: {
 			syntax:  "literal"
 		}
 	}
+	prefetch_count: {
+		description: """
+			The maximum number of unacknowledged messages the server delivers to the consumer.
+
+			The limit applies to this consumer only, as quorum queues don't support limits shared by the whole channel. By default, there is no limit.
+			"""
+		required: false
+		type: uint: {}
+	}
 	queue: {
 		description: "The name of the queue to consume."
 		required:    false
@@ -269,6 +287,15 @@ base: components: sources: amqp: configuration: {
 			syntax:  "literal"
 		}
 	}
+	requeue_on_error: {
+		description: """
+			Whether to requeue the messages whose events couldn't be delivered.
+
+			By default, these messages are rejected, which routes them to the dead letter exchange of the queue if it has one, and drops them otherwise. Messages whose events were rejected by a sink are always rejected, as they would fail again.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	routing_key_field: {
 		description: "The `AMQP` routing key."
 		required:    false
@@ -208,6 +208,21 @@ base: components: sources: aws_s3: configuration: {
 				required: false
 				type: bool: default: true
 			}
+			eventbridge_detail_types: {
+				description: """
+					The `detail-type` values of EventBridge notifications that reference objects to process.
+
+					Only relevant when bucket notifications are delivered to the queue through [Amazon EventBridge][eventbridge].
+					Notifications with any other `detail-type` are ignored.
+
+					[eventbridge]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/EventBridge.html
+					"""
+				required: false
+				type: array: {
+					default: ["Object Created"]
+					items: type: string: syntax: "literal"
+				}
+			}
 			poll_secs: {
 				description: """
 					How long to wait while polling the queue for new messages, in seconds.
@@ -0,0 +1,249 @@
+package metadata
+
+base: components: sources: cloudflare_logs: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level. Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how Vector handles event acknowledgement.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	address: {
+		description: """
+			The address to listen for Logpush requests on.
+
+			Logpush jobs can be pointed at any path on this address.
+			"""
+		relevant_when: "mode = \"push\""
+		required:      true
+		type: string: {
+			examples: ["0.0.0.0:443"]
+			syntax: "literal"
+		}
+	}
+	auth: {
+		description:   "HTTP Basic authentication configuration."
+		relevant_when: "mode = \"push\""
+		required:      false
+		type: object: options: {
+			password: {
+				description: "The password for basic authentication."
+				required:    true
+				type: string: syntax: "literal"
+			}
+			username: {
+				description: "The username for basic authentication."
+				required:    true
+				type: string: syntax: "literal"
+			}
+		}
+	}
+	backfill_secs: {
+		description: """
+			How far back to pull logs from when no checkpoint is persisted, in seconds.
+
+			Logs are retained for seven days at most. By default, only the logs of the requests
+			handled after Vector starts are pulled.
+			"""
+		relevant_when: "mode = \"pull\""
+		required:      false
+		type: uint: default: 0
+	}
+	data_dir: {
+		description: """
+			The directory used to persist the end of the last window pulled.
+
+			By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+			"""
+		relevant_when: "mode = \"pull\""
+		required:      false
+		type: string: syntax: "literal"
+	}
+	delay_secs: {
+		description: """
+			How far back in time the end of a window must be before its logs are pulled, in seconds.
+
+			Cloudflare takes time to make logs available, and rejects windows ending less than a
+			minute ago.
+			"""
+		relevant_when: "mode = \"pull\""
+		required:      false
+		type: uint: default: 300
+	}
+	endpoint: {
+		description:   "The base URL of the Cloudflare API."
+		relevant_when: "mode = \"pull\""
+		required:      false
+		type: string: {
+			default: "https://api.cloudflare.com/client/v4"
+			syntax:  "literal"
+		}
+	}
+	fields: {
+		description: """
+			The fields of the logs to pull.
+
+			By default, the fields selected by the Logpull API are pulled.
+			"""
+		relevant_when: "mode = \"pull\""
+		required:      false
+		type: array: {
+			default: []
+			items: type: string: {
+				examples: ["ClientIP", "ClientRequestHost", "EdgeStartTimestamp"]
+				syntax: "literal"
+			}
+		}
+	}
+	mode: {
+		required: true
+		type: string: enum: {
+			pull: "Pull the HTTP request logs of a zone from the Logpull API."
+			push: "Receive the logs pushed by Logpush jobs with an HTTP destination."
+		}
+	}
+	poll_interval_secs: {
+		description:   "The interval between polls of the Logpull API, in seconds."
+		relevant_when: "mode = \"pull\""
+		required:      false
+		type: uint: default: 60
+	}
+	tls: {
+		description: "Configures the TLS options for incoming/outgoing connections."
+		required:    false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. Prioritized in the order
+					they are defined.
+					"""
+				required: false
+				type: array: items: type: string: syntax: "literal"
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			enabled: {
+				description: """
+					Whether or not to require TLS for incoming/outgoing connections.
+
+					When enabled and used for incoming connections, an identity certificate is also required. See `tls.crt_file` for
+					more information.
+					"""
+				required: false
+				type: bool: {}
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					If enabled, certificates must be valid in terms of not being expired, as well as being issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that not only the leaf certificate (the
+					certificate presented by the client/server) is valid, but also that the issuer of that certificate is valid, and
+					so on until reaching a root certificate.
+
+					Relevant for both incoming and outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+	token: {
+		description: """
+			The API token used to authenticate with the Cloudflare API.
+
+			It must have the `Zone Logs: Read` permission for the zone.
+			"""
+		relevant_when: "mode = \"pull\""
+		required:      true
+		type: string: {
+			examples: ["${CLOUDFLARE_API_TOKEN}"]
+			syntax: "literal"
+		}
+	}
+	window_secs: {
+		description: """
+			The length of the time windows logs are pulled for, in seconds.
+
+			It can be at most one hour.
+			"""
+		relevant_when: "mode = \"pull\""
+		required:      false
+		type: uint: default: 60
+	}
+	zone_id: {
+		description:   "The identifier of the zone whose logs are pulled."
+		relevant_when: "mode = \"pull\""
+		required:      true
+		type: string: {
+			examples: ["023e105f4ecef8ad9ca31a8372d0c353"]
+			syntax: "literal"
+		}
+	}
+}
@@ -6,6 +6,49 @@ base: components: sources: docker_logs: configuration: {
 		required:    false
 		type: bool: default: true
 	}
+	cri: {
+		description: "Configuration for collecting logs from a CRI runtime."
+		required:    false
+		type: object: options: {
+			data_dir: {
+				description: """
+					The directory used to persist file checkpoint positions.
+
+					By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			glob_minimum_cooldown_ms: {
+				description: "Delay between discovering new log files, in milliseconds."
+				required:    false
+				type: uint: default: 1000
+			}
+			log_directory: {
+				description: """
+					The directory containing the container log files written by the CRI runtime.
+
+					Log files are expected to be named `<name>-<container ID>.log`, as done by the `kubelet` in
+					`/var/log/containers`. Files not following this convention are skipped.
+					"""
+				required: false
+				type: string: {
+					default: "/var/log/containers"
+					syntax:  "literal"
+				}
+			}
+			socket_path: {
+				description: """
+					The path of the CRI runtime socket.
+
+					Used to detect the CRI runtime when `runtime` is set to `auto`. If not set, the default locations of the
+					containerd and CRI-O sockets are probed.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+		}
+	}
 	docker_host: {
 		description: """
 			Docker host to connect to.
@@ -170,6 +213,23 @@ base: components: sources: docker_logs: configuration: {
 		required:    false
 		type: uint: default: 2
 	}
+	runtime: {
+		description: "The container runtime to collect logs from."
+		required:    false
+		type: string: {
+			default: "docker"
+			enum: {
+				auto: """
+					Collect logs through the Docker API if the Docker daemon is available, otherwise fall back to a CRI runtime.
+
+					The Docker daemon is considered available if `docker_host` or the `DOCKER_HOST` environment variable is set, or
+					if the default Docker socket exists. A CRI runtime is detected by probing for the containerd and CRI-O sockets.
+					"""
+				cri:    "Collect logs written in the CRI log format by a CRI runtime, such as containerd or CRI-O."
+				docker: "Collect logs through the Docker API."
+			}
+		}
+	}
 	tls: {
 		description: """
 			Configuration of TLS when connecting to the Docker daemon.
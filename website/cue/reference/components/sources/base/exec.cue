@@ -152,18 +152,60 @@ base: components: sources: exec: configuration: {
 		description: "Configuration options for scheduled commands."
 		required:    false
 		type: object: {
-			default: exec_interval_secs: 60
-			options: exec_interval_secs: {
-				description: """
-					The interval, in seconds, between scheduled command runs.
+			default: {
+				exec_interval_secs: 60
+				include_exit_code:  false
+				jitter_secs:        0
+			}
+			options: {
+				exec_interval_secs: {
+					description: """
+						The interval, in seconds, between scheduled command runs.
 
-					If the command takes longer than `exec_interval_secs` to run, it will be killed.
-					"""
-				required: false
-				type: uint: default: 60
+						If the command takes longer than `exec_interval_secs` to run, it will be killed, unless `timeout_secs` is set.
+						"""
+					required: false
+					type: uint: default: 60
+				}
+				include_exit_code: {
+					description: """
+						Whether or not to add the exit code of the command to the events of each run.
+
+						When enabled, the events of a run are held until the command exits, and are then sent with an `exit_code` field. The field is absent if the command was killed, either because it timed out or by a signal.
+						"""
+					required: false
+					type: bool: default: false
+				}
+				jitter_secs: {
+					description: """
+						The maximum amount of time, in seconds, to randomly delay each scheduled command run by.
+
+						This spreads out the runs of a command scheduled on many hosts at once. The delay doesn't count towards the timeout of the run.
+						"""
+					required: false
+					type: uint: default: 0
+				}
+				timeout_secs: {
+					description: """
+						The maximum amount of time, in seconds, that a scheduled command run can take.
+
+						If the command takes longer to run, it will be killed. Defaults to `exec_interval_secs`. Runs that take longer than `exec_interval_secs` delay the next run rather than overlapping with it.
+						"""
+					required: false
+					type: uint: {}
+				}
 			}
 		}
 	}
+	separate_stderr_output: {
+		description: """
+			Whether or not to send the events generated from stderr to a separate output.
+
+			When enabled, these events are sent to the `stderr` output instead of the default one, which other components can consume as `<source_id>.stderr`. Has no effect unless `include_stderr` is enabled.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	streaming: {
 		description: "Configuration options for streaming commands."
 		required:    false
@@ -0,0 +1,187 @@
+package metadata
+
+base: components: sources: github_audit: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level. Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how Vector handles event acknowledgement.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	backfill_secs: {
+		description: """
+			How far back to read events from when no cursor is persisted, in seconds.
+
+			By default, only the events created after Vector starts are read.
+			"""
+		required: false
+		type: uint: default: 0
+	}
+	data_dir: {
+		description: """
+			The directory used to persist the position in the audit log.
+
+			By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+			"""
+		required: false
+		type: string: syntax: "literal"
+	}
+	endpoint: {
+		description: """
+			The base URL of the GitHub REST API.
+
+			For GitHub Enterprise Server, this is `https://<hostname>/api/v3`.
+			"""
+		required: false
+		type: string: {
+			default: "https://api.github.com"
+			examples: ["https://github.example.com/api/v3"]
+			syntax: "literal"
+		}
+	}
+	enterprise: {
+		description: "The slug of the enterprise whose audit log is read."
+		required:    true
+		type: string: {
+			examples: ["acme"]
+			syntax: "literal"
+		}
+	}
+	include: {
+		description: "The kinds of events read from the audit log."
+		required:    false
+		type: string: {
+			default: "web"
+			enum: {
+				all: "Both web and Git events."
+				git: "Git events."
+				web: "Web (non-Git) events."
+			}
+		}
+	}
+	phrase: {
+		description: """
+			A search phrase filtering the events read.
+
+			The `created` qualifier is set by Vector, and must not be part of the phrase.
+			"""
+		required: false
+		type: string: {
+			examples: ["action:repo.destroy"]
+			syntax: "literal"
+		}
+	}
+	poll_interval_secs: {
+		description: "The interval between polls of the audit log, in seconds."
+		required:    false
+		type: uint: default: 60
+	}
+	tls: {
+		description: "TLS configuration."
+		required:    false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. Prioritized in the order
+					they are defined.
+					"""
+				required: false
+				type: array: items: type: string: syntax: "literal"
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					If enabled, certificates must be valid in terms of not being expired, as well as being issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that not only the leaf certificate (the
+					certificate presented by the client/server) is valid, but also that the issuer of that certificate is valid, and
+					so on until reaching a root certificate.
+
+					Relevant for both incoming and outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+	token: {
+		description: """
+			The token used to authenticate with the GitHub API.
+
+			It must have the `read:audit_log` scope, and belong to an enterprise owner.
+			"""
+		required: true
+		type: string: {
+			examples: ["${GITHUB_TOKEN}"]
+			syntax: "literal"
+		}
+	}
+}
@@ -84,6 +84,55 @@ base: components: sources: heroku_logs: configuration: {
 			}
 		}
 	}
+	drain_token_table: {
+		description: """
+			Configuration for looking up the tenants of drain tokens in an enrichment table.
+
+			The drain tokens found in `drain_tokens` aren't looked up.
+			"""
+		required: false
+		type: object: options: {
+			table: {
+				description: "The name of the enrichment table."
+				required:    true
+				type: string: {
+					examples: ["drains"]
+					syntax: "literal"
+				}
+			}
+			tenant_field: {
+				description: "The field of the table holding the tenants."
+				required:    false
+				type: string: {
+					default: "tenant"
+					syntax:  "literal"
+				}
+			}
+			token_field: {
+				description: "The field of the table holding the drain tokens."
+				required:    false
+				type: string: {
+					default: "token"
+					syntax:  "literal"
+				}
+			}
+		}
+	}
+	drain_tokens: {
+		description: """
+			A mapping of drain tokens to tenants.
+
+			When set, or when `drain_token_table` is set, only the requests whose `Logplex-Drain-Token` header is a known
+			drain token are accepted. The tenant of the drain token is added to their events as `tenant`, so that a single
+			endpoint can serve the drains of many applications.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "The tenant of the drain token."
+			required:    true
+			type: string: syntax: "literal"
+		}
+	}
 	framing: {
 		description: """
 			Framing configuration.
@@ -221,6 +221,356 @@ base: components: sources: http_server: configuration: {
 			items: type: string: syntax: "literal"
 		}
 	}
+	routes: {
+		description: """
+			Additional routes to serve on the same listener.
+
+			Each route has its own decoding and header/query parameter capture rules, and sends its events to a named
+			output instead of the default one. Requests are handled by the route with the most specific matching `path`,
+			falling back to the top-level `path` and the default output.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				decoding: {
+					description: "Configures how events are decoded from raw bytes."
+					required:    false
+					type: object: options: codec: {
+						required: true
+						type: string: enum: {
+							bytes: "Uses the raw bytes as-is."
+							gelf: """
+								Decodes the raw bytes as a [GELF][gelf] message.
+
+								[gelf]: https://docs.graylog.org/docs/gelf
+								"""
+							json: """
+								Decodes the raw bytes as [JSON][json].
+
+								[json]: https://www.json.org/
+								"""
+							native: """
+								Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf] ([EXPERIMENTAL][experimental]).
+
+								[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+								[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+								"""
+							native_json: """
+								Decodes the raw bytes as Vector’s [native JSON format][vector_native_json] ([EXPERIMENTAL][experimental]).
+
+								[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+								[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+								"""
+							syslog: """
+								Decodes the raw bytes as a Syslog message.
+
+								Will decode either as the [RFC 3164][rfc3164]-style format ("old" style) or the more modern
+								[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+								[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+								[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+								"""
+						}
+					}
+				}
+				framing: {
+					description: """
+						Framing configuration.
+
+						Framing deals with how events are separated when encoded in a raw byte form, where each event is
+						a "frame" that must be prefixed, or delimited, in a way that marks where an event begins and
+						ends within the byte stream.
+						"""
+					required: false
+					type: object: options: {
+						character_delimited: {
+							description:   "Options for the character delimited decoder."
+							relevant_when: "method = \"character_delimited\""
+							required:      true
+							type: object: options: {
+								delimiter: {
+									description: "The character that delimits byte sequences."
+									required:    true
+									type: uint: {}
+								}
+								max_length: {
+									description: """
+																			The maximum length of the byte buffer.
+
+																			This length does *not* include the trailing delimiter.
+																			"""
+									required: false
+									type: uint: {}
+								}
+							}
+						}
+						method: {
+							required: true
+							type: string: enum: {
+								bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (e.g. split between messages or stream segments)."
+								character_delimited: "Byte frames which are delimited by a chosen character."
+								length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
+								newline_delimited:   "Byte frames which are delimited by a newline character."
+								octet_counting: """
+									Byte frames according to the [octet counting][octet_counting] format.
+
+									[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
+									"""
+							}
+						}
+						newline_delimited: {
+							description:   "Options for the newline delimited decoder."
+							relevant_when: "method = \"newline_delimited\""
+							required:      false
+							type: object: options: max_length: {
+								description: """
+									The maximum length of the byte buffer.
+
+									This length does *not* include the trailing delimiter.
+									"""
+								required: false
+								type: uint: {}
+							}
+						}
+						octet_counting: {
+							description:   "Options for the octet counting decoder."
+							relevant_when: "method = \"octet_counting\""
+							required:      false
+							type: object: options: max_length: {
+								description: "The maximum length of the byte buffer."
+								required:    false
+								type: uint: {}
+							}
+						}
+					}
+				}
+				headers: {
+					description: """
+						A list of HTTP headers to include in the log event.
+
+						These will override any values included in the JSON payload with conflicting names.
+						"""
+					required: false
+					type: array: {
+						default: []
+						items: type: string: syntax: "literal"
+					}
+				}
+				name: {
+					description: """
+						The name of the route.
+
+						Events received on this route are sent to the output of the same name, which other components can consume
+						as `<source_id>.<name>`.
+						"""
+					required: true
+					type: string: syntax: "literal"
+				}
+				path: {
+					description: "The URL path on which requests for this route are accepted."
+					required:    true
+					type: string: syntax: "literal"
+				}
+				query_parameters: {
+					description: """
+						A list of URL query parameters to include in the log event.
+
+						These will override any values included in the body with conflicting names.
+						"""
+					required: false
+					type: array: {
+						default: []
+						items: type: string: syntax: "literal"
+					}
+				}
+				signature: {
+					description: """
+						Webhook signature verification configuration.
+
+						The signature is computed over the request body, after it has been decompressed according to its
+						`Content-Encoding` header.
+						"""
+					required: false
+					type: object: options: {
+						algorithm: {
+							description:   "The hash algorithm used to compute the HMAC."
+							relevant_when: "provider = \"generic\""
+							required:      false
+							type: string: {
+								default: "sha256"
+								enum: {
+									sha1:   "SHA-1."
+									sha256: "SHA-256."
+									sha512: "SHA-512."
+								}
+							}
+						}
+						encoding: {
+							description:   "The encoding of the signature in the header value."
+							relevant_when: "provider = \"generic\""
+							required:      false
+							type: string: {
+								default: "hex"
+								enum: {
+									base64: "Base64."
+									hex:    "Hexadecimal."
+								}
+							}
+						}
+						header: {
+							description:   "The name of the header carrying the signature."
+							relevant_when: "provider = \"generic\""
+							required:      true
+							type: string: syntax: "literal"
+						}
+						on_failure: {
+							description: "What to do with requests whose signature can't be verified."
+							required:    false
+							type: string: {
+								default: "reject"
+								enum: {
+									reject: "Reject the request with a `401 Unauthorized` response."
+									tag: """
+										Accept the request, recording the outcome of the verification in the `signature_verified` field of each
+										event.
+										"""
+								}
+							}
+						}
+						prefix: {
+							description:   "A prefix to strip from the header value before decoding the signature, such as `sha256=`."
+							relevant_when: "provider = \"generic\""
+							required:      false
+							type: string: syntax: "literal"
+						}
+						provider: {
+							description: "The scheme used to sign requests."
+							required:    true
+							type: string: enum: {
+								generic: "An HMAC of the request body, sent in a configurable header."
+								github:  "Signatures sent by GitHub webhooks in the `X-Hub-Signature-256` header."
+								slack:   "Signatures sent by Slack in the `X-Slack-Signature` header."
+								stripe:  "Signatures sent by Stripe webhooks in the `Stripe-Signature` header."
+							}
+						}
+						secret: {
+							description: "The shared secret used to sign requests."
+							required:    true
+							type: string: syntax: "literal"
+						}
+						timestamp_tolerance_secs: {
+							description: """
+								The maximum age of a signed request, in seconds.
+
+								Only relevant for providers that include a timestamp in the signature, where it protects against replayed
+								requests.
+								"""
+							required: false
+							type: uint: default: 300
+						}
+					}
+				}
+				strict_path: {
+					description: """
+						Whether or not to treat the configured `path` as an absolute path.
+
+						If set to `true`, only requests using the exact URL path specified in `path` are handled by this route.
+						Otherwise, requests sent to a URL path that starts with the value of `path` are handled by this route.
+						"""
+					required: false
+					type: bool: default: true
+				}
+			}
+		}
+	}
+	signature: {
+		description: """
+			Webhook signature verification configuration.
+
+			The signature is computed over the request body, after it has been decompressed according to its
+			`Content-Encoding` header.
+			"""
+		required: false
+		type: object: options: {
+			algorithm: {
+				description:   "The hash algorithm used to compute the HMAC."
+				relevant_when: "provider = \"generic\""
+				required:      false
+				type: string: {
+					default: "sha256"
+					enum: {
+						sha1:   "SHA-1."
+						sha256: "SHA-256."
+						sha512: "SHA-512."
+					}
+				}
+			}
+			encoding: {
+				description:   "The encoding of the signature in the header value."
+				relevant_when: "provider = \"generic\""
+				required:      false
+				type: string: {
+					default: "hex"
+					enum: {
+						base64: "Base64."
+						hex:    "Hexadecimal."
+					}
+				}
+			}
+			header: {
+				description:   "The name of the header carrying the signature."
+				relevant_when: "provider = \"generic\""
+				required:      true
+				type: string: syntax: "literal"
+			}
+			on_failure: {
+				description: "What to do with requests whose signature can't be verified."
+				required:    false
+				type: string: {
+					default: "reject"
+					enum: {
+						reject: "Reject the request with a `401 Unauthorized` response."
+						tag: """
+							Accept the request, recording the outcome of the verification in the `signature_verified` field of each
+							event.
+							"""
+					}
+				}
+			}
+			prefix: {
+				description:   "A prefix to strip from the header value before decoding the signature, such as `sha256=`."
+				relevant_when: "provider = \"generic\""
+				required:      false
+				type: string: syntax: "literal"
+			}
+			provider: {
+				description: "The scheme used to sign requests."
+				required:    true
+				type: string: enum: {
+					generic: "An HMAC of the request body, sent in a configurable header."
+					github:  "Signatures sent by GitHub webhooks in the `X-Hub-Signature-256` header."
+					slack:   "Signatures sent by Slack in the `X-Slack-Signature` header."
+					stripe:  "Signatures sent by Stripe webhooks in the `Stripe-Signature` header."
+				}
+			}
+			secret: {
+				description: "The shared secret used to sign requests."
+				required:    true
+				type: string: syntax: "literal"
+			}
+			timestamp_tolerance_secs: {
+				description: """
+					The maximum age of a signed request, in seconds.
+
+					Only relevant for providers that include a timestamp in the signature, where it protects against replayed
+					requests.
+					"""
+				required: false
+				type: uint: default: 300
+			}
+		}
+	}
 	strict_path: {
 		description: """
 			Whether or not to treat the configured `path` as an absolute path.
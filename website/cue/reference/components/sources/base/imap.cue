@@ -0,0 +1,200 @@
+package metadata
+
+base: components: sources: imap: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level. Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how Vector handles event acknowledgement.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	address: {
+		description: "The IMAP server to read messages from, as `host:port`."
+		required:    true
+		type: string: {
+			examples: ["imap.example.com:993"]
+			syntax: "literal"
+		}
+	}
+	after_read: {
+		description: """
+			What to do with a message once it has been read.
+
+			When acknowledgements are enabled, this happens once the event read from the message has been
+			delivered.
+			"""
+		required: false
+		type: object: options: {
+			action: {
+				required: true
+				type: string: enum: {
+					delete:    "Delete the message."
+					mark_seen: "Add the `\\Seen` flag to the message."
+					move: """
+						Move the message to another mailbox.
+
+						The server must support the `MOVE` extension.
+						"""
+				}
+			}
+			mailbox: {
+				description:   "The mailbox to move the message to."
+				relevant_when: "action = \"move\""
+				required:      true
+				type: string: {
+					examples: ["Processed"]
+					syntax: "literal"
+				}
+			}
+		}
+	}
+	mailbox: {
+		description: "The mailbox to read messages from."
+		required:    false
+		type: string: {
+			default: "INBOX"
+			syntax:  "literal"
+		}
+	}
+	password: {
+		description: "The password of the user."
+		required:    true
+		type: string: {
+			examples: ["${IMAP_PASSWORD}"]
+			syntax: "literal"
+		}
+	}
+	poll_interval_secs: {
+		description: "The interval between polls of the mailbox, in seconds."
+		required:    false
+		type: uint: default: 60
+	}
+	search: {
+		description: """
+			The IMAP search criteria selecting the messages to read.
+
+			The default only selects messages without the `\\Seen` flag, which works with every
+			`after_read` action.
+			"""
+		required: false
+		type: string: {
+			default:  "UNSEEN"
+			examples: ["UNSEEN FROM \"abuse@example.com\""]
+			syntax:   "literal"
+		}
+	}
+	tls: {
+		description: "Configures the TLS options for incoming/outgoing connections."
+		required:    false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. Prioritized in the order
+					they are defined.
+					"""
+				required: false
+				type: array: items: type: string: syntax: "literal"
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			enabled: {
+				description: """
+					Whether or not to require TLS for incoming/outgoing connections.
+
+					When enabled and used for incoming connections, an identity certificate is also required. See `tls.crt_file` for
+					more information.
+					"""
+				required: false
+				type: bool: {}
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					If enabled, certificates must be valid in terms of not being expired, as well as being issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that not only the leaf certificate (the
+					certificate presented by the client/server) is valid, but also that the issuer of that certificate is valid, and
+					so on until reaching a root certificate.
+
+					Relevant for both incoming and outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+	username: {
+		description: "The user to log in as."
+		required:    true
+		type: string: {
+			examples: ["alerts@example.com"]
+			syntax: "literal"
+		}
+	}
+}
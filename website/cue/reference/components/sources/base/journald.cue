@@ -27,6 +27,18 @@ base: components: sources: journald: configuration: {
 		required:    false
 		type: uint: {}
 	}
+	boot_id: {
+		description: """
+			Only include entries from the boot with the given ID.
+
+			The ID can be an absolute boot ID, as listed by `journalctl --list-boots`, or a relative offset such as `-1` for the previous boot. Cannot be used when `current_boot_only` is explicitly enabled.
+			"""
+		required: false
+		type: string: {
+			examples: ["5ab4e7c1f0040b57a4b9f0093b1f85f0", "-1"]
+			syntax: "literal"
+		}
+	}
 	current_boot_only: {
 		description: "Only include entries that occurred after the current boot of the system."
 		required:    false
@@ -73,6 +85,17 @@ base: components: sources: journald: configuration: {
 			items: type: string: syntax: "literal"
 		}
 	}
+	group_trusted_fields: {
+		description: """
+			Whether or not to group the trusted journal fields into a single object.
+
+			Trusted fields are those added by `journald` itself, prefixed with a single underscore, such as `_PID`, `_UID` or `_SYSTEMD_UNIT`. When enabled, they are placed under a `trusted` object with the leading underscore removed, instead of alongside the fields supplied by the logging process.
+
+			The `_HOSTNAME` and `_SOURCE_REALTIME_TIMESTAMP` fields are still used to set the host and timestamp of the event.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	include_matches: {
 		description: """
 			A list of sets of field/value pairs to monitor.
@@ -114,6 +137,20 @@ base: components: sources: journald: configuration: {
 		required: false
 		type: string: syntax: "literal"
 	}
+	journal_namespace: {
+		description: """
+			The journal namespace to read from.
+
+			Journal namespaces allow services to log into separate journals, such as one per tenant. If not set, only the default namespace is read.
+
+			The value `*` reads from all namespaces, and `+<name>` reads from the given namespace interleaved with the default one.
+			"""
+		required: false
+		type: string: {
+			examples: ["tenant-a"]
+			syntax: "literal"
+		}
+	}
 	journalctl_path: {
 		description: """
 			The full path of the `journalctl` executable.
@@ -23,6 +23,32 @@ base: components: sources: kubernetes_logs: configuration: {
 		required: false
 		type: uint: default: 60000
 	}
+	exclude_annotation_selector: {
+		description: """
+			Specifies the annotation selector of `Pod`s to exclude.
+
+			`Pod`s matching all of the requirements of this selector are not collected. If empty, no `Pod`s are excluded.
+			"""
+		required: false
+		type: string: {
+			default:  ""
+			examples: ["example.com/noisy=true"]
+			syntax:   "literal"
+		}
+	}
+	exclude_label_selector: {
+		description: """
+			Specifies the label selector of `Pod`s to exclude.
+
+			`Pod`s matching all of the requirements of this selector are not collected. If empty, no `Pod`s are excluded.
+			"""
+		required: false
+		type: string: {
+			default:  ""
+			examples: ["tier in (debug, canary)"]
+			syntax:   "literal"
+		}
+	}
 	exclude_paths_glob_patterns: {
 		description: "A list of glob patterns to exclude from reading the files."
 		required:    false
@@ -31,6 +57,19 @@ base: components: sources: kubernetes_logs: configuration: {
 			items: type: string: syntax: "literal"
 		}
 	}
+	extra_annotation_selector: {
+		description: """
+			Specifies the annotation selector to filter `Pod`s with.
+
+			Uses the same syntax as label selectors, both equality-based (`=`, `==`, `!=`) and set-based (`in`, `notin`, `key`, `!key`). As the Kubernetes API does not support filtering on annotations, this selector is evaluated by Vector before it starts reading the logs of a `Pod`.
+			"""
+		required: false
+		type: string: {
+			default:  ""
+			examples: ["example.com/logs=enabled"]
+			syntax:   "literal"
+		}
+	}
 	extra_field_selector: {
 		description: "Specifies the field selector to filter `Pod`s with, to be used in addition to the built-in `Node` filter."
 		required:    false
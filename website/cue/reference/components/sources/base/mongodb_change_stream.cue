@@ -0,0 +1,105 @@
+package metadata
+
+base: components: sources: mongodb_change_stream: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level. Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how Vector handles event acknowledgement.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	collection: {
+		description: """
+			The collection of `database` to watch.
+
+			By default, all collections of the database are watched.
+			"""
+		required: false
+		type: string: {
+			examples: ["users"]
+			syntax: "literal"
+		}
+	}
+	data_dir: {
+		description: """
+			The directory used to persist the resume token of the last change processed.
+
+			By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+			"""
+		required: false
+		type: string: syntax: "literal"
+	}
+	database: {
+		description: """
+			The database to watch.
+
+			By default, all databases of the deployment are watched.
+			"""
+		required: false
+		type: string: {
+			examples: ["app"]
+			syntax: "literal"
+		}
+	}
+	endpoint: {
+		description: """
+			The MongoDB deployment to watch, in the [Connection String URI
+			format](https://www.mongodb.com/docs/manual/reference/connection-string/).
+
+			Change streams are only available on replica sets and sharded clusters.
+			"""
+		required: true
+		type: string: {
+			examples: ["mongodb://localhost:27017/?replicaSet=rs0"]
+			syntax: "literal"
+		}
+	}
+	full_document: {
+		description: "What the change events include of the document after an update."
+		required:    false
+		type: string: {
+			default: "default"
+			enum: {
+				default:  "Only inserts and replaces include the document."
+				required: "Like `when_available`, but reading fails when the document isn't available."
+				update_lookup: """
+					Updates include the current version of the document, looked up when the change is read.
+
+					The document may include later changes, or be missing if it was deleted since.
+					"""
+				when_available: """
+					Updates include the document as it was right after the change, when available.
+
+					This requires MongoDB 6.0 and `changeStreamPreAndPostImages` to be enabled on the collections.
+					"""
+			}
+		}
+	}
+	full_document_before_change: {
+		description: "What the change events include of the document before an update, replace, or delete."
+		required:    false
+		type: string: {
+			default: "off"
+			enum: {
+				off:      "The document before the change isn't included."
+				required: "Like `when_available`, but reading fails when the document isn't available."
+				when_available: """
+					The document before the change is included, when available.
+
+					This requires MongoDB 6.0 and `changeStreamPreAndPostImages` to be enabled on the collections.
+					"""
+			}
+		}
+	}
+}
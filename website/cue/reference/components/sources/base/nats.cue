@@ -1,6 +1,24 @@
 package metadata
 
 base: components: sources: nats: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level. Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how Vector handles event acknowledgement.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
 	auth: {
 		description: "Configuration of the authentication strategy when interacting with NATS."
 		required:    false
@@ -213,9 +231,44 @@ base: components: sources: nats: configuration: {
 			}
 		}
 	}
+	jetstream: {
+		description: """
+			Configuration for reading messages with a JetStream consumer.
+
+			When set, messages are read from the JetStream stream bound to `subject`, with a durable pull
+			consumer. Each message is acknowledged once its events have been processed, or negatively
+			acknowledged to be redelivered if they couldn't be delivered.
+			"""
+		required: false
+		type: object: options: {
+			batch_size: {
+				description: "The maximum number of messages to fetch at once."
+				required:    false
+				type: uint: default: 100
+			}
+			durable_name: {
+				description: """
+					The name of the durable consumer to read messages with.
+
+					The consumer is created if it doesn't exist yet.
+					"""
+				required: true
+				type: string: syntax: "literal"
+			}
+			fetch_timeout_ms: {
+				description: "The maximum amount of time, in milliseconds, to wait for a batch of messages to be fetched."
+				required:    false
+				type: uint: default: 5000
+			}
+		}
+	}
 	queue: {
-		description: "NATS Queue Group to join."
-		required:    false
+		description: """
+			NATS Queue Group to join.
+
+			Not supported with `jetstream`, where messages are shared by the clients reading with the same consumer.
+			"""
+		required: false
 		type: string: syntax: "literal"
 	}
 	subject: {
@@ -10,12 +10,46 @@ base: components: sources: prometheus_scrape: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			client_id: {
+				description:   "The identifier of the client."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: syntax: "literal"
+			}
+			client_secret: {
+				description:   "The secret of the client."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: syntax: "literal"
+			}
 			password: {
 				description:   "The password to send."
 				relevant_when: "strategy = \"basic\""
 				required:      true
 				type: string: syntax: "literal"
 			}
+			refresh_before_secs: {
+				description: """
+					How long before they expire access tokens are refreshed, in seconds.
+
+					Access tokens expiring sooner than this are refreshed halfway through their lifetime.
+					"""
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: uint: default: 60
+			}
+			scopes: {
+				description:   "The scopes to request access tokens for."
+				relevant_when: "strategy = \"oauth2\""
+				required:      false
+				type: array: {
+					default: []
+					items: type: string: {
+						examples: ["logs:write"]
+						syntax: "literal"
+					}
+				}
+			}
 			strategy: {
 				required: true
 				type: string: enum: {
@@ -31,6 +65,15 @@ base: components: sources: prometheus_scrape: configuration: {
 
 						The bearer token value (OAuth2, JWT, etc) is passed as-is.
 						"""
+					oauth2: """
+						OAuth2 authentication, with the client credentials grant.
+
+						Access tokens are requested from the token endpoint with the credentials of the client, and
+						passed as bearer tokens. They're cached, and refreshed before they expire.
+
+						This is only supported by the `http`, `loki`, and `prometheus_remote_write` sinks, and the
+						`remote_lookup` transform. Other components fail to build with it.
+						"""
 				}
 			}
 			token: {
@@ -39,6 +82,15 @@ base: components: sources: prometheus_scrape: configuration: {
 				required:      true
 				type: string: syntax: "literal"
 			}
+			token_endpoint: {
+				description:   "The token endpoint of the authorization server, which access tokens are requested from."
+				relevant_when: "strategy = \"oauth2\""
+				required:      true
+				type: string: {
+					examples: ["https://auth.example.com/oauth2/token"]
+					syntax: "literal"
+				}
+			}
 			user: {
 				description:   "The username to send."
 				relevant_when: "strategy = \"basic\""
@@ -107,6 +159,95 @@ base: components: sources: prometheus_scrape: configuration: {
 			type: array: items: type: string: syntax: "literal"
 		}
 	}
+	relabel_configs: {
+		description: """
+			Relabeling rules applied, in order, to the scraped metrics.
+
+			The rules are applied after the instance and endpoint tags are added. Metrics dropped by a rule are discarded
+			before being sent downstream, which allows high-cardinality series to be filtered out at the source.
+
+			This matches Prometheus’ `metric_relabel_configs` configuration.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				action: {
+					description: "Action to perform when relabeling a metric."
+					required:    false
+					type: string: {
+						default: "replace"
+						enum: {
+							drop:     "Drops metrics whose source labels match `regex`."
+							keep:     "Drops metrics whose source labels don't match `regex`."
+							labelmap: "Copies the labels whose name matches `regex` to the label named after `replacement`."
+							replace: """
+								Replaces `target_label` with `replacement` when `regex` matches the source labels.
+
+								The label is removed if the replacement is empty.
+								"""
+						}
+					}
+				}
+				regex: {
+					description: """
+						The regular expression matched against the source labels, or the label names for `labelmap`.
+
+						The expression is anchored at both ends.
+						"""
+					required: false
+					type: string: {
+						default: "(.*)"
+						examples: ["go_.*"]
+						syntax: "literal"
+					}
+				}
+				replacement: {
+					description: """
+						The value of the label set by `replace`, or the name of the labels copied by `labelmap`.
+
+						The capture groups of `regex` can be referenced with `$1`, `${1}`, or `${name}`.
+						"""
+					required: false
+					type: string: {
+						default: "$1"
+						syntax:  "literal"
+					}
+				}
+				separator: {
+					description: "The separator placed between the values of the source labels."
+					required:    false
+					type: string: {
+						default: ";"
+						syntax:  "literal"
+					}
+				}
+				source_labels: {
+					description: "The labels whose values are concatenated, with `separator`, to be matched against `regex`."
+					required:    false
+					type: array: {
+						default: []
+						items: type: string: {
+							examples: ["__name__"]
+							syntax: "literal"
+						}
+					}
+				}
+				target_label: {
+					description: """
+						The label set by `replace`.
+
+						The capture groups of `regex` can be referenced, as in `replacement`.
+						"""
+					required: false
+					type: string: {
+						examples: ["job"]
+						syntax: "literal"
+					}
+				}
+			}
+		}
+	}
 	scrape_interval_secs: {
 		description: "The interval between scrapes, in seconds."
 		required:    false
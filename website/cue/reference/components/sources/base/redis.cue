@@ -1,8 +1,26 @@
 package metadata
 
 base: components: sources: redis: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level. Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how Vector handles event acknowledgement.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
 	data_type: {
-		description: "The Redis data type (`list` or `channel`) to use."
+		description: "The Redis data type (`list`, `channel` or `stream`) to use."
 		required:    false
 		type: string: {
 			default: "list"
@@ -13,6 +31,11 @@ base: components: sources: redis: configuration: {
 					This is based on Redis' Pub/Sub capabilities.
 					"""
 				list: "The `list` data type."
+				stream: """
+					The `stream` data type.
+
+					Messages are read from the stream with a consumer group, and are acknowledged once they have been processed.
+					"""
 			}
 		}
 	}
@@ -162,6 +185,82 @@ base: components: sources: redis: configuration: {
 		required: false
 		type: string: syntax: "literal"
 	}
+	stream: {
+		description: "Options for the Redis `stream` data type."
+		required:    false
+		type: object: options: {
+			batch_size: {
+				description: "The maximum number of messages to read at once."
+				required:    false
+				type: uint: default: 100
+			}
+			claim_min_idle_ms: {
+				description: """
+					The minimum time, in milliseconds, a message must have been pending for before it is claimed.
+
+					Messages that were delivered to a consumer of the group but never acknowledged, for example because the consumer stopped, or because the events could not be delivered, are claimed and read again by this consumer once they have been pending for this long. Pending messages are checked for at the same interval.
+
+					Set to `0` to never claim pending messages.
+					"""
+				required: false
+				type: uint: default: 60000
+			}
+			consumer: {
+				description: """
+					The name of the consumer within the consumer group.
+
+					Each Vector instance reading with the same consumer group must use a distinct name. By default, the hostname is used.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			field: {
+				description: """
+					The name of the message field to decode events from.
+
+					Messages without this field are acknowledged and discarded.
+					"""
+				required: false
+				type: string: {
+					default: "message"
+					syntax:  "literal"
+				}
+			}
+			group: {
+				description: """
+					The name of the consumer group to read messages with.
+
+					The consumer group, and the stream itself, are created if they don't exist yet.
+					"""
+				required: false
+				type: string: {
+					default: "vector"
+					syntax:  "literal"
+				}
+			}
+			id_key: {
+				description: """
+					Sets the name of the log field to use to add the message ID to each event.
+
+					By default, this is not set and the field will not be automatically added.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			start_id: {
+				description: """
+					The ID of the first message to read when the consumer group is created.
+
+					By default, only the messages added to the stream after the consumer group is created are read. Use `0` to read the whole stream.
+					"""
+				required: false
+				type: string: {
+					default: "$"
+					syntax:  "literal"
+				}
+			}
+		}
+	}
 	url: {
 		description: """
 			The Redis URL to connect to.
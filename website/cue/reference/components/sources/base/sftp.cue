@@ -0,0 +1,284 @@
+package metadata
+
+base: components: sources: sftp: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level. Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how Vector handles event acknowledgement.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	after_read: {
+		description: """
+			What to do with a file once it has been read.
+
+			When acknowledgements are enabled, this happens once the events read from the file have been
+			delivered.
+			"""
+		required: false
+		type: object: options: {
+			action: {
+				required: true
+				type: string: enum: {
+					delete: "Delete the file from the server."
+					keep: """
+						Leave the file on the server.
+
+						The file is read again only if its size or modification time changes.
+						"""
+					rename: """
+						Rename the file on the server by appending a suffix to its name.
+
+						Renamed files are never read again, even if they match an `include` pattern.
+						"""
+				}
+			}
+			suffix: {
+				description:   "The suffix appended to the name of the file."
+				relevant_when: "action = \"rename\""
+				required:      false
+				type: string: {
+					default: ".processed"
+					syntax:  "literal"
+				}
+			}
+		}
+	}
+	auth: {
+		description: "How to authenticate with the SFTP server."
+		required:    true
+		type: object: options: {
+			passphrase: {
+				description:   "The passphrase of the private key, if it is encrypted."
+				relevant_when: "strategy = \"key\""
+				required:      false
+				type: string: {
+					examples: ["${SFTP_KEY_PASSPHRASE}"]
+					syntax: "literal"
+				}
+			}
+			password: {
+				description:   "The password of the user."
+				relevant_when: "strategy = \"password\""
+				required:      true
+				type: string: {
+					examples: ["${SFTP_PASSWORD}"]
+					syntax: "literal"
+				}
+			}
+			private_key_file: {
+				description:   "The path of the private key file."
+				relevant_when: "strategy = \"key\""
+				required:      true
+				type: string: {
+					examples: ["/etc/vector/id_ed25519"]
+					syntax: "literal"
+				}
+			}
+			strategy: {
+				required: true
+				type: string: enum: {
+					key:      "Authenticate with a private key."
+					password: "Authenticate with a password."
+				}
+			}
+		}
+	}
+	data_dir: {
+		description: """
+			The directory used to persist which files were already read.
+
+			By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+			"""
+		required: false
+		type: string: syntax: "literal"
+	}
+	decoding: {
+		description: "Configures how events are decoded from raw bytes."
+		required:    false
+		type: object: {
+			default: codec: "bytes"
+			options: codec: {
+				required: false
+				type: string: {
+					default: "bytes"
+					enum: {
+						bytes: "Uses the raw bytes as-is."
+						gelf: """
+															Decodes the raw bytes as a [GELF][gelf] message.
+
+															[gelf]: https://docs.graylog.org/docs/gelf
+															"""
+						json: """
+															Decodes the raw bytes as [JSON][json].
+
+															[json]: https://www.json.org/
+															"""
+						native: """
+															Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf] ([EXPERIMENTAL][experimental]).
+
+															[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+															"""
+						native_json: """
+															Decodes the raw bytes as Vector’s [native JSON format][vector_native_json] ([EXPERIMENTAL][experimental]).
+
+															[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+															"""
+						syslog: """
+															Decodes the raw bytes as a Syslog message.
+
+															Will decode either as the [RFC 3164][rfc3164]-style format ("old" style) or the more modern
+															[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+															[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+															[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+															"""
+					}
+				}
+			}
+		}
+	}
+	endpoint: {
+		description: """
+			The SFTP server to download files from, as `host` or `host:port`.
+
+			The port defaults to `22`.
+			"""
+		required: true
+		type: string: {
+			examples: ["sftp.example.com:22"]
+			syntax: "literal"
+		}
+	}
+	framing: {
+		description: """
+			Framing configuration.
+
+			Framing deals with how events are separated when encoded in a raw byte form, where each event is
+			a "frame" that must be prefixed, or delimited, in a way that marks where an event begins and
+			ends within the byte stream.
+			"""
+		required: false
+		type: object: options: {
+			character_delimited: {
+				description:   "Options for the character delimited decoder."
+				relevant_when: "method = \"character_delimited\""
+				required:      true
+				type: object: options: {
+					delimiter: {
+						description: "The character that delimits byte sequences."
+						required:    true
+						type: uint: {}
+					}
+					max_length: {
+						description: """
+																The maximum length of the byte buffer.
+
+																This length does *not* include the trailing delimiter.
+																"""
+						required: false
+						type: uint: {}
+					}
+				}
+			}
+			method: {
+				required: true
+				type: string: enum: {
+					bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (e.g. split between messages or stream segments)."
+					character_delimited: "Byte frames which are delimited by a chosen character."
+					length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
+					newline_delimited:   "Byte frames which are delimited by a newline character."
+					octet_counting: """
+						Byte frames according to the [octet counting][octet_counting] format.
+
+						[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
+						"""
+				}
+			}
+			newline_delimited: {
+				description:   "Options for the newline delimited decoder."
+				relevant_when: "method = \"newline_delimited\""
+				required:      false
+				type: object: options: max_length: {
+					description: """
+						The maximum length of the byte buffer.
+
+						This length does *not* include the trailing delimiter.
+						"""
+					required: false
+					type: uint: {}
+				}
+			}
+			octet_counting: {
+				description:   "Options for the octet counting decoder."
+				relevant_when: "method = \"octet_counting\""
+				required:      false
+				type: object: options: max_length: {
+					description: "The maximum length of the byte buffer."
+					required:    false
+					type: uint: {}
+				}
+			}
+		}
+	}
+	include: {
+		description: """
+			Patterns of the remote files to download.
+
+			Only the file name may contain wildcards, such as `*` and `?`. The directory of each
+			pattern is listed on every poll.
+			"""
+		required: true
+		type: array: items: type: string: {
+			examples: ["/outgoing/*.log"]
+			syntax: "literal"
+		}
+	}
+	known_hosts_file: {
+		description: """
+			The path of an OpenSSH `known_hosts` file used to verify the host key of the server.
+
+			If not set, the host key of the server is not verified.
+			"""
+		required: false
+		type: string: {
+			examples: ["/etc/vector/known_hosts"]
+			syntax: "literal"
+		}
+	}
+	min_file_age_secs: {
+		description: """
+			The minimum time since a file was last modified before it is downloaded, in seconds.
+
+			This prevents downloading files which are still being uploaded.
+			"""
+		required: false
+		type: uint: default: 0
+	}
+	poll_interval_secs: {
+		description: "The interval between polls of the SFTP server, in seconds."
+		required:    false
+		type: uint: default: 60
+	}
+	username: {
+		description: "The user to log in as."
+		required:    true
+		type: string: {
+			examples: ["vector"]
+			syntax: "literal"
+		}
+	}
+}
@@ -10,6 +10,24 @@ base: components: sources: syslog: configuration: {
 			string: syntax: "literal"
 		}
 	}
+	client_certificate_tenants: {
+		description: """
+			A mapping of client certificate identities to tenants.
+
+			When set, only the clients presenting a certificate whose common name, or one of whose subject alternative
+			names, is mapped are allowed to connect. The tenant they're mapped to is added to the TLS client metadata of
+			their events, as `tenant`, to route them per tenant.
+
+			This requires TLS to be enabled, with `tls.verify_certificate`.
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: object: options: "*": {
+			description: "The tenant of the client certificate identity."
+			required:    true
+			type: string: syntax: "literal"
+		}
+	}
 	connection_limit: {
 		description:   "The maximum number of TCP connections that will be allowed at any given time."
 		relevant_when: "mode = \"tcp\""
@@ -28,6 +28,31 @@ base: components: sources: vector: configuration: {
 		required: true
 		type: string: syntax: "literal"
 	}
+	rate_limit: {
+		description: """
+			Limits the rate at which each client can send requests.
+
+			Requests exceeding the limit are rejected, and retried by the `vector` sink of the client.
+			"""
+		required: false
+		type: object: options: {
+			threshold: {
+				description: """
+					The number of requests allowed for a given client per configured `window_secs`.
+
+					Clients are identified by the subject of their TLS certificate, if they presented one, and by their IP
+					address otherwise.
+					"""
+				required: true
+				type: uint: {}
+			}
+			window_secs: {
+				description: "The time window in which the configured `threshold` is applied, in seconds."
+				required:    true
+				type: float: {}
+			}
+		}
+	}
 	tls: {
 		description: "Configures the TLS options for incoming/outgoing connections."
 		required:    false
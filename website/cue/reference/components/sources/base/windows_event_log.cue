@@ -0,0 +1,65 @@
+package metadata
+
+base: components: sources: windows_event_log: configuration: {
+	batch_size: {
+		description: """
+			The maximum number of events read from a channel at once.
+
+			The bookmark of a channel is persisted after each batch.
+			"""
+		required: false
+		type: uint: default: 10
+	}
+	channels: {
+		description: """
+			The event log channels to read from.
+
+			Channels can be classic event logs, such as `System` or `Application`, or the channels of event providers, such as `Microsoft-Windows-Sysmon/Operational`. The channels available on a host are listed by `wevtutil el`.
+			"""
+		required: true
+		type: array: items: type: string: {
+			examples: ["System", "Application", "Microsoft-Windows-Sysmon/Operational"]
+			syntax: "literal"
+		}
+	}
+	data_dir: {
+		description: """
+			The directory used to persist the channel bookmarks.
+
+			By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+			"""
+		required: false
+		type: string: syntax: "literal"
+	}
+	query: {
+		description: """
+			The XPath query used to select the events to read from each channel.
+
+			The query follows the same syntax as the filters of the Event Viewer, which only supports a subset of XPath 1.0. By default, all events are read.
+			"""
+		required: false
+		type: string: {
+			default: "*"
+			examples: ["*[System[(Level=1 or Level=2 or Level=3)]]", "*[System[EventID=4624]]"]
+			syntax: "literal"
+		}
+	}
+	read_existing_events: {
+		description: """
+			Whether or not to read the events already present in a channel, when no bookmark has been persisted for it yet.
+
+			By default, only the events published after Vector starts are read. Once a bookmark has been persisted for a channel, reading always resumes after it.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	render_message: {
+		description: """
+			Whether or not to render the message of each event, using the message resources of its provider.
+
+			When disabled, or when the provider has no message resources installed on the host, the events have no message.
+			"""
+		required: false
+		type: bool: default: true
+	}
+}
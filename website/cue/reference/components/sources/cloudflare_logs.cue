@@ -0,0 +1,123 @@
+package metadata
+
+components: sources: cloudflare_logs: {
+	title: "Cloudflare Logs"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.cloudflare
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				The `pull` mode requires the Logpull retention of the zone to be enabled.
+				""",
+		]
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.cloudflare_logs.configuration
+
+	output: logs: event: {
+		description: "A log of a Cloudflare dataset, with the fields pulled or pushed."
+		fields: {
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["cloudflare_logs"]
+				}
+			}
+			timestamp: {
+				description: """
+					The time of the event, from its `EdgeStartTimestamp`, `Datetime`, or `Timestamp`
+					field when it's formatted as RFC 3339, or the time it was received otherwise.
+					"""
+				required: true
+				type: timestamp: {}
+			}
+			zone: {
+				description: """
+					The zone of the event: the `zone_id` in the `pull` mode, or the `zone` query
+					parameter of the Logpush request in the `push` mode.
+					"""
+				required: false
+				type: string: {
+					examples: ["023e105f4ecef8ad9ca31a8372d0c353", "example.com"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		pull: {
+			title: "Pulling Logs"
+			body: """
+				In the `pull` mode, Vector pulls the HTTP request logs of the zone from the
+				[Logpull API](\(urls.cloudflare_logpull)), one time window of `window_secs` at a
+				time. A window is only pulled once it ended at least `delay_secs` ago, and the end
+				of the last window pulled is persisted in the `data_dir` once its logs have been
+				sent downstream or, when acknowledgements are enabled, delivered. Logs are
+				therefore neither lost nor duplicated across restarts, as long as Vector isn't
+				stopped for longer than Cloudflare retains them.
+				"""
+		}
+		push: {
+			title: "Receiving Logpush Requests"
+			body: """
+				In the `push` mode, Vector listens on `address` for the requests of Logpush jobs
+				with an [HTTP destination](\(urls.cloudflare_logpush_http)), whatever the dataset
+				they push. Set the `zone` query parameter in the destination URL, such as
+				`https://vector.example.com/?zone=example.com`, to have it added to the events.
+				Additional `header_*` parameters can be used to send the `Authorization` header
+				configured with `auth`. Jobs should use the `rfc3339` timestamp format for the
+				time of the events to be parsed. The message Logpush sends to validate the
+				destination is dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
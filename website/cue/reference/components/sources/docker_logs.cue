@@ -355,6 +355,23 @@ components: sources: docker_logs: {
 	]
 
 	how_it_works: {
+		cri_runtime: {
+			title: "CRI Runtimes"
+			body: """
+				Hosts running containerd or CRI-O instead of the Docker daemon, such as most
+				modern Kubernetes nodes, don't expose the Docker API. Setting `runtime` to `cri`
+				makes Vector tail the container log files written in the CRI log format from
+				`cri.log_directory` instead, which defaults to the `/var/log/containers`
+				directory maintained by the `kubelet`. Setting `runtime` to `auto` picks the
+				Docker API when the Docker daemon is available, and falls back to the CRI log
+				files when a containerd or CRI-O socket is found.
+
+				The CRI log files don't carry the container image, creation time, or labels, so
+				those fields are absent from the events, and the `include_labels` and
+				`include_images` options are ignored. Container name and ID filtering is applied
+				based on the log file names.
+				"""
+		}
 		message_merging: {
 			title: "Merging Split Messages"
 			body: """
@@ -69,6 +69,12 @@ components: sources: exec: {
 			required:    false
 			type: bool: default: true
 		}
+		separate_stderr_output: {
+			common:      false
+			description: "Send the events generated from stderr to the `stderr` output, consumed as `<source_id>.stderr`, instead of the default output. Has no effect unless `include_stderr` is enabled."
+			required:    false
+			type: bool: default: false
+		}
 		maximum_buffer_size_bytes: {
 			common:      false
 			description: "The maximum buffer size allowed before a log event will be generated."
@@ -87,7 +93,7 @@ components: sources: exec: {
 				options: {
 					exec_interval_secs: {
 						common:        true
-						description:   "The interval in seconds between scheduled command runs. The command will be killed if it takes longer than exec_interval_secs to run."
+						description:   "The interval in seconds between scheduled command runs. The command will be killed if it takes longer than exec_interval_secs to run, unless timeout_secs is set."
 						relevant_when: "mode = `scheduled`"
 						required:      false
 						type: uint: {
@@ -95,6 +101,33 @@ components: sources: exec: {
 							unit:    "seconds"
 						}
 					}
+					timeout_secs: {
+						common:        false
+						description:   "The maximum time in seconds a scheduled command run can take before it is killed. Defaults to exec_interval_secs."
+						relevant_when: "mode = `scheduled`"
+						required:      false
+						type: uint: {
+							default: null
+							unit:    "seconds"
+						}
+					}
+					jitter_secs: {
+						common:        false
+						description:   "The maximum time in seconds to randomly delay each scheduled command run by."
+						relevant_when: "mode = `scheduled`"
+						required:      false
+						type: uint: {
+							default: 0
+							unit:    "seconds"
+						}
+					}
+					include_exit_code: {
+						common:        false
+						description:   "Add the exit code of the command to the events of each run. The events of a run are held until the command exits."
+						relevant_when: "mode = `scheduled`"
+						required:      false
+						type: bool: default: false
+					}
 				}
 			}
 		}
@@ -142,6 +175,15 @@ components: sources: exec: {
 					examples: ["stdout", "stderr"]
 				}
 			}
+			exit_code: {
+				description: "The exit code of the command. Only set in scheduled mode when `include_exit_code` is enabled, and absent if the command was killed."
+				required:    false
+				common:      false
+				type: int: {
+					default: null
+					examples: [0, 1]
+				}
+			}
 			pid: {
 				description: "The process ID of the command."
 				required:    true
@@ -188,6 +230,19 @@ components: sources: exec: {
 	]
 
 	how_it_works: {
+		exit_codes: {
+			title: "Exit Codes"
+			body: """
+				In scheduled mode, the exit code of each run can be added to its events by enabling
+				[`include_exit_code`](#scheduled.include_exit_code). Vector then holds the events of
+				a run until the command exits, so that all of them carry the `exit_code` field. This
+				allows alerting on failing commands, for example by routing events with a non-zero
+				`exit_code` to a separate sink.
+
+				Runs that are killed, because they exceeded [`timeout_secs`](#scheduled.timeout_secs)
+				or were terminated by a signal, have no exit code.
+				"""
+		}
 		line_delimiters: {
 			title: "Line Delimiters"
 			body: """
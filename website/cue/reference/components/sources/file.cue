@@ -310,19 +310,18 @@ components: sources: file: {
 			title: "Compressed Files"
 			body: """
 				Vector will transparently detect files which have been compressed
-				using Gzip and decompress them for reading. This detection process
-				looks for the unique sequence of bytes in the Gzip header and does
-				not rely on the compressed files adhering to any kind of naming
-				convention.
-
-				One caveat with reading compressed files is that Vector is not able
-				to efficiently seek into them. Rather than implement a
-				potentially-expensive full scan as a seek mechanism, Vector
-				currently will not attempt to make further reads from a file for
-				which it has already stored a checkpoint in a previous run. For
-				this reason, users should take care to allow Vector to fully
-				process any compressed files before shutting the process down or moving the
-				files to another location on disk.
+				using Gzip or Zstandard and decompress them for reading. This
+				detection process looks for the unique sequence of bytes in the
+				Gzip and Zstandard headers and does not rely on the compressed files
+				adhering to any kind of naming convention.
+
+				Checkpoints for compressed files record the offset into the
+				uncompressed contents. As compressed files can't be seeked into,
+				resuming a compressed file from a checkpoint decompresses and
+				discards its contents up to the checkpointed offset, which can take
+				a while for large files. Compressed files are never read when
+				`read_from` is set to `end`, as their contents are assumed to be
+				complete.
 				"""
 		}
 
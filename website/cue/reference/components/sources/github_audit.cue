@@ -0,0 +1,141 @@
+package metadata
+
+components: sources: github_audit: {
+	title: "GitHub Audit Log"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.github
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				The token must have the `read:audit_log` scope, and belong to an owner of the
+				enterprise.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.github_audit.configuration
+
+	output: logs: event: {
+		description: "An event of the audit log, with all the fields returned by the API."
+		fields: {
+			"@timestamp": {
+				description: "The time the event was created, in milliseconds since the Unix epoch."
+				required:    true
+				type: uint: {
+					examples: [1673353800000]
+					unit: "milliseconds"
+				}
+			}
+			"_document_id": {
+				description: "The unique identifier of the event."
+				required:    true
+				type: string: {
+					examples: ["LwW2vpJZCDS-WUmo9Z-ifw"]
+				}
+			}
+			action: {
+				description: "The action performed."
+				required:    true
+				type: string: {
+					examples: ["repo.destroy"]
+				}
+			}
+			actor: {
+				description: "The user who performed the action."
+				required:    false
+				type: string: {
+					examples: ["octocat"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["github_audit"]
+				}
+			}
+			timestamp: {
+				description: "The time the event was created, from its `@timestamp` field."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		polling: {
+			title: "Polling"
+			body: """
+				Every `poll_interval_secs`, Vector searches the [audit log](\(urls.github_audit_log_api))
+				of the enterprise for the events created since the last one read, oldest first,
+				following the pages of the results.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				The time of the last event read, along with the identifiers of the events read at
+				that time, is persisted in the `data_dir` once the events have been sent downstream
+				or, when acknowledgements are enabled, once they have been delivered. Events are
+				therefore neither lost nor duplicated across restarts. When no checkpoint is
+				persisted, reading starts `backfill_secs` before Vector starts.
+				"""
+		}
+		rate_limits: {
+			title: "Rate Limits"
+			body: """
+				When the rate limit of the GitHub API is exhausted, Vector waits for it to be reset
+				before sending the next request, as told by the `Retry-After` or
+				`X-RateLimit-Reset` headers of the response.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
@@ -0,0 +1,120 @@
+package metadata
+
+components: sources: grpc_server: {
+	_port: 50051
+
+	title: "gRPC Server"
+
+	description: """
+		Serves a gRPC method defined by a user-provided descriptor set, turning the request messages into
+		events. Internal services can push telemetry to Vector directly, without an HTTP shim.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.grpc
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: enabled: false
+			keepalive: enabled:            true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.grpc_server.configuration
+
+	output: logs: event: {
+		description: "A request message."
+		fields: {
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["grpc_server"]
+				}
+			}
+			timestamp: fields._current_timestamp
+			"*": {
+				description: "The fields set in the request message, named after the fields of its definition."
+				required:    false
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		descriptor_sets: {
+			title: "Descriptor sets"
+			body: """
+				The served method, and the messages it takes and returns, are read from a
+				[file descriptor set][urls.protobuf_descriptor_set] when the source starts. It must include the
+				definitions imported by the file defining the method, which `protoc` does with its
+				`--include_imports` flag:
+
+				```shell
+				protoc --include_imports --descriptor_set_out=telemetry.desc telemetry.proto
+				```
+
+				Unary and client streaming methods are supported, while server streaming methods are rejected.
+				Calls to methods other than the configured one fail with the `UNIMPLEMENTED` status.
+				"""
+		}
+		message_conversion: {
+			title: "Message conversion"
+			body: """
+				Each request message becomes a log event, with a field for each field set in the message.
+				Nested messages become objects, repeated fields arrays, and maps objects keyed by the map keys.
+				Enumerations are converted to the name of their value, or to their number if it is not defined.
+
+				The call succeeds once the events are accepted, or delivered when acknowledgements are enabled,
+				and returns the default value of the method's output message. Failed deliveries are reported to
+				the client with the `INTERNAL` or `DATA_LOSS` status.
+				"""
+		}
+		compression: {
+			title: "Compression"
+			body: """
+				Request messages compressed with gzip or Zstandard are decompressed before being decoded.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -100,10 +100,35 @@ components: sources: heroku_logs: {
 					examples: ["heroku_logs"]
 				}
 			}
+			tenant: {
+				description: "The tenant of the drain token of the request, when `drain_tokens` or `drain_token_table` is set."
+				required:    false
+				type: string: {
+					examples: ["acme"]
+				}
+			}
 			timestamp: fields._current_timestamp
 		}
 	}
 
+	how_it_works: {
+		drain_tokens: {
+			title: "Drain Tokens"
+			body: """
+				Every request sent by a Logplex drain holds the token of the drain in its
+				`Logplex-Drain-Token` header. When `drain_tokens` or `drain_token_table` is set,
+				requests with an unknown drain token are rejected with a `401 Unauthorized`
+				status, and the events of the others get the tenant of their drain token as
+				`tenant`. This allows a single endpoint to safely serve the drains of many
+				applications, whose events can then be routed per tenant.
+
+				The tenants are looked up in `drain_tokens` first, then in the `drain_token_table`
+				[enrichment table](\(urls.enrichment_tables_concept)), which can be reloaded without
+				restarting Vector.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
 		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
@@ -285,5 +285,47 @@ components: sources: http_server: {
 				Supported algorithms are `gzip`, `deflate`, and `snappy`.
 				"""
 		}
+		routes: {
+			title: "Routes"
+			body: """
+				A single listener can accept several webhook formats by configuring `routes`. Each
+				route matches its own `path`, decodes request bodies with its own `framing` and
+				`decoding` settings, and sends its events to a dedicated output named after the route,
+				which can be consumed as `<source_id>.<route_name>`:
+
+				```toml
+				[sources.webhooks]
+				type = "http_server"
+				address = "0.0.0.0:8080"
+
+				[[sources.webhooks.routes]]
+				name = "github"
+				path = "/github"
+				decoding.codec = "json"
+
+				[sinks.github_events]
+				type = "console"
+				inputs = ["webhooks.github"]
+				encoding.codec = "json"
+				```
+
+				Requests that don't match any route are handled by the top-level `path` and sent to
+				the default output, or rejected with `404 Not Found`.
+				"""
+		}
+		signatures: {
+			title: "Webhook signatures"
+			body: """
+				The source can verify the signatures that webhook senders attach to their requests,
+				removing the need for a proxy in front of Vector. GitHub (`X-Hub-Signature-256`),
+				Stripe (`Stripe-Signature`) and Slack (`X-Slack-Signature`) signatures are supported
+				out of the box, and the `generic` provider covers HMAC signatures sent in any header.
+
+				By default requests with a missing or invalid signature are rejected with
+				`401 Unauthorized`. Setting `signature.on_failure` to `tag` accepts them instead, and
+				records the outcome of the verification in the `signature_verified` field of each
+				event. Signatures can be configured for the top-level `path` and for each route.
+				"""
+		}
 	}
 }
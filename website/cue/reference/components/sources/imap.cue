@@ -0,0 +1,184 @@
+package metadata
+
+components: sources: imap: {
+	title: "IMAP"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.imap
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				Connections use implicit TLS when `tls.enabled` is set, usually on port 993.
+				`STARTTLS` isn't supported.
+				""",
+		]
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.imap.configuration
+
+	output: logs: message: {
+		description: "An email message read from the mailbox"
+		fields: {
+			attachments: {
+				description: "The file name, content type, and size in bytes of the attachments of the message. Their content isn't included."
+				required:    true
+				type: array: items: type: object: {
+					examples: [{"filename": "report.csv", "content_type": "text/csv", "size": 1024}]
+				}
+			}
+			cc: {
+				description: "The `Cc` header of the message."
+				required:    false
+				type: string: {
+					examples: ["security@example.com"]
+				}
+			}
+			date: {
+				description: "The time in the `Date` header of the message."
+				required:    false
+				type: timestamp: {}
+			}
+			from: {
+				description: "The `From` header of the message."
+				required:    false
+				type: string: {
+					examples: ["Alerts <alerts@example.com>"]
+				}
+			}
+			headers: {
+				description: "All the headers of the message, keyed by their lowercased name. Headers appearing more than once are arrays of their values."
+				required:    true
+				type: object: {
+					examples: [{"received": ["from mx1.example.com", "from mx2.example.com"], "subject": "Disk usage above 90%"}]
+				}
+			}
+			html: {
+				description: "The `text/html` body of the message, if any."
+				required:    false
+				type: string: {
+					examples: ["<p>Disk usage on db-1 is 93%.</p>"]
+				}
+			}
+			mailbox: {
+				description: "The mailbox the message was read from."
+				required:    true
+				type: string: {
+					examples: ["INBOX"]
+				}
+			}
+			message: {
+				description: "The `text/plain` body of the message, or its `text/html` body if it has no plain text one."
+				required:    true
+				type: string: {
+					examples: ["Disk usage on db-1 is 93%."]
+				}
+			}
+			message_id: {
+				description: "The `Message-ID` header of the message."
+				required:    false
+				type: string: {
+					examples: ["<20230110123000.1234@example.com>"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["imap"]
+				}
+			}
+			subject: {
+				description: "The `Subject` header of the message, decoded."
+				required:    false
+				type: string: {
+					examples: ["Disk usage above 90%"]
+				}
+			}
+			timestamp: fields._current_timestamp
+			to: {
+				description: "The `To` header of the message."
+				required:    false
+				type: string: {
+					examples: ["ops@example.com"]
+				}
+			}
+			uid: {
+				description: "The unique identifier of the message in the mailbox."
+				required:    true
+				type: uint: {
+					examples: [4823]
+					unit: null
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		polling: {
+			title: "Polling"
+			body: """
+				Every `poll_interval_secs`, Vector logs in to the [IMAP](\(urls.imap)) server,
+				selects the `mailbox`, and [searches](\(urls.imap_search)) for the messages
+				matching `search`. Each message is fetched without setting its `\\Seen` flag, and
+				parsed into an event with its headers, bodies, and the metadata of its attachments.
+				"""
+		}
+		after_read: {
+			title: "After Reading Messages"
+			body: """
+				Once the event of a message has been sent downstream or, when acknowledgements are
+				enabled, once it has been delivered, the `after_read` action is applied: the
+				message is marked as seen, moved to another mailbox, or deleted. If the event isn't
+				delivered, the message is read again on the next poll. The `search` criteria must
+				exclude the messages already processed, which the default `UNSEEN` does for every
+				action.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
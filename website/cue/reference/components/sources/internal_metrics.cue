@@ -132,6 +132,34 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		circuit_breaker_open: {
+			description:       "Whether the circuit breaker of the sink's requests is open or half-open, failing requests without sending them."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		circuit_breaker_rejected_requests_total: {
+			description:       "The number of requests failed by the circuit breaker of the sink without being sent."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		circuit_breaker_state_changes_total: {
+			description:       "The number of times the circuit breaker of the sink's requests changed state."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				state: {
+					description: "The state the circuit breaker changed to."
+					required:    true
+					enum: {
+						closed:    "Requests are sent."
+						half_open: "A single request is sent, to probe whether the service recovered."
+						open:      "Requests fail without being sent."
+					}
+				}
+			}
+		}
 		config_load_errors_total: {
 			description:       "The total number of errors loading the Vector configuration."
 			type:              "counter"
@@ -174,6 +202,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		counter_delta_series: {
+			description:       "The number of series the counter_delta transform holds the previous value of."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		counter_resets_total: {
+			description:       "The number of times the counter_delta transform found counters to have been reset."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		quit_total: {
 			description:       "The total number of times the Vector instance has quit."
 			type:              "counter"
@@ -331,6 +371,24 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		dedupe_cache_events: {
+			description:       "The number of events in the cache of the dedupe transform."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		dedupe_cache_hits_total: {
+			description:       "The number of events the dedupe transform found in its cache, as duplicates."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		dedupe_cache_misses_total: {
+			description:       "The number of events the dedupe transform didn't find in its cache."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		decode_errors_total: {
 			description:       "The total number of decode errors seen when decoding data in a source component."
 			type:              "counter"
@@ -514,6 +572,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		component_discarded_events_by_origin_total: {
+			description:       "The number of events of each origin dropped by this sink, as requests that failed."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & _origin_tags
+		}
+		component_discarded_event_bytes_by_origin_total: {
+			description:       "The number of event bytes of each origin dropped by this sink, as requests that failed."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & _origin_tags
+		}
 		component_errors_total: {
 			description:       "The total number of errors encountered by this component."
 			type:              "counter"
@@ -610,6 +680,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              component_received_events_total.tags
 		}
+		component_request_compression_ratio: {
+			description:       "The ratio of the uncompressed size of the last request completed by this sink to its size on the wire."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & _request_endpoint_tags
+		}
+		component_request_duration_seconds: {
+			description:       "The duration of the requests completed by this sink, whether they succeeded or not."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags & _request_endpoint_tags
+		}
 		component_sent_bytes_total: {
 			description:       "The number of raw bytes sent by this component to destination sinks."
 			type:              "counter"
@@ -645,6 +727,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags & {output: _output}
 		}
+		component_sent_events_by_origin_total: {
+			description:       "The total number of events of each origin sent by this sink."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & _origin_tags
+		}
+		component_sent_event_bytes_by_origin_total: {
+			description:       "The total number of event bytes of each origin sent by this sink."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & _origin_tags
+		}
 		datadog_logs_received_in_total: {
 			description:       "Number of Datadog logs received."
 			type:              "counter"
@@ -851,6 +945,21 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		log_to_metric_cardinality_limit_exceeded_total: {
+			description:       "The number of metrics of new series derived beyond the cardinality limit of their metric."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				action: {
+					description: "What was done with the metric."
+					required:    true
+					enum: {
+						aggregate: "The values of the tags of the metric were replaced with `overflow`."
+						drop:      "The metric was dropped."
+					}
+				}
+			}
+		}
 		logging_driver_errors_total: {
 			description: """
 				The total number of logging driver errors encountered caused by not using either
@@ -890,6 +999,27 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		pii_redaction_detections_total: {
+			description:       "The number of personal data or secrets found by the detectors of the pii_redaction transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				detector: {
+					description: "The detector that found the data."
+					required:    true
+				}
+				action: {
+					description: "The action taken on the data."
+					required:    true
+					enum: {
+						mask:   "The data was masked."
+						hash:   "The data was replaced with its hash."
+						remove: "The field holding the data was removed."
+						tag:    "The log was tagged with the detector."
+					}
+				}
+			}
+		}
 		processed_bytes_total: {
 			description:       "The number of bytes processed by the component."
 			type:              "counter"
@@ -936,6 +1066,39 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		reduce_buffered_bytes: {
+			description:       "The size of the events held by the groups of the `reduce` transform, in bytes."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		reduce_groups_flushed_total: {
+			description:       "The number of groups the `reduce` transform has flushed before their transaction ended."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				reason: {
+					description: "Why the group was flushed."
+					required:    true
+					enum: {
+						group_change: "An event of another group was received."
+						size_limit:   "The events of the group reached `max_bytes`."
+					}
+				}
+			}
+		}
+		remote_lookup_cache_hits_total: {
+			description:       "The number of lookups of the remote_lookup transform whose result was in its cache."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		remote_lookup_cache_misses_total: {
+			description:       "The number of lookups of the remote_lookup transform whose result wasn't in its cache."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		request_errors_total: {
 			description:       "The total number of requests errors for this component."
 			type:              "counter"
@@ -972,6 +1135,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		schema_enforcement_rejected_events_total: {
+			description:       "The number of logs sent to the `rejected` output of the schema_enforcement transform, as they don't conform to its schema."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		send_errors_total: {
 			description:       "The total number of errors sending messages."
 			type:              "counter"
@@ -1072,6 +1241,23 @@ components: sources: internal_metrics: {
 				"""
 			type:              "counter"
 			default_namespace: "vector"
+			tags: _component_tags & {
+				tag_key: {
+					description: "The key of the tag."
+					required:    true
+				}
+			}
+		}
+		tail_sampling_traces_dropped_total: {
+			description:       "The number of traces dropped by the tail_sampling transform, as no policy kept them."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		tail_sampling_traces_kept_total: {
+			description:       "The number of traces kept by the policies of the tail_sampling transform."
+			type:              "counter"
+			default_namespace: "vector"
 			tags:              _component_tags
 		}
 		timestamp_parse_errors_total: {
@@ -1140,7 +1326,12 @@ components: sources: internal_metrics: {
 				"""
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags: _component_tags & {
+				tag_key: {
+					description: "The key of the tag."
+					required:    true
+				}
+			}
 		}
 
 		// Windows metrics
@@ -1202,6 +1393,32 @@ components: sources: internal_metrics: {
 			component_type: _component_type
 		}
 
+		_origin_tags: {
+			source: {
+				description: "The ID of the source the events were received by."
+				required:    false
+				examples: ["my_source"]
+			}
+			service: {
+				description: "The service the events are about, from the field with the `service` meaning of logs and traces, or the `service` tag of metrics."
+				required:    false
+				examples: ["checkout"]
+			}
+			tenant: {
+				description: "The tenant the events belong to, from the `tenant` field of their metadata."
+				required:    false
+				examples: ["acme"]
+			}
+		}
+
+		_request_endpoint_tags: {
+			endpoint: {
+				description: "The endpoint of the destination the requests are sent to. Only set by the `datadog_metrics`, `datadog_traces`, `loki` and `splunk_hec` sinks, and by the `elasticsearch` sink when it has a single endpoint."
+				required:    false
+				examples: ["https://api.datadoghq.com/api/v1/series"]
+			}
+		}
+
 		// All available tags
 		_collector: {
 			description: "Which collector this metric comes from."
@@ -0,0 +1,177 @@
+package metadata
+
+components: sources: mongodb_change_stream: {
+	title: "MongoDB Change Stream"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.mongodb
+
+				interface: {
+					socket: {
+						api: {
+							title: "MongoDB change streams"
+							url:   urls.mongodb_change_streams
+						}
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				Change streams are only available on replica sets and sharded clusters. The user
+				must have the `changeStream` and `find` privileges on the watched collections.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.mongodb_change_stream.configuration
+
+	output: logs: {
+		event: {
+			description: "A change made to a document"
+			fields: {
+				collection: {
+					description: "The collection of the changed document."
+					required:    false
+					type: string: {
+						examples: ["users"]
+					}
+				}
+				database: {
+					description: "The database of the changed document."
+					required:    false
+					type: string: {
+						examples: ["app"]
+					}
+				}
+				document_key: {
+					description: "The `_id` of the changed document, along with the shard key of sharded collections."
+					required:    false
+					type: object: {
+						examples: [{"_id": "63a1b2c3d4e5f60718293a4b"}]
+					}
+				}
+				full_document: {
+					description: "The document after the change, depending on `full_document`."
+					required:    false
+					type: object: {
+						examples: [{"_id": "63a1b2c3d4e5f60718293a4b", "name": "bob"}]
+					}
+				}
+				full_document_before_change: {
+					description: "The document before the change, depending on `full_document_before_change`."
+					required:    false
+					type: object: {
+						examples: [{"_id": "63a1b2c3d4e5f60718293a4b", "name": "alice"}]
+					}
+				}
+				operation_type: {
+					description: "The kind of change."
+					required:    true
+					type: string: {
+						examples: ["insert", "update", "replace", "delete", "drop", "rename", "invalidate"]
+					}
+				}
+				resume_token: {
+					description: "The resume token of the change."
+					required:    true
+					type: object: {
+						examples: [{"_data": "8263A1B2C3000000012B022C0100296E5A1004"}]
+					}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["mongodb_change_stream"]
+					}
+				}
+				timestamp: {
+					description: "The time at which the change was made."
+					required:    true
+					type: timestamp: {}
+				}
+				to: {
+					description: "The new database and collection of a `rename` change."
+					required:    false
+					type: object: {
+						examples: [{"database": "app", "collection": "customers"}]
+					}
+				}
+				update_description: {
+					description: "The fields set and removed by an `update` change."
+					required:    false
+					type: object: {
+						examples: [{"updated_fields": {"name": "bob"}, "removed_fields": ["nickname"]}]
+					}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		change_streams: {
+			title: "Change Streams"
+			body: """
+				Vector watches the deployment, a database, or a single collection with a
+				[change stream](\(urls.mongodb_change_streams)), and emits an event for each change
+				read. BSON values are converted to the matching types where possible: object IDs
+				become their hexadecimal string, and dates become timestamps. Other values are kept
+				in their relaxed extended JSON representation.
+				"""
+		}
+		checkpoints: {
+			title: "Checkpoints"
+			body: """
+				The resume token of the last change of each batch is persisted in the `data_dir`
+				once its changes have been sent downstream or, when acknowledgements are enabled,
+				once they have been delivered. After a restart, watching starts after that token,
+				as long as it's still in the oplog. Without a persisted token, watching starts with
+				the changes made after Vector starts.
+				"""
+		}
+		full_documents: {
+			title: "Full Documents"
+			body: """
+				By default, `update` changes only describe the fields that were updated. Setting
+				`full_document` to `update_lookup` looks up the current version of the document when
+				the change is read. With MongoDB 6.0 and
+				[pre- and post-images](\(urls.mongodb_pre_and_post_images)) enabled on the
+				collections, `full_document` and `full_document_before_change` can instead include
+				the document as it was right after and right before each change.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
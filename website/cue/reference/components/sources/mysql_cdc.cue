@@ -0,0 +1,167 @@
+package metadata
+
+components: sources: mysql_cdc: {
+	title: "MySQL Change Data Capture"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.mysql
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "disabled"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				The server must write its binary log with `binlog_format` set to `ROW` and
+				`binlog_row_image` set to `FULL`.
+				""",
+		]
+		warnings: [
+			"""
+				Column names aren't part of the binary log, and are looked up when the first
+				change of a table is read. Changes made before a schema change that are read after
+				it may be named after the new columns.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.mysql_cdc.configuration
+
+	output: logs: {
+		event: {
+			description: "A change made to a row"
+			fields: {
+				after: {
+					description: "The row after the change. `null` for deletes."
+					required:    true
+					type: object: {
+						examples: [{"id": 1, "name": "bob"}]
+					}
+				}
+				before: {
+					description: "The row before the change. `null` for inserts."
+					required:    true
+					type: object: {
+						examples: [{"id": 1, "name": "alice"}]
+					}
+				}
+				database: {
+					description: "The database of the changed table."
+					required:    true
+					type: string: {
+						examples: ["app"]
+					}
+				}
+				file: {
+					description: "The binary log file of the transaction."
+					required:    true
+					type: string: {
+						examples: ["binlog.000042"]
+					}
+				}
+				gtid: {
+					description: "The global transaction identifier of the transaction, when GTIDs are enabled."
+					required:    false
+					type: string: {
+						examples: ["3e11fa47-71ca-11e1-9e33-c80aa9429562:23"]
+					}
+				}
+				op: {
+					description: "The kind of change."
+					required:    true
+					type: string: {
+						enum: {
+							insert: "A row was inserted."
+							update: "A row was updated."
+							delete: "A row was deleted."
+						}
+					}
+				}
+				position: {
+					description: "The position in the binary log file following the transaction."
+					required:    true
+					type: uint: {
+						examples: [1337]
+						unit: null
+					}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["mysql_cdc"]
+					}
+				}
+				table: {
+					description: "The changed table."
+					required:    true
+					type: string: {
+						examples: ["users"]
+					}
+				}
+				timestamp: {
+					description: "The time at which the change was made."
+					required:    true
+					type: timestamp: {}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		binary_log: {
+			title: "Binary Log"
+			body: """
+				Vector connects to the server as a replica, and streams the row events of its
+				[binary log](\(urls.mysql_binlog)). Each row inserted, updated, or deleted is emitted
+				as an event once its transaction is committed, with the values of its columns
+				converted to the matching types. Without a persisted position, reading starts with
+				the transactions committed after Vector starts.
+				"""
+		}
+		checkpoints: {
+			title: "Checkpoints"
+			body: """
+				The position following each transaction is persisted in the `data_dir` once its
+				changes have been sent downstream or, when acknowledgements are enabled, once they
+				have been delivered. When `gtid` is enabled, the position is the set of
+				[global transaction identifiers](\(urls.mysql_gtids)) read, which stays valid after a
+				failover to another server. Otherwise, it's the name of a binary log file and an
+				offset in it.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
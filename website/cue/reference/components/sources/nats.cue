@@ -4,7 +4,7 @@ components: sources: nats: {
 	title: "NATS"
 
 	features: {
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			from: components._nats.features.collect.from
@@ -48,6 +48,41 @@ components: sources: nats: {
 				examples: ["foo", "API Name Option Example"]
 			}
 		}
+		jetstream: {
+			common:      false
+			description: "Read messages from the JetStream stream bound to `subject` with a durable pull consumer, instead of subscribing to the subject."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					durable_name: {
+						description: "The name of the durable consumer to read messages with. It is created if it doesn't exist yet."
+						required:    true
+						type: string: {
+							examples: ["vector"]
+						}
+					}
+					batch_size: {
+						common:      false
+						description: "The maximum number of messages to fetch at once."
+						required:    false
+						type: uint: {
+							default: 100
+							unit:    null
+						}
+					}
+					fetch_timeout_ms: {
+						common:      false
+						description: "The maximum time to wait for a batch of messages to be fetched."
+						required:    false
+						type: uint: {
+							default: 5000
+							unit:    "milliseconds"
+						}
+					}
+				}
+			}
+		}
 	}
 
 	output: logs: record: {
@@ -81,5 +116,20 @@ components: sources: nats: {
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
 	}
 
-	how_it_works: components._nats.how_it_works
+	how_it_works: components._nats.how_it_works & {
+		jetstream: {
+			title: "JetStream"
+			body: """
+				When [`jetstream`](#jetstream) is set, messages are fetched in batches from the JetStream stream
+				bound to `subject`, with the durable pull consumer named by `jetstream.durable_name`. Several Vector
+				instances using the same consumer share the messages between them.
+
+				Each message is acknowledged once its events have been sent or, when end-to-end acknowledgements are
+				enabled, once they have been delivered by the sinks. Messages whose events fail to be delivered are
+				negatively acknowledged, so that the server redelivers them, while messages whose events are
+				rejected are terminated. Messages that are never acknowledged, for example because Vector stopped,
+				are redelivered by the server once the consumer's acknowledgement wait expires.
+				"""
+		}
+	}
 }
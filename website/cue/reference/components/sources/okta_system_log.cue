@@ -0,0 +1,129 @@
+package metadata
+
+components: sources: okta_system_log: {
+	title: "Okta System Log"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.okta
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.okta_system_log.configuration
+
+	output: logs: event: {
+		description: "An event of the System Log, with all the fields returned by the API."
+		fields: {
+			eventType: {
+				description: "The type of the event."
+				required:    true
+				type: string: {
+					examples: ["user.session.start"]
+				}
+			}
+			published: {
+				description: "The time the event was published."
+				required:    true
+				type: string: {
+					examples: ["2023-01-10T12:30:00.000Z"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["okta_system_log"]
+				}
+			}
+			timestamp: {
+				description: "The time the event was published, from its `published` field."
+				required:    true
+				type: timestamp: {}
+			}
+			uuid: {
+				description: "The unique identifier of the event."
+				required:    true
+				type: string: {
+					examples: ["dc9fd3c0-598c-11ef-8478-2b7584bf8d5a"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		polling: {
+			title: "Polling"
+			body: """
+				Every `poll_interval_secs`, Vector reads the events of the
+				[System Log](\(urls.okta_system_log_api)) oldest first, following the links to the
+				next pages until it reaches an empty one. When `event_types` is set, only the events
+				of those types are read.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				The `after` cursor of the link to the page following the last events read is
+				persisted in the `data_dir` once the events have been sent downstream or, when
+				acknowledgements are enabled, once they have been delivered. Reading resumes from it
+				after a restart, so events are neither lost nor duplicated. When no cursor is
+				persisted, reading starts `backfill_secs` before Vector starts.
+				"""
+		}
+		rate_limits: {
+			title: "Rate Limits"
+			body: """
+				When the [rate limit](\(urls.okta_rate_limits)) of the System Log API is exhausted,
+				as told by the `X-Rate-Limit-Remaining` header of a response, Vector waits until
+				the time in its `X-Rate-Limit-Reset` header before sending the next request.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
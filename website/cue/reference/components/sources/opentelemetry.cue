@@ -40,7 +40,7 @@ components: sources: opentelemetry: {
 		requirements: []
 		warnings: [
 			"""
-				The `opentelemetry` source only supports log events at this time.
+				The `opentelemetry` source doesn't decode the exemplars of metrics at this time.
 				""",
 		]
 		notices: []
@@ -131,6 +131,18 @@ components: sources: opentelemetry: {
 				Received log events will go to this output stream. Use `<component_id>.logs` as an input to downstream transforms and sinks.
 				"""
 		},
+		{
+			name: "metrics"
+			description: """
+				Received metric events will go to this output stream. Use `<component_id>.metrics` as an input to downstream transforms and sinks.
+				"""
+		},
+		{
+			name: "traces"
+			description: """
+				Received trace events will go to this output stream. Use `<component_id>.traces` as an input to downstream transforms and sinks.
+				"""
+		},
 	]
 
 	output: {
@@ -263,6 +275,128 @@ components: sources: opentelemetry: {
 				}
 			}
 		}
+		metrics: {
+			counter:   output._passthrough_counter
+			gauge:     output._passthrough_gauge
+			histogram: output._passthrough_histogram
+			summary:   output._passthrough_summary
+		}
+		traces: {
+			description: "An individual span from a batch of spans received through an OTLP request"
+			fields: {
+				attributes: {
+					description: "Attributes that describe the operation of the span."
+					required:    false
+					type: object: {
+						examples: [{"http.method": "GET"}]
+					}
+				}
+				dropped_attributes_count: {
+					description: "Counts for attributes dropped due to collection limits."
+					required:    true
+					type: uint: unit: null
+				}
+				dropped_events_count: {
+					description: "Counts for events dropped due to collection limits."
+					required:    true
+					type: uint: unit: null
+				}
+				dropped_links_count: {
+					description: "Counts for links dropped due to collection limits."
+					required:    true
+					type: uint: unit: null
+				}
+				end_time_unix_nano: {
+					description: "The UTC Datetime when the span ended."
+					required:    true
+					type: timestamp: {}
+				}
+				events: {
+					description: "The time-stamped annotations of the span, with their `name`, `time_unix_nano`, `attributes`, and `dropped_attributes_count`."
+					required:    false
+					type: array: items: type: object: options: {}
+				}
+				ingest_timestamp: {
+					description: "The UTC Datetime when the span was received by Vector."
+					required:    true
+					type: timestamp: {}
+				}
+				kind: {
+					description: "The kind of span, as the `SpanKind` Protobuf enum value."
+					required:    true
+					type: uint: {
+						examples: [2]
+						unit: null
+					}
+				}
+				links: {
+					description: "The links to other spans, with their `trace_id`, `span_id`, `trace_state`, `attributes`, and `dropped_attributes_count`."
+					required:    false
+					type: array: items: type: object: options: {}
+				}
+				name: {
+					description: "A description of the operation of the span."
+					required:    true
+					type: string: {
+						examples: ["GET /users"]
+					}
+				}
+				parent_span_id: {
+					description: "The hexadecimal span ID of the parent span, for spans that aren't root spans."
+					required:    false
+					type: string: {
+						examples: ["b5d2d4a9c2b1e3f7"]
+					}
+				}
+				resources: {
+					description: "Set of attributes that describe the resource."
+					required:    false
+					type: object: {
+						examples: [{"service.name": "checkout"}]
+					}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["opentelemetry"]
+					}
+				}
+				span_id: {
+					description: "The hexadecimal span ID."
+					required:    true
+					type: string: {
+						examples: ["0b9e4bda2a55530d"]
+					}
+				}
+				start_time_unix_nano: {
+					description: "The UTC Datetime when the span started."
+					required:    true
+					type: timestamp: {}
+				}
+				status: {
+					description: "The final status of the span, with its `message` and `code`."
+					required:    false
+					type: object: {
+						examples: [{"message": "", "code": 1}]
+					}
+				}
+				trace_id: {
+					description: "The hexadecimal trace ID."
+					required:    true
+					type: string: {
+						examples: ["4ac52aadf321c2e531db005df08792f5"]
+					}
+				}
+				trace_state: {
+					description: "The W3C trace context `tracestate` of the span."
+					required:    false
+					type: string: {
+						examples: ["vendor=value"]
+					}
+				}
+			}
+		}
 	}
 
 	telemetry: metrics: {
@@ -275,6 +409,24 @@ components: sources: opentelemetry: {
 	}
 
 	how_it_works: {
+		metrics: {
+			title: "Metrics"
+			body: """
+				Each data point of the received metrics is emitted as a metric event, tagged with its
+				attributes and with the attributes of its resource, prefixed with `resource.`:
+
+				* Gauges and non-monotonic sums become gauges, and monotonic sums become counters.
+				* Histograms and exponential histograms become aggregated histograms. The bucket
+				  bounds of exponential histograms are computed from their scale, with the
+				  zero bucket having an upper limit of `0`.
+				* Summaries become aggregated summaries.
+
+				Data points with a delta aggregation temporality are emitted as incremental metrics,
+				covering the interval since their start time, and those with a cumulative
+				aggregation temporality as absolute metrics. Data points flagged as having no
+				recorded value are dropped.
+				"""
+		}
 		tls: {
 			title: "Transport Layer Security (TLS)"
 			body:  """
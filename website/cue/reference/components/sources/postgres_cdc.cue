@@ -0,0 +1,174 @@
+package metadata
+
+components: sources: postgres_cdc: {
+	title: "PostgreSQL Change Data Capture"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.postgresql
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp", "unix"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				The server must have `wal_level` set to `logical`, and the user Vector connects as
+				must have the `REPLICATION` attribute.
+				""",
+		]
+		warnings: [
+			"""
+				A replication slot retains the write-ahead log until the changes have been read
+				from it. While Vector is stopped, or can't deliver the changes it reads, the
+				write-ahead log keeps growing on the server.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.postgres_cdc.configuration
+
+	output: logs: {
+		event: {
+			description: "A change made to a row"
+			fields: {
+				after: {
+					description: "The row after the change. `null` for deletes and truncates."
+					required:    true
+					type: object: {
+						examples: [{"id": 1, "name": "bob"}]
+					}
+				}
+				before: {
+					description: """
+						The row before the change, for updates and deletes. Only the columns of the
+						replica identity of the table are available, unless it's set to `FULL`, and
+						`null` when no column of the replica identity changed.
+						"""
+					required: true
+					type: object: {
+						examples: [{"id": 1}]
+					}
+				}
+				lsn: {
+					description: "The position in the write-ahead log of the commit of the transaction."
+					required:    true
+					type: string: {
+						examples: ["16/B374D848"]
+					}
+				}
+				op: {
+					description: "The kind of change."
+					required:    true
+					type: string: {
+						enum: {
+							insert:   "A row was inserted."
+							update:   "A row was updated."
+							delete:   "A row was deleted."
+							truncate: "The table was truncated."
+						}
+					}
+				}
+				schema: {
+					description: "The schema of the changed table."
+					required:    true
+					type: string: {
+						examples: ["public"]
+					}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["postgres_cdc"]
+					}
+				}
+				table: {
+					description: "The changed table."
+					required:    true
+					type: string: {
+						examples: ["users"]
+					}
+				}
+				timestamp: {
+					description: "The time at which the transaction was committed."
+					required:    true
+					type: timestamp: {}
+				}
+				xid: {
+					description: "The ID of the transaction."
+					required:    false
+					type: uint: {
+						examples: [731]
+						unit: null
+					}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		replication_slots: {
+			title: "Replication Slots"
+			body: """
+				Vector reads the changes made to the database from a [logical replication
+				slot](\(urls.postgresql_logical_decoding)), which it creates on startup if it doesn't
+				exist yet. Each transaction is read once it's committed, and every row it changed is
+				emitted as an event, along with the transaction ID and commit timestamp.
+				"""
+		}
+		checkpoints: {
+			title: "Checkpoints"
+			body: """
+				Changes are only peeked at when they are read, and the replication slot is advanced
+				past them once they have been sent downstream or, when acknowledgements are
+				enabled, once they have been delivered. The position of the slot is kept by the
+				server, so reading resumes from there after a restart, and changes whose delivery
+				failed are read again. Changes read just before a crash may be emitted twice.
+				"""
+		}
+		plugins: {
+			title: "Output Plugins"
+			body: """
+				The `pgoutput` plugin is built into PostgreSQL 10 and later, and only decodes the
+				changes of the tables in the configured [publications](\(urls.postgresql_publication)).
+				The values of booleans, integers, and floats are converted to the matching types,
+				and the others are kept in their text representation. Alternatively, the
+				[`wal2json`](\(urls.wal2json)) plugin decodes the changes of all tables, but needs to
+				be installed on the server.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
@@ -81,6 +81,18 @@ components: sources: prometheus_remote_write: {
 				are emitted as gauges.
 				"""
 		}
+		exemplars_and_metadata: {
+			title: "Exemplars and metadata"
+			body: """
+				Exemplars sent along with the samples of a series are attached
+				to the metadata of the matching metric event, under
+				`prometheus_remote_write.exemplars`, as objects with `labels`,
+				`value`, and `timestamp` fields. The exemplars of the buckets of
+				a histogram are attached to the histogram. The help text and
+				unit of the metric families are attached under
+				`prometheus_remote_write.help` and `prometheus_remote_write.unit`.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
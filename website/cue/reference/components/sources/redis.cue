@@ -4,7 +4,7 @@ components: sources: redis: {
 	title: "Redis"
 
 	features: {
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			tls: enabled:        false
@@ -79,13 +79,14 @@ components: sources: redis: {
 		}
 		data_type: {
 			common:      false
-			description: "The Redis data type (`list` or `channel`) to use."
+			description: "The Redis data type (`list`, `channel` or `stream`) to use."
 			required:    false
 			type: string: {
 				default: "list"
 				enum: {
 					list:    "Use the Redis `list` data type."
 					channel: "Use the Redis `channel` data type."
+					stream:  "Use the Redis `stream` data type, read with a consumer group."
 				}
 				syntax: "literal"
 			}
@@ -114,6 +115,82 @@ components: sources: redis: {
 				}
 			}
 		}
+		stream: {
+			common:      false
+			description: "Options for the Redis `stream` data type."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					group: {
+						common:      false
+						description: "The name of the consumer group to read messages with. It is created, along with the stream, if it doesn't exist yet."
+						required:    false
+						type: string: {
+							default: "vector"
+							syntax:  "literal"
+						}
+					}
+					consumer: {
+						common:      false
+						description: "The name of the consumer within the consumer group. Defaults to the hostname."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["vector-1"]
+							syntax: "literal"
+						}
+					}
+					start_id: {
+						common:      false
+						description: "The ID of the first message to read when the consumer group is created. Use `0` to read the whole stream."
+						required:    false
+						type: string: {
+							default: "$"
+							syntax:  "literal"
+						}
+					}
+					field: {
+						common:      false
+						description: "The name of the message field to decode events from."
+						required:    false
+						type: string: {
+							default: "message"
+							syntax:  "literal"
+						}
+					}
+					id_key: {
+						common:      false
+						description: "The log field name to use for the message ID. If not set, the ID is not added to the log event."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["redis_id"]
+							syntax: "literal"
+						}
+					}
+					batch_size: {
+						common:      false
+						description: "The maximum number of messages to read at once."
+						required:    false
+						type: uint: {
+							default: 100
+							unit:    null
+						}
+					}
+					claim_min_idle_ms: {
+						common:      false
+						description: "The minimum time a message must have been pending for before it is claimed and read again. Set to `0` to never claim pending messages."
+						required:    false
+						type: uint: {
+							default: 60000
+							unit:    "milliseconds"
+						}
+					}
+				}
+			}
+		}
 		redis_key: {
 			common:      false
 			description: "The log field name to use for the redis key. If set to an empty string or null, the key is not added to the log event."
@@ -153,6 +230,20 @@ components: sources: redis: {
 	}
 
 	how_it_works: {
+		streams: {
+			title: "Streams"
+			body: """
+				With the `stream` data type, messages are read from a Redis stream with `XREADGROUP`, as a consumer of
+				the [`stream.group`](#stream.group) consumer group, which is created if it doesn't exist yet. Several
+				Vector instances can share the work of reading a stream by using the same group with distinct
+				[`stream.consumer`](#stream.consumer) names.
+
+				Messages are acknowledged with `XACK` once their events have been sent, or, when end-to-end
+				acknowledgements are enabled, once their events have been delivered by the sinks. Messages that stay
+				pending for longer than [`stream.claim_min_idle_ms`](#stream.claim_min_idle_ms), for example because a
+				consumer stopped before acknowledging them, are claimed with `XCLAIM` and read again.
+				"""
+		}
 		redis_rs: {
 			title: "redis-rs"
 			body:  """
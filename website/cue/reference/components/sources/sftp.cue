@@ -0,0 +1,134 @@
+package metadata
+
+components: sources: sftp: {
+	title: "SFTP"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		codecs: {
+			enabled:         true
+			default_framing: "`newline_delimited` for codecs other than `native`, which defaults to `length_delimited`"
+		}
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.sftp
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "disabled"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				Without a `known_hosts_file`, the host key of the server isn't verified, and Vector
+				may authenticate with a server impersonating it.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.sftp.configuration
+
+	output: logs: line: {
+		description: "An event decoded from a file downloaded from the server."
+		fields: {
+			host: {
+				description: "The host of the SFTP server the file was downloaded from."
+				required:    true
+				type: string: {
+					examples: ["sftp.example.com"]
+				}
+			}
+			message: {
+				description: "The content decoded from the file, one event per frame."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			path: {
+				description: "The path of the file on the server."
+				required:    true
+				type: string: {
+					examples: ["/outgoing/access.log"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["sftp"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		polling: {
+			title: "Polling"
+			body: """
+				Every `poll_interval_secs`, Vector connects to the [SFTP](\(urls.sftp)) server and
+				lists the directory of each `include` pattern. The files whose name matches the
+				pattern, and which were last modified at least `min_file_age_secs` ago, are
+				downloaded one at a time and decoded into events. A file isn't downloaded again
+				while its size and modification time stay the same.
+				"""
+		}
+		after_read: {
+			title: "After Reading Files"
+			body: """
+				Once the events of a file have been sent downstream or, when acknowledgements are
+				enabled, once they have been delivered, the `after_read` action is applied: the file
+				is kept, deleted, or renamed with a suffix appended to its name. If its events
+				aren't delivered, the file is downloaded again on the next poll.
+				"""
+		}
+		state: {
+			title: "State"
+			body: """
+				The size and modification time of the files read are persisted in the `data_dir`,
+				so files aren't read again after Vector restarts. Files are forgotten once they're
+				no longer listed on the server.
+				"""
+		}
+		host_key_verification: {
+			title: "Host Key Verification"
+			body: """
+				When `known_hosts_file` is set, the host key of the server must match one of its
+				entries, in the [OpenSSH format](\(urls.ssh_known_hosts)), or the connection is
+				aborted.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
@@ -112,11 +112,150 @@ components: sources: statsd: {
 		set:          output._passthrough_set
 	}
 
+	output: logs: {
+		event: {
+			description: "A DogStatsD event."
+			fields: {
+				aggregation_key: {
+					description: "The key used to group the event with others."
+					required:    false
+					type: string: {
+						examples: ["deploy-web"]
+					}
+				}
+				alert_type: {
+					description: "The alert type of the event."
+					required:    false
+					type: string: {
+						examples: ["error", "warning", "info", "success"]
+					}
+				}
+				container_id: _container_id
+				host: {
+					description: "The host the event relates to."
+					required:    false
+					type: string: {
+						examples: ["web-1"]
+					}
+				}
+				message: {
+					description: "The text of the event, with its escaped newlines restored."
+					required:    true
+					type: string: {
+						examples: ["The deploy finished."]
+					}
+				}
+				priority: {
+					description: "The priority of the event."
+					required:    false
+					type: string: {
+						examples: ["normal", "low"]
+					}
+				}
+				source_type: _source_type
+				source_type_name: {
+					description: "The source type name of the event."
+					required:    false
+					type: string: {
+						examples: ["jenkins"]
+					}
+				}
+				tags:      _tags
+				timestamp: _timestamp
+				title: {
+					description: "The title of the event."
+					required:    true
+					type: string: {
+						examples: ["Deploy"]
+					}
+				}
+			}
+		}
+		service_check: {
+			description: "A DogStatsD service check."
+			fields: {
+				container_id: _container_id
+				host: {
+					description: "The host the service check relates to."
+					required:    false
+					type: string: {
+						examples: ["db-1"]
+					}
+				}
+				message: {
+					description: "The message describing the state of the service check."
+					required:    false
+					type: string: {
+						examples: ["Connection refused"]
+					}
+				}
+				name: {
+					description: "The name of the service check."
+					required:    true
+					type: string: {
+						examples: ["redis.can_connect"]
+					}
+				}
+				source_type: _source_type
+				status: {
+					description: "The status of the service check, from `0` (OK) to `3` (unknown)."
+					required:    true
+					type: uint: {
+						examples: [0, 2]
+						unit: null
+					}
+				}
+				tags:      _tags
+				timestamp: _timestamp
+			}
+		}
+	}
+
+	_container_id: {
+		description: "The ID of the container the packet was sent from."
+		required:    false
+		type: string: {
+			examples: ["83c0a99c0a54"]
+		}
+	}
+
+	_source_type: {
+		description: "The name of the source type."
+		required:    true
+		type: string: {
+			examples: ["statsd"]
+		}
+	}
+
+	_tags: {
+		description: "The tags of the packet, tags without a value being set to `true`."
+		required:    false
+		type: object: {
+			examples: [{"env": "prod"}]
+		}
+	}
+
+	_timestamp: {
+		description: "The time of the packet, or the time it was received when it doesn't have one."
+		required:    true
+		type: timestamp: {}
+	}
+
 	how_it_works: {
+		dogstatsd: {
+			title: "DogStatsD"
+			body: """
+				The [DogStatsD](\(urls.dogstatsd)) extensions of the StatsD protocol are supported:
+				several values can be packed in a single metric packet, the `c:` container ID of
+				packets is added as the `container_id` tag, and their `T` timestamp is used as the
+				timestamp of the metric. DogStatsD events and service checks are emitted as log
+				events. Unknown packet fields are ignored.
+				"""
+		}
 		timestamps: {
 			title: "Timestamps"
 			body:  """
-				The StatsD protocol doesn't provide support for sending metric timestamps. You may
+				Apart from the DogStatsD extensions, the StatsD protocol doesn't provide support for sending metric timestamps. You may
 				notice that each parsed metric is assigned a `null` timestamp, which is a special
 				value indicating a realtime metric (i.e. not a historical metric). Normally, such
 				`null` timestamps are substituted with the current time by downstream sinks or
@@ -239,10 +239,23 @@ components: sources: syslog: {
 	]
 
 	how_it_works: {
+		client_certificates: {
+			title: "Client Certificates"
+			body: """
+				When receiving syslog over TLS, as described in [RFC 5425](\(urls.syslog_5425)), the
+				subject, common name, and subject alternative names of client certificates are added to
+				the TLS client metadata of events. Setting `client_certificate_tenants` restricts the
+				clients allowed to connect to those whose certificate identity is mapped to a tenant,
+				and adds that tenant to the metadata, to route events per tenant.
+				"""
+		}
+
 		line_delimiters: {
 			title: "Line Delimiters"
 			body: """
-				Each line is read until a new line delimiter, the `0xA` byte, is found.
+				Each line is read until a new line delimiter, the `0xA` byte, is found, unless
+				it's prefixed with its length, as in the octet-counting framing of
+				[RFC 6587](\(urls.syslog_6587)).
 				"""
 		}
 
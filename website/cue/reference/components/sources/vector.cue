@@ -122,6 +122,27 @@ components: sources: vector: {
 		}
 	}
 
+	how_it_works: {
+		compression: {
+			title: "Compression"
+			body: """
+				The `vector` source accepts requests compressed with gzip or Zstandard, as sent by the `vector` sink
+				depending on its `compression` option. Requests compressed with any other scheme are rejected.
+				"""
+		}
+		rate_limiting: {
+			title: "Rate limiting"
+			body: """
+				The `rate_limit` option limits the number of requests each client can send per time window, protecting
+				the aggregator from misbehaving agents. Clients are identified by the subject of the TLS certificate they
+				presented, when `tls.verify_certificate` is enabled, and by their IP address otherwise.
+
+				Requests exceeding the limit are rejected with the `RESOURCE_EXHAUSTED` gRPC status, which the `vector`
+				sink retries with backoff.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
 		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
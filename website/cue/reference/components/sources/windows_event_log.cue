@@ -0,0 +1,249 @@
+package metadata
+
+components: sources: windows_event_log: {
+	title: "Windows Event Log"
+
+	classes: {
+		commonly_used: true
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: true
+			from: service: services.windows_event_log
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      false
+			"aarch64-unknown-linux-musl":     false
+			"armv7-unknown-linux-gnueabihf":  false
+			"armv7-unknown-linux-musleabihf": false
+			"x86_64-apple-darwin":            false
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       false
+			"x86_64-unknown-linux-musl":      false
+		}
+
+		requirements: [
+			"""
+				Reading the `Security` channel requires Vector to run as an account with the
+				"Manage auditing and security log" privilege, such as `LocalSystem`.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.windows_event_log.configuration
+
+	output: logs: {
+		event: {
+			description: "A Windows event"
+			fields: {
+				channel: {
+					description: "The channel the event was read from."
+					required:    true
+					type: string: {
+						examples: ["System", "Microsoft-Windows-Sysmon/Operational"]
+					}
+				}
+				event_data: {
+					description: """
+						The `EventData` properties of the event. Unnamed properties are named
+						`param1`, `param2`, and so on, in the order they appear in.
+						"""
+					required: false
+					type: object: {
+						examples: [{"TargetUserName": "alice", "LogonType": "2"}]
+					}
+				}
+				event_id: {
+					description: "The identifier of the event, specific to its provider."
+					required:    true
+					type: uint: {
+						examples: [4624, 7036]
+						unit: null
+					}
+				}
+				host: {
+					description: "The name of the computer that logged the event."
+					required:    true
+					type: string: {
+						examples: ["web-01.example.com"]
+					}
+				}
+				keywords: {
+					description: "The keywords of the event, as a hexadecimal bitmask."
+					required:    false
+					type: string: {
+						examples: ["0x8020000000000000"]
+					}
+				}
+				level: {
+					description: "The severity level of the event."
+					required:    false
+					type: string: {
+						enum: {
+							critical:    "A critical error."
+							error:       "An error."
+							warning:     "A warning."
+							information: "An informational event."
+							verbose:     "A verbose event."
+						}
+					}
+				}
+				message: {
+					description: """
+						The message of the event, rendered using the message resources of its provider.
+						Only present when `render_message` is enabled and the message resources are
+						installed on the host.
+						"""
+					required: false
+					type: string: {
+						examples: ["The Windows Update service entered the running state."]
+					}
+				}
+				opcode: {
+					description: "The opcode of the event."
+					required:    false
+					type: uint: {
+						examples: [0]
+						unit: null
+					}
+				}
+				process_id: {
+					description: "The ID of the process that logged the event."
+					required:    false
+					type: uint: {
+						examples: [748]
+						unit: null
+					}
+				}
+				provider_guid: {
+					description: "The GUID of the provider that published the event."
+					required:    false
+					type: string: {
+						examples: ["{54849625-5478-4994-a5ba-3e3b0328c30d}"]
+					}
+				}
+				provider_name: {
+					description: "The name of the provider that published the event."
+					required:    true
+					type: string: {
+						examples: ["Microsoft-Windows-Security-Auditing", "Service Control Manager"]
+					}
+				}
+				record_id: {
+					description: "The number of the event record in the channel."
+					required:    true
+					type: uint: {
+						examples: [184021]
+						unit: null
+					}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["windows_event_log"]
+					}
+				}
+				task: {
+					description: "The task category of the event."
+					required:    false
+					type: uint: {
+						examples: [12544]
+						unit: null
+					}
+				}
+				thread_id: {
+					description: "The ID of the thread that logged the event."
+					required:    false
+					type: uint: {
+						examples: [5272]
+						unit: null
+					}
+				}
+				timestamp: {
+					description: "The time at which the event was logged."
+					required:    true
+					type: timestamp: {}
+				}
+				user_data: {
+					description: "The `UserData` of the event, with its elements converted to objects."
+					required:    false
+					type: object: {
+						examples: [{"SubjectUserName": "bob", "SubjectDomainName": "EXAMPLE"}]
+					}
+				}
+				user_id: {
+					description: "The security identifier of the user the event was logged as."
+					required:    false
+					type: string: {
+						examples: ["S-1-5-18"]
+					}
+				}
+				version: {
+					description: "The version of the event definition."
+					required:    false
+					type: uint: {
+						examples: [2]
+						unit: null
+					}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		bookmarks: {
+			title: "Bookmarks"
+			body: """
+				Vector subscribes to each channel through the Windows Event Log API, and persists a
+				bookmark of the last event read from each of them in the `data_dir` once that event has
+				been sent downstream. When Vector restarts, reading resumes right after the
+				bookmarked events, so no events are lost or read twice. Without a bookmark, only the
+				events published from then on are read, unless `read_existing_events` is enabled.
+				"""
+		}
+		queries: {
+			title: "Event Queries"
+			body: """
+				The `query` option selects the events to read from each channel with an XPath
+				expression, in the same form as the custom views of the Event Viewer. The Windows
+				Event Log only supports a [subset of XPath 1.0](\(urls.windows_event_log_xpath)),
+				evaluated before the events are rendered, which is much cheaper than reading all
+				events and filtering them in Vector.
+				"""
+		}
+		messages: {
+			title: "Rendering Messages"
+			body: """
+				Events only store the values of their properties, with the message templates
+				living in the resources of the providers that published them. When `render_message`
+				is enabled, Vector loads those resources to render the message of each event, as
+				displayed by the Event Viewer. Messages can't be rendered for providers whose
+				resources aren't installed on the host, in which case the event has no message.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
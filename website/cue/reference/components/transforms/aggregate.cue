@@ -5,7 +5,8 @@ components: transforms: aggregate: {
 
 	description: """
 		Aggregates multiple metric events into a single metric event based
-		on a defined interval window. This helps to reduce metric volume at
+		on a defined interval window, or log events into a log event per group
+		holding aggregations of their fields. This helps to reduce volume at
 		the cost of granularity.
 		"""
 
@@ -29,7 +30,7 @@ components: transforms: aggregate: {
 	configuration: base.components.transforms.aggregate.configuration
 
 	input: {
-		logs: false
+		logs: true
 		metrics: {
 			counter:      true
 			distribution: true
@@ -184,6 +185,46 @@ components: transforms: aggregate: {
 				"""
 		}
 
+		log_aggregation: {
+			title: "Log Aggregation"
+			body: """
+				When `aggregations` are set, the transform aggregates log events instead of metrics.
+				Log events are grouped by the values their `group_by` templates render, and at the end
+				of each interval a single log event is emitted per group, holding the rendered `group_by`
+				fields, the fields of the aggregations, and the time of the flush as its timestamp. Log
+				events whose `group_by` templates fail to render are dropped.
+
+				The `sum`, `min`, and `max` functions keep integers as integers as long as all values
+				of the group are integers, while `mean` and `p95` always emit floats. Values that
+				aren't numbers are ignored by all functions but `count`, `last`, and `distinct_count`.
+				The `p95` function takes the nearest-rank percentile, and so keeps all values of groups
+				in memory until they slide out of their window.
+
+				The `rate` function divides the sum of the values, or the number of events without a
+				field, by the length of the window in seconds, which is shorter until the transform has
+				been running for a whole window. The `distinct_count` function estimates the number of
+				distinct values with a HyperLogLog sketch of 4 KiB per interval of each group, with a
+				standard error of about 1.6%.
+				"""
+		}
+
+		sliding_windows: {
+			title: "Sliding Windows"
+			body: """
+				By default, log events are aggregated over windows as long as `interval_ms`, which
+				don't overlap. When `window_ms` is a larger multiple of `interval_ms`, windows slide by
+				the interval, and at the end of each interval a log event is emitted per group with
+				events in the window ending then, holding the aggregations of all events of that window.
+				For example, with an `interval_ms` of `10000` and a `window_ms` of `60000`, the count of
+				requests per minute is emitted every 10 seconds.
+
+				The aggregations of each interval of the window are kept separately and merged when
+				flushing, so memory grows with the number of intervals in windows. The metadata of log
+				events, such as their acknowledgements, goes along with the first log event they're
+				aggregated into.
+				"""
+		}
+
 	}
 
 	telemetry: metrics: {
@@ -1,11 +1,88 @@
 package metadata
 
-base: components: transforms: aggregate: configuration: interval_ms: {
-	description: """
-		The interval between flushes, in milliseconds.
-
-		Over this period metrics with the same series data (name, namespace, tags, …) will be aggregated.
-		"""
-	required: false
-	type: uint: default: 10000
+base: components: transforms: aggregate: configuration: {
+	aggregations: {
+		description: """
+			The fields of the events emitted for each group, and the aggregations computing them.
+
+			When set, log events are aggregated instead of metrics, and one log event is emitted per
+			group with events in the window ending at each interval, holding the fields of `group_by`
+			and of the aggregations.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "An aggregation computing the field."
+			required:    true
+			type: object: options: {
+				field: {
+					description: """
+						The field whose values are aggregated.
+
+						Required by all functions but `count` and `rate`, which count all events of groups without
+						it. Values that aren't numbers are ignored by all functions but `count`, `last`, and
+						`distinct_count`.
+						"""
+					required: false
+					type: string: examples: ["duration_ms"]
+				}
+				function: {
+					description: "A function aggregating the values of a field over the events of a group."
+					required:    true
+					type: string: enum: {
+						count: "The number of events with the field, or of all events without a field."
+						distinct_count: """
+							The estimated number of distinct values.
+
+							Values are counted with a HyperLogLog sketch, whose estimates have a standard error of
+							about 1.6%.
+							"""
+						last: "The value of the last event."
+						max:  "The largest value."
+						mean: "The mean of the values."
+						min:  "The smallest value."
+						p95:  "The 95th percentile of the values."
+						rate: """
+							The sum of the values per second over the window, or the number of events per second
+							without a field.
+							"""
+						sum: "The sum of the values."
+					}
+				}
+			}
+		}
+	}
+	group_by: {
+		description: """
+			The fields of the events emitted for each group, and the templates rendering them.
+
+			Log events are grouped by the values these templates render, and log events whose templates
+			fail to render are dropped. Without any, all log events make up a single group.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "A template rendering the field."
+			required:    true
+			type: string: syntax: "template"
+		}
+	}
+	interval_ms: {
+		description: """
+			The interval between flushes, in milliseconds.
+
+			Over this period metrics with the same series data (name, namespace, tags, …) will be aggregated.
+			"""
+		required: false
+		type: uint: default: 10000
+	}
+	window_ms: {
+		description: """
+			The length of the windows log events are aggregated over, in milliseconds.
+
+			It must be a multiple of `interval_ms`. Windows longer than the interval slide by it, so
+			that log events are aggregated into the events emitted for each window they fall in. By
+			default, windows are as long as the interval, so that they don't overlap.
+			"""
+		required: false
+		type: uint: examples: [60000]
+	}
 }
@@ -0,0 +1,25 @@
+package metadata
+
+base: components: transforms: counter_delta: configuration: {
+	mode: {
+		description: "What absolute counters are converted into."
+		required:    false
+		type: string: {
+			default: "delta"
+			enum: {
+				delta: "Incremental counters of the increase since the previous value of the series."
+				rate:  "Absolute gauges of the increase per second since the previous value of the series."
+			}
+		}
+	}
+	stale_after_secs: {
+		description: """
+			The time, in seconds, after which series that haven't been seen are forgotten.
+
+			The next value of forgotten series is taken as a new reference, like the first value of
+			any series, rather than converted from a value that may predate a restart of their source.
+			"""
+		required: false
+		type: uint: default: 300
+	}
+}
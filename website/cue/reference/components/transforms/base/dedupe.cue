@@ -4,12 +4,50 @@ base: components: transforms: dedupe: configuration: {
 	cache: {
 		description: "Caching configuration for deduplication."
 		required:    false
-		type: object: options: num_events: {
-			description: "Number of events to cache and use for comparing incoming events to previously seen events."
-			required:    false
-			type: uint: default: 5000
+		type: object: options: {
+			eviction: {
+				description: "The policy evicting events from the cache once it's full."
+				required:    false
+				type: string: {
+					default: "lru"
+					enum: {
+						fifo: "Evicts the event that was first cached, whether it was matched since or not."
+						lru: """
+							Evicts the event that was least recently matched, so that events that keep being
+							duplicated stay in the cache.
+							"""
+					}
+				}
+			}
+			num_events: {
+				description: "Number of events to cache and use for comparing incoming events to previously seen events."
+				required:    false
+				type: uint: default: 5000
+			}
+			ttl_secs: {
+				description: """
+					The time window, in seconds, events are deduplicated within.
+
+					Events are only considered duplicates of cached events that were first seen less than this
+					long ago, after which the same event starts a new window. By default, events are
+					deduplicated for as long as they're cached.
+					"""
+				required: false
+				type: uint: examples: [60]
+			}
 		}
 	}
+	duplicate_count_field: {
+		description: """
+			The field the number of duplicates of retained events is added to.
+
+			When set, the first event of each time window is retained until its window ends, or until
+			it's evicted from the cache, and then emitted with the number of duplicates that were
+			dropped in the meantime. Requires `cache.ttl_secs`.
+			"""
+		required: false
+		type: string: examples: ["duplicate_count"]
+	}
 	fields: {
 		description: """
 			Options to control what fields to match against.
@@ -0,0 +1,54 @@
+package metadata
+
+base: components: transforms: explode: configuration: {
+	copy_fields: {
+		description: """
+			The fields of logs copied to the events emitted for their elements.
+
+			By default, all fields of logs are copied.
+			"""
+		required: false
+		type: array: items: type: string: examples: ["host", "timestamp"]
+	}
+	field: {
+		description: """
+			The array field whose elements an event is emitted for each.
+
+			Logs without the field, or with a value that isn't an array, are emitted unchanged.
+			"""
+		required: true
+		type: string: examples: ["records", "payload.items"]
+	}
+	index_field: {
+		description: "The field of the emitted events the indexes of their elements are inserted into."
+		required:    false
+		type: string: examples: ["record_index"]
+	}
+	keep_parent: {
+		description: "Whether logs are emitted as well, after the events of their elements, without `field`."
+		required:    false
+		type: bool: default: false
+	}
+	move_fields: {
+		description: """
+			The fields of logs moved to the events emitted for their elements.
+
+			They're copied as with `copy_fields`, and removed from logs that are kept with
+			`keep_parent`.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["request_id"]
+		}
+	}
+	target_field: {
+		description: """
+			The field of the emitted events the elements are inserted into.
+
+			By default, elements are inserted into `field`.
+			"""
+		required: false
+		type: string: examples: ["record"]
+	}
+}
@@ -1,73 +1,128 @@
 package metadata
 
-base: components: transforms: log_to_metric: configuration: metrics: {
-	description: "A list of metrics to generate."
-	required:    true
-	type: array: items: type: object: options: {
-		field: {
-			description: "Name of the field in the event to generate the metric."
-			required:    true
-			type: string: syntax: "template"
-		}
-		increment_by_value: {
-			description:   "Increments the counter by the value in `field`, instead of only by `1`."
-			relevant_when: "type = \"counter\""
-			required:      false
-			type: bool: default: false
-		}
-		kind: {
-			description: """
-				Metric kind.
+base: components: transforms: log_to_metric: configuration: {
+	all_metrics: {
+		description: """
+			Converts logs encoding whole metrics, as output by the `metric_to_log` transform, back
+			into those metrics, rather than deriving the metrics of `metrics`.
 
-				Metrics can be either absolute of incremental. Absolute metrics represent a sort of "last write wins" scenario,
-				where the latest absolute value seen is meant to be the actual metric value.  In constrast, and perhaps intuitively,
-				incremental metrics are meant to be additive, such that we don't know what total value of the metric is, but we know
-				that we'll be adding or subtracting the given value from it.
+			Logs that don't encode a metric are dropped.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	metrics: {
+		description: "A list of metrics to generate."
+		required:    false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				buckets: {
+					description: """
+						The upper limits of the buckets of the histogram, in increasing order.
 
-				Generally speaking, most metrics storage systems deal with incremental updates. A notable exception is Prometheus,
-				which deals with, and expects, absolute values from clients.
-				"""
-			relevant_when: "type = \"counter\""
-			required:      false
-			type: string: {
-				default: "incremental"
-				enum: {
-					absolute:    "Absolute metric."
-					incremental: "Incremental metric."
+						When set, the histogram is emitted with these buckets rather than as a distribution of
+						samples, so that it is aggregated the same way whatever the sink. Values greater than the
+						last limit are only counted in the sum and count of the histogram.
+						"""
+					relevant_when: "type = \"histogram\""
+					required:      false
+					type: array: {
+						default: []
+						items: type: float: {}
+					}
 				}
-			}
-		}
-		name: {
-			description: """
-				Overrides the name of the counter.
+				cardinality_limit: {
+					description: "Limits the number of series of a metric, as distinct sets of name, namespace, and tags."
+					required:    false
+					type: object: options: {
+						max_series: {
+							description: "The maximum number of series of the metric."
+							required:    true
+							type: uint: examples: [1000]
+						}
+						overflow_action: {
+							description: "What to do with the metrics of new series once the cardinality limit of a metric is reached."
+							required:    false
+							type: string: {
+								default: "drop"
+								enum: {
+									aggregate: """
+										Replace the values of all tags of the metrics of new series with `overflow`, so that they
+										are aggregated into a single series.
+										"""
+									drop: "Drop the metrics of new series."
+								}
+							}
+						}
+					}
+				}
+				field: {
+					description: "Name of the field in the event to generate the metric."
+					required:    true
+					type: string: syntax: "template"
+				}
+				increment_by_value: {
+					description:   "Increments the counter by the value in `field`, instead of only by `1`."
+					relevant_when: "type = \"counter\""
+					required:      false
+					type: bool: default: false
+				}
+				kind: {
+					description: """
+						Metric kind.
 
-				If not specified, `field` is used as the name of the metric.
-				"""
-			required: false
-			type: string: syntax: "template"
-		}
-		namespace: {
-			description: "Sets the namespace for the metric."
-			required:    false
-			type: string: syntax: "template"
-		}
-		tags: {
-			description: "Tags to apply to the metric."
-			required:    false
-			type: object: options: "*": {
-				description: "Tags to apply to the metric."
-				required:    true
-				type: string: syntax: "template"
-			}
-		}
-		type: {
-			required: true
-			type: string: enum: {
-				counter:   "A counter."
-				gauge:     "A gauge."
-				histogram: "A histogram."
-				set:       "A set."
-				summary:   "A summary."
+						Metrics can be either absolute of incremental. Absolute metrics represent a sort of "last write wins" scenario,
+						where the latest absolute value seen is meant to be the actual metric value.  In constrast, and perhaps intuitively,
+						incremental metrics are meant to be additive, such that we don't know what total value of the metric is, but we know
+						that we'll be adding or subtracting the given value from it.
+
+						Generally speaking, most metrics storage systems deal with incremental updates. A notable exception is Prometheus,
+						which deals with, and expects, absolute values from clients.
+						"""
+					relevant_when: "type = \"counter\""
+					required:      false
+					type: string: {
+						default: "incremental"
+						enum: {
+							absolute:    "Absolute metric."
+							incremental: "Incremental metric."
+						}
+					}
+				}
+				name: {
+					description: """
+						Overrides the name of the counter.
+
+						If not specified, `field` is used as the name of the metric.
+						"""
+					required: false
+					type: string: syntax: "template"
+				}
+				namespace: {
+					description: "Sets the namespace for the metric."
+					required:    false
+					type: string: syntax: "template"
+				}
+				tags: {
+					description: "Tags to apply to the metric."
+					required:    false
+					type: object: options: "*": {
+						description: "Tags to apply to the metric."
+						required:    true
+						type: string: syntax: "template"
+					}
+				}
+				type: {
+					required: true
+					type: string: enum: {
+						counter:   "A counter."
+						gauge:     "A gauge."
+						histogram: "A histogram."
+						set:       "A set."
+						summary:   "A summary."
+					}
+				}
 			}
 		}
 	}
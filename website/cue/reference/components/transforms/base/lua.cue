@@ -95,6 +95,55 @@ base: components: transforms: lua: configuration: {
 			}
 		}
 	}
+	runtime: {
+		description: "The runtime scripts run in."
+		required:    false
+		type: object: options: {
+			memory_limit: {
+				description: """
+					The most memory, in bytes, the Lua runtime of each thread can allocate.
+
+					Allocations beyond it fail with an error, aborting the call of the hook or timer handler
+					making them.
+					"""
+				required: false
+				type: uint: examples: [67108864]
+			}
+			queue_size: {
+				description: """
+					The number of events each thread can have waiting to be processed.
+
+					Once all threads have this many, the transform stops taking events, applying backpressure
+					to its inputs.
+					"""
+				required: false
+				type: uint: default: 100
+			}
+			threads: {
+				description: """
+					The number of threads running the script, each with its own Lua runtime.
+
+					Events are processed by whichever thread has room for them, so with more than one thread
+					the order of events isn't kept, and the global variables of scripts aren't shared between
+					events processed by different threads. The `init` and `shutdown` hooks, and the timer
+					handlers, are called on each thread.
+					"""
+				required: false
+				type: uint: default: 1
+			}
+			timeout_ms: {
+				description: """
+					The longest, in milliseconds, a single call of a hook or timer handler can run for before
+					it's aborted with an error.
+
+					The time is checked every thousand Lua instructions, so calls blocked in Lua functions
+					implemented in C, such as those reading files, aren't aborted until they return.
+					"""
+				required: false
+				type: uint: examples: [100]
+			}
+		}
+	}
 	search_dirs: {
 		description: """
 			A list of directories to search when loading a Lua file via the `require` function.
@@ -199,6 +248,11 @@ base: components: transforms: lua: configuration: {
 				This version is deprecated and will be removed in a future version.
 				"""
 			"2": "Lua transform API version 2."
+			"3": """
+				Lua transform API version 3.
+
+				Scripts have the same API as with version two, but run on dedicated threads.
+				"""
 		}
 	}
 }
@@ -0,0 +1,79 @@
+package metadata
+
+base: components: transforms: pii_redaction: configuration: {
+	detectors: {
+		description: """
+			The detectors strings are scanned with, in order, and what is done with what they find.
+
+			By default, strings are scanned with all detectors, and what they find is masked.
+			"""
+		required: false
+		type: array: items: type: object: options: {
+			action: {
+				description: "What is done with the personal data or secrets found by a detector."
+				required:    false
+				type: string: {
+					default: "mask"
+					enum: {
+						hash: """
+							The data is replaced with the hex-encoded SHA-256 hash of it, so that the same data can
+							still be correlated across logs.
+
+							As data such as credit card numbers has few possible values, its hashes can be reversed by
+							hashing all of them, so hashing should only be used where correlating the data is needed.
+							"""
+						mask:   "Each character of the data is replaced with `*`."
+						remove: "The field holding the data is removed from the log."
+						tag:    "The data is left as is, and the name of the detector is added to `tag_field`."
+					}
+				}
+			}
+			type: {
+				description: "A kind of personal data or secret detected in strings."
+				required:    true
+				type: string: enum: {
+					aws_access_key_id: "AWS access key IDs."
+					credit_card: """
+						Credit card numbers, of 13 to 19 digits, optionally separated by spaces or dashes, that
+						pass the Luhn check.
+						"""
+					email:                        "Email addresses."
+					github_token:                 "GitHub personal access, OAuth, and app tokens."
+					jwt:                          "JSON web tokens."
+					private_key:                  "PEM-encoded private keys."
+					slack_token:                  "Slack bot, user, and app tokens."
+					uk_national_insurance_number: "UK national insurance numbers, such as `AB 12 34 56 C`."
+					us_social_security_number:    "US social security numbers, such as `123-45-6789`."
+				}
+			}
+		}
+	}
+	fields: {
+		description: """
+			The fields scanned for personal data and secrets.
+
+			Strings nested in objects and arrays of the fields are scanned as well. By default, all
+			strings of logs are scanned.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: {
+				examples: ["message", "user.email"]
+				syntax: "literal"
+			}
+		}
+	}
+	tag_field: {
+		description: """
+			The field the names of the detectors with the `tag` action are added to, when they find
+			data in a log.
+			"""
+		required: false
+		type: string: {
+			default: "pii_detected"
+			examples: ["_pii"]
+			syntax: "literal"
+		}
+	}
+}
@@ -22,6 +22,16 @@ base: components: transforms: reduce: configuration: {
 			unit:    "milliseconds"
 		}
 	}
+	flush_on_group_change: {
+		description: """
+			Whether to flush the group of the previous event when an event of another group is received.
+
+			Suits streams where the events of each group are consecutive, such as the lines of
+			multi-line messages, as at most one group is then reduced at a time.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	flush_period_ms: {
 		description: "The interval to check for and flush any expired events, in milliseconds."
 		required:    false
@@ -50,6 +60,16 @@ base: components: transforms: reduce: configuration: {
 			}
 		}
 	}
+	max_bytes: {
+		description: """
+			The maximum size of the events of a group, in bytes, after which the group is flushed.
+
+			Groups are flushed with the event that makes them reach this size, so that the memory held
+			by each group is bounded. Sizes are those of events in memory.
+			"""
+		required: false
+		type: uint: examples: [1048576]
+	}
 	merge_strategies: {
 		description: """
 			A map of field names to custom merge strategies.
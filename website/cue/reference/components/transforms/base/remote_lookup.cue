@@ -0,0 +1,340 @@
+package metadata
+
+base: components: transforms: remote_lookup: configuration: {
+	backend: {
+		description: "Where events are looked up."
+		required:    true
+		type: object: options: {
+			auth: {
+				description: """
+					Configuration of the authentication strategy for HTTP requests.
+
+					HTTP authentication should almost always be used with HTTPS only, as the authentication credentials are passed as an
+					HTTP header without any additional encryption beyond what is provided by the transport itself.
+					"""
+				relevant_when: "type = \"http\""
+				required:      false
+				type: object: options: {
+					client_id: {
+						description:   "The identifier of the client."
+						relevant_when: "strategy = \"oauth2\""
+						required:      true
+						type: string: syntax: "literal"
+					}
+					client_secret: {
+						description:   "The secret of the client."
+						relevant_when: "strategy = \"oauth2\""
+						required:      true
+						type: string: syntax: "literal"
+					}
+					password: {
+						description:   "The password to send."
+						relevant_when: "strategy = \"basic\""
+						required:      true
+						type: string: syntax: "literal"
+					}
+					refresh_before_secs: {
+						description: """
+							How long before they expire access tokens are refreshed, in seconds.
+
+							Access tokens expiring sooner than this are refreshed halfway through their lifetime.
+							"""
+						relevant_when: "strategy = \"oauth2\""
+						required:      false
+						type: uint: default: 60
+					}
+					scopes: {
+						description:   "The scopes to request access tokens for."
+						relevant_when: "strategy = \"oauth2\""
+						required:      false
+						type: array: {
+							default: []
+							items: type: string: {
+								examples: ["logs:write"]
+								syntax: "literal"
+							}
+						}
+					}
+					strategy: {
+						required: true
+						type: string: enum: {
+							basic: """
+								Basic authentication.
+
+								The username and password are concatenated and encoded via [base64][base64].
+
+								[base64]: https://en.wikipedia.org/wiki/Base64
+								"""
+							bearer: """
+								Bearer authentication.
+
+								The bearer token value (OAuth2, JWT, etc) is passed as-is.
+								"""
+							oauth2: """
+								OAuth2 authentication, with the client credentials grant.
+
+								Access tokens are requested from the token endpoint with the credentials of the client, and
+								passed as bearer tokens. They're cached, and refreshed before they expire.
+
+								This is only supported by the `http`, `loki`, and `prometheus_remote_write` sinks, and the
+								`remote_lookup` transform. Other components fail to build with it.
+								"""
+						}
+					}
+					token: {
+						description:   "The bearer token to send."
+						relevant_when: "strategy = \"bearer\""
+						required:      true
+						type: string: syntax: "literal"
+					}
+					token_endpoint: {
+						description:   "The token endpoint of the authorization server, which access tokens are requested from."
+						relevant_when: "strategy = \"oauth2\""
+						required:      true
+						type: string: {
+							examples: ["https://auth.example.com/oauth2/token"]
+							syntax: "literal"
+						}
+					}
+					user: {
+						description:   "The username to send."
+						relevant_when: "strategy = \"basic\""
+						required:      true
+						type: string: syntax: "literal"
+					}
+				}
+			}
+			body: {
+				description:   "The template of the body of requests."
+				relevant_when: "type = \"http\""
+				required:      false
+				type: string: {
+					examples: ["{\"ip\": \"{{ client_ip }}\"}"]
+					syntax: "template"
+				}
+			}
+			headers: {
+				description:   "Headers sent with requests."
+				relevant_when: "type = \"http\""
+				required:      false
+				type: object: options: "*": {
+					description: "An HTTP request header."
+					required:    true
+					type: string: syntax: "literal"
+				}
+			}
+			key: {
+				description:   "The template of the key looked up for events."
+				relevant_when: "type = \"redis\""
+				required:      true
+				type: string: {
+					examples: ["users:{{ user_id }}"]
+					syntax: "template"
+				}
+			}
+			method: {
+				description:   "The HTTP method of lookup requests."
+				relevant_when: "type = \"http\""
+				required:      false
+				type: string: {
+					default: "get"
+					enum: {
+						get:  "GET."
+						post: "POST."
+					}
+				}
+			}
+			tls: {
+				description:   "TLS configuration."
+				relevant_when: "type = \"http\""
+				required:      false
+				type: object: options: {
+					alpn_protocols: {
+						description: """
+							Sets the list of supported ALPN protocols.
+
+							Declare the supported ALPN protocols, which are used during negotiation with peer. Prioritized in the order
+							they are defined.
+							"""
+						required: false
+						type: array: items: type: string: syntax: "literal"
+					}
+					ca_file: {
+						description: """
+							Absolute path to an additional CA certificate file.
+
+							The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: syntax: "literal"
+					}
+					crt_file: {
+						description: """
+							Absolute path to a certificate file used to identify this server.
+
+							The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+							an inline string in PEM format.
+
+							If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+							"""
+						required: false
+						type: string: syntax: "literal"
+					}
+					key_file: {
+						description: """
+							Absolute path to a private key file used to identify this server.
+
+							The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: syntax: "literal"
+					}
+					key_pass: {
+						description: """
+							Passphrase used to unlock the encrypted key file.
+
+							This has no effect unless `key_file` is set.
+							"""
+						required: false
+						type: string: syntax: "literal"
+					}
+					verify_certificate: {
+						description: """
+							Enables certificate verification.
+
+							If enabled, certificates must be valid in terms of not being expired, as well as being issued by a trusted
+							issuer. This verification operates in a hierarchical manner, checking that not only the leaf certificate (the
+							certificate presented by the client/server) is valid, but also that the issuer of that certificate is valid, and
+							so on until reaching a root certificate.
+
+							Relevant for both incoming and outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+							"""
+						required: false
+						type: bool: {}
+					}
+					verify_hostname: {
+						description: """
+							Enables hostname verification.
+
+							If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+							the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+							Only relevant for outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+							"""
+						required: false
+						type: bool: {}
+					}
+				}
+			}
+			type: {
+				required: true
+				type: string: enum: {
+					http:  "Events are looked up with HTTP requests."
+					redis: "Events are looked up in Redis strings."
+				}
+			}
+			uri: {
+				description: """
+					The template of the URI looked up for events.
+
+					Values of events are inserted as is, so fields that may hold characters that aren't
+					allowed in URIs must be encoded beforehand, such as with the `encode_percent` function of
+					the `remap` transform.
+					"""
+				relevant_when: "type = \"http\""
+				required:      true
+				type: string: {
+					examples: ["https://users.example.com/api/users/{{ user_id }}"]
+					syntax: "template"
+				}
+			}
+			url: {
+				description:   "The URL of the Redis server."
+				relevant_when: "type = \"redis\""
+				required:      true
+				type: string: {
+					examples: ["redis://127.0.0.1:6379/0"]
+					syntax: "literal"
+				}
+			}
+		}
+	}
+	cache: {
+		description: "Configuration of the cache of the results of lookups."
+		required:    false
+		type: object: options: {
+			max_entries: {
+				description: """
+					The maximum number of results cached, after which the least recently used ones are
+					evicted.
+					"""
+				required: false
+				type: uint: default: 10000
+			}
+			ttl_secs: {
+				description: """
+					How long results are cached for, in seconds.
+
+					Lookups finding nothing are cached as well, while failed lookups aren't.
+					"""
+				required: false
+				type: float: default: 60.0
+			}
+		}
+	}
+	concurrency: {
+		description: """
+			The maximum number of lookups in flight at once.
+
+			Events are emitted in the order they were received, so a slow lookup holds back those of
+			the events after it.
+			"""
+		required: false
+		type: uint: default: 10
+	}
+	fallback: {
+		description: """
+			The value inserted into `target_field` when lookups fail or time out, and `on_error` is
+			`pass`.
+			"""
+		required: false
+		type: string: {
+			examples: ["unknown"]
+			syntax: "literal"
+		}
+	}
+	on_error: {
+		description: "What is done with events whose lookup fails or times out."
+		required:    false
+		type: string: {
+			default: "pass"
+			enum: {
+				drop: "Events are dropped."
+				pass: "Events are passed without the result, or with `fallback` when it's set."
+			}
+		}
+	}
+	target_field: {
+		description: """
+			The field the results of lookups are inserted into.
+
+			Results are parsed as JSON, and inserted as strings when they aren't JSON. Events whose
+			lookup finds nothing are passed without the field.
+			"""
+		required: false
+		type: string: {
+			default: "lookup"
+			examples: ["user"]
+			syntax: "literal"
+		}
+	}
+	timeout_secs: {
+		description: "How long lookups are waited for before failing, in seconds."
+		required:    false
+		type: float: default: 1.0
+	}
+}
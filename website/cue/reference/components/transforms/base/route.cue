@@ -1,18 +1,7 @@
 package metadata
 
-base: components: transforms: route: configuration: route: {
-	description: """
-		A table of route identifiers to logical conditions representing the filter of the route.
-
-		Each route can then be referenced as an input by other components with the name
-		`<transform_name>.<route_id>`. If an event doesn’t match any route, it will be sent to the
-		`<transform_name>._unmatched` output.
-
-		Both `_unmatched`, as well as `_default`, are reserved output names and cannot be used as a
-		route name.
-		"""
-	required: false
-	type: object: options: "*": {
+base: components: transforms: route: configuration: {
+	route: {
 		description: """
 			A table of route identifiers to logical conditions representing the filter of the route.
 
@@ -23,7 +12,99 @@ base: components: transforms: route: configuration: route: {
 			Both `_unmatched`, as well as `_default`, are reserved output names and cannot be used as a
 			route name.
 			"""
-		required: true
-		type: condition: {}
+		required: false
+		type: object: options: "*": {
+			description: """
+				A table of route identifiers to logical conditions representing the filter of the route.
+
+				Each route can then be referenced as an input by other components with the name
+				`<transform_name>.<route_id>`. If an event doesn’t match any route, it will be sent to the
+				`<transform_name>._unmatched` output.
+
+				Both `_unmatched`, as well as `_default`, are reserved output names and cannot be used as a
+				route name.
+				"""
+			required: true
+			type: condition: {}
+		}
+	}
+	route_table: {
+		description: """
+			Routes events to outputs looked up in an enrichment table, in addition to the routes of
+			`route`.
+			"""
+		required: false
+		type: object: options: {
+			event_field: {
+				description: """
+					The name of the field whose value is looked up in the table.
+
+					For metrics, this is the name of a tag.
+					"""
+				required: true
+				type: string: examples: ["tenant"]
+			}
+			key_field: {
+				description: "The field of the table matched against the value of `event_field`."
+				required:    false
+				type: string: default: "key"
+			}
+			output_field: {
+				description: "The field of the table holding the names of outputs."
+				required:    false
+				type: string: default: "output"
+			}
+			outputs: {
+				description: """
+					The outputs events can be routed to by the table.
+
+					Outputs are referenced by other components, so they can't be added by the table. Events
+					whose output isn't found in the table, or isn't one of these, are handled as events matching
+					no route.
+					"""
+				required: true
+				type: array: items: type: string: examples: ["tenant_a", "tenant_b"]
+			}
+			table: {
+				description: "The name of the enrichment table."
+				required:    true
+				type: string: examples: ["tenant_routes"]
+			}
+		}
+	}
+	split: {
+		description: """
+			Splits events across outputs by weight, rather than routing them by conditions.
+
+			Each output can then be referenced as an input by other components with the name
+			`<transform_name>.<output_name>`, such as to send a share of events to a new sink.
+			"""
+		required: false
+		type: object: options: {
+			key_field: {
+				description: """
+					The name of the field events are assigned to outputs by.
+
+					Events with the same value of this field are always sent to the same output. Events without
+					it, or all events when this is not set, are assigned at random.
+					"""
+				required: false
+				type: string: examples: ["user_id"]
+			}
+			weights: {
+				description: """
+					A table of output names to their weights.
+
+					Each event is sent to a single output, each output receiving its weight's share of the sum
+					of all weights. For example, weights of `5` and `95` send 5% of events to the first output.
+					"""
+				required: true
+				type: object: options: "*": {
+					description: "The weight of an output."
+					required:    true
+					type: uint: {}
+				}
+			}
+		}
 	}
 }
@@ -11,13 +11,15 @@ base: components: transforms: sample: configuration: {
 			The name of the log field whose value will be hashed to determine if the event should be
 			passed.
 
-			Consistently samples the same events. Actual rate of sampling may differ from the configured
-			one if values in the field are not uniformly distributed. If left unspecified, or if the
-			event doesn’t have `key_field`, events will be count rated.
+			Consistently samples the same events, so that all events with the same value, such as those
+			of a trace or a session, are either passed or dropped together. Events passed at a rate are
+			also passed at all lower rates. Actual rate of sampling may differ from the configured one
+			if values in the field are not uniformly distributed. If left unspecified, or if the event
+			doesn’t have `key_field`, events will be count rated.
 			"""
 		required: false
 		type: string: {
-			examples: ["message"]
+			examples: ["message", "trace_id"]
 			syntax: "literal"
 		}
 	}
@@ -31,4 +33,59 @@ base: components: transforms: sample: configuration: {
 		required: true
 		type: uint: {}
 	}
+	rate_field: {
+		description: """
+			The name of the log field holding the rate events are sampled at.
+
+			Events without the field, or whose value isn't a positive integer, are sampled at the rate
+			of `rate_table`, or at `rate`.
+			"""
+		required: false
+		type: string: {
+			examples: ["sample_rate"]
+			syntax: "literal"
+		}
+	}
+	rate_table: {
+		description: """
+			Configuration for looking up the rates events are sampled at in an enrichment table.
+
+			Events whose rate isn't found in the table are sampled at `rate`.
+			"""
+		required: false
+		type: object: options: {
+			event_field: {
+				description: "The name of the log field whose value is looked up in the table."
+				required:    true
+				type: string: {
+					examples: ["service"]
+					syntax: "literal"
+				}
+			}
+			key_field: {
+				description: "The field of the table matched against the value of `event_field`."
+				required:    false
+				type: string: {
+					default: "key"
+					syntax:  "literal"
+				}
+			}
+			rate_field: {
+				description: "The field of the table holding the rates."
+				required:    false
+				type: string: {
+					default: "rate"
+					syntax:  "literal"
+				}
+			}
+			table: {
+				description: "The name of the enrichment table."
+				required:    true
+				type: string: {
+					examples: ["sample_rates"]
+					syntax: "literal"
+				}
+			}
+		}
+	}
 }
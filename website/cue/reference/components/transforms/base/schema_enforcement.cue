@@ -0,0 +1,67 @@
+package metadata
+
+base: components: transforms: schema_enforcement: configuration: {
+	coerce: {
+		description: """
+			Whether values of other types than those of the schema are coerced to its types.
+
+			Strings of integers, numbers and booleans are coerced to them, and integers, numbers and
+			booleans are coerced to strings. Logs whose values can't be coerced are rejected, without
+			any of their values being coerced.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	schema: {
+		description: """
+			The JSON Schema logs are validated against, as JSON.
+
+			The `type`, `properties`, `required`, `additionalProperties`, `items`, `enum`, `minimum`,
+			`maximum`, `minLength`, `maxLength`, and `pattern` keywords are supported, and other
+			keywords are ignored. Timestamps are validated as strings.
+			"""
+		required: false
+		type: string: {
+			examples: ["{\"type\": \"object\", \"required\": [\"message\"], \"properties\": {\"message\": {\"type\": \"string\"}}}"]
+			syntax: "literal"
+		}
+	}
+	schema_file: {
+		description: """
+			The path of a file holding the JSON Schema logs are validated against.
+
+			Only one of `schema` and `schema_file` can be provided.
+			"""
+		required: false
+		type: string: {
+			examples: ["/etc/vector/schemas/access_log.json"]
+			syntax: "literal"
+		}
+	}
+	validate_input_definition: {
+		description: """
+			Whether logs are also validated against the schema definition of the inputs of the
+			transform.
+
+			Logs whose fields don't have the types the definition has for them are rejected. This only
+			has an effect with inputs whose schema definitions are known, such as those of sources
+			decoding events with `json` and the `vector` log namespace.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	violations_field: {
+		description: """
+			The field the violations of rejected logs are inserted into.
+
+			Violations are objects with the `path` of the value violating the schema, and a `message`
+			describing the violation.
+			"""
+		required: false
+		type: string: {
+			default: "schema_violations"
+			examples: ["_violations"]
+			syntax: "literal"
+		}
+	}
+}
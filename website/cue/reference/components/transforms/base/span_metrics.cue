@@ -0,0 +1,68 @@
+package metadata
+
+base: components: transforms: span_metrics: configuration: {
+	buckets: {
+		description: "The upper limits of the buckets of the histogram of the duration of spans, in seconds."
+		required:    false
+		type: array: {
+			default: [0.002, 0.004, 0.006, 0.008, 0.01, 0.05, 0.1, 0.2, 0.4, 0.8, 1.0, 1.4, 2.0, 5.0, 10.0, 15.0]
+			items: type: float: {}
+		}
+	}
+	dimensions: {
+		description: """
+			Additional tags of metrics, and the fields of spans holding their values.
+
+			Spans without a field don't have its tag.
+			"""
+		required: false
+		type: object: {
+			default: {}
+			options: "*": {
+				description: "The field of spans holding the value of the tag."
+				required:    true
+				type: string: syntax: "literal"
+			}
+		}
+	}
+	flush_interval_secs: {
+		description: """
+			The interval between the emissions of metrics, in seconds.
+
+			Metrics are incremental, counting the spans seen since the previous emission.
+			"""
+		required: false
+		type: uint: default: 15
+	}
+	namespace: {
+		description: """
+			The namespace of metrics.
+
+			By default, `traces` is used. Set to an empty string to not use a namespace.
+			"""
+		required: false
+		type: string: {
+			default: "traces"
+			syntax:  "literal"
+		}
+	}
+	operation_field: {
+		description: """
+			The field of spans holding the name of their operation, as the `operation` tag of
+			metrics.
+			"""
+		required: false
+		type: string: {
+			default: "name"
+			syntax:  "literal"
+		}
+	}
+	service_field: {
+		description: "The field of spans holding the name of their service, as the `service` tag of metrics."
+		required:    false
+		type: string: {
+			default: "resources.\"service.name\""
+			syntax:  "literal"
+		}
+	}
+}
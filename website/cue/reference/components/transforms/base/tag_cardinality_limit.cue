@@ -23,6 +23,13 @@ base: components: transforms: tag_cardinality_limit: configuration: {
 			enum: {
 				drop_event: "Drop the entire event itself."
 				drop_tag:   "Drop the tag(s) that would exceed the configured limit."
+				replace_tag: """
+					Replace the value of the tag(s) that would exceed the configured limit with
+					`overflow_value`.
+
+					Metrics whose tags are replaced are aggregated into the same series, by sinks, as the
+					others whose values exceeded the limit.
+					"""
 			}
 		}
 	}
@@ -45,6 +52,79 @@ base: components: transforms: tag_cardinality_limit: configuration: {
 				"""
 		}
 	}
+	overflow_value: {
+		description: "The value tags are given by the `replace_tag` action."
+		required:    false
+		type: string: {
+			default: "other"
+			syntax:  "literal"
+		}
+	}
+	per_metric_limits: {
+		description: """
+			Overrides of the limits for the metrics with specific names.
+
+			The values of the tags of these metrics are tracked separately from those of other
+			metrics, so that they neither count towards, nor are rejected by, the limits of others.
+			"""
+		required: false
+		type: object: {
+			default: {}
+			options: "*": {
+				description: "The overrides of the limits for the metric."
+				required:    true
+				type: object: options: {
+					limit_exceeded_action: {
+						description: """
+							The action to take when an event arrives that would exceed the cardinality limit for one
+							or more of its tags.
+							"""
+						required: false
+						type: string: enum: {
+							drop_event: "Drop the entire event itself."
+							drop_tag:   "Drop the tag(s) that would exceed the configured limit."
+							replace_tag: """
+								Replace the value of the tag(s) that would exceed the configured limit with
+								`overflow_value`.
+
+								Metrics whose tags are replaced are aggregated into the same series, by sinks, as the
+								others whose values exceeded the limit.
+								"""
+						}
+					}
+					tag_value_limits: {
+						description: "How many distinct values to accept for specific keys of the metrics."
+						required:    false
+						type: object: {
+							default: {}
+							options: "*": {
+								description: "How many distinct values to accept for the key."
+								required:    true
+								type: uint: {}
+							}
+						}
+					}
+					value_limit: {
+						description: "How many distinct values to accept for any given key of the metrics."
+						required:    false
+						type: uint: {}
+					}
+				}
+			}
+		}
+	}
+	tag_value_limits: {
+		description: "How many distinct values to accept for specific keys, overriding `value_limit`."
+		required:    false
+		type: object: {
+			default: {}
+			options: "*": {
+				description: "How many distinct values to accept for the key."
+				required:    true
+				type: uint: {}
+			}
+		}
+	}
 	value_limit: {
 		description: "How many distinct values to accept for any given key."
 		required:    false
@@ -0,0 +1,77 @@
+package metadata
+
+base: components: transforms: tail_sampling: configuration: {
+	decision_wait_secs: {
+		description: """
+			The time spans of traces are buffered for, from their first span, before deciding whether
+			they're kept, in seconds.
+
+			Spans of traces arriving after their decision follow it, for as long again.
+			"""
+		required: false
+		type: uint: default: 30
+	}
+	max_traces: {
+		description: """
+			The maximum number of traces buffered.
+
+			Once reached, the oldest traces are decided before the end of their window.
+			"""
+		required: false
+		type: uint: default: 50000
+	}
+	policies: {
+		description: """
+			The policies deciding whether traces are kept.
+
+			Traces are kept when any policy keeps them, and dropped otherwise.
+			"""
+		required: true
+		type: array: items: type: object: options: {
+			condition: {
+				description:   "A logical condition, traces with a span matching it are kept."
+				relevant_when: "type = \"condition\""
+				required:      true
+				type: condition: {}
+			}
+			rate: {
+				description: """
+					The rate at which traces are kept, expressed as `1/N`.
+
+					Traces are kept by the hash of their trace ID, so that Vector instances receiving spans of
+					the same traces make the same decisions.
+					"""
+				relevant_when: "type = \"probabilistic\""
+				required:      true
+				type: uint: examples: [10]
+			}
+			threshold_ms: {
+				description: """
+					The duration of traces, from the start of their first span to the end of their last span,
+					from which they're kept, in milliseconds.
+					"""
+				relevant_when: "type = \"latency\""
+				required:      true
+				type: uint: examples: [5000]
+			}
+			type: {
+				required: true
+				type: string: enum: {
+					condition:     "Keeps traces with a span matching a condition."
+					error:         "Keeps traces with a span whose `status.code` is that of errors."
+					latency:       "Keeps traces that lasted at least `threshold_ms`."
+					probabilistic: "Keeps a share of traces."
+				}
+			}
+		}
+	}
+	trace_id_field: {
+		description: """
+			The name of the field of spans holding their trace ID.
+
+			Spans without this field are passed through.
+			"""
+		required: false
+		type: string: default: "trace_id"
+	}
+}
@@ -1,6 +1,42 @@
 package metadata
 
 base: components: transforms: throttle: configuration: {
+	backend: {
+		description: "Where the number of events allowed for each bucket is kept."
+		required:    false
+		type: object: options: {
+			key_prefix: {
+				description: """
+					The prefix of the Redis keys events are counted in.
+
+					Vector instances enforcing the same rate limit must use the same prefix, and those
+					enforcing different ones must use different prefixes.
+					"""
+				relevant_when: "type = \"redis\""
+				required:      false
+				type: string: {
+					default: "vector:throttle"
+					examples: ["vector:throttle:api_logs"]
+				}
+			}
+			type: {
+				required: false
+				type: string: {
+					default: "local"
+					enum: {
+						local: "Events are counted in memory, so each Vector instance allows the full `threshold`."
+						redis: "Events are counted in Redis, so that all Vector instances sharing it share the `threshold`."
+					}
+				}
+			}
+			url: {
+				description:   "The URL of the Redis server."
+				relevant_when: "type = \"redis\""
+				required:      true
+				type: string: examples: ["redis://127.0.0.1:6379/0"]
+			}
+		}
+	}
 	exclude: {
 		description: "A logical condition used to exclude events from sampling."
 		required:    false
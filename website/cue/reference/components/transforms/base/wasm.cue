@@ -0,0 +1,34 @@
+package metadata
+
+base: components: transforms: wasm: configuration: {
+	fuel: {
+		description: """
+			The most fuel the module can consume processing each log.
+
+			Each WebAssembly instruction run consumes fuel, so this bounds how long logs are processed
+			for. Logs whose processing runs out of fuel are dropped.
+			"""
+		required: false
+		type: uint: default: 10000000
+	}
+	memory_limit: {
+		description: """
+			The most memory, in bytes, the module can use.
+
+			Modules whose initial memory is larger fail to load, and attempts to grow memory beyond it
+			fail.
+			"""
+		required: false
+		type: uint: default: 67108864
+	}
+	module: {
+		description: """
+			The path of the WebAssembly module logs are processed with.
+
+			The module must implement the guest ABI of the transform, and can be in either the binary or
+			the text format.
+			"""
+		required: true
+		type: string: examples: ["/etc/vector/modules/enrich.wasm"]
+	}
+}
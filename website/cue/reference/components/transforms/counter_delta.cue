@@ -0,0 +1,87 @@
+package metadata
+
+components: transforms: counter_delta: {
+	title: "Counter Delta"
+
+	description: """
+		Converts absolute counters into the increase, or the rate of increase, of each series since
+		its previous value, for sinks that expect incremental metrics.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.counter_delta.configuration
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		references: {
+			title: "References"
+			body: """
+				The transform holds the previous value of each series of absolute counters, and emits
+				nothing for the first value of a series, which only becomes its reference: converting
+				it would count everything since its source started, for instance again after Vector
+				restarts. Every later value is emitted as an incremental counter of its increase since
+				the reference with the `delta` mode, or as a gauge of the increase per second with the
+				`rate` mode, and becomes the new reference. All other metrics pass through unchanged.
+				"""
+		}
+
+		resets: {
+			title: "Counter Resets"
+			body: """
+				Counters only decrease when their source restarts and counts from zero again, so when a
+				value is lower than the reference, the whole value is taken as the increase.
+				"""
+		}
+
+		rates: {
+			title: "Rates"
+			body: """
+				Rates are computed over the time between the timestamps of values and references, or
+				between their arrival at the transform when either doesn't have a timestamp. Values
+				that aren't later than their reference don't have a rate, and are dropped.
+				"""
+		}
+
+		staleness: {
+			title: "Staleness"
+			body: """
+				Series that aren't seen for `stale_after_secs` are forgotten, so that memory only holds
+				series that are still reported, and so that their next value is taken as a new
+				reference rather than compared to one that may predate a restart of their source.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		counter_delta_series: components.sources.internal_metrics.output.metrics.counter_delta_series
+		counter_resets_total: components.sources.internal_metrics.output.metrics.counter_resets_total
+	}
+}
@@ -83,6 +83,34 @@ components: transforms: dedupe: {
 				"""
 		}
 
+		time_windows: {
+			title: "Time Windows"
+			body: """
+				By default, events are considered duplicates for as long as a matching event is
+				cached. With `cache.ttl_secs`, events are only considered duplicates of events first
+				seen less than that many seconds ago, after which the next matching event starts a new
+				window and is emitted again. Once the cache is full, `cache.eviction` decides which
+				event is evicted: `lru` evicts the event that was least recently matched, while `fifo`
+				evicts the event that was cached first.
+
+				With `duplicate_count_field`, duplicates aren't silently dropped. Instead, the first
+				event of each window is retained until the window ends, or until it's evicted from the
+				cache, and then emitted with the number of dropped duplicates in that field. Retained
+				events are only acknowledged along with their duplicates, and are all emitted when
+				Vector shuts down.
+				"""
+		}
+
+		cache_observability: {
+			title: "Cache Observability"
+			body: """
+				The transform reports the number of cached events with the `dedupe_cache_events`
+				gauge, and the lookups of incoming events with the `dedupe_cache_hits_total` and
+				`dedupe_cache_misses_total` counters. The hit ratio of the cache, the share of events
+				found to be duplicates, is the rate of hits divided by that of both counters.
+				"""
+		}
+
 		missing_fields: {
 			title: "Missing Fields"
 			body: """
@@ -95,6 +123,9 @@ components: transforms: dedupe: {
 	}
 
 	telemetry: metrics: {
-		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+		dedupe_cache_events:       components.sources.internal_metrics.output.metrics.dedupe_cache_events
+		dedupe_cache_hits_total:   components.sources.internal_metrics.output.metrics.dedupe_cache_hits_total
+		dedupe_cache_misses_total: components.sources.internal_metrics.output.metrics.dedupe_cache_misses_total
+		events_discarded_total:    components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
 }
@@ -0,0 +1,75 @@
+package metadata
+
+components: transforms: explode: {
+	title: "Explode"
+
+	description: """
+		Splits logs with array fields into an event for each of the elements of the array.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.explode.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Explode records"
+			input: log: {
+				host: "web-1"
+				records: [{id: 1}, {id: 2}]
+			}
+			configuration: {
+				field:        "records"
+				target_field: "record"
+				index_field:  "index"
+			}
+			output: [
+				{log: {host: "web-1", record: {id: 1}, index: 0}},
+				{log: {host: "web-1", record: {id: 2}, index: 1}},
+			]
+		},
+	]
+
+	how_it_works: {
+		fields: {
+			title: "Fields"
+			body: """
+				Each element of the array in `field` is inserted into `target_field` of an event of its
+				own, along with the other fields of the log. With `copy_fields`, only those fields are
+				copied instead, along with `move_fields`. Logs are dropped once they're split, unless
+				`keep_parent` is set, in which case they're emitted after their elements, without
+				`field` or `move_fields`. Logs with empty arrays are dropped, unless `keep_parent` is set.
+				"""
+		}
+
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				The events emitted for the elements of a log share its acknowledgement, so that when
+				[end-to-end acknowledgements](/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/) are enabled, the log is only
+				acknowledged once all of them have been delivered, and is rejected if any of them is.
+				"""
+		}
+	}
+}
@@ -329,9 +329,41 @@ components: transforms: log_to_metric: {
 				will not be emitted.
 				"""
 		}
+		histogram_buckets: {
+			title: "Histogram Buckets"
+			body: """
+				By default, histograms are emitted as distributions of the samples of their field,
+				which sinks bucket by their own settings. With `buckets`, histograms are emitted with
+				those buckets instead, so that they have the same buckets in all sinks.
+				"""
+		}
+		reconstructing_metrics: {
+			title: "Reconstructing Metrics"
+			body: """
+				With `all_metrics = true`, rather than deriving metrics from fields, each log encoding
+				a whole metric, as output by the [`metric_to_log` transform](\(urls.vector_transforms)/metric_to_log),
+				is converted back into that metric. All types of metrics are reconstructed losslessly,
+				including sketches, aggregated histograms and summaries, and their intervals, so that
+				metrics can go through components or systems that only handle logs. Logs of the legacy
+				namespace have their `host` field turned back into the tag of the same name.
+				"""
+		}
+		cardinality_limits: {
+			title: "Cardinality Limits"
+			body: """
+				Tags rendered from fields of logs, such as paths or user IDs, can derive a new series
+				for each of their values. With `cardinality_limit`, a metric derives at most
+				`max_series` distinct series, tracked in memory by each instance of the transform.
+				Metrics of series beyond the limit are dropped, or with `overflow_action = "aggregate"`,
+				have the values of their tags replaced with `overflow`, so that they are counted in a
+				single series. Either is counted by the
+				`log_to_metric_cardinality_limit_exceeded_total` metric.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
-		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+		log_to_metric_cardinality_limit_exceeded_total: components.sources.internal_metrics.output.metrics.log_to_metric_cardinality_limit_exceeded_total
+		processing_errors_total:                        components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
 }
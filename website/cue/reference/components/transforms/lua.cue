@@ -296,6 +296,22 @@ components: transforms: lua: {
 				[the manual](\(urls.lua_manual)) would suffice.
 				"""
 		}
+		runtime: {
+			title: "Runtime"
+			body:  """
+				With versions one and two, scripts run on the threads of the topology, so a slow
+				script also holds up the other components sharing its thread. With version three,
+				scripts have the same API as with version two, but run on dedicated threads, each with
+				its own Lua runtime, which are sent events through queues of `runtime.queue_size`
+				events.
+
+				With more than one of `runtime.threads`, events are processed concurrently, so their
+				order isn't kept, and the global variables of scripts aren't shared between threads.
+				Calls of hooks and timer handlers can be limited in time with `runtime.timeout_ms`,
+				and Lua runtimes in memory with `runtime.memory_limit`. Calls going beyond the limits
+				are aborted, and the events they were processing are dropped.
+				"""
+		}
 		search_dirs: {
 			title: "Search Directories"
 			body:  """
@@ -91,7 +91,18 @@ components: transforms: metric_to_log: {
 		},
 	]
 
-	how_it_works: {}
+	how_it_works: {
+		non_finite_values: {
+			title: "Non-finite Values"
+			body: """
+				As JSON has no representation of infinite and NaN floats, such values of metrics, such
+				as the upper limit of the last bucket of histograms, are encoded as the strings `inf`,
+				`-inf`, and `NaN`. The [`log_to_metric` transform](\(urls.vector_transforms)/log_to_metric)
+				decodes them with `all_metrics = true`, which converts logs output by this transform
+				back into their metrics.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
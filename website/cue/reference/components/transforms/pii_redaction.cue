@@ -0,0 +1,68 @@
+package metadata
+
+components: transforms: pii_redaction: {
+	title: "PII Redaction"
+
+	description: """
+		Scans the strings of logs for personal data and secrets with built-in detectors, and masks,
+		hashes, or removes what they find, or tags the logs it's found in.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		sanitize: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.pii_redaction.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		detectors: {
+			title: "Detectors"
+			body: """
+				The detectors find data by its format, and check it further where the format allows it:
+				credit card numbers must pass the Luhn check, and US social security and UK national
+				insurance numbers must have the areas and prefixes of assigned ones. As any string
+				matching a format is detected, detectors can find data that isn't personal, such as
+				numbers looking like credit card numbers, and miss data in formats they don't know.
+				Detectors should be complemented with removing fields known to hold personal data, with
+				the `remap` transform.
+				"""
+		}
+
+		actions: {
+			title: "Actions"
+			body: """
+				Each detector has its own action. Detectors are applied in order, so that data masked or
+				hashed by a detector is no longer found by the following ones, and a field is removed as
+				soon as a detector with the `remove` action finds data in it. Detectors with the `tag`
+				action leave data as is, and add their name to `tag_field`, so that logs with personal
+				data can be routed to storage with stricter access.
+
+				The `pii_redaction_detections_total` metric counts the data found by each detector, and
+				the action taken on it.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		pii_redaction_detections_total: components.sources.internal_metrics.output.metrics.pii_redaction_detections_total
+	}
+}
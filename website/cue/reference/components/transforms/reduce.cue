@@ -144,7 +144,26 @@ components: transforms: reduce: {
 		},
 	]
 
+	how_it_works: {
+		bounding_memory: {
+			title: "Bounding memory"
+			body: """
+				Groups are held in memory until their transaction ends, or until they expire after
+				`expire_after_ms`. When the events of each group are consecutive, as with the lines of
+				multi-line messages, `flush_on_group_change` flushes the group of the previous event as
+				soon as an event of another group is received, so that a single group is held at a
+				time. `max_bytes` flushes groups once their events reach a size, whatever their
+				transaction, so that memory stays bounded when transactions never end.
+
+				The `reduce_buffered_bytes` gauge reports the size of the events held by all groups,
+				and the `reduce_groups_flushed_total` counter the groups flushed early, by reason.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
-		stale_events_flushed_total: components.sources.internal_metrics.output.metrics.stale_events_flushed_total
+		reduce_buffered_bytes:       components.sources.internal_metrics.output.metrics.reduce_buffered_bytes
+		reduce_groups_flushed_total: components.sources.internal_metrics.output.metrics.reduce_groups_flushed_total
+		stale_events_flushed_total:  components.sources.internal_metrics.output.metrics.stale_events_flushed_total
 	}
 }
@@ -0,0 +1,85 @@
+package metadata
+
+components: transforms: remote_lookup: {
+	title: "Remote Lookup"
+
+	description: """
+		Enriches events with the results of lookups of HTTP endpoints or Redis keys, rendered from
+		templates, for data that changes too often to be kept in enrichment tables.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		enrich: {
+			from: service: {
+				name:     "Redis"
+				url:      urls.redis
+				versions: null
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.remote_lookup.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		lookups: {
+			title: "Lookups"
+			body: """
+				The lookup of each event is rendered from its templates: the `uri` and `body` of HTTP
+				requests, or the `key` of Redis strings. HTTP responses with a successful status, and
+				existing Redis strings, are inserted into `target_field`, parsed as JSON when they are
+				JSON. HTTP responses with the `404 Not Found` status, and missing Redis keys, find
+				nothing, and their events are passed without `target_field`.
+
+				Unlike [enrichment tables](\(urls.enrichment_tables_concept)), which are loaded in
+				memory, lookups are made while events go through the transform, so they always have the
+				latest data, at the cost of the latency of a request per event that isn't cached.
+				"""
+		}
+
+		concurrency_and_caching: {
+			title: "Concurrency and Caching"
+			body: """
+				Up to `concurrency` lookups are in flight at once, and events are emitted in the order
+				they were received. With `cache`, the results of lookups are kept for `ttl_secs`, by their
+				rendered request, so that events with the same lookup only make one request during that
+				time.
+				"""
+		}
+
+		failures: {
+			title: "Failures"
+			body: """
+				Lookups fail when requests fail, are answered with an unsuccessful status other than
+				`404 Not Found`, or take longer than `timeout_secs`, and when the templates of their
+				event fail to render. Events whose lookup fails are passed without `target_field`, or
+				with `fallback` when it's set, or are dropped when `on_error` is `drop`. Failed lookups
+				aren't cached, so that they are retried for the following events.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		remote_lookup_cache_hits_total:   components.sources.internal_metrics.output.metrics.remote_lookup_cache_hits_total
+		remote_lookup_cache_misses_total: components.sources.internal_metrics.output.metrics.remote_lookup_cache_misses_total
+	}
+}
@@ -94,5 +94,39 @@ components: transforms: route: {
 			name:        "<route_id>"
 			description: "Each route can be referenced as an input by other components with the name `<transform_name>.<route_id>`."
 		},
+		{
+			name:        "<output_name>"
+			description: "With `split` or `route_table`, each declared output can be referenced as an input by other components with the name `<transform_name>.<output_name>`."
+		},
 	]
+
+	how_it_works: {
+		dynamic_routes: {
+			title: "Dynamic routes"
+			body: """
+				With `route_table`, the output of each event is looked up in an
+				[enrichment table](\(urls.enrichment_tables_concept)), by the value of its
+				`route_table.event_field`, such as its tenant. Since outputs are referenced by other
+				components, all outputs the table may route to are declared in `route_table.outputs`,
+				but the assignment of keys to them is read from the table. Routing events of a new
+				tenant to an existing output only takes adding a row to the table, which is reloaded
+				without reloading other components.
+
+				Events are sent both to the routes whose conditions they match and to the output of
+				the table. Events that match neither are sent to the `_unmatched` output.
+				"""
+		}
+		weighted_splitting: {
+			title: "Weighted splitting"
+			body: """
+				Rather than routing events by conditions, `split` sends each event to a single one of
+				its outputs, each receiving its weight's share of events. With weights of `5` and `95`,
+				5% of events are sent to the first output, such as to canary a new sink.
+
+				When `split.key_field` is set, the output is picked by the hash of that field, so that
+				all events with the same value, such as those of a user, are sent to the same output.
+				Events without the field, or all events without `key_field`, are assigned at random.
+				"""
+		}
+	}
 }
@@ -32,6 +32,31 @@ components: transforms: sample: {
 		traces:  true
 	}
 
+	how_it_works: {
+		consistent_sampling: {
+			title: "Consistent Sampling"
+			body: """
+				Without `key_field`, the transform passes one out of every `rate` events. With
+				`key_field`, it hashes the value of the field instead, so that all events with the same
+				value, such as the events of a trace or a session, are either passed or dropped
+				together, by all Vector instances. An event is passed when its hash is among the lowest
+				`1/rate` of all hashes, so that the values passed at a rate are also passed at all
+				lower rates, and changing rates doesn't break up the events of values already passed.
+				"""
+		}
+
+		dynamic_rates: {
+			title: "Dynamic Rates"
+			body: """
+				Events can be sampled at different rates. The rate of each event is taken from its
+				`rate_field`, when it holds a positive integer, then from the row of the
+				[enrichment table](\(urls.enrichment_tables_concept)) of `rate_table` matching the
+				value of its `rate_table.event_field`, and finally from `rate`. The rate each passed
+				event was sampled at is added to its `sample_rate` field.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
@@ -0,0 +1,79 @@
+package metadata
+
+components: transforms: schema_enforcement: {
+	title: "Schema Enforcement"
+
+	description: """
+		Validates logs against a JSON Schema, coercing their values to its types when enabled, and
+		sends those that don't conform to it to the `rejected` output, along with their violations.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.schema_enforcement.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "rejected"
+			description: """
+				Logs that don't conform to the schema are sent to the `rejected` output, with their
+				violations in `violations_field`, and their values as they were received, even with
+				`coerce` enabled. For a transform component named `foo`, this output can be accessed by
+				specifying `foo.rejected` as the input to another component.
+				"""
+		},
+	]
+
+	how_it_works: {
+		contract_boundary: {
+			title: "Contract Boundary"
+			body: """
+				The transform is meant to sit where logs cross from one team to another, such as
+				in front of the sinks of a shared pipeline, so that logs are only passed on when they
+				conform to the schema agreed on by both teams. Logs that don't conform are sent to the
+				`rejected` output, which can be routed to a sink of its own, such as a dead letter
+				queue, for the producers of the logs to fix them.
+
+				Violations are objects with the `path` of the value violating the schema, such as
+				`.user.id` or `.tags[1]`, and a `message` describing the violation, such as
+				`expected integer, got string`.
+				"""
+		}
+
+		coercion: {
+			title: "Coercion"
+			body: """
+				With `coerce` enabled, logs that don't conform to the schema as they are have their
+				values converted to the types of the schema where they can be: strings of integers,
+				numbers, and booleans to them, and integers, numbers, and booleans to strings. Logs are
+				either passed with all of their values coerced, or rejected with none of them coerced.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		schema_enforcement_rejected_events_total: components.sources.internal_metrics.output.metrics.schema_enforcement_rejected_events_total
+	}
+}
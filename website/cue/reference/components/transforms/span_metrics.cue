@@ -0,0 +1,97 @@
+package metadata
+
+components: transforms: span_metrics: {
+	title: "Span Metrics"
+
+	description: """
+		Derives request, error, and duration metrics from spans, aggregated by service and
+		operation.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      true
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.span_metrics.configuration
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	output: metrics: {
+		span_calls_total: {
+			description:       "The number of spans."
+			type:              "counter"
+			default_namespace: "traces"
+			tags:              _span_tags
+		}
+		span_errors_total: {
+			description:       "The number of spans whose status is that of errors."
+			type:              "counter"
+			default_namespace: "traces"
+			tags:              _span_tags
+		}
+		span_duration_seconds: {
+			description:       "The duration of spans, from their start to their end."
+			type:              "histogram"
+			default_namespace: "traces"
+			tags:              _span_tags
+		}
+	}
+
+	_span_tags: {
+		service: {
+			description: "The service of the spans, from `service_field`."
+			required:    false
+		}
+		operation: {
+			description: "The operation of the spans, from `operation_field`."
+			required:    false
+		}
+	}
+
+	how_it_works: {
+		spans: {
+			title: "Spans"
+			body: """
+				The transform consumes spans in the shape of those of the `opentelemetry` source, with
+				one span per trace event. The errors of spans are those whose `status.code` is `2`,
+				and their durations are the time from `start_time_unix_nano` to `end_time_unix_nano`.
+				Spans without both timestamps are counted, but don't have a duration.
+
+				Spans are consumed: to also send them to a sink, add that sink alongside this transform
+				as a consumer of their source.
+				"""
+		}
+
+		aggregation: {
+			title: "Aggregation"
+			body: """
+				Spans are aggregated into a series for each combination of their service, their
+				operation, and their `dimensions`, whose metrics are emitted every
+				`flush_interval_secs` as incremental metrics of the spans since the previous emission,
+				and once more when Vector stops. Series without spans since the previous emission
+				aren't emitted. As each distinct value of a dimension creates series, dimensions
+				should be fields with few values, such as HTTP methods or status codes, rather than
+				identifiers.
+
+				Spans are only acknowledged once the metrics of their series are.
+				"""
+		}
+	}
+}
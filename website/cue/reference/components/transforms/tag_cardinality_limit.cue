@@ -171,6 +171,24 @@ components: transforms: tag_cardinality_limit: {
 				"""
 		}
 
+		limits: {
+			title: "Limits"
+			body: """
+				Each key accepts `value_limit` distinct values, unless it has a limit of its own in
+				`tag_value_limits`. Metrics whose names are in `per_metric_limits` can override these
+				limits, and `limit_exceeded_action`, and the values of their tags are tracked
+				separately from those of all other metrics, so that one noisy metric doesn't exhaust
+				the limits of others. Limits that aren't overridden for a metric are those of all
+				metrics, with the limits of keys in `tag_value_limits` taking precedence over its
+				`value_limit`.
+
+				With the `replace_tag` action, the values of tags exceeding their limit are replaced
+				with `overflow_value`, rather than dropped, so that sinks aggregate the metrics into a
+				series of their own. The `tag_key` tag of the `tag_value_limit_exceeded_total` and
+				`value_limit_reached_total` metrics tells which keys hit their limits.
+				"""
+		}
+
 		restarts: {
 			title: "Restarts"
 			body: """
@@ -0,0 +1,74 @@
+package metadata
+
+components: transforms: tail_sampling: {
+	title: "Tail Sampling"
+
+	description: """
+		Buffers the spans of traces for a decision window, and either keeps or drops whole traces
+		depending on sampling policies.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.tail_sampling.configuration
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	how_it_works: {
+		decision_window: {
+			title: "Decision Window"
+			body: """
+				Spans are grouped in traces by their `trace_id_field`, and buffered until
+				`decision_wait_secs` after the first span of their trace. The policies then decide
+				whether the trace is kept, in which case all of its spans are emitted, or dropped, in
+				which case none of them are. Spans of a trace arriving after its decision follow it
+				without being buffered, for another `decision_wait_secs`, after which they start a new
+				trace. Once `max_traces` traces are buffered, the oldest ones are decided early, and all
+				buffered traces are decided when Vector shuts down.
+
+				Spans are expected to be those of the `opentelemetry` source, with one event per span,
+				so that the transform enables tail sampling between OTLP sources and sinks. All spans of
+				a trace must go through the same Vector instance to be decided together.
+				"""
+		}
+
+		policies: {
+			title: "Policies"
+			body: """
+				Traces are kept when any policy keeps them:
+
+				- `error` keeps traces with a span whose `status.code` is `2`, the code of errors in
+				  OpenTelemetry.
+				- `latency` keeps traces whose duration, from the earliest `start_time_unix_nano` to the
+				  latest `end_time_unix_nano` of their spans, is at least `threshold_ms`.
+				- `probabilistic` keeps one out of every `rate` traces, by the hash of their trace ID, so
+				  that instances decide the same way for the same traces.
+				- `condition` keeps traces with a span matching a condition.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		tail_sampling_traces_dropped_total: components.sources.internal_metrics.output.metrics.tail_sampling_traces_dropped_total
+		tail_sampling_traces_kept_total:    components.sources.internal_metrics.output.metrics.tail_sampling_traces_kept_total
+	}
+}
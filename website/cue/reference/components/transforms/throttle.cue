@@ -112,5 +112,22 @@ components: transforms: throttle: {
 				},
 			]
 		}
+
+		distributed_rate_limiting: {
+			title: "Distributed Rate Limiting"
+			body: """
+				By default, each Vector instance rate limits the events it processes on its own, so that
+				several instances enforcing the same rate limit allow up to `threshold` events per
+				`window_secs` each. With the `redis` backend, instances count the events of each bucket
+				in [Redis](\(urls.redis)) instead, so that they share the `threshold` of each bucket.
+
+				Instances sharing a rate limit must use the same `backend.key_prefix`. Events are
+				counted in fixed windows of `window_secs`, aligned on the clocks of instances, rather
+				than with the Generic Cell Rate Algorithm, so that up to the `threshold` of each window
+				can be allowed in a burst. Each event not excluded adds a round-trip to Redis, and events
+				are allowed while Redis can't be reached, which is reported by the
+				`component_errors_total` metric.
+				"""
+		}
 	}
 }
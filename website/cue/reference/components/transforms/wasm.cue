@@ -0,0 +1,93 @@
+package metadata
+
+components: transforms: wasm: {
+	title: "WebAssembly"
+
+	description: """
+		Processes logs with [WebAssembly](\(urls.wasm)) modules, which can read, set, and remove
+		the fields of logs, emit new logs, and drop logs, within limits of fuel and memory.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		program: {
+			runtime: {
+				name:    "WebAssembly"
+				url:     urls.wasm
+				version: null
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				Modules run on the threads of the topology, so slow modules hold up the other
+				components sharing their thread. Use `fuel` to bound how long each log is processed
+				for.
+				""",
+		]
+		notices: []
+	}
+
+	configuration: base.components.transforms.wasm.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		guest_abi: {
+			title: "Guest ABI"
+			body: """
+				Modules can be written in any of the [languages compiling to WebAssembly](\(urls.wasm_languages)),
+				such as Rust, Go, or AssemblyScript, and must export:
+
+				* `memory`: the memory the host reads strings from and writes them to.
+				* `vector_alloc(len: i32) -> i32`: allocates `len` bytes and returns a pointer to them,
+				  which the host writes the values it returns into. They're owned by the module.
+				* `vector_process() -> i32`: processes the current log, returning `0` to drop it, and
+				  any other number to keep it.
+				* `vector_init()`, optionally: called once each time the module is instantiated.
+
+				While processing a log, modules can import these functions from the `vector` module,
+				with paths such as `message` or `user.id`, and values as JSON, both in UTF-8:
+
+				* `get_field(path_ptr: i32, path_len: i32) -> i64`: writes the value of the field into
+				  memory allocated with `vector_alloc`, and returns its pointer in the upper 32 bits and
+				  its length in the lower ones, or `-1` when the log doesn't have the field.
+				* `set_field(path_ptr: i32, path_len: i32, value_ptr: i32, value_len: i32) -> i32`:
+				  sets the field, returning `0`, or `-1` when the path or the value isn't valid.
+				* `remove_field(path_ptr: i32, path_len: i32) -> i32`: removes the field, returning `0`,
+				  or `-1` when the log doesn't have it.
+				* `emit(value_ptr: i32, value_len: i32) -> i32`: emits a new log with the fields of the
+				  JSON object, after the current log, returning `0`, or `-1` when the value isn't a JSON
+				  object. New logs are acknowledged along with the current one.
+				"""
+		}
+
+		limits: {
+			title: "Limits"
+			body: """
+				Each WebAssembly instruction consumes fuel, and the module is given `fuel` for each log,
+				so that a log whose processing runs out of it is dropped instead of blocking the
+				transform. Memory is limited to `memory_limit` as a whole. Modules trapping, such as by
+				running out of fuel, are instantiated again for the next logs, as they may have been
+				left in any state, which resets their global variables as well.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: cassandra: {
+	name:     "Apache Cassandra"
+	thing:    "an \(name) cluster"
+	url:      urls.cassandra
+	versions: ">= 3.0"
+
+	description: "[Apache Cassandra](\(urls.cassandra)) is an open source, distributed, wide-column store, designed to handle large amounts of data across many servers with no single point of failure. Compatible databases, such as [ScyllaDB](\(urls.scylladb)), are supported as well."
+}
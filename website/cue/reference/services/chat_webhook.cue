@@ -0,0 +1,10 @@
+package metadata
+
+services: chat_webhook: {
+	name:     "chat webhooks"
+	thing:    "a Slack, Mattermost, or Microsoft Teams channel"
+	url:      urls.slack_webhooks
+	versions: null
+
+	description: "Incoming webhooks post messages to the channels of chat platforms, such as [Slack](\(urls.slack_webhooks)), [Mattermost](\(urls.mattermost_webhooks)), and [Microsoft Teams](\(urls.teams_webhooks))."
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: cloudflare: {
+	name:     "Cloudflare"
+	thing:    "a \(name) zone"
+	url:      urls.cloudflare
+	versions: null
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: delta_lake: {
+	name:     "Delta Lake"
+	thing:    "a \(name) table"
+	url:      urls.delta_lake
+	versions: null
+
+	description: "[Delta Lake](\(urls.delta_lake)) is an open table format adding ACID transactions, schema enforcement and time travel to Parquet data files stored in object stores, through a transaction log kept next to the data of tables."
+}
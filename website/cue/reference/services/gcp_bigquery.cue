@@ -0,0 +1,10 @@
+package metadata
+
+services: gcp_bigquery: {
+	name:     "GCP BigQuery"
+	thing:    "a \(name) table"
+	url:      urls.gcp_bigquery
+	versions: null
+
+	description: "[Google BigQuery](\(urls.gcp_bigquery)) is a serverless, highly scalable data warehouse on Google Cloud Platform, which tables can be written to in real time through its Storage Write API."
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: github: {
+	name:     "GitHub"
+	thing:    "a \(name) enterprise"
+	url:      urls.github
+	versions: null
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: iceberg: {
+	name:     "Apache Iceberg"
+	thing:    "an \(name) table"
+	url:      urls.iceberg
+	versions: null
+
+	description: "[Apache Iceberg](\(urls.iceberg)) is an open table format for large analytic datasets, which brings ACID commits, schema evolution and hidden partitioning to data files stored in object stores, and lets engines such as Spark, Trino and Flink query the same tables."
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: imap: {
+	name:     "IMAP"
+	thing:    "an \(name) server"
+	url:      urls.imap
+	versions: null
+}
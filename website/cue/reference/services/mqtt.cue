@@ -0,0 +1,10 @@
+package metadata
+
+services: mqtt: {
+	name:     "MQTT"
+	thing:    "an \(name) broker"
+	url:      urls.mqtt
+	versions: null
+
+	description: "[MQTT](\(urls.mqtt)) is a lightweight publish/subscribe messaging protocol, designed for connecting IoT devices over unreliable networks, and spoken by brokers such as Mosquitto, EMQX, HiveMQ and the IoT platforms of cloud providers."
+}
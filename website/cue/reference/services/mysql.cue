@@ -0,0 +1,10 @@
+package metadata
+
+services: mysql: {
+	name:     "MySQL"
+	thing:    "a \(name) server"
+	url:      urls.mysql
+	versions: ">= 5.7"
+
+	description: "[MySQL](\(urls.mysql)) is a widely used open source relational database, also available as the compatible MariaDB fork."
+}
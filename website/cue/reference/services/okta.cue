@@ -0,0 +1,8 @@
+package metadata
+
+services: okta: {
+	name:     "Okta"
+	thing:    "an \(name) organization"
+	url:      urls.okta
+	versions: null
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: pagerduty: {
+	name:     "PagerDuty"
+	thing:    "a \(name) account"
+	url:      urls.pagerduty
+	versions: null
+
+	description: "[PagerDuty](\(urls.pagerduty)) is an incident response platform, which pages the teams on call for the alerts of their services."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: smtp: {
+	name:     "SMTP"
+	thing:    "an SMTP server"
+	url:      urls.smtp
+	versions: null
+
+	description: "The [Simple Mail Transfer Protocol](\(urls.smtp)) is the protocol email is sent with, which mail servers and email providers accept messages over."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: snowflake: {
+	name:     "Snowflake"
+	thing:    "a \(name) table"
+	url:      urls.snowflake
+	versions: null
+
+	description: "[Snowflake](\(urls.snowflake)) is a cloud data platform, which stores and queries structured and semi-structured data in tables of its data warehouses."
+}
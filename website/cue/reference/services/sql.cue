@@ -0,0 +1,10 @@
+package metadata
+
+services: sql: {
+	name:     "SQL"
+	thing:    "a PostgreSQL, MySQL, or SQLite database"
+	url:      urls.sql
+	versions: null
+
+	description: "[SQL](\(urls.sql)) databases, such as [PostgreSQL](\(urls.postgresql)), [MySQL](\(urls.mysql)), and [SQLite](\(urls.sqlite)), store data as rows of tables, written with SQL statements."
+}
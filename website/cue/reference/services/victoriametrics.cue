@@ -0,0 +1,10 @@
+package metadata
+
+services: victoriametrics: {
+	name:     "VictoriaMetrics"
+	thing:    "a \(name) database"
+	url:      urls.victoriametrics
+	versions: null
+
+	description: "[VictoriaMetrics](\(urls.victoriametrics)) is a fast, cost-effective and scalable time series database, which can be run as a single node or as a cluster."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: windows_event_log: {
+	name:     "Windows Event Log"
+	thing:    "\(name) channels"
+	url:      urls.windows_event_log
+	versions: null
+
+	description: "The [Windows Event Log](\(urls.windows_event_log)) is the standard, centralized store of Windows for the events of the operating system, its services, and applications."
+}
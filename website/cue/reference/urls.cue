@@ -19,6 +19,7 @@ urls: {
 	apache_extended_status:                     "\(apache)/docs/current/mod/core.html#extendedstatus"
 	apache_install:                             "\(apache)/docs/current/install.html"
 	apache_mod_status:                          "http://httpd.apache.org/docs/current/mod/mod_status.html"
+	apache_parquet:                             "https://parquet.apache.org/"
 	apt:                                        "\(wikipedia)/wiki/APT_(software)"
 	arm:                                        "\(wikipedia)/wiki/ARM_architecture"
 	aws_access_keys:                            "\(aws_docs)/IAM/latest/UserGuide/id_credentials_access-keys.html"
@@ -53,9 +54,11 @@ urls: {
 	aws_elb:                                    "https://aws.amazon.com/elasticloadbalancing/"
 	aws_elb_access_format:                      "\(aws_docs)/elasticloadbalancing/latest/application/load-balancer-access-logs.html#access-log-entry-examples"
 	aws_elb_https:                              "\(aws_docs)/elasticloadbalancing/latest/classic/elb-create-https-ssl-load-balancer.html"
+	aws_glue_data_catalog:                      "\(aws_docs)/glue/latest/dg/components-overview.html#data-catalog-intro"
 	aws_iam:                                    "\(aws_docs)/IAM/latest/UserGuide/introduction.html"
 	aws_iam_role:                               "\(aws_docs)/IAM/latest/UserGuide/id_roles.html"
 	aws_imds_v1_security_problems:              "https://aws.amazon.com/blogs/security/defense-in-depth-open-firewalls-reverse-proxies-ssrf-vulnerabilities-ec2-instance-metadata-service/"
+	aws_kinesis_aggregation:                    "https://github.com/awslabs/amazon-kinesis-producer/blob/master/aggregation-format.md"
 	aws_kinesis_firehose:                       "https://aws.amazon.com/kinesis/data-firehose/"
 	aws_kinesis_firehose_http_protocol:         "\(aws_docs)/firehose/latest/dev/create-destination.html#create-destination-http"
 	aws_firehose_http_request_spec:             "\(aws_docs)/firehose/latest/dev/httpdeliveryrequestresponse.html"
@@ -77,6 +80,7 @@ urls: {
 	aws_s3_endpoints:                           "\(aws_docs)/general/latest/gr/rande.html#s3_endpoint"
 	aws_s3_grantee:                             "\(aws_docs)/AmazonS3/latest/dev/acl-overview.html#specifying-grantee"
 	aws_s3_metadata:                            "\(aws_docs)/AmazonS3/latest/dev/UsingMetadata.html#object-metadata"
+	aws_s3_object_lock:                         "\(aws_docs)/AmazonS3/latest/userguide/object-lock.html"
 	aws_s3_regions:                             "\(aws_docs)/general/latest/gr/rande.html#s3_region"
 	aws_s3_server_access_logs:                  "\(aws_docs)/AmazonS3/latest/dev/ServerLogs.html"
 	aws_s3_service_limits:                      "\(aws_docs)/streams/latest/dev/service-sizes-and-limits.html"
@@ -86,12 +90,17 @@ urls: {
 	aws_sqs:                                    "https://aws.amazon.com/sqs/"
 	aws_sqs_api:                                "\(aws_docs)/AWSSimpleQueueService/latest/APIReference/Welcome.html"
 	aws_sqs_create:                             "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/sqs-configure-create-queue.html"
+	aws_sqs_fifo:                               "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/FIFO-queues.html"
 	aws_sqs_message_deduplication_id:           "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/using-messagededuplicationid-property.html"
 	aws_vpc_flow_logs:                          "\(aws_docs)/vpc/latest/userguide/flow-logs.html"
 	axiom:                                      "https://axiom.co"
 	axiom_cloud:                                "https://cloud.axiom.co"
 	azure_blob:                                 "https://azure.microsoft.com/en-us/services/storage/blobs/"
 	azure_blob_endpoints:                       "https://docs.microsoft.com/en-us/rest/api/storageservices/blob-service-rest-api"
+	azure_data_explorer:                        "https://azure.microsoft.com/en-us/products/data-explorer/"
+	azure_data_explorer_ingestion:              "https://learn.microsoft.com/en-us/azure/data-explorer/ingest-data-overview"
+	azure_data_explorer_ingestion_mappings:     "https://learn.microsoft.com/en-us/azure/data-explorer/kusto/management/mappings"
+	azure_data_explorer_streaming_ingestion:    "https://learn.microsoft.com/en-us/azure/data-explorer/ingest-data-streaming"
 	azure_monitor:                              "https://azure.microsoft.com/en-us/services/monitor/"
 	azure_monitor_logs_endpoints:               "https://docs.microsoft.com/en-us/rest/api/monitor/"
 	base64:                                     "\(wikipedia)/wiki/Base64"
@@ -103,11 +112,18 @@ urls: {
 	bind_dnstap:                                "https://kb.isc.org/docs/aa-01342"
 	b_tree_map:                                 "https://doc.rust-lang.org/std/collections/struct.BTreeMap.html"
 	cargo_audit:                                "\(github)/RustSec/cargo-audit"
+	cassandra:                                  "https://cassandra.apache.org/"
+	cassandra_batch:                            "https://cassandra.apache.org/doc/latest/cassandra/cql/dml.html#batch_statement"
 	centos:                                     "https://www.centos.org/"
 	chrono_time_formats:                        "https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers"
 	cgroups_limit_resources:                    "https://the.binbashtheory.com/control-resources-cgroups/"
 	clickhouse:                                 "https://clickhouse.yandex/"
+	clickhouse_async_insert:                    "https://clickhouse.com/docs/en/optimize/asynchronous-inserts"
 	clickhouse_http:                            "https://clickhouse.yandex/docs/en/interfaces/http/"
+	clickhouse_native:                          "https://clickhouse.com/docs/en/interfaces/tcp"
+	cloudflare:                                 "https://www.cloudflare.com/"
+	cloudflare_logpull:                         "https://developers.cloudflare.com/logs/logpull/"
+	cloudflare_logpush_http:                    "https://developers.cloudflare.com/logs/get-started/enable-destinations/http/"
 	cloudsmith:                                 "https://cloudsmith.io/~timber/repos/vector/packages/"
 	cloudsmith_apt:                             "https://cloudsmith.io/~timber/repos/vector/setup/#formats-deb"
 	cloudsmith_yum:                             "https://cloudsmith.io/~timber/repos/vector/setup/#formats-rpm"
@@ -140,6 +156,8 @@ urls: {
 	debian:                                     "https://www.debian.org/"
 	debian_system_groups:                       "https://wiki.debian.org/SystemGroups"
 	default_configuration:                      "\(vector_repo)/blob/master/config/vector.toml"
+	delta_lake:                                 "https://delta.io/"
+	delta_lake_protocol:                        "https://github.com/delta-io/delta/blob/master/PROTOCOL.md"
 	dnstap:                                     "http://dnstap.info/"
 	docker:                                     "https://www.docker.com/"
 	docker_alpine:                              "\(docker_hub)/_/alpine"
@@ -203,6 +221,8 @@ urls: {
 	gcp_authentication_api_key:                 "\(gcp)/docs/authentication/api-keys"
 	gcp_authentication_server_to_server:        "\(gcp)/docs/authentication/production"
 	gcp_authentication_service_account:         "\(gcp)/docs/authentication/production#obtaining_and_providing_service_account_credentials_manually"
+	gcp_bigquery:                               "\(gcp)/bigquery"
+	gcp_bigquery_storage_write_api:             "\(gcp)/bigquery/docs/write-api"
 	gcp_cloud_storage:                          "\(gcp)/storage"
 	gcp_chronicle:                              "https://chronicle.security"
 	gcp_folders:                                "\(gcp)/resource-manager/docs/creating-managing-folders"
@@ -222,8 +242,10 @@ urls: {
 	gcs_predefined_acl:                         "\(gcp)/storage/docs/access-control/lists#predefined-acl"
 	gcs_storage_classes:                        "\(gcp)/storage/docs/storage-classes"
 	gcs_custom_metadata:                        "\(gcp)/storage/docs/metadata#custom-metadata"
+	gcs_cmek:                                   "\(gcp)/storage/docs/encryption/customer-managed-keys"
 	git:                                        "https://git-scm.com/"
 	github:                                     "https://github.com"
+	github_audit_log_api:                       "https://docs.github.com/en/enterprise-cloud@latest/rest/enterprise-admin/audit-log"
 	github_protected_branches:                  "https://help.github.com/en/github/administering-a-repository/about-protected-branches"
 	github_sign_commits:                        "https://help.github.com/en/github/authenticating-to-github/signing-commits"
 	globbing:                                   "\(wikipedia)/wiki/Glob_(programming)"
@@ -234,6 +256,7 @@ urls: {
 	grok:                                       "https://grokdebug.herokuapp.com/"
 	grok_debugger:                              "https://grokdebug.herokuapp.com/"
 	grok_patterns:                              "\(github)/daschl/grok/tree/master/patterns"
+	grpc:                                       "https://grpc.io/"
 	gzip:                                       "https://www.gzip.org/"
 	haproxy:                                    "https://www.haproxy.org/"
 	helm:                                       "https://helm.sh/"
@@ -256,8 +279,12 @@ urls: {
 	iam_instance_profile:                       "\(aws_docs)/IAM/latest/UserGuide/id_roles_use_switch-role-ec2_instance-profiles.html"
 	iana_time_zone_format:                      "\(wikipedia)/wiki/Tz_database#Names_of_time_zones"
 	iana_time_zones:                            "\(wikipedia)/wiki/List_of_tz_database_time_zones"
+	iceberg:                                    "https://iceberg.apache.org/"
+	iceberg_rest_catalog:                       "https://github.com/apache/iceberg/blob/main/open-api/rest-catalog-open-api.yaml"
 	ieee_754:                                   "\(wikipedia)/wiki/IEEE_754"
 	ietf_rfc_6750:                              "https://tools.ietf.org/html/rfc6750"
+	imap:                                       "https://datatracker.ietf.org/doc/html/rfc9051"
+	imap_search:                                "https://datatracker.ietf.org/doc/html/rfc9051#name-search-command"
 	initd:                                      "https://bash.cyberciti.biz/guide//etc/init.d"
 	influxdb:                                   "https://www.influxdata.com/products/influxdb-overview/"
 	influxdb_http_api_v1:                       "https://docs.influxdata.com/influxdb/latest/tools/api/#write-http-endpoint"
@@ -335,6 +362,7 @@ urls: {
 	macos:                                      "\(wikipedia)/wiki/MacOS"
 	magic_bytes:                                "\(wikipedia)/wiki/List_of_file_signatures"
 	mailing_list:                               "/community/"
+	mattermost_webhooks:                        "https://developers.mattermost.com/integrate/webhooks/incoming/"
 	maxmind:                                    "https://www.maxmind.com/en/home"
 	maxmind_db_file_format:                     "https://maxmind.github.io/MaxMind-DB/"
 	maxmind_geoip2:                             "https://dev.maxmind.com/geoip/geoip2/downloadable"
@@ -347,11 +375,18 @@ urls: {
 	metric_event_source:                        "\(vector_repo)/blob/master/src/event/metric.rs"
 	mlua:                                       "\(github)/khvzak/mlua"
 	mongodb:                                    "https://www.mongodb.com"
+	mongodb_change_streams:                     "https://www.mongodb.com/docs/manual/changeStreams/"
 	mongodb_command_server_status:              "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:       "https://docs.mongodb.com/manual/reference/connection-string/"
+	mongodb_pre_and_post_images:                "https://www.mongodb.com/docs/manual/changeStreams/#change-streams-with-document-pre--and-post-images"
+	mqtt:                                       "https://mqtt.org/"
+	mqtt_specification:                         "https://docs.oasis-open.org/mqtt/mqtt/v3.1.1/mqtt-v3.1.1.html"
 	musl_builder_docker_image:                  "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
 	native_proto_schema:                        "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
 	native_json_schema:                         "\(vector_repo)/blob/master/lib/codecs/tests/data/native_encoding/schema.cue"
+	mysql:                                      "https://www.mysql.com/"
+	mysql_binlog:                               "https://dev.mysql.com/doc/refman/8.0/en/binary-log.html"
+	mysql_gtids:                                "https://dev.mysql.com/doc/refman/8.0/en/replication-gtids-concepts.html"
 	nats:                                       "https://nats.io/"
 	nats_rs:                                    "\(github)/nats-io/nats.rs"
 	new_bug_report:                             "\(vector_repo)/issues/new?labels=type%3A+bug"
@@ -371,10 +406,15 @@ urls: {
 	nix:                                        "https://nixos.org/nix/"
 	nixos:                                      "https://nixos.org/"
 	nixpkgs_9682:                               "\(github)/NixOS/nixpkgs/issues/9682"
+	okta:                                       "https://www.okta.com/"
+	okta_rate_limits:                           "https://developer.okta.com/docs/reference/rl-best-practices/"
+	okta_system_log_api:                        "https://developer.okta.com/docs/reference/api/system-log/"
 	openssl:                                    "https://www.openssl.org/"
 	opentelemetry:                              "https://opentelemetry.io"
 	opentelemetry_protocol:                     "\(opentelemetry)/docs/reference/specification/protocol/otlp/"
 	order_of_ops:                               "\(wikipedia)/wiki/Order_of_operations"
+	pagerduty:                                  "https://www.pagerduty.com/"
+	pagerduty_events_api:                       "https://developer.pagerduty.com/docs/events-api-v2/overview/"
 	papertrail:                                 "https://www.papertrail.com/"
 	papertrail_syslog:                          "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"
 	perl_windows:                               "https://www.perl.org/get.html#win32"
@@ -389,8 +429,12 @@ urls: {
 	percent_encoding_www_form_urlencoded:       "https://url.spec.whatwg.org/#application-x-www-form-urlencoded-percent-encode-set"
 	posix_acls:                                 "https://www.usenix.org/legacy/publications/library/proceedings/usenix03/tech/freenix03/full_papers/gruenbacher/gruenbacher_html/main.html"
 	postgresql:                                 "https://www.postgresql.org/"
+	postgresql_logical_decoding:                "https://www.postgresql.org/docs/current/logicaldecoding-explanation.html"
+	postgresql_publication:                     "https://www.postgresql.org/docs/current/sql-createpublication.html"
+	postgresql_copy:                            "https://www.postgresql.org/docs/current/sql-copy.html"
 	postgresql_csvlog:                          "https://www.postgresql.org/docs/current/runtime-config-logging.html#RUNTIME-CONFIG-LOGGING-CSVLOG"
 	postgresql_matching:                        "https://www.postgresql.org/docs/current/functions-matching.html#FUNCTIONS-POSIX-REGEXP"
+	postgresql_upsert:                          "https://www.postgresql.org/docs/current/sql-insert.html#SQL-ON-CONFLICT"
 	procfs:                                     "https://en.wikipedia.org/wiki/Procfs#:~:text=The%20proc%20filesystem%20(procfs)%20is,in%20the%20kernel%20than%20traditional"
 	prometheus:                                 "https://prometheus.io/"
 	prometheus_client:                          "https://prometheus.io/docs/instrumenting/clientlibs/"
@@ -407,8 +451,11 @@ urls: {
 	prometheus_remote_write:                    "https://prometheus.io/docs/prometheus/latest/configuration/configuration/#remote_write"
 	prometheus_remote_write_protocol:           "https://docs.google.com/document/d/1LPhVRSFkGNSuU1fBd81ulhsCPR4hkSZyyBj1SZ8fWOM/edit#heading=h.n0d0vphea3fe"
 	protobuf:                                   "https://developers.google.com/protocol-buffers"
+	protobuf_descriptor_set:                    "https://protobuf.dev/programming-guides/techniques/#self-description"
 	pulsar:                                     "https://pulsar.apache.org/"
 	pulsar_protocol:                            "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
+	questdb:                                    "https://questdb.io/"
+	questdb_ilp:                                "https://questdb.io/docs/reference/api/ilp/overview/"
 	raspbian:                                   "https://www.raspbian.org/"
 	rdkafka:                                    "\(github)/edenhill/librdkafka"
 	regex:                                      "\(wikipedia)/wiki/Regular_expression"
@@ -443,6 +490,7 @@ urls: {
 	rustup:                                     "https://rustup.rs"
 	redis:                                      "https://redis.io"
 	redis_rs:                                   "https://github.com/mitsuhiko/redis-rs"
+	scylladb:                                   "https://www.scylladb.com/"
 	sematext:                                   "https://sematext.com"
 	sematext_create_logs_app:                   "https://apps.sematext.com/ui/integrations"
 	sematext_es:                                "https://sematext.com/docs/logs/index-events-via-elasticsearch-api/"
@@ -450,12 +498,23 @@ urls: {
 	sematext_monitoring:                        "https://sematext.com/docs/monitoring/"
 	sematext_registration:                      "https://apps.sematext.com/ui/registration"
 	semver:                                     "https://semver.org/"
+	sentry:                                     "https://sentry.io/"
+	sentry_envelopes:                           "https://develop.sentry.dev/sdk/envelopes/"
+	sentry_rate_limits:                         "https://develop.sentry.dev/sdk/rate-limiting/"
 	sha1:                                       "\(wikipedia)/wiki/SHA-1"
 	sha2:                                       "\(wikipedia)/wiki/SHA-2"
 	sha3:                                       "\(wikipedia)/wiki/SHA-3"
+	sftp:                                       "\(wikipedia)/wiki/SSH_File_Transfer_Protocol"
 	signal:                                     "\(wikipedia)/wiki/Signal_(IPC)"
+	slack_mrkdwn:                               "https://api.slack.com/reference/surfaces/formatting"
+	slack_webhooks:                             "https://api.slack.com/messaging/webhooks"
+	smtp:                                       "https://datatracker.ietf.org/doc/html/rfc5321"
+	smtp_starttls:                              "https://datatracker.ietf.org/doc/html/rfc3207"
 	snake_case:                                 "\(wikipedia)/wiki/Snake_case"
 	snappy:                                     "https://google.github.io/snappy/"
+	snowflake:                                  "https://www.snowflake.com/"
+	snowflake_key_pair_auth:                    "https://docs.snowflake.com/en/user-guide/key-pair-auth"
+	snowflake_snowpipe_streaming:               "https://docs.snowflake.com/en/user-guide/data-load-snowpipe-streaming-overview"
 	socket:                                     "\(wikipedia)/wiki/Network_socket"
 	splunk:                                     "https://www.splunk.com"
 	splunk_hec:                                 "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
@@ -468,6 +527,10 @@ urls: {
 	splunk_hec_setup:                           "https://docs.splunk.com/Documentation/Splunk/latest/Data/UsetheHTTPEventCollector"
 	splunk_hec_metadata:                        "https://docs.splunk.com/Documentation/Splunk/latest/Data/FormateventsforHTTPEventCollector#Event_metadata"
 	specs_instrumentation:                      "\(vector_repo)/blob/master/docs/specs/instrumentation.md)"
+	sql:                                        "\(wikipedia)/wiki/SQL"
+	sqlite:                                     "https://www.sqlite.org/"
+	sqlite_upsert:                              "https://www.sqlite.org/lang_upsert.html"
+	ssh_known_hosts:                            "https://man.openbsd.org/sshd.8#SSH_KNOWN_HOSTS_FILE_FORMAT"
 	standard_streams:                           "\(wikipedia)/wiki/Standard_streams"
 	statsd:                                     "\(github)/statsd/statsd"
 	statsd_multi:                               "\(github)/statsd/statsd/blob/master/docs/metric_types.md#multi-metric-packets"
@@ -483,6 +546,7 @@ urls: {
 	syslog:                                     "\(wikipedia)/wiki/Syslog"
 	syslog_3164:                                "https://tools.ietf.org/html/rfc3164"
 	syslog_5424:                                "https://tools.ietf.org/html/rfc5424"
+	syslog_5425:                                "https://tools.ietf.org/html/rfc5425"
 	syslog_6587:                                "https://tools.ietf.org/html/rfc6587"
 	syslog_facility:                            "\(wikipedia)/wiki/Syslog#Facility"
 	syslog_levels:                              "\(wikipedia)/wiki/Syslog#Severity_level"
@@ -491,6 +555,7 @@ urls: {
 	systemd_resolved:                           "https://wiki.archlinux.org/index.php/Systemd-resolved"
 	tcp:                                        "\(wikipedia)/wiki/Transmission_Control_Protocol"
 	team:                                       "/community#team"
+	teams_webhooks:                             "https://learn.microsoft.com/en-us/microsoftteams/platform/webhooks-and-connectors/how-to/add-incoming-webhook"
 	timber:                                     "https://timber.io"
 	toml:                                       "\(github)/toml-lang/toml"
 	toml_array:                                 "\(github)/toml-lang/toml#array"
@@ -611,6 +676,8 @@ urls: {
 	vector_twitter:                             "https://twitter.com/vectordotdev"
 	vector_unit_tests:                          "\(vector_configuration)/unit-tests"
 	vector_version_branches:                    "\(vector_repo)/branches/all?query=v"
+	victoriametrics:                            "https://victoriametrics.com/"
+	victoriametrics_json_import:                "https://docs.victoriametrics.com/#how-to-import-data-in-json-line-format"
 	vrl_announcement:                           "/blog/vector-remap-language"
 	vrl_boolean_expression:                     "\(vrl_reference)#boolean-expressions"
 	vrl_error_handling:                         "\(vrl_errors_reference)#handling"
@@ -632,11 +699,14 @@ urls: {
 	vrl_safety:                                 "\(vrl_reference)#safety"
 	vrl_type_safety:                            "\(vrl_reference)#type-safety"
 	vote_feature:                               "\(vector_repo)/issues?q=is%3Aissue+is%3Aopen+sort%3Areactions-%2B1-desc+label%3A%22Type%3A+New+Feature%22"
+	wal2json:                                   "https://github.com/eulerto/wal2json"
 	wasm:                                       "https://webassembly.org/"
 	wasm_languages:                             "\(github)/appcypher/awesome-wasm-langs"
 	websocket:                                  "\(wikipedia)/wiki/WebSocket"
 	wikipedia:                                  "https://en.wikipedia.org"
 	windows:                                    "https://www.microsoft.com/en-us/windows"
+	windows_event_log:                          "https://learn.microsoft.com/en-us/windows/win32/wes/windows-event-log"
+	windows_event_log_xpath:                    "https://learn.microsoft.com/en-us/windows/win32/wes/consuming-events#xpath-10-limitations"
 	windows_installer:                          "\(wikipedia)/wiki/Windows_Installer"
 	windows_service:                            "https://docs.microsoft.com/en-us/powershell/module/microsoft.powershell.management/new-service"
 	woothee:                                    "https://github.com/woothee/woothee"